@@ -14,16 +14,30 @@ pub const IDENTITY_PROMPT_TEMPLATE: &str = include_str!("../prompts/identity_pro
 /// Prompt template for handoff generation (shared with TUI).
 pub const HANDOFF_PROMPT_TEMPLATE: &str = include_str!("../prompts/handoff_prompt.md");
 
+/// Prompt template for `zdx init` AGENTS.md generation.
+pub const INIT_PROMPT_TEMPLATE: &str = include_str!("../prompts/init_prompt.md");
+
 /// Prompt template for the `/prompt-builder` slash command (shared with TUI).
 pub const PROMPT_BUILDER_PROMPT_TEMPLATE: &str =
     include_str!("../prompts/prompt_builder_prompt.md");
 
+/// Prompt template for the `/improve` prompt rewrite (shared with TUI).
+pub const PROMPT_IMPROVE_PROMPT_TEMPLATE: &str =
+    include_str!("../prompts/prompt_improve_prompt.md");
+
+/// System prompt template for each speaker in a `/duel` discussion.
+pub const DUEL_PROMPT_TEMPLATE: &str = include_str!("../prompts/duel_prompt.md");
+
 /// Prompt template for auto thread-title generation (shared with TUI).
 pub const THREAD_TITLE_PROMPT_TEMPLATE: &str = include_str!("../prompts/thread_title_prompt.md");
 
 /// Prompt template for thread TLDR/recap generation (shared with TUI).
 pub const THREAD_TLDR_PROMPT_TEMPLATE: &str = include_str!("../prompts/thread_tldr_prompt.md");
 
+/// Prompt template for idle-time thread summaries (thread picker previews).
+pub const THREAD_SUMMARY_PROMPT_TEMPLATE: &str =
+    include_str!("../prompts/thread_summary_prompt.md");
+
 /// Prompt template for system prompt assembly (`MiniJinja`).
 pub const SYSTEM_PROMPT_TEMPLATE: &str = include_str!("../prompts/system_prompt_template.md");
 
@@ -59,6 +73,19 @@ pub const DEFAULT_MODELS_TOML: &str = include_str!("../default_models.toml");
 /// Embedded `model_overrides.toml`: post-fetch overrides for `zdx models update`.
 pub const MODEL_OVERRIDES_TOML: &str = include_str!("../model_overrides.toml");
 
+// ---------------------------------------------------------------------------
+// `zdx new` scaffolds (`{{NAME}}` is replaced with the chosen name)
+// ---------------------------------------------------------------------------
+
+/// Automation template (`zdx new automation`).
+pub const AUTOMATION_SCAFFOLD: &str = include_str!("../scaffolds/automation.md");
+
+/// `SKILL.md` template (`zdx new skill`).
+pub const SKILL_SCAFFOLD: &str = include_str!("../scaffolds/SKILL.md");
+
+/// `[[tools.custom]]` block appended to config.toml (`zdx new tool`).
+pub const CUSTOM_TOOL_SCAFFOLD: &str = include_str!("../scaffolds/custom_tool.toml");
+
 // ---------------------------------------------------------------------------
 // Built-in subagent definitions
 // ---------------------------------------------------------------------------
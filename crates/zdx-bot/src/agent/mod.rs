@@ -1,13 +1,20 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
 use std::path::Path;
+use std::time::Instant;
 
 use anyhow::{Context, Result};
+use serde_json::Value;
 use tokio_util::sync::CancellationToken;
 use zdx_engine::config::{Config, TextVerbosity};
-use zdx_engine::core::agent::{self, AgentEventRx, AgentOptions, ToolConfig};
+use zdx_engine::core::agent::{self, AgentOptions, ToolConfig, TurnOptions};
+use zdx_engine::core::ask_user::{self, AskUserRx};
 use zdx_engine::core::context::{PromptContextInclusion, build_prompt_with_context_and_layers};
+use zdx_engine::core::event_bus::{EventBus, EventFilter, EventSubscriber};
 use zdx_engine::core::events::AgentEvent;
 use zdx_engine::core::thread_persistence::{self, Thread, ThreadEvent};
 use zdx_engine::providers::{ChatContentBlock, ChatMessage, MessageContent};
+use zdx_engine::tools::protected_files::ProtectedApprovals;
 
 use crate::types::IncomingMessage;
 
@@ -79,9 +86,11 @@ pub(crate) fn record_user_message(
 /// internally — the caller doesn't need to manage it.
 pub(crate) struct AgentTurnHandle {
     /// Event stream for the caller to consume.
-    pub rx: AgentEventRx,
+    pub rx: EventSubscriber,
     /// Cancellation token for this agent turn.
     pub cancel: CancellationToken,
+    /// `Ask_User` questions for the caller to post and route replies to.
+    pub questions: AskUserRx,
     /// Task handle kept alive for the running agent turn.
     pub _task: tokio::task::JoinHandle<Result<(String, Vec<ChatMessage>)>>,
 }
@@ -89,6 +98,7 @@ pub(crate) struct AgentTurnHandle {
 struct PreparedBotTurn {
     config: Config,
     system_prompt: Option<String>,
+    prompt_variant: Option<String>,
 }
 
 fn bot_prompt_context() -> PromptContextInclusion {
@@ -105,6 +115,7 @@ fn collect_bot_instruction_layers(bot_instruction_layer: Option<&str>) -> Vec<&s
 
 fn prepare_bot_turn(
     config: &Config,
+    model: &str,
     root: &Path,
     bot_instruction_layer: Option<&str>,
 ) -> Result<PreparedBotTurn> {
@@ -113,7 +124,7 @@ fn prepare_bot_turn(
     let effective = build_prompt_with_context_and_layers(
         &bot_config,
         root,
-        &bot_config.model,
+        model,
         &instruction_layers,
         true,
         bot_prompt_context(),
@@ -123,20 +134,24 @@ fn prepare_bot_turn(
     Ok(PreparedBotTurn {
         config: bot_config,
         system_prompt: effective.prompt,
+        prompt_variant: effective.prompt_variant,
     })
 }
 
 /// Spawns an agent turn and returns a handle with streaming events.
 ///
-/// Thread persistence is wired internally via `spawn_broadcaster`.
+/// Thread persistence subscribes to the turn's event bus internally.
 /// The caller receives events through `AgentTurnHandle::rx` and should
-/// look for `TurnFinished` to get the terminal result.
+/// look for `TurnFinished` to get the terminal result. `turn` carries the
+/// thread's model/thinking overrides on top of the shared `config`.
 ///
 /// # Errors
 /// Returns an error if the operation fails.
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn spawn_agent_turn(
     messages: Vec<ChatMessage>,
     config: &Config,
+    turn: TurnOptions,
     root: &Path,
     bot_instruction_layer: Option<&str>,
     thread_id: &str,
@@ -149,8 +164,13 @@ pub(crate) fn spawn_agent_turn(
     let PreparedBotTurn {
         config: bot_config,
         system_prompt,
-    } = prepare_bot_turn(config, root, bot_instruction_layer)?;
+        prompt_variant,
+    } = prepare_bot_turn(config, turn.model(config), root, bot_instruction_layer)?;
+    if let Err(err) = thread.clone().set_prompt_variant(prompt_variant) {
+        tracing::warn!(thread_id, %err, "Failed to record prompt variant");
+    }
 
+    let (ask_user, questions) = ask_user::channel();
     let agent_opts = AgentOptions {
         root: root.to_path_buf(),
         tool_config: tool_config.clone(),
@@ -160,17 +180,24 @@ pub(crate) fn spawn_agent_turn(
         activity_kind: Some("telegram".to_string()),
         activity_parent_thread_id: None,
         activity_subagent_name: None,
+        soft_stop: None,
+        steering: None,
+        ask_user: Some(ask_user),
+        // Each turn asks again, so an approval never reaches another chat.
+        protected_approvals: ProtectedApprovals::default(),
+        turn,
     };
 
-    // Create channels: agent -> broadcaster -> [bot, persist]
-    let (agent_tx, agent_rx) = agent::create_event_channel();
+    // Event bus: agent -> [bot, persist]
+    let mut bus = EventBus::new();
     let cancel = CancellationToken::new();
     let run_cancel = cancel.clone();
-    let (bot_tx, bot_rx) = agent::create_event_channel();
-    let (persist_tx, persist_rx) = agent::create_event_channel();
-
-    agent::spawn_broadcaster(agent_rx, vec![bot_tx, persist_tx]);
-    thread_persistence::spawn_thread_persist_task(thread.clone(), persist_rx);
+    let bot_rx = bus.subscribe_lossy("bot", EventFilter::All);
+    thread_persistence::spawn_thread_persist_task(
+        thread.clone(),
+        bus.subscribe("persist", EventFilter::NoDeltas),
+    );
+    let agent_tx = bus.into_sender();
 
     // Spawn agent in background — owned values moved in
     let config = bot_config;
@@ -191,6 +218,7 @@ pub(crate) fn spawn_agent_turn(
     Ok(AgentTurnHandle {
         rx: bot_rx,
         cancel,
+        questions,
         _task: task,
     })
 }
@@ -207,21 +235,101 @@ pub(crate) fn event_to_status(event: &AgentEvent) -> Option<String> {
         }
         AgentEvent::ToolRequested { name, .. } => Some(format!("⚙️ Preparing `{name}`...")),
         AgentEvent::ToolStarted { name, .. } => {
-            let emoji = match name.as_str() {
-                "bash" => "🔧",
-                "read" => "📖",
-                "write" | "edit" | "apply_patch" => "✏️",
-                "web_search" => "🔍",
-                "fetch_webpage" => "🌐",
-                "read_thread" => "💬",
-                _ => "⚙️",
-            };
-            Some(format!("{emoji} Running `{name}`..."))
+            Some(format!("{} Running `{name}`...", tool_emoji(name)))
         }
         _ => None,
     }
 }
 
+fn tool_emoji(name: &str) -> &'static str {
+    match name {
+        "bash" => "🔧",
+        "read" => "📖",
+        "write" | "edit" | "apply_patch" => "✏️",
+        "web_search" => "🔍",
+        "fetch_webpage" => "🌐",
+        "read_thread" => "💬",
+        "schedule_message" => "⏰",
+        _ => "⚙️",
+    }
+}
+
+/// Input fields shown as the one-line detail of a running tool, in order.
+const TOOL_DETAIL_FIELDS: &[&str] = &["command", "file_path", "path", "pattern", "query", "url"];
+const TOOL_DETAIL_MAX_CHARS: usize = 48;
+
+struct RunningTool {
+    id: String,
+    name: String,
+    detail: Option<String>,
+    started_at: Instant,
+}
+
+/// Tracks running tools from agent events for the compact progress line
+/// (`🔧 Running `bash`: cargo test… 42s`).
+#[derive(Default)]
+pub(crate) struct ToolProgress {
+    inputs: HashMap<String, Value>,
+    running: Vec<RunningTool>,
+}
+
+impl ToolProgress {
+    pub(crate) fn observe(&mut self, event: &AgentEvent) {
+        match event {
+            AgentEvent::ToolRequested { id, input, .. }
+            | AgentEvent::ToolInputCompleted { id, input, .. } => {
+                self.inputs.insert(id.clone(), input.clone());
+            }
+            AgentEvent::ToolStarted { id, name } => {
+                let detail = self.inputs.remove(id).and_then(|input| tool_detail(&input));
+                self.running.push(RunningTool {
+                    id: id.clone(),
+                    name: name.clone(),
+                    detail,
+                    started_at: Instant::now(),
+                });
+            }
+            AgentEvent::ToolCompleted { id, .. } => {
+                self.running.retain(|tool| tool.id != *id);
+            }
+            _ => {}
+        }
+    }
+
+    pub(crate) fn is_running(&self) -> bool {
+        !self.running.is_empty()
+    }
+
+    /// Progress line for the most recently started tool, with elapsed time
+    /// and a count of other tools still running.
+    pub(crate) fn status_line(&self) -> Option<String> {
+        let tool = self.running.last()?;
+        let mut line = format!("{} Running `{}`", tool_emoji(&tool.name), tool.name);
+        if let Some(detail) = &tool.detail {
+            line.push_str(": ");
+            line.push_str(&crate::handlers::message::escape_html(detail));
+        }
+        let _ = write!(line, "… {}s", tool.started_at.elapsed().as_secs());
+        let others = self.running.len() - 1;
+        if others > 0 {
+            let _ = write!(line, " (+{others} more)");
+        }
+        Some(line)
+    }
+}
+
+fn tool_detail(input: &Value) -> Option<String> {
+    let value = TOOL_DETAIL_FIELDS
+        .iter()
+        .find_map(|field| input.get(*field)?.as_str())?;
+    let first_line = value.lines().find(|line| !line.trim().is_empty())?.trim();
+    if first_line.chars().count() <= TOOL_DETAIL_MAX_CHARS {
+        return Some(first_line.to_string());
+    }
+    let truncated: String = first_line.chars().take(TOOL_DETAIL_MAX_CHARS).collect();
+    Some(truncated.trim_end().to_string())
+}
+
 fn build_user_text(incoming: &IncomingMessage) -> String {
     let mut parts = Vec::new();
     if let Some(text) = incoming.text.as_ref()
@@ -243,7 +351,8 @@ fn build_user_text(incoming: &IncomingMessage) -> String {
 
     for image in &incoming.images {
         parts.push(format!(
-            "Image attachment saved at {}.",
+            "{} saved at {}.",
+            image.label.as_deref().unwrap_or("Image attachment"),
             image.local_path.display()
         ));
     }
@@ -272,7 +381,8 @@ mod tests {
     use zdx_engine::core::events::{AgentEvent, ToolOutput};
 
     use super::{
-        STATUS_THINKING, STATUS_WAITING, STATUS_WRITING, event_to_status, prepare_bot_turn,
+        STATUS_THINKING, STATUS_WAITING, STATUS_WRITING, ToolProgress, event_to_status,
+        prepare_bot_turn,
     };
 
     fn make_temp_dir() -> std::path::PathBuf {
@@ -334,6 +444,31 @@ mod tests {
         );
     }
 
+    #[test]
+    fn tool_progress_shows_running_tool_with_detail() {
+        let mut progress = ToolProgress::default();
+        progress.observe(&AgentEvent::ToolInputCompleted {
+            id: "1".to_string(),
+            name: "bash".to_string(),
+            input: json!({ "command": "cargo test --workspace\necho done" }),
+        });
+        progress.observe(&AgentEvent::ToolStarted {
+            id: "1".to_string(),
+            name: "bash".to_string(),
+        });
+        assert_eq!(
+            progress.status_line().as_deref(),
+            Some("🔧 Running `bash`: cargo test --workspace… 0s")
+        );
+
+        progress.observe(&AgentEvent::ToolCompleted {
+            id: "1".to_string(),
+            result: ToolOutput::success(json!({ "ok": true })),
+        });
+        assert!(!progress.is_running());
+        assert_eq!(progress.status_line(), None);
+    }
+
     #[test]
     fn prepare_bot_turn_includes_project_context() {
         let dir = make_temp_dir();
@@ -354,7 +489,7 @@ mod tests {
             agents_project: false,
         };
 
-        let prepared = prepare_bot_turn(&config, &dir, None).unwrap();
+        let prepared = prepare_bot_turn(&config, &config.model, &dir, None).unwrap();
         let prompt = prepared.system_prompt.unwrap_or_default();
 
         assert!(prompt.contains("Bot project note"));
@@ -2,14 +2,18 @@ use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::{Arc, RwLock};
 
+use anyhow::{Context, Result};
 use tokio::sync::{Mutex, Notify};
 use tokio_util::sync::CancellationToken;
 use zdx_engine::config::{Config, TelegramProfileConfig};
 use zdx_engine::core::agent::ToolConfig;
 
+use crate::bot::scheduler::TurnScheduler;
 use crate::command_picker::CommandPickerMap;
 use crate::followups::FollowupMap;
 use crate::handlers::message::LauncherMap;
+use crate::questions::QuestionMap;
+use crate::reminders::{ReminderStore, ReminderTarget};
 use crate::staging::StagingMap;
 use crate::telegram::TelegramClient;
 
@@ -38,8 +42,8 @@ pub(crate) fn new_queue_cancel_map() -> QueueCancelMap {
 pub(crate) struct BotContext {
     client: TelegramClient,
     config: RwLock<Config>,
-    allowlist_user_ids: HashSet<i64>,
-    allowlist_chat_ids: HashSet<i64>,
+    allowlist_user_ids: RwLock<HashSet<i64>>,
+    allowlist_chat_ids: RwLock<HashSet<i64>>,
     root: PathBuf,
     bot_instruction_layer: Option<String>,
     tool_config: ToolConfig,
@@ -47,9 +51,12 @@ pub(crate) struct BotContext {
     cancel_map: CancelMap,
     queue_cancel_map: QueueCancelMap,
     followup_map: FollowupMap,
+    question_map: QuestionMap,
     staging_map: StagingMap,
     command_picker_map: CommandPickerMap,
     launcher_map: LauncherMap,
+    turn_scheduler: TurnScheduler,
+    reminders: Arc<ReminderStore>,
 }
 
 #[derive(Debug, Clone)]
@@ -67,9 +74,11 @@ pub(crate) struct BotContextDeps {
     pub cancel_map: CancelMap,
     pub queue_cancel_map: QueueCancelMap,
     pub followup_map: FollowupMap,
+    pub question_map: QuestionMap,
     pub staging_map: StagingMap,
     pub command_picker_map: CommandPickerMap,
     pub launcher_map: LauncherMap,
+    pub reminders: Arc<ReminderStore>,
 }
 
 impl BotContext {
@@ -83,16 +92,18 @@ impl BotContext {
             cancel_map,
             queue_cancel_map,
             followup_map,
+            question_map,
             staging_map,
             command_picker_map,
             launcher_map,
+            reminders,
         } = deps;
         let root = root.canonicalize().unwrap_or(root);
         Self {
             client,
             config: RwLock::new(config),
-            allowlist_user_ids,
-            allowlist_chat_ids,
+            allowlist_user_ids: RwLock::new(allowlist_user_ids),
+            allowlist_chat_ids: RwLock::new(allowlist_chat_ids),
             root,
             bot_instruction_layer,
             tool_config,
@@ -100,9 +111,12 @@ impl BotContext {
             cancel_map,
             queue_cancel_map,
             followup_map,
+            question_map,
             staging_map,
             command_picker_map,
             launcher_map,
+            turn_scheduler: TurnScheduler::new(),
+            reminders,
         }
     }
 
@@ -122,12 +136,36 @@ impl BotContext {
         f(&mut config);
     }
 
-    pub(crate) fn allowlist_user_ids(&self) -> &HashSet<i64> {
-        &self.allowlist_user_ids
+    pub(crate) fn allowlist_user_ids(&self) -> HashSet<i64> {
+        self.allowlist_user_ids
+            .read()
+            .expect("bot allowlist lock poisoned")
+            .clone()
+    }
+
+    pub(crate) fn allowlist_chat_ids(&self) -> HashSet<i64> {
+        self.allowlist_chat_ids
+            .read()
+            .expect("bot allowlist lock poisoned")
+            .clone()
     }
 
-    pub(crate) fn allowlist_chat_ids(&self) -> &HashSet<i64> {
-        &self.allowlist_chat_ids
+    /// Re-reads config (file + `ZDX_*` overrides) and swaps in the new config
+    /// and allowlists. Running turns keep the config snapshot they started
+    /// with; the bot token, root, and tool config are fixed until restart.
+    pub(crate) fn reload_config(&self) -> Result<()> {
+        let config = Config::load().context("load zdx config")?;
+        let (config, settings) = crate::prepare_bot_config(config)?;
+        *self
+            .allowlist_user_ids
+            .write()
+            .expect("bot allowlist lock poisoned") = settings.allowlist_user_ids;
+        *self
+            .allowlist_chat_ids
+            .write()
+            .expect("bot allowlist lock poisoned") = settings.allowlist_chat_ids;
+        *self.config.write().expect("bot config lock poisoned") = config;
+        Ok(())
     }
 
     pub(crate) fn root_for_chat(&self, chat_id: i64) -> ResolvedProfileRoot {
@@ -149,8 +187,14 @@ impl BotContext {
         self.bot_instruction_layer.as_deref()
     }
 
-    pub(crate) fn tool_config(&self) -> &ToolConfig {
-        &self.tool_config
+    /// Tool config for one turn: the shared tools plus `Schedule_Message`
+    /// bound to the turn's chat/topic.
+    pub(crate) fn turn_tool_config(&self, target: ReminderTarget) -> ToolConfig {
+        crate::reminders::with_schedule_tool(&self.tool_config, &self.reminders, target)
+    }
+
+    pub(crate) fn reminders(&self) -> &ReminderStore {
+        &self.reminders
     }
 
     /// Signal the bot to exit (with code 42) so a supervisor can restart it.
@@ -175,6 +219,10 @@ impl BotContext {
         &self.followup_map
     }
 
+    pub(crate) fn question_map(&self) -> &QuestionMap {
+        &self.question_map
+    }
+
     pub(crate) fn staging_map(&self) -> &StagingMap {
         &self.staging_map
     }
@@ -186,6 +234,10 @@ impl BotContext {
     pub(crate) fn launcher_map(&self) -> &LauncherMap {
         &self.launcher_map
     }
+
+    pub(crate) fn turn_scheduler(&self) -> &TurnScheduler {
+        &self.turn_scheduler
+    }
 }
 
 fn profile_root_path(profile: &TelegramProfileConfig) -> PathBuf {
@@ -247,9 +299,14 @@ mod tests {
                 cancel_map: new_cancel_map(),
                 queue_cancel_map: new_queue_cancel_map(),
                 followup_map: crate::followups::new_followup_map(),
+                question_map: crate::questions::new_question_map(),
                 staging_map: crate::staging::new_staging_map(),
                 command_picker_map: crate::command_picker::new_command_picker_map(),
                 launcher_map: crate::handlers::message::new_launcher_map(),
+                reminders: Arc::new(
+                    ReminderStore::load(unique_temp_dir("reminders").join("reminders.json"))
+                        .unwrap(),
+                ),
             },
         )
     }
@@ -1,5 +1,6 @@
 pub(crate) mod context;
 pub(crate) mod queue;
+pub(crate) mod scheduler;
 
 pub(crate) use context::{
     BotContext, BotContextDeps, CancelKey, QueueCancelKey, new_cancel_map, new_queue_cancel_map,
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 use tokio::sync::{Mutex, mpsc};
 use tokio_util::sync::CancellationToken;
 
 use crate::bot::context::{BotContext, QueueCancelKey};
+use crate::bot::scheduler::TurnPermit;
 use crate::commands::{BotCommand, bypasses_queue, is_topic_blocking_command, parse_command};
 use crate::handlers::message::handle_message;
 use crate::telegram::{InlineKeyboardButton, InlineKeyboardMarkup, Message};
@@ -18,6 +20,8 @@ pub(crate) struct QueueItem {
     message: Message,
     /// Cancellation token for this queued item. Checked before processing.
     cancel_token: CancellationToken,
+    /// When the item entered the queue, for wait-time metrics.
+    enqueued_at: Instant,
     /// If this item was queued (not first), holds the status message info
     /// so the worker can clean it up.
     queued_status: Option<QueuedStatus>,
@@ -65,6 +69,11 @@ pub(crate) async fn dispatch_message(
         return;
     }
 
+    // A reply to an `Ask_User` question goes to the waiting turn.
+    if crate::questions::try_answer(context, &message) {
+        return;
+    }
+
     if is_forum_general {
         // Check for commands that shouldn't create a topic
         if let Some(text) = message.text.as_deref()
@@ -193,13 +202,14 @@ fn generate_topic_name(text: Option<&str>) -> String {
 /// Used only for commands that intentionally bypass topic auto-creation.
 fn spawn_standalone(context: Arc<BotContext>, message: Message) {
     tokio::spawn(async move {
-        if let Err(err) = handle_message(context.as_ref(), message).await {
+        if let Err(err) = Box::pin(handle_message(context.as_ref(), message)).await {
             tracing::error!(%err, "Standalone message handling error");
         }
     });
 }
 
 async fn enqueue_message(queues: &ChatQueueMap, context: &Arc<BotContext>, message: Message) {
+    let enqueued_at = Instant::now();
     let key = (message.chat.id, message.effective_thread_id().unwrap_or(0));
     let queues_map = Arc::clone(queues);
     let (sender, should_show_queued) = {
@@ -274,6 +284,7 @@ async fn enqueue_message(queues: &ChatQueueMap, context: &Arc<BotContext>, messa
     let item = QueueItem {
         message,
         cancel_token,
+        enqueued_at,
         queued_status,
     };
 
@@ -301,6 +312,18 @@ async fn enqueue_message(queues: &ChatQueueMap, context: &Arc<BotContext>, messa
     }
 }
 
+async fn acquire_turn_slot(context: &BotContext, key: QueueKey, message: &Message) -> TurnPermit {
+    let config = context.config();
+    let priority = message
+        .from
+        .as_ref()
+        .is_some_and(|user| config.telegram.priority_user_ids.contains(&user.id));
+    context
+        .turn_scheduler()
+        .acquire(key.0, priority, config.telegram.max_concurrent_turns)
+        .await
+}
+
 fn spawn_queue_worker(
     key: QueueKey,
     mut receiver: mpsc::UnboundedReceiver<QueueItem>,
@@ -312,9 +335,17 @@ fn spawn_queue_worker(
             let QueueItem {
                 message,
                 cancel_token,
+                enqueued_at,
                 queued_status,
             } = item;
 
+            // Wait for a global turn slot while the item stays cancellable:
+            // cancelling it while it waits gives up the wait.
+            let permit = tokio::select! {
+                () = cancel_token.cancelled() => None,
+                permit = acquire_turn_slot(&context, key, &message) => Some(permit),
+            };
+
             // Clean up queue cancel map entry
             if let Some(ref status) = queued_status {
                 let queue_cancel_key: QueueCancelKey = (status.chat, status.original);
@@ -356,9 +387,18 @@ fn spawn_queue_worker(
                 tracing::warn!(status_id = status.status, %err, "Failed to delete queued status message");
             }
 
-            if let Err(err) = handle_message(context.as_ref(), message).await {
+            let wait = enqueued_at.elapsed();
+            context.turn_scheduler().record_wait(wait);
+            tracing::debug!(
+                ?key,
+                wait_ms = wait.as_millis(),
+                "Processing queued message"
+            );
+
+            if let Err(err) = Box::pin(handle_message(context.as_ref(), message)).await {
                 tracing::error!(?key, %err, "Message handling error");
             }
+            drop(permit);
 
             let mut queues = queues.lock().await;
             let drained = if let Some(state) = queues.get_mut(&key) {
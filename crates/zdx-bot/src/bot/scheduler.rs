@@ -0,0 +1,258 @@
+//! Global turn scheduler: caps concurrent agent turns across chats.
+//!
+//! Per-topic queues (see `queue.rs`) keep each topic sequential; this
+//! scheduler decides which topic worker runs next once
+//! `[telegram] max_concurrent_turns` turns are active. Waiting turns are
+//! granted round-robin by chat, so a chat flooding messages (or topics) can't
+//! starve the others, and turns from `priority_user_ids` jump ahead of the
+//! round-robin. Queue wait times are recorded in [`QueueWaitStats`].
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use tokio::sync::oneshot;
+
+/// Aggregate queue wait counters since bot start.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct QueueWaitStats {
+    /// Turns that started processing.
+    pub turns: u64,
+    /// Turns that waited for a global slot.
+    pub throttled: u64,
+    pub total_wait: Duration,
+    pub max_wait: Duration,
+}
+
+impl QueueWaitStats {
+    pub(crate) fn average_wait(&self) -> Duration {
+        if self.turns == 0 {
+            return Duration::ZERO;
+        }
+        let turns = u32::try_from(self.turns).unwrap_or(u32::MAX);
+        self.total_wait / turns
+    }
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    /// Limit from the latest `acquire` (0 = unlimited); follows config reloads.
+    limit: usize,
+    running: usize,
+    priority: VecDeque<oneshot::Sender<()>>,
+    /// Waiters per chat, granted round-robin in `ring` order.
+    waiting: HashMap<i64, VecDeque<oneshot::Sender<()>>>,
+    ring: VecDeque<i64>,
+    stats: QueueWaitStats,
+}
+
+impl SchedulerState {
+    fn has_capacity(&self) -> bool {
+        self.limit == 0 || self.running < self.limit
+    }
+
+    fn has_waiters(&self) -> bool {
+        !self.priority.is_empty() || !self.ring.is_empty()
+    }
+
+    fn next_waiter(&mut self) -> Option<oneshot::Sender<()>> {
+        if let Some(waiter) = self.priority.pop_front() {
+            return Some(waiter);
+        }
+        let chat_id = self.ring.pop_front()?;
+        let queue = self.waiting.get_mut(&chat_id)?;
+        let waiter = queue.pop_front();
+        if queue.is_empty() {
+            self.waiting.remove(&chat_id);
+        } else {
+            self.ring.push_back(chat_id);
+        }
+        waiter
+    }
+
+    /// Hands free slots to waiters; skips waiters that went away.
+    fn grant(&mut self) {
+        while self.has_capacity() {
+            let Some(waiter) = self.next_waiter() else {
+                return;
+            };
+            if waiter.send(()).is_ok() {
+                self.running += 1;
+            }
+        }
+    }
+
+    /// Frees a running slot and hands it to the next waiter.
+    fn release(&mut self) {
+        self.running = self.running.saturating_sub(1);
+        self.grant();
+    }
+}
+
+#[derive(Clone, Default)]
+pub(crate) struct TurnScheduler {
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+/// Held while a turn runs; frees the slot on drop.
+pub(crate) struct TurnPermit {
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+impl Drop for TurnPermit {
+    fn drop(&mut self) {
+        self.state
+            .lock()
+            .expect("turn scheduler lock poisoned")
+            .release();
+    }
+}
+
+/// A queued `acquire`. If the wait is abandoned (e.g. the turn is cancelled
+/// while queued), a slot granted in the meantime is released on drop.
+struct PendingGrant {
+    receiver: oneshot::Receiver<()>,
+    state: Arc<Mutex<SchedulerState>>,
+}
+
+impl Drop for PendingGrant {
+    fn drop(&mut self) {
+        let mut state = self.state.lock().expect("turn scheduler lock poisoned");
+        // Closing under the lock stops further grants to this waiter.
+        self.receiver.close();
+        if self.receiver.try_recv().is_ok() {
+            state.release();
+        }
+    }
+}
+
+impl TurnScheduler {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Waits for a turn slot. `limit` is the current
+    /// `max_concurrent_turns` (0 = unlimited). Cancel-safe: dropping the
+    /// future before it resolves never leaks a slot.
+    pub(crate) async fn acquire(&self, chat_id: i64, priority: bool, limit: usize) -> TurnPermit {
+        let receiver = {
+            let mut guard = self.state.lock().expect("turn scheduler lock poisoned");
+            let state = &mut *guard;
+            state.limit = limit;
+            // Raising the limit on reload frees slots for existing waiters.
+            state.grant();
+            if state.has_capacity() && !state.has_waiters() {
+                state.running += 1;
+                None
+            } else {
+                let (sender, receiver) = oneshot::channel();
+                if priority {
+                    state.priority.push_back(sender);
+                } else {
+                    let queue = state.waiting.entry(chat_id).or_default();
+                    if queue.is_empty() {
+                        state.ring.push_back(chat_id);
+                    }
+                    queue.push_back(sender);
+                }
+                state.stats.throttled += 1;
+                Some(receiver)
+            }
+        };
+        if let Some(receiver) = receiver {
+            let mut pending = PendingGrant {
+                receiver,
+                state: Arc::clone(&self.state),
+            };
+            // The sender is only dropped after counting us as running.
+            let _ = (&mut pending.receiver).await;
+        }
+        TurnPermit {
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    /// Records how long a turn waited between enqueue and start.
+    pub(crate) fn record_wait(&self, wait: Duration) {
+        let mut state = self.state.lock().expect("turn scheduler lock poisoned");
+        state.stats.turns += 1;
+        state.stats.total_wait += wait;
+        state.stats.max_wait = state.stats.max_wait.max(wait);
+    }
+
+    pub(crate) fn stats(&self) -> QueueWaitStats {
+        self.state
+            .lock()
+            .expect("turn scheduler lock poisoned")
+            .stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex as StdMutex;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn grants_waiting_chats_round_robin_with_priority_first() {
+        let scheduler = TurnScheduler::new();
+        let first = scheduler.acquire(1, false, 1).await;
+
+        let order = Arc::new(StdMutex::new(Vec::new()));
+        let mut handles = Vec::new();
+        // Chat 1 floods three turns before chat 2 and a priority user queue.
+        for (label, chat_id, priority) in [
+            ("a1", 1, false),
+            ("a2", 1, false),
+            ("a3", 1, false),
+            ("b1", 2, false),
+            ("p", 3, true),
+        ] {
+            let scheduler = scheduler.clone();
+            let order = Arc::clone(&order);
+            handles.push(tokio::spawn(async move {
+                let permit = scheduler.acquire(chat_id, priority, 1).await;
+                order.lock().unwrap().push(label);
+                drop(permit);
+            }));
+            tokio::task::yield_now().await;
+        }
+
+        drop(first);
+        for handle in handles {
+            handle.await.unwrap();
+        }
+        assert_eq!(*order.lock().unwrap(), ["p", "a1", "b1", "a2", "a3"]);
+        assert_eq!(scheduler.stats().throttled, 5);
+    }
+
+    #[tokio::test]
+    async fn abandoned_wait_does_not_leak_a_slot() {
+        let scheduler = TurnScheduler::new();
+        let first = scheduler.acquire(1, false, 1).await;
+
+        let waiter = {
+            let scheduler = scheduler.clone();
+            tokio::spawn(async move { scheduler.acquire(2, false, 1).await })
+        };
+        tokio::task::yield_now().await;
+        // The slot is granted to the waiter, which is then dropped before it
+        // can turn the grant into a permit.
+        drop(first);
+        waiter.abort();
+        let _ = waiter.await;
+
+        let _next = tokio::time::timeout(Duration::from_secs(1), scheduler.acquire(3, false, 1))
+            .await
+            .expect("slot freed by the abandoned waiter");
+    }
+
+    #[tokio::test]
+    async fn unlimited_never_waits() {
+        let scheduler = TurnScheduler::new();
+        let _a = scheduler.acquire(1, false, 0).await;
+        let _b = scheduler.acquire(1, false, 0).await;
+        assert_eq!(scheduler.stats().throttled, 0);
+    }
+}
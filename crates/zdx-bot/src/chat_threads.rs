@@ -0,0 +1,270 @@
+//! Named conversations within one chat or topic (`/threads`, `/thread`).
+//!
+//! Every chat (or forum topic) has a base thread from `thread_id_for_chat`.
+//! Extra conversations are threads named `{base}-thread-{created_ms}`, titled
+//! with the name the user gave them. The active conversation is stored as the
+//! base thread's `alias_to`, which message handling already follows via
+//! `resolve_effective_thread_id`; switching back to the base clears it.
+
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+use zdx_engine::core::thread_persistence::{self, Thread};
+
+use crate::bot::context::BotContext;
+use crate::commands::{ThreadSubcommand, parse_thread_command};
+use crate::handlers::message::{escape_html, resolve_effective_thread_id, thread_id_for_chat};
+use crate::telegram::{CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup, TelegramClient};
+use crate::types::IncomingMessage;
+
+const THREAD_ID_INFIX: &str = "-thread-";
+/// Callback key of the base thread (`th:main`).
+const MAIN_KEY: &str = "main";
+const MAIN_NAME: &str = "Main";
+
+/// One conversation available in a chat.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ChatThread {
+    /// Short key used in callback data: `main` or the creation timestamp.
+    key: String,
+    id: String,
+    name: String,
+}
+
+/// Lists the base thread followed by the chat's named threads, oldest first.
+fn list_chat_threads(base: &str) -> Result<Vec<ChatThread>> {
+    let main = ChatThread {
+        key: MAIN_KEY.to_string(),
+        id: base.to_string(),
+        name: thread_persistence::read_thread_title(base)?.unwrap_or_else(|| MAIN_NAME.to_string()),
+    };
+    let prefix = format!("{base}{THREAD_ID_INFIX}");
+    let mut named: Vec<ChatThread> = thread_persistence::list_all_threads()
+        .context("list threads")?
+        .into_iter()
+        .filter_map(|summary| {
+            let key = summary.id.strip_prefix(&prefix)?.to_string();
+            Some(ChatThread {
+                name: summary.title.unwrap_or_else(|| format!("Thread {key}")),
+                key,
+                id: summary.id,
+            })
+        })
+        .collect();
+    named.sort_by_key(|thread| thread.key.parse::<u128>().unwrap_or(u128::MAX));
+
+    let mut threads = vec![main];
+    threads.extend(named);
+    Ok(threads)
+}
+
+/// Creates a named thread and makes it the chat's active conversation.
+fn create_chat_thread(base: &str, name: Option<&str>) -> Result<ChatThread> {
+    let count = list_chat_threads(base)?.len();
+    let key = chrono::Utc::now().timestamp_millis().to_string();
+    let id = format!("{base}{THREAD_ID_INFIX}{key}");
+    let name = name.map_or_else(|| format!("Thread {}", count + 1), str::to_string);
+
+    let name = Thread::with_id(id.clone())
+        .and_then(|mut thread| thread.set_title(Some(name.clone())))
+        .context("create thread")?
+        .unwrap_or(name);
+    switch_chat_thread(base, &id)?;
+    Ok(ChatThread { key, id, name })
+}
+
+/// Points the chat's base thread at `id` (or back at itself).
+fn switch_chat_thread(base: &str, id: &str) -> Result<()> {
+    Thread::with_id(base.to_string())
+        .and_then(|mut thread| thread.set_alias((id != base).then(|| id.to_string())))
+        .context("switch active thread")
+}
+
+/// Finds a thread by 1-based list number, key, or case-insensitive name.
+fn find_thread<'a>(threads: &'a [ChatThread], query: &str) -> Option<&'a ChatThread> {
+    let query = query.trim();
+    if let Ok(number) = query.parse::<usize>()
+        && let Some(thread) = number.checked_sub(1).and_then(|idx| threads.get(idx))
+    {
+        return Some(thread);
+    }
+    threads
+        .iter()
+        .find(|thread| thread.key == query || thread.name.eq_ignore_ascii_case(query))
+}
+
+fn picker_body(threads: &[ChatThread], active_id: &str) -> String {
+    let mut body = String::from("🧵 <b>Threads</b>");
+    for (idx, thread) in threads.iter().enumerate() {
+        let marker = if thread.id == active_id { " ✅" } else { "" };
+        let _ = write!(body, "\n{}. {}{marker}", idx + 1, escape_html(&thread.name));
+    }
+    body.push_str("\n\nTap a thread to switch, or send <code>/thread new [name]</code>.");
+    body
+}
+
+fn picker_keyboard(threads: &[ChatThread], active_id: &str) -> InlineKeyboardMarkup {
+    InlineKeyboardMarkup {
+        inline_keyboard: threads
+            .iter()
+            .map(|thread| {
+                let marker = if thread.id == active_id { "✅ " } else { "" };
+                vec![InlineKeyboardButton {
+                    text: format!("{marker}{}", thread.name),
+                    callback_data: Some(format!("th:{}", thread.key)),
+                    url: None,
+                }]
+            })
+            .collect(),
+    }
+}
+
+/// Handles `/threads` and `/thread ...`. Returns `true` when the message was a
+/// thread command.
+pub(crate) async fn handle_thread_command(
+    context: &BotContext,
+    incoming: &IncomingMessage,
+    reply_to_message_id: Option<i64>,
+    topic_id: Option<i64>,
+) -> Result<bool> {
+    if !incoming.images.is_empty() || !incoming.audios.is_empty() {
+        return Ok(false);
+    }
+    let Some(subcommand) = incoming.text.as_deref().and_then(parse_thread_command) else {
+        return Ok(false);
+    };
+    let reply = |text: String| async move {
+        context
+            .client()
+            .send_message(incoming.chat_id, &text, reply_to_message_id, topic_id)
+            .await
+            .map(|()| true)
+    };
+    if incoming.is_forum && topic_id.is_none() {
+        return reply("/threads must be used inside a topic, not General.".to_string()).await;
+    }
+
+    let base = thread_id_for_chat(incoming.chat_id, topic_id);
+    let threads = list_chat_threads(&base)?;
+    match subcommand {
+        ThreadSubcommand::List | ThreadSubcommand::Switch(None) => {
+            let active_id = resolve_effective_thread_id(&base);
+            context
+                .client()
+                .send_message_with_markup(
+                    incoming.chat_id,
+                    &picker_body(&threads, &active_id),
+                    reply_to_message_id,
+                    topic_id,
+                    &picker_keyboard(&threads, &active_id),
+                )
+                .await?;
+            Ok(true)
+        }
+        ThreadSubcommand::New(name) => {
+            let thread = create_chat_thread(&base, name.as_deref())?;
+            reply(format!(
+                "🧵 Started <b>{}</b>. New messages go to this thread; use /threads to switch back.",
+                escape_html(&thread.name)
+            ))
+            .await
+        }
+        ThreadSubcommand::Switch(Some(query)) => {
+            let Some(thread) = find_thread(&threads, &query) else {
+                return reply(format!(
+                    "No thread matches <code>{}</code>. Use /threads to see the list.",
+                    escape_html(&query)
+                ))
+                .await;
+            };
+            switch_chat_thread(&base, &thread.id)?;
+            reply(format!(
+                "🧵 Switched to <b>{}</b>.",
+                escape_html(&thread.name)
+            ))
+            .await
+        }
+    }
+}
+
+/// Handles `th:{key}` callbacks from a `/threads` picker.
+pub(crate) async fn handle_callback(client: &TelegramClient, callback: &CallbackQuery, key: &str) {
+    let Some(message) = callback.message.as_ref() else {
+        let _ = client
+            .answer_callback_query(&callback.id, Some("No message context"))
+            .await;
+        return;
+    };
+    let base = thread_id_for_chat(message.chat.id, message.thread_id);
+
+    let switched = list_chat_threads(&base).and_then(|threads| {
+        let thread = threads
+            .iter()
+            .find(|thread| thread.key == key)
+            .cloned()
+            .context("thread no longer exists")?;
+        switch_chat_thread(&base, &thread.id)?;
+        Ok((threads, thread))
+    });
+    match switched {
+        Ok((threads, thread)) => {
+            let _ = client
+                .edit_message_text(
+                    message.chat.id,
+                    message.id,
+                    &picker_body(&threads, &thread.id),
+                    Some(&picker_keyboard(&threads, &thread.id)),
+                )
+                .await;
+            let _ = client
+                .answer_callback_query(&callback.id, Some(&format!("Switched to {}", thread.name)))
+                .await;
+        }
+        Err(err) => {
+            tracing::warn!(chat_id = message.chat.id, %err, "Failed to switch thread");
+            let _ = client
+                .answer_callback_query(&callback.id, Some("Couldn't switch thread"))
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread(key: &str, name: &str) -> ChatThread {
+        ChatThread {
+            key: key.to_string(),
+            id: format!("telegram-1{THREAD_ID_INFIX}{key}"),
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_find_thread_by_number_or_name() {
+        let threads = vec![
+            ChatThread {
+                key: MAIN_KEY.to_string(),
+                id: "telegram-1".to_string(),
+                name: MAIN_NAME.to_string(),
+            },
+            thread("1700000000000", "Research"),
+        ];
+        assert_eq!(find_thread(&threads, "2").unwrap().name, "Research");
+        assert_eq!(
+            find_thread(&threads, "research").unwrap().key,
+            "1700000000000"
+        );
+        assert_eq!(find_thread(&threads, "main").unwrap().id, "telegram-1");
+        assert!(find_thread(&threads, "3").is_none());
+
+        let keyboard = picker_keyboard(&threads, "telegram-1");
+        let buttons: Vec<_> = keyboard.inline_keyboard.iter().flatten().collect();
+        assert_eq!(buttons[0].text, "✅ Main");
+        assert_eq!(
+            buttons[1].callback_data.as_deref(),
+            Some("th:1700000000000")
+        );
+    }
+}
@@ -2,6 +2,8 @@
 pub(crate) enum BotCommand {
     New,
     Exit,
+    Reload,
+    Reminders,
     Status,
     WhereAmI,
     WorktreeCreate,
@@ -10,6 +12,7 @@ pub(crate) enum BotCommand {
     Tldr,
     PromptBuilder,
     ThreadId,
+    HandoffToDesktop,
     Launcher,
 }
 
@@ -46,6 +49,24 @@ const COMMAND_DEFS: &[CommandDef] = &[
             description: "Exit the bot (supervisor will restart it)",
         },
     },
+    CommandDef {
+        command: BotCommand::Reload,
+        patterns: &["/reload"],
+        blocks_topic_autocreate: true,
+        telegram_spec: TelegramCommandSpec {
+            command: "reload",
+            description: "Reload config.toml without restarting",
+        },
+    },
+    CommandDef {
+        command: BotCommand::Reminders,
+        patterns: &["/reminders"],
+        blocks_topic_autocreate: true,
+        telegram_spec: TelegramCommandSpec {
+            command: "reminders",
+            description: "List or cancel scheduled reminders",
+        },
+    },
     CommandDef {
         command: BotCommand::Status,
         patterns: &["/status"],
@@ -118,6 +139,19 @@ const COMMAND_DEFS: &[CommandDef] = &[
             description: "Show only the thread ID",
         },
     },
+    CommandDef {
+        command: BotCommand::HandoffToDesktop,
+        patterns: &[
+            "/handoff_to_desktop",
+            "/handoff-to-desktop",
+            "/handofftodesktop",
+        ],
+        blocks_topic_autocreate: true,
+        telegram_spec: TelegramCommandSpec {
+            command: "handoff_to_desktop",
+            description: "Show the command to continue this thread in the TUI",
+        },
+    },
     CommandDef {
         command: BotCommand::Launcher,
         patterns: &["/launcher", "/menu"],
@@ -140,6 +174,18 @@ pub(crate) fn telegram_command_specs() -> Vec<TelegramCommandSpec> {
         command: "thinking",
         description: "View or change the thinking level",
     });
+    specs.push(TelegramCommandSpec {
+        command: "threads",
+        description: "List and switch this chat's conversations",
+    });
+    specs.push(TelegramCommandSpec {
+        command: "thread",
+        description: "Start or switch conversations (/thread new [name])",
+    });
+    specs.push(TelegramCommandSpec {
+        command: "history",
+        description: "Show the last exchanges of this topic (/history [n])",
+    });
     specs
 }
 
@@ -150,7 +196,9 @@ pub(crate) fn native_command_names() -> Vec<&'static str> {
         .iter()
         .map(|def| def.telegram_spec.command)
         .collect();
-    names.extend(["model", "thinking", "cancel"]);
+    names.extend([
+        "model", "thinking", "threads", "thread", "history", "cancel",
+    ]);
     names
 }
 
@@ -176,13 +224,23 @@ pub(crate) fn is_topic_blocking_command(text: &str) -> bool {
     parse_command(text).is_some_and(blocks_topic_autocreate)
         || parse_model_command(text).is_some()
         || parse_thinking_command(text).is_some()
+        || parse_history_command(text).is_some()
+        || parse_thread_command(text).is_some()
 }
 
 pub(crate) fn bypasses_queue(text: &str) -> bool {
     matches!(
         parse_command(text),
-        Some(BotCommand::Status | BotCommand::WhereAmI | BotCommand::Tldr | BotCommand::ThreadId)
-    )
+        Some(
+            BotCommand::Status
+                | BotCommand::WhereAmI
+                | BotCommand::Tldr
+                | BotCommand::ThreadId
+                | BotCommand::HandoffToDesktop
+                | BotCommand::Reload
+                | BotCommand::Reminders
+        )
+    ) || parse_history_command(text).is_some()
 }
 
 fn command_matches(trimmed_text: &str, command: &str) -> bool {
@@ -203,6 +261,13 @@ pub(crate) enum ModelSubcommand {
     Reset,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum ThreadSubcommand {
+    List,
+    New(Option<String>),
+    Switch(Option<String>),
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(crate) enum ThinkingSubcommand {
     Show,
@@ -255,6 +320,64 @@ pub(crate) fn parse_thinking_command(text: &str) -> Option<ThinkingSubcommand> {
     }
 }
 
+/// Parses `/threads` and `/thread [new [name] | switch [name|number]]`.
+/// Returns None if the text is not a thread command.
+pub(crate) fn parse_thread_command(text: &str) -> Option<ThreadSubcommand> {
+    let trimmed = text.trim();
+    let (command, rest) = ["/threads", "/thread"].into_iter().find_map(|command| {
+        let rest = trimmed.strip_prefix(command)?;
+        if let Some(after_mention) = rest.strip_prefix('@') {
+            Some((
+                command,
+                after_mention.find(' ').map_or("", |i| &after_mention[i..]),
+            ))
+        } else if rest.is_empty() || rest.starts_with(' ') {
+            Some((command, rest))
+        } else {
+            None
+        }
+    })?;
+    if command == "/threads" {
+        return Some(ThreadSubcommand::List);
+    }
+
+    let rest = rest.trim();
+    let (subcommand, arg) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let arg = Some(arg.trim().to_string()).filter(|arg| !arg.is_empty());
+    match subcommand.to_ascii_lowercase().as_str() {
+        "new" => Some(ThreadSubcommand::New(arg)),
+        "switch" => Some(ThreadSubcommand::Switch(arg)),
+        _ => Some(ThreadSubcommand::List),
+    }
+}
+
+/// Default and maximum exchange counts for `/history [n]`.
+const HISTORY_DEFAULT_EXCHANGES: usize = 5;
+const HISTORY_MAX_EXCHANGES: usize = 50;
+
+/// Parses a /history command into the number of exchanges to show. Returns
+/// None if the text is not a /history command; a missing or invalid count
+/// falls back to the default.
+pub(crate) fn parse_history_command(text: &str) -> Option<usize> {
+    let trimmed = text.trim();
+    let rest = trimmed.strip_prefix("/history")?;
+    let args = if let Some(after_mention) = rest.strip_prefix('@') {
+        after_mention.find(' ').map_or("", |i| &after_mention[i..])
+    } else if rest.is_empty() || rest.starts_with(' ') {
+        rest
+    } else {
+        return None;
+    };
+
+    let count = args
+        .split_whitespace()
+        .next()
+        .and_then(|arg| arg.parse::<usize>().ok())
+        .filter(|count| *count > 0)
+        .unwrap_or(HISTORY_DEFAULT_EXCHANGES);
+    Some(count.min(HISTORY_MAX_EXCHANGES))
+}
+
 fn parse_thinking_level(level: &str) -> Option<zdx_engine::config::ThinkingLevel> {
     match level.to_ascii_lowercase().as_str() {
         "off" => Some(zdx_engine::config::ThinkingLevel::Off),
@@ -272,8 +395,9 @@ mod tests {
     use std::collections::HashSet;
 
     use super::{
-        BotCommand, bypasses_queue, command_matches, is_topic_blocking_command, parse_command,
-        parse_model_command, parse_thinking_command, telegram_command_specs,
+        BotCommand, ThreadSubcommand, bypasses_queue, command_matches, is_topic_blocking_command,
+        parse_command, parse_history_command, parse_model_command, parse_thinking_command,
+        parse_thread_command, telegram_command_specs,
     };
 
     #[test]
@@ -394,9 +518,16 @@ mod tests {
         assert!(bypasses_queue("/status@zdx_bot"));
         assert!(bypasses_queue("/whereami"));
         assert!(bypasses_queue("/whereami@zdx_bot"));
+        assert!(bypasses_queue("/reload"));
+        assert!(bypasses_queue("/reminders"));
         assert!(!bypasses_queue("/new"));
         assert!(!bypasses_queue("/model"));
         assert!(!bypasses_queue("/handoff"));
+        assert!(bypasses_queue("/handoff_to_desktop"));
+        assert_eq!(
+            parse_command("/handoff-to-desktop@zdx_bot"),
+            Some(BotCommand::HandoffToDesktop)
+        );
         assert!(bypasses_queue("/tldr"));
         assert_eq!(parse_command("/tldr"), Some(BotCommand::Tldr));
         assert!(!bypasses_queue("/prompt-builder"));
@@ -410,6 +541,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_history_commands() {
+        assert_eq!(parse_history_command("/history"), Some(5));
+        assert_eq!(parse_history_command("/history 12"), Some(12));
+        assert_eq!(parse_history_command("/history@zdx_bot 3"), Some(3));
+        assert_eq!(parse_history_command("/history 500"), Some(50));
+        assert_eq!(parse_history_command("/history all"), Some(5));
+        assert_eq!(parse_history_command("/historyx"), None);
+        assert!(bypasses_queue("/history 2"));
+        assert!(is_topic_blocking_command("/history"));
+    }
+
+    #[test]
+    fn parse_thread_commands() {
+        assert_eq!(
+            parse_thread_command("/threads"),
+            Some(ThreadSubcommand::List)
+        );
+        assert_eq!(
+            parse_thread_command("/thread@zdx_bot new Research notes"),
+            Some(ThreadSubcommand::New(Some("Research notes".to_string())))
+        );
+        assert_eq!(
+            parse_thread_command("/thread new"),
+            Some(ThreadSubcommand::New(None))
+        );
+        assert_eq!(
+            parse_thread_command("/thread switch 2"),
+            Some(ThreadSubcommand::Switch(Some("2".to_string())))
+        );
+        assert_eq!(parse_thread_command("/threadid"), None);
+        assert_eq!(parse_command("/threadid"), Some(BotCommand::ThreadId));
+        assert!(is_topic_blocking_command("/threads"));
+    }
+
     #[test]
     fn parse_model_commands() {
         assert!(matches!(
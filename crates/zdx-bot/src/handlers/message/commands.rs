@@ -57,6 +57,21 @@ pub(super) async fn handle_thread_setup_commands(
             reply_ctx.topic_id,
         )
         .await?
+        || crate::chat_threads::handle_thread_command(
+            context,
+            incoming,
+            reply_ctx.reply_to_message_id,
+            reply_ctx.topic_id,
+        )
+        .await?
+        || super::history::handle_history_command(
+            context,
+            incoming,
+            thread_id,
+            reply_ctx.reply_to_message_id,
+            reply_ctx.topic_id,
+        )
+        .await?
         || handle_threadid_command(
             context,
             incoming,
@@ -65,6 +80,14 @@ pub(super) async fn handle_thread_setup_commands(
             reply_ctx.topic_id,
         )
         .await?
+        || handle_desktop_handoff_command(
+            context,
+            incoming,
+            thread_id,
+            reply_ctx.reply_to_message_id,
+            reply_ctx.topic_id,
+        )
+        .await?
         || handle_thread_commands(
             context,
             incoming,
@@ -158,6 +181,7 @@ pub(super) async fn handle_general_forum_commands(
             | BotCommand::Handoff
             | BotCommand::Commands
             | BotCommand::PromptBuilder
+            | BotCommand::HandoffToDesktop
             | BotCommand::Launcher
     ) {
         return Ok(false);
@@ -189,7 +213,12 @@ pub(super) async fn handle_general_forum_commands(
         BotCommand::Handoff => "/handoff must be used inside a topic, not General.",
         BotCommand::Commands => "/commands must be used inside a topic, not General.",
         BotCommand::PromptBuilder => "/prompt_builder must be used inside a topic, not General.",
+        BotCommand::HandoffToDesktop => {
+            "/handoff_to_desktop must be used inside a topic, not General."
+        }
         BotCommand::Exit => unreachable!("exit is handled by handle_exit_command"),
+        BotCommand::Reload => unreachable!("reload is handled by handle_reload_command"),
+        BotCommand::Reminders => unreachable!("reminders is handled by handle_reminders_command"),
         BotCommand::Status => unreachable!("status is handled by handle_status_command"),
         BotCommand::WhereAmI => unreachable!("whereami is handled by handle_whereami_command"),
         BotCommand::Tldr => unreachable!("tldr is handled by handle_tldr_command"),
@@ -244,6 +273,67 @@ pub(super) async fn handle_exit_command(
     Ok(true)
 }
 
+pub(super) async fn handle_reload_command(
+    context: &BotContext,
+    incoming: &crate::types::IncomingMessage,
+    reply_to_message_id: Option<i64>,
+) -> Result<bool> {
+    if !incoming.images.is_empty() || !incoming.audios.is_empty() {
+        return Ok(false);
+    }
+    if !incoming
+        .text
+        .as_deref()
+        .is_some_and(|text| matches!(parse_command(text), Some(BotCommand::Reload)))
+    {
+        return Ok(false);
+    }
+
+    // Reloading swaps allowlists and profiles for everyone, so it is limited
+    // to the operators listed in `priority_user_ids`.
+    let authorized = context
+        .config()
+        .telegram
+        .priority_user_ids
+        .contains(&incoming.user_id);
+    let message = match authorized.then(|| context.reload_config()) {
+        None => {
+            tracing::warn!(
+                user_id = incoming.user_id,
+                "Rejected /reload from non-priority user"
+            );
+            "⛔ /reload is limited to <code>[telegram] priority_user_ids</code>.".to_string()
+        }
+        Some(Ok(())) => {
+            let config = context.config();
+            format!(
+                "🔄 Config reloaded. Default model <code>{}</code>, thinking {}, {} allowlisted users, {} chats. Running turns keep their previous settings.",
+                config.model,
+                config.thinking_level.display_name(),
+                context.allowlist_user_ids().len(),
+                context.allowlist_chat_ids().len(),
+            )
+        }
+        Some(Err(err)) => {
+            tracing::warn!(%err, "Config reload failed");
+            format!(
+                "❌ Reload failed; keeping current config.\n<code>{}</code>",
+                escape_html(&format!("{err:#}"))
+            )
+        }
+    };
+    context
+        .client()
+        .send_message(
+            incoming.chat_id,
+            &message,
+            reply_to_message_id,
+            incoming.message_thread_id,
+        )
+        .await?;
+    Ok(true)
+}
+
 async fn handle_model_command(
     context: &BotContext,
     incoming: &crate::types::IncomingMessage,
@@ -505,6 +595,7 @@ async fn handle_status_command(
         branch: branch.as_deref(),
         cumulative_usage,
         latest_usage,
+        queue_wait: context.turn_scheduler().stats(),
     });
 
     context
@@ -585,6 +676,62 @@ async fn handle_threadid_command(
     Ok(true)
 }
 
+/// `/handoff_to_desktop`: replies with the `zdx --resume` command that opens
+/// this thread in the TUI. Both sides append to the same thread file, so the
+/// conversation can move back and forth.
+async fn handle_desktop_handoff_command(
+    context: &BotContext,
+    incoming: &crate::types::IncomingMessage,
+    thread_id: &str,
+    reply_to_message_id: Option<i64>,
+    topic_id: Option<i64>,
+) -> Result<bool> {
+    if !incoming.images.is_empty() || !incoming.audios.is_empty() {
+        return Ok(false);
+    }
+    if !incoming
+        .text
+        .as_deref()
+        .is_some_and(|text| matches!(parse_command(text), Some(BotCommand::HandoffToDesktop)))
+    {
+        return Ok(false);
+    }
+
+    let root = crate::command_picker::command_root(context, incoming.chat_id, thread_id)?;
+    let message = format_desktop_handoff_message(thread_id, &root);
+    context
+        .client()
+        .send_message(incoming.chat_id, &message, reply_to_message_id, topic_id)
+        .await?;
+    Ok(true)
+}
+
+pub(super) fn format_desktop_handoff_message(thread_id: &str, root: &Path) -> String {
+    let command = format!(
+        "zdx --root {} --resume {}",
+        shell_quote(&root.display().to_string()),
+        shell_quote(thread_id)
+    );
+    format!(
+        "💻 <b>Continue on desktop</b> <i>(tap to copy)</i>\n<code>{}</code>\n\nReplies from the TUI are saved to this same thread, so you can pick it up here again afterwards.",
+        escape_html(&command)
+    )
+}
+
+/// Quotes `value` for a POSIX shell when it contains anything beyond a safe
+/// path/ID character set.
+fn shell_quote(value: &str) -> String {
+    let is_safe = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "/._-~+:@%,".contains(c));
+    if is_safe {
+        value.to_string()
+    } else {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+}
+
 async fn handle_tldr_command(
     context: &BotContext,
     incoming: &crate::types::IncomingMessage,
@@ -885,10 +1032,7 @@ async fn handle_thread_commands(
     if !incoming.images.is_empty() || !incoming.audios.is_empty() {
         return Ok(false);
     }
-    let Some(text) = incoming.text.as_deref() else {
-        return Ok(false);
-    };
-    let Some(command) = parse_command(text) else {
+    let Some(command) = incoming.text.as_deref().and_then(parse_command) else {
         return Ok(false);
     };
 
@@ -921,12 +1065,15 @@ async fn handle_thread_commands(
         // Handoff/PromptBuilder run via the staging flow; Commands via the
         // picker handler; Tldr via handle_tldr_command.
         BotCommand::Exit
+        | BotCommand::Reload
+        | BotCommand::Reminders
         | BotCommand::Status
         | BotCommand::WhereAmI
         | BotCommand::Handoff
         | BotCommand::Commands
         | BotCommand::Tldr
         | BotCommand::ThreadId
+        | BotCommand::HandoffToDesktop
         | BotCommand::PromptBuilder => {
             return Ok(false);
         }
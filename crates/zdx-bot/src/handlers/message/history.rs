@@ -0,0 +1,226 @@
+//! `/history [n]`: show the last exchanges stored in the topic's thread.
+//!
+//! Short histories are sent as one HTML message; longer ones are attached as
+//! a `history.md` document so nothing is cut off.
+
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+use zdx_engine::core::thread_persistence::{self, ThreadEvent};
+
+use super::escape_html;
+use crate::bot::context::BotContext;
+use crate::commands::parse_history_command;
+
+/// Longest history sent inline; anything longer becomes a document.
+const HISTORY_MESSAGE_MAX_CHARS: usize = 3900;
+const HISTORY_DOCUMENT_NAME: &str = "history.md";
+
+/// One user message and the assistant's replies to it.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Exchange {
+    user: Option<String>,
+    ts: Option<String>,
+    assistant: Vec<String>,
+    tool_calls: usize,
+}
+
+pub(super) async fn handle_history_command(
+    context: &BotContext,
+    incoming: &crate::types::IncomingMessage,
+    thread_id: &str,
+    reply_to_message_id: Option<i64>,
+    topic_id: Option<i64>,
+) -> Result<bool> {
+    if !incoming.images.is_empty() || !incoming.audios.is_empty() {
+        return Ok(false);
+    }
+    let Some(count) = incoming.text.as_deref().and_then(parse_history_command) else {
+        return Ok(false);
+    };
+
+    let events =
+        thread_persistence::load_thread_events(thread_id).context("load thread history")?;
+    let exchanges = collect_exchanges(&events);
+    if exchanges.is_empty() {
+        context
+            .client()
+            .send_message(
+                incoming.chat_id,
+                "🗂 No history yet in this thread.",
+                reply_to_message_id,
+                topic_id,
+            )
+            .await?;
+        return Ok(true);
+    }
+
+    let shown = &exchanges[exchanges.len().saturating_sub(count)..];
+    let html = format_history_html(shown, exchanges.len());
+    if html.chars().count() <= HISTORY_MESSAGE_MAX_CHARS {
+        context
+            .client()
+            .send_message(incoming.chat_id, &html, reply_to_message_id, topic_id)
+            .await?;
+        return Ok(true);
+    }
+
+    let markdown = format_history_markdown(shown, thread_id);
+    context
+        .client()
+        .send_document(
+            incoming.chat_id,
+            markdown.as_bytes(),
+            HISTORY_DOCUMENT_NAME,
+            "text/markdown",
+            Some(&history_header(shown.len(), exchanges.len())),
+            reply_to_message_id,
+            topic_id,
+            None,
+        )
+        .await?;
+    Ok(true)
+}
+
+/// Groups thread events into exchanges, each starting at a user message.
+fn collect_exchanges(events: &[ThreadEvent]) -> Vec<Exchange> {
+    let mut exchanges: Vec<Exchange> = Vec::new();
+    for event in events {
+        match event {
+            ThreadEvent::Message { role, text, ts, .. } if role == "user" => {
+                exchanges.push(Exchange {
+                    user: Some(text.clone()),
+                    ts: Some(ts.clone()),
+                    ..Exchange::default()
+                });
+            }
+            ThreadEvent::Message { role, text, .. } if role == "assistant" => {
+                if exchanges.is_empty() {
+                    exchanges.push(Exchange::default());
+                }
+                if let Some(current) = exchanges.last_mut()
+                    && !text.trim().is_empty()
+                {
+                    current.assistant.push(text.trim().to_string());
+                }
+            }
+            ThreadEvent::ToolUse { .. } => {
+                if let Some(current) = exchanges.last_mut() {
+                    current.tool_calls += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    exchanges
+}
+
+fn history_header(shown: usize, total: usize) -> String {
+    if shown == total {
+        format!(
+            "🗂 History: {total} exchange{}",
+            if total == 1 { "" } else { "s" }
+        )
+    } else {
+        format!("🗂 History: last {shown} of {total} exchanges")
+    }
+}
+
+fn format_timestamp(ts: &str) -> String {
+    chrono::DateTime::parse_from_rfc3339(ts).map_or_else(
+        |_| ts.to_string(),
+        |dt| dt.format("%Y-%m-%d %H:%M").to_string(),
+    )
+}
+
+fn tool_summary(count: usize) -> String {
+    format!("🔧 {count} tool call{}", if count == 1 { "" } else { "s" })
+}
+
+fn format_history_html(shown: &[Exchange], total: usize) -> String {
+    let mut text = format!(
+        "<b>{}</b>",
+        escape_html(&history_header(shown.len(), total))
+    );
+    for exchange in shown {
+        text.push_str("\n\n");
+        if let Some(user) = &exchange.user {
+            let when = exchange
+                .ts
+                .as_deref()
+                .map(|ts| format!(" <i>{}</i>", escape_html(&format_timestamp(ts))))
+                .unwrap_or_default();
+            let _ = write!(
+                text,
+                "👤 <b>You</b>{when}\n<blockquote>{}</blockquote>\n",
+                escape_html(user.trim())
+            );
+        }
+        if exchange.tool_calls > 0 {
+            let _ = writeln!(text, "<i>{}</i>", tool_summary(exchange.tool_calls));
+        }
+        if exchange.assistant.is_empty() {
+            text.push_str("🤖 <i>(no reply)</i>");
+        } else {
+            let _ = write!(
+                text,
+                "🤖 <b>Bot</b>\n<blockquote expandable>{}</blockquote>",
+                escape_html(&exchange.assistant.join("\n\n"))
+            );
+        }
+    }
+    text
+}
+
+fn format_history_markdown(shown: &[Exchange], thread_id: &str) -> String {
+    let mut text = format!("# Thread `{thread_id}`\n");
+    for exchange in shown {
+        if let Some(user) = &exchange.user {
+            let when = exchange
+                .ts
+                .as_deref()
+                .map(|ts| format!(" ({})", format_timestamp(ts)))
+                .unwrap_or_default();
+            let _ = write!(text, "\n## You{when}\n\n{}\n", user.trim());
+        }
+        if exchange.tool_calls > 0 {
+            let _ = write!(text, "\n_{}_\n", tool_summary(exchange.tool_calls));
+        }
+        if !exchange.assistant.is_empty() {
+            let _ = write!(text, "\n## Bot\n\n{}\n", exchange.assistant.join("\n\n"));
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_collect_exchanges_groups_replies_and_tools() {
+        let events = vec![
+            ThreadEvent::user_message("first"),
+            ThreadEvent::assistant_message("reply one"),
+            ThreadEvent::user_message("second <b>"),
+            ThreadEvent::tool_use("t1", "Read", json!({})),
+            ThreadEvent::assistant_message("reply two"),
+        ];
+        let exchanges = collect_exchanges(&events);
+        assert_eq!(exchanges.len(), 2);
+        assert_eq!(exchanges[0].assistant, ["reply one"]);
+        assert_eq!(exchanges[1].tool_calls, 1);
+
+        let html = format_history_html(&exchanges[1..], exchanges.len());
+        assert!(html.starts_with("<b>🗂 History: last 1 of 2 exchanges</b>"));
+        assert!(html.contains("second &lt;b&gt;"));
+        assert!(html.contains("🔧 1 tool call"));
+        assert!(!html.contains("first"));
+
+        let markdown = format_history_markdown(&exchanges, "telegram-1");
+        assert!(markdown.contains("## You"));
+        assert!(markdown.contains("reply one\n\n## You"));
+    }
+}
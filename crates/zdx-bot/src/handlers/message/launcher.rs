@@ -128,7 +128,7 @@ pub(crate) async fn create_topic_resuming(
     chat_id: i64,
     source_thread_id: &str,
 ) -> Result<i64> {
-    if source_thread_id.starts_with("telegram-") {
+    if thread_persistence::is_bot_thread_id(source_thread_id) {
         anyhow::bail!("cannot resume a telegram topic thread: {source_thread_id}");
     }
     if !thread_persistence::thread_exists(source_thread_id) {
@@ -259,7 +259,7 @@ fn resumable_threads(
 ) -> Vec<zdx_engine::core::thread_persistence::ThreadSummary> {
     let root_str = root.display().to_string();
     all.into_iter()
-        .filter(|t| !t.id.starts_with("telegram-"))
+        .filter(|t| !t.is_bot_thread())
         .filter(|t| t.root_path.as_deref() == Some(root_str.as_str()))
         .take(cap)
         .collect()
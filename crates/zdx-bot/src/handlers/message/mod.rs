@@ -1,7 +1,10 @@
 use std::path::Path;
 
 use anyhow::Result;
-use commands::{handle_exit_command, handle_general_forum_commands, handle_thread_setup_commands};
+use commands::{
+    handle_exit_command, handle_general_forum_commands, handle_reload_command,
+    handle_thread_setup_commands,
+};
 use status::{discard_turn_status, finalize_preprocessing_cancelled, setup_preprocessing_status};
 use tokio_util::sync::CancellationToken;
 use turn::run_agent_turn;
@@ -9,10 +12,12 @@ use zdx_engine::config::ThinkingLevel;
 use zdx_engine::core::thread_persistence;
 
 use crate::bot::context::BotContext;
+use crate::bot::scheduler::QueueWaitStats;
 use crate::ingest::{self, AllowlistConfig};
 use crate::telegram::{InlineKeyboardMarkup, Message, ReplyParameters};
 
 mod commands;
+mod history;
 mod launcher;
 mod media;
 mod response;
@@ -48,9 +53,11 @@ pub(crate) async fn handle_message(context: &BotContext, message: Message) -> Re
     } else {
         None
     };
+    let user_ids = context.allowlist_user_ids();
+    let chat_ids = context.allowlist_chat_ids();
     let allowlist = AllowlistConfig {
-        user_ids: context.allowlist_user_ids(),
-        chat_ids: context.allowlist_chat_ids(),
+        user_ids: &user_ids,
+        chat_ids: &chat_ids,
     };
     let Some(incoming) = parse_message_with_status(
         context,
@@ -176,7 +183,14 @@ async fn handle_pre_agent_commands(
 ) -> Result<bool> {
     Ok(
         handle_general_forum_commands(context, incoming, reply_ctx.reply_to_message_id).await?
-            || handle_exit_command(context, incoming, reply_ctx.reply_to_message_id).await?,
+            || handle_exit_command(context, incoming, reply_ctx.reply_to_message_id).await?
+            || handle_reload_command(context, incoming, reply_ctx.reply_to_message_id).await?
+            || crate::reminders::handle_reminders_command(
+                context,
+                incoming,
+                reply_ctx.reply_to_message_id,
+            )
+            .await?,
     )
 }
 
@@ -220,6 +234,7 @@ struct SpawnRequest<'a> {
     thread: &'a zdx_engine::core::thread_persistence::Thread,
     messages: Vec<zdx_engine::providers::ChatMessage>,
     config: &'a zdx_engine::config::Config,
+    turn: zdx_engine::core::agent::TurnOptions,
 }
 
 struct StatusSnapshot<'a> {
@@ -233,6 +248,7 @@ struct StatusSnapshot<'a> {
     branch: Option<&'a str>,
     cumulative_usage: thread_persistence::Usage,
     latest_usage: thread_persistence::Usage,
+    queue_wait: QueueWaitStats,
 }
 
 pub(crate) fn escape_html(text: &str) -> String {
@@ -265,17 +281,14 @@ pub(crate) fn thread_id_for_chat(chat_id: i64, message_thread_id: Option<i64>) -
 /// persists new events to its source thread. One hop only (no chains/loops);
 /// a no-op when the thread has no alias.
 pub(crate) fn resolve_effective_thread_id(id: &str) -> String {
-    match zdx_engine::core::thread_persistence::read_thread_alias(id) {
-        Ok(Some(source)) => source,
-        _ => id.to_string(),
-    }
+    thread_persistence::resolve_thread_alias(id)
 }
 
 #[cfg(test)]
 mod tests {
     use std::path::{Path, PathBuf};
 
-    use super::commands::format_whereami_message;
+    use super::commands::{format_desktop_handoff_message, format_whereami_message};
     use super::media::{is_audio_path, is_image_path, is_voice_note_path, parse_final_response};
 
     #[test]
@@ -404,4 +417,13 @@ mod tests {
         assert!(msg.contains("CWD: <code>/work/zdx</code>"));
         assert!(!msg.contains("Bind this chat"));
     }
+
+    #[test]
+    fn desktop_handoff_shows_resume_command() {
+        let msg = format_desktop_handoff_message("telegram-42-topic-7", Path::new("/work/zdx"));
+        assert!(msg.contains("<code>zdx --root /work/zdx --resume telegram-42-topic-7</code>"));
+
+        let msg = format_desktop_handoff_message("telegram-42", Path::new("/work/my repo"));
+        assert!(msg.contains("--root '/work/my repo' --resume telegram-42"));
+    }
 }
@@ -4,6 +4,26 @@ use super::ReplyContext;
 use super::media::{parse_final_response, send_media_responses};
 use crate::bot::context::BotContext;
 
+/// Telegram's message length limit. Raw HTML tags count against it here, so
+/// chunks stay under the limit Telegram applies after entity parsing.
+const TELEGRAM_MESSAGE_MAX_CHARS: usize = 4096;
+/// Room kept free in each chunk for the tags `split_reply` closes at the end
+/// of a chunk and reopens at the start of the next one.
+const TAG_RESERVE_CHARS: usize = 256;
+/// Max characters of the reply preview in a document caption.
+const DOCUMENT_SUMMARY_MAX_CHARS: usize = 300;
+const REPLY_DOCUMENT_NAME: &str = "response.md";
+
+/// How a reply text is delivered.
+#[derive(Debug, PartialEq, Eq)]
+enum ReplyLayout {
+    /// One or more messages, split at paragraph boundaries.
+    Messages(Vec<String>),
+    /// Too many chunks: the full text is converted to markdown and attached
+    /// as a `.md` document.
+    Document,
+}
+
 pub(super) async fn send_final_response(
     context: &BotContext,
     incoming: &crate::types::IncomingMessage,
@@ -65,6 +85,38 @@ async fn send_text_response(
 ) -> Result<()> {
     tracing::info!(chat_id = incoming.chat_id, "Sending reply");
 
+    let max_messages = context.config().telegram.max_reply_messages;
+    let chunks = match plan_reply(text, max_messages) {
+        ReplyLayout::Messages(chunks) => chunks,
+        ReplyLayout::Document => {
+            return send_document_response(context, incoming, reply_ctx, status_message_id, text)
+                .await;
+        }
+    };
+
+    let mut chunks = chunks.iter();
+    let Some(first) = chunks.next() else {
+        return Ok(());
+    };
+    send_first_message(context, incoming, reply_ctx, status_message_id, first).await?;
+    for chunk in chunks {
+        context
+            .client()
+            .send_message(incoming.chat_id, chunk, None, reply_ctx.topic_id)
+            .await?;
+    }
+    Ok(())
+}
+
+/// Sends the first reply message: edits the status message in place when
+/// possible, otherwise replies to the user's message.
+async fn send_first_message(
+    context: &BotContext,
+    incoming: &crate::types::IncomingMessage,
+    reply_ctx: &ReplyContext,
+    status_message_id: Option<i64>,
+    text: &str,
+) -> Result<()> {
     if let Some(ref reply_parameters) = reply_ctx.cross_topic_reply_parameters {
         if let Some(msg_id) = status_message_id
             && let Err(err) = context
@@ -146,6 +198,332 @@ async fn send_text_response(
     Ok(())
 }
 
+async fn send_document_response(
+    context: &BotContext,
+    incoming: &crate::types::IncomingMessage,
+    reply_ctx: &ReplyContext,
+    status_message_id: Option<i64>,
+    text: &str,
+) -> Result<()> {
+    if let Some(msg_id) = status_message_id
+        && let Err(err) = context
+            .client()
+            .delete_message(incoming.chat_id, msg_id)
+            .await
+    {
+        tracing::warn!(msg_id, %err, "Failed to delete status message");
+    }
+
+    let reply_to_message_id = if reply_ctx.cross_topic_reply_parameters.is_some() {
+        None
+    } else {
+        reply_ctx.reply_to_message_id
+    };
+    let markdown = html_to_markdown(text);
+    context
+        .client()
+        .send_document(
+            incoming.chat_id,
+            markdown.as_bytes(),
+            REPLY_DOCUMENT_NAME,
+            "text/markdown",
+            Some(&document_caption(text)),
+            reply_to_message_id,
+            reply_ctx.topic_id,
+            reply_ctx.cross_topic_reply_parameters.clone(),
+        )
+        .await
+}
+
+fn plan_reply(text: &str, max_messages: usize) -> ReplyLayout {
+    let chunks = split_reply(text, TELEGRAM_MESSAGE_MAX_CHARS - TAG_RESERVE_CHARS);
+    if chunks.len() > 1 && chunks.len() > max_messages {
+        ReplyLayout::Document
+    } else {
+        ReplyLayout::Messages(chunks)
+    }
+}
+
+/// Splits Telegram HTML `text` into chunks of about `max_chars`, preferring
+/// paragraph breaks, then line breaks, then hard character cuts.
+///
+/// Tags still open at the end of a chunk (a `<pre>` spanning a paragraph
+/// break, say) are closed there and reopened at the start of the next chunk,
+/// so every chunk parses on its own. Those tags come on top of `max_chars`.
+fn split_reply(text: &str, max_chars: usize) -> Vec<String> {
+    let paragraphs = text.split("\n\n").flat_map(|paragraph| {
+        if paragraph.chars().count() <= max_chars {
+            vec![paragraph.to_string()]
+        } else {
+            let lines = paragraph
+                .lines()
+                .flat_map(|line| split_chars(line, max_chars));
+            pack(lines, "\n", max_chars)
+        }
+    });
+    balance_tags(pack(paragraphs, "\n\n", max_chars))
+}
+
+/// Closes the tags left open at the end of each chunk and reopens them, with
+/// their attributes, at the start of the next. Chunks left with only tags
+/// are dropped.
+fn balance_tags(chunks: Vec<String>) -> Vec<String> {
+    let mut open: Vec<(String, String)> = Vec::new();
+    chunks
+        .into_iter()
+        .filter_map(|chunk| {
+            let mut out: String = open.iter().map(|(_, tag)| tag.as_str()).collect();
+            out.push_str(&chunk);
+            for atom in atoms(&chunk) {
+                match parse_tag(atom) {
+                    Some(Tag::Open(name)) => open.push((name, atom.to_string())),
+                    Some(Tag::Close(name)) => {
+                        if let Some(index) = open.iter().rposition(|(open, _)| *open == name) {
+                            open.truncate(index);
+                        }
+                    }
+                    None => {}
+                }
+            }
+            for (name, _) in open.iter().rev() {
+                out.push_str("</");
+                out.push_str(name);
+                out.push('>');
+            }
+            (!strip_html_tags(&out).is_empty()).then_some(out)
+        })
+        .collect()
+}
+
+/// Greedily joins `pieces` (each at most `max_chars`) with `separator`.
+fn pack(
+    pieces: impl IntoIterator<Item = String>,
+    separator: &str,
+    max_chars: usize,
+) -> Vec<String> {
+    let separator_chars = separator.chars().count();
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    for piece in pieces {
+        let piece_chars = piece.chars().count();
+        if !current.is_empty() && current_chars + separator_chars + piece_chars > max_chars {
+            chunks.push(std::mem::take(&mut current));
+            current_chars = 0;
+        }
+        if !current.is_empty() {
+            current.push_str(separator);
+            current_chars += separator_chars;
+        }
+        current.push_str(&piece);
+        current_chars += piece_chars;
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Cuts an overlong line into pieces of at most `max_chars`, never inside a
+/// tag or an entity.
+fn split_chars(line: &str, max_chars: usize) -> Vec<String> {
+    if line.is_empty() {
+        return vec![String::new()];
+    }
+    let mut pieces = Vec::new();
+    let mut current = String::new();
+    let mut current_chars = 0;
+    for atom in atoms(line) {
+        let atom_chars = atom.chars().count();
+        if current_chars > 0 && current_chars + atom_chars > max_chars {
+            pieces.push(std::mem::take(&mut current));
+            current_chars = 0;
+        }
+        current.push_str(atom);
+        current_chars += atom_chars;
+    }
+    if !current.is_empty() {
+        pieces.push(current);
+    }
+    pieces
+}
+
+/// Splits HTML into tags (`<...>`), entities (`&...;`), and single chars.
+fn atoms(text: &str) -> Vec<&str> {
+    let mut atoms = Vec::new();
+    let mut rest = text;
+    while let Some(first) = rest.chars().next() {
+        let len = match first {
+            '<' => rest.find('>').map_or(1, |end| end + 1),
+            '&' => rest
+                .char_indices()
+                .skip(1)
+                .take(10)
+                .find(|(_, c)| !c.is_ascii_alphanumeric() && *c != '#')
+                .filter(|(_, c)| *c == ';')
+                .map_or(1, |(end, _)| end + 1),
+            _ => first.len_utf8(),
+        };
+        let (atom, tail) = rest.split_at(len);
+        atoms.push(atom);
+        rest = tail;
+    }
+    atoms
+}
+
+enum Tag {
+    Open(String),
+    Close(String),
+}
+
+/// Parses `<name ...>` or `</name>` into its lowercased tag name.
+fn parse_tag(atom: &str) -> Option<Tag> {
+    let inner = atom.strip_prefix('<')?.strip_suffix('>')?;
+    let (closing, inner) = match inner.strip_prefix('/') {
+        Some(inner) => (true, inner),
+        None => (false, inner),
+    };
+    let name: String = inner
+        .chars()
+        .take_while(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    Some(if closing {
+        Tag::Close(name)
+    } else {
+        Tag::Open(name)
+    })
+}
+
+/// Converts a Telegram HTML reply back to markdown for the `.md` attachment:
+/// `<pre>` becomes a fenced block (keeping a `language-*` class), inline tags
+/// become their markdown marks, links become `[text](href)`, and blockquotes
+/// get `> ` prefixes.
+fn html_to_markdown(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_pre = false;
+    let mut fence_open = false;
+    let mut links: Vec<String> = Vec::new();
+    let mut quote_starts: Vec<usize> = Vec::new();
+    for atom in atoms(text) {
+        let tag = parse_tag(atom);
+        if fence_open && !matches!(&tag, Some(Tag::Open(name)) if name == "code") {
+            out.push('\n');
+            fence_open = false;
+        }
+        match tag {
+            Some(Tag::Open(name)) => match name.as_str() {
+                "pre" => {
+                    if !out.is_empty() && !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```");
+                    in_pre = true;
+                    fence_open = true;
+                }
+                "code" if in_pre => {
+                    if let Some(language) = attribute(atom, "class")
+                        .and_then(|class| class.strip_prefix("language-").map(str::to_string))
+                    {
+                        out.push_str(&language);
+                    }
+                }
+                "code" => out.push('`'),
+                "b" | "strong" => out.push_str("**"),
+                "i" | "em" => out.push('_'),
+                "s" | "strike" | "del" => out.push_str("~~"),
+                "a" => {
+                    out.push('[');
+                    links.push(attribute(atom, "href").unwrap_or_default());
+                }
+                "blockquote" => quote_starts.push(out.len()),
+                _ => {}
+            },
+            Some(Tag::Close(name)) => match name.as_str() {
+                "pre" => {
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```");
+                    in_pre = false;
+                }
+                "code" if in_pre => {}
+                "code" => out.push('`'),
+                "b" | "strong" => out.push_str("**"),
+                "i" | "em" => out.push('_'),
+                "s" | "strike" | "del" => out.push_str("~~"),
+                "a" => {
+                    let href = links.pop().unwrap_or_default();
+                    out.push_str("](");
+                    out.push_str(&href);
+                    out.push(')');
+                }
+                "blockquote" => {
+                    if let Some(start) = quote_starts.pop() {
+                        let quoted = out.split_off(start);
+                        let quoted = quoted
+                            .lines()
+                            .map(|line| format!("> {line}"))
+                            .collect::<Vec<_>>()
+                            .join("\n");
+                        out.push_str(&quoted);
+                    }
+                }
+                _ => {}
+            },
+            None => out.push_str(&unescape_entities(atom)),
+        }
+    }
+    out
+}
+
+/// Value of a double-quoted `name="..."` attribute in an opening tag.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')?;
+    Some(unescape_entities(&tag[start..start + end]))
+}
+
+fn unescape_entities(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&")
+}
+
+/// Plain-text caption: the reply's first paragraph plus the full length.
+fn document_caption(text: &str) -> String {
+    let first_paragraph = text.split("\n\n").next().unwrap_or_default();
+    let summary = strip_html_tags(first_paragraph);
+    let summary = summary.trim();
+    let mut caption: String = summary.chars().take(DOCUMENT_SUMMARY_MAX_CHARS).collect();
+    if summary.chars().count() > DOCUMENT_SUMMARY_MAX_CHARS {
+        caption.push('…');
+    }
+    let total_chars = text.chars().count();
+    format!("{caption}\n\n📎 Full response attached ({total_chars} chars)")
+        .trim_start()
+        .to_string()
+}
+
+/// Drops Telegram HTML tags and unescapes entities for plain-text captions.
+fn strip_html_tags(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut in_tag = false;
+    for ch in text.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => out.push(ch),
+            _ => {}
+        }
+    }
+    unescape_entities(&out)
+}
+
 pub(super) fn normalize_reply_text(text: &str) -> String {
     let mut out = String::new();
     let mut prev_blank = false;
@@ -165,3 +543,101 @@ pub(super) fn normalize_reply_text(text: &str) -> String {
 
     out.trim().to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_at_paragraph_boundaries() {
+        let text = ["a".repeat(6), "b".repeat(6), "c".repeat(6)].join("\n\n");
+        assert_eq!(
+            split_reply(&text, 14),
+            ["aaaaaa\n\nbbbbbb", "cccccc"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn oversized_paragraphs_fall_back_to_lines_then_chars() {
+        let text = format!("{}\n{}", "a".repeat(5), "b".repeat(12));
+        assert_eq!(
+            split_reply(&text, 8),
+            ["aaaaa", "bbbbbbbb", "bbbb"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn too_many_chunks_become_a_document() {
+        let paragraph = "x".repeat(3000);
+        let text = [paragraph.as_str(); 3].join("\n\n");
+
+        assert_eq!(
+            plan_reply("short", 0),
+            ReplyLayout::Messages(vec!["short".into()])
+        );
+        assert!(matches!(plan_reply(&text, 3), ReplyLayout::Messages(chunks) if chunks.len() == 3));
+        assert_eq!(plan_reply(&text, 2), ReplyLayout::Document);
+    }
+
+    #[test]
+    fn long_code_block_is_closed_and_reopened_across_chunks() {
+        let code = (0..40)
+            .map(|i| format!("let value_{i} = {i};"))
+            .collect::<Vec<_>>()
+            .join("\n\n");
+        let text = format!(
+            "<b>Here it is:</b>\n\n<pre><code class=\"language-rust\">{code}</code></pre>\n\nDone."
+        );
+
+        let chunks = split_reply(&text, 200);
+        assert!(chunks.len() > 2, "{chunks:?}");
+        for chunk in &chunks[1..chunks.len() - 1] {
+            assert!(
+                chunk.starts_with("<pre><code class=\"language-rust\">")
+                    && chunk.ends_with("</code></pre>"),
+                "{chunk}"
+            );
+        }
+        for chunk in &chunks {
+            let opens = chunk.matches("<pre>").count();
+            assert_eq!(opens, chunk.matches("</pre>").count(), "{chunk}");
+            assert_eq!(
+                chunk.matches("<code").count(),
+                chunk.matches("</code>").count(),
+                "{chunk}"
+            );
+        }
+        assert!(chunks.last().unwrap().ends_with("</code></pre>\n\nDone."));
+    }
+
+    #[test]
+    fn hard_cuts_never_split_a_tag_or_entity() {
+        assert_eq!(
+            split_reply("aaaaaa&amp;bb", 8),
+            ["aaaaaa", "&amp;bb"].map(String::from)
+        );
+        assert_eq!(
+            split_reply("aaaaaa<b>bb</b>", 8),
+            ["aaaaaa", "<b>bb</b>"].map(String::from)
+        );
+    }
+
+    #[test]
+    fn document_attachment_is_converted_back_to_markdown() {
+        let html = "<b>Title</b> &amp; <a href=\"https://e.x/?a=1&amp;b=2\">link</a>\n\n\
+                    <pre><code class=\"language-rust\">fn main() {}\n</code></pre>\n\n\
+                    <blockquote>quoted\nlines</blockquote> <code>x &lt; y</code>";
+        assert_eq!(
+            html_to_markdown(html),
+            "**Title** & [link](https://e.x/?a=1&b=2)\n\n\
+             ```rust\nfn main() {}\n```\n\n\
+             > quoted\n> lines `x < y`"
+        );
+    }
+
+    #[test]
+    fn document_caption_is_plain_text_summary() {
+        let caption = document_caption("<b>Summary</b> &amp; more\n\nrest of reply");
+        assert!(caption.starts_with("Summary & more\n\n📎"), "{caption}");
+    }
+}
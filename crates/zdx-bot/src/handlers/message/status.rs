@@ -1,7 +1,6 @@
 use std::fmt::Write as _;
 
 use tokio_util::sync::CancellationToken;
-use zdx_engine::core::events::AgentEvent;
 use zdx_engine::core::thread_persistence;
 use zdx_engine::models::{ModelOption, ModelPricing};
 use zdx_engine::providers::{ProviderAuthMode, provider_for_model};
@@ -9,11 +8,15 @@ use zdx_engine::providers::{ProviderAuthMode, provider_for_model};
 use super::{StatusSnapshot, TurnStatus, escape_html};
 use crate::agent;
 use crate::bot::context::BotContext;
+use crate::bot::scheduler::QueueWaitStats;
 use crate::telegram::{InlineKeyboardButton, InlineKeyboardMarkup, Message};
 
 /// Minimum interval between Telegram status message edits (avoid rate limiting).
 pub(super) const STATUS_DEBOUNCE: std::time::Duration = std::time::Duration::from_secs(3);
 
+/// How often a running tool's elapsed time is refreshed in the status message.
+pub(super) const PROGRESS_TICK: std::time::Duration = std::time::Duration::from_secs(5);
+
 pub(super) async fn setup_turn_status(
     context: &BotContext,
     incoming: &crate::types::IncomingMessage,
@@ -180,11 +183,11 @@ pub(super) async fn update_status(
     context: &BotContext,
     chat_id: i64,
     status: &TurnStatus,
-    event: &AgentEvent,
+    new_status: Option<String>,
     current_status: &mut String,
     last_edit: &mut std::time::Instant,
 ) {
-    let Some(new_status) = agent::event_to_status(event) else {
+    let Some(new_status) = new_status else {
         return;
     };
     if new_status == *current_status {
@@ -261,10 +264,21 @@ pub(super) fn format_status_message(snapshot: &StatusSnapshot<'_>) -> String {
         provider.auth_mode(),
         snapshot.cumulative_usage,
     ));
+    lines.push(format_queue_wait_line(snapshot.queue_wait));
 
     lines.join("\n")
 }
 
+fn format_queue_wait_line(stats: QueueWaitStats) -> String {
+    format!(
+        "Queue wait (bot-wide): <code>{} turns, avg {:.1}s, max {:.1}s, {} throttled</code>",
+        stats.turns,
+        stats.average_wait().as_secs_f64(),
+        stats.max_wait.as_secs_f64(),
+        stats.throttled
+    )
+}
+
 fn format_context_usage_line(
     model_meta: Option<&ModelOption>,
     latest_usage: thread_persistence::Usage,
@@ -1,12 +1,16 @@
 use anyhow::Result;
+use zdx_engine::core::agent::TurnOptions;
 use zdx_engine::core::events::{AgentEvent, TurnStatus as AgentTurnStatus};
 use zdx_engine::core::thread_persistence;
 
 use super::response::send_final_response;
-use super::status::{STATUS_DEBOUNCE, cleanup_turn_status, setup_turn_status, update_status};
+use super::status::{
+    PROGRESS_TICK, STATUS_DEBOUNCE, cleanup_turn_status, setup_turn_status, update_status,
+};
 use super::{ReplyContext, SpawnRequest, TurnResult, TurnStatus, format_user_error_message};
 use crate::agent;
 use crate::bot::context::BotContext;
+use crate::reminders::ReminderTarget;
 
 pub(super) async fn run_agent_turn(
     context: &BotContext,
@@ -21,17 +25,11 @@ pub(super) async fn run_agent_turn(
         .map_or_else(|| resolved_root.root.clone(), std::path::PathBuf::from);
     let model_override = thread_persistence::read_thread_model_override(thread_id)?;
     let thinking_override = thread_persistence::read_thread_thinking_override(thread_id)?;
-    let config = if model_override.is_some() || thinking_override.is_some() {
-        let mut cfg = context.config();
-        if let Some(ref model_id) = model_override {
-            cfg.model.clone_from(model_id);
-        }
-        if let Some(level) = thinking_override {
-            cfg.thinking_level = level;
-        }
-        cfg
-    } else {
-        context.config()
+    let config = context.config();
+    let turn = TurnOptions {
+        model: model_override,
+        thinking_level: thinking_override,
+        ..TurnOptions::default()
     };
     let (mut thread, mut messages) = agent::load_thread_state(thread_id)?;
     let pending_topic_title = thread_persistence::read_thread_pending_topic_title(thread_id)?;
@@ -80,6 +78,7 @@ pub(super) async fn run_agent_turn(
         thread: &thread,
         messages,
         config: &config,
+        turn,
     };
     let mut handle = spawn_or_fail(context, &incoming, &status, spawn).await?;
     let result = stream_turn_events(context, &incoming, &mut handle, &mut status).await;
@@ -97,11 +96,17 @@ async fn spawn_or_fail(
     let handle = agent::spawn_agent_turn(
         spawn.messages,
         spawn.config,
+        spawn.turn,
         spawn.worktree_root,
         context.bot_instruction_layer(),
         spawn.thread_id,
         spawn.thread,
-        context.tool_config(),
+        &context.turn_tool_config(ReminderTarget {
+            chat_id: incoming.chat_id,
+            topic_id: incoming.message_thread_id,
+            user_id: incoming.user_id,
+            is_forum: incoming.is_forum,
+        }),
     );
 
     match handle {
@@ -139,6 +144,10 @@ async fn stream_turn_events(
     let mut got_result = false;
     let mut had_error = false;
     let mut error_message = None;
+    let progress_updates = context.config().telegram.progress_updates;
+    let mut progress = agent::ToolProgress::default();
+    let mut progress_tick = tokio::time::interval(PROGRESS_TICK);
+    progress_tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
 
     loop {
         tokio::select! {
@@ -147,6 +156,14 @@ async fn stream_turn_events(
                 handle.cancel.cancel();
                 break;
             }
+            _ = progress_tick.tick(), if progress_updates && progress.is_running() => {
+                // Refresh the elapsed time of a long-running tool.
+                let line = progress.status_line();
+                update_status(context, incoming.chat_id, status, line, &mut current_status, &mut last_edit).await;
+            }
+            Some(question) = handle.questions.recv() => {
+                crate::questions::ask(context, incoming.chat_id, incoming.message_thread_id, question).await;
+            }
             event = handle.rx.recv() => {
                 let Some(event) = event else { break; };
                 match &*event {
@@ -175,8 +192,15 @@ async fn stream_turn_events(
                     AgentEvent::Notice { kind, message, .. } => {
                         tracing::info!(?kind, message, "Agent notice event");
                     }
+                    AgentEvent::LoopDetected { kind, message, .. } => {
+                        tracing::warn!(?kind, message, "Agent loop safeguard tripped");
+                    }
                     other => {
-                        update_status(context, incoming.chat_id, status, other, &mut current_status, &mut last_edit).await;
+                        progress.observe(other);
+                        if progress_updates {
+                            let line = progress.status_line().or_else(|| agent::event_to_status(other));
+                            update_status(context, incoming.chat_id, status, line, &mut current_status, &mut last_edit).await;
+                        }
                     }
                 }
             }
@@ -0,0 +1,103 @@
+//! Still frames from Telegram video media (video notes, video stickers),
+//! extracted with ffmpeg so the model can look at them as images.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, anyhow};
+
+const FFMPEG_BIN: &str = "ffmpeg";
+/// Longest edge of an extracted frame, in pixels.
+const FRAME_MAX_EDGE: u32 = 512;
+
+/// Returns `count` timestamps (seconds) spread evenly across `duration_secs`,
+/// each in the middle of its segment so fade-ins and fade-outs are skipped.
+/// Unknown (zero) durations yield a single frame at the start.
+pub(super) fn frame_timestamps(duration_secs: u64, count: usize) -> Vec<f64> {
+    if duration_secs == 0 || count == 0 {
+        return vec![0.0];
+    }
+    #[allow(clippy::cast_precision_loss)]
+    let (duration, count_f) = (duration_secs as f64, count as f64);
+    (0..count)
+        .map(|idx| {
+            #[allow(clippy::cast_precision_loss)]
+            let idx = idx as f64;
+            duration * (idx + 0.5) / count_f
+        })
+        .collect()
+}
+
+/// Extracts one PNG frame per timestamp from `video`, scaled down to
+/// [`FRAME_MAX_EDGE`]. Returns `Ok(None)` when ffmpeg is not on `PATH`.
+///
+/// # Errors
+/// Returns an error if ffmpeg fails or produces no frame for a timestamp.
+pub(super) async fn extract_frames(
+    video: &Path,
+    timestamps: &[f64],
+) -> Result<Option<Vec<Vec<u8>>>> {
+    extract_frames_with(FFMPEG_BIN, video, timestamps).await
+}
+
+async fn extract_frames_with(
+    ffmpeg_bin: &str,
+    video: &Path,
+    timestamps: &[f64],
+) -> Result<Option<Vec<Vec<u8>>>> {
+    let scale = format!(
+        "scale='min({FRAME_MAX_EDGE},iw)':'min({FRAME_MAX_EDGE},ih)':force_original_aspect_ratio=decrease"
+    );
+    let mut frames = Vec::with_capacity(timestamps.len());
+    for timestamp in timestamps {
+        let result = tokio::process::Command::new(ffmpeg_bin)
+            .args(["-hide_banner", "-loglevel", "error", "-ss"])
+            .arg(format!("{timestamp:.2}"))
+            .arg("-i")
+            .arg(video)
+            .args(["-frames:v", "1", "-vf", &scale])
+            .args(["-f", "image2pipe", "-vcodec", "png", "-"])
+            .output()
+            .await;
+        let completed = match result {
+            Ok(completed) => completed,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(anyhow::Error::new(err).context("spawn ffmpeg for frames")),
+        };
+        if !completed.status.success() {
+            return Err(anyhow!(
+                "ffmpeg frame extraction failed: {}",
+                String::from_utf8_lossy(&completed.stderr).trim()
+            ));
+        }
+        if completed.stdout.is_empty() {
+            return Err(anyhow!("ffmpeg produced no frame at {timestamp:.2}s"))
+                .context("extract video frame");
+        }
+        frames.push(completed.stdout);
+    }
+    Ok(Some(frames))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_frame_timestamps_spread_across_duration() {
+        assert_eq!(frame_timestamps(8, 4), vec![1.0, 3.0, 5.0, 7.0]);
+        assert_eq!(frame_timestamps(0, 4), vec![0.0]);
+        assert_eq!(frame_timestamps(10, 1), vec![5.0]);
+    }
+
+    #[tokio::test]
+    async fn test_extract_frames_returns_none_when_ffmpeg_missing() {
+        let frames = extract_frames_with(
+            "zdx-nonexistent-ffmpeg-binary-xyz",
+            Path::new("note.mp4"),
+            &[0.0],
+        )
+        .await
+        .unwrap();
+        assert!(frames.is_none());
+    }
+}
@@ -1,3 +1,5 @@
+mod frames;
+
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -6,15 +8,20 @@ use anyhow::{Context, Result, anyhow};
 use base64::Engine;
 use base64::engine::general_purpose::STANDARD as BASE64;
 use tokio_util::sync::CancellationToken;
-use zdx_engine::config::{Config, paths};
+use zdx_engine::config::Config;
 
-use crate::telegram::{Audio, Document, Message, PhotoSize, TelegramClient, Voice};
+use crate::telegram::{
+    Audio, Document, Message, PhotoSize, Sticker, TelegramClient, VideoNote, Voice,
+};
 use crate::transcribe;
 use crate::types::{IncomingAudio, IncomingDocument, IncomingImage, IncomingMessage};
 
 const MAX_IMAGE_BYTES: u64 = 3_932_160; // 3.75MB
 const MAX_AUDIO_BYTES: u64 = 25 * 1024 * 1024; // 25MB
 const MAX_DOCUMENT_BYTES: u64 = 25 * 1024 * 1024; // 25MB
+const MAX_VIDEO_BYTES: u64 = 20 * 1024 * 1024; // 20MB (Bot API download limit)
+/// Frames sampled from one video note.
+const VIDEO_NOTE_FRAMES: usize = 4;
 const SUPPORTED_IMAGE_MIMES: &[&str] = &["image/jpeg", "image/png", "image/gif", "image/webp"];
 
 pub(crate) struct AllowlistConfig<'a> {
@@ -35,6 +42,17 @@ struct AttachmentCollection {
     had_attachments: bool,
 }
 
+struct VideoSource<'a> {
+    file_id: &'a str,
+    file_size: Option<u64>,
+    /// Duration in seconds (0 when unknown).
+    duration: u64,
+    frames: usize,
+    extension: &'a str,
+    label: String,
+    thumbnail: Option<&'a PhotoSize>,
+}
+
 struct AudioSource<'a> {
     file_id: &'a str,
     file_size: Option<u64>,
@@ -208,6 +226,9 @@ async fn collect_attachments(
             }
         }
 
+        had_attachments |=
+            collect_sticker_and_video_note(client, target, current, &mut images).await?;
+
         if let Some(voice) = current.voice.as_ref() {
             had_attachments = true;
             push_attachment(
@@ -251,6 +272,35 @@ async fn collect_attachments(
     })
 }
 
+/// Loads `current`'s sticker and video note frames as images. Returns whether
+/// the message had either.
+async fn collect_sticker_and_video_note(
+    client: &TelegramClient,
+    target: &MessageTarget,
+    current: &Message,
+    images: &mut Vec<IncomingImage>,
+) -> Result<bool> {
+    if let Some(sticker) = current.sticker.as_ref() {
+        push_attachment(
+            images,
+            load_sticker(client, target.chat, current.id, sticker),
+            "sticker",
+        )
+        .await?;
+    }
+    if let Some(video_note) = current.video_note.as_ref() {
+        let mut frames = Vec::new();
+        push_attachment(
+            &mut frames,
+            load_video_note(client, target.chat, current.id, video_note),
+            "video note",
+        )
+        .await?;
+        images.extend(frames.into_iter().flatten());
+    }
+    Ok(current.sticker.is_some() || current.video_note.is_some())
+}
+
 async fn push_attachment<T>(
     output: &mut Vec<T>,
     load_future: impl std::future::Future<Output = Result<Option<T>>>,
@@ -289,15 +339,13 @@ async fn handle_empty_message(
 fn extract_text(message: &Message) -> Option<String> {
     let mut parts = Vec::new();
     for current in std::iter::once(message).chain(message.grouped_messages.iter()) {
-        if let Some(text) = current.text.as_deref() {
+        for text in [current.text.as_deref(), current.caption.as_deref()]
+            .into_iter()
+            .flatten()
+        {
             let trimmed = text.trim();
-            if !trimmed.is_empty() {
-                parts.push(trimmed.to_string());
-            }
-        }
-        if let Some(caption) = current.caption.as_deref() {
-            let trimmed = caption.trim();
-            if !trimmed.is_empty() {
+            // Some clients repeat the album caption on every item.
+            if !trimmed.is_empty() && !parts.iter().any(|part| part == trimmed) {
                 parts.push(trimmed.to_string());
             }
         }
@@ -325,14 +373,35 @@ async fn load_photo_attachment(
     message_id: i64,
     photo: &PhotoSize,
 ) -> Result<Option<IncomingImage>> {
-    if photo.file_size.unwrap_or(0) > MAX_IMAGE_BYTES {
-        tracing::debug!(chat_id, "Skipping photo exceeding max image size");
+    load_image_by_id(
+        client,
+        chat_id,
+        message_id,
+        &photo.file_id,
+        photo.file_size,
+        "photo",
+    )
+    .await
+}
+
+/// Downloads an image file (photo, sticker, thumbnail) and checks its type;
+/// `Ok(None)` when it is too large or not a supported image format.
+async fn load_image_by_id(
+    client: &TelegramClient,
+    chat_id: i64,
+    message_id: i64,
+    file_id: &str,
+    file_size: Option<u64>,
+    fallback_stem: &str,
+) -> Result<Option<IncomingImage>> {
+    if file_size.unwrap_or(0) > MAX_IMAGE_BYTES {
+        tracing::debug!(chat_id, "Skipping image exceeding max image size");
         return Ok(None);
     }
 
-    let (file_path, bytes) = download_file_bytes(client, &photo.file_id).await?;
+    let (file_path, bytes) = download_file_bytes(client, file_id).await?;
     if bytes.len() as u64 > MAX_IMAGE_BYTES {
-        tracing::debug!(chat_id, "Downloaded photo exceeds max image size");
+        tracing::debug!(chat_id, "Downloaded image exceeds max image size");
         return Ok(None);
     }
 
@@ -341,8 +410,8 @@ async fn load_photo_attachment(
         return Ok(None);
     };
 
-    let filename =
-        file_name_from_path(&file_path).unwrap_or_else(|| format!("photo_{message_id}.bin"));
+    let filename = file_name_from_path(&file_path)
+        .unwrap_or_else(|| format!("{fallback_stem}_{message_id}.bin"));
     let local_path = save_media_bytes(chat_id, message_id, &filename, &bytes)?;
     let data = BASE64.encode(&bytes);
 
@@ -350,9 +419,157 @@ async fn load_photo_attachment(
         local_path,
         mime_type,
         data,
+        label: None,
     }))
 }
 
+/// Loads a sticker as an image. Static stickers are `.webp` already; video
+/// stickers use their first frame (via ffmpeg); animated `.tgs` stickers
+/// (Lottie) use the thumbnail Telegram renders from the first frame.
+async fn load_sticker(
+    client: &TelegramClient,
+    chat_id: i64,
+    message_id: i64,
+    sticker: &Sticker,
+) -> Result<Option<IncomingImage>> {
+    let label = match sticker.emoji.as_deref() {
+        Some(emoji) => format!("Sticker {emoji}"),
+        None => "Sticker".to_string(),
+    };
+    let image = if sticker.is_video {
+        load_video_frames(
+            client,
+            chat_id,
+            message_id,
+            VideoSource {
+                file_id: &sticker.file_id,
+                file_size: sticker.file_size,
+                duration: 0,
+                frames: 1,
+                extension: "webm",
+                label,
+                thumbnail: sticker.thumbnail.as_ref(),
+            },
+        )
+        .await?
+        .and_then(|frames| frames.into_iter().next())
+    } else if sticker.is_animated {
+        let Some(thumbnail) = sticker.thumbnail.as_ref() else {
+            tracing::debug!(chat_id, "Animated sticker has no thumbnail");
+            return Ok(None);
+        };
+        load_photo_attachment(client, chat_id, message_id, thumbnail)
+            .await?
+            .map(|image| with_label(image, label))
+    } else {
+        load_image_by_id(
+            client,
+            chat_id,
+            message_id,
+            &sticker.file_id,
+            sticker.file_size,
+            "sticker",
+        )
+        .await?
+        .map(|image| with_label(image, label))
+    };
+    Ok(image)
+}
+
+/// Loads a few keyframes spread across a video note.
+async fn load_video_note(
+    client: &TelegramClient,
+    chat_id: i64,
+    message_id: i64,
+    video_note: &VideoNote,
+) -> Result<Option<Vec<IncomingImage>>> {
+    load_video_frames(
+        client,
+        chat_id,
+        message_id,
+        VideoSource {
+            file_id: &video_note.file_id,
+            file_size: video_note.file_size,
+            duration: video_note.duration,
+            frames: VIDEO_NOTE_FRAMES,
+            extension: "mp4",
+            label: "Video note".to_string(),
+            thumbnail: video_note.thumbnail.as_ref(),
+        },
+    )
+    .await
+}
+
+/// Downloads a video and extracts `source.frames` still frames from it. Falls
+/// back to the Telegram thumbnail when the video is too large, ffmpeg is
+/// missing, or extraction fails.
+async fn load_video_frames(
+    client: &TelegramClient,
+    chat_id: i64,
+    message_id: i64,
+    source: VideoSource<'_>,
+) -> Result<Option<Vec<IncomingImage>>> {
+    if source.file_size.unwrap_or(0) <= MAX_VIDEO_BYTES {
+        let (file_path, bytes) = download_file_bytes(client, source.file_id).await?;
+        let filename = file_name_from_path(&file_path)
+            .unwrap_or_else(|| format!("video_{message_id}.{}", source.extension));
+        let local_path = save_media_bytes(chat_id, message_id, &filename, &bytes)?;
+        let timestamps = frames::frame_timestamps(source.duration, source.frames);
+        match frames::extract_frames(&local_path, &timestamps).await {
+            Ok(Some(frames)) => {
+                let total = frames.len();
+                let images = frames
+                    .into_iter()
+                    .zip(&timestamps)
+                    .enumerate()
+                    .map(|(idx, (frame, timestamp))| {
+                        let label = if total == 1 {
+                            source.label.clone()
+                        } else {
+                            format!(
+                                "{} frame {}/{total} at {timestamp:.1}s",
+                                source.label,
+                                idx + 1
+                            )
+                        };
+                        let name = format!("{filename}_frame{}.png", idx + 1);
+                        let local_path = save_media_bytes(chat_id, message_id, &name, &frame)?;
+                        Ok(IncomingImage {
+                            local_path,
+                            mime_type: "image/png".to_string(),
+                            data: BASE64.encode(&frame),
+                            label: Some(label),
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                return Ok(Some(images));
+            }
+            Ok(None) => tracing::warn!(chat_id, "ffmpeg not found on PATH; using video thumbnail"),
+            Err(err) => {
+                tracing::warn!(chat_id, %err, "Video frame extraction failed; using thumbnail");
+            }
+        }
+    } else {
+        tracing::debug!(
+            chat_id,
+            "Skipping video exceeding max size; using thumbnail"
+        );
+    }
+
+    let Some(thumbnail) = source.thumbnail else {
+        return Ok(None);
+    };
+    let image = load_photo_attachment(client, chat_id, message_id, thumbnail).await?;
+    Ok(image.map(|image| vec![with_label(image, format!("{} thumbnail", source.label))]))
+}
+
+fn with_label(image: IncomingImage, label: String) -> IncomingImage {
+    IncomingImage {
+        label: Some(label),
+        ..image
+    }
+}
+
 async fn load_document_image(
     client: &TelegramClient,
     chat_id: i64,
@@ -388,6 +605,7 @@ async fn load_document_image(
         local_path,
         mime_type,
         data,
+        label: None,
     }))
 }
 
@@ -588,7 +806,7 @@ fn save_media_bytes(
     filename: &str,
     bytes: &[u8],
 ) -> Result<PathBuf> {
-    let dir = paths::zdx_home().join("telegram").join(chat_id.to_string());
+    let dir = crate::media::chat_media_dir(chat_id);
     fs::create_dir_all(&dir).context("create media directory")?;
     let safe_name = Path::new(filename)
         .file_name()
@@ -0,0 +1,152 @@
+//! Inline queries: `@bot question` typed in any chat.
+//!
+//! Allowlisted users get a one-shot answer from a no-tools exec turn, capped
+//! at `[telegram] inline_max_tokens`, returned as a single inline article that
+//! posts the answer as plain text. Telegram sends a new query on every
+//! keystroke, so each user's latest query is debounced and superseded
+//! queries are dropped before any model call.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio::sync::Mutex;
+use zdx_engine::config::ThinkingLevel;
+use zdx_engine::core::subagent::{ExecSubagentOptions, run_exec_subagent};
+
+use crate::bot::BotContext;
+use crate::telegram::{InlineQuery, InlineQueryResultArticle, InputTextMessageContent};
+
+/// Quiet period before a query is answered (the user may still be typing).
+const INLINE_DEBOUNCE: Duration = Duration::from_millis(800);
+const INLINE_TIMEOUT: Duration = Duration::from_secs(30);
+const MESSAGE_TEXT_MAX_CHARS: usize = 4096;
+const DESCRIPTION_MAX_CHARS: usize = 120;
+
+const INLINE_SYSTEM_PROMPT: &str = "You answer Telegram inline queries. The answer is posted \
+    as-is into another chat, so reply with the answer only: concise plain text, no preamble, \
+    no markdown headings.";
+
+/// Latest inline query id per user, used to drop superseded queries.
+pub(crate) type InlineQueryMap = Arc<Mutex<HashMap<i64, String>>>;
+
+pub(crate) fn new_inline_query_map() -> InlineQueryMap {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+/// Answers `query` in a background task (after the debounce).
+pub(crate) async fn handle_inline_query(
+    context: &Arc<BotContext>,
+    latest: &InlineQueryMap,
+    query: InlineQuery,
+) {
+    let max_tokens = context.config().telegram.inline_max_tokens;
+    let allowed = context.allowlist_user_ids().contains(&query.from.id);
+    let text = query.query.trim().to_string();
+    if !allowed || max_tokens == 0 || text.is_empty() {
+        if !allowed {
+            tracing::warn!(user_id = query.from.id, "Denied inline query");
+        }
+        if let Err(err) = context.client().answer_inline_query(&query.id, &[]).await {
+            tracing::debug!(%err, "Failed to answer inline query");
+        }
+        return;
+    }
+
+    latest.lock().await.insert(query.from.id, query.id.clone());
+    let context = Arc::clone(context);
+    let latest = Arc::clone(latest);
+    tokio::spawn(async move {
+        tokio::time::sleep(INLINE_DEBOUNCE).await;
+        if !is_latest(&latest, &query).await {
+            return;
+        }
+
+        let results = match answer(&context, query.from.id, &text, max_tokens).await {
+            Ok(answer) => vec![answer_article(&query.id, &text, &answer)],
+            Err(err) => {
+                tracing::warn!(user_id = query.from.id, %err, "Inline query turn failed");
+                Vec::new()
+            }
+        };
+        if !is_latest(&latest, &query).await {
+            return;
+        }
+        if let Err(err) = context
+            .client()
+            .answer_inline_query(&query.id, &results)
+            .await
+        {
+            tracing::warn!(%err, "Failed to answer inline query");
+        }
+
+        let mut latest = latest.lock().await;
+        if latest.get(&query.from.id) == Some(&query.id) {
+            latest.remove(&query.from.id);
+        }
+    });
+}
+
+async fn is_latest(latest: &InlineQueryMap, query: &InlineQuery) -> bool {
+    latest.lock().await.get(&query.from.id) == Some(&query.id)
+}
+
+async fn answer(context: &BotContext, user_id: i64, text: &str, max_tokens: u32) -> Result<String> {
+    // Private chats use chat_id == user_id, so a profile bound to the user's
+    // DM applies; otherwise this is the bot root.
+    let root = context.root_for_chat(user_id).root;
+    let options = ExecSubagentOptions {
+        model: Some(context.config().model),
+        system_prompt: Some(INLINE_SYSTEM_PROMPT.to_string()),
+        thinking_level: Some(ThinkingLevel::Off),
+        no_tools: true,
+        event_filter: Some(vec!["turn_finished".to_string()]),
+        timeout: Some(INLINE_TIMEOUT),
+        activity_kind: Some("helper:inline".to_string()),
+        thread_origin_kind: Some("helper:inline".to_string()),
+        max_tokens: Some(max_tokens),
+        ..Default::default()
+    };
+    run_exec_subagent(&root, text, &options).await
+}
+
+fn answer_article(query_id: &str, question: &str, answer: &str) -> InlineQueryResultArticle {
+    let answer = answer.trim();
+    InlineQueryResultArticle {
+        kind: "article",
+        id: query_id.to_string(),
+        title: truncate_chars(question, DESCRIPTION_MAX_CHARS),
+        description: Some(truncate_chars(answer, DESCRIPTION_MAX_CHARS)),
+        input_message_content: InputTextMessageContent {
+            message_text: truncate_chars(answer, MESSAGE_TEXT_MAX_CHARS),
+        },
+    }
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn article_uses_question_as_title_and_truncates_answer() {
+        let answer = "x".repeat(5000);
+        let article = answer_article("q1", "what is rust?", &answer);
+
+        assert_eq!(article.title, "what is rust?");
+        assert_eq!(
+            article.input_message_content.message_text.chars().count(),
+            MESSAGE_TEXT_MAX_CHARS
+        );
+        assert!(article.description.unwrap().ends_with('…'));
+    }
+}
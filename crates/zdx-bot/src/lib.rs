@@ -17,11 +17,16 @@ use crate::telegram::{CallbackQuery, TelegramClient, TelegramSettings};
 
 mod agent;
 mod bot;
+mod chat_threads;
 mod command_picker;
 mod commands;
 mod followups;
 mod handlers;
 mod ingest;
+mod inline_query;
+pub mod media;
+mod questions;
+mod reminders;
 mod staging;
 pub mod telegram;
 mod topic_title;
@@ -29,11 +34,18 @@ mod transcribe;
 mod types;
 
 const TELEGRAM_INSTRUCTION_LAYER: &str = zdx_engine::prompts::TELEGRAM_INSTRUCTION_LAYER;
+/// Quiet period after the latest album item before the album is dispatched.
 const MEDIA_GROUP_DEBOUNCE: Duration = Duration::from_millis(1500);
+const MEDIA_GC_INTERVAL: Duration = Duration::from_hours(24);
 
 type MediaGroupKey = (i64, Option<i64>, i64, String);
-type PendingMediaGroups =
-    Arc<Mutex<std::collections::HashMap<MediaGroupKey, crate::telegram::Message>>>;
+type PendingMediaGroups = Arc<Mutex<std::collections::HashMap<MediaGroupKey, PendingMediaGroup>>>;
+
+/// Album items received so far (Telegram sends one message per item).
+struct PendingMediaGroup {
+    items: Vec<crate::telegram::Message>,
+    last_item_at: std::time::Instant,
+}
 
 /// Exit code used to signal an active supervisor to restart the bot.
 pub const EXIT_REQUESTED: i32 = 42;
@@ -66,13 +78,10 @@ pub async fn run_with_config_and_root(config: Config, root: PathBuf) -> Result<(
 /// Returns an error if the operation fails.
 pub async fn run_named_with_config_and_root(
     service_name: &str,
-    mut config: Config,
+    config: Config,
     root: PathBuf,
 ) -> Result<()> {
-    // Apply telegram-specific model + thinking_level
-    config.model.clone_from(&config.telegram.model);
-    config.thinking_level = config.telegram.thinking_level;
-    let settings = TelegramSettings::from_config(&config)?;
+    let (config, settings) = prepare_bot_config(config)?;
     zdx_engine::pidfile::ensure_unique(service_name)
         .with_context(|| format!("ensure unique PID for {service_name}"))?;
     let _pid_guard = zdx_engine::pidfile::write(service_name)
@@ -88,7 +97,50 @@ pub async fn run_named_with_config_and_root(
         chats = ?config.telegram.allowlist_chat_ids,
         "Bot config",
     );
-    run_bot(config, settings, root).await
+    Box::pin(run_bot(config, settings, root)).await
+}
+
+/// Applies the Telegram-specific model + thinking level and resolves the
+/// bot settings (token, allowlists).
+pub(crate) fn prepare_bot_config(mut config: Config) -> Result<(Config, TelegramSettings)> {
+    config.model.clone_from(&config.telegram.model);
+    config.thinking_level = config.telegram.thinking_level;
+    let settings = TelegramSettings::from_config(&config)?;
+    Ok((config, settings))
+}
+
+/// Runs media GC at startup and then daily, off the async runtime.
+fn spawn_media_gc_loop(policy: media::GcPolicy) {
+    if policy.is_disabled() {
+        return;
+    }
+    tokio::spawn(async move {
+        let mut tick = tokio::time::interval(MEDIA_GC_INTERVAL);
+        tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            tick.tick().await;
+            let result = tokio::task::spawn_blocking(move || {
+                media::collect_garbage_in(
+                    &media::media_root(),
+                    &zdx_engine::config::paths::threads_dir(),
+                    policy,
+                    std::time::SystemTime::now(),
+                    false,
+                )
+            })
+            .await;
+            match result {
+                Ok(Ok(report)) if !report.deleted.is_empty() => tracing::info!(
+                    deleted = report.deleted.len(),
+                    freed_bytes = report.freed_bytes,
+                    "Media GC"
+                ),
+                Ok(Ok(_)) => {}
+                Ok(Err(err)) => tracing::warn!(%err, "Media GC failed"),
+                Err(err) => tracing::warn!(%err, "Media GC task panicked"),
+            }
+        }
+    });
 }
 
 async fn run_bot(config: Config, settings: TelegramSettings, root: PathBuf) -> Result<()> {
@@ -98,7 +150,9 @@ async fn run_bot(config: Config, settings: TelegramSettings, root: PathBuf) -> R
         Ok(()) => tracing::info!(count = command_specs.len(), "Telegram command menu updated"),
         Err(err) => tracing::error!(%err, "Failed to update Telegram command menu"),
     }
-    let tool_config = ToolConfig::default();
+    let tool_config = ToolConfig::from_config(&config);
+    let reminders = reminders::ReminderStore::load(reminders::ReminderStore::default_path())
+        .context("load reminders")?;
 
     let cancel_map = new_cancel_map();
     let queue_cancel_map = new_queue_cancel_map();
@@ -119,12 +173,17 @@ async fn run_bot(config: Config, settings: TelegramSettings, root: PathBuf) -> R
             cancel_map,
             queue_cancel_map,
             followup_map: followups::new_followup_map(),
+            question_map: questions::new_question_map(),
             staging_map: staging::new_staging_map(),
             command_picker_map: command_picker::new_command_picker_map(),
             launcher_map: crate::handlers::message::new_launcher_map(),
+            reminders: Arc::new(reminders),
         },
     ));
     let chat_queues = new_chat_queues();
+    reminders::spawn_delivery_loop(&context, &chat_queues);
+    spawn_media_gc_loop(media::GcPolicy::from_config(&context.config()));
+    let inline_queries = inline_query::new_inline_query_map();
     let pending_media_groups: PendingMediaGroups =
         Arc::new(Mutex::new(std::collections::HashMap::new()));
 
@@ -132,6 +191,7 @@ async fn run_bot(config: Config, settings: TelegramSettings, root: PathBuf) -> R
     let poll_timeout = Duration::from_secs(30);
     let shutdown = tokio::signal::ctrl_c();
     tokio::pin!(shutdown);
+    let mut hangup = HangupSignal::install();
 
     tracing::info!(
         allowlist_users = allowlist_user_len,
@@ -146,6 +206,7 @@ async fn run_bot(config: Config, settings: TelegramSettings, root: PathBuf) -> R
                 tracing::info!("Shutting down Telegram bot");
                 break;
             }
+            () = hangup.recv() => reload_on_hangup(&context),
             () = context.exit_notified() => {
                 tracing::info!("Exit requested via /exit command");
                 zdx_engine::pidfile::remove("bot");
@@ -178,6 +239,9 @@ async fn run_bot(config: Config, settings: TelegramSettings, root: PathBuf) -> R
                     if let Some(callback) = update.callback_query {
                         handle_callback_query(&context, &client, &chat_queues, callback).await;
                     }
+                    if let Some(query) = update.inline_query {
+                        inline_query::handle_inline_query(&context, &inline_queries, query).await;
+                    }
                 }
             }
         }
@@ -186,6 +250,56 @@ async fn run_bot(config: Config, settings: TelegramSettings, root: PathBuf) -> R
     Ok(())
 }
 
+fn reload_on_hangup(context: &BotContext) {
+    match context.reload_config() {
+        Ok(()) => {
+            let config = context.config();
+            tracing::info!(
+                model = %config.model,
+                thinking = %config.thinking_level.display_name(),
+                "Config reloaded on SIGHUP",
+            );
+        }
+        Err(err) => {
+            tracing::error!(%err, "Config reload on SIGHUP failed; keeping current config");
+        }
+    }
+}
+
+/// SIGHUP listener used to trigger a config reload. Never fires on
+/// platforms without Unix signals.
+struct HangupSignal {
+    #[cfg(unix)]
+    signal: Option<tokio::signal::unix::Signal>,
+}
+
+impl HangupSignal {
+    fn install() -> Self {
+        #[cfg(unix)]
+        {
+            let signal = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::hangup())
+                .inspect_err(|err| tracing::warn!(%err, "Failed to install SIGHUP handler"))
+                .ok();
+            Self { signal }
+        }
+        #[cfg(not(unix))]
+        {
+            Self {}
+        }
+    }
+
+    async fn recv(&mut self) {
+        #[cfg(unix)]
+        if let Some(signal) = self.signal.as_mut() {
+            if signal.recv().await.is_some() {
+                return;
+            }
+            self.signal = None;
+        }
+        std::future::pending::<()>().await;
+    }
+}
+
 async fn route_message_update(
     chat_queues: &ChatQueueMap,
     context: &Arc<BotContext>,
@@ -198,29 +312,61 @@ async fn route_message_update(
     };
 
     let mut pending = pending_media_groups.lock().await;
-    if let Some(existing) = pending.get_mut(&key) {
-        existing.grouped_messages.push(message);
+    if let Some(group) = pending.get_mut(&key) {
+        group.items.push(message);
+        group.last_item_at = std::time::Instant::now();
         return;
     }
 
-    pending.insert(key.clone(), message);
+    pending.insert(
+        key.clone(),
+        PendingMediaGroup {
+            items: vec![message],
+            last_item_at: std::time::Instant::now(),
+        },
+    );
     drop(pending);
 
     let queues = Arc::clone(chat_queues);
     let context = Arc::clone(context);
     let pending_media_groups = Arc::clone(pending_media_groups);
     tokio::spawn(async move {
-        tokio::time::sleep(MEDIA_GROUP_DEBOUNCE).await;
-        let message = {
+        let mut wait = MEDIA_GROUP_DEBOUNCE;
+        let items = loop {
+            tokio::time::sleep(wait).await;
             let mut pending = pending_media_groups.lock().await;
-            pending.remove(&key)
+            let Some(group) = pending.get(&key) else {
+                return;
+            };
+            // Each new item restarts the window so slow uploads stay in one album.
+            let remaining = MEDIA_GROUP_DEBOUNCE.saturating_sub(group.last_item_at.elapsed());
+            if !remaining.is_zero() {
+                wait = remaining;
+                continue;
+            }
+            if let Some(group) = pending.remove(&key) {
+                break group.items;
+            }
+            return;
         };
-        if let Some(message) = message {
+        if let Some(message) = assemble_media_group(items) {
             dispatch_message(&queues, &context, message).await;
         }
     });
 }
 
+/// Folds album items into one message: the earliest item (which carries the
+/// caption and is the reply target) holds the rest in `grouped_messages`.
+fn assemble_media_group(
+    mut items: Vec<crate::telegram::Message>,
+) -> Option<crate::telegram::Message> {
+    items.sort_by_key(|item| item.id);
+    let mut items = items.into_iter();
+    let mut primary = items.next()?;
+    primary.grouped_messages.extend(items);
+    Some(primary)
+}
+
 fn media_group_key(message: &crate::telegram::Message) -> Option<MediaGroupKey> {
     Some((
         message.chat.id,
@@ -305,8 +451,12 @@ async fn handle_callback_query(
         followups::handle_callback(context, chat_queues, client, &callback, rest).await;
     } else if let Some(rest) = data.strip_prefix("stg:") {
         staging::handle_callback(context, chat_queues, client, &callback, rest).await;
+    } else if let Some(rest) = data.strip_prefix("rem:") {
+        reminders::handle_callback(context, client, &callback, rest).await;
     } else if let Some(rest) = data.strip_prefix("cmd:") {
         command_picker::handle_callback(context, chat_queues, client, &callback, rest).await;
+    } else if let Some(rest) = data.strip_prefix("th:") {
+        chat_threads::handle_callback(client, &callback, rest).await;
     } else if let Some(rest) = data.strip_prefix("nt:") {
         crate::handlers::message::handle_launcher_callback(
             context.as_ref(),
@@ -721,7 +871,9 @@ async fn handle_thinking_callback(
 mod tests {
     use serde_json::json;
 
-    use super::{media_group_key, parse_cancel_callback, parse_queue_cancel_callback};
+    use super::{
+        assemble_media_group, media_group_key, parse_cancel_callback, parse_queue_cancel_callback,
+    };
     use crate::telegram::Message;
 
     fn test_message(id: i64, media_group_id: Option<&str>, text: Option<&str>) -> Message {
@@ -746,6 +898,20 @@ mod tests {
         assert_eq!(media_group_key(&non_album), None);
     }
 
+    #[test]
+    fn assembles_album_in_message_order() {
+        let album = assemble_media_group(vec![
+            test_message(102, Some("album-1"), None),
+            test_message(100, Some("album-1"), Some("caption")),
+            test_message(101, Some("album-1"), None),
+        ])
+        .expect("non-empty album");
+
+        assert_eq!(album.id, 100);
+        let grouped: Vec<i64> = album.grouped_messages.iter().map(|m| m.id).collect();
+        assert_eq!(grouped, [101, 102]);
+    }
+
     #[test]
     fn parses_cancel_callbacks() {
         assert_eq!(parse_cancel_callback("cancel:1:2"), Some((1, 2)));
@@ -0,0 +1,318 @@
+//! Telegram media storage (`$ZDX_HOME/telegram/<chat_id>/`) and its garbage
+//! collection.
+//!
+//! Attachments are saved per chat so the agent can read them by path. GC
+//! deletes files older than `[telegram] media_retention_days`, then the
+//! oldest files until the total fits `media_max_mb`. Files whose path appears
+//! in a thread modified within the retention window (the agent may still read
+//! them) are never deleted.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use anyhow::{Context, Result};
+use zdx_engine::config::{Config, paths};
+
+const SECS_PER_DAY: u64 = 24 * 60 * 60;
+/// Window for reference tracking when age-based retention is off.
+const DEFAULT_REFERENCE_DAYS: u64 = 30;
+
+/// Root directory for saved Telegram media; each chat has a subdirectory.
+pub fn media_root() -> PathBuf {
+    paths::zdx_home().join("telegram")
+}
+
+/// Returns the directory media from `chat_id` is saved to.
+pub(crate) fn chat_media_dir(chat_id: i64) -> PathBuf {
+    media_root().join(chat_id.to_string())
+}
+
+/// Retention limits for one GC pass.
+#[derive(Debug, Clone, Copy)]
+pub struct GcPolicy {
+    /// Delete files older than this (`None` = no age limit).
+    pub max_age: Option<Duration>,
+    /// Delete oldest files until the total fits (`None` = no size limit).
+    pub max_bytes: Option<u64>,
+    /// Files referenced by threads modified within this window are kept.
+    pub reference_window: Duration,
+}
+
+impl GcPolicy {
+    /// Builds the policy from `[telegram] media_retention_days` / `media_max_mb`
+    /// (0 disables either limit).
+    pub fn from_config(config: &Config) -> Self {
+        let days = config.telegram.media_retention_days;
+        let max_age = (days > 0).then(|| Duration::from_secs(days * SECS_PER_DAY));
+        let max_mb = config.telegram.media_max_mb;
+        Self {
+            max_age,
+            max_bytes: (max_mb > 0).then(|| max_mb * 1024 * 1024),
+            reference_window: max_age
+                .unwrap_or(Duration::from_secs(DEFAULT_REFERENCE_DAYS * SECS_PER_DAY)),
+        }
+    }
+
+    /// True when neither limit is set, so GC has nothing to do.
+    pub fn is_disabled(&self) -> bool {
+        self.max_age.is_none() && self.max_bytes.is_none()
+    }
+}
+
+/// Outcome of a GC pass.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GcReport {
+    /// Media files found.
+    pub scanned: usize,
+    /// Files deleted (or that would be, in a dry run).
+    pub deleted: Vec<PathBuf>,
+    /// Bytes freed by the deleted files.
+    pub freed_bytes: u64,
+    /// Files past a limit that were kept because a recent thread references them.
+    pub kept_referenced: usize,
+    /// Bytes left after the pass.
+    pub remaining_bytes: u64,
+}
+
+struct MediaFile {
+    path: PathBuf,
+    size: u64,
+    modified: SystemTime,
+}
+
+/// Runs GC over the default media and threads directories.
+///
+/// # Errors
+/// Returns an error if the media directory can't be read.
+pub fn collect_garbage(config: &Config, dry_run: bool) -> Result<GcReport> {
+    collect_garbage_in(
+        &media_root(),
+        &paths::threads_dir(),
+        GcPolicy::from_config(config),
+        SystemTime::now(),
+        dry_run,
+    )
+}
+
+/// Runs GC over `media_root`, protecting paths referenced by recent threads
+/// in `threads_dir`. With `dry_run`, reports what would be deleted.
+///
+/// # Errors
+/// Returns an error if the media directory can't be read.
+pub fn collect_garbage_in(
+    media_root: &Path,
+    threads_dir: &Path,
+    policy: GcPolicy,
+    now: SystemTime,
+    dry_run: bool,
+) -> Result<GcReport> {
+    let mut files = list_media_files(media_root)?;
+    files.sort_by_key(|file| file.modified);
+    let mut report = GcReport {
+        scanned: files.len(),
+        remaining_bytes: files.iter().map(|file| file.size).sum(),
+        ..GcReport::default()
+    };
+    if policy.is_disabled() || files.is_empty() {
+        return Ok(report);
+    }
+
+    let referenced = referenced_paths(media_root, threads_dir, now - policy.reference_window);
+    let age_cutoff = policy.max_age.map(|max_age| now - max_age);
+    let mut kept_referenced = HashSet::new();
+    for file in &files {
+        let too_old = age_cutoff.is_some_and(|cutoff| file.modified < cutoff);
+        let too_big = policy
+            .max_bytes
+            .is_some_and(|max_bytes| report.remaining_bytes > max_bytes);
+        if !too_old && !too_big {
+            continue;
+        }
+        if referenced.contains(&file.path) {
+            kept_referenced.insert(&file.path);
+            continue;
+        }
+        if !dry_run && let Err(err) = fs::remove_file(&file.path) {
+            tracing::warn!(path = %file.path.display(), %err, "Failed to delete media file");
+            continue;
+        }
+        report.deleted.push(file.path.clone());
+        report.freed_bytes += file.size;
+        report.remaining_bytes -= file.size;
+    }
+    report.kept_referenced = kept_referenced.len();
+    if !dry_run {
+        remove_empty_chat_dirs(media_root);
+    }
+    Ok(report)
+}
+
+/// Lists files in the per-chat directories (numeric names) under `media_root`,
+/// skipping other state kept there (e.g. `reminders.json`).
+fn list_media_files(media_root: &Path) -> Result<Vec<MediaFile>> {
+    let entries = match fs::read_dir(media_root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err).context("read media directory"),
+    };
+    let mut files = Vec::new();
+    for dir in entries.flatten() {
+        let is_chat_dir = dir
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.parse::<i64>().is_ok());
+        if !is_chat_dir || !dir.path().is_dir() {
+            continue;
+        }
+        let Ok(chat_entries) = fs::read_dir(dir.path()) else {
+            continue;
+        };
+        for entry in chat_entries.flatten() {
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_file() {
+                files.push(MediaFile {
+                    path: entry.path(),
+                    size: meta.len(),
+                    modified: meta.modified().unwrap_or(SystemTime::UNIX_EPOCH),
+                });
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Collects media paths mentioned in thread files modified since `since`.
+fn referenced_paths(media_root: &Path, threads_dir: &Path, since: SystemTime) -> HashSet<PathBuf> {
+    let mut referenced = HashSet::new();
+    let Some(prefix) = media_root.to_str().map(|root| format!("{root}/")) else {
+        return referenced;
+    };
+    let Ok(entries) = fs::read_dir(threads_dir) else {
+        return referenced;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let recent = entry
+            .metadata()
+            .and_then(|meta| meta.modified())
+            .is_ok_and(|modified| modified >= since);
+        if !recent || path.extension().is_none_or(|ext| ext != "jsonl") {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        referenced.extend(find_media_paths(&content, &prefix));
+    }
+    referenced
+}
+
+/// Extracts every path starting with `prefix` from `text`. Paths end at
+/// whitespace, a quote, or a JSON escape; trailing sentence punctuation
+/// (`Image attachment saved at /…/1_a.jpg.`) is dropped.
+fn find_media_paths(text: &str, prefix: &str) -> Vec<PathBuf> {
+    text.match_indices(prefix)
+        .map(|(start, _)| {
+            let rest = &text[start..];
+            let end = rest
+                .find(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | '`'))
+                .unwrap_or(rest.len());
+            PathBuf::from(rest[..end].trim_end_matches(['.', ',', ';', ':', ')']))
+        })
+        .collect()
+}
+
+fn remove_empty_chat_dirs(media_root: &Path) {
+    let Ok(entries) = fs::read_dir(media_root) else {
+        return;
+    };
+    for dir in entries.flatten() {
+        let path = dir.path();
+        let is_chat_dir = dir
+            .file_name()
+            .to_str()
+            .is_some_and(|name| name.parse::<i64>().is_ok());
+        if is_chat_dir && fs::read_dir(&path).is_ok_and(|mut entries| entries.next().is_none()) {
+            let _ = fs::remove_dir(&path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use super::*;
+
+    fn temp_root() -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir().join(format!("zdx-bot-media-{nanos}"))
+    }
+
+    fn write_file(path: &Path, size: usize, modified: SystemTime) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; size]).unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+    }
+
+    #[test]
+    fn gc_drops_old_then_oldest_files_but_keeps_referenced() {
+        let root = temp_root();
+        let media = root.join("telegram");
+        let threads = root.join("threads");
+        let now = SystemTime::now();
+        let day = Duration::from_secs(SECS_PER_DAY);
+
+        let old = media.join("42").join("1_old.jpg");
+        let old_referenced = media.join("42").join("2_kept.jpg");
+        let mid = media.join("-7").join("3_mid.ogg");
+        let new = media.join("-7").join("4_new.ogg");
+        write_file(&old, 10, now - day * 40);
+        write_file(&old_referenced, 10, now - day * 40);
+        write_file(&mid, 100, now - day * 5);
+        write_file(&new, 100, now - day);
+        write_file(&media.join("reminders.json"), 10, now - day * 400);
+        write_file(&threads.join("telegram-42.jsonl"), 0, now);
+        fs::write(
+            threads.join("telegram-42.jsonl"),
+            format!(
+                "{{\"type\":\"message\",\"text\":\"Image attachment saved at {}.\"}}\n",
+                old_referenced.display()
+            ),
+        )
+        .unwrap();
+
+        let policy = GcPolicy {
+            max_age: Some(day * 30),
+            max_bytes: Some(150),
+            reference_window: day * 30,
+        };
+        let dry = collect_garbage_in(&media, &threads, policy, now, true).unwrap();
+        assert_eq!(dry.deleted, vec![old.clone(), mid.clone()]);
+        assert!(old.exists());
+
+        let report = collect_garbage_in(&media, &threads, policy, now, false).unwrap();
+        assert_eq!(report.scanned, 4);
+        assert_eq!(report.deleted, vec![old.clone(), mid.clone()]);
+        assert_eq!(report.freed_bytes, 110);
+        assert_eq!(report.kept_referenced, 1);
+        assert_eq!(report.remaining_bytes, 110);
+        assert!(!old.exists() && !mid.exists());
+        assert!(old_referenced.exists() && new.exists());
+        assert!(media.join("reminders.json").exists());
+
+        let _ = fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,152 @@
+//! `Ask_User` questions over Telegram.
+//!
+//! When a turn calls `Ask_User`, the bot posts the question in the turn's
+//! chat/topic and parks it here. The user's next plain-text message there
+//! answers it instead of being queued as a new turn. After
+//! `[telegram] ask_user_timeout_secs` the question is dismissed and the agent
+//! continues without an answer.
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use zdx_engine::core::ask_user::UserQuestion;
+
+use crate::bot::context::BotContext;
+use crate::handlers::message::escape_html;
+use crate::telegram::Message;
+
+/// Questions awaiting a reply, keyed by (`chat_id`, `topic_id`; 0 for no
+/// topic). Parallel tool calls can ask more than one; replies answer them
+/// in order.
+pub(crate) type QuestionMap = Arc<Mutex<HashMap<(i64, i64), VecDeque<UserQuestion>>>>;
+
+pub(crate) fn new_question_map() -> QuestionMap {
+    Arc::new(Mutex::new(HashMap::new()))
+}
+
+/// Posts `question` to the chat/topic and waits for the user's reply.
+pub(crate) async fn ask(
+    context: &BotContext,
+    chat_id: i64,
+    topic_id: Option<i64>,
+    question: UserQuestion,
+) {
+    let text = format!(
+        "❓ {}\n\n<i>Reply with your next message.</i>",
+        escape_html(&question.question)
+    );
+    if let Err(err) = context
+        .client()
+        .send_message(chat_id, &text, None, topic_id)
+        .await
+    {
+        tracing::warn!(chat_id, %err, "Failed to send Ask_User question");
+        question.dismiss("The question could not be delivered to the user.");
+        return;
+    }
+
+    let key = (chat_id, topic_id.unwrap_or(0));
+    let tool_use_id = question.tool_use_id.clone();
+    context
+        .question_map()
+        .lock()
+        .expect("question lock poisoned")
+        .entry(key)
+        .or_default()
+        .push_back(question);
+
+    let timeout_secs = context.config().telegram.ask_user_timeout_secs;
+    let map = Arc::clone(context.question_map());
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(timeout_secs)).await;
+        if let Some(question) = take_question(&map, key, &tool_use_id) {
+            question.dismiss(format!(
+                "The user did not reply within {timeout_secs}s. Continue with your best judgment."
+            ));
+        }
+    });
+}
+
+/// Answers the oldest pending question in the message's chat/topic with its
+/// text. Returns `false` (leaving the message to the queue) for commands,
+/// non-text messages, or when nothing is waiting.
+pub(crate) fn try_answer(context: &BotContext, message: &Message) -> bool {
+    let Some(text) = message.text.as_deref().map(str::trim) else {
+        return false;
+    };
+    if text.is_empty() || text.starts_with('/') {
+        return false;
+    }
+    let key = (message.chat.id, message.effective_thread_id().unwrap_or(0));
+    answer_next(context.question_map(), key, text)
+}
+
+fn answer_next(map: &QuestionMap, key: (i64, i64), text: &str) -> bool {
+    let mut map = map.lock().expect("question lock poisoned");
+    let Some(queue) = map.get_mut(&key) else {
+        return false;
+    };
+    let mut answered = false;
+    while let Some(question) = queue.pop_front() {
+        // A canceled turn leaves its question behind; skip it.
+        if question.answer(text) {
+            answered = true;
+            break;
+        }
+    }
+    if queue.is_empty() {
+        map.remove(&key);
+    }
+    answered
+}
+
+fn take_question(map: &QuestionMap, key: (i64, i64), tool_use_id: &str) -> Option<UserQuestion> {
+    let mut map = map.lock().expect("question lock poisoned");
+    let queue = map.get_mut(&key)?;
+    let index = queue
+        .iter()
+        .position(|question| question.tool_use_id == tool_use_id)?;
+    let question = queue.remove(index);
+    if queue.is_empty() {
+        map.remove(&key);
+    }
+    question
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn replies_answer_the_topic_queue_and_timeouts_remove_by_id() {
+        let (handle, mut rx) = zdx_engine::core::ask_user::channel();
+        let map = new_question_map();
+        let key = (-100, 7);
+
+        let first = tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.ask("toolu_1", "Which branch?").await }
+        });
+        let second = tokio::spawn(async move { handle.ask("toolu_2", "Deploy?").await });
+        for _ in 0..2 {
+            let question = rx.recv().await.unwrap();
+            map.lock()
+                .unwrap()
+                .entry(key)
+                .or_default()
+                .push_back(question);
+        }
+        assert_eq!(map.lock().unwrap()[&key].len(), 2);
+
+        assert!(!answer_next(&map, (-100, 8), "wrong topic"));
+        let timed_out = take_question(&map, key, "toolu_2").unwrap();
+        timed_out.dismiss("timeout");
+        assert!(answer_next(&map, key, "main"));
+        assert!(!answer_next(&map, key, "nothing left"));
+        assert!(map.lock().unwrap().is_empty());
+
+        assert_eq!(first.await.unwrap(), Ok("main".to_string()));
+        assert_eq!(second.await.unwrap(), Err("timeout".to_string()));
+    }
+}
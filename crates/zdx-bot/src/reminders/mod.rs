@@ -0,0 +1,290 @@
+//! Scheduled reminders and follow-up turns.
+//!
+//! Bot turns get a `Schedule_Message` tool bound to their chat/topic (see
+//! `tool.rs`). Reminders persist in `$ZDX_HOME/telegram/reminders.json`, so
+//! they survive restarts; a delivery loop checks for due ones every
+//! `DELIVERY_TICK` (15 seconds; reminders that came due while the bot was
+//! down fire on the first check). `message` reminders are sent as-is; `turn` reminders post a notice
+//! and dispatch their text as the user's next message in that topic.
+//! Delivery is at-most-once: a reminder is removed before it is sent.
+//!
+//! `/reminders` lists the chat's pending reminders with `rem:x:{id}` cancel
+//! buttons.
+
+mod store;
+mod tool;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde_json::json;
+pub(crate) use store::{Reminder, ReminderKind, ReminderStore, ReminderTarget};
+pub(crate) use tool::with_schedule_tool;
+
+use crate::bot::context::BotContext;
+use crate::bot::queue::{ChatQueueMap, dispatch_message};
+use crate::commands::{BotCommand, parse_command};
+use crate::handlers::message::escape_html;
+use crate::telegram::{CallbackQuery, InlineKeyboardButton, InlineKeyboardMarkup, TelegramClient};
+
+const DELIVERY_TICK: Duration = Duration::from_secs(15);
+const PREVIEW_MAX_CHARS: usize = 80;
+
+/// Spawns the task that delivers due reminders until the bot exits.
+pub(crate) fn spawn_delivery_loop(context: &Arc<BotContext>, queues: &ChatQueueMap) {
+    tokio::spawn(run_delivery_loop(Arc::clone(context), Arc::clone(queues)));
+}
+
+async fn run_delivery_loop(context: Arc<BotContext>, queues: ChatQueueMap) {
+    let mut tick = tokio::time::interval(DELIVERY_TICK);
+    tick.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+    loop {
+        tick.tick().await;
+        let due = match context.reminders().take_due(Utc::now()) {
+            Ok(due) => due,
+            Err(err) => {
+                tracing::warn!(%err, "Failed to take due reminders");
+                continue;
+            }
+        };
+        for reminder in due {
+            deliver(&context, &queues, reminder).await;
+        }
+    }
+}
+
+async fn deliver(context: &Arc<BotContext>, queues: &ChatQueueMap, reminder: Reminder) {
+    let target = reminder.target;
+    let text = escape_html(&reminder.text);
+    let notice = match reminder.kind {
+        ReminderKind::Message => format!("⏰ {text}"),
+        ReminderKind::Turn => format!("⏰ <i>Scheduled follow-up</i>\n{text}"),
+    };
+    let sent = context
+        .client()
+        .send_message_returning(target.chat_id, &notice, None, target.topic_id)
+        .await;
+    let notice = match sent {
+        Ok(notice) => notice,
+        Err(err) => {
+            tracing::warn!(chat_id = target.chat_id, id = reminder.id, %err, "Failed to deliver reminder");
+            return;
+        }
+    };
+    tracing::info!(
+        chat_id = target.chat_id,
+        id = reminder.id,
+        kind = reminder.kind.label(),
+        "Delivered reminder"
+    );
+    if reminder.kind == ReminderKind::Message {
+        return;
+    }
+
+    // The turn replies to the notice, like a follow-up button tap.
+    let chat_kind = if target.chat_id > 0 {
+        "private"
+    } else {
+        "supergroup"
+    };
+    let synthetic: Result<crate::telegram::Message, _> = serde_json::from_value(json!({
+        "message_id": notice.id,
+        "chat": {
+            "id": target.chat_id,
+            "type": chat_kind,
+            "is_forum": target.is_forum,
+        },
+        "from": { "id": target.user_id, "is_bot": false },
+        "text": reminder.text,
+        "message_thread_id": target.topic_id,
+    }));
+    match synthetic {
+        Ok(synthetic) => dispatch_message(queues, context, synthetic).await,
+        Err(err) => {
+            tracing::error!(chat_id = target.chat_id, %err, "Failed to synthesize reminder turn");
+        }
+    }
+}
+
+/// Handles `/reminders`: lists the chat's pending reminders with cancel
+/// buttons.
+pub(crate) async fn handle_reminders_command(
+    context: &BotContext,
+    incoming: &crate::types::IncomingMessage,
+    reply_to_message_id: Option<i64>,
+) -> Result<bool> {
+    if !incoming.images.is_empty() || !incoming.audios.is_empty() {
+        return Ok(false);
+    }
+    if !incoming
+        .text
+        .as_deref()
+        .is_some_and(|text| matches!(parse_command(text), Some(BotCommand::Reminders)))
+    {
+        return Ok(false);
+    }
+
+    let reminders = context.reminders().for_chat(incoming.chat_id);
+    let text = format_reminder_list(&reminders, Utc::now());
+    match reminder_keyboard(&reminders) {
+        Some(markup) => {
+            context
+                .client()
+                .send_message_with_markup(
+                    incoming.chat_id,
+                    &text,
+                    reply_to_message_id,
+                    incoming.message_thread_id,
+                    &markup,
+                )
+                .await?;
+        }
+        None => {
+            context
+                .client()
+                .send_message(
+                    incoming.chat_id,
+                    &text,
+                    reply_to_message_id,
+                    incoming.message_thread_id,
+                )
+                .await?;
+        }
+    }
+    Ok(true)
+}
+
+/// Handles a `rem:x:{id}` callback: cancels the reminder and refreshes the
+/// list message.
+pub(crate) async fn handle_callback(
+    context: &BotContext,
+    client: &TelegramClient,
+    callback: &CallbackQuery,
+    data: &str,
+) {
+    let Some(message) = callback.message.as_ref() else {
+        let _ = client
+            .answer_callback_query(&callback.id, Some("No message context"))
+            .await;
+        return;
+    };
+    let chat_id = message.chat.id;
+    let Some(id) = data
+        .strip_prefix("x:")
+        .and_then(|id| id.parse::<u64>().ok())
+    else {
+        let _ = client.answer_callback_query(&callback.id, None).await;
+        return;
+    };
+
+    let answer = match context.reminders().cancel(chat_id, id) {
+        Ok(Some(_)) => format!("Cancelled reminder #{id}"),
+        Ok(None) => "Already sent or cancelled".to_string(),
+        Err(err) => {
+            tracing::warn!(chat_id, id, %err, "Failed to cancel reminder");
+            "Couldn't cancel the reminder".to_string()
+        }
+    };
+    let _ = client
+        .answer_callback_query(&callback.id, Some(&answer))
+        .await;
+
+    let reminders = context.reminders().for_chat(chat_id);
+    let text = format_reminder_list(&reminders, Utc::now());
+    let _ = client
+        .edit_message_text(
+            chat_id,
+            message.id,
+            &text,
+            reminder_keyboard(&reminders).as_ref(),
+        )
+        .await;
+}
+
+fn format_reminder_list(reminders: &[Reminder], now: DateTime<Utc>) -> String {
+    if reminders.is_empty() {
+        return "⏰ No pending reminders. Ask me to remind you of something to schedule one."
+            .to_string();
+    }
+    let mut lines = vec![format!("⏰ <b>Reminders</b> ({})", reminders.len())];
+    for reminder in reminders {
+        let topic = reminder
+            .target
+            .topic_id
+            .map(|topic_id| format!(" · topic {topic_id}"))
+            .unwrap_or_default();
+        lines.push(format!(
+            "\n<b>#{}</b> · {} · {}{topic}\n{}",
+            reminder.id,
+            format_due(reminder.due_at, now),
+            reminder.kind.label(),
+            escape_html(&truncate_chars(&reminder.text, PREVIEW_MAX_CHARS)),
+        ));
+    }
+    lines.join("\n")
+}
+
+fn reminder_keyboard(reminders: &[Reminder]) -> Option<InlineKeyboardMarkup> {
+    if reminders.is_empty() {
+        return None;
+    }
+    let inline_keyboard = reminders
+        .chunks(3)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .map(|reminder| InlineKeyboardButton {
+                    text: format!("✕ #{}", reminder.id),
+                    callback_data: Some(format!("rem:x:{}", reminder.id)),
+                    url: None,
+                })
+                .collect()
+        })
+        .collect();
+    Some(InlineKeyboardMarkup { inline_keyboard })
+}
+
+/// Compact relative due time (`in 1h 20m`, `in 3d 4h`, `due now`).
+fn format_due(due_at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let minutes = (due_at - now).num_minutes();
+    if minutes < 1 {
+        return "due now".to_string();
+    }
+    let (days, hours, minutes) = (minutes / 1440, minutes / 60 % 24, minutes % 60);
+    let parts = match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) if minutes == 0 => format!("{hours}h"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        (_, 0) => format!("{days}d"),
+        _ => format!("{days}d {hours}h"),
+    };
+    format!("in {parts}")
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut truncated: String = text.chars().take(max_chars - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    #[test]
+    fn formats_relative_due_times() {
+        let now = Utc::now();
+        assert_eq!(format_due(now, now), "due now");
+        assert_eq!(format_due(now + TimeDelta::minutes(45), now), "in 45m");
+        assert_eq!(format_due(now + TimeDelta::minutes(120), now), "in 2h");
+        assert_eq!(format_due(now + TimeDelta::minutes(80), now), "in 1h 20m");
+        assert_eq!(format_due(now + TimeDelta::hours(76), now), "in 3d 4h");
+    }
+}
@@ -0,0 +1,270 @@
+//! Durable reminder store (`$ZDX_HOME/telegram/reminders.json`).
+//!
+//! Every change is written to disk (temp file + rename) before it becomes
+//! visible in memory, so a failed write leaves both in the previous state.
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use zdx_engine::config::paths;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum ReminderKind {
+    /// Send the text as a bot message.
+    Message,
+    /// Run a new agent turn with the text as the user's prompt.
+    Turn,
+}
+
+impl ReminderKind {
+    pub(crate) fn label(self) -> &'static str {
+        match self {
+            Self::Message => "message",
+            Self::Turn => "turn",
+        }
+    }
+}
+
+/// Chat/topic (and requesting user) a reminder is delivered to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct ReminderTarget {
+    pub chat_id: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub topic_id: Option<i64>,
+    pub user_id: i64,
+    #[serde(default)]
+    pub is_forum: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub(crate) struct Reminder {
+    pub id: u64,
+    #[serde(flatten)]
+    pub target: ReminderTarget,
+    pub kind: ReminderKind,
+    pub text: String,
+    pub due_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct StoreFile {
+    /// Never reused, so a stale cancel button can't hit a newer reminder.
+    next_id: u64,
+    reminders: Vec<Reminder>,
+}
+
+pub(crate) struct ReminderStore {
+    path: PathBuf,
+    state: Mutex<StoreFile>,
+}
+
+impl ReminderStore {
+    pub(crate) fn default_path() -> PathBuf {
+        paths::zdx_home().join("telegram").join("reminders.json")
+    }
+
+    /// Loads the store at `path`; a missing file is an empty store.
+    ///
+    /// # Errors
+    /// Returns an error if the file exists but can't be read or parsed.
+    pub(crate) fn load(path: PathBuf) -> Result<Self> {
+        let state = match fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .with_context(|| format!("parse reminders {}", path.display()))?,
+            Err(err) if err.kind() == ErrorKind::NotFound => StoreFile::default(),
+            Err(err) => {
+                return Err(err).with_context(|| format!("read reminders {}", path.display()));
+            }
+        };
+        Ok(Self {
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    pub(crate) fn schedule(
+        &self,
+        target: ReminderTarget,
+        kind: ReminderKind,
+        text: String,
+        due_at: DateTime<Utc>,
+    ) -> Result<Reminder> {
+        self.update(|state| {
+            state.next_id += 1;
+            let reminder = Reminder {
+                id: state.next_id,
+                target,
+                kind,
+                text,
+                due_at,
+                created_at: Utc::now(),
+            };
+            state.reminders.push(reminder.clone());
+            reminder
+        })
+    }
+
+    /// Pending reminders for `chat_id`, soonest first.
+    pub(crate) fn for_chat(&self, chat_id: i64) -> Vec<Reminder> {
+        let mut reminders: Vec<Reminder> = self
+            .lock()
+            .reminders
+            .iter()
+            .filter(|reminder| reminder.target.chat_id == chat_id)
+            .cloned()
+            .collect();
+        reminders.sort_by_key(|reminder| reminder.due_at);
+        reminders
+    }
+
+    /// Removes reminder `id` if it belongs to `chat_id`.
+    pub(crate) fn cancel(&self, chat_id: i64, id: u64) -> Result<Option<Reminder>> {
+        self.update(|state| {
+            let index = state
+                .reminders
+                .iter()
+                .position(|reminder| reminder.id == id && reminder.target.chat_id == chat_id)?;
+            Some(state.reminders.remove(index))
+        })
+    }
+
+    /// Removes and returns reminders due at `now`, oldest first.
+    pub(crate) fn take_due(&self, now: DateTime<Utc>) -> Result<Vec<Reminder>> {
+        if !self
+            .lock()
+            .reminders
+            .iter()
+            .any(|reminder| reminder.due_at <= now)
+        {
+            return Ok(Vec::new());
+        }
+        self.update(|state| {
+            let (mut due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut state.reminders)
+                .into_iter()
+                .partition(|reminder| reminder.due_at <= now);
+            state.reminders = pending;
+            due.sort_by_key(|reminder| reminder.due_at);
+            due
+        })
+    }
+
+    fn lock(&self) -> MutexGuard<'_, StoreFile> {
+        self.state.lock().expect("reminder store lock poisoned")
+    }
+
+    fn update<T>(&self, f: impl FnOnce(&mut StoreFile) -> T) -> Result<T> {
+        let mut state = self.lock();
+        let mut next = state.clone();
+        let out = f(&mut next);
+        write_store(&self.path, &next)?;
+        *state = next;
+        Ok(out)
+    }
+}
+
+fn write_store(path: &Path, state: &StoreFile) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+    }
+    let tmp = path.with_extension("json.tmp");
+    let raw = serde_json::to_vec_pretty(state).context("serialize reminders")?;
+    fs::write(&tmp, raw).with_context(|| format!("write {}", tmp.display()))?;
+    fs::rename(&tmp, path).with_context(|| format!("replace {}", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    use chrono::TimeDelta;
+
+    use super::*;
+
+    fn temp_store_path() -> PathBuf {
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        std::env::temp_dir()
+            .join(format!("zdx-bot-reminders-{nanos}"))
+            .join("reminders.json")
+    }
+
+    fn target(chat_id: i64) -> ReminderTarget {
+        ReminderTarget {
+            chat_id,
+            topic_id: Some(7),
+            user_id: 1,
+            is_forum: true,
+        }
+    }
+
+    #[test]
+    fn reminders_survive_reload_and_are_taken_once_due() {
+        let path = temp_store_path();
+        let now = Utc::now();
+        let store = ReminderStore::load(path.clone()).unwrap();
+        let soon = store
+            .schedule(
+                target(1),
+                ReminderKind::Message,
+                "soon".into(),
+                now + TimeDelta::minutes(1),
+            )
+            .unwrap();
+        store
+            .schedule(
+                target(1),
+                ReminderKind::Turn,
+                "later".into(),
+                now + TimeDelta::hours(2),
+            )
+            .unwrap();
+
+        let reloaded = ReminderStore::load(path.clone()).unwrap();
+        assert_eq!(reloaded.for_chat(1).len(), 2);
+        assert!(reloaded.for_chat(2).is_empty());
+
+        let due = reloaded.take_due(now + TimeDelta::minutes(5)).unwrap();
+        assert_eq!(due, [soon]);
+        assert!(
+            reloaded
+                .take_due(now + TimeDelta::minutes(5))
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            ReminderStore::load(path.clone()).unwrap().for_chat(1).len(),
+            1
+        );
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+
+    #[test]
+    fn cancel_is_scoped_to_chat_and_ids_are_not_reused() {
+        let path = temp_store_path();
+        let store = ReminderStore::load(path.clone()).unwrap();
+        let due_at = Utc::now() + TimeDelta::hours(1);
+        let first = store
+            .schedule(target(1), ReminderKind::Message, "a".into(), due_at)
+            .unwrap();
+
+        assert_eq!(store.cancel(2, first.id).unwrap(), None);
+        assert_eq!(store.cancel(1, first.id).unwrap(), Some(first.clone()));
+        let second = store
+            .schedule(target(1), ReminderKind::Message, "b".into(), due_at)
+            .unwrap();
+        assert!(second.id > first.id);
+
+        let _ = fs::remove_dir_all(path.parent().unwrap());
+    }
+}
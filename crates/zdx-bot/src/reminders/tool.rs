@@ -0,0 +1,221 @@
+//! `Schedule_Message` tool, registered per bot turn and bound to that turn's
+//! chat/topic.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use zdx_engine::core::agent::{ToolConfig, ToolSelection};
+use zdx_engine::core::events::ToolOutput;
+use zdx_engine::tools::{Tool, ToolContext, ToolDefinition, ToolFuture};
+
+use super::store::{ReminderKind, ReminderStore, ReminderTarget};
+
+const TOOL_NAME: &str = "Schedule_Message";
+const MAX_DELAY_DAYS: i64 = 365;
+
+/// Returns `base` with `Schedule_Message` registered and enabled for a turn
+/// in `target`.
+pub(crate) fn with_schedule_tool(
+    base: &ToolConfig,
+    store: &Arc<ReminderStore>,
+    target: ReminderTarget,
+) -> ToolConfig {
+    let mut config = base.clone();
+    config.registry.register_tool(ScheduleMessage {
+        store: Arc::clone(store),
+        target,
+    });
+    if let ToolSelection::Auto { include, .. } | ToolSelection::ToolSet { include, .. } =
+        &mut config.selection
+    {
+        include.push(TOOL_NAME.to_string());
+    }
+    config
+}
+
+struct ScheduleMessage {
+    store: Arc<ReminderStore>,
+    target: ReminderTarget,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScheduleInput {
+    text: String,
+    #[serde(default)]
+    delay: Option<String>,
+    #[serde(default)]
+    at: Option<String>,
+    #[serde(default)]
+    mode: Option<ReminderKind>,
+}
+
+impl Tool for ScheduleMessage {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: TOOL_NAME.to_string(),
+            description: "Schedule a future Telegram message or follow-up turn in the current chat/topic (e.g. \"remind me in 2h to check the deploy\"). Reminders persist across bot restarts; the user lists or cancels them with /reminders. Use mode `message` to send `text` as-is at the due time, or `turn` to run a new agent turn with `text` as the prompt (for checks that need tools). Give exactly one of `delay` or `at`."
+                .to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "text": {
+                        "type": "string",
+                        "description": "Reminder text to send, or the prompt for the follow-up turn"
+                    },
+                    "delay": {
+                        "type": "string",
+                        "description": "Relative delay such as `30m`, `2h`, `1d`, or `1h30m` (units: s, m, h, d, w)"
+                    },
+                    "at": {
+                        "type": "string",
+                        "description": "Absolute RFC 3339 time with offset, such as `2026-05-01T09:00:00+02:00`"
+                    },
+                    "mode": {
+                        "type": "string",
+                        "enum": ["message", "turn"],
+                        "description": "`message` (default) sends the text; `turn` runs a new agent turn"
+                    }
+                },
+                "required": ["text"],
+                "additionalProperties": false
+            }),
+        }
+    }
+
+    fn execute(&self, input: &Value, _ctx: &ToolContext) -> ToolFuture {
+        let output = self.schedule(input, Utc::now());
+        Box::pin(std::future::ready(output))
+    }
+}
+
+impl ScheduleMessage {
+    fn schedule(&self, input: &Value, now: DateTime<Utc>) -> ToolOutput {
+        let input: ScheduleInput = match serde_json::from_value(input.clone()) {
+            Ok(input) => input,
+            Err(err) => {
+                return ToolOutput::failure(
+                    "invalid_input",
+                    "Invalid input for Schedule_Message tool",
+                    Some(format!("Parse error: {err}")),
+                );
+            }
+        };
+        let text = input.text.trim();
+        if text.is_empty() {
+            return ToolOutput::failure("invalid_input", "text cannot be empty", None);
+        }
+        let due_at = match resolve_due_at(input.delay.as_deref(), input.at.as_deref(), now) {
+            Ok(due_at) => due_at,
+            Err(message) => return ToolOutput::failure("invalid_input", message, None),
+        };
+        let kind = input.mode.unwrap_or(ReminderKind::Message);
+
+        match self
+            .store
+            .schedule(self.target, kind, text.to_string(), due_at)
+        {
+            Ok(reminder) => ToolOutput::success(json!({
+                "id": reminder.id,
+                "due_at": reminder.due_at.to_rfc3339(),
+                "mode": kind.label(),
+                "note": "Delivered in this chat/topic. The user can list or cancel it with /reminders.",
+            })),
+            Err(err) => ToolOutput::failure(
+                "schedule_failed",
+                "Failed to save the reminder",
+                Some(format!("{err:#}")),
+            ),
+        }
+    }
+}
+
+fn resolve_due_at(
+    delay: Option<&str>,
+    at: Option<&str>,
+    now: DateTime<Utc>,
+) -> Result<DateTime<Utc>, String> {
+    let due_at = match (delay, at) {
+        (Some(delay), None) => {
+            let delay = parse_delay(delay)
+                .ok_or_else(|| format!("invalid delay `{delay}` (expected e.g. 30m, 2h, 1h30m)"))?;
+            now + delay
+        }
+        (None, Some(at)) => DateTime::parse_from_rfc3339(at.trim())
+            .map_err(|err| format!("invalid `at` timestamp `{at}`: {err}"))?
+            .with_timezone(&Utc),
+        _ => return Err("give exactly one of `delay` or `at`".to_string()),
+    };
+    if due_at <= now {
+        return Err("the reminder time must be in the future".to_string());
+    }
+    if due_at - now > TimeDelta::days(MAX_DELAY_DAYS) {
+        return Err(format!(
+            "reminders can be at most {MAX_DELAY_DAYS} days ahead"
+        ));
+    }
+    Ok(due_at)
+}
+
+/// Parses `1h30m`-style delays (units: s, m, h, d, w).
+fn parse_delay(raw: &str) -> Option<TimeDelta> {
+    let mut total = TimeDelta::zero();
+    let mut digits = String::new();
+    let mut saw_unit = false;
+    for ch in raw.trim().chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        if ch.is_whitespace() {
+            continue;
+        }
+        let value: i64 = digits.parse().ok()?;
+        digits.clear();
+        let part = match ch.to_ascii_lowercase() {
+            's' => TimeDelta::try_seconds(value),
+            'm' => TimeDelta::try_minutes(value),
+            'h' => TimeDelta::try_hours(value),
+            'd' => TimeDelta::try_days(value),
+            'w' => TimeDelta::try_weeks(value),
+            _ => None,
+        }?;
+        total = total.checked_add(&part)?;
+        saw_unit = true;
+    }
+    (saw_unit && digits.is_empty()).then_some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_compound_delays() {
+        assert_eq!(parse_delay("2h"), Some(TimeDelta::hours(2)));
+        assert_eq!(parse_delay("1h 30m"), Some(TimeDelta::minutes(90)));
+        assert_eq!(parse_delay("1w2d"), Some(TimeDelta::days(9)));
+        assert_eq!(parse_delay("90"), None);
+        assert_eq!(parse_delay("2x"), None);
+        assert_eq!(parse_delay(""), None);
+    }
+
+    #[test]
+    fn due_time_needs_exactly_one_future_source() {
+        let now = DateTime::parse_from_rfc3339("2026-05-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            resolve_due_at(Some("30m"), None, now).unwrap(),
+            now + TimeDelta::minutes(30)
+        );
+        // 12:00+02:00 is 10:00Z, so not in the future.
+        assert!(resolve_due_at(None, Some("2026-05-01T12:00:00+02:00"), now).is_err());
+        assert!(resolve_due_at(None, Some("2026-05-02T09:00:00+02:00"), now).is_ok());
+        assert!(resolve_due_at(Some("1h"), Some("2026-05-02T09:00:00Z"), now).is_err());
+        assert!(resolve_due_at(None, None, now).is_err());
+        assert!(resolve_due_at(Some("400d"), None, now).is_err());
+    }
+}
@@ -16,8 +16,9 @@ mod types;
 
 #[allow(unused_imports)]
 pub use types::{
-    Audio, CallbackQuery, Document, InlineKeyboardButton, InlineKeyboardMarkup, Message, PhotoSize,
-    TelegramFile, Update, Voice,
+    Audio, CallbackQuery, Document, InlineKeyboardButton, InlineKeyboardMarkup, InlineQuery,
+    InlineQueryResultArticle, InputTextMessageContent, Message, PhotoSize, Sticker, TelegramFile,
+    Update, VideoNote, Voice,
 };
 
 pub struct TelegramSettings {
@@ -227,7 +228,7 @@ impl TelegramClient {
         let request = GetUpdatesRequest {
             offset,
             timeout: timeout.as_secs(),
-            allowed_updates: Some(vec!["message", "callback_query"]),
+            allowed_updates: Some(vec!["message", "callback_query", "inline_query"]),
         };
         self.post("getUpdates", &request).await
     }
@@ -485,6 +486,21 @@ impl TelegramClient {
         .map(|_| ())
     }
 
+    /// Send a message and return it (for callers that need its `message_id`).
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub async fn send_message_returning(
+        &self,
+        chat_id: i64,
+        text: &str,
+        reply_to_message_id: Option<i64>,
+        message_thread_id: Option<i64>,
+    ) -> Result<Message> {
+        self.send_message_inner(chat_id, text, reply_to_message_id, message_thread_id, None)
+            .await
+    }
+
     /// Send a message with an inline keyboard. Returns the sent [`Message`] so
     /// the caller can later edit or delete it by `message_id`.
     ///
@@ -714,6 +730,26 @@ impl TelegramClient {
         Ok(())
     }
 
+    /// Answer an inline query. Results are personal and not cached, since
+    /// they are generated per user and per query.
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub async fn answer_inline_query(
+        &self,
+        inline_query_id: &str,
+        results: &[InlineQueryResultArticle],
+    ) -> Result<()> {
+        let request = AnswerInlineQueryRequest {
+            inline_query_id,
+            results,
+            cache_time: 0,
+            is_personal: true,
+        };
+        let _: bool = self.post("answerInlineQuery", &request).await?;
+        Ok(())
+    }
+
     /// Create a forum topic in a supergroup.
     /// Returns the `message_thread_id` of the created topic.
     ///
@@ -836,8 +872,12 @@ impl TelegramClient {
         Ok(())
     }
 
+    /// Send in-memory bytes as a Telegram document (caption is plain text).
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
     #[allow(clippy::too_many_arguments)]
-    async fn send_document(
+    pub async fn send_document(
         &self,
         chat_id: i64,
         document_data: &[u8],
@@ -1173,6 +1213,14 @@ struct AnswerCallbackQueryRequest<'a> {
     text: Option<&'a str>,
 }
 
+#[derive(Debug, Serialize)]
+struct AnswerInlineQueryRequest<'a> {
+    inline_query_id: &'a str,
+    results: &'a [InlineQueryResultArticle],
+    cache_time: u32,
+    is_personal: bool,
+}
+
 pub struct TypingIndicator {
     cancel: tokio_util::sync::CancellationToken,
 }
@@ -6,6 +6,34 @@ pub struct Update {
     pub id: i64,
     pub message: Option<Message>,
     pub callback_query: Option<CallbackQuery>,
+    pub inline_query: Option<InlineQuery>,
+}
+
+/// Incoming inline query (`@bot query` typed in any chat).
+#[derive(Debug, Deserialize)]
+pub struct InlineQuery {
+    pub id: String,
+    pub from: User,
+    /// Text after the bot username (may be empty).
+    #[serde(default)]
+    pub query: String,
+}
+
+/// Inline query result that posts a text message when picked.
+#[derive(Debug, Clone, Serialize)]
+pub struct InlineQueryResultArticle {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub id: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub input_message_content: InputTextMessageContent,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct InputTextMessageContent {
+    pub message_text: String,
 }
 
 /// Incoming callback query from an inline keyboard button.
@@ -56,6 +84,10 @@ pub struct Message {
     pub audio: Option<Audio>,
     #[serde(default)]
     pub document: Option<Document>,
+    #[serde(default)]
+    pub sticker: Option<Sticker>,
+    #[serde(default)]
+    pub video_note: Option<VideoNote>,
     /// Unique identifier of a message thread or forum topic.
     #[serde(default, rename = "message_thread_id")]
     pub thread_id: Option<i64>,
@@ -154,6 +186,36 @@ pub struct Document {
     pub file_size: Option<u64>,
 }
 
+/// Sticker: static `.webp`, animated `.tgs` (gzipped Lottie), or video `.webm`.
+#[derive(Debug, Deserialize)]
+pub struct Sticker {
+    pub file_id: String,
+    #[serde(default)]
+    pub is_animated: bool,
+    #[serde(default)]
+    pub is_video: bool,
+    #[serde(default)]
+    pub emoji: Option<String>,
+    #[serde(default)]
+    pub file_size: Option<u64>,
+    /// Preview rendered by Telegram (`thumb` in older Bot API versions).
+    #[serde(default, alias = "thumb")]
+    pub thumbnail: Option<PhotoSize>,
+}
+
+/// Round video message (`.mp4`).
+#[derive(Debug, Deserialize)]
+pub struct VideoNote {
+    pub file_id: String,
+    /// Duration in seconds.
+    #[serde(default)]
+    pub duration: u64,
+    #[serde(default)]
+    pub file_size: Option<u64>,
+    #[serde(default, alias = "thumb")]
+    pub thumbnail: Option<PhotoSize>,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct TelegramFile {
     #[serde(default)]
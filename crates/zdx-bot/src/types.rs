@@ -18,6 +18,9 @@ pub struct IncomingImage {
     pub local_path: PathBuf,
     pub mime_type: String,
     pub data: String,
+    /// What the image is (e.g. `Sticker 😂`, `Video note frame 1/4 at 0.8s`);
+    /// `None` for plain image attachments.
+    pub label: Option<String>,
 }
 
 pub struct IncomingAudio {
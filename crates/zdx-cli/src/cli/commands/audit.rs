@@ -0,0 +1,28 @@
+//! `zdx audit export` — tool-call audit log across threads.
+
+use std::io::Write;
+
+use anyhow::{Context, Result};
+use chrono::NaiveDate;
+use zdx_engine::core::audit_log::{self, AuditFormat};
+
+/// Writes every recorded tool call on/after `since` to stdout in `format`.
+///
+/// # Errors
+/// Returns an error if `since` is not a `YYYY-MM-DD` date, threads cannot be
+/// read, or writing fails.
+pub fn export(since: Option<&str>, format: &str) -> Result<()> {
+    let since = since
+        .map(|raw| {
+            NaiveDate::parse_from_str(raw.trim(), "%Y-%m-%d")
+                .with_context(|| format!("invalid --since value '{raw}' (expected YYYY-MM-DD)"))
+        })
+        .transpose()?;
+    let format: AuditFormat = format.parse()?;
+
+    let records = audit_log::collect_records(since).context("collect audit records")?;
+    let mut out = std::io::stdout().lock();
+    audit_log::write_records(&records, format, &mut out)?;
+    out.flush()?;
+    Ok(())
+}
@@ -207,7 +207,14 @@ pub async fn run(
     let automation = automations::load_by_name(root, name)
         .with_context(|| format!("load automation '{name}' from {}", root.display()))?;
 
-    run_definition(root, thread_opts, config, &automation, RunTrigger::Manual).await
+    Box::pin(run_definition(
+        root,
+        thread_opts,
+        config,
+        &automation,
+        RunTrigger::Manual,
+    ))
+    .await
 }
 
 /// Runs one parsed automation definition.
@@ -243,8 +250,12 @@ pub async fn run_definition(
             thinking_override: prepared.thinking_override.as_deref(),
             event_filter_override: None,
             tools_override: prepared.tools_override.as_deref(),
+            deny_tools_override: None,
             no_tools: false,
             no_system_prompt: false,
+            dry_run: false,
+            no_cache: false,
+            schema_path: None,
             activity_kind: Some("automation"),
             activity_parent_thread_id: None,
             activity_subagent_name: None,
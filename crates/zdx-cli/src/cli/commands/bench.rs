@@ -0,0 +1,97 @@
+//! `zdx bench` — latency/throughput/cost comparison across models.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Result, bail};
+use zdx_engine::config;
+use zdx_engine::core::bench::{self, BenchResult};
+
+use super::stats::format_cost;
+
+/// Runs `prompt` against each model in turn and prints a comparison table.
+///
+/// Models run sequentially so their timings don't compete for bandwidth.
+pub async fn run(
+    root: &Path,
+    config: &config::Config,
+    models: &[String],
+    prompt: &str,
+) -> Result<()> {
+    let models: Vec<&str> = models
+        .iter()
+        .map(|model| model.trim())
+        .filter(|model| !model.is_empty())
+        .collect();
+    if models.is_empty() {
+        bail!("--models needs at least one model id");
+    }
+    if prompt.trim().is_empty() {
+        bail!("--prompt must not be empty");
+    }
+
+    let mut results = Vec::with_capacity(models.len());
+    for model in models {
+        eprintln!("Benchmarking {model}...");
+        results.push(bench::bench_model(config, model, prompt, root).await);
+    }
+
+    print_table(&results);
+    Ok(())
+}
+
+fn print_table(results: &[BenchResult]) {
+    println!();
+    println!(
+        "  {:<34} {:<16} {:>9} {:>9} {:>9} {:>10}",
+        "MODEL", "PROVIDER", "TTFT", "TOK/S", "TOTAL", "COST"
+    );
+    for result in results {
+        let provider = result.provider.as_deref().unwrap_or("-");
+        println!(
+            "  {:<34} {:<16} {:>9} {:>9} {:>9} {:>10}",
+            truncate(&result.model, 34),
+            truncate(provider, 16),
+            result.ttft.map_or_else(|| "-".to_string(), format_duration),
+            result
+                .tokens_per_sec()
+                .map_or_else(|| "-".to_string(), |tps| format!("{tps:.1}")),
+            format_duration(result.total),
+            result
+                .cost_usd
+                .map_or_else(|| "unknown".to_string(), format_cost_precise),
+        );
+        if let Some(error) = &result.error {
+            println!("    error: {error}");
+        }
+    }
+}
+
+fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    if millis >= 1_000 {
+        format!("{:.2}s", duration.as_secs_f64())
+    } else {
+        format!("{millis}ms")
+    }
+}
+
+/// Single requests usually cost fractions of a cent; show more precision
+/// than the `$x.xx` used for aggregated stats.
+fn format_cost_precise(cost: f64) -> String {
+    if cost >= 0.01 {
+        format_cost(cost)
+    } else {
+        format!("${cost:.5}")
+    }
+}
+
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut out: String = text.chars().take(max.saturating_sub(1)).collect();
+        out.push('…');
+        out
+    }
+}
@@ -75,6 +75,34 @@ pub fn add_profile(config: &Config, name: &str, chat_id: i64, cwd: &Path) -> Res
     Ok(())
 }
 
+pub fn media_gc(config: &Config, dry_run: bool) -> Result<()> {
+    let report = zdx_bot::media::collect_garbage(config, dry_run).context("media gc")?;
+    let verb = if dry_run { "Would delete" } else { "Deleted" };
+    for path in &report.deleted {
+        println!("{verb} {}", path.display());
+    }
+    println!(
+        "{verb} {} of {} files ({:.1} MB); {:.1} MB remaining in {}",
+        report.deleted.len(),
+        report.scanned,
+        megabytes(report.freed_bytes),
+        megabytes(report.remaining_bytes),
+        zdx_bot::media::media_root().display()
+    );
+    if report.kept_referenced > 0 {
+        println!(
+            "Kept {} files referenced by recent threads.",
+            report.kept_referenced
+        );
+    }
+    Ok(())
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn megabytes(bytes: u64) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0)
+}
+
 fn prompt_required(label: &str) -> Result<String> {
     loop {
         let value = prompt(label, None)?;
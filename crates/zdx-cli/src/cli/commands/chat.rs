@@ -5,18 +5,34 @@ use std::path::PathBuf;
 
 use anyhow::{Context, Result};
 use zdx_engine::config;
+use zdx_engine::core::agent::ToolSelection;
 use zdx_engine::core::thread_persistence::ThreadPersistenceOptions;
+use zdx_engine::tools::ToolRegistry;
 
 use super::exec;
 use crate::modes;
 
+/// Chat-launch flags (`--model`, `--thinking`, `--tools`, `--deny-tools`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ChatOverrides<'a> {
+    pub model: Option<&'a str>,
+    pub thinking: Option<&'a str>,
+    pub tools: Option<&'a str>,
+    pub deny_tools: Option<&'a str>,
+}
+
 pub async fn run(
     root: &str,
     thread_opts: &ThreadPersistenceOptions,
     config: &config::Config,
-    model_override: Option<&str>,
-    thinking_override: Option<&str>,
+    overrides: ChatOverrides<'_>,
 ) -> Result<()> {
+    let ChatOverrides {
+        model: model_override,
+        thinking: thinking_override,
+        tools,
+        deny_tools,
+    } = overrides;
     // If stdin is piped, run exec mode instead
     if !std::io::stdin().is_terminal() {
         let mut prompt = String::new();
@@ -35,9 +51,13 @@ pub async fn run(
             tool_timeout_override: None,
             thinking_override,
             event_filter_override: None,
-            tools_override: None,
+            tools_override: tools,
+            deny_tools_override: deny_tools,
             no_tools: false,
             no_system_prompt: false,
+            dry_run: false,
+            no_cache: false,
+            schema_path: None,
             activity_kind: Some("exec"),
             activity_parent_thread_id: None,
             activity_subagent_name: None,
@@ -53,10 +73,13 @@ pub async fn run(
         config.thinking_level = exec::parse_thinking_level(thinking)?;
     }
 
+    let available_tools = ToolRegistry::from_config(&config).tool_names();
+    let tool_selection = ToolSelection::from_cli_flags(tools, deny_tools, &available_tools)?;
+
     let root_path = PathBuf::from(root);
     let thread = thread_opts.resolve(&root_path).context("resolve thread")?;
 
-    modes::run_interactive_chat(&config, thread, root_path)
+    modes::run_interactive_chat(&config, thread, root_path, tool_selection)
         .await
         .context("interactive chat failed")?;
 
@@ -1,7 +1,10 @@
 //! Config command handlers.
 
-use anyhow::{Context, Result};
-use zdx_engine::config;
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use zdx_engine::config_diff::{self, DiffKind};
+use zdx_engine::{config, config_validation};
 
 pub fn path() {
     println!("{}", config::paths::config_path().display());
@@ -20,3 +23,83 @@ pub fn generate() -> Result<()> {
     print!("{toml}");
     Ok(())
 }
+
+pub fn validate(path: Option<&Path>) -> Result<()> {
+    let config_path = path.map_or_else(config::paths::config_path, Path::to_path_buf);
+    let issues = config_validation::validate_file(&config_path)?;
+    for issue in &issues {
+        let severity = if issue.is_failure() {
+            "error"
+        } else {
+            "warning"
+        };
+        println!(
+            "{}:{}:{}: {severity}: {}",
+            config_path.display(),
+            issue.line,
+            issue.column,
+            issue.message
+        );
+    }
+
+    let failures = issues.iter().filter(|issue| issue.is_failure()).count();
+    if failures > 0 {
+        bail!(
+            "{} has {failures} problem{}",
+            config_path.display(),
+            if failures == 1 { "" } else { "s" }
+        );
+    }
+    println!("{}: ok", config_path.display());
+    Ok(())
+}
+
+pub fn diff(path: Option<&Path>) -> Result<()> {
+    let config_path = path.map_or_else(config::paths::config_path, Path::to_path_buf);
+    let entries = config_diff::diff_file(&config_path)?;
+    if entries.is_empty() {
+        println!("{}: matches the bundled defaults", config_path.display());
+        return Ok(());
+    }
+
+    println!("{} vs bundled defaults", config_path.display());
+    for entry in &entries {
+        let name = if entry.is_table {
+            format!("[{}]", entry.path)
+        } else {
+            entry.path.clone()
+        };
+        let line = match (entry.kind, &entry.user, &entry.default) {
+            (DiffKind::Changed, Some(user), Some(default)) => {
+                format!("~ {name} = {user}   (default: {default})")
+            }
+            (DiffKind::Added, Some(user), _) => format!("+ {name} = {user}"),
+            (DiffKind::Removed, _, Some(default)) => {
+                format!("- {name}   (not set; default: {default})")
+            }
+            (DiffKind::Added, ..) => format!("+ {name}"),
+            _ => format!("- {name}   (not set; defaults apply)"),
+        };
+        println!("{line}");
+        if let Some(comment) = &entry.comment {
+            println!("    # {comment}");
+        }
+    }
+    Ok(())
+}
+
+/// Rewrites renamed keys in the config file the first time a new zdx version
+/// runs, reporting the changes on stderr. Failures only warn.
+pub fn migrate_on_upgrade() {
+    let config_path = config::paths::config_path();
+    let stamp_path = config::paths::zdx_home().join("config_version");
+    match config_validation::migrate_on_upgrade(
+        &config_path,
+        &stamp_path,
+        env!("CARGO_PKG_VERSION"),
+    ) {
+        Ok(Some(report)) => eprintln!("{}", report.summary()),
+        Ok(None) => {}
+        Err(err) => tracing::warn!("Config migration skipped: {err:#}"),
+    }
+}
@@ -0,0 +1,145 @@
+//! `zdx context show` — print the assembled system prompt by source.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::Result;
+use zdx_engine::config::{Config, PromptSource, SystemPromptMode};
+use zdx_engine::core::context::{
+    EffectivePrompt, PromptContextInclusion, build_prompt_with_context_and_layers,
+};
+use zdx_engine::prompts;
+
+/// Runs `zdx context show`, printing the system prompt the interactive chat
+/// (or `zdx exec` with `exec`) would send for `model` (default: the configured
+/// model), one annotated section per source.
+///
+/// # Errors
+/// Returns an error if the configured system prompt file cannot be read.
+pub fn show(config: &Config, root: &Path, exec: bool, model: Option<&str>) -> Result<()> {
+    let layer = if exec {
+        prompts::EXEC_INSTRUCTION_LAYER
+    } else {
+        prompts::CHAT_INSTRUCTION_LAYER
+    };
+    let layers: Vec<&str> = Some(layer.trim())
+        .filter(|layer| !layer.is_empty())
+        .into_iter()
+        .collect();
+    let model = model.unwrap_or(&config.model);
+    let effective = build_prompt_with_context_and_layers(
+        config,
+        root,
+        model,
+        &layers,
+        !exec,
+        PromptContextInclusion::default(),
+    )?;
+    print!("{}", format_report(config, model, &effective));
+    Ok(())
+}
+
+fn format_report(config: &Config, model: &str, effective: &EffectivePrompt) -> String {
+    let layers = &config.prompt_layers;
+    let mode = match config.system_prompt_mode {
+        SystemPromptMode::Append => "append",
+        SystemPromptMode::Replace => "replace",
+    };
+    let disabled: Vec<&str> = PromptSource::ALL
+        .into_iter()
+        .filter(|source| !layers.enabled(*source))
+        .map(PromptSource::name)
+        .collect();
+
+    let mut out = String::new();
+    let _ = writeln!(out, "# model: {model}");
+    if let Some(variant) = &effective.prompt_variant {
+        let _ = writeln!(out, "# variant: {variant}");
+    }
+    let _ = writeln!(out, "# mode: {mode}");
+    if layers.order.is_empty() && config.system_prompt_mode == SystemPromptMode::Append {
+        out.push_str("# order: template layout (builtin embeds the other sources)\n");
+    } else {
+        let order: Vec<&str> = layers
+            .resolved_order()
+            .into_iter()
+            .map(PromptSource::name)
+            .collect();
+        let _ = writeln!(out, "# order: {}", order.join(", "));
+    }
+    if !disabled.is_empty() {
+        let _ = writeln!(out, "# disabled: {}", disabled.join(", "));
+    }
+    for path in &effective.loaded_agents_paths {
+        let _ = writeln!(out, "# project context: {}", path.display());
+    }
+    if !effective.loaded_skills.is_empty() {
+        let _ = writeln!(out, "# skills: {}", effective.loaded_skills.len());
+    }
+    for warning in &effective.warnings {
+        match &warning.path {
+            Some(path) => {
+                let _ = writeln!(out, "# warning: {}: {}", path.display(), warning.message);
+            }
+            None => {
+                let _ = writeln!(out, "# warning: {}", warning.message);
+            }
+        }
+    }
+
+    if effective.sections.is_empty() {
+        out.push_str("\n(no system prompt)\n");
+    }
+    for section in &effective.sections {
+        let _ = write!(out, "\n── {} ──\n{}\n", section.label, section.text);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use zdx_engine::config::PromptLayersConfig;
+    use zdx_engine::core::context::PromptSection;
+
+    use super::*;
+
+    #[test]
+    fn test_format_report_annotates_sections_and_disabled_sources() {
+        let config = Config {
+            system_prompt_mode: SystemPromptMode::Replace,
+            prompt_layers: PromptLayersConfig {
+                skills: false,
+                order: vec![PromptSource::ProjectContext],
+                ..PromptLayersConfig::default()
+            },
+            ..Config::default()
+        };
+        let effective = EffectivePrompt {
+            prompt: Some("rules\n\nmine".to_string()),
+            sections: vec![
+                PromptSection {
+                    label: "project_context".to_string(),
+                    text: "rules".to_string(),
+                },
+                PromptSection {
+                    label: "global_instructions".to_string(),
+                    text: "mine".to_string(),
+                },
+            ],
+            prompt_variant: Some("small".to_string()),
+            ..EffectivePrompt::default()
+        };
+
+        let report = format_report(&config, "ollama:qwen3", &effective);
+        assert!(report.starts_with("# model: ollama:qwen3\n# variant: small\n# mode: replace\n"));
+        assert!(
+            report.contains(
+                "# order: project_context, builtin, global_instructions, skills, memory\n"
+            )
+        );
+        assert!(report.contains("# disabled: skills\n"));
+        assert!(
+            report.ends_with("\n── project_context ──\nrules\n\n── global_instructions ──\nmine\n")
+        );
+    }
+}
@@ -63,13 +63,13 @@ pub async fn run(
                     }
 
                     tracing::info!(name = %automation.name, "Running automation");
-                    match automation_commands::run_definition(
+                    match Box::pin(automation_commands::run_definition(
                         root,
                         thread_opts,
                         config,
                         &automation,
                         automation_commands::RunTrigger::Daemon,
-                    )
+                    ))
                     .await
                     {
                         Ok(()) => {
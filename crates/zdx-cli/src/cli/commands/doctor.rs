@@ -0,0 +1,40 @@
+//! `zdx doctor` — environment snapshot (the same facts as the `Env_Info` tool).
+
+use std::path::Path;
+
+use anyhow::Result;
+use zdx_engine::config::paths;
+use zdx_engine::tools::env_info;
+
+/// Runs `zdx doctor` for the project at `root`.
+///
+/// # Errors
+/// Returns an error if JSON serialization fails.
+pub fn run(root: &Path, json: bool) -> Result<()> {
+    let snapshot = env_info::snapshot(root);
+    let home = paths::zdx_home();
+    let config = paths::config_path();
+
+    if json {
+        let out = serde_json::json!({
+            "zdx": {
+                "version": env!("CARGO_PKG_VERSION"),
+                "home": home.display().to_string(),
+                "config": config.display().to_string(),
+                "config_exists": config.exists(),
+            },
+            "root": root.display().to_string(),
+            "environment": snapshot,
+        });
+        println!("{}", serde_json::to_string_pretty(&out)?);
+        return Ok(());
+    }
+
+    let config_note = if config.exists() { "" } else { " (not found)" };
+    println!("zdx         {}", env!("CARGO_PKG_VERSION"));
+    println!("Home        {}", home.display());
+    println!("Config      {}{config_note}", config.display());
+    println!("Root        {}", root.display());
+    print!("{}", snapshot.to_text());
+    Ok(())
+}
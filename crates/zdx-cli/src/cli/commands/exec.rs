@@ -1,15 +1,17 @@
 //! Exec command handler.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use zdx_engine::config::{self, ThinkingLevel};
 use zdx_engine::core::agent::{ToolConfig, ToolSelection};
+use zdx_engine::core::structured_output;
 use zdx_engine::core::thread_persistence::ThreadPersistenceOptions;
 use zdx_engine::tools::ToolRegistry;
 
 use crate::modes;
 
+#[allow(clippy::struct_excessive_bools)]
 pub struct ExecRunOptions<'a> {
     pub root: &'a str,
     pub thread_opts: &'a ThreadPersistenceOptions,
@@ -21,8 +23,13 @@ pub struct ExecRunOptions<'a> {
     pub thinking_override: Option<&'a str>,
     pub event_filter_override: Option<&'a str>,
     pub tools_override: Option<&'a str>,
+    pub deny_tools_override: Option<&'a str>,
     pub no_tools: bool,
     pub no_system_prompt: bool,
+    pub dry_run: bool,
+    pub no_cache: bool,
+    /// JSON schema file for `--schema` structured output.
+    pub schema_path: Option<&'a Path>,
     pub activity_kind: Option<&'a str>,
     pub activity_parent_thread_id: Option<&'a str>,
     pub activity_subagent_name: Option<&'a str>,
@@ -50,7 +57,12 @@ pub async fn run(options: ExecRunOptions<'_>) -> Result<()> {
         c
     };
 
-    let tool_registry = ToolRegistry::builtins();
+    let output_schema = options
+        .schema_path
+        .map(structured_output::load_schema)
+        .transpose()?;
+
+    let tool_registry = ToolRegistry::from_config(&config);
     let available_tool_names = tool_registry.tool_names();
 
     let exec_opts = modes::exec::ExecOptions {
@@ -59,10 +71,13 @@ pub async fn run(options: ExecRunOptions<'_>) -> Result<()> {
             tool_registry,
             if options.no_tools {
                 ToolSelection::Explicit(Vec::new())
-            } else if let Some(raw) = options.tools_override {
-                ToolSelection::Explicit(parse_tools_override(raw, &available_tool_names)?)
             } else {
-                ToolSelection::default()
+                ToolSelection::from_cli_flags(
+                    options.tools_override,
+                    options.deny_tools_override,
+                    &available_tool_names,
+                )?
+                .unwrap_or_default()
             },
         ),
         event_filter: options
@@ -74,6 +89,8 @@ pub async fn run(options: ExecRunOptions<'_>) -> Result<()> {
             .effective_system_prompt_override
             .map(std::string::ToString::to_string),
         no_system_prompt: options.no_system_prompt,
+        dry_run: options.dry_run,
+        no_cache: options.no_cache,
         activity_kind: options.activity_kind.map(std::string::ToString::to_string),
         activity_parent_thread_id: options
             .activity_parent_thread_id
@@ -81,12 +98,18 @@ pub async fn run(options: ExecRunOptions<'_>) -> Result<()> {
         activity_subagent_name: options
             .activity_subagent_name
             .map(std::string::ToString::to_string),
+        output_schema,
     };
 
     // Use streaming variant - response is printed incrementally, final newline added at end
-    modes::exec::run_exec(options.prompt, &config, thread, &exec_opts)
-        .await
-        .context("execute prompt")?;
+    Box::pin(modes::exec::run_exec(
+        options.prompt,
+        &config,
+        thread,
+        &exec_opts,
+    ))
+    .await
+    .context("execute prompt")?;
 
     Ok(())
 }
@@ -105,40 +128,6 @@ pub(super) fn parse_thinking_level(s: &str) -> Result<ThinkingLevel> {
     }
 }
 
-fn parse_tools_override(raw: &str, available: &[String]) -> Result<Vec<String>> {
-    let tools: Vec<String> = raw
-        .split(',')
-        .map(str::trim)
-        .filter(|t| !t.is_empty())
-        .map(std::string::ToString::to_string)
-        .collect();
-
-    if tools.is_empty() {
-        anyhow::bail!("--tools requires a comma-separated list of tool names");
-    }
-
-    let available_set: std::collections::HashSet<String> =
-        available.iter().map(|t| t.to_ascii_lowercase()).collect();
-    let mut unknown: Vec<String> = tools
-        .iter()
-        .filter(|t| !available_set.contains(&t.to_ascii_lowercase()))
-        .cloned()
-        .collect();
-
-    if !unknown.is_empty() {
-        unknown.sort();
-        let mut available_sorted = available.to_vec();
-        available_sorted.sort();
-        anyhow::bail!(
-            "Unknown tool(s): {}. Available tools: {}",
-            unknown.join(", "),
-            available_sorted.join(", ")
-        );
-    }
-
-    Ok(tools)
-}
-
 fn parse_event_filter(raw: &str) -> Result<Vec<String>> {
     let filters: Vec<String> = raw
         .split(',')
@@ -31,6 +31,7 @@ pub struct ImagineRunOptions<'a> {
 }
 
 pub async fn run(options: ImagineRunOptions<'_>) -> Result<()> {
+    zdx_engine::offline::ensure_online(options.config, "Image generation")?;
     let model_input = options.model_override.unwrap_or(DEFAULT_IMAGINE_MODEL);
     let provider_selection = resolve_provider(model_input);
 
@@ -0,0 +1,56 @@
+//! `zdx init` — generate a starter `AGENTS.md` for the project.
+
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use zdx_engine::core::init_generation;
+
+/// Runs `zdx init`: surveys the repository, has a subagent write `AGENTS.md`,
+/// shows it, and writes it after confirmation (`--yes` skips the prompt,
+/// `--print` only prints).
+///
+/// # Errors
+/// Returns an error if generation fails, confirmation is needed but stdin is
+/// not a terminal, or the file cannot be written.
+pub async fn run(root: &Path, model: Option<String>, yes: bool, print_only: bool) -> Result<()> {
+    let path = root.join("AGENTS.md");
+    let exists = path.exists();
+    if !yes && !print_only && !io::stdin().is_terminal() {
+        bail!("stdin is not a terminal; pass --yes to write AGENTS.md or --print to only print it");
+    }
+
+    let action = if exists { "Revising" } else { "Generating" };
+    eprintln!(
+        "{action} AGENTS.md for {} (this can take a few minutes)…",
+        root.display()
+    );
+    let content = init_generation::generate_agents_md(root, model, None).await?;
+
+    if print_only {
+        print!("{content}");
+        return Ok(());
+    }
+
+    if !yes {
+        println!("{content}");
+        let question = if exists {
+            "Overwrite the existing AGENTS.md?"
+        } else {
+            "Write AGENTS.md?"
+        };
+        print!("{question} [y/N] ");
+        io::stdout().flush()?;
+        let mut response = String::new();
+        io::stdin().lock().read_line(&mut response)?;
+        if !response.trim().eq_ignore_ascii_case("y") {
+            println!("Nothing written.");
+            return Ok(());
+        }
+    }
+
+    std::fs::write(&path, content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("Wrote {}", path.display());
+    Ok(())
+}
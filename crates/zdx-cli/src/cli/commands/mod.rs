@@ -1,16 +1,22 @@
 //! CLI command handlers.
 
+pub mod audit;
 pub mod auth;
 pub mod automations;
+pub mod bench;
 pub mod bot;
 pub mod chat;
 pub mod config;
+pub mod context;
 pub mod daemon;
+pub mod doctor;
 pub mod exec;
 pub mod imagine;
+pub mod init;
 pub mod mcp;
 pub mod memory;
 pub mod models;
+pub mod new;
 pub mod quota;
 pub mod speak;
 pub mod stats;
@@ -0,0 +1,35 @@
+//! `zdx new` — scaffold a commented automation, skill, or custom tool.
+
+use std::path::Path;
+
+use anyhow::Result;
+use zdx_engine::scaffold::{self, ScaffoldKind};
+
+/// Creates the scaffold and prints where it went plus how to try it.
+///
+/// # Errors
+/// Returns an error if the name is invalid, the target already exists, or
+/// the file cannot be written.
+pub fn run(root: &Path, kind: ScaffoldKind, name: &str) -> Result<()> {
+    let path = scaffold::create(kind, name, root)?;
+    let name = name.trim();
+    match kind {
+        ScaffoldKind::Automation => {
+            println!("Created automation at {}", path.display());
+            println!(
+                "Check it with `zdx automations validate`; run it now with `zdx automations run {name}`."
+            );
+        }
+        ScaffoldKind::Skill { .. } => {
+            println!("Created skill at {}", path.display());
+            println!("Edit the description and steps; the skill is offered in new sessions.");
+        }
+        ScaffoldKind::Tool => {
+            println!("Added custom tool `{name}` to {}", path.display());
+            println!(
+                "Replace its `command` and `schema`, then check the file with `zdx config validate`."
+            );
+        }
+    }
+    Ok(())
+}
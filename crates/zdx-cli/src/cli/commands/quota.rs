@@ -2,7 +2,7 @@
 
 use anyhow::Result;
 use zdx_engine::providers::subscription_quota::{
-    self, QuotaError, QuotaWindow, SubscriptionQuota, provider_display,
+    self, QuotaError, QuotaWindow, SubscriptionQuota, format_reset_in, provider_display,
 };
 
 /// One provider's fetched result.
@@ -91,21 +91,3 @@ fn print_text(results: &[ProviderResult]) {
         }
     }
 }
-
-/// Formats a future reset instant as `resets in Xh Ym` (mirrors the monitor).
-fn format_reset_in(dt: chrono::DateTime<chrono::Utc>) -> String {
-    let secs = (dt - chrono::Utc::now()).num_seconds();
-    if secs <= 0 {
-        return "reset due".to_string();
-    }
-    let days = secs / 86_400;
-    let hours = (secs % 86_400) / 3_600;
-    let mins = (secs % 3_600) / 60;
-    if days > 0 {
-        format!("resets in {days}d {hours}h")
-    } else if hours > 0 {
-        format!("resets in {hours}h {mins}m")
-    } else {
-        format!("resets in {mins}m")
-    }
-}
@@ -1,9 +1,14 @@
-//! `zdx stats` — usage/cost summary across saved threads.
+//! `zdx stats` — usage/cost summary across saved threads, and the local
+//! `--personal` usage report.
 
 use anyhow::{Context, Result};
 use zdx_engine::config;
+use zdx_engine::core::personal_stats::{self, PersonalStats};
 use zdx_engine::core::usage_stats::{self, UsageRow, UsageStats};
 
+/// Rows shown per ranked section of the personal report.
+const PERSONAL_TOP: usize = 10;
+
 /// Runs `zdx stats`, printing a usage/cost breakdown per provider and model.
 pub fn run(config: &config::Config) -> Result<()> {
     let stats = usage_stats::aggregate_usage(&config.model).context("aggregate usage stats")?;
@@ -11,6 +16,55 @@ pub fn run(config: &config::Config) -> Result<()> {
     Ok(())
 }
 
+/// Runs `zdx stats --personal`, printing a local report of how zdx is used.
+pub fn run_personal() -> Result<()> {
+    let stats = personal_stats::compute().context("compute personal stats")?;
+    print_personal(&stats);
+    Ok(())
+}
+
+fn print_personal(stats: &PersonalStats) {
+    println!("zdx personal stats");
+    println!(
+        "Local only: computed from $ZDX_HOME/threads and $ZDX_HOME/stats/events.jsonl; \
+         nothing is sent anywhere."
+    );
+    println!();
+    println!(
+        "Threads: {} · turns: {} · avg turns/thread: {:.1}",
+        stats.threads_scanned,
+        stats.turns,
+        stats.average_turns(),
+    );
+
+    print_ranked("Most used commands", "COMMAND", &stats.commands);
+    print_ranked("Most used tools", "TOOL", &stats.tools);
+    print_ranked("Most used models", "MODEL", &stats.models);
+
+    println!();
+    println!("Busiest hours (local time, by turns):");
+    let hours = stats.busiest_hours(5);
+    if hours.is_empty() {
+        println!("  (none yet)");
+    }
+    for (hour, count) in hours {
+        println!("  {hour:02}:00–{:02}:00 {count:>8}", (hour + 1) % 24);
+    }
+}
+
+fn print_ranked(title: &str, label: &str, rows: &[(String, u64)]) {
+    println!();
+    println!("{title}:");
+    if rows.is_empty() {
+        println!("  (none yet)");
+        return;
+    }
+    println!("  {label:<34} {:>8}", "COUNT");
+    for (name, count) in rows.iter().take(PERSONAL_TOP) {
+        println!("  {:<34} {count:>8}", truncate(name, 34));
+    }
+}
+
 fn print_stats(stats: &UsageStats) {
     println!("zdx usage stats (estimated)");
     println!("Global across all ZDX threads under $ZDX_HOME/threads.");
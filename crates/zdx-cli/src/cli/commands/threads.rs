@@ -182,6 +182,29 @@ pub fn rename(id: &str, title: &str) -> Result<()> {
     Ok(())
 }
 
+pub fn clone(id: &str, turns: Option<usize>) -> Result<()> {
+    let copy = thread_persistence::clone_thread(id, turns)
+        .with_context(|| format!("clone thread '{id}'"))?;
+    println!("Cloned thread {id} → {}", copy.id);
+    Ok(())
+}
+
+pub fn compact_storage() -> Result<()> {
+    let report = thread_persistence::compact_thread_storage().context("compact thread storage")?;
+    println!(
+        "Thread storage: scanned={}, rewritten={}, externalized={}, orphaned_blobs_removed={}",
+        report.threads_scanned,
+        report.threads_rewritten,
+        report.payloads_externalized,
+        report.orphaned_blobs_removed
+    );
+    println!(
+        "Thread logs: {} → {} bytes",
+        report.bytes_before, report.bytes_after
+    );
+    Ok(())
+}
+
 pub fn export(force: bool, dry_run: bool) -> Result<()> {
     let summary = thread_export::export_threads_incremental(ThreadExportOptions { force, dry_run })
         .context("export threads")?;
@@ -194,12 +217,17 @@ pub fn export(force: bool, dry_run: bool) -> Result<()> {
     Ok(())
 }
 
-pub async fn resume(id: Option<String>, config: &config::Config) -> Result<()> {
+pub async fn resume(id: Option<String>, root_path: PathBuf, config: &config::Config) -> Result<()> {
     let thread_id = match id {
-        Some(id) => id,
-        None => thread_persistence::latest_thread_id()
-            .context("find latest thread id")?
-            .context("No threads found to resume")?,
+        Some(id) => thread_persistence::resolve_thread_alias(&id),
+        None => match modes::last_thread_for(&root_path)
+            .filter(|id| thread_persistence::thread_exists(id))
+        {
+            Some(id) => id,
+            None => thread_persistence::latest_thread_id()
+                .context("find latest thread id")?
+                .context("No threads found to resume")?,
+        },
     };
 
     let history = thread_persistence::load_thread_as_messages(&thread_id)
@@ -208,7 +236,6 @@ pub async fn resume(id: Option<String>, config: &config::Config) -> Result<()> {
     let thread = thread_persistence::Thread::with_id(thread_id.clone())
         .with_context(|| format!("open thread '{thread_id}'"))?;
 
-    let root_path = PathBuf::from(".");
     modes::run_interactive_chat_with_history(config, Some(thread), history, root_path)
         .await
         .context("resume chat failed")?;
@@ -216,6 +243,25 @@ pub async fn resume(id: Option<String>, config: &config::Config) -> Result<()> {
     Ok(())
 }
 
+pub fn replay(id: &str, speed: f64, max_gap_secs: f64) -> Result<()> {
+    if !(speed.is_finite() && speed > 0.0) {
+        anyhow::bail!("--speed must be a positive number");
+    }
+    let max_gap = std::time::Duration::try_from_secs_f64(max_gap_secs)
+        .context("--max-gap must be a non-negative number of seconds")?;
+
+    let thread_id = thread_persistence::resolve_thread_alias(id);
+    let events = thread_persistence::load_thread_events(&thread_id)
+        .with_context(|| format!("load thread '{thread_id}'"))?;
+    if events.is_empty() {
+        println!("Thread '{thread_id}' is empty or not found.");
+        return Ok(());
+    }
+
+    modes::run_thread_replay(&thread_id, events, modes::ReplayOptions { speed, max_gap })
+        .context("thread replay failed")
+}
+
 pub fn search(options: SearchCommandOptions) -> Result<()> {
     let date = parse_date_filter(options.date.as_deref(), "date")?;
     let date_start = parse_date_filter(options.date_start.as_deref(), "date-start")?;
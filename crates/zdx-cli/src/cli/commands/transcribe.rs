@@ -42,6 +42,7 @@ pub async fn run(options: TranscribeRunOptions<'_>) -> Result<()> {
             .language
             .map(str::to_string)
             .or_else(|| base.language.clone()),
+        normalize: base.normalize,
     };
 
     let transcript = transcribe_audio_detailed(
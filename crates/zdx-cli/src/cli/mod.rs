@@ -3,10 +3,14 @@
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::{CommandFactory, FromArgMatches, Parser};
 use zdx_engine::config;
+use zdx_engine::core::personal_stats::{self, CommandSource};
 use zdx_engine::core::thread_persistence::ThreadPersistenceOptions;
 use zdx_engine::core::{interrupt, worktree};
+use zdx_engine::scaffold::ScaffoldKind;
+
+use self::commands::chat::ChatOverrides;
 
 mod commands;
 
@@ -17,6 +21,7 @@ const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "+", env!("ZDX_BUILD_ID
 #[command(version = VERSION)]
 #[command(author = "Talles Borges <talles.borges92@gmail.com>")]
 #[command(about = "ZDX Agentic CLI Tool")]
+#[allow(clippy::struct_excessive_bools)]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
@@ -41,6 +46,24 @@ struct Cli {
     #[arg(long)]
     thinking: Option<String>,
 
+    /// Comma-separated list of tools to enable for this session (chat launch)
+    #[arg(long, value_name = "TOOLS")]
+    tools: Option<String>,
+
+    /// Comma-separated list of tools to disable for this session (chat launch)
+    #[arg(long = "deny-tools", value_name = "TOOLS")]
+    deny_tools: Option<String>,
+
+    /// Screen-reader friendly TUI: no animations, plain-text status
+    /// announcements, no mouse capture
+    #[arg(long)]
+    a11y: bool,
+
+    /// Render the TUI inline (no alternate screen) so the conversation stays
+    /// in terminal scrollback
+    #[arg(long)]
+    inline: bool,
+
     /// Capture raw request/response traces (optional path)
     #[arg(
         long,
@@ -50,6 +73,21 @@ struct Cli {
     )]
     debug_trace: Option<String>,
 
+    /// Block network tools and features; only local model providers (e.g.
+    /// `LMStudio`) may be used
+    #[arg(long)]
+    offline: bool,
+
+    /// Print per-phase startup timings (config, context discovery, skills,
+    /// first paint) to stderr on exit
+    #[arg(long)]
+    profile_startup: bool,
+
+    /// Resume a thread by ID in the TUI (same as `zdx threads resume <ID>`);
+    /// works for Telegram bot threads too
+    #[arg(long, value_name = "THREAD_ID")]
+    resume: Option<String>,
+
     #[command(flatten)]
     thread_args: ThreadArgs,
 }
@@ -61,8 +99,8 @@ struct ThreadArgs {
     #[arg(long, value_name = "ID")]
     thread: Option<String>,
 
-    /// Do not save the thread
-    #[arg(long = "no-thread")]
+    /// Do not save the thread or anything else from the session (incognito)
+    #[arg(long = "no-thread", visible_alias = "incognito")]
     no_save: bool,
 
     /// Internal: origin kind recorded in a new thread's meta (e.g. `subagent`,
@@ -137,10 +175,28 @@ enum Commands {
         #[arg(long, value_name = "TOOLS")]
         tools: Option<String>,
 
+        /// Comma-separated list of tools to disable for this run
+        #[arg(long = "deny-tools", value_name = "TOOLS")]
+        deny_tools: Option<String>,
+
         /// Disable all tools
-        #[arg(long = "no-tools", conflicts_with = "tools")]
+        #[arg(long = "no-tools", conflicts_with_all = ["tools", "deny_tools"])]
         no_tools: bool,
 
+        /// Print the provider request that would be sent (secrets redacted)
+        /// instead of sending it
+        #[arg(long = "dry-run")]
+        dry_run: bool,
+
+        /// Bypass the `[response_cache]` for this run (no lookup, no store)
+        #[arg(long = "no-cache")]
+        no_cache: bool,
+
+        /// Constrain the final answer to the JSON schema in FILE; prints only
+        /// the validated JSON
+        #[arg(long, value_name = "FILE", conflicts_with = "filter")]
+        schema: Option<PathBuf>,
+
         /// Internal: logical role for this run in the active-agents registry
         /// (e.g. `subagent`, `exec`).
         #[arg(long = "activity-kind", hide = true, value_name = "KIND")]
@@ -238,18 +294,63 @@ enum Commands {
         command: ThreadCommands,
     },
     /// Show usage and cost totals per provider and model, across saved threads
-    Stats,
+    Stats {
+        /// Show a local report of your own usage instead: most used commands,
+        /// tools, and models, turns per thread, and busiest hours
+        #[arg(long)]
+        personal: bool,
+    },
+    /// Export an audit log of tool executions across all threads
+    Audit {
+        #[command(subcommand)]
+        command: AuditCommands,
+    },
+    /// Compare TTFT, tokens/sec, latency and cost of one prompt across models
+    Bench {
+        /// Comma-separated model ids (optionally with an `@thinking` suffix)
+        #[arg(long, value_delimiter = ',', required = true)]
+        models: Vec<String>,
+
+        /// Prompt sent unchanged to every model
+        #[arg(short = 'p', long)]
+        prompt: String,
+    },
     /// Show live subscription quota (session/weekly limits) for OAuth providers
     Quota {
         /// Emit machine-readable JSON instead of a text summary
         #[arg(long)]
         json: bool,
     },
+    /// Print an environment snapshot: OS, shell, CPU/RAM, git state, toolchains
+    Doctor {
+        /// Emit machine-readable JSON instead of a text summary
+        #[arg(long)]
+        json: bool,
+    },
+    /// Generate a starter AGENTS.md for the project (asks before writing)
+    Init {
+        /// Model for the generating subagent (optionally with `@thinking`)
+        #[arg(short, long)]
+        model: Option<String>,
+
+        /// Write without asking for confirmation
+        #[arg(short, long, conflicts_with = "print")]
+        yes: bool,
+
+        /// Print the generated file instead of writing it
+        #[arg(long)]
+        print: bool,
+    },
     /// Search and index ZDX memory collections
     Memory {
         #[command(subcommand)]
         command: MemoryCommands,
     },
+    /// Create a commented starter automation, skill, or custom tool
+    New {
+        #[command(subcommand)]
+        command: NewCommands,
+    },
     /// Manage automations
     Automations {
         #[command(subcommand)]
@@ -265,6 +366,11 @@ enum Commands {
         #[command(subcommand)]
         command: ConfigCommands,
     },
+    /// Inspect the assembled system prompt context
+    Context {
+        #[command(subcommand)]
+        command: ContextCommands,
+    },
 
     /// Log in to a provider (authenticate)
     Login {
@@ -346,6 +452,18 @@ enum ThreadCommands {
         #[arg(value_name = "THREAD_ID")]
         id: Option<String>,
     },
+    /// Replay a thread's events with their recorded timing
+    Replay {
+        /// The ID of the thread to replay
+        #[arg(value_name = "THREAD_ID")]
+        id: String,
+        /// Playback speed multiplier (e.g. 2 plays twice as fast)
+        #[arg(long, default_value_t = 1.0)]
+        speed: f64,
+        /// Longest pause between events, in seconds of recorded time
+        #[arg(long = "max-gap", value_name = "SECS", default_value_t = 3.0)]
+        max_gap: f64,
+    },
     /// Rename a thread
     Rename {
         /// The ID of the thread to rename
@@ -355,6 +473,18 @@ enum ThreadCommands {
         #[arg(value_name = "TITLE")]
         title: String,
     },
+    /// Copy a thread into a new thread ID, leaving the original untouched
+    Clone {
+        /// The ID of the thread to copy
+        #[arg(value_name = "THREAD_ID")]
+        id: String,
+        /// Keep only the first N turns (each turn starts at a user message)
+        #[arg(long, value_name = "N")]
+        turns: Option<usize>,
+    },
+    /// Move large payloads (images, long tool output) out of thread logs into
+    /// `threads/blobs/` and remove blobs no thread references
+    CompactStorage,
     /// Append a message to an existing thread
     Append {
         /// The thread ID to append to
@@ -435,6 +565,21 @@ enum ThreadCommands {
     },
 }
 
+#[derive(clap::Subcommand)]
+enum AuditCommands {
+    /// Print every tool call (saved threads and `--no-thread` sessions) as CSV
+    /// or JSONL
+    Export {
+        /// Only include tool calls on/after this date (YYYY-MM-DD, UTC)
+        #[arg(long, value_name = "YYYY-MM-DD")]
+        since: Option<String>,
+
+        /// Output format
+        #[arg(long, default_value = "csv", value_parser = ["csv", "jsonl"])]
+        format: String,
+    },
+}
+
 #[derive(clap::Subcommand)]
 enum MemoryCommands {
     /// Export saved threads and index ZDX memory collections with qmd
@@ -473,6 +618,29 @@ enum MemoryCommands {
     },
 }
 
+#[derive(clap::Subcommand)]
+enum NewCommands {
+    /// Automation in `$ZDX_HOME/automations/<NAME>.md` (cron schedule + prompt)
+    Automation {
+        /// Automation name (file stem)
+        name: String,
+    },
+    /// Skill in `.zdx/skills/<NAME>/SKILL.md` (frontmatter + instructions)
+    Skill {
+        /// Skill name (lowercase letters, digits, `-`)
+        name: String,
+
+        /// Create it in `$ZDX_HOME/skills/` instead of the project
+        #[arg(long)]
+        global: bool,
+    },
+    /// `[[tools.custom]]` entry appended to config.toml
+    Tool {
+        /// Tool name exposed to the model
+        name: String,
+    },
+}
+
 #[derive(clap::Subcommand)]
 enum AutomationCommands {
     /// List discovered automations
@@ -572,6 +740,34 @@ enum ConfigCommands {
     Init,
     /// Generate a fresh config from Rust defaults (for xtask)
     Generate,
+    /// Check the config file for syntax/type errors, unknown keys, and
+    /// deprecated keys, listing every problem (exits non-zero on errors)
+    Validate {
+        /// Config file to check (defaults to the active config path)
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+    },
+    /// Compare the config file with the bundled defaults (added, removed,
+    /// and changed keys)
+    Diff {
+        /// Config file to compare (defaults to the active config path)
+        #[arg(long, value_name = "PATH")]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum ContextCommands {
+    /// Print the final system prompt with source annotations
+    Show {
+        /// Assemble the `zdx exec` prompt instead of the interactive one
+        #[arg(long)]
+        exec: bool,
+
+        /// Assemble the prompt for this model (selects `[[prompt_variants]]`)
+        #[arg(short, long)]
+        model: Option<String>,
+    },
 }
 
 #[derive(clap::Subcommand)]
@@ -599,6 +795,21 @@ enum BotCommands {
         #[command(subcommand)]
         command: BotProfileCommands,
     },
+    /// Manage saved Telegram attachments (`$ZDX_HOME/telegram/<chat>/`)
+    Media {
+        #[command(subcommand)]
+        command: BotMediaCommands,
+    },
+}
+
+#[derive(clap::Subcommand)]
+enum BotMediaCommands {
+    /// Delete attachments past `[telegram] media_retention_days` / `media_max_mb`
+    Gc {
+        /// Show what would be deleted without deleting anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(clap::Subcommand)]
@@ -731,14 +942,15 @@ fn cli_enters_alt_screen(cli: &Cli) -> bool {
         None | Some(
             Commands::Monitor
                 | Commands::Threads {
-                    command: ThreadCommands::Resume { .. },
+                    command: ThreadCommands::Resume { .. } | ThreadCommands::Replay { .. },
                 }
         )
     )
 }
 
 pub fn run() -> Result<()> {
-    let cli = Cli::parse();
+    let matches = Cli::command().get_matches();
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
 
     if let Some(value) = cli.debug_trace.as_deref() {
         // set_var is unsafe in Rust 2024 (process-global mutation)
@@ -747,26 +959,73 @@ pub fn run() -> Result<()> {
         }
     }
 
+    if cli.offline {
+        // Set before config load so `ZDX_OFFLINE` maps to `offline = true`
+        // here and in subagent child processes.
+        unsafe {
+            std::env::set_var(zdx_engine::offline::OFFLINE_ENV_VAR, "1");
+        }
+    }
+
+    if cli.profile_startup {
+        zdx_engine::startup_profile::enable();
+    }
+
     interrupt::init();
 
     // Initialize tracing. Disable the stderr layer when we're about to take
     // over the terminal with an alternate-screen TUI (chat, monitor, or
     // `threads resume`), so warn-level logs don't bleed onto the rendered UI.
     let is_tui = cli_enters_alt_screen(&cli);
-    let _tracing_guards =
+    let _tracing_guards = zdx_engine::startup_profile::time("tracing.init", || {
         zdx_engine::tracing_init::init(&zdx_engine::tracing_init::TracingOptions {
             stderr: !is_tui,
-        });
+        })
+    });
 
     // one tokio runtime for everything
     let rt = tokio::runtime::Runtime::new().context("create tokio runtime")?;
 
-    rt.block_on(dispatch(cli))
+    if !cli.thread_args.no_save {
+        personal_stats::record_command(CommandSource::Cli, &command_path(&matches));
+    }
+
+    let result = rt.block_on(dispatch(cli));
+    if let Some(report) = zdx_engine::startup_profile::report() {
+        eprint!("{report}");
+    }
+    result
+}
+
+/// Returns the invoked subcommand path (e.g. `threads list`) for the
+/// personal usage log, or `chat` when no subcommand was given.
+fn command_path(matches: &clap::ArgMatches) -> String {
+    let mut names = Vec::new();
+    let mut current = matches;
+    while let Some((name, sub)) = current.subcommand() {
+        names.push(name);
+        current = sub;
+    }
+    if names.is_empty() {
+        "chat".to_string()
+    } else {
+        names.join(" ")
+    }
 }
 
 async fn dispatch(cli: Cli) -> Result<()> {
-    let mut config = config::Config::load().context("load config")?;
+    commands::config::migrate_on_upgrade();
+
+    // Config commands must keep working when the config itself is broken.
+    if let Some(Commands::Config { command }) = &cli.command {
+        return dispatch_config(command);
+    }
+
+    let mut config = zdx_engine::startup_profile::time("config.load", config::Config::load)
+        .context("load config")?;
     apply_system_prompt_override(&mut config, cli.system_prompt.as_deref());
+    config.tui.a11y |= cli.a11y;
+    config.tui.inline |= cli.inline;
 
     let Cli {
         command,
@@ -774,20 +1033,31 @@ async fn dispatch(cli: Cli) -> Result<()> {
         system_prompt: _,
         model,
         thinking,
+        tools,
+        deny_tools,
         thread_args,
         worktree,
+        resume,
         ..
     } = cli;
 
     let Some(command) = command else {
-        return run_chat_command(
+        if let Some(id) = resume {
+            let root_path = resolve_root(&root, worktree.as_deref())?;
+            return commands::threads::resume(Some(id), root_path, &config).await;
+        }
+        return Box::pin(run_chat_command(
             &root,
             worktree.as_deref(),
             &thread_args,
             &config,
-            model.as_deref(),
-            thinking.as_deref(),
-        )
+            ChatOverrides {
+                model: model.as_deref(),
+                thinking: thinking.as_deref(),
+                tools: tools.as_deref(),
+                deny_tools: deny_tools.as_deref(),
+            },
+        ))
         .await;
     };
 
@@ -831,19 +1101,17 @@ async fn run_chat_command(
     worktree_id: Option<&str>,
     thread_args: &ThreadArgs,
     config: &config::Config,
-    model_override: Option<&str>,
-    thinking_override: Option<&str>,
+    overrides: ChatOverrides<'_>,
 ) -> Result<()> {
     let thread_opts: ThreadPersistenceOptions = thread_args.into();
     let root_path = resolve_root(root, worktree_id)?;
     let root_string = root_path.to_string_lossy().to_string();
-    commands::chat::run(
+    Box::pin(commands::chat::run(
         &root_string,
         &thread_opts,
         config,
-        model_override,
-        thinking_override,
-    )
+        overrides,
+    ))
     .await
 }
 
@@ -854,6 +1122,7 @@ struct DispatchContext<'a> {
     config: &'a config::Config,
 }
 
+#[allow(clippy::struct_excessive_bools)]
 struct ExecCommandInput {
     prompt: Option<String>,
     prompt_file: Option<PathBuf>,
@@ -862,8 +1131,12 @@ struct ExecCommandInput {
     model: Option<String>,
     thinking: Option<String>,
     tools: Option<String>,
+    deny_tools: Option<String>,
     no_tools: bool,
     no_system_prompt: bool,
+    dry_run: bool,
+    no_cache: bool,
+    schema: Option<PathBuf>,
     activity_kind: Option<String>,
     activity_parent_thread_id: Option<String>,
     activity_subagent_name: Option<String>,
@@ -925,8 +1198,12 @@ async fn run_exec_command(context: &DispatchContext<'_>, input: ExecCommandInput
         thinking_override: input.thinking.as_deref(),
         event_filter_override: input.filter.as_deref(),
         tools_override: input.tools.as_deref(),
+        deny_tools_override: input.deny_tools.as_deref(),
         no_tools: input.no_tools,
         no_system_prompt: input.no_system_prompt,
+        dry_run: input.dry_run,
+        no_cache: input.no_cache,
+        schema_path: input.schema.as_deref(),
         activity_kind: input.activity_kind.as_deref(),
         activity_parent_thread_id: input.activity_parent_thread_id.as_deref(),
         activity_subagent_name: input.activity_subagent_name.as_deref(),
@@ -979,12 +1256,16 @@ async fn dispatch_command(command: Commands, context: &DispatchContext<'_>) -> R
             model,
             thinking,
             tools,
+            deny_tools,
             no_tools,
+            dry_run,
+            no_cache,
+            schema,
             activity_kind,
             activity_parent_thread_id,
             activity_subagent_name,
         } => {
-            run_exec_command(
+            Box::pin(run_exec_command(
                 context,
                 ExecCommandInput {
                     prompt,
@@ -994,18 +1275,38 @@ async fn dispatch_command(command: Commands, context: &DispatchContext<'_>) -> R
                     model,
                     thinking,
                     tools,
+                    deny_tools,
                     no_tools,
                     no_system_prompt,
+                    dry_run,
+                    no_cache,
+                    schema,
                     activity_kind,
                     activity_parent_thread_id,
                     activity_subagent_name,
                 },
-            )
+            ))
             .await
         }
         Commands::Threads { command } => dispatch_threads(command, context).await,
-        Commands::Stats => commands::stats::run(context.config),
+        Commands::Stats { personal: false } => commands::stats::run(context.config),
+        Commands::Stats { personal: true } => commands::stats::run_personal(),
+        Commands::Audit {
+            command: AuditCommands::Export { since, format },
+        } => commands::audit::export(since.as_deref(), &format),
+        Commands::Bench { models, prompt } => {
+            let root_path = resolve_root(context.root, context.worktree_id)?;
+            commands::bench::run(&root_path, context.config, &models, &prompt).await
+        }
         Commands::Quota { json } => commands::quota::run(json).await,
+        Commands::Init { model, yes, print } => {
+            let root_path = resolve_root(context.root, context.worktree_id)?;
+            commands::init::run(&root_path, model, yes, print).await
+        }
+        Commands::Doctor { json } => {
+            let root_path = resolve_root(context.root, context.worktree_id)?;
+            commands::doctor::run(&root_path, json)
+        }
         Commands::Imagine {
             prompt,
             out,
@@ -1070,9 +1371,26 @@ async fn dispatch_command(command: Commands, context: &DispatchContext<'_>) -> R
             .await
         }
         Commands::Memory { command } => dispatch_memory(command, context),
+        Commands::New { command } => {
+            let root_path = resolve_root(context.root, context.worktree_id)?;
+            let (kind, name) = match command {
+                NewCommands::Automation { name } => (ScaffoldKind::Automation, name),
+                NewCommands::Skill { name, global } => (ScaffoldKind::Skill { global }, name),
+                NewCommands::Tool { name } => (ScaffoldKind::Tool, name),
+            };
+            commands::new::run(&root_path, kind, &name)
+        }
         Commands::Automations { command } => Box::pin(dispatch_automations(command, context)).await,
         Commands::Mcp { command } => dispatch_mcp(command, context).await,
         Commands::Config { command } => dispatch_config(&command),
+        Commands::Context {
+            command: ContextCommands::Show { exec, model },
+        } => commands::context::show(
+            context.config,
+            &PathBuf::from(context.root),
+            exec,
+            model.as_deref(),
+        ),
         Commands::Login {
             anthropic,
             claude_cli,
@@ -1123,6 +1441,7 @@ fn parse_image_size(value: &str) -> std::result::Result<String, String> {
 async fn dispatch_bot(command: Option<BotCommands>, context: &DispatchContext<'_>) -> Result<()> {
     match command {
         None => {
+            zdx_engine::offline::ensure_online(context.config, "The Telegram bot")?;
             let root_path = resolve_root(context.root, context.worktree_id)?;
             Box::pin(zdx_bot::run_named_with_config_and_root(
                 "bot",
@@ -1150,6 +1469,9 @@ async fn dispatch_bot(command: Option<BotCommands>, context: &DispatchContext<'_
                 commands::bot::add_profile(context.config, &name, chat_id, &cwd)
             }
         },
+        Some(BotCommands::Media { command }) => match command {
+            BotMediaCommands::Gc { dry_run } => commands::bot::media_gc(context.config, dry_run),
+        },
     }
 }
 
@@ -1157,8 +1479,16 @@ async fn dispatch_threads(command: ThreadCommands, context: &DispatchContext<'_>
     match command {
         ThreadCommands::List { all } => commands::threads::list(all),
         ThreadCommands::Show { id } => commands::threads::show(&id, context.config),
-        ThreadCommands::Resume { id } => commands::threads::resume(id, context.config).await,
+        ThreadCommands::Resume { id } => {
+            let root_path = resolve_root(context.root, context.worktree_id)?;
+            commands::threads::resume(id, root_path, context.config).await
+        }
+        ThreadCommands::Replay { id, speed, max_gap } => {
+            commands::threads::replay(&id, speed, max_gap)
+        }
         ThreadCommands::Rename { id, title } => commands::threads::rename(&id, &title),
+        ThreadCommands::Clone { id, turns } => commands::threads::clone(&id, turns),
+        ThreadCommands::CompactStorage => commands::threads::compact_storage(),
         ThreadCommands::Append { id, role, text } => commands::threads::append(&id, &role, &text),
         ThreadCommands::Export { force, dry_run } => commands::threads::export(force, dry_run),
         ThreadCommands::Search {
@@ -1256,7 +1586,13 @@ async fn dispatch_automations(
         }),
         AutomationCommands::Run { name } => {
             let thread_opts: ThreadPersistenceOptions = context.thread_args.into();
-            commands::automations::run(&root_path, &thread_opts, context.config, &name).await
+            Box::pin(commands::automations::run(
+                &root_path,
+                &thread_opts,
+                context.config,
+                &name,
+            ))
+            .await
         }
     }
 }
@@ -1287,6 +1623,8 @@ fn dispatch_config(command: &ConfigCommands) -> Result<()> {
         }
         ConfigCommands::Init => commands::config::init(),
         ConfigCommands::Generate => commands::config::generate(),
+        ConfigCommands::Validate { path } => commands::config::validate(path.as_deref()),
+        ConfigCommands::Diff { path } => commands::config::diff(path.as_deref()),
     }
 }
 
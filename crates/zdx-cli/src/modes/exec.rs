@@ -7,14 +7,19 @@
 use std::io::{Stdout, Write, stdout};
 use std::path::PathBuf;
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use tokio::task::JoinHandle;
 use tracing::{info, warn};
 use zdx_engine::config::Config;
-use zdx_engine::core::agent::{AgentOptions, ToolConfig};
+use zdx_engine::core::agent::{AgentOptions, ToolConfig, TurnOptions, resolve_active_tools};
+use zdx_engine::core::context::PromptContextInclusion;
+use zdx_engine::core::event_bus::{EventBus, EventFilter, EventSubscriber};
 use zdx_engine::core::events::{AgentEvent, TurnStatus};
+use zdx_engine::core::response_cache::{self, CacheKeyParts};
 use zdx_engine::core::thread_persistence::{self, Thread, ThreadEvent};
-use zdx_engine::providers::ChatMessage;
+use zdx_engine::core::{interrupt, structured_output};
+use zdx_engine::providers::{ChatMessage, OutputSchema, resolve_provider};
+use zdx_engine::tools::protected_files::ProtectedApprovals;
 
 const EXEC_INSTRUCTION_LAYER: &str = zdx_engine::prompts::EXEC_INSTRUCTION_LAYER;
 
@@ -39,12 +44,19 @@ pub struct ExecOptions {
     pub effective_system_prompt: Option<String>,
     /// Disable all system prompt/context composition.
     pub no_system_prompt: bool,
+    /// Print the provider request instead of sending it.
+    pub dry_run: bool,
+    /// Bypass the response cache for this run.
+    pub no_cache: bool,
     /// Logical role for this run in the active-agents registry.
     pub activity_kind: Option<String>,
     /// Parent thread id when this run was spawned by another agent.
     pub activity_parent_thread_id: Option<String>,
     /// Named subagent invoked when this is a `invoke_subagent` child.
     pub activity_subagent_name: Option<String>,
+    /// Constrain the final answer to this JSON schema and print only the
+    /// validated JSON instead of JSONL events.
+    pub output_schema: Option<OutputSchema>,
 }
 
 impl From<&ExecOptions> for AgentOptions {
@@ -58,6 +70,14 @@ impl From<&ExecOptions> for AgentOptions {
             activity_kind: opts.activity_kind.clone(),
             activity_parent_thread_id: opts.activity_parent_thread_id.clone(),
             activity_subagent_name: opts.activity_subagent_name.clone(),
+            soft_stop: None,
+            steering: None,
+            ask_user: None,
+            protected_approvals: ProtectedApprovals::default(),
+            turn: TurnOptions {
+                output_schema: opts.output_schema.clone(),
+                ..TurnOptions::default()
+            },
         }
     }
 }
@@ -71,6 +91,9 @@ impl From<&ExecOptions> for AgentOptions {
 ///
 /// Logs effective context info (project context files, skills) at startup.
 fn log_effective_context(effective: &zdx_engine::core::context::EffectivePrompt) {
+    if let Some(variant) = &effective.prompt_variant {
+        info!(variant = %variant, "exec prompt variant");
+    }
     if !effective.loaded_agents_paths.is_empty() {
         let paths_str: Vec<String> = effective
             .loaded_agents_paths
@@ -97,36 +120,34 @@ fn log_effective_context(effective: &zdx_engine::core::context::EffectivePrompt)
     }
 }
 
-/// This is a backward-compatible wrapper that uses the agent internally.
-pub async fn run_exec(
-    prompt: &str,
+/// Builds the system prompt for this run (none, the `--effective-system-prompt-file`
+/// override, or the composed exec prompt) and logs its warnings and context.
+fn build_effective_prompt(
     config: &Config,
-    mut thread: Option<Thread>,
     options: &ExecOptions,
-) -> Result<String> {
-    let thread_id_ref = thread.as_ref().map(|t| t.id.as_str());
-
-    // Set runtime env vars before building prompt (Slice 1: env-vars-runtime-context)
-    zdx_engine::core::context::set_runtime_env(config, thread_id_ref);
-
+    incognito: bool,
+) -> Result<Option<zdx_engine::core::context::EffectivePrompt>> {
     let effective = if options.no_system_prompt {
         None
     } else if let Some(prompt) = options.effective_system_prompt.as_ref() {
         Some(zdx_engine::core::context::EffectivePrompt {
             prompt: Some(prompt.clone()),
-            loaded_agents_paths: Vec::new(),
-            scoped_context_paths: Vec::new(),
-            warnings: Vec::new(),
-            loaded_skills: Vec::new(),
+            ..Default::default()
         })
     } else {
         let instruction_layers = exec_instruction_layers();
+        // Unsaved runs leave the memory index out so nothing is remembered.
         Some(
-            zdx_engine::core::context::build_effective_system_prompt_with_paths_and_instruction_layers(
+            zdx_engine::core::context::build_prompt_with_context_and_layers(
                 config,
                 &options.root,
+                &config.model,
                 &instruction_layers,
                 false,
+                PromptContextInclusion {
+                    memory_index: !incognito,
+                    ..PromptContextInclusion::default()
+                },
             )?,
         )
     };
@@ -135,82 +156,194 @@ pub async fn run_exec(
         for warning in &effective.warnings {
             warn!(message = %warning.message, "exec context warning");
         }
-    }
-
-    // Emit context info (project context files, skills)
-    if let Some(effective) = &effective {
+        // Emit context info (project context files, skills)
         log_effective_context(effective);
     }
+    Ok(effective)
+}
+
+/// This is a backward-compatible wrapper that uses the agent internally.
+pub async fn run_exec(
+    prompt: &str,
+    config: &Config,
+    mut thread: Option<Thread>,
+    options: &ExecOptions,
+) -> Result<String> {
+    let thread_id_ref = thread.as_ref().map(|t| t.id.as_str());
+
+    // Set runtime env vars before building prompt (Slice 1: env-vars-runtime-context)
+    zdx_engine::core::context::set_runtime_env(config, thread_id_ref);
+
+    let effective = build_effective_prompt(config, options, thread.is_none())?;
 
     // Load thread history if continuing an existing thread
+    let model_prompt = options.output_schema.as_ref().map_or_else(
+        || prompt.to_string(),
+        |schema| format!("{prompt}\n\n{}", structured_output::instructions(schema)),
+    );
     let messages = if let Some(ref existing_thread) = thread {
         let mut history = thread_persistence::load_thread_as_messages(&existing_thread.id)?;
-        history.push(ChatMessage::user(prompt));
+        history.push(ChatMessage::user(&model_prompt));
         history
     } else {
-        vec![ChatMessage::user(prompt)]
+        vec![ChatMessage::user(&model_prompt)]
     };
 
+    let system_prompt = effective.as_ref().and_then(|e| e.prompt.as_deref());
+    if options.dry_run {
+        let request = zdx_engine::core::agent::dry_run_request(
+            &messages,
+            config,
+            &AgentOptions::from(options),
+            system_prompt,
+            thread_id_ref,
+        )
+        .await?;
+        print!("{}", request.to_pretty_string());
+        return Ok(String::new());
+    }
+
+    let cache_key = response_cache_key(config, options, system_prompt, &messages);
+    if let Some(cached) = cache_key
+        .as_deref()
+        .and_then(|key| response_cache::lookup(key, config.response_cache.ttl_secs))
+    {
+        return replay_cached_response(prompt, cached, thread.as_mut(), options);
+    }
+
     // Log user message to thread (ensures meta is written for new threads)
     if let Some(ref mut s) = thread {
         s.append(&ThreadEvent::user_message(prompt))?;
+        s.set_prompt_variant(effective.as_ref().and_then(|e| e.prompt_variant.clone()))?;
+    }
+
+    let final_text = if let Some(schema) = &options.output_schema {
+        run_structured_turns(
+            schema,
+            messages,
+            config,
+            system_prompt,
+            thread.as_mut(),
+            options,
+        )
+        .await?
+    } else {
+        run_agent_turn(messages, config, system_prompt, thread.as_ref(), options)
+            .await?
+            .0
+    };
+
+    emit_final_answer(&final_text, options);
+
+    // Unsaved runs may read the cache but never write to it.
+    if let Some(key) = cache_key.as_deref()
+        && thread.is_some()
+        && !final_text.is_empty()
+        && let Err(err) = response_cache::store(key, &config.model, &final_text)
+    {
+        warn!(error = %err, "exec response cache store failed");
+    }
+
+    // Log assistant response to thread
+    if let Some(ref mut s) = thread {
+        s.append(&ThreadEvent::assistant_message_with_phase(
+            &final_text,
+            Some("final_answer".to_string()),
+        ))?;
     }
+
+    Ok(final_text)
+}
+
+/// Runs one agent turn, rendering its events as JSONL (unless the run prints
+/// structured output) and persisting them to `thread`.
+async fn run_agent_turn(
+    messages: Vec<ChatMessage>,
+    config: &Config,
+    system_prompt: Option<&str>,
+    thread: Option<&Thread>,
+    options: &ExecOptions,
+) -> Result<(String, Vec<ChatMessage>)> {
     let agent_opts = AgentOptions::from(options);
 
-    // Create channels for broadcast
-    let (agent_tx, agent_rx) = zdx_engine::core::agent::create_event_channel();
-    let (render_tx, render_rx) = zdx_engine::core::agent::create_event_channel();
-
-    // Spawn renderer task
-    let renderer_handle =
-        spawn_exec_renderer_task_with_filter(render_rx, options.event_filter.clone());
-
-    // Spawn persist task if thread exists
-    let thread_id = thread.as_ref().map(|t| t.id.clone());
-    let persist_handle = if let Some(thread_handle) = thread.clone() {
-        let (persist_tx, persist_rx) = zdx_engine::core::agent::create_event_channel();
-        let broadcaster =
-            zdx_engine::core::agent::spawn_broadcaster(agent_rx, vec![render_tx, persist_tx]);
-        let persist = thread_persistence::spawn_thread_persist_task(thread_handle, persist_rx);
-        Some((broadcaster, persist))
+    // Every sink subscribes to the turn's event bus before it starts.
+    let mut bus = EventBus::new();
+
+    // Spawn renderer task; structured output replaces the event stream.
+    let renderer_handle = if options.output_schema.is_none() {
+        Some(spawn_exec_renderer_task_with_filter(
+            bus.subscribe("exec_renderer", EventFilter::NoDeltas),
+            options.event_filter.clone(),
+        ))
     } else {
-        // No thread - just broadcast to renderer
-        let broadcaster = zdx_engine::core::agent::spawn_broadcaster(agent_rx, vec![render_tx]);
-        Some((broadcaster, tokio::spawn(async {}))) // Dummy persist task
+        None
     };
 
-    // Run the agent turn
-    let result = zdx_engine::core::agent::run_turn(
+    // Persist to the thread if one exists; `--no-thread` (incognito) runs write nothing.
+    let persist_handle = thread.cloned().map(|thread_handle| {
+        thread_persistence::spawn_thread_persist_task(
+            thread_handle,
+            bus.subscribe("persist", EventFilter::NoDeltas),
+        )
+    });
+
+    // Run the agent turn; Ctrl+C cancels it through the interrupt token.
+    let result = zdx_engine::core::agent::run_turn_with_cancel(
         messages,
         config,
         &agent_opts,
-        effective.as_ref().and_then(|e| e.prompt.as_deref()),
-        thread_id.as_deref(),
-        agent_tx,
+        system_prompt,
+        thread.map(|t| t.id.as_str()),
+        bus.into_sender(),
+        Some(interrupt::cancellation_token()),
     )
     .await;
 
     // Wait for all tasks to complete (even on error, to flush error events)
-    if let Some((broadcaster, persist)) = persist_handle {
-        let _ = broadcaster.await;
+    if let Some(persist) = persist_handle {
         let _ = persist.await;
     }
-    let _ = renderer_handle.await;
+    if let Some(renderer) = renderer_handle {
+        let _ = renderer.await;
+    }
 
     // Propagate error after tasks complete
-    let (final_text, _messages) = result?;
-
-    emit_final_turn_finished(&final_text, &options.event_filter);
+    result
+}
 
-    // Log assistant response to thread
-    if let Some(ref mut s) = thread {
-        s.append(&ThreadEvent::assistant_message_with_phase(
-            &final_text,
-            Some("final_answer".to_string()),
-        ))?;
+/// Runs turns until the final answer validates against `schema`, feeding
+/// validation errors back up to `structured_output::MAX_RETRIES` times.
+/// Returns the validated JSON, compacted.
+async fn run_structured_turns(
+    schema: &OutputSchema,
+    mut messages: Vec<ChatMessage>,
+    config: &Config,
+    system_prompt: Option<&str>,
+    mut thread: Option<&mut Thread>,
+    options: &ExecOptions,
+) -> Result<String> {
+    for attempt in 0..=structured_output::MAX_RETRIES {
+        let (final_text, history) =
+            run_agent_turn(messages, config, system_prompt, thread.as_deref(), options).await?;
+        let errors = match structured_output::validate(schema, &final_text) {
+            Ok(value) => return Ok(serde_json::to_string(&value)?),
+            Err(errors) => errors,
+        };
+        if attempt == structured_output::MAX_RETRIES {
+            bail!(
+                "Response did not match the schema after {} attempts:\n{errors}",
+                attempt + 1
+            );
+        }
+        warn!(attempt = attempt + 1, errors = %errors, "exec structured output invalid, retrying");
+        let retry = structured_output::retry_prompt(&errors);
+        if let Some(s) = thread.as_deref_mut() {
+            s.append(&ThreadEvent::user_message(&retry))?;
+        }
+        messages = history;
+        messages.push(ChatMessage::user(&retry));
     }
-
-    Ok(final_text)
+    unreachable!("the final attempt returns or bails")
 }
 
 /// CLI renderer that writes agent events as compact JSONL to stdout.
@@ -258,12 +391,12 @@ impl ExecRenderer {
     pub fn finish() {}
 }
 
-/// Spawns a renderer task that consumes events from a channel.
+/// Spawns a renderer task that consumes events from a bus subscriber.
 ///
-/// The task owns the `ExecRenderer` and processes events until the channel closes.
+/// The task owns the `ExecRenderer` and processes events until the stream closes.
 /// Returns a `JoinHandle` that resolves when all events have been rendered.
 pub fn spawn_exec_renderer_task_with_filter(
-    mut rx: zdx_engine::core::agent::AgentEventRx,
+    mut rx: EventSubscriber,
     event_filter: Vec<String>,
 ) -> JoinHandle<()> {
     tokio::spawn(async move {
@@ -288,6 +421,8 @@ fn event_type_name(event: &AgentEvent) -> &'static str {
         AgentEvent::ToolCompleted { .. } => "tool_completed",
         AgentEvent::Error { .. } => "error",
         AgentEvent::Notice { .. } => "notice",
+        AgentEvent::LoopDetected { .. } => "loop_detected",
+        AgentEvent::ThinkingEscalated { .. } => "thinking_escalated",
         AgentEvent::ProviderRetry { .. } => "provider_retry",
         AgentEvent::UsageUpdate { .. } => "usage_update",
         // These variants are filtered out earlier by `sanitize_exec_event`,
@@ -296,6 +431,7 @@ fn event_type_name(event: &AgentEvent) -> &'static str {
         AgentEvent::AssistantDelta { .. }
         | AgentEvent::ReasoningDelta { .. }
         | AgentEvent::ToolInputDelta { .. }
+        | AgentEvent::ToolInputPartial { .. }
         | AgentEvent::ToolOutputDelta { .. }
         | AgentEvent::TurnCheckpoint { .. }
         | AgentEvent::TurnFinished { .. } => {
@@ -310,6 +446,7 @@ fn sanitize_exec_event(event: &AgentEvent) -> Option<AgentEvent> {
         | AgentEvent::ReasoningDelta { .. }
         | AgentEvent::ToolOutputDelta { .. }
         | AgentEvent::ToolInputDelta { .. }
+        | AgentEvent::ToolInputPartial { .. }
         | AgentEvent::TurnCheckpoint { .. }
         | AgentEvent::TurnFinished { .. } => None,
         AgentEvent::ReasoningCompleted { block } => {
@@ -327,6 +464,68 @@ fn sanitize_exec_event(event: &AgentEvent) -> Option<AgentEvent> {
     }
 }
 
+/// Completes a run from a response-cache hit: logs the exchange to the
+/// thread and emits the final event as if the turn had run.
+fn replay_cached_response(
+    prompt: &str,
+    cached: String,
+    thread: Option<&mut Thread>,
+    options: &ExecOptions,
+) -> Result<String> {
+    info!("exec response cache hit");
+    if let Some(s) = thread {
+        s.append(&ThreadEvent::user_message(prompt))?;
+        s.append(&ThreadEvent::assistant_message_with_phase(
+            &cached,
+            Some("final_answer".to_string()),
+        ))?;
+    }
+    emit_final_answer(&cached, options);
+    Ok(cached)
+}
+
+/// Cache key for this run, or `None` when `[response_cache]` is off or
+/// bypassed with `--no-cache`.
+fn response_cache_key(
+    config: &Config,
+    options: &ExecOptions,
+    system_prompt: Option<&str>,
+    messages: &[ChatMessage],
+) -> Option<String> {
+    if !config.response_cache.enabled || options.no_cache {
+        return None;
+    }
+    let tools = resolve_active_tools(
+        config,
+        &AgentOptions::from(options),
+        resolve_provider(&config.model).kind,
+    );
+    response_cache::cache_key(&CacheKeyParts {
+        model: &config.model,
+        thinking_level: config.thinking_level,
+        model_params: config.model_params,
+        max_tokens: config.effective_max_tokens_for(&config.model),
+        extra: config.providers.extra_for_model(&config.model),
+        system_prompt,
+        messages,
+        tools: &tools,
+    })
+    .inspect_err(|err| warn!(error = %err, "exec response cache key failed"))
+    .ok()
+}
+
+/// Prints the run's result: the bare JSON for structured output, otherwise
+/// the final `turn_finished` event.
+fn emit_final_answer(final_text: &str, options: &ExecOptions) {
+    if options.output_schema.is_some() {
+        let mut out = stdout();
+        let _ = writeln!(out, "{final_text}");
+        let _ = out.flush();
+        return;
+    }
+    emit_final_turn_finished(final_text, &options.event_filter);
+}
+
 fn emit_final_turn_finished(final_text: &str, event_filter: &[String]) {
     if !event_filter.is_empty()
         && !event_filter
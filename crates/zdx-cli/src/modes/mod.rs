@@ -5,6 +5,10 @@
 
 pub mod exec;
 
+#[cfg(feature = "tui")]
+pub use zdx_tui::replay::{ReplayOptions, run_thread_replay};
+#[cfg(feature = "tui")]
+pub use zdx_tui::ui_state::last_thread_for;
 #[cfg(feature = "tui")]
 pub use zdx_tui::{run_interactive_chat, run_interactive_chat_with_history};
 
@@ -13,6 +17,7 @@ pub async fn run_interactive_chat(
     _config: &zdx_engine::config::Config,
     _thread_log: Option<zdx_engine::core::thread_persistence::Thread>,
     _root: std::path::PathBuf,
+    _tool_selection: Option<zdx_engine::core::agent::ToolSelection>,
 ) -> anyhow::Result<()> {
     anyhow::bail!("TUI support is disabled in this build (feature \"tui\").");
 }
@@ -26,3 +31,24 @@ pub async fn run_interactive_chat_with_history(
 ) -> anyhow::Result<()> {
     anyhow::bail!("TUI support is disabled in this build (feature \"tui\").");
 }
+
+#[cfg(not(feature = "tui"))]
+pub fn last_thread_for(_root: &std::path::Path) -> Option<String> {
+    None
+}
+
+/// Replay settings (mirrors `zdx_tui::replay::ReplayOptions`).
+#[cfg(not(feature = "tui"))]
+pub struct ReplayOptions {
+    pub speed: f64,
+    pub max_gap: std::time::Duration,
+}
+
+#[cfg(not(feature = "tui"))]
+pub fn run_thread_replay(
+    _thread_id: &str,
+    _events: Vec<zdx_engine::core::thread_persistence::ThreadEvent>,
+    _options: ReplayOptions,
+) -> anyhow::Result<()> {
+    anyhow::bail!("TUI support is disabled in this build (feature \"tui\").");
+}
@@ -0,0 +1,142 @@
+//! Integration tests for `zdx audit export`.
+
+use std::fs;
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use serde_json::{Value, json};
+use tempfile::TempDir;
+
+fn write_jsonl(path: &std::path::Path, lines: &[Value]) {
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    let content = lines
+        .iter()
+        .map(|line| serde_json::to_string(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n";
+    fs::write(path, content).unwrap();
+}
+
+fn setup_home() -> TempDir {
+    let temp_dir = TempDir::new().unwrap();
+    write_jsonl(
+        &temp_dir.path().join("threads").join("thread-audit.jsonl"),
+        &[
+            json!({"type": "meta", "schema_version": 1, "ts": "2026-05-10T00:00:00Z"}),
+            json!({
+                "type": "tool_use",
+                "id": "old",
+                "name": "read",
+                "input": {"file_path": "old.md"},
+                "ts": "2026-04-01T00:00:00Z"
+            }),
+            json!({
+                "type": "tool_result",
+                "tool_use_id": "old",
+                "output": {"ok": true, "data": {}},
+                "ok": true,
+                "ts": "2026-04-01T00:00:01Z"
+            }),
+            json!({
+                "type": "tool_use",
+                "id": "bash-1",
+                "name": "bash",
+                "input": {"command": "GITHUB_TOKEN=abc cargo test"},
+                "ts": "2026-05-10T00:00:02Z"
+            }),
+            json!({
+                "type": "tool_result",
+                "tool_use_id": "bash-1",
+                "output": {"ok": true, "data": {"exit_code": 101}},
+                "ok": true,
+                "ts": "2026-05-10T00:00:03Z"
+            }),
+            json!({
+                "type": "tool_use",
+                "id": "edit-1",
+                "name": "edit",
+                "input": {"file_path": "src/lib.rs", "old_string": "a", "new_string": "b\nc"},
+                "ts": "2026-05-10T00:00:04Z"
+            }),
+        ],
+    );
+    write_jsonl(
+        &temp_dir.path().join("audit").join("unsaved.jsonl"),
+        &[json!({
+            "ts": "2026-05-11T00:00:00Z",
+            "thread": "unsaved-1",
+            "saved": false,
+            "tool": "write",
+            "status": "failed",
+            "input_summary": "{\"content\":\"<1 lines>\",\"file_path\":\"out.txt\"}",
+            "files_touched": ["out.txt"]
+        })],
+    );
+    temp_dir
+}
+
+#[test]
+fn test_audit_export_jsonl_merges_threads_and_unsaved_sessions() {
+    let temp_dir = setup_home();
+
+    let output = cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", temp_dir.path())
+        .args([
+            "audit",
+            "export",
+            "--since",
+            "2026-05-01",
+            "--format",
+            "jsonl",
+        ])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let records: Vec<Value> = String::from_utf8(output.stdout)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(records.len(), 3, "older call is filtered by --since");
+
+    assert_eq!(records[0]["tool"], "bash");
+    assert_eq!(records[0]["thread"], "thread-audit");
+    assert_eq!(records[0]["exit_code"], 101);
+    assert_eq!(
+        records[0]["input_summary"],
+        "GITHUB_TOKEN=[REDACTED] cargo test"
+    );
+
+    assert_eq!(records[1]["tool"], "edit");
+    assert_eq!(records[1]["status"], "pending");
+    assert_eq!(records[1]["files_touched"], json!(["src/lib.rs"]));
+    assert!(
+        records[1]["input_summary"]
+            .as_str()
+            .unwrap()
+            .contains("\"new_string\":\"<2 lines>\"")
+    );
+
+    assert_eq!(records[2]["thread"], "unsaved-1");
+    assert_eq!(records[2]["saved"], false);
+}
+
+#[test]
+fn test_audit_export_csv_quotes_fields() {
+    let temp_dir = setup_home();
+
+    let output = cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", temp_dir.path())
+        .args(["audit", "export", "--since", "2026-05-11"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        "ts,thread,saved,tool,status,exit_code,files_touched,input_summary\n\
+         2026-05-11T00:00:00Z,unsaved-1,false,write,failed,,out.txt,\
+         \"{\"\"content\"\":\"\"<1 lines>\"\",\"\"file_path\"\":\"\"out.txt\"\"}\"\n"
+    );
+}
@@ -174,3 +174,94 @@ fn test_daemon_help_shows_poll_interval() {
         .success()
         .stdout(predicate::str::contains("poll-interval-secs"));
 }
+
+#[test]
+fn test_init_help_shows_flags() {
+    cargo_bin_cmd!("zdx")
+        .args(["init", "--help"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("--yes"))
+        .stdout(predicate::str::contains("--print"))
+        .stdout(predicate::str::contains("--model"));
+}
+
+#[test]
+fn test_init_rejects_yes_with_print() {
+    cargo_bin_cmd!("zdx")
+        .args(["init", "--yes", "--print"])
+        .assert()
+        .failure()
+        .code(2);
+}
+
+#[test]
+fn test_init_requires_yes_or_print_without_terminal() {
+    let dir = tempfile::tempdir().unwrap();
+    cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", dir.path())
+        .arg("--root")
+        .arg(dir.path())
+        .arg("init")
+        .write_stdin("")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("pass --yes"));
+    assert!(!dir.path().join("AGENTS.md").exists());
+}
+
+#[test]
+fn test_new_scaffolds_automation_skill_and_tool() {
+    let home = tempfile::tempdir().unwrap();
+    let project = tempfile::tempdir().unwrap();
+    let zdx = |args: &[&str]| {
+        let mut cmd = cargo_bin_cmd!("zdx");
+        cmd.env("ZDX_HOME", home.path())
+            .arg("--root")
+            .arg(project.path())
+            .args(args);
+        cmd
+    };
+
+    zdx(&["new", "automation", "digest"]).assert().success();
+    zdx(&["automations", "validate"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("digest"));
+    zdx(&["new", "automation", "digest"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    zdx(&["new", "skill", "release-notes"]).assert().success();
+    assert!(
+        project
+            .path()
+            .join(".zdx/skills/release-notes/SKILL.md")
+            .exists()
+    );
+
+    zdx(&["new", "tool", "Echo"]).assert().success();
+    zdx(&["config", "validate"]).assert().success();
+}
+
+#[test]
+fn test_exec_deny_tools_suggests_typo_fix() {
+    let dir = tempfile::tempdir().unwrap();
+    cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", dir.path())
+        .args([
+            "exec",
+            "-p",
+            "hi",
+            "--tools",
+            "read,grep",
+            "--deny-tools",
+            "wrte",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Unknown tool(s) in --deny-tools: wrte (did you mean 'write'?)",
+        ));
+}
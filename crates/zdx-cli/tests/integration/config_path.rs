@@ -53,6 +53,28 @@ fn test_config_init_fails_if_exists() {
         .stderr(predicate::str::contains("already exists"));
 }
 
+#[test]
+fn test_config_diff_reports_changed_and_added_keys() {
+    let dir = tempdir().unwrap();
+    fs::write(
+        dir.path().join("config.toml"),
+        "thinking_level = \"high\"\n[providers.custom.local]\nbase_url = \"http://localhost:1234/v1\"\napi_key = \"secret-value\"\n",
+    )
+    .unwrap();
+
+    cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", dir.path())
+        .args(["config", "diff"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "~ thinking_level = \"high\"   (default: \"off\")",
+        ))
+        .stdout(predicate::str::contains("# Extended thinking level"))
+        .stdout(predicate::str::contains("+ [providers.custom.local]"))
+        .stdout(predicate::str::contains("secret-value").not());
+}
+
 #[test]
 fn test_config_help_shows_subcommands() {
     cargo_bin_cmd!("zdx")
@@ -0,0 +1,52 @@
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use serde_json::Value;
+use tempfile::tempdir;
+
+/// `zdx doctor --json` reports zdx paths and toolchains detected from the
+/// project root's marker files.
+#[test]
+fn test_doctor_json_reports_environment_and_toolchains() {
+    let home = tempdir().unwrap();
+    let project = tempdir().unwrap();
+    std::fs::write(project.path().join("go.mod"), "module example\n").unwrap();
+
+    let output = cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", home.path())
+        .arg("--root")
+        .arg(project.path())
+        .args(["doctor", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+
+    let parsed: Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(parsed["zdx"]["config_exists"], Value::Bool(false));
+    let env = &parsed["environment"];
+    assert!(env["os"].is_string());
+    assert!(env["arch"].is_string());
+    assert!(env["cpu"]["cores"].as_u64().unwrap() >= 1);
+    let toolchains = env["toolchains"].as_array().expect("toolchains array");
+    assert_eq!(toolchains.len(), 1);
+    assert_eq!(toolchains[0]["name"], "go");
+    assert_eq!(toolchains[0]["detected_by"], "go.mod");
+}
+
+#[test]
+fn test_doctor_text_lists_sections() {
+    let home = tempdir().unwrap();
+    let project = tempdir().unwrap();
+
+    cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", home.path())
+        .arg("--root")
+        .arg(project.path())
+        .arg("doctor")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Config"))
+        .stdout(predicate::str::contains("OS "))
+        .stdout(predicate::str::contains("none detected"));
+}
@@ -0,0 +1,142 @@
+//! Tests for `zdx exec --schema` structured output with wiremock.
+//!
+//! The first answer fails validation; the retry request carries the
+//! validation errors and its answer is printed as bare JSON. Anthropic
+//! answers can also arrive through the forced `structured_output` tool.
+
+use std::fs;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, Request};
+
+use crate::fixtures::{sse_response, text_sse, tool_use_response};
+
+fn can_bind_localhost() -> bool {
+    std::net::TcpListener::bind("127.0.0.1:0").is_ok()
+}
+
+#[tokio::test]
+async fn test_exec_schema_retries_and_prints_only_json() {
+    if !can_bind_localhost() {
+        eprintln!("Skipping: cannot bind localhost TCP port in this environment.");
+        return;
+    }
+    let zdx_home = TempDir::new().unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let schema_path = temp_dir.path().join("count.json");
+    fs::write(
+        &schema_path,
+        r#"{"type": "object", "properties": {"count": {"type": "integer"}}, "required": ["count"]}"#,
+    )
+    .unwrap();
+
+    let mock_server = MockServer::start().await;
+    let call_count = Arc::new(AtomicUsize::new(0));
+    let call_count_clone = Arc::clone(&call_count);
+    let retry_body = Arc::new(Mutex::new(String::new()));
+    let retry_body_clone = Arc::clone(&retry_body);
+
+    let invalid = text_sse(r#"{"count": "three"}"#);
+    let valid = text_sse("```json\n{\"count\": 3}\n```");
+
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(move |req: &Request| {
+            if call_count_clone.fetch_add(1, Ordering::SeqCst) == 0 {
+                sse_response(&invalid)
+            } else {
+                *retry_body_clone.lock().unwrap() = String::from_utf8_lossy(&req.body).to_string();
+                sse_response(&valid)
+            }
+        })
+        .expect(2)
+        .mount(&mock_server)
+        .await;
+
+    let output = cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", zdx_home.path())
+        .env("ANTHROPIC_API_KEY", "test-api-key")
+        .env("ANTHROPIC_BASE_URL", mock_server.uri())
+        .args([
+            "--root",
+            temp_dir.path().to_str().unwrap(),
+            "--no-thread",
+            "exec",
+            "--no-tools",
+            "--schema",
+            schema_path.to_str().unwrap(),
+            "-p",
+            "Count the files",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "{\"count\":3}\n");
+    assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    assert!(
+        retry_body
+            .lock()
+            .unwrap()
+            .contains("did not match the required JSON schema")
+    );
+}
+
+#[tokio::test]
+async fn test_exec_schema_takes_the_forced_answer_tool_input() {
+    if !can_bind_localhost() {
+        eprintln!("Skipping: cannot bind localhost TCP port in this environment.");
+        return;
+    }
+    let zdx_home = TempDir::new().unwrap();
+    let temp_dir = TempDir::new().unwrap();
+    let schema_path = temp_dir.path().join("count.json");
+    fs::write(
+        &schema_path,
+        r#"{"type": "object", "properties": {"count": {"type": "integer"}}, "required": ["count"]}"#,
+    )
+    .unwrap();
+
+    let mock_server = MockServer::start().await;
+    let request_body = Arc::new(Mutex::new(String::new()));
+    let request_body_clone = Arc::clone(&request_body);
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(move |req: &Request| {
+            *request_body_clone.lock().unwrap() = String::from_utf8_lossy(&req.body).to_string();
+            tool_use_response("toolu_1", "structured_output", r#"{"count": 3}"#)
+        })
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    let output = cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", zdx_home.path())
+        .env("ANTHROPIC_API_KEY", "test-api-key")
+        .env("ANTHROPIC_BASE_URL", mock_server.uri())
+        .args([
+            "--root",
+            temp_dir.path().to_str().unwrap(),
+            "--no-thread",
+            "exec",
+            "--no-tools",
+            "--schema",
+            schema_path.to_str().unwrap(),
+            "-p",
+            "Count the files",
+        ])
+        .output()
+        .unwrap();
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "{\"count\":3}\n");
+    let body: serde_json::Value = serde_json::from_str(&request_body.lock().unwrap()).unwrap();
+    assert_eq!(
+        body["tool_choice"],
+        serde_json::json!({"type": "tool", "name": "structured_output"})
+    );
+}
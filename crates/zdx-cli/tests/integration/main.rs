@@ -6,9 +6,13 @@
 
 mod fixtures;
 
+mod audit_export;
 mod cli_help;
 mod config_path;
+mod doctor;
+mod exec_schema;
 mod login_logout;
+mod offline;
 mod quota;
 mod thread_schema;
 mod threads_export;
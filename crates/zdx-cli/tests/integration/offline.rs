@@ -0,0 +1,46 @@
+//! Tests for `zdx --offline`.
+
+use assert_cmd::cargo::cargo_bin_cmd;
+use predicates::prelude::*;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer};
+
+use crate::fixtures::{sse_response, text_sse};
+
+fn can_bind_localhost() -> bool {
+    std::net::TcpListener::bind("127.0.0.1:0").is_ok()
+}
+
+#[tokio::test]
+async fn test_offline_flag_runs_against_local_provider() {
+    if !can_bind_localhost() {
+        eprintln!("Skipping: cannot bind localhost TCP port in this environment.");
+        return;
+    }
+    let zdx_home = TempDir::new().unwrap();
+    let mock_server = MockServer::start().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/messages"))
+        .respond_with(sse_response(&text_sse("offline hello")))
+        .expect(1)
+        .mount(&mock_server)
+        .await;
+
+    cargo_bin_cmd!("zdx")
+        .env("ZDX_HOME", zdx_home.path())
+        .env("ANTHROPIC_API_KEY", "test-api-key")
+        .env("ANTHROPIC_BASE_URL", mock_server.uri())
+        .args([
+            "--offline",
+            "--no-thread",
+            "exec",
+            "-m",
+            "anthropic:local-test-model",
+            "-p",
+            "hi",
+        ])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("offline hello"));
+}
@@ -85,6 +85,8 @@ async fn test_tool_use_loop_reads_file() {
         ));
 
     assert_eq!(call_count.load(Ordering::SeqCst), 2);
+    // `--no-thread` is incognito: no audit record is written either.
+    assert!(!zdx_home.path().join("audit").exists());
 }
 
 #[tokio::test]
@@ -1,9 +1,13 @@
+mod normalize;
 pub mod speak;
 pub mod transcribe;
 
 use anyhow::{Context, Result, anyhow};
 use tokio_util::sync::CancellationToken;
 
+/// ffmpeg binary used for audio transcoding and normalization.
+const FFMPEG_BIN: &str = "ffmpeg";
+
 /// Marker error for a user-cancelled audio operation (transcription or synthesis).
 #[derive(Debug)]
 pub struct OperationCancelled;
@@ -0,0 +1,90 @@
+//! Audio normalization before transcription.
+//!
+//! Telegram voice notes (OGG/Opus) and quiet recordings sometimes fail or
+//! transcribe poorly. Converting them to 16 kHz mono WAV with loudness
+//! normalization gives every STT provider the same well-supported input.
+
+use anyhow::{Context, Result, anyhow};
+
+use crate::audio::FFMPEG_BIN;
+
+/// Largest normalized WAV sent instead of the original (providers cap
+/// uploads at ~25 MB; 16 kHz mono PCM is ~1.9 MB per minute).
+const MAX_NORMALIZED_BYTES: usize = 25 * 1024 * 1024;
+
+/// Converts audio to 16 kHz mono 16-bit WAV with EBU R128 loudness
+/// normalization (`loudnorm`).
+///
+/// Returns `Ok(None)` when ffmpeg is not on `PATH` or the WAV would be too
+/// large to upload, so callers can send the original bytes.
+///
+/// # Errors
+/// Returns an error if ffmpeg fails to decode or convert the audio.
+pub(crate) async fn normalize_for_transcription(audio: &[u8]) -> Result<Option<Vec<u8>>> {
+    normalize_with(FFMPEG_BIN, audio).await
+}
+
+async fn normalize_with(ffmpeg_bin: &str, audio: &[u8]) -> Result<Option<Vec<u8>>> {
+    let dir = tempfile::tempdir().context("create temp dir for audio normalization")?;
+    let input = dir.path().join("in.audio");
+    let output = dir.path().join("out.wav");
+    std::fs::write(&input, audio).context("write temp audio for normalization")?;
+
+    let result = tokio::process::Command::new(ffmpeg_bin)
+        .args(["-hide_banner", "-loglevel", "error", "-y", "-i"])
+        .arg(&input)
+        .args([
+            "-vn",
+            "-af",
+            "loudnorm",
+            "-ar",
+            "16000",
+            "-ac",
+            "1",
+            "-c:a",
+            "pcm_s16le",
+            "-f",
+            "wav",
+        ])
+        .arg(&output)
+        .output()
+        .await;
+
+    let completed = match result {
+        Ok(completed) => completed,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => {
+            return Err(anyhow::Error::new(err).context("spawn ffmpeg for audio normalization"));
+        }
+    };
+
+    if !completed.status.success() {
+        return Err(anyhow!(
+            "ffmpeg normalization failed: {}",
+            String::from_utf8_lossy(&completed.stderr).trim()
+        ));
+    }
+
+    let bytes = std::fs::read(&output).context("read normalized wav")?;
+    if bytes.len() > MAX_NORMALIZED_BYTES {
+        tracing::debug!(
+            size = bytes.len(),
+            "Normalized audio too large to upload; using original"
+        );
+        return Ok(None);
+    }
+    Ok(Some(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn normalize_returns_none_when_ffmpeg_missing() {
+        let result = normalize_with("zdx-nonexistent-ffmpeg-binary-xyz", b"not-real-audio")
+            .await
+            .unwrap();
+        assert!(result.is_none());
+    }
+}
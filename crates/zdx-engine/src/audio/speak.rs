@@ -2,7 +2,7 @@ use anyhow::{Context, Result, anyhow};
 use base64::Engine;
 use tokio_util::sync::CancellationToken;
 
-use crate::audio::send_checked;
+use crate::audio::{FFMPEG_BIN, send_checked};
 use crate::config::{Config, SpeechConfig};
 use crate::providers::{ProviderKind, resolve_provider};
 
@@ -17,7 +17,6 @@ const DEFAULT_XAI_VOICE: &str = "eve";
 const DEFAULT_XAI_LANGUAGE: &str = "en";
 const OGG_FORMAT: &str = "ogg";
 const DEFAULT_FORMAT: &str = OGG_FORMAT;
-const FFMPEG_BIN: &str = "ffmpeg";
 
 /// Gemini TTS returns raw PCM; the generateContent path is mono 16-bit signed LE.
 const GEMINI_PCM_DEFAULT_RATE: u32 = 24000;
@@ -54,6 +53,7 @@ pub async fn synthesize_speech(
     text: &str,
     cancel_token: Option<&CancellationToken>,
 ) -> Result<SpeechAudio> {
+    crate::offline::ensure_online(config, "Speech synthesis")?;
     let input = text.trim();
     if input.is_empty() {
         return Err(anyhow!("Cannot synthesize speech from empty text"));
@@ -1,8 +1,8 @@
 use anyhow::{Context, Result, anyhow};
 use tokio_util::sync::CancellationToken;
 
-use crate::audio::send_checked;
 pub use crate::audio::{OperationCancelled, is_operation_cancelled};
+use crate::audio::{normalize, send_checked};
 use crate::config::{Config, TranscriptionConfig};
 use crate::providers::{ProviderKind, resolve_provider};
 
@@ -235,6 +235,7 @@ pub async fn transcribe_audio_detailed(
     diarize: bool,
     cancel_token: Option<&CancellationToken>,
 ) -> Result<Option<Transcript>> {
+    crate::offline::ensure_online(config, "Audio transcription")?;
     let Some((provider, model)) = resolve_model(config, transcription)? else {
         return Ok(None);
     };
@@ -251,6 +252,22 @@ pub async fn transcribe_audio_detailed(
     let api_key = provider.resolve_api_key(provider_config.api_key.as_deref())?;
     let base_url = provider.resolve_base_url(provider_config.base_url.as_deref())?;
 
+    let normalized = if transcription.normalize_audio() {
+        normalize_audio(&bytes, filename).await
+    } else {
+        None
+    };
+    let (bytes, wav_name) = match normalized {
+        Some((wav, name)) => (wav, Some(name)),
+        None => (bytes, None),
+    };
+    let filename = wav_name.as_deref().unwrap_or(filename);
+    let mime_type = if wav_name.is_some() {
+        Some("audio/wav")
+    } else {
+        mime_type
+    };
+
     let language = transcription
         .language
         .as_deref()
@@ -280,6 +297,28 @@ pub async fn transcribe_audio_detailed(
     }
 }
 
+/// Normalizes audio with ffmpeg, returning the WAV bytes and a `.wav`
+/// filename, or `None` to send the original (ffmpeg missing or failed).
+async fn normalize_audio(bytes: &[u8], filename: &str) -> Option<(Vec<u8>, String)> {
+    match normalize::normalize_for_transcription(bytes).await {
+        Ok(Some(wav)) => {
+            let stem = std::path::Path::new(filename)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .unwrap_or("audio");
+            Some((wav, format!("{stem}.wav")))
+        }
+        Ok(None) => {
+            tracing::debug!("ffmpeg unavailable; transcribing original audio");
+            None
+        }
+        Err(err) => {
+            tracing::warn!(%err, "Audio normalization failed; transcribing original audio");
+            None
+        }
+    }
+}
+
 /// Resolves the transcription provider and model.
 ///
 /// Priority: `ZDX_TRANSCRIPTION_MODEL` env var > config model > auto-detect.
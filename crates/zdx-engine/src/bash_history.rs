@@ -0,0 +1,220 @@
+//! History for direct bash commands (`$cmd` in the TUI).
+//!
+//! Commands run in bash mode are persisted to `$ZDX_HOME/bash_history`, one
+//! per line and oldest first, so ↑ recalls them across sessions. Optionally,
+//! recent entries from the user's shell history file are imported too; only
+//! commands matching the configured allowlist are kept, since shell history
+//! easily holds one-off commands with inline secrets.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use crate::config::paths;
+
+const HISTORY_FILE: &str = "bash_history";
+
+/// Returns the path to the persisted bash-mode history.
+pub fn history_path() -> PathBuf {
+    paths::zdx_home().join(HISTORY_FILE)
+}
+
+/// Loads up to `limit` persisted commands, oldest first. A missing or
+/// unreadable file yields an empty history.
+pub fn load(limit: usize) -> Vec<String> {
+    load_from(&history_path(), limit)
+}
+
+fn load_from(path: &Path, limit: usize) -> Vec<String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let commands: Vec<String> = contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect();
+    keep_last(dedupe(commands), limit)
+}
+
+/// Appends `command` to the persisted history, dropping earlier copies of it
+/// and keeping the newest `limit` entries. Multi-line commands are skipped.
+///
+/// # Errors
+/// Returns an error if the history file cannot be written.
+pub fn record(command: &str, limit: usize) -> Result<()> {
+    record_to(&history_path(), command, limit)
+}
+
+fn record_to(path: &Path, command: &str, limit: usize) -> Result<()> {
+    let command = command.trim();
+    if limit == 0 || command.is_empty() || command.contains('\n') {
+        return Ok(());
+    }
+    let mut commands = load_from(path, usize::MAX);
+    commands.push(command.to_string());
+    let commands = keep_last(dedupe(commands), limit);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    let mut contents = commands.join("\n");
+    contents.push('\n');
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The shell history file to import from: `$HISTFILE`, else the zsh, bash,
+/// or fish default for `$SHELL`. `None` when no such file exists.
+pub fn default_shell_history_path() -> Option<PathBuf> {
+    if let Some(histfile) = std::env::var_os("HISTFILE").filter(|v| !v.is_empty()) {
+        let path = PathBuf::from(histfile);
+        return path.is_file().then_some(path);
+    }
+    let home = paths::home_dir()?;
+    let shell = std::env::var("SHELL").unwrap_or_default();
+    let candidates = if shell.ends_with("fish") {
+        vec![home.join(".local/share/fish/fish_history")]
+    } else if shell.ends_with("bash") {
+        vec![home.join(".bash_history")]
+    } else {
+        vec![home.join(".zsh_history"), home.join(".bash_history")]
+    };
+    candidates.into_iter().find(|path| path.is_file())
+}
+
+/// Reads the newest `limit` allowlisted commands from the shell history file,
+/// oldest first. Returns nothing when there is no history file or the
+/// allowlist is empty.
+pub fn import_shell_history(allowlist: &[String], limit: usize) -> Vec<String> {
+    if allowlist.is_empty() || limit == 0 {
+        return Vec::new();
+    }
+    let Some(path) = default_shell_history_path() else {
+        return Vec::new();
+    };
+    // zsh history may hold metafied, non-UTF-8 bytes.
+    let Ok(bytes) = fs::read(&path) else {
+        return Vec::new();
+    };
+    let commands = parse_shell_history(&String::from_utf8_lossy(&bytes))
+        .into_iter()
+        .filter(|command| is_allowed(command, allowlist))
+        .collect();
+    keep_last(dedupe(commands), limit)
+}
+
+/// Merges imported shell history (older) with persisted bash-mode history
+/// (newer), keeping the most recent copy of each command.
+pub fn merge(imported: Vec<String>, persisted: Vec<String>) -> Vec<String> {
+    dedupe(imported.into_iter().chain(persisted).collect())
+}
+
+/// Whether `command` is `entry` or starts with `entry ` for some allowlist
+/// entry, so `git` admits `git status` and `git status` admits only that.
+pub fn is_allowed(command: &str, allowlist: &[String]) -> bool {
+    allowlist.iter().map(|entry| entry.trim()).any(|entry| {
+        !entry.is_empty()
+            && (command == entry
+                || command
+                    .strip_prefix(entry)
+                    .is_some_and(|rest| rest.starts_with(' ')))
+    })
+}
+
+/// Parses plain bash, zsh extended (`: <ts>:<dur>;cmd`), and fish
+/// (`- cmd: …`) history. Multi-line entries are dropped.
+fn parse_shell_history(contents: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut in_multiline = false;
+    for line in contents.lines() {
+        // A trailing backslash continues the entry on the next line.
+        let continues = line.ends_with('\\');
+        if std::mem::replace(&mut in_multiline, continues) || continues {
+            continue;
+        }
+        let command = if let Some(rest) = line.strip_prefix(": ") {
+            match rest.split_once(';') {
+                Some((_, command)) => command,
+                None => continue,
+            }
+        } else if let Some(command) = line.strip_prefix("- cmd: ") {
+            command
+        } else if line.starts_with('#') || line.starts_with("  ") {
+            // Bash timestamps and fish metadata (`  when: …`).
+            continue;
+        } else {
+            line
+        };
+        let command = command.trim();
+        if !command.is_empty() {
+            commands.push(command.to_string());
+        }
+    }
+    commands
+}
+
+/// Drops earlier duplicates, keeping each command at its latest position.
+fn dedupe(commands: Vec<String>) -> Vec<String> {
+    let mut seen = std::collections::HashSet::new();
+    let mut kept: Vec<String> = commands
+        .into_iter()
+        .rev()
+        .filter(|command| seen.insert(command.clone()))
+        .collect();
+    kept.reverse();
+    kept
+}
+
+fn keep_last(mut commands: Vec<String>, limit: usize) -> Vec<String> {
+    if commands.len() > limit {
+        commands.drain(..commands.len() - limit);
+    }
+    commands
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_zsh_bash_and_fish_history() {
+        let zsh =
+            ": 1700000000:0;cargo test\n: 1700000001:0;echo a \\\nb\n: 1700000002:0;git status\n";
+        assert_eq!(parse_shell_history(zsh), vec!["cargo test", "git status"]);
+
+        let bash = "#1700000000\nls -la\n\ngit log\n";
+        assert_eq!(parse_shell_history(bash), vec!["ls -la", "git log"]);
+
+        let fish = "- cmd: cargo build\n  when: 1700000000\n- cmd: make\n  when: 1700000001\n";
+        assert_eq!(parse_shell_history(fish), vec!["cargo build", "make"]);
+    }
+
+    #[test]
+    fn allowlist_matches_whole_words() {
+        let allow = vec!["git".to_string(), "cargo test".to_string()];
+        assert!(is_allowed("git", &allow));
+        assert!(is_allowed("git push", &allow));
+        assert!(!is_allowed("gitk", &allow));
+        assert!(is_allowed("cargo test -p zdx", &allow));
+        assert!(!is_allowed("cargo publish", &allow));
+    }
+
+    #[test]
+    fn record_dedupes_and_caps() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(HISTORY_FILE);
+        for command in ["ls", "git status", "ls", "cargo test", "echo a\nb"] {
+            record_to(&path, command, 2).unwrap();
+        }
+        assert_eq!(load_from(&path, 10), vec!["ls", "cargo test"]);
+
+        let merged = merge(
+            vec!["ls".to_string(), "make".to_string()],
+            load_from(&path, 10),
+        );
+        assert_eq!(merged, vec!["make", "ls", "cargo test"]);
+    }
+}
@@ -10,6 +10,8 @@ use std::time::Duration;
 use anyhow::{Context, Result, bail};
 use serde::{Deserialize, Serialize};
 
+use crate::{config_env, config_validation};
+
 /// Skill source toggles grouped by source/type.
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -24,6 +26,10 @@ pub struct SkillSourceToggles {
     pub agents_project: bool,
 }
 
+/// Sampling controls applied to every turn (`[model_params]`).
+pub use zdx_types::ModelParams;
+/// HTTP client settings for one provider (`[providers.<name>.http]`).
+pub use zdx_types::ProviderHttpConfig;
 /// Text verbosity for `OpenAI` Responses-compatible providers.
 pub use zdx_types::TextVerbosity;
 /// Thinking level for extended thinking feature.
@@ -44,6 +50,10 @@ fn default_subagents_enabled() -> bool {
     true
 }
 
+fn default_subagents_cache_results() -> bool {
+    true
+}
+
 /// Prompt template rendering configuration.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[serde(default)]
@@ -54,6 +64,156 @@ pub struct PromptTemplateConfig {
     pub file: Option<String>,
 }
 
+/// How the configured system prompt (`system_prompt` / `system_prompt_file`)
+/// combines with the built-in prompt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SystemPromptMode {
+    /// Layer the configured prompt into the built-in prompt (default).
+    #[default]
+    Append,
+    /// Use the configured prompt instead of the built-in prompt; the other
+    /// enabled sources are still appended in `[prompt_layers].order`.
+    Replace,
+}
+
+/// A source of system prompt content, as named in `[prompt_layers].order`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptSource {
+    /// The built-in prompt template (or `[prompt_template].file`).
+    Builtin,
+    /// `system_prompt` / `system_prompt_file`.
+    GlobalInstructions,
+    /// `AGENTS.md` / `CLAUDE.md` project instructions.
+    ProjectContext,
+    /// The available skills list.
+    Skills,
+    /// The memory index (`Notes/MEMORY.md`).
+    Memory,
+}
+
+impl PromptSource {
+    /// Default layering order.
+    pub const ALL: [PromptSource; 5] = [
+        PromptSource::Builtin,
+        PromptSource::GlobalInstructions,
+        PromptSource::ProjectContext,
+        PromptSource::Skills,
+        PromptSource::Memory,
+    ];
+
+    /// Config name (as written in `order`).
+    pub fn name(self) -> &'static str {
+        match self {
+            PromptSource::Builtin => "builtin",
+            PromptSource::GlobalInstructions => "global_instructions",
+            PromptSource::ProjectContext => "project_context",
+            PromptSource::Skills => "skills",
+            PromptSource::Memory => "memory",
+        }
+    }
+}
+
+/// System prompt layering controls (`[prompt_layers]`).
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptLayersConfig {
+    /// Include `system_prompt` / `system_prompt_file`.
+    pub global_instructions: bool,
+    /// Include `AGENTS.md` / `CLAUDE.md` project instructions.
+    pub project_context: bool,
+    /// Include the available skills list.
+    pub skills: bool,
+    /// Include the memory index.
+    pub memory: bool,
+    /// Custom section order. Empty keeps the built-in template's layout;
+    /// sources left out are appended in the default order.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub order: Vec<PromptSource>,
+}
+
+impl Default for PromptLayersConfig {
+    fn default() -> Self {
+        Self {
+            global_instructions: true,
+            project_context: true,
+            skills: true,
+            memory: true,
+            order: Vec::new(),
+        }
+    }
+}
+
+impl PromptLayersConfig {
+    /// Whether `source` is enabled (the built-in prompt always is; `Replace`
+    /// mode drops it separately).
+    pub fn enabled(&self, source: PromptSource) -> bool {
+        match source {
+            PromptSource::Builtin => true,
+            PromptSource::GlobalInstructions => self.global_instructions,
+            PromptSource::ProjectContext => self.project_context,
+            PromptSource::Skills => self.skills,
+            PromptSource::Memory => self.memory,
+        }
+    }
+
+    /// Sources in layering order: `order` first (deduplicated), then any
+    /// sources it leaves out, in the default order.
+    pub fn resolved_order(&self) -> Vec<PromptSource> {
+        let mut order: Vec<PromptSource> = Vec::new();
+        for source in self.order.iter().chain(PromptSource::ALL.iter()) {
+            if !order.contains(source) {
+                order.push(*source);
+            }
+        }
+        order
+    }
+}
+
+/// A model-specific system prompt variant (`[[prompt_variants]]`).
+///
+/// The first variant whose `models` patterns match the resolved model is
+/// applied when the system prompt is built for that model.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PromptVariantConfig {
+    /// Variant name, recorded in the thread meta.
+    pub name: String,
+    /// Model patterns (`*` wildcards). Patterns with a `provider:` prefix
+    /// match the resolved `provider:model` id; bare patterns match the model id.
+    pub models: Vec<String>,
+    /// Prompt template used instead of `[prompt_template].file`.
+    pub template_file: Option<String>,
+    /// Inline system prompt used instead of `system_prompt`.
+    pub system_prompt: Option<String>,
+    /// System prompt file used instead of `system_prompt_file`.
+    pub system_prompt_file: Option<String>,
+    /// Extra guidance appended as an instruction layer (e.g. tool-usage hints).
+    pub instructions: Option<String>,
+}
+
+impl PromptVariantConfig {
+    /// True if any of this variant's patterns match `model`.
+    pub fn matches_model(&self, model: &str) -> bool {
+        let resolved = crate::providers::resolve_provider(model);
+        let qualified = format!("{}:{}", resolved.kind.id(), resolved.model);
+        self.models
+            .iter()
+            .map(|pattern| pattern.trim())
+            .filter(|pattern| !pattern.is_empty())
+            .any(|pattern| {
+                crate::models::wildcard_match(pattern, model)
+                    || if pattern.contains(':') {
+                        crate::models::wildcard_match(pattern, &qualified)
+                    } else {
+                        crate::models::wildcard_match(pattern, &resolved.model)
+                    }
+            })
+    }
+}
+
 /// Skill discovery configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
@@ -102,6 +262,10 @@ pub struct SubagentsConfig {
     /// registry (same source used by the TUI model picker).
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub available_models: Vec<String>,
+    /// Reuses the result of an identical `invoke_subagent` call (same
+    /// subagent, model, thinking level, and prompt) within a session.
+    #[serde(default = "default_subagents_cache_results")]
+    pub cache_results: bool,
 }
 
 impl Default for SubagentsConfig {
@@ -109,6 +273,7 @@ impl Default for SubagentsConfig {
         Self {
             enabled: default_subagents_enabled(),
             available_models: Vec::new(),
+            cache_results: default_subagents_cache_results(),
         }
     }
 }
@@ -132,6 +297,28 @@ pub struct TelegramConfig {
     /// Per-chat project profiles keyed by profile name.
     #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
     pub profiles: BTreeMap<String, TelegramProfileConfig>,
+    /// Maximum agent turns running at once across all chats (0 = unlimited).
+    /// Waiting turns are served round-robin by chat.
+    pub max_concurrent_turns: usize,
+    /// User IDs whose turns skip ahead of the round-robin when throttled.
+    /// Only these users may run `/reload`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub priority_user_ids: Vec<i64>,
+    /// Edit the turn's status message as tools run (tool, detail, elapsed).
+    pub progress_updates: bool,
+    /// Output token cap for inline query answers (0 disables inline queries).
+    pub inline_max_tokens: u32,
+    /// Max messages a long reply is split into; longer replies are sent as a
+    /// `.md` document instead (0 = always a document when over the limit).
+    pub max_reply_messages: usize,
+    /// Seconds an `Ask_User` question waits for the user's next message
+    /// before the agent continues without an answer.
+    pub ask_user_timeout_secs: u64,
+    /// Days saved attachments are kept before media GC deletes them (0 = no age limit).
+    pub media_retention_days: u64,
+    /// Cap on saved attachment storage in MB; media GC deletes the oldest
+    /// files beyond it (0 = no size limit).
+    pub media_max_mb: u64,
 }
 
 /// Per-chat Telegram project profile.
@@ -152,6 +339,14 @@ impl Default for TelegramConfig {
             model: "claude-cli:claude-opus-4-6".to_string(),
             thinking_level: ThinkingLevel::Low,
             profiles: BTreeMap::new(),
+            max_concurrent_turns: 0,
+            priority_user_ids: Vec::new(),
+            progress_updates: true,
+            inline_max_tokens: 512,
+            max_reply_messages: 3,
+            ask_user_timeout_secs: 600,
+            media_retention_days: 30,
+            media_max_mb: 1024,
         }
     }
 }
@@ -197,7 +392,8 @@ where
     let value = deserialize_optional_non_empty_string(deserializer)?;
     if let Some(value) = &value {
         let path = Path::new(value);
-        let uses_tilde = value == "~" || value.starts_with("~/");
+        let uses_tilde =
+            value == "~" || value.starts_with("~/") || (cfg!(windows) && value.starts_with("~\\"));
         if !uses_tilde && !path.is_absolute() {
             return Err(serde::de::Error::custom(
                 "value must be an absolute path or use ~/",
@@ -223,7 +419,7 @@ where
 ///
 /// This is embedded from `default_config.toml` at compile time.
 /// To update, edit `default_config.toml` directly.
-fn default_config_template() -> &'static str {
+pub(crate) fn default_config_template() -> &'static str {
     zdx_assets::DEFAULT_CONFIG_TOML
 }
 
@@ -363,10 +559,27 @@ pub mod paths {
         exports_dir().join("threads")
     }
 
+    /// Returns the audit log for tool calls in unsaved sessions
+    /// (`$ZDX_HOME/audit/unsaved.jsonl`).
+    pub fn audit_log_path() -> PathBuf {
+        zdx_home().join("audit").join("unsaved.jsonl")
+    }
+
+    /// Returns the local command-usage log read by `zdx stats --personal`
+    /// (`$ZDX_HOME/stats/events.jsonl`).
+    pub fn stats_events_path() -> PathBuf {
+        zdx_home().join("stats").join("events.jsonl")
+    }
+
     /// Returns the artifact root directory (`$ZDX_HOME/artifacts`).
     pub fn artifact_root() -> PathBuf {
         zdx_home().join("artifacts")
     }
+
+    /// Returns the WASM tool plugins directory (`$ZDX_HOME/plugins`).
+    pub fn plugins_dir() -> PathBuf {
+        zdx_home().join("plugins")
+    }
 }
 
 /// Default value for serde when `handoff_model` is missing.
@@ -389,6 +602,11 @@ fn default_tldr_model() -> String {
     Config::DEFAULT_TLDR_MODEL.to_string()
 }
 
+/// Default value for serde when `improve_model` is missing.
+fn default_improve_model() -> String {
+    Config::DEFAULT_IMPROVE_MODEL.to_string()
+}
+
 /// Default value for serde when `prompt_builder_model` is missing.
 fn default_prompt_builder_model() -> String {
     Config::DEFAULT_PROMPT_BUILDER_MODEL.to_string()
@@ -404,6 +622,16 @@ pub struct TranscriptionConfig {
     pub model: Option<String>,
     /// Language hint (ISO 639-1 code like "en", "pt", etc.)
     pub language: Option<String>,
+    /// Convert audio to 16 kHz mono WAV with loudness normalization (via
+    /// ffmpeg, when installed) before transcribing. Defaults to `true`.
+    pub normalize: Option<bool>,
+}
+
+impl TranscriptionConfig {
+    /// Whether audio is normalized with ffmpeg before transcription.
+    pub fn normalize_audio(&self) -> bool {
+        self.normalize.unwrap_or(true)
+    }
 }
 
 /// Text-to-speech (speech synthesis) configuration.
@@ -445,6 +673,498 @@ impl Default for NotificationsConfig {
     }
 }
 
+/// Bash tool configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BashConfig {
+    /// Run bash tool calls in one long-lived shell per thread so `cd`,
+    /// sourced environments, and exported variables persist between calls.
+    /// Threads without a persisted id (e.g. `--no-save`) and Windows always
+    /// use a fresh shell.
+    pub persistent: bool,
+    /// CPU seconds per process of a command (0 disables). Unix only.
+    pub cpu_time_secs: u64,
+    /// Virtual memory per process of a command, in MiB (0 disables). Unix only.
+    pub max_memory_mb: u64,
+    /// Process count for the user while a command runs (0 disables); stops
+    /// fork bombs. Unix only, not enforced for root.
+    pub max_processes: u64,
+    /// Output (stdout + stderr) captured per command before it is killed
+    /// (0 disables).
+    pub max_output_bytes: usize,
+}
+
+impl Default for BashConfig {
+    fn default() -> Self {
+        Self {
+            persistent: false,
+            cpu_time_secs: 0,
+            max_memory_mb: 0,
+            max_processes: 0,
+            max_output_bytes: zdx_tools::bash_limits::DEFAULT_MAX_CAPTURED_BYTES,
+        }
+    }
+}
+
+impl BashConfig {
+    /// Resource limits for bash tool commands.
+    pub fn limits(&self) -> zdx_tools::bash_limits::BashLimits {
+        zdx_tools::bash_limits::BashLimits {
+            cpu_time_secs: self.cpu_time_secs,
+            max_memory_mb: self.max_memory_mb,
+            max_processes: self.max_processes,
+            max_output_bytes: self.max_output_bytes,
+        }
+    }
+}
+
+/// Read tool configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ReadConfig {
+    /// Source files longer than this many lines return their outline instead
+    /// of content when read without `offset`/`limit` (0 disables).
+    pub outline_threshold_lines: usize,
+}
+
+impl Default for ReadConfig {
+    fn default() -> Self {
+        Self {
+            outline_threshold_lines: zdx_tools::read::DEFAULT_OUTLINE_THRESHOLD_LINES,
+        }
+    }
+}
+
+/// Language for TUI chrome (status line, overlay titles, system messages).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TuiLanguage {
+    #[default]
+    #[serde(rename = "en")]
+    English,
+    #[serde(rename = "pt-BR")]
+    BrazilianPortuguese,
+}
+
+/// Interactive TUI configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct TuiConfig {
+    /// UI language for TUI chrome. Model replies are unaffected.
+    pub language: TuiLanguage,
+    /// Screen-reader friendly mode: no spinner animations, plain-text status
+    /// announcements in the transcript, and no mouse capture. Also enabled
+    /// by `zdx --a11y`.
+    pub a11y: bool,
+    /// Render into the normal terminal buffer instead of the alternate
+    /// screen, so finished transcript cells stay in scrollback after exit.
+    /// Also enabled by `zdx --inline`.
+    pub inline: bool,
+    /// Include recent message text and the input draft in crash reports
+    /// written to `$ZDX_HOME/crash/`. Off by default.
+    pub crash_report_content: bool,
+    /// Seconds without input or agent activity before the active thread
+    /// gets a short summary (via `title_model`) for the thread picker. A
+    /// thread is summarized again only after new messages. 0 disables.
+    pub idle_summary_secs: u64,
+    /// Direct bash (`$cmd`) commands kept in `$ZDX_HOME/bash_history` for ↑
+    /// recall across sessions. 0 disables persistence.
+    pub bash_history_size: usize,
+    /// Also recall recent commands from the shell history file (`$HISTFILE`
+    /// or the shell's default), filtered by `shell_history_allowlist`.
+    pub import_shell_history: bool,
+    /// Command prefixes (whole words) imported from shell history.
+    pub shell_history_allowlist: Vec<String>,
+    /// Rows of output shown under `$cmd` cells; longer output scrolls inside
+    /// the cell. 0 keeps the cells compact.
+    pub bash_output_lines: usize,
+    /// Also show output under the agent's bash tool calls.
+    pub bash_tool_output_inline: bool,
+    /// Rounds in a `/duel` discussion; each round is one turn per model.
+    pub duel_max_rounds: usize,
+}
+
+impl Default for TuiConfig {
+    fn default() -> Self {
+        Self {
+            language: TuiLanguage::default(),
+            a11y: false,
+            inline: false,
+            crash_report_content: false,
+            idle_summary_secs: 300,
+            bash_history_size: 500,
+            import_shell_history: false,
+            shell_history_allowlist: [
+                "cargo", "git", "just", "make", "npm", "pnpm", "yarn", "bun", "go", "pytest",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+            bash_output_lines: 12,
+            bash_tool_output_inline: false,
+            duel_max_rounds: 4,
+        }
+    }
+}
+
+/// Agent loop safeguards. A value of 0 disables the corresponding check.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct AgentLoopConfig {
+    /// Maximum tool iterations (model → tools → model rounds) in one turn.
+    pub max_tool_iterations: u32,
+    /// Stop when the model repeats the exact same tool calls this many times
+    /// in a row.
+    pub max_repeated_tool_calls: u32,
+}
+
+impl Default for AgentLoopConfig {
+    fn default() -> Self {
+        Self {
+            max_tool_iterations: 200,
+            max_repeated_tool_calls: 5,
+        }
+    }
+}
+
+/// Thinking-level auto-escalation policy (opt-in).
+///
+/// Turns start at `start_level` and step up one level at a time, up to
+/// `max_level`, when a configured trigger fires.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ThinkingEscalationConfig {
+    /// Enables the policy. When off, turns use the selected thinking level.
+    pub enabled: bool,
+    /// Level each turn starts at.
+    pub start_level: ThinkingLevel,
+    /// Highest level the policy escalates to.
+    pub max_level: ThinkingLevel,
+    /// Conditions that escalate the level.
+    pub triggers: Vec<zdx_types::EscalationTrigger>,
+    /// Consecutive tool rounds with a failing tool call before escalating.
+    pub tool_failure_threshold: u32,
+    /// Case-insensitive phrases in a final answer that signal uncertainty.
+    pub uncertainty_phrases: Vec<String>,
+}
+
+impl Default for ThinkingEscalationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start_level: ThinkingLevel::Low,
+            max_level: ThinkingLevel::High,
+            triggers: vec![
+                zdx_types::EscalationTrigger::Uncertainty,
+                zdx_types::EscalationTrigger::ToolFailures,
+            ],
+            tool_failure_threshold: 2,
+            uncertainty_phrases: [
+                "i'm not sure",
+                "i am not sure",
+                "i'm not certain",
+                "i'm unsure",
+                "i don't know",
+                "i cannot determine",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        }
+    }
+}
+
+/// Secret-file protection (`[security]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SecurityConfig {
+    /// Files `Read` refuses without explicit per-file approval. Patterns
+    /// without `/` match the file name; others match the root-relative path.
+    /// A leading `!` exempts matching files. Empty disables the check.
+    pub protected_globs: Vec<String>,
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        Self {
+            protected_globs: [
+                ".env",
+                ".env.*",
+                "!.env.example",
+                "!.env.sample",
+                "!.env.template",
+                "*.pem",
+                "*.key",
+                "*.p12",
+                "*.pfx",
+                "id_rsa",
+                "id_dsa",
+                "id_ecdsa",
+                "id_ed25519",
+                ".netrc",
+            ]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+        }
+    }
+}
+
+/// Opt-in cache of final `zdx exec` responses for identical invocations.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ResponseCacheConfig {
+    /// Reuse cached final answers for identical exec runs.
+    pub enabled: bool,
+    /// How long a cached response stays valid, in seconds.
+    pub ttl_secs: u64,
+}
+
+impl Default for ResponseCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: 86_400,
+        }
+    }
+}
+
+/// Tool configuration shared by all surfaces.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ToolsConfig {
+    /// User-defined tools backed by external commands (`[[tools.custom]]`).
+    pub custom: Vec<CustomToolConfig>,
+    /// Project check command exposed as the `Check` tool (`[tools.check]`).
+    pub check: Option<CheckToolConfig>,
+    /// Project test command exposed as the `Run_Tests` tool (`[tools.run_tests]`).
+    pub run_tests: Option<RunTestsToolConfig>,
+    /// Prompt-injection guard for web tool results (`[tools.web_guard]`).
+    pub web_guard: WebGuardConfig,
+}
+
+/// Guard applied to `Web_Search` / `Fetch_Webpage` results after they are
+/// wrapped as untrusted content.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct WebGuardConfig {
+    /// Block results with instruction-like `injection_flags` instead of
+    /// returning them to the model.
+    pub block_flagged: bool,
+    /// Optional classifier command (`sh -c` in the project root). It gets
+    /// `{tool, injection_flags, results}` as JSON on stdin; a non-zero exit
+    /// blocks the result, with its output as the reason.
+    pub classifier: Option<String>,
+    /// Kill the classifier after this many seconds (0 disables); a timeout
+    /// blocks the result.
+    pub timeout_secs: u64,
+}
+
+impl Default for WebGuardConfig {
+    fn default() -> Self {
+        Self {
+            block_flagged: false,
+            classifier: None,
+            timeout_secs: 10,
+        }
+    }
+}
+
+/// A user-defined tool: the command runs with the tool input (JSON) on stdin
+/// and its stdout becomes the tool result.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CustomToolConfig {
+    /// Tool name exposed to the model (letters, digits, `_`, `-`).
+    pub name: String,
+    /// Description shown to the model.
+    pub description: String,
+    /// JSON schema for the tool input, as a TOML table or a JSON string.
+    #[serde(default = "default_custom_tool_schema")]
+    pub schema: serde_json::Value,
+    /// Shell command to run (`sh -c` on Unix) in the project root.
+    pub command: String,
+    /// Kill the command after this many seconds (0 disables).
+    #[serde(default = "default_custom_tool_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Truncate stdout/stderr beyond this many bytes.
+    #[serde(default = "default_custom_tool_max_output_bytes")]
+    pub max_output_bytes: usize,
+}
+
+fn default_custom_tool_schema() -> serde_json::Value {
+    serde_json::json!({ "type": "object", "properties": {} })
+}
+
+fn default_custom_tool_timeout_secs() -> u64 {
+    60
+}
+
+fn default_custom_tool_max_output_bytes() -> usize {
+    40 * 1024
+}
+
+/// The `Check` tool: a build/lint/typecheck command whose diagnostics are
+/// parsed and returned to the model.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckToolConfig {
+    /// Shell command to run in the project root
+    /// (e.g. `cargo check --message-format=json`).
+    pub command: String,
+    /// Kill the command after this many seconds (0 disables).
+    #[serde(default = "default_check_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Return at most this many diagnostics (errors first).
+    #[serde(default = "default_check_max_diagnostics")]
+    pub max_diagnostics: usize,
+    /// Run the check after every tool round that edits files and feed
+    /// failures back to the model before its next request.
+    #[serde(default)]
+    pub auto: bool,
+}
+
+fn default_check_timeout_secs() -> u64 {
+    300
+}
+
+fn default_check_max_diagnostics() -> usize {
+    30
+}
+
+/// The `Run_Tests` tool: a test command whose results are parsed into
+/// pass/fail counts and failing test details.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RunTestsToolConfig {
+    /// Shell command to run in the project root (e.g. `cargo test`).
+    pub command: String,
+    /// Output format to parse; `auto` detects it from the output.
+    #[serde(default)]
+    pub format: TestOutputFormat,
+    /// Kill the command after this many seconds (0 disables).
+    #[serde(default = "default_run_tests_timeout_secs")]
+    pub timeout_secs: u64,
+    /// Return details for at most this many failing tests.
+    #[serde(default = "default_run_tests_max_failures")]
+    pub max_failures: usize,
+}
+
+/// Test runner output formats understood by `Run_Tests`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TestOutputFormat {
+    #[default]
+    Auto,
+    Cargo,
+    Pytest,
+    Jest,
+}
+
+fn default_run_tests_timeout_secs() -> u64 {
+    600
+}
+
+fn default_run_tests_max_failures() -> usize {
+    10
+}
+
+/// WASM tool plugins loaded from `<ZDX_HOME>/plugins/*.wasm`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PluginsConfig {
+    /// Load plugins at startup.
+    pub enabled: bool,
+    /// Interrupt a plugin call after this many seconds (0 disables).
+    pub timeout_secs: u64,
+    /// Maximum linear memory per plugin instance, in MiB.
+    pub max_memory_mb: usize,
+    /// Fail a plugin call whose stdout/stderr exceeds this many bytes.
+    pub max_output_bytes: usize,
+    /// Let plugins write to the project root (read-only by default).
+    pub allow_write: bool,
+}
+
+impl Default for PluginsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            timeout_secs: 30,
+            max_memory_mb: 64,
+            max_output_bytes: 40 * 1024,
+            allow_write: false,
+        }
+    }
+}
+
+/// Language servers backing the `Lsp_*` code intelligence tools.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LspConfig {
+    /// Register the `Lsp_*` tools. Servers start on first use.
+    pub enabled: bool,
+    /// Fail a language server request after this many seconds.
+    pub timeout_secs: u64,
+    /// How long `Lsp_Diagnostics` waits for fresh diagnostics, in seconds.
+    pub diagnostics_wait_secs: u64,
+    /// Servers by file extension; replaces the built-in list when set.
+    pub servers: Vec<LspServerConfig>,
+}
+
+impl Default for LspConfig {
+    fn default() -> Self {
+        let server =
+            |name: &str, command: &str, args: &[&str], extensions: &[&str]| LspServerConfig {
+                name: name.to_string(),
+                command: command.to_string(),
+                args: args.iter().map(ToString::to_string).collect(),
+                extensions: extensions.iter().map(ToString::to_string).collect(),
+            };
+        Self {
+            enabled: false,
+            timeout_secs: 30,
+            diagnostics_wait_secs: 5,
+            servers: vec![
+                server("rust", "rust-analyzer", &[], &["rs"]),
+                server("python", "pyright-langserver", &["--stdio"], &["py"]),
+                server(
+                    "typescript",
+                    "typescript-language-server",
+                    &["--stdio"],
+                    &["ts", "tsx", "js", "jsx", "mjs", "cjs"],
+                ),
+            ],
+        }
+    }
+}
+
+/// One language server: a stdio command and the file extensions it serves.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LspServerConfig {
+    /// Display name (e.g. `rust`).
+    pub name: String,
+    /// Executable to launch (resolved via `PATH`).
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// File extensions without the dot (e.g. `rs`).
+    pub extensions: Vec<String>,
+}
+
+impl LspConfig {
+    /// The server configured for `path`'s extension, if any.
+    pub fn server_for(&self, path: &Path) -> Option<&LspServerConfig> {
+        let extension = path.extension()?.to_str()?;
+        self.servers.iter().find(|server| {
+            server
+                .extensions
+                .iter()
+                .any(|candidate| candidate.eq_ignore_ascii_case(extension))
+        })
+    }
+}
+
 /// qmd search backend configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default, deny_unknown_fields)]
@@ -515,8 +1235,13 @@ impl MemoryConfig {
 }
 
 /// Expands `~` at the start of a path to the user's home directory.
+///
+/// Accepts `~\` as well on Windows.
 fn expand_tilde(path: &str) -> std::path::PathBuf {
-    if let Some(rest) = path.strip_prefix("~/") {
+    if let Some(rest) = path
+        .strip_prefix("~/")
+        .or_else(|| path.strip_prefix("~\\").filter(|_| cfg!(windows)))
+    {
         if let Some(home) = paths::home_dir() {
             return home.join(rest);
         }
@@ -575,15 +1300,26 @@ pub struct Config {
     /// Maximum tokens for responses (optional)
     pub max_tokens: Option<u32>,
 
+    /// Sampling controls (temperature, `top_p`, output cap); unset fields
+    /// keep provider defaults.
+    pub model_params: ModelParams,
+
     /// Optional inline system prompt
     pub system_prompt: Option<String>,
 
     /// Optional path to a file containing the system prompt
     pub system_prompt_file: Option<String>,
 
+    /// How the configured system prompt combines with the built-in prompt
+    pub system_prompt_mode: SystemPromptMode,
+
     /// Timeout for tool execution in seconds (0 disables)
     pub tool_timeout_secs: u32,
 
+    /// Abort a provider stream after this many seconds without data
+    /// (0 disables)
+    pub stream_idle_timeout_secs: u32,
+
     /// Provider configuration (base URLs, etc.).
     #[serde(default)]
     pub providers: ProvidersConfig,
@@ -608,10 +1344,19 @@ pub struct Config {
     #[serde(default = "default_prompt_builder_model")]
     pub prompt_builder_model: String,
 
+    /// Model to use for the `/improve` prompt rewrite subagent.
+    #[serde(default = "default_improve_model")]
+    pub improve_model: String,
+
     /// Thinking level for extended thinking feature
     #[serde(default)]
     pub thinking_level: ThinkingLevel,
 
+    /// Offline mode: drop network tools, block network-only features, and
+    /// only allow local model providers (`zdx --offline` / `ZDX_OFFLINE=1`).
+    #[serde(default)]
+    pub offline: bool,
+
     /// Favorite model presets cycled with Tab in the TUI.
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub favorites: Vec<ModelFavorite>,
@@ -628,6 +1373,18 @@ pub struct Config {
     #[serde(default)]
     pub prompt_template: PromptTemplateConfig,
 
+    /// System prompt layering controls (per-source flags and order)
+    #[serde(default)]
+    pub prompt_layers: PromptLayersConfig,
+
+    /// Model-specific system prompt variants (first match wins)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub prompt_variants: Vec<PromptVariantConfig>,
+
+    /// Custom response style presets for `/style` (name → directive)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub styles: BTreeMap<String, String>,
+
     /// Memory system configuration (root directory with derived notes/calendar/index paths)
     #[serde(default)]
     pub memory: MemoryConfig,
@@ -640,6 +1397,42 @@ pub struct Config {
     #[serde(default)]
     pub speech: SpeechConfig,
 
+    /// Bash tool configuration.
+    #[serde(default)]
+    pub bash: BashConfig,
+
+    /// Read tool configuration.
+    #[serde(default)]
+    pub read: ReadConfig,
+
+    /// Agent loop safeguards (tool iteration limit, repeated-call detection).
+    #[serde(default)]
+    pub agent_loop: AgentLoopConfig,
+
+    /// Thinking-level auto-escalation policy.
+    #[serde(default)]
+    pub thinking_escalation: ThinkingEscalationConfig,
+
+    /// Tool configuration (user-defined custom tools).
+    #[serde(default)]
+    pub tools: ToolsConfig,
+
+    /// WASM tool plugin limits.
+    #[serde(default)]
+    pub plugins: PluginsConfig,
+
+    /// Language server integration for the `Lsp_*` tools (opt-in).
+    #[serde(default)]
+    pub lsp: LspConfig,
+
+    /// Exec response cache (opt-in).
+    #[serde(default)]
+    pub response_cache: ResponseCacheConfig,
+
+    /// Secret-file protection for `Read`.
+    #[serde(default)]
+    pub security: SecurityConfig,
+
     /// qmd search backend configuration.
     #[serde(default)]
     pub qmd: QmdConfig,
@@ -648,6 +1441,10 @@ pub struct Config {
     #[serde(default)]
     pub notifications: NotificationsConfig,
 
+    /// Interactive TUI configuration.
+    #[serde(default)]
+    pub tui: TuiConfig,
+
     /// Telegram bot configuration
     #[serde(default)]
     pub telegram: TelegramConfig,
@@ -658,18 +1455,43 @@ impl Config {
     const DEFAULT_MAX_TOKENS: u32 = 12288;
     /// Default is disabled
     const DEFAULT_TOOL_TIMEOUT_SECS: u32 = 0;
+    const DEFAULT_STREAM_IDLE_TIMEOUT_SECS: u32 = 120;
     const DEFAULT_HANDOFF_MODEL: &str = "gemini:gemini-3-flash-preview";
     const DEFAULT_TITLE_MODEL: &str = "gemini:gemini-3.1-flash-lite-preview";
     const DEFAULT_READ_THREAD_MODEL: &str = "gemini:gemini-3.1-flash-lite-preview";
     const DEFAULT_TLDR_MODEL: &str = "gemini:gemini-3.1-flash-lite-preview";
     const DEFAULT_PROMPT_BUILDER_MODEL: &str = "openai:gpt-5.6-terra@low";
+    const DEFAULT_IMPROVE_MODEL: &str = "gemini:gemini-3.1-flash-lite-preview";
 
     /// Loads configuration from the default config path.
     ///
+    /// `ZDX_*` environment overrides (see [`crate::config_env`]) are applied
+    /// on top of the file.
+    ///
     /// # Errors
     /// Returns an error if the operation fails.
     pub fn load() -> Result<Self> {
-        Self::load_from(&paths::config_path())
+        let path = paths::config_path();
+        let overrides = config_env::collect_overrides(std::env::vars());
+        if overrides.is_empty() {
+            return Self::load_from(&path);
+        }
+
+        let mut table: toml::Table = match Self::read_config_text(&path)? {
+            Some(contents) => toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse config from {}", path.display()))?,
+            None => toml::Table::new(),
+        };
+        config_env::apply_overrides(&mut table, &overrides)?;
+        let vars: Vec<&str> = overrides.iter().map(|entry| entry.var.as_str()).collect();
+        tracing::debug!("Applied config env overrides: {}", vars.join(", "));
+        Config::deserialize(table).with_context(|| {
+            format!(
+                "Failed to apply environment overrides ({}) to config from {}",
+                vars.join(", "),
+                path.display()
+            )
+        })
     }
 
     /// Alias of the favorite matching the active model + thinking, if any.
@@ -882,16 +1704,34 @@ impl Config {
     /// # Errors
     /// Returns an error if the operation fails.
     pub fn load_from(path: &Path) -> Result<Self> {
-        if path.exists() {
-            let contents = fs::read_to_string(path)
-                .with_context(|| format!("Failed to read config from {}", path.display()))?;
-            toml::from_str(&contents)
-                .with_context(|| format!("Failed to parse config from {}", path.display()))
-        } else {
-            Ok(Config::default())
+        match Self::read_config_text(path)? {
+            Some(contents) => toml::from_str(&contents)
+                .with_context(|| format!("Failed to parse config from {}", path.display())),
+            None => Ok(Config::default()),
         }
     }
 
+    /// Reads the config file with deprecated keys mapped to their new names.
+    /// Returns `None` if the file doesn't exist.
+    fn read_config_text(path: &Path) -> Result<Option<String>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+        let contents = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config from {}", path.display()))?;
+        let (contents, renamed) = config_validation::migrate_deprecated_keys(&contents)
+            .with_context(|| format!("Failed to parse config from {}", path.display()))?;
+        for key in renamed {
+            tracing::warn!(
+                "Config key `{}` is deprecated; rename it to `{}` in {}",
+                key.old,
+                key.new,
+                path.display()
+            );
+        }
+        Ok(Some(contents))
+    }
+
     /// Saves only the model field to the config file.
     ///
     /// Creates the file if it doesn't exist.
@@ -922,6 +1762,7 @@ impl Config {
         "handoff_model",
         "read_thread_model",
         "prompt_builder_model",
+        "improve_model",
         "transcription.model",
         "speech.model",
     ];
@@ -1145,6 +1986,33 @@ impl Config {
         Ok((!trimmed.is_empty()).then(|| trimmed.to_string()))
     }
 
+    /// Returns the first `[[prompt_variants]]` entry matching `model`.
+    pub fn prompt_variant_for(&self, model: &str) -> Option<&PromptVariantConfig> {
+        self.prompt_variants
+            .iter()
+            .find(|variant| variant.matches_model(model))
+    }
+
+    /// Returns a copy of this config with `variant`'s prompt overrides applied.
+    /// A variant prompt (inline or file) replaces both configured prompts.
+    #[must_use]
+    pub fn with_prompt_variant(&self, variant: &PromptVariantConfig) -> Config {
+        let mut config = self.clone();
+        if variant.system_prompt.is_some() || variant.system_prompt_file.is_some() {
+            config.system_prompt.clone_from(&variant.system_prompt);
+            config
+                .system_prompt_file
+                .clone_from(&variant.system_prompt_file);
+        }
+        if variant.template_file.is_some() {
+            config
+                .prompt_template
+                .file
+                .clone_from(&variant.template_file);
+        }
+        config
+    }
+
     pub fn tool_timeout(&self) -> Option<Duration> {
         if self.tool_timeout_secs == 0 {
             None
@@ -1153,6 +2021,16 @@ impl Config {
         }
     }
 
+    pub fn stream_idle_timeout(&self) -> Option<Duration> {
+        if self.stream_idle_timeout_secs == 0 {
+            None
+        } else {
+            Some(Duration::from_secs(u64::from(
+                self.stream_idle_timeout_secs,
+            )))
+        }
+    }
+
     /// Returns the path to the models file.
     /// Defaults to `<base>/models.toml`.
     pub fn models_path(&self) -> std::path::PathBuf {
@@ -1204,16 +2082,23 @@ impl Config {
             .collect()
     }
 
+    /// Configured output cap: `model_params.max_output_tokens` wins over
+    /// top-level `max_tokens`.
+    #[must_use]
+    pub fn max_output_tokens(&self) -> Option<u32> {
+        self.model_params.max_output_tokens.or(self.max_tokens)
+    }
+
     /// Returns the effective `max_tokens` fallback for a model.
     ///
     /// Resolution order:
-    /// 1) Explicit config `max_tokens` (if set)
+    /// 1) Explicit `model_params.max_output_tokens` or `max_tokens` (if set)
     /// 2) Model output limit from the registry (exclusive, minus 1)
     /// 3) Fallback default
     ///
     /// Callers may still omit max tokens for providers that support provider-side defaults.
     pub fn effective_max_tokens_for(&self, model_id: &str) -> u32 {
-        let configured = self.max_tokens;
+        let configured = self.max_output_tokens();
         let model = crate::models::ModelOption::find_by_id(model_id);
         let output_limit = model
             .map(|model| model.capabilities.output_limit)
@@ -1300,7 +2185,7 @@ impl Config {
 
     /// Writes config content to a file, creating parent directories as needed.
     /// Uses atomic write (temp file + rename) to prevent corruption.
-    fn write_config(path: &Path, content: &str) -> Result<()> {
+    pub(crate) fn write_config(path: &Path, content: &str) -> Result<()> {
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)
                 .with_context(|| format!("Failed to create directory {}", parent.display()))?;
@@ -1326,25 +2211,43 @@ impl Default for Config {
         Self {
             model: Self::DEFAULT_MODEL.to_string(),
             max_tokens: None,
+            model_params: ModelParams::default(),
             system_prompt: None,
             system_prompt_file: None,
+            system_prompt_mode: SystemPromptMode::default(),
             tool_timeout_secs: Self::DEFAULT_TOOL_TIMEOUT_SECS,
+            stream_idle_timeout_secs: Self::DEFAULT_STREAM_IDLE_TIMEOUT_SECS,
             providers: ProvidersConfig::default(),
             handoff_model: Self::DEFAULT_HANDOFF_MODEL.to_string(),
             title_model: Self::DEFAULT_TITLE_MODEL.to_string(),
             read_thread_model: Self::DEFAULT_READ_THREAD_MODEL.to_string(),
             tldr_model: Self::DEFAULT_TLDR_MODEL.to_string(),
             prompt_builder_model: Self::DEFAULT_PROMPT_BUILDER_MODEL.to_string(),
+            improve_model: Self::DEFAULT_IMPROVE_MODEL.to_string(),
             thinking_level: ThinkingLevel::default(),
+            offline: false,
             favorites: Vec::new(),
             skills: SkillsConfig::default(),
             subagents: SubagentsConfig::default(),
             prompt_template: PromptTemplateConfig::default(),
+            prompt_layers: PromptLayersConfig::default(),
+            prompt_variants: Vec::new(),
+            styles: BTreeMap::new(),
             memory: MemoryConfig::default(),
             transcription: TranscriptionConfig::default(),
             speech: SpeechConfig::default(),
+            bash: BashConfig::default(),
+            read: ReadConfig::default(),
+            agent_loop: AgentLoopConfig::default(),
+            thinking_escalation: ThinkingEscalationConfig::default(),
+            tools: ToolsConfig::default(),
+            plugins: PluginsConfig::default(),
+            lsp: LspConfig::default(),
+            response_cache: ResponseCacheConfig::default(),
+            security: SecurityConfig::default(),
             qmd: QmdConfig::default(),
             notifications: NotificationsConfig::default(),
+            tui: TuiConfig::default(),
             telegram: TelegramConfig::default(),
         }
     }
@@ -1396,12 +2299,18 @@ pub struct ProvidersConfig {
     pub meta: ProviderConfig,
     #[serde(default = "default_elevenlabs_provider")]
     pub elevenlabs: ProviderConfig,
-    /// User-defined OpenAI-compatible providers, keyed by name. Used by
-    /// prefixing the model with the name (e.g. `<name>:model-id`).
-    #[serde(default)]
+    /// User-defined OpenAI-compatible providers (proxies, vLLM, llama.cpp,
+    /// LM Studio, ...), keyed by name. Used by prefixing the model with the
+    /// name (e.g. `<name>:model-id`) or as `openai-compat:<name>/<model>`.
+    /// `[providers.openai_compat]` is accepted as an alias of this table.
+    #[serde(default, alias = "openai_compat")]
     pub custom: std::collections::HashMap<String, CustomProviderConfig>,
 }
 
+/// Model prefix that selects a `[providers.custom.<name>]` server as
+/// `openai-compat:<name>/<model>`.
+pub const OPENAI_COMPAT_PROVIDER: &str = "openai-compat";
+
 impl ProvidersConfig {
     /// Returns whether a provider is enabled by its string identifier.
     ///
@@ -1494,13 +2403,27 @@ impl ProvidersConfig {
         }
     }
 
-    /// Resolves a `name:model` / `name/model` prefix to a configured custom
-    /// provider (`[providers.custom.<name>]`), returning its config + bare model.
+    /// Resolves a model to a configured custom provider
+    /// (`[providers.custom.<name>]`), returning its config, name, and bare
+    /// model. Accepts `openai-compat:<name>/<model>` and a `name:model` /
+    /// `name/model` prefix.
     pub fn custom_provider_for_model<'a>(
         &'a self,
         model: &str,
-    ) -> Option<(&'a CustomProviderConfig, String)> {
+    ) -> Option<(&'a CustomProviderConfig, String, String)> {
         let trimmed = model.trim();
+        if let Some(rest) = trimmed
+            .strip_prefix(OPENAI_COMPAT_PROVIDER)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            let (name, bare) = rest.split_once('/')?;
+            let (name, bare) = (name.trim(), bare.trim());
+            if bare.is_empty() {
+                return None;
+            }
+            let cfg = self.custom.get(name)?;
+            return Some((cfg, name.to_string(), bare.to_string()));
+        }
         for sep in [':', '/'] {
             if let Some((prefix, rest)) = trimmed.split_once(sep) {
                 let prefix = prefix.trim();
@@ -1508,12 +2431,24 @@ impl ProvidersConfig {
                 if !rest.is_empty()
                     && let Some(cfg) = self.custom.get(prefix)
                 {
-                    return Some((cfg, rest.to_string()));
+                    return Some((cfg, prefix.to_string(), rest.to_string()));
                 }
             }
         }
         None
     }
+
+    /// `[providers.<id>.extra]` body parameters sent with requests for
+    /// `model`, resolved in turn-setup order: `custom`, then the built-in
+    /// provider.
+    pub fn extra_for_model(&self, model: &str) -> &serde_json::Map<String, serde_json::Value> {
+        if let Some((cfg, _, _)) = self.custom_provider_for_model(model) {
+            return &cfg.extra;
+        }
+        &self
+            .get(crate::providers::resolve_provider(model).kind)
+            .extra
+    }
 }
 
 fn provider_fast_mode_key(provider: crate::providers::ProviderKind) -> &'static str {
@@ -1793,9 +2728,67 @@ pub struct ProviderConfig {
     /// Use the persistent WebSocket transport for the `OpenAI` Responses API.
     #[serde(default)]
     pub websocket: bool,
+    /// Extra headers, proxy, and TLS settings for this provider's HTTP client.
+    pub http: ProviderHttpConfig,
+    /// Provider-specific request body parameters zdx doesn't model (e.g.
+    /// `top_k`, `stop_sequences`); added to every request without replacing
+    /// fields zdx sets.
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+    /// What to do when a conversation outgrows the model's context window.
+    pub truncation: Option<TruncationStrategy>,
+    /// Turns kept by `truncation = "last_turns"` (default 20), plus pinned turns.
+    pub truncation_keep_turns: Option<usize>,
+}
+
+/// Conversation truncation strategy (`[providers.<id>].truncation`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TruncationStrategy {
+    /// Send the full history; the request fails if it doesn't fit (default).
+    #[default]
+    Disabled,
+    /// Drop the oldest turns only when the history outgrows the context
+    /// window. `OpenAI` does this server-side (Responses `truncation: "auto"`);
+    /// other providers drop unpinned turns client-side.
+    Auto,
+    /// Always send only the last `truncation_keep_turns` turns plus pinned turns.
+    LastTurns,
+}
+
+/// Builds a provider HTTP client, expanding `~` in `ca_bundle`.
+fn provider_http_client(http: &ProviderHttpConfig) -> anyhow::Result<Option<reqwest::Client>> {
+    let mut http = http.clone();
+    if let Some(path) = http.ca_bundle.as_deref() {
+        http.ca_bundle = Some(expand_tilde(path.trim()).to_string_lossy().into_owned());
+    }
+    crate::providers::http::build_client(&http)
+}
+
+fn provider_extra_params(
+    extra: &serde_json::Map<String, serde_json::Value>,
+) -> crate::providers::extra::ExtraParams {
+    (!extra.is_empty()).then(|| std::sync::Arc::new(extra.clone()))
 }
 
 impl ProviderConfig {
+    /// Default for `truncation_keep_turns`.
+    pub const DEFAULT_TRUNCATION_KEEP_TURNS: usize = 20;
+
+    /// HTTP client for `[providers.<name>.http]`, or `None` when all settings
+    /// are defaults.
+    ///
+    /// # Errors
+    /// Returns an error for an invalid header, proxy URL, or CA bundle.
+    pub fn http_client(&self) -> anyhow::Result<Option<reqwest::Client>> {
+        provider_http_client(&self.http)
+    }
+
+    /// `extra` as handed to provider clients; `None` when empty.
+    pub fn extra_params(&self) -> crate::providers::extra::ExtraParams {
+        provider_extra_params(&self.extra)
+    }
+
     /// Returns the effective API key if set and non-empty.
     pub fn effective_api_key(&self) -> Option<&str> {
         self.api_key
@@ -1818,6 +2811,20 @@ impl ProviderConfig {
         self.text_verbosity
     }
 
+    /// Returns the configured truncation strategy (`disabled` when unset).
+    #[must_use]
+    pub fn truncation_strategy(&self) -> TruncationStrategy {
+        self.truncation.unwrap_or_default()
+    }
+
+    /// Returns how many recent turns `last_turns` truncation keeps.
+    #[must_use]
+    pub fn truncation_keep_turns(&self) -> usize {
+        self.truncation_keep_turns
+            .filter(|turns| *turns > 0)
+            .unwrap_or(Self::DEFAULT_TRUNCATION_KEEP_TURNS)
+    }
+
     /// Filters a list of tool names based on this provider's tool configuration.
     ///
     /// If `tools` is set, returns only those tools (intersection with available).
@@ -1843,7 +2850,8 @@ impl ProviderConfig {
 }
 
 /// A user-defined OpenAI-compatible provider (`[providers.custom.<name>]`),
-/// e.g. a self-hosted `LiteLLM` proxy. The chat-completions path is appended
+/// e.g. a self-hosted `LiteLLM` proxy or a local vLLM server. The API key is
+/// optional (a placeholder is sent without one). The chat-completions path is appended
 /// to `base_url`, so point it at the OpenAI-compatible root
 /// (e.g. `https://llm.example.com/v1`).
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -1856,9 +2864,27 @@ pub struct CustomProviderConfig {
     pub api_key_env: Option<String>,
     /// Model allow-list shown in the picker (supports `*`).
     pub models: Vec<String>,
+    /// Extra headers, proxy, and TLS settings for this provider's HTTP client.
+    pub http: ProviderHttpConfig,
+    /// Extra request body parameters (see [`ProviderConfig::extra`]).
+    #[serde(skip_serializing_if = "serde_json::Map::is_empty")]
+    pub extra: serde_json::Map<String, serde_json::Value>,
 }
 
 impl CustomProviderConfig {
+    /// HTTP client for `http`, or `None` when all settings are defaults.
+    ///
+    /// # Errors
+    /// Returns an error for an invalid header, proxy URL, or CA bundle.
+    pub fn http_client(&self) -> anyhow::Result<Option<reqwest::Client>> {
+        provider_http_client(&self.http)
+    }
+
+    /// `extra` as handed to provider clients; `None` when empty.
+    pub fn extra_params(&self) -> crate::providers::extra::ExtraParams {
+        provider_extra_params(&self.extra)
+    }
+
     /// Trimmed base URL without a trailing slash.
     ///
     /// # Errors
@@ -1899,6 +2925,19 @@ impl CustomProviderConfig {
         }
         anyhow::bail!("custom provider requires `api_key` or `api_key_env`");
     }
+
+    /// Like [`Self::resolve_api_key`], but sends a placeholder when no key is
+    /// configured: local servers usually ignore the bearer token.
+    ///
+    /// # Errors
+    /// Returns an error if `api_key_env` is set but empty.
+    pub fn resolve_api_key_or_placeholder(&self) -> anyhow::Result<String> {
+        let unset = |value: Option<&str>| value.is_none_or(|v| v.trim().is_empty());
+        if unset(self.api_key.as_deref()) && unset(self.api_key_env.as_deref()) {
+            return Ok("none".to_string());
+        }
+        self.resolve_api_key()
+    }
 }
 
 #[cfg(test)]
@@ -1921,14 +2960,17 @@ mod tests {
                 api_key: Some("sk-test".to_string()),
                 api_key_env: None,
                 models: vec!["model-a".to_string()],
+                http: ProviderHttpConfig::default(),
+                extra: serde_json::Map::new(),
             },
         );
 
         // Both `:` and `/` separators resolve.
         for model in ["myproxy:model-a", "myproxy/model-a"] {
-            let (cfg, bare) = providers
+            let (cfg, name, bare) = providers
                 .custom_provider_for_model(model)
                 .expect("custom provider should resolve");
+            assert_eq!(name, "myproxy");
             assert_eq!(bare, "model-a");
             assert_eq!(cfg.base_url, "https://llm.example.com/v1");
         }
@@ -1951,6 +2993,8 @@ mod tests {
             api_key: Some("  sk-test  ".to_string()),
             api_key_env: None,
             models: vec![],
+            http: ProviderHttpConfig::default(),
+            extra: serde_json::Map::new(),
         };
         assert_eq!(
             cfg.effective_base_url().unwrap(),
@@ -1963,6 +3007,63 @@ mod tests {
         assert!(empty.resolve_api_key().is_err());
     }
 
+    /// `openai-compat:<name>/<model>` selects a custom provider, and a
+    /// keyless entry sends a placeholder key.
+    #[test]
+    fn test_openai_compat_selects_custom_provider() {
+        let mut providers = ProvidersConfig::default();
+        providers.custom.insert(
+            "vllm".to_string(),
+            CustomProviderConfig {
+                base_url: "http://localhost:8000/v1".to_string(),
+                models: vec!["Qwen/Qwen3-8B".to_string()],
+                ..Default::default()
+            },
+        );
+
+        let (cfg, name, bare) = providers
+            .custom_provider_for_model("openai-compat:vllm/Qwen/Qwen3-8B")
+            .expect("openai-compat server should resolve");
+        assert_eq!(name, "vllm");
+        assert_eq!(bare, "Qwen/Qwen3-8B");
+        assert_eq!(cfg.resolve_api_key_or_placeholder().unwrap(), "none");
+        assert!(cfg.resolve_api_key().is_err());
+
+        assert!(
+            providers
+                .custom_provider_for_model("openai-compat:other/model")
+                .is_none()
+        );
+        assert!(
+            providers
+                .custom_provider_for_model("openai-compat:vllm/")
+                .is_none()
+        );
+    }
+
+    /// `[providers.openai_compat.<name>]` loads into the same map as
+    /// `[providers.custom.<name>]`.
+    #[test]
+    fn test_openai_compat_table_is_an_alias_of_custom() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"[providers.openai_compat.local]
+base_url = "http://127.0.0.1:8000/v1"
+models = ["qwen3-coder"]
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&config_path).unwrap();
+        assert_eq!(config.providers.custom["local"].models, ["qwen3-coder"]);
+        for model in ["local:qwen3-coder", "openai-compat:local/qwen3-coder"] {
+            let (_, name, bare) = config.providers.custom_provider_for_model(model).unwrap();
+            assert_eq!((name.as_str(), bare.as_str()), ("local", "qwen3-coder"));
+        }
+    }
+
     /// Custom providers parse from a real config file.
     #[test]
     fn test_custom_provider_loads_from_file() {
@@ -1982,7 +3083,7 @@ models = ["model-a", "model-b"]
 
         let config = Config::load_from(&config_path).unwrap();
         assert_eq!(config.model, "myproxy:model-a");
-        let (cfg, bare) = config
+        let (cfg, _, bare) = config
             .providers
             .custom_provider_for_model(&config.model)
             .expect("custom provider should resolve from loaded config");
@@ -1992,6 +3093,56 @@ models = ["model-a", "model-b"]
         assert!(config.providers.is_enabled("anthropic"));
     }
 
+    /// Provider HTTP settings parse from `[providers.<id>.http]`.
+    #[test]
+    fn test_provider_http_config_loads_from_file() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"[providers.openai.http]
+headers = { "OpenAI-Organization" = "org-1" }
+proxy = "http://127.0.0.1:3128"
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&config_path).unwrap();
+        let http = &config.providers.openai.http;
+        assert_eq!(http.headers["OpenAI-Organization"], "org-1");
+        assert_eq!(http.proxy.as_deref(), Some("http://127.0.0.1:3128"));
+        assert!(config.providers.openai.http_client().unwrap().is_some());
+        assert!(config.providers.anthropic.http.is_default());
+        assert!(config.providers.anthropic.http_client().unwrap().is_none());
+    }
+
+    /// Extra body parameters parse from `[providers.<id>.extra]`.
+    #[test]
+    fn test_provider_extra_params_load_from_file() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        fs::write(
+            &config_path,
+            r#"[providers.anthropic.extra]
+top_k = 50
+stop_sequences = ["END"]
+
+[providers.custom.local]
+base_url = "http://localhost:8080/v1"
+extra = { min_p = 0.05 }
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&config_path).unwrap();
+        let extra = config.providers.anthropic.extra_params().unwrap();
+        assert_eq!(extra["top_k"], 50);
+        assert_eq!(extra["stop_sequences"], serde_json::json!(["END"]));
+        assert!(config.providers.openai.extra_params().is_none());
+        let custom = config.providers.custom["local"].extra_params().unwrap();
+        assert_eq!(custom["min_p"], 0.05);
+    }
+
     /// Config loading: missing file returns defaults (SPEC §9).
     #[test]
     fn test_load_missing_file_returns_defaults() {
@@ -3107,6 +4258,89 @@ file = "prompts/template.md"
         );
     }
 
+    /// Prompt layering controls load from file; `order` fills in missing sources.
+    #[test]
+    fn test_prompt_layers_config_loads_from_file() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+
+        fs::write(
+            &config_path,
+            r#"system_prompt_mode = "replace"
+
+[prompt_layers]
+skills = false
+order = ["project_context", "global_instructions", "project_context"]
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&config_path).unwrap();
+        assert_eq!(config.system_prompt_mode, SystemPromptMode::Replace);
+        assert!(!config.prompt_layers.enabled(PromptSource::Skills));
+        assert!(config.prompt_layers.enabled(PromptSource::Memory));
+        assert_eq!(
+            config.prompt_layers.resolved_order(),
+            [
+                PromptSource::ProjectContext,
+                PromptSource::GlobalInstructions,
+                PromptSource::Builtin,
+                PromptSource::Skills,
+                PromptSource::Memory,
+            ]
+        );
+    }
+
+    /// Prompt variants load from file; the first variant matching the model wins.
+    #[test]
+    fn test_prompt_variants_select_first_match() {
+        let dir = tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+
+        fs::write(
+            &config_path,
+            r#"system_prompt = "Full prompt"
+
+[[prompt_variants]]
+name = "local"
+models = ["lmstudio:*"]
+template_file = "prompts/terse.md"
+system_prompt = "Be brief."
+
+[[prompt_variants]]
+name = "small"
+models = ["*mini*", "lmstudio:qwen*"]
+instructions = "Call one tool at a time."
+"#,
+        )
+        .unwrap();
+
+        let config = Config::load_from(&config_path).unwrap();
+        let local = config.prompt_variant_for("lmstudio:qwen3-4b").unwrap();
+        assert_eq!(local.name, "local");
+        assert_eq!(
+            config.prompt_variant_for("openai:gpt-5-mini").unwrap().name,
+            "small"
+        );
+        assert!(
+            config
+                .prompt_variant_for("anthropic:claude-sonnet-4-5")
+                .is_none()
+        );
+
+        let applied = config.with_prompt_variant(local);
+        assert_eq!(applied.system_prompt.as_deref(), Some("Be brief."));
+        assert_eq!(
+            applied.prompt_template.file.as_deref(),
+            Some("prompts/terse.md")
+        );
+        let small = config.prompt_variant_for("gpt-5-mini").unwrap();
+        assert_eq!(
+            config.with_prompt_variant(small).system_prompt.as_deref(),
+            Some("Full prompt")
+        );
+    }
+
     /// Subagents config loads from file.
     #[test]
     fn test_subagents_config_loads_from_file() {
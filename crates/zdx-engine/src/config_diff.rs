@@ -0,0 +1,226 @@
+//! Diff between a user config file and the bundled default config.
+//!
+//! Backs `zdx config diff`. Both files are compared as parsed TOML (so
+//! formatting and comments never show up as changes), after deprecated keys
+//! in the user file are mapped to their new names. Each entry carries the
+//! first line of the template comment documenting the key, when there is one.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use toml_edit::{DocumentMut, Item, TableLike};
+use zdx_types::is_secret_key;
+
+use crate::config::default_config_template;
+use crate::config_validation;
+
+/// Shown instead of values whose key looks like a credential.
+const REDACTED: &str = "<redacted>";
+
+/// How a key differs from the bundled defaults.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiffKind {
+    /// Set in the user config but not in the defaults.
+    Added,
+    /// In the defaults but missing from the user config (the default applies).
+    Removed,
+    /// Set in both with different values.
+    Changed,
+}
+
+/// One differing key (or whole table, for tables missing from the user file).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiffEntry {
+    pub kind: DiffKind,
+    /// Dotted key path (e.g. `tui.language`).
+    pub path: String,
+    /// Whether `path` names a whole table.
+    pub is_table: bool,
+    pub user: Option<String>,
+    pub default: Option<String>,
+    /// First line of the template comment above the key.
+    pub comment: Option<String>,
+}
+
+/// Diffs the config file at `path` against the bundled defaults. A missing
+/// file diffs as empty.
+///
+/// # Errors
+/// Returns an error if the file cannot be read or either file isn't valid TOML.
+pub fn diff_file(path: &Path) -> Result<Vec<DiffEntry>> {
+    let contents = if path.exists() {
+        fs::read_to_string(path)
+            .with_context(|| format!("Failed to read config from {}", path.display()))?
+    } else {
+        String::new()
+    };
+    diff_str(&contents).with_context(|| format!("Failed to parse config from {}", path.display()))
+}
+
+/// Diffs config contents against the bundled defaults.
+///
+/// # Errors
+/// Returns an error if either document isn't valid TOML.
+pub fn diff_str(contents: &str) -> Result<Vec<DiffEntry>> {
+    diff_with_template(contents, default_config_template())
+}
+
+fn diff_with_template(contents: &str, template: &str) -> Result<Vec<DiffEntry>> {
+    let (migrated, _) = config_validation::migrate_deprecated_keys(contents)?;
+    let user: toml::Table = toml::from_str(&migrated)?;
+    let defaults: toml::Table =
+        toml::from_str(template).context("Failed to parse default config template")?;
+    let template_doc: DocumentMut = template
+        .parse()
+        .context("Failed to parse default config template")?;
+
+    let mut entries = Vec::new();
+    diff_tables(&user, &defaults, "", &mut entries);
+    for entry in &mut entries {
+        entry.comment = template_comment(template_doc.as_table(), &entry.path);
+    }
+    Ok(entries)
+}
+
+fn diff_tables(user: &toml::Table, defaults: &toml::Table, prefix: &str, out: &mut Vec<DiffEntry>) {
+    let path_of = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+
+    for (key, default) in defaults {
+        let path = path_of(key);
+        match (user.get(key), default) {
+            (Some(toml::Value::Table(user)), toml::Value::Table(default)) => {
+                diff_tables(user, default, &path, out);
+            }
+            (Some(value), default) if value != default => out.push(DiffEntry {
+                kind: DiffKind::Changed,
+                user: Some(display(key, value)),
+                default: Some(display(key, default)),
+                ..entry(path, false)
+            }),
+            (Some(_), _) => {}
+            (None, default) => out.push(DiffEntry {
+                kind: DiffKind::Removed,
+                default: (!default.is_table()).then(|| display(key, default)),
+                ..entry(path, default.is_table())
+            }),
+        }
+    }
+    for (key, value) in user {
+        if defaults.contains_key(key) {
+            continue;
+        }
+        out.push(DiffEntry {
+            kind: DiffKind::Added,
+            user: (!value.is_table()).then(|| display(key, value)),
+            ..entry(path_of(key), value.is_table())
+        });
+    }
+}
+
+fn entry(path: String, is_table: bool) -> DiffEntry {
+    DiffEntry {
+        kind: DiffKind::Added,
+        path,
+        is_table,
+        user: None,
+        default: None,
+        comment: None,
+    }
+}
+
+fn display(key: &str, value: &toml::Value) -> String {
+    if is_secret_key(key) {
+        REDACTED.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+/// The last comment block directly above `path` in the template (its first
+/// line). Commented-out examples further up are separated by a blank line.
+fn template_comment(root: &dyn TableLike, path: &str) -> Option<String> {
+    let mut segments: Vec<&str> = path.split('.').collect();
+    let last = segments.pop()?;
+    let mut table = root;
+    for segment in segments {
+        table = table.get(segment)?.as_table_like()?;
+    }
+    let (key, item) = table.get_key_value(last)?;
+    let prefix = match item {
+        Item::Table(table) => table.decor().prefix(),
+        _ => key.leaf_decor().prefix(),
+    }?
+    .as_str()?;
+
+    let block: Vec<&str> = prefix
+        .lines()
+        .rev()
+        .take_while(|line| line.trim_start().starts_with('#'))
+        .collect();
+    block
+        .last()
+        .map(|line| line.trim_start().trim_start_matches('#').trim().to_string())
+        .filter(|line| !line.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEMPLATE: &str = "\
+# Model to use
+model = \"a\"
+
+# Old example
+# retries = 1
+
+# Thinking level
+thinking_level = \"off\"
+
+# Terminal UI
+[tui]
+language = \"en\"
+";
+
+    #[test]
+    fn reports_changed_removed_and_added_keys_with_comments() {
+        let user = "model = \"b\"\n[tui]\nlanguage = \"en\"\n[telegram]\nbot_token = \"t\"\n";
+        let entries = diff_with_template(user, TEMPLATE).unwrap();
+
+        assert_eq!(entries.len(), 3, "{entries:?}");
+        assert_eq!(entries[0].kind, DiffKind::Changed);
+        assert_eq!(entries[0].path, "model");
+        assert_eq!(entries[0].user.as_deref(), Some("\"b\""));
+        assert_eq!(entries[0].comment.as_deref(), Some("Model to use"));
+
+        assert_eq!(entries[1].kind, DiffKind::Removed);
+        assert_eq!(entries[1].path, "thinking_level");
+        assert_eq!(entries[1].comment.as_deref(), Some("Thinking level"));
+
+        assert_eq!(entries[2].kind, DiffKind::Added);
+        assert_eq!(entries[2].path, "telegram");
+        assert!(entries[2].is_table);
+    }
+
+    #[test]
+    fn missing_tables_collapse_and_secrets_are_redacted() {
+        let entries = diff_with_template("model = \"a\"\napi_key = \"sk\"\n", TEMPLATE).unwrap();
+        let tui = entries.iter().find(|e| e.path == "tui").unwrap();
+        assert_eq!(tui.kind, DiffKind::Removed);
+        assert_eq!(tui.comment.as_deref(), Some("Terminal UI"));
+        let key = entries.iter().find(|e| e.path == "api_key").unwrap();
+        assert_eq!(key.user.as_deref(), Some(REDACTED));
+    }
+
+    #[test]
+    fn bundled_template_diffs_clean_against_itself() {
+        assert!(diff_str(default_config_template()).unwrap().is_empty());
+    }
+}
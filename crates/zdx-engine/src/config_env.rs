@@ -0,0 +1,227 @@
+//! `ZDX_*` environment variable overrides for config keys.
+//!
+//! Any top-level config key can be overridden with `ZDX_<KEY>`; nested keys
+//! use a double underscore per level (`ZDX_TELEGRAM__BOT_TOKEN` →
+//! `telegram.bot_token`, `ZDX_PROVIDERS__OPENAI__BASE_URL` →
+//! `providers.openai.base_url`). Names are lowercased before mapping.
+//!
+//! Overrides are applied to the parsed file (after deprecated-key migration)
+//! and before deserializing into [`Config`], so they go through the same
+//! validation as file values. Values are parsed as TOML (`30`, `true`,
+//! `["a", "b"]`) and fall back to a plain string; keys whose default is a
+//! string always take the raw value, and boolean keys also accept `1`/`0`.
+//! Quote the value (`'"123"'`) to force a string for keys without a default.
+//!
+//! Only variables whose first segment names a [`Config`] field are treated as
+//! overrides, so unrelated variables like `ZDX_HOME` are left alone.
+
+use anyhow::{Result, bail};
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, forward_to_deserialize_any};
+
+use crate::config::Config;
+
+/// Prefix shared by all override variables.
+pub const ENV_PREFIX: &str = "ZDX_";
+
+/// One override: the variable name, its config key path, and raw value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvOverride {
+    pub var: String,
+    pub path: Vec<String>,
+    pub value: String,
+}
+
+/// Collects config overrides from `vars` (usually `std::env::vars()`),
+/// sorted by variable name so application order is deterministic.
+pub fn collect_overrides(vars: impl IntoIterator<Item = (String, String)>) -> Vec<EnvOverride> {
+    let fields = config_fields();
+    let mut overrides: Vec<EnvOverride> = vars
+        .into_iter()
+        .filter_map(|(var, value)| {
+            let key = var.strip_prefix(ENV_PREFIX)?.to_ascii_lowercase();
+            let path: Vec<String> = key.split("__").map(str::to_string).collect();
+            if path.iter().any(String::is_empty) || !fields.contains(&path[0].as_str()) {
+                return None;
+            }
+            Some(EnvOverride { var, path, value })
+        })
+        .collect();
+    overrides.sort_by(|a, b| a.var.cmp(&b.var));
+    overrides
+}
+
+/// Writes `overrides` into a parsed config table.
+///
+/// # Errors
+/// Returns an error if an override nests under a key that isn't a table.
+pub fn apply_overrides(table: &mut toml::Table, overrides: &[EnvOverride]) -> Result<()> {
+    let defaults = toml::Value::try_from(Config::default()).ok();
+    for entry in overrides {
+        let default = defaults
+            .as_ref()
+            .and_then(|defaults| lookup(defaults, &entry.path));
+        let value = parse_value(&entry.value, default);
+
+        let Some((last, parents)) = entry.path.split_last() else {
+            continue;
+        };
+        let mut current = &mut *table;
+        for segment in parents {
+            let next = current
+                .entry(segment.clone())
+                .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+            let toml::Value::Table(next) = next else {
+                bail!(
+                    "{}: `{segment}` is not a table in config, cannot override `{}`",
+                    entry.var,
+                    entry.path.join(".")
+                );
+            };
+            current = next;
+        }
+        current.insert(last.clone(), value);
+    }
+    Ok(())
+}
+
+fn lookup<'a>(value: &'a toml::Value, path: &[String]) -> Option<&'a toml::Value> {
+    path.iter()
+        .try_fold(value, |value, segment| value.as_table()?.get(segment))
+}
+
+fn parse_value(raw: &str, default: Option<&toml::Value>) -> toml::Value {
+    if matches!(default, Some(toml::Value::String(_))) {
+        return toml::Value::String(raw.to_string());
+    }
+    if matches!(default, Some(toml::Value::Boolean(_))) {
+        match raw.trim() {
+            "1" => return toml::Value::Boolean(true),
+            "0" => return toml::Value::Boolean(false),
+            _ => {}
+        }
+    }
+    toml::from_str::<toml::Table>(&format!("value = {raw}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(raw.to_string()))
+}
+
+/// Top-level field names of [`Config`], read from its `Deserialize` impl.
+fn config_fields() -> &'static [&'static str] {
+    let mut fields: &'static [&'static str] = &[];
+    let _ = Config::deserialize(FieldNames(&mut fields));
+    fields
+}
+
+/// Deserializer that records the field list a struct asks for, then bails.
+struct FieldNames<'a>(&'a mut &'static [&'static str]);
+
+impl<'de> Deserializer<'de> for FieldNames<'_> {
+    type Error = de::value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("expected a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        *self.0 = fields;
+        Err(de::Error::custom("field names captured"))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ThinkingLevel;
+
+    fn vars(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs
+            .iter()
+            .map(|(key, value)| ((*key).to_string(), (*value).to_string()))
+            .collect()
+    }
+
+    fn load(file: &str, pairs: &[(&str, &str)]) -> Result<Config> {
+        let mut table: toml::Table = toml::from_str(file)?;
+        apply_overrides(&mut table, &collect_overrides(vars(pairs)))?;
+        Ok(Config::deserialize(table)?)
+    }
+
+    #[test]
+    fn ignores_variables_that_are_not_config_keys() {
+        let overrides = collect_overrides(vars(&[
+            ("ZDX_HOME", "/tmp/zdx"),
+            ("ZDX_DEBUG_TRACE", "1"),
+            ("ZDX_MODEL", "openai:gpt-5.5"),
+            ("PATH", "/bin"),
+        ]));
+
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].path, ["model"]);
+    }
+
+    #[test]
+    fn overrides_top_level_and_nested_keys() {
+        let config = load(
+            "model = \"anthropic:claude-sonnet-4-6\"\n[agent_loop]\nmax_repeated_tool_calls = 3\n",
+            &[
+                ("ZDX_MODEL", "openai:gpt-5.5"),
+                ("ZDX_THINKING_LEVEL", "high"),
+                ("ZDX_TELEGRAM__BOT_TOKEN", "123:abc"),
+                ("ZDX_AGENT_LOOP__MAX_TOOL_ITERATIONS", "12"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.model, "openai:gpt-5.5");
+        assert_eq!(config.thinking_level, ThinkingLevel::High);
+        assert_eq!(config.telegram.bot_token.as_deref(), Some("123:abc"));
+        assert_eq!(config.agent_loop.max_tool_iterations, 12);
+        assert_eq!(config.agent_loop.max_repeated_tool_calls, 3);
+    }
+
+    #[test]
+    fn subagent_output_cap_beats_configured_model_params() {
+        let overrides: Vec<_> = crate::core::subagent::MAX_TOKENS_ENV_VARS
+            .iter()
+            .map(|var| (*var, "512"))
+            .collect();
+        let config = load("[model_params]\nmax_output_tokens = 4000\n", &overrides).unwrap();
+
+        assert_eq!(config.max_output_tokens(), Some(512));
+    }
+
+    #[test]
+    fn string_keys_keep_raw_values() {
+        let config = load("", &[("ZDX_MODEL", "true")]).unwrap();
+        assert_eq!(config.model, "true");
+    }
+
+    #[test]
+    fn boolean_keys_accept_one_and_zero() {
+        assert!(load("", &[("ZDX_OFFLINE", "1")]).unwrap().offline);
+        assert!(
+            !load("offline = true", &[("ZDX_OFFLINE", "0")])
+                .unwrap()
+                .offline
+        );
+    }
+
+    #[test]
+    fn nesting_under_a_scalar_is_an_error() {
+        let err = load("model = \"m\"", &[("ZDX_MODEL__NAME", "x")]).unwrap_err();
+        assert!(err.to_string().contains("ZDX_MODEL__NAME"), "{err}");
+    }
+}
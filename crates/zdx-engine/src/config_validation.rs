@@ -0,0 +1,857 @@
+//! Config file validation and deprecated-key migration.
+//!
+//! [`Config::load_from`] stays lenient about keys it doesn't know (most
+//! sections ignore them), so typos silently fall back to defaults. This module
+//! backs `zdx config validate`: it reports syntax and type errors, unknown keys,
+//! and deprecated keys, each with a line/column taken from the TOML spans.
+//!
+//! Every problem is listed, not just the first. Deserializing into [`Config`]
+//! stops at the first bad key, so each failure is recorded, the offending key
+//! is pruned from a copy of the file, and deserialization is retried until it
+//! succeeds. Keys in sections without `deny_unknown_fields` are then found by
+//! round-tripping: the pruned file is deserialized, serialized back, and every
+//! key in the file that did not survive the round trip is reported.
+//!
+//! Deprecated keys listed in [`DEPRECATED_KEYS`] are auto-mapped to their new
+//! name on every load (with a warning), so renames don't break existing files.
+//! The first run of a new zdx version also rewrites them in the file itself
+//! ([`migrate_on_upgrade`]), keeping a backup of the original.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use toml_edit::{DocumentMut, ImDocument, Item, TableLike, Value};
+
+use crate::config::Config;
+
+/// A renamed config key, as dotted paths (e.g. `tui.lang` → `tui.language`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeprecatedKey {
+    pub old: &'static str,
+    pub new: &'static str,
+}
+
+/// Renamed keys, auto-mapped on load. Add an entry here whenever a key is
+/// renamed instead of breaking existing config files.
+pub const DEPRECATED_KEYS: &[DeprecatedKey] = &[];
+
+/// Serde aliases as `(alias path, canonical key)`. The round trip writes the
+/// canonical key, so keys under an alias are checked against it.
+const KEY_ALIASES: &[(&str, &str)] = &[("providers.openai_compat", "custom")];
+
+/// Upper bound on prune-and-retry passes, so a pathological file can't loop.
+const MAX_PASSES: usize = 64;
+
+/// Category of a validation finding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IssueKind {
+    /// Syntax error or a value that doesn't match the expected type.
+    Error,
+    /// Key not recognized by any config section.
+    UnknownKey,
+    /// Key was renamed; still accepted via auto-mapping.
+    DeprecatedKey,
+}
+
+/// One validation finding, positioned in the source file (1-based).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub kind: IssueKind,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    /// Whether this finding should fail validation (deprecations only warn).
+    pub fn is_failure(&self) -> bool {
+        self.kind != IssueKind::DeprecatedKey
+    }
+}
+
+/// Validates the config file at `path`.
+///
+/// # Errors
+/// Returns an error if the file cannot be read.
+pub fn validate_file(path: &Path) -> Result<Vec<ConfigIssue>> {
+    let contents = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config from {}", path.display()))?;
+    Ok(validate_str(&contents))
+}
+
+/// Validates config file contents. Issues are sorted by position.
+pub fn validate_str(contents: &str) -> Vec<ConfigIssue> {
+    validate_with(contents, DEPRECATED_KEYS)
+}
+
+fn validate_with(contents: &str, deprecated: &[DeprecatedKey]) -> Vec<ConfigIssue> {
+    let doc = match ImDocument::parse(contents) {
+        Ok(doc) => doc,
+        Err(err) => {
+            return vec![issue_at(
+                contents,
+                IssueKind::Error,
+                err.span(),
+                err.message().trim().to_string(),
+            )];
+        }
+    };
+
+    let mut issues = Vec::new();
+    for key in deprecated {
+        if let Some(span) = key_span(doc.as_table(), key.old) {
+            issues.push(issue_at(
+                contents,
+                IssueKind::DeprecatedKey,
+                Some(span),
+                format!(
+                    "`{}` is deprecated; rename it to `{}` (auto-mapped for now)",
+                    key.old, key.new
+                ),
+            ));
+        }
+    }
+
+    // Errors are found in the mapped file but positioned in the original.
+    let mut pruned: DocumentMut = doc.clone().into_mut();
+    let mapped = map_deprecated(pruned.as_table_mut(), deprecated);
+    let mut reported = Vec::new();
+    let mut config = None;
+    for _ in 0..MAX_PASSES {
+        let text = pruned.to_string();
+        let err = match toml::from_str::<Config>(&text) {
+            Ok(parsed) => {
+                config = Some(parsed);
+                break;
+            }
+            Err(err) => err,
+        };
+        let path = ImDocument::parse(text.as_str()).ok().and_then(|current| {
+            err.span()
+                .and_then(|span| path_at(current.as_table(), span.start))
+        });
+        let Some(path) = path else {
+            // Nothing to prune (e.g. a missing top-level key): report and stop.
+            let span = err.span().filter(|span| span.end <= contents.len());
+            issues.push(issue_at(
+                contents,
+                IssueKind::Error,
+                span,
+                err.message().trim().to_string(),
+            ));
+            break;
+        };
+        let original = original_path(&path, &mapped);
+        issues.push(issue_for(
+            contents,
+            doc.as_table(),
+            &original,
+            err.message(),
+        ));
+        reported.push(path_string(&original));
+        if !remove_path(pruned.as_table_mut(), &path) {
+            break;
+        }
+    }
+
+    if let Some(config) = config {
+        if let Err(message) = config.model_params.validate() {
+            let span = key_span(doc.as_table(), "model_params");
+            issues.push(issue_at(contents, IssueKind::Error, span, message));
+        }
+
+        if let Ok(toml::Value::Table(known)) = toml::Value::try_from(&config) {
+            let skip: Vec<&str> = reported
+                .iter()
+                .map(String::as_str)
+                .chain(deprecated.iter().map(|key| key.old))
+                .collect();
+            let mut unknown = Vec::new();
+            collect_unknown(doc.as_table(), &known, "", &skip, &mut unknown);
+            for (path, span, suggestion) in unknown {
+                issues.push(issue_at(
+                    contents,
+                    IssueKind::UnknownKey,
+                    span,
+                    unknown_key_message(&path, suggestion.as_deref()),
+                ));
+            }
+        }
+    }
+
+    issues.sort_by_key(|issue| (issue.line, issue.column));
+    issues
+}
+
+/// Rewrites deprecated keys in `contents` to their new names. Returns the
+/// (possibly unchanged) contents and the keys that were mapped.
+///
+/// # Errors
+/// Returns an error if the contents are not valid TOML while a deprecated
+/// key appears to be present.
+pub fn migrate_deprecated_keys(contents: &str) -> Result<(String, Vec<DeprecatedKey>)> {
+    migrate_with(contents, DEPRECATED_KEYS)
+}
+
+fn migrate_with(
+    contents: &str,
+    deprecated: &[DeprecatedKey],
+) -> Result<(String, Vec<DeprecatedKey>)> {
+    // Cheap pre-check so the common case never re-parses the file.
+    let maybe_present = deprecated
+        .iter()
+        .any(|key| contents.contains(leaf(key.old)));
+    if !maybe_present {
+        return Ok((contents.to_string(), Vec::new()));
+    }
+
+    let mut doc: DocumentMut = contents.parse().context("Failed to parse config")?;
+    let mapped = map_deprecated(doc.as_table_mut(), deprecated);
+    Ok((doc.to_string(), mapped))
+}
+
+/// What an upgrade migration rewrote in the config file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    pub path: PathBuf,
+    /// Copy of the file before it was rewritten.
+    pub backup: PathBuf,
+    /// Version recorded by the previous run, if any.
+    pub from_version: Option<String>,
+    pub to_version: String,
+    pub renamed: Vec<DeprecatedKey>,
+}
+
+impl MigrationReport {
+    /// Human-readable summary, one line per renamed key.
+    pub fn summary(&self) -> String {
+        let mut lines = vec![format!(
+            "zdx {}: migrated {} (backup: {})",
+            self.to_version,
+            self.path.display(),
+            self.backup.display()
+        )];
+        lines.extend(
+            self.renamed
+                .iter()
+                .map(|key| format!("  renamed `{}` → `{}`", key.old, key.new)),
+        );
+        lines.push("  Run `zdx config diff` to compare with the current defaults.".to_string());
+        lines.join("\n")
+    }
+}
+
+/// Runs the config migration once per zdx version.
+///
+/// When `version` differs from the one recorded in `stamp_path`, deprecated
+/// keys in `config_path` are rewritten to their new names (the original is
+/// kept next to it as `config.toml.pre-<version>.bak`) and the stamp is
+/// updated. Returns a report only when the file changed.
+///
+/// # Errors
+/// Returns an error if the config or stamp can't be read or written.
+pub fn migrate_on_upgrade(
+    config_path: &Path,
+    stamp_path: &Path,
+    version: &str,
+) -> Result<Option<MigrationReport>> {
+    migrate_on_upgrade_with(config_path, stamp_path, version, DEPRECATED_KEYS)
+}
+
+fn migrate_on_upgrade_with(
+    config_path: &Path,
+    stamp_path: &Path,
+    version: &str,
+    deprecated: &[DeprecatedKey],
+) -> Result<Option<MigrationReport>> {
+    let from_version = fs::read_to_string(stamp_path)
+        .ok()
+        .map(|stamp| stamp.trim().to_string())
+        .filter(|stamp| !stamp.is_empty());
+    if from_version.as_deref() == Some(version) {
+        return Ok(None);
+    }
+
+    let mut report = None;
+    if config_path.exists() {
+        let contents = fs::read_to_string(config_path)
+            .with_context(|| format!("Failed to read config from {}", config_path.display()))?;
+        let (migrated, renamed) = migrate_with(&contents, deprecated)
+            .with_context(|| format!("Failed to parse config from {}", config_path.display()))?;
+        if !renamed.is_empty() {
+            let backup = config_path.with_extension(format!("toml.pre-{version}.bak"));
+            fs::write(&backup, &contents)
+                .with_context(|| format!("Failed to write backup {}", backup.display()))?;
+            Config::write_config(config_path, &migrated)?;
+            report = Some(MigrationReport {
+                path: config_path.to_path_buf(),
+                backup,
+                from_version: from_version.clone(),
+                to_version: version.to_string(),
+                renamed,
+            });
+        }
+    }
+
+    if let Some(parent) = stamp_path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create directory {}", parent.display()))?;
+    }
+    fs::write(stamp_path, version)
+        .with_context(|| format!("Failed to write {}", stamp_path.display()))?;
+    Ok(report)
+}
+
+/// Moves each deprecated key present in `table` to its new name. Returns the
+/// keys that were mapped.
+fn map_deprecated(table: &mut dyn TableLike, deprecated: &[DeprecatedKey]) -> Vec<DeprecatedKey> {
+    let mut mapped = Vec::new();
+    for key in deprecated {
+        let Some(item) = remove_dotted(table, key.old) else {
+            continue;
+        };
+        mapped.push(*key);
+        // An explicit new key wins over the deprecated one.
+        if get_dotted(table, key.new).is_none() {
+            insert_dotted(table, key.new, item);
+        }
+    }
+    mapped
+}
+
+/// Maps a path in the migrated file back to the key the user wrote.
+fn original_path(path: &[Segment], mapped: &[DeprecatedKey]) -> Vec<Segment> {
+    for key in mapped {
+        let new: Vec<Segment> = key
+            .new
+            .split('.')
+            .map(|segment| Segment::Key(segment.to_string()))
+            .collect();
+        if path.starts_with(&new) {
+            return key
+                .old
+                .split('.')
+                .map(|segment| Segment::Key(segment.to_string()))
+                .chain(path[new.len()..].iter().cloned())
+                .collect();
+        }
+    }
+    path.to_vec()
+}
+
+fn leaf(path: &str) -> &str {
+    path.rsplit('.').next().unwrap_or(path)
+}
+
+fn split_parent(path: &str) -> (Vec<&str>, &str) {
+    let mut segments: Vec<&str> = path.split('.').collect();
+    let last = segments.pop().unwrap_or(path);
+    (segments, last)
+}
+
+fn get_dotted<'a>(table: &'a dyn TableLike, path: &str) -> Option<&'a Item> {
+    let (parents, last) = split_parent(path);
+    let mut current = table;
+    for segment in parents {
+        current = current.get(segment)?.as_table_like()?;
+    }
+    current.get(last)
+}
+
+fn remove_dotted(table: &mut dyn TableLike, path: &str) -> Option<Item> {
+    let (parents, last) = split_parent(path);
+    let mut current = table;
+    for segment in parents {
+        current = current.get_mut(segment)?.as_table_like_mut()?;
+    }
+    current.remove(last)
+}
+
+fn insert_dotted(table: &mut dyn TableLike, path: &str, item: Item) {
+    let (parents, last) = split_parent(path);
+    let mut current = table;
+    for segment in parents {
+        let entry = current.entry(segment).or_insert(toml_edit::table());
+        let Some(next) = entry.as_table_like_mut() else {
+            return;
+        };
+        current = next;
+    }
+    current.insert(last, item);
+}
+
+fn key_span(table: &dyn TableLike, path: &str) -> Option<Range<usize>> {
+    let (parents, last) = split_parent(path);
+    let mut current = table;
+    for segment in parents {
+        current = current.get(segment)?.as_table_like()?;
+    }
+    current.get_key_value(last)?.0.span()
+}
+
+/// One step of a key path: a table key or an array-of-tables index.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+fn path_string(path: &[Segment]) -> String {
+    let mut out = String::new();
+    for segment in path {
+        match segment {
+            Segment::Key(key) if out.is_empty() => out.push_str(key),
+            Segment::Key(key) => {
+                out.push('.');
+                out.push_str(key);
+            }
+            Segment::Index(index) => {
+                let _ = write!(out, "[{index}]");
+            }
+        }
+    }
+    out
+}
+
+/// Builds the issue for a deserialization error located at `path`.
+fn issue_for(contents: &str, doc: &dyn TableLike, path: &[Segment], message: &str) -> ConfigIssue {
+    let message = message.trim();
+    let (key_span, value_span) = spans_at(doc, path).unwrap_or_default();
+    let name = path_string(path);
+    if message.starts_with("unknown field") {
+        let suggestion = closest_match(leaf_key(path), expected_names(message));
+        return issue_at(
+            contents,
+            IssueKind::UnknownKey,
+            key_span,
+            unknown_key_message(&name, suggestion),
+        );
+    }
+    issue_at(
+        contents,
+        IssueKind::Error,
+        value_span.or(key_span),
+        format!("`{name}`: {message}"),
+    )
+}
+
+fn unknown_key_message(path: &str, suggestion: Option<&str>) -> String {
+    match suggestion {
+        Some(suggestion) => format!("unknown key `{path}` (did you mean `{suggestion}`?)"),
+        None => format!("unknown key `{path}`"),
+    }
+}
+
+fn leaf_key(path: &[Segment]) -> &str {
+    path.iter()
+        .rev()
+        .find_map(|segment| match segment {
+            Segment::Key(key) => Some(key.as_str()),
+            Segment::Index(_) => None,
+        })
+        .unwrap_or_default()
+}
+
+/// Field names listed after "expected" in a serde unknown-field message.
+fn expected_names(message: &str) -> impl Iterator<Item = &str> {
+    message
+        .split_once("expected")
+        .map_or("", |(_, expected)| expected)
+        .split('`')
+        .skip(1)
+        .step_by(2)
+}
+
+/// Finds the deepest key whose key or value span contains `offset`.
+fn path_at(table: &dyn TableLike, offset: usize) -> Option<Vec<Segment>> {
+    let contains = |span: Option<Range<usize>>| span.is_some_and(|span| span.contains(&offset));
+    for (key, item) in table.iter() {
+        let key_span = table.get_key_value(key).and_then(|(key, _)| key.span());
+        if contains(key_span) {
+            return Some(vec![Segment::Key(key.to_string())]);
+        }
+        let nested = match item {
+            Item::Table(child) => path_at(child, offset),
+            Item::ArrayOfTables(tables) => tables.iter().enumerate().find_map(|(index, child)| {
+                path_at(child, offset).map(|rest| prepend(Segment::Index(index), rest))
+            }),
+            Item::Value(value) if contains(value.span()) => Some(value_path(value, offset)),
+            _ => None,
+        };
+        if let Some(rest) = nested {
+            return Some(prepend(Segment::Key(key.to_string()), rest));
+        }
+    }
+    None
+}
+
+/// Path below a value that contains the error offset (empty if the value
+/// itself is the culprit).
+fn value_path(value: &Value, offset: usize) -> Vec<Segment> {
+    match value {
+        Value::InlineTable(child) => path_at(child, offset).unwrap_or_default(),
+        Value::Array(values) => values
+            .iter()
+            .enumerate()
+            .find_map(|(index, value)| match value {
+                Value::InlineTable(child) => {
+                    path_at(child, offset).map(|rest| prepend(Segment::Index(index), rest))
+                }
+                _ => None,
+            })
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn prepend(segment: Segment, mut rest: Vec<Segment>) -> Vec<Segment> {
+    rest.insert(0, segment);
+    rest
+}
+
+type Span = Option<Range<usize>>;
+
+/// Key and value spans of the item at `path`.
+fn spans_at(table: &dyn TableLike, path: &[Segment]) -> Option<(Span, Span)> {
+    let (Segment::Key(first), rest) = path.split_first()? else {
+        return None;
+    };
+    let (key, item) = table.get_key_value(first)?;
+    if rest.is_empty() {
+        let value_span = item.as_value().and_then(Value::span);
+        return Some((key.span(), value_span));
+    }
+    match (item, rest.first()?) {
+        (Item::ArrayOfTables(tables), Segment::Index(index)) => {
+            spans_at(tables.get(*index)?, &rest[1..])
+        }
+        (Item::Value(Value::Array(values)), Segment::Index(index)) => {
+            spans_at(values.get(*index)?.as_inline_table()?, &rest[1..])
+        }
+        (item, Segment::Key(_)) => spans_at(item.as_table_like()?, rest),
+        _ => None,
+    }
+}
+
+/// Removes the key at `path`. Returns whether anything was removed.
+fn remove_path(table: &mut dyn TableLike, path: &[Segment]) -> bool {
+    let Some((Segment::Key(first), rest)) = path.split_first() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return table.remove(first).is_some();
+    }
+    let Some(item) = table.get_mut(first) else {
+        return false;
+    };
+    match (item, &rest[0]) {
+        (Item::ArrayOfTables(tables), Segment::Index(index)) => tables
+            .get_mut(*index)
+            .is_some_and(|child| remove_path(child, &rest[1..])),
+        (Item::Value(Value::Array(values)), Segment::Index(index)) => values
+            .get_mut(*index)
+            .and_then(Value::as_inline_table_mut)
+            .is_some_and(|child| remove_path(child, &rest[1..])),
+        (item, Segment::Key(_)) => item
+            .as_table_like_mut()
+            .is_some_and(|child| remove_path(child, rest)),
+        _ => false,
+    }
+}
+
+type UnknownKey = (String, Option<Range<usize>>, Option<String>);
+
+/// Walks `table` (the user's file) against `known` (the round-tripped config)
+/// and records keys missing from `known`.
+fn collect_unknown(
+    table: &dyn TableLike,
+    known: &toml::Table,
+    prefix: &str,
+    skip: &[&str],
+    out: &mut Vec<UnknownKey>,
+) {
+    for (key, item) in table.iter() {
+        let path = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        if skip.contains(&path.as_str()) {
+            continue;
+        }
+        let canonical = KEY_ALIASES
+            .iter()
+            .find(|(alias, _)| *alias == path)
+            .map_or(key, |(_, canonical)| *canonical);
+        let Some(known_value) = known.get(canonical) else {
+            // `skip_serializing_if` drops empty collections on the way back.
+            if !is_empty_item(item) {
+                let span = table.get_key_value(key).and_then(|(k, _)| k.span());
+                out.push((path, span, closest_key(key, known)));
+            }
+            continue;
+        };
+        match (item, known_value) {
+            (Item::ArrayOfTables(tables), toml::Value::Array(known_items)) => {
+                for (index, (child, known_child)) in tables.iter().zip(known_items).enumerate() {
+                    if let toml::Value::Table(known_child) = known_child {
+                        let child_prefix = format!("{path}[{index}]");
+                        collect_unknown(child, known_child, &child_prefix, skip, out);
+                    }
+                }
+            }
+            (Item::Value(Value::Array(values)), toml::Value::Array(known_items)) => {
+                for (index, (value, known_child)) in values.iter().zip(known_items).enumerate() {
+                    if let (Value::InlineTable(child), toml::Value::Table(known_child)) =
+                        (value, known_child)
+                    {
+                        let child_prefix = format!("{path}[{index}]");
+                        collect_unknown(child, known_child, &child_prefix, skip, out);
+                    }
+                }
+            }
+            (item, toml::Value::Table(known_child)) => {
+                if let Some(child) = item.as_table_like() {
+                    collect_unknown(child, known_child, &path, skip, out);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn is_empty_item(item: &Item) -> bool {
+    match item {
+        Item::Value(Value::Array(values)) => values.is_empty(),
+        Item::ArrayOfTables(tables) => tables.is_empty(),
+        item => item.as_table_like().is_some_and(TableLike::is_empty),
+    }
+}
+
+/// Suggests the known sibling key closest to `key`, if it's a likely typo.
+fn closest_key(key: &str, known: &toml::Table) -> Option<String> {
+    closest_match(key, known.keys().map(String::as_str)).map(str::to_string)
+}
+
+/// The candidate closest to `name` by edit distance, if it's a likely typo.
+pub(crate) fn closest_match<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+fn issue_at(
+    contents: &str,
+    kind: IssueKind,
+    span: Option<Range<usize>>,
+    message: String,
+) -> ConfigIssue {
+    let (line, column) = span.map_or((1, 1), |span| line_column(contents, span.start));
+    ConfigIssue {
+        kind,
+        line,
+        column,
+        message,
+    }
+}
+
+/// 1-based line and column (in characters) of byte `offset`.
+fn line_column(contents: &str, offset: usize) -> (usize, usize) {
+    let before = &contents[..offset.min(contents.len())];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (line, before[line_start..].chars().count() + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_template_has_no_issues() {
+        let issues = validate_str(crate::config::default_config_template());
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn reports_unknown_key_with_position_and_suggestion() {
+        let issues = validate_str("# zdx\n\nmodle = \"anthropic:claude-sonnet-4-6\"\n");
+
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        let issue = &issues[0];
+        assert_eq!(issue.kind, IssueKind::UnknownKey);
+        assert_eq!((issue.line, issue.column), (3, 1));
+        assert!(issue.message.contains("`modle`"));
+        assert!(issue.message.contains("did you mean `model`?"));
+    }
+
+    #[test]
+    fn dynamic_map_keys_are_not_unknown() {
+        let issues = validate_str(
+            "[providers.custom.local]\nbase_url = \"http://localhost:1234/v1\"\nmodels = [\"m\"]\n",
+        );
+        assert!(issues.is_empty(), "{issues:?}");
+    }
+
+    #[test]
+    fn keys_under_an_alias_are_checked_against_the_canonical_table() {
+        let issues = validate_str(
+            "[providers.openai_compat.local]\nbase_url = \"http://localhost:1234/v1\"\nmodles = [\"m\"]\n",
+        );
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].kind, IssueKind::UnknownKey);
+        assert!(
+            issues[0]
+                .message
+                .contains("`providers.openai_compat.local.modles`"),
+            "{issues:?}"
+        );
+    }
+
+    #[test]
+    fn reports_type_mismatch_with_position() {
+        let issues = validate_str("[agent_loop]\nmax_tool_iterations = \"lots\"\n");
+
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].kind, IssueKind::Error);
+        assert_eq!(issues[0].line, 2);
+    }
+
+    #[test]
+    fn reports_out_of_range_model_params() {
+        let issues = validate_str("model = \"x:y\"\n\n[model_params]\ntop_p = 1.5\n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].kind, IssueKind::Error);
+        assert_eq!(issues[0].line, 3);
+        assert!(issues[0].message.contains("top_p"), "{}", issues[0].message);
+    }
+
+    #[test]
+    fn reports_every_problem_in_the_file() {
+        let issues = validate_str(
+            "modle = \"x:y\"\n\n[tui]\nlanguag = \"en\"\n\n[telegram]\nbot_tokn = \"t\"\nmax_reply_messages = \"three\"\n",
+        );
+
+        let found: Vec<(IssueKind, usize)> = issues
+            .iter()
+            .map(|issue| (issue.kind, issue.line))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (IssueKind::UnknownKey, 1),
+                (IssueKind::UnknownKey, 4),
+                (IssueKind::UnknownKey, 7),
+                (IssueKind::Error, 8),
+            ],
+            "{issues:?}"
+        );
+        assert!(issues[1].message.contains("`tui.languag`"), "{issues:?}");
+        assert!(issues[1].message.contains("did you mean `language`?"));
+        assert!(
+            issues[2].message.contains("`telegram.bot_tokn`"),
+            "{issues:?}"
+        );
+        assert!(issues[3].message.contains("max_reply_messages"));
+    }
+
+    #[test]
+    fn deprecated_keys_are_mapped_and_reported() {
+        let deprecated = [DeprecatedKey {
+            old: "tui.lang",
+            new: "tui.language",
+        }];
+        let contents = "[tui]\nlang = \"pt-BR\"\n";
+
+        let (migrated, mapped) = migrate_with(contents, &deprecated).unwrap();
+        assert_eq!(mapped, deprecated);
+        let config: Config = toml::from_str(&migrated).unwrap();
+        assert_eq!(
+            config.tui.language,
+            crate::config::TuiLanguage::BrazilianPortuguese
+        );
+
+        let issues = validate_with(contents, &deprecated);
+        assert_eq!(issues.len(), 1, "{issues:?}");
+        assert_eq!(issues[0].kind, IssueKind::DeprecatedKey);
+        assert!(!issues[0].is_failure());
+    }
+
+    #[test]
+    fn errors_under_a_mapped_key_point_at_the_deprecated_name() {
+        let deprecated = [DeprecatedKey {
+            old: "tui.lang",
+            new: "tui.language",
+        }];
+        let issues = validate_with("[tui]\nlang = \"klingon\"\n", &deprecated);
+
+        let kinds: Vec<IssueKind> = issues.iter().map(|issue| issue.kind).collect();
+        assert_eq!(
+            kinds,
+            [IssueKind::DeprecatedKey, IssueKind::Error],
+            "{issues:?}"
+        );
+        assert_eq!(issues[1].line, 2);
+        assert!(issues[1].message.contains("`tui.lang`"), "{issues:?}");
+    }
+
+    #[test]
+    fn upgrade_migration_rewrites_file_once_per_version() {
+        let deprecated = [DeprecatedKey {
+            old: "tui.lang",
+            new: "tui.language",
+        }];
+        let dir = tempfile::tempdir().unwrap();
+        let config_path = dir.path().join("config.toml");
+        let stamp_path = dir.path().join("config_version");
+        fs::write(&config_path, "[tui]\n# UI language\nlang = \"pt-BR\"\n").unwrap();
+        fs::write(&stamp_path, "0.1.0").unwrap();
+
+        let report = migrate_on_upgrade_with(&config_path, &stamp_path, "0.2.0", &deprecated)
+            .unwrap()
+            .expect("file should be migrated");
+        assert_eq!(report.from_version.as_deref(), Some("0.1.0"));
+        assert_eq!(report.renamed, deprecated);
+        let migrated = fs::read_to_string(&config_path).unwrap();
+        assert!(migrated.contains("language = \"pt-BR\""), "{migrated}");
+        assert!(!migrated.contains("lang ="), "{migrated}");
+        assert!(
+            fs::read_to_string(&report.backup)
+                .unwrap()
+                .contains("lang = ")
+        );
+        assert_eq!(fs::read_to_string(&stamp_path).unwrap(), "0.2.0");
+
+        // Same version again: nothing to do, even if an old key reappears.
+        fs::write(&config_path, "[tui]\nlang = \"en\"\n").unwrap();
+        assert!(
+            migrate_on_upgrade_with(&config_path, &stamp_path, "0.2.0", &deprecated)
+                .unwrap()
+                .is_none()
+        );
+    }
+}
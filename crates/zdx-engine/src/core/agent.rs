@@ -3,30 +3,41 @@
 //! The agent drives the provider + tool loop and emits `AgentEvent`s
 //! via async channels. No direct stdout/stderr writes occur in this module.
 
+use std::borrow::Cow;
 use std::collections::HashSet;
-use std::future;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use futures_util::StreamExt;
 use serde_json::Value;
-use tokio::sync::mpsc;
-use tokio::task::{JoinHandle, JoinSet};
+use tokio::task::JoinSet;
 use tokio::time::{Duration, timeout};
 use tokio_util::sync::CancellationToken;
 
-use crate::config::{Config, TextVerbosity, ThinkingLevel};
+use crate::config::{Config, ModelParams, TextVerbosity, ThinkingLevel, TruncationStrategy};
+use crate::core::ask_user::AskUserHandle;
 use crate::core::events::{AgentEvent, ErrorKind, NoticeKind, ToolOutput, TurnStatus};
-use crate::core::interrupt::{self, InterruptedError};
+use crate::core::interrupt::InterruptedError;
+use crate::core::loop_guard::{LoopGuard, LoopTrip};
+use crate::core::partial_json;
+use crate::core::pinned_context;
+use crate::core::response_style;
+use crate::core::steering::{self, SteeringQueue};
+use crate::core::stream_resume::{self, ResumePlan};
+use crate::core::thinking_escalation::{Escalation, ThinkingEscalator};
+use crate::core::truncation::{self, TruncationLimit};
+use crate::providers::dry_run::{self, CapturedRequest};
 use crate::providers::{
     ChatContentBlock, ChatMessage, ContentBlockType, ProviderBuildContext, ProviderError,
     ProviderKind, ProviderStream, ReasoningBlock, ReplayToken, StreamEvent, StreamingProvider,
     resolve_provider,
 };
 use crate::subagents;
-use crate::tools::{ToolContext, ToolDefinition, ToolRegistry, ToolResult, ToolSet, todo_write};
+use crate::tools::protected_files::ProtectedApprovals;
+use crate::tools::{
+    ToolContext, ToolDefinition, ToolRegistry, ToolResult, ToolSet, check, todo_write,
+};
 
 /// Options for agent execution.
 #[derive(Debug, Clone)]
@@ -48,6 +59,80 @@ pub struct AgentOptions {
     pub activity_parent_thread_id: Option<String>,
     /// For `invoke_subagent`: the named subagent invoked.
     pub activity_subagent_name: Option<String>,
+    /// Soft-stop signal for this run. Once cancelled, tools already running
+    /// finish, further tool calls are skipped, and the model is asked for a
+    /// short wrap-up instead of continuing the loop. Hard cancellation still
+    /// goes through the `cancel` token passed to `run_turn_with_cancel`.
+    pub soft_stop: Option<CancellationToken>,
+    /// Steering notes from the user, drained between tool calls and appended
+    /// to the conversation before the next provider request.
+    pub steering: Option<SteeringQueue>,
+    /// Question channel to the user for `Ask_User`; `None` makes the tool
+    /// fail fast (exec, subagents).
+    pub ask_user: Option<AskUserHandle>,
+    /// Protected files the user approved reading. Share one set per thread
+    /// (or chat) so approvals carry across its turns but no further.
+    pub protected_approvals: ProtectedApprovals,
+    /// Per-turn overrides layered over `Config` for this run only.
+    pub turn: TurnOptions,
+}
+
+impl AgentOptions {
+    /// Tool selection for this run: the turn override, else `tool_config`.
+    pub fn tool_selection(&self) -> &ToolSelection {
+        self.turn
+            .tool_selection
+            .as_ref()
+            .unwrap_or(&self.tool_config.selection)
+    }
+}
+
+/// Parameters that may vary from one turn to the next.
+///
+/// Every field is optional; `None` falls back to `Config` (or, for tools,
+/// to `AgentOptions::tool_config`). Callers set these instead of cloning and
+/// mutating the session `Config`.
+#[derive(Debug, Clone, Default)]
+pub struct TurnOptions {
+    /// Model id in place of `Config::model`.
+    pub model: Option<String>,
+    /// Thinking level in place of `Config::thinking_level`. Also bypasses
+    /// `[thinking_escalation]`.
+    pub thinking_level: Option<ThinkingLevel>,
+    /// Tool selection in place of `ToolConfig::selection`.
+    pub tool_selection: Option<ToolSelection>,
+    /// Output token cap in place of `Config::max_tokens`.
+    pub max_tokens: Option<u32>,
+    /// JSON schema for the final answer, sent to providers with native
+    /// structured outputs (see `core::structured_output`).
+    pub output_schema: Option<crate::providers::OutputSchema>,
+}
+
+impl TurnOptions {
+    /// Model this turn runs on.
+    pub fn model<'a>(&'a self, config: &'a Config) -> &'a str {
+        self.model.as_deref().unwrap_or(&config.model)
+    }
+
+    /// Returns `config` with this turn's model, thinking, and token
+    /// overrides applied, borrowing it unchanged when there are none.
+    pub fn apply<'a>(&self, config: &'a Config) -> Cow<'a, Config> {
+        if self.model.is_none() && self.thinking_level.is_none() && self.max_tokens.is_none() {
+            return Cow::Borrowed(config);
+        }
+        let mut config = config.clone();
+        if let Some(model) = &self.model {
+            config.model.clone_from(model);
+        }
+        if let Some(level) = self.thinking_level {
+            config.thinking_level = level;
+        }
+        if let Some(max_tokens) = self.max_tokens {
+            config.max_tokens = Some(max_tokens);
+            config.model_params.max_output_tokens = None;
+        }
+        Cow::Owned(config)
+    }
 }
 
 /// Tool configuration for agent execution.
@@ -64,6 +149,14 @@ impl ToolConfig {
             selection,
         }
     }
+
+    /// Default selection over the built-ins plus `[[tools.custom]]` tools.
+    pub fn from_config(config: &Config) -> Self {
+        Self {
+            registry: ToolRegistry::from_config(config),
+            selection: ToolSelection::default(),
+        }
+    }
 }
 
 impl Default for ToolConfig {
@@ -79,9 +172,18 @@ impl Default for ToolConfig {
 #[derive(Debug, Clone)]
 pub enum ToolSelection {
     /// Use provider-configured tools if set; otherwise fall back to a tool set.
-    Auto { base: ToolSet, include: Vec<String> },
-    /// Use a named tool set (with optional includes).
-    ToolSet { base: ToolSet, include: Vec<String> },
+    /// `include` adds tools and `exclude` drops them (case-insensitive).
+    Auto {
+        base: ToolSet,
+        include: Vec<String>,
+        exclude: Vec<String>,
+    },
+    /// Use a named tool set (with optional includes/excludes).
+    ToolSet {
+        base: ToolSet,
+        include: Vec<String>,
+        exclude: Vec<String>,
+    },
     /// Use an explicit list of tools (full override).
     Explicit(Vec<String>),
     /// Use all tools in the registry.
@@ -93,71 +195,88 @@ impl Default for ToolSelection {
         ToolSelection::Auto {
             base: ToolSet::Default,
             include: Vec::new(),
+            exclude: Vec::new(),
         }
     }
 }
 
-/// Channel-based event sender (unbounded).
-///
-/// Used with `run_turn` for concurrent rendering and thread persistence.
-/// Events are wrapped in `Arc` for efficient cloning to multiple consumers.
-pub type AgentEventTx = mpsc::UnboundedSender<Arc<AgentEvent>>;
-
-/// Channel-based event receiver (unbounded).
-pub type AgentEventRx = mpsc::UnboundedReceiver<Arc<AgentEvent>>;
-
-/// Creates an unbounded event channel.
-pub fn create_event_channel() -> (AgentEventTx, AgentEventRx) {
-    mpsc::unbounded_channel()
-}
+impl ToolSelection {
+    /// Builds the selection for the `--tools` / `--deny-tools` CLI flags.
+    /// `tools` replaces the default set; `deny` drops tools from whichever
+    /// set applies. Names are comma-separated and checked case-insensitively
+    /// against `available`, with a suggestion for likely typos. Returns `None`
+    /// when neither flag is set.
+    ///
+    /// # Errors
+    /// Returns an error for an empty list or an unknown tool name.
+    pub fn from_cli_flags(
+        tools: Option<&str>,
+        deny: Option<&str>,
+        available: &[String],
+    ) -> Result<Option<Self>> {
+        let tools = tools
+            .map(|raw| parse_cli_tool_list("--tools", raw, available))
+            .transpose()?;
+        let deny = deny
+            .map(|raw| parse_cli_tool_list("--deny-tools", raw, available))
+            .transpose()?
+            .unwrap_or_default();
 
-/// Event sender wrapper with a single reliable send operation.
-#[derive(Clone)]
-pub struct EventSender {
-    tx: AgentEventTx,
+        Ok(match tools {
+            Some(tools) => Some(ToolSelection::Explicit(
+                tools
+                    .into_iter()
+                    .filter(|tool| !deny.iter().any(|d| d.eq_ignore_ascii_case(tool)))
+                    .collect(),
+            )),
+            None if deny.is_empty() => None,
+            None => Some(ToolSelection::Auto {
+                base: ToolSet::Default,
+                include: Vec::new(),
+                exclude: deny,
+            }),
+        })
+    }
 }
 
-impl EventSender {
-    /// Creates a new `EventSender` wrapping the given channel sender.
-    pub fn new(tx: AgentEventTx) -> Self {
-        Self { tx }
+fn parse_cli_tool_list(flag: &str, raw: &str, available: &[String]) -> Result<Vec<String>> {
+    let tools: Vec<String> = raw
+        .split(',')
+        .map(str::trim)
+        .filter(|tool| !tool.is_empty())
+        .map(str::to_string)
+        .collect();
+    if tools.is_empty() {
+        anyhow::bail!("{flag} requires a comma-separated list of tool names");
     }
 
-    /// Sends an event. Never blocks; ignored silently if the receiver has dropped.
-    pub fn send(&self, ev: AgentEvent) {
-        let _ = self.tx.send(Arc::new(ev));
+    let unknown: Vec<String> = tools
+        .iter()
+        .filter(|tool| !available.iter().any(|a| a.eq_ignore_ascii_case(tool)))
+        .map(|tool| {
+            let lowercase = tool.to_ascii_lowercase();
+            match crate::config_validation::closest_match(
+                &lowercase,
+                available.iter().map(String::as_str),
+            ) {
+                Some(suggestion) => format!("{tool} (did you mean '{suggestion}'?)"),
+                None => tool.clone(),
+            }
+        })
+        .collect();
+    if !unknown.is_empty() {
+        let mut available = available.to_vec();
+        available.sort();
+        anyhow::bail!(
+            "Unknown tool(s) in {flag}: {}. Available tools: {}",
+            unknown.join(", "),
+            available.join(", ")
+        );
     }
+    Ok(tools)
 }
 
-/// Spawns a broadcast task that distributes events to multiple consumers.
-///
-/// Each event is cloned (cheaply, via `Arc`) and sent to all subscribers.
-/// Closed channels are automatically removed.
-///
-/// The task exits when the source channel closes.
-///
-/// # Example
-///
-/// ```ignore
-/// let (agent_tx, agent_rx) = create_event_channel();
-/// let (render_tx, render_rx) = create_event_channel();
-/// let (persist_tx, persist_rx) = create_event_channel();
-///
-/// let broadcaster = spawn_broadcaster(agent_rx, vec![render_tx, persist_tx]);
-///
-/// // Agent sends to agent_tx
-/// // Renderer receives from render_rx
-/// // Persister receives from persist_rx
-/// ```
-pub fn spawn_broadcaster(rx: AgentEventRx, subscribers: Vec<AgentEventTx>) -> JoinHandle<()> {
-    tokio::spawn(async move {
-        let mut rx = rx;
-        let mut subscribers = subscribers;
-        while let Some(event) = rx.recv().await {
-            subscribers.retain(|tx| tx.send(Arc::clone(&event)).is_ok());
-        }
-    })
-}
+pub use crate::core::event_bus::{EventSender, EventSubscriber, create_event_channel};
 
 /// Builder for accumulating tool use data from streaming events.
 #[derive(Debug, Clone)]
@@ -639,11 +758,7 @@ fn emit_turn_error(err: &TurnError, sender: &EventSender, prior_message_count: u
         }
         TurnError::Provider(provider_err) => {
             sender.send(AgentEvent::TurnFinished {
-                status: TurnStatus::Failed {
-                    kind: provider_err.kind.clone().into(),
-                    message: provider_err.message.clone(),
-                    details: provider_err.details.clone(),
-                },
+                status: provider_failure(provider_err),
                 final_text: String::new(),
                 messages: Vec::new(),
                 prior_message_count,
@@ -655,6 +770,8 @@ fn emit_turn_error(err: &TurnError, sender: &EventSender, prior_message_count: u
                     kind: ErrorKind::Parse,
                     message: message.clone(),
                     details: details.clone(),
+                    http_status: None,
+                    request_id: None,
                 },
                 final_text: String::new(),
                 messages: Vec::new(),
@@ -667,6 +784,8 @@ fn emit_turn_error(err: &TurnError, sender: &EventSender, prior_message_count: u
                     kind: ErrorKind::Internal,
                     message: err.to_string(),
                     details: None,
+                    http_status: None,
+                    request_id: None,
                 },
                 final_text: String::new(),
                 messages: Vec::new(),
@@ -676,6 +795,16 @@ fn emit_turn_error(err: &TurnError, sender: &EventSender, prior_message_count: u
     }
 }
 
+fn provider_failure(provider_err: &ProviderError) -> TurnStatus {
+    TurnStatus::Failed {
+        kind: provider_err.kind.clone().into(),
+        message: provider_err.message.clone(),
+        details: provider_err.details.clone(),
+        http_status: provider_err.status,
+        request_id: provider_err.request_id.clone(),
+    }
+}
+
 fn emit_turn_error_with_messages(
     err: &TurnError,
     messages: &[ChatMessage],
@@ -685,11 +814,7 @@ fn emit_turn_error_with_messages(
     match err {
         TurnError::Provider(provider_err) => {
             sender.send(AgentEvent::TurnFinished {
-                status: TurnStatus::Failed {
-                    kind: provider_err.kind.clone().into(),
-                    message: provider_err.message.clone(),
-                    details: provider_err.details.clone(),
-                },
+                status: provider_failure(provider_err),
                 final_text: String::new(),
                 messages: messages.to_vec(),
                 prior_message_count,
@@ -748,12 +873,29 @@ fn merge_tool_defs(
     base
 }
 
+fn exclude_tool_defs(mut tools: Vec<ToolDefinition>, exclude: &[String]) -> Vec<ToolDefinition> {
+    if !exclude.is_empty() {
+        tools.retain(|tool| {
+            !exclude
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&tool.name))
+        });
+    }
+    tools
+}
+
 /// Timeout for stream polling to allow interrupt checks.
 const STREAM_POLL_TIMEOUT: Duration = Duration::from_millis(250);
 /// Abort threshold for repeated malformed-only tool turns.
 const MAX_CONSECUTIVE_MALFORMED_TOOL_TURNS: usize = 3;
 const MALFORMED_TOOL_LOOP_ABORT_MESSAGE: &str =
     "Aborting after repeated malformed tool calls with invalid JSON input";
+/// Ephemeral user note sent with the wrap-up request after a soft stop. It is
+/// not persisted to the thread.
+const SOFT_STOP_WRAP_UP_PROMPT: &str = "The user asked you to stop. Do not call any more tools. \
+     Briefly summarize what you did and what remains, then end your turn.";
+/// Tool result recorded for tool calls skipped after a soft stop.
+const SOFT_STOP_SKIPPED_MESSAGE: &str = "Skipped: user asked to stop";
 /// Maximum number of automatic retries for transient provider errors.
 const MAX_RETRIES: u32 = 3;
 /// Base delay for exponential backoff (milliseconds).
@@ -761,7 +903,7 @@ const RETRY_BASE_DELAY_MS: u64 = 2000;
 
 /// Runs a single turn of the agent using async channels.
 ///
-/// Events are sent via a bounded `mpsc` channel for concurrent rendering
+/// Events are published on `sender`'s event bus for concurrent rendering
 /// and thread persistence.
 ///
 /// Returns the final assistant text and the updated message history.
@@ -774,7 +916,7 @@ pub async fn run_turn(
     options: &AgentOptions,
     system_prompt: Option<&str>,
     thread_id: Option<&str>,
-    tx: AgentEventTx,
+    sender: EventSender,
 ) -> Result<(String, Vec<ChatMessage>)> {
     run_turn_with_cancel(
         messages,
@@ -782,7 +924,7 @@ pub async fn run_turn(
         options,
         system_prompt,
         thread_id,
-        tx,
+        sender,
         None,
     )
     .await
@@ -796,11 +938,11 @@ pub async fn run_turn_with_cancel(
     options: &AgentOptions,
     system_prompt: Option<&str>,
     thread_id: Option<&str>,
-    tx: AgentEventTx,
+    sender: EventSender,
     cancel: Option<CancellationToken>,
 ) -> Result<(String, Vec<ChatMessage>)> {
-    let sender = EventSender::new(tx);
     let initial_message_count = messages.len();
+    let cancel = cancel.unwrap_or_default();
     match run_turn_inner(
         messages,
         config,
@@ -808,7 +950,7 @@ pub async fn run_turn_with_cancel(
         system_prompt,
         thread_id,
         &sender,
-        cancel.as_ref(),
+        &cancel,
     )
     .await
     {
@@ -840,10 +982,33 @@ async fn run_turn_inner(
     system_prompt: Option<&str>,
     thread_id: Option<&str>,
     sender: &EventSender,
-    cancel: Option<&CancellationToken>,
+    cancel: &CancellationToken,
 ) -> RunTurnResult {
-    let setup = build_run_turn_setup(config, options, thread_id)
-        .map_err(|e| (TurnError::from_anyhow(e), messages.clone()))?;
+    let styled_prompt = thread_id
+        .and_then(|id| response_style::load(id, config))
+        .map(|style| pinned_context::append_to_prompt(system_prompt, style));
+    let system_prompt = styled_prompt.as_deref().or(system_prompt);
+    let pinned_prompt = thread_id
+        .and_then(|id| pinned_context::load(id, &options.root))
+        .map(|pinned| pinned_context::append_to_prompt(system_prompt, pinned));
+    let system_prompt = pinned_prompt.as_deref().or(system_prompt);
+    // Boxed so a per-turn `Config` copy doesn't bloat this future.
+    let config = Box::new(options.turn.apply(config));
+    let config: &Config = &config;
+    let mut escalator = ThinkingEscalator::new(
+        &config.thinking_escalation,
+        crate::models::model_supports_reasoning(&config.model),
+    )
+    .filter(|_| options.turn.thinking_level.is_none());
+    let mut setup = match &escalator {
+        Some(escalator) => build_run_turn_setup(
+            &config_with_thinking_level(config, escalator.level()),
+            options,
+            thread_id,
+        ),
+        None => build_run_turn_setup(config, options, thread_id),
+    }
+    .map_err(|e| (TurnError::from_anyhow(e), messages.clone()))?;
     let _run_guard = crate::agent_activity::start(crate::agent_activity::StartParams {
         thread_id,
         surface: options.surface.as_deref(),
@@ -857,10 +1022,30 @@ async fn run_turn_inner(
     let mut messages = messages;
     let initial_message_count = messages.len();
     let mut consecutive_malformed_tool_turns = 0usize;
+    let mut loop_guard = LoopGuard::new(&config.agent_loop);
+    let mut truncation_noticed = false;
 
     loop {
         ensure_not_interrupted(None, cancel).map_err(|e| (e, messages.clone()))?;
 
+        let mut request_messages: Cow<'_, [ChatMessage]> = Cow::Borrowed(&messages);
+        if let Some(truncated) = truncate_history(&setup, &messages, system_prompt, thread_id) {
+            if !truncation_noticed {
+                truncation_noticed = true;
+                notify_history_truncated(sender, messages.len() - truncated.len());
+            }
+            request_messages = Cow::Owned(truncated);
+        }
+        // After a soft stop, the next request carries a one-off wrap-up note
+        // so the model summarizes instead of calling more tools. It is added
+        // after truncation so it never counts as the newest turn.
+        let wrapping_up = soft_stop_requested(options);
+        if wrapping_up {
+            request_messages
+                .to_mut()
+                .push(ChatMessage::user(SOFT_STOP_WRAP_UP_PROMPT));
+        }
+
         // Unified retry loop for transient provider errors.
         //
         // Covers two cases with the same backoff/telemetry:
@@ -869,10 +1054,23 @@ async fn run_turn_inner(
         //      — but only when nothing user-visible has been emitted yet, so a
         //      retry doesn't force the UI to rewind partial text / tool calls /
         //      reasoning deltas.
+        //
+        // Once output is visible, a retryable mid-stream failure resumes the
+        // response instead (see `stream_resume`): the partial turn seeds the
+        // next stream and the request becomes a continuation request.
         let mut stream_state = {
             let mut attempt: u32 = 0;
+            let mut resumes: u32 = 0;
+            let mut resume_seed: Option<AssistantTurnBuilder> = None;
+            let mut continuation: Option<Vec<ChatMessage>> = None;
             'retry: loop {
-                ensure_not_interrupted(None, cancel).map_err(|e| (e, messages.clone()))?;
+                ensure_not_interrupted(None, cancel).map_err(|e| match &resume_seed {
+                    Some(seed) => (
+                        interrupted_turn_from_stream(&messages, seed.clone()),
+                        messages.clone(),
+                    ),
+                    None => (e, messages.clone()),
+                })?;
 
                 let outcome: std::result::Result<
                     StreamState,
@@ -881,7 +1079,7 @@ async fn run_turn_inner(
                     let request_started_at = Instant::now();
                     match request_stream(
                         &setup.client,
-                        &messages,
+                        continuation.as_deref().unwrap_or(&request_messages),
                         &setup.tools,
                         system_prompt,
                         cancel,
@@ -889,14 +1087,19 @@ async fn run_turn_inner(
                     .await
                     {
                         Ok(stream) => {
+                            notify_usage_limits(sender);
                             match consume_stream(
                                 stream,
                                 &messages,
                                 sender,
                                 cancel,
-                                &setup.model,
+                                resume_seed.clone().unwrap_or_else(|| {
+                                    AssistantTurnBuilder::new(setup.model.clone())
+                                }),
                                 &setup.provider,
+                                setup.model_params,
                                 request_started_at,
+                                config.stream_idle_timeout(),
                             )
                             .await
                             {
@@ -907,7 +1110,10 @@ async fn run_turn_inner(
                                 }
                             }
                         }
-                        Err(err) => Err((err, true, None)),
+                        Err(err) => {
+                            notify_usage_limits(sender);
+                            Err((err, true, None))
+                        }
                     }
                 };
 
@@ -919,7 +1125,33 @@ async fn run_turn_inner(
                         state.flush_pending_usage(sender);
                         break 'retry state;
                     }
-                    Err((err, can_retry, state)) => {
+                    Err((err, can_retry, mut state)) => {
+                        if !can_retry
+                            && resumes < stream_resume::MAX_STREAM_RESUMES
+                            && let TurnError::Provider(provider_err) = &err
+                            && provider_err.is_retryable()
+                            && let Some(partial) = state.take()
+                        {
+                            resumes += 1;
+                            match resume_stream(partial, provider_err, resumes, sender) {
+                                ResumeStep::Run(state) => break 'retry state,
+                                ResumeStep::Continue(seed) => {
+                                    continuation = Some(stream_resume::continuation_messages(
+                                        &request_messages,
+                                        &seed,
+                                    ));
+                                    resume_seed = Some(seed);
+                                    // A cancel during the pause is reported
+                                    // with the partial turn at the loop top.
+                                    let _ = wait_for_retry_delay(
+                                        Duration::from_millis(RETRY_BASE_DELAY_MS),
+                                        cancel,
+                                    )
+                                    .await;
+                                    continue 'retry;
+                                }
+                            }
+                        }
                         let retry_err = match &err {
                             TurnError::Provider(p) if p.is_retryable() && can_retry => {
                                 Some(p.clone())
@@ -1001,6 +1233,21 @@ async fn run_turn_inner(
 
         emit_stop_reason_notice(stream_state.stop_reason.as_deref(), sender);
 
+        if stream_state.needs_tool_execution() && (wrapping_up || soft_stop_requested(options)) {
+            let turn_text = skip_tool_turn(&mut messages, &mut stream_state.turn, sender);
+            if wrapping_up {
+                sender.send(AgentEvent::TurnFinished {
+                    status: TurnStatus::Completed,
+                    final_text: turn_text.clone(),
+                    messages: messages.clone(),
+                    prior_message_count: initial_message_count,
+                });
+                return Ok((turn_text, messages));
+            }
+            apply_steering(&mut messages, options);
+            continue;
+        }
+
         if stream_state.needs_tool_execution() {
             let stats = process_tool_turn(
                 &mut messages,
@@ -1029,6 +1276,59 @@ async fn run_turn_inner(
             } else {
                 consecutive_malformed_tool_turns = 0;
             }
+            // `process_tool_turn` appends the assistant message, then its results.
+            let trip = messages
+                .iter()
+                .rev()
+                .nth(1)
+                .and_then(|assistant| loop_guard.record_tool_turn(assistant));
+            if let Some(trip) = trip {
+                return Ok(finish_loop_detected_turn(
+                    trip,
+                    stats.turn_text,
+                    messages,
+                    sender,
+                    initial_message_count,
+                ));
+            }
+            if let Some(escalation) = escalator
+                .as_mut()
+                .and_then(|escalator| escalator.record_tool_round(stats.failed))
+            {
+                apply_escalation(&mut setup, escalation, config, options, thread_id, sender)
+                    .map_err(|e| (TurnError::from_anyhow(e), messages.clone()))?;
+            }
+            if stats.edited > 0 {
+                Box::pin(run_auto_check(
+                    config,
+                    &setup.tool_ctx.root,
+                    &mut messages,
+                    sender,
+                    cancel,
+                ))
+                .await;
+            }
+            apply_steering(&mut messages, options);
+            continue;
+        }
+
+        // A steering note that arrived while the model was answering gets
+        // one more round instead of being dropped with the finished turn.
+        if !wrapping_up && has_pending_steering(options) {
+            commit_assistant_turn(&mut messages, stream_state.turn, sender);
+            apply_steering(&mut messages, options);
+            continue;
+        }
+
+        // An uncertain final answer is dropped and retried one level higher.
+        if !wrapping_up
+            && let Some(escalation) = escalator.as_mut().and_then(|escalator| {
+                escalator.record_final_answer(&stream_state.turn.final_text())
+            })
+        {
+            discard_assistant_turn(stream_state.turn, sender);
+            apply_escalation(&mut setup, escalation, config, options, thread_id, sender)
+                .map_err(|e| (TurnError::from_anyhow(e), messages.clone()))?;
             continue;
         }
 
@@ -1053,32 +1353,111 @@ struct RunTurnSetup {
     enabled_tools: HashSet<String>,
     tool_ctx: ToolContext,
     tool_registry: ToolRegistry,
+    /// Client-side history limit from `[providers.<id>].truncation`.
+    truncation: Option<TruncationLimit>,
+    /// `[model_params]` actually sent (after provider gating); `None` when
+    /// everything is left at provider defaults.
+    model_params: Option<ModelParams>,
 }
 
-fn build_run_turn_setup(
+/// Builds the provider client for `config.model` the same way a turn does,
+/// for callers that stream a single request outside the agent loop (e.g.
+/// `zdx bench`). Returns the client and the id of the provider serving it.
+///
+/// # Errors
+/// Returns an error if the provider configuration cannot be resolved.
+pub fn build_provider_client(
+    config: &Config,
+    root: &Path,
+) -> Result<(Box<dyn StreamingProvider>, String)> {
+    let options = AgentOptions {
+        root: root.to_path_buf(),
+        tool_config: ToolConfig::default(),
+        surface: None,
+        text_verbosity: None,
+        service_tier: None,
+        activity_kind: None,
+        activity_parent_thread_id: None,
+        activity_subagent_name: None,
+        soft_stop: None,
+        steering: None,
+        ask_user: None,
+        protected_approvals: ProtectedApprovals::default(),
+        turn: TurnOptions::default(),
+    };
+    let setup = build_run_turn_setup(config, &options, None)?;
+    Ok((setup.client, setup.provider))
+}
+
+/// Builds the exact provider request the first step of a turn would send
+/// (system prompt, messages, tools, thinking config) without sending it.
+/// Credentials are redacted.
+///
+/// # Errors
+/// Returns an error if the provider client or request cannot be built.
+pub async fn dry_run_request(
+    messages: &[ChatMessage],
     config: &Config,
     options: &AgentOptions,
+    system_prompt: Option<&str>,
     thread_id: Option<&str>,
-) -> Result<RunTurnSetup> {
-    if let Some((custom_cfg, bare_model)) =
+) -> Result<CapturedRequest> {
+    let setup = build_run_turn_setup(&options.turn.apply(config), options, thread_id)?;
+    let request = dry_run::capture(setup.client.stream_messages(
+        messages,
+        &setup.tools,
+        system_prompt,
+    ))
+    .await?;
+    Ok(request.redacted())
+}
+
+/// Setup for models served outside the built-in `ProviderKind`s, in
+/// resolution order: `[providers.custom]`, then the code-level provider
+/// registry. `None` means a built-in provider.
+fn build_non_builtin_run_turn_setup(
+    config: &Config,
+    options: &AgentOptions,
+    thread_id: Option<&str>,
+) -> Option<Result<RunTurnSetup>> {
+    if let Some((custom_cfg, provider_name, bare_model)) =
         config.providers.custom_provider_for_model(&config.model)
     {
-        let provider_name = config
-            .model
-            .split_once(':')
-            .or_else(|| config.model.split_once('/'))
-            .map(|(prefix, _)| prefix.trim().to_string())
-            .unwrap_or_default();
-        return build_custom_run_turn_setup(
-            config,
-            options,
-            thread_id,
-            custom_cfg,
-            bare_model,
-            provider_name,
+        return Some(
+            custom_cfg
+                .resolve_api_key_or_placeholder()
+                .and_then(|api_key| {
+                    build_custom_run_turn_setup(
+                        config,
+                        options,
+                        thread_id,
+                        custom_cfg,
+                        api_key,
+                        bare_model,
+                        provider_name,
+                    )
+                }),
         );
     }
 
+    crate::providers::registry::lookup(&config.model)
+        .map(|registered| build_registered_run_turn_setup(config, options, thread_id, registered))
+}
+
+fn build_run_turn_setup(
+    config: &Config,
+    options: &AgentOptions,
+    thread_id: Option<&str>,
+) -> Result<RunTurnSetup> {
+    crate::offline::ensure_local_model(config)?;
+    config
+        .model_params
+        .validate()
+        .map_err(|message| anyhow!("Invalid [model_params] config: {message}"))?;
+    if let Some(setup) = build_non_builtin_run_turn_setup(config, options, thread_id) {
+        return setup;
+    }
+
     let selection = resolve_provider(&config.model);
     let provider = selection.kind;
     let max_tokens = config.effective_max_tokens_for(&config.model);
@@ -1087,12 +1466,17 @@ fn build_run_turn_setup(
     } else {
         ThinkingLevel::Off
     };
+    let model_params = gate_model_params(config.model_params, provider, thinking_level);
     let provider_config = config.providers.get(provider);
+    let truncation_strategy = provider_config.truncation_strategy();
+    // Only the OpenAI Responses API truncates server-side.
+    let server_truncation =
+        provider == ProviderKind::OpenAI && truncation_strategy == TruncationStrategy::Auto;
     let provider_ctx = ProviderBuildContext {
         model: &selection.model,
         provider,
         max_tokens,
-        config_max_tokens: config.max_tokens,
+        config_max_tokens: config.max_output_tokens(),
         thinking_level,
         cache_key: thread_id.map(str::to_owned),
         text_verbosity: options.text_verbosity,
@@ -1116,7 +1500,17 @@ fn build_run_turn_setup(
         } else {
             None
         },
+        output_schema: options.turn.output_schema.as_ref(),
+        truncation: server_truncation.then_some("auto"),
+        sampling: model_params.into(),
+        http: provider_config
+            .http_client()
+            .with_context(|| format!("Invalid [providers.{}.http] config", provider.id()))?,
+        extra: provider_config.extra_params(),
     };
+    for warning in crate::providers::extra::check(provider, &provider_config.extra) {
+        tracing::warn!("{warning}");
+    }
     let client = provider.build_client(&provider_ctx)?;
     let tool_ctx = ToolContext::new(
         options
@@ -1126,7 +1520,9 @@ fn build_run_turn_setup(
         config.tool_timeout(),
     )
     .with_current_thread_id(thread_id)
-    .with_config(config);
+    .with_config(config)
+    .with_ask_user(options.ask_user.clone())
+    .with_protected_approvals(options.protected_approvals.clone());
     let tool_registry = options.tool_config.registry.clone();
     let tools = resolve_tools(
         config,
@@ -1137,6 +1533,14 @@ fn build_run_turn_setup(
     );
     let enabled_tools = tools.iter().map(|t| t.name.clone()).collect();
 
+    let truncation = TruncationLimit::resolve(
+        truncation_strategy,
+        provider_config.truncation_keep_turns(),
+        server_truncation,
+        crate::models::ModelOption::find_by_id(&config.model).map_or(0, |m| m.context_limit),
+        max_tokens,
+    );
+
     Ok(RunTurnSetup {
         model: selection.model,
         provider: provider.id().to_string(),
@@ -1146,6 +1550,8 @@ fn build_run_turn_setup(
         enabled_tools,
         tool_ctx,
         tool_registry,
+        truncation,
+        model_params: effective_model_params(model_params),
     })
 }
 
@@ -1156,6 +1562,7 @@ fn build_custom_run_turn_setup(
     options: &AgentOptions,
     thread_id: Option<&str>,
     custom_cfg: &crate::config::CustomProviderConfig,
+    api_key: String,
     bare_model: String,
     provider_name: String,
 ) -> Result<RunTurnSetup> {
@@ -1167,28 +1574,27 @@ fn build_custom_run_turn_setup(
         ThinkingLevel::Off
     };
     let base_url = custom_cfg.effective_base_url()?;
-    let api_key = custom_cfg.resolve_api_key()?;
-    let client = crate::providers::openai_compatible::build_custom(
-        base_url,
-        api_key,
-        bare_model.clone(),
-        config.max_tokens,
-        thread_id.map(str::to_owned),
-        thinking_enabled,
-    );
-
-    let tool_ctx = ToolContext::new(
-        options
-            .root
-            .canonicalize()
-            .unwrap_or_else(|_| options.root.clone()),
-        config.tool_timeout(),
-    )
-    .with_current_thread_id(thread_id)
-    .with_config(config);
-    let tool_registry = options.tool_config.registry.clone();
-    let provider_config = crate::config::ProviderConfig::default();
-    let tools = resolve_tools(config, options, &provider_config, false, &tool_registry);
+    let http = custom_cfg
+        .http_client()
+        .with_context(|| format!("Invalid HTTP config for provider '{provider_name}'"))?;
+    let section = format!("custom.{provider_name}");
+    for warning in crate::providers::extra::check_custom(&section, &custom_cfg.extra) {
+        tracing::warn!("{warning}");
+    }
+    let client = crate::providers::http::with_client(http, || {
+        crate::providers::extra::with_params(custom_cfg.extra_params(), || {
+            crate::providers::openai_compatible::build_custom(
+                base_url,
+                api_key,
+                bare_model.clone(),
+                config.max_output_tokens(),
+                thread_id.map(str::to_owned),
+                thinking_enabled,
+                config.model_params.into(),
+            )
+        })
+    });
+    let (tool_ctx, tool_registry, tools) = default_tool_setup(config, options, thread_id);
     let enabled_tools = tools.iter().map(|t| t.name.clone()).collect();
 
     Ok(RunTurnSetup {
@@ -1200,9 +1606,102 @@ fn build_custom_run_turn_setup(
         enabled_tools,
         tool_ctx,
         tool_registry,
+        truncation: None,
+        model_params: effective_model_params(config.model_params),
     })
 }
 
+/// Builds the run-turn setup for a provider added through
+/// `providers::registry`. The client's reported capabilities decide whether
+/// thinking and tools are sent.
+fn build_registered_run_turn_setup(
+    config: &Config,
+    options: &AgentOptions,
+    thread_id: Option<&str>,
+    registered: crate::providers::registry::RegisteredProvider,
+) -> Result<RunTurnSetup> {
+    let client = registered
+        .factory
+        .build(&crate::providers::registry::ProviderRequest {
+            model: &registered.model,
+            thinking_level: config.thinking_level,
+            max_tokens: config.max_output_tokens(),
+            cache_key: thread_id,
+            sampling: config.model_params.into(),
+        })
+        .with_context(|| format!("Failed to build provider '{}'", registered.id))?;
+    let capabilities = client.capabilities();
+    let thinking_level = if capabilities.reasoning {
+        config.thinking_level
+    } else {
+        ThinkingLevel::Off
+    };
+    let (tool_ctx, tool_registry, mut tools) = default_tool_setup(config, options, thread_id);
+    if !capabilities.tools {
+        tools.clear();
+    }
+    let enabled_tools = tools.iter().map(|t| t.name.clone()).collect();
+
+    Ok(RunTurnSetup {
+        model: registered.model,
+        provider: registered.id,
+        thinking_level,
+        client,
+        tools,
+        enabled_tools,
+        tool_ctx,
+        tool_registry,
+        truncation: None,
+        model_params: effective_model_params(config.model_params),
+    })
+}
+
+/// Drops the `[model_params]` controls `provider` rejects at this thinking
+/// level, warning about each.
+fn gate_model_params(
+    params: ModelParams,
+    provider: ProviderKind,
+    thinking_level: ThinkingLevel,
+) -> ModelParams {
+    let (gated, dropped) = provider.gate_model_params(params, thinking_level.is_enabled());
+    if !dropped.is_empty() {
+        tracing::warn!(
+            "Dropping {} from [model_params]: not accepted by {} for this request",
+            dropped.join(", "),
+            provider.id()
+        );
+    }
+    gated
+}
+
+/// `params` as recorded on usage events: `None` when nothing is overridden.
+fn effective_model_params(params: ModelParams) -> Option<ModelParams> {
+    (!params.is_empty()).then_some(params)
+}
+
+/// Tool context and default tool set for providers without a
+/// `ProviderKind` (custom and registered ones).
+fn default_tool_setup(
+    config: &Config,
+    options: &AgentOptions,
+    thread_id: Option<&str>,
+) -> (ToolContext, ToolRegistry, Vec<ToolDefinition>) {
+    let tool_ctx = ToolContext::new(
+        options
+            .root
+            .canonicalize()
+            .unwrap_or_else(|_| options.root.clone()),
+        config.tool_timeout(),
+    )
+    .with_current_thread_id(thread_id)
+    .with_config(config)
+    .with_ask_user(options.ask_user.clone());
+    let tool_registry = options.tool_config.registry.clone();
+    let provider_config = crate::config::ProviderConfig::default();
+    let tools = resolve_tools(config, options, &provider_config, false, &tool_registry);
+    (tool_ctx, tool_registry, tools)
+}
+
 /// Resolves the tool list that would be sent to the LLM for the given
 /// config / agent options / provider.
 ///
@@ -1228,15 +1727,46 @@ pub fn resolve_active_tools(
     )
 }
 
-fn resolve_tools(
+/// Names of the tools `options` would enable for `provider`, without the
+/// `Invoke_Subagent` description enrichment (so no filesystem access).
+#[must_use]
+pub fn selected_tool_names(
     config: &Config,
     options: &AgentOptions,
+    provider: ProviderKind,
+) -> Vec<String> {
+    let provider_config = config.providers.get(provider);
+    let use_codex_toolset = matches!(provider, ProviderKind::OpenAI | ProviderKind::OpenAICodex);
+    let mut tools = select_tools(
+        options.tool_selection(),
+        provider_config,
+        use_codex_toolset,
+        &options.tool_config.registry,
+    );
+    if !config.subagents.enabled {
+        tools.retain(|tool| !tool.name.eq_ignore_ascii_case("Invoke_Subagent"));
+    }
+    if config.offline {
+        tools.retain(|tool| !options.tool_config.registry.requires_network(&tool.name));
+    }
+    tools.into_iter().map(|tool| tool.name).collect()
+}
+
+/// Applies `selection`. User-supplied tools (custom commands, plugins) are
+/// enabled by default alongside the base set, like `include`.
+fn select_tools(
+    selection: &ToolSelection,
     provider_config: &crate::config::ProviderConfig,
     use_codex_toolset: bool,
     tool_registry: &ToolRegistry,
 ) -> Vec<ToolDefinition> {
-    let mut tools = match &options.tool_config.selection {
-        ToolSelection::Auto { base, include } => {
+    let user_tools = tool_registry.user_tool_names();
+    match selection {
+        ToolSelection::Auto {
+            base,
+            include,
+            exclude,
+        } => {
             let base_tools = if provider_config.tools.is_some() {
                 tool_registry.tools_for_provider(provider_config)
             } else {
@@ -1247,16 +1777,41 @@ fn resolve_tools(
                 };
                 tool_registry.tools_for_set(tool_set)
             };
-            merge_tool_defs(base_tools, include, tool_registry)
+            let base_tools = merge_tool_defs(base_tools, user_tools, tool_registry);
+            exclude_tool_defs(merge_tool_defs(base_tools, include, tool_registry), exclude)
         }
-        ToolSelection::ToolSet { base, include } => {
-            merge_tool_defs(tool_registry.tools_for_set(*base), include, tool_registry)
+        ToolSelection::ToolSet {
+            base,
+            include,
+            exclude,
+        } => {
+            let base_tools = merge_tool_defs(
+                tool_registry.tools_for_set(*base),
+                user_tools,
+                tool_registry,
+            );
+            exclude_tool_defs(merge_tool_defs(base_tools, include, tool_registry), exclude)
         }
         ToolSelection::Explicit(names) => {
             tool_registry.tools_from_names(names.iter().map(String::as_str))
         }
         ToolSelection::All => tool_registry.definitions(),
-    };
+    }
+}
+
+fn resolve_tools(
+    config: &Config,
+    options: &AgentOptions,
+    provider_config: &crate::config::ProviderConfig,
+    use_codex_toolset: bool,
+    tool_registry: &ToolRegistry,
+) -> Vec<ToolDefinition> {
+    let mut tools = select_tools(
+        options.tool_selection(),
+        provider_config,
+        use_codex_toolset,
+        tool_registry,
+    );
 
     if config.subagents.enabled {
         match subagents::list_summaries(&options.root) {
@@ -1278,6 +1833,9 @@ fn resolve_tools(
     } else {
         tools.retain(|tool| !tool.name.eq_ignore_ascii_case("Invoke_Subagent"));
     }
+    if config.offline {
+        tools.retain(|tool| !tool_registry.requires_network(&tool.name));
+    }
 
     tools
 }
@@ -1316,6 +1874,9 @@ struct StreamState {
     /// events for per-provider attribution. Empty in unit tests that build a
     /// `StreamState` directly and don't assert provider.
     provider: String,
+    /// `[model_params]` sent with this request, attached to emitted
+    /// `UsageUpdate` events.
+    model_params: Option<ModelParams>,
     /// When this attempt's provider request was initiated (before the stream
     /// opened). Used to derive per-request latency on the terminal usage
     /// event. Defaults to construction time; `consume_stream` overrides it
@@ -1335,6 +1896,7 @@ impl StreamState {
             pending_usage: crate::providers::Usage::default(),
             emitted_visible_content: false,
             provider: String::new(),
+            model_params: None,
             request_started_at: Instant::now(),
             first_token_at: None,
         }
@@ -1406,33 +1968,74 @@ impl StreamState {
             provider: self.provider.clone(),
             duration_ms,
             ttft_ms,
+            model_params: self.model_params,
         });
     }
 }
 
 fn ensure_not_interrupted(
     partial_content: Option<String>,
-    cancel: Option<&CancellationToken>,
+    cancel: &CancellationToken,
 ) -> TurnResult<()> {
-    if interrupt::is_interrupted() || cancel.is_some_and(CancellationToken::is_cancelled) {
+    if cancel.is_cancelled() {
         return Err(TurnError::interrupted(partial_content));
     }
     Ok(())
 }
 
+/// Applies `[providers.<id>].truncation` to the request history, keeping the
+/// thread's pinned turns. Returns `None` when nothing is dropped.
+fn truncate_history(
+    setup: &RunTurnSetup,
+    messages: &[ChatMessage],
+    system_prompt: Option<&str>,
+    thread_id: Option<&str>,
+) -> Option<Vec<ChatMessage>> {
+    let limit = setup.truncation?;
+    let pinned = thread_id
+        .map(|id| {
+            crate::core::thread_persistence::read_thread_pinned_turns(id).unwrap_or_else(|err| {
+                tracing::warn!(thread_id = id, %err, "Failed to read pinned turns");
+                Vec::new()
+            })
+        })
+        .unwrap_or_default();
+    let system_tokens = system_prompt.map_or(0, |prompt| prompt.len() as u64 / 4);
+    truncation::truncate_messages(messages, &pinned, limit, system_tokens)
+}
+
+fn notify_history_truncated(sender: &EventSender, dropped: usize) {
+    sender.send(AgentEvent::Notice {
+        kind: NoticeKind::HistoryTruncated,
+        message: format!(
+            "Left {dropped} older message{} out of the request (conversation truncation).",
+            if dropped == 1 { "" } else { "s" }
+        ),
+        details: Some("Use /pin to keep a turn in the request.".to_string()),
+    });
+}
+
+/// Surfaces subscription usage-limit warnings queued by the last response.
+fn notify_usage_limits(sender: &EventSender) {
+    for message in crate::providers::usage_limits::take_warnings() {
+        sender.send(AgentEvent::Notice {
+            kind: NoticeKind::UsageLimit,
+            message,
+            details: Some("Run /limits for details.".to_string()),
+        });
+    }
+}
+
 async fn request_stream(
     client: &dyn StreamingProvider,
     messages: &[ChatMessage],
     tools: &[ToolDefinition],
     system_prompt: Option<&str>,
-    cancel: Option<&CancellationToken>,
+    cancel: &CancellationToken,
 ) -> TurnResult<ProviderStream> {
     let stream_result = tokio::select! {
         biased;
-        () = interrupt::wait_for_interrupt() => {
-            return Err(TurnError::interrupted(None));
-        }
-        () = wait_for_cancel(cancel) => {
+        () = cancel.cancelled() => {
             return Err(TurnError::interrupted(None));
         }
         result = client.stream_messages(messages, tools, system_prompt) => result,
@@ -1443,14 +2046,10 @@ async fn request_stream(
     }
 }
 
-async fn wait_for_retry_delay(
-    delay: Duration,
-    cancel: Option<&CancellationToken>,
-) -> TurnResult<()> {
+async fn wait_for_retry_delay(delay: Duration, cancel: &CancellationToken) -> TurnResult<()> {
     tokio::select! {
         biased;
-        () = interrupt::wait_for_interrupt() => Err(TurnError::interrupted(None)),
-        () = wait_for_cancel(cancel) => Err(TurnError::interrupted(None)),
+        () = cancel.cancelled() => Err(TurnError::interrupted(None)),
         () = tokio::time::sleep(delay) => Ok(()),
     }
 }
@@ -1458,28 +2057,44 @@ async fn wait_for_retry_delay(
 /// Consumes a provider stream. On error, returns the accumulated `StreamState`
 /// alongside the error so the caller can decide whether a transparent retry is
 /// safe (i.e. nothing externally visible or persisted has been emitted yet).
+#[allow(clippy::too_many_arguments)]
 async fn consume_stream(
     mut stream: ProviderStream,
     prior_messages: &[ChatMessage],
     sender: &EventSender,
-    cancel: Option<&CancellationToken>,
-    model: &str,
+    cancel: &CancellationToken,
+    turn: AssistantTurnBuilder,
     provider: &str,
+    model_params: Option<ModelParams>,
     request_started_at: Instant,
+    idle_timeout: Option<Duration>,
 ) -> std::result::Result<StreamState, (TurnError, StreamState)> {
-    let mut state = StreamState::new(model.to_string());
+    let mut state = StreamState::new(String::new());
+    state.turn = turn;
     state.provider = provider.to_string();
+    state.model_params = model_params;
     state.request_started_at = request_started_at;
+    let mut last_activity = Instant::now();
 
     loop {
-        if interrupt::is_interrupted() || cancel.is_some_and(CancellationToken::is_cancelled) {
+        if cancel.is_cancelled() {
             // User interruption is terminal, not a transparent retry: bill
             // the partial attempt's buffered usage before returning.
             state.flush_pending_usage(sender);
             let turn = std::mem::take(&mut state.turn);
             return Err((interrupted_turn_from_stream(prior_messages, turn), state));
         }
-        let event = match timeout(STREAM_POLL_TIMEOUT, stream.next()).await {
+        // Racing the token drops the in-flight HTTP stream as soon as the
+        // turn is canceled instead of waiting for the next poll timeout.
+        let next = tokio::select! {
+            biased;
+            () = cancel.cancelled() => continue,
+            next = timeout(STREAM_POLL_TIMEOUT, stream.next()) => next,
+        };
+        if next.is_ok() {
+            last_activity = Instant::now();
+        }
+        let event = match next {
             Ok(Some(Ok(event))) => event,
             Ok(Some(Err(err))) => return Err((TurnError::Provider(err), state)),
             Ok(None) => {
@@ -1492,7 +2107,24 @@ async fn consume_stream(
                 state.flush_final_usage(sender);
                 return Ok(state);
             }
-            Err(_) => continue,
+            Err(_) => {
+                // A provider that stops sending mid-stream would otherwise
+                // hang the turn; fail it with a retryable stall error.
+                if let Some(idle) = idle_timeout
+                    && last_activity.elapsed() >= idle
+                {
+                    tracing::warn!(
+                        model = state.turn.model,
+                        provider,
+                        ?idle,
+                        "Provider stream stalled"
+                    );
+                    state.flush_pending_usage(sender);
+                    let err = ProviderError::stream_stall(idle);
+                    return Err((TurnError::Provider(err), state));
+                }
+                continue;
+            }
         };
         if let Err(err) = handle_stream_event(event, sender, &mut state) {
             return Err((err, state));
@@ -1504,6 +2136,52 @@ fn can_transparently_retry_stream(state: &StreamState) -> bool {
     !state.emitted_visible_content
 }
 
+enum ResumeStep {
+    /// Run the complete tool calls as if the response ended there.
+    Run(StreamState),
+    /// Continue the response from this seed.
+    Continue(AssistantTurnBuilder),
+}
+
+/// Recovers a stream that failed after visible output: bills the attempt,
+/// closes tool cells whose input was cut off, and announces the resume.
+fn resume_stream(
+    mut state: StreamState,
+    err: &ProviderError,
+    resume: u32,
+    sender: &EventSender,
+) -> ResumeStep {
+    state.flush_pending_usage(sender);
+    let (plan, dropped) = stream_resume::plan(std::mem::take(&mut state.turn));
+    for id in dropped {
+        sender.send(AgentEvent::ToolCompleted {
+            id,
+            result: ToolOutput::canceled("Response was cut off before this tool call finished"),
+        });
+    }
+    let action = match &plan {
+        ResumePlan::RunTools(_) => "running the tool calls received so far",
+        ResumePlan::Continue(_) => "asking the model to continue",
+    };
+    tracing::warn!(resume, error = %err.message, "Provider stream dropped mid-response, resuming");
+    sender.send(AgentEvent::Notice {
+        kind: NoticeKind::StreamResumed,
+        message: format!(
+            "Connection dropped mid-response; {action} (resume {resume}/{}).",
+            stream_resume::MAX_STREAM_RESUMES
+        ),
+        details: Some(err.message.clone()),
+    });
+    match plan {
+        ResumePlan::RunTools(turn) => {
+            state.turn = turn;
+            state.stop_reason = Some("tool_use".to_string());
+            ResumeStep::Run(state)
+        }
+        ResumePlan::Continue(seed) => ResumeStep::Continue(seed),
+    }
+}
+
 #[allow(clippy::too_many_lines, clippy::match_same_arms)]
 fn handle_stream_event(
     event: StreamEvent,
@@ -1570,9 +2248,12 @@ fn handle_stream_event(
             index,
             partial_json,
         } => {
-            if let Some(event) = build_input_json_delta(index, &partial_json, &mut state.turn) {
+            let events = build_input_json_delta(index, &partial_json, &mut state.turn);
+            if !events.is_empty() {
                 state.flush_pending_usage(sender);
-                sender.send(event);
+                for event in events {
+                    sender.send(event);
+                }
                 state.mark_visible_content();
             }
         }
@@ -1737,23 +2418,46 @@ fn start_reasoning_block(
     });
 }
 
+/// Tool input JSON longer than this stops producing `ToolInputPartial`
+/// events (re-parsing it on every chunk would be quadratic); large bodies
+/// are previewed through `ToolInputDelta` instead.
+const MAX_PARTIAL_INPUT_BYTES: usize = 8 * 1024;
+
 fn build_input_json_delta(
     index: usize,
     partial_json: &str,
     turn: &mut AssistantTurnBuilder,
-) -> Option<AgentEvent> {
-    let tu = turn.find_tool_use_mut(index)?;
+) -> Vec<AgentEvent> {
+    let mut events = Vec::new();
+    let Some(tu) = turn.find_tool_use_mut(index) else {
+        return events;
+    };
+    if partial_json.is_empty() {
+        return events;
+    }
     tu.input_json.push_str(partial_json);
-    let delta = extract_partial_tool_input(&tu.name, &tu.input_json)?;
-    if delta.is_empty() || delta.len() <= tu.input_preview_len {
-        return None;
+
+    if tu.input_json.len() <= MAX_PARTIAL_INPUT_BYTES
+        && let Some(input) = partial_json::parse_partial_object(&tu.input_json)
+    {
+        events.push(AgentEvent::ToolInputPartial {
+            id: tu.id.clone(),
+            name: tu.name.clone(),
+            input,
+        });
     }
-    tu.input_preview_len = delta.len();
-    Some(AgentEvent::ToolInputDelta {
-        id: tu.id.clone(),
-        name: tu.name.clone(),
-        delta,
-    })
+    if let Some(delta) = extract_partial_tool_input(&tu.name, &tu.input_json)
+        && !delta.is_empty()
+        && delta.len() > tu.input_preview_len
+    {
+        tu.input_preview_len = delta.len();
+        events.push(AgentEvent::ToolInputDelta {
+            id: tu.id.clone(),
+            name: tu.name.clone(),
+            delta,
+        });
+    }
+    events
 }
 
 /// Folds a `MessageDelta` usage tick into the stream state's pending-usage
@@ -1873,6 +2577,11 @@ fn invalid_tool_output(input_json: &str, error: &serde_json::Error) -> ToolOutpu
 struct ToolTurnStats {
     executable: usize,
     malformed: usize,
+    /// Executed tool calls that returned an error.
+    failed: usize,
+    /// Successful calls to file-editing tools.
+    edited: usize,
+    turn_text: String,
 }
 
 async fn process_tool_turn(
@@ -1880,7 +2589,7 @@ async fn process_tool_turn(
     turn: &mut AssistantTurnBuilder,
     setup: &RunTurnSetup,
     sender: &EventSender,
-    cancel: Option<&CancellationToken>,
+    cancel: &CancellationToken,
     prior_message_count: usize,
 ) -> TurnResult<ToolTurnStats> {
     let finalized = std::mem::take(turn).finalize();
@@ -1902,10 +2611,17 @@ async fn process_tool_turn(
         cancel,
     )
     .await;
+    let failed_count = tool_results.iter().filter(|result| result.is_error).count();
+    let edited_count = finalized
+        .executable
+        .iter()
+        .zip(&tool_results)
+        .filter(|(tool_use, result)| !result.is_error && check::is_edit_tool(&tool_use.name))
+        .count();
     tool_results.extend(finalized.malformed_results);
     messages.push(ChatMessage::tool_results(tool_results));
 
-    if interrupt::is_interrupted() || cancel.is_some_and(CancellationToken::is_cancelled) {
+    if cancel.is_cancelled() {
         return Err(TurnError::interrupted_with_completion(
             (!turn_text.is_empty()).then_some(turn_text.clone()),
             turn_text,
@@ -1926,21 +2642,191 @@ async fn process_tool_turn(
     Ok(ToolTurnStats {
         executable: executable_count,
         malformed: malformed_count,
+        failed: failed_count,
+        edited: edited_count,
+        turn_text,
     })
 }
 
-fn finalize_non_tool_turn(
-    messages: &mut Vec<ChatMessage>,
-    turn: AssistantTurnBuilder,
+/// Ends the run after a loop safeguard tripped. Tool results are already
+/// recorded, so the thread stays balanced and the turn completes normally.
+fn finish_loop_detected_turn(
+    trip: LoopTrip,
+    final_text: String,
+    messages: Vec<ChatMessage>,
     sender: &EventSender,
     prior_message_count: usize,
 ) -> (String, Vec<ChatMessage>) {
+    tracing::warn!(kind = ?trip.kind, message = %trip.message, "Agent loop safeguard tripped");
+    sender.send(AgentEvent::LoopDetected {
+        kind: trip.kind,
+        message: trip.message,
+        details: trip.details,
+    });
+    sender.send(AgentEvent::TurnFinished {
+        status: TurnStatus::Completed,
+        final_text: final_text.clone(),
+        messages: messages.clone(),
+        prior_message_count,
+    });
+    (final_text, messages)
+}
+
+fn config_with_thinking_level(config: &Config, level: ThinkingLevel) -> Config {
+    let mut config = config.clone();
+    config.thinking_level = level;
+    config
+}
+
+/// Switches the run to the escalated thinking level (rebuilding the provider
+/// client) and reports the change.
+fn apply_escalation(
+    setup: &mut RunTurnSetup,
+    escalation: Escalation,
+    config: &Config,
+    options: &AgentOptions,
+    thread_id: Option<&str>,
+    sender: &EventSender,
+) -> Result<()> {
+    let rebuilt = build_run_turn_setup(
+        &config_with_thinking_level(config, escalation.to),
+        options,
+        thread_id,
+    )?;
+    setup.client = rebuilt.client;
+    setup.thinking_level = rebuilt.thinking_level;
+    tracing::info!(
+        from = escalation.from.display_name(),
+        to = escalation.to.display_name(),
+        trigger = ?escalation.trigger,
+        "Thinking level escalated"
+    );
+    sender.send(AgentEvent::ThinkingEscalated {
+        from: escalation.from,
+        to: escalation.to,
+        trigger: escalation.trigger,
+        message: escalation.message,
+    });
+    Ok(())
+}
+
+/// Closes out a streamed answer without recording it, so the request can be
+/// retried.
+fn discard_assistant_turn(turn: AssistantTurnBuilder, sender: &EventSender) {
     let finalized = turn.finalize();
-    let final_text = finalized.final_text;
-    emit_assistant_completed_if_present(sender, &final_text);
+    emit_assistant_completed_if_present(sender, &finalized.final_text);
+}
+
+fn soft_stop_requested(options: &AgentOptions) -> bool {
+    options
+        .soft_stop
+        .as_ref()
+        .is_some_and(CancellationToken::is_cancelled)
+}
+
+/// Records a tool-calling response without running its tools.
+///
+/// Used after a soft stop: the assistant blocks are kept and every tool call
+/// gets a canceled result so the thread stays balanced. Returns the turn text.
+fn skip_tool_turn(
+    messages: &mut Vec<ChatMessage>,
+    turn: &mut AssistantTurnBuilder,
+    sender: &EventSender,
+) -> String {
+    let finalized = std::mem::take(turn).finalize();
+    emit_assistant_completed_if_present(sender, &finalized.final_text);
+    emit_turn_diagnostics(&finalized.diagnostics, sender);
+    emit_malformed_tool_events(sender, finalized.malformed_tools);
+    messages.push(ChatMessage::assistant_blocks(finalized.blocks));
+
+    let mut tool_results: Vec<ToolResult> = finalized
+        .executable
+        .iter()
+        .map(|tu| {
+            let output = ToolOutput::canceled(SOFT_STOP_SKIPPED_MESSAGE);
+            let result = ToolResult::from_output(tu.id.clone(), &output);
+            sender.send(AgentEvent::ToolCompleted {
+                id: tu.id.clone(),
+                result: output,
+            });
+            result
+        })
+        .collect();
+    tool_results.extend(finalized.malformed_results);
+    messages.push(ChatMessage::tool_results(tool_results));
+    finalized.final_text
+}
+
+/// Runs `[tools.check]` after an edit round when `auto` is set and, if it
+/// fails, appends its diagnostics as a user message for the next request.
+/// Check errors (spawn failure, timeout) are logged and don't stop the turn.
+async fn run_auto_check(
+    config: &Config,
+    root: &Path,
+    messages: &mut Vec<ChatMessage>,
+    sender: &EventSender,
+    cancel: &CancellationToken,
+) {
+    let Some(check_config) = config.tools.check.as_ref().filter(|check| check.auto) else {
+        return;
+    };
+    let report = tokio::select! {
+        () = cancel.cancelled() => return,
+        report = check::run_check(check_config, root) => report,
+    };
+    let report = match report {
+        Ok(report) => report,
+        Err(failure) => {
+            tracing::warn!(?failure, "Automatic check could not run");
+            return;
+        }
+    };
+    if report.passed() {
+        return;
+    }
+    sender.send(AgentEvent::Notice {
+        kind: NoticeKind::CheckFailed,
+        message: report.notice(),
+        details: Some(check_config.command.clone()),
+    });
+    messages.push(report.feedback_message(check_config));
+}
+
+fn has_pending_steering(options: &AgentOptions) -> bool {
+    options
+        .steering
+        .as_ref()
+        .is_some_and(|queue| !queue.is_empty())
+}
+
+/// Appends pending steering notes to `messages` as tagged user messages.
+fn apply_steering(messages: &mut Vec<ChatMessage>, options: &AgentOptions) {
+    if let Some(queue) = &options.steering {
+        messages.extend(queue.drain().into_iter().map(steering::steering_message));
+    }
+}
+
+/// Records a tool-free assistant response and returns its text.
+fn commit_assistant_turn(
+    messages: &mut Vec<ChatMessage>,
+    turn: AssistantTurnBuilder,
+    sender: &EventSender,
+) -> String {
+    let finalized = turn.finalize();
+    emit_assistant_completed_if_present(sender, &finalized.final_text);
     if !finalized.blocks.is_empty() {
         messages.push(ChatMessage::assistant_blocks(finalized.blocks));
     }
+    finalized.final_text
+}
+
+fn finalize_non_tool_turn(
+    messages: &mut Vec<ChatMessage>,
+    turn: AssistantTurnBuilder,
+    sender: &EventSender,
+    prior_message_count: usize,
+) -> (String, Vec<ChatMessage>) {
+    let final_text = commit_assistant_turn(messages, turn, sender);
     sender.send(AgentEvent::TurnFinished {
         status: TurnStatus::Completed,
         final_text: final_text.clone(),
@@ -2115,16 +3001,17 @@ fn emit_stop_reason_notice(stop_reason: Option<&str>, sender: &EventSender) {
 /// are emitted sequentially before spawning to preserve CLI output order.
 /// `ToolCompleted` events are emitted as each task completes.
 ///
-/// On interrupt, aborts all remaining tasks and emits abort results for
-/// incomplete tools. The caller should check `is_interrupted()` after this
-/// function returns to determine if an interrupt occurred.
+/// Each tool gets the turn's `cancel` token via `ToolContext::cancel`. On
+/// cancellation, aborts all remaining tasks and emits abort results for
+/// incomplete tools. The caller should check `cancel` after this function
+/// returns to determine if an interrupt occurred.
 async fn execute_tools_async(
     tool_uses: &[ToolUse],
     ctx: &ToolContext,
     enabled_tools: &HashSet<String>,
     sender: &EventSender,
     tool_registry: &ToolRegistry,
-    cancel: Option<&CancellationToken>,
+    cancel: &CancellationToken,
 ) -> Vec<ToolResult> {
     let mut join_set: JoinSet<(usize, String, ToolOutput, ToolResult)> = JoinSet::new();
     let mut results: Vec<Option<(ToolOutput, ToolResult)>> = vec![None; tool_uses.len()];
@@ -2169,6 +3056,7 @@ async fn execute_tools_async(
         // Clone for 'static requirement
         let tu = tu.clone();
         let mut ctx = ctx.clone();
+        ctx.cancel = Some(cancel.clone());
         ctx.event_sender = Some(sender.clone());
         ctx.tool_use_id = Some(tu.id.clone());
         let enabled_tools = enabled_tools.clone();
@@ -2186,17 +3074,7 @@ async fn execute_tools_async(
     loop {
         tokio::select! {
             biased;
-            () = interrupt::wait_for_interrupt() => {
-                handle_tool_interrupt(
-                    &mut join_set,
-                    &mut completed,
-                    &mut results,
-                    tool_uses,
-                    sender,
-                );
-                break;
-            }
-            () = wait_for_cancel(cancel) => {
+            () = cancel.cancelled() => {
                 handle_tool_interrupt(
                     &mut join_set,
                     &mut completed,
@@ -2239,14 +3117,6 @@ async fn execute_tools_async(
         .collect()
 }
 
-async fn wait_for_cancel(cancel: Option<&CancellationToken>) {
-    if let Some(cancel) = cancel {
-        cancel.cancelled().await;
-    } else {
-        future::pending::<()>().await;
-    }
-}
-
 fn emit_tool_started_events(tool_uses: &[ToolUse], sender: &EventSender) {
     for tu in tool_uses {
         sender.send(AgentEvent::ToolStarted {
@@ -2310,6 +3180,8 @@ fn handle_tool_interrupt(
 /// Builds assistant content blocks from accumulated thinking, reasoning, text, and tool uses.
 #[cfg(test)]
 mod tests {
+    use std::sync::Arc;
+
     use tokio::time::{Duration, timeout};
 
     use super::*;
@@ -2317,8 +3189,7 @@ mod tests {
 
     #[test]
     fn stop_reason_notice_emits_for_refusal() {
-        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         emit_stop_reason_notice(Some("refusal"), &sender);
         let evt = rx.try_recv().expect("expected an event");
         match &*evt {
@@ -2332,8 +3203,7 @@ mod tests {
 
     #[test]
     fn stop_reason_notice_emits_for_context_window_exceeded() {
-        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         emit_stop_reason_notice(Some("model_context_window_exceeded"), &sender);
         let evt = rx.try_recv().expect("expected an event");
         match &*evt {
@@ -2350,8 +3220,7 @@ mod tests {
 
     #[test]
     fn stop_reason_notice_is_silent_for_normal_reasons() {
-        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         emit_stop_reason_notice(Some("end_turn"), &sender);
         emit_stop_reason_notice(Some("tool_use"), &sender);
         emit_stop_reason_notice(None, &sender);
@@ -2443,8 +3312,7 @@ mod tests {
         // sparse-cumulative → incremental conversion still folds correctly:
         // start=(100,2,5,1), +delta(_,+8,_,_)=(0,8,0,0),
         // +delta(120,15,8,1)=(20,5,3,0). Sum: (120,15,8,1).
-        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         let mut state = StreamState::new(String::new());
 
         handle_stream_event(
@@ -2527,8 +3395,7 @@ mod tests {
     /// `ReplayToken::AnthropicRedacted` for next-turn replay.
     #[test]
     fn handle_stream_event_captures_redacted_thinking_block() {
-        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         let mut state = StreamState::new(String::new());
 
         handle_stream_event(
@@ -2612,8 +3479,7 @@ mod tests {
     /// empty block that would be unreplayable on the next turn.
     #[test]
     fn handle_stream_event_rejects_redacted_thinking_without_data() {
-        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
         let mut state = StreamState::new(String::new());
 
         let err = handle_stream_event(
@@ -2658,8 +3524,7 @@ mod tests {
     /// cannot silently persist an unreplayable empty replay token.
     #[test]
     fn handle_stream_event_rejects_redacted_thinking_with_empty_data() {
-        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
         let mut state = StreamState::new(String::new());
 
         let err = handle_stream_event(
@@ -2717,8 +3582,7 @@ mod tests {
             replay: None,
         }];
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         // Run in a task so we can collect events
         let tool_registry = ToolRegistry::builtins();
@@ -2729,7 +3593,7 @@ mod tests {
                 &enabled_tools,
                 &sender,
                 &tool_registry,
-                None,
+                &CancellationToken::new(),
             )
             .await
         });
@@ -2786,8 +3650,7 @@ mod tests {
             },
         ];
 
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
         let tool_registry = ToolRegistry::builtins();
 
         let results = execute_tools_async(
@@ -2796,7 +3659,7 @@ mod tests {
             &enabled_tools,
             &sender,
             &tool_registry,
-            None,
+            &CancellationToken::new(),
         )
         .await;
 
@@ -2841,10 +3704,9 @@ mod tests {
         let (tx, mut rx) = create_event_channel();
 
         // Send one event then drop sender
-        tx.send(Arc::new(AgentEvent::AssistantDelta {
+        tx.send(AgentEvent::AssistantDelta {
             text: "hello".to_string(),
-        }))
-        .unwrap();
+        });
         drop(tx);
 
         // Should receive the event
@@ -2858,11 +3720,10 @@ mod tests {
         assert!(rx.recv().await.is_none());
     }
 
-    /// Verifies `EventSender::send` delivers events reliably via unbounded channel.
+    /// Verifies `EventSender::send` delivers every event, in order.
     #[tokio::test]
     async fn test_event_sender_send_delivers_all_events() {
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         for i in 0..100 {
             sender.send(AgentEvent::AssistantDelta {
@@ -2880,8 +3741,7 @@ mod tests {
     /// Verifies provider failures are rendered as a failed terminal event.
     #[tokio::test]
     async fn test_emit_turn_error_provider_emits_failed_turn_finished() {
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let err = TurnError::Provider(ProviderError::api_error("overloaded_error", "HTTP 502"));
         emit_turn_error(&err, &sender, 0);
@@ -2896,7 +3756,7 @@ mod tests {
                 status: TurnStatus::Failed {
                     kind: ErrorKind::ApiError,
                     message,
-                    details: _,
+                    ..
                 },
                 final_text,
                 messages,
@@ -2911,8 +3771,7 @@ mod tests {
     /// Verifies non-fatal diagnostics are emitted through the centralized helper.
     #[tokio::test]
     async fn test_emit_turn_diagnostics_parse_emits_error_event() {
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         let diagnostics = vec![TurnDiagnostic::Parse {
             message: "Invalid tool input JSON for read: expected value".to_string(),
             details: Some("{bad json}".to_string()),
@@ -2939,8 +3798,7 @@ mod tests {
     /// Verifies interrupted turns emit a single interrupted terminal event.
     #[tokio::test]
     async fn test_emit_turn_error_interrupted_emits_turn_finished() {
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         let messages = vec![ChatMessage::assistant_text("partial", None)];
 
         let err = TurnError::interrupted_with_completion(
@@ -3228,33 +4086,6 @@ mod tests {
         assert_eq!(finalized.blocks.len(), 1);
     }
 
-    /// Verifies broadcaster removes closed channels.
-    #[tokio::test]
-    async fn test_broadcaster_removes_closed_channels() {
-        let (source_tx, source_rx) = create_event_channel();
-        let (out1_tx, mut out1_rx) = create_event_channel();
-        let (out2_tx, out2_rx) = create_event_channel();
-
-        // Drop out2's receiver immediately
-        drop(out2_rx);
-
-        let _broadcaster = spawn_broadcaster(source_rx, vec![out1_tx, out2_tx]);
-
-        // Send an event
-        source_tx
-            .send(Arc::new(AgentEvent::AssistantDelta {
-                text: "test".to_string(),
-            }))
-            .unwrap();
-
-        // out1 should receive it
-        let ev = timeout(Duration::from_secs(1), out1_rx.recv())
-            .await
-            .expect("timeout")
-            .expect("should receive event");
-        assert!(matches!(&*ev, AgentEvent::AssistantDelta { text } if text == "test"));
-    }
-
     /// Transparent retries are only safe before the stream emits any visible
     /// assistant content. Metadata-only emissions (usage ticks) intentionally
     /// do not flip the gate; see `test_consume_stream_keeps_usage_only_retry_safe`.
@@ -3273,8 +4104,7 @@ mod tests {
     async fn test_consume_stream_returns_state_on_midstream_error() {
         use futures_util::stream;
 
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![Err(
             ProviderError::api_error("overloaded_error", "API is temporarily overloaded"),
@@ -3285,10 +4115,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Err((err, state)) = result else {
@@ -3315,8 +4147,7 @@ mod tests {
 
         use crate::providers::ProviderErrorKind;
 
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
 
         // Mirrors the message shape produced by the OpenAI/Anthropic/Gemini
         // SSE parsers when the underlying byte stream errors mid-poll.
@@ -3329,10 +4160,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Err((err, state)) = result else {
@@ -3359,8 +4192,7 @@ mod tests {
     async fn test_consume_stream_keeps_usage_only_retry_safe() {
         use futures_util::stream;
 
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3388,10 +4220,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Err((_err, state)) = result else {
@@ -3410,8 +4244,7 @@ mod tests {
     /// emitting each per-tick delta separately.
     #[test]
     fn flush_pending_usage_emits_combined_event_then_resets() {
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         let mut state = StreamState::new(String::new());
         state.pending_usage = crate::providers::Usage {
             input_tokens: 11,
@@ -3447,8 +4280,7 @@ mod tests {
 
     #[test]
     fn flush_pending_usage_is_noop_when_empty() {
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         let mut state = StreamState::new(String::new());
 
         state.flush_pending_usage(&sender);
@@ -3467,8 +4299,7 @@ mod tests {
     async fn usage_buffer_accumulates_message_start_plus_message_delta_pre_content() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3496,10 +4327,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Err((_err, state)) = result else {
@@ -3530,8 +4363,7 @@ mod tests {
     async fn usage_flushed_before_first_assistant_delta() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3552,10 +4384,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
 
@@ -3586,8 +4420,7 @@ mod tests {
     async fn usage_flushed_before_first_tool_requested() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3612,10 +4445,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
 
@@ -3646,8 +4481,7 @@ mod tests {
     async fn usage_flushed_before_first_tool_input_delta() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3676,10 +4510,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
 
@@ -3711,14 +4547,64 @@ mod tests {
         );
     }
 
+    /// Streaming tool input JSON yields a growing provisional input.
+    #[tokio::test]
+    async fn tool_input_partial_tracks_streaming_command() {
+        use futures_util::stream;
+
+        let (sender, mut rx) = create_event_channel();
+
+        let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
+            Ok(StreamEvent::ContentBlockStart {
+                index: 0,
+                block_type: ContentBlockType::ToolUse,
+                id: Some("toolu_b".to_string()),
+                name: Some("bash".to_string()),
+                data: None,
+                id_origin: Some(zdx_types::IdOrigin::Real),
+            }),
+            Ok(StreamEvent::InputJsonDelta {
+                index: 0,
+                partial_json: "{\"command\":\"cargo".to_string(),
+            }),
+            Ok(StreamEvent::InputJsonDelta {
+                index: 0,
+                partial_json: " test\"}".to_string(),
+            }),
+        ];
+        let provider_stream: ProviderStream = Box::pin(stream::iter(events));
+
+        let _ = consume_stream(
+            provider_stream,
+            &[],
+            &sender,
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
+            "",
+            None,
+            std::time::Instant::now(),
+            None,
+        )
+        .await;
+
+        let commands: Vec<String> = std::iter::from_fn(|| rx.try_recv().ok())
+            .filter_map(|e| match &*e {
+                AgentEvent::ToolInputPartial { input, .. } => {
+                    input["command"].as_str().map(str::to_string)
+                }
+                _ => None,
+            })
+            .collect();
+        assert_eq!(commands, vec!["cargo", "cargo test"]);
+    }
+
     /// Buffered usage flushes immediately before the first non-empty
     /// `ReasoningDelta`.
     #[tokio::test]
     async fn usage_flushed_before_first_reasoning_delta() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3747,10 +4633,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
 
@@ -3779,8 +4667,7 @@ mod tests {
     async fn usage_flushed_before_first_reasoning_completed() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3809,10 +4696,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
 
@@ -3842,8 +4731,7 @@ mod tests {
     async fn empty_reasoning_delta_does_not_flush_or_flip_gate() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3876,10 +4764,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Err((_err, state)) = result else {
@@ -3907,8 +4797,7 @@ mod tests {
     async fn usage_flushed_on_eof_success_without_message_completed() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -3932,10 +4821,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Ok(state) = result else {
@@ -3964,12 +4855,11 @@ mod tests {
     async fn usage_flushed_on_user_interruption_pre_content() {
         use futures_util::stream::{self, StreamExt};
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
         let cancel = CancellationToken::new();
 
-        // Yield `MessageStart` (gets buffered), then stay pending so the
-        // loop hits `STREAM_POLL_TIMEOUT` and re-checks the cancel token.
+        // Yield `MessageStart` (gets buffered), then stay pending until the
+        // cancel token fires.
         let provider_stream: ProviderStream = Box::pin(
             stream::iter(vec![Ok(StreamEvent::MessageStart {
                 model: "claude-test".to_string(),
@@ -3992,10 +4882,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            Some(&cancel),
-            "",
+            &cancel,
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Err((err, state)) = result else {
@@ -4020,6 +4912,77 @@ mod tests {
         );
     }
 
+    /// A stream that stops sending data fails with a retryable stall error
+    /// once the idle timeout passes.
+    #[tokio::test]
+    async fn idle_stream_fails_with_stream_stall() {
+        use futures_util::stream;
+
+        let (sender, _rx) = create_event_channel();
+        let result = consume_stream(
+            Box::pin(stream::pending()),
+            &[],
+            &sender,
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
+            "",
+            None,
+            std::time::Instant::now(),
+            Some(Duration::from_millis(100)),
+        )
+        .await;
+        let Err((TurnError::Provider(err), _)) = result else {
+            panic!("expected a provider error");
+        };
+        assert_eq!(err.kind, crate::providers::ProviderErrorKind::StreamStall);
+        assert!(err.is_retryable());
+    }
+
+    /// Canceling one turn's token drops its stream immediately (no poll
+    /// timeout wait) and leaves a concurrent turn with its own token alone.
+    #[tokio::test]
+    async fn cancel_token_stops_only_its_own_stream() {
+        use futures_util::stream;
+
+        let (sender, _rx) = create_event_channel();
+        let canceled = CancellationToken::new();
+        let other = CancellationToken::new();
+        canceled.cancel();
+
+        let started = std::time::Instant::now();
+        let result = consume_stream(
+            Box::pin(stream::pending()),
+            &[],
+            &sender,
+            &canceled,
+            AssistantTurnBuilder::default(),
+            "",
+            None,
+            started,
+            None,
+        )
+        .await;
+        assert!(matches!(result, Err((TurnError::Interrupted { .. }, _))));
+        assert!(started.elapsed() < STREAM_POLL_TIMEOUT);
+
+        let still_running = tokio::time::timeout(
+            STREAM_POLL_TIMEOUT * 2,
+            consume_stream(
+                Box::pin(stream::pending()),
+                &[],
+                &sender,
+                &other,
+                AssistantTurnBuilder::default(),
+                "",
+                None,
+                std::time::Instant::now(),
+                None,
+            ),
+        )
+        .await;
+        assert!(still_running.is_err(), "other turn must keep streaming");
+    }
+
     /// Simulates a transparent retry at the `consume_stream` layer:
     /// the first attempt buffers usage and fails retryably (state is
     /// discarded by the retry loop). The second attempt streams text and
@@ -4029,8 +4992,7 @@ mod tests {
     async fn usage_emitted_once_after_transparent_retry_success() {
         use futures_util::stream;
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         // Attempt 1: MessageStart + retryable error. State is dropped.
         let attempt1: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
@@ -4047,7 +5009,18 @@ mod tests {
             )),
         ];
         let s1: ProviderStream = Box::pin(stream::iter(attempt1));
-        let r1 = consume_stream(s1, &[], &sender, None, "", "", std::time::Instant::now()).await;
+        let r1 = consume_stream(
+            s1,
+            &[],
+            &sender,
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
+            "",
+            None,
+            std::time::Instant::now(),
+            None,
+        )
+        .await;
         let Err((_, discarded)) = r1 else {
             panic!("attempt 1 should fail");
         };
@@ -4073,7 +5046,18 @@ mod tests {
             }),
         ];
         let s2: ProviderStream = Box::pin(stream::iter(attempt2));
-        let r2 = consume_stream(s2, &[], &sender, None, "", "", std::time::Instant::now()).await;
+        let r2 = consume_stream(
+            s2,
+            &[],
+            &sender,
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
+            "",
+            None,
+            std::time::Instant::now(),
+            None,
+        )
+        .await;
         assert!(r2.is_ok(), "attempt 2 should succeed");
 
         // Drain rx and pin the strict ordering: exactly one UsageUpdate
@@ -4112,8 +5096,7 @@ mod tests {
     async fn test_consume_stream_marks_reasoning_completion_as_retry_unsafe() {
         use futures_util::stream;
 
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::ContentBlockStart {
@@ -4139,10 +5122,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Err((_err, state)) = result else {
@@ -4163,8 +5148,7 @@ mod tests {
     async fn test_consume_stream_marks_text_delta_as_retry_unsafe() {
         use futures_util::stream;
 
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
 
         let events: Vec<crate::providers::ProviderResult<StreamEvent>> = vec![
             Ok(StreamEvent::MessageStart {
@@ -4186,10 +5170,12 @@ mod tests {
             provider_stream,
             &[],
             &sender,
-            None,
-            "",
+            &CancellationToken::new(),
+            AssistantTurnBuilder::default(),
             "",
+            None,
             std::time::Instant::now(),
+            None,
         )
         .await;
         let Err((_err, state)) = result else {
@@ -4213,8 +5199,7 @@ mod tests {
     #[test]
     #[allow(clippy::too_many_lines)]
     fn test_assistant_turn_preserves_part_order() {
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
         let mut state = StreamState::new("gemini-3-pro-preview".to_string());
 
         // index 0: reasoning
@@ -4420,8 +5405,7 @@ mod tests {
     /// turn's source model.
     #[test]
     fn test_assistant_turn_gemini_signature_includes_model_on_text() {
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
         let mut state = StreamState::new("gemini-3-pro-preview".to_string());
 
         handle_stream_event(
@@ -4480,8 +5464,7 @@ mod tests {
     /// signature with the source model.
     #[test]
     fn test_assistant_turn_gemini_signature_includes_model_on_tool_use() {
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
         let mut state = StreamState::new("gemini-3-pro-preview".to_string());
 
         handle_stream_event(
@@ -4539,8 +5522,7 @@ mod tests {
     /// must also carry the source model.
     #[test]
     fn test_reasoning_completion_gemini_signature_includes_model() {
-        let (tx, _rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, _rx) = create_event_channel();
         let mut state = StreamState::new("gemini-3-pro-preview".to_string());
 
         handle_stream_event(
@@ -4609,8 +5591,7 @@ mod tests {
 
         use crate::tools::{ToolContext, ToolRegistry};
 
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let mut turn = AssistantTurnBuilder::new("gemini-3-pro-preview".to_string());
         turn.push_tool_use(ToolUseBuilder {
@@ -4633,11 +5614,14 @@ mod tests {
                 model: "gemini-3-pro-preview".to_string(),
                 max_output_tokens: None,
                 thinking_config: None,
+                sampling: crate::providers::Sampling::default(),
             })),
             tools: Vec::new(),
             enabled_tools: HashSet::new(),
             tool_ctx: ToolContext::new(std::path::PathBuf::from("."), None),
             tool_registry: ToolRegistry::builtins(),
+            truncation: None,
+            model_params: None,
         };
 
         let mut messages: Vec<ChatMessage> = vec![ChatMessage::user("first turn")];
@@ -4646,9 +5630,16 @@ mod tests {
         // Run the tool turn; the unknown tool name produces a tool_result
         // (failure) but the path still emits a TurnCheckpoint when it
         // completes successfully (no interrupt).
-        process_tool_turn(&mut messages, &mut turn, &setup, &sender, None, prior_count)
-            .await
-            .expect("tool turn should complete");
+        process_tool_turn(
+            &mut messages,
+            &mut turn,
+            &setup,
+            &sender,
+            &CancellationToken::new(),
+            prior_count,
+        )
+        .await
+        .expect("tool turn should complete");
 
         // Drain events looking for a TurnCheckpoint with our prior_count.
         let mut saw_checkpoint = false;
@@ -4669,14 +5660,57 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_skip_tool_turn_records_canceled_results_without_executing() {
+        let (sender, mut rx) = create_event_channel();
+
+        let mut turn = AssistantTurnBuilder::new("claude-sonnet-4".to_string());
+        turn.push_tool_use(ToolUseBuilder {
+            index: 0,
+            id: "tool_skipped".to_string(),
+            name: "bash".to_string(),
+            input_json: r#"{"command":"echo hi"}"#.to_string(),
+            input_preview_len: 0,
+            id_origin: zdx_types::IdOrigin::Real,
+            replay: None,
+        });
+
+        let mut messages = vec![ChatMessage::user("do it")];
+        skip_tool_turn(&mut messages, &mut turn, &sender);
+
+        assert_eq!(messages.len(), 3);
+        let zdx_types::MessageContent::Blocks(blocks) = &messages[2].content else {
+            panic!("expected tool result blocks");
+        };
+        match blocks.as_slice() {
+            [ChatContentBlock::ToolResult(result)] => {
+                assert_eq!(result.tool_use_id, "tool_skipped");
+            }
+            other => panic!("unexpected blocks: {other:?}"),
+        }
+
+        let mut saw_canceled = false;
+        while let Ok(event) = rx.try_recv() {
+            match &*event {
+                AgentEvent::ToolStarted { .. } => panic!("skipped tools must not start"),
+                AgentEvent::ToolCompleted { id, result } => {
+                    assert_eq!(id, "tool_skipped");
+                    assert!(matches!(result, ToolOutput::Canceled { .. }));
+                    saw_canceled = true;
+                }
+                _ => {}
+            }
+        }
+        assert!(saw_canceled);
+    }
+
     /// Provider errors after a partial run carry the run-entry
     /// `prior_message_count` on the terminal `TurnFinished`. The cursor is
     /// captured at run entry, not at error time, so persistence can slice
     /// the new turn-suffix correctly.
     #[tokio::test]
     async fn test_turn_finished_cursor_in_provider_error_path() {
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let messages = vec![
             ChatMessage::user("hi"),
@@ -4704,8 +5738,7 @@ mod tests {
     /// cursor. Persistence consumers tolerate `prior_count <= last_persisted`.
     #[tokio::test]
     async fn test_turn_finished_cursor_in_setup_failure() {
-        let (tx, mut rx) = create_event_channel();
-        let sender = EventSender::new(tx);
+        let (sender, mut rx) = create_event_channel();
 
         let err = TurnError::Internal(anyhow!("setup failed"));
         emit_turn_error(&err, &sender, /* prior_message_count */ 3);
@@ -4749,4 +5782,209 @@ mod tests {
         let turn = AssistantTurnBuilder::new("gemini-3-pro-preview".to_string());
         assert_eq!(turn.model, "gemini-3-pro-preview");
     }
+
+    fn agent_options() -> AgentOptions {
+        AgentOptions {
+            root: PathBuf::from("."),
+            tool_config: ToolConfig::default(),
+            surface: None,
+            text_verbosity: None,
+            service_tier: None,
+            activity_kind: None,
+            activity_parent_thread_id: None,
+            activity_subagent_name: None,
+            soft_stop: None,
+            steering: None,
+            ask_user: None,
+            protected_approvals: ProtectedApprovals::default(),
+            turn: TurnOptions::default(),
+        }
+    }
+
+    #[test]
+    fn test_selected_tool_names_applies_include_and_exclude() {
+        let mut config = Config::default();
+        config.subagents.enabled = true;
+        let mut options = agent_options();
+        let names = |options: &AgentOptions| {
+            selected_tool_names(&config, options, ProviderKind::Anthropic)
+                .into_iter()
+                .map(|name| name.to_lowercase())
+                .collect::<Vec<_>>()
+        };
+        let baseline = names(&options);
+        assert!(baseline.contains(&"bash".to_string()));
+        assert!(!baseline.contains(&"apply_patch".to_string()));
+
+        options.tool_config.selection = ToolSelection::Auto {
+            base: ToolSet::Default,
+            include: vec!["apply_patch".to_string()],
+            exclude: vec!["Bash".to_string()],
+        };
+        let filtered = names(&options);
+        assert!(!filtered.contains(&"bash".to_string()));
+        assert!(filtered.contains(&"apply_patch".to_string()));
+        assert_eq!(filtered.len(), baseline.len());
+
+        options.turn.tool_selection = Some(ToolSelection::Explicit(vec!["read".to_string()]));
+        assert_eq!(names(&options), vec!["read".to_string()]);
+    }
+
+    #[test]
+    fn test_tool_selection_from_cli_flags() {
+        let available: Vec<String> = ["read", "grep", "bash", "write", "edit"]
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        assert!(
+            ToolSelection::from_cli_flags(None, None, &available)
+                .unwrap()
+                .is_none()
+        );
+        let Some(ToolSelection::Explicit(tools)) =
+            ToolSelection::from_cli_flags(Some("Read, grep,bash"), Some("bash"), &available)
+                .unwrap()
+        else {
+            panic!("expected an explicit selection");
+        };
+        assert_eq!(tools, vec!["Read".to_string(), "grep".to_string()]);
+
+        let Some(ToolSelection::Auto { exclude, .. }) =
+            ToolSelection::from_cli_flags(None, Some("write,edit"), &available).unwrap()
+        else {
+            panic!("expected the default selection with excludes");
+        };
+        assert_eq!(exclude, vec!["write".to_string(), "edit".to_string()]);
+
+        let err = ToolSelection::from_cli_flags(None, Some("wrte"), &available).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unknown tool(s) in --deny-tools: wrte (did you mean 'write'?)"),
+            "{err}"
+        );
+        assert!(ToolSelection::from_cli_flags(Some(" , "), None, &available).is_err());
+    }
+
+    #[test]
+    fn test_offline_mode_drops_network_tools() {
+        let mut config = Config::default();
+        let options = agent_options();
+        let names = |config: &Config| {
+            selected_tool_names(config, &options, ProviderKind::Anthropic)
+                .into_iter()
+                .map(|name| name.to_lowercase())
+                .collect::<Vec<_>>()
+        };
+        assert!(names(&config).contains(&"web_search".to_string()));
+
+        config.offline = true;
+        let offline = names(&config);
+        assert!(!offline.contains(&"web_search".to_string()));
+        assert!(!offline.contains(&"fetch_webpage".to_string()));
+        assert!(offline.contains(&"bash".to_string()));
+    }
+
+    #[test]
+    fn test_registered_provider_builds_setup_from_capabilities() {
+        use crate::providers::registry::{self, ProviderRequest};
+        use crate::providers::{ProviderCapabilities, StreamingProvider};
+
+        struct NoToolsProvider;
+        impl StreamingProvider for NoToolsProvider {
+            fn stream_messages<'a>(
+                &'a self,
+                _messages: &'a [ChatMessage],
+                _tools: &'a [ToolDefinition],
+                _system: Option<&'a str>,
+            ) -> std::pin::Pin<
+                Box<dyn std::future::Future<Output = Result<ProviderStream>> + Send + 'a>,
+            > {
+                Box::pin(async { Err(anyhow!("unused")) })
+            }
+
+            fn capabilities(&self) -> ProviderCapabilities {
+                ProviderCapabilities {
+                    tools: false,
+                    reasoning: false,
+                }
+            }
+        }
+
+        registry::register(
+            "agent-test-registry",
+            Arc::new(
+                |_: &ProviderRequest<'_>| -> Result<Box<dyn StreamingProvider>> {
+                    Ok(Box::new(NoToolsProvider))
+                },
+            ),
+        )
+        .unwrap();
+        let config = Config {
+            model: "agent-test-registry:local-model".to_string(),
+            thinking_level: ThinkingLevel::High,
+            ..Config::default()
+        };
+
+        let setup = build_run_turn_setup(&config, &agent_options(), None).unwrap();
+        registry::unregister("agent-test-registry");
+
+        assert_eq!(setup.provider, "agent-test-registry");
+        assert_eq!(setup.model, "local-model");
+        assert_eq!(setup.thinking_level, ThinkingLevel::Off);
+        assert!(setup.tools.is_empty());
+    }
+
+    #[test]
+    fn test_model_params_are_gated_per_provider_and_thinking() {
+        let mut config = Config {
+            model: "anthropic:claude-sonnet-4-6".to_string(),
+            thinking_level: ThinkingLevel::Off,
+            model_params: ModelParams {
+                temperature: Some(0.2),
+                top_p: None,
+                max_output_tokens: Some(2048),
+            },
+            ..Config::default()
+        };
+        config.providers.anthropic.api_key = Some("test-key".to_string());
+
+        let setup = build_run_turn_setup(&config, &agent_options(), None).unwrap();
+        assert_eq!(setup.model_params, Some(config.model_params));
+
+        config.thinking_level = ThinkingLevel::High;
+        let setup = build_run_turn_setup(&config, &agent_options(), None).unwrap();
+        let sent = setup.model_params.expect("output cap is still sent");
+        assert_eq!(sent.temperature, None);
+        assert_eq!(sent.max_output_tokens, Some(2048));
+        assert_eq!(config.max_output_tokens(), Some(2048));
+
+        config.model_params.top_p = Some(0.0);
+        let err = build_run_turn_setup(&config, &agent_options(), None)
+            .err()
+            .expect("out-of-range top_p is rejected");
+        assert!(err.to_string().contains("top_p"), "{err}");
+    }
+
+    #[test]
+    fn test_turn_options_override_config_for_one_turn() {
+        let config = Config::default();
+        assert!(matches!(
+            TurnOptions::default().apply(&config),
+            Cow::Borrowed(_)
+        ));
+
+        let turn = TurnOptions {
+            model: Some("openai:gpt-5.2".to_string()),
+            thinking_level: Some(ThinkingLevel::High),
+            max_tokens: Some(1024),
+            ..TurnOptions::default()
+        };
+        let resolved = turn.apply(&config);
+        assert_eq!(resolved.model, "openai:gpt-5.2");
+        assert_eq!(resolved.thinking_level, ThinkingLevel::High);
+        assert_eq!(resolved.max_tokens, Some(1024));
+        assert_eq!(turn.model(&config), "openai:gpt-5.2");
+        assert_ne!(config.model, "openai:gpt-5.2");
+    }
 }
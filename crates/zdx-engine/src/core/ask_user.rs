@@ -0,0 +1,109 @@
+//! Agent→user questions for the `Ask_User` tool.
+//!
+//! A surface that can take answers creates a channel with [`channel`], hands
+//! the [`AskUserHandle`] to the agent via `AgentOptions::ask_user`, and
+//! answers (or dismisses) each [`UserQuestion`] it receives. The tool call
+//! stays pending until then. Runs without a handle (exec, subagents) have no
+//! one to ask, so the tool fails fast there.
+
+use tokio::sync::{mpsc, oneshot};
+
+/// A pending question from the agent. Dropping it unanswered dismisses it.
+#[derive(Debug)]
+pub struct UserQuestion {
+    pub tool_use_id: String,
+    pub question: String,
+    reply: oneshot::Sender<Result<String, String>>,
+}
+
+impl UserQuestion {
+    /// Sends the user's answer. Returns `false` if the asking turn is gone
+    /// (canceled or finished), so the caller can treat the text normally.
+    pub fn answer(self, answer: impl Into<String>) -> bool {
+        self.reply.send(Ok(answer.into())).is_ok()
+    }
+
+    /// Resolves the question without an answer; `reason` is shown to the
+    /// model (e.g. a timeout or the user declining).
+    pub fn dismiss(self, reason: impl Into<String>) {
+        let _ = self.reply.send(Err(reason.into()));
+    }
+
+    /// Whether the asking turn is still waiting for this question.
+    pub fn is_pending(&self) -> bool {
+        !self.reply.is_closed()
+    }
+}
+
+/// Sending side of the question channel, cloned into `AgentOptions`.
+#[derive(Debug, Clone)]
+pub struct AskUserHandle {
+    tx: mpsc::UnboundedSender<UserQuestion>,
+}
+
+/// Receiving side, owned by the surface that shows questions.
+pub type AskUserRx = mpsc::UnboundedReceiver<UserQuestion>;
+
+/// Creates a question channel.
+pub fn channel() -> (AskUserHandle, AskUserRx) {
+    let (tx, rx) = mpsc::unbounded_channel();
+    (AskUserHandle { tx }, rx)
+}
+
+impl AskUserHandle {
+    /// Sends `question` to the surface and waits for the reply.
+    ///
+    /// # Errors
+    /// Returns the reason as text when the question is dismissed or the
+    /// surface is gone.
+    pub async fn ask(&self, tool_use_id: &str, question: &str) -> Result<String, String> {
+        let (reply, answer) = oneshot::channel();
+        self.tx
+            .send(UserQuestion {
+                tool_use_id: tool_use_id.to_string(),
+                question: question.to_string(),
+                reply,
+            })
+            .map_err(|_closed| "No user interface is attached to answer questions.".to_string())?;
+        answer
+            .await
+            .unwrap_or_else(|_| Err("The question was dismissed without an answer.".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn answers_and_dismissals_reach_the_asker() {
+        let (handle, mut rx) = channel();
+
+        let asker = tokio::spawn({
+            let handle = handle.clone();
+            async move { handle.ask("toolu_1", "Which branch?").await }
+        });
+        let question = rx.recv().await.expect("question");
+        assert_eq!(question.question, "Which branch?");
+        assert!(question.is_pending());
+        assert!(question.answer("main"));
+        assert_eq!(asker.await.unwrap(), Ok("main".to_string()));
+
+        let asker = tokio::spawn(async move { handle.ask("toolu_2", "Deploy?").await });
+        rx.recv()
+            .await
+            .expect("question")
+            .dismiss("No reply within 60s.");
+        assert_eq!(
+            asker.await.unwrap(),
+            Err("No reply within 60s.".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn closed_surface_fails_immediately() {
+        let (handle, rx) = channel();
+        drop(rx);
+        assert!(handle.ask("toolu_1", "Anyone?").await.is_err());
+    }
+}
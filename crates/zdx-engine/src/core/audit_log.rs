@@ -0,0 +1,494 @@
+//! Tool-call audit records across saved threads and unsaved sessions.
+//!
+//! Every `tool_use`/`tool_result` pair becomes one [`AuditRecord`]: timestamp,
+//! thread, tool, a redacted one-line input summary, exit status, and the files
+//! the call touched. Saved threads are read straight from their JSONL logs.
+//! TUI turns that run without a thread outside incognito mode have no log, so
+//! [`spawn_audit_task`] appends their records to
+//! `$ZDX_HOME/audit/unsaved.jsonl` as tool calls complete. Only already-redacted
+//! records are written there, never raw tool input. Incognito sessions
+//! (`--no-thread`, `/incognito`) write no audit records at all.
+//!
+//! This is the data source for `zdx audit export`.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::task::JoinHandle;
+use zdx_types::is_secret_key;
+
+use crate::config::paths;
+use crate::core::event_bus::EventSubscriber;
+use crate::core::thread_persistence::{
+    self, ThreadEvent, UsagePersistor, list_all_threads, truncate_str,
+};
+use crate::tools::apply_patch::Hunk;
+use crate::tools::apply_patch::parser::parse_patch;
+
+const INPUT_SUMMARY_MAX_BYTES: usize = 200;
+const REDACTED: &str = "[REDACTED]";
+
+/// Input keys holding file contents; summarized as a line count.
+const CONTENT_KEYS: &[&str] = &["content", "old_string", "new_string", "new_source", "patch"];
+
+/// Well-known credential prefixes redacted wherever they appear in a summary.
+const SECRET_VALUE_PREFIXES: &[&str] = &[
+    "sk-",
+    "ghp_",
+    "gho_",
+    "ghs_",
+    "github_pat_",
+    "xoxb-",
+    "xoxp-",
+    "AKIA",
+    "glpat-",
+];
+
+/// One tool execution.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// RFC 3339 timestamp of the tool call.
+    pub ts: String,
+    /// Thread ID, or the unsaved session ID when `saved` is false.
+    pub thread: String,
+    /// Whether the call belongs to a saved thread.
+    pub saved: bool,
+    pub tool: String,
+    /// `ok`, `failed`, or `pending` (no result was recorded).
+    pub status: String,
+    /// Process exit code for command tools (`bash`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i64>,
+    /// Redacted, single-line summary of the tool input.
+    pub input_summary: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub files_touched: Vec<String>,
+}
+
+/// Export output format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditFormat {
+    Csv,
+    Jsonl,
+}
+
+impl std::str::FromStr for AuditFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "jsonl" => Ok(Self::Jsonl),
+            other => anyhow::bail!("unsupported audit format '{other}' (use 'csv' or 'jsonl')"),
+        }
+    }
+}
+
+/// Pairs `tool_use` events with their `tool_result` into records.
+struct ToolCallPairer {
+    thread: String,
+    saved: bool,
+    pending: HashMap<String, (String, Value, String)>,
+    order: Vec<String>,
+}
+
+impl ToolCallPairer {
+    fn new(thread: String, saved: bool) -> Self {
+        Self {
+            thread,
+            saved,
+            pending: HashMap::new(),
+            order: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, event: ThreadEvent) -> Option<AuditRecord> {
+        match event {
+            ThreadEvent::ToolUse {
+                id,
+                name,
+                input,
+                ts,
+                ..
+            } => {
+                self.order.push(id.clone());
+                self.pending.insert(id, (name, input, ts));
+                None
+            }
+            ThreadEvent::ToolResult {
+                tool_use_id,
+                output,
+                ok,
+                ..
+            } => {
+                let (name, input, ts) = self.pending.remove(&tool_use_id)?;
+                self.order.retain(|id| id != &tool_use_id);
+                let status = if ok { "ok" } else { "failed" };
+                Some(self.record(&name, &input, ts, status, Some(&output)))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns records for tool calls that never got a result.
+    fn finish(mut self) -> Vec<AuditRecord> {
+        let mut pending = std::mem::take(&mut self.pending);
+        std::mem::take(&mut self.order)
+            .into_iter()
+            .filter_map(|id| pending.remove(&id))
+            .map(|(name, input, ts)| self.record(&name, &input, ts, "pending", None))
+            .collect()
+    }
+
+    fn record(
+        &self,
+        name: &str,
+        input: &Value,
+        ts: String,
+        status: &str,
+        output: Option<&Value>,
+    ) -> AuditRecord {
+        AuditRecord {
+            ts,
+            thread: self.thread.clone(),
+            saved: self.saved,
+            tool: name.to_string(),
+            status: status.to_string(),
+            exit_code: output.and_then(|output| {
+                output
+                    .get("data")
+                    .and_then(|data| data.get("exit_code"))
+                    .and_then(Value::as_i64)
+            }),
+            input_summary: summarize_input(input),
+            files_touched: files_touched(name, input),
+        }
+    }
+}
+
+/// Collects audit records from every saved thread (including subagent and
+/// helper runs) and from unsaved sessions, oldest first. `since` keeps
+/// records on or after that UTC date.
+///
+/// # Errors
+/// Returns an error if the threads directory or the unsaved-session log
+/// cannot be read.
+pub fn collect_records(since: Option<NaiveDate>) -> Result<Vec<AuditRecord>> {
+    let mut records = Vec::new();
+
+    for summary in list_all_threads().context("list threads")? {
+        let events = thread_persistence::load_thread_events(&summary.id).unwrap_or_default();
+        let mut pairer = ToolCallPairer::new(summary.id, true);
+        for event in events {
+            records.extend(pairer.push(event));
+        }
+        records.extend(pairer.finish());
+    }
+
+    records.extend(read_unsaved_records()?);
+    records.retain(|record| matches_since(&record.ts, since));
+    records.sort_by(|a, b| a.ts.cmp(&b.ts));
+    Ok(records)
+}
+
+/// Writes `records` in `format`. CSV output starts with a header row and
+/// joins `files_touched` with `;`.
+///
+/// # Errors
+/// Returns an error if writing fails.
+pub fn write_records(
+    records: &[AuditRecord],
+    format: AuditFormat,
+    out: &mut impl Write,
+) -> Result<()> {
+    match format {
+        AuditFormat::Jsonl => {
+            for record in records {
+                serde_json::to_writer(&mut *out, record).context("serialize audit record")?;
+                writeln!(out)?;
+            }
+        }
+        AuditFormat::Csv => {
+            writeln!(
+                out,
+                "ts,thread,saved,tool,status,exit_code,files_touched,input_summary"
+            )?;
+            for record in records {
+                let exit_code = record
+                    .exit_code
+                    .map(|code| code.to_string())
+                    .unwrap_or_default();
+                let fields = [
+                    record.ts.as_str(),
+                    record.thread.as_str(),
+                    if record.saved { "true" } else { "false" },
+                    record.tool.as_str(),
+                    record.status.as_str(),
+                    exit_code.as_str(),
+                    &record.files_touched.join(";"),
+                    record.input_summary.as_str(),
+                ];
+                let row: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+                writeln!(out, "{}", row.join(","))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Spawns a task that appends audit records for an unsaved session.
+///
+/// Consumes the same agent events as the thread persist task and writes one
+/// record per completed tool call (plus any calls still pending when the
+/// stream closes). Writes are best-effort.
+pub fn spawn_audit_task(mut rx: EventSubscriber) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut persistor = UsagePersistor::new();
+        let mut pairer = ToolCallPairer::new(session_id().to_string(), false);
+        while let Some(event) = rx.recv().await {
+            for thread_event in persistor.handle_event(&event) {
+                if let Some(record) = pairer.push(thread_event) {
+                    append_unsaved_record(&record);
+                }
+            }
+        }
+        for record in pairer.finish() {
+            append_unsaved_record(&record);
+        }
+    })
+}
+
+/// Identifies this process's unsaved session in the audit log.
+fn session_id() -> &'static str {
+    static SESSION_ID: OnceLock<String> = OnceLock::new();
+    SESSION_ID.get_or_init(|| format!("unsaved-{}", uuid::Uuid::new_v4()))
+}
+
+fn append_unsaved_record(record: &AuditRecord) {
+    let path = paths::audit_log_path();
+    let result = (|| -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let line = serde_json::to_string(record)?;
+        writeln!(file, "{line}")?;
+        Ok(())
+    })();
+    if let Err(e) = result {
+        tracing::warn!(%e, path = %path.display(), "Failed to append audit record");
+    }
+}
+
+fn read_unsaved_records() -> Result<Vec<AuditRecord>> {
+    let path = paths::audit_log_path();
+    let file = match fs::File::open(&path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("open {}", path.display())),
+    };
+
+    let mut records = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.with_context(|| format!("read {}", path.display()))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // Skip lines torn by a crash mid-write.
+        if let Ok(record) = serde_json::from_str(&line) {
+            records.push(record);
+        }
+    }
+    Ok(records)
+}
+
+fn matches_since(ts: &str, since: Option<NaiveDate>) -> bool {
+    let Some(since) = since else {
+        return true;
+    };
+    DateTime::parse_from_rfc3339(ts).is_ok_and(|ts| ts.with_timezone(&Utc).date_naive() >= since)
+}
+
+/// Builds a redacted one-line input summary: secret-looking keys and known
+/// credential formats are masked, file contents become line counts.
+fn summarize_input(input: &Value) -> String {
+    let summary = if let Some(command) = input.get("command").and_then(Value::as_str) {
+        command.to_string()
+    } else {
+        let mut input = input.clone();
+        redact_value(&mut input);
+        serde_json::to_string(&input).unwrap_or_default()
+    };
+
+    let summary = redact_text(&summary);
+    if summary.len() > INPUT_SUMMARY_MAX_BYTES {
+        format!("{}...", truncate_str(&summary, INPUT_SUMMARY_MAX_BYTES))
+    } else {
+        summary
+    }
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(key) {
+                    *value = Value::String(REDACTED.to_string());
+                } else if CONTENT_KEYS.contains(&key.as_str())
+                    && let Some(text) = value.as_str()
+                {
+                    *value = Value::String(format!("<{} lines>", text.lines().count()));
+                } else {
+                    redact_value(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+/// Masks `NAME=value` assignments with secret-looking names, bearer tokens,
+/// and well-known credential prefixes. Collapses whitespace to single spaces.
+fn redact_text(text: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut redact_next = false;
+    for word in text.split_whitespace() {
+        if redact_next {
+            out.push(REDACTED.to_string());
+            redact_next = false;
+            continue;
+        }
+        if word
+            .trim_matches(['"', '\''])
+            .eq_ignore_ascii_case("bearer")
+        {
+            redact_next = true;
+            out.push(word.to_string());
+            continue;
+        }
+        if let Some((name, _)) = word.split_once('=')
+            && is_secret_key(name)
+        {
+            out.push(format!("{name}={REDACTED}"));
+            continue;
+        }
+        let bare = word.trim_matches(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '-');
+        if bare.len() >= 16 && SECRET_VALUE_PREFIXES.iter().any(|p| bare.starts_with(p)) {
+            out.push(word.replace(bare, REDACTED));
+            continue;
+        }
+        out.push(word.to_string());
+    }
+    out.join(" ")
+}
+
+/// Paths a tool call read or changed, from its input.
+fn files_touched(name: &str, input: &Value) -> Vec<String> {
+    match name.to_ascii_lowercase().as_str() {
+        "read" | "write" | "edit" | "notebook_read" | "notebook_edit" => input
+            .get("file_path")
+            .or_else(|| input.get("path"))
+            .and_then(Value::as_str)
+            .map(|path| vec![path.to_string()])
+            .unwrap_or_default(),
+        "apply_patch" => {
+            let patch_text = input
+                .get("patch")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let mut paths = Vec::new();
+            for hunk in parse_patch(patch_text).unwrap_or_default() {
+                match hunk {
+                    Hunk::AddFile { path, .. } | Hunk::DeleteFile { path } => {
+                        paths.push(path.display().to_string());
+                    }
+                    Hunk::UpdateFile {
+                        path, move_path, ..
+                    } => {
+                        paths.push(path.display().to_string());
+                        paths.extend(move_path.map(|path| path.display().to_string()));
+                    }
+                }
+            }
+            paths
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn summary_redacts_secrets_and_file_contents() {
+        let summary = summarize_input(
+            &json!({"command": "curl -H 'Authorization: Bearer abc123' API_TOKEN=xyz https://x"}),
+        );
+        assert_eq!(
+            summary,
+            "curl -H 'Authorization: Bearer [REDACTED] API_TOKEN=[REDACTED] https://x"
+        );
+
+        let summary = summarize_input(&json!({
+            "file_path": "a.rs",
+            "content": "one\ntwo",
+            "api_key": "sk-live"
+        }));
+        assert!(summary.contains("\"content\":\"<2 lines>\""));
+        assert!(summary.contains("\"api_key\":\"[REDACTED]\""));
+
+        let summary = summarize_input(&json!({"command": "echo sk-abcdefghijklmnopqrstuv"}));
+        assert_eq!(summary, "echo [REDACTED]");
+    }
+
+    #[test]
+    fn pairs_tool_calls_with_exit_code_and_pending() {
+        let mut pairer = ToolCallPairer::new("t1".to_string(), true);
+        assert!(
+            pairer
+                .push(ThreadEvent::tool_use(
+                    "a",
+                    "bash",
+                    json!({"command": "false"})
+                ))
+                .is_none()
+        );
+        pairer.push(ThreadEvent::tool_use(
+            "b",
+            "apply_patch",
+            json!({"patch": "*** Begin Patch\n*** Add File: new.txt\n+hi\n*** End Patch"}),
+        ));
+        let record = pairer
+            .push(ThreadEvent::tool_result(
+                "a",
+                json!({"ok": true, "data": {"exit_code": 1}}),
+                true,
+            ))
+            .unwrap();
+        assert_eq!(record.status, "ok");
+        assert_eq!(record.exit_code, Some(1));
+
+        let pending = pairer.finish();
+        assert_eq!(pending.len(), 1);
+        assert_eq!(pending[0].status, "pending");
+        assert_eq!(pending[0].files_touched, vec!["new.txt".to_string()]);
+    }
+}
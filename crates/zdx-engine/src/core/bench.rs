@@ -0,0 +1,158 @@
+//! Provider benchmarking: streams the same prompt to a model and measures
+//! time to first token, output throughput, total latency, and cost.
+//!
+//! Timing comes from the provider-layer `StreamMetrics` collector (the same
+//! one behind `ZDX_DEBUG_STREAM`), driven in-process instead of writing a
+//! debug file.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use futures_util::StreamExt;
+
+use crate::config::Config;
+use crate::core::agent::build_provider_client;
+use crate::models::{ModelOption, split_model_thinking};
+use crate::providers::{ChatMessage, StreamEvent, StreamMetrics, Usage};
+
+/// Outcome of benchmarking one model.
+#[derive(Debug, Clone)]
+pub struct BenchResult {
+    /// Model spec as given (may include a `@thinking` suffix).
+    pub model: String,
+    /// Provider id serving the model, when the client could be built.
+    pub provider: Option<String>,
+    /// Time to first assistant output (text or text block start).
+    pub ttft: Option<Duration>,
+    /// Total request latency, from send to stream end.
+    pub total: Duration,
+    pub usage: Usage,
+    /// USD cost from registry pricing; `None` when the model has no pricing.
+    pub cost_usd: Option<f64>,
+    /// Failure message; other fields hold whatever was measured before it.
+    pub error: Option<String>,
+}
+
+impl BenchResult {
+    /// Output tokens per second, measured after the first token arrived.
+    pub fn tokens_per_sec(&self) -> Option<f64> {
+        let generation = self.total.checked_sub(self.ttft?)?.as_secs_f64();
+        if self.usage.output_tokens == 0 || generation <= 0.0 {
+            return None;
+        }
+        Some(f64::from(u32::try_from(self.usage.output_tokens).unwrap_or(u32::MAX)) / generation)
+    }
+}
+
+/// Streams `prompt` to `model_spec` with no tools or system prompt.
+///
+/// Never fails: errors are recorded in [`BenchResult::error`] so one broken
+/// provider doesn't abort a comparison run.
+pub async fn bench_model(
+    config: &Config,
+    model_spec: &str,
+    prompt: &str,
+    root: &Path,
+) -> BenchResult {
+    let (model, thinking) = split_model_thinking(model_spec);
+    let mut config = config.clone();
+    config.model = model.to_string();
+    if let Some(thinking) = thinking {
+        config.thinking_level = thinking;
+    }
+
+    let mut result = BenchResult {
+        model: model_spec.to_string(),
+        provider: None,
+        ttft: None,
+        total: Duration::ZERO,
+        usage: Usage::default(),
+        cost_usd: None,
+        error: None,
+    };
+    if let Err(err) = stream_prompt(&config, prompt, root, &mut result).await {
+        result.error = Some(format!("{err:#}"));
+    }
+    result.cost_usd = ModelOption::find_by_id(model)
+        .map(|option| option.pricing)
+        .filter(|pricing| pricing.input > 0.0 || pricing.output > 0.0)
+        .map(|pricing| {
+            pricing.cost(
+                result.usage.input_tokens,
+                result.usage.output_tokens,
+                result.usage.cache_read_input_tokens,
+                result.usage.cache_creation_input_tokens,
+            )
+        });
+    result
+}
+
+async fn stream_prompt(
+    config: &Config,
+    prompt: &str,
+    root: &Path,
+    result: &mut BenchResult,
+) -> Result<()> {
+    let (client, provider) = build_provider_client(config, root)?;
+    result.provider = Some(provider);
+
+    let messages = [ChatMessage::user(prompt)];
+    let mut metrics = StreamMetrics::start();
+    let outcome = async {
+        let mut stream = client.stream_messages(&messages, &[], None).await?;
+        while let Some(event) = stream.next().await {
+            let event = event?;
+            metrics.observe(&event);
+            match event {
+                StreamEvent::MessageStart { usage, .. } => result.usage = usage,
+                StreamEvent::MessageDelta {
+                    usage: Some(delta), ..
+                } => delta.apply_to(&mut result.usage),
+                StreamEvent::Error {
+                    error_type,
+                    message,
+                } => anyhow::bail!("{error_type}: {message}"),
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    .await;
+
+    result.total = metrics.elapsed();
+    result.ttft = metrics.t_first_output;
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(ttft_ms: u64, total_ms: u64, output_tokens: u64) -> BenchResult {
+        BenchResult {
+            model: "m".to_string(),
+            provider: None,
+            ttft: Some(Duration::from_millis(ttft_ms)),
+            total: Duration::from_millis(total_ms),
+            usage: Usage {
+                output_tokens,
+                ..Usage::default()
+            },
+            cost_usd: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn tokens_per_sec_excludes_time_to_first_token() {
+        let tps = result(500, 2500, 100).tokens_per_sec().unwrap();
+        assert!((tps - 50.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn tokens_per_sec_is_none_without_output() {
+        assert!(result(500, 2500, 0).tokens_per_sec().is_none());
+        assert!(result(500, 500, 10).tokens_per_sec().is_none());
+    }
+}
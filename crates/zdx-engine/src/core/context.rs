@@ -23,11 +23,11 @@ use chrono::Utc;
 use minijinja::{Environment, UndefinedBehavior};
 use serde::Serialize;
 
-use crate::config::{Config, paths};
-use crate::core::qmd;
+use crate::config::{Config, PromptSource, SystemPromptMode, paths};
+use crate::core::{context_cache, qmd};
 use crate::providers::{ProviderKind, resolve_provider};
 use crate::skills::{LoadSkillsOptions, LoadSkillsResult, Skill, load_skills, skill_access_path};
-use crate::{prompts, subagents};
+use crate::{prompts, startup_profile, subagents};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct RuntimeEnvVars {
@@ -680,7 +680,7 @@ pub fn render_standalone_prompt_template(
         None
     };
     let skills_result = if inclusion.skills {
-        load_skills_with_config(config, root)
+        startup_profile::time("context.skills", || load_skills_with_config(config, root))
     } else {
         LoadSkillsResult::default()
     };
@@ -831,15 +831,29 @@ const SCOPED_CONTEXT_LIMIT: usize = 200;
 /// Limited to [`SCOPED_CONTEXT_MAX_DEPTH`] levels deep and [`SCOPED_CONTEXT_LIMIT`] files.
 /// Returns files sorted by path for deterministic ordering.
 /// The root context file itself is excluded (it's handled as inline context).
+/// Results are cached per root and reused while no walked directory's mtime
+/// changed (see [`context_cache`]).
 pub fn discover_scoped_context(root: &Path) -> Vec<ScopedContextFile> {
+    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    if let Some(cached) = context_cache::lookup(&canonical_root) {
+        return cached;
+    }
+    let (scoped, visited_dirs) = walk_scoped_context(&canonical_root);
+    context_cache::store(&canonical_root, visited_dirs, &scoped);
+    scoped
+}
+
+fn walk_scoped_context(
+    canonical_root: &Path,
+) -> (Vec<ScopedContextFile>, Vec<context_cache::VisitedDir>) {
     use ignore::WalkBuilder;
 
-    let canonical_root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
     let root_primary = canonical_root.join(PRIMARY_CONTEXT_FILE_NAME);
     let root_fallback = canonical_root.join(FALLBACK_CONTEXT_FILE_NAME);
     let mut candidates: Vec<(String, PathBuf, u8)> = Vec::new();
+    let mut visited_dirs = Vec::new();
 
-    let walker = WalkBuilder::new(&canonical_root)
+    let walker = WalkBuilder::new(canonical_root)
         .hidden(true)
         .git_ignore(true)
         .git_global(false)
@@ -849,6 +863,14 @@ pub fn discover_scoped_context(root: &Path) -> Vec<ScopedContextFile> {
 
     for entry in walker.flatten() {
         let path = entry.path();
+        if entry.file_type().is_some_and(|kind| kind.is_dir())
+            && let Some(mtime) = entry
+                .metadata()
+                .ok()
+                .and_then(|meta| context_cache::dir_mtime(&meta))
+        {
+            visited_dirs.push((path.to_path_buf(), mtime));
+        }
         let Some(priority) = context_file_priority(path) else {
             continue;
         };
@@ -859,7 +881,7 @@ pub fn discover_scoped_context(root: &Path) -> Vec<ScopedContextFile> {
         if canonical == root_primary || canonical == root_fallback {
             continue;
         }
-        if let Ok(relative) = canonical.strip_prefix(&canonical_root)
+        if let Ok(relative) = canonical.strip_prefix(canonical_root)
             && let Some(scope_dir) = relative.parent()
             && !scope_dir.as_os_str().is_empty()
         {
@@ -888,7 +910,7 @@ pub fn discover_scoped_context(root: &Path) -> Vec<ScopedContextFile> {
         }
     }
 
-    scoped
+    (scoped, visited_dirs)
 }
 
 /// Loads inline project context files from the collected hierarchy.
@@ -1074,8 +1096,85 @@ pub struct EffectivePrompt {
     pub warnings: Vec<ContextWarning>,
     /// Skills loaded from configured sources.
     pub loaded_skills: Vec<Skill>,
+    /// The prompt split into source-labelled sections (joined, they form `prompt`).
+    pub sections: Vec<PromptSection>,
+    /// Name of the `[[prompt_variants]]` entry applied for the model, if any.
+    pub prompt_variant: Option<String>,
+}
+
+/// A source-labelled part of the assembled system prompt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptSection {
+    /// Source name (`builtin`, `global_instructions`, ..., `runtime_layers`).
+    pub label: String,
+    pub text: String,
 }
 
+const GLOBAL_INSTRUCTIONS_SECTION: &str = r#"{% if base_prompt %}
+<base_instructions priority="user-defined">
+These are user-defined base instructions. Treat them as baseline instructions for this run unless higher-priority guidance in this prompt overrides them.
+
+{{ base_prompt }}
+</base_instructions>
+{% endif %}"#;
+
+const PROJECT_CONTEXT_SECTION: &str = r"{% if project_context or scoped_context %}
+# Project Instructions
+
+`AGENTS.md` files define project-local rules. If a directory does not contain `AGENTS.md`, use `CLAUDE.md` instead. Deeper files override higher ones. MUST follow these rules when making changes in their scope.
+{% if project_context %}
+{{ project_context }}
+{% endif %}
+{% if scoped_context %}
+The following discovered scoped `AGENTS.md`/`CLAUDE.md` files apply to subdirectories.
+MUST read the relevant file before modifying code in that scope:
+{% for ctx in scoped_context %}- `{{ ctx.path }}`
+{% endfor %}
+{% endif %}
+{% endif %}";
+
+const SKILLS_SECTION: &str = r"{% if skills_list %}
+# Skills
+
+When a task matches an available skill, MUST read the skill file before executing. The skill `<path>` points to `SKILL.md`; use its parent directory as the base for relative references.
+
+<available_skills>
+{% for skill in skills_list %}
+  <skill>
+    <name>{{ skill.name }}</name>
+    <description>{{ skill.description }}</description>
+    <path>{{ skill.path }}</path>
+  </skill>
+{% endfor %}
+</available_skills>
+{% endif %}";
+
+const MEMORY_SECTION: &str = r"{% if memory_index %}
+# Memory
+
+- For any memory-related task, the first step is to read the `memory` skill `SKILL.md`.
+- The memory index lives at `$ZDX_MEMORY_ROOT/Notes/MEMORY.md`.
+- If the user explicitly says 'remember X', MUST save it immediately.
+{% if memory_suggestions %}
+- MAY suggest saving clearly noteworthy items with one line at the end of the response: `💡 Want me to save [specific item] to [specific note]?`
+{% endif %}
+
+<memory_index>
+{{ memory_index }}
+</memory_index>
+{% endif %}";
+
+const RUNTIME_LAYERS_SECTION: &str = r#"{% if instruction_layers %}
+# Runtime Layers
+
+Runtime-specific additive instruction layers. Treat each layer as authoritative for the current surface or workflow.
+{% for instruction_layer in instruction_layers %}
+<instruction_layer index="{{ loop.index }}">
+{{ instruction_layer }}
+</instruction_layer>
+{% endfor %}
+{% endif %}"#;
+
 /// Selects which ambient context blocks are exposed to a rendered prompt template.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PromptContextInclusion {
@@ -1106,7 +1205,9 @@ struct PromptContextSectionsResult {
 fn load_prompt_context_sections(root: &Path, config: &Config) -> PromptContextSectionsResult {
     let mut result = PromptContextSectionsResult::default();
 
-    if let Some(loaded) = load_all_agents_files(root) {
+    if let Some(loaded) =
+        startup_profile::time("context.agents_files", || load_all_agents_files(root))
+    {
         result.loaded_agents_paths = loaded.loaded_paths;
         result.warnings = loaded.warnings;
 
@@ -1116,9 +1217,12 @@ fn load_prompt_context_sections(root: &Path, config: &Config) -> PromptContextSe
         }
     }
 
-    result.scoped_context = discover_scoped_context(root);
+    result.scoped_context =
+        startup_profile::time("context.scoped_discovery", || discover_scoped_context(root));
 
-    if let Some(loaded_memory_index) = load_memory_index(config) {
+    if let Some(loaded_memory_index) =
+        startup_profile::time("context.memory_index", || load_memory_index(config))
+    {
         result.warnings.extend(loaded_memory_index.warnings);
 
         if !loaded_memory_index.content.trim().is_empty() {
@@ -1141,6 +1245,27 @@ fn load_skills_with_config(config: &Config, root: &Path) -> LoadSkillsResult {
     load_skills(&skill_options)
 }
 
+/// Builds the specialized capability catalog, falling back to built-in
+/// metadata (with a warning) when discovery fails.
+fn load_prompt_capabilities(
+    config: &Config,
+    root: &Path,
+    warnings: &mut Vec<ContextWarning>,
+) -> Vec<PromptTemplateCapability> {
+    match build_prompt_template_capabilities(root, config.subagents.enabled) {
+        Ok(capabilities) => capabilities,
+        Err(error) => {
+            warnings.push(ContextWarning {
+                path: None,
+                message: format!(
+                    "Failed to build specialized capability catalog for prompt context: {error}; falling back to built-in capability metadata"
+                ),
+            });
+            fallback_prompt_template_capabilities(config.subagents.enabled)
+        }
+    }
+}
+
 /// Builds an effective prompt from the default system prompt template plus
 /// additive instruction layers rendered with the same context/template pipeline.
 ///
@@ -1154,7 +1279,23 @@ pub fn build_prompt_with_context_and_layers(
     memory_suggestions: bool,
     inclusion: PromptContextInclusion,
 ) -> Result<EffectivePrompt> {
-    let base_prompt = config.effective_system_prompt()?;
+    let variant = config.prompt_variant_for(model);
+    let variant_config = variant.map(|variant| config.with_prompt_variant(variant));
+    let config = variant_config.as_ref().unwrap_or(config);
+    let mut instruction_layers = instruction_layers.to_vec();
+    instruction_layers.extend(variant.and_then(|variant| variant.instructions.as_deref()));
+
+    let layers = &config.prompt_layers;
+    let inclusion = PromptContextInclusion {
+        project_context: inclusion.project_context && layers.project_context,
+        memory_index: inclusion.memory_index && layers.memory,
+        skills: inclusion.skills && layers.skills,
+    };
+    let base_prompt = if layers.global_instructions {
+        config.effective_system_prompt()?
+    } else {
+        None
+    };
 
     let sections_result = load_prompt_context_sections(root, config);
     let loaded_agents_paths = if inclusion.project_context {
@@ -1180,7 +1321,7 @@ pub fn build_prompt_with_context_and_layers(
     };
 
     let skills_result = if inclusion.skills {
-        load_skills_with_config(config, root)
+        startup_profile::time("context.skills", || load_skills_with_config(config, root))
     } else {
         LoadSkillsResult::default()
     };
@@ -1189,46 +1330,38 @@ pub fn build_prompt_with_context_and_layers(
         warnings: skill_warnings,
     } = skills_result;
 
-    let specialized_capabilities = match build_prompt_template_capabilities(
-        root,
-        config.subagents.enabled,
-    ) {
-        Ok(capabilities) => capabilities,
-        Err(error) => {
-            warnings.push(ContextWarning {
-                path: None,
-                message: format!(
-                    "Failed to build specialized capability catalog for prompt context: {error}; falling back to built-in capability metadata"
-                ),
-            });
-            fallback_prompt_template_capabilities(config.subagents.enabled)
-        }
-    };
+    let specialized_capabilities = startup_profile::time("context.capabilities", || {
+        load_prompt_capabilities(config, root, &mut warnings)
+    });
 
-    let mut vars = build_prompt_template_vars(
-        root,
-        model,
-        PromptTemplateSections {
-            base_prompt: base_prompt.as_deref(),
-            project_context: inline_project_context.as_deref(),
-            memory_index: memory_index.as_deref(),
-            memory_suggestions,
-            skills_list: &skills,
-            scoped_context: &scoped_context,
-            specialized_capabilities: &specialized_capabilities,
-        },
-    );
+    let mut vars = startup_profile::time("context.template_vars", || {
+        build_prompt_template_vars(
+            root,
+            model,
+            PromptTemplateSections {
+                base_prompt: base_prompt.as_deref(),
+                project_context: inline_project_context.as_deref(),
+                memory_index: memory_index.as_deref(),
+                memory_suggestions,
+                skills_list: &skills,
+                scoped_context: &scoped_context,
+                specialized_capabilities: &specialized_capabilities,
+            },
+        )
+    });
 
-    vars.instruction_layers = render_instruction_layers(instruction_layers, &vars, &mut warnings);
+    vars.instruction_layers = render_instruction_layers(&instruction_layers, &vars, &mut warnings);
 
-    let prompt = render_system_prompt_with_fallback(
-        config,
-        &vars,
-        &mut warnings,
-        base_prompt.as_deref(),
-        inline_project_context.as_deref(),
-        memory_index.as_deref(),
-    );
+    let (prompt, sections) = startup_profile::time("context.render", || {
+        assemble_prompt(
+            config,
+            &vars,
+            &mut warnings,
+            base_prompt.as_deref(),
+            inline_project_context.as_deref(),
+            memory_index.as_deref(),
+        )
+    });
 
     if skills.len() > 20 {
         warnings.push(ContextWarning {
@@ -1248,6 +1381,143 @@ pub fn build_prompt_with_context_and_layers(
         scoped_context_paths: scoped_context.iter().map(|sa| sa.path.clone()).collect(),
         warnings,
         loaded_skills: skills,
+        sections,
+        prompt_variant: variant.map(|variant| variant.name.clone()),
+    })
+}
+
+/// Renders the final prompt: the template's own layout by default, or
+/// source-by-source layering when `[prompt_layers].order` or `replace` mode is
+/// configured.
+fn assemble_prompt(
+    config: &Config,
+    vars: &PromptTemplateVars,
+    warnings: &mut Vec<ContextWarning>,
+    base_prompt: Option<&str>,
+    inline_project_context: Option<&str>,
+    memory_index: Option<&str>,
+) -> (Option<String>, Vec<PromptSection>) {
+    if config.system_prompt_mode == SystemPromptMode::Append
+        && config.prompt_layers.order.is_empty()
+    {
+        let prompt = render_system_prompt_with_fallback(
+            config,
+            vars,
+            warnings,
+            base_prompt,
+            inline_project_context,
+            memory_index,
+        );
+        let sections = prompt
+            .iter()
+            .map(|text| PromptSection {
+                label: PromptSource::Builtin.name().to_string(),
+                text: text.clone(),
+            })
+            .collect();
+        return (prompt, sections);
+    }
+
+    if config.system_prompt_mode == SystemPromptMode::Replace && base_prompt.is_none() {
+        warnings.push(ContextWarning {
+            path: None,
+            message: "system_prompt_mode = \"replace\" but no system prompt is configured; the built-in prompt is omitted".to_string(),
+        });
+    }
+    assemble_prompt_layers(config, vars, warnings)
+}
+
+/// Assembles the prompt source by source in `[prompt_layers].order`.
+///
+/// The built-in template renders with the layered sources cleared so each
+/// appears only once, at its configured position. In `replace` mode the
+/// built-in prompt is dropped, global instructions are used verbatim, and
+/// runtime instruction layers are appended last.
+fn assemble_prompt_layers(
+    config: &Config,
+    vars: &PromptTemplateVars,
+    warnings: &mut Vec<ContextWarning>,
+) -> (Option<String>, Vec<PromptSection>) {
+    let replace = config.system_prompt_mode == SystemPromptMode::Replace;
+    let mut sections = Vec::new();
+
+    for source in config.prompt_layers.resolved_order() {
+        let text = match source {
+            PromptSource::Builtin if replace => None,
+            PromptSource::Builtin => {
+                let mut builtin_vars = vars.clone();
+                builtin_vars.base_prompt.clear();
+                builtin_vars.project_context.clear();
+                builtin_vars.scoped_context.clear();
+                builtin_vars.skills_list.clear();
+                builtin_vars.available_skills.clear();
+                builtin_vars.memory_index.clear();
+                render_system_prompt_with_fallback(
+                    config,
+                    &builtin_vars,
+                    warnings,
+                    None,
+                    None,
+                    None,
+                )
+            }
+            PromptSource::GlobalInstructions if replace => {
+                let trimmed = vars.base_prompt.trim();
+                (!trimmed.is_empty()).then(|| trimmed.to_string())
+            }
+            PromptSource::GlobalInstructions => {
+                render_layer_section(GLOBAL_INSTRUCTIONS_SECTION, source.name(), vars, warnings)
+            }
+            PromptSource::ProjectContext => {
+                render_layer_section(PROJECT_CONTEXT_SECTION, source.name(), vars, warnings)
+            }
+            PromptSource::Skills => {
+                render_layer_section(SKILLS_SECTION, source.name(), vars, warnings)
+            }
+            PromptSource::Memory => {
+                render_layer_section(MEMORY_SECTION, source.name(), vars, warnings)
+            }
+        };
+        if let Some(text) = text {
+            sections.push(PromptSection {
+                label: source.name().to_string(),
+                text,
+            });
+        }
+    }
+
+    if replace
+        && let Some(text) =
+            render_layer_section(RUNTIME_LAYERS_SECTION, "runtime_layers", vars, warnings)
+    {
+        sections.push(PromptSection {
+            label: "runtime_layers".to_string(),
+            text,
+        });
+    }
+
+    let prompt = (!sections.is_empty()).then(|| {
+        sections
+            .iter()
+            .map(|section| section.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    });
+    (prompt, sections)
+}
+
+fn render_layer_section(
+    template: &str,
+    label: &str,
+    vars: &PromptTemplateVars,
+    warnings: &mut Vec<ContextWarning>,
+) -> Option<String> {
+    render_prompt_template(template, vars).unwrap_or_else(|error| {
+        warnings.push(ContextWarning {
+            path: None,
+            message: format!("Failed to render {label} prompt section: {error}; skipping it"),
+        });
+        None
     })
 }
 
@@ -1996,6 +2266,147 @@ mod tests {
         assert!(prompt.contains("<memory_index>"));
     }
 
+    fn layered_test_config(memory_root: &Path) -> crate::config::Config {
+        let mut config = crate::config::Config {
+            system_prompt: Some("Custom base".to_string()),
+            ..Default::default()
+        };
+        config.memory.root = Some(memory_root.display().to_string());
+        config.subagents.enabled = false;
+        config.skills.sources = SkillSourceToggles {
+            zdx_user: false,
+            zdx_project: false,
+            codex_user: false,
+            claude_user: false,
+            claude_project: false,
+            agents_user: false,
+            agents_project: false,
+        };
+        config
+    }
+
+    #[test]
+    fn test_replace_mode_drops_builtin_and_follows_order() {
+        let project_root = tempdir().unwrap();
+        fs::write(project_root.path().join("AGENTS.md"), "Project rule").unwrap();
+        let memory_root = tempdir().unwrap();
+        fs::create_dir_all(memory_root.path().join("Notes")).unwrap();
+        fs::write(
+            memory_root
+                .path()
+                .join("Notes")
+                .join(MEMORY_INDEX_FILE_NAME),
+            "Remembered fact",
+        )
+        .unwrap();
+
+        let mut config = layered_test_config(memory_root.path());
+        config.system_prompt_mode = SystemPromptMode::Replace;
+        config.prompt_layers.skills = false;
+        config.prompt_layers.order = vec![PromptSource::Memory, PromptSource::ProjectContext];
+
+        let effective = build_effective_system_prompt_with_paths_and_instruction_layers(
+            &config,
+            project_root.path(),
+            &["Surface layer"],
+            false,
+        )
+        .unwrap();
+        let labels: Vec<&str> = effective
+            .sections
+            .iter()
+            .map(|section| section.label.as_str())
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "memory",
+                "project_context",
+                "global_instructions",
+                "runtime_layers"
+            ]
+        );
+        let prompt = effective.prompt.unwrap();
+        assert!(!prompt.contains("# Core Behavior"));
+        assert!(prompt.find("Remembered fact").unwrap() < prompt.find("Project rule").unwrap());
+        assert!(prompt.contains("\n\nCustom base\n\n"));
+        assert!(prompt.ends_with("</instruction_layer>"));
+    }
+
+    #[test]
+    fn test_prompt_variant_applies_for_matching_model() {
+        let project_root = tempdir().unwrap();
+        let memory_root = tempdir().unwrap();
+        let mut config = layered_test_config(memory_root.path());
+        config.prompt_variants = vec![crate::config::PromptVariantConfig {
+            name: "small".to_string(),
+            models: vec!["*mini*".to_string()],
+            system_prompt: Some("Terse base".to_string()),
+            instructions: Some("Call one tool at a time.".to_string()),
+            ..Default::default()
+        }];
+
+        let small = build_prompt_with_context_and_layers(
+            &config,
+            project_root.path(),
+            "openai:gpt-5-mini",
+            &[],
+            false,
+            PromptContextInclusion::default(),
+        )
+        .unwrap();
+        assert_eq!(small.prompt_variant.as_deref(), Some("small"));
+        let prompt = small.prompt.unwrap();
+        assert!(prompt.contains("Terse base"));
+        assert!(!prompt.contains("Custom base"));
+        assert!(prompt.contains("Call one tool at a time."));
+
+        let full = build_prompt_with_context_and_layers(
+            &config,
+            project_root.path(),
+            "anthropic:claude-sonnet-4-5",
+            &[],
+            false,
+            PromptContextInclusion::default(),
+        )
+        .unwrap();
+        assert_eq!(full.prompt_variant, None);
+        assert!(full.prompt.unwrap().contains("Custom base"));
+    }
+
+    #[test]
+    fn test_prompt_layer_flags_disable_sources() {
+        let project_root = tempdir().unwrap();
+        fs::write(project_root.path().join("AGENTS.md"), "Project rule").unwrap();
+        let memory_root = tempdir().unwrap();
+
+        let mut config = layered_test_config(memory_root.path());
+        config.prompt_layers.global_instructions = false;
+        config.prompt_layers.project_context = false;
+
+        let effective =
+            build_effective_system_prompt_with_paths(&config, project_root.path(), false).unwrap();
+        let prompt = effective.prompt.unwrap();
+        assert!(prompt.contains("# Core Behavior"));
+        assert!(!prompt.contains("Custom base"));
+        assert!(!prompt.contains("Project rule"));
+        assert!(effective.loaded_agents_paths.is_empty());
+        assert_eq!(effective.sections.len(), 1);
+        assert_eq!(effective.sections[0].label, "builtin");
+
+        config.prompt_layers.global_instructions = true;
+        config.prompt_layers.order = vec![PromptSource::GlobalInstructions];
+        let effective =
+            build_effective_system_prompt_with_paths(&config, project_root.path(), false).unwrap();
+        assert_eq!(effective.sections[0].label, "global_instructions");
+        assert_eq!(effective.sections[1].label, "builtin");
+        assert_eq!(
+            effective.prompt.unwrap().matches("Custom base").count(),
+            1,
+            "global instructions must not be repeated inside the builtin section"
+        );
+    }
+
     #[test]
     fn test_unreadable_agents_triggers_warning() {
         use std::os::unix::fs::PermissionsExt;
@@ -0,0 +1,147 @@
+//! On-disk cache for scoped context discovery.
+//!
+//! Walking a big repo for nested `AGENTS.md`/`CLAUDE.md` files is the slowest
+//! part of building the prompt. The walk's result is stored per root under
+//! `$ZDX_HOME/cache/context/` together with the mtime of every directory it
+//! visited. Adding, removing, or renaming a file changes its directory's
+//! mtime, so the entry is reused only while all visited directories are
+//! unchanged. The cache is disposable: unreadable entries are misses.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::paths;
+use crate::core::context::ScopedContextFile;
+
+/// Bumped when the walk rules change so old entries are ignored.
+const CACHE_VERSION: u32 = 1;
+
+/// A directory visited by the walk with its mtime (nanoseconds since epoch).
+pub(crate) type VisitedDir = (PathBuf, u128);
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    version: u32,
+    root: PathBuf,
+    dirs: Vec<VisitedDir>,
+    files: Vec<(PathBuf, String)>,
+}
+
+/// Returns the directory's mtime as nanoseconds since the epoch.
+pub(crate) fn dir_mtime(meta: &fs::Metadata) -> Option<u128> {
+    let modified = meta.modified().ok()?;
+    Some(modified.duration_since(UNIX_EPOCH).ok()?.as_nanos())
+}
+
+/// Returns the cached discovery for `root` when no visited directory changed.
+pub(crate) fn lookup(root: &Path) -> Option<Vec<ScopedContextFile>> {
+    lookup_in(&cache_dir(), root)
+}
+
+/// Stores the discovery for `root`. Failures are logged and ignored.
+pub(crate) fn store(root: &Path, dirs: Vec<VisitedDir>, files: &[ScopedContextFile]) {
+    // Unit tests must not write into the user's real `~/.zdx`.
+    #[cfg(test)]
+    if std::env::var_os("ZDX_HOME").is_none() {
+        return;
+    }
+    store_in(&cache_dir(), root, dirs, files);
+}
+
+fn cache_dir() -> PathBuf {
+    paths::zdx_home().join("cache").join("context")
+}
+
+fn entry_path(cache_dir: &Path, root: &Path) -> PathBuf {
+    let key = format!("{:x}", Sha256::digest(root.as_os_str().as_encoded_bytes()));
+    cache_dir.join(format!("{key}.json"))
+}
+
+fn lookup_in(cache_dir: &Path, root: &Path) -> Option<Vec<ScopedContextFile>> {
+    let raw = fs::read(entry_path(cache_dir, root)).ok()?;
+    let entry: CacheEntry = serde_json::from_slice(&raw).ok()?;
+    if entry.version != CACHE_VERSION || entry.root != root {
+        return None;
+    }
+    let unchanged = entry.dirs.iter().all(|(dir, mtime)| {
+        fs::metadata(dir)
+            .ok()
+            .and_then(|meta| dir_mtime(&meta))
+            .is_some_and(|current| current == *mtime)
+    });
+    unchanged.then(|| {
+        entry
+            .files
+            .into_iter()
+            .map(|(path, scope)| ScopedContextFile { path, scope })
+            .collect()
+    })
+}
+
+fn store_in(cache_dir: &Path, root: &Path, dirs: Vec<VisitedDir>, files: &[ScopedContextFile]) {
+    let entry = CacheEntry {
+        version: CACHE_VERSION,
+        root: root.to_path_buf(),
+        dirs,
+        files: files
+            .iter()
+            .map(|file| (file.path.clone(), file.scope.clone()))
+            .collect(),
+    };
+    let path = entry_path(cache_dir, root);
+    let temp_path = path.with_extension("json.tmp");
+    let result = fs::create_dir_all(cache_dir)
+        .and_then(|()| fs::write(&temp_path, serde_json::to_vec(&entry)?))
+        .and_then(|()| fs::rename(&temp_path, &path));
+    if let Err(err) = result {
+        tracing::debug!(root = %root.display(), %err, "Failed to store context discovery cache");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn visited(dir: &Path) -> VisitedDir {
+        let mtime = dir_mtime(&fs::metadata(dir).unwrap()).unwrap();
+        (dir.to_path_buf(), mtime)
+    }
+
+    #[test]
+    fn test_cache_hits_until_a_visited_dir_changes() {
+        let cache = tempfile::tempdir().unwrap();
+        let project = tempfile::tempdir().unwrap();
+        let root = project.path().canonicalize().unwrap();
+        let sub = root.join("sub");
+        fs::create_dir(&sub).unwrap();
+        let files = vec![ScopedContextFile {
+            path: sub.join("AGENTS.md"),
+            scope: "sub".to_string(),
+        }];
+
+        assert!(lookup_in(cache.path(), &root).is_none());
+        store_in(
+            cache.path(),
+            &root,
+            vec![visited(&root), visited(&sub)],
+            &files,
+        );
+        let hit = lookup_in(cache.path(), &root).unwrap();
+        assert_eq!(hit.len(), 1);
+        assert_eq!(hit[0].path, files[0].path);
+        assert_eq!(hit[0].scope, "sub");
+
+        // A stale recorded mtime (as after adding a file) invalidates the entry.
+        store_in(
+            cache.path(),
+            &root,
+            vec![visited(&root), (sub.clone(), 0)],
+            &files,
+        );
+        assert!(lookup_in(cache.path(), &root).is_none());
+    }
+}
@@ -0,0 +1,400 @@
+//! Two-model discussions (`/duel modelA modelB <topic>`).
+//!
+//! Each speaker runs an ordinary tool-less turn over its own view of the
+//! shared discussion: its earlier turns are assistant messages, the other
+//! speaker's turns and moderator interjections arrive as labelled user
+//! messages. The thread records the whole duel in order — a tagged opening
+//! message, each turn as an assistant message headed with the speaker, and
+//! interjections — so it reloads and continues like any other thread.
+
+use anyhow::{Result, ensure};
+use tokio_util::sync::CancellationToken;
+
+use crate::config::Config;
+use crate::core::agent::{AgentOptions, ToolSelection, TurnOptions, run_turn_with_cancel};
+use crate::core::event_bus::{EventBus, EventFilter};
+use crate::core::events::AgentEvent;
+use crate::core::thread_persistence::{ThreadEvent, UsagePersistor};
+use crate::prompts::DUEL_PROMPT_TEMPLATE;
+use crate::providers::{ChatContentBlock, ChatMessage, MessageContent};
+
+/// Message `phase` tagging the opening message and interjections of a duel.
+pub const DUEL_PHASE: &str = "duel";
+
+/// Speaker label for the human's interjections.
+pub const MODERATOR: &str = "Moderator";
+
+/// One of the two speakers. `A` opens the discussion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DuelSide {
+    A,
+    B,
+}
+
+impl DuelSide {
+    /// The opposing speaker.
+    #[must_use]
+    pub fn other(self) -> Self {
+        match self {
+            Self::A => Self::B,
+            Self::B => Self::A,
+        }
+    }
+
+    fn index(self) -> usize {
+        match self {
+            Self::A => 0,
+            Self::B => 1,
+        }
+    }
+}
+
+/// An entry of the discussion, in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DuelEntry {
+    Turn { side: DuelSide, text: String },
+    Interjection(String),
+}
+
+/// State of one discussion: speakers, topic, round limit, and what was said.
+#[derive(Debug, Clone)]
+pub struct Duel {
+    pub topic: String,
+    /// Model specs (`provider:model[@thinking]`) for sides A and B.
+    pub models: [String; 2],
+    /// Rounds before the duel ends; each round is one turn per speaker.
+    pub max_rounds: usize,
+    entries: Vec<DuelEntry>,
+}
+
+impl Duel {
+    /// Starts a discussion with no turns taken.
+    pub fn new(models: [String; 2], topic: impl Into<String>, max_rounds: usize) -> Self {
+        Self {
+            topic: topic.into(),
+            models,
+            max_rounds: max_rounds.max(1),
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn entries(&self) -> &[DuelEntry] {
+        &self.entries
+    }
+
+    /// Model spec for `side`.
+    pub fn model(&self, side: DuelSide) -> &str {
+        &self.models[side.index()]
+    }
+
+    /// Transcript label for `side`: its model spec, disambiguated when both
+    /// sides run the same model.
+    pub fn label(&self, side: DuelSide) -> String {
+        let model = self.model(side);
+        if self.models[0] != self.models[1] {
+            return model.to_string();
+        }
+        match side {
+            DuelSide::A => format!("{model} (A)"),
+            DuelSide::B => format!("{model} (B)"),
+        }
+    }
+
+    pub fn turns_taken(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry, DuelEntry::Turn { .. }))
+            .count()
+    }
+
+    pub fn max_turns(&self) -> usize {
+        self.max_rounds * 2
+    }
+
+    /// Speaker due next, or `None` once the round limit is reached.
+    pub fn next_side(&self) -> Option<DuelSide> {
+        let taken = self.turns_taken();
+        if taken >= self.max_turns() {
+            None
+        } else if taken.is_multiple_of(2) {
+            Some(DuelSide::A)
+        } else {
+            Some(DuelSide::B)
+        }
+    }
+
+    pub fn record_turn(&mut self, side: DuelSide, text: impl Into<String>) {
+        self.entries.push(DuelEntry::Turn {
+            side,
+            text: text.into(),
+        });
+    }
+
+    pub fn interject(&mut self, text: impl Into<String>) {
+        self.entries.push(DuelEntry::Interjection(text.into()));
+    }
+
+    /// System prompt for `side`'s next turn.
+    pub fn system_prompt_for(&self, side: DuelSide) -> String {
+        DUEL_PROMPT_TEMPLATE
+            .replace("{{SELF}}", &self.label(side))
+            .replace("{{OTHER}}", &self.label(side.other()))
+            .replace("{{TOPIC}}", &self.topic)
+            .replace("{{TURN}}", &(self.turns_taken() + 1).to_string())
+            .replace("{{MAX_TURNS}}", &self.max_turns().to_string())
+    }
+
+    /// The discussion as `side` sees it: its own turns as assistant
+    /// messages, everything else as labelled user messages. Consecutive
+    /// messages of the same role are merged so roles strictly alternate.
+    pub fn messages_for(&self, side: DuelSide) -> Vec<ChatMessage> {
+        let other = self.label(side.other());
+        let opening = match side {
+            DuelSide::A => format!("Topic: {}\n\nYou open the discussion.", self.topic),
+            DuelSide::B => format!("Topic: {}\n\n{other} opens the discussion.", self.topic),
+        };
+
+        let mut turns: Vec<(&str, String)> = vec![("user", opening)];
+        for entry in &self.entries {
+            let turn = match entry {
+                DuelEntry::Turn {
+                    side: speaker,
+                    text,
+                } if *speaker == side => ("assistant", text.clone()),
+                DuelEntry::Turn { text, .. } => ("user", format!("{other}: {text}")),
+                DuelEntry::Interjection(text) => ("user", format!("{MODERATOR}: {text}")),
+            };
+            match turns.last_mut() {
+                Some((role, merged)) if *role == turn.0 => {
+                    merged.push_str("\n\n");
+                    merged.push_str(&turn.1);
+                }
+                _ => turns.push(turn),
+            }
+        }
+
+        turns
+            .into_iter()
+            .map(|(role, text)| match role {
+                "assistant" => ChatMessage::assistant_text(text, None),
+                _ => ChatMessage::user(text),
+            })
+            .collect()
+    }
+
+    /// Thread message recorded when the duel starts.
+    pub fn opening_message(&self) -> ChatMessage {
+        duel_message(format!(
+            "Duel: {} vs {} on: {}",
+            self.label(DuelSide::A),
+            self.label(DuelSide::B),
+            self.topic
+        ))
+    }
+}
+
+/// Thread message recorded for a moderator interjection.
+pub fn interjection_message(text: &str) -> ChatMessage {
+    duel_message(format!("{MODERATOR}: {}", text.trim()))
+}
+
+fn duel_message(text: String) -> ChatMessage {
+    ChatMessage {
+        role: "user".to_string(),
+        phase: Some(DUEL_PHASE.to_string()),
+        content: MessageContent::Text(text),
+    }
+}
+
+/// Returns true when `message` opens a duel or interjects in one.
+pub fn is_duel_message(message: &ChatMessage) -> bool {
+    message.role == "user" && message.phase.as_deref() == Some(DUEL_PHASE)
+}
+
+/// Thread text of a turn: the speaker on the first line, then the reply.
+/// Assistant messages carry no duel tag (providers reject unknown
+/// assistant phases), so the header is what identifies the speaker.
+pub fn turn_text(speaker: &str, reply: &str) -> String {
+    format!("[{speaker}]\n{reply}")
+}
+
+/// Splits a turn's thread text back into speaker and reply.
+pub fn parse_turn_text(text: &str) -> Option<(&str, &str)> {
+    text.strip_prefix('[')?.split_once("]\n")
+}
+
+/// Speaker and reply of a turn replayed from the thread: an assistant
+/// message whose only text carries the speaker header.
+pub fn parse_turn(message: &ChatMessage) -> Option<(&str, &str)> {
+    if message.role != "assistant" {
+        return None;
+    }
+    let text = match &message.content {
+        MessageContent::Text(text) => text,
+        MessageContent::Blocks(blocks) => match blocks.as_slice() {
+            [ChatContentBlock::Text { text, .. }] => text,
+            _ => return None,
+        },
+    };
+    parse_turn_text(text)
+}
+
+/// A finished turn: the reply and the thread events to persist for it
+/// (usage followed by the speaker-headed assistant message).
+#[derive(Debug)]
+pub struct DuelTurn {
+    pub text: String,
+    pub events: Vec<ThreadEvent>,
+}
+
+/// Runs `side`'s next turn: a tool-less run on its model over
+/// [`Duel::messages_for`], with the duel system prompt.
+///
+/// # Errors
+/// Returns an error when the provider request fails, the turn is
+/// cancelled, or the model replies with no text.
+pub async fn run_duel_turn(
+    duel: &Duel,
+    side: DuelSide,
+    config: &Config,
+    options: &AgentOptions,
+    thread_id: Option<&str>,
+    cancel: Option<CancellationToken>,
+) -> Result<DuelTurn> {
+    let (model, thinking_level) = crate::models::split_model_thinking(duel.model(side));
+    let mut options = options.clone();
+    options.activity_kind = Some("duel".to_string());
+    options.steering = None;
+    options.ask_user = None;
+    options.turn = TurnOptions {
+        model: Some(model.to_string()),
+        thinking_level,
+        tool_selection: Some(ToolSelection::Explicit(Vec::new())),
+        ..Default::default()
+    };
+
+    // Only usage is kept: the reply is persisted below with its speaker
+    // header instead of as the raw message the run produced.
+    let mut bus = EventBus::new();
+    let mut usage_rx = bus.subscribe(
+        "duel",
+        EventFilter::Matching(|event| matches!(event, AgentEvent::UsageUpdate { .. })),
+    );
+    let collect_usage = async {
+        let mut persistor = UsagePersistor::new();
+        let mut events = Vec::new();
+        while let Some(event) = usage_rx.recv().await {
+            events.extend(persistor.handle_event(&event));
+        }
+        events.extend(persistor.finish());
+        events
+    };
+    let system_prompt = duel.system_prompt_for(side);
+    let (result, mut events) = tokio::join!(
+        run_turn_with_cancel(
+            duel.messages_for(side),
+            config,
+            &options,
+            Some(&system_prompt),
+            thread_id,
+            bus.into_sender(),
+            cancel,
+        ),
+        collect_usage
+    );
+    events.retain(|event| matches!(event, ThreadEvent::Usage { .. }));
+
+    let (text, _) = result?;
+    let text = text.trim().to_string();
+    ensure!(
+        !text.is_empty(),
+        "{} returned an empty reply",
+        duel.label(side)
+    );
+
+    let mut message = ThreadEvent::assistant_message(turn_text(&duel.label(side), &text));
+    if let ThreadEvent::Message { model: slot, .. } = &mut message {
+        *slot = Some(model.to_string());
+    }
+    events.push(message);
+    Ok(DuelTurn { text, events })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(message: &ChatMessage) -> &str {
+        match &message.content {
+            MessageContent::Text(text) => text,
+            MessageContent::Blocks(_) => panic!("expected text content"),
+        }
+    }
+
+    fn sample() -> Duel {
+        let mut duel = Duel::new(
+            ["openai:gpt-5".to_string(), "anthropic:claude".to_string()],
+            "tabs or spaces",
+            2,
+        );
+        duel.record_turn(DuelSide::A, "tabs");
+        duel.interject("be concrete");
+        duel.record_turn(DuelSide::B, "spaces");
+        duel
+    }
+
+    #[test]
+    fn each_side_sees_its_own_turns_as_assistant_messages() {
+        let duel = sample();
+
+        let a = duel.messages_for(DuelSide::A);
+        let roles: Vec<_> = a.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant", "user"]);
+        assert_eq!(text(&a[1]), "tabs");
+        assert_eq!(
+            text(&a[2]),
+            "Moderator: be concrete\n\nanthropic:claude: spaces"
+        );
+
+        let b = duel.messages_for(DuelSide::B);
+        let roles: Vec<_> = b.iter().map(|m| m.role.as_str()).collect();
+        assert_eq!(roles, ["user", "assistant"]);
+        assert!(text(&b[0]).ends_with("openai:gpt-5: tabs\n\nModerator: be concrete"));
+        assert_eq!(text(&b[1]), "spaces");
+    }
+
+    #[test]
+    fn speakers_alternate_until_the_round_limit() {
+        let mut duel = sample();
+        assert_eq!(duel.next_side(), Some(DuelSide::A));
+        duel.record_turn(DuelSide::A, "still tabs");
+        assert_eq!(duel.next_side(), Some(DuelSide::B));
+        duel.record_turn(DuelSide::B, "agree to disagree");
+        assert_eq!(duel.next_side(), None);
+    }
+
+    #[test]
+    fn same_model_sides_get_distinct_labels() {
+        let duel = Duel::new(["m".to_string(), "m".to_string()], "t", 1);
+        assert_eq!(duel.label(DuelSide::A), "m (A)");
+        assert_eq!(duel.label(DuelSide::B), "m (B)");
+        let prompt = duel.system_prompt_for(DuelSide::B);
+        assert!(
+            prompt.starts_with(
+                "You are m (B), one of two AI models in a live discussion with m (A)."
+            )
+        );
+        assert!(prompt.contains("turn 1 of 2"));
+        assert!(!prompt.contains("{{"));
+    }
+
+    #[test]
+    fn turn_text_round_trips_model_specs_with_colons() {
+        let stored = turn_text("openai:gpt-5@high", "hello\n[not a header]");
+        assert_eq!(
+            parse_turn_text(&stored),
+            Some(("openai:gpt-5@high", "hello\n[not a header]"))
+        );
+        assert_eq!(parse_turn_text("plain reply"), None);
+    }
+}
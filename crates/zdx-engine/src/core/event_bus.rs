@@ -0,0 +1,444 @@
+//! Agent event bus.
+//!
+//! A turn publishes its `AgentEvent`s on one [`EventSender`]; every sink (TUI,
+//! thread persistence, audit log, exec renderer, bot progress) holds its own
+//! [`EventSubscriber`]. Subscribers are registered on the [`EventBus`] before
+//! it is turned into the turn's sender, so none of them can miss the first
+//! event, and all of them see the stream close once the last sender clone
+//! drops.
+//!
+//! Subscribers pick one of two delivery modes:
+//!
+//! - [`EventBus::subscribe`] is lossless: the subscriber owns an unbounded
+//!   queue, and its [`EventFilter`] is applied before an event is queued, so
+//!   persistence and audit never buffer deltas they would discard. A slow
+//!   lossless sink only grows its own queue.
+//! - [`EventBus::subscribe_lossy`] reads a shared bounded `broadcast` channel.
+//!   A subscriber that falls more than the bus capacity behind skips the
+//!   oldest events, logs and counts them, and carries on. Display sinks (TUI,
+//!   bot progress) use this so a stalled renderer holds a fixed amount of
+//!   memory.
+//!
+//! Neither mode ever stalls the turn or the other sinks. Lossless subscribers
+//! that drop are removed on the next send.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut bus = EventBus::new();
+//! let ui_rx = bus.subscribe_lossy("tui", EventFilter::All);
+//! let persist_rx = bus.subscribe("persist", EventFilter::NoDeltas);
+//! spawn_thread_persist_task(thread, persist_rx);
+//!
+//! run_turn(messages, &config, &options, None, None, bus.into_sender()).await?;
+//! ```
+
+use std::sync::{Arc, Mutex};
+
+use tokio::sync::{broadcast, mpsc};
+
+use crate::core::events::AgentEvent;
+
+/// Events a lossy subscriber may fall behind by before it starts skipping.
+pub const DEFAULT_CAPACITY: usize = 4096;
+
+/// Which events a subscriber receives.
+#[derive(Debug, Clone, Copy, Default)]
+pub enum EventFilter {
+    /// Every event.
+    #[default]
+    All,
+    /// Everything except streaming previews (`*Delta`, `ToolInputPartial`),
+    /// whose content also arrives in the matching completed event.
+    NoDeltas,
+    /// Events for which the predicate returns `true`.
+    Matching(fn(&AgentEvent) -> bool),
+}
+
+impl EventFilter {
+    /// Returns true if a subscriber with this filter receives `event`.
+    pub fn matches(self, event: &AgentEvent) -> bool {
+        match self {
+            Self::All => true,
+            Self::NoDeltas => !is_delta(event),
+            Self::Matching(predicate) => predicate(event),
+        }
+    }
+}
+
+fn is_delta(event: &AgentEvent) -> bool {
+    matches!(
+        event,
+        AgentEvent::ReasoningDelta { .. }
+            | AgentEvent::AssistantDelta { .. }
+            | AgentEvent::ToolInputDelta { .. }
+            | AgentEvent::ToolInputPartial { .. }
+            | AgentEvent::ToolOutputDelta { .. }
+    )
+}
+
+struct Subscription {
+    tx: mpsc::UnboundedSender<Arc<AgentEvent>>,
+    filter: EventFilter,
+}
+
+/// Subscriber registry for one turn's agent events.
+pub struct EventBus {
+    lossless: Vec<Subscription>,
+    lossy: broadcast::Sender<Arc<AgentEvent>>,
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventBus {
+    /// Creates a bus whose lossy subscribers buffer [`DEFAULT_CAPACITY`]
+    /// events.
+    pub fn new() -> Self {
+        Self::with_capacity(DEFAULT_CAPACITY)
+    }
+
+    /// Creates a bus whose lossy subscribers buffer up to `capacity` events.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let (lossy, _) = broadcast::channel(capacity.max(1));
+        Self {
+            lossless: Vec::new(),
+            lossy,
+        }
+    }
+
+    /// Registers a lossless subscriber. `name` identifies it in debug output.
+    pub fn subscribe(&mut self, name: &'static str, filter: EventFilter) -> EventSubscriber {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.lossless.push(Subscription { tx, filter });
+        EventSubscriber {
+            rx: Receiver::Lossless(rx),
+            name,
+            filter,
+            skipped: 0,
+        }
+    }
+
+    /// Registers a lossy subscriber that skips the oldest events when it
+    /// falls more than the bus capacity behind. `name` identifies it in lag
+    /// warnings.
+    pub fn subscribe_lossy(&self, name: &'static str, filter: EventFilter) -> EventSubscriber {
+        EventSubscriber {
+            rx: Receiver::Lossy(self.lossy.subscribe()),
+            name,
+            filter,
+            skipped: 0,
+        }
+    }
+
+    /// Hands the bus to the producer. No subscribers can be added afterwards.
+    pub fn into_sender(self) -> EventSender {
+        EventSender {
+            lossless: Arc::new(Mutex::new(self.lossless)),
+            lossy: self.lossy,
+        }
+    }
+}
+
+/// Creates a bus with a single unfiltered lossless subscriber.
+pub fn create_event_channel() -> (EventSender, EventSubscriber) {
+    let mut bus = EventBus::new();
+    let rx = bus.subscribe("channel", EventFilter::All);
+    (bus.into_sender(), rx)
+}
+
+/// Producer side of an [`EventBus`].
+#[derive(Clone)]
+pub struct EventSender {
+    lossless: Arc<Mutex<Vec<Subscription>>>,
+    lossy: broadcast::Sender<Arc<AgentEvent>>,
+}
+
+impl EventSender {
+    /// Sends an event to every subscriber. Never blocks; lossless subscribers
+    /// that dropped are removed.
+    pub fn send(&self, ev: AgentEvent) {
+        let event = Arc::new(ev);
+        {
+            let mut lossless = self
+                .lossless
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            lossless.retain(|sub| {
+                if sub.tx.is_closed() {
+                    return false;
+                }
+                !sub.filter.matches(&event) || sub.tx.send(Arc::clone(&event)).is_ok()
+            });
+        }
+        // Fails only when no lossy subscriber is attached.
+        let _ = self.lossy.send(event);
+    }
+
+    #[cfg(test)]
+    fn subscriber_count(&self) -> usize {
+        self.lossless.lock().unwrap().len()
+    }
+}
+
+/// Why [`EventSubscriber::try_recv`] returned no event.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TryRecvError {
+    /// No event is waiting.
+    Empty,
+    /// Every sender dropped and the queued events are drained.
+    Closed,
+}
+
+enum Receiver {
+    Lossless(mpsc::UnboundedReceiver<Arc<AgentEvent>>),
+    Lossy(broadcast::Receiver<Arc<AgentEvent>>),
+}
+
+/// One sink's view of an [`EventBus`].
+pub struct EventSubscriber {
+    rx: Receiver,
+    name: &'static str,
+    filter: EventFilter,
+    skipped: u64,
+}
+
+impl std::fmt::Debug for EventSubscriber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventSubscriber")
+            .field("name", &self.name)
+            .field("filter", &self.filter)
+            .field("lossy", &matches!(self.rx, Receiver::Lossy(_)))
+            .field("skipped", &self.skipped)
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventSubscriber {
+    /// Waits for the next matching event. Returns `None` once the stream
+    /// closes.
+    pub async fn recv(&mut self) -> Option<Arc<AgentEvent>> {
+        let rx = match &mut self.rx {
+            Receiver::Lossless(rx) => return rx.recv().await,
+            Receiver::Lossy(rx) => rx,
+        };
+        loop {
+            match rx.recv().await {
+                Ok(event) if self.filter.matches(&event) => return Some(event),
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(count)) => {
+                    lagged(self.name, &mut self.skipped, count);
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    }
+
+    /// Returns the next matching event without waiting.
+    ///
+    /// # Errors
+    /// Returns `TryRecvError::Empty` if nothing is waiting and
+    /// `TryRecvError::Closed` once the stream closes.
+    pub fn try_recv(&mut self) -> Result<Arc<AgentEvent>, TryRecvError> {
+        let rx = match &mut self.rx {
+            Receiver::Lossless(rx) => {
+                return rx.try_recv().map_err(|err| match err {
+                    mpsc::error::TryRecvError::Empty => TryRecvError::Empty,
+                    mpsc::error::TryRecvError::Disconnected => TryRecvError::Closed,
+                });
+            }
+            Receiver::Lossy(rx) => rx,
+        };
+        loop {
+            match rx.try_recv() {
+                Ok(event) if self.filter.matches(&event) => return Ok(event),
+                Ok(_) => {}
+                Err(broadcast::error::TryRecvError::Lagged(count)) => {
+                    lagged(self.name, &mut self.skipped, count);
+                }
+                Err(broadcast::error::TryRecvError::Empty) => return Err(TryRecvError::Empty),
+                Err(broadcast::error::TryRecvError::Closed) => return Err(TryRecvError::Closed),
+            }
+        }
+    }
+
+    /// Events this subscriber skipped because it fell behind. Always zero for
+    /// lossless subscribers.
+    pub fn skipped(&self) -> u64 {
+        self.skipped
+    }
+}
+
+fn lagged(name: &'static str, skipped: &mut u64, count: u64) {
+    *skipped += count;
+    tracing::warn!(
+        subscriber = name,
+        skipped = count,
+        "Agent event subscriber fell behind; skipped events"
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn delta(text: &str) -> AgentEvent {
+        AgentEvent::AssistantDelta {
+            text: text.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn every_subscriber_sees_every_event_until_close() {
+        let mut bus = EventBus::new();
+        let mut first = bus.subscribe("first", EventFilter::All);
+        let mut second = bus.subscribe("second", EventFilter::All);
+        let tx = bus.into_sender();
+
+        tx.send(AgentEvent::TurnStarted);
+        tx.send(delta("hi"));
+        drop(tx);
+
+        for rx in [&mut first, &mut second] {
+            assert!(matches!(
+                rx.recv().await.as_deref(),
+                Some(AgentEvent::TurnStarted)
+            ));
+            assert!(matches!(
+                rx.recv().await.as_deref(),
+                Some(AgentEvent::AssistantDelta { text }) if text == "hi"
+            ));
+            assert!(rx.recv().await.is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn filters_drop_unwanted_events() {
+        let mut bus = EventBus::new();
+        let mut no_deltas = bus.subscribe("persist", EventFilter::NoDeltas);
+        let mut started = bus.subscribe(
+            "started",
+            EventFilter::Matching(|event| matches!(event, AgentEvent::TurnStarted)),
+        );
+        let tx = bus.into_sender();
+
+        tx.send(delta("a"));
+        tx.send(AgentEvent::TurnStarted);
+        tx.send(AgentEvent::AssistantCompleted {
+            text: "a".to_string(),
+        });
+        drop(tx);
+
+        assert!(matches!(
+            no_deltas.recv().await.as_deref(),
+            Some(AgentEvent::TurnStarted)
+        ));
+        assert!(matches!(
+            no_deltas.recv().await.as_deref(),
+            Some(AgentEvent::AssistantCompleted { .. })
+        ));
+        assert!(no_deltas.recv().await.is_none());
+
+        assert!(matches!(
+            started.try_recv().as_deref(),
+            Ok(AgentEvent::TurnStarted)
+        ));
+        assert_eq!(started.try_recv().unwrap_err(), TryRecvError::Closed);
+    }
+
+    #[tokio::test]
+    async fn slow_subscriber_keeps_every_event_and_filters_before_queueing() {
+        let mut bus = EventBus::new();
+        let mut persist = bus.subscribe("persist", EventFilter::NoDeltas);
+        let mut ui = bus.subscribe("ui", EventFilter::All);
+        let tx = bus.into_sender();
+
+        for i in 0..10_000 {
+            tx.send(AgentEvent::ToolOutputDelta {
+                id: "t".to_string(),
+                chunk: i.to_string(),
+            });
+        }
+        tx.send(AgentEvent::TurnStarted);
+        drop(tx);
+
+        assert!(matches!(
+            persist.try_recv().as_deref(),
+            Ok(AgentEvent::TurnStarted)
+        ));
+        assert_eq!(persist.try_recv().unwrap_err(), TryRecvError::Closed);
+
+        let mut received = 0;
+        while ui.recv().await.is_some() {
+            received += 1;
+        }
+        assert_eq!(received, 10_001);
+    }
+
+    #[tokio::test]
+    async fn lossy_subscriber_skips_oldest_events_while_lossless_keeps_all() {
+        let mut bus = EventBus::with_capacity(4);
+        let mut ui = bus.subscribe_lossy("ui", EventFilter::All);
+        let mut persist = bus.subscribe("persist", EventFilter::All);
+        let tx = bus.into_sender();
+
+        for i in 0..10 {
+            tx.send(delta(&i.to_string()));
+        }
+        drop(tx);
+
+        let mut seen = Vec::new();
+        while let Some(event) = ui.recv().await {
+            if let AgentEvent::AssistantDelta { text } = event.as_ref() {
+                seen.push(text.clone());
+            }
+        }
+        assert_eq!(seen, ["6", "7", "8", "9"]);
+        assert_eq!(ui.skipped(), 6);
+
+        let mut received = 0;
+        while persist.recv().await.is_some() {
+            received += 1;
+        }
+        assert_eq!(received, 10);
+        assert_eq!(persist.skipped(), 0);
+    }
+
+    #[tokio::test]
+    async fn lossy_subscriber_applies_its_filter_and_sees_close() {
+        let bus = EventBus::new();
+        let mut ui = bus.subscribe_lossy("ui", EventFilter::NoDeltas);
+        let tx = bus.into_sender();
+
+        tx.send(delta("a"));
+        tx.send(AgentEvent::TurnStarted);
+
+        assert!(matches!(
+            ui.try_recv().as_deref(),
+            Ok(AgentEvent::TurnStarted)
+        ));
+        assert_eq!(ui.try_recv().unwrap_err(), TryRecvError::Empty);
+        drop(tx);
+        assert_eq!(ui.try_recv().unwrap_err(), TryRecvError::Closed);
+    }
+
+    #[tokio::test]
+    async fn dropped_subscribers_are_removed_without_affecting_others() {
+        let mut bus = EventBus::new();
+        let mut kept = bus.subscribe("kept", EventFilter::All);
+        let dropped = bus.subscribe("dropped", EventFilter::All);
+        let tx = bus.into_sender();
+        drop(dropped);
+
+        tx.send(delta("test"));
+
+        assert_eq!(tx.subscriber_count(), 1);
+        assert!(matches!(
+            kept.recv().await.as_deref(),
+            Some(AgentEvent::AssistantDelta { text }) if text == "test"
+        ));
+    }
+}
@@ -4,5 +4,6 @@
 //! existing `crate::core::events::*` imports keep working.
 
 pub use zdx_types::{
-    AgentEvent, ErrorKind, ImageContent, NoticeKind, ToolError, ToolOutput, TurnStatus,
+    AgentEvent, ErrorKind, EscalationTrigger, ImageContent, LoopKind, NoticeKind, ToolError,
+    ToolOutput, TurnStatus,
 };
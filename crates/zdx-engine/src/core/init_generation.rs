@@ -0,0 +1,484 @@
+//! Starter `AGENTS.md` generation for `zdx init`.
+//!
+//! Surveys the repository locally (language breakdown by file count, build and
+//! test commands read from manifests, top-level directory map), then hands the
+//! survey to a read-only subagent that verifies it against the code and writes
+//! the file. Mirrors `handoff_generation` in shape; the subagent gets tools
+//! because the survey alone is too shallow for conventions and gotchas.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::Result;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::ThinkingLevel;
+use crate::core::subagent::{ExecSubagentOptions, run_exec_subagent_with_cancel};
+use crate::prompts::INIT_PROMPT_TEMPLATE;
+
+/// Timeout for the generation subagent (5 minutes; it explores the repo).
+const INIT_TIMEOUT_SECS: u64 = 300;
+
+/// Files counted before the survey stops walking.
+const SURVEY_MAX_FILES: usize = 50_000;
+
+/// Languages listed in the survey.
+const SURVEY_MAX_LANGUAGES: usize = 8;
+
+/// Read-only tools the generation subagent may use.
+const INIT_TOOLS: &[&str] = &["read", "glob", "grep", "outline"];
+
+/// Extension → language name for the breakdown.
+const LANGUAGES: &[(&str, &str)] = &[
+    ("rs", "Rust"),
+    ("go", "Go"),
+    ("py", "Python"),
+    ("ts", "TypeScript"),
+    ("tsx", "TypeScript"),
+    ("js", "JavaScript"),
+    ("jsx", "JavaScript"),
+    ("mjs", "JavaScript"),
+    ("rb", "Ruby"),
+    ("java", "Java"),
+    ("kt", "Kotlin"),
+    ("swift", "Swift"),
+    ("c", "C"),
+    ("h", "C"),
+    ("cc", "C++"),
+    ("cpp", "C++"),
+    ("hpp", "C++"),
+    ("cs", "C#"),
+    ("php", "PHP"),
+    ("ex", "Elixir"),
+    ("exs", "Elixir"),
+    ("zig", "Zig"),
+    ("lua", "Lua"),
+    ("sh", "Shell"),
+    ("sql", "SQL"),
+    ("html", "HTML"),
+    ("css", "CSS"),
+    ("scss", "CSS"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("md", "Markdown"),
+];
+
+/// What a local walk of the repository found.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RepoSurvey {
+    /// Files walked (gitignored files excluded).
+    pub total_files: usize,
+    /// `(language, file count)`, most files first.
+    pub languages: Vec<(String, usize)>,
+    /// Commands read from manifests in the root.
+    pub commands: Vec<ProjectCommand>,
+    /// `(top-level directory, file count)`, alphabetical.
+    pub directories: Vec<(String, usize)>,
+}
+
+/// A build/test/lint command and the manifest it came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectCommand {
+    pub kind: &'static str,
+    pub command: String,
+    pub source: &'static str,
+}
+
+impl ProjectCommand {
+    fn new(kind: &'static str, command: impl Into<String>, source: &'static str) -> Self {
+        Self {
+            kind,
+            command: command.into(),
+            source,
+        }
+    }
+}
+
+/// Walks `root` (respecting `.gitignore`) and reads its manifests.
+pub fn survey_repo(root: &Path) -> RepoSurvey {
+    let walker = ignore::WalkBuilder::new(root)
+        .hidden(true)
+        .git_ignore(true)
+        .git_global(false)
+        .git_exclude(false)
+        .require_git(false)
+        .build();
+
+    let mut survey = RepoSurvey::default();
+    let mut languages: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut directories: BTreeMap<String, usize> = BTreeMap::new();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|ft| ft.is_file()) {
+            continue;
+        }
+        survey.total_files += 1;
+        if survey.total_files > SURVEY_MAX_FILES {
+            survey.total_files = SURVEY_MAX_FILES;
+            break;
+        }
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let mut components = relative.components();
+        if let (Some(first), Some(_)) = (components.next(), components.next()) {
+            let dir = first.as_os_str().to_string_lossy().into_owned();
+            *directories.entry(dir).or_default() += 1;
+        }
+        let language = relative
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .and_then(|ext| {
+                LANGUAGES
+                    .iter()
+                    .find(|(known, _)| known.eq_ignore_ascii_case(ext))
+            })
+            .map(|(_, language)| *language);
+        if let Some(language) = language {
+            *languages.entry(language).or_default() += 1;
+        }
+    }
+
+    let mut languages: Vec<(String, usize)> = languages
+        .into_iter()
+        .map(|(language, count)| (language.to_string(), count))
+        .collect();
+    languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    languages.truncate(SURVEY_MAX_LANGUAGES);
+    survey.languages = languages;
+    survey.directories = directories.into_iter().collect();
+    survey.commands = manifest_commands(root);
+    survey
+}
+
+/// Reads build/test commands from the manifests in `root`.
+fn manifest_commands(root: &Path) -> Vec<ProjectCommand> {
+    let read = |name: &str| std::fs::read_to_string(root.join(name)).ok();
+    let mut commands = Vec::new();
+
+    if let Some(cargo) = read("Cargo.toml") {
+        let scope = if cargo.contains("[workspace]") {
+            " --workspace"
+        } else {
+            ""
+        };
+        commands.push(ProjectCommand::new(
+            "build",
+            format!("cargo build{scope}"),
+            "Cargo.toml",
+        ));
+        commands.push(ProjectCommand::new(
+            "test",
+            format!("cargo test{scope}"),
+            "Cargo.toml",
+        ));
+        commands.push(ProjectCommand::new(
+            "lint",
+            format!("cargo clippy{scope} --all-targets"),
+            "Cargo.toml",
+        ));
+    }
+    if let Some(package) = read("package.json") {
+        let runner = if root.join("pnpm-lock.yaml").exists() {
+            "pnpm"
+        } else if root.join("yarn.lock").exists() {
+            "yarn"
+        } else if root.join("bun.lock").exists() || root.join("bun.lockb").exists() {
+            "bun"
+        } else {
+            "npm"
+        };
+        commands.extend(package_json_commands(&package, runner));
+    }
+    if root.join("go.mod").exists() {
+        commands.push(ProjectCommand::new("build", "go build ./...", "go.mod"));
+        commands.push(ProjectCommand::new("test", "go test ./...", "go.mod"));
+    }
+    if let Some(pyproject) = read("pyproject.toml") {
+        let runner = if root.join("uv.lock").exists() {
+            "uv run "
+        } else {
+            ""
+        };
+        if pyproject.contains("[tool.pytest") || root.join("tests").is_dir() {
+            commands.push(ProjectCommand::new(
+                "test",
+                format!("{runner}pytest"),
+                "pyproject.toml",
+            ));
+        }
+        if pyproject.contains("[tool.ruff") {
+            commands.push(ProjectCommand::new(
+                "lint",
+                format!("{runner}ruff check ."),
+                "pyproject.toml",
+            ));
+        }
+    }
+    if let Some(makefile) = read("Makefile") {
+        commands.extend(
+            recipe_names(&makefile)
+                .into_iter()
+                .filter_map(|target| {
+                    command_kind(&target).map(|kind| (kind, format!("make {target}")))
+                })
+                .map(|(kind, command)| ProjectCommand::new(kind, command, "Makefile")),
+        );
+    }
+    if let Some(justfile) = read("justfile").or_else(|| read("Justfile")) {
+        commands.extend(
+            recipe_names(&justfile)
+                .into_iter()
+                .filter_map(|recipe| {
+                    command_kind(&recipe).map(|kind| (kind, format!("just {recipe}")))
+                })
+                .map(|(kind, command)| ProjectCommand::new(kind, command, "justfile")),
+        );
+    }
+    commands
+}
+
+/// Commands for the `scripts` in a `package.json` that look like build, test,
+/// or lint steps.
+fn package_json_commands(text: &str, runner: &str) -> Vec<ProjectCommand> {
+    let Ok(package) = serde_json::from_str::<serde_json::Value>(text) else {
+        return Vec::new();
+    };
+    let Some(scripts) = package.get("scripts").and_then(|s| s.as_object()) else {
+        return Vec::new();
+    };
+    scripts
+        .keys()
+        .filter_map(|name| {
+            let kind = command_kind(name)?;
+            let command = if name == "test" && runner != "yarn" {
+                format!("{runner} test")
+            } else if runner == "npm" {
+                format!("npm run {name}")
+            } else {
+                format!("{runner} {name}")
+            };
+            Some(ProjectCommand::new(kind, command, "package.json"))
+        })
+        .collect()
+}
+
+/// Maps a script/target name to the kind of step it runs.
+fn command_kind(name: &str) -> Option<&'static str> {
+    let base = name.split([':', '-', '_']).next().unwrap_or(name);
+    match base {
+        "build" | "compile" => Some("build"),
+        "test" | "tests" | "check" | "e2e" => Some("test"),
+        "lint" | "fmt" | "format" | "typecheck" => Some("lint"),
+        "dev" | "run" | "start" | "serve" => Some("run"),
+        _ => None,
+    }
+}
+
+/// Top-level recipe names in a Makefile or justfile (`name:` / `name arg:` at
+/// column 0; variable assignments and special targets skipped).
+fn recipe_names(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    for line in text.lines() {
+        if line.starts_with([' ', '\t', '#', '.']) {
+            continue;
+        }
+        let Some((head, rest)) = line.split_once(':') else {
+            continue;
+        };
+        if rest.starts_with('=') || head.contains('=') || head.contains('$') {
+            continue;
+        }
+        let Some(name) = head.split_whitespace().next() else {
+            continue;
+        };
+        if !names.iter().any(|known| known == name) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+impl RepoSurvey {
+    /// Markdown rendering embedded in the generation prompt.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "Files (gitignored excluded): {}", self.total_files);
+
+        let counted: usize = self.languages.iter().map(|(_, count)| count).sum();
+        if counted > 0 {
+            let breakdown = self
+                .languages
+                .iter()
+                .map(|(language, count)| format!("{language} {}%", count * 100 / counted))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let _ = writeln!(out, "Languages by file count: {breakdown}");
+        }
+
+        if self.commands.is_empty() {
+            let _ = writeln!(out, "\nCommands from manifests: none found");
+        } else {
+            let _ = writeln!(out, "\nCommands from manifests:");
+            for command in &self.commands {
+                let _ = writeln!(
+                    out,
+                    "- {}: `{}` ({})",
+                    command.kind, command.command, command.source
+                );
+            }
+        }
+
+        if !self.directories.is_empty() {
+            let _ = writeln!(out, "\nTop-level directories:");
+            for (dir, count) in &self.directories {
+                let _ = writeln!(out, "- {dir}/ ({count} files)");
+            }
+        }
+        out
+    }
+}
+
+/// Builds the generation prompt from the survey and any existing `AGENTS.md`.
+fn build_init_prompt(survey: &RepoSurvey, existing: Option<&str>) -> String {
+    INIT_PROMPT_TEMPLATE
+        .replace("{{SURVEY}}", survey.to_markdown().trim_end())
+        .replace("{{EXISTING}}", existing.map_or("(none)", str::trim))
+}
+
+/// Drops a code fence the model wrapped the whole file in, despite being told
+/// not to.
+fn strip_outer_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let Some((_, body)) = rest.split_once('\n') else {
+        return trimmed;
+    };
+    body.strip_suffix("```").map_or(trimmed, str::trim)
+}
+
+/// Generates a starter `AGENTS.md` for `root`. When `root/AGENTS.md` already
+/// exists, the subagent revises it instead of starting over.
+///
+/// # Errors
+/// Returns an error when the subagent fails / times out / is cancelled.
+pub async fn generate_agents_md(
+    root: &Path,
+    model: Option<String>,
+    cancel: Option<CancellationToken>,
+) -> Result<String> {
+    let survey = survey_repo(root);
+    let existing = std::fs::read_to_string(root.join("AGENTS.md")).ok();
+    let prompt = build_init_prompt(&survey, existing.as_deref());
+
+    let (model, thinking_level) = match model {
+        Some(spec) => {
+            let (model, thinking) = crate::models::split_model_thinking(&spec);
+            (
+                Some(model.to_string()),
+                thinking.unwrap_or(ThinkingLevel::Medium),
+            )
+        }
+        None => (None, ThinkingLevel::Medium),
+    };
+
+    let options = ExecSubagentOptions {
+        model,
+        thinking_level: Some(thinking_level),
+        tools_override: Some(INIT_TOOLS.iter().map(ToString::to_string).collect()),
+        event_filter: Some(vec!["turn_finished".to_string()]),
+        timeout: Some(Duration::from_secs(INIT_TIMEOUT_SECS)),
+        activity_kind: Some("helper:init".to_string()),
+        thread_origin_kind: Some("helper:init".to_string()),
+        ..Default::default()
+    };
+
+    let generated = run_exec_subagent_with_cancel(root, &prompt, &options, cancel, None).await?;
+    let mut content = strip_outer_fence(&generated).to_string();
+    content.push('\n');
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn survey_counts_languages_directories_and_commands() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        std::fs::create_dir_all(root.join("web")).unwrap();
+        std::fs::create_dir_all(root.join("target")).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(root.join("Cargo.toml"), "[workspace]\nmembers = []\n").unwrap();
+        std::fs::write(root.join("src/main.rs"), "fn main() {}\n").unwrap();
+        std::fs::write(root.join("src/lib.rs"), "").unwrap();
+        std::fs::write(root.join("web/app.ts"), "").unwrap();
+        std::fs::write(root.join("target/junk.rs"), "").unwrap();
+        std::fs::write(
+            root.join("package.json"),
+            r#"{"scripts": {"build": "tsc", "test": "vitest", "lint:fix": "eslint", "release": "x"}}"#,
+        )
+        .unwrap();
+        std::fs::write(root.join("pnpm-lock.yaml"), "").unwrap();
+        std::fs::write(
+            root.join("Makefile"),
+            "CC := gcc\n.PHONY: test\nbuild: deps\n\tcc x\ntest:\n\t./run\n",
+        )
+        .unwrap();
+
+        let survey = survey_repo(root);
+        assert_eq!(
+            survey.languages,
+            vec![("Rust".to_string(), 2), ("TypeScript".to_string(), 1)]
+        );
+        assert_eq!(
+            survey.directories,
+            vec![("src".to_string(), 2), ("web".to_string(), 1)]
+        );
+        let commands: Vec<&str> = survey.commands.iter().map(|c| c.command.as_str()).collect();
+        assert_eq!(
+            commands,
+            vec![
+                "cargo build --workspace",
+                "cargo test --workspace",
+                "cargo clippy --workspace --all-targets",
+                "pnpm build",
+                "pnpm lint:fix",
+                "pnpm test",
+                "make build",
+                "make test",
+            ]
+        );
+        let markdown = survey.to_markdown();
+        assert!(markdown.contains("Rust 66%, TypeScript 33%"));
+        assert!(markdown.contains("- test: `make test` (Makefile)"));
+    }
+
+    #[test]
+    fn prompt_embeds_survey_and_existing_file() {
+        let survey = RepoSurvey {
+            total_files: 3,
+            ..RepoSurvey::default()
+        };
+        let prompt = build_init_prompt(&survey, Some("# Old\n"));
+        assert!(prompt.contains("Files (gitignored excluded): 3"));
+        assert!(prompt.contains("<existing_agents_md>\n# Old\n</existing_agents_md>"));
+        assert!(build_init_prompt(&survey, None).contains("(none)"));
+    }
+
+    #[test]
+    fn strips_fence_wrapping_the_whole_file() {
+        assert_eq!(strip_outer_fence("```markdown\n# Repo\n```\n"), "# Repo");
+        assert_eq!(
+            strip_outer_fence("# Repo\n```sh\nx\n```"),
+            "# Repo\n```sh\nx\n```"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::sync::OnceLock;
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
 
 static INTERRUPTED: AtomicBool = AtomicBool::new(false);
 static INTERRUPT_NOTIFY: OnceLock<Notify> = OnceLock::new();
@@ -65,6 +66,23 @@ pub async fn wait_for_interrupt() {
     }
 }
 
+/// Returns a token that is canceled when the process-wide interrupt fires.
+///
+/// The engine only honors per-turn tokens; single-run surfaces (`zdx exec`)
+/// use this to turn Ctrl+C into a cancel. Must be called inside a Tokio
+/// runtime.
+pub fn cancellation_token() -> CancellationToken {
+    let token = CancellationToken::new();
+    let watched = token.clone();
+    tokio::spawn(async move {
+        tokio::select! {
+            () = wait_for_interrupt() => watched.cancel(),
+            () = watched.cancelled() => {}
+        }
+    });
+    token
+}
+
 /// Resets the interrupt flag.
 pub fn reset() {
     INTERRUPTED.store(false, Ordering::SeqCst);
@@ -0,0 +1,169 @@
+//! Tool-loop safeguards for a single agent run.
+//!
+//! Tracks tool turns within one run and trips when the run exceeds the
+//! configured tool-iteration budget or repeats the exact same tool calls
+//! (same names, same inputs) too many times in a row.
+
+use zdx_types::LoopKind;
+
+use crate::config::AgentLoopConfig;
+use crate::providers::{ChatContentBlock, ChatMessage, MessageContent};
+
+/// Why the guard stopped the run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopTrip {
+    pub kind: LoopKind,
+    pub message: String,
+    pub details: Option<String>,
+}
+
+#[derive(Debug)]
+pub struct LoopGuard {
+    max_tool_iterations: u32,
+    max_repeated_tool_calls: u32,
+    iterations: u32,
+    last_signature: Option<String>,
+    repeats: u32,
+}
+
+impl LoopGuard {
+    pub fn new(config: &AgentLoopConfig) -> Self {
+        Self {
+            max_tool_iterations: config.max_tool_iterations,
+            max_repeated_tool_calls: config.max_repeated_tool_calls,
+            iterations: 0,
+            last_signature: None,
+            repeats: 0,
+        }
+    }
+
+    /// Records one executed tool turn (the assistant message that requested
+    /// the tools) and returns a trip when a limit is reached.
+    pub fn record_tool_turn(&mut self, assistant: &ChatMessage) -> Option<LoopTrip> {
+        self.iterations += 1;
+
+        let signature = tool_call_signature(assistant);
+        if signature.is_some() && signature == self.last_signature {
+            self.repeats += 1;
+        } else {
+            self.repeats = 1;
+            self.last_signature = signature;
+        }
+
+        if self.max_repeated_tool_calls > 0 && self.repeats >= self.max_repeated_tool_calls {
+            let names = self.last_signature.as_deref().unwrap_or_default();
+            return Some(LoopTrip {
+                kind: LoopKind::RepeatedToolCall,
+                message: format!(
+                    "Stopped: the same tool call repeated {} times in a row.",
+                    self.repeats
+                ),
+                details: Some(truncate(names, 300)),
+            });
+        }
+
+        if self.max_tool_iterations > 0 && self.iterations >= self.max_tool_iterations {
+            return Some(LoopTrip {
+                kind: LoopKind::MaxToolIterations,
+                message: format!(
+                    "Stopped: reached the limit of {} tool iterations for one turn.",
+                    self.max_tool_iterations
+                ),
+                details: Some("Raise [agent_loop] max_tool_iterations to allow more.".to_string()),
+            });
+        }
+
+        None
+    }
+}
+
+/// Serializes the tool calls of an assistant message as `name(input)` pairs.
+/// Returns `None` when the message has no tool calls.
+fn tool_call_signature(message: &ChatMessage) -> Option<String> {
+    let MessageContent::Blocks(blocks) = &message.content else {
+        return None;
+    };
+    let calls: Vec<String> = blocks
+        .iter()
+        .filter_map(|block| match block {
+            ChatContentBlock::ToolUse { name, input, .. } => Some(format!("{name}({input})")),
+            _ => None,
+        })
+        .collect();
+    (!calls.is_empty()).then(|| calls.join(", "))
+}
+
+fn truncate(text: &str, max_chars: usize) -> String {
+    if text.chars().count() <= max_chars {
+        return text.to_string();
+    }
+    let mut out: String = text.chars().take(max_chars).collect();
+    out.push('…');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn tool_turn(name: &str, input: serde_json::Value) -> ChatMessage {
+        ChatMessage::assistant_blocks(vec![ChatContentBlock::tool_use("id", name, input)])
+    }
+
+    fn guard(max_tool_iterations: u32, max_repeated_tool_calls: u32) -> LoopGuard {
+        LoopGuard::new(&AgentLoopConfig {
+            max_tool_iterations,
+            max_repeated_tool_calls,
+        })
+    }
+
+    #[test]
+    fn trips_on_identical_calls_in_a_row() {
+        let mut guard = guard(0, 3);
+        let call = tool_turn("read", json!({"path": "a.rs"}));
+
+        assert_eq!(guard.record_tool_turn(&call), None);
+        assert_eq!(guard.record_tool_turn(&call), None);
+        let trip = guard.record_tool_turn(&call).expect("third repeat trips");
+        assert_eq!(trip.kind, LoopKind::RepeatedToolCall);
+    }
+
+    #[test]
+    fn different_input_resets_repeat_count() {
+        let mut guard = guard(0, 2);
+
+        assert_eq!(
+            guard.record_tool_turn(&tool_turn("read", json!({"path": "a.rs"}))),
+            None
+        );
+        assert_eq!(
+            guard.record_tool_turn(&tool_turn("read", json!({"path": "b.rs"}))),
+            None
+        );
+    }
+
+    #[test]
+    fn trips_on_max_tool_iterations() {
+        let mut guard = guard(2, 0);
+
+        assert_eq!(
+            guard.record_tool_turn(&tool_turn("read", json!({"path": "a.rs"}))),
+            None
+        );
+        let trip = guard
+            .record_tool_turn(&tool_turn("read", json!({"path": "b.rs"})))
+            .expect("second iteration trips");
+        assert_eq!(trip.kind, LoopKind::MaxToolIterations);
+    }
+
+    #[test]
+    fn zero_limits_disable_the_guard() {
+        let mut guard = guard(0, 0);
+        let call = tool_turn("read", json!({"path": "a.rs"}));
+        for _ in 0..50 {
+            assert_eq!(guard.record_tool_turn(&call), None);
+        }
+    }
+}
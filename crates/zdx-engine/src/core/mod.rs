@@ -3,30 +3,69 @@
 //! This module contains:
 //! - `events`: Agent event types for streaming
 //! - `context`: Project context loading (AGENTS.md files)
-//! - `interrupt`: Signal handling for graceful interruption
-//! - `agent`: Agent loop and event channels
+//! - `context_cache`: On-disk cache for scoped context discovery
+//! - `interrupt`: Process-wide Ctrl+C flag, bridged into per-turn cancellation tokens
+//! - `agent`: Agent loop
+//! - `event_bus`: Broadcast bus fanning agent events out to subscribers
+//! - `ask_user`: Agent→user question channel for the `Ask_User` tool
+//! - `audit_log`: Tool-call audit records for `zdx audit export`
+//! - `bench`: Single-prompt latency/throughput/cost benchmark per model
+//! - `duel`: Two-model discussions (`/duel`) over a shared thread
+//! - `loop_guard`: Tool-loop safeguards (iteration limit, repeated calls)
 //! - `handoff_generation`: LLM-based handoff context generation
+//! - `partial_json`: Provisional parsing of streaming tool input JSON
+//! - `pinned_context`: Pinned messages and files sent with every request
 //! - `prompt_builder_generation`: LLM-based prompt-builder generation
 //! - `qmd`: qmd binary discovery and setup
+//! - `regeneration`: `/redo` regenerations that replace the latest attempt
+//! - `response_cache`: Opt-in exec final-response cache
+//! - `response_style`: Per-thread response style presets (`/style`)
+//! - `steering`: Mid-turn user guidance queued into a running turn
+//! - `stream_resume`: Resuming responses after mid-stream disconnects
+//! - `structured_output`: JSON schema-constrained exec answers
 //! - `subagent`: Child `zdx exec` subagent runner
+//! - `summary_generation`: LLM-based idle-time thread summaries
+//! - `thinking_escalation`: Thinking-level auto-escalation policy
 //! - `thread_export`: Thread transcript exports
 //! - `thread_persistence`: Thread persistence
 //! - `title_generation`: LLM-based title generation
 //! - `tldr_generation`: LLM-based thread TLDR/recap generation
+//! - `truncation`: Client-side conversation truncation and pinned turns
 //! - `usage_stats`: Usage/cost aggregation over saved threads
 //! - `worktree`: Git worktree management helpers
 
 pub mod agent;
+pub mod ask_user;
+pub mod audit_log;
+pub mod bench;
 pub mod context;
+pub(crate) mod context_cache;
+pub mod duel;
+pub mod event_bus;
 pub mod events;
 pub mod handoff_generation;
+pub mod init_generation;
 pub mod interrupt;
+pub mod loop_guard;
+pub mod partial_json;
+pub mod personal_stats;
+pub mod pinned_context;
 pub mod prompt_builder_generation;
+pub mod prompt_improve_generation;
 pub mod qmd;
+pub mod regeneration;
+pub mod response_cache;
+pub mod response_style;
+pub mod steering;
+pub mod stream_resume;
+pub mod structured_output;
 pub mod subagent;
+pub mod summary_generation;
+pub mod thinking_escalation;
 pub mod thread_export;
 pub mod thread_persistence;
 pub mod title_generation;
 pub mod tldr_generation;
+pub mod truncation;
 pub mod usage_stats;
 pub mod worktree;
@@ -0,0 +1,145 @@
+//! Best-effort parsing of tool input JSON that is still streaming.
+//!
+//! Providers stream tool inputs as raw JSON fragments (Anthropic
+//! `input_json_delta`). To show a provisional input while it streams, the
+//! prefix is completed by closing the open string and containers; values
+//! that can't be completed (a half-written `tru`, a dangling key) are cut
+//! back to the previous member.
+
+use serde_json::Value;
+
+/// Parses a streaming JSON object prefix into a provisional object.
+///
+/// Returns `None` when the prefix doesn't start an object or nothing
+/// parseable remains.
+pub fn parse_partial_object(text: &str) -> Option<Value> {
+    let text = text.trim_start();
+    if !text.starts_with('{') {
+        return None;
+    }
+
+    let mut end = text.len();
+    loop {
+        let prefix = &text[..end];
+        let scan = Scan::run(prefix);
+        for candidate in scan.completions(prefix) {
+            if let Ok(value @ Value::Object(_)) = serde_json::from_str::<Value>(&candidate) {
+                return Some(value);
+            }
+        }
+        match scan.last_boundary {
+            Some(boundary) if boundary < end => end = boundary,
+            _ => return None,
+        }
+    }
+}
+
+/// Lexical state at the end of a JSON prefix.
+struct Scan {
+    /// Closers for the containers still open, innermost last.
+    closers: Vec<char>,
+    in_string: bool,
+    /// The prefix ends right after a backslash inside a string.
+    escape_pending: bool,
+    /// Last cut point outside a string: before a `,` or just after `{`/`[`.
+    last_boundary: Option<usize>,
+}
+
+impl Scan {
+    fn run(prefix: &str) -> Self {
+        let mut scan = Self {
+            closers: Vec::new(),
+            in_string: false,
+            escape_pending: false,
+            last_boundary: None,
+        };
+        for (pos, ch) in prefix.char_indices() {
+            if scan.in_string {
+                if scan.escape_pending {
+                    scan.escape_pending = false;
+                } else if ch == '\\' {
+                    scan.escape_pending = true;
+                } else if ch == '"' {
+                    scan.in_string = false;
+                }
+                continue;
+            }
+            match ch {
+                '"' => scan.in_string = true,
+                '{' => {
+                    scan.closers.push('}');
+                    scan.last_boundary = Some(pos + 1);
+                }
+                '[' => {
+                    scan.closers.push(']');
+                    scan.last_boundary = Some(pos + 1);
+                }
+                '}' | ']' => {
+                    scan.closers.pop();
+                }
+                ',' => scan.last_boundary = Some(pos),
+                _ => {}
+            }
+        }
+        scan
+    }
+
+    /// Candidate completions of `prefix`, most faithful first.
+    fn completions(&self, prefix: &str) -> [String; 2] {
+        let mut base = prefix.to_string();
+        if self.in_string {
+            if self.escape_pending {
+                base.pop();
+            }
+            base.push('"');
+        }
+        let trimmed_len = base.trim_end().len();
+        base.truncate(trimmed_len);
+        if base.ends_with(',') {
+            base.pop();
+        }
+        if base.ends_with(':') {
+            base.push_str("null");
+        }
+        let closers: String = self.closers.iter().rev().collect();
+        // The second form completes a dangling object key.
+        [format!("{base}{closers}"), format!("{base}:null{closers}")]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_completes_open_string_value() {
+        assert_eq!(
+            parse_partial_object(r#"{"command":"cargo te"#),
+            Some(json!({"command": "cargo te"}))
+        );
+        assert_eq!(
+            parse_partial_object(r#"{"command":"echo \"hi\"#),
+            Some(json!({"command": "echo \"hi"}))
+        );
+    }
+
+    #[test]
+    fn test_handles_dangling_keys_and_literals() {
+        assert_eq!(
+            parse_partial_object(r#"{"command":"ls","timeo"#),
+            Some(json!({"command": "ls", "timeo": null}))
+        );
+        assert_eq!(
+            parse_partial_object(r#"{"command":"ls","background":tr"#),
+            Some(json!({"command": "ls"}))
+        );
+        assert_eq!(
+            parse_partial_object(r#"{"paths":["a","b"#),
+            Some(json!({"paths": ["a", "b"]}))
+        );
+        assert_eq!(parse_partial_object("{"), Some(json!({})));
+        assert_eq!(parse_partial_object(""), None);
+    }
+}
@@ -0,0 +1,276 @@
+//! Local personal-usage report (`zdx stats --personal`).
+//!
+//! Combines two local sources: saved thread logs (turns, tool calls, models,
+//! activity hours) and a small command-usage file,
+//! `$ZDX_HOME/stats/events.jsonl`, holding one `{ts, source, name}` line per
+//! CLI subcommand or TUI slash command run. Only command names are recorded,
+//! never arguments, and nothing here is sent anywhere. Incognito sessions
+//! record nothing.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local, Timelike, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::paths;
+use crate::core::thread_persistence::{self, ThreadEvent, list_threads};
+
+/// Where a recorded command was run from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandSource {
+    /// A `zdx` subcommand (e.g. `exec`, `threads list`).
+    Cli,
+    /// A TUI slash command (e.g. `model`).
+    Slash,
+}
+
+/// One line of the command-usage file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CommandEvent {
+    ts: String,
+    source: CommandSource,
+    name: String,
+}
+
+/// Aggregated personal usage. Count lists are sorted most-used first.
+#[derive(Debug, Clone, Default)]
+pub struct PersonalStats {
+    /// Saved top-level threads scanned (subagent child runs are excluded).
+    pub threads_scanned: usize,
+    /// Threads with at least one user turn.
+    pub threads_with_turns: usize,
+    /// User turns across all scanned threads.
+    pub turns: u64,
+    /// Commands by display name (`zdx exec`, `/model`).
+    pub commands: Vec<(String, u64)>,
+    /// Tool calls by tool name.
+    pub tools: Vec<(String, u64)>,
+    /// Model requests by model id.
+    pub models: Vec<(String, u64)>,
+    /// User turns per local hour of day (index 0–23).
+    pub hours: [u64; 24],
+}
+
+impl PersonalStats {
+    /// Average user turns per thread that has any.
+    pub fn average_turns(&self) -> f64 {
+        if self.threads_with_turns == 0 {
+            0.0
+        } else {
+            self.turns as f64 / self.threads_with_turns as f64
+        }
+    }
+
+    /// The `limit` busiest local hours with activity, busiest first.
+    pub fn busiest_hours(&self, limit: usize) -> Vec<(u32, u64)> {
+        let mut hours: Vec<(u32, u64)> = (0u32..)
+            .zip(self.hours)
+            .filter(|(_, count)| *count > 0)
+            .collect();
+        hours.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        hours.truncate(limit);
+        hours
+    }
+}
+
+/// Appends a command-usage line. Best-effort: failures are logged, never
+/// surfaced.
+pub fn record_command(source: CommandSource, name: &str) {
+    let path = paths::stats_events_path();
+    if let Err(e) = record_command_to(&path, source, name) {
+        tracing::warn!(%e, path = %path.display(), "Failed to record command usage");
+    }
+}
+
+fn record_command_to(path: &Path, source: CommandSource, name: &str) -> Result<()> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Ok(());
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let event = CommandEvent {
+        ts: Utc::now().to_rfc3339(),
+        source,
+        name: name.to_string(),
+    };
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(&event)?)?;
+    Ok(())
+}
+
+/// Builds the report from saved threads and the command-usage file.
+///
+/// # Errors
+/// Returns an error if the threads directory or the command-usage file
+/// cannot be read.
+pub fn compute() -> Result<PersonalStats> {
+    let mut builder = StatsBuilder::default();
+    for summary in list_threads().context("list threads")? {
+        let events = thread_persistence::load_thread_events(&summary.id).unwrap_or_default();
+        builder.add_thread(&events);
+    }
+    builder.add_commands(&read_command_events(&paths::stats_events_path())?);
+    Ok(builder.finish())
+}
+
+#[derive(Default)]
+struct StatsBuilder {
+    stats: PersonalStats,
+    commands: HashMap<String, u64>,
+    tools: HashMap<String, u64>,
+    models: HashMap<String, u64>,
+}
+
+impl StatsBuilder {
+    fn add_thread(&mut self, events: &[ThreadEvent]) {
+        self.stats.threads_scanned += 1;
+        let mut turns = 0;
+        for event in events {
+            match event {
+                ThreadEvent::Message { role, ts, .. } if role == "user" => {
+                    turns += 1;
+                    if let Some(hour) = local_hour(ts) {
+                        self.stats.hours[hour as usize] += 1;
+                    }
+                }
+                ThreadEvent::ToolUse { name, .. } => {
+                    *self.tools.entry(name.clone()).or_default() += 1;
+                }
+                ThreadEvent::Usage {
+                    model: Some(model), ..
+                } => {
+                    *self.models.entry(model.clone()).or_default() += 1;
+                }
+                _ => {}
+            }
+        }
+        if turns > 0 {
+            self.stats.threads_with_turns += 1;
+            self.stats.turns += turns;
+        }
+    }
+
+    fn add_commands(&mut self, events: &[CommandEvent]) {
+        for event in events {
+            let name = match event.source {
+                CommandSource::Cli => format!("zdx {}", event.name),
+                CommandSource::Slash => format!("/{}", event.name),
+            };
+            *self.commands.entry(name).or_default() += 1;
+        }
+    }
+
+    fn finish(mut self) -> PersonalStats {
+        self.stats.commands = ranked(self.commands);
+        self.stats.tools = ranked(self.tools);
+        self.stats.models = ranked(self.models);
+        self.stats
+    }
+}
+
+fn ranked(counts: HashMap<String, u64>) -> Vec<(String, u64)> {
+    let mut rows: Vec<(String, u64)> = counts.into_iter().collect();
+    rows.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    rows
+}
+
+fn local_hour(ts: &str) -> Option<u32> {
+    DateTime::parse_from_rfc3339(ts)
+        .ok()
+        .map(|ts| ts.with_timezone(&Local).hour())
+}
+
+fn read_command_events(path: &Path) -> Result<Vec<CommandEvent>> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e).with_context(|| format!("open {}", path.display())),
+    };
+
+    let mut events = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line = line.with_context(|| format!("read {}", path.display()))?;
+        // Skip blank lines and lines torn by a crash mid-write.
+        if let Ok(event) = serde_json::from_str(&line) {
+            events.push(event);
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn event(value: serde_json::Value) -> ThreadEvent {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn aggregates_turns_tools_models_and_hours() {
+        let ts = "2026-03-02T09:15:00Z";
+        let thread = vec![
+            event(json!({"type": "message", "role": "user", "text": "hi", "ts": ts})),
+            event(json!({"type": "tool_use", "id": "1", "name": "read", "input": {}, "ts": ts})),
+            event(json!({"type": "tool_use", "id": "2", "name": "read", "input": {}, "ts": ts})),
+            event(json!({"type": "tool_use", "id": "3", "name": "bash", "input": {}, "ts": ts})),
+            event(json!({
+                "type": "usage", "input_tokens": 1, "output_tokens": 1,
+                "cache_read_tokens": 0, "cache_write_tokens": 0,
+                "model": "claude-sonnet-4", "ts": ts
+            })),
+            event(json!({"type": "message", "role": "assistant", "text": "ok", "ts": ts})),
+            event(json!({"type": "message", "role": "user", "text": "more", "ts": ts})),
+        ];
+
+        let mut builder = StatsBuilder::default();
+        builder.add_thread(&thread);
+        builder.add_thread(&[]);
+        let stats = builder.finish();
+
+        assert_eq!(stats.threads_scanned, 2);
+        assert_eq!(stats.turns, 2);
+        assert!((stats.average_turns() - 2.0).abs() < f64::EPSILON);
+        assert_eq!(
+            stats.tools,
+            vec![("read".to_string(), 2), ("bash".to_string(), 1)]
+        );
+        assert_eq!(stats.models, vec![("claude-sonnet-4".to_string(), 1)]);
+        let hour = local_hour(ts).unwrap();
+        assert_eq!(stats.busiest_hours(3), vec![(hour, 2)]);
+    }
+
+    #[test]
+    fn recorded_commands_are_ranked_by_source_and_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("stats").join("events.jsonl");
+        record_command_to(&path, CommandSource::Slash, "model").unwrap();
+        record_command_to(&path, CommandSource::Cli, "exec").unwrap();
+        record_command_to(&path, CommandSource::Slash, "model").unwrap();
+        record_command_to(&path, CommandSource::Slash, " ").unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap()
+            .write_all(b"{\"ts\":\"torn")
+            .unwrap();
+
+        let mut builder = StatsBuilder::default();
+        builder.add_commands(&read_command_events(&path).unwrap());
+        let stats = builder.finish();
+
+        assert_eq!(
+            stats.commands,
+            vec![("/model".to_string(), 2), ("zdx exec".to_string(), 1)]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Messages and files pinned to a thread (timeline `p`, `/pin-file`).
+//!
+//! Pins live in the thread's meta line. Each run renders them into a block
+//! appended to the system prompt, so they stay at the top of the context no
+//! matter how much history conversation truncation drops. Files are re-read
+//! on every run, so the model always sees their current contents.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use crate::core::thread_persistence::{self, ThreadPins};
+
+/// Largest pinned file body sent per request; longer files are cut.
+const MAX_PINNED_FILE_BYTES: usize = 32 * 1024;
+
+/// Loads the thread's pins and renders them, or `None` when nothing is pinned.
+pub fn load(thread_id: &str, root: &Path) -> Option<String> {
+    let pins = thread_persistence::read_thread_pins(thread_id)
+        .inspect_err(|err| tracing::warn!(thread_id, %err, "Failed to read thread pins"))
+        .ok()?;
+    render(&pins, root)
+}
+
+/// Renders pinned messages and files as a `<pinned_context>` block. File
+/// paths resolve against `root`.
+pub fn render(pins: &ThreadPins, root: &Path) -> Option<String> {
+    if pins.messages.is_empty() && pins.files.is_empty() {
+        return None;
+    }
+    let mut out = String::from(
+        "<pinned_context>\nThe user pinned these messages and files to the conversation. \
+         Keep them in mind for every reply; file contents are current as of this request.\n",
+    );
+    for message in &pins.messages {
+        let _ = write!(
+            out,
+            "\n<pinned_message role=\"{}\">\n{}\n</pinned_message>\n",
+            message.role,
+            message.text.trim()
+        );
+    }
+    for path in &pins.files {
+        let body = match std::fs::read(root.join(path)) {
+            Ok(bytes) => {
+                let text = String::from_utf8_lossy(&bytes);
+                let kept = thread_persistence::truncate_str(&text, MAX_PINNED_FILE_BYTES);
+                if kept.len() < text.len() {
+                    format!("{kept}\n… (truncated at {MAX_PINNED_FILE_BYTES} bytes)")
+                } else {
+                    kept.to_string()
+                }
+            }
+            Err(err) => format!("(could not read file: {err})"),
+        };
+        let _ = write!(
+            out,
+            "\n<pinned_file path=\"{path}\">\n{}\n</pinned_file>\n",
+            body.trim_end()
+        );
+    }
+    out.push_str("</pinned_context>");
+    Some(out)
+}
+
+/// Appends a per-thread block (pins, response style) to `system_prompt`.
+pub fn append_to_prompt(system_prompt: Option<&str>, pinned: String) -> String {
+    match system_prompt {
+        Some(prompt) if !prompt.trim().is_empty() => format!("{prompt}\n\n{pinned}"),
+        _ => pinned,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::thread_persistence::PinnedMessage;
+
+    #[test]
+    fn test_render_includes_messages_and_current_file_contents() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.md"), "remember the milk\n").unwrap();
+        let pins = ThreadPins {
+            messages: vec![PinnedMessage {
+                role: "user".to_string(),
+                text: "Always answer in French.".to_string(),
+            }],
+            files: vec!["notes.md".to_string(), "missing.md".to_string()],
+        };
+
+        let block = render(&pins, dir.path()).unwrap();
+        assert!(block.starts_with("<pinned_context>"));
+        assert!(block.contains(
+            "<pinned_message role=\"user\">\nAlways answer in French.\n</pinned_message>"
+        ));
+        assert!(
+            block.contains("<pinned_file path=\"notes.md\">\nremember the milk\n</pinned_file>")
+        );
+        assert!(block.contains("<pinned_file path=\"missing.md\">\n(could not read file:"));
+        assert!(render(&ThreadPins::default(), dir.path()).is_none());
+
+        assert_eq!(
+            append_to_prompt(Some("base"), "pins".to_string()),
+            "base\n\npins"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+//! Prompt improvement: rewrites a drafted prompt against a prompt-engineering
+//! rubric via an isolated LLM subagent (`Config::improve_model`).
+//!
+//! Mirrors `prompt_builder_generation` in shape; differs in that the input is
+//! a full draft to tighten rather than a short intent to expand. The helper
+//! run never touches the current thread's context. Used by the TUI
+//! `/improve` command.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Result, ensure};
+use tokio_util::sync::CancellationToken;
+
+use crate::config::ThinkingLevel;
+use crate::core::subagent::{ExecSubagentOptions, run_exec_subagent_with_cancel};
+use crate::prompts::PROMPT_IMPROVE_PROMPT_TEMPLATE;
+use crate::zdx_context::build_zdx_context;
+
+/// Timeout for the prompt improvement subagent (2 minutes).
+const PROMPT_IMPROVE_TIMEOUT_SECS: u64 = 120;
+
+/// Builds the improvement prompt by substituting the draft.
+fn build_prompt_improve_prompt(draft: &str, zdx_context: &str) -> String {
+    PROMPT_IMPROVE_PROMPT_TEMPLATE
+        .replace("{{ZDX_CONTEXT}}", zdx_context)
+        .replace("{{DRAFT}}", draft)
+}
+
+/// Strips a code fence the model wrapped around the whole answer.
+fn strip_wrapping_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let Some(body) = rest.strip_suffix("```") else {
+        return trimmed;
+    };
+    if body.contains("```") {
+        return trimmed;
+    }
+    // Drop the info string (e.g. ```markdown) on the opening line.
+    body.split_once('\n').map_or(body, |(_, body)| body).trim()
+}
+
+/// Returns an improved version of `draft`.
+///
+/// `model` is a `provider:model[@thinking]` spec (thinking defaults to Low).
+/// With `no_save`, the helper run is not persisted as a thread (incognito).
+///
+/// # Errors
+/// Returns an error when the draft is empty, or the subagent fails, times
+/// out, is cancelled, or returns nothing.
+pub async fn improve_prompt(
+    draft: &str,
+    model: &str,
+    root: &Path,
+    no_save: bool,
+    cancel: Option<CancellationToken>,
+) -> Result<String> {
+    let trimmed = draft.trim();
+    ensure!(!trimmed.is_empty(), "Draft prompt cannot be empty");
+
+    let generation_prompt = build_prompt_improve_prompt(trimmed, &build_zdx_context(root));
+    let (model, thinking) = crate::models::split_model_thinking(model);
+
+    let options = ExecSubagentOptions {
+        model: Some(model.to_string()),
+        system_prompt: None,
+        thinking_level: Some(thinking.unwrap_or(ThinkingLevel::Low)),
+        no_tools: true,
+        no_system_prompt: true,
+        tools_override: None,
+        event_filter: Some(vec!["turn_finished".to_string()]),
+        timeout: Some(Duration::from_secs(PROMPT_IMPROVE_TIMEOUT_SECS)),
+        activity_kind: Some("helper:improve".to_string()),
+        thread_origin_kind: Some("helper:improve".to_string()),
+        no_save,
+        ..Default::default()
+    };
+
+    let output =
+        run_exec_subagent_with_cancel(root, &generation_prompt, &options, cancel, None).await?;
+    let improved = strip_wrapping_fence(&output);
+    ensure!(!improved.is_empty(), "The model returned an empty prompt");
+    Ok(improved.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn substitutes_draft_and_context() {
+        let prompt = build_prompt_improve_prompt("fix the login bug", "CTX_MARKER");
+        assert!(prompt.contains("<draft>\nfix the login bug\n</draft>"));
+        assert!(prompt.contains("CTX_MARKER"));
+        assert!(!prompt.contains("{{DRAFT}}"));
+        assert!(!prompt.contains("{{ZDX_CONTEXT}}"));
+        // The rubric survives substitution.
+        assert!(prompt.contains("## Rubric"));
+        assert!(prompt.contains("Never invent details"));
+    }
+
+    #[test]
+    fn strips_only_a_fence_around_the_whole_answer() {
+        assert_eq!(strip_wrapping_fence("```markdown\nDo X.\n```"), "Do X.");
+        assert_eq!(strip_wrapping_fence("```\nDo X.\n```\n"), "Do X.");
+        let inner = "Run this:\n```sh\ncargo test\n```";
+        assert_eq!(strip_wrapping_fence(inner), inner);
+        let two = "```sh\na\n```\nthen\n```sh\nb\n```";
+        assert_eq!(strip_wrapping_fence(two), two);
+    }
+}
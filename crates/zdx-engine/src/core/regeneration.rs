@@ -0,0 +1,118 @@
+//! Response regeneration (`/redo <feedback>`).
+//!
+//! A redo re-runs the latest user prompt with a short feedback note as
+//! steering. The thread log is append-only, so the discarded attempt stays
+//! on disk; the feedback is recorded after it as a user message tagged with
+//! [`REGENERATION_PHASE`]. Replay drops everything between the prompt and
+//! that message, so providers (and a reloaded transcript) only see the
+//! prompt, the feedback, and the regenerated answer.
+
+use crate::providers::{ChatContentBlock, ChatMessage, MessageContent};
+
+/// Message `phase` marking the feedback message of a regeneration.
+pub const REGENERATION_PHASE: &str = "regeneration";
+
+/// Framing sent ahead of the feedback. The model never sees the discarded
+/// attempt, so the note has to stand on its own.
+const FEEDBACK_PREFIX: &str = "Answer my previous message again. Your earlier answer was discarded; \
+     apply this feedback to the new one: ";
+
+/// Builds the user message recorded for a regeneration with `feedback`.
+pub fn regeneration_message(feedback: &str) -> ChatMessage {
+    ChatMessage {
+        role: "user".to_string(),
+        phase: Some(REGENERATION_PHASE.to_string()),
+        content: MessageContent::Text(format!("{FEEDBACK_PREFIX}{}", feedback.trim())),
+    }
+}
+
+/// Returns true when `message` is the feedback message of a regeneration.
+pub fn is_regeneration_message(message: &ChatMessage) -> bool {
+    message.role == "user" && is_regeneration_phase(message.phase.as_deref())
+}
+
+/// Returns true for the phase of a regeneration feedback message.
+pub fn is_regeneration_phase(phase: Option<&str>) -> bool {
+    phase == Some(REGENERATION_PHASE)
+}
+
+/// Returns the user's feedback from a regeneration message's text.
+pub fn regeneration_feedback(text: &str) -> &str {
+    text.strip_prefix(FEEDBACK_PREFIX).unwrap_or(text)
+}
+
+/// Drops the latest attempt: every message after the last user prompt
+/// (assistant replies, tool results, steering, earlier regeneration
+/// feedback). Returns false, leaving `messages` untouched, when there is no
+/// prompt to regenerate.
+pub fn drop_latest_attempt(messages: &mut Vec<ChatMessage>) -> bool {
+    let Some(index) = messages.iter().rposition(is_prompt) else {
+        return false;
+    };
+    messages.truncate(index + 1);
+    true
+}
+
+/// A message the user typed: no phase (steering, check feedback, and
+/// regeneration all set one) and no tool results.
+fn is_prompt(message: &ChatMessage) -> bool {
+    if message.role != "user" || message.phase.is_some() {
+        return false;
+    }
+    match &message.content {
+        MessageContent::Text(_) => true,
+        MessageContent::Blocks(blocks) => !blocks
+            .iter()
+            .any(|block| matches!(block, ChatContentBlock::ToolResult(_))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::steering::steering_message;
+    use crate::tools::{ToolResult, ToolResultContent};
+
+    #[test]
+    fn drops_everything_after_the_last_prompt() {
+        let mut messages = vec![
+            ChatMessage::user("first"),
+            ChatMessage::assistant_text("one", None),
+            ChatMessage::user("second"),
+            ChatMessage::assistant_text("calling a tool", None),
+            ChatMessage::tool_results(vec![ToolResult {
+                tool_use_id: "t1".to_string(),
+                content: ToolResultContent::Text("ok".to_string()),
+                is_error: false,
+            }]),
+            steering_message("use iterators"),
+            regeneration_message("shorter"),
+            ChatMessage::assistant_text("two", None),
+        ];
+
+        assert!(drop_latest_attempt(&mut messages));
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(
+            &messages[2].content,
+            MessageContent::Text(text) if text == "second"
+        ));
+    }
+
+    #[test]
+    fn nothing_to_drop_without_a_prompt() {
+        let mut messages = vec![regeneration_message("shorter")];
+        assert!(!drop_latest_attempt(&mut messages));
+        assert_eq!(messages.len(), 1);
+    }
+
+    #[test]
+    fn feedback_round_trips_through_the_framing() {
+        let message = regeneration_message("  shorter ");
+        assert!(is_regeneration_message(&message));
+        let MessageContent::Text(text) = &message.content else {
+            panic!("expected text content");
+        };
+        assert_eq!(regeneration_feedback(text), "shorter");
+        assert_eq!(regeneration_feedback("plain"), "plain");
+    }
+}
@@ -0,0 +1,196 @@
+//! Opt-in final-response cache for `zdx exec`.
+//!
+//! Identical exec invocations (same model and thinking level, sampling
+//! params and output cap, provider `extra` body params, system prompt, message
+//! history, and tool set) reuse a previously stored final answer
+//! instead of calling the provider. Entries live as one JSON file per key
+//! under `$ZDX_HOME/cache/responses/` and expire after `[response_cache]
+//! ttl_secs`. The cache is disposable: unreadable or stale entries are
+//! treated as misses.
+
+use std::fs;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::{ModelParams, ThinkingLevel, paths};
+use crate::providers::ChatMessage;
+use crate::tools::ToolDefinition;
+
+/// Inputs that determine a cached response.
+pub struct CacheKeyParts<'a> {
+    pub model: &'a str,
+    pub thinking_level: ThinkingLevel,
+    /// `[model_params]` sampling controls.
+    pub model_params: ModelParams,
+    /// Effective output token cap sent with the request.
+    pub max_tokens: u32,
+    /// `[providers.<id>.extra]` body parameters for the model's provider.
+    pub extra: &'a serde_json::Map<String, serde_json::Value>,
+    pub system_prompt: Option<&'a str>,
+    pub messages: &'a [ChatMessage],
+    pub tools: &'a [ToolDefinition],
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    created_at: u64,
+    model: String,
+    response: String,
+}
+
+/// Hex cache key: a hash over the model spec, output cap, and the hashes of
+/// the sampling params, extra body params, system prompt, messages, and tools.
+///
+/// # Errors
+/// Returns an error if any part cannot be serialized.
+pub fn cache_key(parts: &CacheKeyParts<'_>) -> Result<String> {
+    let mut hasher = Sha256::new();
+    hasher.update(parts.model.as_bytes());
+    hasher.update([0]);
+    hasher.update(parts.thinking_level.display_name().as_bytes());
+    hasher.update([0]);
+    hasher.update(parts.max_tokens.to_le_bytes());
+    hasher.update(hash_bytes(
+        &serde_json::to_vec(&parts.model_params).context("serialize model params for cache key")?,
+    ));
+    hasher.update(hash_bytes(
+        &serde_json::to_vec(parts.extra).context("serialize extra params for cache key")?,
+    ));
+    hasher.update(hash_bytes(
+        parts.system_prompt.unwrap_or_default().as_bytes(),
+    ));
+    hasher.update(hash_bytes(
+        &serde_json::to_vec(parts.messages).context("serialize messages for cache key")?,
+    ));
+    hasher.update(hash_bytes(
+        &serde_json::to_vec(parts.tools).context("serialize tools for cache key")?,
+    ));
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Returns the cached response for `key` if present and younger than
+/// `ttl_secs`.
+pub fn lookup(key: &str, ttl_secs: u64) -> Option<String> {
+    let raw = fs::read(entry_path(key)).ok()?;
+    let entry: CacheEntry = serde_json::from_slice(&raw).ok()?;
+    let age = now_secs().saturating_sub(entry.created_at);
+    (age < ttl_secs).then_some(entry.response)
+}
+
+/// Stores `response` under `key`.
+///
+/// # Errors
+/// Returns an error if the cache directory or entry cannot be written.
+pub fn store(key: &str, model: &str, response: &str) -> Result<()> {
+    let path = entry_path(key);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("create response cache dir {}", dir.display()))?;
+    }
+    let entry = CacheEntry {
+        created_at: now_secs(),
+        model: model.to_string(),
+        response: response.to_string(),
+    };
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, serde_json::to_vec(&entry)?)
+        .with_context(|| format!("write response cache entry {}", tmp.display()))?;
+    fs::rename(&tmp, &path)
+        .with_context(|| format!("finalize response cache entry {}", path.display()))?;
+    Ok(())
+}
+
+fn cache_dir() -> PathBuf {
+    paths::zdx_home().join("cache").join("responses")
+}
+
+fn entry_path(key: &str) -> PathBuf {
+    cache_dir().join(format!("{key}.json"))
+}
+
+fn hash_bytes(bytes: &[u8]) -> [u8; 32] {
+    Sha256::digest(bytes).into()
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |duration| duration.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(model: &str, system: Option<&str>, prompt: &str) -> String {
+        let messages = [ChatMessage::user(prompt)];
+        cache_key(&parts(model, system, &messages, &serde_json::Map::new())).unwrap()
+    }
+
+    fn parts<'a>(
+        model: &'a str,
+        system: Option<&'a str>,
+        messages: &'a [ChatMessage],
+        extra: &'a serde_json::Map<String, serde_json::Value>,
+    ) -> CacheKeyParts<'a> {
+        CacheKeyParts {
+            model,
+            thinking_level: ThinkingLevel::Off,
+            model_params: ModelParams::default(),
+            max_tokens: 1024,
+            extra,
+            system_prompt: system,
+            messages,
+            tools: &[],
+        }
+    }
+
+    #[test]
+    fn key_changes_with_each_input() {
+        let base = key("anthropic:claude-sonnet-4-6", Some("sys"), "hi");
+        assert_eq!(base, key("anthropic:claude-sonnet-4-6", Some("sys"), "hi"));
+        assert_ne!(base, key("openai:gpt-5.5", Some("sys"), "hi"));
+        assert_ne!(base, key("anthropic:claude-sonnet-4-6", None, "hi"));
+        assert_ne!(
+            base,
+            key("anthropic:claude-sonnet-4-6", Some("sys"), "hello")
+        );
+    }
+
+    #[test]
+    fn key_changes_with_sampling_params_output_cap_and_extra_body() {
+        let messages = [ChatMessage::user("hi")];
+        let no_extra = serde_json::Map::new();
+        let base = cache_key(&parts("m", None, &messages, &no_extra)).unwrap();
+
+        let mut sampled = parts("m", None, &messages, &no_extra);
+        sampled.model_params.temperature = Some(0.2);
+        assert_ne!(base, cache_key(&sampled).unwrap());
+
+        let mut capped = parts("m", None, &messages, &no_extra);
+        capped.max_tokens = 256;
+        assert_ne!(base, cache_key(&capped).unwrap());
+
+        let mut extra = serde_json::Map::new();
+        extra.insert("top_k".into(), 50.into());
+        assert_ne!(
+            base,
+            cache_key(&parts("m", None, &messages, &extra)).unwrap()
+        );
+    }
+
+    #[test]
+    fn store_then_lookup_respects_ttl() {
+        crate::test_support::temp_zdx_home();
+        let key = key("m", None, "hi");
+
+        assert_eq!(lookup(&key, 60), None);
+        store(&key, "m", "cached answer").unwrap();
+        assert_eq!(lookup(&key, 60).as_deref(), Some("cached answer"));
+        assert_eq!(lookup(&key, 0), None);
+    }
+}
@@ -0,0 +1,105 @@
+//! Response style presets (`/style concise`, `/style pt-BR`, ...).
+//!
+//! A thread's style is stored in its meta line. Each run renders the style's
+//! directive into a short block appended to the system prompt. Custom styles
+//! come from `[styles]` in config and override built-ins with the same name.
+
+use crate::config::Config;
+use crate::core::thread_persistence;
+
+/// Built-in presets, available without any config.
+pub const BUILTIN_STYLES: &[(&str, &str)] = &[
+    (
+        "concise",
+        "Keep replies short. Lead with the answer, skip preamble and recaps, and only add detail the user needs to act.",
+    ),
+    (
+        "explanatory",
+        "Explain as you go. Say why, not just what, and call out trade-offs, alternatives, and anything surprising in the code.",
+    ),
+    (
+        "pt-BR",
+        "Reply in Brazilian Portuguese (pt-BR). Keep code, identifiers, commands, and quoted output unchanged.",
+    ),
+];
+
+/// Returns the directive for style `name`, preferring `[styles]` over the
+/// built-ins. Names match case-insensitively.
+pub fn directive<'a>(config: &'a Config, name: &str) -> Option<&'a str> {
+    config
+        .styles
+        .iter()
+        .map(|(name, directive)| (name.as_str(), directive.as_str()))
+        .chain(BUILTIN_STYLES.iter().copied())
+        .find(|(style, _)| style.eq_ignore_ascii_case(name))
+        .map(|(_, directive)| directive.trim())
+        .filter(|directive| !directive.is_empty())
+}
+
+/// Returns the canonical spelling of style `name`, or `None` if it is unknown.
+pub fn canonical_name<'a>(config: &'a Config, name: &str) -> Option<&'a str> {
+    directive(config, name)?;
+    names(config)
+        .into_iter()
+        .find(|style| style.eq_ignore_ascii_case(name))
+}
+
+/// Lists every available style: built-ins first, then custom ones.
+pub fn names(config: &Config) -> Vec<&str> {
+    let mut names: Vec<&str> = BUILTIN_STYLES.iter().map(|(name, _)| *name).collect();
+    for name in config.styles.keys() {
+        if !names.iter().any(|known| known.eq_ignore_ascii_case(name)) {
+            names.push(name);
+        }
+    }
+    names
+}
+
+/// Renders style `name` as a `<response_style>` block, or `None` when the
+/// style is unknown (e.g. a custom style removed from config).
+pub fn render(config: &Config, name: &str) -> Option<String> {
+    let directive = directive(config, name)?;
+    Some(format!(
+        "<response_style name=\"{name}\">\n{directive}\n</response_style>"
+    ))
+}
+
+/// Loads the thread's style and renders it, or `None` when no style is set.
+pub fn load(thread_id: &str, config: &Config) -> Option<String> {
+    let name = thread_persistence::read_thread_style(thread_id)
+        .inspect_err(|err| tracing::warn!(thread_id, %err, "Failed to read thread style"))
+        .ok()??;
+    let block = render(config, &name);
+    if block.is_none() {
+        tracing::warn!(thread_id, style = %name, "Unknown response style; ignoring");
+    }
+    block
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_styles_override_builtins_and_render() {
+        let mut config = Config::default();
+        config
+            .styles
+            .insert("concise".to_string(), "One line only.".to_string());
+        config
+            .styles
+            .insert("pirate".to_string(), "Talk like a pirate.".to_string());
+
+        assert_eq!(directive(&config, "CONCISE"), Some("One line only."));
+        assert_eq!(canonical_name(&config, "PT-br"), Some("pt-BR"));
+        assert_eq!(canonical_name(&config, "formal"), None);
+        assert_eq!(
+            names(&config),
+            vec!["concise", "explanatory", "pt-BR", "pirate"]
+        );
+        assert_eq!(
+            render(&config, "pirate").unwrap(),
+            "<response_style name=\"pirate\">\nTalk like a pirate.\n</response_style>"
+        );
+    }
+}
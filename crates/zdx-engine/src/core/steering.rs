@@ -0,0 +1,87 @@
+//! Mid-turn steering: user notes injected into a running turn.
+//!
+//! A surface keeps a [`SteeringQueue`] and hands a clone to the agent via
+//! `AgentOptions::steering`. The agent loop drains it between tool calls and
+//! appends each note as a user message before the next provider request, so
+//! the model sees the guidance without the turn being restarted.
+
+use std::sync::{Arc, Mutex};
+
+use crate::providers::{ChatMessage, MessageContent};
+
+/// Message `phase` marking a user message injected mid-turn. Persisted with
+/// the message so thread logs distinguish steering from normal prompts.
+pub const STEERING_PHASE: &str = "steering";
+
+/// Shared queue of pending steering notes.
+#[derive(Debug, Clone, Default)]
+pub struct SteeringQueue {
+    notes: Arc<Mutex<Vec<String>>>,
+}
+
+impl SteeringQueue {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a note to be applied before the next provider request.
+    pub fn push(&self, note: impl Into<String>) {
+        self.lock().push(note.into());
+    }
+
+    /// Takes all pending notes in submission order.
+    pub fn drain(&self) -> Vec<String> {
+        std::mem::take(&mut *self.lock())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lock().is_empty()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<String>> {
+        self.notes
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+}
+
+/// Builds the user message recorded for a steering note.
+pub fn steering_message(note: impl Into<String>) -> ChatMessage {
+    ChatMessage {
+        role: "user".to_string(),
+        phase: Some(STEERING_PHASE.to_string()),
+        content: MessageContent::Text(note.into()),
+    }
+}
+
+/// Returns true when `message` was injected by steering.
+pub fn is_steering_message(message: &ChatMessage) -> bool {
+    message.role == "user" && message.phase.as_deref() == Some(STEERING_PHASE)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drain_returns_notes_in_order_and_empties_queue() {
+        let queue = SteeringQueue::new();
+        let shared = queue.clone();
+        shared.push("focus on the parser");
+        shared.push("ignore the tests");
+
+        assert_eq!(
+            queue.drain(),
+            vec!["focus on the parser", "ignore the tests"]
+        );
+        assert!(queue.is_empty());
+        assert!(shared.drain().is_empty());
+    }
+
+    #[test]
+    fn steering_message_is_tagged() {
+        let message = steering_message("focus on the parser");
+        assert!(is_steering_message(&message));
+        assert!(!is_steering_message(&ChatMessage::user("hi")));
+    }
+}
@@ -0,0 +1,183 @@
+//! Resuming a response whose stream died after visible output.
+//!
+//! Before anything is shown, a dropped stream is simply retried. Afterwards a
+//! plain retry would duplicate text the user already saw, so the agent loop
+//! resumes instead: tool calls whose input never finished streaming are
+//! dropped, complete tool calls are run as if the response ended there, and
+//! otherwise a continuation request asks the model to pick up after the
+//! partial text. The next stream is seeded with the partial turn, so its
+//! deltas land in the same transcript cell and the final assistant message
+//! holds both halves.
+
+use crate::core::agent::{AssistantPart, AssistantTurnBuilder};
+use crate::providers::{ChatContentBlock, ChatMessage, MessageContent};
+
+/// Continuation requests allowed per response.
+pub const MAX_STREAM_RESUMES: u32 = 2;
+/// Characters of already-streamed text quoted in a continuation request.
+const RESUME_TAIL_CHARS: usize = 200;
+
+/// How to recover a partial response.
+#[derive(Debug)]
+pub enum ResumePlan {
+    /// Complete tool calls were received: run them and let the next model
+    /// request carry on from their results.
+    RunTools(AssistantTurnBuilder),
+    /// Ask the model to continue from the partial turn (the seed for the
+    /// next stream). A seed without text makes the continuation a plain
+    /// re-request.
+    Continue(AssistantTurnBuilder),
+}
+
+/// Splits a partial turn into a resume plan and the ids of tool calls whose
+/// input was cut off (their cells need closing; the model re-issues them).
+pub fn plan(turn: AssistantTurnBuilder) -> (ResumePlan, Vec<String>) {
+    let mut dropped = Vec::new();
+    let mut parts = Vec::with_capacity(turn.parts.len());
+    for part in turn.parts {
+        match part {
+            AssistantPart::ToolUse(tool)
+                if serde_json::from_str::<serde_json::Value>(&tool.input_json).is_err() =>
+            {
+                dropped.push(tool.id);
+            }
+            part => parts.push(part),
+        }
+    }
+    let turn = AssistantTurnBuilder {
+        model: turn.model,
+        parts,
+    };
+
+    if turn.has_tool_uses() {
+        return (ResumePlan::RunTools(turn), dropped);
+    }
+    if turn.final_text().is_empty() {
+        // Reasoning alone can't be continued; start the response over.
+        return (
+            ResumePlan::Continue(AssistantTurnBuilder::new(turn.model)),
+            dropped,
+        );
+    }
+    (ResumePlan::Continue(reindex(turn)), dropped)
+}
+
+/// Builds the continuation request: the original request, the partial
+/// assistant message, and a user note quoting where it stopped.
+pub fn continuation_messages(
+    request: &[ChatMessage],
+    seed: &AssistantTurnBuilder,
+) -> Vec<ChatMessage> {
+    let mut messages = request.to_vec();
+    let text = seed.final_text();
+    if text.is_empty() {
+        return messages;
+    }
+    let blocks: Vec<ChatContentBlock> = seed.clone().finalize().blocks;
+    messages.push(ChatMessage {
+        role: "assistant".to_string(),
+        phase: Some("commentary".to_string()),
+        content: MessageContent::Blocks(blocks),
+    });
+    messages.push(ChatMessage::user(continuation_prompt(&text)));
+    messages
+}
+
+fn continuation_prompt(text: &str) -> String {
+    let skip = text.chars().count().saturating_sub(RESUME_TAIL_CHARS);
+    let tail: String = text.chars().skip(skip).collect();
+    format!(
+        "Your previous response was cut off by a connection error. Continue it \
+         exactly where it stopped, without repeating any of it or adding a \
+         preamble. Continue from:\n\n{tail}"
+    )
+}
+
+/// Moves the seed's part indices out of the range a new stream uses, so the
+/// continuation's blocks are appended instead of merged into existing parts.
+fn reindex(mut turn: AssistantTurnBuilder) -> AssistantTurnBuilder {
+    let base = usize::MAX / 2;
+    for (offset, part) in turn.parts.iter_mut().enumerate() {
+        let index = match part {
+            AssistantPart::Reasoning(part) => &mut part.index,
+            AssistantPart::Text(part) => &mut part.index,
+            AssistantPart::ToolUse(part) => &mut part.index,
+        };
+        *index = base + offset;
+    }
+    turn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::agent::ToolUseBuilder;
+
+    fn tool(index: usize, id: &str, input_json: &str) -> AssistantPart {
+        AssistantPart::ToolUse(ToolUseBuilder {
+            index,
+            id: id.to_string(),
+            name: "read".to_string(),
+            input_json: input_json.to_string(),
+            input_preview_len: 0,
+            id_origin: zdx_types::IdOrigin::default(),
+            replay: None,
+        })
+    }
+
+    fn text_turn(text: &str) -> AssistantTurnBuilder {
+        let mut turn = AssistantTurnBuilder::new("m".to_string());
+        turn.ensure_text_part_mut(0).text.push_str(text);
+        turn
+    }
+
+    #[test]
+    fn test_plan_drops_cut_off_tool_calls() {
+        let mut turn = text_turn("Reading files");
+        turn.parts.push(tool(1, "t1", r#"{"path":"a.rs"}"#));
+        turn.parts.push(tool(2, "t2", r#"{"path":"b"#));
+
+        let (plan, dropped) = plan(turn);
+        assert_eq!(dropped, vec!["t2".to_string()]);
+        let ResumePlan::RunTools(turn) = plan else {
+            panic!("complete tool calls must run");
+        };
+        assert_eq!(turn.tool_uses().count(), 1);
+    }
+
+    #[test]
+    fn test_continuation_stitches_onto_the_seed() {
+        let mut partial = text_turn("The answer is");
+        partial.parts.push(tool(1, "t1", r#"{"pa"#));
+        let (ResumePlan::Continue(mut seed), dropped) = plan(partial) else {
+            panic!("partial text must be continued");
+        };
+        assert_eq!(dropped, vec!["t1".to_string()]);
+
+        let request = vec![ChatMessage::user("question")];
+        let messages = continuation_messages(&request, &seed);
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[1].role, "assistant");
+        let MessageContent::Text(prompt) = &messages[2].content else {
+            panic!("expected a text prompt");
+        };
+        assert!(prompt.ends_with("Continue from:\n\nThe answer is"));
+
+        // The next stream reuses index 0 without touching the seed's text.
+        seed.ensure_text_part_mut(0).text.push_str(" 42.");
+        assert_eq!(seed.final_text(), "The answer is 42.");
+        assert_eq!(seed.finalize().blocks.len(), 2);
+    }
+
+    #[test]
+    fn test_partial_without_text_restarts() {
+        let mut turn = AssistantTurnBuilder::new("m".to_string());
+        turn.parts.push(tool(0, "t1", ""));
+        let (ResumePlan::Continue(seed), _) = plan(turn) else {
+            panic!("expected a restart");
+        };
+        assert!(seed.parts.is_empty());
+        let request = vec![ChatMessage::user("question")];
+        assert_eq!(continuation_messages(&request, &seed).len(), 1);
+    }
+}
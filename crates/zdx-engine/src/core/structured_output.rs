@@ -0,0 +1,170 @@
+//! JSON schema-constrained final answers for `zdx exec --schema`.
+//!
+//! The schema goes to the provider when it can enforce it (`OpenAI`
+//! Responses `text.format`; Anthropic and Claude CLI a forced answer tool
+//! whose input is the result) and is always spelled out in the prompt, so
+//! every provider gets the same contract. The final answer is validated
+//! here; on failure the caller feeds the errors back and retries.
+
+use std::path::Path;
+
+use anyhow::{Context, Result, bail};
+use serde_json::Value;
+
+use crate::providers::OutputSchema;
+
+/// Follow-up requests after the first answer fails validation.
+pub const MAX_RETRIES: usize = 2;
+
+/// Loads and compiles the schema at `path`. The schema name comes from its
+/// `title`, else the file stem.
+///
+/// # Errors
+/// Returns an error if the file is unreadable, not JSON, or not a valid
+/// JSON schema.
+pub fn load_schema(path: &Path) -> Result<OutputSchema> {
+    let raw = std::fs::read_to_string(path)
+        .with_context(|| format!("read schema file {}", path.display()))?;
+    let schema: Value = serde_json::from_str(&raw)
+        .with_context(|| format!("parse schema file {}", path.display()))?;
+    if let Err(err) = jsonschema::validator_for(&schema) {
+        bail!("Invalid JSON schema in {}: {err}", path.display());
+    }
+    let name = schema
+        .get("title")
+        .and_then(Value::as_str)
+        .or_else(|| path.file_stem().and_then(|stem| stem.to_str()))
+        .map(schema_name)
+        .unwrap_or_default();
+    Ok(OutputSchema {
+        name: if name.is_empty() {
+            "response".to_string()
+        } else {
+            name
+        },
+        schema,
+    })
+}
+
+/// Prompt suffix that asks for a bare JSON answer matching `schema`.
+pub fn instructions(schema: &OutputSchema) -> String {
+    let pretty = serde_json::to_string_pretty(&schema.schema).unwrap_or_default();
+    format!(
+        "Respond with only a JSON value that matches this JSON schema. Do not wrap it in \
+         Markdown or add any other text.\n\n```json\n{pretty}\n```"
+    )
+}
+
+/// Parses `text` as JSON (tolerating a Markdown code fence) and validates it
+/// against `schema`.
+///
+/// # Errors
+/// Returns a readable list of problems when the text is not JSON or does
+/// not match the schema.
+pub fn validate(schema: &OutputSchema, text: &str) -> Result<Value, String> {
+    let value: Value = serde_json::from_str(strip_code_fence(text))
+        .map_err(|err| format!("response is not valid JSON: {err}"))?;
+    let validator = jsonschema::validator_for(&schema.schema).map_err(|err| err.to_string())?;
+    let errors: Vec<String> = validator
+        .iter_errors(&value)
+        .map(|err| {
+            let path = err.instance_path.to_string();
+            if path.is_empty() {
+                err.to_string()
+            } else {
+                format!("{path}: {err}")
+            }
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(value)
+    } else {
+        Err(errors.join("\n"))
+    }
+}
+
+/// Follow-up user message after a failed validation.
+pub fn retry_prompt(errors: &str) -> String {
+    format!(
+        "Your previous response did not match the required JSON schema:\n{errors}\n\n\
+         Reply again with only the corrected JSON value."
+    )
+}
+
+fn strip_code_fence(text: &str) -> &str {
+    let trimmed = text.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let body = rest.split_once('\n').map_or("", |(_, body)| body);
+    body.trim_end().strip_suffix("```").unwrap_or(body).trim()
+}
+
+fn schema_name(raw: &str) -> String {
+    raw.chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '_' || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .take(64)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn schema() -> OutputSchema {
+        OutputSchema {
+            name: "answer".to_string(),
+            schema: json!({
+                "type": "object",
+                "properties": {"count": {"type": "integer"}},
+                "required": ["count"],
+            }),
+        }
+    }
+
+    #[test]
+    fn valid_answer_is_parsed_even_inside_a_code_fence() {
+        assert_eq!(
+            validate(&schema(), "{\"count\": 3}"),
+            Ok(json!({"count": 3}))
+        );
+        assert_eq!(
+            validate(&schema(), "```json\n{\"count\": 3}\n```"),
+            Ok(json!({"count": 3}))
+        );
+    }
+
+    #[test]
+    fn invalid_answers_report_why() {
+        let not_json = validate(&schema(), "three").unwrap_err();
+        assert!(not_json.contains("not valid JSON"));
+
+        let wrong_type = validate(&schema(), "{\"count\": \"3\"}").unwrap_err();
+        assert!(wrong_type.starts_with("/count:"), "{wrong_type}");
+
+        let missing = validate(&schema(), "{}").unwrap_err();
+        assert!(missing.contains("count"), "{missing}");
+    }
+
+    #[test]
+    fn load_schema_names_it_and_rejects_invalid_schemas() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("release notes.json");
+        std::fs::write(&path, r#"{"type": "object"}"#).unwrap();
+        assert_eq!(load_schema(&path).unwrap().name, "release_notes");
+
+        std::fs::write(&path, r#"{"title": "Summary", "type": "object"}"#).unwrap();
+        assert_eq!(load_schema(&path).unwrap().name, "Summary");
+
+        std::fs::write(&path, r#"{"type": 12}"#).unwrap();
+        assert!(load_schema(&path).is_err());
+    }
+}
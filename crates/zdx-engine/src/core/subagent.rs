@@ -45,6 +45,9 @@ pub struct ExecSubagentOptions {
     pub activity_parent_thread_id: Option<String>,
     /// Named subagent (e.g. `"explorer"`) for the child run.
     pub activity_subagent_name: Option<String>,
+    /// Thread id the child appends to (`--thread`). An id with no thread yet
+    /// creates it with the lineage below; an existing id resumes it.
+    pub thread_id: Option<String>,
     /// Origin kind recorded in the child thread's meta (e.g. `"subagent"`,
     /// `"helper:title"`). When set, the child persists a tagged thread instead
     /// of running throwaway. `None` leaves the child thread untagged.
@@ -53,8 +56,20 @@ pub struct ExecSubagentOptions {
     pub thread_parent_id: Option<String>,
     /// Named subagent recorded in the child thread's meta (subagent runs).
     pub thread_subagent_name: Option<String>,
+    /// Run the child without saving a thread (`--no-thread`), so an unsaved
+    /// parent doesn't leave its prompt behind in a child thread. Overrides
+    /// the thread id and lineage fields above.
+    pub no_save: bool,
+    /// Output token cap for the child run (passed as [`MAX_TOKENS_ENV_VARS`],
+    /// so it beats a configured `[model_params] max_output_tokens`).
+    pub max_tokens: Option<u32>,
 }
 
+/// Env overrides that carry `max_tokens` to the child: the top-level key and
+/// `[model_params] max_output_tokens`, which would otherwise take precedence.
+pub(crate) const MAX_TOKENS_ENV_VARS: [&str; 2] =
+    ["ZDX_MAX_TOKENS", "ZDX_MODEL_PARAMS__MAX_OUTPUT_TOKENS"];
+
 #[derive(Debug)]
 struct TempPromptFile {
     path: TempPath,
@@ -121,6 +136,11 @@ pub async fn run_exec_subagent_with_cancel(
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
         .kill_on_drop(true);
+    if let Some(max_tokens) = options.max_tokens {
+        for var in MAX_TOKENS_ENV_VARS {
+            command.env(var, max_tokens.to_string());
+        }
+    }
 
     let child = command
         .spawn()
@@ -158,12 +178,13 @@ pub async fn run_exec_subagent_with_cancel(
     process_subagent_output(&output)
 }
 
-/// Live sink for relaying a child subagent's tool activity to the parent.
+/// Live sink for relaying a child subagent's progress to the parent.
 ///
-/// Each child tool lifecycle event is re-emitted as an `AgentEvent::ToolOutputDelta`
-/// on the parent's event stream, keyed by the parent `invoke_subagent` tool id.
-/// The `chunk` is a compact JSON object (`{"t":"start"|"input"|"done"|"error", ...}`)
-/// that the TUI parses into a child tool activity list.
+/// Each child tool lifecycle event, assistant text delta, and usage update is
+/// re-emitted as an `AgentEvent::ToolOutputDelta` on the parent's event stream,
+/// keyed by the parent `invoke_subagent` tool id. The `chunk` is a compact JSON
+/// object (`{"t":"start"|"input"|"done"|"error"|"text"|"usage", ...}`) that the
+/// TUI parses into the nested progress shown under the tool cell.
 pub struct SubagentStreamSink {
     pub sender: EventSender,
     pub parent_tool_id: String,
@@ -192,6 +213,15 @@ impl SubagentStreamSink {
     fn emit_error(&self, id: &str) {
         self.emit(&serde_json::json!({ "t": "error", "id": id }));
     }
+
+    fn emit_text(&self, delta: &str) {
+        self.emit(&serde_json::json!({ "t": "text", "delta": delta }));
+    }
+
+    /// Emits the child's cumulative token usage so far.
+    fn emit_usage(&self, input: u64, output: u64) {
+        self.emit(&serde_json::json!({ "t": "usage", "input": input, "output": output }));
+    }
 }
 
 /// Result of draining a child subagent's stdout event stream.
@@ -270,8 +300,9 @@ async fn run_child_streaming(
     }
 }
 
-/// Reads JSONL agent events from a child's stdout, relaying tool lifecycle
-/// events through `sink` and capturing the terminal turn result.
+/// Reads JSONL agent events from a child's stdout, relaying tool lifecycle,
+/// text, and usage events through `sink` and capturing the terminal turn
+/// result.
 async fn read_stdout_events<R: AsyncRead + Unpin>(
     reader: R,
     sink: &SubagentStreamSink,
@@ -280,6 +311,7 @@ async fn read_stdout_events<R: AsyncRead + Unpin>(
     let mut seen_ids: HashSet<String> = HashSet::new();
     let mut final_text = None;
     let mut turn_failed = None;
+    let (mut input_tokens, mut output_tokens) = (0u64, 0u64);
 
     while let Some(line) = lines
         .next_line()
@@ -313,6 +345,19 @@ async fn read_stdout_events<R: AsyncRead + Unpin>(
                     sink.emit_error(&id);
                 }
             }
+            AgentEvent::AssistantDelta { text } if !text.is_empty() => sink.emit_text(&text),
+            AgentEvent::UsageUpdate {
+                input_tokens: input,
+                output_tokens: output,
+                cache_read_input_tokens,
+                cache_creation_input_tokens,
+                ..
+            } => {
+                // Usage updates are additive per request.
+                input_tokens += input + cache_read_input_tokens + cache_creation_input_tokens;
+                output_tokens += output;
+                sink.emit_usage(input_tokens, output_tokens);
+            }
             AgentEvent::TurnFinished {
                 status,
                 final_text: text,
@@ -341,7 +386,7 @@ fn extract_key_arg(tool_name: &str, input: &Value) -> Option<String> {
             .get("command")
             .and_then(Value::as_str)
             .map(truncate_command),
-        "read" | "edit" | "write" => input
+        "read" | "edit" | "write" | "outline" | "notebook_read" | "notebook_edit" => input
             .get("file_path")
             .and_then(Value::as_str)
             .map(str::to_string),
@@ -389,17 +434,25 @@ fn build_exec_args(
 
     // Global thread-lineage flags (before the subcommand) so the persisted
     // child thread records its subagent/helper origin in its meta line.
-    if let Some(kind) = normalize_optional(options.thread_origin_kind.as_deref()) {
-        args.push(OsString::from("--thread-origin-kind"));
-        args.push(OsString::from(kind));
-    }
-    if let Some(parent) = normalize_optional(options.thread_parent_id.as_deref()) {
-        args.push(OsString::from("--thread-parent-id"));
-        args.push(OsString::from(parent));
-    }
-    if let Some(name) = normalize_optional(options.thread_subagent_name.as_deref()) {
-        args.push(OsString::from("--thread-subagent-name"));
-        args.push(OsString::from(name));
+    if options.no_save {
+        args.push(OsString::from("--no-thread"));
+    } else {
+        if let Some(id) = normalize_optional(options.thread_id.as_deref()) {
+            args.push(OsString::from("--thread"));
+            args.push(OsString::from(id));
+        }
+        if let Some(kind) = normalize_optional(options.thread_origin_kind.as_deref()) {
+            args.push(OsString::from("--thread-origin-kind"));
+            args.push(OsString::from(kind));
+        }
+        if let Some(parent) = normalize_optional(options.thread_parent_id.as_deref()) {
+            args.push(OsString::from("--thread-parent-id"));
+            args.push(OsString::from(parent));
+        }
+        if let Some(name) = normalize_optional(options.thread_subagent_name.as_deref()) {
+            args.push(OsString::from("--thread-subagent-name"));
+            args.push(OsString::from(name));
+        }
     }
 
     args.extend([
@@ -613,9 +666,12 @@ mod tests {
                 activity_kind: None,
                 activity_parent_thread_id: None,
                 activity_subagent_name: None,
+                thread_id: None,
                 thread_origin_kind: None,
                 thread_parent_id: None,
                 thread_subagent_name: None,
+                no_save: false,
+                max_tokens: None,
             },
             Some(system_prompt_file),
         );
@@ -653,6 +709,7 @@ mod tests {
             Path::new("/tmp/project"),
             Path::new("/tmp/subagent-prompt.md"),
             &ExecSubagentOptions {
+                thread_id: Some("thread-child".to_string()),
                 thread_origin_kind: Some("subagent".to_string()),
                 thread_parent_id: Some("thread-parent".to_string()),
                 thread_subagent_name: Some("explorer".to_string()),
@@ -670,6 +727,8 @@ mod tests {
             vec![
                 "--root",
                 "/tmp/project",
+                "--thread",
+                "thread-child",
                 "--thread-origin-kind",
                 "subagent",
                 "--thread-parent-id",
@@ -683,6 +742,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn build_exec_args_no_save_skips_thread_lineage_flags() {
+        let args = build_exec_args(
+            Path::new("/tmp/project"),
+            Path::new("/tmp/subagent-prompt.md"),
+            &ExecSubagentOptions {
+                thread_origin_kind: Some("subagent".to_string()),
+                thread_subagent_name: Some("explorer".to_string()),
+                no_save: true,
+                ..Default::default()
+            },
+            None,
+        );
+        let args: Vec<String> = args
+            .iter()
+            .map(|s| s.to_string_lossy().to_string())
+            .collect();
+
+        assert_eq!(
+            args,
+            vec![
+                "--root",
+                "/tmp/project",
+                "--no-thread",
+                "exec",
+                "--prompt-file",
+                "/tmp/subagent-prompt.md"
+            ]
+        );
+    }
+
     #[test]
     fn build_exec_args_propagates_activity_metadata_when_tracking() {
         let args = build_exec_args(
@@ -831,10 +921,10 @@ mod tests {
         assert_eq!(truncated.chars().count(), 61); // 60 chars + ellipsis
     }
 
-    fn make_sink() -> (SubagentStreamSink, crate::core::agent::AgentEventRx) {
-        let (tx, rx) = crate::core::agent::create_event_channel();
+    fn make_sink() -> (SubagentStreamSink, crate::core::agent::EventSubscriber) {
+        let (sender, rx) = crate::core::agent::create_event_channel();
         let sink = SubagentStreamSink {
-            sender: EventSender::new(tx),
+            sender,
             parent_tool_id: "parent-tool".to_string(),
         };
         (sink, rx)
@@ -892,6 +982,48 @@ mod tests {
         assert_eq!(kinds, vec!["start", "input", "done"]);
     }
 
+    #[tokio::test]
+    async fn read_stdout_events_relays_child_text_and_cumulative_usage() {
+        let (sink, mut rx) = make_sink();
+        let usage = |input, output| AgentEvent::UsageUpdate {
+            input_tokens: input,
+            output_tokens: output,
+            cache_read_input_tokens: 10,
+            cache_creation_input_tokens: 0,
+            model: String::new(),
+            provider: String::new(),
+            duration_ms: None,
+            ttft_ms: None,
+            model_params: None,
+        };
+        let events = vec![
+            usage(100, 20),
+            AgentEvent::AssistantDelta {
+                text: "Looking".to_string(),
+            },
+            usage(50, 5),
+        ];
+        let bytes = jsonl(&events);
+
+        read_stdout_events(bytes.as_bytes(), &sink).await.unwrap();
+
+        drop(sink);
+        let mut chunks = Vec::new();
+        while let Some(event) = rx.recv().await {
+            if let AgentEvent::ToolOutputDelta { chunk, .. } = event.as_ref() {
+                chunks.push(serde_json::from_str::<Value>(chunk).unwrap());
+            }
+        }
+        assert_eq!(
+            chunks,
+            vec![
+                serde_json::json!({ "t": "usage", "input": 110, "output": 20 }),
+                serde_json::json!({ "t": "text", "delta": "Looking" }),
+                serde_json::json!({ "t": "usage", "input": 170, "output": 25 }),
+            ]
+        );
+    }
+
     #[tokio::test]
     async fn read_stdout_events_reports_turn_failure() {
         let (sink, _rx) = make_sink();
@@ -900,6 +1032,8 @@ mod tests {
                 kind: crate::core::events::ErrorKind::Internal,
                 message: "boom".to_string(),
                 details: None,
+                http_status: None,
+                request_id: None,
             },
             final_text: String::new(),
             messages: Vec::new(),
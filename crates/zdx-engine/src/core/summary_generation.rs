@@ -0,0 +1,95 @@
+//! Short thread summaries for thread picker previews.
+//!
+//! The TUI asks for one after a thread has sat idle (`[tui] idle_summary_secs`)
+//! so threads that were never titled by hand still show what they were about.
+//! Uses the title model: the output is a sentence or two, not a recap.
+
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, ensure};
+
+use crate::config::ThinkingLevel;
+use crate::core::subagent::{ExecSubagentOptions, run_exec_subagent};
+use crate::core::thread_persistence as tp;
+use crate::prompts::THREAD_SUMMARY_PROMPT_TEMPLATE;
+
+/// Transcript characters sent to the model (the most recent ones).
+const MAX_TRANSCRIPT_CHARS: usize = 24_000;
+/// Stored summaries are cut to this many characters.
+const MAX_SUMMARY_CHARS: usize = 300;
+
+/// Generates a summary of `thread_id` and stores it in the thread meta.
+///
+/// Returns the stored summary.
+///
+/// # Errors
+/// Returns an error when the thread cannot be loaded or updated, has no
+/// messages, or the subagent fails / times out / returns nothing usable.
+pub async fn summarize_thread(thread_id: &str, model: &str, root: &Path) -> Result<String> {
+    let events = tp::load_thread_events(thread_id)
+        .with_context(|| format!("load thread '{thread_id}' for summary"))?;
+    let transcript = tp::format_transcript(&events);
+    let transcript = transcript.trim();
+    ensure!(!transcript.is_empty(), "Thread transcript is empty");
+
+    let skip = transcript
+        .chars()
+        .count()
+        .saturating_sub(MAX_TRANSCRIPT_CHARS);
+    let tail: String = transcript.chars().skip(skip).collect();
+    let prompt = THREAD_SUMMARY_PROMPT_TEMPLATE.replace("{{TRANSCRIPT}}", &tail);
+
+    let (model, thinking) = crate::models::split_model_thinking(model);
+    let options = ExecSubagentOptions {
+        model: Some(model.to_string()),
+        system_prompt: None,
+        thinking_level: Some(thinking.unwrap_or(ThinkingLevel::Low)),
+        no_tools: true,
+        no_system_prompt: true,
+        tools_override: None,
+        event_filter: Some(vec!["turn_finished".to_string()]),
+        timeout: Some(Duration::from_mins(1)),
+        activity_kind: Some("helper:summary".to_string()),
+        thread_origin_kind: Some("helper:summary".to_string()),
+        ..Default::default()
+    };
+
+    let raw = run_exec_subagent(root, &prompt, &options).await?;
+    let summary = sanitize_summary(&raw).ok_or_else(|| anyhow!("Empty summary generated"))?;
+    tp::set_thread_summary(thread_id, Some(summary.clone()))?;
+    Ok(summary)
+}
+
+/// Collapses whitespace, drops a `Summary:` label, and caps the length.
+fn sanitize_summary(raw: &str) -> Option<String> {
+    let text = raw.split_whitespace().collect::<Vec<_>>().join(" ");
+    let text = text
+        .strip_prefix("Summary:")
+        .map_or(text.as_str(), str::trim_start);
+    if text.is_empty() {
+        return None;
+    }
+    if text.chars().count() <= MAX_SUMMARY_CHARS {
+        return Some(text.to_string());
+    }
+    let cut: String = text.chars().take(MAX_SUMMARY_CHARS - 1).collect();
+    Some(format!("{}…", cut.trim_end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sanitize_summary() {
+        assert_eq!(
+            sanitize_summary("Summary:  Fixed the\n\n`config.rs` parser. "),
+            Some("Fixed the `config.rs` parser.".to_string())
+        );
+        assert_eq!(sanitize_summary("  \n"), None);
+        let long = sanitize_summary(&"word ".repeat(200)).unwrap();
+        assert_eq!(long.chars().count(), MAX_SUMMARY_CHARS);
+        assert!(long.ends_with('…'));
+    }
+}
@@ -0,0 +1,187 @@
+//! Thinking-level auto-escalation for a single agent run.
+//!
+//! Starts the run at a low reasoning level and steps it up one level at a
+//! time (up to a cap) when the model signals uncertainty in its final answer
+//! or consecutive tool rounds keep failing.
+
+use zdx_types::EscalationTrigger;
+
+use crate::config::{ThinkingEscalationConfig, ThinkingLevel};
+
+/// One level change decided by the policy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Escalation {
+    pub from: ThinkingLevel,
+    pub to: ThinkingLevel,
+    pub trigger: EscalationTrigger,
+    pub message: String,
+}
+
+#[derive(Debug)]
+pub struct ThinkingEscalator {
+    current: ThinkingLevel,
+    max_level: ThinkingLevel,
+    triggers: Vec<EscalationTrigger>,
+    tool_failure_threshold: u32,
+    uncertainty_phrases: Vec<String>,
+    failing_tool_rounds: u32,
+}
+
+impl ThinkingEscalator {
+    /// Returns the escalator for a run, or `None` when the policy is off or
+    /// the model cannot reason.
+    pub fn new(config: &ThinkingEscalationConfig, supports_reasoning: bool) -> Option<Self> {
+        if !config.enabled || !supports_reasoning {
+            return None;
+        }
+        Some(Self {
+            current: config.start_level,
+            max_level: config.max_level,
+            triggers: config.triggers.clone(),
+            tool_failure_threshold: config.tool_failure_threshold,
+            uncertainty_phrases: config
+                .uncertainty_phrases
+                .iter()
+                .map(|phrase| phrase.trim().to_lowercase())
+                .filter(|phrase| !phrase.is_empty())
+                .collect(),
+            failing_tool_rounds: 0,
+        })
+    }
+
+    /// Level the run currently uses.
+    pub fn level(&self) -> ThinkingLevel {
+        self.current
+    }
+
+    /// Records one executed tool round and escalates after
+    /// `tool_failure_threshold` rounds in a row with a failing tool call.
+    pub fn record_tool_round(&mut self, failed_calls: usize) -> Option<Escalation> {
+        if !self.triggers.contains(&EscalationTrigger::ToolFailures)
+            || self.tool_failure_threshold == 0
+        {
+            return None;
+        }
+        if failed_calls == 0 {
+            self.failing_tool_rounds = 0;
+            return None;
+        }
+        self.failing_tool_rounds += 1;
+        if self.failing_tool_rounds < self.tool_failure_threshold {
+            return None;
+        }
+        let rounds = self.failing_tool_rounds;
+        let escalation = self.escalate(
+            EscalationTrigger::ToolFailures,
+            &format!("{rounds} tool rounds in a row had failing tool calls"),
+        )?;
+        self.failing_tool_rounds = 0;
+        Some(escalation)
+    }
+
+    /// Checks a final answer for uncertainty phrases; on escalation the
+    /// caller discards the answer and retries the request.
+    pub fn record_final_answer(&mut self, text: &str) -> Option<Escalation> {
+        if !self.triggers.contains(&EscalationTrigger::Uncertainty) {
+            return None;
+        }
+        let lowered = text.to_lowercase();
+        let phrase = self
+            .uncertainty_phrases
+            .iter()
+            .find(|phrase| lowered.contains(phrase.as_str()))?
+            .clone();
+        self.escalate(
+            EscalationTrigger::Uncertainty,
+            &format!("answer signaled uncertainty (\"{phrase}\"); retrying"),
+        )
+    }
+
+    fn escalate(&mut self, trigger: EscalationTrigger, reason: &str) -> Option<Escalation> {
+        let to = next_level(self.current).filter(|next| rank(*next) <= rank(self.max_level))?;
+        let from = std::mem::replace(&mut self.current, to);
+        Some(Escalation {
+            from,
+            to,
+            trigger,
+            message: format!(
+                "Thinking escalated {} → {}: {reason}.",
+                from.display_name(),
+                to.display_name()
+            ),
+        })
+    }
+}
+
+fn rank(level: ThinkingLevel) -> usize {
+    ThinkingLevel::all()
+        .iter()
+        .position(|candidate| *candidate == level)
+        .unwrap_or_default()
+}
+
+fn next_level(level: ThinkingLevel) -> Option<ThinkingLevel> {
+    ThinkingLevel::all().get(rank(level) + 1).copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn escalator(start: ThinkingLevel, max: ThinkingLevel) -> ThinkingEscalator {
+        ThinkingEscalator::new(
+            &ThinkingEscalationConfig {
+                enabled: true,
+                start_level: start,
+                max_level: max,
+                ..Default::default()
+            },
+            true,
+        )
+        .expect("enabled")
+    }
+
+    #[test]
+    fn disabled_or_non_reasoning_has_no_escalator() {
+        assert!(ThinkingEscalator::new(&ThinkingEscalationConfig::default(), true).is_none());
+        let enabled = ThinkingEscalationConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(ThinkingEscalator::new(&enabled, false).is_none());
+    }
+
+    #[test]
+    fn uncertainty_steps_up_until_cap() {
+        let mut escalator = escalator(ThinkingLevel::Low, ThinkingLevel::Medium);
+
+        assert!(escalator.record_final_answer("Done, tests pass.").is_none());
+        let escalation = escalator
+            .record_final_answer("I'm not sure this covers every case.")
+            .expect("escalates");
+        assert_eq!(escalation.from, ThinkingLevel::Low);
+        assert_eq!(escalation.to, ThinkingLevel::Medium);
+        assert_eq!(escalation.trigger, EscalationTrigger::Uncertainty);
+        assert!(escalation.message.contains("low → medium"));
+
+        assert!(escalator.record_final_answer("I don't know.").is_none());
+        assert_eq!(escalator.level(), ThinkingLevel::Medium);
+    }
+
+    #[test]
+    fn consecutive_failing_tool_rounds_escalate() {
+        let mut escalator = escalator(ThinkingLevel::Low, ThinkingLevel::High);
+
+        assert!(escalator.record_tool_round(1).is_none());
+        assert!(escalator.record_tool_round(0).is_none());
+        assert!(escalator.record_tool_round(2).is_none());
+        let escalation = escalator
+            .record_tool_round(1)
+            .expect("second failing round");
+        assert_eq!(escalation.trigger, EscalationTrigger::ToolFailures);
+        assert_eq!(escalator.level(), ThinkingLevel::Medium);
+
+        // The streak restarts after an escalation.
+        assert!(escalator.record_tool_round(1).is_none());
+    }
+}
@@ -12,6 +12,7 @@ use std::time::SystemTime;
 use anyhow::{Context, Result};
 
 use crate::config::paths::thread_exports_dir;
+use crate::core::regeneration;
 use crate::core::thread_persistence::{self, ThreadEvent};
 
 /// Options for batch thread transcript export.
@@ -161,13 +162,29 @@ pub fn export_thread(thread_id: &str) -> Result<PathBuf> {
 #[must_use]
 pub fn format_transcript_markdown(thread_id: &str, events: &[ThreadEvent]) -> String {
     let mut output = format!("# Thread {thread_id}\n\n");
+    // Set by a `/redo` until the regenerated answer is labelled.
+    let mut regenerated = false;
 
     for event in events {
-        let ThreadEvent::Message { role, text, .. } = event else {
+        let ThreadEvent::Message {
+            role,
+            text,
+            phase,
+            model,
+            ..
+        } = event
+        else {
             continue;
         };
 
+        let is_redo = role == "user" && regeneration::is_regeneration_phase(phase.as_deref());
+        let text = if is_redo {
+            regeneration::regeneration_feedback(text)
+        } else {
+            text
+        };
         let label = match role.as_str() {
+            "user" if is_redo => "Redo",
             "user" => "User",
             "assistant" => "Assistant",
             _ => continue,
@@ -179,6 +196,16 @@ pub fn format_transcript_markdown(thread_id: &str, events: &[ThreadEvent]) -> St
         }
 
         output.push_str(label);
+        if let Some(model) = model {
+            output.push_str(" (");
+            output.push_str(model);
+            output.push(')');
+        }
+        if is_redo {
+            regenerated = true;
+        } else if role == "assistant" && std::mem::take(&mut regenerated) {
+            output.push_str(" [regenerated]");
+        }
         output.push_str(": ");
         output.push_str(&text);
         output.push('\n');
@@ -250,6 +277,11 @@ mod tests {
 
     use super::*;
 
+    fn redo_event(feedback: &str) -> ThreadEvent {
+        thread_persistence::messages_to_events(&[regeneration::regeneration_message(feedback)])
+            .remove(0)
+    }
+
     #[test]
     fn formats_user_and_assistant_messages_only() {
         let events = vec![
@@ -290,4 +322,34 @@ mod tests {
             "# Thread thread-1\n\nAssistant: done\n"
         );
     }
+
+    #[test]
+    fn marks_redo_feedback_and_the_regenerated_answer() {
+        let events = vec![
+            ThreadEvent::user_message("explain"),
+            ThreadEvent::assistant_message("a long answer"),
+            redo_event("shorter"),
+            ThreadEvent::assistant_message("short"),
+            ThreadEvent::assistant_message("more"),
+        ];
+
+        assert_eq!(
+            format_transcript_markdown("thread-1", &events),
+            "# Thread thread-1\n\nUser: explain\nAssistant: a long answer\nRedo: shorter\n\
+             Assistant [regenerated]: short\nAssistant: more\n"
+        );
+    }
+
+    #[test]
+    fn labels_assistant_messages_with_their_model() {
+        let mut reply = ThreadEvent::assistant_message("done");
+        if let ThreadEvent::Message { model, .. } = &mut reply {
+            *model = Some("claude-sonnet-4".to_string());
+        }
+
+        assert_eq!(
+            format_transcript_markdown("thread-1", &[reply]),
+            "# Thread thread-1\n\nAssistant (claude-sonnet-4): done\n"
+        );
+    }
 }
@@ -0,0 +1,295 @@
+//! Sidecar storage for large thread payloads.
+//!
+//! Base64 images and long tool outputs make thread JSONL files huge and slow
+//! to load. Any string of at least [`BLOB_MIN_BYTES`] inside an event is
+//! written once to `threads/blobs/<sha256>` and replaced in the log by a
+//! `zdx-blob:sha256:<hex>` marker. Loading a thread swaps markers back for the
+//! stored content, so callers always see the original event.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::storage::list_thread_files;
+use crate::config::paths::threads_dir;
+
+/// Strings at least this long are externalized into sidecar blobs.
+pub(crate) const BLOB_MIN_BYTES: usize = 32 * 1024;
+const BLOB_MARKER_PREFIX: &str = "zdx-blob:sha256:";
+const SHA256_HEX_LEN: usize = 64;
+
+/// Directory holding externalized payloads, shared by all threads.
+pub(crate) fn blobs_dir() -> PathBuf {
+    threads_dir().join("blobs")
+}
+
+/// Serializes an event line, externalizing large strings into `blobs`.
+/// Lines under [`BLOB_MIN_BYTES`] are returned as serialized.
+pub(crate) fn externalize_line(json: String, blobs: &Path) -> Result<String> {
+    if json.len() < BLOB_MIN_BYTES {
+        return Ok(json);
+    }
+    let mut value: Value = serde_json::from_str(&json).context("Failed to parse event")?;
+    if value.get("type").and_then(Value::as_str) == Some("meta") {
+        return Ok(json);
+    }
+    if externalize_value(&mut value, blobs)? == 0 {
+        return Ok(json);
+    }
+    serde_json::to_string(&value).context("Failed to serialize event")
+}
+
+/// True when a stored line references externalized payloads.
+pub(crate) fn has_blob_markers(line: &str) -> bool {
+    line.contains(BLOB_MARKER_PREFIX)
+}
+
+/// Replaces blob markers in `value` with the stored payloads. Markers whose
+/// blob is missing are left as-is.
+pub(crate) fn rehydrate_value(value: &mut Value, blobs: &Path) {
+    visit_strings(value, &mut |text| {
+        let Some(hash) = marker_hash(text) else {
+            return;
+        };
+        match fs::read_to_string(blobs.join(hash)) {
+            Ok(content) => *text = content,
+            Err(err) => tracing::warn!(hash, %err, "Missing thread payload blob"),
+        }
+    });
+}
+
+fn externalize_value(value: &mut Value, blobs: &Path) -> Result<usize> {
+    let mut externalized = 0;
+    let mut result = Ok(());
+    visit_strings(value, &mut |text| {
+        if result.is_err() || text.len() < BLOB_MIN_BYTES {
+            return;
+        }
+        match write_blob(text, blobs) {
+            Ok(hash) => {
+                *text = format!("{BLOB_MARKER_PREFIX}{hash}");
+                externalized += 1;
+            }
+            Err(err) => result = Err(err),
+        }
+    });
+    result.map(|()| externalized)
+}
+
+/// Writes `content` to `blobs/<sha256>` (skipped when already stored) and
+/// returns the hash.
+fn write_blob(content: &str, blobs: &Path) -> Result<String> {
+    let hash = hex_sha256(content.as_bytes());
+    let path = blobs.join(&hash);
+    if path.exists() {
+        return Ok(hash);
+    }
+    fs::create_dir_all(blobs).context("Failed to create blobs directory")?;
+    let temp_path = path.with_extension("tmp");
+    fs::write(&temp_path, content).context("Failed to write blob")?;
+    fs::rename(&temp_path, &path).context("Failed to store blob")?;
+    Ok(hash)
+}
+
+fn hex_sha256(bytes: &[u8]) -> String {
+    use std::fmt::Write as _;
+    Sha256::digest(bytes)
+        .iter()
+        .fold(String::with_capacity(SHA256_HEX_LEN), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+}
+
+fn marker_hash(text: &str) -> Option<&str> {
+    text.strip_prefix(BLOB_MARKER_PREFIX).filter(|hash| {
+        hash.len() == SHA256_HEX_LEN && hash.bytes().all(|byte| byte.is_ascii_hexdigit())
+    })
+}
+
+fn visit_strings(value: &mut Value, visit: &mut impl FnMut(&mut String)) {
+    match value {
+        Value::String(text) => visit(text),
+        Value::Array(items) => {
+            for item in items {
+                visit_strings(item, visit);
+            }
+        }
+        Value::Object(map) => {
+            for item in map.values_mut() {
+                visit_strings(item, visit);
+            }
+        }
+        Value::Null | Value::Bool(_) | Value::Number(_) => {}
+    }
+}
+
+/// Outcome of [`compact_thread_storage`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CompactStorageReport {
+    /// Thread files scanned (including archived ones).
+    pub threads_scanned: usize,
+    /// Thread files rewritten with externalized payloads.
+    pub threads_rewritten: usize,
+    /// Payloads moved into sidecar blobs.
+    pub payloads_externalized: usize,
+    /// Total thread file size before compaction.
+    pub bytes_before: u64,
+    /// Total thread file size after compaction.
+    pub bytes_after: u64,
+    /// Blobs no longer referenced by any thread that were removed.
+    pub orphaned_blobs_removed: usize,
+}
+
+/// Migrates existing thread logs (including `threads/archive/`) to sidecar
+/// blobs and removes blobs no thread references anymore (e.g. after
+/// `delete_thread`). Run it while no session is writing to the threads.
+///
+/// # Errors
+/// Returns an error if the threads directory can't be read or a thread file
+/// can't be rewritten.
+pub fn compact_thread_storage() -> Result<CompactStorageReport> {
+    compact_thread_storage_in(&threads_dir())
+}
+
+fn compact_thread_storage_in(dir: &Path) -> Result<CompactStorageReport> {
+    let blobs = dir.join("blobs");
+    let mut files = list_thread_files(dir)?;
+    files.extend(list_thread_files(&dir.join("archive"))?);
+
+    let mut report = CompactStorageReport {
+        threads_scanned: files.len(),
+        ..CompactStorageReport::default()
+    };
+    let mut referenced = HashSet::new();
+    for file in &files {
+        report.bytes_before += file.size;
+        let externalized = compact_thread_file(&file.path, &blobs, &mut referenced)
+            .with_context(|| format!("compact {}", file.path.display()))?;
+        if externalized > 0 {
+            report.threads_rewritten += 1;
+            report.payloads_externalized += externalized;
+        }
+        report.bytes_after += fs::metadata(&file.path).map_or(0, |meta| meta.len());
+    }
+    report.orphaned_blobs_removed = remove_orphaned_blobs(&blobs, &referenced);
+    Ok(report)
+}
+
+/// Rewrites one thread file with large payloads externalized, recording every
+/// blob hash it references. Returns how many payloads were moved.
+fn compact_thread_file(
+    path: &Path,
+    blobs: &Path,
+    referenced: &mut HashSet<String>,
+) -> Result<usize> {
+    let reader = BufReader::new(fs::File::open(path).context("Failed to open thread file")?);
+    let mut lines = Vec::new();
+    let mut externalized = 0;
+    for line in reader.lines() {
+        let line = line.context("Failed to read thread line")?;
+        let compacted = if line.len() >= BLOB_MIN_BYTES
+            && let Ok(mut value) = serde_json::from_str::<Value>(&line)
+            && value.get("type").and_then(Value::as_str) != Some("meta")
+        {
+            let count = externalize_value(&mut value, blobs)?;
+            externalized += count;
+            if count > 0 {
+                serde_json::to_string(&value).context("Failed to serialize event")?
+            } else {
+                line
+            }
+        } else {
+            line
+        };
+        collect_marker_hashes(&compacted, referenced);
+        lines.push(compacted);
+    }
+    if externalized == 0 {
+        return Ok(0);
+    }
+
+    let temp_path = path.with_extension("jsonl.tmp");
+    let mut temp = fs::File::create(&temp_path).context("Failed to create temp thread file")?;
+    for line in &lines {
+        writeln!(temp, "{line}").context("Failed to write thread line")?;
+    }
+    temp.sync_all().context("Failed to sync temp thread file")?;
+    fs::rename(&temp_path, path).context("Failed to replace thread file")?;
+    Ok(externalized)
+}
+
+fn collect_marker_hashes(line: &str, referenced: &mut HashSet<String>) {
+    for (start, _) in line.match_indices(BLOB_MARKER_PREFIX) {
+        let rest = &line[start..];
+        let end = rest.find('"').unwrap_or(rest.len());
+        if let Some(hash) = marker_hash(&rest[..end]) {
+            referenced.insert(hash.to_string());
+        }
+    }
+}
+
+fn remove_orphaned_blobs(blobs: &Path, referenced: &HashSet<String>) -> usize {
+    let Ok(entries) = fs::read_dir(blobs) else {
+        return 0;
+    };
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let orphaned = name.to_str().is_some_and(|name| !referenced.contains(name));
+        if orphaned && fs::remove_file(entry.path()).is_ok() {
+            removed += 1;
+        }
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact_externalizes_large_payloads_and_prunes_orphans() {
+        let dir = tempfile::tempdir().unwrap();
+        let blobs = dir.path().join("blobs");
+        let payload = "A".repeat(BLOB_MIN_BYTES);
+        let small = r#"{"type":"message","role":"user","text":"hi","ts":"t"}"#;
+        let big = serde_json::json!({
+            "type": "tool_result",
+            "tool_use_id": "t1",
+            "output": {"data": {"image": payload}},
+            "ok": true,
+            "ts": "t",
+        })
+        .to_string();
+        let thread = dir.path().join("t.jsonl");
+        fs::write(&thread, format!("{small}\n{big}\n")).unwrap();
+        fs::create_dir_all(&blobs).unwrap();
+        fs::write(blobs.join("stale"), "x").unwrap();
+
+        let report = compact_thread_storage_in(dir.path()).unwrap();
+        assert_eq!(report.threads_scanned, 1);
+        assert_eq!(report.threads_rewritten, 1);
+        assert_eq!(report.payloads_externalized, 1);
+        assert_eq!(report.orphaned_blobs_removed, 1);
+        assert!(report.bytes_after < report.bytes_before);
+
+        let content = fs::read_to_string(&thread).unwrap();
+        let mut lines = content.lines();
+        assert_eq!(lines.next(), Some(small));
+        let stored = lines.next().unwrap();
+        assert!(has_blob_markers(stored));
+        let mut value: Value = serde_json::from_str(stored).unwrap();
+        rehydrate_value(&mut value, &blobs);
+        assert_eq!(value["output"]["data"]["image"], payload.as_str());
+
+        let again = compact_thread_storage_in(dir.path()).unwrap();
+        assert_eq!(again.threads_rewritten, 0);
+        assert_eq!(again.orphaned_blobs_removed, 0);
+    }
+}
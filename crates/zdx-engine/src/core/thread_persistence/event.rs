@@ -81,9 +81,20 @@ impl std::ops::AddAssign for Usage {
 /// Current schema version for new threads.
 pub const SCHEMA_VERSION: u32 = 1;
 
+/// A transcript message pinned to a thread's context.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PinnedMessage {
+    /// `user` or `assistant`.
+    pub role: String,
+    pub text: String,
+}
+
 /// A thread event (polymorphic, tag-based).
 ///
 /// This enum represents all event types that can be persisted in a thread.
+// `Meta` is much larger than the other variants, but there is one per thread
+// file; boxing it would only complicate every pattern match on it.
+#[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ThreadEvent {
@@ -122,6 +133,28 @@ pub enum ThreadEvent {
         /// thread stays a thin pointer (used by Telegram "resume" topics).
         #[serde(default, skip_serializing_if = "Option::is_none")]
         alias_to: Option<String>,
+        /// User-assigned labels (e.g. from the TUI thread picker).
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        tags: Vec<String>,
+        /// Name of the `[[prompt_variants]]` entry used for the latest turn.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        prompt_variant: Option<String>,
+        /// 1-based turn numbers (`/pin`) that conversation truncation never drops.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pinned_turns: Vec<usize>,
+        /// Messages pinned from the transcript, sent with every turn.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pinned_messages: Vec<PinnedMessage>,
+        /// Files (`/pin-file`, relative to the thread root) sent with every turn.
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        pinned_files: Vec<String>,
+        /// Response style preset (`/style`) applied to every turn.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        style: Option<String>,
+        /// Short summary generated while the TUI sat idle (shown in the
+        /// thread picker preview).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        summary: Option<String>,
         ts: String,
     },
 
@@ -137,6 +170,10 @@ pub enum ThreadEvent {
         /// older transcripts.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         replay: Option<crate::providers::ReplayToken>,
+        /// Model that produced this assistant message, taken from the run's
+        /// usage attribution. `None` for user messages and older transcripts.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model: Option<String>,
         ts: String,
     },
 
@@ -207,6 +244,10 @@ pub enum ThreadEvent {
         /// usage event when content arrived. `None` on older transcripts.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         ttft_ms: Option<u64>,
+        /// `[model_params]` sent with the request. `None` when everything was
+        /// left at provider defaults, and on older transcripts.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model_params: Option<zdx_types::ModelParams>,
         ts: String,
     },
 
@@ -238,6 +279,13 @@ impl ThreadEvent {
             thinking_override: None,
             pending_topic_title: false,
             alias_to: None,
+            tags: Vec::new(),
+            prompt_variant: None,
+            pinned_turns: Vec::new(),
+            pinned_messages: Vec::new(),
+            pinned_files: Vec::new(),
+            style: None,
+            summary: None,
             ts: chrono_timestamp(),
         }
     }
@@ -259,6 +307,13 @@ impl ThreadEvent {
             thinking_override: None,
             pending_topic_title: false,
             alias_to: None,
+            tags: Vec::new(),
+            prompt_variant: None,
+            pinned_turns: Vec::new(),
+            pinned_messages: Vec::new(),
+            pinned_files: Vec::new(),
+            style: None,
+            summary: None,
             ts: chrono_timestamp(),
         }
     }
@@ -283,17 +338,30 @@ impl ThreadEvent {
             thinking_override: None,
             pending_topic_title: false,
             alias_to: None,
+            tags: Vec::new(),
+            prompt_variant: None,
+            pinned_turns: Vec::new(),
+            pinned_messages: Vec::new(),
+            pinned_files: Vec::new(),
+            style: None,
+            summary: None,
             ts: chrono_timestamp(),
         }
     }
 
     /// Creates a new user message event.
     pub fn user_message(text: impl Into<String>) -> Self {
+        Self::user_message_with_phase(text, None)
+    }
+
+    /// Creates a new user message event with optional phase.
+    pub fn user_message_with_phase(text: impl Into<String>, phase: Option<String>) -> Self {
         Self::Message {
             role: "user".to_string(),
             text: text.into(),
-            phase: None,
+            phase,
             replay: None,
+            model: None,
             ts: chrono_timestamp(),
         }
     }
@@ -310,6 +378,7 @@ impl ThreadEvent {
             text: text.into(),
             phase,
             replay: None,
+            model: None,
             ts: chrono_timestamp(),
         }
     }
@@ -362,6 +431,7 @@ impl ThreadEvent {
         provider: Option<String>,
         duration_ms: Option<u64>,
         ttft_ms: Option<u64>,
+        model_params: Option<zdx_types::ModelParams>,
     ) -> Self {
         Self::Usage {
             input_tokens: usage.input,
@@ -372,6 +442,7 @@ impl ThreadEvent {
             provider,
             duration_ms,
             ttft_ms,
+            model_params,
             ts: chrono_timestamp(),
         }
     }
@@ -411,6 +482,13 @@ impl ThreadEvent {
                 message: message.clone(),
                 ts: chrono_timestamp(),
             }),
+            // Escalations are recorded like notices so reloads show where
+            // the thinking level changed.
+            AgentEvent::ThinkingEscalated { message, .. } => Some(Self::Notice {
+                kind: crate::core::events::NoticeKind::ThinkingEscalated,
+                message: message.clone(),
+                ts: chrono_timestamp(),
+            }),
             // Streaming events are consumed by the TUI directly; persistence
             // batches them via `flush_messages` on `TurnCheckpoint` /
             // `TurnFinished`.
@@ -5,6 +5,7 @@ use chrono::{DateTime, Utc};
 
 use super::event::ThreadEvent;
 use super::storage::truncate_str;
+use crate::core::regeneration;
 
 /// Returns a shortened thread ID for display.
 pub fn short_thread_id(id: &str) -> String {
@@ -65,9 +66,12 @@ pub fn format_timestamp_relative(time: SystemTime) -> Option<String> {
 }
 
 /// Formats a thread transcript in a human-readable format.
+#[allow(clippy::too_many_lines)]
 pub fn format_transcript(events: &[ThreadEvent]) -> String {
     let mut output = String::new();
     let mut models_used: Vec<String> = Vec::new();
+    // Set by a `/redo` until the regenerated answer is labelled.
+    let mut regenerated = false;
 
     for event in events {
         match event {
@@ -75,13 +79,40 @@ pub fn format_transcript(events: &[ThreadEvent]) -> String {
                 writeln!(output, "### Thread (schema v{schema_version})").expect("write");
                 output.push('\n');
             }
-            ThreadEvent::Message { role, text, .. } => {
+            ThreadEvent::Message {
+                role,
+                text,
+                phase,
+                model,
+                ..
+            } => {
+                if role == "user" && regeneration::is_regeneration_phase(phase.as_deref()) {
+                    regenerated = true;
+                    writeln!(output, "### Redo").expect("write");
+                    writeln!(
+                        output,
+                        "Feedback: {}\n",
+                        regeneration::regeneration_feedback(text)
+                    )
+                    .expect("write");
+                    continue;
+                }
                 let role_label = match role.as_str() {
                     "user" => "You",
                     "assistant" => "Assistant",
                     _ => role,
                 };
-                writeln!(output, "### {role_label}").expect("write");
+                let model_label = model
+                    .as_deref()
+                    .map(|model| format!(" ({model})"))
+                    .unwrap_or_default();
+                let regenerated_label = if role == "assistant" && std::mem::take(&mut regenerated) {
+                    " [regenerated]"
+                } else {
+                    ""
+                };
+                writeln!(output, "### {role_label}{model_label}{regenerated_label}")
+                    .expect("write");
                 output.push_str(text);
                 output.push_str("\n\n");
             }
@@ -13,7 +13,12 @@
 //! { "type": "reasoning", "text": "...", "replay": { "provider": "openai", "id": "...", "encrypted_content": "..." }, "ts": "..." }
 //! { "type": "message", "role": "assistant", "text": "...", "ts": "..." }
 //! ```
+//!
+//! Strings of 32 KiB or more (base64 images, long tool output) are stored in
+//! `threads/blobs/<sha256>` and referenced from the event by a
+//! `zdx-blob:sha256:<hex>` marker; loading rehydrates them transparently.
 
+mod blobs;
 mod event;
 mod format;
 mod persist;
@@ -21,6 +26,7 @@ mod replay;
 mod search;
 mod storage;
 
+pub use blobs::{CompactStorageReport, compact_thread_storage};
 pub use event::*;
 pub use format::*;
 pub use persist::*;
@@ -3,11 +3,12 @@ use tokio::task::JoinHandle;
 use super::event::{ThreadEvent, Usage};
 use super::replay::emit_message_events;
 use super::storage::Thread;
-use crate::core::agent::AgentEventRx;
+use crate::core::event_bus::EventSubscriber;
 
-/// Spawns a thread persistence task that consumes events from a channel.
+/// Spawns a thread persistence task that consumes events from a bus subscriber.
 ///
-/// The task owns the `Thread` and persists relevant events until the channel closes.
+/// The task owns the `Thread` and persists relevant events until the stream closes.
+/// Subscribe with `EventFilter::NoDeltas`; streaming previews are never persisted.
 /// Returns a `JoinHandle` that resolves when all events have been persisted.
 ///
 /// Only tool-related and interrupt events are persisted via this task.
@@ -17,15 +18,17 @@ use crate::core::agent::AgentEventRx;
 ///
 /// ```ignore
 /// let thread = Thread::new_with_root(Path::new("."))?;
-/// let (tx, rx) = agent::create_event_channel();
-/// let persist_handle = spawn_thread_persist_task(thread, rx);
+/// let mut bus = EventBus::new();
+/// let persist_handle =
+///     spawn_thread_persist_task(thread, bus.subscribe("persist", EventFilter::NoDeltas));
+/// let tx = bus.into_sender();
 ///
 /// // ... send events to tx ...
-/// drop(tx); // Close channel
+/// drop(tx); // Close the stream
 ///
 /// persist_handle.await.unwrap(); // Wait for persistence to finish
 /// ```
-pub fn spawn_thread_persist_task(mut thread: Thread, mut rx: AgentEventRx) -> JoinHandle<()> {
+pub fn spawn_thread_persist_task(mut thread: Thread, mut rx: EventSubscriber) -> JoinHandle<()> {
     tokio::spawn(async move {
         let mut usage_persistor = UsagePersistor::new();
         while let Some(event) = rx.recv().await {
@@ -48,12 +51,13 @@ pub fn spawn_thread_persist_task(mut thread: Thread, mut rx: AgentEventRx) -> Jo
 #[derive(Debug, Default)]
 pub(crate) struct UsagePersistor {
     pending: Option<Usage>,
-    /// Model/provider from the most recent `UsageUpdate`, attached to every
+    /// Model/provider/params from the most recent `UsageUpdate`, attached to every
     /// emitted usage event (including the trailing `finish()` flush and
     /// output-only usage) so attribution survives across flush boundaries.
     /// `None` until the first attributed usage arrives.
     current_model: Option<String>,
     current_provider: Option<String>,
+    current_model_params: Option<zdx_types::ModelParams>,
     /// Index into `messages` already flushed to disk for this run.
     /// Combined with the run-entry `prior_message_count` cursor (carried on
     /// every `TurnCheckpoint`/`TurnFinished`), this makes flushes idempotent
@@ -66,7 +70,10 @@ impl UsagePersistor {
         Self::default()
     }
 
-    fn handle_event(&mut self, event: &crate::core::events::AgentEvent) -> Vec<ThreadEvent> {
+    pub(crate) fn handle_event(
+        &mut self,
+        event: &crate::core::events::AgentEvent,
+    ) -> Vec<ThreadEvent> {
         use crate::core::events::AgentEvent;
 
         let mut events = Vec::new();
@@ -81,9 +88,11 @@ impl UsagePersistor {
                 provider,
                 duration_ms,
                 ttft_ms,
+                model_params,
             } => {
                 self.current_model = (!model.is_empty()).then(|| model.clone());
                 self.current_provider = (!provider.is_empty()).then(|| provider.clone());
+                self.current_model_params = *model_params;
 
                 if *input_tokens > 0
                     || *cache_read_input_tokens > 0
@@ -157,7 +166,7 @@ impl UsagePersistor {
         }
 
         for msg in &full[start..] {
-            emit_message_events(msg, events);
+            emit_message_events(msg, self.current_model.as_deref(), events);
         }
 
         self.last_persisted_index = full.len();
@@ -185,10 +194,11 @@ impl UsagePersistor {
             self.current_provider.clone(),
             duration_ms,
             ttft_ms,
+            self.current_model_params,
         )
     }
 
-    fn finish(&mut self) -> Vec<ThreadEvent> {
+    pub(crate) fn finish(&mut self) -> Vec<ThreadEvent> {
         let mut events = Vec::new();
         self.flush_pending(&mut events);
         events
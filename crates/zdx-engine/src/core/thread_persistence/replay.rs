@@ -19,7 +19,7 @@ pub fn load_thread_as_messages(id: &str) -> Result<Vec<crate::providers::ChatMes
 pub fn messages_to_events(messages: &[crate::providers::ChatMessage]) -> Vec<ThreadEvent> {
     let mut events = Vec::new();
     for message in messages {
-        emit_message_events(message, &mut events);
+        emit_message_events(message, None, &mut events);
     }
     events
 }
@@ -31,13 +31,19 @@ pub fn messages_to_events(messages: &[crate::providers::ChatMessage]) -> Vec<Thr
 /// public `messages_to_events` converter so any caller round-tripping
 /// `ChatMessage`s produces the same on-disk shape. Per-part Gemini
 /// `thoughtSignature`s and tool-use `id_origin` data must survive both
-/// paths or implicit-cache replay breaks.
+/// paths or implicit-cache replay breaks. `model` is recorded on assistant
+/// text events for per-turn attribution.
 pub(crate) fn emit_message_events(
     msg: &crate::providers::ChatMessage,
+    model: Option<&str>,
     events: &mut Vec<ThreadEvent>,
 ) {
     use crate::providers::{ChatContentBlock, MessageContent};
 
+    let model = model
+        .filter(|_| msg.role == "assistant")
+        .map(str::to_string);
+
     match &msg.content {
         MessageContent::Text(text) => {
             events.push(ThreadEvent::Message {
@@ -45,6 +51,7 @@ pub(crate) fn emit_message_events(
                 text: text.clone(),
                 phase: msg.phase.clone(),
                 replay: None,
+                model: model.clone(),
                 ts: chrono_timestamp(),
             });
         }
@@ -57,6 +64,7 @@ pub(crate) fn emit_message_events(
                             text: text.clone(),
                             phase: msg.phase.clone(),
                             replay: replay.clone(),
+                            model: model.clone(),
                             ts: chrono_timestamp(),
                         });
                     }
@@ -219,6 +227,10 @@ impl MessageReplay {
         // cancel orphaned tool_uses, then push the user/system message.
         self.flush_pending_assistant_blocks();
         self.cancel_open_tool_uses();
+        // A `/redo` supersedes the attempt it follows; only the prompt stays.
+        if crate::core::regeneration::is_regeneration_phase(phase.as_deref()) {
+            crate::core::regeneration::drop_latest_attempt(&mut self.messages);
+        }
         self.messages.push(ChatMessage {
             role,
             phase,
@@ -5,8 +5,9 @@ use std::time::SystemTime;
 
 use anyhow::{Context, Result, anyhow, bail};
 
-use super::event::{ThreadEvent, normalize_title};
-use super::format::display_title_or_short_id;
+use super::blobs;
+use super::event::{PinnedMessage, ThreadEvent, normalize_title};
+use super::format::{display_title_or_short_id, short_thread_id};
 use crate::config::paths::threads_dir;
 
 /// Truncates a string to at most `max_bytes`, ensuring we don't split a UTF-8 character.
@@ -197,6 +198,7 @@ impl Thread {
             .context("Failed to open thread file")?;
 
         let json = serde_json::to_string(event).context("Failed to serialize event")?;
+        let json = blobs::externalize_line(json, &blobs::blobs_dir())?;
         writeln!(file, "{json}").context("Failed to write to thread file")?;
 
         Ok(())
@@ -284,6 +286,103 @@ impl Thread {
         Ok(())
     }
 
+    /// Records the prompt variant used for the current turn in the meta event.
+    /// Skips the rewrite when the stored variant is already `prompt_variant`.
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub fn set_prompt_variant(&mut self, prompt_variant: Option<String>) -> Result<()> {
+        self.ensure_meta()?;
+        if read_meta(&self.path)?.and_then(|meta| meta.prompt_variant) == prompt_variant {
+            return Ok(());
+        }
+        rewrite_meta_with_prompt_variant(&self.path, prompt_variant)
+    }
+
+    /// Pins or unpins a turn (1-based) so conversation truncation keeps it.
+    /// Returns the pinned turns after the change, sorted.
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub fn set_turn_pinned(&mut self, turn: usize, pinned: bool) -> Result<Vec<usize>> {
+        self.ensure_meta()?;
+        let mut turns = read_meta(&self.path)?
+            .map(|meta| meta.pinned_turns)
+            .unwrap_or_default();
+        if turns.contains(&turn) == pinned {
+            return Ok(turns);
+        }
+        if pinned {
+            turns.push(turn);
+            turns.sort_unstable();
+        } else {
+            turns.retain(|&t| t != turn);
+        }
+        rewrite_meta_with_pinned_turns(&self.path, turns.clone())?;
+        Ok(turns)
+    }
+
+    /// Pins or unpins a transcript message so it is sent with every turn.
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub fn set_message_pinned(&mut self, message: PinnedMessage, pinned: bool) -> Result<()> {
+        self.ensure_meta()?;
+        let mut pins = read_meta(&self.path)?
+            .map(|meta| meta.pins)
+            .unwrap_or_default();
+        if pins.messages.contains(&message) == pinned {
+            return Ok(());
+        }
+        if pinned {
+            pins.messages.push(message);
+        } else {
+            pins.messages.retain(|m| *m != message);
+        }
+        rewrite_meta_with_pins(&self.path, pins.messages, pins.files)
+    }
+
+    /// Pins or unpins a file (path relative to the thread root) so its
+    /// current contents are sent with every turn. Returns the pinned files
+    /// after the change.
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub fn set_file_pinned(&mut self, path: String, pinned: bool) -> Result<Vec<String>> {
+        self.ensure_meta()?;
+        let mut pins = read_meta(&self.path)?
+            .map(|meta| meta.pins)
+            .unwrap_or_default();
+        if pins.files.contains(&path) == pinned {
+            return Ok(pins.files);
+        }
+        if pinned {
+            pins.files.push(path);
+        } else {
+            pins.files.retain(|p| *p != path);
+        }
+        rewrite_meta_with_pins(&self.path, pins.messages, pins.files.clone())?;
+        Ok(pins.files)
+    }
+
+    /// Updates the response style preset (`/style`) stored in the meta event.
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub fn set_style(&mut self, style: Option<String>) -> Result<()> {
+        self.ensure_meta()?;
+        rewrite_meta_with_style(&self.path, style)
+    }
+
+    /// Updates the conversation summary stored in the meta event.
+    ///
+    /// # Errors
+    /// Returns an error if the operation fails.
+    pub fn set_summary(&mut self, summary: Option<String>) -> Result<()> {
+        self.ensure_meta()?;
+        rewrite_meta_with_summary(&self.path, summary)
+    }
+
     /// Updates the alias (source thread redirect) stored in the meta event.
     ///
     /// # Errors
@@ -311,7 +410,7 @@ fn read_thread_events(path: &PathBuf) -> Result<Vec<ThreadEvent>> {
             continue;
         }
 
-        if let Ok(event) = serde_json::from_str::<ThreadEvent>(&line) {
+        if let Some(event) = parse_event_line(&line) {
             events.push(event);
         }
         // Skip unparseable lines (best-effort)
@@ -320,6 +419,16 @@ fn read_thread_events(path: &PathBuf) -> Result<Vec<ThreadEvent>> {
     Ok(events)
 }
 
+/// Parses one stored event, restoring externalized payloads.
+fn parse_event_line(line: &str) -> Option<ThreadEvent> {
+    if !blobs::has_blob_markers(line) {
+        return serde_json::from_str(line).ok();
+    }
+    let mut value = serde_json::from_str(line).ok()?;
+    blobs::rehydrate_value(&mut value, &blobs::blobs_dir());
+    serde_json::from_value(value).ok()
+}
+
 /// Rewrites the meta event with an updated title, preserving the rest of the file.
 fn rewrite_meta_with_title(path: &PathBuf, title: Option<String>) -> Result<()> {
     let file = fs::File::open(path).context("Failed to open thread file")?;
@@ -569,6 +678,258 @@ fn rewrite_meta_with_alias(path: &PathBuf, alias_to: Option<String>) -> Result<(
     Ok(())
 }
 
+/// Rewrites the meta event with an updated prompt variant, preserving the rest of the file.
+fn rewrite_meta_with_prompt_variant(path: &PathBuf, prompt_variant: Option<String>) -> Result<()> {
+    let file = fs::File::open(path).context("Failed to open thread file")?;
+    let reader = BufReader::new(file);
+
+    let temp_path = path.with_extension("jsonl.tmp");
+    let mut temp = fs::File::create(&temp_path).context("Failed to create temp thread file")?;
+
+    let mut lines = reader.lines();
+    let first_line = lines
+        .next()
+        .transpose()
+        .context("Failed to read meta line")?
+        .ok_or_else(|| anyhow!("Thread file is empty"))?;
+
+    let mut meta_event: ThreadEvent =
+        serde_json::from_str(&first_line).context("Failed to parse meta event")?;
+    match meta_event {
+        ThreadEvent::Meta {
+            prompt_variant: ref mut meta_variant,
+            ..
+        } => {
+            *meta_variant = prompt_variant;
+        }
+        _ => bail!("First thread event is not a meta event"),
+    }
+
+    let new_meta =
+        serde_json::to_string(&meta_event).context("Failed to serialize updated meta event")?;
+    writeln!(temp, "{new_meta}").context("Failed to write updated meta")?;
+
+    for line in lines {
+        let line = line.context("Failed to read thread line")?;
+        writeln!(temp, "{line}").context("Failed to write thread line")?;
+    }
+
+    temp.sync_all().context("Failed to sync temp thread file")?;
+    fs::rename(&temp_path, path).context("Failed to replace thread file")?;
+    Ok(())
+}
+
+/// Rewrites the meta event with an updated style, preserving the rest of the file.
+fn rewrite_meta_with_style(path: &PathBuf, style: Option<String>) -> Result<()> {
+    let file = fs::File::open(path).context("Failed to open thread file")?;
+    let reader = BufReader::new(file);
+
+    let temp_path = path.with_extension("jsonl.tmp");
+    let mut temp = fs::File::create(&temp_path).context("Failed to create temp thread file")?;
+
+    let mut lines = reader.lines();
+    let first_line = lines
+        .next()
+        .transpose()
+        .context("Failed to read meta line")?
+        .ok_or_else(|| anyhow!("Thread file is empty"))?;
+
+    let mut meta_event: ThreadEvent =
+        serde_json::from_str(&first_line).context("Failed to parse meta event")?;
+    match meta_event {
+        ThreadEvent::Meta {
+            style: ref mut meta_style,
+            ..
+        } => {
+            *meta_style = style;
+        }
+        _ => bail!("First thread event is not a meta event"),
+    }
+
+    let new_meta =
+        serde_json::to_string(&meta_event).context("Failed to serialize updated meta event")?;
+    writeln!(temp, "{new_meta}").context("Failed to write updated meta")?;
+
+    for line in lines {
+        let line = line.context("Failed to read thread line")?;
+        writeln!(temp, "{line}").context("Failed to write thread line")?;
+    }
+
+    temp.sync_all().context("Failed to sync temp thread file")?;
+    fs::rename(&temp_path, path).context("Failed to replace thread file")?;
+    Ok(())
+}
+
+/// Rewrites the meta event with an updated summary, preserving the rest of the file.
+fn rewrite_meta_with_summary(path: &PathBuf, summary: Option<String>) -> Result<()> {
+    let file = fs::File::open(path).context("Failed to open thread file")?;
+    let reader = BufReader::new(file);
+
+    let temp_path = path.with_extension("jsonl.tmp");
+    let mut temp = fs::File::create(&temp_path).context("Failed to create temp thread file")?;
+
+    let mut lines = reader.lines();
+    let first_line = lines
+        .next()
+        .transpose()
+        .context("Failed to read meta line")?
+        .ok_or_else(|| anyhow!("Thread file is empty"))?;
+
+    let mut meta_event: ThreadEvent =
+        serde_json::from_str(&first_line).context("Failed to parse meta event")?;
+    match meta_event {
+        ThreadEvent::Meta {
+            summary: ref mut meta_summary,
+            ..
+        } => {
+            *meta_summary = summary;
+        }
+        _ => bail!("First thread event is not a meta event"),
+    }
+
+    let new_meta =
+        serde_json::to_string(&meta_event).context("Failed to serialize updated meta event")?;
+    writeln!(temp, "{new_meta}").context("Failed to write updated meta")?;
+
+    for line in lines {
+        let line = line.context("Failed to read thread line")?;
+        writeln!(temp, "{line}").context("Failed to write thread line")?;
+    }
+
+    temp.sync_all().context("Failed to sync temp thread file")?;
+    fs::rename(&temp_path, path).context("Failed to replace thread file")?;
+    Ok(())
+}
+
+/// Rewrites the meta event with updated pinned turns, preserving the rest of the file.
+fn rewrite_meta_with_pinned_turns(path: &PathBuf, pinned_turns: Vec<usize>) -> Result<()> {
+    let file = fs::File::open(path).context("Failed to open thread file")?;
+    let reader = BufReader::new(file);
+
+    let temp_path = path.with_extension("jsonl.tmp");
+    let mut temp = fs::File::create(&temp_path).context("Failed to create temp thread file")?;
+
+    let mut lines = reader.lines();
+    let first_line = lines
+        .next()
+        .transpose()
+        .context("Failed to read meta line")?
+        .ok_or_else(|| anyhow!("Thread file is empty"))?;
+
+    let mut meta_event: ThreadEvent =
+        serde_json::from_str(&first_line).context("Failed to parse meta event")?;
+    match meta_event {
+        ThreadEvent::Meta {
+            pinned_turns: ref mut meta_pinned,
+            ..
+        } => {
+            *meta_pinned = pinned_turns;
+        }
+        _ => bail!("First thread event is not a meta event"),
+    }
+
+    let new_meta =
+        serde_json::to_string(&meta_event).context("Failed to serialize updated meta event")?;
+    writeln!(temp, "{new_meta}").context("Failed to write updated meta")?;
+
+    for line in lines {
+        let line = line.context("Failed to read thread line")?;
+        writeln!(temp, "{line}").context("Failed to write thread line")?;
+    }
+
+    temp.sync_all().context("Failed to sync temp thread file")?;
+    fs::rename(&temp_path, path).context("Failed to replace thread file")?;
+    Ok(())
+}
+
+/// Rewrites the meta event with updated pinned messages and files, preserving the rest of the file.
+fn rewrite_meta_with_pins(
+    path: &PathBuf,
+    pinned_messages: Vec<PinnedMessage>,
+    pinned_files: Vec<String>,
+) -> Result<()> {
+    let file = fs::File::open(path).context("Failed to open thread file")?;
+    let reader = BufReader::new(file);
+
+    let temp_path = path.with_extension("jsonl.tmp");
+    let mut temp = fs::File::create(&temp_path).context("Failed to create temp thread file")?;
+
+    let mut lines = reader.lines();
+    let first_line = lines
+        .next()
+        .transpose()
+        .context("Failed to read meta line")?
+        .ok_or_else(|| anyhow!("Thread file is empty"))?;
+
+    let mut meta_event: ThreadEvent =
+        serde_json::from_str(&first_line).context("Failed to parse meta event")?;
+    match meta_event {
+        ThreadEvent::Meta {
+            pinned_messages: ref mut meta_messages,
+            pinned_files: ref mut meta_files,
+            ..
+        } => {
+            *meta_messages = pinned_messages;
+            *meta_files = pinned_files;
+        }
+        _ => bail!("First thread event is not a meta event"),
+    }
+
+    let new_meta =
+        serde_json::to_string(&meta_event).context("Failed to serialize updated meta event")?;
+    writeln!(temp, "{new_meta}").context("Failed to write updated meta")?;
+
+    for line in lines {
+        let line = line.context("Failed to read thread line")?;
+        writeln!(temp, "{line}").context("Failed to write thread line")?;
+    }
+
+    temp.sync_all().context("Failed to sync temp thread file")?;
+    fs::rename(&temp_path, path).context("Failed to replace thread file")?;
+    Ok(())
+}
+
+/// Rewrites the meta event with updated tags, preserving the rest of the file.
+fn rewrite_meta_with_tags(path: &PathBuf, tags: Vec<String>) -> Result<()> {
+    let file = fs::File::open(path).context("Failed to open thread file")?;
+    let reader = BufReader::new(file);
+
+    let temp_path = path.with_extension("jsonl.tmp");
+    let mut temp = fs::File::create(&temp_path).context("Failed to create temp thread file")?;
+
+    let mut lines = reader.lines();
+    let first_line = lines
+        .next()
+        .transpose()
+        .context("Failed to read meta line")?
+        .ok_or_else(|| anyhow!("Thread file is empty"))?;
+
+    let mut meta_event: ThreadEvent =
+        serde_json::from_str(&first_line).context("Failed to parse meta event")?;
+    match meta_event {
+        ThreadEvent::Meta {
+            tags: ref mut meta_tags,
+            ..
+        } => {
+            *meta_tags = tags;
+        }
+        _ => bail!("First thread event is not a meta event"),
+    }
+
+    let new_meta =
+        serde_json::to_string(&meta_event).context("Failed to serialize updated meta event")?;
+    writeln!(temp, "{new_meta}").context("Failed to write updated meta")?;
+
+    for line in lines {
+        let line = line.context("Failed to read thread line")?;
+        writeln!(temp, "{line}").context("Failed to write thread line")?;
+    }
+
+    temp.sync_all().context("Failed to sync temp thread file")?;
+    fs::rename(&temp_path, path).context("Failed to replace thread file")?;
+    Ok(())
+}
+
 /// Reads only the meta line to extract title (backward compatible).
 /// Parsed meta fields from the first line of a thread file.
 pub(crate) struct ThreadMeta {
@@ -582,6 +943,19 @@ pub(crate) struct ThreadMeta {
     thinking_override: Option<crate::config::ThinkingLevel>,
     pending_topic_title: bool,
     alias_to: Option<String>,
+    tags: Vec<String>,
+    prompt_variant: Option<String>,
+    pinned_turns: Vec<usize>,
+    pins: ThreadPins,
+    style: Option<String>,
+    summary: Option<String>,
+}
+
+/// Messages and files pinned to a thread's context (see `core::pinned_context`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ThreadPins {
+    pub messages: Vec<PinnedMessage>,
+    pub files: Vec<String>,
 }
 
 /// Reads and parses the meta line from a thread file (single open + parse).
@@ -622,6 +996,13 @@ pub(crate) fn read_meta(path: &PathBuf) -> Result<Option<ThreadMeta>> {
         thinking_override,
         pending_topic_title,
         alias_to,
+        tags,
+        prompt_variant,
+        pinned_turns,
+        pinned_messages,
+        pinned_files,
+        style,
+        summary,
         ..
     } = parsed
     {
@@ -636,6 +1017,15 @@ pub(crate) fn read_meta(path: &PathBuf) -> Result<Option<ThreadMeta>> {
             thinking_override,
             pending_topic_title,
             alias_to,
+            tags,
+            prompt_variant,
+            pinned_turns,
+            pins: ThreadPins {
+                messages: pinned_messages,
+                files: pinned_files,
+            },
+            style,
+            summary,
         }))
     } else {
         Ok(None)
@@ -687,6 +1077,10 @@ pub struct ThreadSummary {
     pub parent_thread_id: Option<String>,
     /// Named subagent when `origin_kind == "subagent"`.
     pub subagent_name: Option<String>,
+    /// User-assigned labels.
+    pub tags: Vec<String>,
+    /// Idle-time summary of the conversation (see `core::summary_generation`).
+    pub summary: Option<String>,
 }
 
 impl ThreadSummary {
@@ -700,6 +1094,20 @@ impl ThreadSummary {
     pub fn is_child_run(&self) -> bool {
         self.origin_kind.is_some()
     }
+
+    /// Whether this thread belongs to the Telegram bot (a chat or topic).
+    pub fn is_bot_thread(&self) -> bool {
+        is_bot_thread_id(&self.id)
+    }
+}
+
+/// ID prefix of threads created by the Telegram bot (`telegram-{chat_id}`,
+/// `telegram-{chat_id}-topic-{topic_id}`, and their named threads).
+pub const BOT_THREAD_ID_PREFIX: &str = "telegram-";
+
+/// Whether `id` names a Telegram bot thread.
+pub fn is_bot_thread_id(id: &str) -> bool {
+    id.starts_with(BOT_THREAD_ID_PREFIX)
 }
 
 /// One thread `.jsonl` file with its filesystem metadata. This is a cheap
@@ -782,7 +1190,9 @@ pub fn list_all_threads() -> Result<Vec<ThreadSummary>> {
                 handoff_from: meta.as_ref().and_then(|m| m.handoff_from.clone()),
                 origin_kind: meta.as_ref().and_then(|m| m.origin_kind.clone()),
                 parent_thread_id: meta.as_ref().and_then(|m| m.parent_thread_id.clone()),
-                subagent_name: meta.and_then(|m| m.subagent_name),
+                subagent_name: meta.as_ref().and_then(|m| m.subagent_name.clone()),
+                summary: meta.as_ref().and_then(|m| m.summary.clone()),
+                tags: meta.map(|m| m.tags).unwrap_or_default(),
             }
         })
         .collect();
@@ -865,6 +1275,53 @@ pub fn read_thread_model_override(id: &str) -> Result<Option<String>> {
     read_meta_model_override(&path)
 }
 
+/// Reads the prompt variant recorded for a thread's latest turn (if any).
+///
+/// # Errors
+/// Returns an error if the operation fails.
+pub fn read_thread_prompt_variant(id: &str) -> Result<Option<String>> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    Ok(read_meta(&path)?.and_then(|meta| meta.prompt_variant))
+}
+
+/// Reads the messages and files pinned to a thread's context.
+///
+/// # Errors
+/// Returns an error if the operation fails.
+pub fn read_thread_pins(id: &str) -> Result<ThreadPins> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    Ok(read_meta(&path)?.map(|meta| meta.pins).unwrap_or_default())
+}
+
+/// Reads a thread's response style preset (`/style`), if one is set.
+///
+/// # Errors
+/// Returns an error if the operation fails.
+pub fn read_thread_style(id: &str) -> Result<Option<String>> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    Ok(read_meta(&path)?.and_then(|meta| meta.style))
+}
+
+/// Reads a thread's idle-time summary, if one was generated.
+///
+/// # Errors
+/// Returns an error if the operation fails.
+pub fn read_thread_summary(id: &str) -> Result<Option<String>> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    Ok(read_meta(&path)?.and_then(|meta| meta.summary))
+}
+
+/// Reads a thread's pinned turns (1-based, sorted; empty when none).
+///
+/// # Errors
+/// Returns an error if the operation fails.
+pub fn read_thread_pinned_turns(id: &str) -> Result<Vec<usize>> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    Ok(read_meta(&path)?
+        .map(|meta| meta.pinned_turns)
+        .unwrap_or_default())
+}
+
 /// Reads a thread's thinking override by ID (if present in meta).
 ///
 /// # Errors
@@ -892,6 +1349,25 @@ pub fn read_thread_alias(id: &str) -> Result<Option<String>> {
     read_meta_alias(&path)
 }
 
+/// Follows a single `alias_to` hop: the thread that actually holds `id`'s
+/// history (resumed or switched bot topics point at another thread). Returns
+/// `id` unchanged when it has no alias or its meta cannot be read.
+pub fn resolve_thread_alias(id: &str) -> String {
+    match read_thread_alias(id) {
+        Ok(Some(source)) => source,
+        _ => id.to_string(),
+    }
+}
+
+/// Reads a thread's origin kind (e.g. `subagent`) by ID, if recorded in meta.
+///
+/// # Errors
+/// Returns an error if the operation fails.
+pub fn read_thread_origin_kind(id: &str) -> Result<Option<String>> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    Ok(read_meta(&path)?.and_then(|m| m.origin_kind))
+}
+
 /// Returns whether a thread file exists for the given ID.
 #[must_use]
 pub fn thread_exists(id: &str) -> bool {
@@ -912,6 +1388,178 @@ pub fn set_thread_title(id: &str, title: Option<String>) -> Result<Option<String
     thread.set_title(title)
 }
 
+/// Updates a thread's idle-time summary by ID.
+///
+/// # Errors
+/// Returns an error if the thread doesn't exist or can't be updated.
+pub fn set_thread_summary(id: &str, summary: Option<String>) -> Result<()> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    if !path.exists() {
+        bail!("Thread '{id}' not found");
+    }
+
+    let mut thread = Thread::with_id(id.to_string())?;
+    thread.set_summary(summary)
+}
+
+/// Adds `tag` to a thread's meta tags (no-op if already present).
+///
+/// # Errors
+/// Returns an error if the tag is empty or the thread can't be updated.
+pub fn add_thread_tag(id: &str, tag: &str) -> Result<()> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        bail!("Tag cannot be empty");
+    }
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    let Some(meta) = read_meta(&path)? else {
+        bail!("Thread '{id}' not found");
+    };
+    if meta.tags.iter().any(|existing| existing == tag) {
+        return Ok(());
+    }
+    let mut tags = meta.tags;
+    tags.push(tag.to_string());
+    rewrite_meta_with_tags(&path, tags)
+}
+
+/// Permanently deletes a thread file by ID.
+///
+/// # Errors
+/// Returns an error if the thread doesn't exist or can't be removed.
+pub fn delete_thread(id: &str) -> Result<()> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    if !path.exists() {
+        bail!("Thread '{id}' not found");
+    }
+    fs::remove_file(&path).with_context(|| format!("delete {}", path.display()))
+}
+
+/// Moves a thread into `threads/archive/` so it no longer shows up in
+/// listings, pickers, or search. Returns the archived file path.
+///
+/// # Errors
+/// Returns an error if the thread doesn't exist or can't be moved.
+pub fn archive_thread(id: &str) -> Result<PathBuf> {
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    if !path.exists() {
+        bail!("Thread '{id}' not found");
+    }
+    let archive_dir = threads_dir().join("archive");
+    fs::create_dir_all(&archive_dir)
+        .with_context(|| format!("create {}", archive_dir.display()))?;
+    let target = archive_dir.join(format!("{id}.jsonl"));
+    fs::rename(&path, &target).with_context(|| format!("archive {}", path.display()))?;
+    Ok(target)
+}
+
+/// Copies a thread into a new thread ID and returns the copy's handle.
+///
+/// The copy keeps the source's events (messages, tool calls, usage, and their
+/// timestamps) and its root, model/thinking overrides, tags, pins, and style. It
+/// starts as a top-level thread titled `{title} (copy)` (or `Copy of {short id}`).
+/// Aliased threads are copied from their source. With `turns`, only the first
+/// `turns` turns are kept, where each turn starts at a user message. The
+/// source thread is left untouched.
+///
+/// # Errors
+/// Returns an error if the thread doesn't exist, `turns` is out of range, or
+/// the copy can't be written.
+pub fn clone_thread(id: &str, turns: Option<usize>) -> Result<Thread> {
+    let id = resolve_thread_alias(id);
+    let path = threads_dir().join(format!("{id}.jsonl"));
+    if !path.exists() {
+        bail!("Thread '{id}' not found");
+    }
+    let mut events = read_thread_events(&path)?;
+    let meta = match events.first() {
+        Some(ThreadEvent::Meta { .. }) => events.remove(0),
+        _ => ThreadEvent::meta_with_root(None),
+    };
+    if let Some(turns) = turns {
+        truncate_to_turns(&mut events, turns)?;
+    }
+
+    let ThreadEvent::Meta {
+        title,
+        root_path,
+        model_override,
+        thinking_override,
+        tags,
+        mut pinned_turns,
+        mut pinned_messages,
+        pinned_files,
+        style,
+        ..
+    } = meta
+    else {
+        bail!("Thread '{id}' has no meta event");
+    };
+    if let Some(turns) = turns {
+        pinned_turns.retain(|&turn| turn <= turns);
+        // Pins on messages from dropped turns would re-inject history the
+        // copy no longer has.
+        pinned_messages.retain(|pinned| {
+            events.iter().any(|event| {
+                matches!(event, ThreadEvent::Message { role, text, .. }
+                    if *role == pinned.role && *text == pinned.text)
+            })
+        });
+    }
+    let mut thread = Thread::new_with_root_path_and_source(root_path.clone(), None)?;
+    let mut meta = ThreadEvent::meta_with_root(root_path);
+    if let ThreadEvent::Meta {
+        title: copy_title,
+        model_override: copy_model,
+        thinking_override: copy_thinking,
+        tags: copy_tags,
+        pinned_turns: copy_turns,
+        pinned_messages: copy_messages,
+        pinned_files: copy_files,
+        style: copy_style,
+        ..
+    } = &mut meta
+    {
+        *copy_title = normalize_title(title.map_or_else(
+            || format!("Copy of {}", short_thread_id(&id)),
+            |title| format!("{title} (copy)"),
+        ));
+        *copy_model = model_override;
+        *copy_thinking = thinking_override;
+        *copy_tags = tags;
+        *copy_turns = pinned_turns;
+        *copy_messages = pinned_messages;
+        *copy_files = pinned_files;
+        *copy_style = style;
+    }
+    thread.append_raw(&meta)?;
+    thread.is_new = false;
+    for event in &events {
+        thread.append_raw(event)?;
+    }
+    Ok(thread)
+}
+
+/// Drops every event from the start of turn `turns + 1` onward.
+fn truncate_to_turns(events: &mut Vec<ThreadEvent>, turns: usize) -> Result<()> {
+    let starts: Vec<usize> = events
+        .iter()
+        .enumerate()
+        .filter(|(_, event)| matches!(event, ThreadEvent::Message { role, .. } if role == "user"))
+        .map(|(idx, _)| idx)
+        .collect();
+    if turns == 0 || turns > starts.len() {
+        bail!(
+            "Turn must be between 1 and {} (the thread's turn count)",
+            starts.len()
+        );
+    }
+    if let Some(&end) = starts.get(turns) {
+        events.truncate(end);
+    }
+    Ok(())
+}
+
 /// Thread options for CLI commands.
 #[derive(Debug, Clone, Default)]
 pub struct ThreadPersistenceOptions {
@@ -932,7 +1580,8 @@ impl ThreadPersistenceOptions {
     /// Resolves thread options into an optional Thread.
     ///
     /// Returns None if `no_save` is true.
-    /// Returns existing thread if `thread_id` is provided.
+    /// Returns existing thread if `thread_id` is provided; a `thread_id` with
+    /// no file yet creates that thread with the root and origin recorded.
     /// Returns new thread otherwise.
     ///
     /// # Errors
@@ -943,7 +1592,17 @@ impl ThreadPersistenceOptions {
         }
 
         if let Some(ref id) = self.thread_id {
-            return Ok(Some(Thread::with_id(id.clone())?));
+            let mut thread = Thread::with_id(id.clone())?;
+            if thread.is_new {
+                let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+                thread.root_path = Some(root.display().to_string());
+                thread.set_origin(
+                    self.origin_kind.clone(),
+                    self.parent_thread_id.clone(),
+                    self.subagent_name.clone(),
+                );
+            }
+            return Ok(Some(thread));
         }
 
         let mut thread = Thread::new_with_root(root)?;
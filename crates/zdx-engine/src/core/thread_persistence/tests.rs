@@ -1,6 +1,5 @@
 use std::fs;
 use std::path::Path;
-use std::sync::Arc;
 
 use serde_json::json;
 use tempfile::TempDir;
@@ -194,6 +193,7 @@ fn test_thread_events_to_messages_preserves_replay_tokens_across_fragments() {
                 signature: "sig-first".to_string(),
                 model: "gemini-3-pro-preview".to_string(),
             }),
+            model: None,
             ts: "2026-05-15T00:00:00Z".to_string(),
         },
         ThreadEvent::Message {
@@ -204,6 +204,7 @@ fn test_thread_events_to_messages_preserves_replay_tokens_across_fragments() {
                 signature: "sig-second".to_string(),
                 model: "gemini-3-pro-preview".to_string(),
             }),
+            model: None,
             ts: "2026-05-15T00:00:01Z".to_string(),
         },
     ];
@@ -274,6 +275,27 @@ fn notice_agent_event_persists_as_thread_notice_not_message() {
     }
 }
 
+#[test]
+fn thinking_escalated_event_persists_as_thread_notice() {
+    use crate::config::ThinkingLevel;
+    use crate::core::events::{AgentEvent, EscalationTrigger, NoticeKind};
+
+    let evt = AgentEvent::ThinkingEscalated {
+        from: ThinkingLevel::Low,
+        to: ThinkingLevel::Medium,
+        trigger: EscalationTrigger::ToolFailures,
+        message: "Thinking escalated low → medium.".to_string(),
+    };
+
+    match ThreadEvent::from_agent(&evt) {
+        Some(ThreadEvent::Notice { kind, message, .. }) => {
+            assert_eq!(kind, NoticeKind::ThinkingEscalated);
+            assert_eq!(message, "Thinking escalated low → medium.");
+        }
+        other => panic!("expected ThreadEvent::Notice, got {other:?}"),
+    }
+}
+
 #[test]
 fn notice_thread_event_is_not_replayed_as_chat_message() {
     // Critical contract: notices MUST NOT be re-sent to providers as
@@ -378,6 +400,40 @@ fn test_events_to_messages_preserves_assistant_phase() {
     assert_eq!(messages[0].phase.as_deref(), Some("commentary"));
 }
 
+#[test]
+fn test_events_to_messages_drops_the_attempt_a_redo_replaces() {
+    let redo = crate::core::regeneration::regeneration_message("shorter");
+    let mut events = vec![
+        ThreadEvent::user_message("first"),
+        ThreadEvent::assistant_message("one"),
+        ThreadEvent::user_message("explain"),
+        ThreadEvent::tool_use("t1", "read", json!({"file_path": "main.rs"})),
+        ThreadEvent::assistant_message("a long answer"),
+    ];
+    events.extend(messages_to_events(&[redo]));
+    events.push(ThreadEvent::assistant_message("short"));
+
+    let messages = thread_events_to_messages(events);
+    let roles: Vec<&str> = messages.iter().map(|m| m.role.as_str()).collect();
+    assert_eq!(roles, ["user", "assistant", "user", "user", "assistant"]);
+    assert!(crate::core::regeneration::is_regeneration_message(
+        &messages[3]
+    ));
+    let replayed = format!("{messages:?}");
+    assert!(!replayed.contains("a long answer"));
+    assert!(replayed.contains("short"));
+
+    // The log itself keeps the discarded attempt for exports.
+    let transcript = format_transcript(&[
+        ThreadEvent::assistant_message("a long answer"),
+        messages_to_events(&[crate::core::regeneration::regeneration_message("shorter")]).remove(0),
+        ThreadEvent::assistant_message("short"),
+    ]);
+    assert!(transcript.contains("a long answer"));
+    assert!(transcript.contains("### Redo\nFeedback: shorter"));
+    assert!(transcript.contains("### Assistant [regenerated]\nshort"));
+}
+
 #[test]
 fn test_thread_persistence_options_no_save() {
     let opts = ThreadPersistenceOptions {
@@ -400,6 +456,38 @@ fn test_thread_persistence_options_with_id() {
     assert_eq!(thread.id, id);
 }
 
+#[test]
+fn test_thread_persistence_options_new_id_records_origin() {
+    let _temp = setup_temp_zdx_home();
+
+    let id = unique_thread_id("subagent-child");
+    let opts = ThreadPersistenceOptions {
+        thread_id: Some(id.clone()),
+        origin_kind: Some("subagent".to_string()),
+        parent_thread_id: Some("parent".to_string()),
+        ..Default::default()
+    };
+    let mut thread = opts.resolve(Path::new(".")).unwrap().unwrap();
+    thread.append(&ThreadEvent::user_message("hi")).unwrap();
+    assert_eq!(
+        read_thread_origin_kind(&id).unwrap().as_deref(),
+        Some("subagent")
+    );
+
+    // Resuming an existing thread leaves its recorded origin untouched.
+    let resumed = ThreadPersistenceOptions {
+        thread_id: Some(id.clone()),
+        origin_kind: Some("helper:title".to_string()),
+        ..Default::default()
+    };
+    let mut thread = resumed.resolve(Path::new(".")).unwrap().unwrap();
+    thread.append(&ThreadEvent::user_message("again")).unwrap();
+    assert_eq!(
+        read_thread_origin_kind(&id).unwrap().as_deref(),
+        Some("subagent")
+    );
+}
+
 #[test]
 fn test_format_transcript_with_tools() {
     let events = vec![
@@ -843,7 +931,14 @@ fn test_format_transcript_with_reasoning() {
 
 #[test]
 fn test_usage_event_serialization() {
-    let usage = ThreadEvent::usage(Usage::new(1000, 500, 2000, 100), None, None, None, None);
+    let usage = ThreadEvent::usage(
+        Usage::new(1000, 500, 2000, 100),
+        None,
+        None,
+        None,
+        None,
+        None,
+    );
     let json = serde_json::to_string(&usage).unwrap();
     assert!(json.contains("\"type\":\"usage\""));
     assert!(json.contains("\"input_tokens\":1000"));
@@ -878,10 +973,10 @@ fn test_extract_usage_from_events() {
     let events = vec![
         ThreadEvent::user_message("hello"),
         ThreadEvent::assistant_message("hi"),
-        ThreadEvent::usage(Usage::new(100, 50, 200, 25), None, None, None, None),
+        ThreadEvent::usage(Usage::new(100, 50, 200, 25), None, None, None, None, None),
         ThreadEvent::user_message("bye"),
         ThreadEvent::assistant_message("goodbye"),
-        ThreadEvent::usage(Usage::new(150, 75, 300, 30), None, None, None, None),
+        ThreadEvent::usage(Usage::new(150, 75, 300, 30), None, None, None, None, None),
     ];
 
     let (cumulative, latest) = extract_usage_from_thread_events(&events);
@@ -894,9 +989,9 @@ fn test_extract_usage_latest_folds_output_only_tail_fragments() {
     // Latest must keep context tokens and fold the output-only tail rather
     // than collapsing to the final zero-context fragment.
     let events = vec![
-        ThreadEvent::usage(Usage::new(2, 3, 250_000, 880), None, None, None, None),
-        ThreadEvent::usage(Usage::new(0, 1522, 0, 0), None, None, None, None),
-        ThreadEvent::usage(Usage::new(0, 480, 0, 0), None, None, None, None),
+        ThreadEvent::usage(Usage::new(2, 3, 250_000, 880), None, None, None, None, None),
+        ThreadEvent::usage(Usage::new(0, 1522, 0, 0), None, None, None, None, None),
+        ThreadEvent::usage(Usage::new(0, 480, 0, 0), None, None, None, None, None),
     ];
 
     let (cumulative, latest) = extract_usage_from_thread_events(&events);
@@ -933,6 +1028,7 @@ fn test_thread_usage_roundtrip() {
             None,
             None,
             None,
+            None,
         ))
         .unwrap();
 
@@ -955,7 +1051,7 @@ async fn test_persist_task_records_latency_on_terminal_usage() {
     let persist_handle = spawn_thread_persist_task(thread.clone(), rx);
 
     // Interim usage (input only) carries no latency.
-    tx.send(Arc::new(AgentEvent::UsageUpdate {
+    tx.send(AgentEvent::UsageUpdate {
         input_tokens: 100,
         output_tokens: 0,
         cache_read_input_tokens: 0,
@@ -964,10 +1060,10 @@ async fn test_persist_task_records_latency_on_terminal_usage() {
         provider: "p".to_string(),
         duration_ms: None,
         ttft_ms: None,
-    }))
-    .unwrap();
+        model_params: None,
+    });
     // Terminal usage (output) carries per-request latency.
-    tx.send(Arc::new(AgentEvent::UsageUpdate {
+    tx.send(AgentEvent::UsageUpdate {
         input_tokens: 0,
         output_tokens: 50,
         cache_read_input_tokens: 0,
@@ -976,15 +1072,14 @@ async fn test_persist_task_records_latency_on_terminal_usage() {
         provider: "p".to_string(),
         duration_ms: Some(1234),
         ttft_ms: Some(56),
-    }))
-    .unwrap();
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+        model_params: None,
+    });
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Completed,
         final_text: "done".to_string(),
         messages: Vec::new(),
         prior_message_count: 0,
-    }))
-    .unwrap();
+    });
     drop(tx);
 
     persist_handle.await.unwrap();
@@ -1008,6 +1103,70 @@ async fn test_persist_task_records_latency_on_terminal_usage() {
     assert_eq!(usage, (100, 50, Some(1234), Some(56)));
 }
 
+/// Assistant messages record the model from the latest usage update, so a
+/// mid-thread switch (or fallback) shows up per message.
+#[tokio::test]
+async fn test_persist_task_attributes_assistant_messages_to_model() {
+    use crate::providers::ChatMessage;
+
+    let _temp = setup_temp_zdx_home();
+
+    let thread = Thread::with_id(unique_thread_id("model-attribution")).unwrap();
+    let (tx, rx) = create_event_channel();
+    let persist_handle = spawn_thread_persist_task(thread.clone(), rx);
+
+    let usage = |model: &str| AgentEvent::UsageUpdate {
+        input_tokens: 10,
+        output_tokens: 0,
+        cache_read_input_tokens: 0,
+        cache_creation_input_tokens: 0,
+        model: model.to_string(),
+        provider: "p".to_string(),
+        duration_ms: None,
+        ttft_ms: None,
+        model_params: None,
+    };
+    let mut messages = vec![
+        ChatMessage::user("first"),
+        ChatMessage::assistant_text("from a", None),
+    ];
+    tx.send(usage("model-a"));
+    tx.send(AgentEvent::TurnCheckpoint {
+        messages: messages.clone(),
+        prior_message_count: 0,
+    });
+    messages.push(ChatMessage::user("second"));
+    messages.push(ChatMessage::assistant_text("from b", None));
+    tx.send(usage("model-b"));
+    tx.send(AgentEvent::TurnFinished {
+        status: TurnStatus::Completed,
+        final_text: "from b".to_string(),
+        messages,
+        prior_message_count: 0,
+    });
+    drop(tx);
+    persist_handle.await.unwrap();
+
+    let models: Vec<(String, Option<String>)> = thread
+        .read_events()
+        .unwrap()
+        .into_iter()
+        .filter_map(|e| match e {
+            ThreadEvent::Message { text, model, .. } => Some((text, model)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        models,
+        vec![
+            ("first".to_string(), None),
+            ("from a".to_string(), Some("model-a".to_string())),
+            ("second".to_string(), None),
+            ("from b".to_string(), Some("model-b".to_string())),
+        ]
+    );
+}
+
 #[tokio::test]
 async fn test_persist_task_saves_completed_usage_from_agent_events() {
     let _temp = setup_temp_zdx_home();
@@ -1016,7 +1175,7 @@ async fn test_persist_task_saves_completed_usage_from_agent_events() {
     let (tx, rx) = create_event_channel();
     let persist_handle = spawn_thread_persist_task(thread.clone(), rx);
 
-    tx.send(Arc::new(AgentEvent::UsageUpdate {
+    tx.send(AgentEvent::UsageUpdate {
         input_tokens: 100,
         output_tokens: 0,
         cache_read_input_tokens: 20,
@@ -1025,9 +1184,9 @@ async fn test_persist_task_saves_completed_usage_from_agent_events() {
         provider: String::new(),
         duration_ms: None,
         ttft_ms: None,
-    }))
-    .unwrap();
-    tx.send(Arc::new(AgentEvent::UsageUpdate {
+        model_params: None,
+    });
+    tx.send(AgentEvent::UsageUpdate {
         input_tokens: 0,
         output_tokens: 50,
         cache_read_input_tokens: 0,
@@ -1036,15 +1195,14 @@ async fn test_persist_task_saves_completed_usage_from_agent_events() {
         provider: String::new(),
         duration_ms: None,
         ttft_ms: None,
-    }))
-    .unwrap();
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+        model_params: None,
+    });
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Completed,
         final_text: "done".to_string(),
         messages: Vec::new(),
         prior_message_count: 0,
-    }))
-    .unwrap();
+    });
     drop(tx);
 
     persist_handle.await.unwrap();
@@ -1070,7 +1228,7 @@ async fn test_persist_task_records_model_and_provider_on_usage() {
     let (tx, rx) = create_event_channel();
     let persist_handle = spawn_thread_persist_task(thread.clone(), rx);
 
-    tx.send(Arc::new(AgentEvent::UsageUpdate {
+    tx.send(AgentEvent::UsageUpdate {
         input_tokens: 100,
         output_tokens: 50,
         cache_read_input_tokens: 20,
@@ -1079,8 +1237,8 @@ async fn test_persist_task_records_model_and_provider_on_usage() {
         provider: "claude-cli".to_string(),
         duration_ms: None,
         ttft_ms: None,
-    }))
-    .unwrap();
+        model_params: None,
+    });
     drop(tx);
 
     persist_handle.await.unwrap();
@@ -1107,7 +1265,7 @@ async fn test_persist_task_flushes_partial_usage_on_interrupted_turn() {
     let (tx, rx) = create_event_channel();
     let persist_handle = spawn_thread_persist_task(thread.clone(), rx);
 
-    tx.send(Arc::new(AgentEvent::UsageUpdate {
+    tx.send(AgentEvent::UsageUpdate {
         input_tokens: 500,
         output_tokens: 0,
         cache_read_input_tokens: 100,
@@ -1116,9 +1274,9 @@ async fn test_persist_task_flushes_partial_usage_on_interrupted_turn() {
         provider: String::new(),
         duration_ms: None,
         ttft_ms: None,
-    }))
-    .unwrap();
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+        model_params: None,
+    });
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Interrupted,
         final_text: "partial".to_string(),
         messages: vec![crate::providers::ChatMessage::assistant_text(
@@ -1126,8 +1284,7 @@ async fn test_persist_task_flushes_partial_usage_on_interrupted_turn() {
             Some("commentary".to_string()),
         )],
         prior_message_count: 0,
-    }))
-    .unwrap();
+    });
     drop(tx);
 
     persist_handle.await.unwrap();
@@ -1164,7 +1321,7 @@ async fn test_persist_task_flushes_pending_usage_on_channel_close() {
     let (tx, rx) = create_event_channel();
     let persist_handle = spawn_thread_persist_task(thread.clone(), rx);
 
-    tx.send(Arc::new(AgentEvent::UsageUpdate {
+    tx.send(AgentEvent::UsageUpdate {
         input_tokens: 321,
         output_tokens: 0,
         cache_read_input_tokens: 45,
@@ -1173,8 +1330,8 @@ async fn test_persist_task_flushes_pending_usage_on_channel_close() {
         provider: String::new(),
         duration_ms: None,
         ttft_ms: None,
-    }))
-    .unwrap();
+        model_params: None,
+    });
     drop(tx);
 
     persist_handle.await.unwrap();
@@ -1229,13 +1386,12 @@ async fn test_persistence_round_trip_preserves_order() {
         ]),
     };
 
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Completed,
         final_text: "second text".to_string(),
         messages: vec![assistant],
         prior_message_count: 0,
-    }))
-    .unwrap();
+    });
     drop(tx);
     persist_handle.await.unwrap();
 
@@ -1292,13 +1448,12 @@ async fn test_persistence_carries_per_part_signatures_and_id_origin() {
         ]),
     };
 
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Completed,
         final_text: "answer".to_string(),
         messages: vec![assistant],
         prior_message_count: 0,
-    }))
-    .unwrap();
+    });
     drop(tx);
     persist_handle.await.unwrap();
 
@@ -1369,21 +1524,19 @@ async fn test_streaming_events_no_longer_persisted() {
     let persist_handle = spawn_thread_persist_task(thread.clone(), rx);
 
     // Streaming ToolInputCompleted should NOT be persisted any more.
-    tx.send(Arc::new(AgentEvent::ToolInputCompleted {
+    tx.send(AgentEvent::ToolInputCompleted {
         id: "t1".to_string(),
         name: "bash".to_string(),
         input: json!({"command": "ls"}),
-    }))
-    .unwrap();
+    });
 
     // ReasoningCompleted should NOT be persisted either.
-    tx.send(Arc::new(AgentEvent::ReasoningCompleted {
+    tx.send(AgentEvent::ReasoningCompleted {
         block: crate::providers::ReasoningBlock {
             text: Some("thinking".to_string()),
             replay: None,
         },
-    }))
-    .unwrap();
+    });
 
     let assistant = ChatMessage {
         role: "assistant".to_string(),
@@ -1395,13 +1548,12 @@ async fn test_streaming_events_no_longer_persisted() {
         )]),
     };
 
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Completed,
         final_text: String::new(),
         messages: vec![assistant],
         prior_message_count: 0,
-    }))
-    .unwrap();
+    });
     drop(tx);
     persist_handle.await.unwrap();
 
@@ -1452,13 +1604,12 @@ async fn test_tool_result_persisted_with_tool_use_in_order() {
         is_error: false,
     }]);
 
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Completed,
         final_text: String::new(),
         messages: vec![assistant, tool_results],
         prior_message_count: 0,
-    }))
-    .unwrap();
+    });
     drop(tx);
     persist_handle.await.unwrap();
 
@@ -1661,15 +1812,14 @@ async fn test_checkpoint_then_turn_finished_idempotent() {
     // Caller appended user_msg directly before kicking off the engine, so
     // `prior_message_count` is 1 (covers user_msg). The engine then
     // appended assistant_t1 + tool_result_t1, giving 3 total messages.
-    tx.send(Arc::new(AgentEvent::TurnCheckpoint {
+    tx.send(AgentEvent::TurnCheckpoint {
         messages: vec![
             user_msg.clone(),
             assistant_t1.clone(),
             tool_result_t1.clone(),
         ],
         prior_message_count: 1,
-    }))
-    .unwrap();
+    });
 
     // Second tool turn:
     let assistant_t2 = ChatMessage {
@@ -1687,7 +1837,7 @@ async fn test_checkpoint_then_turn_finished_idempotent() {
         is_error: false,
     }]);
 
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Completed,
         final_text: String::new(),
         messages: vec![
@@ -1698,8 +1848,7 @@ async fn test_checkpoint_then_turn_finished_idempotent() {
             tool_result_t2,
         ],
         prior_message_count: 1,
-    }))
-    .unwrap();
+    });
     drop(tx);
     persist_handle.await.unwrap();
 
@@ -1766,19 +1915,17 @@ async fn test_interrupted_turn_finished_allows_shorter_snapshot_than_checkpoint(
         }]),
     };
 
-    tx.send(Arc::new(AgentEvent::TurnCheckpoint {
+    tx.send(AgentEvent::TurnCheckpoint {
         messages: vec![user_msg.clone(), checkpointed_assistant],
         prior_message_count: 1,
-    }))
-    .unwrap();
+    });
 
-    tx.send(Arc::new(AgentEvent::TurnFinished {
+    tx.send(AgentEvent::TurnFinished {
         status: TurnStatus::Interrupted,
         final_text: String::new(),
         messages: vec![user_msg],
         prior_message_count: 1,
-    }))
-    .unwrap();
+    });
     drop(tx);
     persist_handle.await.unwrap();
 
@@ -1849,11 +1996,10 @@ async fn test_checkpoint_persistence_survives_crash_simulation() {
         is_error: false,
     }]);
 
-    tx.send(Arc::new(AgentEvent::TurnCheckpoint {
+    tx.send(AgentEvent::TurnCheckpoint {
         messages: vec![user_msg, assistant_t1, tool_result_t1],
         prior_message_count: 1,
-    }))
-    .unwrap();
+    });
 
     // Simulate crash: drop the sender without sending TurnFinished. The
     // in-flight second tool turn (t2) is never persisted.
@@ -1908,6 +2054,140 @@ fn test_pending_topic_title_roundtrip() {
     assert!(!read_thread_pending_topic_title(&thread_id).unwrap());
 }
 
+#[test]
+fn test_prompt_variant_roundtrip() {
+    let _temp = setup_temp_zdx_home();
+
+    let thread_id = unique_thread_id("variant");
+    let mut thread = Thread::with_id(thread_id.clone()).unwrap();
+    thread.append(&ThreadEvent::user_message("hi")).unwrap();
+    assert_eq!(read_thread_prompt_variant(&thread_id).unwrap(), None);
+
+    thread
+        .set_prompt_variant(Some("small".to_string()))
+        .unwrap();
+    thread
+        .set_prompt_variant(Some("small".to_string()))
+        .unwrap();
+    assert_eq!(
+        read_thread_prompt_variant(&thread_id).unwrap().as_deref(),
+        Some("small")
+    );
+    let events = thread.read_events().unwrap();
+    assert_eq!(events.len(), 2);
+
+    thread.set_prompt_variant(None).unwrap();
+    assert_eq!(read_thread_prompt_variant(&thread_id).unwrap(), None);
+}
+
+#[test]
+fn test_pinned_turns_roundtrip_and_clone() {
+    let _temp = setup_temp_zdx_home();
+
+    let thread_id = unique_thread_id("pinned");
+    let mut thread = Thread::with_id(thread_id.clone()).unwrap();
+    thread.append(&ThreadEvent::user_message("one")).unwrap();
+    thread.append(&ThreadEvent::user_message("two")).unwrap();
+    assert!(read_thread_pinned_turns(&thread_id).unwrap().is_empty());
+
+    thread.set_turn_pinned(2, true).unwrap();
+    assert_eq!(thread.set_turn_pinned(1, true).unwrap(), vec![1, 2]);
+    assert_eq!(read_thread_pinned_turns(&thread_id).unwrap(), vec![1, 2]);
+
+    let copy = clone_thread(&thread_id, Some(1)).unwrap();
+    assert_eq!(read_thread_pinned_turns(&copy.id).unwrap(), vec![1]);
+
+    assert_eq!(thread.set_turn_pinned(2, false).unwrap(), vec![1]);
+    assert_eq!(thread.read_events().unwrap().len(), 3);
+}
+
+#[test]
+fn test_pinned_messages_and_files_roundtrip_and_clone() {
+    let _temp = setup_temp_zdx_home();
+
+    let thread_id = unique_thread_id("pins");
+    let mut thread = Thread::with_id(thread_id.clone()).unwrap();
+    thread.append(&ThreadEvent::user_message("one")).unwrap();
+    thread.append(&ThreadEvent::user_message("two")).unwrap();
+
+    let pin = |text: &str| PinnedMessage {
+        role: "user".to_string(),
+        text: text.to_string(),
+    };
+    thread.set_message_pinned(pin("one"), true).unwrap();
+    thread.set_message_pinned(pin("one"), true).unwrap();
+    thread.set_message_pinned(pin("two"), true).unwrap();
+    assert_eq!(
+        thread
+            .set_file_pinned("notes.md".to_string(), true)
+            .unwrap(),
+        vec!["notes.md".to_string()]
+    );
+    let pins = read_thread_pins(&thread_id).unwrap();
+    assert_eq!(pins.messages, vec![pin("one"), pin("two")]);
+
+    // A full copy keeps every pin; a truncated copy drops pins on messages
+    // from the turns it left out. File pins are not tied to history.
+    let full = clone_thread(&thread_id, None).unwrap();
+    assert_eq!(read_thread_pins(&full.id).unwrap(), pins);
+    let copy = clone_thread(&thread_id, Some(1)).unwrap();
+    let copy_pins = read_thread_pins(&copy.id).unwrap();
+    assert_eq!(copy_pins.messages, vec![pin("one")]);
+    assert_eq!(copy_pins.files, vec!["notes.md".to_string()]);
+
+    thread.set_message_pinned(pin("one"), false).unwrap();
+    assert_eq!(
+        read_thread_pins(&thread_id).unwrap().messages,
+        vec![pin("two")]
+    );
+}
+
+#[test]
+fn test_style_roundtrip_and_clone() {
+    let _temp = setup_temp_zdx_home();
+
+    let thread_id = unique_thread_id("style");
+    let mut thread = Thread::with_id(thread_id.clone()).unwrap();
+    thread.append(&ThreadEvent::user_message("one")).unwrap();
+
+    thread.set_style(Some("concise".to_string())).unwrap();
+    assert_eq!(
+        read_thread_style(&thread_id).unwrap().as_deref(),
+        Some("concise")
+    );
+
+    let copy = clone_thread(&thread_id, Some(1)).unwrap();
+    assert_eq!(
+        read_thread_style(&copy.id).unwrap().as_deref(),
+        Some("concise")
+    );
+}
+
+#[test]
+fn test_summary_roundtrip_and_listing() {
+    let _temp = setup_temp_zdx_home();
+
+    let thread_id = unique_thread_id("summary");
+    let mut thread = Thread::with_id(thread_id.clone()).unwrap();
+    thread.append(&ThreadEvent::user_message("hello")).unwrap();
+    assert_eq!(read_thread_summary(&thread_id).unwrap(), None);
+
+    set_thread_summary(&thread_id, Some("Greeting only.".to_string())).unwrap();
+    assert_eq!(
+        read_thread_summary(&thread_id).unwrap().as_deref(),
+        Some("Greeting only.")
+    );
+    let listed = list_all_threads()
+        .unwrap()
+        .into_iter()
+        .find(|summary| summary.id == thread_id)
+        .unwrap();
+    assert_eq!(listed.summary.as_deref(), Some("Greeting only."));
+    assert_eq!(thread.read_events().unwrap().len(), 2);
+
+    assert!(set_thread_summary(&unique_thread_id("missing"), None).is_err());
+}
+
 #[test]
 fn test_alias_roundtrip() {
     let _temp = setup_temp_zdx_home();
@@ -1928,8 +2208,13 @@ fn test_alias_roundtrip() {
     let events = thread.read_events().unwrap();
     assert!(matches!(events[0], ThreadEvent::Meta { .. }));
 
+    assert_eq!(resolve_thread_alias(&thread_id), "source-thread-123");
+
     thread.set_alias(None).unwrap();
     assert_eq!(read_thread_alias(&thread_id).unwrap(), None);
+    assert_eq!(resolve_thread_alias(&thread_id), thread_id);
+    assert!(is_bot_thread_id("telegram-42-topic-7"));
+    assert!(!is_bot_thread_id(&thread_id));
 }
 
 #[test]
@@ -1981,6 +2266,57 @@ fn test_thread_lineage_roundtrip_and_list_filtering() {
     assert!(all_ids.contains(&child_id));
 }
 
+#[test]
+fn test_thread_tags_roundtrip_without_duplicates() {
+    let _temp = setup_temp_zdx_home();
+
+    let thread_id = unique_thread_id("tags");
+    let mut thread = Thread::with_id(thread_id.clone()).unwrap();
+    thread.append(&ThreadEvent::user_message("hi")).unwrap();
+
+    add_thread_tag(&thread_id, "wip").unwrap();
+    add_thread_tag(&thread_id, " wip ").unwrap();
+    add_thread_tag(&thread_id, "review").unwrap();
+    assert!(add_thread_tag(&thread_id, "  ").is_err());
+
+    let summary = list_all_threads()
+        .unwrap()
+        .into_iter()
+        .find(|s| s.id == thread_id)
+        .unwrap();
+    assert_eq!(summary.tags, ["wip", "review"]);
+    // The rewrite keeps the events after the meta line.
+    assert_eq!(thread.read_events().unwrap().len(), 2);
+}
+
+#[test]
+fn test_archive_and_delete_remove_thread_from_listing() {
+    let _temp = setup_temp_zdx_home();
+
+    let archived_id = unique_thread_id("archive-me");
+    let mut archived = Thread::with_id(archived_id.clone()).unwrap();
+    archived.append(&ThreadEvent::user_message("keep")).unwrap();
+    let deleted_id = unique_thread_id("delete-me");
+    let mut deleted = Thread::with_id(deleted_id.clone()).unwrap();
+    deleted.append(&ThreadEvent::user_message("drop")).unwrap();
+
+    let archived_path = archive_thread(&archived_id).unwrap();
+    assert!(archived_path.exists());
+    assert!(!thread_exists(&archived_id));
+    delete_thread(&deleted_id).unwrap();
+    assert!(!thread_exists(&deleted_id));
+
+    let ids: Vec<String> = list_all_threads()
+        .unwrap()
+        .into_iter()
+        .map(|s| s.id)
+        .collect();
+    assert!(!ids.contains(&archived_id));
+    assert!(!ids.contains(&deleted_id));
+    assert!(delete_thread(&deleted_id).is_err());
+    assert!(archive_thread(&deleted_id).is_err());
+}
+
 #[test]
 fn test_usage_struct_operations() {
     let u1 = Usage::new(100, 50, 200, 25);
@@ -2063,6 +2399,7 @@ fn test_interrupted_turn_preserves_reasoning_tools_and_partial_text() {
             text: "Here are the files I found so far".to_string(),
             phase: Some("commentary".to_string()),
             replay: None,
+            model: None,
             ts: chrono_timestamp(),
         },
         // User interrupted here — no tool_result, no final assistant message.
@@ -2276,3 +2613,80 @@ fn test_search_threads_exclusion_does_not_underfill_limit() {
         "current thread must not appear in results"
     );
 }
+
+#[test]
+fn test_clone_thread_copies_events_and_truncates_turns() {
+    let _temp = setup_temp_zdx_home();
+
+    let source_id = unique_thread_id("clone-source");
+    let mut source = Thread::with_id(source_id.clone()).unwrap();
+    source.append(&ThreadEvent::user_message("first")).unwrap();
+    source
+        .append(&ThreadEvent::assistant_message("one"))
+        .unwrap();
+    source.append(&ThreadEvent::user_message("second")).unwrap();
+    source
+        .append(&ThreadEvent::assistant_message("two"))
+        .unwrap();
+    source.set_title(Some("Plan".to_string())).unwrap();
+    source
+        .set_model_override(Some("openai:gpt-5".to_string()))
+        .unwrap();
+    let source_events = load_thread_events(&source_id).unwrap();
+
+    let full = clone_thread(&source_id, None).unwrap();
+    assert_ne!(full.id, source_id);
+    assert_eq!(
+        read_thread_title(&full.id).unwrap().as_deref(),
+        Some("Plan (copy)")
+    );
+    assert_eq!(
+        read_thread_model_override(&full.id).unwrap().as_deref(),
+        Some("openai:gpt-5")
+    );
+    assert_eq!(
+        load_thread_events(&full.id).unwrap().len(),
+        source_events.len()
+    );
+
+    let first_turn = clone_thread(&source_id, Some(1)).unwrap();
+    let texts: Vec<String> = load_thread_events(&first_turn.id)
+        .unwrap()
+        .into_iter()
+        .filter_map(|event| match event {
+            ThreadEvent::Message { text, .. } => Some(text),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(texts, ["first", "one"]);
+
+    assert!(clone_thread(&source_id, Some(3)).is_err());
+    assert!(clone_thread(&source_id, Some(0)).is_err());
+    assert_eq!(load_thread_events(&source_id).unwrap(), source_events);
+}
+
+#[test]
+fn test_large_payloads_are_externalized_and_rehydrated() {
+    let _temp = setup_temp_zdx_home();
+
+    let image = "B".repeat(64 * 1024);
+    let mut thread = Thread::with_id(unique_thread_id("blobs")).unwrap();
+    thread.append(&ThreadEvent::user_message("hello")).unwrap();
+    thread
+        .append(&ThreadEvent::tool_result(
+            "t1",
+            json!({"ok": true, "data": {"image": image}}),
+            true,
+        ))
+        .unwrap();
+
+    let content = fs::read_to_string(&thread.path).unwrap();
+    assert!(content.len() < 4 * 1024);
+    assert!(content.contains("zdx-blob:sha256:"));
+
+    let events = thread.read_events().unwrap();
+    let Some(ThreadEvent::ToolResult { output, .. }) = events.last() else {
+        panic!("expected tool result, got {events:?}");
+    };
+    assert_eq!(output["data"]["image"], image.as_str());
+}
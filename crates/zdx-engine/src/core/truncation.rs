@@ -0,0 +1,205 @@
+//! Client-side conversation truncation (`[providers.<id>].truncation`).
+//!
+//! A turn starts at a user message that isn't only tool results and runs
+//! until the next one, so tool calls always stay with their results. Messages
+//! before the first turn, pinned turns (`/pin`), and the latest turn are never
+//! dropped; everything else goes oldest-first.
+
+use std::collections::HashSet;
+
+use crate::config::TruncationStrategy;
+use crate::providers::{ChatContentBlock, ChatMessage, MessageContent};
+
+/// How much history a request may carry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TruncationLimit {
+    /// Keep at most this many unpinned turns (the latest always included).
+    Turns(usize),
+    /// Drop turns until the estimated size fits this many tokens.
+    Tokens(u64),
+}
+
+impl TruncationLimit {
+    /// Resolves the client-side limit for a strategy, or `None` when nothing
+    /// should be dropped locally (`disabled`, or `auto` on a provider that
+    /// truncates server-side, or a model without a known context window).
+    pub fn resolve(
+        strategy: TruncationStrategy,
+        keep_turns: usize,
+        server_side: bool,
+        context_limit: u64,
+        max_tokens: u32,
+    ) -> Option<Self> {
+        match strategy {
+            TruncationStrategy::Disabled => None,
+            TruncationStrategy::LastTurns => Some(Self::Turns(keep_turns)),
+            TruncationStrategy::Auto if server_side || context_limit == 0 => None,
+            TruncationStrategy::Auto => {
+                let reserved = u64::from(max_tokens).min(context_limit / 2);
+                Some(Self::Tokens(context_limit - reserved))
+            }
+        }
+    }
+}
+
+fn is_turn_start(message: &ChatMessage) -> bool {
+    if message.role != "user" {
+        return false;
+    }
+    match &message.content {
+        MessageContent::Text(_) => true,
+        MessageContent::Blocks(blocks) => !blocks
+            .iter()
+            .all(|block| matches!(block, ChatContentBlock::ToolResult(_))),
+    }
+}
+
+/// Returns the index of the message that starts each turn, in order.
+pub fn turn_starts(messages: &[ChatMessage]) -> Vec<usize> {
+    messages
+        .iter()
+        .enumerate()
+        .filter(|(_, message)| is_turn_start(message))
+        .map(|(idx, _)| idx)
+        .collect()
+}
+
+/// Rough token estimate (serialized size / 4), matching what providers see
+/// closely enough to decide what to drop.
+fn estimate_tokens(messages: &[ChatMessage]) -> u64 {
+    messages
+        .iter()
+        .map(|message| serde_json::to_string(message).map_or(0, |json| json.len() as u64 / 4))
+        .sum()
+}
+
+/// Returns `messages` with the oldest unpinned turns dropped to satisfy
+/// `limit`, or `None` when everything fits. `pinned` holds 1-based turn
+/// numbers; `system_tokens` is the estimated size of the system prompt.
+pub fn truncate_messages(
+    messages: &[ChatMessage],
+    pinned: &[usize],
+    limit: TruncationLimit,
+    system_tokens: u64,
+) -> Option<Vec<ChatMessage>> {
+    let starts = turn_starts(messages);
+    let &first = starts.first()?;
+    let prefix = &messages[..first];
+    let turn = |idx: usize| {
+        let end = starts.get(idx + 1).copied().unwrap_or(messages.len());
+        &messages[starts[idx]..end]
+    };
+    let pinned: HashSet<usize> = pinned.iter().copied().collect();
+    let mut dropped: HashSet<usize> = HashSet::new();
+    let droppable = (0..starts.len() - 1).filter(|idx| !pinned.contains(&(idx + 1)));
+
+    match limit {
+        TruncationLimit::Turns(keep) => {
+            let unpinned = (0..starts.len())
+                .filter(|idx| !pinned.contains(&(idx + 1)))
+                .count();
+            dropped.extend(droppable.take(unpinned.saturating_sub(keep.max(1))));
+        }
+        TruncationLimit::Tokens(budget) => {
+            let mut total = system_tokens + estimate_tokens(messages);
+            for idx in droppable {
+                if total <= budget {
+                    break;
+                }
+                total = total.saturating_sub(estimate_tokens(turn(idx)));
+                dropped.insert(idx);
+            }
+        }
+    }
+    if dropped.is_empty() {
+        return None;
+    }
+
+    let mut kept = prefix.to_vec();
+    for idx in (0..starts.len()).filter(|idx| !dropped.contains(idx)) {
+        kept.extend_from_slice(turn(idx));
+    }
+    Some(kept)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::events::ToolOutput;
+    use crate::tools::ToolResult;
+
+    fn conversation(turns: usize) -> Vec<ChatMessage> {
+        let mut messages = Vec::new();
+        for turn in 1..=turns {
+            messages.push(ChatMessage::user(format!("question {turn}")));
+            messages.push(ChatMessage::assistant_blocks(vec![
+                ChatContentBlock::tool_use(format!("t{turn}"), "read", serde_json::json!({})),
+            ]));
+            messages.push(ChatMessage::tool_results(vec![ToolResult::from_output(
+                format!("t{turn}"),
+                &ToolOutput::success(serde_json::json!({})),
+            )]));
+            messages.push(ChatMessage::assistant_text(format!("answer {turn}"), None));
+        }
+        messages
+    }
+
+    fn questions(messages: &[ChatMessage]) -> Vec<usize> {
+        turn_starts(messages)
+            .into_iter()
+            .map(|idx| match &messages[idx].content {
+                MessageContent::Text(text) => text["question ".len()..].parse().unwrap(),
+                MessageContent::Blocks(_) => 0,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_last_turns_keeps_pinned_and_tool_pairs() {
+        let messages = conversation(5);
+        assert_eq!(turn_starts(&messages), vec![0, 4, 8, 12, 16]);
+
+        let kept = truncate_messages(&messages, &[1], TruncationLimit::Turns(2), 0).unwrap();
+        assert_eq!(questions(&kept), vec![1, 4, 5]);
+        assert_eq!(kept.len(), 12);
+        assert_eq!(kept.last(), messages.last());
+
+        assert!(truncate_messages(&messages, &[], TruncationLimit::Turns(5), 0).is_none());
+    }
+
+    #[test]
+    fn test_token_limit_drops_oldest_until_it_fits() {
+        let messages = conversation(4);
+        let total = estimate_tokens(&messages);
+        let per_turn = estimate_tokens(&messages[..4]);
+
+        let kept = truncate_messages(
+            &messages,
+            &[2],
+            TruncationLimit::Tokens(total - per_turn),
+            per_turn,
+        )
+        .unwrap();
+        assert_eq!(questions(&kept), vec![2, 4]);
+
+        // The latest turn is kept even when it alone is over budget.
+        let kept = truncate_messages(&messages, &[], TruncationLimit::Tokens(1), 0).unwrap();
+        assert_eq!(questions(&kept), vec![4]);
+    }
+
+    #[test]
+    fn test_resolve_limit_per_strategy() {
+        assert_eq!(
+            TruncationLimit::resolve(TruncationStrategy::Auto, 20, true, 200_000, 32_000),
+            None
+        );
+        assert_eq!(
+            TruncationLimit::resolve(TruncationStrategy::Auto, 20, false, 200_000, 32_000),
+            Some(TruncationLimit::Tokens(168_000))
+        );
+        assert_eq!(
+            TruncationLimit::resolve(TruncationStrategy::LastTurns, 3, true, 0, 0),
+            Some(TruncationLimit::Turns(3))
+        );
+    }
+}
@@ -3,17 +3,25 @@
 pub mod agent_activity;
 pub mod audio;
 pub mod automations;
+pub mod bash_history;
 pub mod config;
+pub mod config_diff;
+pub mod config_env;
+pub mod config_validation;
 pub mod core;
 pub mod custom_commands;
 pub mod followups;
 pub mod images;
+pub mod lsp;
 pub mod mcp;
 pub mod models;
+pub mod offline;
 pub mod pidfile;
 pub mod prompts;
 pub mod providers;
+pub mod scaffold;
 pub mod skills;
+pub mod startup_profile;
 pub mod subagents;
 #[cfg(test)]
 pub(crate) mod test_support;
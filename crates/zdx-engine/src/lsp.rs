@@ -0,0 +1,493 @@
+//! Minimal Language Server Protocol client backing the `Lsp_*` tools.
+//!
+//! One server process per (project root, server) starts on first use and is
+//! reused for the rest of the process. Messages are JSON-RPC 2.0 framed with
+//! `Content-Length` headers over the server's stdio. Before every request the
+//! target document is re-synced with its full on-disk text, so edits made by
+//! other tools are always visible to the server.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, ChildStdout};
+use tokio::sync::{Notify, oneshot};
+
+use crate::config::{LspConfig, LspServerConfig};
+
+type PendingRequests = HashMap<i64, oneshot::Sender<Result<Value, String>>>;
+
+type ClientMap = HashMap<(PathBuf, String), Arc<LspClient>>;
+
+/// Running clients keyed by project root and server name.
+static CLIENTS: LazyLock<tokio::sync::Mutex<ClientMap>> =
+    LazyLock::new(|| tokio::sync::Mutex::new(HashMap::new()));
+
+/// Returns the running client for `server` in `root`, starting and
+/// initializing it first if needed (or if the previous process exited).
+///
+/// # Errors
+/// Returns an error if the server cannot be spawned or fails to initialize.
+pub async fn client_for(
+    root: &Path,
+    server: &LspServerConfig,
+    config: &LspConfig,
+) -> Result<Arc<LspClient>> {
+    let key = (root.to_path_buf(), server.name.clone());
+    let mut clients = CLIENTS.lock().await;
+    if let Some(client) = clients.get(&key)
+        && client.is_alive()
+    {
+        return Ok(Arc::clone(client));
+    }
+    let client = Arc::new(
+        LspClient::start(
+            root,
+            server,
+            Duration::from_secs(config.timeout_secs.max(1)),
+        )
+        .await?,
+    );
+    clients.insert(key, Arc::clone(&client));
+    Ok(client)
+}
+
+/// State shared between the client and its stdout reader task.
+struct Shared {
+    stdin: tokio::sync::Mutex<ChildStdin>,
+    pending: Mutex<PendingRequests>,
+    /// Latest `publishDiagnostics` per URI, with a counter bumped on each.
+    diagnostics: Mutex<HashMap<String, (u64, Vec<Value>)>>,
+    diagnostics_changed: Notify,
+    alive: AtomicBool,
+}
+
+impl Shared {
+    async fn send(&self, message: &Value) -> Result<()> {
+        let body = message.to_string();
+        let mut stdin = self.stdin.lock().await;
+        stdin
+            .write_all(format!("Content-Length: {}\r\n\r\n{body}", body.len()).as_bytes())
+            .await?;
+        stdin.flush().await?;
+        Ok(())
+    }
+
+    fn fail_pending(&self, reason: &str) {
+        let pending = std::mem::take(&mut *lock(&self.pending));
+        for (_, sender) in pending {
+            let _ = sender.send(Err(reason.to_string()));
+        }
+    }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// A running language server.
+pub struct LspClient {
+    server: String,
+    shared: Arc<Shared>,
+    next_id: AtomicI64,
+    /// Open documents: URI -> (version, last synced text).
+    documents: tokio::sync::Mutex<HashMap<String, (i32, String)>>,
+    timeout: Duration,
+    _child: Child,
+}
+
+impl LspClient {
+    async fn start(root: &Path, server: &LspServerConfig, timeout: Duration) -> Result<Self> {
+        let mut child = tokio::process::Command::new(&server.command)
+            .args(&server.args)
+            .current_dir(root)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .with_context(|| format!("start language server '{}'", server.command))?;
+        let stdin = child.stdin.take().context("language server stdin")?;
+        let stdout = child.stdout.take().context("language server stdout")?;
+
+        let shared = Arc::new(Shared {
+            stdin: tokio::sync::Mutex::new(stdin),
+            pending: Mutex::new(HashMap::new()),
+            diagnostics: Mutex::new(HashMap::new()),
+            diagnostics_changed: Notify::new(),
+            alive: AtomicBool::new(true),
+        });
+        tokio::spawn(read_loop(stdout, Arc::clone(&shared)));
+
+        let client = Self {
+            server: server.name.clone(),
+            shared,
+            next_id: AtomicI64::new(1),
+            documents: tokio::sync::Mutex::new(HashMap::new()),
+            timeout,
+            _child: child,
+        };
+        client.initialize(root).await?;
+        Ok(client)
+    }
+
+    async fn initialize(&self, root: &Path) -> Result<()> {
+        let root_uri = path_to_uri(root)?;
+        let name = root.file_name().map_or_else(
+            || "root".to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        );
+        self.request(
+            "initialize",
+            json!({
+                "processId": std::process::id(),
+                "rootUri": root_uri,
+                "workspaceFolders": [{ "uri": root_uri, "name": name }],
+                "capabilities": {
+                    "textDocument": {
+                        "synchronization": { "didSave": true },
+                        "hover": { "contentFormat": ["markdown", "plaintext"] },
+                        "definition": {},
+                        "references": {},
+                        "rename": {},
+                        "publishDiagnostics": {}
+                    },
+                    "workspace": {
+                        "workspaceEdit": { "documentChanges": true },
+                        "workspaceFolders": true
+                    }
+                }
+            }),
+        )
+        .await
+        .with_context(|| format!("initialize language server '{}'", self.server))?;
+        self.notify("initialized", json!({})).await
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.shared.alive.load(Ordering::SeqCst)
+    }
+
+    /// Sends a request and waits for its result.
+    ///
+    /// # Errors
+    /// Returns an error on a response error, a timeout, or if the server exits.
+    pub async fn request(&self, method: &str, params: Value) -> Result<Value> {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (sender, receiver) = oneshot::channel();
+        lock(&self.shared.pending).insert(id, sender);
+        let message = json!({ "jsonrpc": "2.0", "id": id, "method": method, "params": params });
+        if let Err(error) = self.shared.send(&message).await {
+            lock(&self.shared.pending).remove(&id);
+            return Err(error);
+        }
+        match tokio::time::timeout(self.timeout, receiver).await {
+            Ok(Ok(Ok(result))) => Ok(result),
+            Ok(Ok(Err(message))) => Err(anyhow!("{method}: {message}")),
+            Ok(Err(_closed)) => Err(anyhow!("{method}: language server exited")),
+            Err(_elapsed) => {
+                lock(&self.shared.pending).remove(&id);
+                bail!("{method}: timed out after {}s", self.timeout.as_secs())
+            }
+        }
+    }
+
+    /// Sends a notification.
+    ///
+    /// # Errors
+    /// Returns an error if the server's stdin is closed.
+    pub async fn notify(&self, method: &str, params: Value) -> Result<()> {
+        self.shared
+            .send(&json!({ "jsonrpc": "2.0", "method": method, "params": params }))
+            .await
+    }
+
+    /// Opens `path` or pushes its current on-disk text to the server, and
+    /// returns its URI and text.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read.
+    pub async fn sync_document(&self, path: &Path) -> Result<(String, String)> {
+        let uri = path_to_uri(path)?;
+        let text = tokio::fs::read_to_string(path)
+            .await
+            .with_context(|| format!("read {}", path.display()))?;
+        let mut documents = self.documents.lock().await;
+        match documents.get_mut(&uri) {
+            Some((_, synced)) if *synced == text => return Ok((uri, text)),
+            Some((version, synced)) => {
+                *version += 1;
+                synced.clone_from(&text);
+                let version = *version;
+                self.notify(
+                    "textDocument/didChange",
+                    json!({
+                        "textDocument": { "uri": uri, "version": version },
+                        "contentChanges": [{ "text": text }]
+                    }),
+                )
+                .await?;
+            }
+            None => {
+                documents.insert(uri.clone(), (1, text.clone()));
+                self.notify(
+                    "textDocument/didOpen",
+                    json!({
+                        "textDocument": {
+                            "uri": uri,
+                            "languageId": language_id(path),
+                            "version": 1,
+                            "text": text
+                        }
+                    }),
+                )
+                .await?;
+            }
+        }
+        // The file on disk already matches; some servers only re-check on save.
+        self.notify(
+            "textDocument/didSave",
+            json!({ "textDocument": { "uri": uri } }),
+        )
+        .await?;
+        Ok((uri, text))
+    }
+
+    /// Counter of diagnostics published for `uri` so far.
+    pub fn diagnostics_generation(&self, uri: &str) -> u64 {
+        lock(&self.shared.diagnostics)
+            .get(uri)
+            .map_or(0, |(generation, _)| *generation)
+    }
+
+    /// Waits up to `wait` for diagnostics newer than `after`, then returns
+    /// the latest diagnostics for `uri`.
+    pub async fn diagnostics(&self, uri: &str, after: u64, wait: Duration) -> Vec<Value> {
+        let deadline = tokio::time::Instant::now() + wait;
+        loop {
+            let notified = self.shared.diagnostics_changed.notified();
+            if self.diagnostics_generation(uri) > after {
+                break;
+            }
+            if tokio::time::timeout_at(deadline, notified).await.is_err() {
+                break;
+            }
+        }
+        lock(&self.shared.diagnostics)
+            .get(uri)
+            .map(|(_, diagnostics)| diagnostics.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Reads framed messages from the server until it exits.
+async fn read_loop(stdout: ChildStdout, shared: Arc<Shared>) {
+    let mut reader = BufReader::new(stdout);
+    while let Ok(Some(message)) = read_message(&mut reader).await {
+        handle_message(&shared, message).await;
+    }
+    shared.alive.store(false, Ordering::SeqCst);
+    shared.fail_pending("language server exited");
+    shared.diagnostics_changed.notify_waiters();
+}
+
+async fn read_message<R: tokio::io::AsyncBufRead + Unpin>(reader: &mut R) -> Result<Option<Value>> {
+    let mut content_length = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).await? == 0 {
+            return Ok(None);
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            if content_length.is_some() {
+                break;
+            }
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = Some(value.trim().parse::<usize>()?);
+        }
+    }
+    let mut body = vec![0; content_length.unwrap_or_default()];
+    reader.read_exact(&mut body).await?;
+    Ok(Some(serde_json::from_slice(&body)?))
+}
+
+async fn handle_message(shared: &Shared, message: Value) {
+    let method = message.get("method").and_then(Value::as_str);
+    let id = message.get("id");
+    match (method, id) {
+        // Response to one of our requests.
+        (None, Some(id)) => {
+            let Some(sender) = id.as_i64().and_then(|id| lock(&shared.pending).remove(&id)) else {
+                return;
+            };
+            let result = match message.get("error") {
+                Some(error) => Err(error
+                    .get("message")
+                    .and_then(Value::as_str)
+                    .unwrap_or("unknown error")
+                    .to_string()),
+                None => Ok(message.get("result").cloned().unwrap_or(Value::Null)),
+            };
+            let _ = sender.send(result);
+        }
+        // Server-to-client request: acknowledge so the server doesn't stall.
+        (Some(method), Some(id)) => {
+            let result = match method {
+                "workspace/configuration" => {
+                    let items = message
+                        .pointer("/params/items")
+                        .and_then(Value::as_array)
+                        .map_or(0, Vec::len);
+                    Value::Array(vec![Value::Null; items])
+                }
+                _ => Value::Null,
+            };
+            let reply = json!({ "jsonrpc": "2.0", "id": id, "result": result });
+            if let Err(error) = shared.send(&reply).await {
+                tracing::debug!(%error, method, "Failed to answer language server request");
+            }
+        }
+        (Some("textDocument/publishDiagnostics"), None) => {
+            let Some(uri) = message.pointer("/params/uri").and_then(Value::as_str) else {
+                return;
+            };
+            let diagnostics = message
+                .pointer("/params/diagnostics")
+                .and_then(Value::as_array)
+                .cloned()
+                .unwrap_or_default();
+            let mut all = lock(&shared.diagnostics);
+            let entry = all.entry(uri.to_string()).or_default();
+            entry.0 += 1;
+            entry.1 = diagnostics;
+            drop(all);
+            shared.diagnostics_changed.notify_waiters();
+        }
+        _ => {}
+    }
+}
+
+/// LSP `languageId` for `path`.
+fn language_id(path: &Path) -> String {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    match extension.as_str() {
+        "rs" => "rust",
+        "py" => "python",
+        "ts" => "typescript",
+        "tsx" => "typescriptreact",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "javascriptreact",
+        "go" => "go",
+        "c" | "h" => "c",
+        "cc" | "cpp" | "hpp" => "cpp",
+        other => other,
+    }
+    .to_string()
+}
+
+/// `file://` URI for an absolute path.
+///
+/// # Errors
+/// Returns an error if `path` is not absolute.
+pub fn path_to_uri(path: &Path) -> Result<String> {
+    url::Url::from_file_path(path)
+        .map(String::from)
+        .map_err(|()| anyhow!("not an absolute path: {}", path.display()))
+}
+
+/// Local path for a `file://` URI.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    url::Url::parse(uri).ok()?.to_file_path().ok()
+}
+
+/// UTF-16 offset (LSP `character`) of the 1-based `column` on `line`.
+pub fn column_to_utf16(line: &str, column: usize) -> usize {
+    line.chars()
+        .take(column.saturating_sub(1))
+        .map(char::len_utf16)
+        .sum()
+}
+
+/// 1-based column of the UTF-16 offset `character` on `line`.
+pub fn utf16_to_column(line: &str, character: usize) -> usize {
+    let mut units = 0;
+    let mut column = 1;
+    for ch in line.chars() {
+        if units >= character {
+            break;
+        }
+        units += ch.len_utf16();
+        column += 1;
+    }
+    column
+}
+
+/// Byte offset in `text` of an LSP position (0-based line, UTF-16 character).
+/// Positions past the end clamp to the end of the line or text.
+pub fn position_to_offset(text: &str, line: usize, character: usize) -> usize {
+    let mut offset = 0;
+    for (index, line_text) in text.split_inclusive('\n').enumerate() {
+        if index == line {
+            let content = line_text.trim_end_matches(['\n', '\r']);
+            let mut units = 0;
+            for (byte, ch) in content.char_indices() {
+                if units >= character {
+                    return offset + byte;
+                }
+                units += ch.len_utf16();
+            }
+            return offset + content.len();
+        }
+        offset += line_text.len();
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_columns_and_positions_through_utf16() {
+        let line = "let é = \"😀x\";";
+        assert_eq!(column_to_utf16(line, 1), 0);
+        assert_eq!(column_to_utf16(line, 11), 11);
+        assert_eq!(utf16_to_column(line, 11), 11);
+
+        let text = "fn a() {}\nlet 😀 = 1;\n";
+        assert_eq!(position_to_offset(text, 1, 0), 10);
+        assert_eq!(&text[position_to_offset(text, 1, 6)..], " = 1;\n");
+        assert_eq!(position_to_offset(text, 1, 99), text.len() - 1);
+        assert_eq!(position_to_offset(text, 5, 0), text.len());
+    }
+
+    #[tokio::test]
+    async fn reads_content_length_framed_messages() {
+        let body = r#"{"jsonrpc":"2.0","id":1,"result":null}"#;
+        let stream = format!(
+            "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc\r\n\r\n{body}",
+            body.len()
+        );
+        let mut reader = BufReader::new(stream.as_bytes());
+        let message = read_message(&mut reader).await.unwrap().unwrap();
+        assert_eq!(message["id"], json!(1));
+        assert!(read_message(&mut reader).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,130 @@
+//! Offline mode (`zdx --offline` / `offline = true`).
+//!
+//! When offline, network tools (`Tool::requires_network`) are dropped from
+//! every turn's tool list, network-only features (transcription, speech,
+//! image generation, the Telegram bot) fail fast, and model requests are only
+//! allowed when the selected provider's base URL points at this machine
+//! (e.g. LM Studio or a local `[providers.custom.<name>]` server).
+//!
+//! The CLI flag sets `ZDX_OFFLINE=1`, which the `ZDX_*` config overrides map
+//! to `offline = true`, so subagent child processes inherit it.
+
+use anyhow::{Result, bail};
+use url::{Host, Url};
+
+use crate::config::Config;
+use crate::providers::resolve_provider;
+
+/// Environment variable set by `zdx --offline`.
+pub const OFFLINE_ENV_VAR: &str = "ZDX_OFFLINE";
+
+/// Fails with a clear error when `feature` needs the network and offline mode
+/// is on.
+///
+/// # Errors
+/// Returns an error in offline mode.
+pub fn ensure_online(config: &Config, feature: &str) -> Result<()> {
+    if config.offline {
+        bail!("{feature} is unavailable in offline mode (--offline / `offline = true`)");
+    }
+    Ok(())
+}
+
+/// Fails unless the configured model is served from this machine, when
+/// offline mode is on.
+///
+/// # Errors
+/// Returns an error in offline mode when the provider is remote or its base
+/// URL cannot be resolved.
+pub fn ensure_local_model(config: &Config) -> Result<()> {
+    if !config.offline {
+        return Ok(());
+    }
+
+    let model = &config.model;
+    let base_url = if let Some((custom, _, _)) = config.providers.custom_provider_for_model(model) {
+        Some(custom.effective_base_url()?)
+    } else if crate::providers::registry::lookup(model).is_some() {
+        None
+    } else {
+        let provider = resolve_provider(model).kind;
+        let provider_config = config.providers.get(provider);
+        Some(provider.resolve_base_url(provider_config.base_url.as_deref())?)
+    };
+
+    match base_url {
+        Some(url) if is_local_url(&url) => Ok(()),
+        Some(url) => bail!(
+            "Offline mode: model '{model}' is served from {url}. Select a local provider (e.g. lmstudio:<model> or a localhost custom provider), or drop --offline."
+        ),
+        None => bail!(
+            "Offline mode: model '{model}' uses a registered provider that cannot be verified as local. Select a local provider, or drop --offline."
+        ),
+    }
+}
+
+/// Whether `url` points at a loopback host (`localhost`, `127.0.0.0/8`, `::1`).
+pub fn is_local_url(url: &str) -> bool {
+    let Ok(url) = Url::parse(url.trim()) else {
+        return false;
+    };
+    match url.host() {
+        Some(Host::Domain(domain)) => {
+            let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+            domain == "localhost" || domain.ends_with(".localhost")
+        }
+        Some(Host::Ipv4(ip)) => ip.is_loopback(),
+        Some(Host::Ipv6(ip)) => ip.is_loopback(),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_local_url() {
+        assert!(is_local_url("http://127.0.0.1:1234/v1"));
+        assert!(is_local_url("http://localhost:11434/v1"));
+        assert!(is_local_url("http://[::1]:8080"));
+        assert!(!is_local_url("https://api.openai.com/v1"));
+        assert!(!is_local_url("http://192.168.1.10:1234/v1"));
+        assert!(!is_local_url("not a url"));
+    }
+
+    #[test]
+    fn test_ensure_local_model_gates_remote_providers() {
+        use crate::config::CustomProviderConfig;
+
+        let custom = |base_url: &str| CustomProviderConfig {
+            base_url: base_url.to_string(),
+            api_key: None,
+            api_key_env: None,
+            models: vec!["model-a".to_string()],
+            http: crate::config::ProviderHttpConfig::default(),
+            extra: serde_json::Map::new(),
+        };
+        let mut config = Config {
+            model: "remote:model-a".to_string(),
+            ..Config::default()
+        };
+        config
+            .providers
+            .custom
+            .insert("remote".to_string(), custom("https://llm.example.com/v1"));
+        config
+            .providers
+            .custom
+            .insert("local".to_string(), custom("http://localhost:8080/v1"));
+        assert!(ensure_local_model(&config).is_ok());
+
+        config.offline = true;
+        let err = ensure_local_model(&config).unwrap_err().to_string();
+        assert!(err.contains("https://llm.example.com/v1"));
+        assert!(ensure_online(&config, "Audio transcription").is_err());
+
+        config.model = "local:model-a".to_string();
+        assert!(ensure_local_model(&config).is_ok());
+    }
+}
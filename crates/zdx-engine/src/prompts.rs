@@ -4,20 +4,28 @@
 pub use zdx_assets::AUTOMATION_HARNESS_INSTRUCTION_LAYER;
 /// Instruction layer for interactive TUI chat.
 pub use zdx_assets::CHAT_INSTRUCTION_LAYER;
+/// System prompt template for `/duel` speakers (shared with TUI).
+pub use zdx_assets::DUEL_PROMPT_TEMPLATE;
 /// Instruction layer for non-interactive exec mode.
 pub use zdx_assets::EXEC_INSTRUCTION_LAYER;
 /// Prompt template for handoff generation (shared with TUI).
 pub use zdx_assets::HANDOFF_PROMPT_TEMPLATE;
 /// Shared identity prompt for ZDX-coded agent surfaces.
 pub use zdx_assets::IDENTITY_PROMPT_TEMPLATE;
+/// Prompt template for `zdx init` AGENTS.md generation.
+pub use zdx_assets::INIT_PROMPT_TEMPLATE;
 /// Prompt template for the `/prompt-builder` slash command (shared with TUI).
 pub use zdx_assets::PROMPT_BUILDER_PROMPT_TEMPLATE;
+/// Prompt template for the `/improve` prompt rewrite (shared with TUI).
+pub use zdx_assets::PROMPT_IMPROVE_PROMPT_TEMPLATE;
 /// Prompt template for read thread tool (shared with tool execution).
 pub use zdx_assets::READ_THREAD_PROMPT_TEMPLATE;
 /// Prompt template for system prompt assembly (`MiniJinja`).
 pub use zdx_assets::SYSTEM_PROMPT_TEMPLATE;
 /// Instruction layer for Telegram bot.
 pub use zdx_assets::TELEGRAM_INSTRUCTION_LAYER;
+/// Prompt template for idle-time thread summaries (shared with TUI).
+pub use zdx_assets::THREAD_SUMMARY_PROMPT_TEMPLATE;
 /// Prompt template for auto thread-title generation (shared with TUI).
 pub use zdx_assets::THREAD_TITLE_PROMPT_TEMPLATE;
 /// Prompt template for thread TLDR/recap generation (shared with TUI).
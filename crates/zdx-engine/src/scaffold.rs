@@ -0,0 +1,222 @@
+//! `zdx new` scaffolding: commented starter files for automations, skills,
+//! and custom tools.
+//!
+//! Templates live in `zdx-assets/scaffolds/` with a `{{NAME}}` placeholder.
+//! Automations and skills are new files and never overwrite; a custom tool is
+//! a `[[tools.custom]]` block appended to `config.toml`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use zdx_assets::{AUTOMATION_SCAFFOLD, CUSTOM_TOOL_SCAFFOLD, SKILL_SCAFFOLD};
+
+use crate::config::paths;
+use crate::tools::custom::is_valid_tool_name;
+
+/// What `zdx new` creates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScaffoldKind {
+    /// `<ZDX_HOME>/automations/<name>.md`
+    Automation,
+    /// `<root>/.zdx/skills/<name>/SKILL.md`, or `<ZDX_HOME>/skills/` when global.
+    Skill { global: bool },
+    /// `[[tools.custom]]` block in `<ZDX_HOME>/config.toml`.
+    Tool,
+}
+
+/// Renders the template for `kind` with `name` filled in.
+#[must_use]
+pub fn render(kind: ScaffoldKind, name: &str) -> String {
+    let template = match kind {
+        ScaffoldKind::Automation => AUTOMATION_SCAFFOLD,
+        ScaffoldKind::Skill { .. } => SKILL_SCAFFOLD,
+        ScaffoldKind::Tool => CUSTOM_TOOL_SCAFFOLD,
+    };
+    template.replace("{{NAME}}", name)
+}
+
+/// Checks `name` against the rules the loader for `kind` enforces.
+///
+/// # Errors
+/// Returns an error describing the first rule `name` breaks.
+pub fn validate_name(kind: ScaffoldKind, name: &str) -> Result<()> {
+    match kind {
+        ScaffoldKind::Automation => {
+            if name.is_empty()
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                bail!("Automation name '{name}' must use only letters, digits, `_`, `-`");
+            }
+        }
+        ScaffoldKind::Skill { .. } => {
+            if name.is_empty() {
+                bail!("Skill name cannot be empty");
+            }
+            crate::skills::validate_name(name).map_err(anyhow::Error::msg)?;
+        }
+        ScaffoldKind::Tool => {
+            if !is_valid_tool_name(name) {
+                bail!("Tool name '{name}' must use only letters, digits, `_`, `-`");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Where `kind` is written for `name`.
+fn target_path_in(kind: ScaffoldKind, name: &str, root: &Path, zdx_home: &Path) -> PathBuf {
+    match kind {
+        ScaffoldKind::Automation => zdx_home.join("automations").join(format!("{name}.md")),
+        ScaffoldKind::Skill { global: true } => zdx_home.join("skills").join(name).join("SKILL.md"),
+        ScaffoldKind::Skill { global: false } => {
+            root.join(".zdx").join("skills").join(name).join("SKILL.md")
+        }
+        ScaffoldKind::Tool => zdx_home.join("config.toml"),
+    }
+}
+
+/// Writes the scaffold for `name` and returns the path written.
+///
+/// # Errors
+/// Returns an error if the name is invalid, the automation/skill already
+/// exists, a custom tool with that name is already configured, or I/O fails.
+pub fn create(kind: ScaffoldKind, name: &str, root: &Path) -> Result<PathBuf> {
+    create_in(kind, name, root, &paths::zdx_home())
+}
+
+fn create_in(kind: ScaffoldKind, name: &str, root: &Path, zdx_home: &Path) -> Result<PathBuf> {
+    let name = name.trim();
+    validate_name(kind, name)?;
+    let path = target_path_in(kind, name, root, zdx_home);
+    let content = render(kind, name);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("create directory {}", parent.display()))?;
+    }
+
+    if kind == ScaffoldKind::Tool {
+        append_custom_tool(&path, name, &content)?;
+    } else {
+        if path.exists() {
+            bail!("{} already exists", path.display());
+        }
+        fs::write(&path, content).with_context(|| format!("write {}", path.display()))?;
+    }
+    Ok(path)
+}
+
+/// Appends the tool block to `config_path`, refusing duplicates and anything
+/// that would leave the file unparsable.
+fn append_custom_tool(config_path: &Path, name: &str, block: &str) -> Result<()> {
+    let existing = match fs::read_to_string(config_path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("read {}", config_path.display()));
+        }
+    };
+
+    let parsed: toml::Table =
+        toml::from_str(&existing).with_context(|| format!("parse {}", config_path.display()))?;
+    let configured = parsed
+        .get("tools")
+        .and_then(|tools| tools.get("custom"))
+        .and_then(toml::Value::as_array)
+        .is_some_and(|tools| {
+            tools.iter().any(|tool| {
+                tool.get("name")
+                    .and_then(toml::Value::as_str)
+                    .is_some_and(|n| n.trim().eq_ignore_ascii_case(name))
+            })
+        });
+    if configured {
+        bail!(
+            "A custom tool named '{name}' is already configured in {}",
+            config_path.display()
+        );
+    }
+
+    let mut updated = existing;
+    if !updated.is_empty() && !updated.ends_with('\n') {
+        updated.push('\n');
+    }
+    updated.push_str(block);
+    toml::from_str::<toml::Table>(&updated).with_context(|| {
+        format!(
+            "{} defines `tools.custom` in a form `[[tools.custom]]` cannot extend",
+            config_path.display()
+        )
+    })?;
+    fs::write(config_path, updated).with_context(|| format!("write {}", config_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::config::Config;
+    use crate::skills::{SkillSource, load_skills_from_dir};
+
+    #[test]
+    fn automation_and_skill_templates_load() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path().join("home");
+        let root = temp.path().join("project");
+
+        create_in(ScaffoldKind::Automation, "daily-digest", &root, &home).unwrap();
+        let automations =
+            crate::automations::discover_with_user_dir(&root, &home.join("automations")).unwrap();
+        assert_eq!(automations.len(), 1);
+        assert_eq!(automations[0].name, "daily-digest");
+        assert_eq!(automations[0].schedule.as_deref(), Some("0 9 * * 1-5"));
+        let again = create_in(ScaffoldKind::Automation, "daily-digest", &root, &home);
+        assert!(again.unwrap_err().to_string().contains("already exists"));
+
+        let path = create_in(
+            ScaffoldKind::Skill { global: false },
+            "release-notes",
+            &root,
+            &home,
+        )
+        .unwrap();
+        assert_eq!(path, root.join(".zdx/skills/release-notes/SKILL.md"));
+        let loaded = load_skills_from_dir(&root.join(".zdx/skills"), SkillSource::ZdxProject);
+        assert!(loaded.warnings.is_empty(), "{:?}", loaded.warnings);
+        assert_eq!(loaded.skills.len(), 1);
+        assert_eq!(loaded.skills[0].name, "release-notes");
+
+        let invalid = create_in(
+            ScaffoldKind::Skill { global: true },
+            "Bad_Name",
+            &root,
+            &home,
+        );
+        assert!(invalid.is_err());
+    }
+
+    #[test]
+    fn custom_tool_is_appended_to_config_once() {
+        let temp = TempDir::new().unwrap();
+        let home = temp.path();
+        fs::write(home.join("config.toml"), "model = \"x:y\"").unwrap();
+
+        let path = create_in(ScaffoldKind::Tool, "Echo", temp.path(), home).unwrap();
+        let config = Config::load_from(&path).unwrap();
+        assert_eq!(config.model, "x:y");
+        assert_eq!(config.tools.custom.len(), 1);
+        assert_eq!(config.tools.custom[0].name, "Echo");
+        assert_eq!(config.tools.custom[0].command, "cat");
+
+        let again = create_in(ScaffoldKind::Tool, "echo", temp.path(), home);
+        assert!(
+            again
+                .unwrap_err()
+                .to_string()
+                .contains("already configured")
+        );
+    }
+}
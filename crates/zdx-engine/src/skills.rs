@@ -787,7 +787,7 @@ fn strip_utf8_bom(content: &str) -> &str {
     content.strip_prefix('\u{feff}').unwrap_or(content)
 }
 
-fn validate_name(name: &str) -> Result<(), String> {
+pub(crate) fn validate_name(name: &str) -> Result<(), String> {
     if name.len() > 64 {
         return Err(format!("Skill name '{name}' exceeds 64 characters"));
     }
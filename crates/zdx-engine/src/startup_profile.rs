@@ -0,0 +1,124 @@
+//! Startup phase timings (`zdx --profile-startup`).
+//!
+//! When enabled, startup code records how long each phase took (config load,
+//! context discovery, skills, first paint, ...). Recording is a no-op until
+//! [`enable`] is called and stops at [`finish`], so prompt refreshes later in
+//! the session don't pollute the report.
+
+use std::fmt::Write as _;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+struct Profile {
+    started: Instant,
+    phases: Vec<(&'static str, Duration)>,
+    milestones: Vec<(&'static str, Duration)>,
+    finished: bool,
+}
+
+static PROFILE: OnceLock<Mutex<Profile>> = OnceLock::new();
+
+/// Starts recording. Milestones are measured from the first call.
+pub fn enable() {
+    PROFILE.get_or_init(|| {
+        Mutex::new(Profile {
+            started: Instant::now(),
+            phases: Vec::new(),
+            milestones: Vec::new(),
+            finished: false,
+        })
+    });
+}
+
+/// True while startup phases are being recorded.
+pub fn is_recording() -> bool {
+    with_profile(|_| ()).is_some()
+}
+
+/// Runs `f` and records its duration as `phase`.
+pub fn time<T>(phase: &'static str, f: impl FnOnce() -> T) -> T {
+    if !is_recording() {
+        return f();
+    }
+    let started = Instant::now();
+    let value = f();
+    record(phase, started.elapsed());
+    value
+}
+
+/// Records a phase duration measured by the caller.
+pub fn record(phase: &'static str, elapsed: Duration) {
+    with_profile(|profile| profile.phases.push((phase, elapsed)));
+}
+
+/// Records `milestone` (e.g. `first_paint`) at the time since [`enable`].
+pub fn mark(milestone: &'static str) {
+    with_profile(|profile| {
+        let at = profile.started.elapsed();
+        profile.milestones.push((milestone, at));
+    });
+}
+
+/// Stops recording; later phases and milestones are ignored.
+pub fn finish() {
+    with_profile(|profile| profile.finished = true);
+}
+
+/// Formats the recorded phases and milestones, or `None` when profiling is
+/// off.
+pub fn report() -> Option<String> {
+    let profile = PROFILE.get()?.lock().ok()?;
+    Some(format_report(&profile.phases, &profile.milestones))
+}
+
+fn with_profile<T>(f: impl FnOnce(&mut Profile) -> T) -> Option<T> {
+    let mut profile = PROFILE.get()?.lock().ok()?;
+    (!profile.finished).then(|| f(&mut profile))
+}
+
+fn format_report(
+    phases: &[(&'static str, Duration)],
+    milestones: &[(&'static str, Duration)],
+) -> String {
+    let width = phases
+        .iter()
+        .chain(milestones)
+        .map(|(name, _)| name.len())
+        .max()
+        .unwrap_or(0);
+    let mut out = String::from("Startup profile:\n");
+    for (name, elapsed) in phases {
+        let _ = writeln!(out, "  {name:<width$}  {:>8.1} ms", millis(*elapsed));
+    }
+    for (name, at) in milestones {
+        let _ = writeln!(out, "  {name:<width$}  @{:>7.1} ms", millis(*at));
+    }
+    out
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_report_aligns_phases_and_milestones() {
+        let report = format_report(
+            &[
+                ("config.load", Duration::from_micros(1500)),
+                ("context.skills", Duration::from_millis(42)),
+            ],
+            &[("first_paint", Duration::from_millis(80))],
+        );
+        assert_eq!(
+            report,
+            "Startup profile:\n  \
+             config.load          1.5 ms\n  \
+             context.skills      42.0 ms\n  \
+             first_paint     @   80.0 ms\n"
+        );
+    }
+}
@@ -0,0 +1,96 @@
+//! Ask user tool.
+//!
+//! Pauses the turn until the user answers a question through the surface
+//! (TUI overlay, Telegram reply). See `core::ask_user`.
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use super::{ToolContext, ToolDefinition};
+use crate::core::events::ToolOutput;
+
+/// Returns the tool definition for the ask user tool.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "Ask_User".to_string(),
+        description: "Ask the user a question and wait for their reply. Use only when you cannot continue without information or a decision that only the user can give (e.g. ambiguous requirements, choosing between incompatible options); do not ask for confirmation of routine steps or things you can find out yourself. Ask one specific question at a time. Unavailable in non-interactive runs (`zdx exec`, subagents), where it fails immediately: proceed with your best judgment and state your assumptions."
+            .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "question": {
+                    "type": "string",
+                    "description": "The question to show the user."
+                }
+            },
+            "required": ["question"],
+            "additionalProperties": false
+        }),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AskUserInput {
+    question: String,
+}
+
+/// Asks the user through `ctx.ask_user` and returns `{ "answer": ... }`.
+pub async fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
+    let question = match serde_json::from_value::<AskUserInput>(input.clone()) {
+        Ok(input) if !input.question.trim().is_empty() => input.question.trim().to_string(),
+        Ok(_) => {
+            return ToolOutput::failure("invalid_input", "question must not be empty", None);
+        }
+        Err(err) => {
+            return ToolOutput::failure("invalid_input", format!("Invalid input: {err}"), None);
+        }
+    };
+    let Some(handle) = ctx.ask_user.as_ref() else {
+        return ToolOutput::failure(
+            "ask_user_unavailable",
+            "Ask_User is unavailable in this non-interactive run; no user can answer.",
+            Some("Proceed with your best judgment and state your assumptions.".to_string()),
+        );
+    };
+
+    let tool_use_id = ctx.tool_use_id.as_deref().unwrap_or_default();
+    let cancel = ctx.cancel.clone().unwrap_or_default();
+    let reply = tokio::select! {
+        () = cancel.cancelled() => Err("The turn was interrupted before the user answered.".to_string()),
+        reply = handle.ask(tool_use_id, &question) => reply,
+    };
+    match reply {
+        Ok(answer) => ToolOutput::success(json!({ "answer": answer })),
+        Err(reason) => ToolOutput::failure("no_answer", reason, None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn fails_fast_without_a_surface() {
+        let ctx = ToolContext::new(PathBuf::from("."), None);
+        let output = execute(&json!({"question": "Which branch?"}), &ctx).await;
+        let text = serde_json::to_string(&output).unwrap();
+        assert!(text.contains("ask_user_unavailable"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn returns_the_answer() {
+        let (handle, mut rx) = crate::core::ask_user::channel();
+        let mut ctx = ToolContext::new(PathBuf::from("."), None);
+        ctx.ask_user = Some(handle);
+        tokio::spawn(async move {
+            let question = rx.recv().await.expect("question");
+            question.answer("main");
+        });
+
+        let output = execute(&json!({"question": "Which branch?"}), &ctx).await;
+        let text = serde_json::to_string(&output).unwrap();
+        assert!(text.contains("\"answer\":\"main\""), "{text}");
+    }
+}
@@ -0,0 +1,546 @@
+//! The `Check` tool: a configured build/lint/typecheck command (`[tools.check]`).
+//!
+//! The command runs in the project root and its output is parsed into
+//! compact `{file, line, column, severity, message, code}` diagnostics.
+//! Recognized formats: cargo/rustc JSON (`--message-format=json`), `ESLint`
+//! JSON (`--format json`), `tsc` (`file(line,col): error TS…`), and the
+//! common `file:line:col: message` shape. With `auto = true` the agent loop
+//! also runs it after file edits and feeds failures back to the model.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::custom::read_capped;
+use super::{Tool, ToolContext, ToolDefinition, ToolFuture, ToolRegistry};
+use crate::config::CheckToolConfig;
+use crate::core::events::ToolOutput;
+use crate::providers::{ChatMessage, MessageContent};
+
+/// Tool name exposed to the model.
+pub const CHECK_TOOL_NAME: &str = "Check";
+
+/// Message `phase` marking check failures injected after file edits.
+pub const CHECK_PHASE: &str = "check";
+
+/// Output read from the command for parsing; cargo JSON is verbose.
+const MAX_OUTPUT_BYTES: usize = 4 * 1024 * 1024;
+
+/// Raw output kept when no diagnostic could be recognized.
+pub(super) const OUTPUT_TAIL_BYTES: usize = 4 * 1024;
+
+/// Registers the `Check` tool when `[tools.check]` is configured.
+pub fn register_check_tool(registry: &mut ToolRegistry, config: Option<&CheckToolConfig>) {
+    let Some(config) = config else {
+        return;
+    };
+    if config.command.trim().is_empty() {
+        tracing::warn!("Skipping check tool: command is empty");
+        return;
+    }
+    registry.register_user_tool(CheckTool {
+        config: config.clone(),
+    });
+}
+
+struct CheckTool {
+    config: CheckToolConfig,
+}
+
+impl Tool for CheckTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: CHECK_TOOL_NAME.to_string(),
+            description: format!(
+                "Run the project's check command (`{}`) and return its diagnostics \
+                 (file, line, message). Use it after editing code to verify the build.",
+                self.config.command
+            ),
+            input_schema: json!({ "type": "object", "properties": {} }),
+        }
+    }
+
+    fn execute(&self, _input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let config = self.config.clone();
+        let root = ctx.root.clone();
+        Box::pin(async move {
+            match run_check(&config, &root).await {
+                Ok(report) => report.to_output(&config),
+                Err(failure) => failure,
+            }
+        })
+    }
+}
+
+/// Severity of a diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+/// One diagnostic reported by the check command.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnostic {
+    pub file: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<u32>,
+    pub severity: Severity,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+}
+
+impl Diagnostic {
+    /// `file:line:col: severity[code]: message` on one line.
+    fn compact(&self) -> String {
+        let mut location = self.file.clone();
+        for part in [self.line, self.column].into_iter().flatten() {
+            let _ = write!(location, ":{part}");
+        }
+        let severity = match self.severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+        let code = self
+            .code
+            .as_deref()
+            .map(|code| format!("[{code}]"))
+            .unwrap_or_default();
+        format!("{location}: {severity}{code}: {}", self.message)
+    }
+}
+
+/// Result of one check run.
+#[derive(Debug, Clone)]
+pub struct CheckReport {
+    pub exit_code: i32,
+    /// Parsed diagnostics, errors first.
+    pub diagnostics: Vec<Diagnostic>,
+    /// Tail of the raw output, kept when no diagnostic was recognized.
+    pub output_tail: String,
+}
+
+impl CheckReport {
+    pub fn passed(&self) -> bool {
+        self.exit_code == 0 && self.count(Severity::Error) == 0
+    }
+
+    fn count(&self, severity: Severity) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diagnostic| diagnostic.severity == severity)
+            .count()
+    }
+
+    fn summary(&self) -> String {
+        format!(
+            "{} error(s), {} warning(s)",
+            self.count(Severity::Error),
+            self.count(Severity::Warning)
+        )
+    }
+
+    /// Tool result: a failing check is still a successful tool call, so the
+    /// model reads the diagnostics instead of a generic error.
+    fn to_output(&self, config: &CheckToolConfig) -> ToolOutput {
+        let shown = config.max_diagnostics.min(self.diagnostics.len());
+        let mut data = json!({
+            "passed": self.passed(),
+            "exit_code": self.exit_code,
+            "errors": self.count(Severity::Error),
+            "warnings": self.count(Severity::Warning),
+            "diagnostics": &self.diagnostics[..shown],
+            "truncated": shown < self.diagnostics.len(),
+        });
+        if !self.output_tail.is_empty() {
+            data["output"] = json!(self.output_tail);
+        }
+        ToolOutput::success(data)
+    }
+
+    /// User message injected after an edit round whose check failed.
+    pub fn feedback_message(&self, config: &CheckToolConfig) -> ChatMessage {
+        let mut text = format!(
+            "The automatic check (`{}`) failed after your last edits (exit code {}, {}). \
+             Fix these before continuing:\n",
+            config.command,
+            self.exit_code,
+            self.summary()
+        );
+        let shown = config.max_diagnostics.min(self.diagnostics.len());
+        for diagnostic in &self.diagnostics[..shown] {
+            text.push('\n');
+            text.push_str(&diagnostic.compact());
+        }
+        if shown < self.diagnostics.len() {
+            let _ = write!(text, "\n… {} more omitted", self.diagnostics.len() - shown);
+        }
+        if !self.output_tail.is_empty() {
+            let _ = write!(text, "\n```\n{}\n```", self.output_tail.trim_end());
+        }
+        ChatMessage {
+            role: "user".to_string(),
+            phase: Some(CHECK_PHASE.to_string()),
+            content: MessageContent::Text(text),
+        }
+    }
+
+    /// One-line summary for surfaces (e.g. a transcript notice).
+    pub fn notice(&self) -> String {
+        format!("Check failed: {}", self.summary())
+    }
+}
+
+/// Returns true when `message` was injected by an automatic check.
+pub fn is_check_message(message: &ChatMessage) -> bool {
+    message.role == "user" && message.phase.as_deref() == Some(CHECK_PHASE)
+}
+
+/// Returns true when a successful call to `tool_name` may have edited files.
+pub fn is_edit_tool(tool_name: &str) -> bool {
+    [
+        "write",
+        "edit",
+        "apply_patch",
+        "lsp_rename",
+        "notebook_edit",
+    ]
+    .contains(&tool_name.to_ascii_lowercase().as_str())
+}
+
+/// Runs the check command in `root` and parses its output.
+///
+/// # Errors
+/// Returns a failure [`ToolOutput`] when the command cannot be spawned or
+/// times out.
+pub async fn run_check(config: &CheckToolConfig, root: &Path) -> Result<CheckReport, ToolOutput> {
+    let (exit_code, output) =
+        run_project_command("Check", &config.command, root, config.timeout_secs).await?;
+    let diagnostics = parse_diagnostics(&output);
+    let output_tail = if diagnostics.is_empty() && exit_code != 0 {
+        tail(output.trim(), OUTPUT_TAIL_BYTES).to_string()
+    } else {
+        String::new()
+    };
+    Ok(CheckReport {
+        exit_code,
+        diagnostics,
+        output_tail,
+    })
+}
+
+/// Runs a configured project command in `root` and returns its exit code
+/// and combined stdout + stderr. `label` names the command in errors.
+pub(super) async fn run_project_command(
+    label: &str,
+    command: &str,
+    root: &Path,
+    timeout_secs: u64,
+) -> Result<(i32, String), ToolOutput> {
+    let mut cmd = zdx_tools::bash::shell_command(command);
+    cmd.current_dir(root)
+        .env("TERM", "dumb")
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let mut child = cmd.spawn().map_err(|e| {
+        ToolOutput::failure(
+            "spawn_error",
+            format!("Failed to execute {label} command '{command}'"),
+            Some(format!("Error: {e}")),
+        )
+    })?;
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let io = async {
+        tokio::join!(
+            read_capped(stdout, MAX_OUTPUT_BYTES),
+            read_capped(stderr, MAX_OUTPUT_BYTES),
+            child.wait(),
+        )
+    };
+
+    let ((stdout, _), (stderr, _), status) = if timeout_secs > 0 {
+        tokio::time::timeout(Duration::from_secs(timeout_secs), io)
+            .await
+            .map_err(|_elapsed| {
+                ToolOutput::failure(
+                    "timeout",
+                    format!("{label} timed out after {timeout_secs} seconds"),
+                    None,
+                )
+            })?
+    } else {
+        io.await
+    };
+    let exit_code = status
+        .map_err(|e| {
+            ToolOutput::failure(
+                "wait_error",
+                format!("Failed to wait for {label} command '{command}'"),
+                Some(format!("Error: {e}")),
+            )
+        })?
+        .code()
+        .unwrap_or(-1);
+    Ok((exit_code, format!("{stdout}\n{stderr}")))
+}
+
+/// Last `max_bytes` of `text`, on a character boundary.
+pub(super) fn tail(text: &str, max_bytes: usize) -> &str {
+    let mut start = text.len().saturating_sub(max_bytes);
+    while !text.is_char_boundary(start) {
+        start += 1;
+    }
+    &text[start..]
+}
+
+/// Parses diagnostics from any of the supported output formats, deduplicated
+/// and sorted errors first (otherwise in output order).
+pub fn parse_diagnostics(output: &str) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let trimmed = output.trim();
+    if trimmed.starts_with('[')
+        && let Ok(Value::Array(files)) = serde_json::from_str::<Value>(trimmed)
+    {
+        diagnostics.extend(files.iter().flat_map(eslint_diagnostics));
+    } else {
+        for line in output.lines() {
+            let line = line.trim_end();
+            let diagnostic = if line.starts_with('{') {
+                serde_json::from_str::<Value>(line)
+                    .ok()
+                    .and_then(|value| cargo_diagnostic(&value))
+            } else {
+                tsc_diagnostic(line).or_else(|| location_diagnostic(line))
+            };
+            diagnostics.extend(diagnostic);
+        }
+    }
+
+    let mut unique: Vec<Diagnostic> = Vec::with_capacity(diagnostics.len());
+    for diagnostic in diagnostics {
+        if !unique.contains(&diagnostic) {
+            unique.push(diagnostic);
+        }
+    }
+    unique.sort_by_key(|diagnostic| diagnostic.severity);
+    unique
+}
+
+/// `{"reason":"compiler-message","message":{...}}` from cargo.
+fn cargo_diagnostic(value: &Value) -> Option<Diagnostic> {
+    if value.get("reason").and_then(Value::as_str) != Some("compiler-message") {
+        return None;
+    }
+    let message = value.get("message")?;
+    let severity = match message.get("level").and_then(Value::as_str)? {
+        level if level.starts_with("error") => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => return None,
+    };
+    let span = message
+        .get("spans")
+        .and_then(Value::as_array)?
+        .iter()
+        .find(|span| span.get("is_primary").and_then(Value::as_bool) == Some(true))?;
+    let number = |key: &str| {
+        span.get(key)
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+    };
+    Some(Diagnostic {
+        file: span.get("file_name").and_then(Value::as_str)?.to_string(),
+        line: number("line_start"),
+        column: number("column_start"),
+        severity,
+        message: message.get("message").and_then(Value::as_str)?.to_string(),
+        code: message
+            .get("code")
+            .and_then(|code| code.get("code"))
+            .and_then(Value::as_str)
+            .map(str::to_string),
+    })
+}
+
+/// One file entry of the `ESLint` JSON formatter.
+fn eslint_diagnostics(file: &Value) -> Vec<Diagnostic> {
+    let Some(path) = file.get("filePath").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+    let Some(messages) = file.get("messages").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    messages
+        .iter()
+        .filter_map(|message| {
+            let number = |key: &str| {
+                message
+                    .get(key)
+                    .and_then(Value::as_u64)
+                    .and_then(|n| u32::try_from(n).ok())
+            };
+            Some(Diagnostic {
+                file: path.to_string(),
+                line: number("line"),
+                column: number("column"),
+                severity: if message.get("severity").and_then(Value::as_u64) == Some(1) {
+                    Severity::Warning
+                } else {
+                    Severity::Error
+                },
+                message: message.get("message").and_then(Value::as_str)?.to_string(),
+                code: message
+                    .get("ruleId")
+                    .and_then(Value::as_str)
+                    .map(str::to_string),
+            })
+        })
+        .collect()
+}
+
+/// `src/a.ts(3,5): error TS2322: message` from `tsc`.
+fn tsc_diagnostic(line: &str) -> Option<Diagnostic> {
+    let (head, rest) = line.split_once("): ")?;
+    let (file, position) = head.rsplit_once('(')?;
+    let (line_no, column) = position.split_once(',')?;
+    let (severity, code, message) = split_severity(rest)?;
+    Some(Diagnostic {
+        file: file.to_string(),
+        line: Some(line_no.parse().ok()?),
+        column: Some(column.parse().ok()?),
+        severity,
+        message,
+        code,
+    })
+}
+
+/// `file:line[:col]: [severity:] message` (gcc, clang, go vet, mypy, ...).
+fn location_diagnostic(line: &str) -> Option<Diagnostic> {
+    let (file, rest) = line.split_once(':')?;
+    if file.is_empty() || file.chars().any(char::is_whitespace) {
+        return None;
+    }
+    let (line_no, rest) = rest.split_once(':')?;
+    let line_no: u32 = line_no.parse().ok()?;
+    let (column, rest) = match rest.split_once(':') {
+        Some((column, rest)) if column.parse::<u32>().is_ok() => (column.parse().ok(), rest),
+        _ => (None, rest),
+    };
+    let (severity, code, message) =
+        split_severity(rest).unwrap_or_else(|| (Severity::Error, None, rest.trim().to_string()));
+    if message.is_empty() {
+        return None;
+    }
+    Some(Diagnostic {
+        file: file.to_string(),
+        line: Some(line_no),
+        column,
+        severity,
+        message,
+        code,
+    })
+}
+
+/// Splits `error[CODE]: msg`, `error TS123: msg`, or `warning: msg` into
+/// parts. Returns `None` when `text` has no severity prefix; `note`/`help`
+/// lines are not diagnostics of their own.
+fn split_severity(text: &str) -> Option<(Severity, Option<String>, String)> {
+    let text = text.trim_start();
+    let (label, rest) = text.split_once(':')?;
+    if label == "fatal error" {
+        return Some((Severity::Error, None, rest.trim().to_string()));
+    }
+    let (word, code) = match label.split_once(['[', ' ']) {
+        Some((word, code)) => (word, Some(code.trim_end_matches(']').trim().to_string())),
+        None => (label, None),
+    };
+    let severity = match word.to_ascii_lowercase().as_str() {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        _ => return None,
+    };
+    Some((
+        severity,
+        code.filter(|code| !code.is_empty()),
+        rest.trim().to_string(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cargo_json_messages() {
+        let output = [
+            r#"{"reason":"compiler-artifact","target":{}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"cannot find value `x` in this scope","code":{"code":"E0425"},"spans":[{"file_name":"src/main.rs","line_start":3,"column_start":5,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"warning","message":"unused import","code":null,"spans":[{"file_name":"src/lib.rs","line_start":1,"column_start":1,"is_primary":true}]}}"#,
+            r#"{"reason":"compiler-message","message":{"level":"error","message":"aborting due to 1 previous error","code":null,"spans":[]}}"#,
+            "error: could not compile `demo`",
+        ]
+        .join("\n");
+
+        let diagnostics = parse_diagnostics(&output);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].compact(),
+            "src/main.rs:3:5: error[E0425]: cannot find value `x` in this scope"
+        );
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn parses_eslint_tsc_and_generic_output() {
+        let eslint = r#"[{"filePath":"/p/a.js","messages":[{"ruleId":"no-unused-vars","severity":1,"message":"'x' is unused","line":2,"column":7},{"ruleId":null,"severity":2,"message":"Parsing error","line":9,"column":1}]}]"#;
+        let diagnostics = parse_diagnostics(eslint);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].message, "Parsing error");
+        assert_eq!(diagnostics[1].code.as_deref(), Some("no-unused-vars"));
+
+        let tsc = "src/a.ts(3,5): error TS2322: Type 'string' is not assignable to type 'number'.";
+        let diagnostic = &parse_diagnostics(tsc)[0];
+        assert_eq!(
+            (
+                diagnostic.line,
+                diagnostic.column,
+                diagnostic.code.as_deref()
+            ),
+            (Some(3), Some(5), Some("TS2322"))
+        );
+
+        let generic = "main.c:10:2: warning: unused variable 'y'\n  Compiling demo v0.1.0\nmain.go:4: undefined: foo";
+        let diagnostics = parse_diagnostics(generic);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].compact(), "main.go:4: error: undefined: foo");
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn failing_command_keeps_output_tail_when_unparsed() {
+        let config = CheckToolConfig {
+            command: "echo 'something broke' >&2; exit 2".to_string(),
+            timeout_secs: 5,
+            max_diagnostics: 10,
+            auto: true,
+        };
+        let report = run_check(&config, &std::env::temp_dir()).await.unwrap();
+        assert!(!report.passed());
+        assert_eq!(report.exit_code, 2);
+        assert!(report.output_tail.contains("something broke"));
+        assert!(is_check_message(&report.feedback_message(&config)));
+    }
+}
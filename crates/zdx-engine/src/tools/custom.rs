@@ -0,0 +1,301 @@
+//! User-defined tools backed by external commands (`[[tools.custom]]`).
+//!
+//! Each invocation runs the configured command in the project root with the
+//! tool input (JSON) on stdin; stdout becomes the tool result.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
+
+use super::{Tool, ToolContext, ToolDefinition, ToolFuture, ToolRegistry};
+use crate::config::{CustomToolConfig, ToolsConfig};
+use crate::core::events::ToolOutput;
+
+/// Registers every valid custom tool from `config` into `registry`.
+///
+/// Invalid entries (bad name, schema, or a name that shadows a built-in) are
+/// skipped with a warning so one broken entry doesn't disable the rest.
+pub fn register_custom_tools(registry: &mut ToolRegistry, config: &ToolsConfig) {
+    let builtin_names = registry.tool_names();
+    for tool in &config.custom {
+        match CustomTool::new(tool, &builtin_names) {
+            Ok(tool) => registry.register_user_tool(tool),
+            Err(message) => tracing::warn!(tool = %tool.name, "Skipping custom tool: {message}"),
+        }
+    }
+}
+
+struct CustomTool {
+    definition: ToolDefinition,
+    command: String,
+    timeout: Option<Duration>,
+    max_output_bytes: usize,
+}
+
+impl CustomTool {
+    fn new(config: &CustomToolConfig, builtin_names: &[String]) -> Result<Self, String> {
+        let name = config.name.trim();
+        if !is_valid_tool_name(name) {
+            return Err("name must be non-empty and use only letters, digits, `_`, `-`".into());
+        }
+        if builtin_names
+            .iter()
+            .any(|builtin| builtin.eq_ignore_ascii_case(name))
+        {
+            return Err("name conflicts with a built-in tool".into());
+        }
+        if config.command.trim().is_empty() {
+            return Err("command is empty".into());
+        }
+
+        let input_schema = match &config.schema {
+            Value::String(raw) => {
+                serde_json::from_str(raw).map_err(|e| format!("schema is not valid JSON: {e}"))?
+            }
+            schema => schema.clone(),
+        };
+        if !input_schema.is_object() {
+            return Err("schema must be a JSON object".into());
+        }
+
+        Ok(Self {
+            definition: ToolDefinition {
+                name: name.to_string(),
+                description: config.description.clone(),
+                input_schema,
+            },
+            command: config.command.clone(),
+            timeout: (config.timeout_secs > 0).then(|| Duration::from_secs(config.timeout_secs)),
+            max_output_bytes: config.max_output_bytes,
+        })
+    }
+}
+
+/// Tool names must be non-empty and use only ASCII letters, digits, `_`, `-`.
+pub(crate) fn is_valid_tool_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+impl Tool for CustomTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let input = input.clone();
+        let root = ctx.root.clone();
+        let command = self.command.clone();
+        let timeout = self.timeout;
+        let max_output_bytes = self.max_output_bytes;
+        Box::pin(async move { run(&command, &input, &root, timeout, max_output_bytes).await })
+    }
+}
+
+/// Runs `command` in `root` with `input` (JSON) on stdin. A zero exit
+/// returns stdout/stderr; anything else is a failure.
+pub(super) async fn run(
+    command: &str,
+    input: &Value,
+    root: &std::path::Path,
+    timeout: Option<Duration>,
+    max_output_bytes: usize,
+) -> ToolOutput {
+    let mut cmd = zdx_tools::bash::shell_command(command);
+    cmd.current_dir(root)
+        .env("TERM", "dumb")
+        .env("NO_COLOR", "1")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            return ToolOutput::failure(
+                "spawn_error",
+                format!("Failed to execute command '{command}'"),
+                Some(format!("Error: {e}")),
+            );
+        }
+    };
+
+    let stdin = child.stdin.take();
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+    let payload = input.to_string();
+
+    let io = async {
+        let write_stdin = async {
+            if let Some(mut stdin) = stdin {
+                // A command that ignores stdin may exit before reading it;
+                // the resulting broken pipe is not an error.
+                let _ = stdin.write_all(payload.as_bytes()).await;
+            }
+        };
+        let ((), stdout, stderr, status) = tokio::join!(
+            write_stdin,
+            read_capped(stdout, max_output_bytes),
+            read_capped(stderr, max_output_bytes),
+            child.wait(),
+        );
+        (stdout, stderr, status)
+    };
+
+    let ((stdout, stdout_truncated), (stderr, stderr_truncated), status) = match timeout {
+        Some(duration) => match tokio::time::timeout(duration, io).await {
+            Ok(result) => result,
+            Err(_) => {
+                return ToolOutput::failure(
+                    "timeout",
+                    format!("Command timed out after {} seconds", duration.as_secs()),
+                    None,
+                );
+            }
+        },
+        None => io.await,
+    };
+
+    let exit_code = match status {
+        Ok(status) => status.code().unwrap_or(-1),
+        Err(e) => {
+            return ToolOutput::failure(
+                "wait_error",
+                format!("Failed to wait for command '{command}'"),
+                Some(format!("Error: {e}")),
+            );
+        }
+    };
+
+    if exit_code != 0 {
+        return ToolOutput::failure(
+            "command_failed",
+            format!("Command exited with code {exit_code}"),
+            Some(if stderr.is_empty() { stdout } else { stderr }),
+        );
+    }
+
+    ToolOutput::success(json!({
+        "output": stdout,
+        "output_truncated": stdout_truncated,
+        "stderr": stderr,
+        "stderr_truncated": stderr_truncated,
+    }))
+}
+
+/// Reads `reader` to EOF, keeping at most `max_bytes` (lossy UTF-8).
+///
+/// Output past the cap is drained and dropped so the child never blocks on
+/// a full pipe.
+pub(super) async fn read_capped<R: AsyncRead + Unpin>(
+    reader: Option<R>,
+    max_bytes: usize,
+) -> (String, bool) {
+    let Some(mut reader) = reader else {
+        return (String::new(), false);
+    };
+    let mut kept = Vec::new();
+    let mut truncated = false;
+    let mut chunk = vec![0u8; 8192];
+    loop {
+        match reader.read(&mut chunk).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => {
+                let room = max_bytes.saturating_sub(kept.len());
+                if n > room {
+                    truncated = true;
+                }
+                kept.extend_from_slice(&chunk[..n.min(room)]);
+            }
+        }
+    }
+    // Don't split a multi-byte character at the cap.
+    if truncated
+        && let Err(e) = std::str::from_utf8(&kept)
+        && e.error_len().is_none()
+    {
+        kept.truncate(e.valid_up_to());
+    }
+    (String::from_utf8_lossy(&kept).into_owned(), truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tool_config(name: &str, command: &str) -> CustomToolConfig {
+        CustomToolConfig {
+            name: name.to_string(),
+            description: "test tool".to_string(),
+            schema: json!({ "type": "object" }),
+            command: command.to_string(),
+            timeout_secs: 5,
+            max_output_bytes: 1024,
+        }
+    }
+
+    #[test]
+    fn test_register_skips_invalid_and_builtin_names() {
+        let mut registry = ToolRegistry::builtins();
+        let before = registry.tool_names().len();
+        let config = ToolsConfig {
+            custom: vec![
+                tool_config("bash", "echo shadow"),
+                tool_config("bad name", "echo"),
+                tool_config("Echo_Input", "cat"),
+            ],
+            ..ToolsConfig::default()
+        };
+        register_custom_tools(&mut registry, &config);
+        let names = registry.tool_names();
+        assert_eq!(names.len(), before + 1);
+        assert!(names.contains(&"echo_input".to_string()));
+    }
+
+    #[test]
+    fn test_schema_accepts_json_string() {
+        let mut config = tool_config("Lookup", "cat");
+        config.schema = Value::String(r#"{"type":"object","required":["q"]}"#.to_string());
+        let tool = CustomTool::new(&config, &[]).unwrap();
+        assert_eq!(tool.definition().input_schema["required"], json!(["q"]));
+
+        config.schema = Value::String("[1, 2]".to_string());
+        assert!(CustomTool::new(&config, &[]).is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_receives_input_on_stdin() {
+        let root = std::env::temp_dir();
+        let output = run("cat", &json!({ "q": "hi" }), &root, None, 1024).await;
+        assert!(output.is_ok());
+        let data = output.data().unwrap();
+        assert_eq!(data["output"], json!(r#"{"q":"hi"}"#));
+        assert_eq!(data["output_truncated"], json!(false));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_output_is_capped_and_timeout_enforced() {
+        let root = std::env::temp_dir();
+        let output = run("yes | head -c 5000", &json!({}), &root, None, 100).await;
+        let data = output.data().unwrap();
+        assert_eq!(data["output"].as_str().unwrap().len(), 100);
+        assert_eq!(data["output_truncated"], json!(true));
+
+        let output = run(
+            "sleep 5",
+            &json!({}),
+            &root,
+            Some(Duration::from_millis(100)),
+            100,
+        )
+        .await;
+        assert!(!output.is_ok());
+    }
+}
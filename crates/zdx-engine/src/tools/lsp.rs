@@ -0,0 +1,555 @@
+//! `Lsp_*` code intelligence tools backed by language servers (`[lsp]`).
+//!
+//! Positions in tool inputs and results are 1-based line/column; the
+//! conversion to LSP's 0-based UTF-16 positions happens here. The server is
+//! picked by file extension (see [`LspConfig::server_for`]).
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+use super::{Tool, ToolContext, ToolDefinition, ToolFuture, ToolRegistry, resolve_existing_path};
+use crate::config::LspConfig;
+use crate::core::events::ToolOutput;
+use crate::lsp::{self, LspClient};
+
+/// Maximum locations returned by `Lsp_Definition` / `Lsp_References`.
+const MAX_LOCATIONS: usize = 200;
+
+/// Registers the `Lsp_*` tools when `[lsp].enabled` is set.
+pub fn register_lsp_tools(registry: &mut ToolRegistry, config: &LspConfig) {
+    if !config.enabled {
+        return;
+    }
+    for kind in [
+        LspToolKind::Definition,
+        LspToolKind::References,
+        LspToolKind::Hover,
+        LspToolKind::Diagnostics,
+        LspToolKind::Rename,
+    ] {
+        registry.register_user_tool(LspTool {
+            kind,
+            config: config.clone(),
+        });
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LspToolKind {
+    Definition,
+    References,
+    Hover,
+    Diagnostics,
+    Rename,
+}
+
+struct LspTool {
+    kind: LspToolKind,
+    config: LspConfig,
+}
+
+fn position_properties() -> serde_json::Map<String, Value> {
+    let mut properties = serde_json::Map::new();
+    properties.insert(
+        "file_path".to_string(),
+        json!({ "type": "string", "description": "File containing the symbol" }),
+    );
+    properties.insert(
+        "line".to_string(),
+        json!({ "type": "integer", "minimum": 1, "description": "1-based line of the symbol" }),
+    );
+    properties.insert(
+        "column".to_string(),
+        json!({ "type": "integer", "minimum": 1, "description": "1-based column of the symbol" }),
+    );
+    properties
+}
+
+impl Tool for LspTool {
+    fn definition(&self) -> ToolDefinition {
+        let mut properties = position_properties();
+        let mut required = vec!["file_path", "line", "column"];
+        let (name, description) = match self.kind {
+            LspToolKind::Definition => (
+                "Lsp_Definition",
+                "Find where the symbol at a position is defined, using the language server.",
+            ),
+            LspToolKind::References => {
+                properties.insert(
+                    "include_declaration".to_string(),
+                    json!({ "type": "boolean", "description": "Include the declaration itself (default true)" }),
+                );
+                (
+                    "Lsp_References",
+                    "Find all references to the symbol at a position, using the language server. \
+                     More accurate than text search for code symbols.",
+                )
+            }
+            LspToolKind::Hover => (
+                "Lsp_Hover",
+                "Show the type signature and documentation of the symbol at a position.",
+            ),
+            LspToolKind::Diagnostics => {
+                properties.remove("line");
+                properties.remove("column");
+                required = vec!["file_path"];
+                (
+                    "Lsp_Diagnostics",
+                    "Get the language server's current errors and warnings for a file.",
+                )
+            }
+            LspToolKind::Rename => {
+                properties.insert(
+                    "new_name".to_string(),
+                    json!({ "type": "string", "description": "New name for the symbol" }),
+                );
+                required.push("new_name");
+                (
+                    "Lsp_Rename",
+                    "Rename the symbol at a position across the project, using the language \
+                     server. Edits the affected files and lists them.",
+                )
+            }
+        };
+        ToolDefinition {
+            name: name.to_string(),
+            description: description.to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": properties,
+                "required": required,
+            }),
+        }
+    }
+
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let kind = self.kind;
+        let config = self.config.clone();
+        let input = input.clone();
+        let root = ctx.root.canonicalize().unwrap_or_else(|_| ctx.root.clone());
+        Box::pin(async move {
+            execute(kind, &config, &input, &root)
+                .await
+                .unwrap_or_else(|failure| failure)
+        })
+    }
+}
+
+fn lsp_error(error: &anyhow::Error) -> ToolOutput {
+    ToolOutput::failure("lsp_error", format!("{error:#}"), None)
+}
+
+async fn execute(
+    kind: LspToolKind,
+    config: &LspConfig,
+    input: &Value,
+    root: &Path,
+) -> Result<ToolOutput, ToolOutput> {
+    let file_path = input
+        .get("file_path")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    let resolved = resolve_existing_path(file_path, root)?;
+    let path = resolved.resolved_path;
+    let server = config.server_for(&path).ok_or_else(|| {
+        ToolOutput::failure(
+            "no_language_server",
+            format!("No language server configured for {}", path.display()),
+            Some("Add an entry to [[lsp.servers]] for this file extension".to_string()),
+        )
+    })?;
+    let client = lsp::client_for(root, server, config).await.map_err(|e| {
+        ToolOutput::failure(
+            "lsp_unavailable",
+            format!("Language server '{}' is not available", server.name),
+            Some(format!("{e:#}")),
+        )
+    })?;
+
+    let before =
+        client.diagnostics_generation(&lsp::path_to_uri(&path).map_err(|e| lsp_error(&e))?);
+    let (uri, text) = client
+        .sync_document(&path)
+        .await
+        .map_err(|e| lsp_error(&e))?;
+
+    if kind == LspToolKind::Diagnostics {
+        let wait = Duration::from_secs(config.diagnostics_wait_secs);
+        let diagnostics = client.diagnostics(&uri, before, wait).await;
+        return Ok(ToolOutput::success(json!({
+            "file": display_path(&path, root),
+            "diagnostics": diagnostics
+                .iter()
+                .map(|diagnostic| diagnostic_json(diagnostic, &text))
+                .collect::<Vec<_>>(),
+        })));
+    }
+
+    let position = position_input(input, &text)?;
+    let params = json!({ "textDocument": { "uri": uri }, "position": position });
+    match kind {
+        LspToolKind::Definition => {
+            let result = client
+                .request("textDocument/definition", params)
+                .await
+                .map_err(|e| lsp_error(&e))?;
+            Ok(locations_output(&result, root))
+        }
+        LspToolKind::References => {
+            let include_declaration = input
+                .get("include_declaration")
+                .and_then(Value::as_bool)
+                .unwrap_or(true);
+            let mut params = params;
+            params["context"] = json!({ "includeDeclaration": include_declaration });
+            let result = client
+                .request("textDocument/references", params)
+                .await
+                .map_err(|e| lsp_error(&e))?;
+            Ok(locations_output(&result, root))
+        }
+        LspToolKind::Hover => {
+            let result = client
+                .request("textDocument/hover", params)
+                .await
+                .map_err(|e| lsp_error(&e))?;
+            let contents = result.get("contents").map(hover_text).unwrap_or_default();
+            Ok(ToolOutput::success(json!({ "contents": contents })))
+        }
+        LspToolKind::Rename => {
+            let new_name = input
+                .get("new_name")
+                .and_then(Value::as_str)
+                .map(str::trim)
+                .filter(|name| !name.is_empty())
+                .ok_or_else(|| {
+                    ToolOutput::failure("invalid_input", "new_name cannot be empty", None)
+                })?;
+            let mut params = params;
+            params["newName"] = json!(new_name);
+            let edit = client
+                .request("textDocument/rename", params)
+                .await
+                .map_err(|e| lsp_error(&e))?;
+            rename_output(&client, &edit, root).await
+        }
+        LspToolKind::Diagnostics => unreachable!("handled above"),
+    }
+}
+
+/// LSP position for the 1-based `line`/`column` input.
+fn position_input(input: &Value, text: &str) -> Result<Value, ToolOutput> {
+    let number = |key: &str| {
+        input
+            .get(key)
+            .and_then(Value::as_u64)
+            .filter(|n| *n >= 1)
+            .and_then(|n| usize::try_from(n).ok())
+            .ok_or_else(|| {
+                ToolOutput::failure(
+                    "invalid_input",
+                    format!("{key} must be a positive integer"),
+                    None,
+                )
+            })
+    };
+    let line = number("line")?;
+    let column = number("column")?;
+    let line_text = text.lines().nth(line - 1).unwrap_or_default();
+    Ok(json!({
+        "line": line - 1,
+        "character": lsp::column_to_utf16(line_text, column),
+    }))
+}
+
+fn display_path(path: &Path, root: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .display()
+        .to_string()
+}
+
+/// `(path, 0-based line, UTF-16 character)` of an LSP `Location` or `LocationLink`.
+fn location_parts(location: &Value) -> Option<(PathBuf, usize, usize)> {
+    let uri = location
+        .get("uri")
+        .or_else(|| location.get("targetUri"))
+        .and_then(Value::as_str)?;
+    let start = location
+        .pointer("/range/start")
+        .or_else(|| location.pointer("/targetSelectionRange/start"))?;
+    let number = |key: &str| {
+        start
+            .get(key)
+            .and_then(Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+    };
+    Some((
+        lsp::uri_to_path(uri)?,
+        number("line")?,
+        number("character")?,
+    ))
+}
+
+/// Formats definition/reference results as `{file, line, column, text}`.
+fn locations_output(result: &Value, root: &Path) -> ToolOutput {
+    let locations: Vec<&Value> = match result {
+        Value::Array(items) => items.iter().collect(),
+        Value::Null => Vec::new(),
+        single => vec![single],
+    };
+    let total = locations.len();
+    let mut files: BTreeMap<PathBuf, Option<String>> = BTreeMap::new();
+    let mut output = Vec::new();
+    for (path, line, character) in locations
+        .into_iter()
+        .filter_map(location_parts)
+        .take(MAX_LOCATIONS)
+    {
+        let contents = files
+            .entry(path.clone())
+            .or_insert_with(|| std::fs::read_to_string(&path).ok());
+        let line_text = contents
+            .as_deref()
+            .and_then(|contents| contents.lines().nth(line))
+            .unwrap_or_default();
+        output.push(json!({
+            "file": display_path(&path, root),
+            "line": line + 1,
+            "column": lsp::utf16_to_column(line_text, character),
+            "text": line_text.trim(),
+        }));
+    }
+    ToolOutput::success(json!({
+        "locations": output,
+        "truncated": total > MAX_LOCATIONS,
+    }))
+}
+
+/// Flattens hover `contents` (`MarkupContent`, `MarkedString`, or a list).
+fn hover_text(contents: &Value) -> String {
+    match contents {
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(hover_text)
+            .filter(|text| !text.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+        Value::Object(object) => {
+            let value = object
+                .get("value")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            match object.get("language").and_then(Value::as_str) {
+                Some(language) => format!("```{language}\n{value}\n```"),
+                None => value.to_string(),
+            }
+        }
+        _ => String::new(),
+    }
+}
+
+fn diagnostic_json(diagnostic: &Value, text: &str) -> Value {
+    let start = diagnostic.pointer("/range/start");
+    let number = |key: &str| {
+        start
+            .and_then(|start| start.get(key))
+            .and_then(Value::as_u64)
+            .and_then(|n| usize::try_from(n).ok())
+            .unwrap_or(0)
+    };
+    let line = number("line");
+    let line_text = text.lines().nth(line).unwrap_or_default();
+    let severity = match diagnostic.get("severity").and_then(Value::as_u64) {
+        Some(1) => "error",
+        Some(2) => "warning",
+        Some(3) => "information",
+        _ => "hint",
+    };
+    let code = match diagnostic.get("code") {
+        Some(Value::String(code)) => Some(code.clone()),
+        Some(Value::Number(code)) => Some(code.to_string()),
+        _ => None,
+    };
+    json!({
+        "line": line + 1,
+        "column": lsp::utf16_to_column(line_text, number("character")),
+        "severity": severity,
+        "message": diagnostic.get("message").and_then(Value::as_str).unwrap_or_default(),
+        "source": diagnostic.get("source"),
+        "code": code,
+    })
+}
+
+/// Text edits per file from a `WorkspaceEdit` (`changes` or `documentChanges`).
+fn workspace_text_edits(edit: &Value) -> BTreeMap<String, Vec<Value>> {
+    let mut edits: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    if let Some(changes) = edit.get("changes").and_then(Value::as_object) {
+        for (uri, file_edits) in changes {
+            if let Some(file_edits) = file_edits.as_array() {
+                edits
+                    .entry(uri.clone())
+                    .or_default()
+                    .extend(file_edits.iter().cloned());
+            }
+        }
+    }
+    for change in edit
+        .get("documentChanges")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default()
+    {
+        // File create/rename/delete operations carry a `kind` and are skipped.
+        let (Some(uri), Some(file_edits)) = (
+            change.pointer("/textDocument/uri").and_then(Value::as_str),
+            change.get("edits").and_then(Value::as_array),
+        ) else {
+            continue;
+        };
+        edits
+            .entry(uri.to_string())
+            .or_default()
+            .extend(file_edits.iter().cloned());
+    }
+    edits
+}
+
+/// Applies LSP `TextEdit`s to `text`, last edit first so earlier offsets hold.
+pub(crate) fn apply_text_edits(text: &str, edits: &[Value]) -> String {
+    let mut ranges: Vec<(usize, usize, &str)> = edits
+        .iter()
+        .filter_map(|edit| {
+            let position = |pointer: &str| {
+                let point = edit.pointer(pointer)?;
+                let line = usize::try_from(point.get("line")?.as_u64()?).ok()?;
+                let character = usize::try_from(point.get("character")?.as_u64()?).ok()?;
+                Some(lsp::position_to_offset(text, line, character))
+            };
+            Some((
+                position("/range/start")?,
+                position("/range/end")?,
+                edit.get("newText")?.as_str()?,
+            ))
+        })
+        .collect();
+    ranges.sort_by_key(|(start, end, _)| std::cmp::Reverse((*start, *end)));
+    let mut result = text.to_string();
+    for (start, end, new_text) in ranges {
+        if start <= end && end <= result.len() {
+            result.replace_range(start..end, new_text);
+        }
+    }
+    result
+}
+
+/// Applies a rename's workspace edit to files under `root` and reports them.
+async fn rename_output(
+    client: &LspClient,
+    edit: &Value,
+    root: &Path,
+) -> Result<ToolOutput, ToolOutput> {
+    if edit.is_null() {
+        return Err(ToolOutput::failure(
+            "lsp_error",
+            "The language server returned no edits for this rename",
+            None,
+        ));
+    }
+    let mut changed = Vec::new();
+    let mut skipped = Vec::new();
+    for (uri, edits) in workspace_text_edits(edit) {
+        let Some(path) = lsp::uri_to_path(&uri).filter(|path| path.starts_with(root)) else {
+            skipped.push(uri);
+            continue;
+        };
+        let text = tokio::fs::read_to_string(&path).await.map_err(|e| {
+            ToolOutput::failure(
+                "read_error",
+                format!("Failed to read {}", path.display()),
+                Some(e.to_string()),
+            )
+        })?;
+        tokio::fs::write(&path, apply_text_edits(&text, &edits))
+            .await
+            .map_err(|e| {
+                ToolOutput::failure(
+                    "write_error",
+                    format!("Failed to write {}", path.display()),
+                    Some(e.to_string()),
+                )
+            })?;
+        if let Err(error) = client.sync_document(&path).await {
+            tracing::debug!(%error, "Failed to resync renamed document");
+        }
+        changed.push(json!({ "file": display_path(&path, root), "edits": edits.len() }));
+    }
+    Ok(ToolOutput::success(json!({
+        "changed_files": changed,
+        "skipped_outside_root": skipped,
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn applies_workspace_edits_from_both_shapes() {
+        let edit = json!({
+            "changes": {
+                "file:///p/a.rs": [
+                    { "range": { "start": { "line": 0, "character": 3 }, "end": { "line": 0, "character": 6 } }, "newText": "bar" }
+                ]
+            },
+            "documentChanges": [
+                { "textDocument": { "uri": "file:///p/a.rs", "version": 2 }, "edits": [
+                    { "range": { "start": { "line": 1, "character": 4 }, "end": { "line": 1, "character": 7 } }, "newText": "bar" }
+                ] },
+                { "kind": "rename", "oldUri": "file:///p/b.rs", "newUri": "file:///p/c.rs" }
+            ]
+        });
+        let edits = workspace_text_edits(&edit);
+        assert_eq!(edits.len(), 1);
+        let text = "fn foo() {}\n    foo();\n";
+        assert_eq!(
+            apply_text_edits(text, &edits["file:///p/a.rs"]),
+            "fn bar() {}\n    bar();\n"
+        );
+    }
+
+    #[test]
+    fn flattens_hover_contents() {
+        assert_eq!(
+            hover_text(&json!({ "kind": "markdown", "value": "**fn** foo()" })),
+            "**fn** foo()"
+        );
+        assert_eq!(
+            hover_text(&json!([{ "language": "rust", "value": "fn foo()" }, "Docs"])),
+            "```rust\nfn foo()\n```\n\nDocs"
+        );
+    }
+
+    #[test]
+    fn registers_tools_only_when_enabled() {
+        let mut registry = ToolRegistry::default();
+        register_lsp_tools(&mut registry, &LspConfig::default());
+        assert!(registry.tool_names().is_empty());
+
+        let config = LspConfig {
+            enabled: true,
+            ..LspConfig::default()
+        };
+        register_lsp_tools(&mut registry, &config);
+        assert_eq!(registry.user_tool_names().len(), 5);
+        assert!(
+            registry
+                .tool_names()
+                .contains(&"lsp_references".to_string())
+        );
+    }
+}
@@ -4,23 +4,36 @@
 //! along with schema definitions for the Anthropic API.
 
 // Leaf tools re-exported from zdx-tools
-pub use zdx_tools::{apply_patch, bash, edit, fetch_webpage, glob, grep, read, web_search, write};
+pub use zdx_tools::{
+    apply_patch, bash, edit, env_info, fetch_webpage, glob, grep, notebook, outline, read,
+    web_search, write,
+};
 
 // Engine-backed tools (need full ToolContext with config, threads, etc.)
+pub mod ask_user;
+pub mod check;
+pub mod custom;
+pub mod lsp;
 pub mod memory_get;
 pub mod memory_search;
+pub mod plugin;
+pub mod protected_files;
 pub mod read_thread;
+pub mod run_tests;
 pub mod subagent;
 pub mod thread_search;
 pub mod todo_write;
+pub mod web_guard;
 
+use std::collections::HashMap;
 use std::future::Future;
 use std::path::PathBuf;
 use std::pin::Pin;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
 
 use serde_json::Value;
+use tokio_util::sync::CancellationToken;
 // Re-export path helpers and serde helpers from zdx-tools for backward compat
 pub use zdx_tools::{
     ResolvedPath, expand_env_vars, insert_file_path_fields, resolve_existing_path,
@@ -62,6 +75,15 @@ pub struct ToolContext {
     /// Available model list for subagent delegation.
     pub subagent_available_models: Vec<String>,
 
+    /// Whether bash calls reuse a persistent shell session for the current thread.
+    pub bash_persistent: bool,
+
+    /// Resource limits for bash tool commands.
+    pub bash_limits: zdx_tools::bash_limits::BashLimits,
+
+    /// Line count above which `Read` without a range returns the file outline.
+    pub read_outline_threshold: usize,
+
     /// Event sender for emitting streaming tool output events.
     /// Set by the engine before tool execution; used by `bash_handler`
     /// to bridge output chunks to `ToolOutputDelta` events.
@@ -69,6 +91,18 @@ pub struct ToolContext {
 
     /// Tool use ID for the current execution (needed for `ToolOutputDelta` events).
     pub tool_use_id: Option<String>,
+
+    /// Cancellation token of the running turn. Set by the engine before tool
+    /// execution; tools that spawn child runs (subagents) forward it so a
+    /// canceled turn stops them too.
+    pub cancel: Option<CancellationToken>,
+
+    /// Channel to the user for `Ask_User`. `None` in non-interactive runs
+    /// (exec, subagents), where the tool fails fast.
+    pub ask_user: Option<crate::core::ask_user::AskUserHandle>,
+
+    /// Protected files the user approved reading (`[security] protected_globs`).
+    pub protected_approvals: protected_files::ProtectedApprovals,
 }
 
 impl std::fmt::Debug for ToolContext {
@@ -83,8 +117,14 @@ impl std::fmt::Debug for ToolContext {
             .field("config", &self.config.is_some())
             .field("subagents_enabled", &self.subagents_enabled)
             .field("subagent_available_models", &self.subagent_available_models)
+            .field("bash_persistent", &self.bash_persistent)
+            .field("bash_limits", &self.bash_limits)
+            .field("read_outline_threshold", &self.read_outline_threshold)
             .field("event_sender", &self.event_sender.as_ref().map(|_| ".."))
             .field("tool_use_id", &self.tool_use_id)
+            .field("cancel", &self.cancel.as_ref().map(|_| ".."))
+            .field("ask_user", &self.ask_user.is_some())
+            .field("protected_approvals", &self.protected_approvals)
             .finish()
     }
 }
@@ -101,8 +141,14 @@ impl ToolContext {
             config: None,
             subagents_enabled: true,
             subagent_available_models: Vec::new(),
+            bash_persistent: false,
+            bash_limits: zdx_tools::bash_limits::BashLimits::default(),
+            read_outline_threshold: read::DEFAULT_OUTLINE_THRESHOLD_LINES,
             event_sender: None,
             tool_use_id: None,
+            cancel: None,
+            ask_user: None,
+            protected_approvals: protected_files::ProtectedApprovals::default(),
         }
     }
 
@@ -114,6 +160,9 @@ impl ToolContext {
         self.thinking_level = Some(config.thinking_level);
         self.subagents_enabled = config.subagents.enabled;
         self.subagent_available_models = config.subagent_available_models();
+        self.bash_persistent = config.bash.persistent;
+        self.bash_limits = config.bash.limits();
+        self.read_outline_threshold = config.read.outline_threshold_lines;
         self
     }
 
@@ -126,10 +175,26 @@ impl ToolContext {
         self
     }
 
+    #[must_use]
+    pub fn with_ask_user(mut self, ask_user: Option<crate::core::ask_user::AskUserHandle>) -> Self {
+        self.ask_user = ask_user;
+        self
+    }
+
+    #[must_use]
+    pub fn with_protected_approvals(
+        mut self,
+        approvals: protected_files::ProtectedApprovals,
+    ) -> Self {
+        self.protected_approvals = approvals;
+        self
+    }
+
     /// Convert to a leaf tool context (for zdx-tools).
     #[must_use]
     pub fn as_leaf(&self) -> zdx_tools::ToolContext {
         zdx_tools::ToolContext::new(self.root.clone(), self.timeout)
+            .with_bash_limits(self.bash_limits)
     }
 }
 
@@ -144,14 +209,19 @@ impl ToolSet {
     pub fn tool_names(self) -> &'static [&'static str] {
         match self {
             ToolSet::Default => &[
+                "ask_user",
                 "bash",
                 "edit",
+                "env_info",
                 "fetch_webpage",
                 "glob",
                 "grep",
                 "invoke_subagent",
                 "memory_get",
                 "memory_search",
+                "notebook_edit",
+                "notebook_read",
+                "outline",
                 "read",
                 "read_thread",
                 "todo_write",
@@ -160,14 +230,19 @@ impl ToolSet {
                 "write",
             ],
             ToolSet::OpenAICodex => &[
+                "ask_user",
                 "bash",
                 "apply_patch",
+                "env_info",
                 "fetch_webpage",
                 "glob",
                 "grep",
                 "invoke_subagent",
                 "memory_get",
                 "memory_search",
+                "notebook_edit",
+                "notebook_read",
+                "outline",
                 "read",
                 "read_thread",
                 "todo_write",
@@ -199,25 +274,34 @@ pub trait Tool: Send + Sync {
 
     /// Executes the tool with the given JSON input and context.
     fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture;
+
+    /// Whether the tool reaches the network (dropped in offline mode).
+    fn requires_network(&self) -> bool {
+        false
+    }
 }
 
 /// Tool registry (definitions + executors).
 #[derive(Clone, Default)]
 pub struct ToolRegistry {
     tools: Vec<Arc<dyn Tool>>,
+    /// Lowercased names of user-supplied tools (`[[tools.custom]]`, WASM
+    /// plugins), enabled by default alongside the base tool set.
+    user_tools: Vec<String>,
 }
 
 impl std::fmt::Debug for ToolRegistry {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("ToolRegistry")
             .field("tools_len", &self.tools.len())
+            .field("user_tools", &self.user_tools)
             .finish()
     }
 }
 
 impl ToolRegistry {
     pub fn new() -> Self {
-        Self { tools: Vec::new() }
+        Self::default()
     }
 
     pub fn builtins() -> Self {
@@ -226,11 +310,37 @@ impl ToolRegistry {
         registry
     }
 
+    /// Built-in tools plus user-supplied tools: `[[tools.custom]]` commands
+    /// and WASM plugins from `<ZDX_HOME>/plugins`.
+    pub fn from_config(config: &crate::config::Config) -> Self {
+        let mut registry = Self::builtins();
+        check::register_check_tool(&mut registry, config.tools.check.as_ref());
+        run_tests::register_run_tests_tool(&mut registry, config.tools.run_tests.as_ref());
+        lsp::register_lsp_tools(&mut registry, &config.lsp);
+        custom::register_custom_tools(&mut registry, &config.tools);
+        plugin::register_plugins(&mut registry, &config.plugins);
+        registry
+    }
+
     /// Registers a concrete [`Tool`] implementation.
     pub fn register_tool<T: Tool + 'static>(&mut self, tool: T) {
         self.register_boxed(Arc::new(tool));
     }
 
+    /// Registers a user-supplied tool that is enabled by default.
+    pub fn register_user_tool<T: Tool + 'static>(&mut self, tool: T) {
+        let name = tool.definition().name.to_ascii_lowercase();
+        self.register_tool(tool);
+        if !self.user_tools.contains(&name) {
+            self.user_tools.push(name);
+        }
+    }
+
+    /// Names of the user-supplied tools (lowercased).
+    pub fn user_tool_names(&self) -> &[String] {
+        &self.user_tools
+    }
+
     /// Registers a boxed trait object directly.
     pub fn register_boxed(&mut self, tool: Arc<dyn Tool>) {
         // Remove any existing tool with the same name (case-insensitive).
@@ -244,6 +354,13 @@ impl ToolRegistry {
         self.tools.iter().map(|t| t.definition()).collect()
     }
 
+    /// Whether the registered tool `name` reaches the network.
+    pub fn requires_network(&self, name: &str) -> bool {
+        self.tools
+            .iter()
+            .any(|t| t.definition().name.eq_ignore_ascii_case(name) && t.requires_network())
+    }
+
     pub fn tool_names(&self) -> Vec<String> {
         self.tools
             .iter()
@@ -320,6 +437,7 @@ impl ToolRegistry {
     }
 
     fn register_builtin_tools(&mut self) {
+        self.register_tool(AskUser);
         self.register_tool(Bash);
         self.register_tool(ApplyPatch);
         self.register_tool(Edit);
@@ -335,11 +453,154 @@ impl ToolRegistry {
         self.register_tool(FetchWebpage);
         self.register_tool(Grep);
         self.register_tool(Glob);
+        self.register_tool(Outline);
+        self.register_tool(NotebookRead);
+        self.register_tool(NotebookEdit);
+        self.register_tool(EnvInfo);
     }
 }
 
 // -- Builtin tool implementations --
 
+/// Idle time after which an unused persistent shell is closed.
+const SHELL_SESSION_IDLE_TTL: Duration = Duration::from_mins(30);
+/// Most persistent shells kept at once; the least recently used idle one is
+/// closed to make room for a new thread.
+const SHELL_SESSION_MAX: usize = 16;
+/// How often the reaper task closes shells idle past the TTL.
+const SHELL_SESSION_REAP_INTERVAL: Duration = Duration::from_mins(1);
+
+/// Persistent shell sessions keyed by thread id (see `[bash] persistent`).
+type ShellSessionSlot = Arc<tokio::sync::Mutex<Option<zdx_tools::shell_session::ShellSession>>>;
+static SHELL_SESSIONS: LazyLock<Mutex<ShellSessions>> =
+    LazyLock::new(|| Mutex::new(ShellSessions::default()));
+
+struct ShellSessionEntry {
+    slot: ShellSessionSlot,
+    last_used: Instant,
+}
+
+impl ShellSessionEntry {
+    /// Whether a bash call still holds the slot; such sessions are never closed.
+    fn in_use(&self) -> bool {
+        Arc::strong_count(&self.slot) > 1
+    }
+}
+
+/// Open sessions plus whether a reaper task is watching them. Dropping an
+/// entry drops its `ShellSession`, which kills the shell.
+#[derive(Default)]
+struct ShellSessions {
+    entries: HashMap<String, ShellSessionEntry>,
+    reaper_running: bool,
+}
+
+impl ShellSessions {
+    /// Closes sessions idle for longer than [`SHELL_SESSION_IDLE_TTL`].
+    fn reap(&mut self, now: Instant) {
+        self.entries.retain(|_, entry| {
+            entry.in_use() || now.duration_since(entry.last_used) < SHELL_SESSION_IDLE_TTL
+        });
+    }
+
+    /// Returns the slot for `thread_id`, creating it on first use and closing
+    /// the least recently used idle session when at [`SHELL_SESSION_MAX`].
+    fn slot(&mut self, thread_id: &str, now: Instant) -> ShellSessionSlot {
+        self.reap(now);
+        if !self.entries.contains_key(thread_id)
+            && self.entries.len() >= SHELL_SESSION_MAX
+            && let Some(lru) = self
+                .entries
+                .iter()
+                .filter(|(_, entry)| !entry.in_use())
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(id, _)| id.clone())
+        {
+            self.entries.remove(&lru);
+        }
+        let entry = self
+            .entries
+            .entry(thread_id.to_string())
+            .or_insert_with(|| ShellSessionEntry {
+                slot: ShellSessionSlot::default(),
+                last_used: now,
+            });
+        entry.last_used = now;
+        Arc::clone(&entry.slot)
+    }
+}
+
+fn lock_shell_sessions() -> std::sync::MutexGuard<'static, ShellSessions> {
+    SHELL_SESSIONS
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+}
+
+/// Returns the persistent shell slot for `thread_id`, creating it on first
+/// use and starting the idle reaper if none is running.
+fn shell_session_slot(thread_id: &str) -> ShellSessionSlot {
+    let mut sessions = lock_shell_sessions();
+    let slot = sessions.slot(thread_id, Instant::now());
+    if !sessions.reaper_running {
+        sessions.reaper_running = true;
+        tokio::spawn(reap_shell_sessions());
+    }
+    slot
+}
+
+/// Periodically closes idle shells; exits once none are left. If the task is
+/// dropped early (runtime shutdown), the next session starts a new one.
+async fn reap_shell_sessions() {
+    struct Running;
+    impl Drop for Running {
+        fn drop(&mut self) {
+            lock_shell_sessions().reaper_running = false;
+        }
+    }
+    let _running = Running;
+    loop {
+        tokio::time::sleep(SHELL_SESSION_REAP_INTERVAL).await;
+        let mut sessions = lock_shell_sessions();
+        sessions.reap(Instant::now());
+        if sessions.entries.is_empty() {
+            return;
+        }
+    }
+}
+
+/// Runs bash either in the thread's persistent shell or as a one-shot command.
+async fn execute_bash(
+    input: &Value,
+    ctx: &ToolContext,
+    output_tx: Option<tokio::sync::mpsc::UnboundedSender<String>>,
+) -> ToolOutput {
+    let leaf = ctx.as_leaf();
+    match ctx.current_thread_id.as_deref() {
+        // Sessions drive a `bash` process, so they are Unix-only; Windows
+        // always runs one-shot `PowerShell` commands.
+        Some(thread_id) if ctx.bash_persistent && cfg!(unix) => {
+            let slot = shell_session_slot(thread_id);
+            // Held for the whole call so parallel bash calls in one thread run
+            // sequentially against the same shell.
+            let mut session = slot.lock().await;
+            bash::execute_in_session(input, &leaf, &mut session, ctx.timeout, output_tx).await
+        }
+        _ => bash::execute(input, &leaf, ctx.timeout, output_tx).await,
+    }
+}
+
+struct AskUser;
+impl Tool for AskUser {
+    fn definition(&self) -> ToolDefinition {
+        ask_user::definition()
+    }
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        Box::pin(async move { ask_user::execute(&input, &ctx).await })
+    }
+}
+
 struct Bash;
 impl Tool for Bash {
     fn definition(&self) -> ToolDefinition {
@@ -355,23 +616,17 @@ impl Tool for Bash {
             if let (Some(sender), Some(id)) = (event_sender, tool_use_id) {
                 let (output_tx, mut output_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
 
-                let leaf = ctx.as_leaf();
-                let timeout = ctx.timeout;
-
-                tokio::join!(
-                    bash::execute(&input, &leaf, timeout, Some(output_tx)),
-                    async {
-                        while let Some(chunk) = output_rx.recv().await {
-                            sender.send(AgentEvent::ToolOutputDelta {
-                                id: id.clone(),
-                                chunk,
-                            });
-                        }
+                tokio::join!(execute_bash(&input, &ctx, Some(output_tx)), async {
+                    while let Some(chunk) = output_rx.recv().await {
+                        sender.send(AgentEvent::ToolOutputDelta {
+                            id: id.clone(),
+                            chunk,
+                        });
                     }
-                )
+                })
                 .0
             } else {
-                bash::execute(&input, &ctx.as_leaf(), ctx.timeout, None).await
+                execute_bash(&input, &ctx, None).await
             }
         })
     }
@@ -409,7 +664,13 @@ impl Tool for Read {
     fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
         let input = input.clone();
         let ctx = ctx.clone();
-        Box::pin(async move { execute_read(&input, &ctx.as_leaf()).await })
+        let threshold = ctx.read_outline_threshold;
+        Box::pin(async move {
+            if let Err(blocked) = protected_files::check_read(&input, &ctx).await {
+                return blocked;
+            }
+            execute_read(&input, &ctx.as_leaf(), threshold).await
+        })
     }
 }
 
@@ -433,7 +694,13 @@ impl Tool for WebSearch {
     fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
         let input = input.clone();
         let ctx = ctx.clone();
-        Box::pin(async move { web_search::execute(&input, &ctx.as_leaf()).await })
+        Box::pin(async move {
+            let output = web_search::execute(&input, &ctx.as_leaf()).await;
+            web_guard::apply("Web_Search", output, &ctx).await
+        })
+    }
+    fn requires_network(&self) -> bool {
+        true
     }
 }
 
@@ -445,7 +712,13 @@ impl Tool for FetchWebpage {
     fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
         let input = input.clone();
         let ctx = ctx.clone();
-        Box::pin(async move { fetch_webpage::execute(&input, &ctx.as_leaf()).await })
+        Box::pin(async move {
+            let output = fetch_webpage::execute(&input, &ctx.as_leaf()).await;
+            web_guard::apply("Fetch_Webpage", output, &ctx).await
+        })
+    }
+    fn requires_network(&self) -> bool {
+        true
     }
 }
 
@@ -473,6 +746,54 @@ impl Tool for Glob {
     }
 }
 
+struct Outline;
+impl Tool for Outline {
+    fn definition(&self) -> ToolDefinition {
+        outline::definition()
+    }
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        Box::pin(async move { execute_outline(&input, &ctx.as_leaf()).await })
+    }
+}
+
+struct NotebookRead;
+impl Tool for NotebookRead {
+    fn definition(&self) -> ToolDefinition {
+        notebook::read_definition()
+    }
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        Box::pin(async move { execute_notebook_read(&input, &ctx.as_leaf()).await })
+    }
+}
+
+struct NotebookEdit;
+impl Tool for NotebookEdit {
+    fn definition(&self) -> ToolDefinition {
+        notebook::edit_definition()
+    }
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        Box::pin(async move { execute_notebook_edit(&input, &ctx.as_leaf()).await })
+    }
+}
+
+struct EnvInfo;
+impl Tool for EnvInfo {
+    fn definition(&self) -> ToolDefinition {
+        env_info::definition()
+    }
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        Box::pin(async move { execute_env_info(&input, &ctx.as_leaf()).await })
+    }
+}
+
 struct ReadThread;
 impl Tool for ReadThread {
     fn definition(&self) -> ToolDefinition {
@@ -625,11 +946,15 @@ async fn execute_apply_patch(input: &Value, ctx: &zdx_tools::ToolContext) -> Too
     .await
 }
 
-async fn execute_read(input: &Value, ctx: &zdx_tools::ToolContext) -> ToolOutput {
+async fn execute_read(
+    input: &Value,
+    ctx: &zdx_tools::ToolContext,
+    outline_threshold: usize,
+) -> ToolOutput {
     execute_blocking(ctx.timeout, {
         let input = input.clone();
         let ctx = ctx.clone();
-        move || read::execute(&input, &ctx)
+        move || read::execute_with_outline_threshold(&input, &ctx, outline_threshold)
     })
     .await
 }
@@ -699,6 +1024,42 @@ async fn execute_glob(input: &Value, ctx: &zdx_tools::ToolContext) -> ToolOutput
     .await
 }
 
+async fn execute_outline(input: &Value, ctx: &zdx_tools::ToolContext) -> ToolOutput {
+    execute_blocking(ctx.timeout, {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        move || outline::execute(&input, &ctx)
+    })
+    .await
+}
+
+async fn execute_notebook_read(input: &Value, ctx: &zdx_tools::ToolContext) -> ToolOutput {
+    execute_blocking(ctx.timeout, {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        move || notebook::execute_read(&input, &ctx)
+    })
+    .await
+}
+
+async fn execute_notebook_edit(input: &Value, ctx: &zdx_tools::ToolContext) -> ToolOutput {
+    execute_blocking(ctx.timeout, {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        move || notebook::execute_edit(&input, &ctx)
+    })
+    .await
+}
+
+async fn execute_env_info(input: &Value, ctx: &zdx_tools::ToolContext) -> ToolOutput {
+    execute_blocking(ctx.timeout, {
+        let input = input.clone();
+        let ctx = ctx.clone();
+        move || env_info::execute(&input, &ctx)
+    })
+    .await
+}
+
 /// Execute a blocking tool function with optional timeout.
 async fn execute_blocking<F>(timeout: Option<Duration>, f: F) -> ToolOutput
 where
@@ -864,6 +1225,7 @@ mod tests {
         assert_eq!(names.len(), tools.len());
 
         // Verify all expected tools are present
+        assert!(names.contains(&"ask_user".to_string()));
         assert!(names.contains(&"bash".to_string()));
         assert!(names.contains(&"apply_patch".to_string()));
         assert!(names.contains(&"edit".to_string()));
@@ -945,4 +1307,28 @@ mod tests {
         );
         assert!(data["content"].as_str().unwrap().contains("# Memory"));
     }
+
+    #[test]
+    fn test_shell_sessions_close_idle_and_least_recently_used() {
+        let start = Instant::now();
+        let mut sessions = ShellSessions::default();
+        let held = sessions.slot("busy", start);
+        for i in 1..SHELL_SESSION_MAX {
+            drop(sessions.slot(&format!("t{i}"), start + Duration::from_secs(i as u64)));
+        }
+        assert_eq!(sessions.entries.len(), SHELL_SESSION_MAX);
+
+        // At the cap the least recently used idle session makes room; the
+        // older "busy" one survives because a call still holds it.
+        drop(sessions.slot("new", start + Duration::from_secs(100)));
+        assert_eq!(sessions.entries.len(), SHELL_SESSION_MAX);
+        assert!(sessions.entries.contains_key("busy"));
+        assert!(!sessions.entries.contains_key("t1"));
+
+        sessions.reap(start + SHELL_SESSION_IDLE_TTL + Duration::from_secs(100));
+        assert_eq!(sessions.entries.keys().collect::<Vec<_>>(), ["busy"]);
+        drop(held);
+        sessions.reap(start + SHELL_SESSION_IDLE_TTL);
+        assert!(sessions.entries.is_empty());
+    }
 }
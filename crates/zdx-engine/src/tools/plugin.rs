@@ -0,0 +1,410 @@
+//! WASM tool plugins loaded from `<ZDX_HOME>/plugins/*.wasm`.
+//!
+//! A plugin is a WASI preview1 module exporting two functions that take no
+//! arguments (`_initialize` is called first when present):
+//! - `describe`: writes `{"name", "description", "input_schema"}` JSON to stdout.
+//! - `execute`: reads the tool input (JSON) from stdin and writes the result
+//!   to stdout.
+//!
+//! Every call runs in a fresh instance with no environment, no network, only
+//! the project root pre-opened (as `.`), a linear-memory cap, and a timeout.
+
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{Value, json};
+use wasmtime::{Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder, Trap};
+use wasmtime_wasi::p2::pipe::{MemoryInputPipe, MemoryOutputPipe};
+use wasmtime_wasi::preview1::{self, WasiP1Ctx};
+use wasmtime_wasi::{DirPerms, FilePerms, I32Exit, WasiCtxBuilder};
+
+use super::custom::is_valid_tool_name;
+use super::{Tool, ToolContext, ToolDefinition, ToolFuture, ToolRegistry};
+use crate::config::{PluginsConfig, paths};
+use crate::core::events::ToolOutput;
+
+/// Epoch tick used to enforce call timeouts.
+const EPOCH_TICK: Duration = Duration::from_millis(100);
+
+/// Loads every valid plugin from `<ZDX_HOME>/plugins` into `registry`.
+///
+/// Plugins that fail to compile or describe themselves, or whose name
+/// conflicts with an already registered tool, are skipped with a warning.
+pub fn register_plugins(registry: &mut ToolRegistry, config: &PluginsConfig) {
+    if config.enabled {
+        register_plugins_from(registry, &paths::plugins_dir(), config);
+    }
+}
+
+fn register_plugins_from(registry: &mut ToolRegistry, dir: &Path, config: &PluginsConfig) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "wasm"))
+        .collect();
+    files.sort();
+
+    let limits = Limits::from_config(config);
+    let mut existing = registry.tool_names();
+    for path in files {
+        let loaded = std::fs::read(&path)
+            .map_err(|e| format!("failed to read: {e}"))
+            .and_then(|bytes| PluginTool::load(&bytes, limits));
+        match loaded {
+            Ok(tool)
+                if existing
+                    .iter()
+                    .any(|name| name.eq_ignore_ascii_case(&tool.definition.name)) =>
+            {
+                tracing::warn!(
+                    plugin = %path.display(),
+                    "Skipping plugin: name `{}` conflicts with an existing tool",
+                    tool.definition.name
+                );
+            }
+            Ok(tool) => {
+                existing.push(tool.definition.name.clone());
+                registry.register_user_tool(tool);
+            }
+            Err(message) => {
+                tracing::warn!(plugin = %path.display(), "Skipping plugin: {message}");
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Limits {
+    timeout: Option<Duration>,
+    max_memory_bytes: usize,
+    max_output_bytes: usize,
+    allow_write: bool,
+}
+
+impl Limits {
+    fn from_config(config: &PluginsConfig) -> Self {
+        Self {
+            timeout: (config.timeout_secs > 0).then(|| Duration::from_secs(config.timeout_secs)),
+            max_memory_bytes: config.max_memory_mb.saturating_mul(1024 * 1024),
+            max_output_bytes: config.max_output_bytes,
+            allow_write: config.allow_write,
+        }
+    }
+}
+
+struct PluginTool {
+    definition: ToolDefinition,
+    module: Module,
+    limits: Limits,
+}
+
+#[derive(Deserialize)]
+struct Manifest {
+    name: String,
+    #[serde(default)]
+    description: String,
+    #[serde(default = "default_input_schema")]
+    input_schema: Value,
+}
+
+fn default_input_schema() -> Value {
+    json!({ "type": "object", "properties": {} })
+}
+
+impl PluginTool {
+    fn load(bytes: &[u8], limits: Limits) -> Result<Self, String> {
+        let engine = engine().map_err(|e| format!("failed to start WASM engine: {e}"))?;
+        let module =
+            Module::new(&engine, bytes).map_err(|e| format!("failed to compile: {e:#}"))?;
+
+        // Sync WASI blocks on the ambient tokio runtime when there is one,
+        // which panics on a runtime worker; describe on a plain thread.
+        let output = std::thread::scope(|scope| {
+            scope
+                .spawn(|| call(&module, "describe", b"", None, limits))
+                .join()
+        })
+        .map_err(|_panic| "describe panicked".to_string())?
+        .map_err(|e| format!("describe failed: {}", e.message()))?;
+
+        let manifest: Manifest = serde_json::from_str(&output.stdout)
+            .map_err(|e| format!("describe output is not valid JSON: {e}"))?;
+        let name = manifest.name.trim();
+        if !is_valid_tool_name(name) {
+            return Err("name must be non-empty and use only letters, digits, `_`, `-`".into());
+        }
+        if !manifest.input_schema.is_object() {
+            return Err("input_schema must be a JSON object".into());
+        }
+
+        Ok(Self {
+            definition: ToolDefinition {
+                name: name.to_string(),
+                description: manifest.description,
+                input_schema: manifest.input_schema,
+            },
+            module,
+            limits,
+        })
+    }
+}
+
+impl Tool for PluginTool {
+    fn definition(&self) -> ToolDefinition {
+        self.definition.clone()
+    }
+
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let module = self.module.clone();
+        let limits = self.limits;
+        let root = ctx.root.clone();
+        let payload = input.to_string();
+        Box::pin(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                call(&module, "execute", payload.as_bytes(), Some(&root), limits)
+            })
+            .await;
+            match result {
+                Ok(Ok(output)) => ToolOutput::success(json!({
+                    "output": output.stdout,
+                    "stderr": output.stderr,
+                })),
+                Ok(Err(error)) => error.into_tool_output(),
+                Err(e) => ToolOutput::failure(
+                    "plugin_error",
+                    "Plugin call panicked",
+                    Some(format!("Error: {e}")),
+                ),
+            }
+        })
+    }
+}
+
+struct PluginState {
+    wasi: WasiP1Ctx,
+    limits: StoreLimits,
+}
+
+struct CallOutput {
+    stdout: String,
+    stderr: String,
+}
+
+enum CallError {
+    Timeout(Duration),
+    Exit { code: i32, stderr: String },
+    Failed { message: String, stderr: String },
+}
+
+impl CallError {
+    fn message(&self) -> String {
+        match self {
+            Self::Timeout(duration) => {
+                format!("Plugin timed out after {} seconds", duration.as_secs())
+            }
+            Self::Exit { code, .. } => format!("Plugin exited with code {code}"),
+            Self::Failed { message, .. } => message.clone(),
+        }
+    }
+
+    fn into_tool_output(self) -> ToolOutput {
+        let message = self.message();
+        match self {
+            Self::Timeout(_) => ToolOutput::failure("timeout", message, None),
+            Self::Exit { stderr, .. } => {
+                ToolOutput::failure("plugin_failed", message, Some(stderr))
+            }
+            Self::Failed { stderr, .. } => {
+                ToolOutput::failure("plugin_error", message, Some(stderr))
+            }
+        }
+    }
+}
+
+/// Instantiates `module` in a fresh sandbox and calls `export`.
+fn call(
+    module: &Module,
+    export: &str,
+    stdin: &[u8],
+    root: Option<&Path>,
+    limits: Limits,
+) -> Result<CallOutput, CallError> {
+    let stdout = MemoryOutputPipe::new(limits.max_output_bytes);
+    let stderr = MemoryOutputPipe::new(limits.max_output_bytes);
+    let collected_stderr = || String::from_utf8_lossy(&stderr.contents()).into_owned();
+
+    let mut builder = WasiCtxBuilder::new();
+    builder
+        .stdin(MemoryInputPipe::new(stdin.to_vec()))
+        .stdout(stdout.clone())
+        .stderr(stderr.clone());
+    if let Some(root) = root {
+        let (dir_perms, file_perms) = if limits.allow_write {
+            (DirPerms::all(), FilePerms::all())
+        } else {
+            (DirPerms::READ, FilePerms::READ)
+        };
+        builder
+            .preopened_dir(root, ".", dir_perms, file_perms)
+            .map_err(|e| CallError::Failed {
+                message: format!("Failed to pre-open {}: {e}", root.display()),
+                stderr: String::new(),
+            })?;
+    }
+
+    let state = PluginState {
+        wasi: builder.build_p1(),
+        limits: StoreLimitsBuilder::new()
+            .memory_size(limits.max_memory_bytes)
+            .build(),
+    };
+    let mut store = Store::new(module.engine(), state);
+    store.limiter(|state| &mut state.limits);
+    store.set_epoch_deadline(limits.timeout.map_or(u64::MAX, |timeout| {
+        (timeout.as_millis() / EPOCH_TICK.as_millis()) as u64 + 1
+    }));
+
+    let result = (|| {
+        let mut linker = Linker::new(module.engine());
+        preview1::add_to_linker_sync(&mut linker, |state: &mut PluginState| &mut state.wasi)?;
+        let instance = linker.instantiate(&mut store, module)?;
+        if let Ok(init) = instance.get_typed_func::<(), ()>(&mut store, "_initialize") {
+            init.call(&mut store, ())?;
+        }
+        instance
+            .get_typed_func::<(), ()>(&mut store, export)?
+            .call(&mut store, ())
+    })();
+
+    match result {
+        Ok(()) => {}
+        Err(e) => match e.downcast_ref::<I32Exit>() {
+            Some(I32Exit(0)) => {}
+            Some(I32Exit(code)) => {
+                return Err(CallError::Exit {
+                    code: *code,
+                    stderr: collected_stderr(),
+                });
+            }
+            None if e.downcast_ref::<Trap>() == Some(&Trap::Interrupt) => {
+                return Err(CallError::Timeout(limits.timeout.unwrap_or_default()));
+            }
+            None => {
+                return Err(CallError::Failed {
+                    message: format!("Plugin `{export}` failed: {e:#}"),
+                    stderr: collected_stderr(),
+                });
+            }
+        },
+    }
+
+    Ok(CallOutput {
+        stdout: String::from_utf8_lossy(&stdout.contents()).into_owned(),
+        stderr: collected_stderr(),
+    })
+}
+
+/// Shared engine with epoch interruption, ticked by a background thread.
+fn engine() -> anyhow::Result<Engine> {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    if let Some(engine) = ENGINE.get() {
+        return Ok(engine.clone());
+    }
+
+    let mut config = wasmtime::Config::new();
+    config.epoch_interruption(true);
+    let engine = Engine::new(&config)?;
+    Ok(ENGINE
+        .get_or_init(|| {
+            let ticker = engine.clone();
+            let _ = std::thread::Builder::new()
+                .name("zdx-wasm-epoch".to_string())
+                .spawn(move || {
+                    loop {
+                        std::thread::sleep(EPOCH_TICK);
+                        ticker.increment_epoch();
+                    }
+                });
+            engine
+        })
+        .clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reactor plugin: `describe` prints a fixed description, `execute`
+    /// echoes stdin to stdout.
+    const ECHO_PLUGIN: &str = r#"
+        (module
+          (import "wasi_snapshot_preview1" "fd_read"
+            (func $fd_read (param i32 i32 i32 i32) (result i32)))
+          (import "wasi_snapshot_preview1" "fd_write"
+            (func $fd_write (param i32 i32 i32 i32) (result i32)))
+          (memory (export "memory") 1)
+          (data (i32.const 1024)
+            "{\"name\":\"Echo\",\"description\":\"Echoes input\",\"input_schema\":{\"type\":\"object\"}}")
+          (func (export "describe")
+            (i32.store (i32.const 0) (i32.const 1024))
+            (i32.store (i32.const 4) (i32.const 77))
+            (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8))))
+          (func (export "execute")
+            (i32.store (i32.const 0) (i32.const 2048))
+            (i32.store (i32.const 4) (i32.const 1024))
+            (drop (call $fd_read (i32.const 0) (i32.const 0) (i32.const 1) (i32.const 8)))
+            (i32.store (i32.const 4) (i32.load (i32.const 8)))
+            (drop (call $fd_write (i32.const 1) (i32.const 0) (i32.const 1) (i32.const 8)))))
+    "#;
+
+    const SPIN_PLUGIN: &str = r#"
+        (module
+          (func (export "execute") (loop (br 0))))
+    "#;
+
+    fn limits() -> Limits {
+        Limits {
+            timeout: Some(Duration::from_millis(200)),
+            max_memory_bytes: 1024 * 1024,
+            max_output_bytes: 4096,
+            allow_write: false,
+        }
+    }
+
+    fn module(wat: &str) -> Module {
+        Module::new(&engine().unwrap(), wat).unwrap()
+    }
+
+    #[test]
+    fn test_load_reads_description() {
+        let tool = PluginTool::load(ECHO_PLUGIN.as_bytes(), limits()).unwrap();
+        let definition = tool.definition();
+        assert_eq!(definition.name, "Echo");
+        assert_eq!(definition.description, "Echoes input");
+        assert_eq!(definition.input_schema, json!({ "type": "object" }));
+    }
+
+    #[tokio::test]
+    async fn test_execute_passes_input_on_stdin() {
+        let tool = PluginTool::load(ECHO_PLUGIN.as_bytes(), limits()).unwrap();
+        let ctx = ToolContext::new(std::env::temp_dir(), None);
+        let output = tool.execute(&json!({ "q": "hi" }), &ctx).await;
+        assert!(output.is_ok());
+        assert_eq!(output.data().unwrap()["output"], json!(r#"{"q":"hi"}"#));
+    }
+
+    #[test]
+    fn test_timeout_and_memory_limits_are_enforced() {
+        let result = call(&module(SPIN_PLUGIN), "execute", b"", None, limits());
+        assert!(matches!(result, Err(CallError::Timeout(_))));
+
+        let big_memory = module(r#"(module (memory 32) (func (export "execute")))"#);
+        let result = call(&big_memory, "execute", b"", None, limits());
+        assert!(matches!(result, Err(CallError::Failed { .. })));
+    }
+}
@@ -0,0 +1,285 @@
+//! Secret-file guard for `Read` (`[security] protected_globs`).
+//!
+//! A file whose name or root-relative path matches a protected glob (`.env`,
+//! `*.pem`, `id_rsa`, …) is not read unless the user approves that file, so
+//! credentials don't end up in a provider request by accident. Interactive
+//! runs ask through the `Ask_User` channel; an approved file stays readable
+//! for whoever owns the [`ProtectedApprovals`] set (a TUI thread, a single
+//! bot turn), never for other threads or chats. Runs with no one to ask
+//! (exec, subagents) block the read.
+
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use globset::{GlobBuilder, GlobMatcher};
+use serde_json::Value;
+
+use super::ToolContext;
+use crate::config::SecurityConfig;
+use crate::core::events::ToolOutput;
+
+/// Protected files the user approved, by canonical path. Clones share the
+/// set, so one approval covers every turn that holds the same handle.
+#[derive(Debug, Clone, Default)]
+pub struct ProtectedApprovals(Arc<Mutex<HashSet<PathBuf>>>);
+
+impl ProtectedApprovals {
+    fn contains(&self, path: &Path) -> bool {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .contains(path)
+    }
+
+    fn insert(&self, path: PathBuf) {
+        self.0
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .insert(path);
+    }
+}
+
+/// Returns the first protected glob matching `path`, unless a `!` pattern
+/// exempts it. `path` is checked as given and after resolving symlinks; each
+/// is exempted on its own, so a link whose name is exempt still counts as
+/// protected when its target is.
+pub fn matching_glob(globs: &[String], path: &Path, root: &Path) -> Option<String> {
+    let canonical = path.canonicalize().ok();
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let matches = |pattern: &str, candidate: &Path| {
+        let Some(matcher) = compile(pattern) else {
+            return false;
+        };
+        if pattern.contains('/') {
+            let relative = candidate.strip_prefix(&root).unwrap_or(candidate);
+            matcher.is_match(relative)
+        } else {
+            candidate
+                .file_name()
+                .is_some_and(|name| matcher.is_match(Path::new(name)))
+        }
+    };
+
+    std::iter::once(path)
+        .chain(canonical.as_deref())
+        .filter(|candidate| {
+            !globs
+                .iter()
+                .filter_map(|glob| glob.strip_prefix('!'))
+                .any(|exempt| matches(exempt, candidate))
+        })
+        .find_map(|candidate| {
+            globs
+                .iter()
+                .filter(|glob| !glob.starts_with('!'))
+                .find(|glob| matches(glob, candidate))
+                .cloned()
+        })
+}
+
+fn compile(pattern: &str) -> Option<GlobMatcher> {
+    GlobBuilder::new(pattern)
+        .literal_separator(true)
+        .build()
+        .inspect_err(|err| tracing::warn!(pattern, %err, "Invalid [security] protected glob"))
+        .ok()
+        .map(|glob| glob.compile_matcher())
+}
+
+/// Checks a `Read` call against the protected globs.
+///
+/// # Errors
+/// Returns the `protected_file` failure to report instead of reading when the
+/// file is protected and the user did not approve it.
+pub async fn check_read(input: &Value, ctx: &ToolContext) -> Result<(), ToolOutput> {
+    let Some(requested) = input
+        .get("file_path")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|path| !path.is_empty())
+    else {
+        return Ok(());
+    };
+    let default_config = SecurityConfig::default();
+    let config = ctx
+        .config
+        .as_ref()
+        .map_or(&default_config, |config| &config.security);
+    let normalized = zdx_tools::normalize_input_path(requested);
+    let path = zdx_tools::resolve_path_against_root(&normalized, &ctx.root);
+    let Some(glob) = matching_glob(&config.protected_globs, &path, &ctx.root) else {
+        return Ok(());
+    };
+
+    let key = path.canonicalize().unwrap_or_else(|_| path.clone());
+    if ctx.protected_approvals.contains(&key) {
+        return Ok(());
+    }
+    let Some(handle) = ctx.ask_user.as_ref() else {
+        return Err(blocked(
+            requested,
+            &glob,
+            "No user can approve it in this run. Ask the user to share the values you need, \
+             or to change [security] protected_globs.",
+        ));
+    };
+
+    let question = format!(
+        "The agent wants to read `{requested}`, which matches the protected pattern `{glob}` \
+         ([security] protected_globs) and may contain secrets. Its contents would be sent to \
+         the model provider. Allow reading this file? (yes/no)"
+    );
+    let tool_use_id = ctx.tool_use_id.as_deref().unwrap_or_default();
+    let cancel = ctx.cancel.clone().unwrap_or_default();
+    let reply = tokio::select! {
+        () = cancel.cancelled() => Err("The turn was interrupted.".to_string()),
+        reply = handle.ask(tool_use_id, &question) => reply,
+    };
+    match reply {
+        Ok(answer) if is_yes(&answer) => {
+            ctx.protected_approvals.insert(key);
+            Ok(())
+        }
+        Ok(_) => Err(blocked(
+            requested,
+            &glob,
+            "The user declined. Do not try to read it another way.",
+        )),
+        Err(reason) => Err(blocked(requested, &glob, &reason)),
+    }
+}
+
+fn blocked(requested: &str, glob: &str, details: &str) -> ToolOutput {
+    ToolOutput::failure(
+        "protected_file",
+        format!(
+            "'{requested}' matches the protected pattern '{glob}' ([security] protected_globs) \
+             and was not read"
+        ),
+        Some(details.to_string()),
+    )
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(
+        answer
+            .trim()
+            .trim_end_matches('.')
+            .to_ascii_lowercase()
+            .as_str(),
+        "y" | "yes" | "allow" | "ok"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn defaults() -> Vec<String> {
+        SecurityConfig::default().protected_globs
+    }
+
+    #[test]
+    fn default_globs_match_secret_file_names_only() {
+        let root = Path::new("/repo");
+        let globs = defaults();
+        assert_eq!(
+            matching_glob(&globs, Path::new("/repo/.env"), root).as_deref(),
+            Some(".env")
+        );
+        assert_eq!(
+            matching_glob(&globs, Path::new("/repo/api/.env.production"), root).as_deref(),
+            Some(".env.*")
+        );
+        assert_eq!(
+            matching_glob(&globs, Path::new("/home/me/.ssh/id_ed25519"), root).as_deref(),
+            Some("id_ed25519")
+        );
+        assert!(matching_glob(&globs, Path::new("/repo/certs/server.pem"), root).is_some());
+        assert!(matching_glob(&globs, Path::new("/repo/.env.example"), root).is_none());
+        assert!(matching_glob(&globs, Path::new("/repo/src/env.rs"), root).is_none());
+        assert!(matching_glob(&globs, Path::new("/repo/id_rsa.pub"), root).is_none());
+    }
+
+    #[test]
+    fn globs_with_slash_match_the_root_relative_path() {
+        let root = Path::new("/repo");
+        let globs = vec!["config/secrets/**".to_string()];
+        assert!(matching_glob(&globs, Path::new("/repo/config/secrets/prod.yml"), root).is_some());
+        assert!(matching_glob(&globs, Path::new("/repo/config/app.yml"), root).is_none());
+    }
+
+    #[tokio::test]
+    async fn read_without_a_user_is_blocked() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=secret").unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "hello").unwrap();
+        let ctx = ToolContext::new(dir.path().to_path_buf(), None);
+
+        let output = check_read(&json!({"file_path": ".env"}), &ctx)
+            .await
+            .expect_err("protected file must be blocked");
+        let text = serde_json::to_string(&output).unwrap();
+        assert!(text.contains("protected_file"), "{text}");
+
+        assert!(
+            check_read(&json!({"file_path": "notes.txt"}), &ctx)
+                .await
+                .is_ok()
+        );
+    }
+
+    #[tokio::test]
+    async fn approval_is_asked_once_per_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("deploy.key"), "-----BEGIN").unwrap();
+        let (handle, mut rx) = crate::core::ask_user::channel();
+        let mut ctx = ToolContext::new(dir.path().to_path_buf(), None);
+        ctx.ask_user = Some(handle);
+
+        let answerer = tokio::spawn(async move {
+            let question = rx.recv().await.expect("question");
+            assert!(question.question.contains("deploy.key"));
+            question.answer("yes");
+            rx
+        });
+        let input = json!({"file_path": "deploy.key"});
+        assert!(check_read(&input, &ctx).await.is_ok());
+        let rx = answerer.await.unwrap();
+
+        // Approved: no second question (the receiver is gone, so asking
+        // again would fail).
+        drop(rx);
+        assert!(check_read(&input, &ctx).await.is_ok());
+
+        // Another thread or chat holds its own approvals and is not covered.
+        let mut other = ToolContext::new(dir.path().to_path_buf(), None);
+        other.ask_user = ctx.ask_user.clone();
+        assert!(check_read(&input, &other).await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn exempt_link_name_does_not_unprotect_its_target() {
+        use std::os::unix::fs::symlink;
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".env"), "TOKEN=secret").unwrap();
+        let link = dir.path().join(".env.example");
+        symlink(dir.path().join(".env"), &link).unwrap();
+        let globs = defaults();
+
+        assert_eq!(
+            matching_glob(&globs, &link, dir.path()).as_deref(),
+            Some(".env")
+        );
+        // A real exempt file stays readable.
+        let sub = dir.path().join("sub");
+        std::fs::create_dir(&sub).unwrap();
+        std::fs::write(sub.join(".env.example"), "TOKEN=").unwrap();
+        assert!(matching_glob(&globs, &sub.join(".env.example"), dir.path()).is_none());
+    }
+}
@@ -1,13 +1,14 @@
 //! Read thread tool.
 //!
-//! Answers a goal based on a saved thread transcript.
+//! Answers a goal based on a saved thread transcript, or returns a filtered
+//! slice of the transcript directly when no goal is given.
 
 use serde::Deserialize;
 use serde_json::{Value, json};
 
 use super::{ToolContext, ToolDefinition};
 use crate::core::events::ToolOutput;
-use crate::core::subagent::{ExecSubagentOptions, run_exec_subagent};
+use crate::core::subagent::{ExecSubagentOptions, run_exec_subagent_with_cancel};
 use crate::core::thread_persistence as tp;
 use crate::prompts::READ_THREAD_PROMPT_TEMPLATE;
 use crate::zdx_context::build_zdx_context;
@@ -16,7 +17,7 @@ use crate::zdx_context::build_zdx_context;
 pub fn definition() -> ToolDefinition {
     ToolDefinition {
         name: "Read_Thread".to_string(),
-        description: "Answer a goal using a saved thread transcript. If the caller already provides a thread_id (for example the user pasted one), use it directly — do not call Thread_Search first. Only fall back to Thread_Search when the thread_id is unknown; never guess or invent one. Provide a specific `goal` describing what to extract; vague goals return vague answers. Best for historical thread context, prior decisions, or past outputs rather than current filesystem state. Returns response text only."
+        description: "Answer a goal using a saved thread transcript. If the caller already provides a thread_id (for example the user pasted one), use it directly — do not call Thread_Search first. Only fall back to Thread_Search when the thread_id is unknown; never guess or invent one. Provide a specific `goal` describing what to extract; vague goals return vague answers. Best for historical thread context, prior decisions, or past outputs rather than current filesystem state. Narrow long threads with `kinds`, `from_turn`/`to_turn`, or `tail` instead of reading everything. Omit `goal` to get the selected transcript itself (capped by `max_tokens`, with a hint for paging further back); with a goal, returns response text only."
             .to_string(),
         input_schema: json!({
             "type": "object",
@@ -27,19 +28,118 @@ pub fn definition() -> ToolDefinition {
                 },
                 "goal": {
                     "type": "string",
-                    "description": "A clear description of what information you need from the thread. Be specific about what to extract."
+                    "description": "A clear description of what information you need from the thread. Be specific about what to extract. Omit to return the selected transcript verbatim."
+                },
+                "kinds": {
+                    "type": "array",
+                    "description": "Only include these event kinds. Defaults to all.",
+                    "items": {
+                        "type": "string",
+                        "enum": ["messages", "tool_calls", "reasoning", "notices"]
+                    }
+                },
+                "from_turn": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "First turn to include (1-based; a turn starts at each user message)."
+                },
+                "to_turn": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Last turn to include (inclusive)."
+                },
+                "tail": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Only include the last N matching events (e.g. 2 for the last exchange)."
+                },
+                "max_tokens": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Approximate token budget for the selected transcript; older events are dropped first. Defaults to 8000 when `goal` is omitted, unlimited otherwise."
                 }
             },
-            "required": ["thread_id", "goal"],
+            "required": ["thread_id"],
             "additionalProperties": false
         }),
     }
 }
 
+/// Default transcript budget when returning the transcript verbatim.
+const DEFAULT_RAW_MAX_TOKENS: usize = 8_000;
+
 #[derive(Debug, Deserialize)]
 struct ReadThreadInput {
     thread_id: String,
-    goal: String,
+    #[serde(default)]
+    goal: Option<String>,
+    #[serde(flatten)]
+    filter: EventFilter,
+}
+
+/// Event kinds a caller can select.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EventKind {
+    Messages,
+    ToolCalls,
+    Reasoning,
+    Notices,
+}
+
+/// Which slice of the thread to read.
+#[derive(Debug, Default, Deserialize)]
+struct EventFilter {
+    #[serde(default)]
+    kinds: Option<Vec<EventKind>>,
+    #[serde(default)]
+    from_turn: Option<usize>,
+    #[serde(default)]
+    to_turn: Option<usize>,
+    #[serde(default)]
+    tail: Option<usize>,
+    #[serde(default)]
+    max_tokens: Option<usize>,
+}
+
+impl EventFilter {
+    fn is_empty(&self) -> bool {
+        self.kinds.is_none()
+            && self.from_turn.is_none()
+            && self.to_turn.is_none()
+            && self.tail.is_none()
+            && self.max_tokens.is_none()
+    }
+}
+
+/// Result of applying an [`EventFilter`] to a thread.
+#[derive(Debug)]
+struct Selection {
+    events: Vec<tp::ThreadEvent>,
+    total_turns: usize,
+    matched_events: usize,
+    shown_events: usize,
+    /// Inclusive turn span of the shown events.
+    turn_span: Option<(usize, usize)>,
+    estimated_tokens: usize,
+    truncated: bool,
+}
+
+impl Selection {
+    /// Paging hint when the budget dropped matching events.
+    fn truncation_hint(&self) -> Option<String> {
+        if !self.truncated {
+            return None;
+        }
+        let span = self
+            .turn_span
+            .map(|(first, last)| format!(" (turns {first}–{last} of {})", self.total_turns))
+            .unwrap_or_default();
+        Some(format!(
+            "Showing the last {} of {} matching events{span}, ~{} tokens; older events were dropped to fit max_tokens. Use from_turn/to_turn, kinds, or tail to read further back.",
+            self.shown_events, self.matched_events, self.estimated_tokens
+        ))
+    }
 }
 
 /// Executes the read thread tool and returns a structured envelope.
@@ -60,10 +160,24 @@ pub async fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
         return ToolOutput::failure("invalid_input", "thread_id cannot be empty", None);
     }
 
-    let goal = input.goal.trim().to_string();
-    if goal.is_empty() {
+    let goal = input
+        .goal
+        .as_deref()
+        .map(str::trim)
+        .filter(|goal| !goal.is_empty())
+        .map(str::to_string);
+    if input.goal.is_some() && goal.is_none() {
         return ToolOutput::failure("invalid_input", "goal cannot be empty", None);
     }
+    if let (Some(from), Some(to)) = (input.filter.from_turn, input.filter.to_turn)
+        && from > to
+    {
+        return ToolOutput::failure(
+            "invalid_input",
+            format!("from_turn ({from}) must not exceed to_turn ({to})"),
+            None,
+        );
+    }
 
     let events = match tp::load_thread_events(&thread_id) {
         Ok(events) => events,
@@ -84,22 +198,138 @@ pub async fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
         );
     }
 
-    let thread_content = tp::format_transcript(&events);
-    let prompt = build_read_thread_prompt(&thread_content, &goal, &build_zdx_context(&ctx.root));
     let parent = tp::extract_handoff_from_from_events(&events);
+    let Some(goal) = goal else {
+        let max_tokens = input.filter.max_tokens.unwrap_or(DEFAULT_RAW_MAX_TOKENS);
+        let selection = select_events(events, &input.filter, Some(max_tokens));
+        return ToolOutput::success(json!({
+            "thread_id": thread_id,
+            "transcript": tp::format_transcript(&selection.events),
+            "total_turns": selection.total_turns,
+            "matched_events": selection.matched_events,
+            "shown_events": selection.shown_events,
+            "estimated_tokens": selection.estimated_tokens,
+            "truncated": selection.truncated,
+            "hint": selection.truncation_hint(),
+            "parent_handoff_thread": parent,
+        }));
+    };
+
+    let (thread_content, hint) = if input.filter.is_empty() {
+        (tp::format_transcript(&events), None)
+    } else {
+        let selection = select_events(events, &input.filter, input.filter.max_tokens);
+        let hint = selection.truncation_hint();
+        (tp::format_transcript(&selection.events), hint)
+    };
+    let prompt = build_read_thread_prompt(&thread_content, &goal, &build_zdx_context(&ctx.root));
 
     match run_subagent(prompt, ctx).await {
-        Ok(response) => {
-            let output = match &parent {
-                Some(parent_id) => format!("{response}\n\nParent handoff thread: {parent_id}"),
-                None => response,
-            };
+        Ok(mut output) => {
+            if let Some(hint) = hint {
+                output = format!("{output}\n\nNote: {hint}");
+            }
+            if let Some(parent_id) = &parent {
+                output = format!("{output}\n\nParent handoff thread: {parent_id}");
+            }
             ToolOutput::success(Value::String(output))
         }
         Err(err) => ToolOutput::failure("execution_failed", "Read thread failed", Some(err)),
     }
 }
 
+/// Maps a content event to its selectable kind; `None` for bookkeeping events
+/// (meta, usage) that are always kept.
+fn event_kind(event: &tp::ThreadEvent) -> Option<EventKind> {
+    match event {
+        tp::ThreadEvent::Message { .. } => Some(EventKind::Messages),
+        tp::ThreadEvent::ToolUse { .. } | tp::ThreadEvent::ToolResult { .. } => {
+            Some(EventKind::ToolCalls)
+        }
+        tp::ThreadEvent::Reasoning { .. } => Some(EventKind::Reasoning),
+        tp::ThreadEvent::Interrupted { .. } | tp::ThreadEvent::Notice { .. } => {
+            Some(EventKind::Notices)
+        }
+        tp::ThreadEvent::Meta { .. } | tp::ThreadEvent::Usage { .. } => None,
+    }
+}
+
+/// Rough token estimate (~4 bytes per token) for budgeting transcript size.
+fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// Applies kind/turn/tail filters, then drops the oldest matching events
+/// until the formatted transcript fits `max_tokens`.
+fn select_events(
+    events: Vec<tp::ThreadEvent>,
+    filter: &EventFilter,
+    max_tokens: Option<usize>,
+) -> Selection {
+    let mut turn = 0usize;
+    let mut kept = Vec::new();
+    let mut matched: Vec<(usize, tp::ThreadEvent)> = Vec::new();
+    for event in events {
+        if matches!(&event, tp::ThreadEvent::Message { role, .. } if role == "user") {
+            turn += 1;
+        }
+        let Some(kind) = event_kind(&event) else {
+            kept.push(event);
+            continue;
+        };
+        let event_turn = turn.max(1);
+        let kind_ok = filter
+            .kinds
+            .as_ref()
+            .is_none_or(|kinds| kinds.contains(&kind));
+        let turn_ok = filter.from_turn.is_none_or(|from| event_turn >= from)
+            && filter.to_turn.is_none_or(|to| event_turn <= to);
+        if kind_ok && turn_ok {
+            matched.push((event_turn, event));
+        }
+    }
+    let total_turns = turn;
+
+    if let Some(tail) = filter.tail {
+        let skip = matched.len().saturating_sub(tail);
+        matched.drain(..skip);
+    }
+    let matched_events = matched.len();
+
+    let mut costs: Vec<usize> = matched
+        .iter()
+        .map(|(_, event)| estimate_tokens(&tp::format_transcript(std::slice::from_ref(event))))
+        .collect();
+    let mut estimated_tokens: usize = costs.iter().sum();
+    let mut truncated = false;
+    if let Some(budget) = max_tokens {
+        // Always keep the most recent event so the caller sees something.
+        while estimated_tokens > budget && matched.len() > 1 {
+            matched.remove(0);
+            estimated_tokens -= costs.remove(0);
+            truncated = true;
+        }
+    }
+
+    let turn_span = matched
+        .first()
+        .zip(matched.last())
+        .map(|((first, _), (last, _))| (*first, *last));
+    let shown_events = matched.len();
+    // Bookkeeping events lead so the transcript header stays first.
+    kept.extend(matched.into_iter().map(|(_, event)| event));
+
+    Selection {
+        events: kept,
+        total_turns,
+        matched_events,
+        shown_events,
+        turn_span,
+        estimated_tokens,
+        truncated,
+    }
+}
+
 fn build_read_thread_prompt(thread_content: &str, goal: &str, zdx_context: &str) -> String {
     READ_THREAD_PROMPT_TEMPLATE
         .replace("{{ZDX_CONTEXT}}", zdx_context)
@@ -126,10 +356,101 @@ async fn run_subagent(prompt: String, ctx: &ToolContext) -> Result<String, Strin
         activity_kind: Some("helper:read_thread".to_string()),
         thread_origin_kind: Some("helper:read_thread".to_string()),
         thread_parent_id: ctx.current_thread_id.clone(),
+        no_save: ctx.current_thread_id.is_none(),
         ..Default::default()
     };
 
-    run_exec_subagent(&ctx.root, &prompt, &options)
+    run_exec_subagent_with_cancel(&ctx.root, &prompt, &options, ctx.cancel.clone(), None)
         .await
         .map_err(|err| format!("Read thread failed: {err}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_events() -> Vec<tp::ThreadEvent> {
+        vec![
+            tp::ThreadEvent::meta_with_root(None),
+            tp::ThreadEvent::user_message("first question"),
+            tp::ThreadEvent::tool_use("t1", "read", json!({"file_path": "a.rs"})),
+            tp::ThreadEvent::tool_result("t1", json!({"ok": true}), true),
+            tp::ThreadEvent::assistant_message("first answer"),
+            tp::ThreadEvent::user_message("second question"),
+            tp::ThreadEvent::assistant_message("second answer"),
+        ]
+    }
+
+    fn texts(selection: &Selection) -> Vec<String> {
+        selection
+            .events
+            .iter()
+            .filter_map(|event| match event {
+                tp::ThreadEvent::Message { text, .. } => Some(text.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_select_events_filters_kinds_and_turns() {
+        let filter = EventFilter {
+            kinds: Some(vec![EventKind::Messages]),
+            from_turn: Some(2),
+            ..Default::default()
+        };
+        let selection = select_events(sample_events(), &filter, None);
+
+        assert_eq!(selection.total_turns, 2);
+        assert_eq!(texts(&selection), vec!["second question", "second answer"]);
+        assert_eq!(selection.turn_span, Some((2, 2)));
+        assert!(matches!(
+            selection.events.first(),
+            Some(tp::ThreadEvent::Meta { .. })
+        ));
+    }
+
+    #[test]
+    fn test_select_events_tail_returns_last_exchange() {
+        let filter = EventFilter {
+            tail: Some(2),
+            ..Default::default()
+        };
+        let selection = select_events(sample_events(), &filter, None);
+
+        assert_eq!(selection.shown_events, 2);
+        assert_eq!(texts(&selection), vec!["second question", "second answer"]);
+        assert!(selection.truncation_hint().is_none());
+    }
+
+    #[test]
+    fn test_select_events_budget_drops_oldest_with_hint() {
+        let selection = select_events(sample_events(), &EventFilter::default(), Some(10));
+
+        assert!(selection.truncated);
+        assert_eq!(selection.shown_events, 1);
+        assert_eq!(selection.matched_events, 6);
+        assert_eq!(texts(&selection), vec!["second answer"]);
+        let hint = selection.truncation_hint().unwrap();
+        assert!(hint.contains("last 1 of 6 matching events"));
+        assert!(hint.contains("turns 2–2 of 2"));
+    }
+
+    #[test]
+    fn test_input_goal_is_optional() {
+        let input: ReadThreadInput = serde_json::from_value(json!({
+            "thread_id": "abc",
+            "kinds": ["messages", "tool_calls"],
+            "tail": 4
+        }))
+        .unwrap();
+
+        assert!(input.goal.is_none());
+        assert_eq!(
+            input.filter.kinds,
+            Some(vec![EventKind::Messages, EventKind::ToolCalls])
+        );
+        assert_eq!(input.filter.tail, Some(4));
+        assert!(!input.filter.is_empty());
+    }
+}
@@ -0,0 +1,515 @@
+//! The `Run_Tests` tool: a configured test command (`[tools.run_tests]`).
+//!
+//! Output from `cargo test`, `pytest`, and `jest` is parsed into pass/fail
+//! counts plus the failing tests (name, message, truncated trace), so the
+//! model reads a compact summary instead of the raw log. Unrecognized
+//! output falls back to its tail.
+
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::check::{OUTPUT_TAIL_BYTES, run_project_command, tail};
+use super::{Tool, ToolContext, ToolDefinition, ToolFuture, ToolRegistry};
+use crate::config::{RunTestsToolConfig, TestOutputFormat};
+use crate::core::events::ToolOutput;
+
+/// Tool name exposed to the model.
+pub const RUN_TESTS_TOOL_NAME: &str = "Run_Tests";
+
+/// Trace snippets keep at most this many lines and bytes.
+const TRACE_MAX_LINES: usize = 20;
+const TRACE_MAX_BYTES: usize = 2000;
+
+/// Registers the `Run_Tests` tool when `[tools.run_tests]` is configured.
+pub fn register_run_tests_tool(registry: &mut ToolRegistry, config: Option<&RunTestsToolConfig>) {
+    let Some(config) = config else {
+        return;
+    };
+    if config.command.trim().is_empty() {
+        tracing::warn!("Skipping run_tests tool: command is empty");
+        return;
+    }
+    registry.register_user_tool(RunTestsTool {
+        config: config.clone(),
+    });
+}
+
+struct RunTestsTool {
+    config: RunTestsToolConfig,
+}
+
+impl Tool for RunTestsTool {
+    fn definition(&self) -> ToolDefinition {
+        ToolDefinition {
+            name: RUN_TESTS_TOOL_NAME.to_string(),
+            description: format!(
+                "Run the project's tests (`{}`) and return pass/fail counts and the failing \
+                 tests with their messages. Prefer this over running the test command with Bash.",
+                self.config.command
+            ),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "args": {
+                        "type": "string",
+                        "description": "Extra arguments appended to the test command (e.g. a test name filter)"
+                    }
+                }
+            }),
+        }
+    }
+
+    fn execute(&self, input: &Value, ctx: &ToolContext) -> ToolFuture {
+        let config = self.config.clone();
+        let root = ctx.root.clone();
+        let args = input
+            .get("args")
+            .and_then(Value::as_str)
+            .map(str::trim)
+            .unwrap_or_default()
+            .to_string();
+        Box::pin(async move { run_tests(&config, &args, &root).await })
+    }
+}
+
+async fn run_tests(config: &RunTestsToolConfig, args: &str, root: &Path) -> ToolOutput {
+    let command = if args.is_empty() {
+        config.command.clone()
+    } else {
+        format!("{} {args}", config.command)
+    };
+    let (exit_code, output) =
+        match run_project_command("Run_Tests", &command, root, config.timeout_secs).await {
+            Ok(result) => result,
+            Err(failure) => return failure,
+        };
+    parse_test_output(config.format, &output).to_output(exit_code, config.max_failures, &output)
+}
+
+/// Pass/fail/skip totals, summed over every test binary or suite.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TestCounts {
+    pub passed: usize,
+    pub failed: usize,
+    pub skipped: usize,
+}
+
+/// One failing test.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TestFailure {
+    pub name: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub trace: String,
+}
+
+/// Parsed test run. `format` is `None` when the output was not recognized.
+#[derive(Debug, Clone, Default)]
+pub struct TestReport {
+    pub format: Option<TestOutputFormat>,
+    pub counts: TestCounts,
+    pub failures: Vec<TestFailure>,
+}
+
+impl TestReport {
+    /// Tool result: failing tests are still a successful tool call, so the
+    /// model reads the summary instead of a generic error.
+    fn to_output(&self, exit_code: i32, max_failures: usize, output: &str) -> ToolOutput {
+        let shown = max_failures.min(self.failures.len());
+        let format = self.format.map(|format| match format {
+            TestOutputFormat::Cargo => "cargo",
+            TestOutputFormat::Pytest => "pytest",
+            TestOutputFormat::Jest => "jest",
+            TestOutputFormat::Auto => "auto",
+        });
+        let mut data = json!({
+            "passed": exit_code == 0 && self.counts.failed == 0,
+            "exit_code": exit_code,
+            "format": format,
+            "counts": self.counts,
+            "failures": &self.failures[..shown],
+            "truncated": shown < self.failures.len(),
+        });
+        if self.format.is_none() || (exit_code != 0 && self.failures.is_empty()) {
+            data["output"] = json!(tail(output.trim(), OUTPUT_TAIL_BYTES));
+        }
+        ToolOutput::success(data)
+    }
+}
+
+/// Parses test output in `format`, detecting it when `Auto`.
+pub fn parse_test_output(format: TestOutputFormat, output: &str) -> TestReport {
+    let format = match format {
+        TestOutputFormat::Auto => detect_format(output),
+        format => Some(format),
+    };
+    let (counts, failures) = match format {
+        Some(TestOutputFormat::Cargo) => parse_cargo(output),
+        Some(TestOutputFormat::Pytest) => parse_pytest(output),
+        Some(TestOutputFormat::Jest) => parse_jest(output),
+        Some(TestOutputFormat::Auto) | None => Default::default(),
+    };
+    TestReport {
+        format,
+        counts,
+        failures,
+    }
+}
+
+fn detect_format(output: &str) -> Option<TestOutputFormat> {
+    output.lines().find_map(|line| {
+        if line.starts_with("test result: ") {
+            Some(TestOutputFormat::Cargo)
+        } else if pytest_summary(line).is_some() {
+            Some(TestOutputFormat::Pytest)
+        } else if line.trim_start().starts_with("Tests:") {
+            Some(TestOutputFormat::Jest)
+        } else {
+            None
+        }
+    })
+}
+
+/// Adds `N word` pairs (e.g. `3 passed`, `1 failed`) from `parts` to `counts`.
+fn add_counts<'a>(counts: &mut TestCounts, parts: impl Iterator<Item = &'a str>) {
+    for part in parts {
+        let mut words = part.split_whitespace();
+        let (Some(number), Some(word)) = (words.next(), words.next()) else {
+            continue;
+        };
+        let Ok(number) = number.parse::<usize>() else {
+            continue;
+        };
+        match word.trim_end_matches([',', '.']) {
+            "passed" => counts.passed += number,
+            "failed" | "error" | "errors" => counts.failed += number,
+            "ignored" | "skipped" | "todo" => counts.skipped += number,
+            _ => {}
+        }
+    }
+}
+
+/// Keeps the first lines/bytes of a failure trace.
+fn snippet(lines: &[&str]) -> String {
+    let mut trace = lines
+        .iter()
+        .take(TRACE_MAX_LINES)
+        .copied()
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut truncated = lines.len() > TRACE_MAX_LINES;
+    if trace.len() > TRACE_MAX_BYTES {
+        let mut end = TRACE_MAX_BYTES;
+        while !trace.is_char_boundary(end) {
+            end -= 1;
+        }
+        trace.truncate(end);
+        truncated = true;
+    }
+    if truncated {
+        trace.push_str("\n…");
+    }
+    trace.trim().to_string()
+}
+
+/// `cargo test`: `test name ... FAILED` lines, `---- name stdout ----`
+/// blocks, and one `test result:` line per test binary.
+fn parse_cargo(output: &str) -> (TestCounts, Vec<TestFailure>) {
+    let mut counts = TestCounts::default();
+    let mut failed_names = Vec::new();
+    let mut blocks: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+
+    for line in output.lines() {
+        if let Some(summary) = line.strip_prefix("test result: ") {
+            let summary = summary.split_once(". ").map_or(summary, |(_, rest)| rest);
+            add_counts(&mut counts, summary.split(';'));
+        } else if let Some(name) = line
+            .strip_prefix("test ")
+            .and_then(|rest| rest.strip_suffix(" ... FAILED"))
+        {
+            failed_names.push(name.to_string());
+        }
+
+        if let Some(name) = line
+            .strip_prefix("---- ")
+            .and_then(|rest| rest.strip_suffix(" stdout ----"))
+        {
+            blocks.extend(current.take());
+            current = Some((name.to_string(), Vec::new()));
+        } else if line == "failures:" || line.starts_with("test result: ") {
+            blocks.extend(current.take());
+        } else if let Some((_, block)) = current.as_mut() {
+            block.push(line);
+        }
+    }
+    blocks.extend(current);
+
+    let failures = failed_names
+        .into_iter()
+        .map(|name| {
+            let block = blocks
+                .iter()
+                .find(|(block_name, _)| *block_name == name)
+                .map(|(_, lines)| lines.as_slice())
+                .unwrap_or_default();
+            let panic_line = block.iter().position(|line| line.contains("panicked at"));
+            let message = panic_line
+                .and_then(|index| {
+                    block[index + 1..]
+                        .iter()
+                        .find(|line| !line.trim().is_empty())
+                        .filter(|_| block[index].trim_end().ends_with(':'))
+                        .or(Some(&block[index]))
+                })
+                .or_else(|| block.iter().find(|line| !line.trim().is_empty()))
+                .map_or_else(String::new, |line| line.trim().to_string());
+            TestFailure {
+                name,
+                message,
+                trace: snippet(block),
+            }
+        })
+        .collect();
+    (counts, failures)
+}
+
+/// The `=== 1 failed, 2 passed in 0.12s ===` line closing a pytest run.
+fn pytest_summary(line: &str) -> Option<&str> {
+    let inner = line.strip_prefix('=')?.trim_matches(['=', ' ']);
+    let (counts, _duration) = inner.rsplit_once(" in ")?;
+    let known = [
+        "passed", "failed", "error", "errors", "skipped", "xfailed", "xpassed",
+    ];
+    counts
+        .split(", ")
+        .any(|part| known.iter().any(|word| part.ends_with(word)))
+        .then_some(counts)
+}
+
+/// `pytest`: `____ test_name ____` failure sections, the
+/// `FAILED path::test - message` short summary, and the final count line.
+fn parse_pytest(output: &str) -> (TestCounts, Vec<TestFailure>) {
+    let mut counts = TestCounts::default();
+    let mut summary_failures: Vec<(String, String)> = Vec::new();
+    let mut blocks: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+
+    for line in output.lines() {
+        if let Some(summary) = pytest_summary(line) {
+            add_counts(&mut counts, summary.split(", "));
+        }
+        let trimmed = line.trim();
+        if let Some(rest) = trimmed
+            .strip_prefix("FAILED ")
+            .or_else(|| trimmed.strip_prefix("ERROR "))
+        {
+            let (name, message) = rest.split_once(" - ").unwrap_or((rest, ""));
+            summary_failures.push((name.trim().to_string(), message.trim().to_string()));
+        }
+
+        if trimmed.starts_with("___") && trimmed.ends_with("___") {
+            blocks.extend(current.take());
+            let title = trimmed.trim_matches(['_', ' ']);
+            current = Some((title.to_string(), Vec::new()));
+        } else if trimmed.starts_with("===") {
+            blocks.extend(current.take());
+        } else if let Some((_, block)) = current.as_mut() {
+            block.push(line);
+        }
+    }
+    blocks.extend(current);
+
+    let block_message = |block: &[&str]| {
+        block
+            .iter()
+            .find_map(|line| line.strip_prefix("E "))
+            .map(|line| line.trim().to_string())
+            .unwrap_or_default()
+    };
+    let failures = if summary_failures.is_empty() {
+        blocks
+            .iter()
+            .map(|(title, block)| TestFailure {
+                name: title.clone(),
+                message: block_message(block),
+                trace: snippet(block),
+            })
+            .collect()
+    } else {
+        summary_failures
+            .into_iter()
+            .map(|(name, message)| {
+                let block = blocks
+                    .iter()
+                    .find(|(title, _)| name.ends_with(&title.replace('.', "::")))
+                    .map(|(_, block)| block.as_slice())
+                    .unwrap_or_default();
+                TestFailure {
+                    message: if message.is_empty() {
+                        block_message(block)
+                    } else {
+                        message
+                    },
+                    name,
+                    trace: snippet(block),
+                }
+            })
+            .collect()
+    };
+    (counts, failures)
+}
+
+/// `jest`: `● Suite › test` failure sections and the `Tests:` count line.
+fn parse_jest(output: &str) -> (TestCounts, Vec<TestFailure>) {
+    let mut counts = TestCounts::default();
+    let mut blocks: Vec<(String, Vec<&str>)> = Vec::new();
+    let mut current: Option<(String, Vec<&str>)> = None;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(summary) = trimmed.strip_prefix("Tests:") {
+            add_counts(&mut counts, summary.split(", "));
+        }
+        if let Some(name) = trimmed.strip_prefix("● ") {
+            blocks.extend(current.take());
+            current = Some((name.trim().to_string(), Vec::new()));
+        } else if trimmed.starts_with("Test Suites:") {
+            blocks.extend(current.take());
+        } else if let Some((_, block)) = current.as_mut() {
+            block.push(line);
+        }
+    }
+    blocks.extend(current);
+
+    let mut failures: Vec<TestFailure> = Vec::new();
+    for (name, block) in blocks {
+        if failures.iter().any(|failure| failure.name == name) {
+            continue;
+        }
+        let message = block
+            .iter()
+            .find(|line| !line.trim().is_empty())
+            .map_or_else(String::new, |line| line.trim().to_string());
+        failures.push(TestFailure {
+            name,
+            message,
+            trace: snippet(&block),
+        });
+    }
+    (counts, failures)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_cargo_test_output() {
+        let output = "\
+running 3 tests
+test parser::ok_case ... ok
+test parser::skipped ... ignored
+test parser::breaks ... FAILED
+
+failures:
+
+---- parser::breaks stdout ----
+
+thread 'parser::breaks' panicked at src/parser.rs:42:9:
+assertion `left == right` failed
+  left: 1
+ right: 2
+note: run with `RUST_BACKTRACE=1` environment variable to display a backtrace
+
+
+failures:
+    parser::breaks
+
+test result: FAILED. 1 passed; 1 failed; 1 ignored; 0 measured; 0 filtered out; finished in 0.01s
+
+running 2 tests
+test result: ok. 2 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s
+";
+        let report = parse_test_output(TestOutputFormat::Auto, output);
+        assert_eq!(report.format, Some(TestOutputFormat::Cargo));
+        assert_eq!(
+            report.counts,
+            TestCounts {
+                passed: 3,
+                failed: 1,
+                skipped: 1
+            }
+        );
+        assert_eq!(report.failures.len(), 1);
+        assert_eq!(report.failures[0].name, "parser::breaks");
+        assert_eq!(
+            report.failures[0].message,
+            "assertion `left == right` failed"
+        );
+        assert!(report.failures[0].trace.contains("src/parser.rs:42:9"));
+    }
+
+    #[test]
+    fn parses_pytest_output() {
+        let output = "\
+============================= test session starts ==============================
+collected 3 items
+
+tests/test_math.py .F.                                                   [100%]
+
+=================================== FAILURES ===================================
+__________________________________ test_add ___________________________________
+
+    def test_add():
+>       assert add(1, 1) == 3
+E       assert 2 == 3
+
+tests/test_math.py:5: AssertionError
+=========================== short test summary info ============================
+FAILED tests/test_math.py::test_add - assert 2 == 3
+========================= 1 failed, 2 passed in 0.03s ==========================
+";
+        let report = parse_test_output(TestOutputFormat::Auto, output);
+        assert_eq!(report.format, Some(TestOutputFormat::Pytest));
+        assert_eq!((report.counts.passed, report.counts.failed), (2, 1));
+        assert_eq!(report.failures[0].name, "tests/test_math.py::test_add");
+        assert_eq!(report.failures[0].message, "assert 2 == 3");
+        assert!(report.failures[0].trace.contains("tests/test_math.py:5"));
+    }
+
+    #[test]
+    fn parses_jest_output_and_falls_back_for_unknown_output() {
+        let output = "\
+ FAIL  src/sum.test.js
+  ● sum › adds numbers
+
+    expect(received).toBe(expected) // Object.is equality
+
+    Expected: 4
+    Received: 3
+
+Test Suites: 1 failed, 1 total
+Tests:       1 failed, 1 skipped, 3 passed, 5 total
+";
+        let report = parse_test_output(TestOutputFormat::Auto, output);
+        assert_eq!(report.format, Some(TestOutputFormat::Jest));
+        assert_eq!(
+            report.counts,
+            TestCounts {
+                passed: 3,
+                failed: 1,
+                skipped: 1
+            }
+        );
+        assert_eq!(report.failures[0].name, "sum › adds numbers");
+        assert!(report.failures[0].message.starts_with("expect(received)"));
+
+        let unknown = parse_test_output(TestOutputFormat::Auto, "all good");
+        assert!(unknown.format.is_none());
+        let data = unknown.to_output(1, 5, "all good");
+        assert_eq!(data.data().unwrap()["output"], json!("all good"));
+    }
+}
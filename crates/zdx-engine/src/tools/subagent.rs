@@ -1,7 +1,12 @@
 //! Subagent delegation tool.
 //!
 //! Allows the model to delegate a scoped task to an isolated child `zdx exec`
-//! run and return response text only.
+//! run. Each run persists as its own subagent thread parented to the caller,
+//! so it can be resumed or inspected later with `read_thread`.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
 
 use serde::Deserialize;
 use serde_json::{Value, json};
@@ -12,6 +17,7 @@ use crate::core::events::ToolOutput;
 use crate::core::subagent::{
     ExecSubagentOptions, SubagentStreamSink, run_exec_subagent_with_cancel,
 };
+use crate::core::thread_persistence;
 use crate::providers::{ProviderKind, resolve_provider};
 use crate::subagents::{self, RuntimeSubagentSelection, SubagentSummary};
 
@@ -46,6 +52,10 @@ pub fn definition_with_subagents(subagents: &[SubagentSummary]) -> ToolDefinitio
                     "type": "string",
                     "description": "Optional reasoning-level override for this invocation. Set only when the user explicitly requests this level; otherwise omit it to preserve the named subagent profile.",
                     "enum": ["off", "low", "medium", "high", "xhigh", "max"]
+                },
+                "thread_id": {
+                    "type": "string",
+                    "description": "Optional `thread_id` from an earlier invoke_subagent result. Resumes that subagent thread with the new prompt as a follow-up turn, keeping its prior history. Omit to start a fresh child run."
                 }
             },
             "required": ["prompt"],
@@ -60,8 +70,64 @@ struct SubagentInput {
     subagent: Option<String>,
     model: Option<String>,
     thinking_level: Option<crate::config::ThinkingLevel>,
+    thread_id: Option<String>,
+}
+
+/// Most delegated runs kept in the result cache; the oldest is evicted first.
+const RESULT_CACHE_MAX_ENTRIES: usize = 128;
+/// Age after which a cached delegated run is no longer reused.
+const RESULT_CACHE_TTL: Duration = Duration::from_mins(30);
+
+/// Identity of a delegated run for the per-session result cache.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct CacheKey {
+    parent_thread_id: String,
+    subagent: String,
+    model: String,
+    thinking_level: Option<crate::config::ThinkingLevel>,
+    prompt: String,
+}
+
+/// A completed delegated run: its response and the child thread it persisted.
+#[derive(Debug, Clone)]
+struct CachedRun {
+    response: String,
+    thread_id: String,
+}
+
+/// Completed runs with their insertion time, bounded by
+/// [`RESULT_CACHE_MAX_ENTRIES`] and [`RESULT_CACHE_TTL`].
+#[derive(Debug, Default)]
+struct ResultCache {
+    entries: HashMap<CacheKey, (Instant, CachedRun)>,
+}
+
+impl ResultCache {
+    fn get(&mut self, key: &CacheKey, now: Instant) -> Option<CachedRun> {
+        self.entries
+            .retain(|_, (stored_at, _)| now.duration_since(*stored_at) < RESULT_CACHE_TTL);
+        self.entries.get(key).map(|(_, run)| run.clone())
+    }
+
+    fn insert(&mut self, key: CacheKey, run: CachedRun, now: Instant) {
+        if !self.entries.contains_key(&key)
+            && self.entries.len() >= RESULT_CACHE_MAX_ENTRIES
+            && let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, (stored_at, _))| *stored_at)
+                .map(|(key, _)| key.clone())
+        {
+            self.entries.remove(&oldest);
+        }
+        self.entries.insert(key, (now, run));
+    }
 }
 
+/// Results of completed subagent runs, keyed by parent thread + task.
+static RESULT_CACHE: LazyLock<Mutex<ResultCache>> =
+    LazyLock::new(|| Mutex::new(ResultCache::default()));
+
 /// Executes the `invoke_subagent` tool and returns a structured envelope.
 pub async fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
     if !ctx.subagents_enabled {
@@ -76,6 +142,10 @@ pub async fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
         Ok(value) => value,
         Err(err) => return err,
     };
+    let resume_thread_id = match validate_resume_thread(input.thread_id.clone()) {
+        Ok(thread_id) => thread_id,
+        Err(err) => return err,
+    };
     let config = ctx.config.clone().unwrap_or_default();
     let selection = match resolve_subagent_selection(&ctx.root, input.subagent.clone()) {
         Ok(selection) => selection,
@@ -102,7 +172,29 @@ pub async fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
         Err(err) => return err,
     };
 
-    let options = build_exec_options(definition, ctx, model, system_prompt, input.thinking_level);
+    let mut options =
+        build_exec_options(definition, ctx, model, system_prompt, input.thinking_level);
+
+    // Resumed runs continue a conversation, so identical prompts are not
+    // interchangeable there; only fresh delegations from a saved parent
+    // thread are cached, so unrelated unsaved runs never share results.
+    let cache_key = ctx
+        .current_thread_id
+        .clone()
+        .filter(|_| resume_thread_id.is_none() && config.subagents.cache_results)
+        .map(|parent_thread_id| CacheKey {
+            parent_thread_id,
+            subagent: options.thread_subagent_name.clone().unwrap_or_default(),
+            model: options.model.clone().unwrap_or_default(),
+            thinking_level: options.thinking_level,
+            prompt: prompt.clone(),
+        });
+    if let Some(hit) = cache_key.as_ref().and_then(cached_run) {
+        return success_output(&hit.response, &hit.thread_id, true);
+    }
+
+    let thread_id = resume_thread_id.unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    options.thread_id = Some(thread_id.clone());
 
     // Relay the child's tool activity live to the parent's `invoke_subagent`
     // cell when the engine wired an event sender + tool id for this call.
@@ -114,8 +206,21 @@ pub async fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
         _ => None,
     };
 
-    match run_exec_subagent_with_cancel(&ctx.root, &prompt, &options, None, stream).await {
-        Ok(response) => ToolOutput::success(Value::String(response)),
+    match run_exec_subagent_with_cancel(&ctx.root, &prompt, &options, ctx.cancel.clone(), stream)
+        .await
+    {
+        Ok(response) => {
+            if let Some(key) = cache_key {
+                store_cached_run(
+                    key,
+                    CachedRun {
+                        response: response.clone(),
+                        thread_id: thread_id.clone(),
+                    },
+                );
+            }
+            success_output(&response, &thread_id, false)
+        }
         Err(err) => ToolOutput::failure(
             "execution_failed",
             "Subagent execution failed",
@@ -145,6 +250,56 @@ fn parse_input(input: &Value) -> Result<(SubagentInput, String), ToolOutput> {
     Ok((input, prompt))
 }
 
+fn success_output(response: &str, thread_id: &str, cached: bool) -> ToolOutput {
+    ToolOutput::success(json!({
+        "response": response,
+        "thread_id": thread_id,
+        "cached": cached,
+    }))
+}
+
+fn cached_run(key: &CacheKey) -> Option<CachedRun> {
+    RESULT_CACHE
+        .lock()
+        .ok()
+        .and_then(|mut cache| cache.get(key, Instant::now()))
+}
+
+fn store_cached_run(key: CacheKey, run: CachedRun) {
+    if let Ok(mut cache) = RESULT_CACHE.lock() {
+        cache.insert(key, run, Instant::now());
+    }
+}
+
+/// Validates a requested resume target: it must be an existing subagent thread.
+fn validate_resume_thread(thread_id: Option<String>) -> Result<Option<String>, ToolOutput> {
+    let Some(thread_id) = normalize_optional(thread_id) else {
+        return Ok(None);
+    };
+    if !thread_persistence::thread_exists(&thread_id) {
+        return Err(ToolOutput::failure(
+            "invalid_input",
+            format!("Subagent thread '{thread_id}' not found"),
+            Some("Pass the thread_id returned by an earlier invoke_subagent call".to_string()),
+        ));
+    }
+    let origin = thread_persistence::read_thread_origin_kind(&thread_id).map_err(|err| {
+        ToolOutput::failure(
+            "read_error",
+            format!("Failed to read thread '{thread_id}'"),
+            Some(err.to_string()),
+        )
+    })?;
+    if origin.as_deref() != Some("subagent") {
+        return Err(ToolOutput::failure(
+            "invalid_input",
+            format!("Thread '{thread_id}' is not a subagent thread"),
+            Some("Only threads created by invoke_subagent can be resumed".to_string()),
+        ));
+    }
+    Ok(Some(thread_id))
+}
+
 fn build_delegated_prompt(
     prompt: &str,
     can_consult_origin_thread: bool,
@@ -321,14 +476,19 @@ fn build_exec_options(
             "tool_started".to_string(),
             "tool_input_completed".to_string(),
             "tool_completed".to_string(),
+            "assistant_delta".to_string(),
+            "usage_update".to_string(),
         ]),
         timeout: ctx.timeout,
         activity_kind: Some("subagent".to_string()),
         activity_parent_thread_id: ctx.current_thread_id.clone(),
         activity_subagent_name: Some(subagent_name.clone()),
+        thread_id: None,
         thread_origin_kind: Some("subagent".to_string()),
         thread_parent_id: ctx.current_thread_id.clone(),
         thread_subagent_name: Some(subagent_name),
+        no_save: ctx.current_thread_id.is_none(),
+        max_tokens: None,
     }
 }
 
@@ -361,7 +521,7 @@ fn build_subagent_field_description(subagents: &[SubagentSummary]) -> String {
 }
 
 fn build_description(subagents: &[SubagentSummary]) -> String {
-    let mut description = "Delegate a scoped task to an isolated child agent run. Choose the named specialist when one clearly fits: use `explorer` for multi-round local exploration, repo understanding, or thread-history discovery; use `oracle` for deep diagnosis, debugging dead ends, architecture, or tradeoff analysis; use `task` for scoped implementation when no named specialist fits better. Exact one-hop reads or exact string/symbol lookups are direct work and should not be delegated. When multiple subtasks are independent, call `invoke_subagent` multiple times in the same response to run them in parallel; `explorer` is especially appropriate for fan-out across separate search areas. Child runs are self-contained and do not share your full parent reasoning or implicit context, so every important decision, relevant detail, file path, constraint, non-goal, and acceptance criterion must be made explicit in the prompt. Provide a focused prompt with the goal, relevant context, constraints/non-goals, file paths, expected output, and how success should be verified. Trust but verify: if a child reports edits or claims you plan to rely on, inspect the resulting files or key evidence before reporting success. Use `subagent` to select a named configuration, or `task` for the default delegated ZDX behavior when no named specialist fits. Optional `model` and `thinking_level` values are user-controlled overrides for that invocation only; never change a named subagent's defaults autonomously. Returns response text only. Skill names are invalid unless they are also listed as supported subagents.".to_string();
+    let mut description = "Delegate a scoped task to an isolated child agent run. Choose the named specialist when one clearly fits: use `explorer` for multi-round local exploration, repo understanding, or thread-history discovery; use `oracle` for deep diagnosis, debugging dead ends, architecture, or tradeoff analysis; use `task` for scoped implementation when no named specialist fits better. Exact one-hop reads or exact string/symbol lookups are direct work and should not be delegated. When multiple subtasks are independent, call `invoke_subagent` multiple times in the same response to run them in parallel; `explorer` is especially appropriate for fan-out across separate search areas. Child runs are self-contained and do not share your full parent reasoning or implicit context, so every important decision, relevant detail, file path, constraint, non-goal, and acceptance criterion must be made explicit in the prompt. Provide a focused prompt with the goal, relevant context, constraints/non-goals, file paths, expected output, and how success should be verified. Trust but verify: if a child reports edits or claims you plan to rely on, inspect the resulting files or key evidence before reporting success. Use `subagent` to select a named configuration, or `task` for the default delegated ZDX behavior when no named specialist fits. Optional `model` and `thinking_level` values are user-controlled overrides for that invocation only; never change a named subagent's defaults autonomously. Returns `{response, thread_id, cached}`: each run persists as its own subagent thread, so pass `thread_id` back to resume it with a follow-up prompt, or to `read_thread` to inspect what the child did. Identical delegations (same subagent, model, thinking level, and prompt) within a session return the earlier result with `cached: true` instead of re-running. Skill names are invalid unless they are also listed as supported subagents.".to_string();
 
     if !subagents.is_empty() {
         let listed = subagents
@@ -527,6 +687,7 @@ mod tests {
         assert!(props.get("subagent").is_some());
         assert!(props.get("model").is_some());
         assert!(props.get("thinking_level").is_some());
+        assert!(props.get("thread_id").is_some());
     }
 
     #[test]
@@ -553,6 +714,76 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_validate_resume_thread() {
+        assert!(validate_resume_thread(None).unwrap().is_none());
+        assert!(
+            validate_resume_thread(Some("  ".to_string()))
+                .unwrap()
+                .is_none()
+        );
+
+        let missing = validate_resume_thread(Some(uuid::Uuid::new_v4().to_string()));
+        assert!(missing.is_err());
+    }
+
+    #[test]
+    fn test_result_cache_is_keyed_by_task() {
+        let key = CacheKey {
+            parent_thread_id: uuid::Uuid::new_v4().to_string(),
+            subagent: "explorer".to_string(),
+            model: "openai:gpt-5.2".to_string(),
+            thinking_level: Some(crate::config::ThinkingLevel::Low),
+            prompt: "find the config loader".to_string(),
+        };
+        assert!(cached_run(&key).is_none());
+
+        store_cached_run(
+            key.clone(),
+            CachedRun {
+                response: "config.rs".to_string(),
+                thread_id: "child-1".to_string(),
+            },
+        );
+        let hit = cached_run(&key).unwrap();
+        assert_eq!(hit.response, "config.rs");
+        assert_eq!(hit.thread_id, "child-1");
+
+        let other_thinking = CacheKey {
+            thinking_level: Some(crate::config::ThinkingLevel::High),
+            ..key
+        };
+        assert!(cached_run(&other_thinking).is_none());
+    }
+
+    #[test]
+    fn test_result_cache_expires_and_evicts_oldest() {
+        let key = |prompt: usize| CacheKey {
+            parent_thread_id: "parent".to_string(),
+            subagent: "task".to_string(),
+            model: "m".to_string(),
+            thinking_level: None,
+            prompt: prompt.to_string(),
+        };
+        let run = CachedRun {
+            response: "done".to_string(),
+            thread_id: "child".to_string(),
+        };
+        let start = Instant::now();
+        let mut cache = ResultCache::default();
+
+        for i in 0..=RESULT_CACHE_MAX_ENTRIES {
+            cache.insert(key(i), run.clone(), start + Duration::from_secs(i as u64));
+        }
+        assert_eq!(cache.entries.len(), RESULT_CACHE_MAX_ENTRIES);
+        assert!(cache.get(&key(0), start).is_none());
+        assert!(cache.get(&key(1), start).is_some());
+
+        let later = start + RESULT_CACHE_TTL + Duration::from_secs(2);
+        assert!(cache.get(&key(1), later).is_none());
+        assert!(cache.get(&key(RESULT_CACHE_MAX_ENTRIES), later).is_some());
+    }
+
     #[test]
     fn test_resolve_model_prefers_invocation_override() {
         let mut ctx = ToolContext::new(std::path::PathBuf::from("."), None);
@@ -139,11 +139,12 @@ fn build_section_lines(section_obj: &serde_json::Map<String, Value>) -> Vec<Conf
 /// Top-level objects each become their own named section.
 /// Config model fields handled by helper subagents, grouped together on the
 /// Config tab (in display order) instead of being scattered through `core`.
-const HELPER_MODEL_KEYS: [&str; 5] = [
+const HELPER_MODEL_KEYS: [&str; 6] = [
     "title_model",
     "tldr_model",
     "handoff_model",
     "prompt_builder_model",
+    "improve_model",
     "read_thread_model",
 ];
 
@@ -1664,7 +1665,8 @@ pub(crate) fn editable_model_fields(lines: &[ConfigLine]) -> Vec<EditableModelFi
                     ("core", "model")
                     | (
                         "helper models",
-                        "title_model" | "tldr_model" | "handoff_model" | "read_thread_model",
+                        "title_model" | "tldr_model" | "handoff_model" | "read_thread_model"
+                        | "improve_model",
                     ) => Some((key.clone(), ModelFieldKind::Chat)),
                     ("transcription", "model") => Some((
                         "transcription.model".to_string(),
@@ -2112,6 +2114,7 @@ mod transcript_tests {
                 "tldr_model",
                 "handoff_model",
                 "prompt_builder_model",
+                "improve_model",
                 "read_thread_model",
             ],
             "helpers section should list every helper model in display order"
@@ -2,17 +2,18 @@
 
 use anyhow::{Result, bail};
 use zdx_types::ToolDefinition;
+use zdx_types::config::ThinkingLevel;
 
 use super::shared::{
     build_api_messages_with_cache_control, build_beta_header, build_system_blocks,
-    build_thinking_and_output_config, build_tool_defs, send_streaming_request,
-    should_enable_interleaved_thinking_beta,
+    build_thinking_and_output_config, build_tool_defs, build_tool_defs_with_output, output_tool,
+    output_tool_as_text, send_streaming_request, should_enable_interleaved_thinking_beta,
 };
 use super::types::{
     CountTokensRequest, CountTokensResponse, EffortLevel, StreamingMessagesRequest,
 };
 use crate::ProviderKind;
-use crate::shared::{ChatMessage, ProviderStream};
+use crate::shared::{ChatMessage, ProviderStream, Sampling};
 
 const API_VERSION: &str = "2023-06-01";
 
@@ -30,6 +31,10 @@ pub struct AnthropicConfig {
     pub thinking_budget_tokens: u32,
     /// Optional effort level for supported models
     pub thinking_effort: Option<EffortLevel>,
+    /// Answer tool forced via `tool_choice` for `zdx exec --schema`
+    pub output_tool: Option<ToolDefinition>,
+    /// `temperature`/`top_p` (left unset while thinking is enabled)
+    pub sampling: Sampling,
 }
 
 impl AnthropicConfig {
@@ -70,6 +75,8 @@ impl AnthropicConfig {
             thinking_enabled,
             thinking_budget_tokens,
             thinking_effort,
+            output_tool: None,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -78,6 +85,7 @@ impl AnthropicConfig {
 pub struct AnthropicClient {
     config: AnthropicConfig,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
 }
 
 impl AnthropicClient {
@@ -115,7 +123,8 @@ impl AnthropicClient {
 
         Self {
             config,
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
         }
     }
 
@@ -140,18 +149,29 @@ impl AnthropicClient {
 
         let url = format!("{}/v1/messages", self.config.base_url);
 
-        send_streaming_request(&self.http, &url, &request, |builder| {
-            let builder = builder
-                .header("anthropic-version", API_VERSION)
-                .header("x-api-key", &self.config.api_key);
-
-            if beta_header.is_empty() {
-                builder
-            } else {
-                builder.header("anthropic-beta", beta_header)
-            }
+        let stream = send_streaming_request(
+            &self.http,
+            &url,
+            &request,
+            self.extra.as_deref(),
+            |builder| {
+                let builder = builder
+                    .header("anthropic-version", API_VERSION)
+                    .header("x-api-key", &self.config.api_key);
+
+                if beta_header.is_empty() {
+                    builder
+                } else {
+                    builder.header("anthropic-beta", beta_header)
+                }
+            },
+        )
+        .await?;
+        Ok(if self.config.output_tool.is_some() {
+            output_tool_as_text(stream)
+        } else {
+            stream
         })
-        .await
     }
 
     /// Counts the input tokens an outgoing request would consume.
@@ -219,7 +239,8 @@ impl AnthropicClient {
         // to respect Anthropic's limit of 4 cache_control blocks total.
         let api_messages = build_api_messages_with_cache_control(messages);
 
-        let tool_defs = build_tool_defs(tools);
+        let (tool_defs, tool_choice) =
+            build_tool_defs_with_output(tools, self.config.output_tool.as_ref());
 
         let system_blocks = build_system_blocks(system, None);
 
@@ -237,7 +258,9 @@ impl AnthropicClient {
             tools: tool_defs,
             system: system_blocks,
             thinking,
+            tool_choice,
             output_config,
+            sampling: self.config.sampling,
             stream: true,
         };
 
@@ -252,19 +275,28 @@ impl AnthropicClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    let thinking_budget_tokens = ctx
-        .thinking_level
+    // A forced `tool_choice` is rejected with extended thinking on.
+    let output_tool = output_tool(ctx.output_schema);
+    let thinking_level = if output_tool.is_some() {
+        ThinkingLevel::Off
+    } else {
+        ctx.thinking_level
+    };
+    let thinking_budget_tokens = thinking_level
         .compute_reasoning_budget(ctx.max_tokens)
         .unwrap_or(0);
-    Ok(Box::new(AnthropicClient::new(AnthropicConfig::from_env(
+    let mut config = AnthropicConfig::from_env(
         ctx.model.to_string(),
         ctx.max_tokens,
         ctx.base_url,
         ctx.api_key,
-        ctx.thinking_level.is_enabled(),
+        thinking_level.is_enabled(),
         thinking_budget_tokens,
-        EffortLevel::from_thinking_level(ctx.thinking_level, ctx.model),
-    )?)))
+        EffortLevel::from_thinking_level(thinking_level, ctx.model),
+    )?;
+    config.sampling = ctx.sampling;
+    config.output_tool = output_tool;
+    Ok(Box::new(AnthropicClient::new(config)))
 }
 
 #[cfg(test)]
@@ -283,6 +315,8 @@ mod tests {
             thinking_enabled: true,
             thinking_budget_tokens: 2048,
             thinking_effort: Some(EffortLevel::High),
+            output_tool: None,
+            sampling: Sampling::default(),
         };
         let client = AnthropicClient::new(config);
 
@@ -308,6 +342,11 @@ mod tests {
             thinking_enabled: true,
             thinking_budget_tokens: 1024,
             thinking_effort: Some(EffortLevel::Medium),
+            output_tool: None,
+            sampling: Sampling {
+                temperature: None,
+                top_p: Some(0.9),
+            },
         };
         let client = AnthropicClient::new(config);
 
@@ -321,5 +360,51 @@ mod tests {
             json!({"type": "enabled", "budget_tokens": 1024})
         );
         assert_eq!(payload["output_config"], json!({"effort": "medium"}));
+        assert_eq!(payload["top_p"], json!(0.9));
+        assert!(payload.get("temperature").is_none());
+    }
+
+    #[test]
+    fn output_schema_forces_the_answer_tool_without_thinking() {
+        let schema = crate::OutputSchema {
+            name: "answer".to_string(),
+            schema: json!({"type": "object", "properties": {"count": {"type": "integer"}}}),
+        };
+        let config = AnthropicConfig {
+            api_key: "test-key".to_string(),
+            base_url: "http://mock-server".to_string(),
+            model: "claude-sonnet-4-6".to_string(),
+            max_tokens: 4096,
+            thinking_enabled: false,
+            thinking_budget_tokens: 0,
+            thinking_effort: None,
+            output_tool: output_tool(Some(&schema)),
+            sampling: Sampling::default(),
+        };
+        let client = AnthropicClient::new(config);
+
+        let request = client
+            .build_streaming_request(&[ChatMessage::user("hi")], &[], None)
+            .unwrap();
+        let payload = serde_json::to_value(&request).unwrap();
+        assert_eq!(
+            payload["tool_choice"],
+            json!({"type": "tool", "name": "structured_output"})
+        );
+        assert_eq!(payload["tools"][0]["input_schema"], schema.schema);
+        assert!(payload.get("thinking").is_none());
+
+        let read = ToolDefinition {
+            name: "Read".to_string(),
+            description: "Read a file".to_string(),
+            input_schema: json!({"type": "object"}),
+        };
+        let tools = [read];
+        let request = client
+            .build_streaming_request(&[ChatMessage::user("hi")], &tools, None)
+            .unwrap();
+        let payload = serde_json::to_value(&request).unwrap();
+        assert_eq!(payload["tool_choice"], json!({"type": "any"}));
+        assert_eq!(payload["tools"][1]["name"], "structured_output");
     }
 }
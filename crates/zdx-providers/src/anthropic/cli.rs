@@ -2,11 +2,12 @@
 
 use anyhow::{Context, Result};
 use zdx_types::ToolDefinition;
+use zdx_types::config::ThinkingLevel;
 
 use super::shared::{
     build_api_messages_with_cache_control, build_beta_header, build_system_blocks,
-    build_thinking_and_output_config, build_tool_defs, send_streaming_request,
-    should_enable_interleaved_thinking_beta,
+    build_thinking_and_output_config, build_tool_defs_with_output, output_tool,
+    output_tool_as_text, send_streaming_request, should_enable_interleaved_thinking_beta,
 };
 use super::types::{EffortLevel, StreamingMessagesRequest};
 use crate::oauth::claude_cli as oauth_claude_cli;
@@ -28,6 +29,8 @@ pub struct ClaudeCliConfig {
     pub thinking_budget_tokens: u32,
     /// Optional effort level for supported models
     pub thinking_effort: Option<EffortLevel>,
+    /// Answer tool forced via `tool_choice` for `zdx exec --schema`
+    pub output_tool: Option<ToolDefinition>,
 }
 
 impl ClaudeCliConfig {
@@ -49,6 +52,7 @@ impl ClaudeCliConfig {
             thinking_enabled,
             thinking_budget_tokens,
             thinking_effort,
+            output_tool: None,
         }
     }
 }
@@ -83,6 +87,7 @@ pub async fn resolve_credentials() -> Result<oauth_claude_cli::ClaudeCliCredenti
 pub struct ClaudeCliClient {
     config: ClaudeCliConfig,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
 }
 
 impl ClaudeCliClient {
@@ -90,7 +95,8 @@ impl ClaudeCliClient {
     pub fn new(config: ClaudeCliConfig) -> Self {
         Self {
             config,
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
         }
     }
 
@@ -115,16 +121,27 @@ impl ClaudeCliClient {
 
         let url = format!("{}/v1/messages?beta=true", self.config.base_url);
 
-        send_streaming_request(&self.http, &url, &request, |builder| {
-            builder
-                .header("anthropic-version", API_VERSION)
-                .header("Authorization", format!("Bearer {}", creds.access))
-                .header("anthropic-beta", beta_header)
-                .header("user-agent", "claude-cli/2.1.2 (external, cli)")
-                .header("anthropic-dangerous-direct-browser-access", "true")
-                .header("x-app", "cli")
+        let stream = send_streaming_request(
+            &self.http,
+            &url,
+            &request,
+            self.extra.as_deref(),
+            |builder| {
+                builder
+                    .header("anthropic-version", API_VERSION)
+                    .header("Authorization", format!("Bearer {}", creds.access))
+                    .header("anthropic-beta", beta_header)
+                    .header("user-agent", "claude-cli/2.1.2 (external, cli)")
+                    .header("anthropic-dangerous-direct-browser-access", "true")
+                    .header("x-app", "cli")
+            },
+        )
+        .await?;
+        Ok(if self.config.output_tool.is_some() {
+            output_tool_as_text(stream)
+        } else {
+            stream
         })
-        .await
     }
 
     fn build_streaming_request<'a>(
@@ -137,7 +154,8 @@ impl ClaudeCliClient {
         // Only the last content block of the last user message gets cache_control.
         let api_messages = build_api_messages_with_cache_control(messages);
 
-        let tool_defs = build_tool_defs(tools);
+        let (tool_defs, tool_choice) =
+            build_tool_defs_with_output(tools, self.config.output_tool.as_ref());
 
         let system_blocks = build_system_blocks(system, Some(CLAUDE_CODE_SYSTEM_PROMPT));
 
@@ -155,7 +173,10 @@ impl ClaudeCliClient {
             tools: tool_defs,
             system: system_blocks,
             thinking,
+            tool_choice,
             output_config,
+            // The Claude subscription endpoint does not take sampling controls.
+            sampling: crate::shared::Sampling::default(),
             stream: true,
         };
 
@@ -170,18 +191,26 @@ impl ClaudeCliClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    let thinking_budget_tokens = ctx
-        .thinking_level
+    // A forced `tool_choice` is rejected with extended thinking on.
+    let output_tool = output_tool(ctx.output_schema);
+    let thinking_level = if output_tool.is_some() {
+        ThinkingLevel::Off
+    } else {
+        ctx.thinking_level
+    };
+    let thinking_budget_tokens = thinking_level
         .compute_reasoning_budget(ctx.max_tokens)
         .unwrap_or(0);
-    Ok(Box::new(ClaudeCliClient::new(ClaudeCliConfig::new(
+    let mut config = ClaudeCliConfig::new(
         ctx.model.to_string(),
         ctx.max_tokens,
         ctx.base_url,
-        ctx.thinking_level.is_enabled(),
+        thinking_level.is_enabled(),
         thinking_budget_tokens,
-        EffortLevel::from_thinking_level(ctx.thinking_level, ctx.model),
-    ))))
+        EffortLevel::from_thinking_level(thinking_level, ctx.model),
+    );
+    config.output_tool = output_tool;
+    Ok(Box::new(ClaudeCliClient::new(config)))
 }
 
 #[cfg(test)]
@@ -4,16 +4,18 @@
 //! and `ClaudeCliClient` (OAuth).
 
 use anyhow::{Result, bail};
-use zdx_types::ToolDefinition;
+use futures_util::StreamExt;
+use serde_json::Value;
+use zdx_types::{ContentBlockType, StreamEvent, ToolDefinition};
 
 use super::sse::SseParser;
 use super::types::{
     ApiContentBlock, ApiMessage, ApiMessageContent, ApiToolDef, CacheControl, EffortLevel,
-    OutputConfig, StreamingMessagesRequest, SystemBlock, ThinkingConfig,
+    OutputConfig, StreamingMessagesRequest, SystemBlock, ThinkingConfig, ToolChoice,
 };
 use crate::debug_metrics::maybe_wrap_with_metrics;
-use crate::shared::{ChatMessage, ProviderError, ProviderStream, classify_reqwest_error};
-use crate::{DebugTrace, wrap_stream};
+use crate::shared::{ChatMessage, ProviderStream, classify_reqwest_error, http_status_error};
+use crate::{DebugTrace, OutputSchema, wrap_stream};
 
 pub(crate) const INTERLEAVED_THINKING_BETA_HEADER: &str = "interleaved-thinking-2025-05-14";
 
@@ -46,6 +48,112 @@ pub(crate) fn build_tool_defs(tools: &[ToolDefinition]) -> Option<Vec<ApiToolDef
     }
 }
 
+/// Name of the tool that carries a schema-constrained final answer.
+pub(crate) const OUTPUT_TOOL_NAME: &str = "structured_output";
+
+/// Tool whose input schema is the output schema, so the final answer can be
+/// forced through `tool_choice`. `None` when the schema's root is not an
+/// object (tool inputs must be objects); those rely on the prompt alone.
+pub(crate) fn output_tool(schema: Option<&OutputSchema>) -> Option<ToolDefinition> {
+    let schema = schema?;
+    if schema.schema.get("type").and_then(Value::as_str) != Some("object") {
+        return None;
+    }
+    Some(ToolDefinition {
+        name: OUTPUT_TOOL_NAME.to_string(),
+        description: format!(
+            "Return the final answer (`{}`) as this tool's input. Call it once, when the task \
+             is done.",
+            schema.name
+        ),
+        input_schema: schema.schema.clone(),
+    })
+}
+
+/// Tool definitions with the output tool appended, plus the `tool_choice`
+/// that forces it: the output tool itself when it is the only tool, else
+/// `any`, so the agent's own tools stay usable and the answer still has to
+/// arrive as a tool call.
+pub(crate) fn build_tool_defs_with_output<'a>(
+    tools: &'a [ToolDefinition],
+    output_tool: Option<&'a ToolDefinition>,
+) -> (Option<Vec<ApiToolDef<'a>>>, Option<ToolChoice<'a>>) {
+    let Some(output_tool) = output_tool else {
+        return (build_tool_defs(tools), None);
+    };
+    let mut defs: Vec<ApiToolDef<'a>> = tools.iter().map(ApiToolDef::from).collect();
+    defs.push(ApiToolDef::from(output_tool));
+    let choice = if tools.is_empty() {
+        ToolChoice::Tool {
+            name: &output_tool.name,
+        }
+    } else {
+        ToolChoice::Any
+    };
+    (Some(defs), Some(choice))
+}
+
+/// Rewrites the output tool call in `stream` into a text block holding its
+/// JSON input, so the engine sees (and validates) an ordinary final answer.
+pub(crate) fn output_tool_as_text(stream: ProviderStream) -> ProviderStream {
+    let mut output_index = None;
+    let mut other_tool = false;
+    stream
+        .map(move |event| {
+            Ok(match event? {
+                StreamEvent::ContentBlockStart {
+                    index,
+                    block_type: ContentBlockType::ToolUse,
+                    name,
+                    id,
+                    data,
+                    id_origin,
+                } => {
+                    if name.as_deref() == Some(OUTPUT_TOOL_NAME) {
+                        output_index = Some(index);
+                        StreamEvent::ContentBlockStart {
+                            index,
+                            block_type: ContentBlockType::Text,
+                            id: None,
+                            name: None,
+                            data: None,
+                            id_origin: None,
+                        }
+                    } else {
+                        other_tool = true;
+                        StreamEvent::ContentBlockStart {
+                            index,
+                            block_type: ContentBlockType::ToolUse,
+                            name,
+                            id,
+                            data,
+                            id_origin,
+                        }
+                    }
+                }
+                StreamEvent::InputJsonDelta {
+                    index,
+                    partial_json,
+                } if output_index == Some(index) => StreamEvent::TextDelta {
+                    index,
+                    text: partial_json,
+                },
+                // Only the answer was called: the turn is over.
+                StreamEvent::MessageDelta {
+                    stop_reason: Some(reason),
+                    usage,
+                } if reason == "tool_use" && output_index.is_some() && !other_tool => {
+                    StreamEvent::MessageDelta {
+                        stop_reason: Some("end_turn".to_string()),
+                        usage,
+                    }
+                }
+                event => event,
+            })
+        })
+        .boxed()
+}
+
 ///
 /// # Errors
 /// Returns an error if the operation fails.
@@ -176,17 +284,25 @@ pub(crate) async fn send_streaming_request(
     client: &reqwest::Client,
     url: &str,
     request: &StreamingMessagesRequest<'_>,
+    extra: Option<&serde_json::Map<String, serde_json::Value>>,
     header_fn: impl FnOnce(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
 ) -> Result<ProviderStream> {
     use crate::shared::USER_AGENT;
 
     let trace = DebugTrace::from_env(request.model, None);
+    let request = &crate::extra::apply(request, extra)?;
     let builder = client
         .post(url)
         .header("content-type", "application/json")
         .header("accept", "application/json")
         .header("user-agent", USER_AGENT);
 
+    if crate::dry_run::is_active() {
+        return Err(crate::dry_run::intercept(
+            &header_fn(builder.json(request)).build()?,
+        ));
+    }
+
     let response = if let Some(trace) = &trace {
         let body = serde_json::to_vec(request)?;
         trace.write_request(&body);
@@ -201,10 +317,9 @@ pub(crate) async fn send_streaming_request(
             .map_err(|e| classify_reqwest_error(&e))?
     };
 
-    let status = response.status();
-    if !status.is_success() {
-        let error_body = response.text().await.unwrap_or_default();
-        return Err(ProviderError::http_status(status.as_u16(), &error_body).into());
+    crate::usage_limits::observe_claude_headers(response.headers());
+    if !response.status().is_success() {
+        return Err(http_status_error(response).await.into());
     }
 
     let byte_stream = wrap_stream(trace, response.bytes_stream());
@@ -709,4 +824,87 @@ mod tests {
             "claude-code-20250219,oauth-2025-04-20"
         );
     }
+
+    #[tokio::test]
+    async fn output_tool_call_is_streamed_as_a_final_text_answer() {
+        let start = |index, name: &str| StreamEvent::ContentBlockStart {
+            index,
+            block_type: ContentBlockType::ToolUse,
+            id: Some(format!("toolu_{index}")),
+            name: Some(name.to_string()),
+            data: None,
+            id_origin: None,
+        };
+        let events = vec![
+            Ok(start(0, OUTPUT_TOOL_NAME)),
+            Ok(StreamEvent::InputJsonDelta {
+                index: 0,
+                partial_json: "{\"count\":".to_string(),
+            }),
+            Ok(StreamEvent::InputJsonDelta {
+                index: 0,
+                partial_json: " 3}".to_string(),
+            }),
+            Ok(StreamEvent::MessageDelta {
+                stop_reason: Some("tool_use".to_string()),
+                usage: None,
+            }),
+        ];
+        let rewritten: Vec<StreamEvent> =
+            output_tool_as_text(futures_util::stream::iter(events).boxed())
+                .map(Result::unwrap)
+                .collect()
+                .await;
+
+        assert!(matches!(
+            rewritten[0],
+            StreamEvent::ContentBlockStart {
+                block_type: ContentBlockType::Text,
+                name: None,
+                ..
+            }
+        ));
+        let text: String = rewritten
+            .iter()
+            .filter_map(|event| match event {
+                StreamEvent::TextDelta { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "{\"count\": 3}");
+        assert!(matches!(
+            &rewritten[3],
+            StreamEvent::MessageDelta { stop_reason: Some(reason), .. } if reason == "end_turn"
+        ));
+
+        // A real tool call alongside keeps the turn going.
+        let events = vec![
+            Ok(start(0, "read")),
+            Ok(StreamEvent::MessageDelta {
+                stop_reason: Some("tool_use".to_string()),
+                usage: None,
+            }),
+        ];
+        let rewritten: Vec<StreamEvent> =
+            output_tool_as_text(futures_util::stream::iter(events).boxed())
+                .map(Result::unwrap)
+                .collect()
+                .await;
+        assert!(matches!(
+            &rewritten[1],
+            StreamEvent::MessageDelta { stop_reason: Some(reason), .. } if reason == "tool_use"
+        ));
+    }
+
+    #[test]
+    fn output_tool_needs_an_object_schema() {
+        let schema = |schema| OutputSchema {
+            name: "answer".to_string(),
+            schema,
+        };
+        let tool = output_tool(Some(&schema(json!({"type": "object"})))).unwrap();
+        assert_eq!(tool.name, OUTPUT_TOOL_NAME);
+        assert!(output_tool(Some(&schema(json!({"type": "array"})))).is_none());
+        assert!(output_tool(None).is_none());
+    }
 }
@@ -1,18 +1,24 @@
+use std::collections::VecDeque;
 use std::pin::Pin;
 
 use eventsource_stream::{EventStream, Eventsource};
 use futures_util::Stream;
 use serde::Deserialize;
-use serde::de::DeserializeOwned;
+use serde::de::{DeserializeOwned, IgnoredAny};
+use serde_json::Value;
 
 use crate::shared::{
     ContentBlockType, ProviderError, ProviderErrorKind, ProviderResult, SignatureProvider,
     StreamEvent, Usage, UsageDelta, map_event_stream_error,
 };
+use crate::sse_resync::FrameResync;
 
 /// SSE parser that converts a byte stream into `StreamEvents`.
 pub struct SseParser<S> {
     inner: EventStream<S>,
+    resync: FrameResync,
+    /// Events salvaged from a malformed frame, emitted before reading on.
+    pending: VecDeque<ProviderResult<StreamEvent>>,
 }
 
 impl<S> SseParser<S> {
@@ -22,8 +28,32 @@ impl<S> SseParser<S> {
     {
         Self {
             inner: stream.eventsource(),
+            resync: FrameResync::new("anthropic"),
+            pending: VecDeque::new(),
         }
     }
+
+    /// Parses one frame. Frames whose data isn't valid JSON are salvaged via
+    /// [`FrameResync`], using each recovered object's `type` as event name.
+    fn handle_frame(&mut self, event_type: &str, data: &str) -> ProviderResult<StreamEvent> {
+        if data.trim().is_empty() || serde_json::from_str::<IgnoredAny>(data).is_ok() {
+            self.resync.reset();
+            return parse_sse_event_fields(event_type, data);
+        }
+        for value in self.resync.parse(data)? {
+            let kind = value
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            self.pending
+                .push_back(parse_sse_event_fields(kind, &value.to_string()));
+        }
+        self.pending.pop_front().unwrap_or_else(|| {
+            Ok(StreamEvent::Ignored {
+                kind: "anthropic_malformed_frame".to_string(),
+            })
+        })
+    }
 }
 
 impl<S, E> Stream for SseParser<S>
@@ -39,9 +69,12 @@ where
     ) -> std::task::Poll<Option<Self::Item>> {
         use std::task::Poll;
 
+        if let Some(result) = self.pending.pop_front() {
+            return Poll::Ready(Some(result));
+        }
         match Pin::new(&mut self.inner).poll_next(cx) {
             Poll::Ready(Some(Ok(event))) => {
-                Poll::Ready(Some(parse_sse_event_fields(&event.event, &event.data)))
+                Poll::Ready(Some(self.handle_frame(&event.event, &event.data)))
             }
             Poll::Ready(Some(Err(e))) => Poll::Ready(Some(Err(map_event_stream_error(e)))),
             Poll::Ready(None) => Poll::Ready(None),
@@ -1119,6 +1152,46 @@ data: {"type":"message_stop"}
         );
     }
 
+    /// SSE fixture with a lost frame boundary (two deltas glued into one
+    /// frame) followed by a frame cut off mid-JSON.
+    const SSE_MALFORMED_FRAMES: &str = r#"event: content_block_delta
+data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hel"}}
+event: content_block_delta
+data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"lo"}}
+
+event: content_block_delta
+data: {"type":"content_block_delta","index":0,"delta":{"type":"te
+
+event: message_stop
+data: {"type":"message_stop"}
+
+"#;
+
+    #[tokio::test]
+    async fn test_sse_parser_resyncs_on_malformed_frames() {
+        let stream = mock_byte_stream(SSE_MALFORMED_FRAMES);
+        let mut parser = SseParser::new(stream);
+
+        let mut events = Vec::new();
+        while let Some(result) = parser.next().await {
+            events.push(result.expect("malformed frames must not fail the stream"));
+        }
+
+        let text: String = events
+            .iter()
+            .filter_map(|event| match event {
+                StreamEvent::TextDelta { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "Hello");
+        assert!(matches!(
+            &events[2],
+            StreamEvent::Ignored { kind } if kind == "anthropic_malformed_frame"
+        ));
+        assert!(matches!(events.last(), Some(StreamEvent::MessageCompleted)));
+    }
+
     /// SSE fixture where a `redacted_thinking` `content_block_start`
     /// event is missing the required `data` field entirely. The parser
     /// must reject this as a `ProviderErrorKind::Parse` rather than
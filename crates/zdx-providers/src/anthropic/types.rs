@@ -147,10 +147,24 @@ pub(crate) struct StreamingMessagesRequest<'a> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) thinking: Option<ThinkingConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) tool_choice: Option<ToolChoice<'a>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub(crate) output_config: Option<OutputConfig>,
+    #[serde(flatten)]
+    pub(crate) sampling: crate::shared::Sampling,
     pub(crate) stream: bool,
 }
 
+/// `tool_choice` forcing a tool call (used for structured output).
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub(crate) enum ToolChoice<'a> {
+    /// Some tool must be called.
+    Any,
+    /// This tool must be called.
+    Tool { name: &'a str },
+}
+
 /// System message block with optional cache control.
 #[derive(Debug, Serialize)]
 pub(crate) struct SystemBlock {
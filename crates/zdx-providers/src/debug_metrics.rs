@@ -2,7 +2,9 @@
 //!
 //! When the `ZDX_DEBUG_STREAM` environment variable is set to a file path,
 //! this wrapper tracks timing metrics and writes them to the specified file
-//! when the stream completes.
+//! when the stream completes. In-process consumers (e.g. `zdx bench`) can
+//! drive [`StreamMetrics`] directly via [`StreamMetrics::start`] and
+//! [`StreamMetrics::observe`].
 
 use std::collections::HashMap;
 use std::fs::OpenOptions;
@@ -166,6 +168,22 @@ impl StreamMetrics {
         }
     }
 
+    /// Starts collecting metrics for a stream consumed in-process. Call this
+    /// right before sending the request so timings include connection setup.
+    pub fn start() -> Self {
+        Self::new()
+    }
+
+    /// Records one stream event.
+    pub fn observe(&mut self, event: &StreamEvent) {
+        self.record_event(event);
+    }
+
+    /// Time elapsed since the request started.
+    pub fn elapsed(&self) -> Duration {
+        self.stream_start.elapsed()
+    }
+
     /// Record a `poll_next` call (for backpressure detection).
     fn record_poll(&mut self) {
         let now = Instant::now();
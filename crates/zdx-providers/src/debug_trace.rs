@@ -18,23 +18,50 @@ pub struct DebugTrace {
     dir: PathBuf,
 }
 
-impl DebugTrace {
-    pub fn from_env(model: &str, cache_key: Option<&str>) -> Option<Self> {
-        let raw = std::env::var("ZDX_DEBUG_TRACE").ok()?;
-        let trimmed = raw.trim();
-        if trimmed.is_empty() {
-            return None;
-        }
+/// Trace directory from `ZDX_DEBUG_TRACE`, created on demand.
+fn trace_dir() -> Option<PathBuf> {
+    let raw = std::env::var("ZDX_DEBUG_TRACE").ok()?;
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
 
-        let dir = if trimmed == "1" || trimmed.eq_ignore_ascii_case("true") {
-            std::env::temp_dir().join("zdx-trace")
-        } else {
-            PathBuf::from(trimmed)
-        };
+    let dir = if trimmed == "1" || trimmed.eq_ignore_ascii_case("true") {
+        std::env::temp_dir().join("zdx-trace")
+    } else {
+        PathBuf::from(trimmed)
+    };
 
-        if fs::create_dir_all(&dir).is_err() {
-            return None;
-        }
+    fs::create_dir_all(&dir).ok()?;
+    Some(dir)
+}
+
+/// Appends a malformed SSE frame to `malformed_frames.log` in the trace
+/// directory when `ZDX_DEBUG_TRACE` is set.
+pub(crate) fn record_malformed_frame(provider: &str, error: &str, raw: &str) {
+    let Some(dir) = trace_dir() else {
+        return;
+    };
+    let Ok(mut file) = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dir.join("malformed_frames.log"))
+    else {
+        return;
+    };
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_millis());
+    let _ = writeln!(
+        file,
+        "--- {ts} {provider} ({error}, {} bytes)\n{raw}",
+        raw.len()
+    );
+}
+
+impl DebugTrace {
+    pub fn from_env(model: &str, cache_key: Option<&str>) -> Option<Self> {
+        let dir = trace_dir()?;
 
         let prefix = cache_key.unwrap_or(model);
         let mut safe = prefix
@@ -8,7 +8,7 @@ use serde_json::json;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ProviderKind, ProviderStream};
 
 /// `DeepSeek` API configuration.
@@ -21,6 +21,7 @@ pub struct DeepSeekConfig {
     pub prompt_cache_key: Option<String>,
     pub thinking_enabled: bool,
     pub reasoning_effort: Option<String>,
+    pub sampling: Sampling,
 }
 
 impl DeepSeekConfig {
@@ -56,6 +57,7 @@ impl DeepSeekConfig {
             prompt_cache_key,
             thinking_enabled,
             reasoning_effort,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -104,6 +106,7 @@ impl DeepSeekClient {
                     include_usage: true,
                     include_reasoning_content: config.thinking_enabled,
                     thinking: Some(config.thinking_enabled.into()),
+                    sampling: config.sampling,
                 },
                 extra_body,
             ),
@@ -133,7 +136,7 @@ impl DeepSeekClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(DeepSeekClient::new(DeepSeekConfig::from_env(
+    let mut config = DeepSeekConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
@@ -141,5 +144,7 @@ pub fn build(
         ctx.cache_key.clone(),
         ctx.thinking_level.is_enabled(),
         crate::openai::reasoning_effort_from_thinking_level(ctx.thinking_level).map(str::to_owned),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(DeepSeekClient::new(config)))
 }
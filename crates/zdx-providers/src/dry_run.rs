@@ -0,0 +1,229 @@
+//! Dry-run capture of provider requests.
+//!
+//! [`capture`] runs a `stream_messages` call with a task-local capture slot
+//! installed. Every streaming send site checks [`is_active`] right before the
+//! request would go out and, when set, records the fully built request (URL,
+//! headers, JSON body) via [`intercept`] and aborts instead of sending.
+//! Captured requests can be redacted with [`CapturedRequest::redacted`]
+//! before being shown.
+
+use std::fmt::Write as _;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+use anyhow::{Result, bail};
+use serde::Serialize;
+use serde_json::Value;
+use zdx_types::is_secret_key;
+
+/// Placeholder written in place of secret values.
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Header names whose values are credentials.
+const SECRET_HEADERS: &[&str] = &[
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "x-goog-api-key",
+    "api-key",
+    "cookie",
+    "chatgpt-account-id",
+];
+
+type Slot = Arc<Mutex<Option<CapturedRequest>>>;
+
+tokio::task_local! {
+    static CAPTURE: Slot;
+}
+
+/// A provider request captured instead of being sent.
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturedRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    /// JSON body; a non-JSON body is kept as a string value.
+    pub body: Value,
+}
+
+impl CapturedRequest {
+    /// Replaces credential headers, URL query secrets, and secret-looking
+    /// body fields with [`REDACTED`].
+    #[must_use]
+    pub fn redacted(mut self) -> Self {
+        for (name, value) in &mut self.headers {
+            if SECRET_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                *value = match value.split_once(' ') {
+                    Some((scheme, _)) if scheme.eq_ignore_ascii_case("bearer") => {
+                        format!("{scheme} {REDACTED}")
+                    }
+                    _ => REDACTED.to_string(),
+                };
+            }
+        }
+        self.url = redact_url(&self.url);
+        redact_value(&mut self.body);
+        self
+    }
+
+    /// Pretty-prints the request as an HTTP-like header block followed by
+    /// the indented JSON body.
+    pub fn to_pretty_string(&self) -> String {
+        let mut out = format!("{} {}\n", self.method, self.url);
+        for (name, value) in &self.headers {
+            let _ = writeln!(out, "{name}: {value}");
+        }
+        out.push('\n');
+        out.push_str(
+            &serde_json::to_string_pretty(&self.body).unwrap_or_else(|_| self.body.to_string()),
+        );
+        out.push('\n');
+        out
+    }
+}
+
+/// Runs `future` (a provider `stream_messages` call) in dry-run mode and
+/// returns the request it would have sent.
+///
+/// # Errors
+/// Returns the provider's error if it failed before building the request, or
+/// an error if the provider completed without reaching a send site.
+pub async fn capture<F, T>(future: F) -> Result<CapturedRequest>
+where
+    F: Future<Output = Result<T>>,
+{
+    let slot: Slot = Arc::new(Mutex::new(None));
+    let outcome = CAPTURE.scope(Arc::clone(&slot), future).await;
+    let captured = slot.lock().ok().and_then(|mut slot| slot.take());
+    match (captured, outcome) {
+        (Some(request), _) => Ok(request),
+        (None, Err(err)) => Err(err),
+        (None, Ok(_)) => bail!("provider does not support dry-run request capture"),
+    }
+}
+
+/// Whether the current task is running under [`capture`].
+pub(crate) fn is_active() -> bool {
+    CAPTURE.try_with(|_| ()).is_ok()
+}
+
+/// Records a built HTTP request and returns the error that aborts the send.
+pub(crate) fn intercept(request: &reqwest::Request) -> anyhow::Error {
+    let body = request
+        .body()
+        .and_then(reqwest::Body::as_bytes)
+        .map_or(Value::Null, |bytes| {
+            serde_json::from_slice(bytes)
+                .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(bytes).into_owned()))
+        });
+    let headers = request
+        .headers()
+        .iter()
+        .map(|(name, value)| {
+            (
+                name.as_str().to_string(),
+                String::from_utf8_lossy(value.as_bytes()).into_owned(),
+            )
+        })
+        .collect();
+    intercept_parts(CapturedRequest {
+        method: request.method().as_str().to_string(),
+        url: request.url().to_string(),
+        headers,
+        body,
+    })
+}
+
+/// Records an already-assembled request (e.g. a WebSocket opening frame).
+pub(crate) fn intercept_parts(request: CapturedRequest) -> anyhow::Error {
+    let _ = CAPTURE.try_with(|slot| {
+        if let Ok(mut slot) = slot.lock() {
+            *slot = Some(request);
+        }
+    });
+    anyhow::anyhow!("dry run: request captured, not sent")
+}
+
+fn redact_url(url: &str) -> String {
+    let Ok(mut parsed) = url::Url::parse(url) else {
+        return url.to_string();
+    };
+    if parsed.query().is_none() {
+        return url.to_string();
+    }
+    let pairs: Vec<(String, String)> = parsed
+        .query_pairs()
+        .map(|(key, value)| {
+            let value = if is_secret_key(&key) {
+                REDACTED.to_string()
+            } else {
+                value.into_owned()
+            };
+            (key.into_owned(), value)
+        })
+        .collect();
+    parsed.query_pairs_mut().clear().extend_pairs(pairs);
+    parsed.to_string()
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map.iter_mut() {
+                if is_secret_key(key) && value.is_string() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(value);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_value),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn redacts_credentials_in_headers_url_and_body() {
+        let request = CapturedRequest {
+            method: "POST".to_string(),
+            url: "https://api.example.com/v1/generate?alt=sse&key=sk-secret".to_string(),
+            headers: vec![
+                ("authorization".to_string(), "Bearer sk-secret".to_string()),
+                ("x-api-key".to_string(), "sk-secret".to_string()),
+                ("content-type".to_string(), "application/json".to_string()),
+            ],
+            body: json!({"model": "m", "metadata": {"api_key": "sk-secret"}}),
+        }
+        .redacted();
+
+        let printed = request.to_pretty_string();
+        assert!(!printed.contains("sk-secret"), "{printed}");
+        assert!(printed.contains("authorization: Bearer [REDACTED]"));
+        assert!(printed.contains("content-type: application/json"));
+        assert!(printed.contains("alt=sse"));
+    }
+
+    #[tokio::test]
+    async fn capture_returns_intercepted_request() {
+        let captured = capture(async {
+            assert!(is_active());
+            let request = reqwest::Client::new()
+                .post("https://api.example.com/v1/messages")
+                .json(&json!({"model": "m"}))
+                .build()?;
+            Err::<(), _>(intercept(&request))
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(captured.method, "POST");
+        assert_eq!(captured.body, json!({"model": "m"}));
+        assert!(!is_active());
+    }
+}
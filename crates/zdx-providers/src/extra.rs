@@ -0,0 +1,279 @@
+//! Provider-specific request parameters (`[providers.<name>.extra]`).
+//!
+//! Works like [`crate::http`]: [`ProviderKind::build_client`](crate::ProviderKind::build_client)
+//! wraps the provider build in [`with_params`], client constructors capture
+//! [`params`], and send sites merge them into the JSON body with [`apply`].
+//! Extra keys only add to the body: a key zdx already sets is kept (objects
+//! merge key by key), so extras can't clobber the model, messages, or tools.
+
+use std::cell::RefCell;
+use std::sync::Arc;
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::ProviderKind;
+
+/// Extra body parameters captured by a client; `None` when unset.
+pub type ExtraParams = Option<Arc<Map<String, Value>>>;
+
+thread_local! {
+    static PARAMS_OVERRIDE: RefCell<ExtraParams> = const { RefCell::new(None) };
+}
+
+/// Top-level keys zdx builds itself in at least one request format. Extras
+/// for these are always ignored.
+const MANAGED_KEYS: &[&str] = &[
+    "model",
+    "messages",
+    "system",
+    "tools",
+    "stream",
+    "input",
+    "instructions",
+    "contents",
+    "systemInstruction",
+];
+
+/// Documented Anthropic Messages parameters zdx doesn't set itself.
+const MESSAGES_KEYS: &[&str] = &[
+    "top_k",
+    "stop_sequences",
+    "metadata",
+    "service_tier",
+    "container",
+    "mcp_servers",
+    "context_management",
+];
+
+/// Documented `OpenAI` Responses parameters zdx doesn't set itself.
+const RESPONSES_KEYS: &[&str] = &[
+    "background",
+    "conversation",
+    "max_tool_calls",
+    "metadata",
+    "previous_response_id",
+    "prompt",
+    "prompt_cache_retention",
+    "safety_identifier",
+    "top_logprobs",
+    "user",
+];
+
+/// Chat Completions parameters, including the common gateway extensions
+/// (`OpenRouter`, Mistral, local servers).
+const CHAT_COMPLETIONS_KEYS: &[&str] = &[
+    "stop",
+    "seed",
+    "frequency_penalty",
+    "presence_penalty",
+    "logit_bias",
+    "logprobs",
+    "top_logprobs",
+    "n",
+    "user",
+    "response_format",
+    "parallel_tool_calls",
+    "prediction",
+    "metadata",
+    "store",
+    "service_tier",
+    "top_k",
+    "min_p",
+    "repetition_penalty",
+    "provider",
+    "transforms",
+    "models",
+    "route",
+    "reasoning",
+    "safe_prompt",
+    "random_seed",
+];
+
+/// Gemini `generateContent` fields zdx doesn't set itself (or merges into).
+const GEMINI_KEYS: &[&str] = &[
+    "generationConfig",
+    "safetySettings",
+    "cachedContent",
+    "toolConfig",
+    "labels",
+];
+
+/// Returns the extra params for the provider being built: the configured ones
+/// inside [`with_params`], otherwise `None`.
+pub fn params() -> ExtraParams {
+    PARAMS_OVERRIDE.with_borrow(Clone::clone)
+}
+
+/// Runs `f` with [`params`] returning `params` (when set) on this thread.
+pub fn with_params<T>(params: ExtraParams, f: impl FnOnce() -> T) -> T {
+    let Some(params) = params.filter(|p| !p.is_empty()) else {
+        return f();
+    };
+    let previous = PARAMS_OVERRIDE.replace(Some(params));
+    let result = f();
+    PARAMS_OVERRIDE.set(previous);
+    result
+}
+
+/// Serializes `request` with `extra` merged in.
+///
+/// # Errors
+/// Returns an error if `request` cannot be serialized.
+pub fn apply<T: Serialize>(request: &T, extra: Option<&Map<String, Value>>) -> Result<Value> {
+    let mut body = serde_json::to_value(request)?;
+    if let Some(extra) = extra {
+        merge(&mut body, extra);
+    }
+    Ok(body)
+}
+
+/// Adds `extra` to `body` without replacing values `body` already has.
+/// Objects present on both sides are merged recursively.
+fn merge(body: &mut Value, extra: &Map<String, Value>) {
+    let Value::Object(body) = body else {
+        return;
+    };
+    for (key, value) in extra {
+        if MANAGED_KEYS.contains(&key.as_str()) {
+            continue;
+        }
+        match (body.get_mut(key), value) {
+            (None, _) => {
+                body.insert(key.clone(), value.clone());
+            }
+            (Some(existing @ Value::Object(_)), Value::Object(nested)) => merge(existing, nested),
+            (Some(_), _) => {}
+        }
+    }
+}
+
+/// Warnings for `extra` on `kind`: keys zdx manages (ignored) and keys that
+/// aren't documented parameters of the provider's API (still sent).
+pub fn check(kind: ProviderKind, extra: &Map<String, Value>) -> Vec<String> {
+    let known = match kind {
+        ProviderKind::Anthropic | ProviderKind::ClaudeCli => {
+            Some(("Anthropic Messages", MESSAGES_KEYS))
+        }
+        ProviderKind::OpenAI
+        | ProviderKind::OpenAICodex
+        | ProviderKind::Xai
+        | ProviderKind::GrokBuild => Some(("Responses API", RESPONSES_KEYS)),
+        ProviderKind::Gemini | ProviderKind::GoogleAntigravity => Some(("Gemini", GEMINI_KEYS)),
+        // OpenCode Go routes each model to a different API.
+        ProviderKind::OpencodeGo | ProviderKind::ElevenLabs => None,
+        _ => Some(("Chat Completions", CHAT_COMPLETIONS_KEYS)),
+    };
+    check_keys(&kind.id().replace('-', "_"), known, extra)
+}
+
+/// [`check`] for a custom OpenAI-compatible provider; `section` is its
+/// config path below `providers` (e.g. `custom.local`).
+pub fn check_custom(section: &str, extra: &Map<String, Value>) -> Vec<String> {
+    check_keys(
+        section,
+        Some(("Chat Completions", CHAT_COMPLETIONS_KEYS)),
+        extra,
+    )
+}
+
+fn check_keys(
+    section: &str,
+    known: Option<(&str, &[&str])>,
+    extra: &Map<String, Value>,
+) -> Vec<String> {
+    extra
+        .keys()
+        .filter_map(|key| {
+            if MANAGED_KEYS.contains(&key.as_str()) {
+                Some(format!(
+                    "[providers.{section}.extra] `{key}` is set by zdx and will be ignored"
+                ))
+            } else if let Some((api, known)) = known
+                && !known.contains(&key.as_str())
+            {
+                Some(format!(
+                    "[providers.{section}.extra] `{key}` is not a known {api} parameter; sending it anyway"
+                ))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn map(value: Value) -> Map<String, Value> {
+        match value {
+            Value::Object(map) => map,
+            _ => panic!("expected object"),
+        }
+    }
+
+    #[test]
+    fn test_apply_adds_keys_without_overriding_request() {
+        let extra = map(json!({
+            "top_k": 50,
+            "stop_sequences": ["END"],
+            "model": "other",
+            "max_tokens": 1,
+            "generationConfig": {"stopSequences": ["END"], "maxOutputTokens": 1},
+        }));
+        let request = json!({
+            "model": "m",
+            "max_tokens": 1024,
+            "generationConfig": {"maxOutputTokens": 8192},
+        });
+
+        let body = apply(&request, Some(&extra)).unwrap();
+        assert_eq!(body["model"], "m");
+        assert_eq!(body["max_tokens"], 1024);
+        assert_eq!(body["top_k"], 50);
+        assert_eq!(body["stop_sequences"], json!(["END"]));
+        assert_eq!(body["generationConfig"]["maxOutputTokens"], 8192);
+        assert_eq!(body["generationConfig"]["stopSequences"], json!(["END"]));
+        assert_eq!(apply(&request, None).unwrap(), request);
+    }
+
+    #[test]
+    fn test_check_flags_managed_and_unknown_keys() {
+        let extra = map(json!({"top_k": 50, "messages": [], "top_kk": 1}));
+        let warnings = check(ProviderKind::Anthropic, &extra);
+        assert_eq!(warnings.len(), 2, "{warnings:?}");
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("`messages` is set by zdx"))
+        );
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("`top_kk` is not a known"))
+        );
+
+        assert!(check(ProviderKind::OpencodeGo, &map(json!({"anything": 1}))).is_empty());
+        let warnings = check_custom("custom.local", &map(json!({"min_p": 0.1, "topk": 1})));
+        assert_eq!(
+            warnings,
+            vec![
+                "[providers.custom.local.extra] `topk` is not a known Chat Completions parameter; sending it anyway"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn test_with_params_scopes_override() {
+        assert!(params().is_none());
+        let extra = Some(Arc::new(map(json!({"top_k": 5}))));
+        let inside = with_params(extra, params);
+        assert_eq!(inside.unwrap()["top_k"], 5);
+        assert!(params().is_none());
+    }
+}
@@ -14,8 +14,8 @@ use super::shared::{
 use super::sse::GeminiSseParser;
 use crate::debug_metrics::maybe_wrap_with_metrics;
 use crate::oauth::google_antigravity as oauth_antigravity;
-use crate::shared::{classify_reqwest_error, merge_system_prompt};
-use crate::{ChatMessage, ProviderError, ProviderStream};
+use crate::shared::{Sampling, classify_reqwest_error, http_status_error, merge_system_prompt};
+use crate::{ChatMessage, ProviderStream};
 
 const API_ENDPOINT: &str = "https://daily-cloudcode-pa.googleapis.com";
 const STREAM_PATH: &str = "/v1internal:streamGenerateContent";
@@ -26,6 +26,7 @@ pub struct AntigravityConfig {
     pub max_tokens: Option<u32>,
     pub session_id: String,
     pub thinking_config: Option<GeminiThinkingConfig>,
+    pub sampling: Sampling,
 }
 
 impl AntigravityConfig {
@@ -39,6 +40,7 @@ impl AntigravityConfig {
             max_tokens,
             session_id: uuid::Uuid::new_v4().to_string(),
             thinking_config,
+            sampling: Sampling::default(),
         }
     }
 }
@@ -77,6 +79,7 @@ pub async fn resolve_credentials() -> Result<oauth_antigravity::AntigravityCrede
 pub struct AntigravityClient {
     config: AntigravityConfig,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
     prompt_seq: AtomicU32,
 }
 
@@ -84,7 +87,8 @@ impl AntigravityClient {
     pub fn new(config: AntigravityConfig) -> Self {
         Self {
             config,
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
             prompt_seq: AtomicU32::new(0),
         }
     }
@@ -118,16 +122,27 @@ impl AntigravityClient {
                 session_id: &self.config.session_id,
                 prompt_seq: seq,
                 thinking_config: self.config.thinking_config.as_ref(),
+                sampling: self.config.sampling,
                 include_thoughts: true,
                 request_type: Some("agent"),
                 user_agent: Some("antigravity"),
                 request_id: Some(request_id),
             },
         );
+        // Extras target the wrapped Gemini request, not the Cloud Code envelope.
+        let mut request = request;
+        if let Some(inner) = request.get_mut("request") {
+            *inner = crate::extra::apply(inner, self.extra.as_deref())?;
+        }
 
         let url = format!("{API_ENDPOINT}{STREAM_PATH}?alt=sse");
         let headers = build_headers(&creds.access)?;
 
+        if crate::dry_run::is_active() {
+            let request = self.http.post(&url).headers(headers).json(&request);
+            return Err(crate::dry_run::intercept(&request.build()?));
+        }
+
         let response = self
             .http
             .post(&url)
@@ -137,10 +152,8 @@ impl AntigravityClient {
             .await
             .map_err(|e| classify_reqwest_error(&e))?;
 
-        let status = response.status();
-        if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            return Err(ProviderError::http_status(status.as_u16(), &error_body).into());
+        if !response.status().is_success() {
+            return Err(http_status_error(response).await.into());
         }
 
         let byte_stream = response.bytes_stream();
@@ -210,7 +223,7 @@ fn antigravity_thinking_config(
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(AntigravityClient::new(AntigravityConfig::new(
+    let mut config = AntigravityConfig::new(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         Some(antigravity_thinking_config(
@@ -218,5 +231,7 @@ pub fn build(
             ctx.model,
             ctx.config_max_tokens,
         )),
-    ))))
+    );
+    config.sampling = ctx.sampling;
+    Ok(Box::new(AntigravityClient::new(config)))
 }
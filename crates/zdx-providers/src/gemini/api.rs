@@ -9,7 +9,7 @@ use zdx_types::ToolDefinition;
 use super::shared::{GeminiThinkingConfig, build_gemini_request};
 use super::sse::GeminiSseParser;
 use crate::debug_metrics::maybe_wrap_with_metrics;
-use crate::shared::{classify_reqwest_error, merge_system_prompt};
+use crate::shared::{Sampling, classify_reqwest_error, http_status_error, merge_system_prompt};
 use crate::{ChatMessage, DebugTrace, ProviderError, ProviderKind, ProviderStream, wrap_stream};
 
 /// Gemini API configuration.
@@ -21,6 +21,8 @@ pub struct GeminiConfig {
     pub max_output_tokens: Option<u32>,
     /// Thinking configuration (level for Gemini 3, budget for Gemini 2.5)
     pub thinking_config: Option<GeminiThinkingConfig>,
+    /// `temperature`/`topP` for `generationConfig`
+    pub sampling: Sampling,
 }
 
 impl GeminiConfig {
@@ -52,6 +54,7 @@ impl GeminiConfig {
             model,
             max_output_tokens,
             thinking_config,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -60,6 +63,7 @@ impl GeminiConfig {
 pub struct GeminiClient {
     config: GeminiConfig,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
 }
 
 /// A source image for editing / composition.
@@ -98,7 +102,8 @@ impl GeminiClient {
     pub fn new(config: GeminiConfig) -> Self {
         Self {
             config,
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
         }
     }
 
@@ -118,8 +123,10 @@ impl GeminiClient {
             system_prompt.as_deref(),
             self.config.max_output_tokens,
             self.config.thinking_config.as_ref(),
+            self.config.sampling,
             &self.config.model,
         );
+        let request = crate::extra::apply(&request, self.extra.as_deref())?;
         let trace = DebugTrace::from_env(&self.config.model, None);
         let url = format!(
             "{}/models/{}:streamGenerateContent?alt=sse",
@@ -127,6 +134,11 @@ impl GeminiClient {
         );
         let headers = build_headers(&self.config.api_key)?;
 
+        if crate::dry_run::is_active() {
+            let request = self.http.post(&url).headers(headers).json(&request);
+            return Err(crate::dry_run::intercept(&request.build()?));
+        }
+
         let response = if let Some(trace) = &trace {
             let body = serde_json::to_vec(&request)?;
             trace.write_request(&body);
@@ -147,10 +159,8 @@ impl GeminiClient {
                 .map_err(|e| classify_reqwest_error(&e))?
         };
 
-        let status = response.status();
-        if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            return Err(ProviderError::http_status(status.as_u16(), &error_body).into());
+        if !response.status().is_success() {
+            return Err(http_status_error(response).await.into());
         }
 
         let byte_stream = wrap_stream(trace, response.bytes_stream());
@@ -322,7 +332,7 @@ fn build_json_headers(api_key: &str) -> anyhow::Result<HeaderMap> {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(GeminiClient::new(GeminiConfig::from_env(
+    let mut config = GeminiConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
@@ -334,7 +344,9 @@ pub fn build(
             ctx.thinking_level,
             ctx.model,
         )),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(GeminiClient::new(config)))
 }
 
 #[cfg(test)]
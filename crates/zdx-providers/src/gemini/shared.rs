@@ -11,6 +11,7 @@ use std::collections::HashMap;
 use serde_json::{Value, json};
 use zdx_types::{ThinkingLevel, ToolDefinition};
 
+use crate::shared::Sampling;
 use crate::{ChatContentBlock, ChatMessage, IdOrigin, MessageContent, ReplayToken};
 
 /// Thinking configuration for Gemini models.
@@ -532,6 +533,7 @@ pub fn build_gemini_request(
     system: Option<&str>,
     max_output_tokens: Option<u32>,
     thinking_config: Option<&GeminiThinkingConfig>,
+    sampling: Sampling,
     model: &str,
 ) -> Value {
     let contents = build_contents(messages, model);
@@ -559,6 +561,7 @@ pub fn build_gemini_request(
     {
         generation_config["maxOutputTokens"] = json!(max_output_tokens);
     }
+    apply_sampling(&mut generation_config, sampling);
 
     if let Some(thinking) = thinking_config {
         let mut thinking_obj = thinking.to_json();
@@ -573,6 +576,16 @@ pub fn build_gemini_request(
     request
 }
 
+/// Sets `temperature`/`topP` on a `generationConfig` object.
+fn apply_sampling(generation_config: &mut Value, sampling: Sampling) {
+    if let Some(temperature) = sampling.temperature {
+        generation_config["temperature"] = json!(temperature);
+    }
+    if let Some(top_p) = sampling.top_p {
+        generation_config["topP"] = json!(top_p);
+    }
+}
+
 /// Parameters for building a Cloud Code Assist request.
 pub struct CloudCodeRequestParams<'a> {
     pub model: &'a str,
@@ -581,6 +594,7 @@ pub struct CloudCodeRequestParams<'a> {
     pub session_id: &'a str,
     pub prompt_seq: u32,
     pub thinking_config: Option<&'a GeminiThinkingConfig>,
+    pub sampling: Sampling,
     pub include_thoughts: bool,
     pub request_type: Option<&'a str>,
     pub user_agent: Option<&'a str>,
@@ -622,6 +636,7 @@ pub fn build_cloud_code_assist_request(
     {
         generation_config["maxOutputTokens"] = json!(tokens);
     }
+    apply_sampling(&mut generation_config, params.sampling);
 
     // Note: Cloud Code Assist API does NOT support includeThoughts
     // (unlike the standard Gemini API at generativelanguage.googleapis.com).
@@ -1683,6 +1698,7 @@ mod integration_tests {
                 session_id: "test-session",
                 prompt_seq: 0,
                 thinking_config: Some(&thinking_config),
+                sampling: Sampling::default(),
                 include_thoughts: false,
                 request_type: None,
                 user_agent: None,
@@ -1730,6 +1746,7 @@ mod integration_tests {
                 session_id: "test-session",
                 prompt_seq: 0,
                 thinking_config: Some(&thinking_config),
+                sampling: Sampling::default(),
                 include_thoughts: false,
                 request_type: None,
                 user_agent: None,
@@ -1772,6 +1789,7 @@ mod integration_tests {
             system,
             Some(8192),
             Some(&thinking_config),
+            Sampling::default(),
             "gemini-3-flash-preview",
         );
 
@@ -1809,6 +1827,7 @@ mod integration_tests {
             system,
             Some(8192),
             Some(&thinking_config),
+            Sampling::default(),
             "gemini-2.5-flash",
         );
 
@@ -1896,6 +1915,7 @@ mod integration_tests {
             Some("system prompt"),
             None,
             None,
+            Sampling::default(),
             "gemini-3-flash-preview",
         );
 
@@ -1926,6 +1946,10 @@ mod integration_tests {
             system,
             Some(8192),
             None,
+            Sampling {
+                temperature: Some(0.3),
+                top_p: Some(0.8),
+            },
             "gemini-3-flash-preview",
         );
         let gen_config = &request["generationConfig"];
@@ -1933,6 +1957,8 @@ mod integration_tests {
             gen_config.get("thinkingConfig").is_none(),
             "thinkingConfig should NOT be present when None"
         );
+        assert_eq!(gen_config["temperature"], json!(0.3));
+        assert_eq!(gen_config["topP"], json!(0.8));
     }
 
     /// Gemini 2.5 Pro: Off maps to minimum budget (128)
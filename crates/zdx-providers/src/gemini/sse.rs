@@ -11,9 +11,11 @@ use serde_json::Value;
 use uuid::Uuid;
 use zdx_types::messages::IdOrigin;
 
+use crate::sse_resync::FrameResync;
+
 use crate::{
-    ContentBlockType, ProviderError, ProviderErrorKind, ProviderResult, SignatureProvider,
-    StreamEvent, Usage, error_message_from_payload, map_event_stream_error,
+    ContentBlockType, ProviderResult, SignatureProvider, StreamEvent, Usage,
+    error_message_from_payload, map_event_stream_error,
 };
 
 /// Gemini SSE stream parser.
@@ -37,6 +39,7 @@ pub struct GeminiSseParser<S> {
     final_usage: Option<Usage>,
     final_finish_reason: Option<String>,
     emitted_done: bool,
+    resync: FrameResync,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -79,6 +82,7 @@ impl<S> GeminiSseParser<S> {
             final_usage: None,
             final_finish_reason: None,
             emitted_done: false,
+            resync: FrameResync::new("gemini"),
         }
     }
 
@@ -88,13 +92,10 @@ impl<S> GeminiSseParser<S> {
             return Ok(());
         }
 
-        let value = serde_json::from_str::<Value>(trimmed).map_err(|err| {
-            ProviderError::new(
-                ProviderErrorKind::Parse,
-                format!("Failed to parse SSE JSON: {err}"),
-            )
-        })?;
-        self.handle_chunk(value)
+        for value in self.resync.parse(trimmed)? {
+            self.handle_chunk(value)?;
+        }
+        Ok(())
     }
 
     #[allow(clippy::needless_pass_by_value, clippy::unnecessary_wraps)]
@@ -627,6 +628,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
+    use crate::ProviderErrorKind;
 
     /// Creates a mock SSE parser for testing.
     fn create_test_parser() -> GeminiSseParser<impl Stream<Item = Result<Bytes, std::io::Error>>> {
@@ -64,6 +64,7 @@ fn build_headers(access_token: &str) -> Result<HeaderMap> {
 pub struct GrokBuildClient {
     config: ResponsesConfig,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
 }
 
 impl GrokBuildClient {
@@ -84,6 +85,7 @@ impl GrokBuildClient {
                 reasoning_summary: None,
                 instructions: None,
                 text_verbosity: None,
+                text_format: None,
                 store: Some(false),
                 include: Some(vec!["reasoning.encrypted_content".to_string()]),
                 stream_options: None,
@@ -92,8 +94,11 @@ impl GrokBuildClient {
                 tool_choice: Some("auto".to_string()),
                 truncation: None,
                 service_tier: None,
+                // The Grok subscription backend rejects sampling controls.
+                sampling: crate::shared::Sampling::default(),
             },
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
         }
     }
 
@@ -110,6 +115,7 @@ impl GrokBuildClient {
         let system = merge_system_prompt(system);
         send_responses_stream(
             &self.http,
+            self.extra.as_deref(),
             &self.config,
             build_headers(&access_token)?,
             messages,
@@ -0,0 +1,157 @@
+//! Per-provider HTTP client construction (`[providers.<name>.http]`).
+//!
+//! Provider clients create their `reqwest::Client` in their constructors via
+//! [`client`]. [`ProviderKind::build_client`](crate::ProviderKind::build_client)
+//! wraps the (synchronous) provider build in [`with_client`], so every client
+//! built for that call picks up the configured headers, proxy, and TLS roots
+//! without each provider module threading the settings through its config.
+
+use std::cell::RefCell;
+
+use anyhow::{Context, Result};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use zdx_types::config::ProviderHttpConfig;
+
+thread_local! {
+    static CLIENT_OVERRIDE: RefCell<Option<reqwest::Client>> = const { RefCell::new(None) };
+}
+
+/// Returns the HTTP client for the provider being built: the configured one
+/// inside [`with_client`], otherwise a default client.
+pub fn client() -> reqwest::Client {
+    CLIENT_OVERRIDE
+        .with_borrow(Clone::clone)
+        .unwrap_or_default()
+}
+
+/// Runs `f` with [`client`] returning `http` (when set) on this thread.
+pub fn with_client<T>(http: Option<reqwest::Client>, f: impl FnOnce() -> T) -> T {
+    let Some(http) = http else {
+        return f();
+    };
+    let previous = CLIENT_OVERRIDE.replace(Some(http));
+    let result = f();
+    CLIENT_OVERRIDE.set(previous);
+    result
+}
+
+/// Builds a client from provider HTTP settings, or `None` when they are all
+/// defaults.
+///
+/// # Errors
+/// Returns an error for an invalid header, proxy URL, or CA bundle.
+pub fn build_client(config: &ProviderHttpConfig) -> Result<Option<reqwest::Client>> {
+    if config.is_default() {
+        return Ok(None);
+    }
+
+    let mut headers = HeaderMap::new();
+    for (name, value) in &config.headers {
+        let header_name = HeaderName::from_bytes(name.trim().as_bytes())
+            .with_context(|| format!("Invalid HTTP header name '{name}'"))?;
+        let header_value = HeaderValue::from_str(value.trim())
+            .with_context(|| format!("Invalid value for HTTP header '{name}'"))?;
+        headers.insert(header_name, header_value);
+    }
+
+    let mut builder = reqwest::Client::builder().default_headers(headers);
+    if let Some(proxy) = config
+        .proxy
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    {
+        let proxy = reqwest::Proxy::all(proxy)
+            .with_context(|| format!("Invalid HTTP proxy URL '{proxy}'"))?;
+        builder = builder.proxy(proxy);
+    }
+    if let Some(path) = config
+        .ca_bundle
+        .as_deref()
+        .map(str::trim)
+        .filter(|p| !p.is_empty())
+    {
+        let pem =
+            std::fs::read(path).with_context(|| format!("Failed to read CA bundle '{path}'"))?;
+        let certs = reqwest::Certificate::from_pem_bundle(&pem)
+            .with_context(|| format!("Invalid PEM in CA bundle '{path}'"))?;
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    if config.accept_invalid_certs {
+        builder = builder.danger_accept_invalid_certs(true);
+    }
+
+    builder
+        .build()
+        .map(Some)
+        .context("Failed to build provider HTTP client")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_client_rejects_invalid_settings() {
+        assert!(
+            build_client(&ProviderHttpConfig::default())
+                .unwrap()
+                .is_none()
+        );
+
+        let mut config = ProviderHttpConfig::default();
+        config
+            .headers
+            .insert("Bad Header".to_string(), "x".to_string());
+        let err = build_client(&config).unwrap_err();
+        assert!(err.to_string().contains("Bad Header"));
+
+        let config = ProviderHttpConfig {
+            ca_bundle: Some("/nonexistent/ca.pem".to_string()),
+            ..ProviderHttpConfig::default()
+        };
+        assert!(build_client(&config).is_err());
+
+        let mut config = ProviderHttpConfig {
+            proxy: Some("http://127.0.0.1:3128".to_string()),
+            ..ProviderHttpConfig::default()
+        };
+        config
+            .headers
+            .insert("Helicone-Auth".to_string(), "Bearer k".to_string());
+        assert!(build_client(&config).unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_with_client_applies_default_headers() {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let server = std::thread::spawn(move || {
+            use std::io::{Read, Write};
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut buf = vec![0; 4096];
+            let n = stream.read(&mut buf).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+            String::from_utf8_lossy(&buf[..n]).to_ascii_lowercase()
+        });
+
+        let mut config = ProviderHttpConfig::default();
+        config
+            .headers
+            .insert("OpenAI-Organization".to_string(), "org-1".to_string());
+        let http = build_client(&config).unwrap();
+        let client = with_client(http, client);
+        client
+            .get(format!("http://{address}/"))
+            .send()
+            .await
+            .unwrap();
+
+        let request = server.join().unwrap();
+        assert!(request.contains("openai-organization: org-1"));
+    }
+}
@@ -2,12 +2,16 @@
 
 mod debug_metrics;
 mod debug_trace;
+pub mod dry_run;
+mod sse_resync;
 pub mod thinking_parser;
 
 pub mod anthropic;
 pub mod deepseek;
+pub mod extra;
 pub mod gemini;
 pub mod grok_build;
+pub mod http;
 pub mod lmstudio;
 pub mod meta;
 pub mod minimax;
@@ -18,9 +22,11 @@ pub mod openai;
 pub mod openai_compatible;
 pub mod opencode_go;
 pub mod openrouter;
+pub mod registry;
 pub mod shared;
 pub mod stepfun;
 pub mod subscription_quota;
+pub mod usage_limits;
 pub mod xai;
 pub mod xiaomi;
 pub mod xiaomi_plan;
@@ -29,16 +35,17 @@ pub mod zai;
 use std::future::Future;
 use std::pin::Pin;
 
+pub use debug_metrics::StreamMetrics;
 pub use debug_trace::{DebugTrace, TraceStream, wrap_stream};
 pub use shared::{
     ChatContentBlock, ChatMessage, ContentBlockType, IdOrigin, MessageContent, ProviderError,
-    ProviderErrorKind, ProviderResult, ProviderStream, ReasoningBlock, ReplayToken,
+    ProviderErrorKind, ProviderResult, ProviderStream, ReasoningBlock, ReplayToken, Sampling,
     SignatureProvider, StreamEvent, Usage, UsageDelta, error_message_from_payload,
     map_event_stream_error, resolve_api_key, resolve_base_url, strip_voice_transcript,
     wrap_voice_transcript,
 };
 use zdx_types::ToolDefinition;
-use zdx_types::config::{TextVerbosity, ThinkingLevel};
+use zdx_types::config::{ModelParams, TextVerbosity, ThinkingLevel};
 
 /// Object-safe trait for streaming LLM providers.
 ///
@@ -55,6 +62,41 @@ pub trait StreamingProvider: Send + Sync {
         tools: &'a [ToolDefinition],
         system: Option<&'a str>,
     ) -> Pin<Box<dyn Future<Output = anyhow::Result<ProviderStream>> + Send + 'a>>;
+
+    /// What this client supports. Built-in clients rely on the model
+    /// registry instead, so only registered providers need to override it.
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities::default()
+    }
+
+    /// Counts the input tokens a request would consume, or `None` when the
+    /// provider has no counting endpoint.
+    fn count_tokens<'a>(
+        &'a self,
+        _messages: &'a [ChatMessage],
+        _tools: &'a [ToolDefinition],
+        _system: Option<&'a str>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<Option<u64>>> + Send + 'a>> {
+        Box::pin(async { Ok(None) })
+    }
+}
+
+/// Feature support reported by a `StreamingProvider`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    /// Accepts tool definitions and emits tool calls.
+    pub tools: bool,
+    /// Honors a thinking level (reasoning output).
+    pub reasoning: bool,
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self {
+            tools: true,
+            reasoning: false,
+        }
+    }
 }
 
 macro_rules! impl_streaming_provider {
@@ -74,8 +116,38 @@ macro_rules! impl_streaming_provider {
     };
 }
 
+impl StreamingProvider for anthropic::api::AnthropicClient {
+    fn stream_messages<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        tools: &'a [ToolDefinition],
+        system: Option<&'a str>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<ProviderStream>> + Send + 'a>> {
+        Box::pin(self.send_messages_stream(messages, tools, system))
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            tools: true,
+            reasoning: true,
+        }
+    }
+
+    fn count_tokens<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        tools: &'a [ToolDefinition],
+        system: Option<&'a str>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<Option<u64>>> + Send + 'a>> {
+        Box::pin(async move {
+            anthropic::api::AnthropicClient::count_tokens(self, messages, tools, system)
+                .await
+                .map(Some)
+        })
+    }
+}
+
 impl_streaming_provider!(
-    anthropic::api::AnthropicClient,
     anthropic::cli::ClaudeCliClient,
     openai::api::OpenAIClient,
     openai::codex::OpenAICodexClient,
@@ -112,6 +184,76 @@ impl<T: StreamingProvider + ?Sized> StreamingProvider for Box<T> {
     ) -> Pin<Box<dyn Future<Output = anyhow::Result<ProviderStream>> + Send + 'a>> {
         (**self).stream_messages(messages, tools, system)
     }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        (**self).capabilities()
+    }
+
+    fn count_tokens<'a>(
+        &'a self,
+        messages: &'a [ChatMessage],
+        tools: &'a [ToolDefinition],
+        system: Option<&'a str>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<Option<u64>>> + Send + 'a>> {
+        (**self).count_tokens(messages, tools, system)
+    }
+}
+
+/// JSON schema the final answer must match (`zdx exec --schema`).
+///
+/// `OpenAI` constrains decoding with it (`text.format`), Anthropic and
+/// Claude CLI force it as the input schema of an answer tool; the engine
+/// validates the answer either way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputSchema {
+    /// Schema name for providers that require one (`[A-Za-z0-9_-]`).
+    pub name: String,
+    pub schema: serde_json::Value,
+}
+
+impl OutputSchema {
+    /// Whether the schema fits `OpenAI` strict mode: every object schema sets
+    /// `additionalProperties: false` and lists all of its properties as
+    /// `required`.
+    pub fn is_strict_compatible(&self) -> bool {
+        strict_compatible(&self.schema)
+    }
+
+    /// Responses API `text.format` value for this schema.
+    pub fn responses_text_format(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": "json_schema",
+            "name": self.name,
+            "schema": self.schema,
+            "strict": self.is_strict_compatible(),
+        })
+    }
+}
+
+fn strict_compatible(schema: &serde_json::Value) -> bool {
+    match schema {
+        serde_json::Value::Object(map) => {
+            if let Some(properties) = map.get("properties").and_then(|p| p.as_object()) {
+                let closed =
+                    map.get("additionalProperties") == Some(&serde_json::Value::Bool(false));
+                let required: Vec<&str> = map
+                    .get("required")
+                    .and_then(|r| r.as_array())
+                    .map(|r| r.iter().filter_map(|v| v.as_str()).collect())
+                    .unwrap_or_default();
+                if !closed
+                    || !properties
+                        .keys()
+                        .all(|key| required.contains(&key.as_str()))
+                {
+                    return false;
+                }
+            }
+            map.values().all(strict_compatible)
+        }
+        serde_json::Value::Array(items) => items.iter().all(strict_compatible),
+        _ => true,
+    }
 }
 
 /// Generic inputs for provider client construction.
@@ -133,6 +275,8 @@ pub struct ProviderBuildContext<'a> {
     /// Global `config.max_tokens` (`Option<u32>`) — used by `OpenAI`, `OpenRouter`, `Gemini`, etc.
     pub config_max_tokens: Option<u32>,
     pub thinking_level: ThinkingLevel,
+    /// `temperature`/`top_p`, already gated by [`ProviderKind::gate_model_params`].
+    pub sampling: Sampling,
     pub cache_key: Option<String>,
     pub text_verbosity: Option<TextVerbosity>,
     pub service_tier: Option<String>,
@@ -146,6 +290,15 @@ pub struct ProviderBuildContext<'a> {
     pub websocket: bool,
     /// API routing hint for the `opencode-go` meta-provider.
     pub api_hint: Option<String>,
+    /// Schema for the final answer (`OpenAI` sends it natively; Anthropic and
+    /// Claude CLI force an answer tool with it).
+    pub output_schema: Option<&'a OutputSchema>,
+    /// Responses API `truncation` value (`OpenAI` only); `None` keeps the API default.
+    pub truncation: Option<&'a str>,
+    /// HTTP client built from `[providers.<name>.http]`; `None` uses the default.
+    pub http: Option<reqwest::Client>,
+    /// Body parameters from `[providers.<name>.extra]`; see [`extra`].
+    pub extra: extra::ExtraParams,
 }
 
 /// Provider selection based on model naming.
@@ -522,6 +675,44 @@ impl ProviderKind {
         }
     }
 
+    /// Whether requests may carry `temperature`/`top_p`.
+    ///
+    /// Anthropic rejects them alongside extended thinking and `OpenAI`/xAI
+    /// reasoning models reject them outright; the subscription backends
+    /// (`claude-cli`, `openai-codex`, `grok-build`) never accept them.
+    pub fn accepts_sampling(self, thinking_enabled: bool) -> bool {
+        match self {
+            Self::ClaudeCli | Self::OpenAICodex | Self::GrokBuild | Self::ElevenLabs => false,
+            Self::Anthropic | Self::OpenAI | Self::Xai | Self::OpencodeGo => !thinking_enabled,
+            _ => true,
+        }
+    }
+
+    /// Drops the sampling controls this provider can't take. Returns the
+    /// params to send and the names of the dropped controls.
+    pub fn gate_model_params(
+        self,
+        params: ModelParams,
+        thinking_enabled: bool,
+    ) -> (ModelParams, Vec<&'static str>) {
+        if self.accepts_sampling(thinking_enabled) {
+            return (params, Vec::new());
+        }
+        let mut dropped = Vec::new();
+        if params.temperature.is_some() {
+            dropped.push("temperature");
+        }
+        if params.top_p.is_some() {
+            dropped.push("top_p");
+        }
+        let gated = ModelParams {
+            temperature: None,
+            top_p: None,
+            ..params
+        };
+        (gated, dropped)
+    }
+
     /// Builds a provider client from the given context.
     ///
     /// Thin dispatcher that delegates to each provider module's `build()` function.
@@ -534,6 +725,15 @@ impl ProviderKind {
     pub fn build_client(
         &self,
         ctx: &ProviderBuildContext<'_>,
+    ) -> anyhow::Result<Box<dyn StreamingProvider>> {
+        http::with_client(ctx.http.clone(), || {
+            extra::with_params(ctx.extra.clone(), || self.build_client_inner(ctx))
+        })
+    }
+
+    fn build_client_inner(
+        self,
+        ctx: &ProviderBuildContext<'_>,
     ) -> anyhow::Result<Box<dyn StreamingProvider>> {
         match self {
             Self::Anthropic => anthropic::api::build(ctx),
@@ -5,7 +5,7 @@ use reqwest::header::HeaderMap;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ChatMessage, ProviderKind, ProviderStream};
 
 /// `LMStudio` API configuration.
@@ -17,6 +17,7 @@ pub struct LMStudioConfig {
     pub max_tokens: Option<u32>,
     pub prompt_cache_key: Option<String>,
     pub thinking_enabled: bool,
+    pub sampling: Sampling,
 }
 
 impl LMStudioConfig {
@@ -53,6 +54,7 @@ impl LMStudioConfig {
             max_tokens,
             prompt_cache_key,
             thinking_enabled,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -77,6 +79,7 @@ impl LMStudioClient {
                 include_usage: true,
                 include_reasoning_content: config.thinking_enabled,
                 thinking: None,
+                sampling: config.sampling,
             }),
         }
     }
@@ -104,12 +107,14 @@ impl LMStudioClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(LMStudioClient::new(LMStudioConfig::from_env(
+    let mut config = LMStudioConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
         ctx.api_key,
         ctx.cache_key.clone(),
         ctx.thinking_level.is_enabled(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(LMStudioClient::new(config)))
 }
@@ -13,7 +13,7 @@ use serde_json::json;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ProviderKind, ProviderStream};
 
 /// Meta Model API configuration.
@@ -26,6 +26,7 @@ pub struct MetaConfig {
     pub prompt_cache_key: Option<String>,
     pub thinking_enabled: bool,
     pub reasoning_effort: Option<String>,
+    pub sampling: Sampling,
 }
 
 impl MetaConfig {
@@ -61,6 +62,7 @@ impl MetaConfig {
             prompt_cache_key,
             thinking_enabled,
             reasoning_effort,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -93,6 +95,7 @@ impl MetaClient {
                     include_usage: true,
                     include_reasoning_content: config.thinking_enabled,
                     thinking: Some(config.thinking_enabled.into()),
+                    sampling: config.sampling,
                 },
                 extra_body,
             ),
@@ -122,7 +125,7 @@ impl MetaClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(MetaClient::new(MetaConfig::from_env(
+    let mut config = MetaConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
@@ -130,7 +133,9 @@ pub fn build(
         ctx.cache_key.clone(),
         ctx.thinking_level.is_enabled(),
         crate::openai::reasoning_effort_from_thinking_level(ctx.thinking_level).map(str::to_owned),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(MetaClient::new(config)))
 }
 
 #[cfg(test)]
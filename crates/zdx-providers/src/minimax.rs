@@ -8,7 +8,7 @@ use serde_json::Value;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ChatMessage, ProviderKind, ProviderStream};
 
 /// `MiniMax` API configuration.
@@ -20,6 +20,7 @@ pub struct MinimaxConfig {
     pub max_tokens: Option<u32>,
     pub prompt_cache_key: Option<String>,
     pub thinking_enabled: bool,
+    pub sampling: Sampling,
 }
 
 impl MinimaxConfig {
@@ -53,6 +54,7 @@ impl MinimaxConfig {
             max_tokens,
             prompt_cache_key,
             thinking_enabled,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -85,6 +87,7 @@ impl MinimaxClient {
                     include_reasoning_content: config.thinking_enabled,
                     // MiniMax uses reasoning_split in extra_body, not the thinking parameter.
                     thinking: None,
+                    sampling: config.sampling,
                 },
                 extra_body,
             ),
@@ -114,12 +117,14 @@ impl MinimaxClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(MinimaxClient::new(MinimaxConfig::from_env(
+    let mut config = MinimaxConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
         ctx.api_key,
         ctx.cache_key.clone(),
         ctx.thinking_level.is_enabled(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(MinimaxClient::new(config)))
 }
@@ -5,7 +5,7 @@ use reqwest::header::HeaderMap;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ProviderKind, ProviderStream};
 
 /// Mistral API configuration.
@@ -16,6 +16,7 @@ pub struct MistralConfig {
     pub model: String,
     pub max_tokens: Option<u32>,
     pub prompt_cache_key: Option<String>,
+    pub sampling: Sampling,
 }
 
 impl MistralConfig {
@@ -47,6 +48,7 @@ impl MistralConfig {
             model,
             max_tokens,
             prompt_cache_key,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -71,6 +73,7 @@ impl MistralClient {
                 include_usage: true,
                 include_reasoning_content: false,
                 thinking: None,
+                sampling: config.sampling,
             }),
         }
     }
@@ -98,11 +101,13 @@ impl MistralClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(MistralClient::new(MistralConfig::from_env(
+    let mut config = MistralConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
         ctx.api_key,
         ctx.cache_key.clone(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(MistralClient::new(config)))
 }
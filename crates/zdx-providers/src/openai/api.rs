@@ -10,7 +10,7 @@ use super::image_generation::{
 };
 use super::responses_ws::OpenAIResponsesWsClient;
 use crate::openai::responses::{ResponsesConfig, StreamOptions, send_responses_stream};
-use crate::shared::classify_reqwest_error;
+use crate::shared::{Sampling, classify_reqwest_error};
 use crate::{ProviderKind, ProviderStream};
 
 const RESPONSES_PATH: &str = "/responses";
@@ -28,6 +28,12 @@ pub struct OpenAIConfig {
     pub service_tier: Option<String>,
     /// Use the persistent WebSocket transport instead of HTTP/SSE.
     pub websocket: bool,
+    /// Structured-output schema sent as `text.format`.
+    pub output_schema: Option<crate::OutputSchema>,
+    /// Responses API `truncation` (`"auto"` drops old items server-side).
+    pub truncation: Option<String>,
+    /// `temperature`/`top_p` (left unset for reasoning models).
+    pub sampling: Sampling,
 }
 
 impl OpenAIConfig {
@@ -68,6 +74,9 @@ impl OpenAIConfig {
             prompt_cache_key,
             service_tier,
             websocket,
+            output_schema: None,
+            truncation: None,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -76,6 +85,7 @@ impl OpenAIConfig {
 pub struct OpenAIClient {
     config: OpenAIConfig,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
     ws: Option<Box<OpenAIResponsesWsClient>>,
 }
 
@@ -89,7 +99,8 @@ impl OpenAIClient {
         });
         Self {
             config,
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
             ws,
         }
     }
@@ -108,7 +119,16 @@ impl OpenAIClient {
         }
         let headers = build_headers(&self.config.api_key)?;
         let config = responses_config(&self.config);
-        send_responses_stream(&self.http, &config, headers, messages, tools, system).await
+        send_responses_stream(
+            &self.http,
+            self.extra.as_deref(),
+            &config,
+            headers,
+            messages,
+            tools,
+            system,
+        )
+        .await
     }
 
     /// Generate image content using the hosted Responses API `image_generation` tool.
@@ -158,6 +178,10 @@ fn responses_config(config: &OpenAIConfig) -> ResponsesConfig {
                 .as_str()
                 .to_string(),
         ),
+        text_format: config
+            .output_schema
+            .as_ref()
+            .map(crate::OutputSchema::responses_text_format),
         store: Some(false),
         include: Some(vec!["reasoning.encrypted_content".to_string()]),
         stream_options: Some(StreamOptions {
@@ -166,8 +190,10 @@ fn responses_config(config: &OpenAIConfig) -> ResponsesConfig {
         prompt_cache_key: config.prompt_cache_key.clone(),
         parallel_tool_calls: Some(true),
         tool_choice: Some("auto".to_string()),
-        truncation: None, // Default: "disabled" - fail if context exceeded
+        // Unset means the API default ("disabled"): fail if context exceeded.
+        truncation: config.truncation.clone(),
         service_tier: config.service_tier.clone(),
+        sampling: config.sampling,
     }
 }
 
@@ -193,7 +219,7 @@ fn build_headers(api_key: &str) -> anyhow::Result<HeaderMap> {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(OpenAIClient::new(OpenAIConfig::from_env(
+    let mut config = OpenAIConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
@@ -203,7 +229,11 @@ pub fn build(
         ctx.cache_key.clone(),
         ctx.service_tier.clone(),
         ctx.websocket,
-    )?)))
+    )?;
+    config.output_schema = ctx.output_schema.cloned();
+    config.truncation = ctx.truncation.map(str::to_owned);
+    config.sampling = ctx.sampling;
+    Ok(Box::new(OpenAIClient::new(config)))
 }
 
 #[cfg(test)]
@@ -222,6 +252,9 @@ mod tests {
             prompt_cache_key: None,
             service_tier: None,
             websocket: false,
+            output_schema: None,
+            truncation: None,
+            sampling: Sampling::default(),
         };
 
         assert_eq!(
@@ -12,11 +12,12 @@ use serde_json::Value;
 use zdx_types::{ToolDefinition, ToolResult};
 
 use crate::debug_metrics::maybe_wrap_with_metrics;
-use crate::shared::classify_reqwest_error;
+use crate::shared::{Sampling, classify_reqwest_error, http_status_error};
+use crate::sse_resync::FrameResync;
 use crate::{
-    ChatContentBlock, ChatMessage, ContentBlockType, DebugTrace, MessageContent, ProviderError,
-    ProviderErrorKind, ProviderResult, ProviderStream, StreamEvent, Usage,
-    error_message_from_payload, map_event_stream_error, wrap_stream,
+    ChatContentBlock, ChatMessage, ContentBlockType, DebugTrace, MessageContent, ProviderResult,
+    ProviderStream, StreamEvent, Usage, error_message_from_payload, map_event_stream_error,
+    wrap_stream,
 };
 
 const CHAT_COMPLETIONS_PATH: &str = "/chat/completions";
@@ -35,6 +36,7 @@ pub struct OpenAIChatCompletionsConfig {
     pub include_usage: bool,
     pub include_reasoning_content: bool,
     pub thinking: Option<ThinkingConfig>,
+    pub sampling: Sampling,
 }
 
 /// OpenAI-compatible chat completions client.
@@ -42,6 +44,7 @@ pub struct OpenAIChatCompletionsClient {
     config: OpenAIChatCompletionsConfig,
     extra_body: HashMap<String, Value>,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
 }
 
 impl OpenAIChatCompletionsClient {
@@ -56,7 +59,8 @@ impl OpenAIChatCompletionsClient {
         Self {
             config,
             extra_body,
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
         }
     }
 
@@ -71,12 +75,18 @@ impl OpenAIChatCompletionsClient {
     ) -> Result<ProviderStream> {
         let request =
             ChatCompletionRequest::new(&self.config, &self.extra_body, messages, tools, system);
+        let request = crate::extra::apply(&request, self.extra.as_deref())?;
         let trace =
             DebugTrace::from_env(&self.config.model, self.config.prompt_cache_key.as_deref());
 
         let url = format!("{}{}", self.config.base_url, CHAT_COMPLETIONS_PATH);
         let headers = build_headers(&self.config.api_key, &self.config.extra_headers)?;
 
+        if crate::dry_run::is_active() {
+            let request = self.http.post(&url).headers(headers).json(&request);
+            return Err(crate::dry_run::intercept(&request.build()?));
+        }
+
         let response = if let Some(trace) = &trace {
             let body = serde_json::to_vec(&request)?;
             trace.write_request(&body);
@@ -97,10 +107,8 @@ impl OpenAIChatCompletionsClient {
                 .map_err(|e| classify_reqwest_error(&e))?
         };
 
-        let status = response.status();
-        if !status.is_success() {
-            let error_body = response.text().await.unwrap_or_default();
-            return Err(ProviderError::http_status(status.as_u16(), &error_body).into());
+        if !response.status().is_success() {
+            return Err(http_status_error(response).await.into());
         }
 
         let byte_stream = wrap_stream(trace, response.bytes_stream());
@@ -149,6 +157,8 @@ struct ChatCompletionRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     prompt_cache_key: Option<String>,
     #[serde(flatten)]
+    sampling: Sampling,
+    #[serde(flatten)]
     extra_body: HashMap<String, Value>,
 }
 
@@ -297,6 +307,7 @@ impl ChatCompletionRequest {
                 .map(|effort| ReasoningConfig { effort }),
             thinking: config.thinking.clone(),
             prompt_cache_key: config.prompt_cache_key.clone(),
+            sampling: config.sampling,
             extra_body: extra_body.clone(),
         }
     }
@@ -556,6 +567,7 @@ struct ChatCompletionsSseParser<S> {
     final_usage: Option<Usage>,
     final_finish_reason: Option<String>,
     emitted_done: bool,
+    resync: FrameResync,
 }
 
 impl<S> ChatCompletionsSseParser<S> {
@@ -576,6 +588,7 @@ impl<S> ChatCompletionsSseParser<S> {
             final_usage: None,
             final_finish_reason: None,
             emitted_done: false,
+            resync: FrameResync::new("chat_completions"),
         }
     }
 
@@ -685,15 +698,12 @@ impl<S> ChatCompletionsSseParser<S> {
             return Ok(());
         }
 
-        let value = serde_json::from_str::<Value>(trimmed).map_err(|err| {
-            ProviderError::new(
-                ProviderErrorKind::Parse,
-                format!("Failed to parse SSE JSON: {err}"),
-            )
-        })?;
-        self.handle_chunk(&value);
+        for value in self.resync.parse(trimmed)? {
+            self.handle_chunk(&value);
+        }
         Ok(())
     }
+
     fn handle_chunk(&mut self, value: &Value) {
         // Handle errors first - these are terminal, no completion should follow
         if let Some(error) = value.get("error") {
@@ -967,7 +977,7 @@ mod tests {
 
     use super::{
         ChatCompletionRequest, ChatCompletionsSseParser, ContentBlockType,
-        OpenAIChatCompletionsConfig, StreamEvent, ThinkingConfig, parse_usage,
+        OpenAIChatCompletionsConfig, Sampling, StreamEvent, ThinkingConfig, parse_usage,
     };
 
     #[test]
@@ -1009,11 +1019,17 @@ mod tests {
             include_usage: true,
             include_reasoning_content: true,
             thinking: Some(ThinkingConfig::from(true)),
+            sampling: Sampling {
+                temperature: Some(0.2),
+                top_p: None,
+            },
         };
 
         let request = ChatCompletionRequest::new(&config, &extra_body, &[], &[], None);
         let value = serde_json::to_value(&request).expect("request should serialize");
 
+        assert_eq!(value.get("temperature"), Some(&json!(0.2)));
+        assert!(value.get("top_p").is_none());
         assert_eq!(value.get("reasoning_split"), Some(&json!(true)));
         assert_eq!(value.get("custom_flag"), Some(&json!("enabled")));
         assert!(value.get("extra_body").is_none());
@@ -1033,6 +1049,7 @@ mod tests {
             include_usage: true,
             include_reasoning_content,
             thinking: Some(ThinkingConfig::from(include_reasoning_content)),
+            sampling: Sampling::default(),
         }
     }
 
@@ -122,6 +122,7 @@ fn decode_account_id(token: &str) -> Option<String> {
 pub struct OpenAICodexClient {
     config: OpenAICodexConfig,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
     ws: Option<Box<OpenAIResponsesWsClient>>,
 }
 
@@ -136,7 +137,8 @@ impl OpenAICodexClient {
         });
         Self {
             config,
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
             ws,
         }
     }
@@ -164,7 +166,16 @@ impl OpenAICodexClient {
 
         // For Codex, send the system prompt through top-level `instructions`.
         // Keep `system` input empty here to avoid duplication in `input`.
-        send_responses_stream(&self.http, &config, headers, messages, tools, None).await
+        send_responses_stream(
+            &self.http,
+            self.extra.as_deref(),
+            &config,
+            headers,
+            messages,
+            tools,
+            None,
+        )
+        .await
     }
 
     /// Generate image content using the hosted Responses API `image_generation` tool.
@@ -239,6 +250,7 @@ fn codex_responses_config(
                 .as_str()
                 .to_string(),
         ),
+        text_format: None,
         store: Some(false),
         include: Some(vec!["reasoning.encrypted_content".to_string()]),
         stream_options: None,
@@ -247,6 +259,8 @@ fn codex_responses_config(
         tool_choice: Some("auto".to_string()),
         truncation: None, // Default: "disabled" - fail if context exceeded
         service_tier: config.service_tier.clone(),
+        // The ChatGPT backend rejects sampling controls.
+        sampling: crate::shared::Sampling::default(),
     }
 }
 
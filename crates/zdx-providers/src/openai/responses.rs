@@ -10,10 +10,10 @@ pub use super::responses_types::{
     SummaryItem, TextConfig,
 };
 use crate::debug_metrics::maybe_wrap_with_metrics;
-use crate::shared::classify_reqwest_error;
+use crate::shared::{classify_reqwest_error, http_status_error};
 use crate::{
-    ChatContentBlock, ChatMessage, DebugTrace, ProviderError, ProviderStream, ReasoningBlock,
-    ReplayToken, wrap_stream,
+    ChatContentBlock, ChatMessage, DebugTrace, ProviderStream, ReasoningBlock, ReplayToken,
+    wrap_stream,
 };
 
 /// Shared configuration for Responses API requests.
@@ -27,6 +27,8 @@ pub struct ResponsesConfig {
     pub reasoning_summary: Option<String>,
     pub instructions: Option<String>,
     pub text_verbosity: Option<String>,
+    /// Responses API `text.format` (structured outputs).
+    pub text_format: Option<serde_json::Value>,
     pub store: Option<bool>,
     pub include: Option<Vec<String>>,
     pub stream_options: Option<StreamOptions>,
@@ -39,6 +41,8 @@ pub struct ResponsesConfig {
     pub truncation: Option<String>,
     /// `OpenAI` Responses API service tier: `"priority"` for faster inference (2× cost), `"flex"` for reduced cost.
     pub service_tier: Option<String>,
+    /// `temperature`/`top_p` (reasoning models reject them).
+    pub sampling: crate::shared::Sampling,
 }
 
 /// Sends a Responses API request and returns a stream of normalized events.
@@ -47,6 +51,7 @@ pub struct ResponsesConfig {
 /// Returns an error if the operation fails.
 pub async fn send_responses_stream(
     http: &reqwest::Client,
+    extra: Option<&serde_json::Map<String, serde_json::Value>>,
     config: &ResponsesConfig,
     headers: HeaderMap,
     messages: &[ChatMessage],
@@ -54,9 +59,15 @@ pub async fn send_responses_stream(
     system: Option<&str>,
 ) -> Result<ProviderStream> {
     let request = build_request_body(config, messages, tools, system, None)?;
+    let request = crate::extra::apply(&request, extra)?;
 
     let url = format!("{}{}", config.base_url, config.path);
 
+    if crate::dry_run::is_active() {
+        let request = http.post(&url).headers(headers).json(&request);
+        return Err(crate::dry_run::intercept(&request.build()?));
+    }
+
     let trace = DebugTrace::from_env(&config.model, config.prompt_cache_key.as_deref());
 
     let response = if let Some(trace) = &trace {
@@ -77,10 +88,8 @@ pub async fn send_responses_stream(
             .map_err(|e| classify_reqwest_error(&e))?
     };
 
-    let status = response.status();
-    if !status.is_success() {
-        let error_body = response.text().await.unwrap_or_default();
-        return Err(ProviderError::http_status(status.as_u16(), &error_body).into());
+    if !response.status().is_success() {
+        return Err(http_status_error(response).await.into());
     }
 
     let byte_stream = wrap_stream(trace, response.bytes_stream());
@@ -135,8 +144,11 @@ pub(crate) fn build_request_body_from_input(
         previous_response_id,
         max_output_tokens: config.max_output_tokens,
         instructions: config.instructions.clone(),
-        text: config.text_verbosity.as_ref().map(|verbosity| TextConfig {
-            verbosity: verbosity.clone(),
+        text: (config.text_verbosity.is_some() || config.text_format.is_some()).then(|| {
+            TextConfig {
+                verbosity: config.text_verbosity.clone(),
+                format: config.text_format.clone(),
+            }
         }),
         reasoning: config
             .reasoning_effort
@@ -153,6 +165,7 @@ pub(crate) fn build_request_body_from_input(
         prompt_cache_key: config.prompt_cache_key.clone(),
         parallel_tool_calls: config.parallel_tool_calls,
         service_tier: config.service_tier.clone(),
+        sampling: config.sampling,
     }
 }
 
@@ -449,6 +462,7 @@ mod tests {
             reasoning_summary: None,
             instructions: None,
             text_verbosity: None,
+            text_format: None,
             store: Some(false),
             include: None,
             stream_options: None,
@@ -457,10 +471,57 @@ mod tests {
             tool_choice: Some("auto".to_string()),
             truncation: None,
             service_tier: None,
+            sampling: crate::shared::Sampling::default(),
         };
 
         let without_tools = build_request_body_from_input(&config, vec![], &[], None);
         assert!(without_tools.tools.is_none());
         assert!(without_tools.tool_choice.is_none());
     }
+
+    #[test]
+    fn output_schema_is_sent_as_text_format() {
+        let schema = crate::OutputSchema {
+            name: "answer".to_string(),
+            schema: serde_json::json!({
+                "type": "object",
+                "properties": {"ok": {"type": "boolean"}},
+                "required": ["ok"],
+                "additionalProperties": false,
+            }),
+        };
+        let config = ResponsesConfig {
+            base_url: "https://api.openai.com/v1".to_string(),
+            path: "/responses".to_string(),
+            model: "gpt-5.4".to_string(),
+            max_output_tokens: None,
+            reasoning_effort: None,
+            reasoning_summary: None,
+            instructions: None,
+            text_verbosity: None,
+            text_format: Some(schema.responses_text_format()),
+            store: Some(false),
+            include: None,
+            stream_options: None,
+            prompt_cache_key: None,
+            parallel_tool_calls: None,
+            tool_choice: None,
+            truncation: None,
+            service_tier: None,
+            sampling: crate::shared::Sampling::default(),
+        };
+
+        let body = serde_json::to_value(build_request_body_from_input(&config, vec![], &[], None))
+            .expect("serialize");
+        assert_eq!(body["text"]["format"]["type"], "json_schema");
+        assert_eq!(body["text"]["format"]["name"], "answer");
+        assert_eq!(body["text"]["format"]["strict"], true);
+        assert!(body["text"].get("verbosity").is_none());
+
+        let loose = crate::OutputSchema {
+            name: "answer".to_string(),
+            schema: serde_json::json!({"type": "object", "properties": {"ok": {}}}),
+        };
+        assert!(!loose.is_strict_compatible());
+    }
 }
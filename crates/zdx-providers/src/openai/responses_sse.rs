@@ -7,8 +7,10 @@ use eventsource_stream::{EventStream, Eventsource};
 use futures_util::Stream;
 use serde_json::Value;
 
+use crate::sse_resync::FrameResync;
+
 use crate::{
-    ContentBlockType, ProviderError, ProviderErrorKind, ProviderResult, StreamEvent, Usage,
+    ContentBlockType, ProviderError, ProviderResult, StreamEvent, Usage,
     error_message_from_payload, map_event_stream_error,
 };
 
@@ -135,6 +137,7 @@ pub struct ResponsesEventMapper {
     pending: VecDeque<StreamEvent>,
     last_response_id: Option<String>,
     terminal_outcome: Option<TerminalOutcome>,
+    resync: FrameResync,
 }
 
 impl ResponsesEventMapper {
@@ -145,13 +148,15 @@ impl ResponsesEventMapper {
             pending: VecDeque::new(),
             last_response_id: None,
             terminal_outcome: None,
+            resync: FrameResync::new("openai_responses"),
         }
     }
 
     /// Parses one JSON event payload and queues the resulting `StreamEvent`(s).
+    /// Malformed payloads are salvaged via [`FrameResync`].
     ///
     /// # Errors
-    /// Returns a parse error if the payload is not valid JSON.
+    /// Returns a parse error after too many unrecoverable payloads in a row.
     pub fn push_json(&mut self, data: &str) -> ProviderResult<()> {
         let trimmed = data.trim();
         if trimmed.is_empty() {
@@ -167,14 +172,10 @@ impl ResponsesEventMapper {
             };
         }
 
-        let value = serde_json::from_str::<Value>(trimmed).map_err(|err| {
-            ProviderError::new(
-                ProviderErrorKind::Parse,
-                format!("Failed to parse SSE JSON: {err}"),
-            )
-        })?;
-        let event = self.map_event(value)?;
-        self.pending.push_back(event);
+        for value in self.resync.parse(trimmed)? {
+            let event = self.map_event(value)?;
+            self.pending.push_back(event);
+        }
         Ok(())
     }
 
@@ -638,6 +639,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
+    use crate::ProviderErrorKind;
 
     fn mapper() -> ResponsesEventMapper {
         ResponsesEventMapper::new("gpt-5.3-codex-spark".to_string())
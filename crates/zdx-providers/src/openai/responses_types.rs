@@ -37,6 +37,8 @@ pub struct RequestBody {
     pub parallel_tool_calls: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub service_tier: Option<String>,
+    #[serde(flatten)]
+    pub sampling: crate::shared::Sampling,
 }
 
 #[derive(Debug, Serialize)]
@@ -48,7 +50,11 @@ pub struct ReasoningConfig {
 
 #[derive(Debug, Serialize)]
 pub struct TextConfig {
-    pub verbosity: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<String>,
+    /// Structured-output format (`{"type": "json_schema", ...}`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -187,6 +193,7 @@ mod tests {
             prompt_cache_key: None,
             parallel_tool_calls: None,
             service_tier: None,
+            sampling: crate::shared::Sampling::default(),
         };
 
         let omitted = serde_json::to_value(&body).unwrap();
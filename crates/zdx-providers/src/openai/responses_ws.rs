@@ -51,6 +51,7 @@ pub struct OpenAIResponsesWsClient {
     /// and omitted from `input` (Codex); otherwise it becomes a `developer`
     /// input item (`OpenAI` API-key path).
     system_as_instructions: bool,
+    extra: crate::extra::ExtraParams,
     session: Arc<Mutex<SessionInner>>,
 }
 
@@ -64,6 +65,7 @@ impl OpenAIResponsesWsClient {
             header_factory,
             config,
             system_as_instructions,
+            extra: crate::extra::params(),
             session: Arc::new(Mutex::new(SessionInner::default())),
         }
     }
@@ -96,6 +98,13 @@ impl OpenAIResponsesWsClient {
         tools: &[ToolDefinition],
         system: Option<&str>,
     ) -> Result<ProviderStream> {
+        if crate::dry_run::is_active() {
+            return Err(match self.dry_run_request(messages, tools, system).await {
+                Ok(request) => crate::dry_run::intercept_parts(request),
+                Err(err) => err,
+            });
+        }
+
         let mut guard = Arc::clone(&self.session).lock_owned().await;
 
         if guard.socket.is_none() {
@@ -105,16 +114,7 @@ impl OpenAIResponsesWsClient {
             guard.last_input.clear();
         }
 
-        let (input_system, instructions) = if self.system_as_instructions {
-            let resolved = system
-                .map(str::trim)
-                .filter(|prompt| !prompt.is_empty())
-                .map(ToOwned::to_owned)
-                .or_else(|| self.config.instructions.clone());
-            (None, resolved)
-        } else {
-            (system, self.config.instructions.clone())
-        };
+        let (input_system, instructions) = self.split_system(system);
 
         let mut full_items = build_input(messages, input_system);
         if full_items.is_empty() {
@@ -143,7 +143,7 @@ impl OpenAIResponsesWsClient {
         let mut request =
             build_request_body_from_input(&self.config, input, tools, previous_response_id);
         request.instructions = instructions;
-        let frame = response_create_frame(&request)?;
+        let frame = response_create_frame(&request, self.extra.as_deref())?;
 
         let send_result = {
             let socket = guard
@@ -166,6 +166,42 @@ impl OpenAIResponsesWsClient {
         Ok(maybe_wrap_with_metrics(turn_event_stream(turn)))
     }
 
+    /// Splits the per-turn system prompt into the `input` system item and
+    /// top-level `instructions`, depending on `system_as_instructions`.
+    fn split_system<'a>(&self, system: Option<&'a str>) -> (Option<&'a str>, Option<String>) {
+        if self.system_as_instructions {
+            let resolved = system
+                .map(str::trim)
+                .filter(|prompt| !prompt.is_empty())
+                .map(ToOwned::to_owned)
+                .or_else(|| self.config.instructions.clone());
+            (None, resolved)
+        } else {
+            (system, self.config.instructions.clone())
+        }
+    }
+
+    /// Builds the full-input `response.create` frame and handshake headers
+    /// for a dry run, without touching the session socket.
+    async fn dry_run_request(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[ToolDefinition],
+        system: Option<&str>,
+    ) -> Result<crate::dry_run::CapturedRequest> {
+        let (input_system, instructions) = self.split_system(system);
+        let input = build_input(messages, input_system);
+        let mut request = build_request_body_from_input(&self.config, input, tools, None);
+        request.instructions = instructions;
+        let frame = response_create_frame(&request, self.extra.as_deref())?;
+        Ok(crate::dry_run::CapturedRequest {
+            method: "WEBSOCKET".to_string(),
+            url: to_ws_url(&self.config.base_url, &self.config.path),
+            headers: (self.header_factory)().await?,
+            body: serde_json::from_str(&frame)?,
+        })
+    }
+
     async fn connect(&self) -> Result<WsSocket> {
         let ws_url = to_ws_url(&self.config.base_url, &self.config.path);
         let mut request = ws_url
@@ -224,10 +260,13 @@ fn plan_send(
     }
 }
 
-/// Serializes a `RequestBody` as a `response.create` frame; the transport-only
-/// `stream` field is dropped.
-fn response_create_frame(request: &RequestBody) -> Result<String> {
-    let mut value = serde_json::to_value(request)?;
+/// Serializes a `RequestBody` (plus `extra` params) as a `response.create`
+/// frame; the transport-only `stream` field is dropped.
+fn response_create_frame(
+    request: &RequestBody,
+    extra: Option<&serde_json::Map<String, Value>>,
+) -> Result<String> {
+    let mut value = crate::extra::apply(request, extra)?;
     let object = value
         .as_object_mut()
         .expect("RequestBody serializes to a JSON object");
@@ -610,6 +649,7 @@ mod tests {
             reasoning_summary: None,
             instructions: None,
             text_verbosity: None,
+            text_format: None,
             store: Some(false),
             include: None,
             stream_options: None,
@@ -618,11 +658,12 @@ mod tests {
             tool_choice: None,
             truncation: None,
             service_tier: None,
+            sampling: crate::shared::Sampling::default(),
         };
         let input = build_input(&[ChatMessage::user("hello")], None);
         let request = build_request_body_from_input(&config, input, &[], None);
 
-        let frame = response_create_frame(&request).unwrap();
+        let frame = response_create_frame(&request, None).unwrap();
         let value: Value = serde_json::from_str(&frame).unwrap();
 
         assert_eq!(
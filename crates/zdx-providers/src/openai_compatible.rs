@@ -8,7 +8,7 @@ use reqwest::header::HeaderMap;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ChatMessage, ProviderStream};
 
 /// Generic OpenAI-compatible chat-completions client.
@@ -41,6 +41,7 @@ pub fn build_custom(
     max_tokens: Option<u32>,
     prompt_cache_key: Option<String>,
     thinking_enabled: bool,
+    sampling: Sampling,
 ) -> Box<dyn crate::StreamingProvider> {
     Box::new(OpenAICompatibleClient {
         inner: OpenAIChatCompletionsClient::new(OpenAIChatCompletionsConfig {
@@ -55,6 +56,7 @@ pub fn build_custom(
             include_usage: true,
             include_reasoning_content: thinking_enabled,
             thinking: None,
+            sampling,
         }),
     })
 }
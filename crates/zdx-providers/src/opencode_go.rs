@@ -11,7 +11,7 @@ use crate::gemini::api::{GeminiClient, GeminiConfig};
 use crate::gemini::shared::GeminiThinkingConfig;
 use crate::openai::api::{OpenAIClient, OpenAIConfig};
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ProviderKind, ProviderStream, StreamingProvider};
 
 #[derive(Debug, Clone)]
@@ -29,6 +29,7 @@ pub struct OpencodeGoConfig {
     pub cache_key: Option<String>,
     /// API routing hint from the model registry (e.g. "anthropic-messages").
     pub api_hint: Option<String>,
+    pub sampling: Sampling,
 }
 
 impl OpencodeGoConfig {
@@ -67,6 +68,7 @@ impl OpencodeGoConfig {
             reasoning_effort,
             cache_key,
             api_hint,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -118,6 +120,8 @@ impl OpencodeGoClient {
                     thinking_enabled: config.thinking_enabled,
                     thinking_budget_tokens: config.thinking_budget_tokens,
                     thinking_effort: config.thinking_effort,
+                    output_tool: None,
+                    sampling: config.sampling,
                 }))
             }
             GoRoute::OpenAIResponses => {
@@ -132,6 +136,9 @@ impl OpencodeGoClient {
                     prompt_cache_key: config.cache_key,
                     service_tier: None,
                     websocket: false,
+                    output_schema: None,
+                    truncation: None,
+                    sampling: config.sampling,
                 }))
             }
             GoRoute::GoogleGenerativeAI => {
@@ -142,6 +149,7 @@ impl OpencodeGoClient {
                     model: config.model,
                     max_output_tokens: config.max_tokens,
                     thinking_config: config.gemini_thinking,
+                    sampling: config.sampling,
                 }))
             }
             GoRoute::OpenAICompletions => {
@@ -164,6 +172,7 @@ impl OpencodeGoClient {
                         thinking: config
                             .thinking_enabled
                             .then(|| config.thinking_enabled.into()),
+                        sampling: config.sampling,
                     },
                 ))
             }
@@ -200,7 +209,7 @@ pub fn build(
         .thinking_level
         .compute_reasoning_budget(ctx.max_tokens)
         .unwrap_or(0);
-    Ok(Box::new(OpencodeGoClient::new(OpencodeGoConfig::from_env(
+    let mut config = OpencodeGoConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.max_tokens,
@@ -216,7 +225,9 @@ pub fn build(
         crate::openai::reasoning_effort_from_thinking_level(ctx.thinking_level).map(str::to_owned),
         ctx.cache_key.clone(),
         ctx.api_hint.clone(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(OpencodeGoClient::new(config)))
 }
 
 #[cfg(test)]
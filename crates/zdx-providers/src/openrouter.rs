@@ -5,7 +5,7 @@ use reqwest::header::{HeaderMap, HeaderValue};
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ProviderKind, ProviderStream};
 
 /// `OpenRouter` API configuration.
@@ -18,6 +18,7 @@ pub struct OpenRouterConfig {
     pub reasoning_effort: Option<String>,
     pub prompt_cache_key: Option<String>,
     pub include_openrouter_headers: bool,
+    pub sampling: Sampling,
 }
 
 impl OpenRouterConfig {
@@ -52,6 +53,7 @@ impl OpenRouterConfig {
             reasoning_effort,
             prompt_cache_key,
             include_openrouter_headers: true,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -76,6 +78,7 @@ impl OpenRouterClient {
             include_usage: true,
             include_reasoning_content: false,
             thinking: None,
+            sampling: config.sampling,
         });
 
         Self { inner }
@@ -118,12 +121,14 @@ fn build_openrouter_headers(include_openrouter_headers: bool) -> HeaderMap {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(OpenRouterClient::new(OpenRouterConfig::from_env(
+    let mut config = OpenRouterConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
         ctx.api_key,
         crate::openai::reasoning_effort_from_thinking_level(ctx.thinking_level).map(str::to_owned),
         ctx.cache_key.clone(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(OpenRouterClient::new(config)))
 }
@@ -0,0 +1,176 @@
+//! Runtime registry for providers defined outside this crate.
+//!
+//! Built-in providers are selected by `ProviderKind`. Other crates can add a
+//! provider under its own model prefix (`<id>:<model>` or `<id>/<model>`) by
+//! registering a [`ProviderFactory`]; the engine consults the registry before
+//! falling back to the built-in kinds. Plain OpenAI-compatible servers don't
+//! need code at all: declare them under `[providers.custom.<name>]`.
+
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, RwLock};
+
+use anyhow::{Result, bail};
+use zdx_types::config::ThinkingLevel;
+
+use crate::{ProviderKind, Sampling, StreamingProvider};
+
+/// Inputs for building a registered provider's client for one turn.
+#[derive(Debug, Clone)]
+pub struct ProviderRequest<'a> {
+    /// Model id with the registry prefix stripped.
+    pub model: &'a str,
+    pub thinking_level: ThinkingLevel,
+    /// Global `config.max_tokens`, if set.
+    pub max_tokens: Option<u32>,
+    /// Prompt-cache key (the thread id), when the provider supports one.
+    pub cache_key: Option<&'a str>,
+    /// `[model_params]` sampling controls, ungated; factories whose backend
+    /// rejects them should leave them out.
+    pub sampling: Sampling,
+}
+
+/// Builds clients for a registered provider.
+pub trait ProviderFactory: Send + Sync {
+    /// Builds a client for `request.model`.
+    ///
+    /// # Errors
+    /// Returns an error if the client cannot be configured (missing key, bad URL).
+    fn build(&self, request: &ProviderRequest<'_>) -> Result<Box<dyn StreamingProvider>>;
+}
+
+impl<F> ProviderFactory for F
+where
+    F: Fn(&ProviderRequest<'_>) -> Result<Box<dyn StreamingProvider>> + Send + Sync,
+{
+    fn build(&self, request: &ProviderRequest<'_>) -> Result<Box<dyn StreamingProvider>> {
+        self(request)
+    }
+}
+
+/// A registry hit: the provider id, its factory, and the bare model id.
+#[derive(Clone)]
+pub struct RegisteredProvider {
+    pub id: String,
+    pub factory: Arc<dyn ProviderFactory>,
+    pub model: String,
+}
+
+static REGISTRY: LazyLock<RwLock<HashMap<String, Arc<dyn ProviderFactory>>>> =
+    LazyLock::new(|| RwLock::new(HashMap::new()));
+
+/// Registers `factory` for models prefixed with `id`, replacing any
+/// previous registration under the same id.
+///
+/// # Errors
+/// Returns an error if `id` is empty, contains a separator, or names a
+/// built-in provider.
+pub fn register(id: &str, factory: Arc<dyn ProviderFactory>) -> Result<()> {
+    let id = normalize_id(id);
+    if id.is_empty() || id.contains([':', '/']) {
+        bail!("Invalid provider id '{id}'");
+    }
+    if ProviderKind::from_id(&id).is_some() {
+        bail!("Provider id '{id}' is reserved for a built-in provider");
+    }
+    REGISTRY
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(id, factory);
+    Ok(())
+}
+
+/// Removes the provider registered under `id`. Returns whether one existed.
+pub fn unregister(id: &str) -> bool {
+    REGISTRY
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .remove(&normalize_id(id))
+        .is_some()
+}
+
+/// Ids of all registered providers, sorted.
+pub fn registered_ids() -> Vec<String> {
+    let mut ids: Vec<String> = REGISTRY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .keys()
+        .cloned()
+        .collect();
+    ids.sort();
+    ids
+}
+
+/// Resolves `model` against the registry by its `<id>:` or `<id>/` prefix.
+pub fn lookup(model: &str) -> Option<RegisteredProvider> {
+    let trimmed = model.trim();
+    let registry = REGISTRY
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if registry.is_empty() {
+        return None;
+    }
+    [':', '/'].into_iter().find_map(|sep| {
+        let (prefix, rest) = trimmed.split_once(sep)?;
+        let rest = rest.trim();
+        if rest.is_empty() {
+            return None;
+        }
+        let id = normalize_id(prefix);
+        let factory = Arc::clone(registry.get(&id)?);
+        Some(RegisteredProvider {
+            id,
+            factory,
+            model: rest.to_string(),
+        })
+    })
+}
+
+fn normalize_id(id: &str) -> String {
+    id.trim().to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::openai_compatible;
+
+    fn factory() -> Arc<dyn ProviderFactory> {
+        Arc::new(
+            |request: &ProviderRequest<'_>| -> Result<Box<dyn StreamingProvider>> {
+                Ok(openai_compatible::build_custom(
+                    "http://127.0.0.1:9/v1".to_string(),
+                    String::new(),
+                    request.model.to_string(),
+                    request.max_tokens,
+                    None,
+                    false,
+                    request.sampling,
+                ))
+            },
+        )
+    }
+
+    #[test]
+    fn registered_prefix_resolves_to_bare_model() {
+        register("Acme", factory()).unwrap();
+
+        let hit = lookup("acme:fast-1").expect("registered");
+        assert_eq!(hit.id, "acme");
+        assert_eq!(hit.model, "fast-1");
+        assert!(lookup("acme/fast-1").is_some());
+        assert!(lookup("acme:").is_none());
+        assert!(lookup("other:fast-1").is_none());
+        assert!(registered_ids().contains(&"acme".to_string()));
+
+        assert!(unregister("acme"));
+        assert!(lookup("acme:fast-1").is_none());
+    }
+
+    #[test]
+    fn built_in_and_malformed_ids_are_rejected() {
+        assert!(register("openai", factory()).is_err());
+        assert!(register("claude", factory()).is_err());
+        assert!(register("a:b", factory()).is_err());
+        assert!(register("  ", factory()).is_err());
+    }
+}
@@ -17,6 +17,25 @@ pub use zdx_types::{
 /// use mimicked User-Agents for compatibility.
 pub const USER_AGENT: &str = concat!("zdx/", env!("CARGO_PKG_VERSION"));
 
+/// `temperature`/`top_p` for a request body. Flattens into JSON bodies that
+/// use those field names; `None` fields are omitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, serde::Serialize)]
+pub struct Sampling {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+}
+
+impl From<zdx_types::ModelParams> for Sampling {
+    fn from(params: zdx_types::ModelParams) -> Self {
+        Self {
+            temperature: params.temperature,
+            top_p: params.top_p,
+        }
+    }
+}
+
 /// Parses a string into an HTTP header value, returning a contextual error
 /// instead of silently substituting an empty value when the input contains
 /// bytes that are invalid in an HTTP header (e.g. a stray newline or control
@@ -164,6 +183,30 @@ pub fn classify_reqwest_error(err: &reqwest::Error) -> ProviderError {
     }
 }
 
+/// Response headers that carry a provider request ID, in lookup order.
+const REQUEST_ID_HEADERS: &[&str] = &["request-id", "x-request-id", "x-goog-request-id"];
+
+/// Returns the provider request ID from response headers, if present.
+pub(crate) fn request_id_from_headers(headers: &reqwest::header::HeaderMap) -> Option<String> {
+    REQUEST_ID_HEADERS.iter().find_map(|name| {
+        headers
+            .get(*name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(ToString::to_string)
+    })
+}
+
+/// Builds an HTTP status error from a non-success response, keeping the
+/// provider request ID so it can be shown to the user.
+pub(crate) async fn http_status_error(response: reqwest::Response) -> ProviderError {
+    let status = response.status().as_u16();
+    let request_id = request_id_from_headers(response.headers());
+    let body = response.text().await.unwrap_or_default();
+    ProviderError::http_status(status, &body).with_request_id(request_id)
+}
+
 /// Extracts text and an optional image from tool result content.
 ///
 /// Returns the text output and `Some((mime_type, base64_data))` when the
@@ -0,0 +1,143 @@
+//! Recovery for malformed SSE frames.
+//!
+//! Provider streams occasionally break mid-frame: a lost blank line glues two
+//! events into one `data` payload, or a chunk arrives cut off. Instead of
+//! failing the turn on the first bad frame, parsers run every payload through
+//! [`FrameResync::parse`], which salvages the complete JSON objects a broken
+//! frame still holds and skips the rest. Each malformed frame is logged and,
+//! with `ZDX_DEBUG_TRACE` set, its raw bytes are appended to
+//! `malformed_frames.log` in the trace directory. A run of unrecoverable
+//! frames still fails the stream so a garbage response can't loop forever.
+
+use serde_json::Value;
+
+use crate::debug_trace;
+use crate::shared::{ProviderError, ProviderErrorKind, ProviderResult};
+
+/// Unrecoverable frames in a row before the stream is treated as broken.
+const MAX_CONSECUTIVE_MALFORMED: usize = 8;
+/// Longest raw excerpt included in log lines.
+const LOG_PREVIEW_CHARS: usize = 200;
+
+/// Per-stream malformed frame tracking.
+#[derive(Debug)]
+pub(crate) struct FrameResync {
+    provider: &'static str,
+    consecutive_malformed: usize,
+}
+
+impl FrameResync {
+    pub(crate) fn new(provider: &'static str) -> Self {
+        Self {
+            provider,
+            consecutive_malformed: 0,
+        }
+    }
+
+    /// Marks a well-formed frame, ending any run of malformed ones.
+    pub(crate) fn reset(&mut self) {
+        self.consecutive_malformed = 0;
+    }
+
+    /// Parses an SSE `data` payload into the JSON objects it carries: one for
+    /// a well-formed frame, the salvaged objects (possibly none) for a
+    /// malformed one.
+    ///
+    /// # Errors
+    /// Returns a parse error once too many frames in a row were unrecoverable.
+    pub(crate) fn parse(&mut self, data: &str) -> ProviderResult<Vec<Value>> {
+        let err = match serde_json::from_str::<Value>(data.trim()) {
+            Ok(value) => {
+                self.reset();
+                return Ok(vec![value]);
+            }
+            Err(err) => err,
+        };
+
+        let salvaged = salvage_json_payloads(data);
+        let preview: String = data.chars().take(LOG_PREVIEW_CHARS).collect();
+        tracing::warn!(
+            provider = self.provider,
+            %err,
+            bytes = data.len(),
+            salvaged = salvaged.len(),
+            preview,
+            "Malformed SSE frame"
+        );
+        debug_trace::record_malformed_frame(self.provider, &err.to_string(), data);
+
+        if salvaged.is_empty() {
+            self.consecutive_malformed += 1;
+            if self.consecutive_malformed >= MAX_CONSECUTIVE_MALFORMED {
+                return Err(ProviderError::new(
+                    ProviderErrorKind::Parse,
+                    format!(
+                        "Failed to parse SSE JSON ({} malformed frames in a row): {err}",
+                        self.consecutive_malformed
+                    ),
+                ));
+            }
+        } else {
+            self.reset();
+        }
+        Ok(salvaged)
+    }
+}
+
+/// Recovers complete JSON objects from a payload that isn't valid JSON.
+///
+/// Each line is read on its own (stray `data:` prefixes from a lost frame
+/// boundary are stripped, other SSE fields skipped), and every object that
+/// parses from the start of the line is kept; a line cut off at either end
+/// yields only the objects before the break.
+pub(crate) fn salvage_json_payloads(data: &str) -> Vec<Value> {
+    let mut values = Vec::new();
+    for line in data.lines() {
+        let line = line.trim();
+        let line = line.strip_prefix("data:").map_or(line, str::trim_start);
+        if !line.starts_with(['{', '[']) {
+            continue;
+        }
+        for value in serde_json::Deserializer::from_str(line).into_iter::<Value>() {
+            match value {
+                Ok(value) if value.is_object() => values.push(value),
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+    }
+    values
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_salvage_splits_glued_and_truncated_frames() {
+        let data = "{\"a\":1}\nevent: delta\ndata: {\"b\":2}{\"c\":3}\n{\"d\":";
+        assert_eq!(
+            salvage_json_payloads(data),
+            vec![json!({"a": 1}), json!({"b": 2}), json!({"c": 3})]
+        );
+        assert!(salvage_json_payloads("\":4}").is_empty());
+    }
+
+    #[test]
+    fn test_parse_fails_after_consecutive_unrecoverable_frames() {
+        let mut resync = FrameResync::new("test");
+        assert_eq!(resync.parse(" {\"ok\":true} ").unwrap().len(), 1);
+        for _ in 1..MAX_CONSECUTIVE_MALFORMED {
+            assert!(resync.parse("garbage").unwrap().is_empty());
+        }
+        // A salvageable frame resets the run.
+        assert_eq!(resync.parse("{\"x\":1}{\"y\"").unwrap().len(), 1);
+        for _ in 1..MAX_CONSECUTIVE_MALFORMED {
+            assert!(resync.parse("garbage").unwrap().is_empty());
+        }
+        let err = resync.parse("garbage").unwrap_err();
+        assert_eq!(err.kind, ProviderErrorKind::Parse);
+    }
+}
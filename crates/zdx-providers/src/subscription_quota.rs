@@ -125,6 +125,25 @@ impl QuotaError {
     }
 }
 
+/// Formats a future reset instant as `resets in Xh Ym` (mirrors the monitor).
+#[must_use]
+pub fn format_reset_in(dt: DateTime<Utc>) -> String {
+    let secs = (dt - Utc::now()).num_seconds();
+    if secs <= 0 {
+        return "reset due".to_string();
+    }
+    let days = secs / 86_400;
+    let hours = (secs % 86_400) / 3_600;
+    let mins = (secs % 3_600) / 60;
+    if days > 0 {
+        format!("resets in {days}d {hours}h")
+    } else if hours > 0 {
+        format!("resets in {hours}h {mins}m")
+    } else {
+        format!("resets in {mins}m")
+    }
+}
+
 /// Loads stored OAuth credentials **read-only** (no refresh, no write); maps a
 /// missing store or missing provider entry to [`QuotaError::NotAuthenticated`].
 fn require_creds(
@@ -0,0 +1,216 @@
+//! Usage limits observed on Claude subscription (OAuth) responses.
+//!
+//! Messages API responses for OAuth accounts carry
+//! `anthropic-ratelimit-unified-*` headers with the 5-hour and weekly
+//! utilization. The latest snapshot is kept process-wide so the UI can show
+//! it without an extra request, and crossing a warning threshold queues a
+//! one-time warning per window and reset for the agent loop to surface.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{LazyLock, Mutex};
+
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::header::HeaderMap;
+
+use crate::subscription_quota::{
+    PROVIDER_CLAUDE, QuotaWindow, SubscriptionQuota, format_reset_in, provider_display,
+};
+
+const HEADER_PREFIX: &str = "anthropic-ratelimit-unified";
+/// Header window keys and the labels `subscription_quota` uses for them.
+const WINDOWS: &[(&str, &str)] = &[("5h", "5h"), ("7d", "weekly")];
+/// Percent-used thresholds that each warn once per window reset.
+pub const WARN_THRESHOLDS: &[f64] = &[80.0, 95.0];
+
+/// A quota snapshot taken from response headers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObservedLimits {
+    pub quota: SubscriptionQuota,
+    /// Unified status (`allowed`, `allowed_warning`, `rejected`).
+    pub status: Option<String>,
+    pub observed_at: DateTime<Utc>,
+}
+
+#[derive(Default)]
+struct State {
+    latest: HashMap<&'static str, ObservedLimits>,
+    /// (window label, reset timestamp, threshold) already warned about.
+    warned: HashSet<(String, Option<i64>, u32)>,
+    pending: Vec<String>,
+}
+
+static STATE: LazyLock<Mutex<State>> = LazyLock::new(Mutex::default);
+
+/// Records Claude usage limits from a Messages API response, if present.
+pub fn observe_claude_headers(headers: &HeaderMap) {
+    let Some(observed) = parse_claude_headers(headers, Utc::now()) else {
+        return;
+    };
+    let mut state = STATE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let warnings = new_warnings(&mut state.warned, PROVIDER_CLAUDE, &observed.quota);
+    state.pending.extend(warnings);
+    state.latest.insert(PROVIDER_CLAUDE, observed);
+}
+
+/// The latest snapshot observed for `provider`, if any response carried one.
+#[must_use]
+pub fn observed(provider: &str) -> Option<ObservedLimits> {
+    let state = STATE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    state.latest.get(provider).cloned()
+}
+
+/// Drains the warnings queued since the last call.
+#[must_use]
+pub fn take_warnings() -> Vec<String> {
+    let mut state = STATE
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    std::mem::take(&mut state.pending)
+}
+
+/// The window closest to its cap, for compact displays.
+#[must_use]
+pub fn tightest_window(quota: &SubscriptionQuota) -> Option<&QuotaWindow> {
+    quota
+        .windows
+        .iter()
+        .max_by(|a, b| a.used_percent.total_cmp(&b.used_percent))
+}
+
+fn parse_claude_headers(headers: &HeaderMap, now: DateTime<Utc>) -> Option<ObservedLimits> {
+    let header = |name: &str| {
+        headers
+            .get(format!("{HEADER_PREFIX}-{name}"))
+            .and_then(|value| value.to_str().ok())
+            .map(str::trim)
+    };
+
+    let windows: Vec<QuotaWindow> = WINDOWS
+        .iter()
+        .filter_map(|(key, label)| {
+            let utilization: f64 = header(&format!("{key}-utilization"))?.parse().ok()?;
+            let resets_at = header(&format!("{key}-reset"))
+                .and_then(|v| v.parse::<i64>().ok())
+                .and_then(|secs| Utc.timestamp_opt(secs, 0).single());
+            Some(QuotaWindow {
+                label: (*label).to_string(),
+                used_percent: (utilization * 100.0).clamp(0.0, 100.0),
+                resets_at,
+                scope: None,
+            })
+        })
+        .collect();
+    if windows.is_empty() {
+        return None;
+    }
+
+    Some(ObservedLimits {
+        quota: SubscriptionQuota {
+            plan: None,
+            windows,
+        },
+        status: header("status").map(str::to_string),
+        observed_at: now,
+    })
+}
+
+fn new_warnings(
+    warned: &mut HashSet<(String, Option<i64>, u32)>,
+    provider: &str,
+    quota: &SubscriptionQuota,
+) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for window in &quota.windows {
+        let reset = window.resets_at.map(|dt| dt.timestamp());
+        let mut crossed = false;
+        for threshold in WARN_THRESHOLDS {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            let key = (window.label.clone(), reset, *threshold as u32);
+            if window.used_percent >= *threshold && warned.insert(key) {
+                crossed = true;
+            }
+        }
+        if crossed {
+            let reset = window
+                .resets_at
+                .map(|dt| format!(" · {}", format_reset_in(dt)))
+                .unwrap_or_default();
+            warnings.push(format!(
+                "{} {} limit {:.0}% used{reset}",
+                provider_display(provider),
+                window.label,
+                window.used_percent
+            ));
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HeaderMap {
+        let mut map = HeaderMap::new();
+        for (name, value) in pairs {
+            map.insert(
+                reqwest::header::HeaderName::from_bytes(
+                    format!("{HEADER_PREFIX}-{name}").as_bytes(),
+                )
+                .unwrap(),
+                HeaderValue::from_str(value).unwrap(),
+            );
+        }
+        map
+    }
+
+    #[test]
+    fn test_parses_unified_headers() {
+        let map = headers(&[
+            ("status", "allowed_warning"),
+            ("5h-utilization", "0.42"),
+            ("5h-reset", "1760000000"),
+            ("7d-utilization", "0.9"),
+        ]);
+        let observed = parse_claude_headers(&map, Utc::now()).unwrap();
+        assert_eq!(observed.status.as_deref(), Some("allowed_warning"));
+        let windows = &observed.quota.windows;
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].label, "5h");
+        assert!((windows[0].used_percent - 42.0).abs() < 1e-9);
+        assert_eq!(windows[0].resets_at.unwrap().timestamp(), 1_760_000_000);
+        assert_eq!(tightest_window(&observed.quota).unwrap().label, "weekly");
+
+        assert!(parse_claude_headers(&HeaderMap::new(), Utc::now()).is_none());
+    }
+
+    #[test]
+    fn test_warns_once_per_threshold_and_reset() {
+        let quota = |percent: f64| SubscriptionQuota {
+            plan: None,
+            windows: vec![QuotaWindow {
+                label: "5h".to_string(),
+                used_percent: percent,
+                resets_at: None,
+                scope: None,
+            }],
+        };
+        let mut warned = HashSet::new();
+        assert!(new_warnings(&mut warned, PROVIDER_CLAUDE, &quota(50.0)).is_empty());
+        assert_eq!(
+            new_warnings(&mut warned, PROVIDER_CLAUDE, &quota(81.0)),
+            vec!["Claude 5h limit 81% used".to_string()]
+        );
+        assert!(new_warnings(&mut warned, PROVIDER_CLAUDE, &quota(85.0)).is_empty());
+        assert_eq!(
+            new_warnings(&mut warned, PROVIDER_CLAUDE, &quota(96.0)).len(),
+            1
+        );
+    }
+}
@@ -6,7 +6,7 @@ use zdx_types::ToolDefinition;
 use zdx_types::config::ThinkingLevel;
 
 use crate::openai::responses::{ResponsesConfig, send_responses_stream};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ChatMessage, ProviderKind, ProviderStream};
 
 const RESPONSES_PATH: &str = "/responses";
@@ -20,6 +20,7 @@ pub struct XaiConfig {
     pub max_tokens: Option<u32>,
     pub prompt_cache_key: Option<String>,
     pub reasoning_effort: String,
+    pub sampling: Sampling,
 }
 
 impl XaiConfig {
@@ -53,6 +54,7 @@ impl XaiConfig {
             max_tokens,
             prompt_cache_key,
             reasoning_effort,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -85,6 +87,7 @@ pub struct XaiClient {
     api_key: String,
     config: ResponsesConfig,
     http: reqwest::Client,
+    extra: crate::extra::ExtraParams,
 }
 
 impl XaiClient {
@@ -100,6 +103,7 @@ impl XaiClient {
                 reasoning_summary: None,
                 instructions: None,
                 text_verbosity: None,
+                text_format: None,
                 store: Some(false),
                 include: Some(vec!["reasoning.encrypted_content".to_string()]),
                 stream_options: None,
@@ -108,8 +112,10 @@ impl XaiClient {
                 tool_choice: Some("auto".to_string()),
                 truncation: None,
                 service_tier: None,
+                sampling: config.sampling,
             },
-            http: reqwest::Client::new(),
+            http: crate::http::client(),
+            extra: crate::extra::params(),
         }
     }
 
@@ -125,6 +131,7 @@ impl XaiClient {
         let system = merge_system_prompt(system);
         send_responses_stream(
             &self.http,
+            self.extra.as_deref(),
             &self.config,
             build_headers(&self.api_key)?,
             messages,
@@ -142,21 +149,25 @@ impl XaiClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(XaiClient::new(XaiConfig::from_env(
+    let mut config = XaiConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
         ctx.api_key,
         ctx.cache_key.clone(),
         reasoning_effort_from_thinking_level(ctx.thinking_level).to_string(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(XaiClient::new(config)))
 }
 
 #[cfg(test)]
 mod tests {
     use zdx_types::config::ThinkingLevel;
 
-    use super::{RESPONSES_PATH, XaiClient, XaiConfig, reasoning_effort_from_thinking_level};
+    use super::{
+        RESPONSES_PATH, Sampling, XaiClient, XaiConfig, reasoning_effort_from_thinking_level,
+    };
 
     fn test_config(model: &str) -> XaiConfig {
         XaiConfig {
@@ -166,6 +177,7 @@ mod tests {
             max_tokens: Some(1024),
             prompt_cache_key: Some("thread-123".to_string()),
             reasoning_effort: "high".to_string(),
+            sampling: Sampling::default(),
         }
     }
 
@@ -7,7 +7,7 @@ use reqwest::header::HeaderMap;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ProviderKind, ProviderStream};
 
 /// `Xiaomi` API configuration.
@@ -19,6 +19,7 @@ pub struct XiaomiConfig {
     pub max_tokens: Option<u32>,
     pub prompt_cache_key: Option<String>,
     pub thinking_enabled: bool,
+    pub sampling: Sampling,
 }
 
 impl XiaomiConfig {
@@ -52,6 +53,7 @@ impl XiaomiConfig {
             max_tokens,
             prompt_cache_key,
             thinking_enabled,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -76,6 +78,7 @@ impl XiaomiClient {
                 include_usage: true,
                 include_reasoning_content: config.thinking_enabled,
                 thinking: Some(config.thinking_enabled.into()),
+                sampling: config.sampling,
             }),
         }
     }
@@ -103,12 +106,14 @@ impl XiaomiClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(XiaomiClient::new(XiaomiConfig::from_env(
+    let mut config = XiaomiConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
         ctx.api_key,
         None,
         ctx.thinking_level.is_enabled(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(XiaomiClient::new(config)))
 }
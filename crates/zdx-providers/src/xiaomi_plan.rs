@@ -9,7 +9,7 @@ use reqwest::header::HeaderMap;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ProviderKind, ProviderStream};
 
 /// `XiaomiPlan` API configuration.
@@ -21,6 +21,7 @@ pub struct XiaomiPlanConfig {
     pub max_tokens: Option<u32>,
     pub prompt_cache_key: Option<String>,
     pub thinking_enabled: bool,
+    pub sampling: Sampling,
 }
 
 impl XiaomiPlanConfig {
@@ -54,6 +55,7 @@ impl XiaomiPlanConfig {
             max_tokens,
             prompt_cache_key,
             thinking_enabled,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -78,6 +80,7 @@ impl XiaomiPlanClient {
                 include_usage: true,
                 include_reasoning_content: config.thinking_enabled,
                 thinking: Some(config.thinking_enabled.into()),
+                sampling: config.sampling,
             }),
         }
     }
@@ -105,12 +108,14 @@ impl XiaomiPlanClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(XiaomiPlanClient::new(XiaomiPlanConfig::from_env(
+    let mut config = XiaomiPlanConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
         ctx.api_key,
         None,
         ctx.thinking_level.is_enabled(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(XiaomiPlanClient::new(config)))
 }
@@ -5,7 +5,7 @@ use reqwest::header::HeaderMap;
 use zdx_types::ToolDefinition;
 
 use crate::openai::chat_completions::{OpenAIChatCompletionsClient, OpenAIChatCompletionsConfig};
-use crate::shared::merge_system_prompt;
+use crate::shared::{Sampling, merge_system_prompt};
 use crate::{ChatMessage, ProviderKind, ProviderStream};
 
 /// `Z.AI` API configuration.
@@ -17,6 +17,7 @@ pub struct ZaiConfig {
     pub max_tokens: Option<u32>,
     pub prompt_cache_key: Option<String>,
     pub thinking_enabled: bool,
+    pub sampling: Sampling,
 }
 
 impl ZaiConfig {
@@ -50,6 +51,7 @@ impl ZaiConfig {
             max_tokens,
             prompt_cache_key,
             thinking_enabled,
+            sampling: Sampling::default(),
         })
     }
 }
@@ -74,6 +76,7 @@ impl ZaiClient {
                 include_usage: true,
                 include_reasoning_content: config.thinking_enabled,
                 thinking: Some(config.thinking_enabled.into()),
+                sampling: config.sampling,
             }),
         }
     }
@@ -101,12 +104,14 @@ impl ZaiClient {
 pub fn build(
     ctx: &crate::ProviderBuildContext<'_>,
 ) -> anyhow::Result<Box<dyn crate::StreamingProvider>> {
-    Ok(Box::new(ZaiClient::new(ZaiConfig::from_env(
+    let mut config = ZaiConfig::from_env(
         ctx.model.to_string(),
         ctx.config_max_tokens,
         ctx.base_url,
         ctx.api_key,
         ctx.cache_key.clone(),
         ctx.thinking_level.is_enabled(),
-    )?)))
+    )?;
+    config.sampling = ctx.sampling;
+    Ok(Box::new(ZaiClient::new(config)))
 }
@@ -5,16 +5,21 @@
 
 use std::fs::File;
 use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::process::ExitStatusExt;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 
 use serde::Deserialize;
 use serde_json::{Value, json};
-use tokio::io::{AsyncBufReadExt, AsyncRead};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt};
 use uuid::Uuid;
 
 use super::{ToolContext, ToolDefinition, ToolOutput};
+use crate::bash_limits::{BashLimits, OutputBudget, output_limit_notice};
+use crate::shell_session::ShellSession;
 
 /// Maximum bytes per output stream (stdout/stderr) before truncation.
 const MAX_OUTPUT_BYTES: usize = 40 * 1024; // 40KB
@@ -46,10 +51,16 @@ fn write_temp_file(bytes: &[u8], stream_name: &str) -> Option<String> {
 
 /// Returns the tool definition for the bash tool.
 pub fn definition() -> ToolDefinition {
+    let mut description = "Execute a shell command when no dedicated tool exists. Use Bash for builds, tests, git, gh, and other CLI workflows with no first-class tool. NEVER use grep, rg, cat, head, tail, less, find, or ls through Bash for file operations — use the dedicated Read, Grep, and Glob tools instead, which return structured output with pagination and .gitignore awareness. To run in a subdirectory (e.g. one package of a multi-package repo), set cwd instead of prefixing `cd dir &&`. Chain dependent shell steps in one command, but prefer parallel tool calls for independent work. Do not use Bash to communicate with the user. Defaults to a 120 second timeout; set timeout_secs for commands expected to run longer, or 0 to disable the timeout. Returns stdout, stderr, and exit code. When stdout or stderr is truncated, use Read on the returned temp file to inspect the full output."
+        .to_string();
+    if cfg!(windows) {
+        description.push_str(
+            " On Windows, commands run in PowerShell (cmd when PowerShell is unavailable), so use PowerShell syntax.",
+        );
+    }
     ToolDefinition {
         name: "Bash".to_string(),
-        description: "Execute a shell command when no dedicated tool exists. Use Bash for builds, tests, git, gh, and other CLI workflows with no first-class tool. NEVER use grep, rg, cat, head, tail, less, find, or ls through Bash for file operations — use the dedicated Read, Grep, and Glob tools instead, which return structured output with pagination and .gitignore awareness. Chain dependent shell steps in one command, but prefer parallel tool calls for independent work. Do not use Bash to communicate with the user. Defaults to a 120 second timeout; set timeout_secs for commands expected to run longer, or 0 to disable the timeout. Returns stdout, stderr, and exit code. When stdout or stderr is truncated, use Read on the returned temp file to inspect the full output."
-            .to_string(),
+        description,
         input_schema: json!({
             "type": "object",
             "properties": {
@@ -61,6 +72,10 @@ pub fn definition() -> ToolDefinition {
                     "type": "integer",
                     "minimum": 0,
                     "description": "Optional timeout in seconds. Defaults to 120. Use 0 to disable for a known long-running command."
+                },
+                "cwd": {
+                    "type": "string",
+                    "description": "Optional working directory for this command, relative to the project root (must stay inside it). Defaults to the root."
                 }
             },
             "required": ["command"],
@@ -77,6 +92,8 @@ struct BashInput {
         deserialize_with = "crate::u64_or_string::deserialize_optional"
     )]
     timeout_secs: Option<u64>,
+    #[serde(default)]
+    cwd: Option<String>,
 }
 
 fn resolve_timeout(
@@ -90,6 +107,61 @@ fn resolve_timeout(
     }
 }
 
+/// Resolves a per-command working directory against `root`.
+///
+/// Relative paths (and `$VAR`/`~` expansions) resolve from `root`; the result
+/// must be an existing directory inside `root`, so a command cannot be pointed
+/// elsewhere via `..` or symlinks. Returns the directory and its root-relative
+/// display form (`.` for the root itself).
+///
+/// # Errors
+/// Returns a failure envelope if the directory is missing or outside `root`.
+pub fn resolve_cwd(cwd: &str, root: &Path) -> Result<(PathBuf, String), ToolOutput> {
+    let requested = super::resolve_input_path(cwd, root)?;
+    let dir = requested.canonicalize().map_err(|e| {
+        ToolOutput::failure(
+            "path_error",
+            format!("Working directory does not exist '{}'", requested.display()),
+            Some(format!("OS error: {e}")),
+        )
+    })?;
+    if !dir.is_dir() {
+        return Err(ToolOutput::failure(
+            "path_error",
+            format!("Working directory is not a directory '{}'", dir.display()),
+            None,
+        ));
+    }
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let Ok(relative) = dir.strip_prefix(&root) else {
+        return Err(ToolOutput::failure(
+            "path_error",
+            format!(
+                "Working directory '{}' is outside the project root",
+                cwd.trim()
+            ),
+            Some(format!("Root: {}", root.display())),
+        ));
+    };
+    let display = if relative.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        relative.to_string_lossy().replace('\\', "/")
+    };
+    Ok((dir, display))
+}
+
+/// Resolves an optional `cwd` input; blank means the root.
+fn resolve_optional_cwd(
+    cwd: Option<&str>,
+    root: &Path,
+) -> Result<Option<(PathBuf, String)>, ToolOutput> {
+    match cwd.map(str::trim).filter(|cwd| !cwd.is_empty()) {
+        Some(cwd) => resolve_cwd(cwd, root).map(Some),
+        None => Ok(None),
+    }
+}
+
 /// Output from a bash command execution.
 #[derive(Debug)]
 pub struct BashOutput {
@@ -105,9 +177,26 @@ pub struct BashOutput {
     pub stdout_file: Option<String>,
     /// Path to temp file containing full stderr (when truncated).
     pub stderr_file: Option<String>,
+    /// Root-relative working directory, when the command set one.
+    pub cwd: Option<String>,
 }
 
 impl BashOutput {
+    /// Appends a notice line to stderr and streams it through `output_tx`.
+    pub(crate) fn append_notice(
+        &mut self,
+        notice: &str,
+        output_tx: Option<&tokio::sync::mpsc::UnboundedSender<String>>,
+    ) {
+        if let Some(tx) = output_tx {
+            let _ = tx.send(notice.to_string());
+        }
+        if !self.stderr.is_empty() && !self.stderr.ends_with('\n') {
+            self.stderr.push('\n');
+        }
+        self.stderr.push_str(notice);
+    }
+
     /// Converts to structured envelope format.
     pub fn into_tool_output(self) -> ToolOutput {
         let mut data = json!({
@@ -128,6 +217,9 @@ impl BashOutput {
         if let Some(path) = self.stderr_file {
             data["stderr_file"] = json!(path);
         }
+        if let Some(cwd) = self.cwd {
+            data["cwd"] = json!(cwd);
+        }
 
         ToolOutput::success(data)
     }
@@ -160,11 +252,12 @@ pub async fn execute(
     }
 
     let timeout = resolve_timeout(input.timeout_secs, timeout);
+    let cwd = match resolve_optional_cwd(input.cwd.as_deref(), &ctx.root) {
+        Ok(cwd) => cwd,
+        Err(e) => return e,
+    };
 
-    match run_command(&input.command, ctx, timeout, output_tx).await {
-        Ok(output) => output.into_tool_output(),
-        Err(e) => e,
-    }
+    run_with_cwd(&input.command, ctx, cwd, timeout, output_tx, false).await
 }
 
 /// Executes a bash command directly (convenience wrapper).
@@ -172,10 +265,13 @@ pub async fn execute(
 /// This is a simpler API that takes the command string directly,
 /// useful for direct user invocation (e.g., `$` shortcut).
 ///
-/// If `output_tx` is provided, stdout/stderr lines are streamed through the
-/// channel as they arrive.
+/// `cwd` works like the tool's `cwd` input. If `output_tx` is provided,
+/// stdout/stderr lines are streamed through the channel as they arrive.
+/// Unlike the model-facing tool, color output stays on so the caller can
+/// render it.
 pub async fn run(
     command: &str,
+    cwd: Option<&str>,
     ctx: &ToolContext,
     timeout: Option<Duration>,
     output_tx: Option<tokio::sync::mpsc::UnboundedSender<String>>,
@@ -185,9 +281,97 @@ pub async fn run(
     }
 
     let timeout = resolve_timeout(None, timeout);
+    let cwd = match resolve_optional_cwd(cwd, &ctx.root) {
+        Ok(cwd) => cwd,
+        Err(e) => return e,
+    };
+
+    run_with_cwd(command, ctx, cwd, timeout, output_tx, true).await
+}
+
+async fn run_with_cwd(
+    command: &str,
+    ctx: &ToolContext,
+    cwd: Option<(PathBuf, String)>,
+    timeout: Option<Duration>,
+    output_tx: Option<tokio::sync::mpsc::UnboundedSender<String>>,
+    color: bool,
+) -> ToolOutput {
+    let (dir, display) = cwd.unzip();
+    let dir = dir.as_deref().unwrap_or(&ctx.root);
+    let limits = if color {
+        // Direct user commands are trusted; limits guard model-run commands.
+        BashLimits::NONE
+    } else {
+        ctx.bash_limits
+    };
+    match run_command(command, dir, timeout, output_tx, color, limits).await {
+        Ok(mut output) => {
+            output.cwd = display;
+            output.into_tool_output()
+        }
+        Err(e) => e,
+    }
+}
+
+/// Executes the bash tool inside a persistent shell session.
+///
+/// `session` holds the long-lived shell for a thread. It is (re)spawned in
+/// `ctx.root` when empty or no longer usable (after a timeout, interrupt, or
+/// `exit`), so callers only need to keep the slot around between calls.
+pub async fn execute_in_session(
+    input: &Value,
+    ctx: &ToolContext,
+    session: &mut Option<ShellSession>,
+    timeout: Option<Duration>,
+    output_tx: Option<tokio::sync::mpsc::UnboundedSender<String>>,
+) -> ToolOutput {
+    let input: BashInput = match serde_json::from_value(input.clone()) {
+        Ok(i) => i,
+        Err(e) => {
+            return ToolOutput::failure(
+                "invalid_input",
+                format!("Invalid input for bash tool: {e}"),
+                None,
+            );
+        }
+    };
+
+    if input.command.trim().is_empty() {
+        return ToolOutput::failure("invalid_input", "command cannot be empty", None);
+    }
+
+    let timeout = resolve_timeout(input.timeout_secs, timeout);
+    let (cwd, cwd_display) = match resolve_optional_cwd(input.cwd.as_deref(), &ctx.root) {
+        Ok(cwd) => cwd.unzip(),
+        Err(e) => return e,
+    };
+
+    if !session.as_ref().is_some_and(ShellSession::is_usable) {
+        match ShellSession::spawn(&ctx.root, ctx.bash_limits) {
+            Ok(fresh) => *session = Some(fresh),
+            Err(e) => {
+                *session = None;
+                return ToolOutput::failure(
+                    "spawn_error",
+                    "Failed to start persistent shell session",
+                    Some(format!("Error: {e}")),
+                );
+            }
+        }
+    }
+    let Some(shell) = session.as_mut() else {
+        return ToolOutput::failure("spawn_error", "Persistent shell session unavailable", None);
+    };
 
-    match run_command(command, ctx, timeout, output_tx).await {
-        Ok(output) => output.into_tool_output(),
+    match shell
+        .run(&input.command, cwd.as_deref(), timeout, output_tx.as_ref())
+        .await
+    {
+        Ok(mut output) => {
+            output.cwd = cwd_display;
+            output.into_tool_output()
+        }
         Err(e) => e,
     }
 }
@@ -198,7 +382,7 @@ pub async fn run(
 /// This ensures child processes (python, curl, gcloud, etc.) spawned by
 /// the shell are also terminated on interrupt/timeout.
 #[cfg(unix)]
-fn kill_process_group(pgid: i32) {
+pub(crate) fn kill_process_group(pgid: i32) {
     unsafe {
         libc::killpg(pgid, libc::SIGTERM);
     }
@@ -213,14 +397,14 @@ fn kill_process_group(pgid: i32) {
 /// Ensures all child processes are cleaned up even if the future is
 /// cancelled (e.g., by tokio task abort on user interrupt).
 #[cfg(unix)]
-struct ProcessGroupGuard {
+pub(crate) struct ProcessGroupGuard {
     pgid: i32,
     disarmed: bool,
 }
 
 #[cfg(unix)]
 impl ProcessGroupGuard {
-    fn new(pgid: i32) -> Self {
+    pub(crate) fn new(pgid: i32) -> Self {
         Self {
             pgid,
             disarmed: false,
@@ -228,7 +412,7 @@ impl ProcessGroupGuard {
     }
 
     /// Disarm the guard (process completed normally, no cleanup needed).
-    fn disarm(&mut self) {
+    pub(crate) fn disarm(&mut self) {
         self.disarmed = true;
     }
 }
@@ -254,6 +438,7 @@ fn spawn_stream_reader<H>(
     handle: Option<H>,
     tx: Option<tokio::sync::mpsc::UnboundedSender<String>>,
     buf: StreamBuffer,
+    budget: Arc<OutputBudget>,
 ) -> tokio::task::JoinHandle<()>
 where
     H: AsyncRead + Unpin + Send + 'static,
@@ -264,15 +449,23 @@ where
         let mut line = String::new();
         loop {
             line.clear();
-            match reader.read_line(&mut line).await {
+            // Bounded by the budget so one endless line can't grow unchecked.
+            let read = match budget.read_limit() {
+                Some(limit) => (&mut reader).take(limit).read_line(&mut line).await,
+                None => reader.read_line(&mut line).await,
+            };
+            match read {
                 Ok(0) | Err(_) => break,
-                Ok(_) => {
+                Ok(n) => {
                     if let Some(ref tx) = tx {
                         let _ = tx.send(line.clone());
                     }
                     if let Ok(mut guard) = buf.lock() {
                         guard.extend_from_slice(line.as_bytes());
                     }
+                    if !budget.consume(n) {
+                        break;
+                    }
                 }
             }
         }
@@ -307,23 +500,91 @@ async fn finish_reader(mut task: tokio::task::JoinHandle<()>, buf: StreamBuffer)
     )
 }
 
-/// Runs a shell command in the context's root directory.
+/// Builds the platform shell invocation for a one-shot command.
+///
+/// Unix runs `sh -c`. Windows prefers `PowerShell` (`pwsh`, then Windows
+/// `PowerShell`) and falls back to `cmd /C` when neither is on `PATH`.
+pub fn shell_command(command: &str) -> tokio::process::Command {
+    #[cfg(not(windows))]
+    {
+        let mut cmd = tokio::process::Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+    #[cfg(windows)]
+    {
+        if let Some(powershell) = windows_powershell() {
+            let mut cmd = tokio::process::Command::new(powershell);
+            cmd.args([
+                "-NoLogo",
+                "-NoProfile",
+                "-NonInteractive",
+                "-EncodedCommand",
+            ])
+            .arg(encode_powershell_command(command));
+            cmd
+        } else {
+            // `cmd` does its own parsing of the command line, so pass the
+            // command through verbatim instead of MSVC-style quoting.
+            let mut cmd = tokio::process::Command::new("cmd.exe");
+            cmd.args(["/D", "/S", "/C"])
+                .raw_arg(format!("\"{command}\""));
+            cmd
+        }
+    }
+}
+
+/// Returns the first `PowerShell` executable found on `PATH` (cached).
+#[cfg(windows)]
+fn windows_powershell() -> Option<&'static str> {
+    static POWERSHELL: std::sync::OnceLock<Option<&'static str>> = std::sync::OnceLock::new();
+    *POWERSHELL.get_or_init(|| {
+        let path = std::env::var_os("PATH")?;
+        ["pwsh.exe", "powershell.exe"]
+            .into_iter()
+            .find(|exe| std::env::split_paths(&path).any(|dir| dir.join(exe).is_file()))
+    })
+}
+
+/// Encodes a script for `PowerShell -EncodedCommand` (base64 of UTF-16LE).
+///
+/// Avoids every quoting pitfall of passing arbitrary commands through the
+/// Windows command line.
+#[cfg(any(windows, test))]
+fn encode_powershell_command(command: &str) -> String {
+    use base64::Engine;
+
+    let utf16: Vec<u8> = command.encode_utf16().flat_map(u16::to_le_bytes).collect();
+    base64::engine::general_purpose::STANDARD.encode(utf16)
+}
+
+/// Runs a shell command in `cwd` under `limits`.
+///
+/// With `color`, programs are asked to emit ANSI colors even though output is
+/// piped (`CLICOLOR_FORCE`, `FORCE_COLOR`, `CARGO_TERM_COLOR`).
 #[allow(clippy::too_many_lines)]
 async fn run_command(
     command: &str,
-    ctx: &ToolContext,
+    cwd: &Path,
     timeout: Option<Duration>,
     output_tx: Option<tokio::sync::mpsc::UnboundedSender<String>>,
+    color: bool,
+    limits: BashLimits,
 ) -> Result<BashOutput, ToolOutput> {
-    let mut cmd = tokio::process::Command::new("sh");
-    cmd.arg("-c")
-        .arg(command)
-        .current_dir(&ctx.root)
+    let mut cmd = shell_command(command);
+    if color {
+        cmd.env("TERM", "xterm-256color")
+            .env_remove("NO_COLOR")
+            .env("CLICOLOR_FORCE", "1")
+            .env("FORCE_COLOR", "1")
+            .env("CARGO_TERM_COLOR", "always");
+    } else {
         // Signal to programs that we are a non-interactive, dumb terminal.
         // This suppresses ANSI escape sequences, color output, and progress bars
         // in most well-behaved CLI tools (e.g. gcloud, npm, pip).
-        .env("TERM", "dumb")
-        .env("NO_COLOR", "1")
+        cmd.env("TERM", "dumb").env("NO_COLOR", "1");
+    }
+    cmd.current_dir(cwd)
         // Force non-interactive stdin so child processes do not block waiting
         // for user input or keep client/daemon sessions alive (for example,
         // `gradlew` under piped exec environments).
@@ -335,11 +596,12 @@ async fn run_command(
     // Without this, interrupting only kills the shell, leaving python/curl/etc orphaned.
     #[cfg(unix)]
     unsafe {
-        cmd.pre_exec(|| {
+        cmd.pre_exec(move || {
             // Create a new process group with this process as the leader.
             // All child processes inherit this group.
             libc::setpgid(0, 0);
-            Ok(())
+            // Resource limits are inherited by everything the shell starts.
+            crate::bash_limits::apply_rlimits(&limits)
         });
     }
 
@@ -371,35 +633,51 @@ async fn run_command(
     let stdout_buf: StreamBuffer = Arc::new(Mutex::new(Vec::new()));
     let stderr_buf: StreamBuffer = Arc::new(Mutex::new(Vec::new()));
 
-    let stdout_task = spawn_stream_reader(child_stdout, output_tx.clone(), Arc::clone(&stdout_buf));
-    let stderr_task = spawn_stream_reader(child_stderr, output_tx.clone(), Arc::clone(&stderr_buf));
-
-    // Wait for child exit, with optional timeout.
-    // Reader tasks run independently — they'll see EOF after the process exits
-    // (or is killed on timeout).
-    let (timed_out, exit_code) = match timeout {
-        Some(dur) => {
-            match tokio::time::timeout(dur, child.wait()).await {
-                Ok(Ok(status)) => (false, status.code().unwrap_or(-1)),
-                Ok(Err(_)) => (false, -1),
-                Err(_) => {
-                    // Timeout: kill the process group / child.
-                    #[cfg(unix)]
-                    kill_process_group(child_pid);
-                    #[cfg(not(unix))]
-                    let _ = child.kill().await;
-
-                    // Reap the child to avoid zombies.
-                    let _ = child.wait().await;
-                    (true, -1)
-                }
-            }
+    let budget = Arc::new(OutputBudget::new(limits.max_output_bytes));
+    let stdout_task = spawn_stream_reader(
+        child_stdout,
+        output_tx.clone(),
+        Arc::clone(&stdout_buf),
+        Arc::clone(&budget),
+    );
+    let stderr_task = spawn_stream_reader(
+        child_stderr,
+        output_tx.clone(),
+        Arc::clone(&stderr_buf),
+        Arc::clone(&budget),
+    );
+
+    // Wait for child exit, with optional timeout, or until the output budget
+    // runs out. Reader tasks run independently — they'll see EOF after the
+    // process exits (or is killed).
+    let deadline = async {
+        match timeout {
+            Some(dur) => tokio::time::sleep(dur).await,
+            None => std::future::pending().await,
         }
-        None => match child.wait().await {
-            Ok(status) => (false, status.code().unwrap_or(-1)),
-            Err(_) => (false, -1),
-        },
     };
+    let exited = tokio::select! {
+        status = child.wait() => Some(status.ok()),
+        () = deadline => None,
+        () = budget.exceeded() => Some(None),
+    };
+    let timed_out = exited.is_none();
+    // The command may also exit on its own (SIGPIPE) once its reader stops.
+    let output_limited = matches!(exited, Some(None)) || budget.is_exceeded();
+    let status = if let Some(Some(status)) = exited {
+        Some(status)
+    } else {
+        // Timeout or output limit: kill the process group / child.
+        #[cfg(unix)]
+        kill_process_group(child_pid);
+        #[cfg(not(unix))]
+        let _ = child.kill().await;
+
+        // Reap the child to avoid zombies.
+        let _ = child.wait().await;
+        None
+    };
+    let exit_code = status.and_then(|status| status.code()).unwrap_or(-1);
 
     // Disarm the guard now that the child has exited (normal, timeout, or error).
     #[cfg(unix)]
@@ -411,20 +689,56 @@ async fn run_command(
     let stdout_buf = finish_reader(stdout_task, stdout_buf).await;
     let stderr_buf = finish_reader(stderr_task, stderr_buf).await;
 
-    // Apply truncation to accumulated buffers.
+    let mut output = build_output(
+        &stdout_buf,
+        &stderr_buf,
+        exit_code,
+        timed_out,
+        timeout,
+        output_tx.as_ref(),
+    );
+    if output_limited {
+        output.append_notice(
+            &output_limit_notice(limits.max_output_bytes),
+            output_tx.as_ref(),
+        );
+    }
+    #[cfg(unix)]
+    if let Some(notice) = crate::bash_limits::cpu_limit_notice(
+        &limits,
+        exit_code,
+        status.and_then(|status| status.signal()),
+    ) {
+        output.append_notice(&notice, output_tx.as_ref());
+    }
+    Ok(output)
+}
+
+/// Applies truncation to captured stream buffers and assembles a [`BashOutput`].
+///
+/// When `timed_out` is set, a notice naming `timeout` is appended to stderr and
+/// streamed through `output_tx`.
+pub(crate) fn build_output(
+    stdout_buf: &[u8],
+    stderr_buf: &[u8],
+    exit_code: i32,
+    timed_out: bool,
+    timeout: Option<Duration>,
+    output_tx: Option<&tokio::sync::mpsc::UnboundedSender<String>>,
+) -> BashOutput {
     let (stdout, stdout_truncated, stdout_total_bytes) =
-        super::truncate_bytes_to_byte_limit(&stdout_buf, MAX_OUTPUT_BYTES);
+        super::truncate_bytes_to_byte_limit(stdout_buf, MAX_OUTPUT_BYTES);
     let (stderr_text, stderr_truncated, stderr_total_bytes) =
-        super::truncate_bytes_to_byte_limit(&stderr_buf, MAX_OUTPUT_BYTES);
+        super::truncate_bytes_to_byte_limit(stderr_buf, MAX_OUTPUT_BYTES);
 
     // Write full output to temp files when truncated
     let stdout_file = if stdout_truncated {
-        write_temp_file(&stdout_buf, "stdout")
+        write_temp_file(stdout_buf, "stdout")
     } else {
         None
     };
     let stderr_file = if stderr_truncated {
-        write_temp_file(&stderr_buf, "stderr")
+        write_temp_file(stderr_buf, "stderr")
     } else {
         None
     };
@@ -434,7 +748,7 @@ async fn run_command(
             "Command timed out after {} seconds",
             timeout.map_or(0, |d| d.as_secs())
         );
-        if let Some(ref tx) = output_tx {
+        if let Some(tx) = output_tx {
             let _ = tx.send(timeout_msg.clone());
         }
         let stderr = if stderr_text.is_empty() {
@@ -442,7 +756,7 @@ async fn run_command(
         } else {
             format!("{stderr_text}\n{timeout_msg}")
         };
-        return Ok(BashOutput {
+        return BashOutput {
             stdout,
             stderr,
             exit_code: -1,
@@ -453,10 +767,11 @@ async fn run_command(
             stderr_total_bytes,
             stdout_file,
             stderr_file,
-        });
+            cwd: None,
+        };
     }
 
-    Ok(BashOutput {
+    BashOutput {
         stdout,
         stderr: stderr_text,
         exit_code,
@@ -467,7 +782,8 @@ async fn run_command(
         stderr_total_bytes,
         stdout_file,
         stderr_file,
-    })
+        cwd: None,
+    }
 }
 
 #[cfg(test)]
@@ -552,6 +868,58 @@ mod tests {
         assert!(data["stdout"].as_str().unwrap().contains("test.txt"));
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_bash_limits_kill_runaway_commands() {
+        let temp = TempDir::new().unwrap();
+        let ctx = ToolContext::new(temp.path().to_path_buf(), Some(Duration::from_secs(20)))
+            .with_bash_limits(BashLimits {
+                cpu_time_secs: 1,
+                max_output_bytes: 64 * 1024,
+                ..BashLimits::NONE
+            });
+
+        let result = execute(&json!({"command": "yes"}), &ctx, None, None).await;
+        let data = result.data().expect("should have data");
+        assert!(data["stdout_total_bytes"].as_u64().unwrap() <= 64 * 1024 + 8192);
+        assert!(
+            data["stderr"]
+                .as_str()
+                .unwrap()
+                .contains("output exceeded the 64 KiB limit")
+        );
+
+        let input = json!({"command": "while :; do :; done"});
+        let result = execute(&input, &ctx, None, None).await;
+        let data = result.data().expect("should have data");
+        assert_eq!(data["timed_out"], false);
+        assert!(
+            data["stderr"]
+                .as_str()
+                .unwrap()
+                .contains("CPU time limit of 1s exceeded")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bash_runs_in_cwd_inside_root_only() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("pkg/src")).unwrap();
+        let ctx = ToolContext::new(temp.path().to_path_buf(), None);
+
+        let input = json!({"command": "basename \"$PWD\"", "cwd": "pkg/src/"});
+        let result = execute(&input, &ctx, None, None).await;
+        let data = result.data().expect("should have data");
+        assert_eq!(data["stdout"], "src\n");
+        assert_eq!(data["cwd"], "pkg/src");
+
+        for cwd in ["..", "missing"] {
+            let input = json!({"command": "pwd", "cwd": cwd});
+            let payload = serde_json::to_value(execute(&input, &ctx, None, None).await).unwrap();
+            assert_eq!(payload["error"]["code"], "path_error", "cwd {cwd}");
+        }
+    }
+
     #[tokio::test]
     async fn test_bash_timeout() {
         let temp = TempDir::new().unwrap();
@@ -624,7 +992,7 @@ mod tests {
         let temp = TempDir::new().unwrap();
         let ctx = ToolContext::new(temp.path().to_path_buf(), None);
 
-        let result = run("", &ctx, None, None).await;
+        let result = run("", None, &ctx, None, None).await;
         assert!(!result.is_ok());
         let payload = serde_json::to_value(result).unwrap();
         assert_eq!(payload["error"]["code"], "invalid_input");
@@ -701,6 +1069,12 @@ mod tests {
         assert!(data.get("stderr_file").is_none());
     }
 
+    #[test]
+    fn test_encode_powershell_command_is_utf16le_base64() {
+        assert_eq!(encode_powershell_command("ab"), "YQBiAA==");
+        assert_eq!(encode_powershell_command("é"), "6QA=");
+    }
+
     #[test]
     fn test_write_temp_file() {
         let content = b"Hello, temp file!";
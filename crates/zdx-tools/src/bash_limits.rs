@@ -0,0 +1,224 @@
+//! Resource limits for bash tool commands.
+//!
+//! The model occasionally produces commands that run away (`find /`, a stray
+//! fork bomb, a loop printing forever). CPU time, memory, and process counts
+//! are capped with `setrlimit` in the spawned shell (inherited by everything
+//! it starts); captured output is capped here, and a command that exceeds it
+//! is killed.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use tokio::sync::Notify;
+
+/// Default cap on captured output (stdout + stderr) per command.
+pub const DEFAULT_MAX_CAPTURED_BYTES: usize = 16 * 1024 * 1024;
+
+/// Limits applied to bash tool commands. Zero disables a limit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BashLimits {
+    /// CPU seconds per process (`RLIMIT_CPU`). Each process in the command
+    /// gets its own budget; the kernel sends `SIGXCPU`, then `SIGKILL`.
+    pub cpu_time_secs: u64,
+    /// Virtual memory per process in MiB (`RLIMIT_AS`). Linux does not
+    /// enforce RSS limits, so address space is the closest cap; allocations
+    /// past it fail.
+    pub max_memory_mb: u64,
+    /// Processes for the user (`RLIMIT_NPROC`), which stops fork bombs. Not
+    /// enforced for root.
+    pub max_processes: u64,
+    /// Output bytes (stdout + stderr) captured before the command is killed.
+    pub max_output_bytes: usize,
+}
+
+impl BashLimits {
+    /// No limits at all (direct user commands).
+    pub const NONE: Self = Self {
+        cpu_time_secs: 0,
+        max_memory_mb: 0,
+        max_processes: 0,
+        max_output_bytes: 0,
+    };
+}
+
+impl Default for BashLimits {
+    fn default() -> Self {
+        Self {
+            max_output_bytes: DEFAULT_MAX_CAPTURED_BYTES,
+            ..Self::NONE
+        }
+    }
+}
+
+/// Applies the rlimits to the current process. Only async-signal-safe calls,
+/// so it can run in a `pre_exec` hook. Limits are clamped to the existing
+/// hard limits, which an unprivileged process cannot raise.
+///
+/// # Errors
+/// Returns the OS error if a limit cannot be set.
+#[cfg(unix)]
+pub(crate) fn apply_rlimits(limits: &BashLimits) -> std::io::Result<()> {
+    if limits.cpu_time_secs > 0 {
+        // A hard limit one second past the soft one lets `SIGXCPU` arrive
+        // before `SIGKILL`.
+        set_rlimit(
+            libc::RLIMIT_CPU,
+            limits.cpu_time_secs,
+            limits.cpu_time_secs.saturating_add(1),
+        )?;
+    }
+    if limits.max_memory_mb > 0 {
+        let bytes = limits.max_memory_mb.saturating_mul(1024 * 1024);
+        set_rlimit(libc::RLIMIT_AS, bytes, bytes)?;
+    }
+    if limits.max_processes > 0 {
+        set_rlimit(
+            libc::RLIMIT_NPROC,
+            limits.max_processes,
+            limits.max_processes,
+        )?;
+    }
+    Ok(())
+}
+
+/// The `resource` argument type of `getrlimit`/`setrlimit` (an enum on glibc).
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+type Resource = libc::__rlimit_resource_t;
+#[cfg(all(unix, not(all(target_os = "linux", target_env = "gnu"))))]
+type Resource = libc::c_int;
+
+#[cfg(unix)]
+fn set_rlimit(resource: Resource, soft: u64, hard: u64) -> std::io::Result<()> {
+    let mut current = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // SAFETY: `current` is a valid, writable rlimit struct.
+    if unsafe { libc::getrlimit(resource, &raw mut current) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    let max = current.rlim_max;
+    let hard = libc::rlim_t::try_from(hard).unwrap_or(max).min(max);
+    let soft = libc::rlim_t::try_from(soft).unwrap_or(hard).min(hard);
+    let limit = libc::rlimit {
+        rlim_cur: soft,
+        rlim_max: hard,
+    };
+    // SAFETY: `limit` is a valid rlimit struct.
+    if unsafe { libc::setrlimit(resource, &raw const limit) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Shared output budget for one command's stdout and stderr readers.
+#[derive(Debug)]
+pub(crate) struct OutputBudget {
+    max: usize,
+    used: AtomicUsize,
+    exceeded: Notify,
+}
+
+impl OutputBudget {
+    /// A budget of `max` bytes (0 = unlimited).
+    pub(crate) fn new(max: usize) -> Self {
+        Self {
+            max,
+            used: AtomicUsize::new(0),
+            exceeded: Notify::new(),
+        }
+    }
+
+    /// Bytes a reader may still take before going over, plus one so a read
+    /// can detect the overflow. `None` when unlimited.
+    pub(crate) fn read_limit(&self) -> Option<u64> {
+        (self.max > 0).then(|| {
+            let left = self.max.saturating_sub(self.used.load(Ordering::Relaxed));
+            left as u64 + 1
+        })
+    }
+
+    /// Records `bytes` of output. Returns false (and wakes `exceeded`) once
+    /// the budget is used up.
+    pub(crate) fn consume(&self, bytes: usize) -> bool {
+        if self.max == 0 {
+            return true;
+        }
+        let used = self.used.fetch_add(bytes, Ordering::Relaxed) + bytes;
+        if used > self.max {
+            self.exceeded.notify_one();
+            return false;
+        }
+        true
+    }
+
+    /// Whether more output arrived than the budget allows.
+    pub(crate) fn is_exceeded(&self) -> bool {
+        self.max > 0 && self.used.load(Ordering::Relaxed) > self.max
+    }
+
+    /// Resolves once the budget is used up.
+    pub(crate) async fn exceeded(&self) {
+        self.exceeded.notified().await;
+    }
+}
+
+/// Notice appended to stderr when a command was killed for its output size.
+pub(crate) fn output_limit_notice(max_output_bytes: usize) -> String {
+    format!(
+        "Command killed: output exceeded the {} limit ([bash] max_output_bytes)",
+        format_bytes(max_output_bytes)
+    )
+}
+
+/// Notice for a command stopped by the CPU time limit, detected from the
+/// `SIGXCPU`/`SIGKILL` it got (or a shell's `128 + SIGXCPU` exit code).
+#[cfg(unix)]
+pub(crate) fn cpu_limit_notice(
+    limits: &BashLimits,
+    exit_code: i32,
+    signal: Option<i32>,
+) -> Option<String> {
+    let xcpu = signal == Some(libc::SIGXCPU) || exit_code == 128 + libc::SIGXCPU;
+    (limits.cpu_time_secs > 0 && xcpu).then(|| {
+        format!(
+            "Command stopped: CPU time limit of {}s exceeded ([bash] cpu_time_secs)",
+            limits.cpu_time_secs
+        )
+    })
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 && bytes.is_multiple_of(1024 * 1024) {
+        format!("{} MiB", bytes / (1024 * 1024))
+    } else if bytes >= 1024 && bytes.is_multiple_of(1024) {
+        format!("{} KiB", bytes / 1024)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn budget_reports_overflow_once_used_up() {
+        let budget = OutputBudget::new(10);
+        assert_eq!(budget.read_limit(), Some(11));
+        assert!(budget.consume(6));
+        assert_eq!(budget.read_limit(), Some(5));
+        assert!(budget.consume(4));
+        assert!(!budget.consume(1));
+        assert!(budget.is_exceeded());
+
+        let unlimited = OutputBudget::new(0);
+        assert_eq!(unlimited.read_limit(), None);
+        assert!(unlimited.consume(usize::MAX));
+    }
+
+    #[test]
+    fn output_limit_notice_formats_sizes() {
+        assert!(output_limit_notice(16 * 1024 * 1024).contains("16 MiB"));
+        assert!(output_limit_notice(1000).contains("1000 bytes"));
+    }
+}
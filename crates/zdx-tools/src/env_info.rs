@@ -0,0 +1,482 @@
+//! Environment snapshot tool.
+//!
+//! `Env_Info` reports the OS, architecture, shell, CPU and memory, git state,
+//! and the versions of the toolchains the project uses (detected from marker
+//! files in the root, e.g. `Cargo.toml` → `rustc`), so the model doesn't
+//! start a session with a round of `uname`/`rustc --version`/`git status`
+//! bash calls. `zdx doctor` prints the same snapshot.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+use serde_json::{Value, json};
+
+use super::{ToolContext, ToolDefinition, ToolOutput};
+
+/// How long a single probe command (`rustc --version`, `git status`) may run.
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Toolchains probed when one of their marker files is in the project root:
+/// `(name, markers, program, args)`.
+const TOOLCHAINS: &[(&str, &[&str], &str, &[&str])] = &[
+    (
+        "rustc",
+        &["Cargo.toml", "rust-toolchain.toml", "rust-toolchain"],
+        "rustc",
+        &["--version"],
+    ),
+    ("cargo", &["Cargo.toml"], "cargo", &["--version"]),
+    (
+        "node",
+        &["package.json", ".nvmrc", ".node-version"],
+        "node",
+        &["--version"],
+    ),
+    ("npm", &["package-lock.json"], "npm", &["--version"]),
+    ("pnpm", &["pnpm-lock.yaml"], "pnpm", &["--version"]),
+    ("yarn", &["yarn.lock"], "yarn", &["--version"]),
+    ("bun", &["bun.lock", "bun.lockb"], "bun", &["--version"]),
+    ("deno", &["deno.json", "deno.jsonc"], "deno", &["--version"]),
+    (
+        "python",
+        &[
+            "pyproject.toml",
+            "requirements.txt",
+            "setup.py",
+            "Pipfile",
+            ".python-version",
+        ],
+        "python3",
+        &["--version"],
+    ),
+    ("uv", &["uv.lock"], "uv", &["--version"]),
+    ("go", &["go.mod"], "go", &["version"]),
+    ("ruby", &["Gemfile"], "ruby", &["--version"]),
+    (
+        "java",
+        &["pom.xml", "build.gradle", "build.gradle.kts"],
+        "java",
+        &["-version"],
+    ),
+];
+
+/// Returns the tool definition for the env info tool.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "Env_Info".to_string(),
+        description: "Return a snapshot of the environment: OS and version, architecture, shell, CPU and memory, git state of the project root (branch, commit, changed files, ahead/behind), and versions of the toolchains the project uses (detected from files like Cargo.toml, package.json, pyproject.toml, go.mod). Call it once when you need these facts instead of running exploratory bash commands.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {},
+            "additionalProperties": false
+        }),
+    }
+}
+
+/// Executes the env info tool.
+pub fn execute(_input: &Value, ctx: &ToolContext) -> ToolOutput {
+    match serde_json::to_value(snapshot(&ctx.root)) {
+        Ok(data) => ToolOutput::success(data),
+        Err(e) => ToolOutput::failure(
+            "serialize_error",
+            "Failed to serialize environment snapshot",
+            Some(e.to_string()),
+        ),
+    }
+}
+
+/// Environment facts for the project at a root.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvSnapshot {
+    pub os: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub os_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kernel: Option<String>,
+    pub arch: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+    pub cpu: CpuInfo,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryInfo>,
+    /// `None` when the root is not inside a git work tree.
+    pub git: Option<GitState>,
+    pub toolchains: Vec<Toolchain>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CpuInfo {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    pub cores: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct MemoryInfo {
+    pub total_bytes: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub available_bytes: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GitState {
+    /// Branch name; `None` on a detached HEAD.
+    pub branch: Option<String>,
+    /// Short commit id; `None` before the first commit.
+    pub commit: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upstream: Option<String>,
+    pub ahead: u32,
+    pub behind: u32,
+    /// Modified, staged, and untracked paths.
+    pub changed_files: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Toolchain {
+    pub name: String,
+    /// First line of the version output; `None` when the program is missing.
+    pub version: Option<String>,
+    /// Marker file that made the project use it.
+    pub detected_by: String,
+}
+
+/// Collects the snapshot for `root`. Probe commands run in parallel, each
+/// bounded by a short timeout.
+pub fn snapshot(root: &Path) -> EnvSnapshot {
+    let detected: Vec<(&str, &str, &str, &[&str])> = TOOLCHAINS
+        .iter()
+        .filter_map(|(name, markers, program, args)| {
+            markers
+                .iter()
+                .find(|marker| root.join(marker).exists())
+                .map(|marker| (*name, *marker, *program, *args))
+        })
+        .collect();
+
+    std::thread::scope(|scope| {
+        let git = scope.spawn(|| git_state(root));
+        let toolchain_probes: Vec<_> = detected
+            .iter()
+            .map(|&(name, marker, program, args)| {
+                scope.spawn(move || Toolchain {
+                    name: name.to_string(),
+                    version: probe(program, args, root),
+                    detected_by: marker.to_string(),
+                })
+            })
+            .collect();
+
+        let (os_version, kernel) = os_version();
+        EnvSnapshot {
+            os: std::env::consts::OS.to_string(),
+            os_version,
+            kernel,
+            arch: std::env::consts::ARCH.to_string(),
+            shell: std::env::var("SHELL")
+                .ok()
+                .or_else(|| std::env::var("COMSPEC").ok())
+                .filter(|shell| !shell.is_empty()),
+            cpu: CpuInfo {
+                model: cpu_model(),
+                cores: std::thread::available_parallelism().map_or(1, usize::from),
+            },
+            memory: memory_info(),
+            git: git.join().unwrap_or_default(),
+            toolchains: toolchain_probes
+                .into_iter()
+                .filter_map(|probe| probe.join().ok())
+                .collect(),
+        }
+    })
+}
+
+impl EnvSnapshot {
+    /// Human-readable rendering (used by `zdx doctor`).
+    pub fn to_text(&self) -> String {
+        let mut out = String::new();
+        let os = self.os_version.as_deref().unwrap_or(&self.os);
+        let kernel = self
+            .kernel
+            .as_deref()
+            .map(|kernel| format!("{} {kernel}, ", self.os))
+            .unwrap_or_default();
+        let _ = writeln!(out, "OS          {os} ({kernel}{})", self.arch);
+        let _ = writeln!(
+            out,
+            "Shell       {}",
+            self.shell.as_deref().unwrap_or("unknown")
+        );
+        let model = self.cpu.model.as_deref().unwrap_or("unknown CPU");
+        let cores = self.cpu.cores;
+        let plural = if cores == 1 { "" } else { "s" };
+        let _ = writeln!(out, "CPU         {model} ({cores} core{plural})");
+        if let Some(memory) = self.memory {
+            let total = format_gib(memory.total_bytes);
+            match memory.available_bytes {
+                Some(available) => {
+                    let available = format_gib(available);
+                    let _ = writeln!(out, "Memory      {available} available of {total}");
+                }
+                None => {
+                    let _ = writeln!(out, "Memory      {total}");
+                }
+            }
+        }
+        let _ = match &self.git {
+            Some(git) => writeln!(out, "Git         {}", git_summary(git)),
+            None => writeln!(out, "Git         not a git repository"),
+        };
+        if self.toolchains.is_empty() {
+            let _ = writeln!(out, "Toolchains  none detected in the project root");
+        } else {
+            let _ = writeln!(out, "Toolchains");
+            for toolchain in &self.toolchains {
+                let _ = writeln!(
+                    out,
+                    "  {:<8}  {}  ({})",
+                    toolchain.name,
+                    toolchain.version.as_deref().unwrap_or("not found"),
+                    toolchain.detected_by
+                );
+            }
+        }
+        out
+    }
+}
+
+fn git_summary(git: &GitState) -> String {
+    let mut summary = git.branch.clone().unwrap_or_else(|| "detached".to_string());
+    if let Some(commit) = &git.commit {
+        let _ = write!(summary, " @ {commit}");
+    }
+    let _ = write!(summary, " · {} changed", git.changed_files);
+    if let Some(upstream) = &git.upstream {
+        let _ = write!(summary, " · ↑{} ↓{} {upstream}", git.ahead, git.behind);
+    }
+    summary
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_gib(bytes: u64) -> String {
+    format!("{:.1} GiB", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+}
+
+/// Runs `program args` in `root` and returns the first non-empty line of its
+/// output (stdout, else stderr — `java -version` prints there). `None` when
+/// the program is missing, fails, or runs past the probe timeout.
+fn probe(program: &str, args: &[&str], root: &Path) -> Option<String> {
+    let output = run_probe(program, args, root)?;
+    if !output.status.success() {
+        return None;
+    }
+    [&output.stdout, &output.stderr]
+        .into_iter()
+        .find_map(|bytes| {
+            String::from_utf8_lossy(bytes)
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+                .map(str::to_string)
+        })
+}
+
+fn run_probe(program: &str, args: &[&str], root: &Path) -> Option<std::process::Output> {
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(root)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .ok()?;
+    let started = Instant::now();
+    loop {
+        match child.try_wait() {
+            Ok(Some(_)) => return child.wait_with_output().ok(),
+            Ok(None) if started.elapsed() < PROBE_TIMEOUT => {
+                std::thread::sleep(Duration::from_millis(10));
+            }
+            _ => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+        }
+    }
+}
+
+fn git_state(root: &Path) -> Option<GitState> {
+    let output = run_probe("git", &["status", "--porcelain=v2", "--branch"], root)?;
+    output
+        .status
+        .success()
+        .then(|| parse_git_status(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parses `git status --porcelain=v2 --branch` output.
+fn parse_git_status(text: &str) -> GitState {
+    let mut state = GitState::default();
+    for line in text.lines() {
+        if let Some(header) = line.strip_prefix("# ") {
+            let (key, value) = header.split_once(' ').unwrap_or((header, ""));
+            match key {
+                "branch.oid" if value != "(initial)" => {
+                    state.commit = Some(value.chars().take(12).collect());
+                }
+                "branch.head" if value != "(detached)" => state.branch = Some(value.to_string()),
+                "branch.upstream" => state.upstream = Some(value.to_string()),
+                "branch.ab" => {
+                    for part in value.split_whitespace() {
+                        if let Some(n) = part.strip_prefix('+') {
+                            state.ahead = n.parse().unwrap_or(0);
+                        } else if let Some(n) = part.strip_prefix('-') {
+                            state.behind = n.parse().unwrap_or(0);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        } else if !line.trim().is_empty() {
+            state.changed_files += 1;
+        }
+    }
+    state
+}
+
+/// Distribution/product name and kernel release.
+fn os_version() -> (Option<String>, Option<String>) {
+    let kernel = if cfg!(unix) {
+        run_probe("uname", &["-r"], Path::new("/"))
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+            .filter(|kernel| !kernel.is_empty())
+    } else {
+        None
+    };
+    let version = if cfg!(target_os = "linux") {
+        std::fs::read_to_string("/etc/os-release")
+            .ok()
+            .and_then(|text| parse_os_release(&text))
+    } else if cfg!(target_os = "macos") {
+        probe("sw_vers", &["-productVersion"], Path::new("/"))
+            .map(|version| format!("macOS {version}"))
+    } else {
+        None
+    };
+    (version, kernel)
+}
+
+fn parse_os_release(text: &str) -> Option<String> {
+    text.lines()
+        .find_map(|line| line.strip_prefix("PRETTY_NAME="))
+        .map(|value| value.trim().trim_matches('"').to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn cpu_model() -> Option<String> {
+    if cfg!(target_os = "linux") {
+        let text = std::fs::read_to_string("/proc/cpuinfo").ok()?;
+        text.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            matches!(key.trim(), "model name" | "Model" | "Hardware")
+                .then(|| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        })
+    } else if cfg!(target_os = "macos") {
+        probe(
+            "sysctl",
+            &["-n", "machdep.cpu.brand_string"],
+            Path::new("/"),
+        )
+    } else {
+        None
+    }
+}
+
+fn memory_info() -> Option<MemoryInfo> {
+    if cfg!(target_os = "linux") {
+        parse_meminfo(&std::fs::read_to_string("/proc/meminfo").ok()?)
+    } else if cfg!(target_os = "macos") {
+        let total = probe("sysctl", &["-n", "hw.memsize"], Path::new("/"))?;
+        Some(MemoryInfo {
+            total_bytes: total.parse().ok()?,
+            available_bytes: None,
+        })
+    } else {
+        None
+    }
+}
+
+/// Parses `MemTotal`/`MemAvailable` (in kB) from `/proc/meminfo`.
+fn parse_meminfo(text: &str) -> Option<MemoryInfo> {
+    let field = |name: &str| -> Option<u64> {
+        text.lines().find_map(|line| {
+            let rest = line.strip_prefix(name)?.strip_prefix(':')?;
+            let kb: u64 = rest.trim().trim_end_matches("kB").trim().parse().ok()?;
+            Some(kb * 1024)
+        })
+    };
+    Some(MemoryInfo {
+        total_bytes: field("MemTotal")?,
+        available_bytes: field("MemAvailable"),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn parses_git_status_headers_and_changes() {
+        let text = "# branch.oid 0123456789abcdef0123\n# branch.head main\n# branch.upstream origin/main\n# branch.ab +2 -1\n1 .M N... 100644 100644 100644 a b src/lib.rs\n? new.txt\n";
+        let state = parse_git_status(text);
+        assert_eq!(state.branch.as_deref(), Some("main"));
+        assert_eq!(state.commit.as_deref(), Some("0123456789ab"));
+        assert_eq!(state.upstream.as_deref(), Some("origin/main"));
+        assert_eq!((state.ahead, state.behind), (2, 1));
+        assert_eq!(state.changed_files, 2);
+
+        let fresh = parse_git_status("# branch.oid (initial)\n# branch.head (detached)\n");
+        assert_eq!(fresh, GitState::default());
+    }
+
+    #[test]
+    fn parses_meminfo_and_os_release() {
+        let meminfo =
+            "MemTotal:       16384000 kB\nMemFree:  100 kB\nMemAvailable:    8192000 kB\n";
+        assert_eq!(
+            parse_meminfo(meminfo),
+            Some(MemoryInfo {
+                total_bytes: 16_384_000 * 1024,
+                available_bytes: Some(8_192_000 * 1024),
+            })
+        );
+        let os_release = "NAME=\"Ubuntu\"\nPRETTY_NAME=\"Ubuntu 24.04 LTS\"\n";
+        assert_eq!(
+            parse_os_release(os_release).as_deref(),
+            Some("Ubuntu 24.04 LTS")
+        );
+    }
+
+    #[test]
+    fn detects_toolchains_from_root_markers() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("go.mod"), "module x\n").unwrap();
+        let snapshot = snapshot(temp.path());
+        let names: Vec<_> = snapshot
+            .toolchains
+            .iter()
+            .map(|t| t.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["go"]);
+        assert_eq!(snapshot.toolchains[0].detected_by, "go.mod");
+        assert!(snapshot.cpu.cores >= 1);
+        assert!(snapshot.to_text().contains("Toolchains\n  go"));
+    }
+}
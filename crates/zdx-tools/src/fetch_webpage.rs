@@ -231,12 +231,12 @@ pub async fn execute(input: &Value, _ctx: &ToolContext) -> ToolOutput {
         );
     }
 
-    // Build successful response
-    ToolOutput::success(json!({
+    // Build successful response (wrapped as untrusted content)
+    ToolOutput::success(super::web_guard::envelope(json!({
         "extract_id": extract_response.extract_id,
         "results": extract_response.results,
         "warnings": extract_response.warnings
-    }))
+    })))
 }
 
 #[cfg(test)]
@@ -5,11 +5,17 @@
 
 pub mod apply_patch;
 pub mod bash;
+pub mod bash_limits;
 pub mod edit;
+pub mod env_info;
 pub mod fetch_webpage;
 pub mod glob;
 pub mod grep;
+pub mod notebook;
+pub mod outline;
 pub mod read;
+pub mod shell_session;
+pub mod web_guard;
 pub mod web_search;
 pub mod write;
 
@@ -33,11 +39,23 @@ pub struct ToolContext {
     pub root: PathBuf,
     /// Optional timeout for tool execution.
     pub timeout: Option<Duration>,
+    /// Resource limits for bash tool commands.
+    pub bash_limits: bash_limits::BashLimits,
 }
 
 impl ToolContext {
     pub fn new(root: PathBuf, timeout: Option<Duration>) -> Self {
-        Self { root, timeout }
+        Self {
+            root,
+            timeout,
+            bash_limits: bash_limits::BashLimits::default(),
+        }
+    }
+
+    #[must_use]
+    pub fn with_bash_limits(mut self, limits: bash_limits::BashLimits) -> Self {
+        self.bash_limits = limits;
+        self
     }
 }
 
@@ -331,24 +349,36 @@ pub fn expand_env_vars(input: &str) -> String {
 
 /// Expand a leading `~` or `~/` in `path` to `$HOME` when available.
 ///
-/// Returns the unmodified path if it does not start with `~`, if `HOME` is
-/// not set, or if the leading `~` is followed by characters other than `/`
-/// (for example `~user/foo`, which is not supported here).
+/// Falls back to `%USERPROFILE%` when `HOME` is unset and also accepts `~\`
+/// on Windows. Returns the unmodified path if it does not start with `~`, if
+/// no home directory is known, or if the leading `~` is followed by other
+/// characters (for example `~user/foo`, which is not supported here).
 #[must_use]
 pub fn expand_tilde(path: &Path) -> PathBuf {
     let Some(s) = path.to_str() else {
         return path.to_path_buf();
     };
-    if s != "~" && !s.starts_with("~/") {
+    let rest = if s == "~" {
+        ""
+    } else if let Some(rest) = s
+        .strip_prefix("~/")
+        .or_else(|| s.strip_prefix("~\\").filter(|_| cfg!(windows)))
+    {
+        rest
+    } else {
         return path.to_path_buf();
-    }
-    let Ok(home) = std::env::var("HOME") else {
+    };
+    let Some(home) = ["HOME", "USERPROFILE"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .find(|home| !home.is_empty())
+    else {
         return path.to_path_buf();
     };
-    if s == "~" {
+    if rest.is_empty() {
         return PathBuf::from(home);
     }
-    PathBuf::from(home).join(&s[2..])
+    PathBuf::from(home).join(rest)
 }
 
 /// Resolve a path against the tool root after expanding environment variables
@@ -0,0 +1,697 @@
+//! Jupyter notebook tools.
+//!
+//! `Notebook_Read` returns a notebook's cells with their indices, types,
+//! sources, and outputs (image outputs attached as image blocks).
+//! `Notebook_Edit` replaces, inserts, or deletes a single cell by index while
+//! leaving notebook and cell metadata untouched, so the model never edits raw
+//! `.ipynb` JSON by hand.
+
+use std::fs;
+use std::path::Path;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value, json};
+
+use super::{
+    ImageContent, ToolContext, ToolDefinition, ToolOutput, insert_file_path_fields,
+    resolve_existing_path,
+};
+
+/// Maximum bytes of text kept per cell output.
+const MAX_OUTPUT_BYTES: usize = 2000;
+
+/// Maximum bytes of cell sources and outputs returned per read.
+const MAX_READ_BYTES: usize = 40 * 1024;
+
+/// Maximum number of image outputs attached per read.
+const MAX_IMAGES: usize = 5;
+
+/// Image outputs larger than this (decoded estimate) are not attached.
+const MAX_IMAGE_BYTES: usize = 3_932_160;
+
+/// Image MIME types attached from `display_data` / `execute_result` outputs.
+const IMAGE_MIME_TYPES: [&str; 4] = ["image/png", "image/jpeg", "image/gif", "image/webp"];
+
+static ANSI_ESCAPE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\x1b\[[0-9;]*[A-Za-z]").expect("valid ANSI regex"));
+
+/// Returns the tool definition for the notebook read tool.
+pub fn read_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "Notebook_Read".to_string(),
+        description: "Read a Jupyter notebook (.ipynb). Returns `cells` with `index` (0-based), `cell_type`, `source`, and for code cells `execution_count` and `outputs` (stream text, results, errors with tracebacks). Image outputs are attached as images. Use this instead of Read for notebooks, and pass `cell_index` to read a single cell.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Path to the .ipynb file. Relative paths resolve from the current working directory. Supports $VAR/${VAR} env vars."
+                },
+                "cell_index": {
+                    "type": "integer",
+                    "description": "Only return the cell at this 0-based index."
+                }
+            },
+            "required": ["file_path"],
+            "additionalProperties": false
+        }),
+    }
+}
+
+/// Returns the tool definition for the notebook edit tool.
+pub fn edit_definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "Notebook_Edit".to_string(),
+        description: "Edit one cell of a Jupyter notebook (.ipynb) by 0-based index. `edit_mode` is `replace` (default; replaces the cell source, and `cell_type` may change its type), `insert` (inserts a new cell at `cell_index`, shifting later cells; `cell_type` required; use the cell count to append), or `delete`. Replacing a code cell clears its outputs and execution count. Notebook and cell metadata are preserved. Read the notebook with Notebook_Read first. Never edit .ipynb JSON with Edit or Write.".to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Path to the .ipynb file. Relative paths resolve from the current working directory. Supports $VAR/${VAR} env vars."
+                },
+                "cell_index": {
+                    "type": "integer",
+                    "description": "0-based index of the cell to replace or delete, or the position to insert at."
+                },
+                "edit_mode": {
+                    "type": "string",
+                    "enum": ["replace", "insert", "delete"],
+                    "description": "Edit to apply (default: replace)."
+                },
+                "new_source": {
+                    "type": "string",
+                    "description": "New cell source. Required for replace and insert."
+                },
+                "cell_type": {
+                    "type": "string",
+                    "enum": ["code", "markdown"],
+                    "description": "Cell type. Required for insert; optional for replace."
+                }
+            },
+            "required": ["file_path", "cell_index"],
+            "additionalProperties": false
+        }),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NotebookReadInput {
+    file_path: String,
+    #[serde(default)]
+    cell_index: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum EditMode {
+    #[default]
+    Replace,
+    Insert,
+    Delete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum CellType {
+    Code,
+    Markdown,
+}
+
+impl CellType {
+    fn as_str(self) -> &'static str {
+        match self {
+            CellType::Code => "code",
+            CellType::Markdown => "markdown",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NotebookEditInput {
+    file_path: String,
+    cell_index: usize,
+    #[serde(default)]
+    edit_mode: EditMode,
+    #[serde(default)]
+    new_source: Option<String>,
+    #[serde(default)]
+    cell_type: Option<CellType>,
+}
+
+/// A loaded notebook plus the indentation to write it back with.
+struct Notebook {
+    root: Map<String, Value>,
+    indent: usize,
+}
+
+impl Notebook {
+    fn load(path: &Path) -> Result<Self, ToolOutput> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            ToolOutput::failure(
+                "read_error",
+                format!("Failed to read notebook '{}'", path.display()),
+                Some(format!("OS error: {e}")),
+            )
+        })?;
+        let root = match serde_json::from_str::<Value>(&text) {
+            Ok(Value::Object(root)) if root.get("cells").is_some_and(Value::is_array) => root,
+            Ok(_) => {
+                return Err(ToolOutput::failure(
+                    "invalid_notebook",
+                    "File is not a Jupyter notebook (missing `cells` array)",
+                    None,
+                ));
+            }
+            Err(e) => {
+                return Err(ToolOutput::failure(
+                    "invalid_notebook",
+                    "Notebook is not valid JSON",
+                    Some(e.to_string()),
+                ));
+            }
+        };
+        Ok(Self {
+            root,
+            indent: detect_indent(&text),
+        })
+    }
+
+    fn cells(&self) -> &[Value] {
+        self.root
+            .get("cells")
+            .and_then(Value::as_array)
+            .map_or(&[], Vec::as_slice)
+    }
+
+    fn cells_mut(&mut self) -> &mut Vec<Value> {
+        match self.root.get_mut("cells") {
+            Some(Value::Array(cells)) => cells,
+            _ => unreachable!("load() checks that `cells` is an array"),
+        }
+    }
+
+    /// Whether cells carry ids (nbformat 4.5+).
+    fn uses_cell_ids(&self) -> bool {
+        let minor = self.root.get("nbformat_minor").and_then(Value::as_u64);
+        let major = self.root.get("nbformat").and_then(Value::as_u64);
+        matches!((major, minor), (Some(4), Some(m)) if m >= 5)
+            || self.cells().iter().any(|cell| cell.get("id").is_some())
+    }
+
+    fn language(&self) -> Option<&str> {
+        let metadata = self.root.get("metadata")?;
+        metadata
+            .pointer("/language_info/name")
+            .or_else(|| metadata.pointer("/kernelspec/language"))
+            .and_then(Value::as_str)
+    }
+
+    /// Writes the notebook back the way Jupyter does: keys in their loaded
+    /// order, `indent`-space indentation, and a trailing newline.
+    fn save(&self, path: &Path) -> Result<(), ToolOutput> {
+        let indent = " ".repeat(self.indent);
+        let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+        let mut out = Vec::new();
+        let mut serializer = serde_json::Serializer::with_formatter(&mut out, formatter);
+        self.root.serialize(&mut serializer).map_err(|e| {
+            ToolOutput::failure(
+                "write_error",
+                "Failed to serialize notebook",
+                Some(e.to_string()),
+            )
+        })?;
+        out.push(b'\n');
+        fs::write(path, out).map_err(|e| {
+            ToolOutput::failure(
+                "write_error",
+                format!("Failed to write notebook '{}'", path.display()),
+                Some(format!("OS error: {e}")),
+            )
+        })
+    }
+}
+
+/// Indentation width of the first nested line (Jupyter writes 1 space).
+fn detect_indent(text: &str) -> usize {
+    text.lines()
+        .nth(1)
+        .map(|line| line.len() - line.trim_start_matches(' ').len())
+        .filter(|&n| n > 0)
+        .unwrap_or(1)
+}
+
+/// Joins a multiline notebook string (string or list of lines).
+fn multiline_text(value: Option<&Value>) -> String {
+    match value {
+        Some(Value::String(s)) => s.clone(),
+        Some(Value::Array(parts)) => parts.iter().filter_map(Value::as_str).collect(),
+        _ => String::new(),
+    }
+}
+
+/// Splits a source into nbformat's list-of-lines form (newlines kept).
+fn source_lines(source: &str) -> Value {
+    Value::Array(
+        source
+            .split_inclusive('\n')
+            .map(|line| Value::String(line.to_string()))
+            .collect(),
+    )
+}
+
+fn capped(text: &str, max_bytes: usize) -> String {
+    let (mut text, truncated) = super::truncate_str_to_byte_limit(text, max_bytes);
+    if truncated {
+        text.push_str("\n[output truncated]");
+    }
+    text
+}
+
+/// Summarizes one cell output, collecting attachable images into `images`.
+fn summarize_output(output: &Value, images: &mut Vec<ImageContent>) -> Value {
+    let output_type = output
+        .get("output_type")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown");
+    let mut summary = Map::new();
+    summary.insert("output_type".to_string(), Value::from(output_type));
+
+    match output_type {
+        "stream" => {
+            if let Some(name) = output.get("name") {
+                summary.insert("name".to_string(), name.clone());
+            }
+            let text = multiline_text(output.get("text"));
+            summary.insert(
+                "text".to_string(),
+                Value::String(capped(&text, MAX_OUTPUT_BYTES)),
+            );
+        }
+        "error" => {
+            for key in ["ename", "evalue"] {
+                if let Some(value) = output.get(key) {
+                    summary.insert(key.to_string(), value.clone());
+                }
+            }
+            let traceback = output
+                .get("traceback")
+                .and_then(Value::as_array)
+                .map(|lines| {
+                    lines
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .unwrap_or_default();
+            let traceback = ANSI_ESCAPE.replace_all(&traceback, "");
+            summary.insert(
+                "traceback".to_string(),
+                Value::String(capped(&traceback, MAX_OUTPUT_BYTES)),
+            );
+        }
+        _ => {
+            let data = output.get("data");
+            if let Some(text) = data.and_then(|d| d.get("text/plain")) {
+                summary.insert(
+                    "text".to_string(),
+                    Value::String(capped(&multiline_text(Some(text)), MAX_OUTPUT_BYTES)),
+                );
+            }
+            for mime_type in IMAGE_MIME_TYPES {
+                let Some(encoded) = data.and_then(|d| d.get(mime_type)) else {
+                    continue;
+                };
+                let encoded: String = multiline_text(Some(encoded))
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .collect();
+                let attached =
+                    images.len() < MAX_IMAGES && encoded.len() / 4 * 3 <= MAX_IMAGE_BYTES;
+                if attached {
+                    images.push(ImageContent {
+                        mime_type: mime_type.to_string(),
+                        data: encoded,
+                    });
+                }
+                summary.insert(
+                    "image".to_string(),
+                    json!({ "mime_type": mime_type, "attached": attached }),
+                );
+                break;
+            }
+        }
+    }
+    Value::Object(summary)
+}
+
+fn summarize_cell(index: usize, cell: &Value, images: &mut Vec<ImageContent>) -> Value {
+    let mut summary = Map::new();
+    summary.insert("index".to_string(), Value::from(index));
+    summary.insert(
+        "cell_type".to_string(),
+        cell.get("cell_type").cloned().unwrap_or(Value::Null),
+    );
+    if let Some(id) = cell.get("id") {
+        summary.insert("id".to_string(), id.clone());
+    }
+    summary.insert(
+        "source".to_string(),
+        Value::String(multiline_text(cell.get("source"))),
+    );
+    if cell.get("cell_type").and_then(Value::as_str) == Some("code") {
+        summary.insert(
+            "execution_count".to_string(),
+            cell.get("execution_count").cloned().unwrap_or(Value::Null),
+        );
+        let outputs = cell
+            .get("outputs")
+            .and_then(Value::as_array)
+            .map(|outputs| {
+                outputs
+                    .iter()
+                    .map(|output| summarize_output(output, images))
+                    .collect()
+            })
+            .unwrap_or_default();
+        summary.insert("outputs".to_string(), Value::Array(outputs));
+    }
+    Value::Object(summary)
+}
+
+fn resolve_notebook(file_path: &str, root: &Path) -> Result<super::ResolvedPath, ToolOutput> {
+    if file_path.trim().is_empty() {
+        return Err(ToolOutput::failure(
+            "invalid_input",
+            "file_path cannot be empty",
+            None,
+        ));
+    }
+    let resolved = resolve_existing_path(file_path, root)?;
+    let is_ipynb = resolved
+        .resolved_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("ipynb"));
+    if !is_ipynb {
+        return Err(ToolOutput::failure(
+            "invalid_input",
+            "file_path must be a .ipynb notebook",
+            Some("Use Read/Edit for other files".to_string()),
+        ));
+    }
+    Ok(resolved)
+}
+
+/// Executes the notebook read tool.
+pub fn execute_read(input: &Value, ctx: &ToolContext) -> ToolOutput {
+    let input: NotebookReadInput = match super::parse_tool_input(input, "notebook_read") {
+        Ok(i) => i,
+        Err(out) => return out,
+    };
+    let resolved = match resolve_notebook(&input.file_path, &ctx.root) {
+        Ok(r) => r,
+        Err(out) => return out,
+    };
+    let notebook = match Notebook::load(&resolved.resolved_path) {
+        Ok(nb) => nb,
+        Err(out) => return out,
+    };
+
+    let cells = notebook.cells();
+    let range = match input.cell_index {
+        Some(index) if index >= cells.len() => {
+            return ToolOutput::failure(
+                "invalid_input",
+                format!(
+                    "cell_index {index} is out of range (notebook has {} cells)",
+                    cells.len()
+                ),
+                None,
+            );
+        }
+        Some(index) => index..index + 1,
+        None => 0..cells.len(),
+    };
+
+    let mut images = Vec::new();
+    let mut summaries = Vec::new();
+    let mut used_bytes = 0;
+    let mut truncated = false;
+    for index in range {
+        let summary = summarize_cell(index, &cells[index], &mut images);
+        used_bytes += summary.to_string().len();
+        if used_bytes > MAX_READ_BYTES && !summaries.is_empty() {
+            truncated = true;
+            break;
+        }
+        summaries.push(summary);
+    }
+
+    let mut data = Map::new();
+    insert_file_path_fields(&mut data, &resolved.path, Some(&resolved.resolved_path));
+    if let Some(language) = notebook.language() {
+        data.insert("language".to_string(), Value::from(language));
+    }
+    data.insert("cell_count".to_string(), Value::from(cells.len()));
+    data.insert("cells".to_string(), Value::Array(summaries));
+    data.insert("truncated".to_string(), Value::Bool(truncated));
+    if truncated {
+        data.insert(
+            "warning".to_string(),
+            Value::from("Output limit reached; read remaining cells with cell_index"),
+        );
+    }
+
+    ToolOutput::success_with_images(Value::Object(data), images)
+}
+
+fn new_cell(cell_type: CellType, source: &str, with_id: bool) -> Value {
+    let mut cell = Map::new();
+    cell.insert("cell_type".to_string(), Value::from(cell_type.as_str()));
+    if cell_type == CellType::Code {
+        cell.insert("execution_count".to_string(), Value::Null);
+    }
+    if with_id {
+        let id = uuid::Uuid::new_v4().simple().to_string();
+        cell.insert("id".to_string(), Value::String(id[..8].to_string()));
+    }
+    cell.insert("metadata".to_string(), json!({}));
+    if cell_type == CellType::Code {
+        cell.insert("outputs".to_string(), json!([]));
+    }
+    cell.insert("source".to_string(), source_lines(source));
+    Value::Object(cell)
+}
+
+/// Replaces a cell's source (and optionally type), keeping its id and metadata.
+fn replace_cell(cell: &mut Value, source: &str, cell_type: Option<CellType>) {
+    let Some(cell) = cell.as_object_mut() else {
+        return;
+    };
+    if let Some(cell_type) = cell_type {
+        cell.insert("cell_type".to_string(), Value::from(cell_type.as_str()));
+    }
+    let is_code = cell.get("cell_type").and_then(Value::as_str) == Some("code");
+    if is_code {
+        cell.insert("execution_count".to_string(), Value::Null);
+        cell.insert("outputs".to_string(), json!([]));
+    } else {
+        cell.remove("execution_count");
+        cell.remove("outputs");
+    }
+    cell.insert("source".to_string(), source_lines(source));
+}
+
+/// Executes the notebook edit tool.
+pub fn execute_edit(input: &Value, ctx: &ToolContext) -> ToolOutput {
+    let input: NotebookEditInput = match super::parse_tool_input(input, "notebook_edit") {
+        Ok(i) => i,
+        Err(out) => return out,
+    };
+    let resolved = match resolve_notebook(&input.file_path, &ctx.root) {
+        Ok(r) => r,
+        Err(out) => return out,
+    };
+    let mut notebook = match Notebook::load(&resolved.resolved_path) {
+        Ok(nb) => nb,
+        Err(out) => return out,
+    };
+
+    let cell_count = notebook.cells().len();
+    let index = input.cell_index;
+    let in_range = match input.edit_mode {
+        EditMode::Insert => index <= cell_count,
+        EditMode::Replace | EditMode::Delete => index < cell_count,
+    };
+    if !in_range {
+        return ToolOutput::failure(
+            "invalid_input",
+            format!("cell_index {index} is out of range (notebook has {cell_count} cells)"),
+            None,
+        );
+    }
+
+    let source = input.new_source.as_deref();
+    match (input.edit_mode, source, input.cell_type) {
+        (EditMode::Replace, Some(source), cell_type) => {
+            replace_cell(&mut notebook.cells_mut()[index], source, cell_type);
+        }
+        (EditMode::Insert, Some(source), Some(cell_type)) => {
+            let cell = new_cell(cell_type, source, notebook.uses_cell_ids());
+            notebook.cells_mut().insert(index, cell);
+        }
+        (EditMode::Insert, Some(_), None) => {
+            return ToolOutput::failure("invalid_input", "cell_type is required for insert", None);
+        }
+        (EditMode::Delete, _, _) => {
+            notebook.cells_mut().remove(index);
+        }
+        (EditMode::Replace | EditMode::Insert, None, _) => {
+            return ToolOutput::failure(
+                "invalid_input",
+                "new_source is required for replace and insert",
+                None,
+            );
+        }
+    }
+
+    if let Err(out) = notebook.save(&resolved.resolved_path) {
+        return out;
+    }
+
+    let mut data = Map::new();
+    insert_file_path_fields(&mut data, &resolved.path, Some(&resolved.resolved_path));
+    data.insert("edit_mode".to_string(), json!(input.edit_mode));
+    data.insert("cell_index".to_string(), Value::from(index));
+    if let Some(cell_type) = notebook
+        .cells()
+        .get(index)
+        .filter(|_| input.edit_mode != EditMode::Delete)
+        .and_then(|cell| cell.get("cell_type"))
+    {
+        data.insert("cell_type".to_string(), cell_type.clone());
+    }
+    data.insert(
+        "cell_count".to_string(),
+        Value::from(notebook.cells().len()),
+    );
+    ToolOutput::success(Value::Object(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const NOTEBOOK: &str = r##"{
+ "cells": [
+  {
+   "cell_type": "markdown",
+   "id": "aaaa0001",
+   "metadata": {"tags": ["intro"]},
+   "source": ["# Title\n", "text"]
+  },
+  {
+   "cell_type": "code",
+   "execution_count": 3,
+   "id": "aaaa0002",
+   "metadata": {"scrolled": true},
+   "outputs": [
+    {"name": "stdout", "output_type": "stream", "text": ["hi\n"]},
+    {"data": {"image/png": "iVBORw0KGgo=\n", "text/plain": ["<Figure>"]}, "metadata": {}, "output_type": "display_data"},
+    {"ename": "ValueError", "evalue": "bad", "output_type": "error", "traceback": ["\u001b[0;31mValueError\u001b[0m: bad"]}
+   ],
+   "source": "print('hi')"
+  }
+ ],
+ "metadata": {"kernelspec": {"language": "python", "name": "python3"}},
+ "nbformat": 4,
+ "nbformat_minor": 5
+}
+"##;
+
+    fn setup() -> (TempDir, ToolContext) {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("nb.ipynb"), NOTEBOOK).unwrap();
+        let ctx = ToolContext::new(temp.path().to_path_buf(), None);
+        (temp, ctx)
+    }
+
+    #[test]
+    fn test_read_returns_cells_outputs_and_images() {
+        let (_temp, ctx) = setup();
+        let result = execute_read(&json!({"file_path": "nb.ipynb"}), &ctx);
+        let data = result.data().unwrap();
+        assert_eq!(data["language"], "python");
+        assert_eq!(data["cell_count"], 2);
+        assert_eq!(data["cells"][0]["source"], "# Title\ntext");
+        let outputs = &data["cells"][1]["outputs"];
+        assert_eq!(outputs[0]["text"], "hi\n");
+        assert_eq!(outputs[1]["image"]["attached"], true);
+        assert_eq!(outputs[2]["traceback"], "ValueError: bad");
+        assert_eq!(result.images().len(), 1);
+        assert_eq!(result.images()[0].data, "iVBORw0KGgo=");
+    }
+
+    #[test]
+    fn test_edit_replace_insert_delete_preserves_metadata() {
+        let (temp, ctx) = setup();
+        let path = temp.path().join("nb.ipynb");
+
+        let out = execute_edit(
+            &json!({"file_path": "nb.ipynb", "cell_index": 1, "new_source": "x = 1\ny = 2"}),
+            &ctx,
+        );
+        assert!(out.is_ok(), "{out:?}");
+        let nb: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let cell = &nb["cells"][1];
+        assert_eq!(cell["source"], json!(["x = 1\n", "y = 2"]));
+        assert_eq!(cell["outputs"], json!([]));
+        assert_eq!(cell["execution_count"], Value::Null);
+        assert_eq!(cell["metadata"]["scrolled"], true);
+        assert_eq!(cell["id"], "aaaa0002");
+
+        let out = execute_edit(
+            &json!({"file_path": "nb.ipynb", "cell_index": 0, "edit_mode": "insert", "cell_type": "code", "new_source": "import os"}),
+            &ctx,
+        );
+        assert_eq!(out.data().unwrap()["cell_count"], 3);
+        let nb: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(nb["cells"][0]["cell_type"], "code");
+        assert_eq!(nb["cells"][0]["id"].as_str().unwrap().len(), 8);
+        assert_eq!(nb["cells"][1]["metadata"]["tags"], json!(["intro"]));
+
+        let out = execute_edit(
+            &json!({"file_path": "nb.ipynb", "cell_index": 0, "edit_mode": "delete"}),
+            &ctx,
+        );
+        assert_eq!(out.data().unwrap()["cell_count"], 2);
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.starts_with("{\n \"cells\": [\n  {\n   \"cell_type\": \"markdown\""));
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap()["metadata"]["kernelspec"]["name"],
+            "python3"
+        );
+    }
+
+    #[test]
+    fn test_edit_rejects_out_of_range_and_missing_fields() {
+        let (_temp, ctx) = setup();
+        let out = execute_edit(
+            &json!({"file_path": "nb.ipynb", "cell_index": 2, "new_source": "x"}),
+            &ctx,
+        );
+        assert_eq!(out.error_info().unwrap().0, "invalid_input");
+        let out = execute_edit(
+            &json!({"file_path": "nb.ipynb", "cell_index": 2, "edit_mode": "insert", "new_source": "x"}),
+            &ctx,
+        );
+        assert!(out.error_info().unwrap().1.contains("cell_type"));
+    }
+}
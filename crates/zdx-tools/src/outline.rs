@@ -0,0 +1,425 @@
+//! Outline tool for structural file summaries.
+//!
+//! Parses a source file with a bundled tree-sitter grammar and returns its
+//! symbol tree (functions, types, impls, classes) with 1-based line ranges, so
+//! the model can target `Read` offsets and edits without reading whole files.
+//! Each grammar is behind an `outline-<language>` cargo feature.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_json::{Map, Value, json};
+use tree_sitter::{Language, Node, Parser};
+
+use super::{
+    ToolContext, ToolDefinition, ToolOutput, insert_file_path_fields, resolve_existing_path,
+};
+
+/// Maximum file size to parse (larger files are usually generated code).
+pub(crate) const MAX_FILE_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Maximum number of symbols to return (prevents context flooding).
+const MAX_SYMBOLS: usize = 1000;
+
+/// Maximum length of the one-line signature attached to each symbol.
+const MAX_SIGNATURE_CHARS: usize = 160;
+
+/// Returns the tool definition for the outline tool.
+pub fn definition() -> ToolDefinition {
+    ToolDefinition {
+        name: "Outline".to_string(),
+        description:
+            "Return the symbol tree of a source file (functions, methods, structs, enums, traits, impls, classes, interfaces, types) with 1-based start/end line ranges and a one-line signature. Use Outline before Read on large files to find the exact region to read or edit, then Read with offset/limit. Supports Rust, Python, TypeScript/TSX, JavaScript and Go (depending on build features)."
+                .to_string(),
+        input_schema: json!({
+            "type": "object",
+            "properties": {
+                "file_path": {
+                    "type": "string",
+                    "description": "Path to the source file. Relative paths resolve from the current working directory. Supports $VAR/${VAR} env vars."
+                }
+            },
+            "required": ["file_path"],
+            "additionalProperties": false
+        }),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OutlineInput {
+    file_path: String,
+}
+
+/// A symbol in the outline, with nested children (e.g. methods in an impl).
+#[derive(Debug)]
+struct Symbol {
+    name: String,
+    kind: &'static str,
+    start_line: usize,
+    end_line: usize,
+    signature: String,
+    children: Vec<Symbol>,
+}
+
+impl Symbol {
+    fn to_json(&self) -> Value {
+        let mut value = json!({
+            "name": self.name,
+            "kind": self.kind,
+            "start_line": self.start_line,
+            "end_line": self.end_line,
+            "signature": self.signature,
+        });
+        if !self.children.is_empty() {
+            value["children"] = Value::Array(self.children.iter().map(Self::to_json).collect());
+        }
+        value
+    }
+}
+
+/// Returns the language name and grammar for a file extension, if bundled.
+pub(crate) fn language_for(path: &Path) -> Option<(&'static str, Language)> {
+    let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+    match ext.as_str() {
+        #[cfg(feature = "outline-rust")]
+        "rs" => Some(("rust", tree_sitter_rust::LANGUAGE.into())),
+        #[cfg(feature = "outline-python")]
+        "py" | "pyi" => Some(("python", tree_sitter_python::LANGUAGE.into())),
+        #[cfg(feature = "outline-typescript")]
+        "ts" | "mts" | "cts" => Some((
+            "typescript",
+            tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
+        )),
+        #[cfg(feature = "outline-typescript")]
+        "tsx" => Some(("tsx", tree_sitter_typescript::LANGUAGE_TSX.into())),
+        #[cfg(feature = "outline-javascript")]
+        "js" | "jsx" | "mjs" | "cjs" => {
+            Some(("javascript", tree_sitter_javascript::LANGUAGE.into()))
+        }
+        #[cfg(feature = "outline-go")]
+        "go" => Some(("go", tree_sitter_go::LANGUAGE.into())),
+        _ => None,
+    }
+}
+
+/// File extensions with a grammar compiled into this build.
+fn supported_extensions() -> Vec<&'static str> {
+    let mut exts = Vec::new();
+    if cfg!(feature = "outline-rust") {
+        exts.push(".rs");
+    }
+    if cfg!(feature = "outline-python") {
+        exts.extend([".py", ".pyi"]);
+    }
+    if cfg!(feature = "outline-typescript") {
+        exts.extend([".ts", ".tsx", ".mts", ".cts"]);
+    }
+    if cfg!(feature = "outline-javascript") {
+        exts.extend([".js", ".jsx", ".mjs", ".cjs"]);
+    }
+    if cfg!(feature = "outline-go") {
+        exts.push(".go");
+    }
+    exts
+}
+
+/// Maps a tree-sitter node kind to an outline symbol kind.
+///
+/// Node kind names are distinct enough across the bundled grammars that a
+/// single table covers all of them.
+fn symbol_kind(node_kind: &str) -> Option<&'static str> {
+    Some(match node_kind {
+        "function_item"
+        | "function_signature_item"
+        | "function_definition"
+        | "function_declaration"
+        | "generator_function_declaration"
+        | "variable_declarator" => "function",
+        "method_definition" | "method_declaration" | "method_signature" => "method",
+        "struct_item" => "struct",
+        "enum_item" | "enum_declaration" => "enum",
+        "union_item" => "union",
+        "trait_item" => "trait",
+        "impl_item" => "impl",
+        "mod_item" | "internal_module" => "module",
+        "type_item" | "type_alias_declaration" | "type_spec" => "type",
+        "const_item" => "const",
+        "static_item" => "static",
+        "macro_definition" => "macro",
+        "class_definition" | "class_declaration" | "abstract_class_declaration" => "class",
+        "interface_declaration" => "interface",
+        _ => return None,
+    })
+}
+
+fn node_text<'a>(node: Node<'_>, source: &'a [u8]) -> &'a str {
+    node.utf8_text(source).unwrap_or_default()
+}
+
+/// Resolves the display name for a symbol node.
+fn symbol_name(node: Node<'_>, source: &[u8]) -> Option<String> {
+    match node.kind() {
+        "impl_item" => {
+            let ty = node_text(node.child_by_field_name("type")?, source);
+            Some(match node.child_by_field_name("trait") {
+                Some(tr) => format!("{} for {ty}", node_text(tr, source)),
+                None => ty.to_string(),
+            })
+        }
+        // Only `const foo = () => {}` style declarators count as functions.
+        "variable_declarator" => {
+            let value = node.child_by_field_name("value")?;
+            if !matches!(
+                value.kind(),
+                "arrow_function" | "function_expression" | "function" | "generator_function"
+            ) {
+                return None;
+            }
+            Some(node_text(node.child_by_field_name("name")?, source).to_string())
+        }
+        _ => Some(node_text(node.child_by_field_name("name")?, source).to_string()),
+    }
+}
+
+/// First line of the node's source, whitespace-collapsed and capped.
+fn signature(node: Node<'_>, source: &[u8]) -> String {
+    let first_line = node_text(node, source).lines().next().unwrap_or_default();
+    let collapsed = first_line.split_whitespace().collect::<Vec<_>>().join(" ");
+    let trimmed = collapsed.trim_end_matches('{').trim_end();
+    if trimmed.chars().count() > MAX_SIGNATURE_CHARS {
+        let cut: String = trimmed.chars().take(MAX_SIGNATURE_CHARS).collect();
+        format!("{cut}…")
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Recursively collects symbols under `node`.
+///
+/// Function bodies are not descended into, so local helpers and closures do
+/// not clutter the outline.
+fn collect_symbols(
+    node: Node<'_>,
+    source: &[u8],
+    parent_kind: Option<&'static str>,
+    count: &mut usize,
+) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    let mut cursor = node.walk();
+    for child in node.named_children(&mut cursor) {
+        if *count >= MAX_SYMBOLS {
+            break;
+        }
+        let Some(mut kind) = symbol_kind(child.kind()) else {
+            symbols.extend(collect_symbols(child, source, parent_kind, count));
+            continue;
+        };
+        let Some(name) = symbol_name(child, source) else {
+            symbols.extend(collect_symbols(child, source, parent_kind, count));
+            continue;
+        };
+        if kind == "function" && matches!(parent_kind, Some("impl" | "trait" | "class")) {
+            kind = "method";
+        }
+        *count += 1;
+        let children = if matches!(kind, "function" | "method") {
+            Vec::new()
+        } else {
+            collect_symbols(child, source, Some(kind), count)
+        };
+        symbols.push(Symbol {
+            name,
+            kind,
+            start_line: child.start_position().row + 1,
+            end_line: child.end_position().row + 1,
+            signature: signature(child, source),
+            children,
+        });
+    }
+    symbols
+}
+
+/// Symbol tree of a parsed source file.
+pub(crate) struct FileOutline {
+    language: &'static str,
+    symbols: Vec<Symbol>,
+    pub(crate) symbol_count: usize,
+    pub(crate) has_errors: bool,
+}
+
+impl FileOutline {
+    /// Inserts `language`, `symbols`, and `symbol_count` into a result object.
+    pub(crate) fn insert_fields(&self, data: &mut Map<String, Value>) {
+        data.insert("language".to_string(), Value::from(self.language));
+        data.insert(
+            "symbols".to_string(),
+            Value::Array(self.symbols.iter().map(Symbol::to_json).collect()),
+        );
+        data.insert("symbol_count".to_string(), Value::from(self.symbol_count));
+    }
+}
+
+/// Parses `source` and collects its outline.
+pub(crate) fn parse_outline(
+    language_name: &'static str,
+    language: &Language,
+    source: &[u8],
+) -> Result<FileOutline, ToolOutput> {
+    let mut parser = Parser::new();
+    if let Err(e) = parser.set_language(language) {
+        return Err(ToolOutput::failure(
+            "parse_error",
+            format!("Failed to load {language_name} grammar: {e}"),
+            None,
+        ));
+    }
+    let Some(tree) = parser.parse(source, None) else {
+        return Err(ToolOutput::failure(
+            "parse_error",
+            "Failed to parse file",
+            None,
+        ));
+    };
+
+    let mut symbol_count = 0;
+    let symbols = collect_symbols(tree.root_node(), source, None, &mut symbol_count);
+    Ok(FileOutline {
+        language: language_name,
+        symbols,
+        symbol_count,
+        has_errors: tree.root_node().has_error(),
+    })
+}
+
+/// Executes the outline tool and returns the file's symbol tree.
+pub fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
+    let input: OutlineInput = match super::parse_tool_input(input, "outline") {
+        Ok(i) => i,
+        Err(out) => return out,
+    };
+
+    let resolved = match resolve_existing_path(&input.file_path, &ctx.root) {
+        Ok(p) => p,
+        Err(e) => return e,
+    };
+    let path = &resolved.resolved_path;
+
+    let Some((language_name, language)) = language_for(path) else {
+        return ToolOutput::failure(
+            "unsupported_language",
+            format!("No outline grammar for '{}'", input.file_path),
+            Some(format!(
+                "Supported extensions: {}",
+                supported_extensions().join(", ")
+            )),
+        );
+    };
+
+    match fs::metadata(path) {
+        Ok(meta) if meta.len() > MAX_FILE_BYTES => {
+            return ToolOutput::failure(
+                "file_too_large",
+                format!(
+                    "File is {} bytes; outline is limited to {MAX_FILE_BYTES} bytes",
+                    meta.len()
+                ),
+                None,
+            );
+        }
+        Ok(_) => {}
+        Err(e) => {
+            return ToolOutput::failure("read_error", format!("Failed to read file: {e}"), None);
+        }
+    }
+
+    let source = match fs::read(path) {
+        Ok(s) => s,
+        Err(e) => {
+            return ToolOutput::failure("read_error", format!("Failed to read file: {e}"), None);
+        }
+    };
+
+    let outline = match parse_outline(language_name, &language, &source) {
+        Ok(outline) => outline,
+        Err(out) => return out,
+    };
+    let total_lines = String::from_utf8_lossy(&source).lines().count();
+
+    let mut data = serde_json::Map::new();
+    insert_file_path_fields(&mut data, &input.file_path, Some(path));
+    data.insert("total_lines".to_string(), Value::from(total_lines));
+    outline.insert_fields(&mut data);
+    data.insert(
+        "truncated".to_string(),
+        Value::from(outline.symbol_count >= MAX_SYMBOLS),
+    );
+    if outline.has_errors {
+        data.insert(
+            "warning".to_string(),
+            Value::from("File has syntax errors; outline may be incomplete"),
+        );
+    }
+
+    ToolOutput::success(Value::Object(data))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn run(dir: &TempDir, name: &str, contents: &str) -> Value {
+        fs::write(dir.path().join(name), contents).unwrap();
+        let ctx = ToolContext::new(dir.path().to_path_buf(), None);
+        let out = execute(&json!({ "file_path": name }), &ctx);
+        serde_json::to_value(&out).unwrap()
+    }
+
+    #[cfg(feature = "outline-rust")]
+    #[test]
+    fn test_rust_outline_nests_impl_methods() {
+        let dir = TempDir::new().unwrap();
+        let out = run(
+            &dir,
+            "lib.rs",
+            "pub struct Foo;\n\nimpl Display for Foo {\n    fn fmt(&self) {\n        let inner = || {};\n    }\n}\n\nfn main() {}\n",
+        );
+        let symbols = &out["data"]["symbols"];
+        assert_eq!(symbols[0]["name"], "Foo");
+        assert_eq!(symbols[0]["kind"], "struct");
+        assert_eq!(symbols[1]["name"], "Display for Foo");
+        assert_eq!(symbols[1]["start_line"], 3);
+        assert_eq!(symbols[1]["end_line"], 7);
+        assert_eq!(symbols[1]["children"][0]["name"], "fmt");
+        assert_eq!(symbols[1]["children"][0]["kind"], "method");
+        assert_eq!(symbols[2]["signature"], "fn main() {}");
+        assert_eq!(out["data"]["symbol_count"], 4);
+    }
+
+    #[cfg(feature = "outline-python")]
+    #[test]
+    fn test_python_outline_classes_and_decorated_functions() {
+        let dir = TempDir::new().unwrap();
+        let out = run(
+            &dir,
+            "app.py",
+            "class Api:\n    def get(self):\n        pass\n\n@cache\ndef load():\n    pass\n",
+        );
+        let symbols = &out["data"]["symbols"];
+        assert_eq!(out["data"]["language"], "python");
+        assert_eq!(symbols[0]["kind"], "class");
+        assert_eq!(symbols[0]["children"][0]["kind"], "method");
+        assert_eq!(symbols[1]["name"], "load");
+        assert_eq!(symbols[1]["start_line"], 6);
+    }
+
+    #[test]
+    fn test_unsupported_extension_fails() {
+        let dir = TempDir::new().unwrap();
+        let out = run(&dir, "notes.txt", "hello\n");
+        assert_eq!(out["ok"], false);
+        assert_eq!(out["error"]["code"], "unsupported_language");
+    }
+}
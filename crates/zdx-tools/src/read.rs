@@ -49,10 +49,13 @@ where
 }
 
 use super::{
-    ImageContent, ToolContext, ToolDefinition, ToolOutput, insert_file_path_fields,
+    ImageContent, ToolContext, ToolDefinition, ToolOutput, insert_file_path_fields, outline,
     resolve_existing_path,
 };
 
+/// Source files longer than this return their outline when read without a range.
+pub const DEFAULT_OUTLINE_THRESHOLD_LINES: usize = 1000;
+
 /// Maximum number of lines to return (truncation threshold).
 const MAX_LINES: usize = 2000;
 
@@ -95,7 +98,7 @@ fn sniff_image_mime(data: &[u8]) -> Option<&'static str> {
 pub fn definition() -> ToolDefinition {
     ToolDefinition {
         name: "Read".to_string(),
-        description: "Read the contents of a file from disk. NEVER use cat, head, tail, less, or more through Bash for file reading — use this tool instead. Always provide `file_path`. Once you know the relevant area, prefer targeted reads with `offset` (1-indexed) and `limit` instead of rereading the whole file. Responses are capped to 2000 lines and ~40KB total, including protection against huge single lines (for example minified files or embedded base64). If the result is truncated, call Read again with a higher offset or use grep to locate relevant sections first. Returns structured metadata including `content`, `offset`, `lines_shown`, `total_lines`, `truncated`, `byte_limited`, and an optional `warning`. Also supports JPEG, PNG, GIF, and WebP images for visual analysis.".to_string(),
+        description: "Read the contents of a file from disk. NEVER use cat, head, tail, less, or more through Bash for file reading — use this tool instead. Always provide `file_path`. Once you know the relevant area, prefer targeted reads with `offset` (1-indexed) and `limit` instead of rereading the whole file. Responses are capped to 2000 lines and ~40KB total, including protection against huge single lines (for example minified files or embedded base64). If the result is truncated, call Read again with a higher offset or use grep to locate relevant sections first. Reading a large source file without `offset`/`limit` returns its symbol outline (`outline_only: true`) instead of content; then read the ranges you need, or pass `force_full: true`. Returns structured metadata including `content`, `offset`, `lines_shown`, `total_lines`, `truncated`, `byte_limited`, and an optional `warning`. Also supports JPEG, PNG, GIF, and WebP images for visual analysis.".to_string(),
         input_schema: json!({
             "type": "object",
             "properties": {
@@ -110,6 +113,10 @@ pub fn definition() -> ToolDefinition {
                 "limit": {
                     "type": "integer",
                     "description": "Maximum number of lines to return (default: 2000). Use with `offset` when reading specific ranges or paging through a file."
+                },
+                "force_full": {
+                    "type": "boolean",
+                    "description": "Read from the top even when the file is large enough that Read would otherwise return its outline (default: false). Prefer targeted `offset`/`limit` reads."
                 }
             },
             "required": ["file_path"],
@@ -128,10 +135,23 @@ struct ReadInput {
     /// Maximum number of lines to return (default: `MAX_LINES`)
     #[serde(default, deserialize_with = "deserialize_optional_usize")]
     limit: Option<usize>,
+    /// Read from the top even when the file is large enough for outline mode.
+    #[serde(default, deserialize_with = "crate::bool_or_string::deserialize")]
+    force_full: bool,
 }
 
 /// Executes the read tool and returns a structured envelope.
 pub fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
+    execute_with_outline_threshold(input, ctx, DEFAULT_OUTLINE_THRESHOLD_LINES)
+}
+
+/// Executes the read tool, returning the outline of source files longer than
+/// `outline_threshold` lines when no range is requested (`0` disables).
+pub fn execute_with_outline_threshold(
+    input: &Value,
+    ctx: &ToolContext,
+    outline_threshold: usize,
+) -> ToolOutput {
     let input: ReadInput = match super::parse_tool_input(input, "read") {
         Ok(i) => i,
         Err(out) => return out,
@@ -157,12 +177,54 @@ pub fn execute(input: &Value, ctx: &ToolContext) -> ToolOutput {
         return read_image(&resolved.path, file_path, mime_type);
     }
 
+    if outline_threshold > 0
+        && !input.force_full
+        && input.offset.is_none()
+        && input.limit.is_none()
+        && let Some(output) = read_outline(&resolved.path, file_path, outline_threshold)
+    {
+        return output;
+    }
+
     // Read as text file with offset/limit
     let offset = input.offset.unwrap_or(1).max(1); // 1-indexed, minimum 1
     let limit = input.limit.unwrap_or(MAX_LINES).min(MAX_LINES); // Cap at MAX_LINES
     read_text(&resolved.path, file_path, offset, limit)
 }
 
+/// Returns the outline of a source file longer than `threshold` lines.
+///
+/// Returns `None` (read normally) for short files, files without a bundled
+/// grammar, and anything that fails to load or parse.
+fn read_outline(display_path: &str, path: &Path, threshold: usize) -> Option<ToolOutput> {
+    let (language_name, language) = outline::language_for(path)?;
+    if fs::metadata(path).ok()?.len() > outline::MAX_FILE_BYTES {
+        return None;
+    }
+    let source = fs::read(path).ok()?;
+    let total_lines = String::from_utf8_lossy(&source).lines().count();
+    if total_lines <= threshold {
+        return None;
+    }
+    let outline = outline::parse_outline(language_name, &language, &source).ok()?;
+    if outline.symbol_count == 0 {
+        return None;
+    }
+
+    let mut data = serde_json::Map::new();
+    insert_file_path_fields(&mut data, display_path, Some(path));
+    data.insert("outline_only".to_string(), Value::Bool(true));
+    data.insert("total_lines".to_string(), Value::from(total_lines));
+    outline.insert_fields(&mut data);
+    data.insert(
+        "warning".to_string(),
+        Value::String(format!(
+            "File has {total_lines} lines (over {threshold}), so its outline is returned instead of content. Read the symbols you need with offset/limit (start_line/end_line), or pass force_full: true to read from the top."
+        )),
+    );
+    Some(ToolOutput::success(Value::Object(data)))
+}
+
 /// Reads an image file and returns it as base64-encoded content.
 fn read_image(display_path: &str, path: &Path, mime_type: &str) -> ToolOutput {
     // Check file size
@@ -1047,4 +1109,32 @@ mod tests {
         let json_str = result.to_json_string();
         assert!(json_str.contains(r#""code":"invalid_input""#));
     }
+
+    #[cfg(feature = "outline-rust")]
+    #[test]
+    fn test_large_source_file_returns_outline_unless_forced() {
+        let temp = TempDir::new().unwrap();
+        let mut source = String::from("pub fn first() {}\n");
+        for i in 0..DEFAULT_OUTLINE_THRESHOLD_LINES {
+            let _ = writeln!(source, "// filler {i}");
+        }
+        source.push_str("fn last() {}\n");
+        fs::write(temp.path().join("big.rs"), &source).unwrap();
+        let ctx = ToolContext::new(temp.path().to_path_buf(), None);
+
+        let result = execute(&json!({"file_path": "big.rs"}), &ctx);
+        let data = result.data().expect("should have data");
+        assert_eq!(data["outline_only"], true);
+        assert!(data.get("content").is_none());
+        assert_eq!(data["symbols"][1]["name"], "last");
+        assert_eq!(data["symbols"][1]["start_line"], 1002);
+
+        let ranged = execute(&json!({"file_path": "big.rs", "offset": 1002}), &ctx);
+        assert_eq!(ranged.data().unwrap()["content"], "fn last() {}\n");
+
+        let forced = execute(&json!({"file_path": "big.rs", "force_full": true}), &ctx);
+        let data = forced.data().unwrap();
+        assert!(data.get("outline_only").is_none());
+        assert_eq!(data["lines_shown"], 1002);
+    }
 }
@@ -0,0 +1,422 @@
+//! Persistent shell sessions for the bash tool.
+//!
+//! A session keeps one long-lived `bash` process alive and feeds it commands
+//! sequentially over stdin, so `cd`, sourced virtualenvs, and exported
+//! variables carry over between calls. Each command is followed by a unique
+//! sentinel on stdout and stderr that marks where its output ends (and, on
+//! stdout, carries its exit code).
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWriteExt};
+use tokio::process::{Child, ChildStdin};
+use tokio::sync::mpsc;
+use uuid::Uuid;
+
+use crate::ToolOutput;
+use crate::bash::{BashOutput, build_output};
+use crate::bash_limits::{BashLimits, output_limit_notice};
+
+/// One line read from the shell, tagged with the stream it came from.
+enum StreamLine {
+    Stdout(String),
+    Stderr(String),
+}
+
+/// A long-lived shell process that runs commands one at a time.
+pub struct ShellSession {
+    child: Child,
+    stdin: ChildStdin,
+    lines: mpsc::UnboundedReceiver<StreamLine>,
+    /// Process group id of the shell (Unix only; the shell is the leader).
+    #[cfg(unix)]
+    pgid: i32,
+    /// Limits the shell was started with; rlimits cover every command, and
+    /// the output cap applies per command.
+    limits: BashLimits,
+    /// Set when the shell exited or was killed.
+    dead: bool,
+    /// Set while a command runs. Still set on the next call means the previous
+    /// run was cancelled mid-command and the output stream is out of sync.
+    in_flight: bool,
+}
+
+impl std::fmt::Debug for ShellSession {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShellSession")
+            .field("pid", &self.child.id())
+            .field("dead", &self.dead)
+            .field("in_flight", &self.in_flight)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ShellSession {
+    /// Spawns a new `bash` session rooted at `root`, under `limits`.
+    ///
+    /// # Errors
+    /// Returns an error if the shell process cannot be started.
+    pub fn spawn(root: &Path, limits: BashLimits) -> std::io::Result<Self> {
+        let mut cmd = tokio::process::Command::new("bash");
+        cmd.args(["--noprofile", "--norc"])
+            .current_dir(root)
+            // Same non-interactive terminal signals as one-shot bash commands.
+            .env("TERM", "dumb")
+            .env("NO_COLOR", "1")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        // Put the shell in its own process group so a timeout or interrupt can
+        // kill it together with everything it spawned.
+        #[cfg(unix)]
+        unsafe {
+            cmd.pre_exec(move || {
+                libc::setpgid(0, 0);
+                crate::bash_limits::apply_rlimits(&limits)
+            });
+        }
+
+        let mut child = cmd.spawn()?;
+        let stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| std::io::Error::other("shell stdin unavailable"))?;
+
+        let (tx, lines) = mpsc::unbounded_channel();
+        let max_line = limits.max_output_bytes;
+        spawn_line_reader(
+            child.stdout.take(),
+            tx.clone(),
+            StreamLine::Stdout,
+            max_line,
+        );
+        spawn_line_reader(child.stderr.take(), tx, StreamLine::Stderr, max_line);
+
+        Ok(Self {
+            #[cfg(unix)]
+            pgid: child.id().unwrap_or(0) as i32,
+            child,
+            stdin,
+            lines,
+            limits,
+            dead: false,
+            in_flight: false,
+        })
+    }
+
+    /// Returns true when the session can accept another command.
+    pub fn is_usable(&self) -> bool {
+        !self.dead && !self.in_flight
+    }
+
+    /// Runs one command in the session and waits for its sentinel.
+    ///
+    /// With `cwd`, the command runs in that directory and the shell returns to
+    /// its previous directory afterwards; a `cd` inside the command then only
+    /// lasts for that command.
+    ///
+    /// On timeout the whole session is killed (it cannot be resynchronized
+    /// safely); the next call spawns a fresh shell. If the future is dropped
+    /// mid-command the process group is killed as well.
+    ///
+    /// # Errors
+    /// Returns a failure envelope if the command cannot be written to the shell.
+    pub async fn run(
+        &mut self,
+        command: &str,
+        cwd: Option<&Path>,
+        timeout: Option<Duration>,
+        output_tx: Option<&mpsc::UnboundedSender<String>>,
+    ) -> Result<BashOutput, ToolOutput> {
+        let marker = format!("__ZDX_SHELL_DONE_{}__", Uuid::new_v4().simple());
+        let script = framed_script(command, cwd, &marker);
+
+        self.in_flight = true;
+        if let Err(e) = self.write_script(&script).await {
+            self.dead = true;
+            return Err(ToolOutput::failure(
+                "spawn_error",
+                "Persistent shell session is no longer running",
+                Some(format!("Error: {e}")),
+            ));
+        }
+
+        #[cfg(unix)]
+        let mut pg_guard = crate::bash::ProcessGroupGuard::new(self.pgid);
+
+        let deadline = timeout.map(|d| tokio::time::Instant::now() + d);
+        let mut stdout_buf = Vec::new();
+        let mut stderr_buf = Vec::new();
+        let mut stdout_done = false;
+        let mut stderr_done = false;
+        let mut exit_code = -1;
+        let mut timed_out = false;
+        let mut output_limited = false;
+        let max_output = self.limits.max_output_bytes;
+
+        while !(stdout_done && stderr_done) {
+            let next = match deadline {
+                Some(deadline) => tokio::time::timeout_at(deadline, self.lines.recv()).await,
+                None => Ok(self.lines.recv().await),
+            };
+            match next {
+                Err(_) => {
+                    timed_out = true;
+                    self.kill();
+                    break;
+                }
+                Ok(None) => {
+                    // Both pipes closed: the command ran `exit` or the shell died.
+                    self.dead = true;
+                    exit_code = match self.child.wait().await {
+                        Ok(status) => status.code().unwrap_or(-1),
+                        Err(_) => -1,
+                    };
+                    break;
+                }
+                Ok(Some(StreamLine::Stdout(line))) => {
+                    if let Some(idx) = line.find(&marker) {
+                        stdout_buf.extend_from_slice(&line.as_bytes()[..idx]);
+                        forward_partial(output_tx, &line[..idx]);
+                        exit_code = line[idx + marker.len()..].trim().parse().unwrap_or(-1);
+                        stdout_done = true;
+                    } else {
+                        forward_partial(output_tx, &line);
+                        stdout_buf.extend_from_slice(line.as_bytes());
+                    }
+                }
+                Ok(Some(StreamLine::Stderr(line))) => {
+                    if let Some(idx) = line.find(&marker) {
+                        stderr_buf.extend_from_slice(&line.as_bytes()[..idx]);
+                        forward_partial(output_tx, &line[..idx]);
+                        stderr_done = true;
+                    } else {
+                        forward_partial(output_tx, &line);
+                        stderr_buf.extend_from_slice(line.as_bytes());
+                    }
+                }
+            }
+            if max_output > 0 && stdout_buf.len() + stderr_buf.len() > max_output {
+                output_limited = true;
+                self.kill();
+                break;
+            }
+        }
+
+        #[cfg(unix)]
+        pg_guard.disarm();
+        self.in_flight = false;
+
+        let mut output = build_output(
+            &stdout_buf,
+            &stderr_buf,
+            exit_code,
+            timed_out,
+            timeout,
+            output_tx,
+        );
+        if output_limited {
+            output.append_notice(&output_limit_notice(max_output), output_tx);
+        }
+        #[cfg(unix)]
+        if let Some(notice) = crate::bash_limits::cpu_limit_notice(&self.limits, exit_code, None) {
+            output.append_notice(&notice, output_tx);
+        }
+        Ok(output)
+    }
+
+    async fn write_script(&mut self, script: &str) -> std::io::Result<()> {
+        self.stdin.write_all(script.as_bytes()).await?;
+        self.stdin.flush().await
+    }
+
+    /// Kills the shell and every process it spawned.
+    fn kill(&mut self) {
+        self.dead = true;
+        #[cfg(unix)]
+        crate::bash::kill_process_group(self.pgid);
+        #[cfg(not(unix))]
+        let _ = self.child.start_kill();
+    }
+}
+
+impl Drop for ShellSession {
+    fn drop(&mut self) {
+        if !self.dead {
+            self.kill();
+        }
+    }
+}
+
+/// Wraps `command` so it runs in the session shell and is followed by the
+/// sentinel lines.
+///
+/// The command goes through `eval` as a single-quoted word so unbalanced
+/// quotes or syntax errors fail that command instead of swallowing the
+/// sentinel. Stdin is detached so commands cannot read the session's script.
+/// With `cwd`, the command runs there and the previous directory is restored.
+fn framed_script(command: &str, cwd: Option<&Path>, marker: &str) -> String {
+    let quoted = single_quote(command);
+    let run = match cwd {
+        Some(cwd) => {
+            let dir = single_quote(&cwd.to_string_lossy());
+            format!(
+                "__zdx_prev_pwd=\"$PWD\"\ncd -- {dir} && eval {quoted} < /dev/null\n__zdx_status=$?\ncd -- \"$__zdx_prev_pwd\"\n"
+            )
+        }
+        None => format!("eval {quoted} < /dev/null\n__zdx_status=$?\n"),
+    };
+    format!("{run}printf '%s %d\\n' '{marker}' \"$__zdx_status\"\nprintf '%s\\n' '{marker}' >&2\n")
+}
+
+fn single_quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', r"'\''"))
+}
+
+fn forward_partial(output_tx: Option<&mpsc::UnboundedSender<String>>, text: &str) {
+    if let Some(tx) = output_tx
+        && !text.is_empty()
+    {
+        let _ = tx.send(text.to_string());
+    }
+}
+
+/// Forwards lines from `handle`. Lines longer than `max_line` bytes (the
+/// output cap, 0 = unlimited) arrive in pieces, so an endless line can't grow
+/// unchecked.
+fn spawn_line_reader<H>(
+    handle: Option<H>,
+    tx: mpsc::UnboundedSender<StreamLine>,
+    wrap: fn(String) -> StreamLine,
+    max_line: usize,
+) where
+    H: AsyncRead + Unpin + Send + 'static,
+{
+    tokio::spawn(async move {
+        let Some(handle) = handle else { return };
+        let mut reader = tokio::io::BufReader::new(handle);
+        loop {
+            let mut line = String::new();
+            let read = if max_line > 0 {
+                (&mut reader)
+                    .take(max_line as u64 + 1)
+                    .read_line(&mut line)
+                    .await
+            } else {
+                reader.read_line(&mut line).await
+            };
+            match read {
+                Ok(0) | Err(_) => break,
+                Ok(_) => {
+                    if tx.send(wrap(line)).is_err() {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    async fn run_ok(session: &mut ShellSession, command: &str) -> BashOutput {
+        session
+            .run(command, None, Some(Duration::from_secs(10)), None)
+            .await
+            .expect("command should run")
+    }
+
+    #[tokio::test]
+    async fn test_session_persists_cwd_and_exports() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("sub")).unwrap();
+        let mut session = ShellSession::spawn(temp.path(), BashLimits::default()).unwrap();
+
+        run_ok(&mut session, "cd sub && export ZDX_SESSION_TEST=kept").await;
+        let output = run_ok(&mut session, "basename \"$PWD\"; echo $ZDX_SESSION_TEST").await;
+
+        assert_eq!(output.stdout, "sub\nkept\n");
+        assert_eq!(output.exit_code, 0);
+    }
+
+    #[tokio::test]
+    async fn test_session_cwd_applies_to_one_command() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir(temp.path().join("sub")).unwrap();
+        let mut session = ShellSession::spawn(temp.path(), BashLimits::default()).unwrap();
+
+        let output = session
+            .run(
+                "basename \"$PWD\"; cd /; exit_code_check() { return 3; }; exit_code_check",
+                Some(&temp.path().join("sub")),
+                Some(Duration::from_secs(10)),
+                None,
+            )
+            .await
+            .unwrap();
+        assert_eq!(output.stdout, "sub\n");
+        assert_eq!(output.exit_code, 3);
+
+        let output = run_ok(&mut session, "pwd").await;
+        let root = temp.path().canonicalize().unwrap();
+        assert_eq!(output.stdout.trim(), root.to_string_lossy());
+    }
+
+    #[tokio::test]
+    async fn test_session_reports_exit_code_and_unterminated_output() {
+        let temp = TempDir::new().unwrap();
+        let mut session = ShellSession::spawn(temp.path(), BashLimits::default()).unwrap();
+
+        let output = run_ok(&mut session, "printf partial; echo oops >&2; false").await;
+
+        assert_eq!(output.stdout, "partial");
+        assert_eq!(output.stderr, "oops\n");
+        assert_eq!(output.exit_code, 1);
+        assert!(session.is_usable());
+    }
+
+    #[tokio::test]
+    async fn test_session_survives_syntax_errors() {
+        let temp = TempDir::new().unwrap();
+        let mut session = ShellSession::spawn(temp.path(), BashLimits::default()).unwrap();
+
+        let output = run_ok(&mut session, "echo \"unterminated").await;
+        assert_ne!(output.exit_code, 0);
+
+        let output = run_ok(&mut session, "echo 'still here'").await;
+        assert_eq!(output.stdout, "still here\n");
+    }
+
+    #[tokio::test]
+    async fn test_session_exit_marks_session_dead() {
+        let temp = TempDir::new().unwrap();
+        let mut session = ShellSession::spawn(temp.path(), BashLimits::default()).unwrap();
+
+        let output = run_ok(&mut session, "exit 7").await;
+
+        assert_eq!(output.exit_code, 7);
+        assert!(!session.is_usable());
+    }
+
+    #[tokio::test]
+    async fn test_session_timeout_kills_session() {
+        let temp = TempDir::new().unwrap();
+        let mut session = ShellSession::spawn(temp.path(), BashLimits::default()).unwrap();
+
+        let output = session
+            .run("sleep 5", None, Some(Duration::from_millis(200)), None)
+            .await
+            .unwrap();
+
+        assert!(output.timed_out);
+        assert!(!session.is_usable());
+    }
+}
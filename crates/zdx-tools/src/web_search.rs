@@ -283,12 +283,12 @@ pub async fn execute(input: &Value, _ctx: &ToolContext) -> ToolOutput {
         Err(output) => return output,
     };
 
-    // Build successful response
-    ToolOutput::success(json!({
+    // Build successful response (wrapped as untrusted content)
+    ToolOutput::success(super::web_guard::envelope(json!({
         "search_id": search_response.search_id,
         "results": search_response.results,
         "warnings": search_response.warnings
-    }))
+    })))
 }
 
 #[cfg(test)]
@@ -0,0 +1,309 @@
+//! ANSI SGR parsing for command output.
+//!
+//! Bash output is shown with its colors: SGR sequences (`ESC [ … m`) become
+//! [`AnsiStyle`]s carried by [`Style::Ansi`], and every other escape sequence
+//! (cursor movement, OSC titles/links) is dropped. A carriage return keeps only
+//! the text after it, so progress bars show their final state.
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::style::{Style, StyledLine, StyledSpan};
+use crate::text::ratatui_width;
+
+/// A terminal color: one of the 256 palette entries or 24-bit RGB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnsiColor {
+    /// Palette index (0–7 normal, 8–15 bright, 16–255 extended).
+    Indexed(u8),
+    Rgb(u8, u8, u8),
+}
+
+/// Colors and attributes set by SGR sequences.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AnsiStyle {
+    pub fg: Option<AnsiColor>,
+    pub bg: Option<AnsiColor>,
+    /// Bitset of `BOLD`, `DIM`, `ITALIC`, `UNDERLINE`, and `REVERSE`.
+    pub attrs: u8,
+}
+
+impl AnsiStyle {
+    pub const BOLD: u8 = 1;
+    pub const DIM: u8 = 1 << 1;
+    pub const ITALIC: u8 = 1 << 2;
+    pub const UNDERLINE: u8 = 1 << 3;
+    pub const REVERSE: u8 = 1 << 4;
+
+    /// Whether no color or attribute is set.
+    pub fn is_plain(self) -> bool {
+        self == Self::default()
+    }
+
+    pub fn has(self, attr: u8) -> bool {
+        self.attrs & attr != 0
+    }
+
+    /// Applies the parameters of one SGR sequence.
+    fn apply_sgr(&mut self, params: &str) {
+        let codes: Vec<u16> = params
+            .split([';', ':'])
+            .map(|code| code.parse().unwrap_or(0))
+            .collect();
+        let mut i = 0;
+        while i < codes.len() {
+            match codes[i] {
+                0 => *self = Self::default(),
+                1 => self.attrs |= Self::BOLD,
+                2 => self.attrs |= Self::DIM,
+                3 => self.attrs |= Self::ITALIC,
+                4 => self.attrs |= Self::UNDERLINE,
+                7 => self.attrs |= Self::REVERSE,
+                22 => self.attrs &= !(Self::BOLD | Self::DIM),
+                23 => self.attrs &= !Self::ITALIC,
+                24 => self.attrs &= !Self::UNDERLINE,
+                27 => self.attrs &= !Self::REVERSE,
+                code @ 30..=37 => self.fg = Some(AnsiColor::Indexed((code - 30) as u8)),
+                code @ 90..=97 => self.fg = Some(AnsiColor::Indexed((code - 90 + 8) as u8)),
+                code @ 40..=47 => self.bg = Some(AnsiColor::Indexed((code - 40) as u8)),
+                code @ 100..=107 => self.bg = Some(AnsiColor::Indexed((code - 100 + 8) as u8)),
+                39 => self.fg = None,
+                49 => self.bg = None,
+                code @ (38 | 48) => {
+                    let (color, used) = extended_color(&codes[i + 1..]);
+                    i += used;
+                    if code == 38 {
+                        self.fg = color;
+                    } else {
+                        self.bg = color;
+                    }
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+    }
+}
+
+/// Parses `5;n` / `2;r;g;b` after a 38/48 code. Returns the color and how
+/// many codes it consumed.
+fn extended_color(codes: &[u16]) -> (Option<AnsiColor>, usize) {
+    let byte = |i: usize| codes.get(i).map(|&c| c.min(255) as u8);
+    match codes.first() {
+        Some(5) => (byte(1).map(AnsiColor::Indexed), 2),
+        Some(2) => match (byte(1), byte(2), byte(3)) {
+            (Some(r), Some(g), Some(b)) => (Some(AnsiColor::Rgb(r, g, b)), 4),
+            _ => (None, codes.len()),
+        },
+        _ => (None, 0),
+    }
+}
+
+/// Splits `text` into lines of styled spans. Text without SGR styling uses
+/// `base`; styling carries across line breaks like in a terminal.
+pub fn parse_ansi(text: &str, base: Style) -> Vec<StyledLine> {
+    let mut lines = Vec::new();
+    let mut current = AnsiStyle::default();
+    for raw in text.lines() {
+        // Overwritten progress output: keep what was drawn last.
+        let raw = raw.rsplit('\r').next().unwrap_or(raw);
+        let mut spans: Vec<StyledSpan> = Vec::new();
+        let mut push = |segment: &str, style: AnsiStyle| {
+            if segment.is_empty() {
+                return;
+            }
+            let style = if style.is_plain() {
+                base
+            } else {
+                Style::Ansi(style)
+            };
+            match spans.last_mut() {
+                Some(last) if last.style == style => last.text.push_str(segment),
+                _ => spans.push(StyledSpan {
+                    text: segment.to_string(),
+                    style,
+                }),
+            }
+        };
+        let mut rest = raw;
+        while let Some(esc) = rest.find('\x1b') {
+            push(&clean_text(&rest[..esc]), current);
+            let (sgr, len) = escape_sequence(&rest[esc..]);
+            if let Some(params) = sgr {
+                current.apply_sgr(params);
+            }
+            rest = &rest[esc + len..];
+        }
+        push(&clean_text(rest), current);
+        lines.push(StyledLine { spans });
+    }
+    lines
+}
+
+/// Removes ANSI escape sequences (and resolves carriage returns) from `text`.
+pub fn strip_ansi(text: &str) -> String {
+    if !text.contains('\x1b') && !text.contains('\r') {
+        return text.to_string();
+    }
+    parse_ansi(text, Style::Plain)
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.text.as_str())
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Reads one escape sequence at the start of `s` (which begins with ESC).
+/// Returns the SGR parameters when it is one, and the sequence's byte length.
+fn escape_sequence(s: &str) -> (Option<&str>, usize) {
+    let bytes = s.as_bytes();
+    match bytes.get(1) {
+        // CSI: parameters and intermediates, then a final byte in @..~.
+        Some(b'[') => {
+            let Some(end) = bytes[2..].iter().position(|b| (0x40..=0x7e).contains(b)) else {
+                return (None, s.len());
+            };
+            let end = end + 2;
+            let sgr = (bytes[end] == b'm').then(|| &s[2..end]);
+            (sgr, end + 1)
+        }
+        // OSC: terminated by BEL or ESC \.
+        Some(b']') => {
+            let bel = s.find('\x07').map(|i| i + 1);
+            let st = s.find("\x1b\\").map(|i| i + 2);
+            let end = match (bel, st) {
+                (Some(a), Some(b)) => a.min(b),
+                (a, b) => a.or(b).unwrap_or(s.len()),
+            };
+            (None, end)
+        }
+        Some(_) => (None, 1 + s[1..].chars().next().map_or(0, char::len_utf8)),
+        None => (None, 1),
+    }
+}
+
+/// Expands tabs and drops other control characters.
+fn clean_text(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\t' => out.push_str("    "),
+            c if c.is_control() => {}
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Hard-wraps a styled line at `width` columns (by grapheme, keeping
+/// whitespace), the way a terminal wraps output. Each resulting line starts
+/// with `prefix`.
+pub(crate) fn wrap_output_line(
+    line: &StyledLine,
+    width: usize,
+    prefix: &StyledSpan,
+) -> Vec<StyledLine> {
+    let width = width.saturating_sub(ratatui_width(&prefix.text)).max(1);
+    let mut lines = Vec::new();
+    let mut spans = vec![prefix.clone()];
+    let mut used = 0;
+    for span in &line.spans {
+        let mut piece = String::new();
+        for grapheme in span.text.graphemes(true) {
+            let grapheme_width = ratatui_width(grapheme);
+            if used + grapheme_width > width && used > 0 {
+                if !piece.is_empty() {
+                    spans.push(StyledSpan {
+                        text: std::mem::take(&mut piece),
+                        style: span.style,
+                    });
+                }
+                lines.push(StyledLine {
+                    spans: std::mem::replace(&mut spans, vec![prefix.clone()]),
+                });
+                used = 0;
+            }
+            piece.push_str(grapheme);
+            used += grapheme_width;
+        }
+        if !piece.is_empty() {
+            spans.push(StyledSpan {
+                text: piece,
+                style: span.style,
+            });
+        }
+    }
+    lines.push(StyledLine { spans });
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn span_texts(line: &StyledLine) -> Vec<(&str, Style)> {
+        line.spans
+            .iter()
+            .map(|span| (span.text.as_str(), span.style))
+            .collect()
+    }
+
+    #[test]
+    fn parses_sgr_colors_and_carries_them_across_lines() {
+        let lines = parse_ansi(
+            "ok \x1b[1;31merror\x1b[0m done\n\x1b[38;5;208mwarn\nstill\x1b[39m plain",
+            Style::ToolOutput,
+        );
+        let red_bold = AnsiStyle {
+            fg: Some(AnsiColor::Indexed(1)),
+            attrs: AnsiStyle::BOLD,
+            ..AnsiStyle::default()
+        };
+        assert_eq!(
+            span_texts(&lines[0]),
+            vec![
+                ("ok ", Style::ToolOutput),
+                ("error", Style::Ansi(red_bold)),
+                (" done", Style::ToolOutput),
+            ]
+        );
+        let orange = Style::Ansi(AnsiStyle {
+            fg: Some(AnsiColor::Indexed(208)),
+            ..AnsiStyle::default()
+        });
+        assert_eq!(span_texts(&lines[1]), vec![("warn", orange)]);
+        assert_eq!(
+            span_texts(&lines[2]),
+            vec![("still", orange), (" plain", Style::ToolOutput)]
+        );
+    }
+
+    #[test]
+    fn strips_other_sequences_and_resolves_carriage_returns() {
+        let text = "\x1b]0;title\x07\x1b[2K 10%\r100% \x1b[32mdone\x1b[m\tok";
+        assert_eq!(strip_ansi(text), "100% done    ok");
+    }
+
+    #[test]
+    fn wraps_output_by_width_keeping_styles() {
+        let line = StyledLine {
+            spans: vec![StyledSpan {
+                text: "abcdefgh".to_string(),
+                style: Style::ToolOutput,
+            }],
+        };
+        let prefix = StyledSpan {
+            text: "│ ".to_string(),
+            style: Style::ToolBracket,
+        };
+        let wrapped = wrap_output_line(&line, 5, &prefix);
+        let texts: Vec<String> = wrapped
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.text.as_str()).collect())
+            .collect();
+        assert_eq!(texts, vec!["│ abc", "│ def", "│ gh"]);
+    }
+}
@@ -4,6 +4,7 @@
 
 use std::collections::HashMap;
 
+use chrono::{DateTime, Utc};
 use zdx_engine::core::events::ToolOutput;
 use zdx_engine::core::thread_persistence::ThreadEvent;
 
@@ -17,7 +18,12 @@ use crate::reasoning::reasoning_display_text;
 /// - `ToolUse` + `ToolResult` → `Tool` cells (paired by ID)
 /// - unmatched `ToolUse` → cancelled `Tool` cell (the historical turn ended without a result)
 /// - `Thinking`/`Reasoning` → `Thinking` cells
-/// - Skips `Meta` and `Interrupted` events
+/// - Skips `Meta` and `Interrupted` events, except that cells matching the
+///   meta's `pinned_messages` are marked pinned
+///
+/// Cells take their `created_at` from the event timestamp (tool cells also
+/// take `completed_at` from their result), so restored history keeps its
+/// original times.
 ///
 /// Consecutive assistant `Message` events with the same `phase` coalesce
 /// into a single cell. Persistence emits one event per `ChatContentBlock::Text`,
@@ -26,25 +32,36 @@ use crate::reasoning::reasoning_display_text;
 /// events. Live rendering already collapses all deltas into one cell;
 /// restore must mirror that, otherwise markdown spans whose pair straddles
 /// a fragment boundary (`**bold` … `text**`) render as literal `**`.
-/// Boundaries: any non-Message event, role change, or phase change.
+/// Boundaries: any non-Message event, role change, phase change, or model
+/// change.
 pub fn build_transcript_from_events(events: &[ThreadEvent]) -> Vec<HistoryCell> {
     let mut cells = Vec::new();
     // Track tool cells by ID for pairing with results
     let mut tool_cells: HashMap<String, usize> = HashMap::new();
-    // (phase, accumulated_text) for the in-progress assistant coalesce.
-    let mut pending_assistant: Option<(Option<String>, String)> = None;
+    // In-progress assistant coalesce run.
+    let mut pending_assistant: Option<PendingAssistant> = None;
 
     for event in events {
         match event {
             ThreadEvent::Message {
-                role, text, phase, ..
+                role,
+                text,
+                phase,
+                model,
+                ts,
+                ..
             } if role == "assistant" => match pending_assistant.as_mut() {
-                Some((p, accumulated)) if *p == *phase => {
-                    accumulated.push_str(text);
+                Some(pending) if pending.phase == *phase && pending.model == *model => {
+                    pending.text.push_str(text);
                 }
                 _ => {
                     flush_pending_assistant(&mut pending_assistant, &mut cells);
-                    pending_assistant = Some((phase.clone(), text.clone()));
+                    pending_assistant = Some(PendingAssistant {
+                        phase: phase.clone(),
+                        text: text.clone(),
+                        at: parse_ts(ts),
+                        model: model.clone(),
+                    });
                 }
             },
             ThreadEvent::Meta { .. }
@@ -53,39 +70,56 @@ pub fn build_transcript_from_events(events: &[ThreadEvent]) -> Vec<HistoryCell>
                 // Non-display events still flush the pending assistant run.
                 flush_pending_assistant(&mut pending_assistant, &mut cells);
             }
-            ThreadEvent::Notice { message, .. } => {
+            ThreadEvent::Notice {
+                kind, message, ts, ..
+            } => {
                 flush_pending_assistant(&mut pending_assistant, &mut cells);
-                cells.push(HistoryCell::system(format!("⚠ {message}")));
+                let marker = match kind {
+                    zdx_engine::core::events::NoticeKind::ThinkingEscalated => "⇡",
+                    _ => "⚠",
+                };
+                push_at(
+                    &mut cells,
+                    HistoryCell::system(format!("{marker} {message}")),
+                    ts,
+                );
             }
-            ThreadEvent::Message { role, text, .. } => {
+            ThreadEvent::Message { role, text, ts, .. } => {
                 flush_pending_assistant(&mut pending_assistant, &mut cells);
                 let cell = match role.as_str() {
                     "user" => HistoryCell::user(text),
                     _ => continue,
                 };
-                cells.push(cell);
+                push_at(&mut cells, cell, ts);
             }
-            ThreadEvent::Reasoning { text, replay, .. } => {
+            ThreadEvent::Reasoning {
+                text, replay, ts, ..
+            } => {
                 flush_pending_assistant(&mut pending_assistant, &mut cells);
                 if let Some(display) = reasoning_display_text(text.as_deref(), replay.as_ref()) {
                     let mut cell = HistoryCell::thinking_streaming(display);
                     cell.finalize_thinking(replay.clone());
-                    cells.push(cell);
+                    push_at(&mut cells, cell, ts);
                 }
             }
             ThreadEvent::ToolUse {
-                id, name, input, ..
+                id,
+                name,
+                input,
+                ts,
+                ..
             } => {
                 flush_pending_assistant(&mut pending_assistant, &mut cells);
                 // Create a running tool cell (will be updated by result)
                 let cell = HistoryCell::tool_running(id, name, input.clone());
                 let idx = cells.len();
                 tool_cells.insert(id.clone(), idx);
-                cells.push(cell);
+                push_at(&mut cells, cell, ts);
             }
             ThreadEvent::ToolResult {
                 tool_use_id,
                 output,
+                ts,
                 ..
             } => {
                 flush_pending_assistant(&mut pending_assistant, &mut cells);
@@ -93,17 +127,7 @@ pub fn build_transcript_from_events(events: &[ThreadEvent]) -> Vec<HistoryCell>
                 if let Some(idx) = tool_cells.remove(tool_use_id)
                     && let Some(cell) = cells.get_mut(idx)
                 {
-                    // Deserialize the stored JSON back to ToolOutput
-                    // (it was serialized via serde_json::to_value in ThreadEvent::from_agent)
-                    let tool_output: ToolOutput = serde_json::from_value(output.clone())
-                        .unwrap_or_else(|e| {
-                            ToolOutput::failure(
-                                "parse_error",
-                                "Failed to parse tool result",
-                                Some(format!("Deserialization error: {e}")),
-                            )
-                        });
-                    cell.set_tool_result(tool_output);
+                    apply_tool_result(cell, output, ts);
                 }
                 // If no matching tool cell found, skip (incomplete pair)
             }
@@ -112,6 +136,13 @@ pub fn build_transcript_from_events(events: &[ThreadEvent]) -> Vec<HistoryCell>
 
     flush_pending_assistant(&mut pending_assistant, &mut cells);
 
+    cancel_unmatched_tools(&mut cells, tool_cells);
+    mark_pinned_cells(&mut cells, events);
+    cells
+}
+
+/// Marks tool cells that never got a result as canceled.
+fn cancel_unmatched_tools(cells: &mut [HistoryCell], tool_cells: HashMap<String, usize>) {
     for idx in tool_cells.into_values() {
         if let Some(cell) = cells.get_mut(idx) {
             cell.set_tool_result(ToolOutput::canceled(
@@ -119,17 +150,78 @@ pub fn build_transcript_from_events(events: &[ThreadEvent]) -> Vec<HistoryCell>
             ));
         }
     }
+}
 
-    cells
+/// Marks user and assistant cells whose text matches one of the meta's
+/// pinned messages.
+fn mark_pinned_cells(cells: &mut [HistoryCell], events: &[ThreadEvent]) {
+    let Some(ThreadEvent::Meta {
+        pinned_messages: pinned,
+        ..
+    }) = events.first()
+    else {
+        return;
+    };
+    for cell in cells {
+        if let Some((role, text)) = cell.pinnable_message()
+            && pinned
+                .iter()
+                .any(|pin| pin.role == role && pin.text == text)
+        {
+            cell.set_pinned(true);
+        }
+    }
+}
+
+/// A run of assistant text events being coalesced into one cell.
+struct PendingAssistant {
+    phase: Option<String>,
+    text: String,
+    /// Timestamp of the first event in the run.
+    at: Option<DateTime<Utc>>,
+    model: Option<String>,
+}
+
+fn parse_ts(ts: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(ts)
+        .ok()
+        .map(|at| at.with_timezone(&Utc))
+}
+
+/// Pushes `cell`, stamped with the event timestamp when it parses.
+fn push_at(cells: &mut Vec<HistoryCell>, mut cell: HistoryCell, ts: &str) {
+    if let Some(at) = parse_ts(ts) {
+        cell.set_created_at(at);
+    }
+    cells.push(cell);
+}
+
+/// Sets a recorded tool result (and its completion time) on `cell`.
+fn apply_tool_result(cell: &mut HistoryCell, output: &serde_json::Value, ts: &str) {
+    // Deserialize the stored JSON back to ToolOutput
+    // (it was serialized via serde_json::to_value in ThreadEvent::from_agent)
+    let tool_output: ToolOutput = serde_json::from_value(output.clone()).unwrap_or_else(|e| {
+        ToolOutput::failure(
+            "parse_error",
+            "Failed to parse tool result",
+            Some(format!("Deserialization error: {e}")),
+        )
+    });
+    cell.set_tool_result(tool_output);
+    if let Some(at) = parse_ts(ts) {
+        cell.set_tool_completed_at(at);
+    }
 }
 
 /// Pushes any pending coalesced assistant text as one `Assistant` cell.
-fn flush_pending_assistant(
-    pending: &mut Option<(Option<String>, String)>,
-    cells: &mut Vec<HistoryCell>,
-) {
-    if let Some((_phase, text)) = pending.take() {
-        cells.push(HistoryCell::assistant(text));
+fn flush_pending_assistant(pending: &mut Option<PendingAssistant>, cells: &mut Vec<HistoryCell>) {
+    if let Some(pending) = pending.take() {
+        let mut cell = HistoryCell::assistant(pending.text);
+        if let Some(at) = pending.at {
+            cell.set_created_at(at);
+        }
+        cell.set_model(pending.model);
+        cells.push(cell);
     }
 }
 
@@ -140,6 +232,33 @@ mod tests {
     use super::*;
     use crate::cell::ToolState;
 
+    #[test]
+    fn test_build_transcript_marks_pinned_messages() {
+        let mut meta = ThreadEvent::meta_with_root(None);
+        if let ThreadEvent::Meta {
+            pinned_messages, ..
+        } = &mut meta
+        {
+            pinned_messages.push(zdx_engine::core::thread_persistence::PinnedMessage {
+                role: "assistant".to_string(),
+                text: "Use tabs.".to_string(),
+            });
+        }
+        let events = vec![
+            meta,
+            ThreadEvent::user_message("Use tabs."),
+            ThreadEvent::assistant_message("Use tabs.\n<followups>\n- Why?\n</followups>"),
+        ];
+        let cells = build_transcript_from_events(&events);
+        assert!(!cells[0].is_pinned());
+        assert!(cells[1].is_pinned());
+        assert!(
+            cells[1].display_lines(80, 0).last().unwrap().spans[0]
+                .text
+                .contains("📌")
+        );
+    }
+
     #[test]
     fn test_build_transcript_from_events_empty() {
         let events: Vec<ThreadEvent> = vec![];
@@ -162,6 +281,13 @@ mod tests {
                 thinking_override: None,
                 pending_topic_title: false,
                 alias_to: None,
+                tags: Vec::new(),
+                prompt_variant: None,
+                pinned_turns: Vec::new(),
+                pinned_messages: Vec::new(),
+                pinned_files: Vec::new(),
+                style: None,
+                summary: None,
                 ts: "2024-01-01T00:00:00Z".to_string(),
             },
             ThreadEvent::Message {
@@ -169,6 +295,7 @@ mod tests {
                 text: "Hello".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2024-01-01T00:00:01Z".to_string(),
             },
             ThreadEvent::Message {
@@ -176,6 +303,7 @@ mod tests {
                 text: "Hi there!".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2024-01-01T00:00:02Z".to_string(),
             },
         ];
@@ -198,6 +326,12 @@ mod tests {
             }
             _ => panic!("Expected Assistant cell"),
         }
+
+        // Cells keep the recorded event times.
+        assert_eq!(
+            cells[1].created_at().to_rfc3339(),
+            "2024-01-01T00:00:02+00:00"
+        );
     }
 
     #[test]
@@ -310,6 +444,13 @@ mod tests {
                 thinking_override: None,
                 pending_topic_title: false,
                 alias_to: None,
+                tags: Vec::new(),
+                prompt_variant: None,
+                pinned_turns: Vec::new(),
+                pinned_messages: Vec::new(),
+                pinned_files: Vec::new(),
+                style: None,
+                summary: None,
                 ts: "2024-01-01T00:00:00Z".to_string(),
             },
             ThreadEvent::Message {
@@ -317,6 +458,7 @@ mod tests {
                 text: "Read the file".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2024-01-01T00:00:01Z".to_string(),
             },
             ThreadEvent::Reasoning {
@@ -346,6 +488,7 @@ mod tests {
                 text: "Done!".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2024-01-01T00:00:05Z".to_string(),
             },
             ThreadEvent::Interrupted {
@@ -376,6 +519,7 @@ mod tests {
                 text: "hi".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-04-16T00:00:00Z".to_string(),
             },
             ThreadEvent::Notice {
@@ -487,6 +631,7 @@ mod tests {
                 text: "Hello **wor".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:00Z".to_string(),
             },
             ThreadEvent::Message {
@@ -494,6 +639,7 @@ mod tests {
                 text: "ld** how are you?".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:00Z".to_string(),
             },
         ];
@@ -520,6 +666,7 @@ mod tests {
                 text: (*t).to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:00Z".to_string(),
             })
             .collect();
@@ -544,6 +691,7 @@ mod tests {
                 text: "Before reasoning".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:00Z".to_string(),
             },
             ThreadEvent::Reasoning {
@@ -556,6 +704,7 @@ mod tests {
                 text: "After reasoning".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:02Z".to_string(),
             },
         ];
@@ -581,6 +730,7 @@ mod tests {
                 text: "Let me read the file.".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:00Z".to_string(),
             },
             ThreadEvent::ToolUse {
@@ -602,6 +752,7 @@ mod tests {
                 text: "Done.".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:03Z".to_string(),
             },
         ];
@@ -624,6 +775,7 @@ mod tests {
                 text: "Commentary text.".to_string(),
                 phase: Some("commentary".to_string()),
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:00Z".to_string(),
             },
             ThreadEvent::Message {
@@ -631,6 +783,7 @@ mod tests {
                 text: "Final text.".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:01Z".to_string(),
             },
         ];
@@ -654,6 +807,7 @@ mod tests {
                 text: "first question".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:00Z".to_string(),
             },
             ThreadEvent::Message {
@@ -661,6 +815,7 @@ mod tests {
                 text: "second question".to_string(),
                 phase: None,
                 replay: None,
+                model: None,
                 ts: "2026-05-15T00:00:01Z".to_string(),
             },
         ];
@@ -689,6 +844,7 @@ mod tests {
                     signature: "sig-first".to_string(),
                     model: "gemini-3-pro-preview".to_string(),
                 }),
+                model: None,
                 ts: "2026-05-15T00:00:00Z".to_string(),
             },
             ThreadEvent::Message {
@@ -699,6 +855,7 @@ mod tests {
                     signature: "sig-second".to_string(),
                     model: "gemini-3-pro-preview".to_string(),
                 }),
+                model: None,
                 ts: "2026-05-15T00:00:01Z".to_string(),
             },
         ];
@@ -716,4 +873,22 @@ mod tests {
             _ => panic!("expected single coalesced Assistant cell"),
         }
     }
+
+    #[test]
+    fn test_build_transcript_shows_model_badge_on_attributed_replies() {
+        let mut reply = ThreadEvent::assistant_message("Done.");
+        if let ThreadEvent::Message { model, .. } = &mut reply {
+            *model = Some("anthropic:claude-sonnet-4".to_string());
+        }
+        let cells = build_transcript_from_events(&[reply, ThreadEvent::assistant_message("x")]);
+
+        let badge = cells[0].display_lines(80, 0).pop().unwrap();
+        assert_eq!(badge.spans[0].text, "[claude-sonnet-4]");
+        assert!(
+            !cells[1]
+                .display_lines(80, 0)
+                .iter()
+                .any(|line| line.spans.iter().any(|span| span.text.starts_with('[')))
+        );
+    }
 }
@@ -11,11 +11,14 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 use chrono::{DateTime, Local, Utc};
 use serde_json::Value;
-use zdx_engine::core::events::ToolOutput;
+use zdx_engine::core::events::{ErrorKind, ToolOutput};
 use zdx_engine::providers::ReplayToken;
 
+use crate::ansi::{parse_ansi, wrap_output_line};
+use crate::changes::{FileChange, FileChangeKind};
 use crate::style::{Style, StyledLine, StyledSpan};
 use crate::text::{ratatui_width, truncate_with_ellipsis};
+use crate::timestamp::format_span_duration;
 use crate::wrap::{WrapCache, render_prefixed_content};
 
 fn value_as_trimmed_str<'a>(input: &'a Value, key: &str) -> Option<&'a str> {
@@ -95,12 +98,29 @@ fn summarize_apply_patch_targets(patch: &str) -> Option<String> {
     }
 }
 
+/// The command, prefixed with `[dir]` when it ran outside the root. The
+/// effective directory from the result wins over the requested one.
+fn bash_key_arg(input: &Value, result: Option<&ToolOutput>) -> Option<String> {
+    let command = value_as_trimmed_str(input, "command")?;
+    let cwd = result
+        .and_then(ToolOutput::data)
+        .and_then(|data| value_as_trimmed_str(data, "cwd"))
+        .or_else(|| value_as_trimmed_str(input, "cwd"))
+        .filter(|cwd| *cwd != ".");
+    Some(match cwd {
+        Some(cwd) => format!("[{cwd}] {command}"),
+        None => command.to_string(),
+    })
+}
+
 fn tool_key_arg(name: &str, input: &Value) -> Option<String> {
     match name {
-        "bash" => value_as_trimmed_str(input, "command").map(str::to_string),
-        "read" | "write" | "edit" => value_as_trimmed_str(input, "file_path")
-            .or_else(|| value_as_trimmed_str(input, "path"))
-            .map(str::to_string),
+        "bash" => bash_key_arg(input, None),
+        "read" | "write" | "edit" | "outline" | "notebook_read" | "notebook_edit" => {
+            value_as_trimmed_str(input, "file_path")
+                .or_else(|| value_as_trimmed_str(input, "path"))
+                .map(str::to_string)
+        }
         "apply_patch" => {
             value_as_trimmed_str(input, "patch").and_then(summarize_apply_patch_targets)
         }
@@ -266,6 +286,12 @@ const SPINNER_FRAMES: &[&str] = &["◐", "◓", "◑", "◒"];
 /// Every cell has a unique `id` for addressing and an optional `created_at`
 /// timestamp for ordering/display.
 #[inline]
+/// Short model name for assistant badges: the model id without its
+/// `provider:` prefix.
+fn model_badge(model: &str) -> &str {
+    model.rsplit_once(':').map_or(model, |(_, bare)| bare)
+}
+
 fn streaming_discriminator(content_len: usize, is_streaming: bool, is_interrupted: bool) -> usize {
     (content_len << 2) | (usize::from(is_streaming) << 1) | usize::from(is_interrupted)
 }
@@ -287,6 +313,26 @@ pub struct ChildToolEntry {
     pub state: ChildToolState,
 }
 
+/// Streaming text and token usage relayed live from a running `invoke_subagent`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ChildProgress {
+    /// Tail of the child's streamed assistant text.
+    pub text: String,
+    /// Cumulative child token usage as `(input, output)`.
+    pub usage: Option<(u64, u64)>,
+    /// Whether the nested child activity is folded into a one-line summary.
+    pub collapsed: bool,
+}
+
+/// Inline output view of a bash tool cell: at most `max_lines` rows under
+/// the header, scrolled `scroll` rows up from the tail. Kept to `u16`s so
+/// the tool variant stays small.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InlineOutput {
+    pub max_lines: u16,
+    pub scroll: u16,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum HistoryCell {
     /// User input message.
@@ -297,6 +343,8 @@ pub enum HistoryCell {
         content: String,
         is_interrupted: bool,
         image_paths: Vec<String>,
+        /// Pinned to the thread's context (shown with a 📌 badge).
+        pinned: bool,
     },
 
     /// Assistant response.
@@ -304,12 +352,17 @@ pub enum HistoryCell {
     /// During streaming, `content` accumulates deltas.
     /// `is_streaming` indicates if more content is expected.
     /// `is_interrupted` indicates if streaming was cancelled by user.
+    /// `model` names the model that produced the reply, shown as a badge
+    /// once the reply is finalized. `pinned` marks replies pinned to the
+    /// thread's context.
     Assistant {
         id: CellId,
         created_at: DateTime<Utc>,
         content: String,
         is_streaming: bool,
         is_interrupted: bool,
+        model: Option<String>,
+        pinned: bool,
     },
 
     /// Tool invocation with state and optional result.
@@ -324,12 +377,16 @@ pub enum HistoryCell {
         output_delta: Option<String>,
         /// Live child tool activity relayed from a running `invoke_subagent`.
         child_tools: Vec<ChildToolEntry>,
+        /// Live child text/usage relayed from a running `invoke_subagent`.
+        child_progress: Box<ChildProgress>,
         state: ToolState,
         started_at: DateTime<Utc>,
         /// Timestamp when the tool finished (Done, Error, or Cancelled).
         completed_at: Option<DateTime<Utc>>,
         /// Some when tool has finished (Done or Error).
         result: Option<ToolOutput>,
+        /// Shows command output under the header (bash cells only).
+        inline_output: Option<InlineOutput>,
     },
 
     /// System message or informational banner.
@@ -339,6 +396,22 @@ pub enum HistoryCell {
         content: String,
     },
 
+    /// Turn failure with its classified kind and provider metadata.
+    ///
+    /// `retry_available` shows the inline retry hint; it is cleared once the
+    /// user retries or starts another turn.
+    Error {
+        id: CellId,
+        created_at: DateTime<Utc>,
+        kind: ErrorKind,
+        message: String,
+        /// HTTP status of the failed provider response, when known.
+        http_status: Option<u16>,
+        /// Provider request ID, useful when reporting the failure upstream.
+        request_id: Option<String>,
+        retry_available: bool,
+    },
+
     /// Thinking block (extended thinking from the model).
     ///
     /// During streaming, `content` accumulates deltas and `replay` is None.
@@ -364,6 +437,26 @@ pub enum HistoryCell {
         duration: std::time::Duration,
         tool_count: usize,
     },
+
+    /// Files created/modified/deleted during a turn, pushed when it ends.
+    FileChanges {
+        id: CellId,
+        created_at: DateTime<Utc>,
+        changes: Vec<FileChange>,
+    },
+
+    /// One speaker's turn in a `/duel` discussion.
+    ///
+    /// `second` selects the accent of the speaker who replies (the opener
+    /// uses the first). `is_pending` shows a spinner until the reply lands.
+    Duel {
+        id: CellId,
+        created_at: DateTime<Utc>,
+        speaker: String,
+        second: bool,
+        content: String,
+        is_pending: bool,
+    },
 }
 
 impl HistoryCell {
@@ -374,8 +467,136 @@ impl HistoryCell {
             HistoryCell::Assistant { id, .. } => *id,
             HistoryCell::Tool { id, .. } => *id,
             HistoryCell::System { id, .. } => *id,
+            HistoryCell::Error { id, .. } => *id,
             HistoryCell::Thinking { id, .. } => *id,
             HistoryCell::Timing { id, .. } => *id,
+            HistoryCell::FileChanges { id, .. } => *id,
+            HistoryCell::Duel { id, .. } => *id,
+        }
+    }
+
+    /// Returns when the cell was created (for tools, when the call started).
+    pub fn created_at(&self) -> DateTime<Utc> {
+        match self {
+            HistoryCell::User { created_at, .. }
+            | HistoryCell::Assistant { created_at, .. }
+            | HistoryCell::Tool { created_at, .. }
+            | HistoryCell::System { created_at, .. }
+            | HistoryCell::Error { created_at, .. }
+            | HistoryCell::Thinking { created_at, .. }
+            | HistoryCell::Timing { created_at, .. }
+            | HistoryCell::FileChanges { created_at, .. }
+            | HistoryCell::Duel { created_at, .. } => *created_at,
+        }
+    }
+
+    /// Returns when the cell's work ended: a finished tool's completion time,
+    /// otherwise its creation time.
+    pub fn finished_at(&self) -> DateTime<Utc> {
+        match self {
+            HistoryCell::Tool {
+                completed_at: Some(completed_at),
+                ..
+            } => *completed_at,
+            _ => self.created_at(),
+        }
+    }
+
+    /// Rough estimate of the memory held by this cell (inline size plus
+    /// owned text and tool payloads), for the `/stats` memory report.
+    pub fn estimated_bytes(&self) -> usize {
+        let heap = match self {
+            HistoryCell::User {
+                content,
+                image_paths,
+                ..
+            } => content.len() + image_paths.iter().map(String::len).sum::<usize>(),
+            HistoryCell::Assistant { content, model, .. } => {
+                content.len() + model.as_ref().map_or(0, String::len)
+            }
+            HistoryCell::System { content, .. } | HistoryCell::Thinking { content, .. } => {
+                content.len()
+            }
+            HistoryCell::Error {
+                message,
+                request_id,
+                ..
+            } => message.len() + request_id.as_ref().map_or(0, String::len),
+            HistoryCell::Tool {
+                tool_use_id,
+                name,
+                input,
+                input_delta,
+                output_delta,
+                child_tools,
+                child_progress,
+                result,
+                ..
+            } => {
+                let json_len = |value: &Value| value.to_string().len();
+                let result_len = result.as_ref().map_or(0, |result| {
+                    let images = match result {
+                        ToolOutput::Success { images, .. } => {
+                            images.iter().map(|image| image.data.len()).sum()
+                        }
+                        _ => 0,
+                    };
+                    serde_json::to_string(result).map_or(0, |json| json.len()) + images
+                });
+                tool_use_id.len()
+                    + name.len()
+                    + json_len(input)
+                    + input_delta.as_ref().map_or(0, String::len)
+                    + output_delta.as_ref().map_or(0, String::len)
+                    + child_tools.len() * size_of::<ChildToolEntry>()
+                    + child_progress.text.len()
+                    + result_len
+            }
+            HistoryCell::Duel {
+                speaker, content, ..
+            } => speaker.len() + content.len(),
+            HistoryCell::Timing { .. } => 0,
+            HistoryCell::FileChanges { changes, .. } => changes
+                .iter()
+                .map(|change| {
+                    size_of::<FileChange>() + change.path.len() + change.tool_use_id.len()
+                })
+                .sum(),
+        };
+        size_of::<Self>() + heap
+    }
+
+    /// Overrides the creation time (e.g. with a timestamp from a thread
+    /// event when rebuilding history). Tool cells also move `started_at`.
+    pub fn set_created_at(&mut self, at: DateTime<Utc>) {
+        match self {
+            HistoryCell::Tool {
+                created_at,
+                started_at,
+                ..
+            } => {
+                *created_at = at;
+                *started_at = at;
+            }
+            HistoryCell::User { created_at, .. }
+            | HistoryCell::Assistant { created_at, .. }
+            | HistoryCell::System { created_at, .. }
+            | HistoryCell::Error { created_at, .. }
+            | HistoryCell::Thinking { created_at, .. }
+            | HistoryCell::Timing { created_at, .. }
+            | HistoryCell::FileChanges { created_at, .. }
+            | HistoryCell::Duel { created_at, .. } => *created_at = at,
+        }
+    }
+
+    /// Returns `true` while the cell is still streaming or running.
+    pub fn is_in_progress(&self) -> bool {
+        match self {
+            HistoryCell::Assistant { is_streaming, .. }
+            | HistoryCell::Thinking { is_streaming, .. } => *is_streaming,
+            HistoryCell::Tool { state, .. } => *state == ToolState::Running,
+            HistoryCell::Duel { is_pending, .. } => *is_pending,
+            _ => false,
         }
     }
 
@@ -387,6 +608,7 @@ impl HistoryCell {
             content: content.into(),
             is_interrupted: false,
             image_paths: Vec::new(),
+            pinned: false,
         }
     }
 
@@ -398,6 +620,7 @@ impl HistoryCell {
             content: content.into(),
             is_interrupted: false,
             image_paths,
+            pinned: false,
         }
     }
 
@@ -409,6 +632,8 @@ impl HistoryCell {
             content: content.into(),
             is_streaming: false,
             is_interrupted: false,
+            model: None,
+            pinned: false,
         }
     }
 
@@ -420,6 +645,8 @@ impl HistoryCell {
             content: content.into(),
             is_streaming: true,
             is_interrupted: false,
+            model: None,
+            pinned: false,
         }
     }
 
@@ -439,10 +666,12 @@ impl HistoryCell {
             input_delta: None,
             output_delta: None,
             child_tools: Vec::new(),
+            child_progress: Box::default(),
             state: ToolState::Running,
             started_at: now,
             completed_at: None,
             result: None,
+            inline_output: None,
         }
     }
 
@@ -455,6 +684,25 @@ impl HistoryCell {
         }
     }
 
+    /// Creates an error cell for a failed turn.
+    pub fn error(
+        kind: ErrorKind,
+        message: impl Into<String>,
+        http_status: Option<u16>,
+        request_id: Option<String>,
+        retry_available: bool,
+    ) -> Self {
+        HistoryCell::Error {
+            id: CellId::new(),
+            created_at: Utc::now(),
+            kind,
+            message: message.into(),
+            http_status,
+            request_id,
+            retry_available,
+        }
+    }
+
     /// Creates a new streaming thinking cell.
     pub fn thinking_streaming(content: impl Into<String>) -> Self {
         HistoryCell::Thinking {
@@ -477,6 +725,53 @@ impl HistoryCell {
         }
     }
 
+    /// Creates a file change summary cell.
+    pub fn file_changes(changes: Vec<FileChange>) -> Self {
+        HistoryCell::FileChanges {
+            id: CellId::new(),
+            created_at: Utc::now(),
+            changes,
+        }
+    }
+
+    /// Creates a finished `/duel` turn cell.
+    pub fn duel(speaker: impl Into<String>, second: bool, content: impl Into<String>) -> Self {
+        HistoryCell::Duel {
+            id: CellId::new(),
+            created_at: Utc::now(),
+            speaker: speaker.into(),
+            second,
+            content: content.into(),
+            is_pending: false,
+        }
+    }
+
+    /// Creates a `/duel` turn cell waiting for the speaker's reply.
+    pub fn duel_pending(speaker: impl Into<String>, second: bool) -> Self {
+        HistoryCell::Duel {
+            id: CellId::new(),
+            created_at: Utc::now(),
+            speaker: speaker.into(),
+            second,
+            content: String::new(),
+            is_pending: true,
+        }
+    }
+
+    /// Fills a pending `/duel` cell with the speaker's reply. No-op on
+    /// other cells.
+    pub fn finish_duel(&mut self, reply: impl Into<String>) {
+        if let HistoryCell::Duel {
+            content,
+            is_pending,
+            ..
+        } = self
+        {
+            *content = reply.into();
+            *is_pending = false;
+        }
+    }
+
     /// Appends text to an assistant cell's content.
     ///
     /// # Panics
@@ -503,6 +798,47 @@ impl HistoryCell {
         }
     }
 
+    /// Returns the role and text of a finished user or assistant cell, for
+    /// pinning. Assistant text leaves out any `<followups>` block so live
+    /// and restored cells produce the same text.
+    pub fn pinnable_message(&self) -> Option<(&'static str, String)> {
+        match self {
+            HistoryCell::User { content, .. } => Some(("user", content.trim().to_string())),
+            HistoryCell::Assistant {
+                content,
+                is_streaming: false,
+                ..
+            } => {
+                let (text, _) = zdx_engine::followups::extract_followups(content);
+                Some(("assistant", text.trim().to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns whether a user or assistant cell is pinned.
+    pub fn is_pinned(&self) -> bool {
+        matches!(
+            self,
+            HistoryCell::User { pinned: true, .. } | HistoryCell::Assistant { pinned: true, .. }
+        )
+    }
+
+    /// Marks a user or assistant cell as pinned. No-op for other cells.
+    pub fn set_pinned(&mut self, value: bool) {
+        if let HistoryCell::User { pinned, .. } | HistoryCell::Assistant { pinned, .. } = self {
+            *pinned = value;
+        }
+    }
+
+    /// Records the model that produced an assistant cell. No-op for other
+    /// cells.
+    pub fn set_model(&mut self, new_model: Option<String>) {
+        if let HistoryCell::Assistant { model, .. } = self {
+            *model = new_model;
+        }
+    }
+
     /// Strips a trailing `<followups>` block from an assistant cell's content,
     /// returning the extracted suggestions. No-op for non-assistant cells.
     pub fn strip_followups(&mut self) -> Vec<String> {
@@ -568,6 +904,21 @@ impl HistoryCell {
         }
     }
 
+    /// Updates the input on a tool cell while its JSON is still streaming.
+    ///
+    /// Used for `ToolInputPartial` so the header's key argument (e.g. the
+    /// bash command) grows as it streams. Unlike `set_tool_input`, the
+    /// streaming preview is kept.
+    ///
+    /// # Panics
+    /// Panics if called on a non-tool cell.
+    pub fn set_tool_provisional_input(&mut self, new_input: serde_json::Value) {
+        match self {
+            HistoryCell::Tool { input, .. } => *input = new_input,
+            _ => panic!("set_tool_provisional_input called on non-tool cell"),
+        }
+    }
+
     /// Updates the streaming input preview on a tool cell.
     ///
     /// Used for tool input streaming before JSON is complete.
@@ -622,9 +973,11 @@ impl HistoryCell {
     /// Applies a streaming `ToolOutputDelta` chunk to a tool cell.
     ///
     /// For `invoke_subagent` cells the chunk is a compact JSON descriptor of a
-    /// child tool's lifecycle (`{"t":"start"|"input"|"done"|"error", …}`) and
-    /// updates the structured `child_tools` list. Any other cell — or a chunk
-    /// that isn't a recognized child-tool event — appends as raw output text.
+    /// child tool's lifecycle (`{"t":"start"|"input"|"done"|"error", …}`),
+    /// which updates the structured `child_tools` list, or of the child's
+    /// streamed text / cumulative usage (`{"t":"text"|"usage", …}`), which
+    /// updates `child_progress`. Any other cell — or a chunk that isn't a
+    /// recognized child event — appends as raw output text.
     ///
     /// # Panics
     /// Panics if called on a non-tool cell.
@@ -641,12 +994,48 @@ impl HistoryCell {
     /// Returns `true` when the chunk was a recognized child-tool event (and
     /// consumed), `false` when it should fall through to raw output append.
     fn apply_child_tool_delta(&mut self, chunk: &str) -> bool {
-        let HistoryCell::Tool { child_tools, .. } = self else {
+        /// Streamed child text kept for the live preview.
+        const MAX_CHILD_TEXT_BYTES: usize = 4096;
+
+        let HistoryCell::Tool {
+            child_tools,
+            child_progress,
+            ..
+        } = self
+        else {
             return false;
         };
         let Ok(value) = serde_json::from_str::<Value>(chunk) else {
             return false;
         };
+        match value.get("t").and_then(Value::as_str) {
+            Some("text") => {
+                let Some(delta) = value.get("delta").and_then(Value::as_str) else {
+                    return false;
+                };
+                let text = &mut child_progress.text;
+                text.push_str(delta);
+                if text.len() > MAX_CHILD_TEXT_BYTES * 2 {
+                    let mut cut = text.len() - MAX_CHILD_TEXT_BYTES;
+                    while !text.is_char_boundary(cut) {
+                        cut += 1;
+                    }
+                    text.drain(..cut);
+                }
+                return true;
+            }
+            Some("usage") => {
+                let (Some(input), Some(output)) = (
+                    value.get("input").and_then(Value::as_u64),
+                    value.get("output").and_then(Value::as_u64),
+                ) else {
+                    return false;
+                };
+                child_progress.usage = Some((input, output));
+                return true;
+            }
+            _ => {}
+        }
         let (Some(kind), Some(id)) = (
             value.get("t").and_then(Value::as_str),
             value.get("id").and_then(Value::as_str),
@@ -698,6 +1087,64 @@ impl HistoryCell {
         true
     }
 
+    /// Folds (`true`) or unfolds the nested child activity of a subagent tool
+    /// cell. No-op for other cells.
+    pub fn set_child_collapsed(&mut self, collapsed: bool) {
+        if let HistoryCell::Tool { child_progress, .. } = self {
+            child_progress.collapsed = collapsed;
+        }
+    }
+
+    /// Shows a bash cell's output under its header, at most `max_lines` rows
+    /// (0 hides it). No-op for other cells.
+    pub fn set_inline_output(&mut self, max_lines: usize) {
+        if let HistoryCell::Tool {
+            name,
+            inline_output,
+            ..
+        } = self
+            && name.eq_ignore_ascii_case("bash")
+        {
+            *inline_output = (max_lines > 0).then_some(InlineOutput {
+                max_lines: u16::try_from(max_lines).unwrap_or(u16::MAX),
+                scroll: 0,
+            });
+        }
+    }
+
+    /// Scrolls inline output by `delta` rows (positive = toward older output)
+    /// as rendered at `width`. Returns false when there is nothing to scroll
+    /// in that direction.
+    pub fn scroll_inline_output(&mut self, delta: isize, width: usize) -> bool {
+        let HistoryCell::Tool {
+            inline_output: Some(view),
+            result,
+            output_delta,
+            ..
+        } = self
+        else {
+            return false;
+        };
+        let Some(text) = command_output_text(result.as_ref(), output_delta.as_deref()) else {
+            return false;
+        };
+        let hidden = inline_output_rows(&text, width)
+            .len()
+            .saturating_sub(usize::from(view.max_lines));
+        let current = usize::from(view.scroll).min(hidden);
+        let scroll = current.saturating_add_signed(delta).min(hidden);
+        view.scroll = u16::try_from(scroll).unwrap_or(u16::MAX);
+        scroll != current
+    }
+
+    /// Overrides a finished tool cell's completion time (e.g. with the
+    /// timestamp of its recorded result). No-op for other cells.
+    pub fn set_tool_completed_at(&mut self, at: DateTime<Utc>) {
+        if let HistoryCell::Tool { completed_at, .. } = self {
+            *completed_at = Some(at);
+        }
+    }
+
     /// Sets the result on a tool cell and updates state to Done or Error.
     ///
     /// # Panics
@@ -780,6 +1227,29 @@ impl HistoryCell {
         }
     }
 
+    /// Returns true if this is an error cell still offering a retry.
+    pub fn is_retryable_error(&self) -> bool {
+        matches!(
+            self,
+            HistoryCell::Error {
+                retry_available: true,
+                ..
+            }
+        )
+    }
+
+    /// Hides the retry hint on an error cell.
+    ///
+    /// Only affects Error cells.
+    pub fn disarm_retry(&mut self) {
+        if let HistoryCell::Error {
+            retry_available, ..
+        } = self
+        {
+            *retry_available = false;
+        }
+    }
+
     /// Marks a user cell as interrupted (request cancelled before any response).
     ///
     /// Only affects User cells.
@@ -803,6 +1273,7 @@ impl HistoryCell {
                 content,
                 is_interrupted,
                 image_paths,
+                pinned,
                 ..
             } => {
                 let prefix = "│ ";
@@ -846,6 +1317,17 @@ impl HistoryCell {
                     );
                 }
 
+                if *pinned && let Some(first) = lines.first_mut() {
+                    let at = usize::from(!first.spans.is_empty());
+                    first.spans.insert(
+                        at,
+                        StyledSpan {
+                            text: "📌 ".to_string(),
+                            style: Style::Timing,
+                        },
+                    );
+                }
+
                 // Append interrupted indicator to last line if request was cancelled
                 if *is_interrupted && let Some(last) = lines.last_mut() {
                     last.spans.push(StyledSpan {
@@ -859,6 +1341,8 @@ impl HistoryCell {
                 content,
                 is_streaming,
                 is_interrupted,
+                model,
+                pinned,
                 ..
             } => {
                 // Use markdown rendering for assistant responses
@@ -899,6 +1383,21 @@ impl HistoryCell {
                         style: Style::Interrupted,
                     });
                 }
+
+                if !*is_streaming && (model.is_some() || *pinned) {
+                    let badges = [
+                        model
+                            .as_deref()
+                            .map(|model| format!("[{}]", model_badge(model))),
+                        pinned.then(|| "📌 pinned".to_string()),
+                    ];
+                    lines.push(StyledLine {
+                        spans: vec![StyledSpan {
+                            text: badges.into_iter().flatten().collect::<Vec<_>>().join(" "),
+                            style: Style::Timing,
+                        }],
+                    });
+                }
                 lines
             }
             HistoryCell::Tool {
@@ -907,6 +1406,11 @@ impl HistoryCell {
                 input,
                 result,
                 child_tools,
+                child_progress,
+                started_at,
+                completed_at,
+                output_delta,
+                inline_output,
                 ..
             } => {
                 let mut lines = Vec::new();
@@ -938,13 +1442,22 @@ impl HistoryCell {
                     },
                 ];
 
-                if let Some(key_arg) = tool_key_arg(name, input) {
+                let badges = bash_badges(name, result.as_ref(), *started_at, *completed_at);
+                let badges_width = badges.iter().map(|s| ratatui_width(&s.text)).sum::<usize>();
+
+                let key_arg = if name == "bash" {
+                    bash_key_arg(input, result.as_ref())
+                } else {
+                    tool_key_arg(name, input)
+                };
+                if let Some(key_arg) = key_arg {
                     // icon(1-2) + space(1) + name + double-space(2)
                     let used_width = header_spans
                         .iter()
                         .map(|s| ratatui_width(&s.text))
                         .sum::<usize>()
-                        + 2;
+                        + 2
+                        + badges_width;
                     let remaining = width.saturating_sub(used_width).max(4);
                     let truncated_arg = truncate_with_ellipsis(&key_arg, remaining);
                     header_spans.push(StyledSpan {
@@ -957,68 +1470,32 @@ impl HistoryCell {
                     });
                 }
 
+                header_spans.extend(badges);
                 lines.push(StyledLine {
                     spans: header_spans,
                 });
 
-                // Live child subagent tool activity, rendered as a small tree
-                // under the header. Capped to the most recent rows.
-                if !child_tools.is_empty() {
-                    const MAX_CHILD_ROWS: usize = 5;
-                    let total = child_tools.len();
-                    let skip = total.saturating_sub(MAX_CHILD_ROWS);
-                    if skip > 0 {
-                        lines.push(StyledLine {
-                            spans: vec![StyledSpan {
-                                text: format!("… {skip} more"),
-                                style: Style::ToolOutput,
-                            }],
-                        });
-                    }
-                    for (idx, entry) in child_tools.iter().enumerate().skip(skip) {
-                        let is_last = idx + 1 == total;
-                        let branch = if is_last { "└─ " } else { "├─ " };
-                        let (glyph, glyph_style) = match entry.state {
-                            ChildToolState::Running => (
-                                SPINNER_FRAMES[spinner_frame % SPINNER_FRAMES.len()].to_string(),
-                                Style::ToolRunning,
-                            ),
-                            ChildToolState::Done => ("✓".to_string(), Style::ToolSuccess),
-                            ChildToolState::Error => ("✗".to_string(), Style::ToolError),
-                        };
-                        let mut spans = vec![
-                            StyledSpan {
-                                text: branch.to_string(),
-                                style: Style::Plain,
-                            },
-                            StyledSpan {
-                                text: glyph,
-                                style: glyph_style,
-                            },
-                            StyledSpan {
-                                text: " ".to_string(),
-                                style: Style::Plain,
-                            },
-                            StyledSpan {
-                                text: entry.name.clone(),
-                                style: Style::ToolStatus,
-                            },
-                        ];
-                        if let Some(arg) = entry.key_arg.as_deref().filter(|arg| !arg.is_empty()) {
-                            let used_width =
-                                spans.iter().map(|s| ratatui_width(&s.text)).sum::<usize>() + 2;
-                            let remaining = width.saturating_sub(used_width).max(4);
-                            spans.push(StyledSpan {
-                                text: "  ".to_string(),
-                                style: Style::Plain,
-                            });
-                            spans.push(StyledSpan {
-                                text: truncate_with_ellipsis(arg, remaining),
-                                style: Style::ToolOutput,
-                            });
-                        }
-                        lines.push(StyledLine { spans });
-                    }
+                if let Some(view) = inline_output
+                    && *state != ToolState::Running
+                    && let Some(text) =
+                        command_output_text(result.as_ref(), output_delta.as_deref())
+                {
+                    lines.extend(render_inline_output(&text, *view, width));
+                }
+
+                lines.extend(render_child_activity(
+                    child_tools,
+                    child_progress,
+                    *state == ToolState::Running,
+                    width,
+                    spinner_frame,
+                ));
+
+                if *state == ToolState::Done
+                    && name.eq_ignore_ascii_case("run_tests")
+                    && let Some(data) = result.as_ref().and_then(|res| res.data())
+                {
+                    lines.extend(test_summary_lines(data, width));
                 }
 
                 // Live `input_delta` / `output_delta` streaming is rendered only
@@ -1060,6 +1537,21 @@ impl HistoryCell {
                     false,
                 )
             }
+            HistoryCell::Error {
+                kind,
+                message,
+                http_status,
+                request_id,
+                retry_available,
+                ..
+            } => render_error_cell(
+                kind,
+                message,
+                *http_status,
+                request_id.as_deref(),
+                *retry_available,
+                width,
+            ),
             HistoryCell::Thinking {
                 content,
                 is_streaming,
@@ -1149,6 +1641,14 @@ impl HistoryCell {
                     }],
                 }]
             }
+            HistoryCell::FileChanges { changes, .. } => file_changes_lines(changes, width),
+            HistoryCell::Duel {
+                speaker,
+                second,
+                content,
+                is_pending,
+                ..
+            } => duel_lines(speaker, *second, content, *is_pending, width, spinner_frame),
         }
     }
 
@@ -1162,8 +1662,11 @@ impl HistoryCell {
             HistoryCell::Assistant { .. } => true,
             HistoryCell::Tool { state, .. } => *state != ToolState::Running,
             HistoryCell::System { .. } => true,
+            HistoryCell::Error { .. } => true,
             HistoryCell::Thinking { .. } => true,
             HistoryCell::Timing { .. } => true,
+            HistoryCell::FileChanges { .. } => true,
+            HistoryCell::Duel { is_pending, .. } => !*is_pending,
         }
     }
 
@@ -1177,15 +1680,21 @@ impl HistoryCell {
                 content,
                 is_interrupted,
                 image_paths,
+                pinned,
                 ..
             } => {
-                // Include is_interrupted and image_paths len in discriminator to invalidate cache when marked
-                (content.len() << 8) | (image_paths.len() << 1) | usize::from(*is_interrupted)
+                // Include is_interrupted, pinned, and image_paths len in discriminator to invalidate cache when marked
+                (content.len() << 9)
+                    | (usize::from(*pinned) << 8)
+                    | (image_paths.len() << 1)
+                    | usize::from(*is_interrupted)
             }
             HistoryCell::Assistant {
                 content,
                 is_streaming,
                 is_interrupted,
+                model,
+                pinned,
                 ..
             } => {
                 if *is_streaming {
@@ -1199,11 +1708,30 @@ impl HistoryCell {
                     let has_content = usize::from(!content.is_empty());
                     usize::from(*is_interrupted) | (1 << 1) | (has_content << 2) | (committed << 3)
                 } else {
-                    streaming_discriminator(content.len(), false, *is_interrupted)
+                    (streaming_discriminator(content.len(), false, *is_interrupted) << 2)
+                        | (usize::from(*pinned) << 1)
+                        | usize::from(model.is_some())
                 }
             }
-            HistoryCell::Tool { result, .. } => usize::from(result.is_some()),
+            HistoryCell::Tool {
+                result,
+                child_progress,
+                inline_output,
+                ..
+            } => {
+                let inline = inline_output.map_or(0, |view| {
+                    (usize::from(view.scroll) << 16) | usize::from(view.max_lines)
+                });
+                (inline << 2)
+                    | (usize::from(child_progress.collapsed) << 1)
+                    | usize::from(result.is_some())
+            }
             HistoryCell::System { content, .. } => content.len(),
+            HistoryCell::Error {
+                message,
+                retry_available,
+                ..
+            } => (message.len() << 1) | usize::from(*retry_available),
             HistoryCell::Thinking {
                 content,
                 is_streaming,
@@ -1231,6 +1759,12 @@ impl HistoryCell {
                 // Duration doesn't change, use millis as discriminator
                 duration.as_millis() as usize
             }
+            HistoryCell::FileChanges { changes, .. } => changes.len(),
+            HistoryCell::Duel {
+                content,
+                is_pending,
+                ..
+            } => (content.len() << 1) | usize::from(*is_pending),
         }
     }
 
@@ -1263,6 +1797,488 @@ impl HistoryCell {
     }
 }
 
+/// `  exit N · 1.2s` badges for a finished bash cell's header.
+fn bash_badges(
+    name: &str,
+    result: Option<&ToolOutput>,
+    started_at: DateTime<Utc>,
+    completed_at: Option<DateTime<Utc>>,
+) -> Vec<StyledSpan> {
+    let Some(data) = result.and_then(ToolOutput::data) else {
+        return Vec::new();
+    };
+    if !name.eq_ignore_ascii_case("bash") {
+        return Vec::new();
+    }
+    let timed_out = data
+        .get("timed_out")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let exit_code = data.get("exit_code").and_then(Value::as_i64);
+    let mut spans = Vec::new();
+    let status = if timed_out {
+        Some(("timed out".to_string(), Style::ToolError))
+    } else {
+        exit_code.map(|code| {
+            let style = if code == 0 {
+                Style::Timing
+            } else {
+                Style::ToolError
+            };
+            (format!("exit {code}"), style)
+        })
+    };
+    if let Some((text, style)) = status {
+        spans.push(StyledSpan {
+            text: format!("  {text}"),
+            style,
+        });
+    }
+    if let Some(completed_at) = completed_at
+        && let Ok(elapsed) = completed_at.signed_duration_since(started_at).to_std()
+    {
+        let separator = if spans.is_empty() { "  " } else { " · " };
+        spans.push(StyledSpan {
+            text: format!("{separator}{}", format_span_duration(elapsed)),
+            style: Style::Timing,
+        });
+    }
+    spans
+}
+
+/// stdout followed by stderr from a finished command, or the streamed
+/// output of an interrupted one. None when there is no output.
+fn command_output_text(result: Option<&ToolOutput>, output_delta: Option<&str>) -> Option<String> {
+    let text = match result.and_then(ToolOutput::data) {
+        Some(data) => {
+            let stream = |key: &str| data.get(key).and_then(Value::as_str).unwrap_or("");
+            let (stdout, stderr) = (stream("stdout"), stream("stderr"));
+            if stdout.is_empty() || stderr.is_empty() {
+                format!("{stdout}{stderr}")
+            } else {
+                format!("{}\n{stderr}", stdout.trim_end_matches('\n'))
+            }
+        }
+        None => output_delta.unwrap_or_default().to_string(),
+    };
+    let text = text.trim_end();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Output rows with a `│ ` gutter, hard-wrapped at `width`.
+fn inline_output_rows(text: &str, width: usize) -> Vec<StyledLine> {
+    let gutter = StyledSpan {
+        text: "│ ".to_string(),
+        style: Style::ToolBracket,
+    };
+    parse_ansi(text, Style::ToolOutput)
+        .iter()
+        .flat_map(|line| wrap_output_line(line, width, &gutter))
+        .collect()
+}
+
+/// The visible window of inline output plus, when rows are hidden, a footer
+/// with the position.
+fn render_inline_output(text: &str, view: InlineOutput, width: usize) -> Vec<StyledLine> {
+    let mut rows = inline_output_rows(text, width);
+    let total = rows.len();
+    let max_lines = usize::from(view.max_lines);
+    if total <= max_lines {
+        return rows;
+    }
+    let start = total - max_lines - usize::from(view.scroll).min(total - max_lines);
+    let end = start + max_lines;
+    rows.truncate(end);
+    let mut lines = rows.split_off(start);
+    lines.push(StyledLine {
+        spans: vec![StyledSpan {
+            text: format!(
+                "└ rows {}–{end} of {total} · scroll here · click header for full output",
+                start + 1
+            ),
+            style: Style::Timing,
+        }],
+    });
+    lines
+}
+
+/// Renders the live child activity of a subagent tool cell under its header.
+///
+/// Expanded: a small tree of the most recent child tools, the tail of the
+/// child's streamed text (while running), and its token usage. Collapsed: a
+/// single summary line with the tool count, current tool, and usage.
+fn render_child_activity(
+    child_tools: &[ChildToolEntry],
+    progress: &ChildProgress,
+    is_running: bool,
+    width: usize,
+    spinner_frame: usize,
+) -> Vec<StyledLine> {
+    const MAX_CHILD_ROWS: usize = 5;
+    const MAX_TEXT_ROWS: usize = 2;
+
+    let mut lines = Vec::new();
+    if child_tools.is_empty() && progress.text.is_empty() && progress.usage.is_none() {
+        return lines;
+    }
+    let usage = progress.usage.map(|(input, output)| {
+        format!(
+            "{} in · {} out",
+            format_token_count(input),
+            format_token_count(output)
+        )
+    });
+
+    if progress.collapsed {
+        let mut parts = vec![match child_tools.len() {
+            1 => "1 tool".to_string(),
+            n => format!("{n} tools"),
+        }];
+        if is_running
+            && let Some(current) = child_tools
+                .iter()
+                .rev()
+                .find(|entry| entry.state == ChildToolState::Running)
+        {
+            parts.push(
+                match current.key_arg.as_deref().filter(|arg| !arg.is_empty()) {
+                    Some(arg) => format!("{} {arg}", current.name),
+                    None => current.name.clone(),
+                },
+            );
+        }
+        parts.extend(usage);
+        lines.push(StyledLine {
+            spans: vec![StyledSpan {
+                text: truncate_with_ellipsis(&format!("└─ ▸ {}", parts.join(" · ")), width),
+                style: Style::ToolOutput,
+            }],
+        });
+        return lines;
+    }
+
+    let total = child_tools.len();
+    let skip = total.saturating_sub(MAX_CHILD_ROWS);
+    if skip > 0 {
+        lines.push(StyledLine {
+            spans: vec![StyledSpan {
+                text: format!("… {skip} more"),
+                style: Style::ToolOutput,
+            }],
+        });
+    }
+    let text_rows: Vec<&str> = if is_running {
+        let rows: Vec<&str> = progress
+            .text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect();
+        rows[rows.len().saturating_sub(MAX_TEXT_ROWS)..].to_vec()
+    } else {
+        Vec::new()
+    };
+    let has_trailer = !text_rows.is_empty() || usage.is_some();
+    for (idx, entry) in child_tools.iter().enumerate().skip(skip) {
+        let is_last = idx + 1 == total && !has_trailer;
+        let branch = if is_last { "└─ " } else { "├─ " };
+        let (glyph, glyph_style) = match entry.state {
+            ChildToolState::Running => (
+                SPINNER_FRAMES[spinner_frame % SPINNER_FRAMES.len()].to_string(),
+                Style::ToolRunning,
+            ),
+            ChildToolState::Done => ("✓".to_string(), Style::ToolSuccess),
+            ChildToolState::Error => ("✗".to_string(), Style::ToolError),
+        };
+        let mut spans = vec![
+            StyledSpan {
+                text: branch.to_string(),
+                style: Style::Plain,
+            },
+            StyledSpan {
+                text: glyph,
+                style: glyph_style,
+            },
+            StyledSpan {
+                text: " ".to_string(),
+                style: Style::Plain,
+            },
+            StyledSpan {
+                text: entry.name.clone(),
+                style: Style::ToolStatus,
+            },
+        ];
+        if let Some(arg) = entry.key_arg.as_deref().filter(|arg| !arg.is_empty()) {
+            let used_width = spans.iter().map(|s| ratatui_width(&s.text)).sum::<usize>() + 2;
+            let remaining = width.saturating_sub(used_width).max(4);
+            spans.push(StyledSpan {
+                text: "  ".to_string(),
+                style: Style::Plain,
+            });
+            spans.push(StyledSpan {
+                text: truncate_with_ellipsis(arg, remaining),
+                style: Style::ToolOutput,
+            });
+        }
+        lines.push(StyledLine { spans });
+    }
+    for row in text_rows {
+        lines.push(StyledLine {
+            spans: vec![
+                StyledSpan {
+                    text: "│  ".to_string(),
+                    style: Style::Plain,
+                },
+                StyledSpan {
+                    text: truncate_with_ellipsis(row, width.saturating_sub(3).max(4)),
+                    style: Style::ToolOutput,
+                },
+            ],
+        });
+    }
+    if let Some(usage) = usage {
+        lines.push(StyledLine {
+            spans: vec![
+                StyledSpan {
+                    text: "└─ ".to_string(),
+                    style: Style::Plain,
+                },
+                StyledSpan {
+                    text: format!("tokens: {usage}"),
+                    style: Style::ToolOutput,
+                },
+            ],
+        });
+    }
+    lines
+}
+
+/// Formats a token count compactly (e.g. "12.5k", "1.2M").
+fn format_token_count(count: u64) -> String {
+    if count >= 1_000_000 {
+        format!("{:.1}M", count as f64 / 1_000_000.0)
+    } else if count >= 1_000 {
+        format!("{:.1}k", count as f64 / 1_000.0)
+    } else {
+        count.to_string()
+    }
+}
+
+/// Retry hint shown under a retryable error cell.
+const ERROR_RETRY_HINT: &str = "press r to retry turn";
+
+fn error_kind_label(kind: &ErrorKind) -> &'static str {
+    match kind {
+        ErrorKind::Transport => "transport error",
+        ErrorKind::Request => "request error",
+        ErrorKind::HttpStatus => "http error",
+        ErrorKind::Timeout => "timeout",
+        ErrorKind::Parse => "parse error",
+        ErrorKind::ApiError => "api error",
+        ErrorKind::StreamStall => "stream stalled",
+        ErrorKind::Internal => "internal error",
+    }
+}
+
+/// Renders an error cell: the message, a metadata line (kind, HTTP status,
+/// request ID), and the retry hint when available.
+fn render_error_cell(
+    kind: &ErrorKind,
+    message: &str,
+    http_status: Option<u16>,
+    request_id: Option<&str>,
+    retry_available: bool,
+    width: usize,
+) -> Vec<StyledLine> {
+    let prefix = "Error: ";
+    let indent = " ".repeat(ratatui_width(prefix));
+    let mut lines = render_prefixed_content(
+        prefix,
+        message,
+        width,
+        Style::ErrorPrefix,
+        Style::Error,
+        false,
+    );
+
+    let mut meta = vec![error_kind_label(kind).to_string()];
+    if let Some(status) = http_status {
+        meta.push(format!("HTTP {status}"));
+    }
+    if let Some(request_id) = request_id {
+        meta.push(format!("request {request_id}"));
+    }
+    lines.extend(render_prefixed_content(
+        &indent,
+        &meta.join(" · "),
+        width,
+        Style::Plain,
+        Style::System,
+        true,
+    ));
+
+    if retry_available {
+        lines.extend(render_prefixed_content(
+            &indent,
+            ERROR_RETRY_HINT,
+            width,
+            Style::Plain,
+            Style::ErrorHint,
+            true,
+        ));
+    }
+    lines
+}
+
+/// Renders a `/duel` turn: the speaker in its accent, then the reply as
+/// markdown behind an accent bar so the two voices stay apart.
+fn duel_lines(
+    speaker: &str,
+    second: bool,
+    content: &str,
+    is_pending: bool,
+    width: usize,
+    spinner_frame: usize,
+) -> Vec<StyledLine> {
+    let accent = if second { Style::DuelB } else { Style::DuelA };
+    let bar = "┃ ";
+    let mut header = vec![StyledSpan {
+        text: format!("{bar}{speaker}"),
+        style: accent,
+    }];
+    if is_pending {
+        header.push(StyledSpan {
+            text: format!("  {}", SPINNER_FRAMES[spinner_frame % SPINNER_FRAMES.len()]),
+            style: Style::ToolRunning,
+        });
+    }
+    let mut lines = vec![StyledLine { spans: header }];
+    if content.is_empty() {
+        return lines;
+    }
+
+    let body_width = width.saturating_sub(ratatui_width(bar)).max(1);
+    for line in crate::markdown::render_markdown(content, body_width) {
+        let mut spans = vec![StyledSpan {
+            text: bar.to_string(),
+            style: accent,
+        }];
+        spans.extend(line.spans);
+        lines.push(StyledLine { spans });
+    }
+    lines
+}
+
+/// Renders the end-of-turn file change summary: a header with totals, then
+/// one `A`/`M`/`D` line per file.
+fn file_changes_lines(changes: &[FileChange], width: usize) -> Vec<StyledLine> {
+    let span = |text: String, style: Style| StyledSpan { text, style };
+    let counts = |added: usize, removed: usize| {
+        let mut spans = Vec::new();
+        if added > 0 {
+            spans.push(span(format!(" +{added}"), Style::DiffAdded));
+        }
+        if removed > 0 {
+            spans.push(span(format!(" -{removed}"), Style::DiffRemoved));
+        }
+        spans
+    };
+
+    let files = if changes.len() == 1 { "file" } else { "files" };
+    let mut header = vec![span(
+        format!("Changed {} {files}", changes.len()),
+        Style::ToolStatus,
+    )];
+    header.extend(counts(
+        changes.iter().map(|change| change.added).sum(),
+        changes.iter().map(|change| change.removed).sum(),
+    ));
+    let mut lines = vec![StyledLine { spans: header }];
+
+    for change in changes {
+        let marker_style = match change.kind {
+            FileChangeKind::Created => Style::DiffAdded,
+            FileChangeKind::Modified => Style::ListBullet,
+            FileChangeKind::Deleted => Style::DiffRemoved,
+        };
+        let counts = counts(change.added, change.removed);
+        let counts_width: usize = counts.iter().map(|span| ratatui_width(&span.text)).sum();
+        let path_width = width.saturating_sub(4 + counts_width).max(1);
+        let mut spans = vec![
+            span("  ".to_string(), Style::Plain),
+            span(change.kind.marker().to_string(), marker_style),
+            span(" ".to_string(), Style::Plain),
+            span(
+                truncate_with_ellipsis(&change.path, path_width),
+                Style::Assistant,
+            ),
+        ];
+        spans.extend(counts);
+        lines.push(StyledLine { spans });
+    }
+    lines
+}
+
+/// Maximum failing tests listed under a `Run_Tests` cell.
+const MAX_TEST_FAILURE_LINES: usize = 5;
+
+/// Renders the `Run_Tests` result: a counts line, then one line per failing
+/// test (`✗ name  message`).
+fn test_summary_lines(data: &Value, width: usize) -> Vec<StyledLine> {
+    let span = |text: String, style: Style| StyledSpan { text, style };
+    let Some(counts) = data.get("counts") else {
+        return Vec::new();
+    };
+    let count = |key: &str| counts.get(key).and_then(Value::as_u64).unwrap_or(0);
+
+    let mut summary = vec![
+        span("  ".to_string(), Style::Plain),
+        span(format!("{} passed", count("passed")), Style::DiffAdded),
+    ];
+    if count("failed") > 0 {
+        summary.push(span(" · ".to_string(), Style::Timing));
+        summary.push(span(
+            format!("{} failed", count("failed")),
+            Style::DiffRemoved,
+        ));
+    }
+    if count("skipped") > 0 {
+        summary.push(span(" · ".to_string(), Style::Timing));
+        summary.push(span(format!("{} skipped", count("skipped")), Style::Timing));
+    }
+    let mut lines = vec![StyledLine { spans: summary }];
+
+    let failures = data
+        .get("failures")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for failure in failures.iter().take(MAX_TEST_FAILURE_LINES) {
+        let field = |key: &str| failure.get(key).and_then(Value::as_str).unwrap_or_default();
+        let text = if field("message").is_empty() {
+            field("name").to_string()
+        } else {
+            format!("{}  {}", field("name"), field("message"))
+        };
+        lines.push(StyledLine {
+            spans: vec![
+                span("  ✗ ".to_string(), Style::ToolError),
+                span(
+                    truncate_with_ellipsis(&text, width.saturating_sub(4).max(1)),
+                    Style::ToolOutput,
+                ),
+            ],
+        });
+    }
+    let hidden = failures.len().saturating_sub(MAX_TEST_FAILURE_LINES);
+    if hidden > 0 {
+        lines.push(StyledLine {
+            spans: vec![span(format!("  … {hidden} more"), Style::Timing)],
+        });
+    }
+    lines
+}
+
 fn render_thinking_markdown(prefix: &str, content: &str, width: usize) -> Vec<StyledLine> {
     if content.trim() == "<!-- -->" {
         return Vec::new();
@@ -1512,6 +2528,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_bash_badges_and_scrollable_inline_output() {
+        let mut cell =
+            HistoryCell::tool_running("1", "bash", serde_json::json!({"command": "make"}));
+        cell.set_inline_output(3);
+        let stdout = "line 1\nline 2\nline 3\nline 4\nline 5\n";
+        cell.set_tool_result(ToolOutput::success(serde_json::json!({
+            "stdout": stdout,
+            "stderr": "\u{1b}[31mfailed\u{1b}[0m\n",
+            "exit_code": 2,
+        })));
+        cell.set_tool_completed_at(cell.created_at() + chrono::Duration::milliseconds(1500));
+
+        let texts = |cell: &HistoryCell| -> Vec<String> {
+            cell.display_lines(80, 0)
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.text.as_str()).collect())
+                .collect()
+        };
+        let lines = texts(&cell);
+        assert_eq!(lines[0], "✓ bash  make  exit 2 · 1.5s");
+        assert_eq!(&lines[1..4], ["│ line 4", "│ line 5", "│ failed"]);
+        assert!(lines[4].starts_with("└ rows 4–6 of 6"));
+        let failed = &cell.display_lines(80, 0)[3].spans[1];
+        assert!(matches!(failed.style, Style::Ansi(ansi) if ansi.fg.is_some()));
+
+        assert!(!cell.scroll_inline_output(-1, 80), "already at the tail");
+        assert!(cell.scroll_inline_output(10, 80));
+        assert_eq!(texts(&cell)[1], "│ line 1");
+        assert!(!cell.scroll_inline_output(1, 80), "already at the top");
+    }
+
+    #[test]
+    fn test_bash_header_shows_effective_cwd() {
+        let mut cell = HistoryCell::tool_running(
+            "1",
+            "bash",
+            serde_json::json!({"command": "make", "cwd": "pkg/"}),
+        );
+        let header = |cell: &HistoryCell| -> String {
+            cell.display_lines(80, 0)[0]
+                .spans
+                .iter()
+                .map(|s| s.text.as_str())
+                .collect()
+        };
+        assert!(header(&cell).ends_with("bash  [pkg/] make"));
+        cell.set_tool_result(ToolOutput::success(
+            serde_json::json!({"stdout": "", "exit_code": 0, "cwd": "pkg"}),
+        ));
+        assert!(header(&cell).contains("bash  [pkg] make  exit 0"));
+    }
+
     #[test]
     fn test_tool_failure() {
         let mut cell = HistoryCell::tool_running(
@@ -1588,6 +2657,35 @@ mod tests {
         assert_eq!(lines[0].spans[0].text, "System: ");
     }
 
+    #[test]
+    fn test_error_cell_shows_metadata_and_retry_hint() {
+        let mut cell = HistoryCell::error(
+            ErrorKind::HttpStatus,
+            "HTTP 529: Overloaded",
+            Some(529),
+            Some("req_123".to_string()),
+            true,
+        );
+        let text = |cell: &HistoryCell| -> Vec<String> {
+            cell.display_lines(80, 0)
+                .iter()
+                .map(|line| line.spans.iter().map(|span| span.text.as_str()).collect())
+                .collect()
+        };
+
+        let lines = text(&cell);
+        assert_eq!(lines[0], "Error: HTTP 529: Overloaded");
+        assert_eq!(lines[1].trim(), "http error · HTTP 529 · request req_123");
+        assert_eq!(lines[2].trim(), ERROR_RETRY_HINT);
+        assert!(cell.is_retryable_error());
+
+        let before = cell.cache_discriminator();
+        cell.disarm_retry();
+        assert!(!cell.is_retryable_error());
+        assert_ne!(cell.cache_discriminator(), before);
+        assert_eq!(text(&cell).len(), 2);
+    }
+
     #[test]
     fn test_multiline_content() {
         let cell = HistoryCell::user("Line 1\nLine 2\nLine 3");
@@ -1875,6 +2973,41 @@ mod tests {
         // This mainly tests that clear() doesn't panic
     }
 
+    #[test]
+    fn test_wrap_cache_bytes_stay_within_budget_over_10k_cells() {
+        // Renders 10k cells end to end at two widths: the cache's own byte
+        // accounting must stay within its budget and keep recent cells warm.
+        // This checks the cache's estimate, not process memory.
+        const BUDGET: usize = 256 * 1024;
+        let cache = WrapCache::with_max_bytes(BUDGET);
+        let cells: Vec<HistoryCell> = (0..10_000)
+            .map(|i| {
+                HistoryCell::assistant(format!(
+                    "Message {i}: the quick brown fox jumps over the lazy dog, again and again."
+                ))
+            })
+            .collect();
+
+        let mut peak = 0;
+        for width in [80, 40] {
+            for cell in &cells {
+                let _ = cell.display_lines_cached(width, 0, &cache);
+                peak = peak.max(cache.stats().bytes);
+            }
+        }
+
+        let stats = cache.stats();
+        assert!(peak <= BUDGET + 4096, "peak {peak} over budget");
+        assert!(stats.evictions > 0);
+        assert!(stats.entries < cells.len());
+
+        // The most recently rendered cell is still a cache hit.
+        let last = cells.last().unwrap();
+        let entries = stats.entries;
+        let _ = last.display_lines_cached(40, 0, &cache);
+        assert_eq!(cache.stats().entries, entries);
+    }
+
     #[test]
     fn streaming_assistant_cache_key_stable_until_commit() {
         // Deltas that don't complete a line must not change the cache key, so
@@ -2089,6 +3222,75 @@ mod tests {
         assert_eq!(lines[0].spans[0].style, Style::Timing);
     }
 
+    #[test]
+    fn test_duel_cell_display() {
+        let text = |cell: &HistoryCell| -> Vec<String> {
+            cell.display_lines(40, 0)
+                .iter()
+                .map(|line| line.spans.iter().map(|span| span.text.as_str()).collect())
+                .collect()
+        };
+
+        let mut cell = HistoryCell::duel_pending("openai:gpt-5", true);
+        assert!(cell.is_in_progress());
+        assert_eq!(text(&cell), ["┃ openai:gpt-5  ◐"]);
+
+        cell.finish_duel("Spaces **win**.");
+        assert!(!cell.is_in_progress());
+        assert_eq!(text(&cell), ["┃ openai:gpt-5", "┃ Spaces win."]);
+        assert!(
+            cell.display_lines(40, 0)
+                .iter()
+                .flat_map(|line| &line.spans)
+                .any(|span| span.style == Style::DuelB)
+        );
+    }
+
+    #[test]
+    fn test_file_changes_cell_display() {
+        let change = |path: &str, kind, added, removed| FileChange {
+            path: path.to_string(),
+            kind,
+            added,
+            removed,
+            tool_use_id: "t1".to_string(),
+        };
+        let cell = HistoryCell::file_changes(vec![
+            change("src/lib.rs", FileChangeKind::Modified, 3, 1),
+            change("old.rs", FileChangeKind::Deleted, 0, 0),
+        ]);
+        let text: Vec<String> = cell
+            .display_lines(40, 0)
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.text.as_str()).collect())
+            .collect();
+        assert_eq!(
+            text,
+            [
+                "Changed 2 files +3 -1",
+                "  M src/lib.rs +3 -1",
+                "  D old.rs"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_tests_cell_shows_summary_and_failures() {
+        let mut cell = HistoryCell::tool_running("t1", "Run_Tests", serde_json::json!({}));
+        cell.set_tool_result(ToolOutput::success(serde_json::json!({
+            "passed": false,
+            "counts": {"passed": 12, "failed": 1, "skipped": 0},
+            "failures": [{"name": "parser::breaks", "message": "assertion failed"}],
+        })));
+        let text: Vec<String> = cell
+            .display_lines(80, 0)
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.text.as_str()).collect())
+            .collect();
+        assert_eq!(text[1], "  12 passed · 1 failed");
+        assert_eq!(text[2], "  ✗ parser::breaks  assertion failed");
+    }
+
     #[test]
     fn test_timing_cell_display_multiple_tools() {
         use std::time::Duration;
@@ -2300,6 +3502,55 @@ mod tests {
         );
     }
 
+    fn rendered_text(cell: &HistoryCell) -> Vec<String> {
+        cell.display_lines(80, 0)
+            .iter()
+            .map(|line| line.spans.iter().map(|span| span.text.as_str()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn apply_child_progress_tracks_text_and_usage() {
+        let mut cell =
+            HistoryCell::tool_running("parent", "invoke_subagent", serde_json::json!({}));
+        cell.apply_tool_output_delta(r#"{"t":"start","id":"c1","name":"grep"}"#);
+        cell.apply_tool_output_delta(r#"{"t":"text","delta":"Found the\nhandler in "}"#);
+        cell.apply_tool_output_delta(r#"{"t":"text","delta":"src/lib.rs"}"#);
+        cell.apply_tool_output_delta(r#"{"t":"usage","input":12500,"output":340}"#);
+
+        assert_eq!(output_delta_of(&cell), None);
+        let lines = rendered_text(&cell);
+        assert_eq!(
+            &lines[1..],
+            [
+                "├─ ◐ grep",
+                "│  Found the",
+                "│  handler in src/lib.rs",
+                "└─ tokens: 12.5k in · 340 out",
+            ]
+        );
+    }
+
+    #[test]
+    fn collapsed_child_activity_renders_single_summary_line() {
+        let mut cell =
+            HistoryCell::tool_running("parent", "invoke_subagent", serde_json::json!({}));
+        cell.apply_tool_output_delta(r#"{"t":"start","id":"c1","name":"read"}"#);
+        cell.apply_tool_output_delta(r#"{"t":"done","id":"c1"}"#);
+        cell.apply_tool_output_delta(r#"{"t":"start","id":"c2","name":"bash"}"#);
+        cell.apply_tool_output_delta(r#"{"t":"input","id":"c2","arg":"cargo test"}"#);
+        cell.apply_tool_output_delta(r#"{"t":"usage","input":900,"output":40}"#);
+        let expanded = cell.cache_discriminator();
+
+        cell.set_child_collapsed(true);
+        assert_ne!(cell.cache_discriminator(), expanded);
+        let lines = rendered_text(&cell);
+        assert_eq!(
+            &lines[1..],
+            ["└─ ▸ 2 tools · bash cargo test · 900 in · 40 out"]
+        );
+    }
+
     #[test]
     fn apply_tool_output_delta_appends_raw_for_non_subagent_tool() {
         let mut cell = HistoryCell::tool_running("t", "bash", serde_json::json!({}));
@@ -0,0 +1,350 @@
+//! Per-turn file change summary built from completed file tool calls.
+//!
+//! `Write`, `Edit`, `Apply_Patch`, and `Notebook_Edit` calls are folded per
+//! path into the summary shown at the end of a turn. Line counts come from the
+//! tool inputs: a `Write` over an existing file counts only the lines it wrote.
+
+use serde_json::Value;
+use zdx_engine::tools::apply_patch::Hunk;
+use zdx_engine::tools::apply_patch::parser::parse_patch;
+
+use crate::cell::{HistoryCell, ToolState};
+
+/// How a file changed over the turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileChangeKind {
+    Created,
+    Modified,
+    Deleted,
+}
+
+impl FileChangeKind {
+    /// One-letter marker (`A`/`M`/`D`, as in `git status`).
+    pub fn marker(self) -> &'static str {
+        match self {
+            FileChangeKind::Created => "A",
+            FileChangeKind::Modified => "M",
+            FileChangeKind::Deleted => "D",
+        }
+    }
+}
+
+/// Net change to one file over a turn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub path: String,
+    pub kind: FileChangeKind,
+    pub added: usize,
+    pub removed: usize,
+    /// Last tool call that touched the file (opened on click).
+    pub tool_use_id: String,
+}
+
+/// Collects file changes from the successful file tool calls in `cells`, in
+/// order of first touch. A file created and then deleted in the same turn
+/// is dropped.
+pub fn collect_file_changes(cells: &[HistoryCell]) -> Vec<FileChange> {
+    let mut changes: Vec<FileChange> = Vec::new();
+    for cell in cells {
+        let HistoryCell::Tool {
+            tool_use_id,
+            name,
+            input,
+            state: ToolState::Done,
+            result: Some(result),
+            ..
+        } = cell
+        else {
+            continue;
+        };
+        if !result.is_ok() {
+            continue;
+        }
+        let data = result.data();
+        for (path, kind, added, removed) in tool_file_changes(name, input, data) {
+            record(&mut changes, path, kind, added, removed, tool_use_id);
+        }
+    }
+    changes.retain(|change| !change.path.is_empty());
+    changes
+}
+
+/// Paths the successful `Read` and file-changing tool calls in `cells`
+/// touched, most recent first. Files whose last change deleted them are left
+/// out.
+pub fn collect_touched_files(cells: &[HistoryCell]) -> Vec<String> {
+    let mut touched: Vec<String> = Vec::new();
+    let mut deleted: Vec<String> = Vec::new();
+    for cell in cells.iter().rev() {
+        let HistoryCell::Tool {
+            name,
+            input,
+            state: ToolState::Done,
+            result: Some(result),
+            ..
+        } = cell
+        else {
+            continue;
+        };
+        if !result.is_ok() {
+            continue;
+        }
+        let paths: Vec<(String, FileChangeKind)> = if name.eq_ignore_ascii_case("read") {
+            input
+                .get("file_path")
+                .or_else(|| input.get("path"))
+                .and_then(Value::as_str)
+                .map(|path| vec![(path.to_string(), FileChangeKind::Modified)])
+                .unwrap_or_default()
+        } else {
+            tool_file_changes(name, input, result.data())
+                .into_iter()
+                .map(|(path, kind, ..)| (path, kind))
+                .collect()
+        };
+        for (path, kind) in paths {
+            if path.is_empty() || touched.contains(&path) || deleted.contains(&path) {
+                continue;
+            }
+            if kind == FileChangeKind::Deleted {
+                deleted.push(path);
+            } else {
+                touched.push(path);
+            }
+        }
+    }
+    touched
+}
+
+/// `(path, kind, added, removed)` for each file one tool call changed.
+fn tool_file_changes(
+    name: &str,
+    input: &Value,
+    data: Option<&Value>,
+) -> Vec<(String, FileChangeKind, usize, usize)> {
+    let path = || {
+        input
+            .get("file_path")
+            .or_else(|| input.get("path"))
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let text = |key: &str| input.get(key).and_then(Value::as_str).unwrap_or_default();
+
+    match name.to_ascii_lowercase().as_str() {
+        "write" => {
+            let created = data
+                .and_then(|data| data.get("created"))
+                .and_then(Value::as_bool)
+                .unwrap_or(false);
+            let kind = if created {
+                FileChangeKind::Created
+            } else {
+                FileChangeKind::Modified
+            };
+            vec![(path(), kind, text("content").lines().count(), 0)]
+        }
+        "edit" => {
+            let replacements = data
+                .and_then(|data| data.get("replacements"))
+                .and_then(Value::as_u64)
+                .map_or(1, |count| count as usize);
+            vec![(
+                path(),
+                FileChangeKind::Modified,
+                text("new_string").lines().count() * replacements,
+                text("old_string").lines().count() * replacements,
+            )]
+        }
+        "notebook_edit" => vec![(
+            path(),
+            FileChangeKind::Modified,
+            text("new_source").lines().count(),
+            0,
+        )],
+        "apply_patch" => parse_patch(text("patch"))
+            .map(|hunks| hunks.iter().flat_map(hunk_changes).collect())
+            .unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn hunk_changes(hunk: &Hunk) -> Vec<(String, FileChangeKind, usize, usize)> {
+    match hunk {
+        Hunk::AddFile { path, contents } => vec![(
+            path.display().to_string(),
+            FileChangeKind::Created,
+            contents.lines().count(),
+            0,
+        )],
+        Hunk::DeleteFile { path } => {
+            vec![(path.display().to_string(), FileChangeKind::Deleted, 0, 0)]
+        }
+        Hunk::UpdateFile {
+            path,
+            move_path,
+            chunks,
+        } => {
+            let added = chunks.iter().map(|chunk| chunk.new_lines.len()).sum();
+            let removed = chunks.iter().map(|chunk| chunk.old_lines.len()).sum();
+            match move_path {
+                Some(target) => vec![
+                    (path.display().to_string(), FileChangeKind::Deleted, 0, 0),
+                    (
+                        target.display().to_string(),
+                        FileChangeKind::Created,
+                        added,
+                        removed,
+                    ),
+                ],
+                None => vec![(
+                    path.display().to_string(),
+                    FileChangeKind::Modified,
+                    added,
+                    removed,
+                )],
+            }
+        }
+    }
+}
+
+fn record(
+    changes: &mut Vec<FileChange>,
+    path: String,
+    kind: FileChangeKind,
+    added: usize,
+    removed: usize,
+    tool_use_id: &str,
+) {
+    let Some(index) = changes.iter().position(|change| change.path == path) else {
+        changes.push(FileChange {
+            path,
+            kind,
+            added,
+            removed,
+            tool_use_id: tool_use_id.to_string(),
+        });
+        return;
+    };
+
+    let existing = &mut changes[index];
+    match (existing.kind, kind) {
+        (FileChangeKind::Created, FileChangeKind::Deleted) => {
+            changes.remove(index);
+            return;
+        }
+        (FileChangeKind::Deleted, FileChangeKind::Created) => {
+            existing.kind = FileChangeKind::Modified;
+        }
+        (FileChangeKind::Created, _) => {}
+        (_, kind) => existing.kind = kind,
+    }
+    existing.added += added;
+    existing.removed += removed;
+    tool_use_id.clone_into(&mut existing.tool_use_id);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zdx_engine::core::events::ToolOutput;
+
+    use super::*;
+
+    fn done(id: &str, name: &str, input: Value, data: Value) -> HistoryCell {
+        let mut cell = HistoryCell::tool_running(id, name, input);
+        cell.set_tool_result(ToolOutput::success(data));
+        cell
+    }
+
+    #[test]
+    fn folds_file_tools_per_path() {
+        let cells = vec![
+            done(
+                "t1",
+                "Write",
+                json!({"file_path": "new.rs", "content": "a\nb\nc\n"}),
+                json!({"created": true}),
+            ),
+            done(
+                "t2",
+                "Edit",
+                json!({"file_path": "new.rs", "old_string": "a", "new_string": "x\ny"}),
+                json!({"replacements": 1}),
+            ),
+            done(
+                "t3",
+                "Apply_Patch",
+                json!({"patch": "*** Begin Patch\n*** Update File: lib.rs\n@@\n-old\n+new\n+more\n*** Delete File: gone.rs\n*** End Patch"}),
+                json!({"applied": []}),
+            ),
+            done("t4", "Bash", json!({"command": "rm -rf target"}), json!({})),
+        ];
+
+        let changes = collect_file_changes(&cells);
+        let summary: Vec<_> = changes
+            .iter()
+            .map(|c| {
+                (
+                    c.path.as_str(),
+                    c.kind,
+                    c.added,
+                    c.removed,
+                    c.tool_use_id.as_str(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("new.rs", FileChangeKind::Created, 5, 1, "t2"),
+                ("lib.rs", FileChangeKind::Modified, 2, 1, "t3"),
+                ("gone.rs", FileChangeKind::Deleted, 0, 0, "t3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn created_then_deleted_files_are_dropped() {
+        let cells = vec![
+            done(
+                "t1",
+                "Write",
+                json!({"file_path": "tmp.txt", "content": "x"}),
+                json!({"created": true}),
+            ),
+            done(
+                "t2",
+                "Apply_Patch",
+                json!({"patch": "*** Begin Patch\n*** Delete File: tmp.txt\n*** End Patch"}),
+                json!({"applied": []}),
+            ),
+        ];
+        assert!(collect_file_changes(&cells).is_empty());
+    }
+
+    #[test]
+    fn touched_files_are_most_recent_first() {
+        let cells = vec![
+            done("t1", "Read", json!({"file_path": "a.rs"}), json!({})),
+            done(
+                "t2",
+                "Write",
+                json!({"file_path": "new.rs", "content": "x"}),
+                json!({"created": true}),
+            ),
+            done(
+                "t3",
+                "Apply_Patch",
+                json!({"patch": "*** Begin Patch\n*** Delete File: gone.rs\n*** End Patch"}),
+                json!({"applied": []}),
+            ),
+            done("t4", "Read", json!({"file_path": "gone.rs"}), json!({})),
+            done("t5", "Read", json!({"file_path": "a.rs"}), json!({})),
+        ];
+        // `gone.rs` was read after the delete, so it still counts.
+        assert_eq!(collect_touched_files(&cells), ["a.rs", "gone.rs", "new.rs"]);
+        assert_eq!(collect_touched_files(&cells[..3]), ["new.rs", "a.rs"]);
+    }
+}
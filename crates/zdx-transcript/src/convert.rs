@@ -5,6 +5,7 @@
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 
+use crate::ansi::{AnsiColor, AnsiStyle};
 use crate::cell::HistoryCell;
 use crate::style::{Style as TranscriptStyle, StyledLine};
 use crate::text::ratatui_text;
@@ -59,7 +60,22 @@ pub fn convert_style(style: TranscriptStyle) -> Style {
         TranscriptStyle::ToolStatus => Style::default()
             .fg(Color::White)
             .add_modifier(Modifier::BOLD),
-        TranscriptStyle::ToolError => Style::default().fg(Color::Red),
+        TranscriptStyle::ToolError | TranscriptStyle::Error | TranscriptStyle::DiffRemoved => {
+            Style::default().fg(Color::Red)
+        }
+        TranscriptStyle::DiffAdded => Style::default().fg(Color::Green),
+        TranscriptStyle::DuelA => Style::default()
+            .fg(Color::Blue)
+            .add_modifier(Modifier::BOLD),
+        TranscriptStyle::DuelB => Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+        TranscriptStyle::ErrorPrefix => {
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+        }
+        TranscriptStyle::ErrorHint => Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::ITALIC),
         TranscriptStyle::ToolRunning | TranscriptStyle::CodeInline | TranscriptStyle::CodeBlock => {
             Style::default().fg(Color::Cyan)
         }
@@ -97,5 +113,57 @@ pub fn convert_style(style: TranscriptStyle) -> Style {
         TranscriptStyle::ImagePlaceholder => Style::default()
             .fg(Color::Magenta)
             .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
+        TranscriptStyle::Ansi(ansi) => convert_ansi_style(ansi),
+    }
+}
+
+/// Converts an ANSI SGR style; unset colors keep the terminal defaults.
+pub fn convert_ansi_style(ansi: AnsiStyle) -> Style {
+    let mut style = Style::default();
+    if let Some(fg) = ansi.fg {
+        style = style.fg(convert_ansi_color(fg));
+    }
+    if let Some(bg) = ansi.bg {
+        style = style.bg(convert_ansi_color(bg));
+    }
+    let modifiers = [
+        (AnsiStyle::BOLD, Modifier::BOLD),
+        (AnsiStyle::DIM, Modifier::DIM),
+        (AnsiStyle::ITALIC, Modifier::ITALIC),
+        (AnsiStyle::UNDERLINE, Modifier::UNDERLINED),
+        (AnsiStyle::REVERSE, Modifier::REVERSED),
+    ];
+    for (attr, modifier) in modifiers {
+        if ansi.has(attr) {
+            style = style.add_modifier(modifier);
+        }
+    }
+    style
+}
+
+/// Maps the 16 base palette entries to named colors so the terminal theme
+/// applies; other entries and RGB pass through.
+fn convert_ansi_color(color: AnsiColor) -> Color {
+    match color {
+        AnsiColor::Indexed(index) => match index {
+            0 => Color::Black,
+            1 => Color::Red,
+            2 => Color::Green,
+            3 => Color::Yellow,
+            4 => Color::Blue,
+            5 => Color::Magenta,
+            6 => Color::Cyan,
+            7 => Color::Gray,
+            8 => Color::DarkGray,
+            9 => Color::LightRed,
+            10 => Color::LightGreen,
+            11 => Color::LightYellow,
+            12 => Color::LightBlue,
+            13 => Color::LightMagenta,
+            14 => Color::LightCyan,
+            15 => Color::White,
+            index => Color::Indexed(index),
+        },
+        AnsiColor::Rgb(r, g, b) => Color::Rgb(r, g, b),
     }
 }
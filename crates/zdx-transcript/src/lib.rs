@@ -6,18 +6,26 @@
 //! crate. The interactive pieces (selection, lazy virtualization, viewport
 //! state) stay in `zdx-tui`.
 
+mod ansi;
 mod build;
 mod cell;
+mod changes;
 mod convert;
 pub mod markdown;
 mod reasoning;
 mod style;
 pub mod text;
+mod timestamp;
 mod wrap;
 
+pub use ansi::{AnsiColor, AnsiStyle, parse_ansi, strip_ansi};
 pub use build::build_transcript_from_events;
-pub use cell::{CellId, ChildToolEntry, ChildToolState, HistoryCell, ToolState};
+pub use cell::{
+    CellId, ChildProgress, ChildToolEntry, ChildToolState, HistoryCell, InlineOutput, ToolState,
+};
+pub use changes::{FileChange, FileChangeKind, collect_file_changes, collect_touched_files};
 pub use convert::{cells_to_lines, convert_style, convert_styled_line};
 pub use reasoning::reasoning_display_text;
 pub use style::{Style, StyledLine, StyledSpan};
-pub use wrap::WrapCache;
+pub use timestamp::{TimestampMode, format_span_duration, timestamp_line, turn_duration};
+pub use wrap::{WrapCache, WrapCacheStats};
@@ -1,3 +1,5 @@
+use crate::ansi::AnsiStyle;
+
 /// A styled span of text (UI-agnostic).
 ///
 /// This is a minimal representation that can be converted to
@@ -65,6 +67,20 @@ pub enum Style {
     Thinking,
     /// Timing/duration message (muted, shows tool execution time).
     Timing,
+    /// Error cell prefix ("Error: ").
+    ErrorPrefix,
+    /// Error cell message.
+    Error,
+    /// Error cell retry hint.
+    ErrorHint,
+    /// Added line count / created file marker (green).
+    DiffAdded,
+    /// Removed line count / deleted file marker (red).
+    DiffRemoved,
+    /// `/duel` speaker label and bar, opening speaker.
+    DuelA,
+    /// `/duel` speaker label and bar, replying speaker.
+    DuelB,
 
     // Markdown styles
     /// Inline code (`code`).
@@ -95,4 +111,7 @@ pub enum Style {
     ListNumber,
     /// Image placeholder in message text (e.g., `[Image 1]`), clickable and styled distinctly.
     ImagePlaceholder,
+
+    /// Command output colored by its own ANSI SGR sequences.
+    Ansi(AnsiStyle),
 }
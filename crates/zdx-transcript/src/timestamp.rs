@@ -0,0 +1,192 @@
+//! Per-cell timestamp labels (`/timestamps`).
+//!
+//! Labels are rendered as a muted line above each cell, outside the cell's
+//! cached lines, so switching modes or letting relative times age never
+//! invalidates the wrap cache.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::cell::HistoryCell;
+use crate::style::{Style, StyledLine, StyledSpan};
+
+/// How cell timestamps are shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimestampMode {
+    /// No timestamps.
+    #[default]
+    Off,
+    /// Local wall-clock time (with the date when it isn't today).
+    Absolute,
+    /// Age relative to now (e.g. `5m ago`).
+    Relative,
+}
+
+impl TimestampMode {
+    /// Returns the mode `/timestamps` switches to next: off → absolute →
+    /// relative → off.
+    #[must_use]
+    pub fn next(self) -> Self {
+        match self {
+            TimestampMode::Off => TimestampMode::Absolute,
+            TimestampMode::Absolute => TimestampMode::Relative,
+            TimestampMode::Relative => TimestampMode::Off,
+        }
+    }
+
+    /// Short name for notices.
+    pub fn label(self) -> &'static str {
+        match self {
+            TimestampMode::Off => "off",
+            TimestampMode::Absolute => "absolute",
+            TimestampMode::Relative => "relative",
+        }
+    }
+}
+
+/// Formats a duration compactly (`850ms`, `4.2s`, `3m12s`, `1h05m`).
+pub fn format_span_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    } else if secs >= 60 {
+        format!("{}m{:02}s", secs / 60, secs % 60)
+    } else if duration.as_millis() >= 1000 {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        format!("{}ms", duration.as_millis())
+    }
+}
+
+fn format_time(at: DateTime<Utc>, mode: TimestampMode, now: DateTime<Utc>) -> String {
+    match mode {
+        TimestampMode::Off => String::new(),
+        TimestampMode::Absolute => {
+            let local = at.with_timezone(&Local);
+            if local.date_naive() == now.with_timezone(&Local).date_naive() {
+                local.format("%H:%M:%S").to_string()
+            } else {
+                local.format("%Y-%m-%d %H:%M:%S").to_string()
+            }
+        }
+        TimestampMode::Relative => {
+            let secs = (now - at).num_seconds();
+            match secs {
+                ..5 => "just now".to_string(),
+                5..60 => format!("{secs}s ago"),
+                60..3600 => format!("{}m ago", secs / 60),
+                3600..86_400 => format!("{}h ago", secs / 3600),
+                _ => format!("{}d ago", secs / 86_400),
+            }
+        }
+    }
+}
+
+/// Returns how long the agent turn started by the user cell at `idx` took:
+/// from the user message to the end of the last cell before the next user
+/// message. `None` for non-user cells, turns with no output yet, and turns
+/// still in progress.
+pub fn turn_duration(cells: &[HistoryCell], idx: usize) -> Option<Duration> {
+    let user = cells.get(idx)?;
+    if !matches!(user, HistoryCell::User { .. }) {
+        return None;
+    }
+    let turn: Vec<&HistoryCell> = cells[idx + 1..]
+        .iter()
+        .take_while(|cell| !matches!(cell, HistoryCell::User { .. }))
+        .collect();
+    if turn.is_empty() || turn.iter().any(|cell| cell.is_in_progress()) {
+        return None;
+    }
+    let end = turn.iter().map(|cell| cell.finished_at()).max()?;
+    (end - user.created_at()).to_std().ok()
+}
+
+/// Builds the timestamp line shown above `cell`, or `None` when timestamps
+/// are off.
+///
+/// Finished tool cells also show how long the call took; user cells show
+/// `turn` (see [`turn_duration`]) when it is known.
+pub fn timestamp_line(
+    cell: &HistoryCell,
+    mode: TimestampMode,
+    now: DateTime<Utc>,
+    turn: Option<Duration>,
+) -> Option<StyledLine> {
+    if mode == TimestampMode::Off {
+        return None;
+    }
+    let mut text = format_time(cell.created_at(), mode, now);
+    let took = match cell {
+        HistoryCell::Tool {
+            started_at,
+            completed_at: Some(completed_at),
+            ..
+        } => (*completed_at - *started_at).to_std().ok(),
+        HistoryCell::User { .. } => turn,
+        _ => None,
+    };
+    if let Some(took) = took {
+        text.push_str(" · took ");
+        text.push_str(&format_span_duration(took));
+    }
+    Some(StyledLine {
+        spans: vec![StyledSpan {
+            text,
+            style: Style::Timing,
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zdx_engine::core::events::ToolOutput;
+
+    use super::*;
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        DateTime::from_timestamp(1_767_261_600 + secs, 0).unwrap()
+    }
+
+    fn line_text(line: &StyledLine) -> String {
+        line.spans.iter().map(|span| span.text.as_str()).collect()
+    }
+
+    #[test]
+    fn test_timestamp_line_shows_relative_age_and_durations() {
+        let mut user = HistoryCell::user("hi");
+        user.set_created_at(at(0));
+        let mut tool = HistoryCell::tool_running("t1", "bash", json!({}));
+        tool.set_created_at(at(1));
+        tool.set_tool_result(ToolOutput::success(json!({})));
+        tool.set_tool_completed_at(at(4));
+        let mut reply = HistoryCell::assistant("done");
+        reply.set_created_at(at(75));
+        let cells = vec![user, tool, reply];
+
+        let turn = turn_duration(&cells, 0);
+        assert_eq!(turn, Some(Duration::from_secs(75)));
+        assert_eq!(turn_duration(&cells, 1), None);
+
+        let now = at(600);
+        let user_line = timestamp_line(&cells[0], TimestampMode::Relative, now, turn).unwrap();
+        assert_eq!(line_text(&user_line), "10m ago · took 1m15s");
+        let tool_line = timestamp_line(&cells[1], TimestampMode::Relative, now, None).unwrap();
+        assert_eq!(line_text(&tool_line), "9m ago · took 3.0s");
+        assert!(timestamp_line(&cells[2], TimestampMode::Off, now, None).is_none());
+    }
+
+    #[test]
+    fn test_turn_duration_waits_for_streaming_turn() {
+        let cells = vec![
+            HistoryCell::user("hi"),
+            HistoryCell::assistant_streaming("par"),
+        ];
+        assert_eq!(turn_duration(&cells, 0), None);
+        assert_eq!(TimestampMode::Relative.next(), TimestampMode::Off);
+    }
+}
@@ -8,27 +8,74 @@ use crate::cell::CellId;
 use crate::style::{Style, StyledLine, StyledSpan};
 use crate::text::ratatui_width;
 
+/// Default memory budget for [`WrapCache`].
+pub const DEFAULT_WRAP_CACHE_BYTES: usize = 16 * 1024 * 1024;
+
 /// Cache for wrapped lines to avoid re-computing on every frame.
 ///
 /// Keyed by `(CellId, width)`. The `discriminator` invalidates entries when a
 /// cell's content changes. Lines are stored behind an `Rc` so cache hits are
 /// cheap pointer clones rather than deep `Vec<StyledLine>` copies.
 ///
+/// The cache is bounded by an estimated byte budget. When an insert pushes it
+/// over budget, the least recently used entries are evicted until it is back
+/// under three quarters of the budget, so long sessions (and resizes that
+/// leave entries for old widths behind) don't grow memory without limit.
+///
 /// Uses interior mutability (`RefCell`) to allow caching during immutable
 /// render passes.
-type CacheEntry = (usize, Rc<[StyledLine]>);
+#[derive(Debug)]
+pub struct WrapCache {
+    inner: RefCell<CacheInner>,
+    max_bytes: usize,
+}
 
 #[derive(Debug, Default)]
-pub struct WrapCache {
-    /// Maps (`cell_id`, width) -> (discriminator, cached styled lines)
-    cache: RefCell<HashMap<(CellId, usize), CacheEntry>>,
+struct CacheInner {
+    /// Maps (`cell_id`, width) -> cached entry
+    entries: HashMap<(CellId, usize), CacheEntry>,
+    /// Estimated bytes held by all entries.
+    bytes: usize,
+    /// Monotonic access counter used for LRU ordering.
+    tick: u64,
+    /// Entries evicted since the cache was created.
+    evictions: u64,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    discriminator: usize,
+    lines: Rc<[StyledLine]>,
+    bytes: usize,
+    last_used: u64,
+}
+
+/// Memory usage snapshot of a [`WrapCache`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WrapCacheStats {
+    pub entries: usize,
+    pub bytes: usize,
+    pub max_bytes: usize,
+    pub evictions: u64,
+}
+
+impl Default for WrapCache {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl WrapCache {
-    /// Creates a new empty cache.
+    /// Creates a new empty cache with the default memory budget.
     pub fn new() -> Self {
+        Self::with_max_bytes(DEFAULT_WRAP_CACHE_BYTES)
+    }
+
+    /// Creates a new empty cache bounded to roughly `max_bytes`.
+    pub fn with_max_bytes(max_bytes: usize) -> Self {
         Self {
-            cache: RefCell::new(HashMap::new()),
+            inner: RefCell::new(CacheInner::default()),
+            max_bytes,
         }
     }
 
@@ -36,7 +83,20 @@ impl WrapCache {
     ///
     /// Call this on terminal resize to invalidate width-dependent caches.
     pub fn clear(&self) {
-        self.cache.borrow_mut().clear();
+        let mut inner = self.inner.borrow_mut();
+        inner.entries.clear();
+        inner.bytes = 0;
+    }
+
+    /// Returns the current entry count and estimated memory usage.
+    pub fn stats(&self) -> WrapCacheStats {
+        let inner = self.inner.borrow();
+        WrapCacheStats {
+            entries: inner.entries.len(),
+            bytes: inner.bytes,
+            max_bytes: self.max_bytes,
+            evictions: inner.evictions,
+        }
     }
 
     /// Gets cached lines for a cell, returning a cheap `Rc` clone if present
@@ -47,14 +107,19 @@ impl WrapCache {
         width: usize,
         discriminator: usize,
     ) -> Option<Rc<[StyledLine]>> {
-        self.cache
-            .borrow()
-            .get(&(cell_id, width))
-            .filter(|(cached_disc, _)| *cached_disc == discriminator)
-            .map(|(_, lines)| Rc::clone(lines))
+        let mut inner = self.inner.borrow_mut();
+        inner.tick += 1;
+        let tick = inner.tick;
+        let entry = inner
+            .entries
+            .get_mut(&(cell_id, width))
+            .filter(|entry| entry.discriminator == discriminator)?;
+        entry.last_used = tick;
+        Some(Rc::clone(&entry.lines))
     }
 
-    /// Stores wrapped lines in the cache.
+    /// Stores wrapped lines in the cache, evicting old entries when over
+    /// budget.
     pub(crate) fn insert(
         &self,
         cell_id: CellId,
@@ -62,12 +127,60 @@ impl WrapCache {
         discriminator: usize,
         lines: Rc<[StyledLine]>,
     ) {
-        self.cache
-            .borrow_mut()
-            .insert((cell_id, width), (discriminator, lines));
+        let bytes = lines_bytes(&lines);
+        let mut inner = self.inner.borrow_mut();
+        inner.tick += 1;
+        let entry = CacheEntry {
+            discriminator,
+            lines,
+            bytes,
+            last_used: inner.tick,
+        };
+        inner.bytes += bytes;
+        if let Some(old) = inner.entries.insert((cell_id, width), entry) {
+            inner.bytes -= old.bytes;
+        }
+        if inner.bytes > self.max_bytes {
+            inner.evict_to(self.max_bytes / 4 * 3);
+        }
     }
 }
 
+impl CacheInner {
+    /// Evicts least recently used entries until `bytes <= target`.
+    fn evict_to(&mut self, target: usize) {
+        let mut by_age: Vec<_> = self
+            .entries
+            .iter()
+            .map(|(key, entry)| (entry.last_used, *key, entry.bytes))
+            .collect();
+        by_age.sort_unstable_by_key(|(last_used, _, _)| *last_used);
+        for (_, key, bytes) in by_age {
+            if self.bytes <= target {
+                break;
+            }
+            self.entries.remove(&key);
+            self.bytes -= bytes;
+            self.evictions += 1;
+        }
+    }
+}
+
+/// Estimates the heap and inline size of cached lines.
+fn lines_bytes(lines: &[StyledLine]) -> usize {
+    lines
+        .iter()
+        .map(|line| {
+            size_of::<StyledLine>()
+                + line
+                    .spans
+                    .iter()
+                    .map(|span| size_of::<StyledSpan>() + span.text.capacity())
+                    .sum::<usize>()
+        })
+        .sum()
+}
+
 /// Renders content with a prefix, handling line wrapping.
 ///
 /// The prefix appears on the first line; subsequent wrapped lines
@@ -286,6 +399,36 @@ pub(crate) fn wrap_chars(text: &str, width: usize) -> Vec<String> {
 mod tests {
     use super::*;
 
+    fn lines(text: &str) -> Rc<[StyledLine]> {
+        Rc::from(vec![StyledLine {
+            spans: vec![StyledSpan {
+                text: text.to_string(),
+                style: Style::Plain,
+            }],
+        }])
+    }
+
+    #[test]
+    fn test_wrap_cache_evicts_least_recently_used() {
+        let entry_bytes = lines_bytes(&lines(&"x".repeat(100)));
+        let cache = WrapCache::with_max_bytes(entry_bytes * 4);
+        for id in 0..4 {
+            cache.insert(CellId(id), 80, 0, lines(&"x".repeat(100)));
+        }
+        // Touch the oldest entry so it becomes the most recently used.
+        assert!(cache.get(CellId(0), 80, 0).is_some());
+
+        cache.insert(CellId(4), 80, 0, lines(&"x".repeat(100)));
+
+        let stats = cache.stats();
+        assert!(stats.bytes <= entry_bytes * 3);
+        assert_eq!(stats.evictions, 2);
+        assert!(cache.get(CellId(0), 80, 0).is_some());
+        assert!(cache.get(CellId(4), 80, 0).is_some());
+        assert!(cache.get(CellId(1), 80, 0).is_none());
+        assert!(cache.get(CellId(2), 80, 0).is_none());
+    }
+
     #[test]
     fn test_wrap_text_basic() {
         let wrapped = wrap_text("hello world", 20);
@@ -15,6 +15,8 @@ pub struct Command {
     pub category: &'static str,
     /// Keyboard shortcut hint (e.g., "Ctrl s").
     pub shortcut: Option<&'static str>,
+    /// Argument usage shown in slash completion (e.g., "<path>", "[model]").
+    pub args: Option<&'static str>,
 }
 
 impl Command {
@@ -48,6 +50,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Ask a side question in a new tab",
         category: "thread",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "close-tab",
@@ -55,6 +58,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Close the current tab",
         category: "tab",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "commands-refresh",
@@ -62,6 +66,23 @@ pub const COMMANDS: &[Command] = &[
         description: "Reload custom slash commands from disk",
         category: "config",
         shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "compare",
+        aliases: &[],
+        description: "Run your next message on two models side by side and keep one",
+        category: "model",
+        shortcut: None,
+        args: Some("<model-a> <model-b>"),
+    },
+    Command {
+        name: "duel",
+        aliases: &[],
+        description: "Let two models discuss a topic in turns (experimental)",
+        category: "model",
+        shortcut: None,
+        args: Some("<model-a> <model-b> <topic> | stop"),
     },
     Command {
         name: "config",
@@ -69,6 +90,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Open config file in default editor",
         category: "config",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "context",
@@ -76,6 +98,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Show per-section token breakdown of the current LLM context",
         category: "debug",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "copy-id",
@@ -83,6 +106,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Copy current thread ID to clipboard",
         category: "thread",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "debug",
@@ -90,6 +114,15 @@ pub const COMMANDS: &[Command] = &[
         description: "Toggle debug/performance status line",
         category: "debug",
         shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "debug-request",
+        aliases: &[],
+        description: "Show the provider request the next turn would send",
+        category: "debug",
+        shortcut: None,
+        args: None,
     },
     Command {
         name: "handoff",
@@ -97,6 +130,15 @@ pub const COMMANDS: &[Command] = &[
         description: "Start new thread with context from current",
         category: "thread",
         shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "improve",
+        aliases: &[],
+        description: "Suggest an improved version of the drafted prompt",
+        category: "prompt",
+        shortcut: None,
+        args: None,
     },
     Command {
         name: "prompt-builder",
@@ -104,6 +146,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Build a polished prompt from a short intent",
         category: "prompt",
         shortcut: Some("Ctrl+B"),
+        args: None,
     },
     Command {
         name: "login",
@@ -111,6 +154,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Authenticate with the active provider",
         category: "auth",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "logout",
@@ -118,6 +162,15 @@ pub const COMMANDS: &[Command] = &[
         description: "Clear auth for the active provider",
         category: "auth",
         shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "limits",
+        aliases: &["quota"],
+        description: "Show Claude subscription usage limits and resets",
+        category: "auth",
+        shortcut: None,
+        args: None,
     },
     Command {
         name: "rename",
@@ -125,6 +178,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Rename the current thread",
         category: "thread",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "model",
@@ -132,6 +186,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Switch model",
         category: "model",
         shortcut: None,
+        args: Some("[model]"),
     },
     Command {
         name: "models",
@@ -139,6 +194,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Open models config in default editor",
         category: "config",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "skills",
@@ -146,6 +202,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Browse and install skills",
         category: "skills",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "new",
@@ -153,13 +210,31 @@ pub const COMMANDS: &[Command] = &[
         description: "Start a new thread",
         category: "thread",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "new-tab",
         aliases: &["tab-new"],
         description: "Open a new blank tab",
         category: "tab",
+        shortcut: Some("Ctrl+N"),
+        args: None,
+    },
+    Command {
+        name: "incognito",
+        aliases: &[],
+        description: "Toggle incognito mode (nothing is saved)",
+        category: "thread",
+        shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "tour",
+        aliases: &["onboarding"],
+        description: "Show or hide the guided tour of core features",
+        category: "app",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "quit",
@@ -167,6 +242,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Quit ZDX",
         category: "app",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "tabs",
@@ -174,6 +250,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Switch to the next tab",
         category: "tab",
         shortcut: Some("Ctrl+PgDn"),
+        args: None,
     },
     Command {
         name: "threads",
@@ -181,6 +258,15 @@ pub const COMMANDS: &[Command] = &[
         description: "Browse and switch threads",
         category: "thread",
         shortcut: None,
+        args: Some("[title]"),
+    },
+    Command {
+        name: "recent",
+        aliases: &[],
+        description: "Quick-switch between recent threads",
+        category: "thread",
+        shortcut: Some("Ctrl+P"),
+        args: None,
     },
     Command {
         name: "worktree",
@@ -188,6 +274,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Create/switch to a per-thread git worktree",
         category: "git",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "worktree-remove",
@@ -195,6 +282,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Remove current worktree and switch to project root",
         category: "git",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "open",
@@ -202,6 +290,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Open a new terminal at the current root",
         category: "app",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "pwd",
@@ -209,6 +298,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Copy current root path to clipboard",
         category: "git",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "root-new",
@@ -216,6 +306,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Start a new thread from the original project root",
         category: "thread",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "fast",
@@ -223,6 +314,7 @@ pub const COMMANDS: &[Command] = &[
         description: "Toggle fast mode for OpenAI models (priority tier, 2× cost)",
         category: "model",
         shortcut: None,
+        args: None,
     },
     Command {
         name: "thinking",
@@ -230,6 +322,63 @@ pub const COMMANDS: &[Command] = &[
         description: "Change thinking level",
         category: "model",
         shortcut: Some("Ctrl+T"),
+        args: Some("[level]"),
+    },
+    Command {
+        name: "redo",
+        aliases: &["regenerate"],
+        description: "Regenerate the last response with your feedback",
+        category: "thread",
+        shortcut: None,
+        args: Some("<feedback>"),
+    },
+    Command {
+        name: "duplicate",
+        aliases: &["clone"],
+        description: "Copy this thread (optionally up to turn N) and switch to the copy",
+        category: "thread",
+        shortcut: None,
+        args: Some("[turn]"),
+    },
+    Command {
+        name: "pin",
+        aliases: &[],
+        description: "Pin the latest turn (or turn N) so conversation truncation keeps it",
+        category: "thread",
+        shortcut: None,
+        args: Some("[turn]"),
+    },
+    Command {
+        name: "unpin",
+        aliases: &[],
+        description: "Unpin the latest turn (or turn N)",
+        category: "thread",
+        shortcut: None,
+        args: Some("[turn]"),
+    },
+    Command {
+        name: "pin-file",
+        aliases: &[],
+        description: "Pin a file so its contents are sent with every turn",
+        category: "thread",
+        shortcut: None,
+        args: Some("<path>"),
+    },
+    Command {
+        name: "unpin-file",
+        aliases: &[],
+        description: "Unpin a file pinned with /pin-file",
+        category: "thread",
+        shortcut: None,
+        args: Some("<path>"),
+    },
+    Command {
+        name: "style",
+        aliases: &[],
+        description: "Set the response style for this thread (concise, pt-BR, off, ...)",
+        category: "thread",
+        shortcut: None,
+        args: Some("<name|off>"),
     },
     Command {
         name: "timeline",
@@ -237,6 +386,15 @@ pub const COMMANDS: &[Command] = &[
         description: "Jump to a thread turn",
         category: "thread",
         shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "stats",
+        aliases: &["statistics"],
+        description: "Show turns, tool calls, tokens, cost, and files for this thread",
+        category: "thread",
+        shortcut: None,
+        args: None,
     },
     Command {
         name: "tldr",
@@ -244,6 +402,39 @@ pub const COMMANDS: &[Command] = &[
         description: "Show a TLDR of recent activity in this thread",
         category: "thread",
         shortcut: Some("Ctrl+R"),
+        args: None,
+    },
+    Command {
+        name: "tools",
+        aliases: &[],
+        description: "Enable or disable tools for this session",
+        category: "model",
+        shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "params",
+        aliases: &["sampling"],
+        description: "Set temperature, top_p, and max output tokens for this session",
+        category: "model",
+        shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "subagent-details",
+        aliases: &["subagents"],
+        description: "Expand or collapse live subagent progress in tool cells",
+        category: "thread",
+        shortcut: None,
+        args: None,
+    },
+    Command {
+        name: "timestamps",
+        aliases: &[],
+        description: "Cycle cell timestamps: off, absolute, relative",
+        category: "thread",
+        shortcut: None,
+        args: None,
     },
 ];
 
@@ -252,6 +443,9 @@ pub fn command_available(command: &Command, model_id: &str) -> bool {
     if command.name == "thinking" {
         return zdx_engine::models::model_supports_reasoning(model_id);
     }
+    if command.name == "limits" {
+        return resolve_provider(model_id).kind == ProviderKind::ClaudeCli;
+    }
     if command.name == "fast" {
         return matches!(
             resolve_provider(model_id).kind,
@@ -331,6 +525,10 @@ mod tests {
             "models (models-config)"
         );
         assert_eq!(find_command("skills").display_name(), "skills (skill)");
+        assert_eq!(
+            find_command("subagent-details").display_name(),
+            "subagent-details (subagents)"
+        );
         assert_eq!(find_command("new").display_name(), "new (clear)");
         assert_eq!(find_command("quit").display_name(), "quit (q, exit)");
         assert_eq!(find_command("threads").display_name(), "threads (history)");
@@ -345,6 +543,7 @@ mod tests {
         assert_eq!(find_command("fast").display_name(), "fast");
         assert_eq!(find_command("thinking").display_name(), "thinking");
         assert_eq!(find_command("timeline").display_name(), "timeline");
+        assert_eq!(find_command("tools").display_name(), "tools");
     }
 
     #[test]
@@ -0,0 +1,190 @@
+//! Locale table for TUI chrome.
+//!
+//! Status line labels, overlay titles, and common system messages are looked
+//! up here instead of being inlined, so the UI follows `[tui] language`.
+//! The language is process-wide: it is set once at startup from config and
+//! read by render/update code via [`tr`].
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use zdx_engine::config::TuiLanguage;
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+/// Sets the language used by [`tr`].
+pub fn set_language(language: TuiLanguage) {
+    let value = match language {
+        TuiLanguage::English => 0,
+        TuiLanguage::BrazilianPortuguese => 1,
+    };
+    LANGUAGE.store(value, Ordering::Relaxed);
+}
+
+/// Returns the active language.
+pub fn language() -> TuiLanguage {
+    match LANGUAGE.load(Ordering::Relaxed) {
+        1 => TuiLanguage::BrazilianPortuguese,
+        _ => TuiLanguage::English,
+    }
+}
+
+/// Translatable TUI strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Text {
+    // Status line
+    RecordingVoice,
+    TranscribingVoice,
+    RunningBash,
+    Waiting,
+    Streaming,
+    ToCancel,
+    CommandsHint,
+    QuitHint,
+
+    // Overlay titles
+    CommandPaletteTitle,
+    SelectModelTitle,
+    ThinkingLevelTitle,
+    RenameThreadTitle,
+    TimelineTitle,
+    SuggestedRepliesTitle,
+    InstallSkillTitle,
+    LoadedSkillsTitle,
+    SkillDetailsTitle,
+    ToolsTitle,
+    ModelParamsTitle,
+    AgentQuestionTitle,
+
+    // System messages
+    ThreadCleared,
+    CannotClearWhileStreaming,
+    StopCurrentTaskFirst,
+    NoActiveThread,
+    NoTimelineEntrySelected,
+    StoppingAfterCurrentStep,
+    SteerUsage,
+    VoiceTranscriptionInProgress,
+
+    // Accessibility announcements
+    AgentStarted,
+    AgentFinished,
+    AgentStopped,
+    AgentFailed,
+    ToolStartedPrefix,
+    ToolFinishedPrefix,
+    ToolFailedPrefix,
+}
+
+/// Returns `text` in the active language.
+pub fn tr(text: Text) -> &'static str {
+    match language() {
+        TuiLanguage::English => english(text),
+        TuiLanguage::BrazilianPortuguese => brazilian_portuguese(text),
+    }
+}
+
+fn english(text: Text) -> &'static str {
+    match text {
+        Text::RecordingVoice => "Recording voice...",
+        Text::TranscribingVoice => "Transcribing voice...",
+        Text::RunningBash => "Running bash...",
+        Text::Waiting => "Waiting...",
+        Text::Streaming => "Streaming...",
+        Text::ToCancel => " to cancel",
+        Text::CommandsHint => " commands  ",
+        Text::QuitHint => " quit",
+        Text::CommandPaletteTitle => "Command Palette",
+        Text::SelectModelTitle => "Select Model",
+        Text::ThinkingLevelTitle => "Thinking Level",
+        Text::RenameThreadTitle => "Rename Thread",
+        Text::TimelineTitle => "Timeline",
+        Text::SuggestedRepliesTitle => "Suggested replies",
+        Text::InstallSkillTitle => "Install Skill",
+        Text::LoadedSkillsTitle => "Loaded Skills",
+        Text::SkillDetailsTitle => "Skill Details",
+        Text::ToolsTitle => "Tools",
+        Text::ModelParamsTitle => "Model Params",
+        Text::AgentQuestionTitle => "Agent Question",
+        Text::ThreadCleared => "Thread cleared.",
+        Text::CannotClearWhileStreaming => "Cannot clear while streaming.",
+        Text::StopCurrentTaskFirst => "Stop the current task first.",
+        Text::NoActiveThread => "No active thread.",
+        Text::NoTimelineEntrySelected => "No timeline entry selected.",
+        Text::StoppingAfterCurrentStep => {
+            "Stopping after the current step… press again to cancel now."
+        }
+        Text::SteerUsage => "Usage: /steer <note>",
+        Text::VoiceTranscriptionInProgress => "Voice transcription already in progress.",
+        Text::AgentStarted => "Agent started",
+        Text::AgentFinished => "Agent finished",
+        Text::AgentStopped => "Agent stopped",
+        Text::AgentFailed => "Agent failed",
+        Text::ToolStartedPrefix => "Tool started: ",
+        Text::ToolFinishedPrefix => "Tool finished: ",
+        Text::ToolFailedPrefix => "Tool failed: ",
+    }
+}
+
+fn brazilian_portuguese(text: Text) -> &'static str {
+    match text {
+        Text::RecordingVoice => "Gravando voz...",
+        Text::TranscribingVoice => "Transcrevendo voz...",
+        Text::RunningBash => "Executando bash...",
+        Text::Waiting => "Aguardando...",
+        Text::Streaming => "Recebendo...",
+        Text::ToCancel => " para cancelar",
+        Text::CommandsHint => " comandos  ",
+        Text::QuitHint => " sair",
+        Text::CommandPaletteTitle => "Paleta de Comandos",
+        Text::SelectModelTitle => "Selecionar Modelo",
+        Text::ThinkingLevelTitle => "Nível de Raciocínio",
+        Text::RenameThreadTitle => "Renomear Conversa",
+        Text::TimelineTitle => "Linha do Tempo",
+        Text::SuggestedRepliesTitle => "Respostas sugeridas",
+        Text::InstallSkillTitle => "Instalar Skill",
+        Text::LoadedSkillsTitle => "Skills Carregadas",
+        Text::SkillDetailsTitle => "Detalhes da Skill",
+        Text::ToolsTitle => "Ferramentas",
+        Text::ModelParamsTitle => "Parâmetros do Modelo",
+        Text::AgentQuestionTitle => "Pergunta do Agente",
+        Text::ThreadCleared => "Conversa limpa.",
+        Text::CannotClearWhileStreaming => "Não é possível limpar durante a resposta.",
+        Text::StopCurrentTaskFirst => "Pare a tarefa atual primeiro.",
+        Text::NoActiveThread => "Nenhuma conversa ativa.",
+        Text::NoTimelineEntrySelected => "Nenhum item da linha do tempo selecionado.",
+        Text::StoppingAfterCurrentStep => {
+            "Parando após a etapa atual… pressione de novo para cancelar agora."
+        }
+        Text::SteerUsage => "Uso: /steer <nota>",
+        Text::VoiceTranscriptionInProgress => "Transcrição de voz já em andamento.",
+        Text::AgentStarted => "Agente iniciado",
+        Text::AgentFinished => "Agente concluído",
+        Text::AgentStopped => "Agente interrompido",
+        Text::AgentFailed => "Agente falhou",
+        Text::ToolStartedPrefix => "Ferramenta iniciada: ",
+        Text::ToolFinishedPrefix => "Ferramenta concluída: ",
+        Text::ToolFailedPrefix => "Ferramenta falhou: ",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn english_is_the_default() {
+        assert_eq!(language(), TuiLanguage::English);
+        assert_eq!(english(Text::ThreadCleared), "Thread cleared.");
+    }
+
+    #[test]
+    fn portuguese_table_differs_from_english() {
+        for text in [
+            Text::Waiting,
+            Text::CommandPaletteTitle,
+            Text::ThreadCleared,
+        ] {
+            assert_ne!(english(text), brazilian_portuguese(text));
+        }
+    }
+}
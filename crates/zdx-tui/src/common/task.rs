@@ -20,11 +20,15 @@ impl TaskSeq {
 #[derive(Enum, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum TaskKind {
     ThreadList,
+    ThreadCompletion,
     ThreadLoad,
     ThreadRename,
+    ThreadAction,
     ThreadTitle,
+    ThreadSummary,
     ThreadTldr,
     ContextAnalyze,
+    DebugRequest,
     ThreadPreview,
     ThreadCreate,
     ThreadFork,
@@ -35,11 +39,14 @@ pub enum TaskKind {
     Bash,
     Handoff,
     PromptBuilder,
+    PromptImprove,
+    Duel,
     LoginExchange,
     LoginCallback,
     ImageDecode,
     VoiceRecord,
     VoiceTranscribe,
+    StartupContext,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -49,6 +56,7 @@ pub enum TaskMeta {
     Bash {
         id: String,
         command: String,
+        cwd: Option<String>,
     },
     Handoff {
         next_message: String,
@@ -0,0 +1,277 @@
+//! Side-by-side model comparison (`/compare <model-a> <model-b>`).
+//!
+//! An armed comparison sends the next message to two branch tabs forked from
+//! the current conversation (btw tabs, one per model) instead of the current
+//! tab. While the comparison is open, the tab it started from renders both
+//! branches side by side. Picking a branch switches to its tab, a persisted
+//! fork thread that keeps that model, and closes the other.
+
+use zdx_engine::models::split_model_thinking;
+
+use crate::effects::UiEffect;
+use crate::input::{self, CompareInput, TabContext};
+use crate::mutations::{StateMutation, TranscriptMutation};
+use crate::state::{AppState, TabId, TuiState};
+use crate::transcript;
+use crate::update::{apply_tab_mutations, build_btw_base_messages, create_btw_tab};
+
+/// `/compare` requests that act on tabs rather than a single composer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompareRequest {
+    /// Runs `prompt` on both models (`model[@thinking]` specs).
+    Start { models: [String; 2], prompt: String },
+    /// Continues in branch `0` or `1`.
+    Pick(usize),
+    /// Closes both branches.
+    Discard,
+}
+
+/// An open comparison.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompareSession {
+    /// Tab the comparison started from; it shows the split view.
+    pub origin: TabId,
+    /// Branch tabs, in `/compare` argument order.
+    pub branches: [TabId; 2],
+}
+
+pub fn handle_request(app: &mut AppState, request: &CompareRequest) -> Vec<UiEffect> {
+    match request {
+        CompareRequest::Start { models, prompt } => start(app, models, prompt),
+        CompareRequest::Pick(index) => pick(app, *index),
+        CompareRequest::Discard => discard(app),
+    }
+}
+
+/// Branch tabs to render side by side: only on the origin tab, and only while
+/// both branches are still open.
+pub fn visible_branches(app: &AppState) -> Option<[&TuiState; 2]> {
+    let session = app.compare.as_ref()?;
+    if app.tui.tab_id != session.origin {
+        return None;
+    }
+    Some([app.tab(session.branches[0])?, app.tab(session.branches[1])?])
+}
+
+/// Applies coalesced streaming text for the branch tabs. Background tabs
+/// otherwise only catch up when they become active.
+pub fn apply_pending_deltas(app: &mut AppState) {
+    let Some(session) = &app.compare else {
+        return;
+    };
+    for tab in &mut app.background_tabs {
+        if session.branches.contains(&tab.tab_id) {
+            transcript::apply_pending_delta(&mut tab.transcript, &mut tab.agent_state);
+        }
+    }
+}
+
+fn start(app: &mut AppState, models: &[String; 2], prompt: &str) -> Vec<UiEffect> {
+    let base_messages = build_btw_base_messages(&app.tui);
+    let mut effects = Vec::new();
+    let mut branches = [TabId(0); 2];
+    for (branch, spec) in branches.iter_mut().zip(models) {
+        let tab_id = app.next_tab_id();
+        let mut tab = create_btw_tab(tab_id, base_messages.clone(), &app.tui);
+        let (model, thinking) = split_model_thinking(spec);
+        tab.config.model = model.to_string();
+        if let Some(level) = thinking {
+            tab.config.thinking_level = level;
+        }
+        let (send_effects, mutations) = input::build_send_effects_for_tab(
+            prompt,
+            None,
+            false,
+            Vec::new(),
+            TabContext::Background(tab_id),
+        );
+        apply_tab_mutations(&mut tab, mutations);
+        app.background_tabs.push(tab);
+        effects.extend(send_effects);
+        *branch = tab_id;
+    }
+
+    app.tui.input.compare = CompareInput::Open;
+    app.compare = Some(CompareSession {
+        origin: app.tui.tab_id,
+        branches,
+    });
+    effects
+}
+
+fn pick(app: &mut AppState, index: usize) -> Vec<UiEffect> {
+    let Some(session) = app.compare.clone() else {
+        return notice(&mut app.tui, "No comparison is open.".to_string());
+    };
+    let chosen = session.branches[index];
+    let branch = index + 1;
+    let Some(tab) = app.tab(chosen) else {
+        return notice(
+            &mut app.tui,
+            format!("Branch {branch} was closed; /compare off discards the comparison."),
+        );
+    };
+    if tab.agent_state.is_running() {
+        return notice(
+            &mut app.tui,
+            format!("Branch {branch} is still running; wait for it or /compare off."),
+        );
+    }
+    let model = tab.config.model.clone();
+
+    let discarded = session.branches[1 - index];
+    let effects = close_session(app, &session, vec![discarded]);
+    if let Some(origin) = app.tab_mut(session.origin) {
+        apply_tab_mutations(
+            origin,
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(format!(
+                    "Compared models; continued with branch {branch} ({model}) in tab {}.",
+                    chosen.0 + 1
+                )),
+            )],
+        );
+    }
+    app.switch_to_tab(chosen);
+    effects
+}
+
+fn discard(app: &mut AppState) -> Vec<UiEffect> {
+    let Some(session) = app.compare.clone() else {
+        return notice(&mut app.tui, "No comparison is open.".to_string());
+    };
+    if session.branches.contains(&app.tui.tab_id) {
+        app.switch_to_tab(session.origin);
+    }
+    let effects = close_session(app, &session, session.branches.to_vec());
+    if let Some(origin) = app.tab_mut(session.origin) {
+        apply_tab_mutations(
+            origin,
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage("Comparison discarded.".to_string()),
+            )],
+        );
+    }
+    effects
+}
+
+/// Ends `session` and closes `closed` branch tabs.
+fn close_session(
+    app: &mut AppState,
+    session: &CompareSession,
+    closed: Vec<TabId>,
+) -> Vec<UiEffect> {
+    app.compare = None;
+    if let Some(origin) = app.tab_mut(session.origin) {
+        origin.input.compare = CompareInput::Off;
+    }
+    vec![UiEffect::CloseBackgroundTabs { tab_ids: closed }]
+}
+
+fn notice(tui: &mut TuiState, message: String) -> Vec<UiEffect> {
+    apply_tab_mutations(
+        tui,
+        vec![StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(message),
+        )],
+    );
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use zdx_engine::config::{Config, ThinkingLevel};
+
+    use super::*;
+    use crate::state::AgentState;
+
+    fn app() -> AppState {
+        AppState::new(Config::default(), PathBuf::from("."), None, None)
+    }
+
+    fn start_request() -> CompareRequest {
+        CompareRequest::Start {
+            models: [
+                "anthropic:claude-sonnet-4-5".to_string(),
+                "openai:gpt-5@high".to_string(),
+            ],
+            prompt: "Explain this module".to_string(),
+        }
+    }
+
+    #[test]
+    fn start_forks_one_branch_per_model() {
+        let mut app = app();
+        let effects = handle_request(&mut app, &start_request());
+
+        let session = app.compare.clone().expect("session");
+        assert_eq!(session.origin, app.tui.tab_id);
+        assert_eq!(app.tui.input.compare, CompareInput::Open);
+        assert_eq!(app.background_tabs.len(), 2);
+        let started: Vec<TabId> = effects
+            .iter()
+            .filter_map(|effect| match effect {
+                UiEffect::StartAgentTurnInBackgroundTab { tab_id, .. } => Some(*tab_id),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(started, session.branches.to_vec());
+
+        let [a, b] = visible_branches(&app).expect("split view");
+        assert_eq!(a.config.model, "anthropic:claude-sonnet-4-5");
+        assert_eq!(b.config.model, "openai:gpt-5");
+        assert_eq!(b.config.thinking_level, ThinkingLevel::High);
+        assert!(
+            a.thread
+                .messages
+                .last()
+                .is_some_and(|message| message.role == "user")
+        );
+    }
+
+    #[test]
+    fn pick_switches_to_branch_and_closes_the_other() {
+        let mut app = app();
+        handle_request(&mut app, &start_request());
+        let session = app.compare.clone().unwrap();
+        let origin = app.tui.tab_id;
+
+        let effects = handle_request(&mut app, &CompareRequest::Pick(1));
+        assert!(app.compare.is_none());
+        assert_eq!(app.tui.tab_id, session.branches[1]);
+        assert_eq!(app.tab(origin).unwrap().input.compare, CompareInput::Off);
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::CloseBackgroundTabs { tab_ids }] if *tab_ids == vec![session.branches[0]]
+        ));
+        assert!(visible_branches(&app).is_none());
+    }
+
+    #[test]
+    fn pick_waits_for_running_branch() {
+        let mut app = app();
+        handle_request(&mut app, &start_request());
+        let session = app.compare.clone().unwrap();
+        let (_tx, rx) = zdx_engine::core::agent::create_event_channel();
+        app.background_tab_mut(session.branches[0])
+            .unwrap()
+            .agent_state = AgentState::Waiting {
+            rx,
+            cancel: tokio_util::sync::CancellationToken::new(),
+            soft_stop: tokio_util::sync::CancellationToken::new(),
+        };
+
+        assert!(handle_request(&mut app, &CompareRequest::Pick(0)).is_empty());
+        assert_eq!(app.compare, Some(session.clone()));
+
+        let effects = handle_request(&mut app, &CompareRequest::Discard);
+        assert!(app.compare.is_none());
+        assert_eq!(app.tui.input.compare, CompareInput::Off);
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::CloseBackgroundTabs { tab_ids }] if *tab_ids == session.branches.to_vec()
+        ));
+    }
+}
@@ -0,0 +1,340 @@
+//! Local crash reports for TUI panics.
+//!
+//! The runtime records a label for every dispatched event (the last
+//! `MAX_EVENTS` are kept) and a sanitized snapshot of `AppState`, refreshed
+//! at most every `SNAPSHOT_INTERVAL`. On panic the hook writes both, with the
+//! panic message and a backtrace, to `$ZDX_HOME/crash/crash-<timestamp>.json`.
+//!
+//! Snapshots hold counts and identifiers only. Message bodies and the input
+//! text are included when `[tui].crash_report_content = true` and no tab is
+//! incognito.
+
+use std::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::panic::PanicHookInfo;
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, Mutex, TryLockError};
+use std::time::{Duration, Instant};
+
+use crossterm::event::Event as CrosstermEvent;
+use serde_json::{Value, json};
+use zdx_engine::core::events::AgentEvent;
+
+use crate::events::UiEvent;
+use crate::overlays::Overlay;
+use crate::state::{AgentState, AppState, TuiState};
+use crate::transcript::HistoryCell;
+
+/// Events kept in the ring buffer.
+const MAX_EVENTS: usize = 100;
+/// Minimum time between state snapshots.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_millis(250);
+/// Transcript cells included when message content is opted in.
+const MAX_CONTENT_CELLS: usize = 20;
+
+#[derive(Default)]
+struct Recorder {
+    events: VecDeque<String>,
+    snapshot: Option<Value>,
+    snapshot_at: Option<Instant>,
+}
+
+static RECORDER: LazyLock<Mutex<Recorder>> = LazyLock::new(Mutex::default);
+
+/// Records a dispatched event. Ticks and frames are skipped.
+pub fn record_event(event: &UiEvent) {
+    let Some(label) = event_label(event) else {
+        return;
+    };
+    let mut recorder = RECORDER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if recorder.events.len() == MAX_EVENTS {
+        recorder.events.pop_front();
+    }
+    recorder.events.push_back(label);
+}
+
+/// Refreshes the state snapshot unless one was taken within
+/// `SNAPSHOT_INTERVAL`.
+pub fn record_state(app: &AppState) {
+    let mut recorder = RECORDER
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if recorder
+        .snapshot_at
+        .is_some_and(|at| at.elapsed() < SNAPSHOT_INTERVAL)
+    {
+        return;
+    }
+    let include_content =
+        app.tui.config.tui.crash_report_content && !app.all_tabs().any(|tab| tab.thread.incognito);
+    recorder.snapshot = Some(snapshot(app, include_content));
+    recorder.snapshot_at = Some(Instant::now());
+}
+
+/// Writes a crash report for `info` and returns its path.
+///
+/// Called from the panic hook, so it never blocks on the recorder: if the
+/// panicking thread holds the lock, the report is written without state.
+pub fn write_report(info: &PanicHookInfo<'_>) -> Option<PathBuf> {
+    let dir = zdx_engine::config::paths::zdx_home().join("crash");
+    let (state, events) = match RECORDER.try_lock() {
+        Ok(recorder) => (recorder.snapshot.clone(), recorder.events.clone()),
+        Err(TryLockError::Poisoned(poisoned)) => {
+            let recorder = poisoned.into_inner();
+            (recorder.snapshot.clone(), recorder.events.clone())
+        }
+        Err(TryLockError::WouldBlock) => (None, VecDeque::new()),
+    };
+    let report = json!({
+        "version": env!("CARGO_PKG_VERSION"),
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "thread": std::thread::current().name().map(str::to_string),
+        "panic": {
+            "message": panic_message(info),
+            "location": info.location().map(ToString::to_string),
+        },
+        "backtrace": std::backtrace::Backtrace::force_capture().to_string(),
+        "state": state,
+        "events": events,
+    });
+    save_report(&dir, &report).ok()
+}
+
+fn save_report(dir: &Path, report: &Value) -> std::io::Result<PathBuf> {
+    fs::create_dir_all(dir)?;
+    let name = format!(
+        "crash-{}.json",
+        chrono::Local::now().format("%Y%m%d-%H%M%S%.3f")
+    );
+    let path = dir.join(name);
+    fs::write(&path, serde_json::to_vec_pretty(report)?)?;
+    Ok(path)
+}
+
+fn panic_message(info: &PanicHookInfo<'_>) -> String {
+    let payload = info.payload();
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "<non-string panic payload>".to_string())
+}
+
+fn snapshot(app: &AppState, include_content: bool) -> Value {
+    json!({
+        "tab_count": app.tab_count(),
+        "overlay": app.overlay.as_ref().map(overlay_name),
+        "focused": app.is_focused,
+        "pending_questions": app.pending_questions.len(),
+        "active_tab": tab_snapshot(&app.tui, include_content),
+        "background_tabs": app
+            .background_tabs
+            .iter()
+            .map(|tab| tab_snapshot(tab, false))
+            .collect::<Vec<_>>(),
+    })
+}
+
+fn tab_snapshot(tui: &TuiState, include_content: bool) -> Value {
+    let cells = tui.transcript.cells();
+    let mut cell_kinds: BTreeMap<&str, usize> = BTreeMap::new();
+    for cell in cells {
+        *cell_kinds.entry(cell_kind(cell)).or_default() += 1;
+    }
+    let running_tasks: Vec<String> = tui
+        .tasks
+        .0
+        .iter()
+        .filter(|(_, task)| task.is_running())
+        .map(|(kind, _)| format!("{kind:?}"))
+        .collect();
+    let input = tui.input.get_text();
+
+    let mut tab = json!({
+        "tab_id": tui.tab_id.0,
+        "model": tui.config.model,
+        "thinking_level": tui.config.thinking_level,
+        "thread_id": tui.thread.thread_handle.as_ref().map(|thread| thread.id.clone()),
+        "agent_state": agent_state_name(&tui.agent_state),
+        "running_tasks": running_tasks,
+        "transcript_cells": cells.len(),
+        "cell_kinds": cell_kinds,
+        "input_chars": input.chars().count(),
+        "queued_prompts": tui.input.queued.len(),
+    });
+    if include_content {
+        let skip = cells.len().saturating_sub(MAX_CONTENT_CELLS);
+        let messages: Vec<Value> = cells
+            .iter()
+            .skip(skip)
+            .filter_map(HistoryCell::pinnable_message)
+            .map(|(role, text)| json!({ "role": role, "text": text }))
+            .collect();
+        tab["input"] = Value::String(input);
+        tab["messages"] = Value::Array(messages);
+    }
+    tab
+}
+
+fn cell_kind(cell: &HistoryCell) -> &'static str {
+    match cell {
+        HistoryCell::User { .. } => "user",
+        HistoryCell::Assistant { .. } => "assistant",
+        HistoryCell::Tool { .. } => "tool",
+        HistoryCell::System { .. } => "system",
+        HistoryCell::Error { .. } => "error",
+        HistoryCell::Thinking { .. } => "thinking",
+        HistoryCell::Timing { .. } => "timing",
+        HistoryCell::FileChanges { .. } => "file_changes",
+        HistoryCell::Duel { .. } => "duel",
+    }
+}
+
+fn agent_state_name(state: &AgentState) -> &'static str {
+    match state {
+        AgentState::Idle => "idle",
+        AgentState::Waiting { .. } => "waiting",
+        AgentState::Streaming { .. } => "streaming",
+    }
+}
+
+fn overlay_name(overlay: &Overlay) -> &'static str {
+    match overlay {
+        Overlay::CommandPalette(_) => "command_palette",
+        Overlay::ModelPicker(_) => "model_picker",
+        Overlay::SkillPicker(_) => "skill_picker",
+        Overlay::ThinkingPicker(_) => "thinking_picker",
+        Overlay::ToolPicker(_) => "tool_picker",
+        Overlay::Params(_) => "params",
+        Overlay::ThreadPicker(_) => "thread_picker",
+        Overlay::QuickSwitch(_) => "quick_switch",
+        Overlay::Login(_) => "login",
+        Overlay::FilePicker(_) => "file_picker",
+        Overlay::SlashComplete(_) => "slash_complete",
+        Overlay::Timeline(_) => "timeline",
+        Overlay::Rename(_) => "rename",
+        Overlay::Tldr(_) => "tldr",
+        Overlay::Improve(_) => "improve",
+        Overlay::Context(_) => "context",
+        Overlay::Stats(_) => "stats",
+        Overlay::ImagePreview(_) => "image_preview",
+        Overlay::ToolDetail(_) => "tool_detail",
+        Overlay::FollowupPicker(_) => "followup_picker",
+        Overlay::AskUser(_) => "ask_user",
+        Overlay::Confirm(_) => "confirm",
+    }
+}
+
+/// A content-free label for an event (`None` for ticks and frames).
+fn event_label(event: &UiEvent) -> Option<String> {
+    let label = match event {
+        UiEvent::Tick | UiEvent::Frame { .. } => return None,
+        UiEvent::Terminal(event) => format!("terminal.{}", terminal_event_name(event)),
+        UiEvent::Agent(event) => format!("agent.{}", agent_event_name(event)),
+        UiEvent::BackgroundTabAgent { tab_id, event } => {
+            format!("tab{}.agent.{}", tab_id.0, agent_event_name(event))
+        }
+        UiEvent::AgentSpawned { .. } => "agent_spawned".to_string(),
+        UiEvent::BackgroundTabAgentSpawned { .. } => "background_tab_agent_spawned".to_string(),
+        UiEvent::UserQuestion(_) => "user_question".to_string(),
+        UiEvent::LoginResult { .. } => "login_result".to_string(),
+        UiEvent::LoginCallbackResult(_) => "login_callback_result".to_string(),
+        UiEvent::HandoffResult { .. } => "handoff_result".to_string(),
+        UiEvent::PromptBuilderResult { .. } => "prompt_builder_result".to_string(),
+        UiEvent::FilesDiscovered(files) => format!("files_discovered({})", files.len()),
+        UiEvent::ClipboardCopied => "clipboard_copied".to_string(),
+        UiEvent::BashExecuted { .. } => "bash_executed".to_string(),
+        UiEvent::RootDisplayResolved { .. } => "root_display_resolved".to_string(),
+        UiEvent::SystemPromptRefreshed { .. } => "system_prompt_refreshed".to_string(),
+        UiEvent::StartupContextLoaded { .. } => "startup_context_loaded".to_string(),
+        UiEvent::TaskStarted { kind, .. } => format!("task_started.{kind:?}"),
+        UiEvent::TaskCompleted { kind, .. } => format!("task_completed.{kind:?}"),
+        UiEvent::Thread(_) => "thread".to_string(),
+        UiEvent::Skill(_) => "skill".to_string(),
+        UiEvent::ImagePreviewDecoded { .. } => "image_preview_decoded".to_string(),
+        UiEvent::VoiceRecorded { .. } => "voice_recorded".to_string(),
+        UiEvent::VoiceTranscribed { .. } => "voice_transcribed".to_string(),
+        UiEvent::TldrResult { .. } => "tldr_result".to_string(),
+        UiEvent::PromptImproveResult { .. } => "prompt_improve_result".to_string(),
+        UiEvent::DuelTurnResult { .. } => "duel_turn_result".to_string(),
+        UiEvent::ContextResult { .. } => "context_result".to_string(),
+        UiEvent::DebugRequestResult { .. } => "debug_request_result".to_string(),
+    };
+    Some(label)
+}
+
+fn terminal_event_name(event: &CrosstermEvent) -> &'static str {
+    match event {
+        CrosstermEvent::Key(_) => "key",
+        CrosstermEvent::Mouse(_) => "mouse",
+        CrosstermEvent::Paste(_) => "paste",
+        CrosstermEvent::Resize(..) => "resize",
+        CrosstermEvent::FocusGained => "focus_gained",
+        CrosstermEvent::FocusLost => "focus_lost",
+    }
+}
+
+fn agent_event_name(event: &AgentEvent) -> &'static str {
+    match event {
+        AgentEvent::TurnStarted => "turn_started",
+        AgentEvent::ReasoningDelta { .. } => "reasoning_delta",
+        AgentEvent::ReasoningCompleted { .. } => "reasoning_completed",
+        AgentEvent::AssistantDelta { .. } => "assistant_delta",
+        AgentEvent::AssistantCompleted { .. } => "assistant_completed",
+        AgentEvent::ToolRequested { .. } => "tool_requested",
+        AgentEvent::ToolInputCompleted { .. } => "tool_input_completed",
+        AgentEvent::ToolInputDelta { .. } => "tool_input_delta",
+        AgentEvent::ToolInputPartial { .. } => "tool_input_partial",
+        AgentEvent::ToolStarted { .. } => "tool_started",
+        AgentEvent::ToolOutputDelta { .. } => "tool_output_delta",
+        AgentEvent::ToolCompleted { .. } => "tool_completed",
+        AgentEvent::Error { .. } => "error",
+        AgentEvent::Notice { .. } => "notice",
+        AgentEvent::LoopDetected { .. } => "loop_detected",
+        AgentEvent::ThinkingEscalated { .. } => "thinking_escalated",
+        AgentEvent::ProviderRetry { .. } => "provider_retry",
+        AgentEvent::TurnFinished { .. } => "turn_finished",
+        AgentEvent::TurnCheckpoint { .. } => "turn_checkpoint",
+        AgentEvent::UsageUpdate { .. } => "usage_update",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_event_labels_skip_ticks_and_hide_content() {
+        assert_eq!(event_label(&UiEvent::Tick), None);
+        let delta = UiEvent::Agent(AgentEvent::AssistantDelta {
+            text: "secret".to_string(),
+        });
+        assert_eq!(
+            event_label(&delta).as_deref(),
+            Some("agent.assistant_delta")
+        );
+        let paste = UiEvent::Terminal(CrosstermEvent::Paste("secret".to_string()));
+        assert_eq!(event_label(&paste).as_deref(), Some("terminal.paste"));
+    }
+
+    #[test]
+    fn test_snapshot_omits_content_unless_opted_in() {
+        let mut app = AppState::new(
+            zdx_engine::config::Config::default(),
+            PathBuf::from("."),
+            None,
+            None,
+        );
+        app.tui.input.set_text("draft prompt");
+
+        let sanitized = snapshot(&app, false);
+        assert_eq!(sanitized["active_tab"]["input_chars"], 12);
+        assert!(sanitized["active_tab"].get("input").is_none());
+        assert!(!sanitized.to_string().contains("draft prompt"));
+
+        let full = snapshot(&app, true);
+        assert_eq!(full["active_tab"]["input"], "draft prompt");
+    }
+}
@@ -0,0 +1,283 @@
+//! Two-model discussions (`/duel <model-a> <model-b> <topic>`).
+//!
+//! The two models take turns on the current thread until `[tui]
+//! duel_max_rounds` rounds are done. Each turn runs as a `TaskKind::Duel`
+//! task (see `zdx_engine::core::duel`); its cell waits with a spinner until
+//! the reply lands. While a duel runs, anything the user sends is a moderator
+//! interjection that the next speaker answers. Esc or `/duel stop` ends it.
+
+use zdx_engine::core::duel::{self, Duel, DuelSide, DuelTurn};
+use zdx_engine::core::thread_persistence;
+use zdx_engine::providers::ChatMessage;
+
+use crate::common::TaskKind;
+use crate::common::i18n::{Text, tr};
+use crate::effects::UiEffect;
+use crate::mutations::TranscriptMutation;
+use crate::state::{AppState, TuiState};
+use crate::transcript::{CellId, HistoryCell};
+
+/// `/duel` requests routed from the composer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DuelRequest {
+    /// Starts a discussion between two `model[@thinking]` specs.
+    Start { models: [String; 2], topic: String },
+    /// Adds a moderator message the next speaker answers.
+    Interject(String),
+    /// Ends the discussion, cancelling the turn in flight.
+    Stop,
+}
+
+/// A running discussion on a tab.
+#[derive(Debug, Clone)]
+pub struct DuelSession {
+    pub duel: Duel,
+    /// Cell of the turn in flight.
+    pub pending_cell: Option<CellId>,
+}
+
+pub fn handle_request(app: &mut AppState, request: &DuelRequest) -> Vec<UiEffect> {
+    let tui = &mut app.tui;
+    match request {
+        DuelRequest::Start { models, topic } => start(tui, models, topic),
+        DuelRequest::Interject(text) => interject(tui, text),
+        DuelRequest::Stop => stop(tui),
+    }
+}
+
+fn start(tui: &mut TuiState, models: &[String; 2], topic: &str) -> Vec<UiEffect> {
+    if tui.agent_state.is_running() || tui.duel.is_some() {
+        return notice(tui, tr(Text::StopCurrentTaskFirst).to_string());
+    }
+    let duel = Duel::new(models.clone(), topic, tui.config.tui.duel_max_rounds);
+    tui.transcript
+        .apply(TranscriptMutation::AppendSystemMessage(format!(
+            "Duel: {} vs {} on: {topic} ({} rounds). Type to interject; Esc or /duel stop ends it.",
+            duel.label(DuelSide::A),
+            duel.label(DuelSide::B),
+            duel.max_rounds
+        )));
+    let mut effects = record(tui, duel.opening_message());
+    tui.duel = Some(DuelSession {
+        duel,
+        pending_cell: None,
+    });
+    effects.extend(next_turn(tui));
+    effects
+}
+
+fn interject(tui: &mut TuiState, text: &str) -> Vec<UiEffect> {
+    let Some(session) = tui.duel.as_mut() else {
+        return notice(tui, "No duel is running.".to_string());
+    };
+    let text = text.trim();
+    session.duel.interject(text);
+    tui.transcript
+        .apply(TranscriptMutation::AppendSystemMessage(format!(
+            "{}: {text}",
+            duel::MODERATOR
+        )));
+    record(tui, duel::interjection_message(text))
+}
+
+fn stop(tui: &mut TuiState) -> Vec<UiEffect> {
+    let Some(session) = tui.duel.take() else {
+        return notice(tui, "No duel is running.".to_string());
+    };
+    if let Some(id) = session.pending_cell {
+        tui.transcript.remove_cell_by_id(id);
+    }
+    tui.transcript
+        .apply(TranscriptMutation::AppendSystemMessage(format!(
+            "Duel stopped after {} of {} turns.",
+            session.duel.turns_taken(),
+            session.duel.max_turns()
+        )));
+    vec![UiEffect::CancelTask {
+        kind: TaskKind::Duel,
+        token: None,
+    }]
+}
+
+/// Starts the next speaker's turn, or ends the duel at the round limit.
+fn next_turn(tui: &mut TuiState) -> Vec<UiEffect> {
+    let Some(session) = tui.duel.as_mut() else {
+        return vec![];
+    };
+    let Some(side) = session.duel.next_side() else {
+        let rounds = session.duel.max_rounds;
+        tui.duel = None;
+        return notice(tui, format!("Duel finished after {rounds} rounds."));
+    };
+    let cell = HistoryCell::duel_pending(session.duel.label(side), side == DuelSide::B);
+    session.pending_cell = Some(cell.id());
+    tui.transcript.push_cell(cell);
+    vec![UiEffect::RunDuelTurn { side }]
+}
+
+/// Applies a finished turn: fills its cell, records the reply in the duel
+/// and the thread, and hands over to the other speaker.
+pub fn handle_turn_result(
+    tui: &mut TuiState,
+    side: DuelSide,
+    result: Result<DuelTurn, String>,
+) -> Vec<UiEffect> {
+    let Some(session) = tui.duel.as_mut() else {
+        return vec![];
+    };
+    let pending = session.pending_cell.take();
+    let turn = match result {
+        Ok(turn) => turn,
+        Err(error) => {
+            tui.duel = None;
+            if let Some(id) = pending {
+                tui.transcript.remove_cell_by_id(id);
+            }
+            return notice(tui, format!("Duel stopped: {error}"));
+        }
+    };
+
+    let label = session.duel.label(side);
+    session.duel.record_turn(side, turn.text.clone());
+    if let Some(id) = pending {
+        tui.transcript.finish_duel_cell(id, &turn.text);
+    }
+    tui.thread.messages.push(ChatMessage::assistant_text(
+        duel::turn_text(&label, &turn.text),
+        None,
+    ));
+    let mut effects = Vec::new();
+    if tui.thread.thread_handle.is_some() {
+        effects.extend(
+            turn.events
+                .into_iter()
+                .map(|event| UiEffect::SaveThread { event }),
+        );
+    }
+    effects.extend(next_turn(tui));
+    effects
+}
+
+/// Appends `message` to the thread, persisting it when the thread is saved.
+fn record(tui: &mut TuiState, message: ChatMessage) -> Vec<UiEffect> {
+    let mut effects = Vec::new();
+    if tui.thread.thread_handle.is_some() {
+        effects.extend(
+            thread_persistence::messages_to_events(std::slice::from_ref(&message))
+                .into_iter()
+                .map(|event| UiEffect::SaveThread { event }),
+        );
+    }
+    tui.thread.messages.push(message);
+    effects
+}
+
+fn notice(tui: &mut TuiState, message: String) -> Vec<UiEffect> {
+    tui.transcript
+        .apply(TranscriptMutation::AppendSystemMessage(message));
+    vec![]
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use zdx_engine::config::Config;
+
+    use super::*;
+
+    fn app() -> AppState {
+        let mut config = Config::default();
+        config.tui.duel_max_rounds = 1;
+        AppState::new(config, PathBuf::from("."), None, None)
+    }
+
+    fn start_request() -> DuelRequest {
+        DuelRequest::Start {
+            models: ["openai:gpt-5".to_string(), "anthropic:claude".to_string()],
+            topic: "tabs or spaces".to_string(),
+        }
+    }
+
+    fn reply(text: &str) -> DuelTurn {
+        DuelTurn {
+            text: text.to_string(),
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn speakers_alternate_until_the_round_limit() {
+        let mut app = app();
+        let effects = handle_request(&mut app, &start_request());
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::RunDuelTurn { side: DuelSide::A }]
+        ));
+        assert!(app.tui.thread.messages[0].phase.as_deref() == Some(duel::DUEL_PHASE));
+
+        handle_request(&mut app, &DuelRequest::Interject("be concrete".to_string()));
+        let effects = handle_turn_result(&mut app.tui, DuelSide::A, Ok(reply("tabs")));
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::RunDuelTurn { side: DuelSide::B }]
+        ));
+        let effects = handle_turn_result(&mut app.tui, DuelSide::B, Ok(reply("spaces")));
+        assert!(effects.is_empty());
+        assert!(app.tui.duel.is_none());
+
+        let cells = app.tui.transcript.cells();
+        let turns: Vec<_> = cells
+            .iter()
+            .filter_map(|cell| match cell {
+                HistoryCell::Duel {
+                    content,
+                    second,
+                    is_pending: false,
+                    ..
+                } => Some((content.as_str(), *second)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(turns, [("tabs", false), ("spaces", true)]);
+        assert!(matches!(
+            cells.last(),
+            Some(HistoryCell::System { content, .. }) if content == "Duel finished after 1 rounds."
+        ));
+
+        // The thread reloads with the same speakers.
+        let rebuilt = TuiState::build_transcript_from_history(&app.tui.thread.messages);
+        let speakers: Vec<_> = rebuilt
+            .iter()
+            .filter_map(|cell| match cell {
+                HistoryCell::Duel { speaker, .. } => Some(speaker.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(speakers, ["openai:gpt-5", "anthropic:claude"]);
+    }
+
+    #[test]
+    fn stop_cancels_the_turn_in_flight() {
+        let mut app = app();
+        handle_request(&mut app, &start_request());
+        let effects = handle_request(&mut app, &DuelRequest::Stop);
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::CancelTask {
+                kind: TaskKind::Duel,
+                token: None
+            }]
+        ));
+        assert!(app.tui.duel.is_none());
+        assert!(
+            !app.tui
+                .transcript
+                .cells()
+                .iter()
+                .any(|cell| matches!(cell, HistoryCell::Duel { .. }))
+        );
+        // A late result for the cancelled turn is ignored.
+        assert!(handle_turn_result(&mut app.tui, DuelSide::A, Ok(reply("late"))).is_empty());
+    }
+}
@@ -22,6 +22,7 @@ use std::path::PathBuf;
 
 use tokio_util::sync::CancellationToken;
 use zdx_engine::config::ThinkingLevel;
+use zdx_engine::core::ask_user::UserQuestion;
 use zdx_engine::core::thread_persistence::ThreadEvent;
 use zdx_engine::providers::ProviderKind;
 
@@ -29,6 +30,44 @@ use crate::common::TaskKind;
 use crate::events::RecordedAudio;
 use crate::state::TabId;
 
+/// Bulk action from the thread picker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThreadAction {
+    /// Permanently delete the thread files.
+    Delete,
+    /// Move the threads into `threads/archive/`.
+    Archive,
+    /// Add a tag to each thread's meta.
+    Tag(String),
+    /// Write Markdown transcripts to the thread exports dir.
+    Export,
+}
+
+impl ThreadAction {
+    /// Whether the action removes threads from listings.
+    pub fn removes_threads(&self) -> bool {
+        matches!(self, ThreadAction::Delete | ThreadAction::Archive)
+    }
+
+    /// Result line for `count` threads, e.g. `Archived 3 threads`.
+    pub fn summary(&self, count: usize) -> String {
+        let threads = if count == 1 {
+            "1 thread".to_string()
+        } else {
+            format!("{count} threads")
+        };
+        match self {
+            ThreadAction::Delete => format!("Deleted {threads}"),
+            ThreadAction::Archive => format!("Archived {threads}"),
+            ThreadAction::Tag(tag) => format!("Tagged {threads} #{tag}"),
+            ThreadAction::Export => format!(
+                "Exported {threads} to {}",
+                zdx_engine::config::paths::thread_exports_dir().display()
+            ),
+        }
+    }
+}
+
 /// Effects returned by the reducer for the runtime to execute.
 ///
 /// The reducer returns `Vec<UiEffect>` from each update call.
@@ -41,11 +80,19 @@ pub enum UiEffect {
     /// Close the current tab when another tab exists; otherwise no-op.
     CloseCurrentTab,
 
+    /// Close background tabs, cancelling any running agent or bash task.
+    CloseBackgroundTabs { tab_ids: Vec<TabId> },
+
     /// Cycle to the next tab.
     CycleTab,
 
     /// Start an agent turn with the current input.
-    StartAgentTurn,
+    ///
+    /// `thinking_override` carries a `!think:<level>` prefix for this turn
+    /// only; the session's thinking level is left untouched.
+    StartAgentTurn {
+        thinking_override: Option<ThinkingLevel>,
+    },
 
     /// Start an agent turn for a background (non-active) tab.
     ///
@@ -55,11 +102,31 @@ pub enum UiEffect {
     /// `UiEvent::BackgroundTabAgentSpawned` so the reducer can apply the
     /// resulting `rx`/`cancel` to that tab without disturbing the active
     /// tab's `agent_state`.
-    StartAgentTurnInBackgroundTab { tab_id: TabId },
+    StartAgentTurnInBackgroundTab {
+        tab_id: TabId,
+        thinking_override: Option<ThinkingLevel>,
+    },
 
     /// Interrupt the running agent task.
     InterruptAgent,
 
+    /// Ask the running agent to finish its current tools and wrap up.
+    SoftStopAgent,
+
+    /// Queue a steering note for the running agent turn.
+    SteerAgent { note: String },
+
+    /// Regenerate the latest response with `feedback` as steering (`/redo`).
+    /// The old attempt stays in the thread log but leaves the transcript and
+    /// the context sent to the model.
+    RedoTurn { feedback: String },
+
+    /// Resolve an `Ask_User` question: `Some` answers it, `None` declines.
+    AnswerUserQuestion {
+        question: UserQuestion,
+        answer: Option<String>,
+    },
+
     /// Interrupt the running direct bash command.
     InterruptBash,
 
@@ -121,9 +188,18 @@ pub enum UiEffect {
         title: Option<String>,
     },
 
+    /// Apply a thread picker action to one or more threads.
+    ApplyThreadAction {
+        thread_ids: Vec<String>,
+        action: ThreadAction,
+    },
+
     /// Suggest a thread title from the first user message.
     SuggestThreadTitle { thread_id: String, message: String },
 
+    /// Summarize an idle thread and store the summary in its meta.
+    SummarizeThread { thread_id: String },
+
     /// Generate a TLDR/recap for the given thread (cheap subagent call).
     ///
     /// Result arrives as `UiEvent::TldrResult` and updates the open
@@ -139,6 +215,10 @@ pub enum UiEffect {
     /// required).
     AnalyzeContext { mode: crate::runtime::AnalysisMode },
 
+    /// Build the provider request the next turn would send, without sending
+    /// it. Result arrives as `UiEvent::DebugRequestResult`.
+    DebugRequest,
+
     /// Persist the model preference to config.
     PersistModel { model: String },
 
@@ -167,6 +247,16 @@ pub enum UiEffect {
     /// `<cwd>/.zdx/commands`) and refresh the in-memory list.
     ReloadCustomCommands,
 
+    /// Record a built-in slash command run in the local personal usage log
+    /// (`zdx stats --personal`). Skipped for incognito sessions.
+    RecordCommandUse { name: &'static str },
+
+    /// Show or hide the `/tour` onboarding tour (loads saved progress).
+    ToggleTour,
+
+    /// Persist `/tour` progress to `$ZDX_HOME/tour.json`.
+    SaveTourProgress { progress: crate::tour::TourProgress },
+
     /// Open models config file in default system editor/app.
     OpenModelsConfig,
 
@@ -183,6 +273,17 @@ pub enum UiEffect {
     /// inserted into the composer (never auto-sent).
     StartPromptBuilder { intent: String },
 
+    /// Rewrite a drafted prompt with `improve_model` for `/improve`.
+    /// The result arrives as `UiEvent::PromptImproveResult` and updates the
+    /// open `Overlay::Improve` if it still shows the same draft.
+    ImprovePrompt { draft: String },
+
+    /// Run `side`'s next `/duel` turn from `TuiState::duel`. The reply
+    /// arrives as `UiEvent::DuelTurnResult`.
+    RunDuelTurn {
+        side: zdx_engine::core::duel::DuelSide,
+    },
+
     /// Submit handoff prompt: create new thread and prefill the first message draft.
     HandoffSubmit {
         prompt: String,
@@ -195,9 +296,37 @@ pub enum UiEffect {
         mode: crate::overlays::ThreadPickerMode,
     },
 
+    /// Open the recent-thread quick switcher (loads thread list via I/O).
+    OpenQuickSwitch,
+
+    /// Load thread titles for `/threads <title>` completion.
+    ListThreadsForCompletion,
+
     /// Load a thread by ID (switch to that thread).
     LoadThread { thread_id: String },
 
+    /// Copy a thread into a new thread ID (optionally keeping only its first
+    /// `turns` turns) and switch to the copy.
+    DuplicateThread {
+        thread_id: String,
+        turns: Option<usize>,
+    },
+
+    /// Pin (or unpin) a turn of the active thread so conversation truncation
+    /// keeps it; `None` means the latest turn.
+    PinTurn { turn: Option<usize>, pinned: bool },
+
+    /// Pin (or unpin) the user/assistant message at transcript `cell_index`
+    /// so it is sent with every turn of the active thread.
+    SetMessagePinned { cell_index: usize, pinned: bool },
+
+    /// Pin (or unpin) a file so its contents are sent with every turn of the
+    /// active thread.
+    SetFilePinned { path: String, pinned: bool },
+
+    /// Set (or clear with `None`) the active thread's response style preset.
+    SetStyle { style: Option<String> },
+
     /// Load a thread by ID into a new background tab (keep current thread).
     LoadThreadAsTab { thread_id: String },
 
@@ -255,8 +384,12 @@ pub enum UiEffect {
         turn_number: usize,
     },
 
-    /// Execute a bash command directly (user `$` shortcut).
-    ExecuteBash { command: String },
+    /// Execute a bash command directly (user `$` shortcut), optionally in a
+    /// root-relative `cwd` (`$[dir] cmd`).
+    ExecuteBash {
+        command: String,
+        cwd: Option<String>,
+    },
 
     // ========================================================================
     // Cancellation Effects
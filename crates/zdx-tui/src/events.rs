@@ -26,19 +26,31 @@
 
 use std::collections::HashSet;
 use std::path::PathBuf;
-use std::sync::Arc;
 
 use crossterm::event::Event as CrosstermEvent;
-use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
+use zdx_engine::core::ask_user::UserQuestion;
+use zdx_engine::core::event_bus::EventSubscriber;
 use zdx_engine::core::events::{AgentEvent, ToolOutput};
 use zdx_engine::core::thread_persistence::{Thread, ThreadSummary, Usage};
 use zdx_engine::providers::ChatMessage;
 
 use crate::common::{TaskCompleted, TaskKind, TaskStarted};
+use crate::effects::ThreadAction;
 use crate::state::TabId;
 use crate::transcript::HistoryCell;
 
+/// System prompt and context discovered at startup (context files, skills,
+/// warnings), loaded after the first paint so the TUI appears immediately.
+#[derive(Debug)]
+pub struct StartupContext {
+    pub prompt: Option<String>,
+    pub prompt_variant: Option<String>,
+    pub loaded_agents_paths: Vec<PathBuf>,
+    pub loaded_skills: Vec<zdx_engine::skills::Skill>,
+    pub warnings: Vec<String>,
+}
+
 /// Thread event enum for async thread operations.
 ///
 /// Results-only events for thread I/O. Loading flags are set by the reducer
@@ -53,6 +65,18 @@ pub enum ThreadUiEvent {
         mode: crate::overlays::ThreadPickerMode,
     },
 
+    /// Recent threads loaded for the quick switcher.
+    RecentLoaded {
+        threads: Vec<ThreadSummary>,
+        active_thread_ids: HashSet<String>,
+    },
+
+    /// Thread list loaded for slash-command completion.
+    CompletionListLoaded {
+        threads: Vec<ThreadSummary>,
+        active_thread_ids: HashSet<String>,
+    },
+
     /// Thread list load failed.
     ListFailed { error: String },
 
@@ -138,6 +162,14 @@ pub enum ThreadUiEvent {
     /// Thread rename failed.
     RenameFailed { error: String },
 
+    /// Thread picker action finished; `thread_ids` are the threads it
+    /// succeeded for, `errors` describe the rest.
+    ActionApplied {
+        action: ThreadAction,
+        thread_ids: Vec<String>,
+        errors: Vec<String>,
+    },
+
     /// Worktree setup succeeded.
     WorktreeReady { path: PathBuf },
 
@@ -156,6 +188,12 @@ pub enum ThreadUiEvent {
         thread_id: String,
         title: Option<String>,
     },
+
+    /// Idle-time thread summary stored (None if generation failed).
+    SummaryGenerated {
+        thread_id: String,
+        summary: Option<String>,
+    },
 }
 
 /// Skill list/install events.
@@ -246,8 +284,10 @@ pub enum UiEvent {
 
     /// Agent turn spawned; reducer should set agent state to Waiting.
     AgentSpawned {
-        rx: mpsc::UnboundedReceiver<Arc<AgentEvent>>,
+        rx: EventSubscriber,
         cancel: CancellationToken,
+        /// Soft-stop token: finish running tools, then wrap up.
+        soft_stop: CancellationToken,
         /// For btw tabs: thread handle created on first send.
         thread_handle: Option<Thread>,
         /// For btw tabs: updated message list after thread preparation.
@@ -263,8 +303,9 @@ pub enum UiEvent {
     /// turn on the right tab.
     BackgroundTabAgentSpawned {
         tab_id: TabId,
-        rx: mpsc::UnboundedReceiver<Arc<AgentEvent>>,
+        rx: EventSubscriber,
         cancel: CancellationToken,
+        soft_stop: CancellationToken,
         thread_handle: Option<Thread>,
         messages: Option<Vec<ChatMessage>>,
     },
@@ -272,6 +313,9 @@ pub enum UiEvent {
     /// Agent event for a background (non-active) tab.
     BackgroundTabAgent { tab_id: TabId, event: AgentEvent },
 
+    /// The agent (any tab) asked the user a question via `Ask_User`.
+    UserQuestion(UserQuestion),
+
     /// Async login token exchange completed.
     LoginResult { result: Result<(), String> },
 
@@ -290,6 +334,19 @@ pub enum UiEvent {
         result: Result<String, String>,
     },
 
+    /// `/improve` rewrite completed (Ok = suggested prompt, Err = error message).
+    PromptImproveResult {
+        draft: String,
+        result: Result<String, String>,
+    },
+
+    /// A `/duel` turn completed (Ok = reply and its thread events, Err =
+    /// error message).
+    DuelTurnResult {
+        side: zdx_engine::core::duel::DuelSide,
+        result: Result<zdx_engine::core::duel::DuelTurn, String>,
+    },
+
     /// File discovery completed.
     FilesDiscovered(Vec<PathBuf>),
 
@@ -300,6 +357,7 @@ pub enum UiEvent {
     BashExecuted {
         id: String,
         command: String,
+        cwd: Option<String>,
         result: ToolOutput,
     },
 
@@ -310,9 +368,17 @@ pub enum UiEvent {
         display_path: String,
     },
 
-    /// Effective system prompt refresh result for the current root.
+    /// Effective system prompt refresh result for the current root, with the
+    /// prompt variant it was built with.
     SystemPromptRefreshed {
-        result: Result<Option<String>, String>,
+        result: Result<(Option<String>, Option<String>), String>,
+    },
+
+    /// Startup context for `root`, built in the background after the first
+    /// paint.
+    StartupContextLoaded {
+        root: PathBuf,
+        result: Result<StartupContext, String>,
     },
 
     /// Task lifecycle: runtime started a task (cancel token optional).
@@ -365,4 +431,8 @@ pub enum UiEvent {
     ContextResult {
         result: Result<crate::runtime::ContextReport, String>,
     },
+
+    /// `/debug request` completed (Ok = pretty-printed redacted request,
+    /// Err = error message).
+    DebugRequestResult { result: Result<String, String> },
 }
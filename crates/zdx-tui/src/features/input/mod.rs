@@ -19,7 +19,7 @@ mod update;
 // Re-export reducer functions
 // Re-export view functions
 pub use render::{calculate_input_height, render_input, render_input_with_cursor};
-pub use state::{HandoffState, InputState, PromptBuilderState};
+pub use state::{CompareInput, HandoffState, InputState, PromptBuilderState};
 pub use text_buffer::{CursorMove, TextBuffer};
 pub use update::{
     InputContext, TabContext, build_fast_mode_toggle_actions, build_send_effects,
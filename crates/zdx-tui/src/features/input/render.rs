@@ -305,35 +305,7 @@ pub fn render_input_with_cursor(
         return;
     }
 
-    // Build top-left title: model name + fast/thinking badges
-    let base_style = Style::default().fg(Color::DarkGray);
-    let fast_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::DIM);
-    let thinking_style = Style::default()
-        .fg(Color::DarkGray)
-        .add_modifier(Modifier::DIM);
-
-    // Show the favorite alias alongside the actual model id when one matches.
-    let favorite_alias = state.config.active_favorite_alias();
-    let label = match favorite_alias {
-        Some(alias) => format!(" {alias} · {}", state.config.model),
-        None => format!(" {}", state.config.model),
-    };
-    let mut title_spans = vec![Span::styled(label, base_style)];
-
-    if fast_mode_enabled_for_model(&state.config, &state.config.model) {
-        title_spans.push(Span::styled(" [F]", fast_style));
-    }
-
-    if state.config.thinking_level != ThinkingLevel::Off
-        && model_supports_reasoning(&state.config.model)
-    {
-        title_spans.push(Span::styled(
-            format!(" [{}]", state.config.thinking_level.display_name()),
-            thinking_style,
-        ));
-    }
-
-    title_spans.push(Span::styled(" ", base_style));
+    let title_spans = build_title_spans(state);
 
     // Build top-right title: AMP-style usage display
     // Format: "{percentage}% of {context} · ${cost} (cached: ${savings})"
@@ -696,6 +668,58 @@ fn build_token_breakdown(usage: &ThreadUsage) -> Vec<Span<'static>> {
     ]
 }
 
+/// Builds the top-left input title: model name plus fast/thinking/style/queue
+/// badges.
+fn build_title_spans(state: &TuiState) -> Vec<Span<'static>> {
+    let base_style = Style::default().fg(Color::DarkGray);
+    let fast_style = Style::default().fg(Color::Cyan).add_modifier(Modifier::DIM);
+    let thinking_style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::DIM);
+
+    // Show the favorite alias alongside the actual model id when one matches.
+    let favorite_alias = state.config.active_favorite_alias();
+    let label = match favorite_alias {
+        Some(alias) => format!(" {alias} · {}", state.config.model),
+        None => format!(" {}", state.config.model),
+    };
+    let mut title_spans = vec![Span::styled(label, base_style)];
+
+    if fast_mode_enabled_for_model(&state.config, &state.config.model) {
+        title_spans.push(Span::styled(" [F]", fast_style));
+    }
+
+    if state.config.thinking_level != ThinkingLevel::Off
+        && model_supports_reasoning(&state.config.model)
+    {
+        title_spans.push(Span::styled(
+            format!(" [{}]", state.config.thinking_level.display_name()),
+            thinking_style,
+        ));
+    }
+
+    if let Some(style) = &state.thread.style {
+        title_spans.push(Span::styled(format!(" [style: {style}]"), thinking_style));
+    }
+
+    if let Some(badge) = queued_badge(state.input.queued.len()) {
+        title_spans.push(Span::styled(
+            badge,
+            Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::DIM),
+        ));
+    }
+
+    title_spans.push(Span::styled(" ", base_style));
+    title_spans
+}
+
+/// Input title badge for prompts waiting behind the running turn.
+fn queued_badge(count: usize) -> Option<String> {
+    (count > 0).then(|| format!(" · {count} queued"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -735,6 +759,13 @@ mod tests {
         assert_eq!((after_emoji.cursor_row, after_emoji.cursor_col), (0, 3));
     }
 
+    #[test]
+    fn queued_badge_shows_count_only_when_queued() {
+        assert_eq!(queued_badge(0), None);
+        assert_eq!(queued_badge(1).as_deref(), Some(" · 1 queued"));
+        assert_eq!(queued_badge(3).as_deref(), Some(" · 3 queued"));
+    }
+
     fn wrap_textarea_with_text(text: &str, width: usize) -> WrappedTextarea {
         let mut textarea = TextBuffer::default();
         textarea.insert_str(text);
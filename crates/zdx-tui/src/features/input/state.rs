@@ -68,6 +68,18 @@ pub enum HandoffState {
     Ready,
 }
 
+/// `/compare` state for this tab's composer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum CompareInput {
+    #[default]
+    Off,
+    /// The next message runs on both models (`model[@thinking]` specs).
+    Armed([String; 2]),
+    /// A comparison started from this tab is waiting for a pick; normal
+    /// messages are held until it is picked or discarded.
+    Open,
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum VoicePhase {
     #[default]
@@ -217,6 +229,15 @@ pub struct InputState {
     /// Draft text saved when navigating history.
     pub draft: Option<String>,
 
+    /// Bash-mode (`$cmd`) history shared across threads and sessions, oldest
+    /// first and without the `$` prefix. Only the active tab holds it; tab
+    /// switches move it along.
+    pub bash_history: Vec<String>,
+
+    /// `$`-prefixed entries being navigated, when navigation started from a
+    /// `$` draft (None = navigating `history`).
+    bash_matches: Option<Vec<String>>,
+
     /// Handoff feature state.
     pub handoff: HandoffState,
 
@@ -240,6 +261,9 @@ pub struct InputState {
 
     /// Voice dictation state.
     pub voice: VoiceState,
+
+    /// `/compare` state.
+    pub compare: CompareInput,
 }
 
 impl Default for InputState {
@@ -258,6 +282,8 @@ impl InputState {
             history: Vec::new(),
             history_index: None,
             draft: None,
+            bash_history: Vec::new(),
+            bash_matches: None,
             handoff: HandoffState::Idle,
             prompt_builder: PromptBuilderState::Idle,
             queued: std::collections::VecDeque::new(),
@@ -266,6 +292,7 @@ impl InputState {
             pending_images: Vec::new(),
             image_counter: 0,
             voice: VoiceState::default(),
+            compare: CompareInput::Off,
         }
     }
 
@@ -903,6 +930,34 @@ impl InputState {
     pub fn reset_navigation(&mut self) {
         self.history_index = None;
         self.draft = None;
+        self.bash_matches = None;
+    }
+
+    /// Records a bash-mode command, moving an earlier copy to the end.
+    pub fn push_bash_history(&mut self, command: &str) {
+        self.bash_history.retain(|entry| entry != command);
+        self.bash_history.push(command.to_string());
+    }
+
+    /// Bash history entries (as `$cmd`) matching a single-line `$` draft, or
+    /// None when `text` isn't one or there is no bash history.
+    fn bash_matches_for(&self, text: &str) -> Option<Vec<String>> {
+        let prefix = text.strip_prefix('$')?.trim_start();
+        if text.contains('\n') || self.bash_history.is_empty() {
+            return None;
+        }
+        Some(
+            self.bash_history
+                .iter()
+                .filter(|command| command.starts_with(prefix))
+                .map(|command| format!("${command}"))
+                .collect(),
+        )
+    }
+
+    /// The entries ↑/↓ currently move through.
+    fn nav_entries(&self) -> &[String] {
+        self.bash_matches.as_deref().unwrap_or(&self.history)
     }
 
     /// Clears command history (for /new, handoff submit).
@@ -916,6 +971,17 @@ impl InputState {
         self.queued.push_back(QueuedPrompt { text, images });
     }
 
+    /// Puts prompts at the front of the queue, keeping their order. Used for
+    /// steering notes the finished turn never applied.
+    pub fn requeue_front(&mut self, texts: Vec<String>) {
+        for text in texts.into_iter().rev() {
+            self.queued.push_front(QueuedPrompt {
+                text,
+                images: Vec::new(),
+            });
+        }
+    }
+
     /// Pops the next queued prompt, if any.
     pub fn pop_queued_prompt(&mut self) -> Option<QueuedPrompt> {
         self.queued.pop_front()
@@ -939,14 +1005,17 @@ impl InputState {
     }
 
     /// Returns true if up arrow should navigate history (not move cursor).
+    ///
+    /// A single-line `$` draft navigates bash history instead, when there is any.
     pub fn should_navigate_up(&self) -> bool {
-        if self.history.is_empty() {
-            return false;
-        }
         if self.history_index.is_some() {
             return true;
         }
-        if self.get_text().is_empty() {
+        let text = self.get_text();
+        if self.history.is_empty() && self.bash_matches_for(&text).is_none() {
+            return false;
+        }
+        if text.is_empty() {
             return true;
         }
         let (row, _col) = self.textarea.cursor();
@@ -964,15 +1033,20 @@ impl InputState {
     }
 
     /// Navigates up in command history.
+    ///
+    /// Starting from a `$` draft cycles bash history entries that begin with
+    /// the typed command.
     pub fn navigate_up(&mut self) {
-        if self.history.is_empty() {
-            return;
-        }
-
         if self.history_index.is_none() {
             let current = self.get_text();
+            self.bash_matches = self.bash_matches_for(&current);
+            let len = self.nav_entries().len();
+            if len == 0 {
+                self.bash_matches = None;
+                return;
+            }
             self.draft = Some(current);
-            self.history_index = Some(self.history.len() - 1);
+            self.history_index = Some(len - 1);
         } else if let Some(idx) = self.history_index
             && idx > 0
         {
@@ -980,7 +1054,7 @@ impl InputState {
         }
 
         if let Some(idx) = self.history_index
-            && let Some(entry) = self.history.get(idx).cloned()
+            && let Some(entry) = self.nav_entries().get(idx).cloned()
         {
             self.set_text(&entry);
         }
@@ -992,14 +1066,14 @@ impl InputState {
             return;
         };
 
-        if idx + 1 < self.history.len() {
+        if idx + 1 < self.nav_entries().len() {
             self.history_index = Some(idx + 1);
-            if let Some(entry) = self.history.get(idx + 1).cloned() {
+            if let Some(entry) = self.nav_entries().get(idx + 1).cloned() {
                 self.set_text(&entry);
             }
         } else {
             let draft = self.draft.take().unwrap_or_default();
-            self.history_index = None;
+            self.reset_navigation();
             self.set_text(&draft);
         }
     }
@@ -1009,6 +1083,31 @@ impl InputState {
 mod tests {
     use super::*;
 
+    #[test]
+    fn dollar_draft_navigates_matching_bash_history() {
+        let mut input = InputState::new();
+        input.history = vec!["explain this".to_string()];
+        for command in ["git status", "cargo test", "git log", "git status"] {
+            input.push_bash_history(command);
+        }
+
+        input.set_text("$git");
+        assert!(input.should_navigate_up());
+        input.navigate_up();
+        assert_eq!(input.get_text(), "$git status");
+        input.navigate_up();
+        assert_eq!(input.get_text(), "$git log");
+        input.navigate_up();
+        assert_eq!(input.get_text(), "$git log", "stops at the oldest match");
+        input.navigate_down();
+        input.navigate_down();
+        assert_eq!(input.get_text(), "$git");
+
+        input.set_text("");
+        input.navigate_up();
+        assert_eq!(input.get_text(), "explain this");
+    }
+
     #[test]
     fn set_text_drops_pending_images_whose_placeholder_is_gone() {
         let mut input = InputState::new();
@@ -6,15 +6,19 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers as CrosstermKeyModifiers};
 use zdx_engine::agent_activity;
 use zdx_engine::config::{Config, ModelFavorite, ThinkingLevel};
+use zdx_engine::core::response_style;
 use zdx_engine::core::thread_persistence::ThreadEvent;
 use zdx_engine::providers::ChatMessage;
 
 use super::CursorMove;
 use super::state::{
-    HandoffState, InputState, LARGE_PASTE_CHAR_THRESHOLD, PendingImage, PendingPaste,
+    CompareInput, HandoffState, InputState, LARGE_PASTE_CHAR_THRESHOLD, PendingImage, PendingPaste,
     PromptBuilderState,
 };
+use crate::common::i18n::{Text, tr};
 use crate::common::{TaskKind, Tasks, sanitize_for_display};
+use crate::compare::CompareRequest;
+use crate::duel::DuelRequest;
 use crate::effects::UiEffect;
 use crate::mutations::{ConfigMutation, StateMutation, ThreadMutation, TranscriptMutation};
 use crate::overlays::OverlayRequest;
@@ -427,6 +431,21 @@ fn handle_navigation(input: &mut InputState, key: KeyEvent, mods: &Modifiers) ->
 // Control keys: Ctrl+C, Escape
 // =============================================================================
 
+/// First stop request asks the agent to wrap up after the running tools; a
+/// second one cancels the turn immediately.
+fn stop_agent(agent_state: &AgentState) -> KeyResult {
+    if agent_state.soft_stop_requested() {
+        return (vec![UiEffect::InterruptAgent], vec![], None);
+    }
+    (
+        vec![UiEffect::SoftStopAgent],
+        vec![StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(tr(Text::StoppingAfterCurrentStep).to_string()),
+        )],
+        None,
+    )
+}
+
 fn handle_control_keys(
     input: &mut InputState,
     ctx: &InputContext<'_>,
@@ -438,7 +457,7 @@ fn handle_control_keys(
         // Ctrl+C: interrupt agent, clear input, or quit app
         KeyCode::Char('c') if mods.ctrl() => {
             if ctx.agent_state.is_running() {
-                Some((vec![UiEffect::InterruptAgent], vec![], None))
+                Some(stop_agent(ctx.agent_state))
             } else if ctx.tasks.state(TaskKind::Bash).is_running() {
                 Some((vec![UiEffect::InterruptBash], vec![], None))
             } else if !input.get_text().is_empty() {
@@ -457,7 +476,13 @@ fn handle_control_keys(
                 return Some(result);
             }
             if ctx.agent_state.is_running() {
-                Some((vec![UiEffect::InterruptAgent], vec![], None))
+                Some(stop_agent(ctx.agent_state))
+            } else if ctx.tasks.state(TaskKind::Duel).is_running() {
+                Some((
+                    vec![],
+                    vec![],
+                    Some(OverlayRequest::Duel(DuelRequest::Stop)),
+                ))
             } else if ctx.tasks.state(TaskKind::Bash).is_running() {
                 Some((
                     vec![UiEffect::CancelTask {
@@ -573,7 +598,7 @@ fn handle_voice_hotkey(input: &mut InputState) -> KeyResult {
             vec![],
             vec![StateMutation::Transcript(
                 TranscriptMutation::AppendSystemMessage(
-                    "Voice transcription already in progress.".to_string(),
+                    tr(Text::VoiceTranscriptionInProgress).to_string(),
                 ),
             )],
             None,
@@ -594,9 +619,10 @@ fn handle_overlays(
     mods: &Modifiers,
 ) -> Option<KeyResult> {
     match code {
-        // `/` when input is empty: open command palette
+        // `/` when input is empty: insert it and open inline command completion
         KeyCode::Char('/') if mods.none() && input.get_text().is_empty() => {
-            Some((vec![], vec![], Some(OverlayRequest::CommandPalette)))
+            input.textarea.insert_char('/');
+            Some((vec![], vec![], Some(OverlayRequest::SlashComplete)))
         }
         // Ctrl+O: open command palette
         KeyCode::Char('o') if mods.only_ctrl() => {
@@ -618,6 +644,10 @@ fn handle_overlays(
         KeyCode::Char('r') if mods.only_ctrl() => {
             Some((vec![], vec![], Some(OverlayRequest::Tldr)))
         }
+        // Ctrl+P: quick-switch between recent threads (mirrors `/recent`)
+        KeyCode::Char('p') if mods.only_ctrl() => {
+            Some((vec![UiEffect::OpenQuickSwitch], vec![], None))
+        }
         // Ctrl+B: open prompt builder (mirrors `/prompt-builder`).
         // Mirrors guards in `execute_prompt_builder` in the command palette.
         KeyCode::Char('b') if mods.only_ctrl() => {
@@ -871,6 +901,37 @@ fn compute_at_trigger_position(input: &InputState) -> usize {
 // Input submission logic
 // =============================================================================
 
+/// Claims the submission while a duel, bash command, or thread creation is
+/// running.
+fn handle_submit_while_task_running(
+    input: &mut InputState,
+    trimmed: &str,
+    tasks: &Tasks,
+) -> Option<KeyResult> {
+    if tasks.state(TaskKind::Duel).is_running() {
+        return Some(handle_submit_during_duel(input, trimmed));
+    }
+
+    let bash_running = tasks.state(TaskKind::Bash).is_running();
+    if bash_running {
+        return Some((vec![], vec![], None));
+    }
+
+    let thread_create_running = tasks.state(TaskKind::ThreadCreate).is_running();
+    if thread_create_running {
+        return Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(
+                    "Creating new thread. Wait for it to finish before sending.".to_string(),
+                ),
+            )],
+            None,
+        ));
+    }
+    None
+}
+
 /// Handles input submission.
 #[allow(clippy::too_many_arguments)]
 fn submit_input(
@@ -897,22 +958,8 @@ fn submit_input(
         return handle_submit_while_agent_running(input, trimmed, &text);
     }
 
-    let bash_running = tasks.state(TaskKind::Bash).is_running();
-    if bash_running {
-        return (vec![], vec![], None);
-    }
-
-    let thread_create_running = tasks.state(TaskKind::ThreadCreate).is_running();
-    if thread_create_running {
-        return (
-            vec![],
-            vec![StateMutation::Transcript(
-                TranscriptMutation::AppendSystemMessage(
-                    "Creating new thread. Wait for it to finish before sending.".to_string(),
-                ),
-            )],
-            None,
-        );
+    if let Some(result) = handle_submit_while_task_running(input, trimmed, tasks) {
+        return result;
     }
 
     let title_task_running = tasks.state(TaskKind::ThreadTitle).is_running();
@@ -945,9 +992,33 @@ fn submit_input(
     }
 
     // Try slash commands (/fast, etc.)
+    if let Some(result) = handle_duplicate_command(input, trimmed, thread_id.as_deref()) {
+        return result;
+    }
+    if let Some(result) = handle_pin_file_command(input, trimmed, thread_id.as_deref()) {
+        return result;
+    }
+    if let Some(result) = handle_pin_command(input, trimmed, thread_id.as_deref()) {
+        return result;
+    }
+    if let Some(result) = handle_style_command(input, trimmed, config, thread_id.as_deref()) {
+        return result;
+    }
+    if let Some(result) = handle_debug_request_command(input, trimmed) {
+        return result;
+    }
+    if let Some(result) = handle_redo_command(input, trimmed) {
+        return result;
+    }
     if let Some(result) = handle_slash_commands(input, trimmed, config, model_id) {
         return result;
     }
+    if let Some(result) = handle_compare_command(input, trimmed) {
+        return result;
+    }
+    if let Some(result) = handle_duel_command(input, trimmed) {
+        return result;
+    }
 
     // Try bash commands
     if let Some((mut effects, mutations, overlay)) = handle_bash_commands(input, trimmed, &text) {
@@ -965,6 +1036,20 @@ fn submit_input(
         return (vec![], vec![], None);
     }
 
+    if let Err(message) = split_thinking_prefix(&text) {
+        return (
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(message),
+            )],
+            None,
+        );
+    }
+
+    if let Some(result) = handle_compare_submission(input, &text) {
+        return result;
+    }
+
     let images = input.take_images();
     input.history.push(text.clone());
     input.reset_navigation();
@@ -974,6 +1059,40 @@ fn submit_input(
     (effects, mutations, None)
 }
 
+/// Prefix that overrides the thinking level for a single turn, e.g.
+/// `!think:high refactor this module`.
+const THINKING_PREFIX: &str = "!think:";
+
+/// Splits a leading `!think:<level>` prefix off a message.
+///
+/// Returns the per-turn level (if any) and the message body without the
+/// prefix. Errors on an unknown level or an empty body so the composer can
+/// keep the text for the user to fix.
+pub fn split_thinking_prefix(text: &str) -> Result<(Option<ThinkingLevel>, &str), String> {
+    let Some(rest) = text.trim_start().strip_prefix(THINKING_PREFIX) else {
+        return Ok((None, text));
+    };
+    let (level_name, body) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+    let Some(level) = ThinkingLevel::from_name(level_name) else {
+        let valid = ThinkingLevel::all()
+            .iter()
+            .map(ThinkingLevel::display_name)
+            .collect::<Vec<_>>()
+            .join(", ");
+        return Err(format!(
+            "Unknown thinking level '{level_name}'. Use {THINKING_PREFIX}<level> with one of: {valid}."
+        ));
+    };
+    let body = body.trim_start();
+    if body.trim().is_empty() {
+        return Err(format!(
+            "Add a message after {THINKING_PREFIX}{}.",
+            level.display_name()
+        ));
+    }
+    Ok((Some(level), body))
+}
+
 /// Returns `Some(advisory)` if a modal flow's generation phase currently
 /// owns the composer, blocking the user from submitting via Enter.
 ///
@@ -1032,6 +1151,31 @@ fn handle_submit_while_agent_running(
             None,
         );
     }
+    if let Some(result) = handle_steer_command(input, trimmed) {
+        return result;
+    }
+    if redo_feedback(trimmed).is_some() {
+        return (
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(
+                    "Stop the current turn before /redo, or use /steer to guide it.".to_string(),
+                ),
+            )],
+            None,
+        );
+    }
+    if duel_args(trimmed).is_some() {
+        return (
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(
+                    "Stop the current turn before starting a /duel.".to_string(),
+                ),
+            )],
+            None,
+        );
+    }
     if trimmed.starts_with('$') {
         return (
             vec![],
@@ -1052,6 +1196,248 @@ fn handle_submit_while_agent_running(
     (vec![], vec![], None)
 }
 
+/// Handles `/steer <note>` while a turn runs: the note is handed to the agent
+/// and applied before its next provider call instead of waiting in the queue.
+fn handle_steer_command(input: &mut InputState, trimmed: &str) -> Option<KeyResult> {
+    let rest = trimmed.strip_prefix("/steer")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    let note = rest.trim();
+    if note.is_empty() {
+        return Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(tr(Text::SteerUsage).to_string()),
+            )],
+            None,
+        ));
+    }
+
+    input.history.push(trimmed.to_string());
+    input.reset_navigation();
+    input.clear();
+    Some((
+        vec![UiEffect::SteerAgent {
+            note: note.to_string(),
+        }],
+        vec![StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(format!(
+                "Steering: {note} (applied before the next model call)"
+            )),
+        )],
+        None,
+    ))
+}
+
+/// Returns the argument of `/redo` (or `/regenerate`), `None` for other input.
+fn redo_feedback(trimmed: &str) -> Option<&str> {
+    let rest = trimmed
+        .strip_prefix("/redo")
+        .or_else(|| trimmed.strip_prefix("/regenerate"))?;
+    (rest.is_empty() || rest.starts_with(char::is_whitespace)).then(|| rest.trim())
+}
+
+/// Handles `/redo <feedback>`: regenerates the latest response with the
+/// feedback as steering.
+fn handle_redo_command(input: &mut InputState, trimmed: &str) -> Option<KeyResult> {
+    let feedback = redo_feedback(trimmed)?;
+    if feedback.is_empty() {
+        input.clear();
+        return Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(
+                    "Usage: /redo <feedback>, e.g. /redo shorter".to_string(),
+                ),
+            )],
+            None,
+        ));
+    }
+    input.history.push(trimmed.to_string());
+    input.reset_navigation();
+    let effect = UiEffect::RedoTurn {
+        feedback: feedback.to_string(),
+    };
+    input.clear();
+    Some((vec![effect], vec![], None))
+}
+
+/// Handles `/duplicate N`: copies the current thread up to turn `N` and
+/// switches to the copy. Bare `/duplicate` goes through the command palette.
+fn handle_duplicate_command(
+    input: &mut InputState,
+    trimmed: &str,
+    thread_id: Option<&str>,
+) -> Option<KeyResult> {
+    let rest = trimmed
+        .strip_prefix("/duplicate")
+        .or_else(|| trimmed.strip_prefix("/clone"))?;
+    if !rest.starts_with(char::is_whitespace) || rest.trim().is_empty() {
+        return None;
+    }
+    input.clear();
+    let message = |text: &str| {
+        Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(text.to_string()),
+            )],
+            None,
+        ))
+    };
+    let Ok(turns) = rest.trim().parse::<usize>() else {
+        return message("Usage: /duplicate [turn number]");
+    };
+    let Some(thread_id) = thread_id else {
+        return message("Duplicate requires an active thread.");
+    };
+    Some((
+        vec![UiEffect::DuplicateThread {
+            thread_id: thread_id.to_string(),
+            turns: Some(turns),
+        }],
+        vec![],
+        None,
+    ))
+}
+
+/// Handles `/pin N` and `/unpin N`: pins (or unpins) turn `N` so
+/// conversation truncation keeps it. Bare `/pin` goes through the command
+/// palette and acts on the latest turn.
+fn handle_pin_command(
+    input: &mut InputState,
+    trimmed: &str,
+    thread_id: Option<&str>,
+) -> Option<KeyResult> {
+    let (command, rest, pinned) = match trimmed.strip_prefix("/pin") {
+        Some(rest) => ("pin", rest, true),
+        None => ("unpin", trimmed.strip_prefix("/unpin")?, false),
+    };
+    if !rest.starts_with(char::is_whitespace) || rest.trim().is_empty() {
+        return None;
+    }
+    input.clear();
+    let message = |text: String| {
+        Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(text),
+            )],
+            None,
+        ))
+    };
+    let Ok(turn) = rest.trim().parse::<usize>() else {
+        return message(format!("Usage: /{command} [turn number]"));
+    };
+    if thread_id.is_none() {
+        return message("Pinning requires an active thread.".to_string());
+    }
+    Some((
+        vec![UiEffect::PinTurn {
+            turn: Some(turn),
+            pinned,
+        }],
+        vec![],
+        None,
+    ))
+}
+
+/// Handles `/pin-file <path>` and `/unpin-file <path>`: pins (or unpins) a
+/// file so its contents are sent with every turn.
+fn handle_pin_file_command(
+    input: &mut InputState,
+    trimmed: &str,
+    thread_id: Option<&str>,
+) -> Option<KeyResult> {
+    let (command, rest, pinned) = match trimmed.strip_prefix("/pin-file") {
+        Some(rest) => ("pin-file", rest, true),
+        None => ("unpin-file", trimmed.strip_prefix("/unpin-file")?, false),
+    };
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    input.clear();
+    let message = |text: String| {
+        Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(text),
+            )],
+            None,
+        ))
+    };
+    let path = rest.trim();
+    if path.is_empty() {
+        return message(format!("Usage: /{command} <path>"));
+    }
+    if thread_id.is_none() {
+        return message("Pinning requires an active thread.".to_string());
+    }
+    Some((
+        vec![UiEffect::SetFilePinned {
+            path: path.to_string(),
+            pinned,
+        }],
+        vec![],
+        None,
+    ))
+}
+
+/// Handles `/style [name|off]`: sets the thread's response style preset, or
+/// lists the available styles when no name is given.
+fn handle_style_command(
+    input: &mut InputState,
+    trimmed: &str,
+    config: &Config,
+    thread_id: Option<&str>,
+) -> Option<KeyResult> {
+    let rest = trimmed.strip_prefix("/style")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    input.clear();
+    let message = |text: String| {
+        Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(text),
+            )],
+            None,
+        ))
+    };
+    let styles = response_style::names(config).join(", ");
+    let name = rest.trim();
+    if name.is_empty() {
+        return message(format!(
+            "Styles: {styles}. Use /style <name>, or /style off to clear."
+        ));
+    }
+    let style = if name.eq_ignore_ascii_case("off") {
+        None
+    } else {
+        let Some(style) = response_style::canonical_name(config, name) else {
+            return message(format!("Unknown style '{name}'. Styles: {styles}."));
+        };
+        Some(style.to_string())
+    };
+    if thread_id.is_none() {
+        return message("Styles require an active thread.".to_string());
+    }
+    Some((vec![UiEffect::SetStyle { style }], vec![], None))
+}
+
+/// Handles `/debug request`: shows the provider request the next turn would
+/// send, built without sending it.
+fn handle_debug_request_command(input: &mut InputState, trimmed: &str) -> Option<KeyResult> {
+    let rest = trimmed.strip_prefix("/debug")?;
+    if rest.trim() != "request" || !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    input.clear();
+    Some((vec![UiEffect::DebugRequest], vec![], None))
+}
+
 fn handle_slash_commands(
     input: &mut InputState,
     trimmed: &str,
@@ -1086,7 +1472,192 @@ fn handle_slash_commands(
         }
     };
 
-    Some((effects, mutations, None))
+    Some((effects, mutations, None))
+}
+
+/// Handles `/compare <model-a> <model-b>` (arms the next message),
+/// `/compare 1|2` (continues a branch), and `/compare off`.
+fn handle_compare_command(input: &mut InputState, trimmed: &str) -> Option<KeyResult> {
+    let rest = trimmed.strip_prefix("/compare")?;
+    if !rest.is_empty() && !rest.starts_with(char::is_whitespace) {
+        return None;
+    }
+    input.clear();
+    let message = |text: String| {
+        Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(text),
+            )],
+            None,
+        ))
+    };
+    let request =
+        |request: CompareRequest| Some((vec![], vec![], Some(OverlayRequest::Compare(request))));
+    let args: Vec<&str> = rest.split_whitespace().collect();
+    match args.as_slice() {
+        [] => match &input.compare {
+            CompareInput::Armed([a, b]) => message(format!(
+                "Your next message runs on {a} and {b}. /compare off cancels."
+            )),
+            _ => message(
+                "Usage: /compare <model-a> <model-b> runs your next message on both models side by side."
+                    .to_string(),
+            ),
+        },
+        ["off"] if matches!(input.compare, CompareInput::Armed(_)) => {
+            input.compare = CompareInput::Off;
+            message("Comparison cancelled.".to_string())
+        }
+        ["off"] => request(CompareRequest::Discard),
+        [pick @ ("1" | "2")] => request(CompareRequest::Pick(usize::from(*pick == "2"))),
+        [a, b] => {
+            if input.compare == CompareInput::Open {
+                return message(COMPARE_OPEN_MSG.to_string());
+            }
+            input.compare = CompareInput::Armed([(*a).to_string(), (*b).to_string()]);
+            message(format!(
+                "Your next message runs on {a} and {b} side by side. /compare off cancels."
+            ))
+        }
+        _ => message("Usage: /compare <model-a> <model-b> | 1 | 2 | off".to_string()),
+    }
+}
+
+const COMPARE_OPEN_MSG: &str = "A comparison is open: /compare 1 or /compare 2 continues that branch, /compare off discards both.";
+
+/// Routes a normal message to `/compare`: starts the armed comparison, or
+/// holds the message while one is open.
+fn handle_compare_submission(input: &mut InputState, text: &str) -> Option<KeyResult> {
+    let message = |text: &str| {
+        Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(text.to_string()),
+            )],
+            None,
+        ))
+    };
+    match &input.compare {
+        CompareInput::Off => None,
+        CompareInput::Open => message(COMPARE_OPEN_MSG),
+        CompareInput::Armed(_) if !input.pending_images.is_empty() => {
+            message("Comparisons don't support image attachments; remove them or /compare off.")
+        }
+        CompareInput::Armed(models) => {
+            let models = models.clone();
+            input.compare = CompareInput::Off;
+            input.history.push(text.to_string());
+            input.reset_navigation();
+            input.clear();
+            Some((
+                vec![],
+                vec![],
+                Some(OverlayRequest::Compare(CompareRequest::Start {
+                    models,
+                    prompt: text.to_string(),
+                })),
+            ))
+        }
+    }
+}
+
+/// Returns the arguments of `/duel`, `None` for other input.
+fn duel_args(trimmed: &str) -> Option<&str> {
+    let rest = trimmed.strip_prefix("/duel")?;
+    (rest.is_empty() || rest.starts_with(char::is_whitespace)).then(|| rest.trim())
+}
+
+/// Splits `<model-a> <model-b> <topic>`; `None` when any part is missing.
+fn split_duel_start(args: &str) -> Option<(&str, &str, &str)> {
+    let (a, rest) = args.split_once(char::is_whitespace)?;
+    let (b, topic) = rest.trim_start().split_once(char::is_whitespace)?;
+    let topic = topic.trim();
+    (!topic.is_empty()).then_some((a, b, topic))
+}
+
+/// Handles `/duel <model-a> <model-b> <topic>` (starts a discussion) and
+/// `/duel stop`.
+fn handle_duel_command(input: &mut InputState, trimmed: &str) -> Option<KeyResult> {
+    let args = duel_args(trimmed)?;
+    let request = if args == "stop" {
+        DuelRequest::Stop
+    } else if let Some((a, b, topic)) = split_duel_start(args) {
+        input.history.push(trimmed.to_string());
+        input.reset_navigation();
+        DuelRequest::Start {
+            models: [a.to_string(), b.to_string()],
+            topic: topic.to_string(),
+        }
+    } else {
+        input.clear();
+        return Some((
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(
+                    "Usage: /duel <model-a> <model-b> <topic> lets two models discuss the topic in turns; /duel stop ends it."
+                        .to_string(),
+                ),
+            )],
+            None,
+        ));
+    };
+    input.clear();
+    Some((vec![], vec![], Some(OverlayRequest::Duel(request))))
+}
+
+/// Routes input while a `/duel` runs: `/duel stop` ends it, other commands
+/// wait, and anything else is a moderator interjection.
+fn handle_submit_during_duel(input: &mut InputState, trimmed: &str) -> KeyResult {
+    if trimmed.is_empty() {
+        return (vec![], vec![], None);
+    }
+    if duel_args(trimmed) == Some("stop") {
+        input.clear();
+        return (
+            vec![],
+            vec![],
+            Some(OverlayRequest::Duel(DuelRequest::Stop)),
+        );
+    }
+    if trimmed.starts_with('/') || trimmed.starts_with('$') {
+        return (
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(
+                    "A duel is running: type to interject, or Esc / /duel stop to end it."
+                        .to_string(),
+                ),
+            )],
+            None,
+        );
+    }
+    input.history.push(trimmed.to_string());
+    input.reset_navigation();
+    input.clear();
+    (
+        vec![],
+        vec![],
+        Some(OverlayRequest::Duel(DuelRequest::Interject(
+            trimmed.to_string(),
+        ))),
+    )
+}
+
+/// Splits a leading `[dir]` working-directory prefix off a `$` command:
+/// `[crates/core] cargo test` runs `cargo test` in `crates/core`. A space
+/// after `[` means the `[` test builtin, not a prefix.
+fn split_bash_cwd(command: &str) -> (Option<&str>, &str) {
+    if let Some(rest) = command.strip_prefix('[')
+        && let Some((dir, rest)) = rest.split_once(']')
+        && !dir.is_empty()
+        && !dir.starts_with(char::is_whitespace)
+        && rest.starts_with(char::is_whitespace)
+        && !rest.trim().is_empty()
+    {
+        return (Some(dir.trim_end()), rest.trim());
+    }
+    (None, command)
 }
 
 fn handle_bash_commands(input: &mut InputState, trimmed: &str, text: &str) -> Option<KeyResult> {
@@ -1098,18 +1669,21 @@ fn handle_bash_commands(input: &mut InputState, trimmed: &str, text: &str) -> Op
                 vec![],
                 vec![StateMutation::Transcript(
                     TranscriptMutation::AppendSystemMessage(
-                        "Usage: $<command> (e.g., $ls -la)".to_string(),
+                        "Usage: $<command> or $[dir] <command> (e.g., $ls -la, $[crates/core] cargo test)".to_string(),
                     ),
                 )],
                 None,
             ));
         }
         input.history.push(text.to_string());
+        input.push_bash_history(command);
         input.reset_navigation();
         input.clear();
+        let (cwd, command) = split_bash_cwd(command);
         return Some((
             vec![UiEffect::ExecuteBash {
                 command: command.to_string(),
+                cwd: cwd.map(str::to_string),
             }],
             vec![],
             None,
@@ -1267,29 +1841,34 @@ pub fn build_send_effects_for_tab(
     images: Vec<PendingImage>,
     tab: TabContext,
 ) -> (Vec<UiEffect>, Vec<StateMutation>) {
+    // Invalid prefixes are rejected at submit time; anything that still
+    // fails to parse here (e.g. a queued draft) is sent verbatim.
+    let (thinking_override, text) = split_thinking_prefix(text).unwrap_or((None, text));
     let user_event = ThreadEvent::user_message(text);
     let mut effects: Vec<UiEffect> = match tab {
         TabContext::Active => {
+            let start = UiEffect::StartAgentTurn { thinking_override };
             if thread_id.is_some() {
-                vec![
-                    UiEffect::SaveThread { event: user_event },
-                    UiEffect::StartAgentTurn,
-                ]
+                vec![UiEffect::SaveThread { event: user_event }, start]
             } else {
-                vec![UiEffect::StartAgentTurn]
+                vec![start]
             }
         }
         TabContext::Background(tab_id) => {
+            let start = UiEffect::StartAgentTurnInBackgroundTab {
+                tab_id,
+                thinking_override,
+            };
             if thread_id.is_some() {
                 vec![
                     UiEffect::SaveThreadInBackgroundTab {
                         tab_id,
                         event: user_event,
                     },
-                    UiEffect::StartAgentTurnInBackgroundTab { tab_id },
+                    start,
                 ]
             } else {
-                vec![UiEffect::StartAgentTurnInBackgroundTab { tab_id }]
+                vec![start]
             }
         }
     };
@@ -1313,10 +1892,18 @@ pub fn build_send_effects_for_tab(
         )
     };
 
-    let mutations = vec![
+    let mut mutations = vec![
         StateMutation::Transcript(TranscriptMutation::AppendCell(Box::new(cell))),
         StateMutation::Thread(ThreadMutation::AppendMessage(message)),
     ];
+    if let Some(level) = thinking_override {
+        mutations.push(StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(format!(
+                "Thinking level {} for this turn.",
+                level.display_name()
+            )),
+        ));
+    }
 
     // Title suggestion is intentionally only emitted for the active tab.
     // Background tabs are queue-drain only: their first turn (which is
@@ -1585,6 +2172,17 @@ mod tests {
     use crate::common::{TaskId, Tasks};
     use crate::state::AgentState;
 
+    #[test]
+    fn bash_cwd_prefix_is_split_off() {
+        assert_eq!(
+            split_bash_cwd("[crates/core] cargo test"),
+            (Some("crates/core"), "cargo test")
+        );
+        assert_eq!(split_bash_cwd("[ -f x ] && ls"), (None, "[ -f x ] && ls"));
+        assert_eq!(split_bash_cwd("[dir]"), (None, "[dir]"));
+        assert_eq!(split_bash_cwd("ls -la"), (None, "ls -la"));
+    }
+
     #[test]
     fn submit_is_blocked_while_thread_create_is_running() {
         let mut input = InputState::default();
@@ -1637,6 +2235,288 @@ mod tests {
         }
     }
 
+    #[test]
+    fn steer_while_running_emits_note_instead_of_queueing() {
+        let mut input = InputState::default();
+        input.set_text("/steer focus on the parser");
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let (_tx, rx) = zdx_engine::core::agent::create_event_channel();
+        let agent_state = AgentState::Waiting {
+            rx,
+            cancel: tokio_util::sync::CancellationToken::new(),
+            soft_stop: tokio_util::sync::CancellationToken::new(),
+        };
+        let ctx = InputContext {
+            agent_state: &agent_state,
+            tasks: &tasks,
+            thread_id: Some("thread-123".to_string()),
+            thread_title: None,
+            config: &config,
+            model_id: &config.model,
+            active_thread_ids: &active_thread_ids,
+            root: std::path::Path::new("."),
+        };
+
+        let (effects, _mutations, _overlay) = handle_main_key(
+            &mut input,
+            &ctx,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::SteerAgent { note }] if note == "focus on the parser"
+        ));
+        assert!(!input.has_queued());
+        assert!(input.get_text().is_empty());
+    }
+
+    #[test]
+    fn debug_request_command_emits_dry_run_effect() {
+        let mut input = InputState::default();
+        input.set_text("/debug request");
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let ctx = InputContext {
+            agent_state: &AgentState::Idle,
+            tasks: &tasks,
+            thread_id: Some("thread-123".to_string()),
+            thread_title: None,
+            config: &config,
+            model_id: &config.model,
+            active_thread_ids: &active_thread_ids,
+            root: std::path::Path::new("."),
+        };
+
+        let (effects, _mutations, _overlay) = handle_main_key(
+            &mut input,
+            &ctx,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+
+        assert!(matches!(effects.as_slice(), [UiEffect::DebugRequest]));
+        assert!(input.get_text().is_empty());
+    }
+
+    #[test]
+    fn duel_command_starts_and_routes_interjections() {
+        let mut tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        let mut input = InputState::default();
+        input.set_text("/duel openai:gpt-5 anthropic:claude@high  tabs or spaces?");
+        let ctx = make_idle_ctx(&tasks, &active_thread_ids, &config);
+        let (_effects, _mutations, overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(matches!(
+            overlay,
+            Some(OverlayRequest::Duel(DuelRequest::Start { models, topic }))
+                if models == ["openai:gpt-5", "anthropic:claude@high"] && topic == "tabs or spaces?"
+        ));
+
+        input.set_text("/duel gpt-5 claude");
+        let (_effects, mutations, overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(overlay.is_none());
+        assert!(matches!(
+            mutations.as_slice(),
+            [StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(text))]
+                if text.starts_with("Usage: /duel")
+        ));
+
+        tasks.state_mut(TaskKind::Duel).active = Some(TaskId(1));
+        let ctx = make_idle_ctx(&tasks, &active_thread_ids, &config);
+        input.set_text("be concrete");
+        let (_effects, _mutations, overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(matches!(
+            overlay,
+            Some(OverlayRequest::Duel(DuelRequest::Interject(text))) if text == "be concrete"
+        ));
+
+        let esc = KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE);
+        let (_effects, _mutations, overlay) = handle_main_key(&mut input, &ctx, esc);
+        assert!(matches!(
+            overlay,
+            Some(OverlayRequest::Duel(DuelRequest::Stop))
+        ));
+    }
+
+    #[test]
+    fn redo_command_emits_redo_effect_with_feedback() {
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let ctx = make_idle_ctx(&tasks, &active_thread_ids, &config);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        let mut input = InputState::default();
+        input.set_text("/redo  use iterators instead ");
+        let (effects, _mutations, _overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::RedoTurn { feedback }] if feedback == "use iterators instead"
+        ));
+        assert!(input.get_text().is_empty());
+
+        input.set_text("/redo");
+        let (effects, mutations, _overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(effects.is_empty());
+        assert!(matches!(
+            mutations.as_slice(),
+            [StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(text))]
+                if text.starts_with("Usage: /redo")
+        ));
+    }
+
+    #[test]
+    fn duplicate_command_with_turn_emits_duplicate_effect() {
+        let mut input = InputState::default();
+        input.set_text("/duplicate 2");
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let ctx = InputContext {
+            agent_state: &AgentState::Idle,
+            tasks: &tasks,
+            thread_id: Some("thread-123".to_string()),
+            thread_title: None,
+            config: &config,
+            model_id: &config.model,
+            active_thread_ids: &active_thread_ids,
+            root: std::path::Path::new("."),
+        };
+
+        let (effects, _mutations, _overlay) = handle_main_key(
+            &mut input,
+            &ctx,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::DuplicateThread { thread_id, turns: Some(2) }] if thread_id == "thread-123"
+        ));
+        assert!(input.get_text().is_empty());
+    }
+
+    #[test]
+    fn style_command_resolves_preset_name() {
+        let mut input = InputState::default();
+        input.set_text("/style PT-BR");
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let ctx = InputContext {
+            agent_state: &AgentState::Idle,
+            tasks: &tasks,
+            thread_id: Some("thread-123".to_string()),
+            thread_title: None,
+            config: &config,
+            model_id: &config.model,
+            active_thread_ids: &active_thread_ids,
+            root: std::path::Path::new("."),
+        };
+
+        let (effects, _mutations, _overlay) = handle_main_key(
+            &mut input,
+            &ctx,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::SetStyle { style: Some(style) }] if style == "pt-BR"
+        ));
+
+        input.set_text("/style formal");
+        let (effects, mutations, _overlay) = handle_main_key(
+            &mut input,
+            &ctx,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+        assert!(effects.is_empty());
+        assert_eq!(mutations.len(), 1);
+    }
+
+    #[test]
+    fn unpin_command_with_turn_emits_pin_effect() {
+        let mut input = InputState::default();
+        input.set_text("/unpin 3");
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let ctx = InputContext {
+            agent_state: &AgentState::Idle,
+            tasks: &tasks,
+            thread_id: Some("thread-123".to_string()),
+            thread_title: None,
+            config: &config,
+            model_id: &config.model,
+            active_thread_ids: &active_thread_ids,
+            root: std::path::Path::new("."),
+        };
+
+        let (effects, _mutations, _overlay) = handle_main_key(
+            &mut input,
+            &ctx,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::PinTurn {
+                turn: Some(3),
+                pinned: false
+            }]
+        ));
+        assert!(input.get_text().is_empty());
+    }
+
+    #[test]
+    fn compare_arms_then_routes_next_message() {
+        let mut input = InputState::default();
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let ctx = make_idle_ctx(&tasks, &active_thread_ids, &config);
+        let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+        input.set_text("/compare model-a model-b@high");
+        let (effects, _mutations, overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(effects.is_empty() && overlay.is_none());
+        assert_eq!(
+            input.compare,
+            CompareInput::Armed(["model-a".to_string(), "model-b@high".to_string()])
+        );
+
+        input.set_text("Review this diff");
+        let (effects, mutations, overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(effects.is_empty() && mutations.is_empty());
+        assert!(matches!(
+            overlay,
+            Some(OverlayRequest::Compare(CompareRequest::Start { models, prompt }))
+                if models[1] == "model-b@high" && prompt == "Review this diff"
+        ));
+        assert_eq!(input.compare, CompareInput::Off);
+        assert!(input.get_text().is_empty());
+
+        // While a comparison is open, messages stay in the composer.
+        input.compare = CompareInput::Open;
+        input.set_text("follow-up");
+        let (_effects, _mutations, overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(overlay.is_none());
+        assert_eq!(input.get_text(), "follow-up");
+
+        input.set_text("/compare 2");
+        let (_effects, _mutations, overlay) = handle_main_key(&mut input, &ctx, enter);
+        assert!(matches!(
+            overlay,
+            Some(OverlayRequest::Compare(CompareRequest::Pick(1)))
+        ));
+    }
+
     fn fav(alias: &str, model: &str, thinking: ThinkingLevel) -> ModelFavorite {
         ModelFavorite {
             alias: alias.to_string(),
@@ -2121,13 +3001,78 @@ mod tests {
         // prompt as the user message.
         let started_turn = effects
             .iter()
-            .any(|e| matches!(e, UiEffect::StartAgentTurn));
+            .any(|e| matches!(e, UiEffect::StartAgentTurn { .. }));
         assert!(
             started_turn,
             "expected StartAgentTurn after accepting Ready; got {effects:?}"
         );
     }
 
+    #[test]
+    fn thinking_prefix_overrides_level_for_one_turn() {
+        let mut input = InputState::default();
+        input.set_text("!think:high refactor this module");
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let ctx = make_idle_ctx(&tasks, &active_thread_ids, &config);
+
+        let (effects, mutations, _overlay) = handle_main_key(
+            &mut input,
+            &ctx,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::StartAgentTurn {
+                thinking_override: Some(ThinkingLevel::High)
+            }]
+        ));
+        assert!(mutations.iter().any(|mutation| matches!(
+            mutation,
+            StateMutation::Thread(ThreadMutation::AppendMessage(message))
+                if matches!(
+                    &message.content,
+                    zdx_engine::providers::MessageContent::Text(text) if text == "refactor this module"
+                )
+        )));
+        assert!(
+            !mutations
+                .iter()
+                .any(|mutation| matches!(mutation, StateMutation::Config(_)))
+        );
+    }
+
+    #[test]
+    fn thinking_prefix_rejects_unknown_level_and_keeps_input() {
+        let mut input = InputState::default();
+        input.set_text("!think:huge do it");
+        let tasks = Tasks::default();
+        let active_thread_ids = std::collections::HashSet::new();
+        let config = Config::default();
+        let ctx = make_idle_ctx(&tasks, &active_thread_ids, &config);
+
+        let (effects, mutations, _overlay) = handle_main_key(
+            &mut input,
+            &ctx,
+            KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE),
+        );
+
+        assert!(effects.is_empty());
+        assert_eq!(input.get_text(), "!think:huge do it");
+        assert!(mutations.iter().any(|mutation| matches!(
+            mutation,
+            StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(message))
+                if message.starts_with("Unknown thinking level 'huge'")
+        )));
+        assert!(split_thinking_prefix("!think:low").is_err());
+        assert_eq!(
+            split_thinking_prefix("plain !think:low").unwrap(),
+            (None, "plain !think:low")
+        );
+    }
+
     #[test]
     fn is_image_path_accepts_local_image_paths() {
         assert!(is_image_path("/tmp/photo.png"));
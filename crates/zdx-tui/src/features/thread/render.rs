@@ -19,6 +19,13 @@ use crate::overlays::{ThreadPickerState, ThreadScope};
 /// dynamically but capped at this value.
 pub const MAX_VISIBLE_THREADS: usize = 10;
 
+/// Thread list width inside the modal.
+const LIST_WIDTH: u16 = 60;
+/// Preview pane width; the pane is only shown when the terminal fits both.
+const PREVIEW_WIDTH: u16 = 56;
+/// Minimum modal height while the preview pane is shown.
+const PREVIEW_MIN_HEIGHT: u16 = 16;
+
 /// Renders the thread picker overlay.
 ///
 /// - `Switch` mode (opened from command palette): centered modal with title + hints.
@@ -58,11 +65,21 @@ fn render_thread_picker_modal(
             .count(),
     };
 
-    let picker_width = 60;
-    let picker_height = (visible_count as u16 + 7).max(9);
+    let show_preview = area.width >= LIST_WIDTH + PREVIEW_WIDTH + 4;
+    let (picker_width, min_height) = if show_preview {
+        (LIST_WIDTH + PREVIEW_WIDTH, PREVIEW_MIN_HEIGHT)
+    } else {
+        (LIST_WIDTH, 9)
+    };
+    let picker_height = (visible_count as u16 + 7).max(min_height);
 
     let picker_area = calculate_overlay_area(area, input_top_y, picker_width, picker_height);
     let title = thread_picker_title(picker.scope, tree_items.len(), thread_count);
+    let title = if picker.marked.is_empty() {
+        title
+    } else {
+        format!("{title} · {} marked", picker.marked.len())
+    };
     render_overlay_container(frame, picker_area, &title, Color::Magenta);
 
     let inner = Rect::new(
@@ -82,13 +99,27 @@ fn render_thread_picker_modal(
 
     // filter(1) + sep(1) top, sep(1) + hints(1) bottom
     let list_height = inner.height.saturating_sub(4) as usize;
-    let list_area = Rect::new(inner.x, inner.y + 2, inner.width, list_height as u16);
+    let list_width = if show_preview {
+        LIST_WIDTH.saturating_sub(2)
+    } else {
+        inner.width
+    };
+    let list_area = Rect::new(inner.x, inner.y + 2, list_width, list_height as u16);
+    if show_preview {
+        let preview_area = Rect::new(
+            inner.x + list_width,
+            inner.y + 2,
+            inner.width.saturating_sub(list_width),
+            list_height as u16,
+        );
+        render_preview_pane(frame, picker, preview_area);
+    }
 
     let items: Vec<ListItem> = tree_items
         .iter()
         .skip(picker.offset)
         .take(list_height)
-        .map(|item| build_thread_list_item(item, picker, inner.width))
+        .map(|item| build_thread_list_item(item, picker, list_width))
         .collect();
 
     let list = List::new(items)
@@ -248,8 +279,104 @@ fn render_empty_picker(
     }
 }
 
+/// Renders the highlighted thread's summary (when one was generated) and its
+/// first/last messages right of the list.
+fn render_preview_pane(frame: &mut Frame, picker: &ThreadPickerState, area: Rect) {
+    use ratatui::widgets::{Block, Borders, Wrap};
+
+    let block = Block::default()
+        .borders(Borders::LEFT)
+        .border_style(Style::default().fg(Color::DarkGray));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = match (picker.selected_thread(), picker.selected_preview()) {
+        (Some(thread), _) if picker.is_thread_active(&thread.id) => {
+            vec![Line::styled("Running — no preview", dim)]
+        }
+        (Some(_), Some(preview)) if preview.head.is_empty() => {
+            vec![Line::styled("No messages", dim)]
+        }
+        (Some(_), Some(preview)) => {
+            let mut lines: Vec<Line> = preview.head.iter().map(preview_line).collect();
+            if preview.skipped > 0 {
+                lines.push(Line::styled(
+                    format!("  ··· {} more ···", preview.skipped),
+                    dim,
+                ));
+            }
+            lines.extend(preview.tail.iter().map(preview_line));
+            lines
+        }
+        (Some(_), None) => vec![Line::styled("Loading…", dim)],
+        (None, _) => Vec::new(),
+    };
+    if let Some(summary) = picker
+        .selected_thread()
+        .filter(|thread| !picker.is_thread_active(&thread.id))
+        .and_then(|thread| thread.summary.as_deref())
+    {
+        lines.splice(
+            0..0,
+            [
+                Line::styled(
+                    summary.to_string(),
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::ITALIC),
+                ),
+                Line::default(),
+            ],
+        );
+    }
+    frame.render_widget(
+        Paragraph::new(lines).wrap(Wrap { trim: true }),
+        Rect::new(
+            inner.x + 1,
+            inner.y,
+            inner.width.saturating_sub(1),
+            inner.height,
+        ),
+    );
+}
+
+fn preview_line(message: &crate::overlays::PreviewMessage) -> Line<'static> {
+    const MAX_CHARS: usize = 160;
+    let (marker, color) = if message.is_user {
+        ("› ", Color::Cyan)
+    } else {
+        ("‹ ", Color::White)
+    };
+    let text = message
+        .text
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    Line::from(vec![
+        Span::styled(marker, Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            truncate_with_ellipsis(&text, MAX_CHARS),
+            Style::default().fg(color),
+        ),
+    ])
+}
+
 fn render_picker_hints(frame: &mut Frame, picker: &ThreadPickerState, inner_area: Rect) {
     use crate::overlays::render_utils::{InputHint, render_hints};
+    if let Some(message) = picker_status_line(picker) {
+        let hints_area = Rect::new(
+            inner_area.x,
+            inner_area.y + inner_area.height.saturating_sub(1),
+            inner_area.width,
+            1,
+        );
+        frame.render_widget(
+            Paragraph::new(message).alignment(Alignment::Center),
+            hints_area,
+        );
+        return;
+    }
     let copy_hint = if picker.should_show_copied() {
         InputHint::new("✓", "Copied!")
     } else {
@@ -268,6 +395,12 @@ fn render_picker_hints(frame: &mut Frame, picker: &ThreadPickerState, inner_area
             copy_hint,
             InputHint::new("Ctrl+S", toggle_hint),
             InputHint::new("Ctrl+T", "open as tab"),
+            InputHint::new("Ctrl+Y", "duplicate"),
+            InputHint::new("Space", "mark"),
+            InputHint::new("Ctrl+D", "delete"),
+            InputHint::new("Ctrl+A", "archive"),
+            InputHint::new("Ctrl+G", "tag"),
+            InputHint::new("Ctrl+E", "export"),
             InputHint::new("Ctrl+U", "clear filter"),
             InputHint::new("Esc", "cancel"),
         ],
@@ -275,6 +408,29 @@ fn render_picker_hints(frame: &mut Frame, picker: &ThreadPickerState, inner_area
     );
 }
 
+/// Tag input or last action result, shown instead of the hints.
+fn picker_status_line(picker: &ThreadPickerState) -> Option<Line<'static>> {
+    if let Some(tag) = &picker.tag_input {
+        let count = picker.action_targets().len();
+        return Some(Line::from(vec![
+            Span::styled(
+                format!("Tag {count} thread(s): #"),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(tag.clone(), Style::default().fg(Color::Magenta)),
+            Span::styled("█", Style::default().fg(Color::Magenta)),
+            Span::styled(
+                " Enter apply · Esc cancel",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]));
+    }
+    picker
+        .notice
+        .as_ref()
+        .map(|notice| Line::styled(notice.clone(), Style::default().fg(Color::Yellow)))
+}
+
 fn build_thread_list_item(
     item: &crate::thread::ThreadDisplayItem<'_>,
     picker: &ThreadPickerState,
@@ -303,6 +459,11 @@ fn build_thread_list_item(
         if item.depth > 0 { "└── " } else { "" }
     );
     let handoff_label = if item.is_handoff { "[handoff] " } else { "" };
+    let source_label = if thread.is_bot_thread() {
+        "[telegram] "
+    } else {
+        ""
+    };
     let running_label = if picker.is_thread_active(&thread.id) {
         "[running] "
     } else {
@@ -313,13 +474,27 @@ fn build_thread_list_item(
         .as_ref()
         .is_some_and(|id| id == &thread.id);
     let current_label = if is_current { "(current) " } else { "" };
+    let mark_label = if picker.marked.contains(&thread.id) {
+        "● "
+    } else {
+        ""
+    };
+    let tags_label = if thread.tags.is_empty() {
+        String::new()
+    } else {
+        let tags: Vec<String> = thread.tags.iter().map(|tag| format!("#{tag}")).collect();
+        format!(" {}", tags.join(" "))
+    };
 
     let highlight_width = 3;
     let available_width = (inner_width as usize).saturating_sub(highlight_width);
     let date_width = ratatui_width(&timestamp);
     let name_max_width = available_width.saturating_sub(
         ratatui_width(&tree_prefix)
+            + ratatui_width(mark_label)
+            + ratatui_width(&tags_label)
             + ratatui_width(handoff_label)
+            + ratatui_width(source_label)
             + ratatui_width(running_label)
             + ratatui_width(current_label)
             + date_width
@@ -329,7 +504,10 @@ fn build_thread_list_item(
     let gap = available_width
         .saturating_sub(
             ratatui_width(&tree_prefix)
+                + ratatui_width(mark_label)
+                + ratatui_width(&tags_label)
                 + ratatui_width(handoff_label)
+                + ratatui_width(source_label)
                 + ratatui_width(running_label)
                 + ratatui_width(&display_name)
                 + ratatui_width(current_label)
@@ -339,13 +517,19 @@ fn build_thread_list_item(
 
     let line = Line::from(vec![
         Span::styled(tree_prefix, Style::default().fg(Color::DarkGray)),
+        Span::styled(mark_label.to_string(), Style::default().fg(Color::Magenta)),
         Span::styled(
             handoff_label.to_string(),
             Style::default().fg(Color::Yellow),
         ),
+        Span::styled(
+            source_label.to_string(),
+            Style::default().fg(Color::LightBlue),
+        ),
         Span::styled(running_label.to_string(), Style::default().fg(Color::Green)),
         Span::styled(current_label.to_string(), Style::default().fg(Color::Cyan)),
         Span::styled(display_name, Style::default().fg(Color::White)),
+        Span::styled(tags_label, Style::default().fg(Color::Blue)),
         Span::styled(" ".repeat(gap), Style::default()),
         Span::styled(timestamp, Style::default().fg(Color::DarkGray)),
     ]);
@@ -6,6 +6,7 @@ use zdx_engine::config::ThinkingLevel;
 use zdx_engine::core::thread_persistence::{Thread, Usage};
 use zdx_engine::models::ModelPricing;
 use zdx_engine::providers::ChatMessage;
+use zdx_engine::tools::protected_files::ProtectedApprovals;
 
 use crate::mutations::ThreadMutation;
 
@@ -28,8 +29,23 @@ pub struct ThreadState {
     /// Optional thread-local thinking override.
     pub thinking_override: Option<ThinkingLevel>,
 
+    /// Response style preset (`/style`) stored in the thread meta.
+    pub style: Option<String>,
+
     /// Cumulative token usage for this thread.
     pub usage: ThreadUsage,
+
+    /// Thread id and message count when the idle summary was last requested,
+    /// so an unchanged thread isn't summarized again.
+    pub summary_mark: Option<(String, usize)>,
+
+    /// Incognito mode: the conversation runs without a thread and nothing
+    /// from it (prompt history, crash content, memory) is written to disk.
+    pub incognito: bool,
+
+    /// Protected files (`[security] protected_globs`) the user approved
+    /// reading in this thread. Reset when another thread is opened.
+    pub protected_approvals: ProtectedApprovals,
 }
 
 impl Default for ThreadState {
@@ -47,7 +63,11 @@ impl ThreadState {
             messages: Vec::new(),
             model_override: None,
             thinking_override: None,
+            style: None,
             usage: ThreadUsage::new(),
+            summary_mark: None,
+            incognito: false,
+            protected_approvals: ProtectedApprovals::default(),
         }
     }
 
@@ -57,13 +77,25 @@ impl ThreadState {
             .as_ref()
             .and_then(|log| zdx_engine::core::thread_persistence::read_thread_title(&log.id).ok())
             .flatten();
+        let style = read_style(thread_handle.as_ref());
+        let summary_mark = thread_handle
+            .as_ref()
+            .filter(|log| {
+                zdx_engine::core::thread_persistence::read_thread_summary(&log.id)
+                    .is_ok_and(|summary| summary.is_some())
+            })
+            .map(|log| (log.id.clone(), messages.len()));
         Self {
             thread_handle,
             title,
             messages,
             model_override: None,
             thinking_override: None,
+            style,
             usage: ThreadUsage::new(),
+            summary_mark,
+            incognito: false,
+            protected_approvals: ProtectedApprovals::default(),
         }
     }
 
@@ -74,8 +106,21 @@ impl ThreadState {
             ThreadMutation::SetMessages(messages) => self.messages = messages,
             ThreadMutation::AppendMessage(message) => self.messages.push(message),
             ThreadMutation::SetThread(thread_handle) => {
+                if self.thread_handle.as_ref().map(|log| &log.id)
+                    != thread_handle.as_ref().map(|log| &log.id)
+                {
+                    self.protected_approvals = ProtectedApprovals::default();
+                }
                 self.thread_handle = thread_handle;
-                if self.thread_handle.is_none() {
+                self.style = read_style(self.thread_handle.as_ref());
+                if self.summary_mark.as_ref().map(|(id, _)| id)
+                    != self.thread_handle.as_ref().map(|log| &log.id)
+                {
+                    self.summary_mark = None;
+                }
+                if self.thread_handle.is_some() {
+                    self.incognito = false;
+                } else {
                     self.title = None;
                     self.model_override = None;
                     self.thinking_override = None;
@@ -94,6 +139,7 @@ impl ThreadState {
                 self.usage.restore(cumulative, latest);
             }
             ThreadMutation::SetTitle(title) => self.title = title,
+            ThreadMutation::SetIncognito(incognito) => self.incognito = incognito,
             ThreadMutation::UpdateUsage {
                 input,
                 output,
@@ -104,6 +150,15 @@ impl ThreadState {
     }
 }
 
+/// Reads the response style stored in `thread`'s meta, if any.
+fn read_style(thread: Option<&Thread>) -> Option<String> {
+    thread.and_then(|thread| {
+        zdx_engine::core::thread_persistence::read_thread_style(&thread.id)
+            .ok()
+            .flatten()
+    })
+}
+
 /// Token usage for the current thread.
 ///
 /// Tracks both cumulative tokens (for cost calculation) and latest request
@@ -15,6 +15,7 @@ use zdx_engine::config::ThinkingLevel;
 use zdx_engine::core::thread_persistence::{Thread, ThreadSummary, Usage, short_thread_id};
 use zdx_engine::providers::ChatMessage;
 
+use crate::common::i18n::{Text, tr};
 use crate::effects::UiEffect;
 use crate::events::ThreadUiEvent;
 use crate::mutations::{InputMutation, StateMutation, ThreadMutation, TranscriptMutation};
@@ -32,6 +33,10 @@ pub enum ThreadOverlayAction {
         original_cells: Vec<HistoryCell>,
         mode: crate::overlays::ThreadPickerMode,
     },
+    OpenQuickSwitch {
+        threads: Vec<ThreadSummary>,
+        active_thread_ids: HashSet<String>,
+    },
     None,
 }
 
@@ -55,6 +60,16 @@ pub fn handle_thread_event(
             };
             vec![]
         }
+        ThreadUiEvent::RecentLoaded {
+            threads,
+            active_thread_ids,
+        } => {
+            overlay_action = ThreadOverlayAction::OpenQuickSwitch {
+                threads,
+                active_thread_ids,
+            };
+            vec![]
+        }
         ThreadUiEvent::ListFailed { error } => {
             mutations.push(StateMutation::Transcript(
                 TranscriptMutation::AppendSystemMessage(error),
@@ -159,7 +174,7 @@ pub fn handle_thread_event(
                 TranscriptMutation::AppendSystemMessage(error),
             ));
             mutations.push(StateMutation::Transcript(
-                TranscriptMutation::AppendSystemMessage("Thread cleared.".to_string()),
+                TranscriptMutation::AppendSystemMessage(tr(Text::ThreadCleared).to_string()),
             ));
             vec![]
         }
@@ -182,12 +197,33 @@ pub fn handle_thread_event(
             }
             vec![]
         }
+        // Only an open thread picker shows summaries (see `update.rs`).
+        ThreadUiEvent::SummaryGenerated { .. } | ThreadUiEvent::CompletionListLoaded { .. } => {
+            vec![]
+        }
         ThreadUiEvent::RenameFailed { error } => {
             mutations.push(StateMutation::Transcript(
                 TranscriptMutation::AppendSystemMessage(error),
             ));
             vec![]
         }
+        ThreadUiEvent::ActionApplied {
+            action,
+            thread_ids,
+            errors,
+        } => {
+            if !thread_ids.is_empty() {
+                mutations.push(StateMutation::Transcript(
+                    TranscriptMutation::AppendSystemMessage(action.summary(thread_ids.len())),
+                ));
+            }
+            for error in errors {
+                mutations.push(StateMutation::Transcript(
+                    TranscriptMutation::AppendSystemMessage(error),
+                ));
+            }
+            vec![]
+        }
         ThreadUiEvent::WorktreeReady { path } => {
             let effects = vec![
                 UiEffect::ResolveRootDisplay { path: path.clone() },
@@ -8,6 +8,7 @@
 
 // New feature slice modules
 mod render;
+mod scrollback;
 mod selection;
 mod state;
 mod update;
@@ -16,6 +17,7 @@ mod update;
 // crate so non-interactive consumers (e.g. the monitor) can reuse them.
 // Re-export render functions
 pub use render::{SPINNER_SPEED_DIVISOR, calculate_cell_line_counts, render_transcript};
+pub use scrollback::Scrollback;
 // Re-export selection types (only those used externally)
 pub use selection::{LineInteraction, LineMapping, SelectionState};
 // Re-export scroll types
@@ -25,6 +27,8 @@ pub use state::{TranscriptState, VisibleRange};
 // Re-export update functions
 pub use update::{apply_pending_delta, handle_agent_event, handle_mouse};
 pub use zdx_transcript::{
-    CellId, ChildToolEntry, ChildToolState, HistoryCell, Style, StyledLine, StyledSpan, ToolState,
-    WrapCache, build_transcript_from_events, convert_styled_line, markdown, reasoning_display_text,
+    CellId, ChildToolEntry, ChildToolState, FileChange, FileChangeKind, HistoryCell, Style,
+    StyledLine, StyledSpan, ToolState, WrapCache, build_transcript_from_events,
+    collect_file_changes, collect_touched_files, convert_style, convert_styled_line, markdown,
+    parse_ansi, reasoning_display_text, strip_ansi,
 };
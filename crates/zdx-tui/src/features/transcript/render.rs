@@ -7,9 +7,10 @@
 //! - Style conversion helpers
 //! - Cell line count calculation
 
+use chrono::{DateTime, Utc};
 use ratatui::style::Modifier;
 use ratatui::text::{Line, Span};
-use zdx_transcript::{convert_style, convert_styled_line};
+use zdx_transcript::{TimestampMode, convert_style, convert_styled_line};
 
 use crate::common::ratatui_text;
 use crate::state::TuiState;
@@ -56,18 +57,20 @@ fn render_transcript_full(state: &TuiState, width: usize) -> Vec<Line<'static>>
     use unicode_segmentation::UnicodeSegmentation;
 
     let mut lines = Vec::new();
+    let now = Utc::now();
 
     // Clear and rebuild the position map
     state.transcript.position_map.clear();
 
-    for cell in state.transcript.cells() {
+    for (cell_idx, cell) in state.transcript.cells().iter().enumerate() {
         let styled_lines = cell.display_lines_cached(
             width,
             state.spinner_frame / SPINNER_SPEED_DIVISOR,
             &state.transcript.wrap_cache,
         );
+        let stamp = cell_timestamp_line(state, cell_idx, now);
 
-        for styled_line in styled_lines.iter() {
+        for styled_line in stamp.iter().chain(styled_lines.iter()) {
             let interaction = detect_line_interaction(styled_line);
             let non_selectable_prefix_graphemes =
                 non_selectable_prefix_graphemes_for_line(styled_line);
@@ -137,16 +140,19 @@ fn render_transcript_lazy(
     // cap, a single huge visible cell would materialize every one of its lines
     // (including those below the viewport) instead of just the visible slice.
     let max_lines = state.transcript.viewport_height;
+    let now = Utc::now();
 
     'cells: for (cell_idx, cell) in state.transcript.cells()[visible.cell_range.clone()]
         .iter()
         .enumerate()
     {
-        let styled_lines = cell.display_lines_cached(
+        let cached_lines = cell.display_lines_cached(
             width,
             state.spinner_frame / SPINNER_SPEED_DIVISOR,
             &state.transcript.wrap_cache,
         );
+        let stamp = cell_timestamp_line(state, visible.cell_range.start + cell_idx, now);
+        let styled_lines: Vec<&StyledLine> = stamp.iter().chain(cached_lines.iter()).collect();
 
         // For the first cell, skip lines above the viewport by slicing rather
         // than iterating and discarding them. `global_line_idx` already starts
@@ -157,7 +163,7 @@ fn render_transcript_lazy(
             0
         };
 
-        for styled_line in &styled_lines[skip_count..] {
+        for &styled_line in &styled_lines[skip_count..] {
             let interaction = detect_line_interaction(styled_line);
             let non_selectable_prefix_graphemes =
                 non_selectable_prefix_graphemes_for_line(styled_line);
@@ -223,6 +229,7 @@ pub fn calculate_cell_line_counts(
 
     let cells = state.transcript.cells();
     let from_index = from_index.min(cells.len());
+    let stamp_lines = usize::from(state.transcript.timestamp_mode != TimestampMode::Off);
 
     cells[from_index..]
         .iter()
@@ -233,11 +240,29 @@ pub fn calculate_cell_line_counts(
                 &state.transcript.wrap_cache,
             );
             // +1 for blank line between cells
-            lines.len() + 1
+            lines.len() + stamp_lines + 1
         })
         .collect()
 }
 
+/// Returns the `/timestamps` line drawn above the cell at `idx`, if enabled.
+///
+/// Kept outside the cell's cached lines so relative times can age without
+/// invalidating the wrap cache.
+fn cell_timestamp_line(state: &TuiState, idx: usize, now: DateTime<Utc>) -> Option<StyledLine> {
+    let mode = state.transcript.timestamp_mode;
+    if mode == TimestampMode::Off {
+        return None;
+    }
+    let cells = state.transcript.cells();
+    zdx_transcript::timestamp_line(
+        cells.get(idx)?,
+        mode,
+        now,
+        zdx_transcript::turn_duration(cells, idx),
+    )
+}
+
 // ============================================================================
 // Style Conversion Helpers
 // ============================================================================
@@ -0,0 +1,130 @@
+//! Scrollback flushing for inline mode (`zdx --inline`).
+//!
+//! Inline mode has no alternate screen: finished cells are printed once
+//! above the viewport, where they scroll into the terminal's own scrollback.
+//! The viewport only shows cells that can still change (streaming text,
+//! running tools), plus the input and status line.
+
+use ratatui::style::{Color, Style};
+use ratatui::text::{Line, Span};
+use zdx_transcript::convert_styled_line;
+
+use crate::state::{TabId, TuiState};
+use crate::transcript::{CellId, HistoryCell, ToolState};
+
+/// Tracks which transcript cells were already printed to scrollback.
+#[derive(Debug, Default)]
+pub struct Scrollback {
+    /// Tab and first cell of the transcript that was flushed; a change means
+    /// the transcript was replaced (`/new`, thread switch, tab switch).
+    source: Option<(TabId, CellId)>,
+    /// Number of leading cells already printed.
+    flushed: usize,
+}
+
+impl Scrollback {
+    /// Index of the first cell not yet printed (the live cells).
+    pub fn live_from(&self) -> usize {
+        self.flushed
+    }
+
+    /// Lines for the cells that finished since the last call, wrapped to
+    /// `width`. With `include_live`, still-changing cells are printed too
+    /// (used on exit so nothing is lost).
+    pub fn take_lines(
+        &mut self,
+        state: &TuiState,
+        width: usize,
+        include_live: bool,
+    ) -> Vec<Line<'static>> {
+        let cells = state.transcript.cells();
+        let Some(first) = cells.first() else {
+            return Vec::new();
+        };
+
+        let mut lines = Vec::new();
+        let source = (state.tab_id, first.id());
+        if self.source != Some(source) || self.flushed > cells.len() {
+            if self.source.is_some() {
+                lines.push(Line::from(Span::styled(
+                    "─".repeat(width.min(40)),
+                    Style::default().fg(Color::DarkGray),
+                )));
+                lines.push(Line::default());
+            }
+            self.source = Some(source);
+            self.flushed = 0;
+        }
+
+        let end = if include_live {
+            cells.len()
+        } else {
+            cells[self.flushed..]
+                .iter()
+                .position(|cell| is_live(state, cells, cell))
+                .map_or(cells.len(), |offset| self.flushed + offset)
+        };
+        for cell in &cells[self.flushed..end] {
+            lines.extend(cell.display_lines(width, 0).iter().map(convert_styled_line));
+            lines.push(Line::default());
+        }
+        self.flushed = end;
+        lines
+    }
+}
+
+/// Whether a cell can still change and must stay in the viewport.
+///
+/// A trailing user message during a turn stays live because it is marked
+/// interrupted if the turn is canceled before any reply.
+fn is_live(state: &TuiState, cells: &[HistoryCell], cell: &HistoryCell) -> bool {
+    match cell {
+        HistoryCell::Assistant { is_streaming, .. }
+        | HistoryCell::Thinking { is_streaming, .. } => *is_streaming,
+        HistoryCell::Tool { state, .. } => *state == ToolState::Running,
+        HistoryCell::Duel { is_pending, .. } => *is_pending,
+        HistoryCell::User { id, .. } => {
+            state.agent_state.is_running() && cells.last().is_some_and(|last| last.id() == *id)
+        }
+        HistoryCell::System { .. }
+        | HistoryCell::Error { .. }
+        | HistoryCell::Timing { .. }
+        | HistoryCell::FileChanges { .. } => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::state::AppState;
+
+    fn text(lines: &[Line<'static>]) -> Vec<String> {
+        lines.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn flushes_finished_cells_once_and_holds_back_streaming_ones() {
+        let mut app = AppState::new(
+            zdx_engine::config::Config::default(),
+            std::path::PathBuf::new(),
+            None,
+            None,
+        );
+        let mut scrollback = Scrollback::default();
+        app.tui.transcript.push_cell(HistoryCell::system("hello"));
+        app.tui
+            .transcript
+            .push_cell(HistoryCell::assistant_streaming("partial\n"));
+
+        let lines = scrollback.take_lines(&app.tui, 40, false);
+        assert!(text(&lines).iter().any(|line| line.contains("hello")));
+        assert!(!text(&lines).iter().any(|line| line.contains("partial")));
+        let cell_count = app.tui.transcript.cells().len();
+        assert_eq!(scrollback.live_from(), cell_count - 1);
+        assert!(scrollback.take_lines(&app.tui, 40, false).is_empty());
+
+        let lines = scrollback.take_lines(&app.tui, 40, true);
+        assert!(text(&lines).iter().any(|line| line.contains("partial")));
+        assert_eq!(scrollback.live_from(), cell_count);
+    }
+}
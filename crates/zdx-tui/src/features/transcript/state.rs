@@ -313,6 +313,21 @@ pub struct TranscriptState {
     /// stacking one banner per switch. Cleared implicitly once any other cell
     /// becomes the last cell.
     last_switch_cell_id: Option<super::CellId>,
+
+    /// Whether subagent tool cells fold their live child activity into a
+    /// one-line summary. Applied to new subagent cells as they appear.
+    pub subagent_details_collapsed: bool,
+
+    /// Rows of inline output under the agent's bash tool cells (0 = compact).
+    /// Applied to new and reloaded cells.
+    pub bash_tool_output_lines: usize,
+
+    /// How `/timestamps` labels are drawn above each cell.
+    pub timestamp_mode: zdx_transcript::TimestampMode,
+
+    /// Model reported by the latest usage update; stamped on new assistant
+    /// cells as their model badge.
+    pub response_model: Option<String>,
 }
 
 impl Default for TranscriptState {
@@ -331,6 +346,10 @@ impl Default for TranscriptState {
             // Force an initial full build on the first frame.
             line_info_dirty: Some(0),
             last_switch_cell_id: None,
+            subagent_details_collapsed: false,
+            bash_tool_output_lines: 0,
+            timestamp_mode: zdx_transcript::TimestampMode::Off,
+            response_model: None,
         }
     }
 }
@@ -391,6 +410,21 @@ impl TranscriptState {
         }
     }
 
+    /// Removes every cell after the latest user cell: the response `/redo`
+    /// replaces. Returns false when there is no user cell.
+    pub fn drop_latest_response(&mut self) -> bool {
+        let Some(index) = self
+            .cells
+            .iter()
+            .rposition(|cell| matches!(cell, super::HistoryCell::User { .. }))
+        else {
+            return false;
+        };
+        self.cells.truncate(index + 1);
+        self.mark_line_info_dirty_from(index);
+        true
+    }
+
     /// Pushes a cell and marks line info dirty from the new cell.
     pub fn push_cell(&mut self, cell: super::HistoryCell) {
         if let super::HistoryCell::User { id, .. } = &cell {
@@ -400,6 +434,92 @@ impl TranscriptState {
         self.mark_line_info_dirty_from(self.cells.len() - 1);
     }
 
+    /// Flips `subagent_details_collapsed` and applies it to every cell.
+    pub fn toggle_subagent_details(&mut self) {
+        self.set_subagent_details_collapsed(!self.subagent_details_collapsed);
+    }
+
+    /// Sets `subagent_details_collapsed` and applies it to every cell.
+    pub fn set_subagent_details_collapsed(&mut self, collapsed: bool) {
+        self.subagent_details_collapsed = collapsed;
+        for cell in &mut self.cells {
+            cell.set_child_collapsed(collapsed);
+        }
+        self.invalidate_line_info();
+    }
+
+    /// Sets `bash_tool_output_lines` and applies it to the current cells.
+    pub fn set_bash_tool_output_lines(&mut self, max_lines: usize) {
+        self.bash_tool_output_lines = max_lines;
+        self.apply_bash_tool_output_lines();
+    }
+
+    fn apply_bash_tool_output_lines(&mut self) {
+        if self.bash_tool_output_lines == 0 {
+            return;
+        }
+        for cell in &mut self.cells {
+            cell.set_inline_output(self.bash_tool_output_lines);
+        }
+        self.invalidate_line_info();
+    }
+
+    /// Scrolls the inline output of the cell at `index` (see
+    /// `HistoryCell::scroll_inline_output`). Row counts don't change, so
+    /// line info stays valid.
+    pub fn scroll_cell_inline_output(&mut self, index: usize, delta: isize, width: usize) -> bool {
+        self.cells
+            .get_mut(index)
+            .is_some_and(|cell| cell.scroll_inline_output(delta, width))
+    }
+
+    /// Pushes an empty streaming assistant cell attributed to
+    /// `response_model` and returns its id.
+    pub fn push_streaming_assistant(&mut self) -> super::CellId {
+        let mut cell = super::HistoryCell::assistant_streaming("");
+        cell.set_model(self.response_model.clone());
+        let id = cell.id();
+        self.push_cell(cell);
+        id
+    }
+
+    /// Records the model serving the current turn and attributes the
+    /// streaming assistant cell to it if it has none yet.
+    pub fn set_response_model(&mut self, model: &str) {
+        if model.is_empty() {
+            return;
+        }
+        self.response_model = Some(model.to_string());
+        if let Some(cell) = self.cells.last_mut()
+            && let super::HistoryCell::Assistant {
+                is_streaming: true,
+                model: None,
+                ..
+            } = cell
+        {
+            cell.set_model(Some(model.to_string()));
+        }
+    }
+
+    /// Shows or hides the pin badge on the cell at `index`.
+    pub fn set_cell_pinned(&mut self, index: usize, pinned: bool) {
+        if let Some(cell) = self.cells.get_mut(index) {
+            cell.set_pinned(pinned);
+            self.mark_line_info_dirty_from(index);
+        }
+    }
+
+    /// Switches to the next timestamp mode (off → absolute → relative).
+    pub fn cycle_timestamps(&mut self) {
+        self.set_timestamp_mode(self.timestamp_mode.next());
+    }
+
+    pub fn set_timestamp_mode(&mut self, mode: zdx_transcript::TimestampMode) {
+        self.timestamp_mode = mode;
+        // Timestamp lines change every cell's line count.
+        self.invalidate_line_info();
+    }
+
     /// Activates the pending user cell for the current turn.
     pub fn activate_pending_user_cell(&mut self) {
         if let Some(id) = self.pending_user_cell_id.take() {
@@ -455,6 +575,16 @@ impl TranscriptState {
         }
     }
 
+    /// Returns the tool name for a cell by `tool_use_id`.
+    pub fn tool_name_for(&self, tool_id: &str) -> Option<&str> {
+        self.cells.iter().find_map(|c| match c {
+            super::HistoryCell::Tool {
+                tool_use_id, name, ..
+            } if tool_use_id == tool_id => Some(name.as_str()),
+            _ => None,
+        })
+    }
+
     /// Sets tool input for a cell by `tool_use_id`.
     pub fn set_tool_input_for(&mut self, tool_id: &str, input: serde_json::Value) {
         if let Some(index) = self.cells.iter().position(
@@ -465,6 +595,16 @@ impl TranscriptState {
         }
     }
 
+    /// Sets the provisional (still streaming) input for a cell by `tool_use_id`.
+    pub fn set_tool_provisional_input_for(&mut self, tool_id: &str, input: serde_json::Value) {
+        if let Some(index) = self.cells.iter().position(
+            |c| matches!(c, super::HistoryCell::Tool { tool_use_id, .. } if tool_use_id == tool_id),
+        ) {
+            self.cells[index].set_tool_provisional_input(input);
+            self.mark_line_info_dirty_from(index);
+        }
+    }
+
     /// Sets tool input preview for a cell by `tool_use_id`.
     pub fn set_tool_input_delta_for(&mut self, tool_id: &str, delta: String) {
         if let Some(index) = self.cells.iter().position(
@@ -511,6 +651,14 @@ impl TranscriptState {
         items
     }
 
+    /// Fills a pending `/duel` cell by `cell_id` with the speaker's reply.
+    pub fn finish_duel_cell(&mut self, cell_id: super::CellId, reply: &str) {
+        if let Some(index) = self.cells.iter().position(|c| c.id() == cell_id) {
+            self.cells[index].finish_duel(reply);
+            self.mark_line_info_dirty_from(index);
+        }
+    }
+
     /// Appends delta to a streaming assistant cell by `cell_id`.
     pub fn append_to_streaming_cell(&mut self, cell_id: super::CellId, delta: &str) {
         if let Some(index) = self.cells.iter().position(|c| c.id() == cell_id) {
@@ -635,6 +783,38 @@ impl TranscriptState {
         self.invalidate_line_info();
     }
 
+    /// Returns true when the latest turn ended in an error that still offers
+    /// a retry. Timing, file change, and system cells pushed after the error
+    /// (e.g. a model switch notice) don't hide it.
+    pub fn has_retryable_error(&self) -> bool {
+        self.cells
+            .iter()
+            .rev()
+            .find(|cell| {
+                !matches!(
+                    cell,
+                    super::HistoryCell::Timing { .. }
+                        | super::HistoryCell::FileChanges { .. }
+                        | super::HistoryCell::System { .. }
+                )
+            })
+            .is_some_and(super::HistoryCell::is_retryable_error)
+    }
+
+    /// Hides the retry hint on every error cell. Called when a new turn starts.
+    pub fn disarm_error_retry(&mut self) {
+        if let Some(index) = self
+            .cells
+            .iter()
+            .position(super::HistoryCell::is_retryable_error)
+        {
+            for cell in &mut self.cells[index..] {
+                cell.disarm_retry();
+            }
+            self.mark_line_info_dirty_from(index);
+        }
+    }
+
     /// Applies a cross-slice transcript mutation.
     pub fn apply(&mut self, mutation: TranscriptMutation) {
         match mutation {
@@ -669,6 +849,7 @@ impl TranscriptState {
             TranscriptMutation::Clear => self.reset(),
             TranscriptMutation::ReplaceCells(cells) => {
                 self.cells = cells;
+                self.apply_bash_tool_output_lines();
                 // Full rebuild: cell identities changed entirely.
                 self.invalidate_line_info();
                 self.pending_user_cell_id = None;
@@ -682,6 +863,8 @@ impl TranscriptState {
             TranscriptMutation::ScrollToBottom => self.scroll_to_bottom(),
             TranscriptMutation::PageUp => self.page_up(),
             TranscriptMutation::PageDown => self.page_down(),
+            TranscriptMutation::ToggleSubagentDetails => self.toggle_subagent_details(),
+            TranscriptMutation::CycleTimestamps => self.cycle_timestamps(),
         }
     }
 
@@ -55,8 +55,14 @@ pub fn handle_agent_event(
             }
             vec![]
         }
-        AgentEvent::Error { message, .. } => {
-            transcript.push_cell(HistoryCell::system(format!("Error: {message}")));
+        AgentEvent::Error { kind, message, .. } => {
+            transcript.push_cell(HistoryCell::error(
+                kind.clone(),
+                message.clone(),
+                None,
+                None,
+                false,
+            ));
             vec![]
         }
         AgentEvent::Notice { message, .. } => {
@@ -66,6 +72,20 @@ pub fn handle_agent_event(
             transcript.push_cell(HistoryCell::system(format!("⚠ {message}")));
             vec![]
         }
+        AgentEvent::LoopDetected {
+            message, details, ..
+        } => {
+            let text = match details {
+                Some(details) => format!("⚠ {message}\n{details}"),
+                None => format!("⚠ {message}"),
+            };
+            transcript.push_cell(HistoryCell::system(text));
+            vec![]
+        }
+        AgentEvent::ThinkingEscalated { message, .. } => {
+            transcript.push_cell(HistoryCell::system(format!("⇡ {message}")));
+            vec![]
+        }
         AgentEvent::ProviderRetry {
             message,
             attempt,
@@ -82,17 +102,25 @@ pub fn handle_agent_event(
             vec![]
         }
         AgentEvent::ToolRequested { id, name, input } => {
-            let tool_cell = HistoryCell::tool_running(id, name, input.clone());
+            let mut tool_cell = HistoryCell::tool_running(id, name, input.clone());
+            tool_cell.set_child_collapsed(transcript.subagent_details_collapsed);
+            tool_cell.set_inline_output(transcript.bash_tool_output_lines);
             let cell_id = tool_cell.id();
             transcript.push_cell(tool_cell);
 
             // Transition from Waiting to Streaming so UI shows activity
             if let AgentState::Waiting { .. } = agent_state {
                 let old_state = std::mem::replace(agent_state, AgentState::Idle);
-                if let AgentState::Waiting { rx, cancel } = old_state {
+                if let AgentState::Waiting {
+                    rx,
+                    cancel,
+                    soft_stop,
+                } = old_state
+                {
                     *agent_state = AgentState::Streaming {
                         rx,
                         cancel,
+                        soft_stop,
                         cell_id,
                         pending_delta: String::new(),
                     };
@@ -108,6 +136,10 @@ pub fn handle_agent_event(
             transcript.set_tool_input_delta_for(id, delta.clone());
             vec![]
         }
+        AgentEvent::ToolInputPartial { id, input, .. } => {
+            transcript.set_tool_provisional_input_for(id, input.clone());
+            vec![]
+        }
         AgentEvent::ToolStarted { .. } => vec![],
         AgentEvent::ToolCompleted { id, result } => {
             transcript.set_tool_result_for(id, result.clone());
@@ -150,7 +182,13 @@ pub fn handle_agent_event(
                     *agent_state = AgentState::Idle;
                     vec![]
                 }
-                TurnStatus::Failed { message, .. } => {
+                TurnStatus::Failed {
+                    kind,
+                    message,
+                    http_status,
+                    request_id,
+                    ..
+                } => {
                     // Preserve committed messages so manual 'continue' resumes from the
                     // correct state (with tool results from the failed attempt intact).
                     if !messages.is_empty() {
@@ -159,7 +197,13 @@ pub fn handle_agent_event(
                         )));
                     }
                     transcript.mark_errored();
-                    transcript.push_cell(HistoryCell::system(format!("Error: {message}")));
+                    transcript.push_cell(HistoryCell::error(
+                        kind.clone(),
+                        message.clone(),
+                        *http_status,
+                        request_id.clone(),
+                        true,
+                    ));
                     *agent_state = AgentState::Idle;
                     vec![]
                 }
@@ -179,8 +223,10 @@ pub fn handle_agent_event(
             output_tokens,
             cache_read_input_tokens,
             cache_creation_input_tokens,
+            model,
             ..
         } => {
+            transcript.set_response_model(model);
             mutations.push(StateMutation::Thread(ThreadMutation::UpdateUsage {
                 input: *input_tokens,
                 output: *output_tokens,
@@ -222,15 +268,19 @@ fn handle_assistant_delta(
     match agent_state {
         AgentState::Waiting { .. } => {
             // Create streaming cell and transition to Streaming state
-            let cell = HistoryCell::assistant_streaming("");
-            let cell_id = cell.id();
-            transcript.push_cell(cell);
+            let cell_id = transcript.push_streaming_assistant();
 
             let old_state = std::mem::replace(agent_state, AgentState::Idle);
-            if let AgentState::Waiting { rx, cancel } = old_state {
+            if let AgentState::Waiting {
+                rx,
+                cancel,
+                soft_stop,
+            } = old_state
+            {
                 *agent_state = AgentState::Streaming {
                     rx,
                     cancel,
+                    soft_stop,
                     cell_id,
                     pending_delta: text.to_string(),
                 };
@@ -259,10 +309,7 @@ fn handle_assistant_delta(
                 });
 
             if needs_new_cell {
-                let new_cell = HistoryCell::assistant_streaming("");
-                let new_cell_id = new_cell.id();
-                transcript.push_cell(new_cell);
-                *cell_id = new_cell_id;
+                *cell_id = transcript.push_streaming_assistant();
                 pending_delta.clear();
                 pending_delta.push_str(text);
             } else {
@@ -282,13 +329,19 @@ fn handle_thinking_delta(
     // Transition from Waiting to Streaming
     if let AgentState::Waiting { .. } = agent_state {
         let old_state = std::mem::replace(agent_state, AgentState::Idle);
-        if let AgentState::Waiting { rx, cancel } = old_state {
+        if let AgentState::Waiting {
+            rx,
+            cancel,
+            soft_stop,
+        } = old_state
+        {
             let cell = HistoryCell::thinking_streaming(text);
             let cell_id = cell.id();
             transcript.push_cell(cell);
             *agent_state = AgentState::Streaming {
                 rx,
                 cancel,
+                soft_stop,
                 cell_id,
                 pending_delta: String::new(),
             };
@@ -393,14 +446,18 @@ pub fn handle_mouse(
             if !contains_point(transcript_area, mouse.column, mouse.row) {
                 return None;
             }
-            transcript.scroll_up(MOUSE_SCROLL_LINES);
+            if !scroll_inline_output_at(transcript, mouse, transcript_area, 1) {
+                transcript.scroll_up(MOUSE_SCROLL_LINES);
+            }
             None
         }
         MouseEventKind::ScrollDown => {
             if !contains_point(transcript_area, mouse.column, mouse.row) {
                 return None;
             }
-            transcript.scroll_down(MOUSE_SCROLL_LINES);
+            if !scroll_inline_output_at(transcript, mouse, transcript_area, -1) {
+                transcript.scroll_down(MOUSE_SCROLL_LINES);
+            }
             None
         }
         MouseEventKind::Down(MouseButton::Left) => {
@@ -425,6 +482,12 @@ pub fn handle_mouse(
                     });
                 }
 
+                // A file line in a turn's change summary opens the tool call
+                // that last changed that file.
+                if let Some(request) = check_file_change_click(transcript, line) {
+                    return Some(request);
+                }
+
                 if transcript.register_click(line, col) {
                     if !transcript.select_word_at(line, col) {
                         transcript.start_selection(line, col);
@@ -458,6 +521,31 @@ pub fn handle_mouse(
     }
 }
 
+/// Scrolls the inline output of the bash cell under the pointer by
+/// `MOUSE_SCROLL_LINES` rows (`direction` 1 = older output). Returns false
+/// when the pointer isn't over one or its output is already at that end, so
+/// the transcript scrolls instead.
+fn scroll_inline_output_at(
+    transcript: &mut TranscriptState,
+    mouse: MouseEvent,
+    transcript_area: Rect,
+    direction: isize,
+) -> bool {
+    let Some((line, _)) =
+        screen_to_transcript_pos(transcript, mouse.column, mouse.row, transcript_area)
+    else {
+        return false;
+    };
+    let Some(cell_idx) = transcript.scroll.cell_index_for_line(line) else {
+        return false;
+    };
+    transcript.scroll_cell_inline_output(
+        cell_idx,
+        direction * MOUSE_SCROLL_LINES.cast_signed(),
+        transcript_area.width as usize,
+    )
+}
+
 fn contains_point(area: Rect, x: u16, y: u16) -> bool {
     x >= area.x
         && x < area.x.saturating_add(area.width)
@@ -465,6 +553,29 @@ fn contains_point(area: Rect, x: u16, y: u16) -> bool {
         && y < area.y.saturating_add(area.height)
 }
 
+/// Checks if a click on the given line is on a file in a change summary cell
+/// and returns a tool detail request for the call that last changed it.
+fn check_file_change_click(
+    transcript: &TranscriptState,
+    line: usize,
+) -> Option<crate::overlays::OverlayRequest> {
+    let mapping = transcript.position_map.get_by_global_line(line)?;
+    let cell_idx = transcript.scroll.cell_index_for_line(line)?;
+    let HistoryCell::FileChanges { changes, .. } = transcript.cells().get(cell_idx)? else {
+        return None;
+    };
+    let change = changes.iter().find(|change| {
+        mapping
+            .text
+            .strip_prefix("  ")
+            .and_then(|rest| rest.get(2..))
+            .is_some_and(|text| text.starts_with(change.path.as_str()))
+    })?;
+    Some(crate::overlays::OverlayRequest::ToolDetail {
+        tool_use_id: change.tool_use_id.clone(),
+    })
+}
+
 /// Checks if a click on the given line is on an image placeholder and returns an overlay request.
 fn check_image_click(
     transcript: &TranscriptState,
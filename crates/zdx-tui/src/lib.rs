@@ -1,26 +1,35 @@
 //! Full-screen TUI implementation for ZDX.
 
 pub mod common;
+pub mod compare;
+pub mod crash;
+pub mod duel;
 pub mod effects;
 pub mod events;
 pub mod features;
 pub mod mutations;
 pub mod overlays;
 pub mod render;
+pub mod replay;
 pub mod runtime;
 pub mod state;
 pub mod terminal;
+pub mod tour;
+pub mod ui_state;
 pub mod update;
 
 use std::io::{IsTerminal, Write, stderr};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::Result;
 pub use features::transcript::markdown;
 pub use features::{auth, input, statusline, thread, transcript};
 pub use runtime::TuiRuntime;
 use zdx_engine::config::Config;
-use zdx_engine::core::context::ContextWarning;
+use zdx_engine::core::agent::ToolSelection;
+use zdx_engine::core::context::{
+    ContextWarning, EffectivePrompt, PromptContextInclusion, build_prompt_with_context_and_layers,
+};
 use zdx_engine::core::thread_persistence::Thread;
 use zdx_engine::providers::ChatMessage;
 use zdx_engine::skills::Skill;
@@ -37,7 +46,28 @@ pub(crate) fn tui_instruction_layers() -> Vec<&'static str> {
         .collect()
 }
 
-fn format_context_warning(warning: &ContextWarning) -> String {
+/// Builds the TUI system prompt for `root`. Incognito sessions leave out the
+/// memory index and memory suggestions so the agent isn't prompted to save
+/// anything from them.
+pub(crate) fn tui_effective_prompt(
+    config: &Config,
+    root: &Path,
+    incognito: bool,
+) -> Result<EffectivePrompt> {
+    build_prompt_with_context_and_layers(
+        config,
+        root,
+        &config.model,
+        &tui_instruction_layers(),
+        !incognito,
+        PromptContextInclusion {
+            memory_index: !incognito,
+            ..PromptContextInclusion::default()
+        },
+    )
+}
+
+pub(crate) fn format_context_warning(warning: &ContextWarning) -> String {
     match &warning.path {
         Some(path) => format!("{} (path: {})", warning.message, path.display()),
         None => warning.message.clone(),
@@ -52,8 +82,9 @@ pub async fn run_interactive_chat(
     config: &Config,
     thread_handle: Option<Thread>,
     root: PathBuf,
+    tool_selection: Option<ToolSelection>,
 ) -> Result<()> {
-    run_interactive_chat_with_history(config, thread_handle, Vec::new(), root).await
+    run_chat(config, thread_handle, Vec::new(), root, tool_selection).await
 }
 
 /// Runs the interactive chat loop with pre-loaded history.
@@ -65,6 +96,18 @@ pub async fn run_interactive_chat_with_history(
     thread_handle: Option<Thread>,
     history: Vec<ChatMessage>,
     root: PathBuf,
+) -> Result<()> {
+    run_chat(config, thread_handle, history, root, None).await
+}
+
+/// Runs the TUI; `tool_selection` (from `--tools` / `--deny-tools`) replaces
+/// the first tab's default tool selection.
+async fn run_chat(
+    config: &Config,
+    thread_handle: Option<Thread>,
+    history: Vec<ChatMessage>,
+    root: PathBuf,
+    tool_selection: Option<ToolSelection>,
 ) -> Result<()> {
     tokio::task::yield_now().await;
     // Chat mode requires a terminal to render the TUI
@@ -80,15 +123,6 @@ pub async fn run_interactive_chat_with_history(
     // Set runtime env vars before building prompt (Slice 1: env-vars-runtime-context)
     zdx_engine::core::context::set_runtime_env(config, thread_id_ref);
 
-    let instruction_layers = tui_instruction_layers();
-    let effective =
-        zdx_engine::core::context::build_effective_system_prompt_with_paths_and_instruction_layers(
-            config,
-            &root,
-            &instruction_layers,
-            true,
-        )?;
-
     // Print pre-TUI info to stderr (will be replaced by alternate screen)
     let mut err = stderr();
     writeln!(err, "ZDX Chat")?;
@@ -99,31 +133,29 @@ pub async fn run_interactive_chat_with_history(
     if !history.is_empty() {
         writeln!(err, "Loaded {} previous messages", history.len())?;
     }
-
-    // Emit warnings from context loading (per SPEC §10)
-    for warning in &effective.warnings {
-        writeln!(err, "Warning: {}", format_context_warning(warning))?;
-    }
-
-    // Small delay so user can see the info before TUI takes over
     err.flush()?;
 
-    // Extract context info before prompt is moved into runtime
-    let loaded_agents_paths = effective.loaded_agents_paths.clone();
-    let loaded_skills = effective.loaded_skills.clone();
-
-    // Create and run the TUI
-    let mut runtime = if history.is_empty() {
-        TuiRuntime::new(config.clone(), root, effective.prompt, thread_handle)?
-    } else {
-        TuiRuntime::with_history(
-            config.clone(),
-            root,
-            effective.prompt,
-            thread_handle,
-            history,
-        )?
-    };
+    common::i18n::set_language(config.tui.language);
+
+    // Create and run the TUI. The system prompt, context files, and skills
+    // load in the background after the first paint (context warnings and the
+    // loaded files/skills are reported in the transcript then).
+    let mut runtime = zdx_engine::startup_profile::time("tui.runtime_init", || {
+        if history.is_empty() {
+            TuiRuntime::new(config.clone(), root, None, thread_handle)
+        } else {
+            TuiRuntime::with_history(config.clone(), root, None, thread_handle, history)
+        }
+    })?;
+    runtime.defer_startup_context();
+    if let Some(selection) = tool_selection {
+        let message = runtime.state.tui.apply_tool_selection(selection);
+        runtime
+            .state
+            .tui
+            .transcript
+            .push_cell(HistoryCell::system(message));
+    }
 
     // Add system message for config path (only if config exists on disk).
     let config_path = zdx_engine::config::paths::config_path();
@@ -143,14 +175,13 @@ pub async fn run_interactive_chat_with_history(
         .thread_handle
         .as_ref()
         .map(|log| log.path().as_path());
-    for message in thread_startup_messages(thread_path, &loaded_agents_paths, &loaded_skills) {
+    for message in thread_startup_messages(thread_path, &[], &[]) {
         runtime
             .state
             .tui
             .transcript
             .push_cell(HistoryCell::system(message));
     }
-    runtime.state.tui.loaded_skills = loaded_skills;
 
     runtime.run()?;
 
@@ -5,7 +5,8 @@
 
 use std::path::PathBuf;
 
-use zdx_engine::config::ThinkingLevel;
+use zdx_engine::config::{ModelParams, ThinkingLevel};
+use zdx_engine::core::agent::ToolSelection;
 use zdx_engine::core::thread_persistence::{Thread, Usage};
 use zdx_engine::providers::{ChatMessage, ProviderKind};
 
@@ -29,13 +30,24 @@ pub enum StateMutation {
         model_override: Option<String>,
         thinking_override: Option<ThinkingLevel>,
     },
-    SetSystemPrompt(Option<String>),
+    SetSystemPrompt {
+        prompt: Option<String>,
+        variant: Option<String>,
+    },
     SetLastSkillRepo(String),
     SetLoadedSkills(Vec<zdx_engine::skills::Skill>),
     /// Replace the active tab's suggested replies (empty clears).
     SetLastFollowups(Vec<String>),
     /// Toggle the debug status line visibility.
     ToggleDebugStatus,
+    /// Remember the thread picker's scope for the next time it opens.
+    SetThreadPickerScope(crate::overlays::ThreadScope),
+    /// Replace the tab's tool selection (`/tools`); `summary` is the
+    /// `(enabled, total)` count shown in the status line, `None` when unfiltered.
+    SetToolSelection {
+        selection: ToolSelection,
+        summary: Option<(usize, usize)>,
+    },
 }
 
 /// Transcript slice mutations requested by other slices.
@@ -59,6 +71,10 @@ pub enum TranscriptMutation {
     ScrollToBottom,
     PageUp,
     PageDown,
+    /// Folds/unfolds the live child activity of every subagent tool cell.
+    ToggleSubagentDetails,
+    /// Cycles `/timestamps` between off, absolute, and relative times.
+    CycleTimestamps,
 }
 
 /// Input slice mutations requested by other slices.
@@ -107,6 +123,8 @@ pub enum ThreadMutation {
     },
     /// Set the thread title (if any).
     SetTitle(Option<String>),
+    /// Enter or leave incognito mode (no thread, nothing persisted).
+    SetIncognito(bool),
     UpdateUsage {
         input: u64,
         output: u64,
@@ -130,4 +148,5 @@ pub enum ConfigMutation {
         provider: ProviderKind,
         enabled: bool,
     },
+    SetModelParams(ModelParams),
 }
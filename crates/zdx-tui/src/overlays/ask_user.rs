@@ -0,0 +1,206 @@
+//! Ask user overlay: answers an `Ask_User` question from the agent.
+//!
+//! The asking tool call stays pending until the user submits (Enter) or
+//! declines (Esc). Questions that arrive while another overlay is open wait
+//! in `AppState::pending_questions`.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Paragraph, Wrap};
+use zdx_engine::core::ask_user::UserQuestion;
+
+use super::OverlayUpdate;
+use super::render_utils::{clear_word_left, wrapped_rows};
+use crate::common::i18n::{Text, tr};
+use crate::effects::UiEffect;
+
+const OVERLAY_WIDTH: u16 = 70;
+
+/// State for the ask user overlay.
+#[derive(Debug)]
+pub struct AskUserState {
+    /// The question being answered; taken when the overlay resolves it.
+    question: Option<UserQuestion>,
+    /// The answer typed so far.
+    pub input: String,
+}
+
+impl AskUserState {
+    pub fn open(question: UserQuestion) -> Self {
+        Self {
+            question: Some(question),
+            input: String::new(),
+        }
+    }
+
+    /// Whether the asking turn is still waiting (false once it was canceled).
+    pub fn is_pending(&self) -> bool {
+        self.question.as_ref().is_some_and(UserQuestion::is_pending)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, input_y: u16) {
+        render_ask_user_overlay(frame, self, area, input_y);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> OverlayUpdate {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('c') if key.code == KeyCode::Esc || ctrl => {
+                self.resolve(None)
+            }
+            KeyCode::Enter => {
+                let answer = self.input.trim();
+                if answer.is_empty() {
+                    OverlayUpdate::stay()
+                } else {
+                    let answer = answer.to_string();
+                    self.resolve(Some(answer))
+                }
+            }
+            KeyCode::Backspace => {
+                self.input.pop();
+                OverlayUpdate::stay()
+            }
+            KeyCode::Char('w') if ctrl => {
+                clear_word_left(&mut self.input);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.input.push(c);
+                OverlayUpdate::stay()
+            }
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    fn resolve(&mut self, answer: Option<String>) -> OverlayUpdate {
+        let effects = self
+            .question
+            .take()
+            .map(|question| UiEffect::AnswerUserQuestion { question, answer })
+            .into_iter()
+            .collect();
+        OverlayUpdate::close().with_ui_effects(effects)
+    }
+}
+
+fn render_ask_user_overlay(frame: &mut Frame, state: &AskUserState, area: Rect, input_top_y: u16) {
+    use super::render_utils::{
+        InputHint, InputLine, OverlayConfig, render_input_line, render_overlay, render_separator,
+    };
+
+    let question = state
+        .question
+        .as_ref()
+        .map_or("", |question| question.question.as_str());
+    let text_width = OVERLAY_WIDTH
+        .min(area.width.saturating_sub(4))
+        .saturating_sub(2);
+    let question_rows = wrapped_rows(question, text_width);
+
+    let hints = [
+        InputHint::new("Enter", "answer"),
+        InputHint::new("Esc", "decline"),
+    ];
+    let layout = render_overlay(
+        frame,
+        area,
+        input_top_y,
+        &OverlayConfig {
+            title: tr(Text::AgentQuestionTitle),
+            border_color: Color::Magenta,
+            width: OVERLAY_WIDTH,
+            height: question_rows.saturating_add(5),
+            hints: &hints,
+        },
+    );
+
+    // Keep the separator and input visible when the question is too long
+    // for the screen; the question is clipped instead.
+    let question_height = layout.body.height.saturating_sub(2);
+    let question_area = Rect::new(
+        layout.body.x,
+        layout.body.y,
+        layout.body.width,
+        question_height,
+    );
+    frame.render_widget(
+        Paragraph::new(question)
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: false }),
+        question_area,
+    );
+
+    render_separator(frame, layout.body, question_height);
+
+    let input_area = Rect::new(
+        layout.body.x,
+        layout.body.y + question_height + 1,
+        layout.body.width,
+        1,
+    );
+    render_input_line(
+        frame,
+        input_area,
+        &InputLine {
+            value: &state.input,
+            placeholder: Some("Type your answer..."),
+            prompt: "> ",
+            prompt_color: Color::DarkGray,
+            text_color: Color::Magenta,
+            placeholder_color: Color::DarkGray,
+            cursor_color: Color::Magenta,
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyEventKind, KeyEventState};
+
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent {
+            code,
+            modifiers: KeyModifiers::NONE,
+            kind: KeyEventKind::Press,
+            state: KeyEventState::NONE,
+        }
+    }
+
+    fn answer_effect(update: OverlayUpdate) -> Option<String> {
+        match update.effects.into_iter().next() {
+            Some(UiEffect::AnswerUserQuestion { answer, .. }) => answer,
+            other => panic!("expected AnswerUserQuestion, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn enter_answers_and_esc_declines() {
+        let (handle, mut rx) = zdx_engine::core::ask_user::channel();
+        let asks = tokio::spawn(async move {
+            (
+                handle.ask("toolu_1", "Which branch?").await,
+                handle.ask("toolu_2", "Deploy?").await,
+            )
+        });
+
+        let mut state = AskUserState::open(rx.recv().await.unwrap());
+        assert!(state.handle_key(key(KeyCode::Enter)).effects.is_empty());
+        for c in "main".chars() {
+            state.handle_key(key(KeyCode::Char(c)));
+        }
+        assert_eq!(
+            answer_effect(state.handle_key(key(KeyCode::Enter))),
+            Some("main".to_string())
+        );
+
+        let mut state = AskUserState::open(rx.recv().await.unwrap());
+        assert_eq!(answer_effect(state.handle_key(key(KeyCode::Esc))), None);
+        asks.abort();
+    }
+}
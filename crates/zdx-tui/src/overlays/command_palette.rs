@@ -8,10 +8,11 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{List, ListItem, ListState, Paragraph};
 use zdx_engine::custom_commands::CustomCommand;
 
-use super::{OverlayRequest, OverlayUpdate};
+use super::{ConfirmState, OverlayRequest, OverlayUpdate};
 use crate::common::TaskKind;
 use crate::common::clipboard::Clipboard;
 use crate::common::commands::{COMMANDS, Command, command_available};
+use crate::common::i18n::{Text, tr};
 use crate::effects::UiEffect;
 use crate::input::{HandoffState, PromptBuilderState, build_fast_mode_toggle_actions};
 use crate::mutations::{
@@ -172,7 +173,8 @@ impl CommandPaletteState {
                 };
                 match entry {
                     PaletteEntry::Builtin(cmd) => {
-                        let (open_overlay, effects, mutations) = execute_command(tui, cmd.name);
+                        let (open_overlay, mut effects, mutations) = execute_command(tui, cmd.name);
+                        effects.push(UiEffect::RecordCommandUse { name: cmd.name });
                         let update = match open_overlay {
                             Some(request) => OverlayUpdate::open(request),
                             None => OverlayUpdate::close(),
@@ -269,7 +271,7 @@ impl CommandPaletteState {
 }
 
 #[allow(clippy::too_many_lines)]
-fn execute_command(
+pub(crate) fn execute_command(
     tui: &TuiState,
     cmd_name: &str,
 ) -> (Option<OverlayRequest>, Vec<UiEffect>, Vec<StateMutation>) {
@@ -293,6 +295,7 @@ fn execute_command(
         "commands-refresh" => (None, vec![UiEffect::ReloadCustomCommands], vec![]),
         "config" => (None, vec![UiEffect::OpenConfig], vec![]),
         "debug" => (None, vec![], vec![StateMutation::ToggleDebugStatus]),
+        "debug-request" => (None, vec![UiEffect::DebugRequest], vec![]),
         "fast" => match build_fast_mode_toggle_actions(&tui.config, &tui.config.model) {
             Ok((effects, mutations)) => (None, effects, mutations),
             Err(message) => (
@@ -313,6 +316,13 @@ fn execute_command(
             let (effects, mutations) = execute_logout(tui);
             (None, effects, mutations)
         }
+        "limits" => (
+            None,
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(limits_summary()),
+            )],
+        ),
         "rename" => {
             if tui.thread.thread_handle.is_none() {
                 (
@@ -331,6 +341,7 @@ fn execute_command(
         "model" => (Some(OverlayRequest::ModelPicker), vec![], vec![]),
         "skills" => (Some(OverlayRequest::SkillPicker), vec![], vec![]),
         "tabs" => (None, vec![UiEffect::CycleTab], vec![]),
+        "recent" => (None, vec![UiEffect::OpenQuickSwitch], vec![]),
         "threads" => {
             if tui.tasks.state(TaskKind::ThreadList).is_running() {
                 return (None, vec![], vec![]);
@@ -341,7 +352,7 @@ fn execute_command(
                     vec![],
                     vec![StateMutation::Transcript(
                         TranscriptMutation::AppendSystemMessage(
-                            "Stop the current task first.".to_string(),
+                            tr(Text::StopCurrentTaskFirst).to_string(),
                         ),
                     )],
                 );
@@ -354,6 +365,66 @@ fn execute_command(
                 vec![],
             )
         }
+        "duplicate" => {
+            if tui.agent_state.is_running() {
+                return (
+                    None,
+                    vec![],
+                    vec![StateMutation::Transcript(
+                        TranscriptMutation::AppendSystemMessage(
+                            tr(Text::StopCurrentTaskFirst).to_string(),
+                        ),
+                    )],
+                );
+            }
+            match &tui.thread.thread_handle {
+                Some(handle) => (
+                    None,
+                    vec![UiEffect::DuplicateThread {
+                        thread_id: handle.id.clone(),
+                        turns: None,
+                    }],
+                    vec![],
+                ),
+                None => (
+                    None,
+                    vec![],
+                    vec![StateMutation::Transcript(
+                        TranscriptMutation::AppendSystemMessage(
+                            "Duplicate requires an active thread.".to_string(),
+                        ),
+                    )],
+                ),
+            }
+        }
+        "pin" | "unpin" => {
+            if tui.thread.thread_handle.is_none() {
+                return (
+                    None,
+                    vec![],
+                    vec![StateMutation::Transcript(
+                        TranscriptMutation::AppendSystemMessage(
+                            "Pinning requires an active thread.".to_string(),
+                        ),
+                    )],
+                );
+            }
+            (
+                None,
+                vec![UiEffect::PinTurn {
+                    turn: None,
+                    pinned: cmd_name == "pin",
+                }],
+                vec![],
+            )
+        }
+        "pin-file" | "unpin-file" | "style" | "compare" | "duel" | "redo" => (
+            None,
+            vec![],
+            vec![StateMutation::Input(InputMutation::SetText(format!(
+                "/{cmd_name} "
+            )))],
+        ),
         "pwd" => {
             let path = tui.agent_opts.root.display().to_string();
             match Clipboard::copy(&path) {
@@ -402,11 +473,28 @@ fn execute_command(
         }
         "root-new" => {
             let (effects, mutations) = execute_root_new(tui);
-            (None, effects, mutations)
+            confirm_discarding_draft(tui, effects, mutations)
         }
         "thinking" => (Some(OverlayRequest::ThinkingPicker), vec![], vec![]),
         "timeline" => (Some(OverlayRequest::Timeline), vec![], vec![]),
+        "stats" => (Some(OverlayRequest::Stats), vec![], vec![]),
         "tldr" => (Some(OverlayRequest::Tldr), vec![], vec![]),
+        "tools" => (Some(OverlayRequest::ToolPicker), vec![], vec![]),
+        "params" => (Some(OverlayRequest::Params), vec![], vec![]),
+        "subagent-details" => (
+            None,
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::ToggleSubagentDetails,
+            )],
+        ),
+        "timestamps" => (
+            None,
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::CycleTimestamps,
+            )],
+        ),
         "context" => (Some(OverlayRequest::Context), vec![], vec![]),
         "handoff" => {
             let (effects, mutations) = execute_handoff(tui);
@@ -416,16 +504,60 @@ fn execute_command(
             let (effects, mutations) = execute_prompt_builder(tui);
             (None, effects, mutations)
         }
+        "improve" => execute_improve(tui),
         "new" => {
             let (effects, mutations) = execute_new(tui);
-            (None, effects, mutations)
+            confirm_discarding_draft(tui, effects, mutations)
         }
         "new-tab" => (Some(OverlayRequest::NewTab), vec![], vec![]),
-        "quit" => (None, execute_quit(tui), vec![]),
+        "incognito" => {
+            let (effects, mutations) = execute_incognito(tui);
+            confirm_discarding_draft(tui, effects, mutations)
+        }
+        "quit" => execute_quit(tui),
+        "tour" => (None, vec![UiEffect::ToggleTour], vec![]),
         _ => (None, vec![], vec![]),
     }
 }
 
+/// Summarizes the Claude usage limits observed on this session's responses.
+fn limits_summary() -> String {
+    use std::fmt::Write as _;
+
+    use zdx_engine::providers::subscription_quota::{PROVIDER_CLAUDE, format_reset_in};
+    use zdx_engine::providers::usage_limits;
+
+    let Some(observed) = usage_limits::observed(PROVIDER_CLAUDE) else {
+        return "No usage limits observed yet; they update with each Claude response. \
+                Run `zdx quota` for a live snapshot."
+            .to_string();
+    };
+    let status = observed
+        .status
+        .as_deref()
+        .map(|s| format!(", status {s}"))
+        .unwrap_or_default();
+    let mut summary = format!(
+        "Claude usage limits (as of {}{status}):",
+        observed
+            .observed_at
+            .with_timezone(&chrono::Local)
+            .format("%H:%M")
+    );
+    for window in &observed.quota.windows {
+        let reset = window
+            .resets_at
+            .map(|dt| format!("   {}", format_reset_in(dt)))
+            .unwrap_or_default();
+        let _ = write!(
+            summary,
+            "\n  {:<7} {:>4.0}%{reset}",
+            window.label, window.used_percent
+        );
+    }
+    summary
+}
+
 fn execute_logout(tui: &TuiState) -> (Vec<UiEffect>, Vec<StateMutation>) {
     use zdx_engine::providers::oauth::{claude_cli, grok_build, openai_codex};
     use zdx_engine::providers::provider_for_model;
@@ -502,7 +634,7 @@ fn execute_copy_id(tui: &TuiState) -> (Vec<UiEffect>, Vec<StateMutation>) {
         None => (
             vec![],
             vec![StateMutation::Transcript(
-                TranscriptMutation::AppendSystemMessage("No active thread.".to_string()),
+                TranscriptMutation::AppendSystemMessage(tr(Text::NoActiveThread).to_string()),
             )],
         ),
     }
@@ -583,6 +715,42 @@ fn execute_prompt_builder(tui: &TuiState) -> (Vec<UiEffect>, Vec<StateMutation>)
     )
 }
 
+/// Opens `/improve` for the composer draft. Run it from the command palette
+/// (Ctrl+O) so the draft stays in the input.
+fn execute_improve(tui: &TuiState) -> (Option<OverlayRequest>, Vec<UiEffect>, Vec<StateMutation>) {
+    let notice = |message: &str| {
+        (
+            None,
+            vec![],
+            vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(message.to_string()),
+            )],
+        )
+    };
+    if tui.input.handoff.is_active() || tui.input.prompt_builder.is_active() {
+        return notice("Finish or cancel the handoff/prompt-builder before /improve.");
+    }
+    if !tui.input.pending_images.is_empty() {
+        return notice("/improve works on text drafts; remove attached images first.");
+    }
+    // Expand paste placeholders so the helper sees the whole draft; the
+    // composer keeps them until the user accepts a suggestion.
+    let draft = tui
+        .input
+        .pending_pastes
+        .iter()
+        .fold(tui.input.get_text(), |acc, paste| {
+            acc.replace(&paste.placeholder, &paste.content)
+        })
+        .trim()
+        .to_string();
+    // Typed as `/improve`, the input only holds the command itself.
+    if draft.is_empty() || draft.starts_with('/') {
+        return notice("Type a draft prompt first, then run /improve from the palette (Ctrl+O).");
+    }
+    (Some(OverlayRequest::Improve { draft }), vec![], vec![])
+}
+
 fn execute_new(tui: &TuiState) -> (Vec<UiEffect>, Vec<StateMutation>) {
     if let Some(result) = prepare_new_thread_transition(tui) {
         return result;
@@ -594,12 +762,42 @@ fn execute_new(tui: &TuiState) -> (Vec<UiEffect>, Vec<StateMutation>) {
         (vec![UiEffect::CreateNewThread], mutations)
     } else {
         mutations.push(StateMutation::Transcript(
-            TranscriptMutation::AppendSystemMessage("Thread cleared.".to_string()),
+            TranscriptMutation::AppendSystemMessage(tr(Text::ThreadCleared).to_string()),
         ));
         (vec![], mutations)
     }
 }
 
+/// Toggles incognito mode. Entering starts a fresh conversation with no
+/// thread; leaving starts a new saved thread.
+fn execute_incognito(tui: &TuiState) -> (Vec<UiEffect>, Vec<StateMutation>) {
+    if let Some(result) = prepare_new_thread_transition(tui) {
+        return result;
+    }
+
+    let incognito = !tui.thread.incognito;
+    let mut mutations = new_thread_reset_mutations();
+    mutations.push(StateMutation::Thread(ThreadMutation::SetIncognito(
+        incognito,
+    )));
+    if incognito {
+        mutations.push(StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(
+                "Incognito on: nothing from this conversation is saved.".to_string(),
+            ),
+        ));
+        let path = tui.agent_opts.root.clone();
+        (vec![UiEffect::RefreshSystemPrompt { path }], mutations)
+    } else {
+        mutations.push(StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(
+                "Incognito off: started a new saved thread.".to_string(),
+            ),
+        ));
+        (vec![UiEffect::CreateNewThread], mutations)
+    }
+}
+
 fn execute_root_new(tui: &TuiState) -> (Vec<UiEffect>, Vec<StateMutation>) {
     if let Some(result) = prepare_new_thread_transition(tui) {
         return result;
@@ -616,7 +814,7 @@ fn prepare_new_thread_transition(tui: &TuiState) -> Option<(Vec<UiEffect>, Vec<S
             vec![],
             vec![StateMutation::Transcript(
                 TranscriptMutation::AppendSystemMessage(
-                    "Cannot clear while streaming.".to_string(),
+                    tr(Text::CannotClearWhileStreaming).to_string(),
                 ),
             )],
         ));
@@ -645,14 +843,56 @@ fn new_thread_reset_mutations() -> Vec<StateMutation> {
     ]
 }
 
-fn execute_quit(tui: &TuiState) -> Vec<UiEffect> {
+fn execute_quit(tui: &TuiState) -> (Option<OverlayRequest>, Vec<UiEffect>, Vec<StateMutation>) {
     if tui.agent_state.is_running() {
-        vec![UiEffect::InterruptAgent, UiEffect::Quit]
+        let confirm =
+            ConfirmState::new("Quit?", "A turn is still running. Quitting interrupts it.")
+                .danger()
+                .confirm_label("quit")
+                .on_confirm(vec![UiEffect::InterruptAgent, UiEffect::Quit], vec![]);
+        (
+            Some(OverlayRequest::Confirm(Box::new(confirm))),
+            vec![],
+            vec![],
+        )
     } else {
-        vec![UiEffect::Quit]
+        (None, vec![UiEffect::Quit], vec![])
     }
 }
 
+/// Asks before a new-thread reset that would drop queued prompts or an
+/// unsaved conversation; otherwise applies it directly.
+fn confirm_discarding_draft(
+    tui: &TuiState,
+    effects: Vec<UiEffect>,
+    mutations: Vec<StateMutation>,
+) -> (Option<OverlayRequest>, Vec<UiEffect>, Vec<StateMutation>) {
+    // Blocked transitions (turn running, thread being created) reset nothing.
+    let resets = mutations
+        .iter()
+        .any(|m| matches!(m, StateMutation::Thread(ThreadMutation::ClearMessages)));
+    let mut losses = Vec::new();
+    let queued = tui.input.queued.len();
+    if queued > 0 {
+        losses.push(format!("{queued} queued prompt(s) will be discarded."));
+    }
+    if tui.thread.thread_handle.is_none() && !tui.thread.messages.is_empty() {
+        losses.push("This conversation isn't saved and will be lost.".to_string());
+    }
+    if !resets || losses.is_empty() {
+        return (None, effects, mutations);
+    }
+    let confirm = ConfirmState::new("Start a new thread?", losses.join(" "))
+        .danger()
+        .confirm_label("start new")
+        .on_confirm(effects, mutations);
+    (
+        Some(OverlayRequest::Confirm(Box::new(confirm))),
+        vec![],
+        vec![],
+    )
+}
+
 pub fn render_command_palette(
     frame: &mut Frame,
     palette: &CommandPaletteState,
@@ -680,7 +920,7 @@ pub fn render_command_palette(
         area,
         input_top_y,
         &OverlayConfig {
-            title: "Command Palette",
+            title: tr(Text::CommandPaletteTitle),
             border_color: Color::Magenta,
             width: palette_width,
             height: palette_height,
@@ -1001,6 +1241,34 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_incognito_toggle_skips_thread_creation_only_when_entering() {
+        use crate::state::AppState;
+
+        let config = zdx_engine::config::Config::default();
+        // No thread at launch (`--no-thread`) starts incognito.
+        let mut app = AppState::new(config, PathBuf::new(), None, None);
+        assert!(app.tui.thread.incognito);
+
+        let (effects, mutations) = execute_incognito(&app.tui);
+        assert!(matches!(effects.as_slice(), [UiEffect::CreateNewThread]));
+        assert!(mutations.iter().any(|mutation| matches!(
+            mutation,
+            StateMutation::Thread(ThreadMutation::SetIncognito(false))
+        )));
+
+        app.tui.thread.incognito = false;
+        let (effects, mutations) = execute_incognito(&app.tui);
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::RefreshSystemPrompt { .. }]
+        ));
+        assert!(mutations.iter().any(|mutation| matches!(
+            mutation,
+            StateMutation::Thread(ThreadMutation::SetIncognito(true))
+        )));
+    }
+
     #[test]
     fn test_palette_includes_custom_commands_after_builtins() {
         let customs = vec![
@@ -1215,6 +1483,35 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn test_improve_opens_only_for_a_real_draft() {
+        use crate::state::AppState;
+
+        let config = zdx_engine::config::Config::default();
+        let mut app = AppState::new(config, PathBuf::new(), None, None);
+
+        let (overlay, _, mutations) = execute_command(&app.tui, "improve");
+        assert!(overlay.is_none());
+        assert!(matches!(
+            mutations.as_slice(),
+            [StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(_)
+            )]
+        ));
+
+        app.tui.input.set_text("/improve");
+        let (overlay, _, _) = execute_command(&app.tui, "improve");
+        assert!(overlay.is_none());
+
+        app.tui.input.set_text("fix the login bug");
+        let (overlay, effects, mutations) = execute_command(&app.tui, "improve");
+        assert!(effects.is_empty() && mutations.is_empty());
+        assert!(matches!(
+            overlay,
+            Some(OverlayRequest::Improve { draft }) if draft == "fix the login bug"
+        ));
+    }
+
     #[test]
     fn test_palette_prompt_builder_command_arms_pending_state() {
         use crate::mutations::InputMutation;
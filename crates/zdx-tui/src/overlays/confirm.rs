@@ -0,0 +1,198 @@
+//! Confirmation overlay for destructive actions.
+//!
+//! Shows a title and body with confirm (`y`/Enter) and cancel (`n`/Esc)
+//! keys. Confirming applies the effects and mutations it was built with;
+//! either way it closes, or returns to the overlay it was opened from when
+//! built with [`ConfirmState::return_to_previous`] (e.g. the thread picker).
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Style};
+use ratatui::widgets::{Paragraph, Wrap};
+
+use super::render_utils::wrapped_rows;
+use super::{Overlay, OverlayUpdate};
+use crate::effects::UiEffect;
+use crate::mutations::StateMutation;
+
+const OVERLAY_WIDTH: u16 = 60;
+
+/// State for the confirmation overlay.
+#[derive(Debug)]
+pub struct ConfirmState {
+    pub title: String,
+    pub body: String,
+    /// Hint label for the confirm key (e.g. "delete", "quit").
+    pub confirm_label: String,
+    /// Red styling for irreversible actions.
+    pub danger: bool,
+    effects: Vec<UiEffect>,
+    mutations: Vec<StateMutation>,
+    return_to_previous: bool,
+    previous: Option<Box<Overlay>>,
+}
+
+impl ConfirmState {
+    pub fn new(title: impl Into<String>, body: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            body: body.into(),
+            confirm_label: "confirm".to_string(),
+            danger: false,
+            effects: Vec::new(),
+            mutations: Vec::new(),
+            return_to_previous: false,
+            previous: None,
+        }
+    }
+
+    #[must_use]
+    pub fn danger(mut self) -> Self {
+        self.danger = true;
+        self
+    }
+
+    #[must_use]
+    pub fn confirm_label(mut self, label: impl Into<String>) -> Self {
+        self.confirm_label = label.into();
+        self
+    }
+
+    /// Effects and mutations applied when the user confirms.
+    #[must_use]
+    pub fn on_confirm(mut self, effects: Vec<UiEffect>, mutations: Vec<StateMutation>) -> Self {
+        self.effects = effects;
+        self.mutations = mutations;
+        self
+    }
+
+    /// Reopen the overlay that requested the confirmation once resolved.
+    #[must_use]
+    pub fn return_to_previous(mut self) -> Self {
+        self.return_to_previous = true;
+        self
+    }
+
+    /// Stores the overlay being replaced, if this confirmation returns to it.
+    pub(crate) fn attach_previous(&mut self, previous: Option<Overlay>) {
+        if self.return_to_previous {
+            self.previous = previous.map(Box::new);
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, input_y: u16) {
+        render_confirm_overlay(frame, self, area, input_y);
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> OverlayUpdate {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Char('y' | 'Y') | KeyCode::Enter if !ctrl => {
+                let effects = std::mem::take(&mut self.effects);
+                let mutations = std::mem::take(&mut self.mutations);
+                self.finish()
+                    .with_ui_effects(effects)
+                    .with_mutations(mutations)
+            }
+            KeyCode::Char('n' | 'N') | KeyCode::Esc => self.finish(),
+            KeyCode::Char('c') if ctrl => self.finish(),
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    fn finish(&mut self) -> OverlayUpdate {
+        match self.previous.take() {
+            Some(previous) => OverlayUpdate::replace(*previous),
+            None => OverlayUpdate::close(),
+        }
+    }
+}
+
+fn render_confirm_overlay(frame: &mut Frame, state: &ConfirmState, area: Rect, input_top_y: u16) {
+    use super::render_utils::{InputHint, OverlayConfig, render_overlay};
+
+    let border_color = if state.danger {
+        Color::Red
+    } else {
+        Color::Yellow
+    };
+    let text_width = OVERLAY_WIDTH
+        .min(area.width.saturating_sub(4))
+        .saturating_sub(2);
+    let body_rows = wrapped_rows(&state.body, text_width);
+
+    let hints = [
+        InputHint::new("y", &state.confirm_label),
+        InputHint::new("n/Esc", "cancel"),
+    ];
+    let layout = render_overlay(
+        frame,
+        area,
+        input_top_y,
+        &OverlayConfig {
+            title: &state.title,
+            border_color,
+            width: OVERLAY_WIDTH,
+            height: body_rows.saturating_add(3),
+            hints: &hints,
+        },
+    );
+
+    frame.render_widget(
+        Paragraph::new(state.body.as_str())
+            .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: false }),
+        layout.body,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::overlays::{OverlayTransition, RenameState};
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn confirm_applies_effects_and_cancel_discards_them() {
+        let mut state = ConfirmState::new("Quit?", "A turn is running.")
+            .danger()
+            .on_confirm(vec![UiEffect::Quit], vec![]);
+        assert!(matches!(
+            state.handle_key(key(KeyCode::Char('x'))).transition,
+            OverlayTransition::Stay
+        ));
+        let update = state.handle_key(key(KeyCode::Char('y')));
+        assert!(matches!(update.transition, OverlayTransition::Close));
+        assert!(matches!(update.effects.as_slice(), [UiEffect::Quit]));
+
+        let mut state = ConfirmState::new("Quit?", "").on_confirm(vec![UiEffect::Quit], vec![]);
+        let update = state.handle_key(key(KeyCode::Esc));
+        assert!(matches!(update.transition, OverlayTransition::Close));
+        assert!(update.effects.is_empty());
+    }
+
+    #[test]
+    fn returns_to_the_previous_overlay() {
+        let (rename, _) = RenameState::open("t1".to_string(), None);
+        let mut state = ConfirmState::new("Delete?", "").return_to_previous();
+        state.attach_previous(Some(Overlay::Rename(rename)));
+
+        let update = state.handle_key(key(KeyCode::Char('n')));
+        assert!(matches!(
+            update.transition,
+            OverlayTransition::Replace(overlay) if matches!(*overlay, Overlay::Rename(_))
+        ));
+
+        let mut state = ConfirmState::new("Delete?", "");
+        let (rename, _) = RenameState::open("t1".to_string(), None);
+        state.attach_previous(Some(Overlay::Rename(rename)));
+        assert!(matches!(
+            state.handle_key(key(KeyCode::Enter)).transition,
+            OverlayTransition::Close
+        ));
+    }
+}
@@ -20,11 +20,15 @@ use super::OverlayUpdate;
 use crate::effects::UiEffect;
 use crate::input::InputState;
 use crate::mutations::{InputMutation, StateMutation};
+use crate::transcript::{HistoryCell, collect_touched_files};
 
 const MAX_VISIBLE_FILES: usize = 10;
 /// Visible list rows in the dropdown (no title/hints overhead).
 const VISIBLE_HEIGHT: usize = MAX_VISIBLE_FILES;
 const MAX_DEPTH: usize = 15;
+/// Files the agent touched in this thread, listed ahead of the workspace.
+const MAX_RECENT_FILES: usize = 20;
+const RECENT_TAG: &str = "  recent";
 
 /// A matched file with its score and matched character indices.
 #[derive(Debug, Clone)]
@@ -50,6 +54,9 @@ pub struct FilePickerState {
     pub selected: usize,
     pub offset: usize,
     pub loading: bool,
+    /// Files the agent recently read or changed, most recent first. They lead
+    /// `files` and outrank other matches.
+    pub recent: Vec<PathBuf>,
 }
 
 impl FilePickerState {
@@ -62,6 +69,7 @@ impl FilePickerState {
                 selected: 0,
                 offset: 0,
                 loading: true,
+                recent: Vec::new(),
             },
             vec![UiEffect::DiscoverFiles],
         )
@@ -190,8 +198,14 @@ impl FilePickerState {
                 })
                 .collect();
 
-            // Sort by score descending (best matches first)
-            matched_files.sort_by_key(|m| std::cmp::Reverse(m.score.unwrap_or(i64::MIN)));
+            // Recent files first (most recent first), then by score descending
+            let recent = self.recent.len();
+            matched_files.sort_by(|a, b| match (a.file_idx < recent, b.file_idx < recent) {
+                (true, true) => a.file_idx.cmp(&b.file_idx),
+                (true, false) => std::cmp::Ordering::Less,
+                (false, true) => std::cmp::Ordering::Greater,
+                (false, false) => b.score.cmp(&a.score),
+            });
 
             self.filtered = matched_files;
         }
@@ -200,8 +214,18 @@ impl FilePickerState {
         self.offset = 0;
     }
 
+    /// Lists `recent` ahead of the workspace files (shown while discovery
+    /// is still running).
+    pub fn set_recent(&mut self, recent: Vec<PathBuf>) {
+        self.files.clone_from(&recent);
+        self.recent = recent;
+        self.apply_filter("");
+    }
+
     pub fn set_files(&mut self, files: Vec<PathBuf>) {
-        self.files = files;
+        let mut merged = self.recent.clone();
+        merged.extend(files.into_iter().filter(|file| !self.recent.contains(file)));
+        self.files = merged;
         self.loading = false;
         // Initialize filtered with all files (no highlighting)
         self.filtered = (0..self.files.len())
@@ -313,6 +337,30 @@ impl FilePickerState {
     }
 }
 
+/// Files touched by the thread's tool calls that still exist, most recent
+/// first, relative to `root` when inside it. Includes files the agent created
+/// that discovery skips (e.g. gitignored output).
+pub fn recent_files(cells: &[HistoryCell], root: &std::path::Path) -> Vec<PathBuf> {
+    let mut recent: Vec<PathBuf> = Vec::new();
+    for path in collect_touched_files(cells) {
+        let path = PathBuf::from(path);
+        let absolute = root.join(&path);
+        if !absolute.is_file() {
+            continue;
+        }
+        let path = absolute
+            .strip_prefix(root)
+            .map_or(path, std::path::Path::to_path_buf);
+        if !recent.contains(&path) {
+            recent.push(path);
+        }
+        if recent.len() == MAX_RECENT_FILES {
+            break;
+        }
+    }
+    recent
+}
+
 /// Discovers project files, respecting .gitignore.
 ///
 /// Hidden dotfiles/dotdirs are skipped by default (via `standard_filters`),
@@ -458,7 +506,7 @@ pub fn render_file_picker(
     let picker_width = area.width.saturating_sub(4).min(80);
 
     // Height: 2 borders + list rows (no title/hints overhead)
-    let inner_height: u16 = if picker.loading || file_count == 0 {
+    let inner_height: u16 = if file_count == 0 {
         1
     } else {
         visible_count as u16
@@ -483,7 +531,7 @@ pub fn render_file_picker(
         return;
     }
 
-    if picker.loading {
+    if picker.loading && picker.filtered.is_empty() {
         let loading_msg =
             Paragraph::new("Loading files...").style(Style::default().fg(Color::DarkGray));
         frame.render_widget(loading_msg, inner);
@@ -511,7 +559,9 @@ pub fn render_file_picker(
         .filter_map(|file_match| {
             picker.files.get(file_match.file_idx).map(|path| {
                 let path_str = path.to_string_lossy();
-                let max_width = inner.width.saturating_sub(2) as usize;
+                let is_recent = file_match.file_idx < picker.recent.len();
+                let tag_width = if is_recent { RECENT_TAG.len() } else { 0 };
+                let max_width = (inner.width.saturating_sub(2) as usize).saturating_sub(tag_width);
 
                 let (display, adjusted_indices) = if path_str.len() > max_width {
                     let truncate_at = path_str.len() - max_width + 1;
@@ -532,7 +582,13 @@ pub fn render_file_picker(
                     (path_str.to_string(), file_match.match_indices.clone())
                 };
 
-                let line = build_highlighted_line(&display, &adjusted_indices);
+                let mut line = build_highlighted_line(&display, &adjusted_indices);
+                if is_recent {
+                    line.spans.push(Span::styled(
+                        RECENT_TAG,
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
                 ListItem::new(line)
             })
         })
@@ -894,4 +950,27 @@ mod tests {
             "expected .git/ to remain hidden, got: {files:?}"
         );
     }
+
+    #[test]
+    fn test_recent_files_lead_and_outrank_matches() {
+        let (mut picker, _) = FilePickerState::open(0);
+        picker.set_recent(vec![PathBuf::from("out/new_report.md")]);
+        assert_eq!(
+            picker.filtered.len(),
+            1,
+            "recent files show before discovery"
+        );
+
+        picker.set_files(vec![
+            PathBuf::from("report.md"),
+            PathBuf::from("out/new_report.md"),
+        ]);
+        assert_eq!(picker.files.len(), 2, "recent files are not duplicated");
+
+        picker.apply_filter("report");
+        assert_eq!(
+            picker.selected_file(),
+            Some(&PathBuf::from("out/new_report.md"))
+        );
+    }
 }
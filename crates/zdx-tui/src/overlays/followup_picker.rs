@@ -6,6 +6,7 @@ use ratatui::text::Line;
 use ratatui::widgets::{List, ListItem, ListState};
 
 use super::OverlayUpdate;
+use crate::common::i18n::{Text, tr};
 use crate::mutations::StateMutation;
 use crate::state::TuiState;
 
@@ -96,7 +97,7 @@ fn render_followup_picker(
         area,
         input_top_y,
         &OverlayConfig {
-            title: "Suggested replies",
+            title: tr(Text::SuggestedRepliesTitle),
             border_color: Color::Green,
             width: 60,
             height: picker_height,
@@ -0,0 +1,281 @@
+//! Prompt improvement overlay (`/improve`).
+//!
+//! Shows the drafted prompt next to a rewrite from a small helper model
+//! (`Config::improve_model`), generated by a subagent so the current
+//! thread's context is untouched.
+//!
+//! States:
+//! - `Loading`: spinner shown while the subagent runs
+//! - `Ready(text)`: draft and suggestion side by side
+//! - `Error(message)`: failure message (e.g. timeout)
+//!
+//! Keys: Enter sends the suggestion (or puts it in the input while a turn is
+//! running), `e` puts it in the input for editing, Esc keeps the draft.
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+
+use super::OverlayUpdate;
+use super::render_utils::centered_rect;
+use crate::common::TaskKind;
+use crate::effects::UiEffect;
+use crate::mutations::{InputMutation, StateMutation};
+use crate::state::TuiState;
+use crate::transcript::SPINNER_SPEED_DIVISOR;
+
+/// Spinner frames shared with other overlays.
+const SPINNER_FRAMES: &[&str] = &["◐", "◓", "◑", "◒"];
+
+/// State of the improvement request.
+#[derive(Debug, Clone)]
+pub enum ImprovePhase {
+    Loading,
+    Ready(String),
+    Error(String),
+}
+
+#[derive(Debug)]
+pub struct ImproveState {
+    /// Draft the suggestion was requested for. Used to ignore stale results.
+    pub draft: String,
+    pub phase: ImprovePhase,
+}
+
+impl ImproveState {
+    pub fn open(draft: String) -> Self {
+        Self {
+            draft,
+            phase: ImprovePhase::Loading,
+        }
+    }
+
+    pub fn set_result(&mut self, result: Result<String, String>) {
+        self.phase = match result {
+            Ok(text) => ImprovePhase::Ready(text),
+            Err(message) => ImprovePhase::Error(message),
+        };
+    }
+
+    pub fn handle_key(&mut self, tui: &TuiState, key: KeyEvent) -> OverlayUpdate {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q' | 'r') => {
+                let task = tui.tasks.state(TaskKind::PromptImprove);
+                if matches!(self.phase, ImprovePhase::Loading) && task.is_running() {
+                    return OverlayUpdate::close().with_ui_effects(vec![UiEffect::CancelTask {
+                        kind: TaskKind::PromptImprove,
+                        token: task.cancel.clone(),
+                    }]);
+                }
+                OverlayUpdate::close()
+            }
+            KeyCode::Enter | KeyCode::Char('a') => {
+                let ImprovePhase::Ready(text) = &self.phase else {
+                    return OverlayUpdate::stay();
+                };
+                if tui.agent_state.is_running() {
+                    return edit(text);
+                }
+                let thread_id = tui.thread.thread_handle.as_ref().map(|t| t.id.clone());
+                let (effects, mutations) =
+                    crate::input::build_send_effects(text, thread_id, false, vec![]);
+                let mut all = vec![StateMutation::Input(InputMutation::Clear)];
+                all.extend(mutations);
+                OverlayUpdate::close()
+                    .with_ui_effects(effects)
+                    .with_mutations(all)
+            }
+            KeyCode::Char('e') => match &self.phase {
+                ImprovePhase::Ready(text) => edit(text),
+                _ => OverlayUpdate::stay(),
+            },
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, spinner_frame: usize) {
+        let popup_area = centered_rect(90, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let (icon, border_color, status) = match &self.phase {
+            ImprovePhase::Loading => {
+                let idx = (spinner_frame / SPINNER_SPEED_DIVISOR) % SPINNER_FRAMES.len();
+                (SPINNER_FRAMES[idx], Color::Cyan, "Improving prompt…")
+            }
+            ImprovePhase::Ready(_) => ("✓", Color::Green, "Improved prompt"),
+            ImprovePhase::Error(_) => ("✗", Color::Red, "Error"),
+        };
+
+        let key = |text: &'static str| Span::styled(text, Style::default().fg(Color::Yellow));
+        let label = |text: &'static str| Span::styled(text, Style::default().fg(Color::DarkGray));
+        let footer = if matches!(self.phase, ImprovePhase::Ready(_)) {
+            Line::from(vec![
+                key(" [Enter]"),
+                label(" send suggestion  "),
+                key("[e]"),
+                label(" edit suggestion  "),
+                key("[Esc]"),
+                label(" keep draft "),
+            ])
+        } else {
+            Line::from(vec![key(" [Esc]"), label(" keep draft ")])
+        };
+        let block = Block::default()
+            .title(format!(" {icon} {status} "))
+            .title_style(
+                Style::default()
+                    .fg(border_color)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title_bottom(footer);
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+        if inner.height == 0 || inner.width == 0 {
+            return;
+        }
+
+        let panes = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(inner);
+
+        let draft_lines: Vec<Line<'static>> = self
+            .draft
+            .lines()
+            .map(|l| Line::from(l.to_string()))
+            .collect();
+        render_pane(frame, panes[0], "Your draft", Color::DarkGray, draft_lines);
+
+        let muted = Style::default()
+            .fg(Color::DarkGray)
+            .add_modifier(Modifier::ITALIC);
+        let suggestion_lines: Vec<Line<'static>> = match &self.phase {
+            ImprovePhase::Loading => vec![Line::from(Span::styled(
+                "Rewriting against the prompt rubric…",
+                muted,
+            ))],
+            ImprovePhase::Ready(text) => text.lines().map(|l| Line::from(l.to_string())).collect(),
+            ImprovePhase::Error(message) => {
+                let mut lines = vec![
+                    Line::from(Span::styled(
+                        "Could not improve the prompt.",
+                        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    )),
+                    Line::default(),
+                ];
+                lines.extend(message.lines().map(|l| {
+                    Line::from(Span::styled(
+                        l.to_string(),
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                }));
+                lines
+            }
+        };
+        render_pane(
+            frame,
+            panes[1],
+            "Suggestion",
+            border_color,
+            suggestion_lines,
+        );
+    }
+}
+
+/// Puts `text` in the input for editing and closes the overlay.
+fn edit(text: &str) -> OverlayUpdate {
+    OverlayUpdate::close().with_mutations(vec![StateMutation::Input(InputMutation::SetText(
+        text.to_string(),
+    ))])
+}
+
+fn render_pane(
+    frame: &mut Frame,
+    area: Rect,
+    title: &'static str,
+    color: Color,
+    lines: Vec<Line<'static>>,
+) {
+    let block = Block::default()
+        .title(Span::styled(
+            format!(" {title} "),
+            Style::default().fg(color).add_modifier(Modifier::BOLD),
+        ))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(color));
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(block)
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crossterm::event::KeyModifiers;
+    use zdx_engine::config::Config;
+
+    use super::*;
+    use crate::state::AppState;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn ready() -> ImproveState {
+        let mut state = ImproveState::open("fix bug".to_string());
+        state.set_result(Ok("Fix the login bug in `auth.rs`.".to_string()));
+        state
+    }
+
+    #[test]
+    fn accept_sends_the_suggestion() {
+        let app = AppState::new(Config::default(), PathBuf::new(), None, None);
+        let update = ready().handle_key(&app.tui, press(KeyCode::Enter));
+        assert!(
+            update
+                .effects
+                .iter()
+                .any(|e| matches!(e, UiEffect::StartAgentTurn { .. }))
+        );
+        assert!(matches!(
+            update.mutations.first(),
+            Some(StateMutation::Input(InputMutation::Clear))
+        ));
+    }
+
+    #[test]
+    fn edit_replaces_the_input_and_reject_keeps_it() {
+        let app = AppState::new(Config::default(), PathBuf::new(), None, None);
+        let update = ready().handle_key(&app.tui, press(KeyCode::Char('e')));
+        assert!(update.effects.is_empty());
+        assert!(matches!(
+            update.mutations.as_slice(),
+            [StateMutation::Input(InputMutation::SetText(text))]
+                if text == "Fix the login bug in `auth.rs`."
+        ));
+
+        let update = ready().handle_key(&app.tui, press(KeyCode::Esc));
+        assert!(update.effects.is_empty());
+        assert!(update.mutations.is_empty());
+    }
+
+    #[test]
+    fn accept_is_ignored_while_loading() {
+        let app = AppState::new(Config::default(), PathBuf::new(), None, None);
+        let mut state = ImproveState::open("fix bug".to_string());
+        let update = state.handle_key(&app.tui, press(KeyCode::Enter));
+        assert!(matches!(
+            update.transition,
+            crate::overlays::OverlayTransition::Stay
+        ));
+    }
+}
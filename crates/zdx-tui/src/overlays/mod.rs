@@ -5,14 +5,22 @@
 //!
 //! ## Module Structure
 //!
-//! - `command_palette.rs`: Command palette (Ctrl+O or `/` when input empty)
+//! - `ask_user.rs`: Agent question prompt (`Ask_User` tool)
+//! - `command_palette.rs`: Command palette (Ctrl+O)
+//! - `confirm.rs`: Generic confirmation for destructive actions
 //! - `model_picker.rs`: Model selection picker
+//! - `params.rs`: Session sampling controls (`/params`)
+//! - `improve.rs`: Draft vs. improved prompt comparison (`/improve`)
 //! - `skill_picker.rs`: Skill installer picker
 //! - `thinking_picker.rs`: Thinking level selection picker
 //! - `thread_picker.rs`: Thread history picker
+//! - `quick_switch.rs`: Recent-thread quick switcher (Ctrl+P)
+//! - `tool_picker.rs`: Per-session tool enable/disable picker (`/tools`)
 //! - `login.rs`: OAuth login flow overlay
 //! - `file_picker.rs`: File picker triggered by `@`
+//! - `slash_complete.rs`: Inline slash-command completion triggered by `/`
 //! - `rename.rs`: Thread rename overlay
+//! - `stats.rs`: Thread statistics overlay (`/stats`)
 //! - `render_utils.rs`: Shared rendering utilities for overlays
 //! - `update.rs`: Overlay key handling and update logic
 //!
@@ -21,40 +29,58 @@
 //! `OverlayExt` provides convenience methods for `Option<Overlay>` to encapsulate
 //! the common patterns used in the reducer.
 
+pub mod ask_user;
 pub mod command_palette;
+pub mod confirm;
 pub mod context;
 pub mod file_picker;
 pub mod followup_picker;
 pub mod image_preview;
+pub mod improve;
 pub mod login;
 pub mod model_picker;
+pub mod params;
+pub mod quick_switch;
 pub mod rename;
 pub mod render_utils;
 pub mod skill_picker;
+pub mod slash_complete;
+pub mod stats;
 pub mod thinking_picker;
 pub mod thread_picker;
 pub mod timeline;
 pub mod tldr;
 pub mod tool_detail;
+pub mod tool_picker;
 mod update;
 
+pub use ask_user::AskUserState;
 pub use command_palette::CommandPaletteState;
+pub use confirm::ConfirmState;
 pub use context::{ContextPhase, ContextState};
 use crossterm::event::KeyEvent;
 pub use file_picker::{FilePickerState, discover_files};
 pub use followup_picker::FollowupPickerState;
 pub use image_preview::ImagePreviewState;
+pub use improve::{ImprovePhase, ImproveState};
 pub use login::LoginState;
 pub use model_picker::ModelPickerState;
+pub use params::ParamsState;
+pub use quick_switch::QuickSwitchState;
 use ratatui::Frame;
 use ratatui::layout::Rect;
 pub use rename::RenameState;
 pub use skill_picker::SkillPickerState;
+pub use slash_complete::SlashCompleteState;
+pub use stats::StatsState;
 pub use thinking_picker::ThinkingPickerState;
-pub use thread_picker::{ThreadPickerMode, ThreadPickerState, ThreadScope};
+pub use thread_picker::{
+    PreviewMessage, ThreadPickerMode, ThreadPickerState, ThreadPreview, ThreadScope,
+};
 pub use timeline::TimelineState;
 pub use tldr::{TldrPhase, TldrState};
 pub use tool_detail::ToolDetailState;
+pub use tool_picker::ToolPickerState;
 // Re-export update functions
 pub use update::{handle_files_discovered, handle_overlay_key};
 
@@ -74,16 +100,25 @@ pub enum OverlayRequest {
     ModelPicker,
     SkillPicker,
     ThinkingPicker,
+    ToolPicker,
+    Params,
     NewTab,
     Btw,
+    Compare(crate::compare::CompareRequest),
+    Duel(crate::duel::DuelRequest),
     Login,
     FilePicker {
         trigger_pos: usize,
     },
+    SlashComplete,
     Timeline,
     Rename,
     Tldr,
+    Improve {
+        draft: String,
+    },
     Context,
+    Stats,
     ImagePreview {
         image_path: String,
         image_index: usize,
@@ -91,6 +126,7 @@ pub enum OverlayRequest {
     ToolDetail {
         tool_use_id: String,
     },
+    Confirm(Box<ConfirmState>),
 }
 
 /// Transition returned by overlay key handlers.
@@ -99,6 +135,8 @@ pub enum OverlayTransition {
     Stay,
     Close,
     Open(OverlayRequest),
+    /// Swap in an existing overlay (e.g. back from a confirmation).
+    Replace(Box<Overlay>),
 }
 
 /// Update returned by overlay key handlers.
@@ -130,6 +168,10 @@ impl OverlayUpdate {
         Self::new(OverlayTransition::Open(request))
     }
 
+    pub fn replace(overlay: Overlay) -> Self {
+        Self::new(OverlayTransition::Replace(Box::new(overlay)))
+    }
+
     #[must_use]
     pub fn with_mutations(mut self, mutations: Vec<StateMutation>) -> Self {
         self.mutations = mutations;
@@ -153,16 +195,24 @@ pub enum Overlay {
     ModelPicker(ModelPickerState),
     SkillPicker(SkillPickerState),
     ThinkingPicker(ThinkingPickerState),
+    ToolPicker(ToolPickerState),
+    Params(ParamsState),
     ThreadPicker(ThreadPickerState),
+    QuickSwitch(QuickSwitchState),
     Login(LoginState),
     FilePicker(FilePickerState),
+    SlashComplete(SlashCompleteState),
     Timeline(TimelineState),
     Rename(RenameState),
     Tldr(TldrState),
+    Improve(ImproveState),
     Context(ContextState),
+    Stats(StatsState),
     ImagePreview(ImagePreviewState),
     ToolDetail(ToolDetailState),
     FollowupPicker(FollowupPickerState),
+    AskUser(AskUserState),
+    Confirm(ConfirmState),
 }
 
 impl Overlay {
@@ -172,12 +222,19 @@ impl Overlay {
             Overlay::ModelPicker(p) => p.render(frame, area, input_y),
             Overlay::SkillPicker(p) => p.render(frame, area, input_y),
             Overlay::ThinkingPicker(p) => p.render(frame, area, input_y),
+            Overlay::ToolPicker(p) => p.render(frame, area, input_y),
+            Overlay::Params(p) => p.render(frame, area, input_y),
             Overlay::ThreadPicker(p) => p.render(frame, area, input_y),
+            Overlay::QuickSwitch(s) => s.render(frame, area, input_y),
             Overlay::FilePicker(p) => p.render(frame, area, input_y),
+            Overlay::SlashComplete(s) => s.render(frame, area, input_y),
             Overlay::Login(l) => l.render(frame, area, input_y),
             Overlay::Timeline(t) => t.render(frame, area, input_y),
             Overlay::Rename(r) => r.render(frame, area, input_y),
+            Overlay::Stats(s) => s.render(frame, area, input_y),
             Overlay::FollowupPicker(p) => p.render(frame, area, input_y),
+            Overlay::AskUser(a) => a.render(frame, area, input_y),
+            Overlay::Confirm(c) => c.render(frame, area, input_y),
             Overlay::ImagePreview(p) => p.render(
                 frame,
                 area,
@@ -187,8 +244,12 @@ impl Overlay {
             // Rendered separately in render.rs:
             //   - Tldr needs spinner_frame for the loading indicator
             //   - Context needs spinner_frame for the loading indicator
+            //   - Improve needs spinner_frame for the loading indicator
             //   - ToolDetail looks up its live cell from the transcript
-            Overlay::Tldr(_) | Overlay::Context(_) | Overlay::ToolDetail(_) => {}
+            Overlay::Tldr(_)
+            | Overlay::Context(_)
+            | Overlay::Improve(_)
+            | Overlay::ToolDetail(_) => {}
         }
     }
 
@@ -198,19 +259,33 @@ impl Overlay {
             Overlay::ModelPicker(p) => p.handle_key(tui, key),
             Overlay::SkillPicker(p) => p.handle_key(tui, key),
             Overlay::ThinkingPicker(p) => p.handle_key(tui, key),
+            Overlay::ToolPicker(p) => p.handle_key(tui, key),
+            Overlay::Params(p) => p.handle_key(tui, key),
             Overlay::ThreadPicker(p) => p.handle_key(tui, key),
+            Overlay::QuickSwitch(s) => s.handle_key(tui, key),
             Overlay::FilePicker(p) => p.handle_key(&tui.input, key),
+            Overlay::SlashComplete(s) => s.handle_key(tui, key),
             Overlay::Login(l) => l.handle_key(tui, key),
             Overlay::Timeline(t) => t.handle_key(tui, key),
             Overlay::Rename(r) => r.handle_key(tui, key),
             Overlay::FollowupPicker(p) => p.handle_key(tui, key),
             Overlay::ImagePreview(p) => p.handle_key(tui, key),
             Overlay::Tldr(t) => t.handle_key(key),
+            Overlay::Improve(i) => i.handle_key(tui, key),
             Overlay::Context(c) => c.handle_key(key),
+            Overlay::Stats(s) => s.handle_key(key),
             Overlay::ToolDetail(t) => t.handle_key(key),
+            Overlay::AskUser(a) => a.handle_key(key),
+            Overlay::Confirm(c) => c.handle_key(key),
         }
     }
 
+    /// Whether typing still edits the composer while this overlay is open
+    /// (so the input keeps its cursor).
+    pub fn routes_input(&self) -> bool {
+        matches!(self, Overlay::FilePicker(_) | Overlay::SlashComplete(_))
+    }
+
     pub fn as_file_picker_mut(&mut self) -> Option<&mut FilePickerState> {
         match self {
             Overlay::FilePicker(p) => Some(p),
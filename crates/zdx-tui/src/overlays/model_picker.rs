@@ -13,6 +13,7 @@ use zdx_engine::models::{
 use zdx_engine::providers::{ProviderKind, resolve_provider};
 
 use super::OverlayUpdate;
+use crate::common::i18n::{Text, tr};
 use crate::effects::UiEffect;
 use crate::mutations::{ConfigMutation, StateMutation, TranscriptMutation};
 use crate::state::TuiState;
@@ -101,57 +102,7 @@ impl ModelPickerState {
                 let Some(model) = self.filtered_models().get(self.selected).copied() else {
                     return OverlayUpdate::close();
                 };
-
-                // Include provider prefix so we don't rely on auto-detection
-                let model_id = format!("{}:{}", model.provider, model.id);
-                let display_name = model_label(model);
-
-                let root = tui.agent_opts.root.clone();
-                let use_thread_override = tui.thread.thread_handle.is_some()
-                    && (tui.thread.model_override.is_some()
-                        || tui.thread.thinking_override.is_some());
-                if use_thread_override && tui.agent_state.is_running() {
-                    return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
-                        TranscriptMutation::AppendSystemMessage(
-                            "Stop the current task first before changing this thread's model override."
-                                .to_string(),
-                        ),
-                    )]);
-                }
-                OverlayUpdate::close()
-                    .with_ui_effects(vec![
-                        if use_thread_override {
-                            UiEffect::PersistThreadModelOverride {
-                                model: model_id.clone(),
-                            }
-                        } else {
-                            UiEffect::PersistModel {
-                                model: model_id.clone(),
-                            }
-                        },
-                        UiEffect::RefreshSystemPrompt { path: root },
-                    ])
-                    .with_mutations(vec![
-                        if use_thread_override {
-                            StateMutation::Thread(crate::mutations::ThreadMutation::SetOverrides {
-                                model_override: Some(model_id.clone()),
-                                thinking_override: tui.thread.thinking_override,
-                            })
-                        } else {
-                            StateMutation::Config(ConfigMutation::SetModel(model_id.clone()))
-                        },
-                        StateMutation::SetActiveThreadOverrides {
-                            model_override: if use_thread_override {
-                                Some(model_id)
-                            } else {
-                                tui.thread.model_override.clone()
-                            },
-                            thinking_override: tui.thread.thinking_override,
-                        },
-                        StateMutation::Transcript(TranscriptMutation::AppendOrReplaceSwitchNotice(
-                            format!("Switched to {display_name}"),
-                        )),
-                    ])
+                select_model(tui, model)
             }
             // Ctrl+U (or Command+Backspace on macOS): clear the current line
             KeyCode::Char('u') if ctrl && !shift && !alt => {
@@ -196,6 +147,71 @@ impl ModelPickerState {
     }
 }
 
+/// Models the picker offers: registry and custom-provider models from enabled
+/// providers, narrowed by each provider's `models` patterns.
+pub(crate) fn enabled_models(providers: &ProvidersConfig) -> Vec<&'static ModelOption> {
+    let enabled_providers = collect_enabled_providers(providers);
+    available_models()
+        .iter()
+        .chain(custom_provider_models(providers).iter())
+        .filter(|m| model_passes_provider_filter(m, &enabled_providers))
+        .collect()
+}
+
+/// Switches to `model`: the thread's model override when the thread already
+/// has overrides, otherwise the global model.
+pub(crate) fn select_model(tui: &TuiState, model: &ModelOption) -> OverlayUpdate {
+    // Include provider prefix so we don't rely on auto-detection
+    let model_id = format!("{}:{}", model.provider, model.id);
+    let display_name = model_label(model);
+
+    let root = tui.agent_opts.root.clone();
+    let use_thread_override = tui.thread.thread_handle.is_some()
+        && (tui.thread.model_override.is_some() || tui.thread.thinking_override.is_some());
+    if use_thread_override && tui.agent_state.is_running() {
+        return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(
+                "Stop the current task first before changing this thread's model override."
+                    .to_string(),
+            ),
+        )]);
+    }
+    OverlayUpdate::close()
+        .with_ui_effects(vec![
+            if use_thread_override {
+                UiEffect::PersistThreadModelOverride {
+                    model: model_id.clone(),
+                }
+            } else {
+                UiEffect::PersistModel {
+                    model: model_id.clone(),
+                }
+            },
+            UiEffect::RefreshSystemPrompt { path: root },
+        ])
+        .with_mutations(vec![
+            if use_thread_override {
+                StateMutation::Thread(crate::mutations::ThreadMutation::SetOverrides {
+                    model_override: Some(model_id.clone()),
+                    thinking_override: tui.thread.thinking_override,
+                })
+            } else {
+                StateMutation::Config(ConfigMutation::SetModel(model_id.clone()))
+            },
+            StateMutation::SetActiveThreadOverrides {
+                model_override: if use_thread_override {
+                    Some(model_id)
+                } else {
+                    tui.thread.model_override.clone()
+                },
+                thinking_override: tui.thread.thinking_override,
+            },
+            StateMutation::Transcript(TranscriptMutation::AppendOrReplaceSwitchNotice(format!(
+                "Switched to {display_name}"
+            ))),
+        ])
+}
+
 pub fn render_model_picker(
     frame: &mut Frame,
     picker: &ModelPickerState,
@@ -231,7 +247,7 @@ pub fn render_model_picker(
         area,
         input_top_y,
         &OverlayConfig {
-            title: "Select Model",
+            title: tr(Text::SelectModelTitle),
             border_color: Color::Magenta,
             width: picker_width,
             height: picker_height,
@@ -297,7 +313,7 @@ pub fn render_model_picker(
     render_capabilities_line(frame, layout.body, 3 + list_height, selected_model);
 }
 
-fn model_label(model: &ModelOption) -> String {
+pub(crate) fn model_label(model: &ModelOption) -> String {
     let label = provider_label(model.provider);
     let name = cleaned_display_name(model, model.provider);
     format!("{label} · {name}")
@@ -481,7 +497,7 @@ fn trim_price(value: f64) -> String {
     text
 }
 
-fn model_matches_filter(model: &ModelOption, filter: &str) -> bool {
+pub(crate) fn model_matches_filter(model: &ModelOption, filter: &str) -> bool {
     let filter = filter.to_lowercase();
     if filter.is_empty() {
         return true;
@@ -0,0 +1,267 @@
+//! Sampling controls overlay (`/params`).
+//!
+//! Edits `temperature`, `top_p`, and `max_output_tokens` for the current
+//! session on top of the `[model_params]` config section. An empty value keeps
+//! the provider default.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use zdx_engine::config::ModelParams;
+use zdx_engine::providers::provider_for_model;
+
+use super::OverlayUpdate;
+use crate::common::i18n::{Text, tr};
+use crate::mutations::{ConfigMutation, StateMutation, TranscriptMutation};
+use crate::state::TuiState;
+
+const FIELDS: [&str; 3] = ["temperature", "top_p", "max_output_tokens"];
+const MAX_OUTPUT_TOKENS: usize = 2;
+
+#[derive(Debug, Clone)]
+pub struct ParamsState {
+    /// Typed value per entry in `FIELDS`; empty means provider default.
+    pub values: [String; 3],
+    pub selected: usize,
+    /// Validation error from the last Enter.
+    pub error: Option<String>,
+}
+
+impl ParamsState {
+    pub fn open(current: ModelParams) -> Self {
+        Self {
+            values: [
+                current
+                    .temperature
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+                current.top_p.map(|v| v.to_string()).unwrap_or_default(),
+                current
+                    .max_output_tokens
+                    .map(|v| v.to_string())
+                    .unwrap_or_default(),
+            ],
+            selected: 0,
+            error: None,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, input_y: u16) {
+        render_params(frame, self, area, input_y);
+    }
+
+    pub fn handle_key(&mut self, tui: &TuiState, key: KeyEvent) -> OverlayUpdate {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        if !matches!(key.code, KeyCode::Enter | KeyCode::Esc) {
+            self.error = None;
+        }
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('c') if key.code == KeyCode::Esc || ctrl => {
+                OverlayUpdate::close()
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                self.selected = self.selected.saturating_sub(1);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Down | KeyCode::Tab => {
+                if self.selected + 1 < FIELDS.len() {
+                    self.selected += 1;
+                }
+                OverlayUpdate::stay()
+            }
+            KeyCode::Backspace => {
+                self.values[self.selected].pop();
+                OverlayUpdate::stay()
+            }
+            KeyCode::Char(c)
+                if !ctrl
+                    && (c.is_ascii_digit() || (c == '.' && self.selected != MAX_OUTPUT_TOKENS)) =>
+            {
+                self.values[self.selected].push(c);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Enter => match self.parse() {
+                Ok(params) => apply_params(tui, params),
+                Err(error) => {
+                    self.error = Some(error);
+                    OverlayUpdate::stay()
+                }
+            },
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    /// Parses and range-checks the typed values.
+    fn parse(&self) -> Result<ModelParams, String> {
+        fn field<T>(name: &str, value: &str) -> Result<Option<T>, String>
+        where
+            T: std::str::FromStr,
+            T::Err: std::fmt::Display,
+        {
+            let value = value.trim();
+            if value.is_empty() {
+                return Ok(None);
+            }
+            value
+                .parse()
+                .map(Some)
+                .map_err(|err| format!("{name}: '{value}' is not a valid number ({err})"))
+        }
+
+        let params = ModelParams {
+            temperature: field(FIELDS[0], &self.values[0])?,
+            top_p: field(FIELDS[1], &self.values[1])?,
+            max_output_tokens: field(FIELDS[2], &self.values[2])?,
+        };
+        params.validate()?;
+        Ok(params)
+    }
+}
+
+fn apply_params(tui: &TuiState, params: ModelParams) -> OverlayUpdate {
+    let summary = params.summary();
+    let thinking_enabled = zdx_engine::models::model_supports_reasoning(&tui.config.model)
+        && tui.config.thinking_level.is_enabled();
+    let provider = provider_for_model(&tui.config.model);
+    let message = if (params.temperature.is_some() || params.top_p.is_some())
+        && !provider.accepts_sampling(thinking_enabled)
+    {
+        format!(
+            "Model params: {summary} (temperature/top_p are not sent to {} with the current model settings)",
+            provider.id()
+        )
+    } else {
+        format!("Model params: {summary}")
+    };
+    OverlayUpdate::close().with_mutations(vec![
+        StateMutation::Config(ConfigMutation::SetModelParams(params)),
+        StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(message)),
+    ])
+}
+
+fn render_params(frame: &mut Frame, state: &ParamsState, area: Rect, input_top_y: u16) {
+    use super::render_utils::{InputHint, OverlayConfig, render_overlay, render_separator};
+
+    let overlay_width = 50;
+    let overlay_height = FIELDS.len() as u16 + 6;
+
+    let hints = [
+        InputHint::new("↑↓", "navigate"),
+        InputHint::new("Enter", "apply"),
+        InputHint::new("Esc", "cancel"),
+    ];
+    let layout = render_overlay(
+        frame,
+        area,
+        input_top_y,
+        &OverlayConfig {
+            title: tr(Text::ModelParamsTitle),
+            border_color: Color::Magenta,
+            width: overlay_width,
+            height: overlay_height,
+            hints: &hints,
+        },
+    );
+
+    let name_width = FIELDS.iter().map(|name| name.len()).max().unwrap_or(0);
+    for (row, (name, value)) in FIELDS.iter().zip(&state.values).enumerate() {
+        let selected = row == state.selected;
+        let marker = if selected { "▶ " } else { "  " };
+        let name_style = if selected {
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        let mut spans = vec![
+            Span::raw(marker),
+            Span::styled(format!("{name:<name_width$}  "), name_style),
+        ];
+        if value.is_empty() && !selected {
+            spans.push(Span::styled(
+                "default",
+                Style::default().fg(Color::DarkGray),
+            ));
+        } else {
+            spans.push(Span::styled(
+                value.clone(),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        if selected {
+            spans.push(Span::styled("█", Style::default().fg(Color::Yellow)));
+        }
+        let row_area = Rect::new(
+            layout.body.x,
+            layout.body.y + row as u16,
+            layout.body.width,
+            1,
+        );
+        frame.render_widget(Paragraph::new(Line::from(spans)), row_area);
+    }
+
+    let rows = FIELDS.len() as u16;
+    render_separator(frame, layout.body, rows);
+
+    let (help_text, help_style) = match &state.error {
+        Some(error) => (error.as_str(), Style::default().fg(Color::Red)),
+        None => (
+            "Empty keeps the provider default",
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    let help_area = Rect::new(
+        layout.body.x,
+        layout.body.y + rows + 1,
+        layout.body.width,
+        1,
+    );
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(help_text, help_style))),
+        help_area,
+    );
+
+    render_separator(frame, layout.body, rows + 2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(values: [&str; 3]) -> ParamsState {
+        ParamsState {
+            values: values.map(str::to_string),
+            selected: 0,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_parse_keeps_empty_fields_unset() {
+        let params = state(["0.4", "", "4096"]).parse().unwrap();
+        assert_eq!(params.temperature, Some(0.4));
+        assert_eq!(params.top_p, None);
+        assert_eq!(params.max_output_tokens, Some(4096));
+
+        let reopened = ParamsState::open(params);
+        assert_eq!(reopened.values, ["0.4", "", "4096"].map(str::to_string));
+    }
+
+    #[test]
+    fn test_parse_rejects_invalid_values() {
+        assert!(state(["1..2", "", ""]).parse().is_err());
+        assert!(
+            state(["", "1.5", ""])
+                .parse()
+                .unwrap_err()
+                .contains("top_p")
+        );
+        assert!(state(["", "", "0"]).parse().is_err());
+    }
+}
@@ -0,0 +1,309 @@
+//! Recent-thread quick switcher (Ctrl+P).
+//!
+//! A compact, recency-ordered list of the last few threads (the current one
+//! excluded), so Ctrl+P, Enter bounces back to the previous conversation.
+//! Typing fuzzy-filters the list; the full picker (`/threads`) stays the place
+//! for browsing, previews, and bulk actions.
+
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState, Paragraph};
+use zdx_engine::core::thread_persistence::{self, ThreadSummary};
+
+use super::OverlayUpdate;
+use super::thread_picker::thread_fuzzy_score;
+use crate::common::i18n::{Text, tr};
+use crate::common::{TaskKind, truncate_with_ellipsis};
+use crate::effects::UiEffect;
+use crate::mutations::{StateMutation, TranscriptMutation};
+use crate::state::TuiState;
+
+/// Threads listed by the switcher.
+pub const QUICK_SWITCH_LIMIT: usize = 10;
+const WIDTH: u16 = 64;
+
+#[derive(Debug, Clone)]
+pub struct QuickSwitchState {
+    threads: Vec<ThreadSummary>,
+    active_thread_ids: HashSet<String>,
+    pub filter: String,
+    pub selected: usize,
+}
+
+impl QuickSwitchState {
+    /// Keeps the `QUICK_SWITCH_LIMIT` most recent threads (`threads` is
+    /// newest first), leaving out `current_thread_id`.
+    pub fn open(
+        threads: Vec<ThreadSummary>,
+        active_thread_ids: HashSet<String>,
+        current_thread_id: Option<&str>,
+    ) -> Self {
+        let threads = threads
+            .into_iter()
+            .filter(|thread| Some(thread.id.as_str()) != current_thread_id)
+            .take(QUICK_SWITCH_LIMIT)
+            .collect();
+        Self {
+            threads,
+            active_thread_ids,
+            filter: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Threads matching the filter, best match first (recency order when the
+    /// filter is empty).
+    pub fn visible_threads(&self) -> Vec<&ThreadSummary> {
+        if self.filter.is_empty() {
+            return self.threads.iter().collect();
+        }
+        let mut ranked: Vec<_> = self
+            .threads
+            .iter()
+            .filter_map(|thread| {
+                thread_fuzzy_score(thread, &self.filter).map(|score| (thread, score))
+            })
+            .collect();
+        // Stable sort keeps recency order among equal scores.
+        ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+        ranked.into_iter().map(|(thread, _)| thread).collect()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, input_y: u16) {
+        render_quick_switch(frame, self, area, input_y);
+    }
+
+    pub fn handle_key(&mut self, tui: &TuiState, key: KeyEvent) -> OverlayUpdate {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => OverlayUpdate::close(),
+            KeyCode::Char('c') if ctrl => OverlayUpdate::close(),
+            KeyCode::Up => {
+                self.move_selection(-1);
+                OverlayUpdate::stay()
+            }
+            // Ctrl+P again steps down, like cycling recent files.
+            KeyCode::Down | KeyCode::Tab => {
+                self.move_selection(1);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Char('p' | 'n') if ctrl => {
+                self.move_selection(1);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Enter => self.switch_to_selected(tui),
+            KeyCode::Backspace => {
+                self.filter.pop();
+                self.selected = 0;
+                OverlayUpdate::stay()
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.filter.push(c);
+                self.selected = 0;
+                OverlayUpdate::stay()
+            }
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    /// Moves the selection, wrapping at both ends.
+    fn move_selection(&mut self, delta: isize) {
+        let count = self.visible_threads().len();
+        if count == 0 {
+            return;
+        }
+        self.selected =
+            (self.selected.cast_signed() + delta).rem_euclid(count.cast_signed()) as usize;
+    }
+
+    fn switch_to_selected(&self, tui: &TuiState) -> OverlayUpdate {
+        let visible = self.visible_threads();
+        let Some(thread) = visible.get(self.selected) else {
+            return OverlayUpdate::stay();
+        };
+        switch_to_thread(tui, &thread.id, &self.active_thread_ids)
+    }
+}
+
+/// Loads `thread_id`, unless a turn is running here or the thread is running
+/// in another tab.
+pub(crate) fn switch_to_thread(
+    tui: &TuiState,
+    thread_id: &str,
+    active_thread_ids: &HashSet<String>,
+) -> OverlayUpdate {
+    if tui.agent_state.is_running() {
+        return OverlayUpdate::close().with_mutations(vec![StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(tr(Text::StopCurrentTaskFirst).to_string()),
+        )]);
+    }
+    if active_thread_ids.contains(thread_id) {
+        return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(
+                "This thread is still running in the background. Wait for it to finish before opening it.".to_string(),
+            ),
+        )]);
+    }
+    if tui.tasks.state(TaskKind::ThreadLoad).is_running() {
+        return OverlayUpdate::stay();
+    }
+    OverlayUpdate::close().with_ui_effects(vec![UiEffect::LoadThread {
+        thread_id: thread_id.to_string(),
+    }])
+}
+
+fn render_quick_switch(frame: &mut Frame, state: &QuickSwitchState, area: Rect, input_top_y: u16) {
+    use super::render_utils::{InputHint, OverlayConfig, render_overlay, render_separator};
+
+    let visible = state.visible_threads();
+    let rows = visible.len().max(1) as u16;
+    let hints = [
+        InputHint::new("Enter", "switch"),
+        InputHint::new("↑↓", "navigate"),
+        InputHint::new("Esc", "close"),
+    ];
+    let layout = render_overlay(
+        frame,
+        area,
+        input_top_y,
+        &OverlayConfig {
+            title: "Recent threads",
+            border_color: Color::Magenta,
+            width: WIDTH,
+            height: rows + 7,
+            hints: &hints,
+        },
+    );
+    let body = layout.body;
+
+    let filter_line = Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::DarkGray)),
+        Span::styled(state.filter.clone(), Style::default().fg(Color::Magenta)),
+        Span::styled("█", Style::default().fg(Color::Magenta)),
+    ]);
+    frame.render_widget(
+        Paragraph::new(filter_line),
+        Rect::new(body.x, body.y, body.width, 1),
+    );
+    render_separator(frame, body, 1);
+
+    let list_height = body.height.saturating_sub(3);
+    let list_area = Rect::new(body.x, body.y + 2, body.width, list_height);
+    if visible.is_empty() {
+        let message = if state.filter.is_empty() {
+            "No other threads yet"
+        } else {
+            "No matching threads"
+        };
+        frame.render_widget(
+            Paragraph::new(Line::styled(message, Style::default().fg(Color::DarkGray))),
+            list_area,
+        );
+    } else {
+        let width = body.width.saturating_sub(2) as usize;
+        let items: Vec<ListItem> = visible
+            .iter()
+            .map(|thread| {
+                ListItem::new(thread_line(
+                    thread,
+                    state.active_thread_ids.contains(&thread.id),
+                    width,
+                ))
+            })
+            .collect();
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Magenta)
+                    .fg(Color::Black)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▶ ");
+        let mut list_state = ListState::default();
+        list_state.select(Some(state.selected));
+        frame.render_stateful_widget(list, list_area, &mut list_state);
+    }
+    render_separator(frame, body, 2 + list_height);
+}
+
+/// `title … age` with the age right-aligned; running threads get a marker.
+fn thread_line(thread: &ThreadSummary, running: bool, width: usize) -> Line<'static> {
+    let age = thread
+        .modified
+        .and_then(thread_persistence::format_timestamp_relative)
+        .unwrap_or_default();
+    let marker = if running { "● " } else { "" };
+    let title_width = width.saturating_sub(age.chars().count() + marker.chars().count() + 2);
+    let title = truncate_with_ellipsis(&thread.display_title(), title_width);
+    let pad = width
+        .saturating_sub(marker.chars().count() + title.chars().count() + age.chars().count())
+        .max(1);
+    Line::from(vec![
+        Span::styled(marker, Style::default().fg(Color::Yellow)),
+        Span::raw(title),
+        Span::raw(" ".repeat(pad)),
+        Span::styled(age, Style::default().fg(Color::DarkGray)),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn thread(id: &str, title: &str) -> ThreadSummary {
+        ThreadSummary {
+            id: id.to_string(),
+            title: Some(title.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn recent(count: usize) -> Vec<ThreadSummary> {
+        (0..count)
+            .map(|i| thread(&format!("t{i}"), &format!("Thread {i}")))
+            .collect()
+    }
+
+    #[test]
+    fn test_open_skips_current_and_caps_list() {
+        let state = QuickSwitchState::open(recent(15), HashSet::new(), Some("t0"));
+        let ids: Vec<_> = state
+            .visible_threads()
+            .iter()
+            .map(|t| t.id.clone())
+            .collect();
+        assert_eq!(ids.len(), QUICK_SWITCH_LIMIT);
+        assert_eq!(ids[0], "t1", "the previous thread is preselected");
+    }
+
+    #[test]
+    fn test_filter_and_wrapping_selection() {
+        let mut state = QuickSwitchState::open(
+            vec![
+                thread("a", "Fix login bug"),
+                thread("b", "Refactor parser"),
+                thread("c", "Parser benchmarks"),
+            ],
+            HashSet::new(),
+            None,
+        );
+        state.move_selection(-1);
+        assert_eq!(state.selected, 2);
+        state.move_selection(1);
+        assert_eq!(state.selected, 0);
+
+        state.filter = "parser".to_string();
+        let ids: Vec<_> = state
+            .visible_threads()
+            .iter()
+            .map(|t| t.id.clone())
+            .collect();
+        assert_eq!(ids.len(), 2);
+        assert!(!ids.contains(&"a".to_string()));
+    }
+}
@@ -9,6 +9,7 @@ use ratatui::widgets::Paragraph;
 
 use super::OverlayUpdate;
 use crate::common::TaskKind;
+use crate::common::i18n::{Text, tr};
 use crate::effects::UiEffect;
 use crate::state::TuiState;
 
@@ -103,7 +104,7 @@ fn render_rename_overlay(frame: &mut Frame, state: &RenameState, area: Rect, inp
         area,
         input_top_y,
         &OverlayConfig {
-            title: "Rename Thread",
+            title: tr(Text::RenameThreadTitle),
             border_color: Color::Yellow,
             width: overlay_width,
             height: overlay_height,
@@ -3,6 +3,7 @@ use ratatui::layout::{Alignment, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+use unicode_width::UnicodeWidthStr;
 
 use crate::common::truncate_start_with_ellipsis;
 
@@ -33,6 +34,17 @@ pub fn render_overlay_container(frame: &mut Frame, area: Rect, title: &str, bord
     frame.render_widget(block, area);
 }
 
+/// Rows needed to show `text` wrapped at `width` columns (an estimate for
+/// sizing overlays around a wrapped `Paragraph`).
+pub fn wrapped_rows(text: &str, width: u16) -> u16 {
+    let width = usize::from(width.max(1));
+    let rows: usize = text
+        .lines()
+        .map(|line| line.width().div_ceil(width).max(1))
+        .sum();
+    u16::try_from(rows.max(1)).unwrap_or(u16::MAX)
+}
+
 /// Clears characters from the end of the string back to the previous word boundary.
 pub fn clear_word_left(input: &mut String) {
     let trimmed_len = input.trim_end().len();
@@ -16,6 +16,7 @@ use ratatui::text::{Line, Span};
 use ratatui::widgets::{List, ListItem, ListState, Paragraph, Wrap};
 
 use super::OverlayUpdate;
+use crate::common::i18n::{Text, tr};
 use crate::effects::UiEffect;
 use crate::mutations::StateMutation;
 use crate::state::TuiState;
@@ -576,7 +577,7 @@ fn render_skill_list(frame: &mut Frame, picker: &SkillPickerState, area: Rect, i
         area,
         input_top_y,
         &OverlayConfig {
-            title: "Install Skill",
+            title: tr(Text::InstallSkillTitle),
             border_color: Color::Magenta,
             width: picker_width,
             height: picker_height,
@@ -706,7 +707,7 @@ fn render_skill_loaded(frame: &mut Frame, picker: &SkillPickerState, area: Rect,
         area,
         input_top_y,
         &OverlayConfig {
-            title: "Loaded Skills",
+            title: tr(Text::LoadedSkillsTitle),
             border_color: Color::Magenta,
             width: picker_width,
             height: picker_height,
@@ -880,7 +881,7 @@ fn render_skill_detail(
         area,
         input_top_y,
         &OverlayConfig {
-            title: "Skill Details",
+            title: tr(Text::SkillDetailsTitle),
             border_color: Color::Magenta,
             width: detail_width,
             height: detail_height,
@@ -0,0 +1,681 @@
+//! Inline slash-command completion.
+//!
+//! Opened when `/` is typed into an empty composer. Unlike the command
+//! palette, keystrokes keep going into the input (the reducer routes them
+//! there, as for the `@` file picker) and the popup follows the text:
+//! `/mo` lists matching commands with their arguments and descriptions, and
+//! `/model ` switches to completing the argument (model ids, thinking levels,
+//! thread titles, style names). Tab completes; Enter runs the selection.
+
+use std::collections::HashSet;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph};
+use zdx_engine::config::{Config, ThinkingLevel};
+use zdx_engine::core::response_style;
+use zdx_engine::core::thread_persistence::{self, ThreadSummary};
+use zdx_engine::custom_commands::CustomCommand;
+use zdx_engine::models::ModelOption;
+
+use super::OverlayUpdate;
+use super::command_palette::execute_command;
+use super::model_picker::{enabled_models, model_label, model_matches_filter, select_model};
+use super::quick_switch::switch_to_thread;
+use super::thinking_picker::select_thinking_level;
+use super::thread_picker::thread_fuzzy_score;
+use crate::common::commands::{COMMANDS, Command, command_available};
+use crate::common::truncate_with_ellipsis;
+use crate::effects::UiEffect;
+use crate::input::InputState;
+use crate::mutations::{InputMutation, StateMutation};
+use crate::state::TuiState;
+
+const MAX_VISIBLE_ROWS: usize = 8;
+const MAX_WIDTH: u16 = 90;
+/// Commands whose arguments are parsed when the input is submitted, so Enter
+/// on `/<name> <args>` goes through normal submission.
+const SUBMIT_PARSED: &[&str] = &[
+    "duplicate",
+    "pin",
+    "unpin",
+    "pin-file",
+    "unpin-file",
+    "style",
+    "compare",
+    "duel",
+    "redo",
+];
+
+/// Argument sources with enumerable values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgKind {
+    Model,
+    Thinking,
+    Thread,
+    Style,
+}
+
+fn arg_kind(command: &Command) -> Option<ArgKind> {
+    match command.name {
+        "model" => Some(ArgKind::Model),
+        "thinking" => Some(ArgKind::Thinking),
+        "threads" => Some(ArgKind::Thread),
+        "style" => Some(ArgKind::Style),
+        _ => None,
+    }
+}
+
+/// What the composer text is completing.
+#[derive(Debug, Clone, Copy)]
+enum Stage {
+    /// `/<partial name>`
+    Command,
+    /// `/<name> <partial argument>`
+    Argument(&'static Command),
+}
+
+#[derive(Debug, Clone)]
+enum Target {
+    Builtin(&'static Command),
+    Custom(usize),
+    Model(&'static ModelOption),
+    Thinking(ThinkingLevel),
+    Thread(String),
+    Style,
+}
+
+/// One completion row.
+#[derive(Debug, Clone)]
+pub struct Completion {
+    /// Command name, or argument value, as inserted into the input.
+    pub value: String,
+    /// Argument usage (command rows only).
+    pub args: Option<&'static str>,
+    pub description: String,
+    target: Target,
+}
+
+#[derive(Debug)]
+pub struct SlashCompleteState {
+    model_id: String,
+    custom_commands: Vec<CustomCommand>,
+    models: Vec<&'static ModelOption>,
+    styles: Vec<String>,
+    /// Loaded on first use of `/threads <title>`.
+    threads: Option<Vec<ThreadSummary>>,
+    active_thread_ids: HashSet<String>,
+    threads_requested: bool,
+    /// Composer text the completions were computed from.
+    text: String,
+    stage: Stage,
+    pub items: Vec<Completion>,
+    pub selected: usize,
+    pub offset: usize,
+}
+
+impl SlashCompleteState {
+    pub fn open(config: &Config, custom_commands: Vec<CustomCommand>) -> Self {
+        Self {
+            model_id: config.model.clone(),
+            custom_commands,
+            models: enabled_models(&config.providers),
+            styles: response_style::names(config)
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+            threads: None,
+            active_thread_ids: HashSet::new(),
+            threads_requested: false,
+            text: String::new(),
+            stage: Stage::Command,
+            items: Vec::new(),
+            selected: 0,
+            offset: 0,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, input_y: u16) {
+        render_slash_complete(frame, self, area, input_y);
+    }
+
+    /// Keys that keep editing the composer while the popup is open.
+    pub fn should_route_input_key(key: KeyEvent) -> bool {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Tab | KeyCode::Up | KeyCode::Down => false,
+            KeyCode::Char('c' | 'p' | 'n') if ctrl => false,
+            _ => true,
+        }
+    }
+
+    /// Recomputes completions from the composer. Returns `None` when the text
+    /// no longer looks like a slash command (the popup should close), or the
+    /// effects needed to fill it (loading thread titles).
+    pub fn update_from_input(&mut self, input: &InputState) -> Option<Vec<UiEffect>> {
+        self.refresh(input.get_text())
+    }
+
+    fn refresh(&mut self, text: String) -> Option<Vec<UiEffect>> {
+        if text != self.text {
+            self.selected = 0;
+            self.offset = 0;
+        }
+        self.text = text;
+        let rest = self.text.strip_prefix('/')?;
+        if rest.contains('\n') {
+            return None;
+        }
+        match rest.split_once(char::is_whitespace) {
+            None => {
+                let filter = rest.to_string();
+                self.stage = Stage::Command;
+                self.items = self.command_items(&filter);
+                if self.items.is_empty() {
+                    return None;
+                }
+            }
+            Some((name, arg)) => {
+                let command = self.find_builtin(name)?;
+                self.stage = Stage::Argument(command);
+                self.items = self.argument_items(command, arg.trim_start());
+                if arg_kind(command) == Some(ArgKind::Thread) && !self.threads_requested {
+                    self.threads_requested = true;
+                    return Some(vec![UiEffect::ListThreadsForCompletion]);
+                }
+            }
+        }
+        self.selected = self.selected.min(self.items.len().saturating_sub(1));
+        Some(vec![])
+    }
+
+    /// Fills thread-title completions once the list has loaded.
+    pub fn set_threads(&mut self, threads: Vec<ThreadSummary>, active_thread_ids: HashSet<String>) {
+        self.threads = Some(threads);
+        self.active_thread_ids = active_thread_ids;
+        let text = self.text.clone();
+        let _ = self.refresh(text);
+    }
+
+    /// Composer text to submit normally when Enter is pressed, for commands
+    /// whose arguments are parsed on submit (`/style concise`, `/pin 3`).
+    pub fn submit_text(&self) -> Option<String> {
+        let Stage::Argument(command) = self.stage else {
+            return None;
+        };
+        if !SUBMIT_PARSED.contains(&command.name) {
+            return None;
+        }
+        match self.items.get(self.selected) {
+            Some(item) => Some(self.argument_text(&item.value)),
+            None => Some(self.text.clone()),
+        }
+    }
+
+    pub fn handle_key(&mut self, tui: &TuiState, key: KeyEvent) -> OverlayUpdate {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => OverlayUpdate::close(),
+            KeyCode::Char('c') if ctrl => OverlayUpdate::close(),
+            KeyCode::Up => {
+                self.move_selection(false);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Char('p') if ctrl => {
+                self.move_selection(false);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Down => {
+                self.move_selection(true);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Char('n') if ctrl => {
+                self.move_selection(true);
+                OverlayUpdate::stay()
+            }
+            KeyCode::Tab => self.complete(),
+            KeyCode::Enter => self.accept(tui),
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    fn move_selection(&mut self, down: bool) {
+        if down {
+            if self.selected + 1 < self.items.len() {
+                self.selected += 1;
+            }
+        } else {
+            self.selected = self.selected.saturating_sub(1);
+        }
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if self.selected >= self.offset + MAX_VISIBLE_ROWS {
+            self.offset = self.selected + 1 - MAX_VISIBLE_ROWS;
+        }
+    }
+
+    /// Tab: completes the selected command name (plus a space when it takes
+    /// arguments) or argument value.
+    fn complete(&mut self) -> OverlayUpdate {
+        let Some(item) = self.items.get(self.selected) else {
+            return OverlayUpdate::stay();
+        };
+        let text = match self.stage {
+            Stage::Command if item.args.is_some() => format!("/{} ", item.value),
+            Stage::Command => format!("/{}", item.value),
+            Stage::Argument(_) => self.argument_text(&item.value),
+        };
+        self.set_text(text)
+    }
+
+    fn set_text(&mut self, text: String) -> OverlayUpdate {
+        let mutations = vec![StateMutation::Input(InputMutation::SetText(text.clone()))];
+        match self.refresh(text) {
+            Some(effects) => OverlayUpdate::stay()
+                .with_mutations(mutations)
+                .with_ui_effects(effects),
+            None => OverlayUpdate::close().with_mutations(mutations),
+        }
+    }
+
+    /// Enter: runs the selected command, or applies the selected argument.
+    fn accept(&mut self, tui: &TuiState) -> OverlayUpdate {
+        let target = self
+            .items
+            .get(self.selected)
+            .map(|item| item.target.clone());
+        match (self.stage, target) {
+            (Stage::Command, Some(Target::Builtin(command))) => {
+                // Required arguments: complete the name instead of running it.
+                if command.args.is_some_and(|args| args.starts_with('<')) {
+                    return self.set_text(format!("/{} ", command.name));
+                }
+                run_builtin(tui, command)
+            }
+            (Stage::Command, Some(Target::Custom(index))) => {
+                let content = self.custom_commands[index].content.clone();
+                OverlayUpdate::close()
+                    .with_mutations(vec![StateMutation::Input(InputMutation::SetText(content))])
+            }
+            (_, Some(Target::Model(model))) => with_cleared_input(select_model(tui, model)),
+            (_, Some(Target::Thinking(level))) => {
+                with_cleared_input(select_thinking_level(tui, level))
+            }
+            (_, Some(Target::Thread(thread_id))) => {
+                with_cleared_input(switch_to_thread(tui, &thread_id, &self.active_thread_ids))
+            }
+            (Stage::Argument(command), None) if command.args.is_none() => run_builtin(tui, command),
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    fn argument_text(&self, value: &str) -> String {
+        let name = self
+            .text
+            .trim_start_matches('/')
+            .split(char::is_whitespace)
+            .next()
+            .unwrap_or_default();
+        format!("/{name} {value}")
+    }
+
+    /// Resolves a typed name (or alias) to an available built-in command.
+    fn find_builtin(&self, name: &str) -> Option<&'static Command> {
+        COMMANDS.iter().find(|command| {
+            command_available(command, &self.model_id)
+                && (command.name == name || command.aliases.contains(&name))
+        })
+    }
+
+    /// Commands matching `filter`: exact name/alias first, then prefix, then
+    /// substring matches, each in palette order (built-ins before customs).
+    fn command_items(&self, filter: &str) -> Vec<Completion> {
+        let filter = filter.to_lowercase();
+        let rank = |names: &mut dyn Iterator<Item = &str>| {
+            names
+                .filter_map(|name| {
+                    let name = name.to_lowercase();
+                    if name == filter {
+                        Some(0)
+                    } else if name.starts_with(&filter) {
+                        Some(1)
+                    } else if name.contains(&filter) {
+                        Some(2)
+                    } else {
+                        None
+                    }
+                })
+                .min()
+        };
+
+        let builtins = COMMANDS
+            .iter()
+            .filter(|command| command_available(command, &self.model_id))
+            .filter_map(|command| {
+                let mut names =
+                    std::iter::once(command.name).chain(command.aliases.iter().copied());
+                rank(&mut names).map(|rank| {
+                    (
+                        rank,
+                        Completion {
+                            value: command.name.to_string(),
+                            args: command.args,
+                            description: command.description.to_string(),
+                            target: Target::Builtin(command),
+                        },
+                    )
+                })
+            });
+        let customs = self
+            .custom_commands
+            .iter()
+            .enumerate()
+            .filter_map(|(index, command)| {
+                rank(&mut std::iter::once(command.name.as_str())).map(|rank| {
+                    (
+                        rank,
+                        Completion {
+                            value: command.name.clone(),
+                            args: None,
+                            description: command
+                                .description
+                                .clone()
+                                .unwrap_or_else(|| "(custom)".to_string()),
+                            target: Target::Custom(index),
+                        },
+                    )
+                })
+            });
+
+        let mut ranked: Vec<_> = builtins.chain(customs).collect();
+        ranked.sort_by_key(|(rank, _)| *rank);
+        ranked.into_iter().map(|(_, item)| item).collect()
+    }
+
+    fn argument_items(&self, command: &Command, filter: &str) -> Vec<Completion> {
+        let lower = filter.to_lowercase();
+        let item = |value: &str, description: String, target: Target| Completion {
+            value: value.to_string(),
+            args: None,
+            description,
+            target,
+        };
+        match arg_kind(command) {
+            None => vec![],
+            Some(ArgKind::Model) => self
+                .models
+                .iter()
+                .filter(|model| model_matches_filter(model, filter))
+                .map(|model| item(model.id, model_label(model), Target::Model(model)))
+                .collect(),
+            Some(ArgKind::Thinking) => ThinkingLevel::all()
+                .iter()
+                .filter(|level| level.display_name().starts_with(&lower))
+                .map(|level| {
+                    item(
+                        level.display_name(),
+                        String::new(),
+                        Target::Thinking(*level),
+                    )
+                })
+                .collect(),
+            Some(ArgKind::Style) => self
+                .styles
+                .iter()
+                .map(String::as_str)
+                .chain(std::iter::once("off"))
+                .filter(|name| name.to_lowercase().starts_with(&lower))
+                .map(|name| {
+                    let description = if name == "off" {
+                        "Clear the thread's style".to_string()
+                    } else {
+                        String::new()
+                    };
+                    item(name, description, Target::Style)
+                })
+                .collect(),
+            Some(ArgKind::Thread) => {
+                let Some(threads) = &self.threads else {
+                    return vec![];
+                };
+                let mut ranked: Vec<_> = threads
+                    .iter()
+                    .filter_map(|thread| {
+                        thread_fuzzy_score(thread, filter).map(|score| (thread, score))
+                    })
+                    .collect();
+                // Stable sort keeps recency order among equal scores.
+                ranked.sort_by(|(_, a), (_, b)| b.cmp(a));
+                ranked
+                    .into_iter()
+                    .map(|(thread, _)| {
+                        let age = thread
+                            .modified
+                            .and_then(thread_persistence::format_timestamp_relative)
+                            .unwrap_or_default();
+                        item(
+                            &thread.display_title(),
+                            age,
+                            Target::Thread(thread.id.clone()),
+                        )
+                    })
+                    .collect()
+            }
+        }
+    }
+}
+
+/// Runs a built-in command the way the command palette does, clearing the
+/// typed `/name` first.
+fn run_builtin(tui: &TuiState, command: &Command) -> OverlayUpdate {
+    let (request, mut effects, mutations) = execute_command(tui, command.name);
+    effects.push(UiEffect::RecordCommandUse { name: command.name });
+    let update = match request {
+        Some(request) => OverlayUpdate::open(request),
+        None => OverlayUpdate::close(),
+    };
+    let mut all = vec![StateMutation::Input(InputMutation::Clear)];
+    all.extend(mutations);
+    update.with_ui_effects(effects).with_mutations(all)
+}
+
+/// Clears the typed command once a selection closes the popup.
+fn with_cleared_input(mut update: OverlayUpdate) -> OverlayUpdate {
+    if !matches!(update.transition, super::OverlayTransition::Stay) {
+        update
+            .mutations
+            .insert(0, StateMutation::Input(InputMutation::Clear));
+    }
+    update
+}
+
+#[allow(clippy::too_many_lines)]
+fn render_slash_complete(
+    frame: &mut Frame,
+    state: &SlashCompleteState,
+    area: Rect,
+    input_top_y: u16,
+) {
+    let help = match state.stage {
+        Stage::Argument(command) => Some(command),
+        Stage::Command => None,
+    };
+    let status = match state.stage {
+        Stage::Argument(command)
+            if arg_kind(command) == Some(ArgKind::Thread) && state.threads.is_none() =>
+        {
+            Some("Loading threads…")
+        }
+        Stage::Argument(command) if arg_kind(command).is_some() && state.items.is_empty() => {
+            Some("No matches")
+        }
+        _ => None,
+    };
+    let rows = state.items.len().min(MAX_VISIBLE_ROWS) + usize::from(status.is_some());
+    #[allow(clippy::cast_possible_truncation)]
+    let height = (rows + usize::from(help.is_some())) as u16 + 2;
+    let width = area.width.saturating_sub(4).min(MAX_WIDTH);
+    let popup = Rect::new(0, input_top_y.saturating_sub(height), width, height);
+
+    frame.render_widget(Clear, popup);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::DarkGray))
+        .title_bottom(Line::styled(
+            " Tab complete · Enter run · Esc close ",
+            Style::default().fg(Color::DarkGray),
+        ));
+    let inner = block.inner(popup);
+    frame.render_widget(block, popup);
+    if inner.height == 0 || inner.width == 0 {
+        return;
+    }
+
+    let mut y = inner.y;
+    if let Some(command) = help {
+        let usage = command
+            .args
+            .map(|args| format!(" {args}"))
+            .unwrap_or_default();
+        let line = Line::from(vec![
+            Span::styled(
+                format!("/{}{usage}", command.name),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                format!("  {}", command.description),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]);
+        frame.render_widget(Paragraph::new(line), Rect::new(inner.x, y, inner.width, 1));
+        y += 1;
+    }
+    if let Some(status) = status {
+        frame.render_widget(
+            Paragraph::new(Line::styled(status, Style::default().fg(Color::DarkGray))),
+            Rect::new(inner.x, y, inner.width, 1),
+        );
+        y += 1;
+    }
+
+    let list_area = Rect::new(inner.x, y, inner.width, inner.bottom().saturating_sub(y));
+    let is_command = matches!(state.stage, Stage::Command);
+    let name_width = state
+        .items
+        .iter()
+        .map(|item| {
+            let args = item.args.map_or(0, |args| args.chars().count() + 1);
+            item.value.chars().count() + usize::from(is_command) + args
+        })
+        .max()
+        .unwrap_or(0)
+        .min(usize::from(inner.width) / 2);
+    let items: Vec<ListItem> = state
+        .items
+        .iter()
+        .skip(state.offset)
+        .take(MAX_VISIBLE_ROWS)
+        .map(|item| {
+            let prefix = if is_command { "/" } else { "" };
+            let name = truncate_with_ellipsis(&format!("{prefix}{}", item.value), name_width);
+            let args = item
+                .args
+                .map(|args| format!(" {args}"))
+                .filter(|_| name.chars().count() < name_width)
+                .unwrap_or_default();
+            let pad = name_width.saturating_sub(name.chars().count() + args.chars().count());
+            let description_width = usize::from(inner.width).saturating_sub(name_width + 4);
+            ListItem::new(Line::from(vec![
+                Span::styled(name, Style::default().fg(Color::Cyan)),
+                Span::styled(args, Style::default().fg(Color::Yellow)),
+                Span::raw(" ".repeat(pad + 2)),
+                Span::styled(
+                    truncate_with_ellipsis(&item.description, description_width),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ]))
+        })
+        .collect();
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD),
+    );
+    let mut list_state = ListState::default();
+    if !state.items.is_empty() {
+        list_state.select(Some(state.selected.saturating_sub(state.offset)));
+    }
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state() -> SlashCompleteState {
+        SlashCompleteState::open(&Config::default(), vec![])
+    }
+
+    fn values(state: &SlashCompleteState) -> Vec<&str> {
+        state.items.iter().map(|item| item.value.as_str()).collect()
+    }
+
+    #[test]
+    fn test_command_stage_ranks_exact_and_prefix_first() {
+        let mut state = state();
+        assert!(state.refresh("/pin".to_string()).is_some());
+        assert_eq!(&values(&state)[..2], ["pin", "pin-file"]);
+        assert_eq!(state.items[1].args, Some("<path>"));
+
+        // Aliases resolve to their command.
+        state.refresh("/clear".to_string()).unwrap();
+        assert_eq!(values(&state)[0], "new");
+
+        // Paths and other non-commands close the popup.
+        assert!(state.refresh("/usr/bin/env".to_string()).is_none());
+        assert!(state.refresh("hello".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_argument_stage_completes_enums() {
+        let mut state = state();
+        state.refresh("/style con".to_string()).unwrap();
+        assert_eq!(values(&state), ["concise"]);
+        assert_eq!(
+            state.submit_text().as_deref(),
+            Some("/style concise"),
+            "style arguments are submitted through the normal path"
+        );
+
+        let update = state.complete();
+        assert!(matches!(
+            update.transition,
+            super::super::OverlayTransition::Stay
+        ));
+        assert_eq!(state.text, "/style concise");
+
+        // Thread titles load lazily, once.
+        let effects = state.refresh("/threads ".to_string()).unwrap();
+        assert!(matches!(effects[..], [UiEffect::ListThreadsForCompletion]));
+        assert!(state.refresh("/threads x".to_string()).unwrap().is_empty());
+        state.set_threads(
+            vec![ThreadSummary {
+                id: "t1".to_string(),
+                title: Some("Fix parser".to_string()),
+                ..Default::default()
+            }],
+            HashSet::new(),
+        );
+        state.refresh("/threads pars".to_string()).unwrap();
+        assert_eq!(values(&state), ["Fix parser"]);
+        assert!(state.submit_text().is_none());
+
+        // Commands without enumerable arguments only show their usage line.
+        state.refresh("/pin-file src/".to_string()).unwrap();
+        assert!(state.items.is_empty());
+        assert_eq!(state.submit_text().as_deref(), Some("/pin-file src/"));
+    }
+}
@@ -0,0 +1,501 @@
+//! Thread statistics overlay (`/stats`).
+//!
+//! Summarizes the current thread as scrollable markdown: turns, tool calls by
+//! tool, tokens and cost per model, wall-clock time, files touched, and the
+//! largest context contributors, and transcript memory. Token usage comes from the thread's saved
+//! usage events when it is persisted, otherwise from the session totals.
+
+use std::cell::Cell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write as _;
+use std::time::Duration;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use serde_json::Value;
+use zdx_engine::core::usage_stats::{self, UsageRow};
+use zdx_engine::models::ModelOption;
+use zdx_engine::providers::{ChatContentBlock, ChatMessage, MessageContent};
+use zdx_transcript::{WrapCacheStats, format_span_duration, turn_duration};
+
+use super::OverlayUpdate;
+use super::render_utils::centered_rect;
+use crate::state::TuiState;
+use crate::thread::ThreadUsage;
+use crate::transcript::markdown::render_markdown;
+use crate::transcript::{HistoryCell, ToolState, collect_file_changes, convert_styled_line};
+
+/// How many context contributors are listed.
+const TOP_CONTRIBUTORS: usize = 5;
+/// Longest input summary shown next to a tool result.
+const SUMMARY_MAX_CHARS: usize = 48;
+
+#[derive(Debug)]
+pub struct StatsState {
+    markdown: String,
+    scroll_offset: Cell<usize>,
+}
+
+impl StatsState {
+    pub fn open(tui: &TuiState) -> Self {
+        let usage = tui.thread.thread_handle.as_ref().and_then(|handle| {
+            usage_stats::thread_usage_stats(&handle.id, &tui.config.model)
+                .inspect_err(|err| tracing::warn!(%err, "Failed to load thread usage"))
+                .ok()
+        });
+        let rows = match usage {
+            Some(stats) if !stats.by_model.is_empty() => stats.by_model,
+            _ => session_usage_rows(&tui.thread.usage, &tui.config.model),
+        };
+        Self {
+            markdown: build_report(
+                tui.transcript.cells(),
+                &tui.thread.messages,
+                &rows,
+                tui.transcript.wrap_cache.stats(),
+            ),
+            scroll_offset: Cell::new(0),
+        }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> OverlayUpdate {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('q') => OverlayUpdate::close(),
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.scroll_offset
+                    .set(self.scroll_offset.get().saturating_add(1));
+                OverlayUpdate::stay()
+            }
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.scroll_offset
+                    .set(self.scroll_offset.get().saturating_sub(1));
+                OverlayUpdate::stay()
+            }
+            KeyCode::PageDown => {
+                self.scroll_offset
+                    .set(self.scroll_offset.get().saturating_add(10));
+                OverlayUpdate::stay()
+            }
+            KeyCode::PageUp => {
+                self.scroll_offset
+                    .set(self.scroll_offset.get().saturating_sub(10));
+                OverlayUpdate::stay()
+            }
+            KeyCode::Home | KeyCode::Char('g') => {
+                self.scroll_offset.set(0);
+                OverlayUpdate::stay()
+            }
+            KeyCode::End | KeyCode::Char('G') => {
+                self.scroll_offset.set(usize::MAX);
+                OverlayUpdate::stay()
+            }
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, _input_y: u16) {
+        let popup_area = centered_rect(80, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let tmp_block = Block::default().borders(Borders::ALL);
+        let inner = tmp_block.inner(popup_area);
+        if inner.height == 0 || inner.width == 0 {
+            return;
+        }
+        let body_width = inner.width as usize;
+
+        let lines: Vec<Line<'static>> = render_markdown(&self.markdown, body_width)
+            .iter()
+            .map(convert_styled_line)
+            .collect();
+
+        let viewport_height = inner.height as usize;
+        let wrapped_total: usize = lines
+            .iter()
+            .map(|line| line.width().div_ceil(body_width.max(1)).max(1))
+            .sum();
+        let max_scroll = wrapped_total.saturating_sub(viewport_height);
+        let clamped = self.scroll_offset.get().min(max_scroll);
+        self.scroll_offset.set(clamped);
+
+        let scroll_indicator = if wrapped_total > viewport_height {
+            format!(" [{}/{wrapped_total}] ", clamped + 1)
+        } else {
+            String::new()
+        };
+
+        let border_color = Color::Cyan;
+        let block = Block::default()
+            .title(" Thread stats ")
+            .title_style(
+                Style::default()
+                    .fg(border_color)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title_bottom(Line::from(vec![
+                Span::styled(" [Esc/q]", Style::default().fg(Color::Yellow)),
+                Span::styled(" close  ", Style::default().fg(Color::DarkGray)),
+                Span::styled("[j/k]", Style::default().fg(Color::Yellow)),
+                Span::styled(" scroll  ", Style::default().fg(Color::DarkGray)),
+                Span::styled("[g/G]", Style::default().fg(Color::Yellow)),
+                Span::styled(" top/bottom ", Style::default().fg(Color::DarkGray)),
+                Span::styled(scroll_indicator, Style::default().fg(Color::Cyan)),
+            ]));
+
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let para = Paragraph::new(lines)
+            .scroll((clamped.min(u16::MAX as usize) as u16, 0))
+            .wrap(Wrap { trim: false });
+        frame.render_widget(para, inner);
+    }
+}
+
+/// Usage row for the current model built from the session's running totals,
+/// used when the thread has no saved usage events.
+fn session_usage_rows(usage: &ThreadUsage, model: &str) -> Vec<UsageRow> {
+    if usage.input_tokens + usage.output_tokens + usage.cache_read_tokens == 0 {
+        return Vec::new();
+    }
+    let pricing = ModelOption::find_by_id(model).map(|m| m.pricing);
+    let (provider, bare_model) = model.split_once(':').unwrap_or(("", model));
+    vec![UsageRow {
+        provider: provider.to_string(),
+        model: Some(bare_model.to_string()),
+        requests: 0,
+        input: usage.input_tokens,
+        output: usage.output_tokens,
+        cache_read: usage.cache_read_tokens,
+        cache_write: usage.cache_write_tokens,
+        cost_usd: pricing.as_ref().map_or(0.0, |p| usage.calculate_cost(p)),
+        subscription: false,
+        cost_known: pricing.is_some(),
+        estimated: true,
+    }]
+}
+
+/// Builds the markdown report shown by the overlay.
+fn build_report(
+    cells: &[HistoryCell],
+    messages: &[ChatMessage],
+    usage: &[UsageRow],
+    wrap_cache: WrapCacheStats,
+) -> String {
+    let mut out = String::from("# Thread stats\n\n");
+    write_overview(&mut out, cells);
+    write_tools(&mut out, cells);
+    write_usage(&mut out, usage);
+    write_files(&mut out, cells);
+    write_contributors(&mut out, cells, messages);
+    write_memory(&mut out, cells, wrap_cache);
+    out
+}
+
+fn write_overview(out: &mut String, cells: &[HistoryCell]) {
+    let turns: Vec<usize> = cells
+        .iter()
+        .enumerate()
+        .filter(|(_, cell)| matches!(cell, HistoryCell::User { .. }))
+        .map(|(idx, _)| idx)
+        .collect();
+    let _ = writeln!(out, "- **Turns:** {}", turns.len());
+
+    let wall_clock = cells.first().and_then(|first| {
+        let end = cells.iter().map(HistoryCell::finished_at).max()?;
+        (end - first.created_at()).to_std().ok()
+    });
+    if let Some(wall_clock) = wall_clock {
+        let agent: Duration = turns
+            .iter()
+            .filter_map(|&idx| turn_duration(cells, idx))
+            .sum();
+        let _ = writeln!(
+            out,
+            "- **Wall clock:** {} (agent working {})",
+            format_span_duration(wall_clock),
+            format_span_duration(agent)
+        );
+    }
+    out.push('\n');
+}
+
+fn write_tools(out: &mut String, cells: &[HistoryCell]) {
+    let mut by_tool: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for cell in cells {
+        if let HistoryCell::Tool { name, state, .. } = cell {
+            let entry = by_tool.entry(name.as_str()).or_default();
+            entry.0 += 1;
+            if *state == ToolState::Error {
+                entry.1 += 1;
+            }
+        }
+    }
+    let total: usize = by_tool.values().map(|(calls, _)| calls).sum();
+    let _ = writeln!(out, "## Tool calls ({total})\n");
+    if by_tool.is_empty() {
+        out.push_str("_No tool calls yet._\n\n");
+        return;
+    }
+    let mut rows: Vec<_> = by_tool.into_iter().collect();
+    rows.sort_by_key(|(_, (calls, _))| std::cmp::Reverse(*calls));
+    out.push_str("| Tool | Calls | Failed |\n|---|---:|---:|\n");
+    for (name, (calls, failed)) in rows {
+        let _ = writeln!(out, "| {name} | {calls} | {failed} |");
+    }
+    out.push('\n');
+}
+
+fn write_usage(out: &mut String, usage: &[UsageRow]) {
+    out.push_str("## Tokens and cost\n\n");
+    if usage.is_empty() {
+        out.push_str("_No usage recorded yet._\n\n");
+        return;
+    }
+    out.push_str("| Model | Input | Output | Cache read | Cache write | Cost |\n");
+    out.push_str("|---|---:|---:|---:|---:|---:|\n");
+    let mut total_cost = 0.0;
+    for row in usage {
+        let cost = if row.subscription {
+            "subscription".to_string()
+        } else if row.cost_known {
+            total_cost += row.cost_usd;
+            ThreadUsage::format_cost(row.cost_usd)
+        } else {
+            "unknown".to_string()
+        };
+        let _ = writeln!(
+            out,
+            "| {} | {} | {} | {} | {} | {cost} |",
+            row.model.as_deref().unwrap_or(&row.provider),
+            ThreadUsage::format_tokens(row.input),
+            ThreadUsage::format_tokens(row.output),
+            ThreadUsage::format_tokens(row.cache_read),
+            ThreadUsage::format_tokens(row.cache_write),
+        );
+    }
+    if usage.len() > 1 {
+        let sum = |field: fn(&UsageRow) -> u64| usage.iter().map(field).sum::<u64>();
+        let _ = writeln!(
+            out,
+            "| **Total** | {} | {} | {} | {} | {} |",
+            ThreadUsage::format_tokens(sum(|row| row.input)),
+            ThreadUsage::format_tokens(sum(|row| row.output)),
+            ThreadUsage::format_tokens(sum(|row| row.cache_read)),
+            ThreadUsage::format_tokens(sum(|row| row.cache_write)),
+            ThreadUsage::format_cost(total_cost),
+        );
+    }
+    out.push('\n');
+}
+
+fn write_files(out: &mut String, cells: &[HistoryCell]) {
+    let changes = collect_file_changes(cells);
+    let _ = writeln!(out, "## Files touched ({})\n", changes.len());
+    if changes.is_empty() {
+        out.push_str("_No files changed._\n\n");
+        return;
+    }
+    for change in changes {
+        let _ = writeln!(
+            out,
+            "- `{} {}` +{} -{}",
+            change.kind.marker(),
+            change.path,
+            change.added,
+            change.removed
+        );
+    }
+    out.push('\n');
+}
+
+fn write_contributors(out: &mut String, cells: &[HistoryCell], messages: &[ChatMessage]) {
+    let tools: HashMap<&str, (&str, &Value)> = cells
+        .iter()
+        .filter_map(|cell| match cell {
+            HistoryCell::Tool {
+                tool_use_id,
+                name,
+                input,
+                ..
+            } => Some((tool_use_id.as_str(), (name.as_str(), input))),
+            _ => None,
+        })
+        .collect();
+
+    let mut items: Vec<(String, u64)> = Vec::new();
+    for message in messages {
+        let role = if message.role == "user" {
+            "User"
+        } else {
+            "Assistant"
+        };
+        match &message.content {
+            MessageContent::Text(text) => {
+                items.push((format!("{role} message"), text.len() as u64));
+            }
+            MessageContent::Blocks(blocks) => {
+                for block in blocks {
+                    let chars = crate::runtime::content_block_chars(block);
+                    items.push((block_label(role, block, &tools), chars));
+                }
+            }
+        }
+    }
+    let total: u64 = items.iter().map(|(_, chars)| chars).sum();
+
+    out.push_str("## Largest context contributors\n\n");
+    if total == 0 {
+        out.push_str("_Context is empty._\n");
+        return;
+    }
+    items.sort_by_key(|(_, chars)| std::cmp::Reverse(*chars));
+    out.push_str("| Item | ~Tokens | Share |\n|---|---:|---:|\n");
+    for (label, chars) in items.into_iter().take(TOP_CONTRIBUTORS) {
+        let _ = writeln!(
+            out,
+            "| {label} | {} | {:.0}% |",
+            ThreadUsage::format_tokens(chars / 4),
+            chars as f64 * 100.0 / total as f64
+        );
+    }
+    let _ = writeln!(
+        out,
+        "\n_Estimated from message characters (~4 chars per token); {} total._",
+        ThreadUsage::format_tokens(total / 4)
+    );
+}
+
+fn write_memory(out: &mut String, cells: &[HistoryCell], wrap_cache: WrapCacheStats) {
+    let transcript: usize = cells.iter().map(HistoryCell::estimated_bytes).sum();
+    out.push_str("\n## Memory\n\n");
+    let _ = writeln!(
+        out,
+        "- **Transcript:** {} cells, ~{}",
+        cells.len(),
+        format_bytes(transcript)
+    );
+    let _ = writeln!(
+        out,
+        "- **Wrap cache:** {} entries, ~{} of {} ({} evicted)",
+        wrap_cache.entries,
+        format_bytes(wrap_cache.bytes),
+        format_bytes(wrap_cache.max_bytes),
+        wrap_cache.evictions
+    );
+}
+
+fn format_bytes(bytes: usize) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{bytes} bytes")
+    }
+}
+
+fn block_label(
+    role: &str,
+    block: &ChatContentBlock,
+    tools: &HashMap<&str, (&str, &Value)>,
+) -> String {
+    match block {
+        ChatContentBlock::Text { .. } => format!("{role} message"),
+        ChatContentBlock::Reasoning(_) => "Reasoning".to_string(),
+        ChatContentBlock::Image { .. } => "Image".to_string(),
+        ChatContentBlock::ToolUse { name, input, .. } => {
+            with_summary(format!("{name} call"), input)
+        }
+        ChatContentBlock::ToolResult(result) => match tools.get(result.tool_use_id.as_str()) {
+            Some((name, input)) => with_summary(format!("{name} result"), input),
+            None => "Tool result".to_string(),
+        },
+    }
+}
+
+/// Appends the most telling tool input (path, command, or pattern).
+fn with_summary(label: String, input: &Value) -> String {
+    let Some(summary) = ["file_path", "path", "command", "pattern", "url", "query"]
+        .iter()
+        .find_map(|key| input.get(key).and_then(Value::as_str))
+    else {
+        return label;
+    };
+    let mut summary: String = summary
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .replace('|', "\\|")
+        .replace('`', "'");
+    if summary.chars().count() > SUMMARY_MAX_CHARS {
+        summary = summary.chars().take(SUMMARY_MAX_CHARS - 1).collect();
+        summary.push('…');
+    }
+    format!("{label} · `{summary}`")
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use zdx_engine::core::events::ToolOutput;
+    use zdx_types::{ToolResult, ToolResultContent};
+
+    use super::*;
+
+    #[test]
+    fn test_build_report_counts_tools_and_ranks_contributors() {
+        let mut read = HistoryCell::tool_running("t1", "Read", json!({"file_path": "src/lib.rs"}));
+        read.set_tool_result(ToolOutput::success(json!({})));
+        let mut bash = HistoryCell::tool_running("t2", "Bash", json!({"command": "cargo test"}));
+        bash.set_tool_result(ToolOutput::failure("failed", "boom", None));
+        let cells = vec![
+            HistoryCell::user("hi"),
+            read,
+            bash,
+            HistoryCell::assistant("done"),
+        ];
+        let messages = vec![
+            ChatMessage::user("hi"),
+            ChatMessage::tool_results(vec![ToolResult {
+                tool_use_id: "t1".to_string(),
+                content: ToolResultContent::Text("x".repeat(4000)),
+                is_error: false,
+            }]),
+        ];
+        let usage = vec![UsageRow {
+            provider: "anthropic".to_string(),
+            model: Some("claude-sonnet-4".to_string()),
+            requests: 2,
+            input: 1500,
+            output: 200,
+            cache_read: 0,
+            cache_write: 0,
+            cost_usd: 0.0075,
+            subscription: false,
+            cost_known: true,
+            estimated: false,
+        }];
+
+        let wrap_cache = WrapCacheStats {
+            entries: 3,
+            bytes: 2048,
+            max_bytes: 16 * 1024 * 1024,
+            evictions: 0,
+        };
+        let report = build_report(&cells, &messages, &usage, wrap_cache);
+        assert!(report.contains("- **Turns:** 1"));
+        assert!(report.contains("## Tool calls (2)"));
+        assert!(report.contains("| Bash | 1 | 1 |"));
+        assert!(report.contains("| claude-sonnet-4 | 1.5k | 200 |"));
+        assert!(report.contains("| Read result · `src/lib.rs` | 1.0k | 100% |"));
+        assert!(report.contains("## Files touched (0)"));
+        assert!(report.contains("- **Wrap cache:** 3 entries, ~2.0 KB of 16.0 MB (0 evicted)"));
+    }
+}
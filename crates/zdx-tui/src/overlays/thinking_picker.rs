@@ -7,6 +7,7 @@ use ratatui::widgets::{List, ListItem, ListState};
 use zdx_engine::config::ThinkingLevel;
 
 use super::OverlayUpdate;
+use crate::common::i18n::{Text, tr};
 use crate::effects::UiEffect;
 use crate::mutations::{ConfigMutation, StateMutation, TranscriptMutation};
 use crate::state::TuiState;
@@ -53,54 +54,58 @@ impl ThinkingPickerState {
                 let Some(&level) = levels.get(self.selected) else {
                     return OverlayUpdate::close();
                 };
-
-                let message = if level == ThinkingLevel::Off {
-                    "Thinking disabled".to_string()
-                } else {
-                    format!("Thinking level set to {}", level.display_name())
-                };
-                let use_thread_override = tui.thread.thread_handle.is_some()
-                    && (tui.thread.model_override.is_some()
-                        || tui.thread.thinking_override.is_some());
-                if use_thread_override && tui.agent_state.is_running() {
-                    return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
-                        TranscriptMutation::AppendSystemMessage(
-                            "Stop the current task first before changing this thread's thinking override."
-                                .to_string(),
-                        ),
-                    )]);
-                }
-                OverlayUpdate::close()
-                    .with_ui_effects(vec![if use_thread_override {
-                        UiEffect::PersistThreadThinkingOverride { level }
-                    } else {
-                        UiEffect::PersistThinking { level }
-                    }])
-                    .with_mutations(vec![
-                        if use_thread_override {
-                            StateMutation::Thread(crate::mutations::ThreadMutation::SetOverrides {
-                                model_override: tui.thread.model_override.clone(),
-                                thinking_override: Some(level),
-                            })
-                        } else {
-                            StateMutation::Config(ConfigMutation::SetThinkingLevel(level))
-                        },
-                        StateMutation::SetActiveThreadOverrides {
-                            model_override: tui.thread.model_override.clone(),
-                            thinking_override: if use_thread_override {
-                                Some(level)
-                            } else {
-                                tui.thread.thinking_override
-                            },
-                        },
-                        StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(message)),
-                    ])
+                select_thinking_level(tui, level)
             }
             _ => OverlayUpdate::stay(),
         }
     }
 }
 
+/// Sets the thinking level: the thread's override when the thread already has
+/// overrides, otherwise the global level.
+pub(crate) fn select_thinking_level(tui: &TuiState, level: ThinkingLevel) -> OverlayUpdate {
+    let message = if level == ThinkingLevel::Off {
+        "Thinking disabled".to_string()
+    } else {
+        format!("Thinking level set to {}", level.display_name())
+    };
+    let use_thread_override = tui.thread.thread_handle.is_some()
+        && (tui.thread.model_override.is_some() || tui.thread.thinking_override.is_some());
+    if use_thread_override && tui.agent_state.is_running() {
+        return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(
+                "Stop the current task first before changing this thread's thinking override."
+                    .to_string(),
+            ),
+        )]);
+    }
+    OverlayUpdate::close()
+        .with_ui_effects(vec![if use_thread_override {
+            UiEffect::PersistThreadThinkingOverride { level }
+        } else {
+            UiEffect::PersistThinking { level }
+        }])
+        .with_mutations(vec![
+            if use_thread_override {
+                StateMutation::Thread(crate::mutations::ThreadMutation::SetOverrides {
+                    model_override: tui.thread.model_override.clone(),
+                    thinking_override: Some(level),
+                })
+            } else {
+                StateMutation::Config(ConfigMutation::SetThinkingLevel(level))
+            },
+            StateMutation::SetActiveThreadOverrides {
+                model_override: tui.thread.model_override.clone(),
+                thinking_override: if use_thread_override {
+                    Some(level)
+                } else {
+                    tui.thread.thinking_override
+                },
+            },
+            StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(message)),
+        ])
+}
+
 pub fn render_thinking_picker(
     frame: &mut Frame,
     picker: &ThinkingPickerState,
@@ -124,7 +129,7 @@ pub fn render_thinking_picker(
         area,
         input_top_y,
         &OverlayConfig {
-            title: "Thinking Level",
+            title: tr(Text::ThinkingLevelTitle),
             border_color: Color::Magenta,
             width: picker_width,
             height: picker_height,
@@ -4,11 +4,13 @@ use std::time::Instant;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use ratatui::Frame;
 use ratatui::layout::Rect;
-use zdx_engine::core::thread_persistence::ThreadSummary;
+use serde::{Deserialize, Serialize};
+use zdx_engine::core::thread_persistence::{ThreadSummary, short_thread_id};
 
-use super::OverlayUpdate;
+use super::{ConfirmState, OverlayRequest, OverlayUpdate};
 use crate::common::TaskKind;
-use crate::effects::UiEffect;
+use crate::common::i18n::{Text, tr};
+use crate::effects::{ThreadAction, UiEffect};
 use crate::input::InputState;
 use crate::mutations::{InputMutation, StateMutation, TranscriptMutation};
 use crate::state::TuiState;
@@ -16,9 +18,16 @@ use crate::thread::{MAX_VISIBLE_THREADS, ThreadDisplayItem, render_thread_picker
 use crate::transcript::HistoryCell;
 
 const COPIED_FEEDBACK_DURATION_MS: u128 = 300;
+/// Messages shown from each end of a thread in the preview pane.
+const PREVIEW_EDGE_MESSAGES: usize = 3;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Which threads the picker lists (Ctrl+S toggles; remembered across
+/// launches in `ui_state.json`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ThreadScope {
+    /// Threads started in the current project root.
+    #[default]
     Current,
     All,
 }
@@ -51,6 +60,55 @@ impl ThreadPickerMode {
     }
 }
 
+/// One user/assistant message in the picker preview pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PreviewMessage {
+    pub is_user: bool,
+    pub text: String,
+}
+
+/// First and last few messages of the highlighted thread.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThreadPreview {
+    pub thread_id: String,
+    pub head: Vec<PreviewMessage>,
+    /// Messages omitted between `head` and `tail`.
+    pub skipped: usize,
+    pub tail: Vec<PreviewMessage>,
+}
+
+impl ThreadPreview {
+    fn from_cells(thread_id: &str, cells: &[HistoryCell]) -> Self {
+        let mut messages: Vec<PreviewMessage> = cells
+            .iter()
+            .filter_map(|cell| match cell {
+                HistoryCell::User { content, .. } => Some((true, content)),
+                HistoryCell::Assistant { content, .. } => Some((false, content)),
+                _ => None,
+            })
+            .filter(|(_, content)| !content.trim().is_empty())
+            .map(|(is_user, content)| PreviewMessage {
+                is_user,
+                text: content.trim().to_string(),
+            })
+            .collect();
+        let (skipped, tail) = if messages.len() > PREVIEW_EDGE_MESSAGES * 2 {
+            let tail = messages.split_off(messages.len() - PREVIEW_EDGE_MESSAGES);
+            let skipped = messages.len() - PREVIEW_EDGE_MESSAGES;
+            messages.truncate(PREVIEW_EDGE_MESSAGES);
+            (skipped, tail)
+        } else {
+            (0, Vec::new())
+        };
+        Self {
+            thread_id: thread_id.to_string(),
+            head: messages,
+            skipped,
+            tail,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct ThreadPickerState {
     pub all_threads: Vec<ThreadSummary>,
@@ -67,6 +125,14 @@ pub struct ThreadPickerState {
     pub current_thread_id: Option<String>,
     /// Search filter text (filters by thread ID or title).
     pub filter: String,
+    /// Preview pane contents (may lag behind the selection while loading).
+    pub preview: Option<ThreadPreview>,
+    /// Threads marked with Space for bulk actions.
+    pub marked: HashSet<String>,
+    /// Tag being typed for the action targets (Ctrl+G); Enter applies.
+    pub tag_input: Option<String>,
+    /// Result of the last action, shown in place of the hints.
+    pub notice: Option<String>,
 }
 
 impl ThreadPickerState {
@@ -77,6 +143,7 @@ impl ThreadPickerState {
         current_root: &std::path::Path,
         current_thread_id: Option<String>,
         mode: ThreadPickerMode,
+        scope: ThreadScope,
     ) -> (Self, Vec<UiEffect>) {
         let current_root = current_root
             .canonicalize()
@@ -86,7 +153,7 @@ impl ThreadPickerState {
         let mut state = Self {
             all_threads: threads,
             active_thread_ids,
-            scope: ThreadScope::Current,
+            scope,
             current_root,
             selected: 0,
             offset: 0,
@@ -95,6 +162,10 @@ impl ThreadPickerState {
             copied_at: None,
             current_thread_id,
             filter: String::new(),
+            preview: None,
+            marked: HashSet::new(),
+            tag_input: None,
+            notice: None,
         };
         let effects = state.preview_selected_effects();
         (state, effects)
@@ -110,9 +181,18 @@ impl ThreadPickerState {
         let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
         let alt = key.modifiers.contains(KeyModifiers::ALT);
         let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+        if self.tag_input.is_some() {
+            return self.handle_tag_input_key(key);
+        }
+        self.notice = None;
 
         match key.code {
             KeyCode::Char('t') if ctrl => self.open_as_tab(tui),
+            KeyCode::Char('y') if ctrl => self.duplicate_selected(tui),
+            KeyCode::Char('d') if ctrl => self.request_delete(),
+            KeyCode::Char('a') if ctrl => self.apply_to_targets(ThreadAction::Archive),
+            KeyCode::Char('e') if ctrl => self.apply_to_targets(ThreadAction::Export),
+            KeyCode::Char('g') if ctrl => self.start_tag_input(),
             KeyCode::Char('s') if ctrl => self.toggle_scope(),
             KeyCode::Esc | KeyCode::Char('c') if key.code == KeyCode::Esc || ctrl => {
                 self.close_overlay()
@@ -136,6 +216,7 @@ impl ThreadPickerState {
                 self.clamp_selection();
                 self.preview_update()
             }
+            KeyCode::Char(' ') if self.mode.is_switch() && !ctrl && !alt => self.toggle_mark(),
             KeyCode::Char(c) if !ctrl => {
                 self.filter.push(c);
                 self.clamp_selection();
@@ -149,7 +230,11 @@ impl ThreadPickerState {
         self.scope = self.scope.toggle();
         self.selected = 0;
         self.offset = 0;
-        self.preview_update()
+        let mut update = self.preview_update();
+        update
+            .mutations
+            .push(StateMutation::SetThreadPickerScope(self.scope));
+        update
     }
 
     fn close_overlay(&self) -> OverlayUpdate {
@@ -200,7 +285,7 @@ impl ThreadPickerState {
     fn switch_to_selected_thread(&self, tui: &TuiState) -> OverlayUpdate {
         if tui.agent_state.is_running() {
             return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
-                TranscriptMutation::AppendSystemMessage("Stop the current task first.".to_string()),
+                TranscriptMutation::AppendSystemMessage(tr(Text::StopCurrentTaskFirst).to_string()),
             )]);
         }
 
@@ -243,6 +328,221 @@ impl ThreadPickerState {
         }
     }
 
+    /// Copies the selected thread and switches to the copy.
+    fn duplicate_selected(&self, tui: &TuiState) -> OverlayUpdate {
+        if !self.mode.is_switch() || tui.tasks.state(TaskKind::ThreadLoad).is_running() {
+            return OverlayUpdate::stay();
+        }
+        if tui.agent_state.is_running() {
+            return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(tr(Text::StopCurrentTaskFirst).to_string()),
+            )]);
+        }
+        match self.selected_thread() {
+            Some(thread) => {
+                OverlayUpdate::close().with_ui_effects(vec![UiEffect::DuplicateThread {
+                    thread_id: thread.id.clone(),
+                    turns: None,
+                }])
+            }
+            None => OverlayUpdate::stay(),
+        }
+    }
+
+    fn handle_tag_input_key(&mut self, key: KeyEvent) -> OverlayUpdate {
+        let Some(tag) = self.tag_input.as_mut() else {
+            return OverlayUpdate::stay();
+        };
+        match key.code {
+            KeyCode::Esc => self.tag_input = None,
+            KeyCode::Enter => {
+                let tag = tag.trim().to_string();
+                self.tag_input = None;
+                if !tag.is_empty() {
+                    return self.apply_to_targets(ThreadAction::Tag(tag));
+                }
+            }
+            KeyCode::Backspace => {
+                tag.pop();
+            }
+            KeyCode::Char(c)
+                if !key.modifiers.contains(KeyModifiers::CONTROL) && !c.is_whitespace() =>
+            {
+                tag.push(c);
+            }
+            _ => {}
+        }
+        OverlayUpdate::stay()
+    }
+
+    fn toggle_mark(&mut self) -> OverlayUpdate {
+        let Some(thread_id) = self.selected_thread().map(|thread| thread.id.clone()) else {
+            return OverlayUpdate::stay();
+        };
+        if !self.marked.remove(&thread_id) {
+            self.marked.insert(thread_id);
+        }
+        self.navigate_down()
+    }
+
+    fn start_tag_input(&mut self) -> OverlayUpdate {
+        if !self.action_targets().is_empty() {
+            self.tag_input = Some(String::new());
+        }
+        OverlayUpdate::stay()
+    }
+
+    /// Asks for confirmation, then returns to the picker, which drops the
+    /// deleted threads in place once the action is applied.
+    fn request_delete(&mut self) -> OverlayUpdate {
+        let thread_ids = self.removable_targets();
+        if thread_ids.is_empty() {
+            return OverlayUpdate::stay();
+        }
+        let target = match thread_ids.as_slice() {
+            [thread_id] => {
+                let title = self
+                    .all_threads
+                    .iter()
+                    .find(|thread| &thread.id == thread_id)
+                    .map_or_else(|| short_thread_id(thread_id), ThreadSummary::display_title);
+                format!("“{title}”")
+            }
+            _ => format!("{} threads", thread_ids.len()),
+        };
+        let confirm = ConfirmState::new(
+            "Delete threads",
+            format!("Delete {target}? This can't be undone."),
+        )
+        .danger()
+        .confirm_label("delete")
+        .on_confirm(
+            vec![UiEffect::ApplyThreadAction {
+                thread_ids,
+                action: ThreadAction::Delete,
+            }],
+            vec![],
+        )
+        .return_to_previous();
+        OverlayUpdate::open(OverlayRequest::Confirm(Box::new(confirm)))
+    }
+
+    fn apply_to_targets(&mut self, action: ThreadAction) -> OverlayUpdate {
+        let thread_ids = if action.removes_threads() {
+            self.removable_targets()
+        } else {
+            self.action_targets()
+        };
+        if thread_ids.is_empty() {
+            return OverlayUpdate::stay();
+        }
+        OverlayUpdate::stay()
+            .with_ui_effects(vec![UiEffect::ApplyThreadAction { thread_ids, action }])
+    }
+
+    /// Threads an action applies to: the marked ones (in list order), or the
+    /// selected thread when none are marked.
+    pub fn action_targets(&self) -> Vec<String> {
+        if !self.mode.is_switch() {
+            return Vec::new();
+        }
+        if self.marked.is_empty() {
+            return self
+                .selected_thread()
+                .map(|thread| vec![thread.id.clone()])
+                .unwrap_or_default();
+        }
+        self.all_threads
+            .iter()
+            .filter(|thread| self.marked.contains(&thread.id))
+            .map(|thread| thread.id.clone())
+            .collect()
+    }
+
+    /// Action targets minus the current and running threads, which can't be
+    /// deleted or archived; sets a notice when any are skipped.
+    fn removable_targets(&mut self) -> Vec<String> {
+        let targets = self.action_targets();
+        let total = targets.len();
+        let removable: Vec<String> = targets
+            .into_iter()
+            .filter(|id| {
+                self.current_thread_id.as_deref() != Some(id.as_str())
+                    && !self.active_thread_ids.contains(id)
+            })
+            .collect();
+        let skipped = total - removable.len();
+        if skipped > 0 {
+            self.notice = Some(if total == 1 {
+                "Can't remove the current or a running thread".to_string()
+            } else {
+                format!("Skipping {skipped} current/running thread(s)")
+            });
+        }
+        removable
+    }
+
+    /// Updates the list in place after an action: removed threads disappear,
+    /// tagged threads show the new tag.
+    pub fn handle_action_applied(
+        &mut self,
+        action: &ThreadAction,
+        thread_ids: &[String],
+        errors: &[String],
+    ) -> OverlayUpdate {
+        let summary = (!thread_ids.is_empty()).then(|| action.summary(thread_ids.len()));
+        let failure = errors
+            .first()
+            .map(|first| format!("{} failed: {first}", errors.len()));
+        let notice = [summary, failure]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join("; ");
+        self.notice = Some(notice);
+        for thread_id in thread_ids {
+            self.marked.remove(thread_id);
+        }
+
+        match action {
+            ThreadAction::Delete | ThreadAction::Archive => {
+                self.all_threads
+                    .retain(|thread| !thread_ids.contains(&thread.id));
+                if self
+                    .preview
+                    .as_ref()
+                    .is_some_and(|preview| thread_ids.contains(&preview.thread_id))
+                {
+                    self.preview = None;
+                }
+                self.clamp_selection();
+                self.preview_update()
+            }
+            ThreadAction::Tag(tag) => {
+                for thread in &mut self.all_threads {
+                    if thread_ids.contains(&thread.id) && !thread.tags.contains(tag) {
+                        thread.tags.push(tag.clone());
+                    }
+                }
+                OverlayUpdate::stay()
+            }
+            ThreadAction::Export => OverlayUpdate::stay(),
+        }
+    }
+
+    /// Stores preview pane contents for a loaded thread.
+    pub fn set_preview(&mut self, thread_id: &str, cells: &[HistoryCell]) {
+        self.preview = Some(ThreadPreview::from_cells(thread_id, cells));
+    }
+
+    /// Preview for the selected thread, if it has loaded.
+    pub fn selected_preview(&self) -> Option<&ThreadPreview> {
+        let selected = self.selected_thread()?;
+        self.preview
+            .as_ref()
+            .filter(|preview| preview.thread_id == selected.id)
+    }
+
     fn insert_selected_thread(&self, tui: &TuiState) -> OverlayUpdate {
         let mut mutations = Vec::new();
         if let Some(mutation) = self.select_thread_and_insert(&tui.input) {
@@ -447,8 +747,8 @@ impl ThreadPickerState {
 
 /// Returns a fuzzy match score if the thread matches the filter, or `None` if no match.
 ///
-/// Matches against thread ID and title using nucleo fuzzy matching.
-fn thread_fuzzy_score(thread: &ThreadSummary, filter: &str) -> Option<u32> {
+/// Matches against thread ID, title, and tags using nucleo fuzzy matching.
+pub(crate) fn thread_fuzzy_score(thread: &ThreadSummary, filter: &str) -> Option<u32> {
     use nucleo_matcher::pattern::{CaseMatching, Normalization, Pattern};
     use nucleo_matcher::{Config, Matcher, Utf32Str};
 
@@ -471,13 +771,21 @@ fn thread_fuzzy_score(thread: &ThreadSummary, filter: &str) -> Option<u32> {
         pattern.score(haystack, &mut matcher)
     });
 
-    // Take the best score from either field
-    match (id_score, title_score) {
-        (Some(a), Some(b)) => Some(a.max(b)),
-        (Some(a), None) => Some(a),
-        (None, Some(b)) => Some(b),
-        (None, None) => None,
-    }
+    let tag_score = thread
+        .tags
+        .iter()
+        .filter_map(|tag| {
+            let mut buf = Vec::new();
+            let haystack = Utf32Str::new(tag, &mut buf);
+            pattern.score(haystack, &mut matcher)
+        })
+        .max();
+
+    // Take the best score from any field
+    [id_score, title_score, tag_score]
+        .into_iter()
+        .flatten()
+        .max()
 }
 
 #[cfg(test)]
@@ -486,6 +794,13 @@ mod tests {
 
     use super::*;
 
+    fn confirm_request(update: OverlayUpdate) -> ConfirmState {
+        match update.transition {
+            crate::overlays::OverlayTransition::Open(OverlayRequest::Confirm(confirm)) => *confirm,
+            other => panic!("expected a confirmation, got {other:?}"),
+        }
+    }
+
     #[test]
     fn test_thread_picker_state_new_empty() {
         let (state, _) = ThreadPickerState::open(
@@ -495,6 +810,7 @@ mod tests {
             std::path::Path::new("."),
             None,
             ThreadPickerMode::Switch,
+            ThreadScope::Current,
         );
         assert_eq!(state.selected, 0);
         assert_eq!(state.offset, 0);
@@ -535,6 +851,7 @@ mod tests {
             std::path::Path::new("."),
             None,
             ThreadPickerMode::Switch,
+            ThreadScope::Current,
         );
         assert_eq!(state.selected, 0);
         assert_eq!(state.all_threads.len(), 2);
@@ -562,6 +879,7 @@ mod tests {
             std::path::Path::new("."),
             None,
             ThreadPickerMode::Switch,
+            ThreadScope::Current,
         );
         assert_eq!(state.original_cells.len(), 2);
     }
@@ -598,6 +916,7 @@ mod tests {
             std::path::Path::new("."),
             None,
             ThreadPickerMode::Switch,
+            ThreadScope::Current,
         );
 
         let items = state.visible_tree_items();
@@ -645,6 +964,7 @@ mod tests {
             std::path::Path::new("."),
             None,
             ThreadPickerMode::Switch,
+            ThreadScope::Current,
         );
 
         assert_eq!(state.selected, 0);
@@ -674,6 +994,7 @@ mod tests {
             std::path::Path::new("."),
             None,
             ThreadPickerMode::Switch,
+            ThreadScope::Current,
         );
 
         // Switch to All scope so threads without root_path are visible
@@ -713,6 +1034,7 @@ mod tests {
             std::path::Path::new("."),
             None,
             ThreadPickerMode::Switch,
+            ThreadScope::Current,
         );
 
         // Switch to All scope so threads without root_path are visible
@@ -729,4 +1051,168 @@ mod tests {
         assert_eq!(picker.selected, 3);
         assert_eq!(picker.offset, 3);
     }
+
+    #[test]
+    fn test_preview_keeps_first_and_last_messages() {
+        let mut cells = vec![HistoryCell::system("banner")];
+        for i in 0..5 {
+            cells.push(HistoryCell::user(format!("question {i}")));
+            cells.push(HistoryCell::assistant(format!("answer {i}")));
+        }
+        let preview = ThreadPreview::from_cells("t1", &cells);
+
+        let texts = |messages: &[PreviewMessage]| {
+            messages
+                .iter()
+                .map(|message| message.text.clone())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            texts(&preview.head),
+            ["question 0", "answer 0", "question 1"]
+        );
+        assert_eq!(texts(&preview.tail), ["answer 3", "question 4", "answer 4"]);
+        assert_eq!(preview.skipped, 4);
+        assert!(preview.head[0].is_user);
+
+        let short = ThreadPreview::from_cells("t2", &cells[..5]);
+        assert_eq!(short.head.len(), 4);
+        assert!(short.tail.is_empty());
+        assert_eq!(short.skipped, 0);
+    }
+
+    #[test]
+    fn test_delete_requires_confirmation_and_removes_in_place() {
+        use crate::overlays::OverlayTransition;
+        use crate::state::AppState;
+
+        let threads = ["s1", "s2", "s3"]
+            .into_iter()
+            .map(|id| ThreadSummary {
+                id: id.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let (mut picker, _) = ThreadPickerState::open(
+            threads,
+            HashSet::new(),
+            vec![],
+            std::path::Path::new("."),
+            Some("s1".to_string()),
+            ThreadPickerMode::Switch,
+            ThreadScope::Current,
+        );
+        picker.scope = ThreadScope::All;
+        let app = AppState::new(
+            zdx_engine::config::Config::default(),
+            std::path::PathBuf::new(),
+            None,
+            None,
+        );
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+
+        // The current thread can't be removed.
+        let update = picker.handle_key(&app.tui, ctrl_d);
+        assert!(update.effects.is_empty());
+        assert!(matches!(update.transition, OverlayTransition::Stay));
+        assert!(picker.notice.is_some());
+
+        // Other threads go through the confirmation overlay.
+        picker.selected = 1;
+        let mut confirm = confirm_request(picker.handle_key(&app.tui, ctrl_d));
+        assert!(confirm.danger);
+        let update = confirm.handle_key(KeyEvent::new(KeyCode::Char('y'), KeyModifiers::NONE));
+        assert!(matches!(
+            update.effects.as_slice(),
+            [UiEffect::ApplyThreadAction { thread_ids, action: ThreadAction::Delete }]
+                if thread_ids == &["s2"]
+        ));
+
+        let update = picker.handle_action_applied(&ThreadAction::Delete, &["s2".to_string()], &[]);
+        let ids: Vec<_> = picker.all_threads.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["s1", "s3"]);
+        assert_eq!(picker.selected_thread().unwrap().id, "s3");
+        assert!(matches!(
+            update.effects.as_slice(),
+            [UiEffect::PreviewThread { thread_id }] if thread_id == "s3"
+        ));
+
+        let update = picker.handle_key(
+            &app.tui,
+            KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL),
+        );
+        assert!(matches!(
+            update.effects.as_slice(),
+            [UiEffect::ApplyThreadAction { thread_ids, action: ThreadAction::Archive }]
+                if thread_ids == &["s3"]
+        ));
+    }
+
+    #[test]
+    fn test_marked_threads_are_bulk_targets() {
+        use crate::state::AppState;
+
+        let threads = ["s1", "s2", "s3", "s4"]
+            .into_iter()
+            .map(|id| ThreadSummary {
+                id: id.to_string(),
+                ..Default::default()
+            })
+            .collect();
+        let (mut picker, _) = ThreadPickerState::open(
+            threads,
+            HashSet::new(),
+            vec![],
+            std::path::Path::new("."),
+            Some("s1".to_string()),
+            ThreadPickerMode::Switch,
+            ThreadScope::Current,
+        );
+        picker.scope = ThreadScope::All;
+        let app = AppState::new(
+            zdx_engine::config::Config::default(),
+            std::path::PathBuf::new(),
+            None,
+            None,
+        );
+        let key = |code, modifiers| KeyEvent::new(code, modifiers);
+        let space = key(KeyCode::Char(' '), KeyModifiers::NONE);
+
+        // Space marks and moves down: s1 (current), s2, then skip s3, mark s4.
+        let _ = picker.handle_key(&app.tui, space);
+        let _ = picker.handle_key(&app.tui, space);
+        let _ = picker.handle_key(&app.tui, key(KeyCode::Down, KeyModifiers::NONE));
+        let _ = picker.handle_key(&app.tui, space);
+        assert!(picker.filter.is_empty());
+        assert_eq!(picker.action_targets(), ["s1", "s2", "s4"]);
+
+        // Tagging applies to every marked thread.
+        let _ = picker.handle_key(&app.tui, key(KeyCode::Char('g'), KeyModifiers::CONTROL));
+        for c in "wip".chars() {
+            let _ = picker.handle_key(&app.tui, key(KeyCode::Char(c), KeyModifiers::NONE));
+        }
+        let update = picker.handle_key(&app.tui, key(KeyCode::Enter, KeyModifiers::NONE));
+        assert!(matches!(
+            update.effects.as_slice(),
+            [UiEffect::ApplyThreadAction { thread_ids, action: ThreadAction::Tag(tag) }]
+                if thread_ids.len() == 3 && tag == "wip"
+        ));
+
+        // Delete skips the current thread and confirms the rest.
+        let update = picker.handle_key(&app.tui, key(KeyCode::Char('d'), KeyModifiers::CONTROL));
+        assert!(picker.notice.is_some());
+        let mut confirm = confirm_request(update);
+        assert!(confirm.body.contains("2 threads"));
+        assert!(matches!(
+            confirm.handle_key(key(KeyCode::Enter, KeyModifiers::NONE)).effects.as_slice(),
+            [UiEffect::ApplyThreadAction { thread_ids, action: ThreadAction::Delete }]
+                if thread_ids == &["s2", "s4"]
+        ));
+
+        let removed = ["s2".to_string(), "s4".to_string()];
+        let _ = picker.handle_action_applied(&ThreadAction::Archive, &removed, &[]);
+        let ids: Vec<_> = picker.all_threads.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, ["s1", "s3"]);
+        assert_eq!(picker.marked, HashSet::from(["s1".to_string()]));
+    }
 }
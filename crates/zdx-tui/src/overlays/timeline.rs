@@ -12,9 +12,11 @@ use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{List, ListItem, ListState, Paragraph};
 use serde_json::json;
+use zdx_engine::core::duel::turn_text;
 use zdx_engine::core::thread_persistence::ThreadEvent;
 
 use super::OverlayUpdate;
+use crate::common::i18n::{Text, tr};
 use crate::common::{TaskKind, sanitize_for_display, truncate_with_ellipsis};
 use crate::effects::UiEffect;
 use crate::mutations::{StateMutation, TranscriptMutation};
@@ -51,6 +53,7 @@ pub struct TimelineEntry {
     pub cell_index: usize,
     pub role: TimelineRole,
     pub preview: String,
+    pub pinned: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -138,7 +141,7 @@ impl TimelineState {
                 if tui.agent_state.is_running() {
                     return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
                         TranscriptMutation::AppendSystemMessage(
-                            "Stop the current task first.".to_string(),
+                            tr(Text::StopCurrentTaskFirst).to_string(),
                         ),
                     )]);
                 }
@@ -148,7 +151,7 @@ impl TimelineState {
                         .with_mutations(vec![StateMutation::Transcript(command)]),
                     None => OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
                         TranscriptMutation::AppendSystemMessage(
-                            "No timeline entry selected.".to_string(),
+                            tr(Text::NoTimelineEntrySelected).to_string(),
                         ),
                     )]),
                 }
@@ -157,7 +160,7 @@ impl TimelineState {
                 if tui.agent_state.is_running() {
                     return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
                         TranscriptMutation::AppendSystemMessage(
-                            "Stop the current task first.".to_string(),
+                            tr(Text::StopCurrentTaskFirst).to_string(),
                         ),
                     )]);
                 }
@@ -165,38 +168,48 @@ impl TimelineState {
                 if tui.tasks.state(TaskKind::ThreadFork).is_running() {
                     return OverlayUpdate::stay();
                 }
-
-                match self.fork_effect(tui) {
-                    Some(effect) => OverlayUpdate::close()
-                        .with_ui_effects(vec![effect])
-                        .with_mutations(vec![]),
-                    None => OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
-                        TranscriptMutation::AppendSystemMessage(
-                            "No timeline entry selected.".to_string(),
-                        ),
-                    )]),
+                // A fork is a saved thread, which would keep incognito content.
+                if tui.thread.incognito {
+                    return incognito_fork_notice();
                 }
+
+                fork_update(self.fork_effect(tui))
             }
             KeyCode::Char('t') => {
                 if tui.tasks.state(TaskKind::ThreadFork).is_running() {
                     return OverlayUpdate::stay();
                 }
-
-                match self.fork_as_tab_effect(tui) {
-                    Some(effect) => OverlayUpdate::close()
-                        .with_ui_effects(vec![effect])
-                        .with_mutations(vec![]),
-                    None => OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
-                        TranscriptMutation::AppendSystemMessage(
-                            "No timeline entry selected.".to_string(),
-                        ),
-                    )]),
+                // A fork is a saved thread, which would keep incognito content.
+                if tui.thread.incognito {
+                    return incognito_fork_notice();
                 }
+
+                fork_update(self.fork_as_tab_effect(tui))
             }
+            KeyCode::Char('p') => self.toggle_pin(tui),
             _ => OverlayUpdate::stay(),
         }
     }
 
+    /// Pins or unpins the selected message for the rest of the thread.
+    fn toggle_pin(&mut self, tui: &TuiState) -> OverlayUpdate {
+        if tui.thread.thread_handle.is_none() {
+            return OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
+                TranscriptMutation::AppendSystemMessage(
+                    "Pinning requires an active thread.".to_string(),
+                ),
+            )]);
+        }
+        let Some(entry) = self.entries.get_mut(self.selected) else {
+            return OverlayUpdate::stay();
+        };
+        entry.pinned = !entry.pinned;
+        OverlayUpdate::stay().with_ui_effects(vec![UiEffect::SetMessagePinned {
+            cell_index: entry.cell_index,
+            pinned: entry.pinned,
+        }])
+    }
+
     fn visible_height(&self) -> usize {
         if self.entries.is_empty() {
             1
@@ -293,6 +306,25 @@ impl TimelineState {
     }
 }
 
+fn fork_update(effect: Option<UiEffect>) -> OverlayUpdate {
+    match effect {
+        Some(effect) => OverlayUpdate::close()
+            .with_ui_effects(vec![effect])
+            .with_mutations(vec![]),
+        None => OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
+            TranscriptMutation::AppendSystemMessage(tr(Text::NoTimelineEntrySelected).to_string()),
+        )]),
+    }
+}
+
+fn incognito_fork_notice() -> OverlayUpdate {
+    OverlayUpdate::stay().with_mutations(vec![StateMutation::Transcript(
+        TranscriptMutation::AppendSystemMessage(
+            "Forking is unavailable in incognito mode.".to_string(),
+        ),
+    )])
+}
+
 fn build_entries(cells: &[HistoryCell]) -> Vec<TimelineEntry> {
     cells
         .iter()
@@ -309,6 +341,7 @@ fn build_entries(cells: &[HistoryCell]) -> Vec<TimelineEntry> {
                 cell_index: idx,
                 role,
                 preview: line.to_string(),
+                pinned: cells[idx].is_pinned(),
             }
         })
         .collect()
@@ -328,6 +361,17 @@ fn cells_to_events(cells: &[HistoryCell]) -> Vec<ThreadEvent> {
                     Some("final_answer".to_string()),
                 ));
             }
+            HistoryCell::Duel {
+                speaker,
+                content,
+                is_pending: false,
+                ..
+            } => {
+                events.push(ThreadEvent::assistant_message_with_phase(
+                    turn_text(speaker, content),
+                    Some("final_answer".to_string()),
+                ));
+            }
             HistoryCell::Thinking {
                 content, replay, ..
             } => {
@@ -358,8 +402,10 @@ fn cells_to_events(cells: &[HistoryCell]) -> Vec<ThreadEvent> {
                     ));
                 }
             }
-            HistoryCell::System { .. } => {}
-            HistoryCell::Timing { .. } => {}
+            HistoryCell::System { .. } | HistoryCell::Error { .. } => {}
+            HistoryCell::Timing { .. }
+            | HistoryCell::FileChanges { .. }
+            | HistoryCell::Duel { .. } => {}
         }
     }
 
@@ -375,7 +421,7 @@ fn render_timeline(frame: &mut Frame, state: &TimelineState, area: Rect, input_y
     let overlay_height = (visible_rows + 5).max(7);
     let overlay_area = calculate_overlay_area(area, input_y, OVERLAY_WIDTH, overlay_height);
 
-    render_overlay_container(frame, overlay_area, "Timeline", Color::Green);
+    render_overlay_container(frame, overlay_area, tr(Text::TimelineTitle), Color::Green);
 
     let inner_area = Rect::new(
         overlay_area.x + 1,
@@ -414,9 +460,12 @@ fn render_timeline(frame: &mut Frame, state: &TimelineState, area: Rect, input_y
 
     for entry in state.entries.iter().skip(state.offset).take(list_height) {
         let role_label = format!("[{}] ", entry.role.badge());
-        let preview = truncate_with_ellipsis(&entry.preview, max_content_width);
+        let (pin, pin_width) = if entry.pinned { ("📌 ", 3) } else { ("", 0) };
+        let preview =
+            truncate_with_ellipsis(&entry.preview, max_content_width.saturating_sub(pin_width));
         let line = Line::from(vec![
             Span::styled(role_label, Style::default().fg(entry.role.color())),
+            Span::raw(pin),
             Span::styled(preview, Style::default().fg(Color::White)),
         ]);
         items.push(ListItem::new(line));
@@ -445,6 +494,7 @@ fn render_timeline(frame: &mut Frame, state: &TimelineState, area: Rect, input_y
             InputHint::new("Enter", "jump"),
             InputHint::new("f", "fork"),
             InputHint::new("t", "fork as tab"),
+            InputHint::new("p", "pin"),
             InputHint::new("Esc", "close"),
         ],
         Color::Green,
@@ -17,11 +17,36 @@ use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
 use super::OverlayUpdate;
 use super::render_utils::centered_rect;
-use crate::transcript::{ChildToolState, HistoryCell, SPINNER_SPEED_DIVISOR, ToolState};
+use crate::thread::ThreadUsage;
+use crate::transcript::{
+    self, ChildToolState, HistoryCell, SPINNER_SPEED_DIVISOR, ToolState, convert_style, parse_ansi,
+};
 
 /// Spinner frames for popup title animation.
 const SPINNER_FRAMES: &[&str] = &["◐", "◓", "◑", "◒"];
 
+/// Tool output lines keeping the output's own ANSI colors; uncolored text
+/// uses `color`.
+fn output_lines(text: &str, color: Color) -> impl Iterator<Item = Line<'static>> {
+    parse_ansi(text, transcript::Style::Plain)
+        .into_iter()
+        .map(move |line| {
+            Line::from(
+                line.spans
+                    .into_iter()
+                    .map(|span| {
+                        let style = if span.style == transcript::Style::Plain {
+                            Style::default().fg(color)
+                        } else {
+                            convert_style(span.style)
+                        };
+                        Span::styled(span.text, style)
+                    })
+                    .collect::<Vec<_>>(),
+            )
+        })
+}
+
 fn format_byte_truncation(stream: &str, total_bytes: u64) -> String {
     let size_str = if total_bytes >= 1024 * 1024 {
         format!("{:.1} MB", total_bytes as f64 / (1024.0 * 1024.0))
@@ -197,6 +222,7 @@ impl ToolDetailState {
             input_delta,
             output_delta,
             child_tools,
+            child_progress,
             ..
         } = cell
         else {
@@ -289,6 +315,39 @@ impl ToolDetailState {
             lines.push(Line::from(""));
         }
 
+        // --- Child output section (streamed subagent text while running) ---
+        if *state == ToolState::Running && !child_progress.text.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "─── Child output ───",
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for line in child_progress.text.lines() {
+                lines.push(Line::from(Span::styled(
+                    line.to_string(),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+        if let Some((input_tokens, output_tokens)) = child_progress.usage {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    "Child tokens: ",
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!(
+                    "{} in · {} out",
+                    ThreadUsage::format_tokens(input_tokens),
+                    ThreadUsage::format_tokens(output_tokens)
+                )),
+            ]));
+            lines.push(Line::from(""));
+        }
+
         // --- Output section ---
         lines.push(Line::from(Span::styled(
             "─── Output ───",
@@ -300,12 +359,7 @@ impl ToolDetailState {
         if let Some(res) = result {
             if let Some(data) = res.data() {
                 let output_text = build_popup_output_text(name, data);
-                for line in output_text.lines() {
-                    lines.push(Line::from(Span::styled(
-                        line.to_string(),
-                        Style::default().fg(Color::White),
-                    )));
-                }
+                lines.extend(output_lines(&output_text, Color::White));
 
                 // Truncation warnings
                 if data
@@ -379,12 +433,7 @@ impl ToolDetailState {
         } else if *state == ToolState::Running {
             // Show streaming output_delta first, then input_delta, then placeholder
             if let Some(delta) = output_delta.as_deref().filter(|d| !d.is_empty()) {
-                for line in delta.lines() {
-                    lines.push(Line::from(Span::styled(
-                        line.to_string(),
-                        Style::default().fg(Color::White),
-                    )));
-                }
+                lines.extend(output_lines(delta, Color::White));
             } else if let Some(delta) = input_delta.as_deref().filter(|d| !d.is_empty()) {
                 for line in delta.lines() {
                     lines.push(Line::from(Span::styled(
@@ -402,12 +451,7 @@ impl ToolDetailState {
             }
         } else if let Some(delta) = output_delta.as_deref().filter(|d| !d.is_empty()) {
             // Show preserved partial output for cancelled/errored tools
-            for line in delta.lines() {
-                lines.push(Line::from(Span::styled(
-                    line.to_string(),
-                    Style::default().fg(Color::DarkGray),
-                )));
-            }
+            lines.extend(output_lines(delta, Color::DarkGray));
         } else {
             lines.push(Line::from(Span::styled(
                 "(no output)",
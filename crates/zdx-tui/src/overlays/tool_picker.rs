@@ -0,0 +1,315 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{List, ListItem, ListState};
+use zdx_engine::core::agent::{AgentOptions, ToolSelection, selected_tool_names};
+use zdx_engine::providers::provider_for_model;
+
+use super::OverlayUpdate;
+use crate::common::i18n::{Text, tr};
+use crate::mutations::{StateMutation, TranscriptMutation};
+use crate::state::TuiState;
+
+/// One registered tool and whether it is enabled for this session.
+#[derive(Debug, Clone)]
+pub struct ToolEntry {
+    pub name: String,
+    pub description: String,
+    pub enabled: bool,
+}
+
+/// Picker that toggles registered tools on/off for the current session.
+///
+/// Space toggles the selected tool; Enter applies the toggles to the tab's
+/// `ToolSelection` (used by subsequent turns); Esc discards them.
+#[derive(Debug, Clone)]
+pub struct ToolPickerState {
+    pub entries: Vec<ToolEntry>,
+    pub selected: usize,
+    /// Tools enabled by the selection with no includes/excludes applied.
+    baseline: Vec<String>,
+}
+
+impl ToolPickerState {
+    pub fn open(tui: &TuiState) -> Self {
+        let provider = provider_for_model(&tui.config.model);
+        let enabled = selected_tool_names(&tui.config, &tui.agent_opts, provider);
+        let baseline = selected_tool_names(
+            &tui.config,
+            &AgentOptions {
+                tool_config: zdx_engine::core::agent::ToolConfig::new(
+                    tui.agent_opts.tool_config.registry.clone(),
+                    without_filter(&tui.agent_opts.tool_config.selection),
+                ),
+                ..tui.agent_opts.clone()
+            },
+            provider,
+        );
+
+        let entries = tui
+            .agent_opts
+            .tool_config
+            .registry
+            .definitions()
+            .into_iter()
+            .filter(|tool| {
+                tui.config.subagents.enabled || !tool.name.eq_ignore_ascii_case("Invoke_Subagent")
+            })
+            .map(|tool| ToolEntry {
+                enabled: contains_name(&enabled, &tool.name),
+                description: tool.description.lines().next().unwrap_or("").to_string(),
+                name: tool.name,
+            })
+            .collect();
+
+        Self {
+            entries,
+            selected: 0,
+            baseline,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, input_y: u16) {
+        render_tool_picker(frame, self, area, input_y);
+    }
+
+    pub fn handle_key(&mut self, tui: &TuiState, key: KeyEvent) -> OverlayUpdate {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('c') if key.code == KeyCode::Esc || ctrl => {
+                OverlayUpdate::close()
+            }
+            KeyCode::Up => {
+                if self.selected > 0 {
+                    self.selected -= 1;
+                }
+                OverlayUpdate::stay()
+            }
+            KeyCode::Down => {
+                if self.selected + 1 < self.entries.len() {
+                    self.selected += 1;
+                }
+                OverlayUpdate::stay()
+            }
+            KeyCode::Char(' ') => {
+                if let Some(entry) = self.entries.get_mut(self.selected) {
+                    entry.enabled = !entry.enabled;
+                }
+                OverlayUpdate::stay()
+            }
+            KeyCode::Enter => {
+                let selection = self.selection(&tui.agent_opts.tool_config.selection);
+                let summary = self.summary();
+                let message = match summary {
+                    Some((enabled, total)) => format!("Tools: {enabled}/{total} enabled"),
+                    None => "Tools: all defaults enabled".to_string(),
+                };
+                OverlayUpdate::close().with_mutations(vec![
+                    StateMutation::SetToolSelection { selection, summary },
+                    StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(message)),
+                ])
+            }
+            _ => OverlayUpdate::stay(),
+        }
+    }
+
+    /// Applies the toggles on top of `current`, keeping its base tool set.
+    fn selection(&self, current: &ToolSelection) -> ToolSelection {
+        let include: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|entry| entry.enabled && !contains_name(&self.baseline, &entry.name))
+            .map(|entry| entry.name.clone())
+            .collect();
+        let exclude: Vec<String> = self
+            .entries
+            .iter()
+            .filter(|entry| !entry.enabled && contains_name(&self.baseline, &entry.name))
+            .map(|entry| entry.name.clone())
+            .collect();
+
+        match current {
+            ToolSelection::Auto { base, .. } => ToolSelection::Auto {
+                base: *base,
+                include,
+                exclude,
+            },
+            ToolSelection::ToolSet { base, .. } => ToolSelection::ToolSet {
+                base: *base,
+                include,
+                exclude,
+            },
+            ToolSelection::Explicit(_) | ToolSelection::All => ToolSelection::Explicit(
+                self.entries
+                    .iter()
+                    .filter(|entry| entry.enabled)
+                    .map(|entry| entry.name.clone())
+                    .collect(),
+            ),
+        }
+    }
+
+    /// `(enabled, total)` when the toggles differ from the defaults.
+    fn summary(&self) -> Option<(usize, usize)> {
+        let differs = self
+            .entries
+            .iter()
+            .any(|entry| entry.enabled != contains_name(&self.baseline, &entry.name));
+        differs.then(|| {
+            let enabled = self.entries.iter().filter(|entry| entry.enabled).count();
+            (enabled, self.entries.len())
+        })
+    }
+}
+
+fn without_filter(selection: &ToolSelection) -> ToolSelection {
+    match selection {
+        ToolSelection::Auto { base, .. } => ToolSelection::Auto {
+            base: *base,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        },
+        ToolSelection::ToolSet { base, .. } => ToolSelection::ToolSet {
+            base: *base,
+            include: Vec::new(),
+            exclude: Vec::new(),
+        },
+        ToolSelection::Explicit(_) | ToolSelection::All => selection.clone(),
+    }
+}
+
+fn contains_name(names: &[String], name: &str) -> bool {
+    names.iter().any(|n| n.eq_ignore_ascii_case(name))
+}
+
+pub fn render_tool_picker(
+    frame: &mut Frame,
+    picker: &ToolPickerState,
+    area: Rect,
+    input_top_y: u16,
+) {
+    use super::render_utils::{InputHint, OverlayConfig, render_overlay, render_separator};
+
+    let picker_width = 70;
+    let picker_height = (picker.entries.len() as u16 + 5).clamp(7, 20);
+
+    let hints = [
+        InputHint::new("↑↓", "navigate"),
+        InputHint::new("Space", "toggle"),
+        InputHint::new("Enter", "apply"),
+        InputHint::new("Esc", "cancel"),
+    ];
+    let layout = render_overlay(
+        frame,
+        area,
+        input_top_y,
+        &OverlayConfig {
+            title: tr(Text::ToolsTitle),
+            border_color: Color::Magenta,
+            width: picker_width,
+            height: picker_height,
+            hints: &hints,
+        },
+    );
+
+    let list_height = layout.body.height.saturating_sub(1);
+    let list_area = Rect::new(layout.body.x, layout.body.y, layout.body.width, list_height);
+
+    let name_width = picker
+        .entries
+        .iter()
+        .map(|entry| entry.name.chars().count())
+        .max()
+        .unwrap_or(0);
+    // Available width: body - highlight "▶ " - checkbox "[x] " - name - gap.
+    let desc_width = (layout.body.width as usize).saturating_sub(2 + 4 + name_width + 1);
+
+    let items: Vec<ListItem> = picker
+        .entries
+        .iter()
+        .map(|entry| {
+            let (checkbox, name_style) = if entry.enabled {
+                (
+                    "[x] ",
+                    Style::default()
+                        .fg(Color::Cyan)
+                        .add_modifier(Modifier::BOLD),
+                )
+            } else {
+                ("[ ] ", Style::default().fg(Color::DarkGray))
+            };
+            let desc: String = entry.description.chars().take(desc_width).collect();
+            ListItem::new(Line::from(vec![
+                Span::raw(checkbox),
+                Span::styled(format!("{:<name_width$} ", entry.name), name_style),
+                Span::styled(desc, Style::default().fg(Color::DarkGray)),
+            ]))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .highlight_style(
+            Style::default()
+                .bg(Color::Magenta)
+                .fg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+
+    let mut list_state = ListState::default();
+    list_state.select(Some(picker.selected));
+    frame.render_stateful_widget(list, list_area, &mut list_state);
+
+    render_separator(frame, layout.body, list_height);
+}
+
+#[cfg(test)]
+mod tests {
+    use zdx_engine::tools::ToolSet;
+
+    use super::*;
+
+    fn picker(entries: &[(&str, bool)], baseline: &[&str]) -> ToolPickerState {
+        ToolPickerState {
+            entries: entries
+                .iter()
+                .map(|(name, enabled)| ToolEntry {
+                    name: (*name).to_string(),
+                    description: String::new(),
+                    enabled: *enabled,
+                })
+                .collect(),
+            selected: 0,
+            baseline: baseline.iter().map(|name| (*name).to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_selection_includes_and_excludes_relative_to_baseline() {
+        let picker = picker(
+            &[("Bash", false), ("Read", true), ("Apply_Patch", true)],
+            &["bash", "read"],
+        );
+        let ToolSelection::Auto {
+            base,
+            include,
+            exclude,
+        } = picker.selection(&ToolSelection::default())
+        else {
+            panic!("expected auto selection");
+        };
+        assert_eq!(base, ToolSet::Default);
+        assert_eq!(include, vec!["Apply_Patch".to_string()]);
+        assert_eq!(exclude, vec!["Bash".to_string()]);
+        assert_eq!(picker.summary(), Some((2, 3)));
+    }
+
+    #[test]
+    fn test_summary_is_none_when_matching_defaults() {
+        let picker = picker(&[("Bash", true), ("Apply_Patch", false)], &["Bash"]);
+        assert_eq!(picker.summary(), None);
+    }
+}
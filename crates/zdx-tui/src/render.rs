@@ -14,6 +14,7 @@ use ratatui::style::{Color, Style};
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph};
 
+use crate::common::i18n::{Text, tr};
 use crate::common::{Scrollbar, TaskKind, truncate_with_ellipsis};
 use crate::state::{AgentState, AppState, TuiState, TurnOutcome};
 use crate::statusline::render_debug_status_line;
@@ -51,8 +52,6 @@ pub fn render(app: &AppState, frame: &mut Frame) {
     let state = &app.tui;
     let show_tab_bar = app.tab_count() > 1;
     let metrics = compute_render_metrics(state, area, show_tab_bar);
-    let (visible_lines, total_lines, scroll_offset) =
-        build_visible_transcript_lines(state, metrics.transcript_width, metrics.transcript_height);
     let chunks = split_main_layout(area, &metrics, state.show_debug_status, show_tab_bar);
 
     // Tab bar (only when multiple tabs exist)
@@ -68,27 +67,212 @@ pub fn render(app: &AppState, frame: &mut Frame) {
         render_tab_bar(app, frame, chunks[tab_bar_idx]);
     }
 
-    // Transcript area with horizontal margins (also accounts for scrollbar)
+    if let Some(branches) = crate::compare::visible_branches(app) {
+        render_compare_branches(
+            branches,
+            app.tui.spinner_frame,
+            frame,
+            chunks[transcript_idx],
+        );
+        // Clicks and selection target the origin transcript, which is hidden.
+        state.transcript_area.set(Rect::default());
+    } else {
+        render_transcript_area(state, frame, chunks[transcript_idx], &metrics);
+    }
+
+    // Input area with model on top-left border and path on bottom-right
+    if metrics.queue_height > 0 {
+        render_queue_panel(
+            frame,
+            chunks[queue_idx],
+            &metrics.queue_summaries,
+            metrics.queue_total,
+        );
+    }
+
+    // Input area — hide cursor when an overlay is covering the screen
+    let show_input_cursor = app
+        .overlay
+        .as_ref()
+        .is_none_or(crate::overlays::Overlay::routes_input);
+    input::render_input_with_cursor(state, frame, chunks[input_idx], show_input_cursor);
+    state.input_area.set(chunks[input_idx]);
+
+    // Status line below input
+    render_status_line(state, frame, chunks[status_idx]);
+
+    // Debug status line (when enabled)
+    if state.show_debug_status {
+        let status_line = state.status_line.snapshot();
+        render_debug_status_line(&status_line, frame, chunks[debug_status_idx]);
+    }
+
+    // Render overlay (last, so it appears on top)
+    render_overlay_layer(app, frame, area, chunks[input_idx].y);
+}
+
+/// Renders the transcript with horizontal margins and a scrollbar.
+fn render_transcript_area(
+    state: &TuiState,
+    frame: &mut Frame,
+    area: Rect,
+    metrics: &RenderMetrics,
+) {
+    let (visible_lines, total_lines, scroll_offset) =
+        build_visible_transcript_lines(state, metrics.transcript_width, metrics.transcript_height);
+
     // NOTE: No .wrap() here - content is already pre-wrapped by render_transcript()
     // Adding wrap would cause double-wrapping and visual artifacts
     let transcript = Paragraph::new(visible_lines).block(Block::default().borders(Borders::NONE));
     let transcript_area = Rect {
-        x: chunks[transcript_idx].x + TRANSCRIPT_MARGIN,
-        y: chunks[transcript_idx].y,
-        width: chunks[transcript_idx]
+        x: area.x + TRANSCRIPT_MARGIN,
+        y: area.y,
+        width: area
             .width
             .saturating_sub(TRANSCRIPT_MARGIN * 2 + SCROLLBAR_WIDTH),
-        height: chunks[transcript_idx].height,
+        height: area.height,
     };
     frame.render_widget(transcript, transcript_area);
     state.transcript_area.set(transcript_area);
 
     frame.render_widget(
         Scrollbar::new(total_lines, metrics.transcript_height, scroll_offset),
-        chunks[transcript_idx],
+        area,
+    );
+}
+
+/// Renders `/compare` branches side by side, each with a model/status header,
+/// following the tail of its transcript.
+fn render_compare_branches(
+    branches: [&TuiState; 2],
+    spinner_frame: usize,
+    frame: &mut Frame,
+    area: Rect,
+) {
+    let [body, hint_area] = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .areas(area);
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(body);
+    let spinner =
+        SPINNER_FRAMES[(spinner_frame / transcript::SPINNER_SPEED_DIVISOR) % SPINNER_FRAMES.len()];
+
+    for (index, (tab, column)) in branches.iter().zip(columns.iter()).enumerate() {
+        let block = if index == 0 {
+            Block::default().borders(Borders::NONE)
+        } else {
+            Block::default()
+                .borders(Borders::LEFT)
+                .border_style(Style::default().fg(Color::DarkGray))
+        };
+        let inner = block.inner(*column);
+        frame.render_widget(block, *column);
+
+        let (glyph, color) = if tab.agent_state.is_running() {
+            (spinner, Color::Yellow)
+        } else {
+            match tab.last_turn_outcome {
+                Some(TurnOutcome::Failed) => ("✗", Color::Red),
+                Some(TurnOutcome::Succeeded) => ("✓", Color::Green),
+                None => ("·", Color::DarkGray),
+            }
+        };
+        let header = Line::from(vec![
+            Span::styled(
+                format!(" {} ", index + 1),
+                Style::default().fg(Color::Black).bg(Color::Cyan),
+            ),
+            Span::raw(" "),
+            Span::styled(
+                truncate_with_ellipsis(&tab.config.model, inner.width.saturating_sub(6) as usize),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(format!(" {glyph}"), Style::default().fg(color)),
+        ]);
+        let [header_area, lines_area] = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1), Constraint::Min(0)])
+            .areas(inner);
+        frame.render_widget(
+            Paragraph::new(header),
+            Rect {
+                x: header_area.x + TRANSCRIPT_MARGIN,
+                width: header_area.width.saturating_sub(TRANSCRIPT_MARGIN),
+                ..header_area
+            },
+        );
+
+        let lines_area = Rect {
+            x: lines_area.x + TRANSCRIPT_MARGIN,
+            width: lines_area.width.saturating_sub(TRANSCRIPT_MARGIN * 2),
+            ..lines_area
+        };
+        // Branch tabs are never scrolled, so this follows the streaming tail.
+        let (lines, _, _) = build_visible_transcript_lines(
+            tab,
+            lines_area.width as usize,
+            lines_area.height as usize,
+        );
+        frame.render_widget(Paragraph::new(lines), lines_area);
+    }
+
+    frame.render_widget(
+        Paragraph::new(Line::from(Span::styled(
+            " /compare 1 or /compare 2 continues that branch · /compare off discards both",
+            Style::default().fg(Color::DarkGray),
+        ))),
+        hint_area,
+    );
+}
+
+/// Renders the inline-mode viewport (`zdx --inline`).
+///
+/// Cells before `live_from` were already printed to scrollback, so only the
+/// live tail of the transcript is drawn, above the input and status line.
+pub fn render_inline(app: &AppState, frame: &mut Frame, live_from: usize) {
+    let area = frame.area();
+    let state = &app.tui;
+    let show_tab_bar = app.tab_count() > 1;
+    let metrics = compute_render_metrics(state, area, show_tab_bar);
+    let chunks = split_main_layout(area, &metrics, state.show_debug_status, show_tab_bar);
+
+    let transcript_idx = usize::from(show_tab_bar);
+    let queue_idx = transcript_idx + 1;
+    let input_idx = queue_idx + 1;
+    let status_idx = input_idx + 1;
+
+    if show_tab_bar {
+        render_tab_bar(app, frame, chunks[0]);
+    }
+
+    let width = area.width.saturating_sub(TRANSCRIPT_MARGIN * 2) as usize;
+    let spinner_frame = state.spinner_frame / transcript::SPINNER_SPEED_DIVISOR;
+    let mut lines: Vec<Line<'static>> = Vec::new();
+    for cell in state.transcript.cells().iter().skip(live_from) {
+        lines.extend(
+            cell.display_lines(width, spinner_frame)
+                .iter()
+                .map(transcript::convert_styled_line),
+        );
+        lines.push(Line::default());
+    }
+    let height = metrics.transcript_height;
+    let tail = lines.split_off(lines.len().saturating_sub(height));
+    let transcript_area = Rect {
+        x: chunks[transcript_idx].x + TRANSCRIPT_MARGIN,
+        width: chunks[transcript_idx]
+            .width
+            .saturating_sub(TRANSCRIPT_MARGIN * 2),
+        ..chunks[transcript_idx]
+    };
+    frame.render_widget(
+        Paragraph::new(bottom_align_lines(tail, height)),
+        transcript_area,
     );
 
-    // Input area with model on top-left border and path on bottom-right
     if metrics.queue_height > 0 {
         render_queue_panel(
             frame,
@@ -97,46 +281,57 @@ pub fn render(app: &AppState, frame: &mut Frame) {
             metrics.queue_total,
         );
     }
-
-    // Input area — hide cursor when an overlay is covering the screen
-    let show_input_cursor = app.overlay.is_none();
-    input::render_input_with_cursor(state, frame, chunks[input_idx], show_input_cursor);
+    input::render_input_with_cursor(
+        state,
+        frame,
+        chunks[input_idx],
+        app.overlay
+            .as_ref()
+            .is_none_or(crate::overlays::Overlay::routes_input),
+    );
     state.input_area.set(chunks[input_idx]);
-
-    // Status line below input
     render_status_line(state, frame, chunks[status_idx]);
-
-    // Debug status line (when enabled)
     if state.show_debug_status {
         let status_line = state.status_line.snapshot();
-        render_debug_status_line(&status_line, frame, chunks[debug_status_idx]);
+        render_debug_status_line(&status_line, frame, chunks[status_idx + 1]);
     }
 
-    // Render overlay (last, so it appears on top)
-    // ToolDetail, Tldr, and Context need special handling: they look up
-    // live state and animate spinners, so we route them around the
-    // generic Overlay::render.
-    if let Some(ref overlay) = app.overlay {
-        match overlay {
-            crate::overlays::Overlay::ToolDetail(state) => {
-                let cell = app.tui.transcript.cells().iter().find(|c| {
-                    matches!(
-                        c,
-                        transcript::HistoryCell::Tool { tool_use_id, .. }
-                            if *tool_use_id == state.tool_use_id
-                    )
-                });
-                state.render(frame, area, cell, app.tui.spinner_frame);
-            }
-            crate::overlays::Overlay::Tldr(state) => {
-                state.render(frame, area, chunks[input_idx].y, app.tui.spinner_frame);
-            }
-            crate::overlays::Overlay::Context(state) => {
-                state.render(frame, area, chunks[input_idx].y, app.tui.spinner_frame);
-            }
-            _ => {
-                overlay.render(frame, area, chunks[input_idx].y, &app.tui.tasks);
-            }
+    render_overlay_layer(app, frame, area, chunks[input_idx].y);
+}
+
+/// Renders the active overlay on top of everything else, or the `/tour`
+/// callout when no overlay is open.
+///
+/// `ToolDetail`, `Tldr`, `Context`, and `Improve` need special handling: they look up
+/// live state and animate spinners, so we route them around the generic
+/// `Overlay::render`.
+fn render_overlay_layer(app: &AppState, frame: &mut Frame, area: Rect, input_y: u16) {
+    let Some(ref overlay) = app.overlay else {
+        crate::tour::render_callout(app, frame, area, input_y);
+        return;
+    };
+    match overlay {
+        crate::overlays::Overlay::ToolDetail(state) => {
+            let cell = app.tui.transcript.cells().iter().find(|c| {
+                matches!(
+                    c,
+                    transcript::HistoryCell::Tool { tool_use_id, .. }
+                        if *tool_use_id == state.tool_use_id
+                )
+            });
+            state.render(frame, area, cell, app.tui.spinner_frame);
+        }
+        crate::overlays::Overlay::Tldr(state) => {
+            state.render(frame, area, input_y, app.tui.spinner_frame);
+        }
+        crate::overlays::Overlay::Context(state) => {
+            state.render(frame, area, input_y, app.tui.spinner_frame);
+        }
+        crate::overlays::Overlay::Improve(state) => {
+            state.render(frame, area, app.tui.spinner_frame);
+        }
+        _ => {
+            overlay.render(frame, area, input_y, &app.tui.tasks);
         }
     }
 }
@@ -336,6 +531,34 @@ fn format_elapsed(d: std::time::Duration) -> String {
     }
 }
 
+/// Claude subscription usage for the tightest window (e.g. `5h 42% · resets
+/// in 1h 20m`), once a response has reported it.
+fn usage_limit_span(state: &TuiState) -> Option<Span<'static>> {
+    use zdx_engine::providers::subscription_quota::{PROVIDER_CLAUDE, format_reset_in};
+    use zdx_engine::providers::{ProviderKind, provider_for_model, usage_limits};
+
+    if provider_for_model(&state.config.model) != ProviderKind::ClaudeCli {
+        return None;
+    }
+    let observed = usage_limits::observed(PROVIDER_CLAUDE)?;
+    let window = usage_limits::tightest_window(&observed.quota)?;
+    let color = if window.used_percent >= 95.0 {
+        Color::Red
+    } else if window.used_percent >= 80.0 {
+        Color::Yellow
+    } else {
+        Color::DarkGray
+    };
+    let reset = window
+        .resets_at
+        .map(|dt| format!(" · {}", format_reset_in(dt)))
+        .unwrap_or_default();
+    Some(Span::styled(
+        format!("  {} {:.0}%{reset}", window.label, window.used_percent),
+        Style::default().fg(color),
+    ))
+}
+
 /// Renders the status line below the input.
 fn render_status_line(state: &TuiState, frame: &mut Frame, area: Rect) {
     let spinner_idx =
@@ -347,7 +570,7 @@ fn render_status_line(state: &TuiState, frame: &mut Frame, area: Rect) {
     let elapsed_span = elapsed.map(|d| format!(" ({})", format_elapsed(d)));
 
     // Check for bash execution first (takes priority over idle state)
-    let spans: Vec<Span> = if state.input.voice.is_recording() {
+    let mut spans: Vec<Span> = if state.input.voice.is_recording() {
         // Blink the recording dot ~ once per second so the user can tell
         // recording is alive (and not frozen).
         let dot_color = if (state.spinner_frame / 30).is_multiple_of(2) {
@@ -358,94 +581,78 @@ fn render_status_line(state: &TuiState, frame: &mut Frame, area: Rect) {
         vec![
             Span::styled("●", Style::default().fg(dot_color)),
             Span::raw(" "),
-            Span::styled("Recording voice...", Style::default().fg(Color::Red)),
+            Span::styled(tr(Text::RecordingVoice), Style::default().fg(Color::Red)),
             Span::raw("  "),
             Span::styled("Esc", Style::default().fg(Color::DarkGray)),
-            Span::raw(" to cancel"),
+            Span::raw(tr(Text::ToCancel)),
         ]
     } else if state.input.voice.is_transcribing() {
-        vec![
-            Span::styled(spinner, Style::default().fg(Color::Cyan)),
-            Span::raw(" "),
-            Span::styled("Transcribing voice...", Style::default().fg(Color::Cyan)),
-            Span::raw("  "),
-            Span::styled("Esc", Style::default().fg(Color::DarkGray)),
-            Span::raw(" to cancel"),
-        ]
+        busy_status_spans(spinner, tr(Text::TranscribingVoice), Color::Cyan, None)
     } else if state.tasks.state(TaskKind::Bash).is_running() {
-        let mut spans = vec![
-            Span::styled(spinner, Style::default().fg(Color::Green)),
-            Span::raw(" "),
-            Span::styled("Running bash...", Style::default().fg(Color::Green)),
-        ];
-        if let Some(ref elapsed) = elapsed_span {
-            spans.push(Span::styled(
-                elapsed.clone(),
-                Style::default().fg(Color::DarkGray),
-            ));
-        }
-        spans.extend([
-            Span::raw("  "),
-            Span::styled("Esc", Style::default().fg(Color::DarkGray)),
-            Span::raw(" to cancel"),
-        ]);
-        spans
+        busy_status_spans(spinner, tr(Text::RunningBash), Color::Green, elapsed_span)
     } else {
         match &state.agent_state {
             AgentState::Idle => {
                 // Show helpful shortcuts when idle
                 vec![
                     Span::styled("Ctrl+O", Style::default().fg(Color::DarkGray)),
-                    Span::raw(" commands  "),
+                    Span::raw(tr(Text::CommandsHint)),
                     Span::styled("Ctrl+C", Style::default().fg(Color::DarkGray)),
-                    Span::raw(" quit"),
+                    Span::raw(tr(Text::QuitHint)),
                 ]
             }
             AgentState::Waiting { .. } => {
-                let mut spans = vec![
-                    Span::styled(spinner, Style::default().fg(Color::Yellow)),
-                    Span::raw(" "),
-                    Span::styled("Waiting...", Style::default().fg(Color::Yellow)),
-                ];
-                if let Some(ref elapsed) = elapsed_span {
-                    spans.push(Span::styled(
-                        elapsed.clone(),
-                        Style::default().fg(Color::DarkGray),
-                    ));
-                }
-                spans.extend([
-                    Span::raw("  "),
-                    Span::styled("Esc", Style::default().fg(Color::DarkGray)),
-                    Span::raw(" to cancel"),
-                ]);
-                spans
+                busy_status_spans(spinner, tr(Text::Waiting), Color::Yellow, elapsed_span)
             }
             AgentState::Streaming { .. } => {
-                let mut spans = vec![
-                    Span::styled(spinner, Style::default().fg(Color::Cyan)),
-                    Span::raw(" "),
-                    Span::styled("Streaming...", Style::default().fg(Color::Cyan)),
-                ];
-                if let Some(ref elapsed) = elapsed_span {
-                    spans.push(Span::styled(
-                        elapsed.clone(),
-                        Style::default().fg(Color::DarkGray),
-                    ));
-                }
-                spans.extend([
-                    Span::raw("  "),
-                    Span::styled("Esc", Style::default().fg(Color::DarkGray)),
-                    Span::raw(" to cancel"),
-                ]);
-                spans
+                busy_status_spans(spinner, tr(Text::Streaming), Color::Cyan, elapsed_span)
             }
         }
     };
 
+    if state.thread.incognito {
+        spans.push(Span::styled(
+            "  incognito",
+            Style::default().fg(Color::Magenta),
+        ));
+    }
+
+    if let Some((enabled, total)) = state.tool_filter {
+        spans.push(Span::styled(
+            format!("  tools: {enabled}/{total}"),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+
+    spans.extend(usage_limit_span(state));
+
     let status = Paragraph::new(Line::from(spans)).alignment(Alignment::Left);
     frame.render_widget(status, area);
 }
 
+/// Builds "<spinner> <label> (<elapsed>)  Esc to cancel" status spans.
+fn busy_status_spans(
+    spinner: &'static str,
+    label: &'static str,
+    color: Color,
+    elapsed: Option<String>,
+) -> Vec<Span<'static>> {
+    let mut spans = vec![
+        Span::styled(spinner, Style::default().fg(color)),
+        Span::raw(" "),
+        Span::styled(label, Style::default().fg(color)),
+    ];
+    if let Some(elapsed) = elapsed {
+        spans.push(Span::styled(elapsed, Style::default().fg(Color::DarkGray)));
+    }
+    spans.extend([
+        Span::raw("  "),
+        Span::styled("Esc", Style::default().fg(Color::DarkGray)),
+        Span::raw(tr(Text::ToCancel)),
+    ]);
+    spans
+}
+
 /// Renders the queued prompt summary panel between transcript and input.
 fn render_queue_panel(frame: &mut Frame, area: Rect, summaries: &[String], total: usize) {
     if summaries.is_empty() || area.height == 0 {
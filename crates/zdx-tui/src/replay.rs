@@ -0,0 +1,415 @@
+//! Thread replay viewer (`zdx threads replay <ID>`).
+//!
+//! Replays a saved thread's events with their recorded timing, rendered with
+//! the shared transcript renderer. Threads only store one timestamp per event
+//! (often flushed together at the end of a turn), so the gap before each
+//! group of same-timestamp events is spread across the group by estimated
+//! duration, and long idle gaps are capped. Assistant text and reasoning
+//! stream in over their share of the gap; tool calls show as running until
+//! their result arrives.
+
+use std::io::Stdout;
+use std::time::{Duration, Instant};
+
+use anyhow::{Context, Result};
+use chrono::DateTime;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::Terminal;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use zdx_engine::core::thread_persistence::ThreadEvent;
+
+use crate::terminal::{install_panic_hook, restore_terminal, setup_terminal};
+
+/// Frame interval while playing.
+const TICK: Duration = Duration::from_millis(33);
+/// Estimated streaming rate for text without usable timestamps.
+const STREAM_CHARS_PER_SEC: f64 = 120.0;
+/// Estimated duration of a tool call without usable timestamps.
+const TOOL_ESTIMATE: Duration = Duration::from_millis(600);
+/// Estimated pause before a user message.
+const USER_ESTIMATE: Duration = Duration::from_millis(400);
+/// Events whose timestamps are this close are treated as flushed together.
+const SAME_FLUSH_TOLERANCE: Duration = Duration::from_millis(250);
+const MIN_SPEED: f64 = 0.25;
+const MAX_SPEED: f64 = 64.0;
+
+/// Replay settings.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayOptions {
+    /// Playback speed multiplier (2.0 plays twice as fast).
+    pub speed: f64,
+    /// Longest pause before any single event, in recorded time.
+    pub max_gap: Duration,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            speed: 1.0,
+            max_gap: Duration::from_secs(3),
+        }
+    }
+}
+
+/// Whether an event's text streams in during its delay instead of appearing
+/// at the end of it.
+fn is_streaming(event: &ThreadEvent) -> bool {
+    match event {
+        ThreadEvent::Message { role, .. } => role == "assistant",
+        ThreadEvent::Reasoning { text, .. } => text.is_some(),
+        _ => false,
+    }
+}
+
+fn event_ts(event: &ThreadEvent) -> &str {
+    match event {
+        ThreadEvent::Meta { ts, .. }
+        | ThreadEvent::Message { ts, .. }
+        | ThreadEvent::ToolUse { ts, .. }
+        | ThreadEvent::ToolResult { ts, .. }
+        | ThreadEvent::Interrupted { ts, .. }
+        | ThreadEvent::Reasoning { ts, .. }
+        | ThreadEvent::Usage { ts, .. }
+        | ThreadEvent::Notice { ts, .. } => ts,
+    }
+}
+
+/// Duration an event would take with no timestamps to go on.
+fn estimate(event: &ThreadEvent) -> Duration {
+    let streamed =
+        |text: &str| Duration::from_secs_f64(text.chars().count() as f64 / STREAM_CHARS_PER_SEC);
+    match event {
+        ThreadEvent::Message { role, text, .. } if role == "assistant" => streamed(text),
+        ThreadEvent::Message { .. } => USER_ESTIMATE,
+        ThreadEvent::Reasoning {
+            text: Some(text), ..
+        } => streamed(text),
+        ThreadEvent::ToolResult { .. } => TOOL_ESTIMATE,
+        _ => Duration::ZERO,
+    }
+}
+
+/// Computes the recorded-time delay before each event finishes appearing.
+///
+/// Consecutive events with (nearly) the same timestamp form a group; the gap
+/// since the previous group is split across the group in proportion to each
+/// event's [`estimate`]. Groups without a usable gap fall back to the
+/// estimates. Every delay is capped at `max_gap`.
+fn plan_delays(events: &[ThreadEvent], max_gap: Duration) -> Vec<Duration> {
+    let times: Vec<Option<DateTime<chrono::FixedOffset>>> = events
+        .iter()
+        .map(|event| DateTime::parse_from_rfc3339(event_ts(event)).ok())
+        .collect();
+    let estimates: Vec<Duration> = events.iter().map(estimate).collect();
+    let tolerance = chrono::Duration::from_std(SAME_FLUSH_TOLERANCE).unwrap_or_default();
+
+    let mut delays = estimates.clone();
+    let mut previous_time: Option<DateTime<chrono::FixedOffset>> = None;
+    let mut start = 0;
+    while start < events.len() {
+        let mut end = start + 1;
+        if let Some(time) = times[start] {
+            while end < events.len()
+                && times[end].is_some_and(|next| (next - time).abs() <= tolerance)
+            {
+                end += 1;
+            }
+        }
+
+        let group_time = times[end - 1];
+        let gap = match (previous_time, group_time) {
+            (Some(prev), Some(time)) => (time - prev).to_std().ok(),
+            _ => None,
+        };
+        let total_estimate: Duration = estimates[start..end].iter().sum();
+        if let Some(gap) = gap.filter(|gap| *gap > total_estimate) {
+            let count = (end - start) as u32;
+            for idx in start..end {
+                delays[idx] = if total_estimate.is_zero() {
+                    gap / count
+                } else {
+                    gap.mul_f64(estimates[idx].as_secs_f64() / total_estimate.as_secs_f64())
+                };
+            }
+        }
+        if group_time.is_some() {
+            previous_time = group_time;
+        }
+        start = end;
+    }
+
+    for delay in &mut delays {
+        *delay = (*delay).min(max_gap);
+    }
+    delays
+}
+
+/// Takes the first `fraction` of `text`'s characters.
+fn text_prefix(text: &str, fraction: f64) -> String {
+    let count = text.chars().count();
+    let shown = ((count as f64) * fraction.clamp(0.0, 1.0)).round() as usize;
+    text.chars().take(shown).collect()
+}
+
+/// Returns `event` with only the first `fraction` of its text revealed.
+fn partial_event(event: &ThreadEvent, fraction: f64) -> ThreadEvent {
+    let mut partial = event.clone();
+    if let ThreadEvent::Message { text, .. }
+    | ThreadEvent::Reasoning {
+        text: Some(text), ..
+    } = &mut partial
+    {
+        *text = text_prefix(text, fraction);
+    }
+    partial
+}
+
+/// Playback position and controls.
+struct Replay {
+    thread_id: String,
+    events: Vec<ThreadEvent>,
+    delays: Vec<Duration>,
+    /// Events fully shown.
+    shown: usize,
+    /// Recorded time spent so far on the next event.
+    elapsed: Duration,
+    speed: f64,
+    paused: bool,
+}
+
+impl Replay {
+    fn new(thread_id: String, events: Vec<ThreadEvent>, options: ReplayOptions) -> Self {
+        let delays = plan_delays(&events, options.max_gap);
+        Self {
+            thread_id,
+            events,
+            delays,
+            shown: 0,
+            elapsed: Duration::ZERO,
+            speed: options.speed.clamp(MIN_SPEED, MAX_SPEED),
+            paused: false,
+        }
+    }
+
+    fn is_finished(&self) -> bool {
+        self.shown >= self.events.len()
+    }
+
+    /// Advances playback by `real` wall-clock time.
+    fn advance(&mut self, real: Duration) {
+        if self.paused {
+            return;
+        }
+        self.elapsed += real.mul_f64(self.speed);
+        while let Some(delay) = self.delays.get(self.shown) {
+            if self.elapsed < *delay {
+                break;
+            }
+            self.elapsed -= *delay;
+            self.shown += 1;
+        }
+        if self.is_finished() {
+            self.elapsed = Duration::ZERO;
+        }
+    }
+
+    fn seek(&mut self, shown: usize) {
+        self.shown = shown.min(self.events.len());
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Events to render: everything shown plus the streaming prefix of the
+    /// next event.
+    fn visible_events(&self) -> Vec<ThreadEvent> {
+        let mut visible = self.events[..self.shown].to_vec();
+        if let Some(next) = self.events.get(self.shown)
+            && is_streaming(next)
+        {
+            let delay = self.delays[self.shown].as_secs_f64();
+            let fraction = if delay > 0.0 {
+                self.elapsed.as_secs_f64() / delay
+            } else {
+                0.0
+            };
+            visible.push(partial_event(next, fraction));
+        }
+        visible
+    }
+
+    /// Returns `true` when the viewer should exit.
+    fn handle_key(&mut self, code: KeyCode) -> bool {
+        match code {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Char(' ') => self.paused = !self.paused,
+            KeyCode::Right | KeyCode::Char('n') => self.seek(self.shown + 1),
+            KeyCode::Left | KeyCode::Char('p') => self.seek(self.shown.saturating_sub(1)),
+            KeyCode::Char('+' | '=') => self.speed = (self.speed * 2.0).min(MAX_SPEED),
+            KeyCode::Char('-') => self.speed = (self.speed / 2.0).max(MIN_SPEED),
+            KeyCode::Home | KeyCode::Char('g') => self.seek(0),
+            KeyCode::End | KeyCode::Char('G') => self.seek(self.events.len()),
+            _ => {}
+        }
+        false
+    }
+
+    fn status_line(&self) -> Line<'static> {
+        let state = if self.is_finished() {
+            "■ done"
+        } else if self.paused {
+            "⏸ paused"
+        } else {
+            "▶ playing"
+        };
+        let recorded = self
+            .shown
+            .checked_sub(1)
+            .and_then(|idx| self.events.get(idx))
+            .and_then(|event| DateTime::parse_from_rfc3339(event_ts(event)).ok())
+            .map(|time| format!(" · {}", time.format("%Y-%m-%d %H:%M:%S")))
+            .unwrap_or_default();
+        Line::from(vec![
+            Span::styled(
+                format!(" Replay {} ", self.thread_id),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(
+                format!(
+                    "{state} · {}/{} events · {}x{recorded}",
+                    self.shown,
+                    self.events.len(),
+                    self.speed
+                ),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                "  space pause · ←/→ step · +/- speed · g/G start/end · q quit",
+                Style::default().fg(Color::DarkGray),
+            ),
+        ])
+    }
+}
+
+/// Runs the replay viewer for `events` until the user quits.
+///
+/// # Errors
+/// Returns an error if the terminal cannot be set up or read.
+pub fn run_thread_replay(
+    thread_id: &str,
+    events: Vec<ThreadEvent>,
+    options: ReplayOptions,
+) -> Result<()> {
+    install_panic_hook();
+    let mut terminal = setup_terminal()?;
+    let result = replay_loop(
+        &mut terminal,
+        Replay::new(thread_id.to_string(), events, options),
+    );
+    restore_terminal()?;
+    result
+}
+
+fn replay_loop(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    mut replay: Replay,
+) -> Result<()> {
+    let mut last_frame = Instant::now();
+    loop {
+        replay.advance(last_frame.elapsed());
+        last_frame = Instant::now();
+
+        let visible = replay.visible_events();
+        terminal.draw(|frame| {
+            let [body, status] =
+                Layout::vertical([Constraint::Min(1), Constraint::Length(1)]).areas(frame.area());
+            let cells = zdx_transcript::build_transcript_from_events(&visible);
+            let lines = zdx_transcript::cells_to_lines(&cells, usize::from(body.width).max(1));
+            let skip = lines.len().saturating_sub(usize::from(body.height));
+            frame.render_widget(
+                Paragraph::new(lines.into_iter().skip(skip).collect::<Vec<_>>()),
+                body,
+            );
+            frame.render_widget(Paragraph::new(replay.status_line()), status);
+        })?;
+
+        if event::poll(TICK).context("poll events")?
+            && let Event::Key(key) = event::read().context("read event")?
+            && key.kind == KeyEventKind::Press
+            && replay.handle_key(key.code)
+        {
+            return Ok(());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn at(mut event: ThreadEvent, ts: &str) -> ThreadEvent {
+        match &mut event {
+            ThreadEvent::Message { ts: t, .. }
+            | ThreadEvent::ToolUse { ts: t, .. }
+            | ThreadEvent::ToolResult { ts: t, .. } => *t = ts.to_string(),
+            _ => {}
+        }
+        event
+    }
+
+    #[test]
+    fn test_plan_delays_spreads_flushed_groups_and_caps_idle_gaps() {
+        let events = vec![
+            at(ThreadEvent::user_message("hi"), "2026-01-01T10:00:00Z"),
+            // Flushed together at the end of a 10s turn.
+            at(
+                ThreadEvent::tool_use("t1", "Read", json!({})),
+                "2026-01-01T10:00:10Z",
+            ),
+            at(
+                ThreadEvent::tool_result("t1", json!({"ok": true}), true),
+                "2026-01-01T10:00:10Z",
+            ),
+            at(
+                ThreadEvent::assistant_message("x".repeat(240)),
+                "2026-01-01T10:00:10Z",
+            ),
+            // The user came back an hour later.
+            at(ThreadEvent::user_message("again"), "2026-01-01T11:00:10Z"),
+        ];
+        let delays = plan_delays(&events, Duration::from_secs(30));
+
+        // 600ms tool estimate + 2s streaming estimate share the 10s gap.
+        assert_eq!(delays[1], Duration::ZERO);
+        let tool = delays[2].as_secs_f64();
+        let text = delays[3].as_secs_f64();
+        assert!((tool + text - 10.0).abs() < 0.01);
+        assert!(text > tool);
+        assert_eq!(delays[4], Duration::from_secs(30));
+    }
+
+    #[test]
+    fn test_replay_streams_assistant_text() {
+        let events = vec![
+            ThreadEvent::user_message("hi"),
+            ThreadEvent::assistant_message("abcdefghij"),
+        ];
+        let mut replay = Replay::new("t".to_string(), events, ReplayOptions::default());
+        replay.seek(1);
+        replay.elapsed = replay.delays[1] / 2;
+
+        let visible = replay.visible_events();
+        assert!(matches!(
+            &visible[1],
+            ThreadEvent::Message { text, .. } if text == "abcde"
+        ));
+
+        replay.advance(Duration::from_secs(10));
+        assert!(replay.is_finished());
+        assert!(replay.handle_key(KeyCode::Char('q')));
+    }
+}
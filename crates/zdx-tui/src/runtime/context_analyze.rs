@@ -24,7 +24,7 @@ use zdx_engine::core::agent::{AgentOptions, resolve_active_tools};
 use zdx_engine::core::context::build_effective_system_prompt_with_paths_and_instruction_layers;
 use zdx_engine::models::ModelOption;
 use zdx_engine::providers::anthropic::{AnthropicClient, AnthropicConfig};
-use zdx_engine::providers::{ChatMessage, ProviderKind};
+use zdx_engine::providers::{ChatContentBlock, ChatMessage, ProviderKind};
 use zdx_types::ToolDefinition;
 
 use crate::events::UiEvent;
@@ -81,7 +81,7 @@ pub async fn analyze_context(
     messages: Vec<ChatMessage>,
     mode: AnalysisMode,
 ) -> UiEvent {
-    let result = run(model_id, config, agent_opts, messages, mode)
+    let result = Box::pin(run(model_id, config, agent_opts, messages, mode))
         .await
         .map_err(|e| e.to_string());
     UiEvent::ContextResult { result }
@@ -224,41 +224,43 @@ fn compute_char_counts(
 }
 
 fn chat_message_chars(msg: &ChatMessage) -> u64 {
-    use zdx_engine::providers::{ChatContentBlock, MessageContent};
+    use zdx_engine::providers::MessageContent;
     let role = msg.role.len() as u64;
     let body = match &msg.content {
         MessageContent::Text(t) => t.len() as u64,
-        MessageContent::Blocks(blocks) => blocks
-            .iter()
-            .map(|b| match b {
-                ChatContentBlock::Text { text, .. } => text.len() as u64,
-                ChatContentBlock::Reasoning(r) => r.text.as_deref().map_or(0, |s| s.len() as u64),
-                ChatContentBlock::ToolUse { name, input, .. } => {
-                    name.len() as u64 + serde_json::to_string(input).map_or(0, |s| s.len() as u64)
-                }
-                ChatContentBlock::ToolResult(result) => {
-                    use zdx_types::{ToolResultBlock, ToolResultContent};
-                    match &result.content {
-                        ToolResultContent::Text(t) => t.len() as u64,
-                        ToolResultContent::Blocks(blocks) => blocks
-                            .iter()
-                            .map(|b| match b {
-                                ToolResultBlock::Text { text } => text.len() as u64,
-                                // Images aren't text; report 0 chars. The
-                                // Tokens view (via Anthropic) reflects
-                                // their real cost.
-                                ToolResultBlock::Image { .. } => 0,
-                            })
-                            .sum(),
-                    }
-                }
-                ChatContentBlock::Image { .. } => 0,
-            })
-            .sum(),
+        MessageContent::Blocks(blocks) => blocks.iter().map(content_block_chars).sum(),
     };
     role + body
 }
 
+/// Character count of one message content block, as used by the chars
+/// heuristic. Also used by `/stats` to rank context contributors.
+pub(crate) fn content_block_chars(block: &ChatContentBlock) -> u64 {
+    match block {
+        ChatContentBlock::Text { text, .. } => text.len() as u64,
+        ChatContentBlock::Reasoning(r) => r.text.as_deref().map_or(0, |s| s.len() as u64),
+        ChatContentBlock::ToolUse { name, input, .. } => {
+            name.len() as u64 + serde_json::to_string(input).map_or(0, |s| s.len() as u64)
+        }
+        ChatContentBlock::ToolResult(result) => {
+            use zdx_types::{ToolResultBlock, ToolResultContent};
+            match &result.content {
+                ToolResultContent::Text(t) => t.len() as u64,
+                ToolResultContent::Blocks(blocks) => blocks
+                    .iter()
+                    .map(|b| match b {
+                        ToolResultBlock::Text { text } => text.len() as u64,
+                        // Images aren't text; report 0 chars. The Tokens view
+                        // (via Anthropic) reflects their real cost.
+                        ToolResultBlock::Image { .. } => 0,
+                    })
+                    .sum(),
+            }
+        }
+        ChatContentBlock::Image { .. } => 0,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tokens path: exact counts via /v1/messages/count_tokens
 // ---------------------------------------------------------------------------
@@ -0,0 +1,48 @@
+//! `/debug request` handler.
+//!
+//! Builds the provider request the next turn would send (via
+//! `core::agent::dry_run_request`) without sending it, and emits a
+//! `UiEvent::DebugRequestResult` carrying the pretty-printed payload with
+//! credentials redacted.
+
+use zdx_engine::config::Config;
+use zdx_engine::core::agent::{AgentOptions, dry_run_request};
+use zdx_engine::providers::ChatMessage;
+
+use crate::events::UiEvent;
+
+/// Stand-in user message for an empty thread; providers reject empty input.
+const PLACEHOLDER_MESSAGE: &str = ".";
+
+/// Builds the redacted request for the current thread state.
+pub async fn build_debug_request(
+    config: Config,
+    agent_opts: AgentOptions,
+    mut messages: Vec<ChatMessage>,
+    system_prompt: Option<String>,
+    thread_id: Option<String>,
+) -> UiEvent {
+    let placeholder = messages.is_empty();
+    if placeholder {
+        messages.push(ChatMessage::user(PLACEHOLDER_MESSAGE));
+    }
+    let result = dry_run_request(
+        &messages,
+        &config,
+        &agent_opts,
+        system_prompt.as_deref(),
+        thread_id.as_deref(),
+    )
+    .await
+    .map(|request| {
+        let mut text = String::from("Provider request (not sent, secrets redacted):\n");
+        if placeholder {
+            text.push_str("Thread is empty; a placeholder user message was used.\n");
+        }
+        text.push('\n');
+        text.push_str(&request.to_pretty_string());
+        text
+    })
+    .map_err(|err| format!("Failed to build request: {err:#}"));
+    UiEvent::DebugRequestResult { result }
+}
@@ -0,0 +1,34 @@
+//! `/duel` turn handler.
+//!
+//! Thin TUI adapter over `zdx_engine::core::duel`: runs one speaker's turn
+//! and wraps the outcome in a `UiEvent` for `crate::duel`.
+
+use tokio_util::sync::CancellationToken;
+use zdx_engine::config::Config;
+use zdx_engine::core::agent::AgentOptions;
+use zdx_engine::core::duel::{Duel, DuelSide, run_duel_turn};
+
+use crate::events::UiEvent;
+
+/// Runs `side`'s next turn and returns `UiEvent::DuelTurnResult`;
+/// cancellation is cooperative via the supplied token.
+pub async fn duel_turn(
+    duel: Duel,
+    side: DuelSide,
+    config: Config,
+    agent_opts: AgentOptions,
+    thread_id: Option<String>,
+    cancel: Option<CancellationToken>,
+) -> UiEvent {
+    let result = run_duel_turn(
+        &duel,
+        side,
+        &config,
+        &agent_opts,
+        thread_id.as_deref(),
+        cancel,
+    )
+    .await
+    .map_err(|err| format!("{err:#}"));
+    UiEvent::DuelTurnResult { side, result }
+}
@@ -1,5 +1,8 @@
 use anyhow::Context;
 use tokio_util::sync::CancellationToken;
+use zdx_engine::config::ThinkingLevel;
+use zdx_engine::core::ask_user::AskUserHandle;
+use zdx_engine::core::event_bus::{EventBus, EventFilter};
 use zdx_engine::core::thread_persistence::{self, ThreadEvent};
 use zdx_engine::providers::ChatMessage;
 
@@ -13,36 +16,90 @@ pub fn interrupt_agent(tui: &TuiState) {
     }
 }
 
+/// Requests a soft stop: running tools finish, then the model wraps up.
+pub fn soft_stop_agent(tui: &TuiState) {
+    if let Some(soft_stop) = tui.agent_state.soft_stop_token() {
+        soft_stop.cancel();
+    }
+}
+
+/// True when the current model selects a different `[[prompt_variants]]`
+/// entry than the cached system prompt was built with.
+pub fn prompt_variant_stale(tui: &TuiState) -> bool {
+    let variant = tui.config.prompt_variant_for(&tui.config.model);
+    variant.map(|variant| variant.name.as_str()) != tui.system_prompt_variant.as_deref()
+}
+
+/// Returns this turn's system prompt and the prompt variant it uses,
+/// rebuilding it for the current model when the cached one is stale.
+fn turn_system_prompt(tui: &TuiState) -> (Option<String>, Option<String>) {
+    if !prompt_variant_stale(tui) {
+        return (tui.system_prompt.clone(), tui.system_prompt_variant.clone());
+    }
+    match crate::tui_effective_prompt(&tui.config, &tui.agent_opts.root, tui.thread.incognito) {
+        Ok(effective) => (effective.prompt, effective.prompt_variant),
+        Err(err) => {
+            tracing::warn!(%err, "Failed to rebuild system prompt for model");
+            (tui.system_prompt.clone(), tui.system_prompt_variant.clone())
+        }
+    }
+}
+
+/// Records the turn's prompt variant in the thread meta (no-op when unchanged).
+fn record_prompt_variant(thread: Option<&thread_persistence::Thread>, variant: Option<String>) {
+    if let Some(mut thread) = thread.cloned()
+        && let Err(err) = thread.set_prompt_variant(variant)
+    {
+        tracing::warn!(%err, "Failed to record prompt variant");
+    }
+}
+
 /// Spawns an agent turn for the active tab.
 ///
 /// For btw tabs, this prepends the forked base messages and creates a
-/// persistent thread on the first send.
-pub fn spawn_agent_turn(tui: &TuiState) -> UiEvent {
-    // For btw tabs, handle thread creation and message merging
-    if let TabKind::Btw { ref base_messages } = tui.tab_kind {
-        return spawn_btw_tab_turn(tui, base_messages);
+/// persistent thread on the first send. `thinking_override` replaces the
+/// session thinking level for this turn only. `ask_user` lets the turn's
+/// `Ask_User` calls open the question overlay.
+pub fn spawn_agent_turn(
+    tui: &TuiState,
+    thinking_override: Option<ThinkingLevel>,
+    ask_user: AskUserHandle,
+) -> UiEvent {
+    // For btw tabs, handle thread creation and message merging (incognito
+    // btw tabs already hold the base messages and stay unsaved)
+    if let TabKind::Btw { ref base_messages } = tui.tab_kind
+        && !tui.thread.incognito
+    {
+        return spawn_btw_tab_turn(tui, base_messages, thinking_override, ask_user);
     }
 
-    let (agent_tx, agent_rx) = zdx_engine::core::agent::create_event_channel();
+    let mut bus = EventBus::new();
     let cancel = CancellationToken::new();
     let run_cancel = cancel.clone();
+    let soft_stop = CancellationToken::new();
 
     let messages = tui.thread.messages.clone();
     let config = tui.config.clone();
-    let agent_opts = tui.agent_opts.clone();
-    let system_prompt = tui.system_prompt.clone();
+    let mut agent_opts = tui.agent_opts.clone();
+    agent_opts.soft_stop = Some(soft_stop.clone());
+    agent_opts.steering = Some(tui.steering.clone());
+    agent_opts.ask_user = Some(ask_user);
+    agent_opts.protected_approvals = tui.thread.protected_approvals.clone();
+    agent_opts.turn.thinking_level = thinking_override;
+    let (system_prompt, prompt_variant) = turn_system_prompt(tui);
+    record_prompt_variant(tui.thread.thread_handle.as_ref(), prompt_variant);
     let thread_id = tui.thread.thread_handle.as_ref().map(|h| h.id.clone());
 
-    let (tui_tx, tui_rx) = zdx_engine::core::agent::create_event_channel();
-
+    let tui_rx = bus.subscribe_lossy("tui", EventFilter::All);
+    // Incognito turns write nothing, not even the unsaved-session audit log.
     if let Some(thread_handle) = tui.thread.thread_handle.clone() {
-        let (persist_tx, persist_rx) = zdx_engine::core::agent::create_event_channel();
-        let _broadcaster =
-            zdx_engine::core::agent::spawn_broadcaster(agent_rx, vec![tui_tx, persist_tx]);
+        let persist_rx = bus.subscribe("persist", EventFilter::NoDeltas);
         let _persist = thread_persistence::spawn_thread_persist_task(thread_handle, persist_rx);
-    } else {
-        let _broadcaster = zdx_engine::core::agent::spawn_broadcaster(agent_rx, vec![tui_tx]);
+    } else if !tui.thread.incognito {
+        let audit_rx = bus.subscribe("audit", EventFilter::NoDeltas);
+        let _audit = zdx_engine::core::audit_log::spawn_audit_task(audit_rx);
     }
+    let agent_tx = bus.into_sender();
 
     // Spawn the agent task - it will send TurnFinished when done
     tokio::spawn(async move {
@@ -52,7 +109,7 @@ pub fn spawn_agent_turn(tui: &TuiState) -> UiEvent {
             &agent_opts,
             system_prompt.as_deref(),
             thread_id.as_deref(),
-            agent_tx.clone(),
+            agent_tx,
             Some(run_cancel),
         )
         .await;
@@ -61,6 +118,7 @@ pub fn spawn_agent_turn(tui: &TuiState) -> UiEvent {
     UiEvent::AgentSpawned {
         rx: tui_rx,
         cancel,
+        soft_stop,
         thread_handle: None,
         messages: None,
     }
@@ -72,7 +130,12 @@ pub fn spawn_agent_turn(tui: &TuiState) -> UiEvent {
 /// base-message context to it. On subsequent sends, reuses the existing
 /// thread. The agent always sees `base_messages + btw_messages` as the
 /// conversation, but only btw-specific turns are persisted incrementally.
-fn spawn_btw_tab_turn(tui: &TuiState, base_messages: &[ChatMessage]) -> UiEvent {
+fn spawn_btw_tab_turn(
+    tui: &TuiState,
+    base_messages: &[ChatMessage],
+    thinking_override: Option<ThinkingLevel>,
+    ask_user: AskUserHandle,
+) -> UiEvent {
     // Prepare thread and messages (create thread on first send)
     let prepared = match prepare_btw_tab_thread(tui, base_messages) {
         Ok(result) => result,
@@ -83,21 +146,28 @@ fn spawn_btw_tab_turn(tui: &TuiState, base_messages: &[ChatMessage]) -> UiEvent
         }
     };
 
-    let (agent_tx, agent_rx) = zdx_engine::core::agent::create_event_channel();
+    let mut bus = EventBus::new();
     let cancel = CancellationToken::new();
     let run_cancel = cancel.clone();
+    let soft_stop = CancellationToken::new();
 
     let config = tui.config.clone();
-    let agent_opts = tui.agent_opts.clone();
-    let system_prompt = tui.system_prompt.clone();
+    let mut agent_opts = tui.agent_opts.clone();
+    agent_opts.soft_stop = Some(soft_stop.clone());
+    agent_opts.steering = Some(tui.steering.clone());
+    agent_opts.ask_user = Some(ask_user);
+    agent_opts.protected_approvals = tui.thread.protected_approvals.clone();
+    agent_opts.turn.thinking_level = thinking_override;
+    let (system_prompt, prompt_variant) = turn_system_prompt(tui);
+    record_prompt_variant(Some(&prepared.thread_handle), prompt_variant);
     let thread_id = prepared.thread_handle.id.clone();
 
-    let (tui_tx, tui_rx) = zdx_engine::core::agent::create_event_channel();
-    let (persist_tx, persist_rx) = zdx_engine::core::agent::create_event_channel();
-    let _broadcaster =
-        zdx_engine::core::agent::spawn_broadcaster(agent_rx, vec![tui_tx, persist_tx]);
-    let _persist =
-        thread_persistence::spawn_thread_persist_task(prepared.thread_handle, persist_rx);
+    let tui_rx = bus.subscribe_lossy("tui", EventFilter::All);
+    let _persist = thread_persistence::spawn_thread_persist_task(
+        prepared.thread_handle,
+        bus.subscribe("persist", EventFilter::NoDeltas),
+    );
+    let agent_tx = bus.into_sender();
 
     let run_messages = prepared.run_messages;
     tokio::spawn(async move {
@@ -107,7 +177,7 @@ fn spawn_btw_tab_turn(tui: &TuiState, base_messages: &[ChatMessage]) -> UiEvent
             &agent_opts,
             system_prompt.as_deref(),
             Some(&thread_id),
-            agent_tx.clone(),
+            agent_tx,
             Some(run_cancel),
         )
         .await;
@@ -116,6 +186,7 @@ fn spawn_btw_tab_turn(tui: &TuiState, base_messages: &[ChatMessage]) -> UiEvent
     UiEvent::AgentSpawned {
         rx: tui_rx,
         cancel,
+        soft_stop,
         thread_handle: prepared.thread_update,
         messages: prepared.messages_update,
     }
@@ -10,6 +10,7 @@ use crate::events::UiEvent;
 pub async fn bash_execution(
     id: String,
     command: String,
+    cwd: Option<String>,
     root: PathBuf,
     cancel: Option<CancellationToken>,
 ) -> UiEvent {
@@ -21,7 +22,7 @@ pub async fn bash_execution(
 
     let ctx = ToolContext::new(root, None);
     let leaf = ctx.as_leaf();
-    let run_fut = bash::run(&cmd, &leaf, None, None);
+    let run_fut = bash::run(&cmd, cwd.as_deref(), &leaf, None, None);
     let result = if let Some(cancel) = cancel {
         let cancel_clone = cancel.clone();
         tokio::select! {
@@ -34,6 +35,7 @@ pub async fn bash_execution(
     UiEvent::BashExecuted {
         id: result_id,
         command,
+        cwd,
         result,
     }
 }
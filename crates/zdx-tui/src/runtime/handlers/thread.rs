@@ -4,12 +4,106 @@ use std::process::Command;
 
 use anyhow::{Context, anyhow, bail};
 use zdx_engine::agent_activity;
+use zdx_engine::core::thread_export::export_thread;
 use zdx_engine::core::thread_persistence::ThreadEvent;
 use zdx_engine::core::{thread_persistence as tp, worktree};
 
-use crate::events::{ThreadUiEvent, UiEvent};
+use crate::effects::ThreadAction;
+use crate::events::{StartupContext, ThreadUiEvent, UiEvent};
 use crate::transcript::{HistoryCell, build_transcript_from_events};
 
+/// Pins or unpins `turn` (default: the latest turn) in the thread's meta and
+/// returns the notice to show.
+pub fn pin_turn(
+    thread: &mut tp::Thread,
+    messages: &[zdx_engine::providers::ChatMessage],
+    turn: Option<usize>,
+    pinned: bool,
+) -> String {
+    let count = zdx_engine::core::truncation::turn_starts(messages).len();
+    if count == 0 {
+        return "No turns to pin yet.".to_string();
+    }
+    let turn = turn.unwrap_or(count);
+    if turn == 0 || turn > count {
+        return format!("Turn must be between 1 and {count}.");
+    }
+    match thread.set_turn_pinned(turn, pinned) {
+        Ok(turns) => {
+            let action = if pinned { "Pinned" } else { "Unpinned" };
+            if turns.is_empty() {
+                return format!("{action} turn {turn}. No turns are pinned.");
+            }
+            let list: Vec<String> = turns.iter().map(ToString::to_string).collect();
+            format!(
+                "{action} turn {turn}. Pinned turns (kept by conversation truncation): {}",
+                list.join(", ")
+            )
+        }
+        Err(err) => format!("Failed to update pinned turns: {err}"),
+    }
+}
+
+/// Pins or unpins a transcript message in the thread's meta and returns the
+/// notice to show.
+pub fn pin_message(
+    thread: &mut tp::Thread,
+    cell: &HistoryCell,
+    pinned: bool,
+) -> Result<String, String> {
+    let Some((role, text)) = cell.pinnable_message() else {
+        return Err("Only finished user and assistant messages can be pinned.".to_string());
+    };
+    thread
+        .set_message_pinned(
+            tp::PinnedMessage {
+                role: role.to_string(),
+                text,
+            },
+            pinned,
+        )
+        .map_err(|err| format!("Failed to update pinned messages: {err}"))?;
+    Ok(if pinned {
+        format!("📌 Pinned {role} message; it is sent with every turn of this thread.")
+    } else {
+        format!("Unpinned {role} message.")
+    })
+}
+
+/// Pins or unpins `path` (relative to `root`) in the thread's meta and
+/// returns the notice to show. Pinning checks that the file exists.
+pub fn pin_file(thread: &mut tp::Thread, root: &Path, path: &str, pinned: bool) -> String {
+    let path = path.trim();
+    if pinned && !root.join(path).is_file() {
+        return format!("No such file: {path}");
+    }
+    match thread.set_file_pinned(path.to_string(), pinned) {
+        Ok(files) => {
+            let action = if pinned { "📌 Pinned" } else { "Unpinned" };
+            if files.is_empty() {
+                format!("{action} {path}. No files are pinned.")
+            } else {
+                format!(
+                    "{action} {path}. Pinned files (sent with every turn): {}",
+                    files.join(", ")
+                )
+            }
+        }
+        Err(err) => format!("Failed to update pinned files: {err}"),
+    }
+}
+
+/// Stores the thread's response style preset (`None` clears it).
+pub fn set_style(thread: &mut tp::Thread, style: Option<String>) -> Result<String, String> {
+    thread
+        .set_style(style.clone())
+        .map_err(|err| format!("Failed to update style: {err}"))?;
+    Ok(match style {
+        Some(style) => format!("Response style set to {style} for this thread."),
+        None => "Response style cleared.".to_string(),
+    })
+}
+
 /// Loads the list of threads.
 ///
 /// Pure async function - runtime spawns and sends result to inbox.
@@ -45,6 +139,50 @@ pub async fn thread_list_load(
     })
 }
 
+/// Loads the recent-thread list for the quick switcher.
+pub async fn thread_recent_load() -> UiEvent {
+    list_threads_with_active(|threads, active_thread_ids| ThreadUiEvent::RecentLoaded {
+        threads,
+        active_thread_ids,
+    })
+    .await
+}
+
+/// Loads the thread list for `/threads <title>` completion.
+pub async fn thread_completion_list_load() -> UiEvent {
+    list_threads_with_active(
+        |threads, active_thread_ids| ThreadUiEvent::CompletionListLoaded {
+            threads,
+            active_thread_ids,
+        },
+    )
+    .await
+}
+
+/// Lists threads (newest first) and the ids running in background agents.
+async fn list_threads_with_active(
+    loaded: fn(Vec<tp::ThreadSummary>, HashSet<String>) -> ThreadUiEvent,
+) -> UiEvent {
+    tokio::task::spawn_blocking(move || match tp::list_threads() {
+        Ok(threads) => {
+            let active_thread_ids: HashSet<String> = agent_activity::list_active()
+                .into_iter()
+                .filter_map(|run| run.thread_id)
+                .collect();
+            UiEvent::Thread(loaded(threads, active_thread_ids))
+        }
+        Err(e) => UiEvent::Thread(ThreadUiEvent::ListFailed {
+            error: format!("Failed to load threads: {e}"),
+        }),
+    })
+    .await
+    .unwrap_or_else(|e| {
+        UiEvent::Thread(ThreadUiEvent::ListFailed {
+            error: format!("Task failed: {e}"),
+        })
+    })
+}
+
 /// Loads a thread by ID (full switch).
 ///
 /// Pure async function - runtime spawns and sends result to inbox.
@@ -58,8 +196,30 @@ pub async fn thread_load(thread_id: String, root: PathBuf) -> UiEvent {
         })
 }
 
+/// Copies a thread and loads the copy.
+///
+/// Pure async function - runtime spawns and sends result to inbox.
+pub async fn thread_duplicate(thread_id: String, turns: Option<usize>, root: PathBuf) -> UiEvent {
+    tokio::task::spawn_blocking(move || match tp::clone_thread(&thread_id, turns) {
+        Ok(copy) => load_thread_sync(&copy.id, &root),
+        Err(e) => UiEvent::Thread(ThreadUiEvent::LoadFailed {
+            error: format!("Failed to duplicate thread: {e}"),
+        }),
+    })
+    .await
+    .unwrap_or_else(|e| {
+        UiEvent::Thread(ThreadUiEvent::LoadFailed {
+            error: format!("Task failed: {e}"),
+        })
+    })
+}
+
 /// Synchronous thread loading (runs in blocking task).
 fn load_thread_sync(thread_id: &str, root: &Path) -> UiEvent {
+    // Bot topics that were resumed or switched point at the thread holding
+    // their history; open that one so new turns land there too.
+    let thread_id = tp::resolve_thread_alias(thread_id);
+    let thread_id = thread_id.as_str();
     let is_active_elsewhere = agent_activity::list_active()
         .into_iter()
         .filter_map(|run| run.thread_id)
@@ -161,22 +321,52 @@ pub fn resolve_root_display(path: PathBuf) -> UiEvent {
     }
 }
 
-/// Refreshes the effective system prompt for a new root.
-pub fn refresh_system_prompt(config: &zdx_engine::config::Config, path: &Path) -> UiEvent {
-    let instruction_layers = crate::tui_instruction_layers();
-    let result =
-        zdx_engine::core::context::build_effective_system_prompt_with_paths_and_instruction_layers(
-            config,
-            path,
-            &instruction_layers,
-            true,
-        )
-        .map(|context| context.prompt)
+/// Refreshes the effective system prompt for a new root (or for a model that
+/// selects a different prompt variant).
+pub fn refresh_system_prompt(
+    config: &zdx_engine::config::Config,
+    path: &Path,
+    incognito: bool,
+) -> UiEvent {
+    let result = crate::tui_effective_prompt(config, path, incognito)
+        .map(|context| (context.prompt, context.prompt_variant))
         .map_err(|error| format!("Failed to refresh system prompt: {error}"));
 
     UiEvent::SystemPromptRefreshed { result }
 }
 
+/// Builds the startup context (prompt, context files, skills) and warms the
+/// model registry. Runs off the UI thread after the first paint.
+pub fn load_startup_context(
+    config: &zdx_engine::config::Config,
+    root: &Path,
+    incognito: bool,
+) -> UiEvent {
+    use zdx_engine::startup_profile;
+
+    startup_profile::time("models.registry", zdx_engine::models::available_models);
+    let result = crate::tui_effective_prompt(config, root, incognito)
+        .map(|effective| StartupContext {
+            prompt: effective.prompt,
+            prompt_variant: effective.prompt_variant,
+            loaded_agents_paths: effective.loaded_agents_paths,
+            loaded_skills: effective.loaded_skills,
+            warnings: effective
+                .warnings
+                .iter()
+                .map(crate::format_context_warning)
+                .collect(),
+        })
+        .map_err(|error| format!("Failed to build system prompt: {error}"));
+    startup_profile::mark("context_ready");
+    startup_profile::finish();
+
+    UiEvent::StartupContextLoaded {
+        root: root.to_path_buf(),
+        result,
+    }
+}
+
 fn get_git_branch(root: &Path) -> Option<String> {
     let head_path = root.join(".git/HEAD");
     if let Ok(content) = std::fs::read_to_string(head_path)
@@ -489,3 +679,40 @@ pub async fn thread_rename(thread_id: String, title: Option<String>) -> UiEvent
         })
     })
 }
+
+/// Applies a thread picker action to each thread, collecting per-thread
+/// failures instead of stopping at the first one.
+///
+/// Pure async function - runtime spawns and sends result to inbox.
+pub async fn thread_apply_action(thread_ids: Vec<String>, action: ThreadAction) -> UiEvent {
+    let fallback_action = action.clone();
+    tokio::task::spawn_blocking(move || {
+        let mut done = Vec::new();
+        let mut errors = Vec::new();
+        for thread_id in thread_ids {
+            let result = match &action {
+                ThreadAction::Delete => tp::delete_thread(&thread_id),
+                ThreadAction::Archive => tp::archive_thread(&thread_id).map(|_| ()),
+                ThreadAction::Tag(tag) => tp::add_thread_tag(&thread_id, tag),
+                ThreadAction::Export => export_thread(&thread_id).map(|_| ()),
+            };
+            match result {
+                Ok(()) => done.push(thread_id),
+                Err(e) => errors.push(format!("{}: {e:#}", tp::short_thread_id(&thread_id))),
+            }
+        }
+        UiEvent::Thread(ThreadUiEvent::ActionApplied {
+            action,
+            thread_ids: done,
+            errors,
+        })
+    })
+    .await
+    .unwrap_or_else(|e| {
+        UiEvent::Thread(ThreadUiEvent::ActionApplied {
+            action: fallback_action,
+            thread_ids: Vec::new(),
+            errors: vec![format!("Task failed: {e}")],
+        })
+    })
+}
@@ -25,11 +25,15 @@
 //! - `image_ops.rs`: shared image loading/transform helpers (preview + attachments)
 
 mod context_analyze;
+mod debug_request;
+mod duel_turn;
 mod handlers;
 mod handoff;
 mod image_ops;
 mod inbox;
 mod prompt_builder;
+mod prompt_improve;
+mod thread_summary;
 mod thread_title;
 mod thread_tldr;
 
@@ -38,6 +42,7 @@ use std::io::Stdout;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
+pub(crate) use context_analyze::content_block_chars;
 pub use context_analyze::{AnalysisMode, ContextReport, DisplayMode, refine_supported};
 use crossterm::event;
 use inbox::{UiEventReceiver, UiEventSender};
@@ -46,17 +51,22 @@ use ratatui::backend::CrosstermBackend;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use zdx_engine::config::Config;
+use zdx_engine::core::ask_user::{self, AskUserHandle, AskUserRx};
+use zdx_engine::core::event_bus::TryRecvError;
 use zdx_engine::core::events::{AgentEvent, ErrorKind, TurnStatus};
 use zdx_engine::core::interrupt;
 use zdx_engine::core::thread_persistence::Thread;
 use zdx_engine::custom_commands::load_custom_commands;
 use zdx_engine::providers::ChatMessage;
 
+use crate::common::i18n::{Text, tr};
 use crate::common::{TaskCompleted, TaskKind, TaskMeta, TaskStarted};
 use crate::effects::UiEffect;
 use crate::events::UiEvent;
 use crate::state::{AgentState, AppState};
-use crate::{render, terminal, update};
+use crate::transcript::Scrollback;
+use crate::ui_state::{self, UiState};
+use crate::{crash, render, terminal, update};
 
 /// Target frame rate for streaming updates (60fps = ~16ms per frame).
 pub const FRAME_DURATION: std::time::Duration = std::time::Duration::from_millis(16);
@@ -65,6 +75,9 @@ pub const FRAME_DURATION: std::time::Duration = std::time::Duration::from_millis
 /// Longer timeout reduces CPU usage when nothing is happening.
 pub const IDLE_POLL_DURATION: std::time::Duration = std::time::Duration::from_millis(100);
 
+/// Viewport rows in inline mode (capped at the terminal height).
+const INLINE_VIEWPORT_HEIGHT: u16 = 16;
+
 // Helper for target FPS
 #[inline]
 fn effective_frame_duration(is_focused: bool) -> std::time::Duration {
@@ -159,6 +172,10 @@ pub struct TuiRuntime {
     inbox_tx: UiEventSender,
     /// Inbox receiver - runtime drains this each frame.
     inbox_rx: UiEventReceiver,
+    /// `Ask_User` channel shared by every tab's agent turns.
+    ask_user: AskUserHandle,
+    /// Questions from `Ask_User`, drained alongside the inbox.
+    questions_rx: AskUserRx,
     /// Last time a Tick event was emitted.
     last_tick: std::time::Instant,
     /// Last time a render occurred (for FPS calculation).
@@ -167,6 +184,13 @@ pub struct TuiRuntime {
     last_terminal_event: std::time::Instant,
     /// Kitty graphics protocol image lifecycle manager.
     kitty: KittyImageManager,
+    /// Printed-cell tracking in inline mode; `None` in full-screen mode.
+    scrollback: Option<Scrollback>,
+    /// Build the startup context (prompt, context files, skills) in the
+    /// background once the first frame is drawn.
+    deferred_startup_context: bool,
+    /// UI preferences as last saved to `ui_state.json`.
+    ui_state: UiState,
 }
 
 impl TuiRuntime {
@@ -203,14 +227,17 @@ impl TuiRuntime {
         // Reset interrupt flag in case it was set from a previous run
         interrupt::reset();
 
-        // Enter alternate screen and raw mode
-        let terminal = terminal::setup_terminal().context("Failed to setup terminal")?;
+        // Enter raw mode and the alternate screen (or the inline viewport)
+        let inline = config.tui.inline;
+        let terminal = setup_terminal(inline)?;
 
         // Discover custom slash commands once at startup. Failures here are
         // never fatal: missing dirs/parse warnings are surfaced via tracing
         // and the app continues with whatever loaded successfully.
         let builtin_identifiers = crate::common::commands::builtin_command_identifiers();
-        let custom_load = load_custom_commands(&root, &builtin_identifiers);
+        let custom_load = zdx_engine::startup_profile::time("tui.custom_commands", || {
+            load_custom_commands(&root, &builtin_identifiers)
+        });
         for warning in &custom_load.warnings {
             tracing::warn!(
                 path = %warning.path.display(),
@@ -219,12 +246,20 @@ impl TuiRuntime {
             );
         }
 
+        let bash_history = zdx_engine::startup_profile::time("tui.bash_history", || {
+            load_bash_history(&config.tui)
+        });
+
         // Create state
-        let state = AppState::with_history(config, root, system_prompt, thread_handle, history)
+        let mut state = AppState::with_history(config, root, system_prompt, thread_handle, history)
             .with_custom_commands(custom_load.commands);
+        state.tui.input.bash_history = bash_history;
+        let ui_state = ui_state::load();
+        ui_state.apply(&mut state.tui);
 
         // Create inbox channel for async event collection
         let (inbox_tx, inbox_rx) = mpsc::unbounded_channel();
+        let (ask_user, questions_rx) = ask_user::channel();
 
         let now = std::time::Instant::now();
         Ok(Self {
@@ -232,22 +267,37 @@ impl TuiRuntime {
             state,
             inbox_tx,
             inbox_rx,
+            ask_user,
+            questions_rx,
             last_tick: now,
             last_render: now,
             last_terminal_event: now,
             kitty: KittyImageManager::new(),
+            scrollback: inline.then(Scrollback::default),
+            deferred_startup_context: false,
+            ui_state,
         })
     }
 
+    /// Builds the startup context (system prompt, context files, skills) on a
+    /// background task after the first paint instead of before the TUI opens.
+    /// A turn started before it finishes builds the context synchronously.
+    pub fn defer_startup_context(&mut self) {
+        self.deferred_startup_context = true;
+    }
+
     /// Runs the main event loop.
     ///
     /// # Errors
     /// Returns an error if the operation fails.
     pub fn run(&mut self) -> Result<()> {
         // Enable bracketed paste and mouse capture
-        terminal::enable_input_features()?;
+        terminal::enable_input_features(self.mouse_capture())?;
 
-        let result = self.event_loop();
+        let mut result = self.event_loop();
+        if result.is_ok() {
+            result = self.finish_inline();
+        }
 
         // Disable mouse capture and bracketed paste
         let _ = terminal::disable_input_features();
@@ -268,10 +318,11 @@ impl TuiRuntime {
         while !self.state.tui.should_quit {
             // Check for Ctrl+C signal (only quit if agent is idle)
             // If agent is running, the interrupt is meant to cancel it, not quit the app.
-            // The agent will send an Interrupted event which resets the flag.
+            // The engine only watches the turn's token, so cancel it here; the
+            // agent's Interrupted event resets the flag.
             if interrupt::is_interrupted() {
                 if self.state.tui.agent_state.is_running() {
-                    // Let the agent handle the interrupt.
+                    self.execute_effect(UiEffect::InterruptAgent);
                 } else if self.state.tui.tasks.state(TaskKind::Bash).is_running() {
                     self.execute_effect(UiEffect::InterruptBash);
                     interrupt::reset();
@@ -329,6 +380,7 @@ impl TuiRuntime {
                 }
                 self.execute_effects(effects);
             }
+            self.sync_ui_state();
 
             // Only render if something changed and enough time has passed (cap at 60 FPS, or 10 FPS if unfocused)
             let effective_frame_duration = effective_frame_duration(self.state.is_focused);
@@ -338,23 +390,67 @@ impl TuiRuntime {
                 self.last_render = std::time::Instant::now();
 
                 // Render - state is a separate field, no borrow conflict
-                self.terminal.draw(|frame| {
-                    render::render(&self.state, frame);
-                })?;
+                if let Some(scrollback) = &mut self.scrollback {
+                    let width = self.terminal.size()?.width;
+                    let lines = scrollback.take_lines(
+                        &self.state.tui,
+                        width.saturating_sub(render::TRANSCRIPT_MARGIN * 2) as usize,
+                        false,
+                    );
+                    insert_scrollback_lines(&mut self.terminal, lines)?;
+                    let live_from = scrollback.live_from();
+                    self.terminal.draw(|frame| {
+                        render::render_inline(&self.state, frame, live_from);
+                    })?;
+                } else {
+                    self.terminal.draw(|frame| {
+                        render::render(&self.state, frame);
+                    })?;
 
-                // Post-render: manage Kitty graphics image lifecycle
-                self.flush_kitty_image();
+                    // Post-render: manage Kitty graphics image lifecycle
+                    self.flush_kitty_image();
+                }
 
                 dirty = false;
 
                 // Update FPS based on actual render interval
                 self.state.tui.status_line.on_frame(frame_ms);
+
+                if std::mem::take(&mut self.deferred_startup_context) {
+                    zdx_engine::startup_profile::mark("first_paint");
+                    self.spawn_startup_context();
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Mouse capture is off in a11y mode (terminal selection) and inline mode
+    /// (the wheel scrolls the terminal's own scrollback).
+    fn mouse_capture(&self) -> bool {
+        let tui = &self.state.tui.config.tui;
+        !tui.a11y && !tui.inline
+    }
+
+    /// In inline mode, prints whatever is still live to scrollback and clears
+    /// the viewport so the shell prompt continues below the conversation.
+    fn finish_inline(&mut self) -> Result<()> {
+        let Some(scrollback) = &mut self.scrollback else {
+            return Ok(());
+        };
+        let width = self.terminal.size()?.width;
+        let lines = scrollback.take_lines(
+            &self.state.tui,
+            width.saturating_sub(render::TRANSCRIPT_MARGIN * 2) as usize,
+            true,
+        );
+        insert_scrollback_lines(&mut self.terminal, lines)?;
+        self.terminal.clear()?;
+        self.terminal.show_cursor()?;
+        Ok(())
+    }
+
     // ========================================================================
     // Kitty Graphics Lifecycle
     // ========================================================================
@@ -494,6 +590,9 @@ impl TuiRuntime {
         while let Ok(ev) = self.inbox_rx.try_recv() {
             events.push(ev);
         }
+        while let Ok(question) = self.questions_rx.try_recv() {
+            events.push(UiEvent::UserQuestion(question));
+        }
     }
 
     // ========================================================================
@@ -501,6 +600,18 @@ impl TuiRuntime {
     // ========================================================================
 
     /// Executes effects returned by the reducer.
+    /// Saves UI preferences when the active tab's differ from the saved ones.
+    fn sync_ui_state(&mut self) {
+        let current = self.ui_state.capture(&self.state.tui);
+        if current == self.ui_state {
+            return;
+        }
+        if let Err(err) = ui_state::save(&current) {
+            tracing::warn!(error = %err, "failed to save UI state");
+        }
+        self.ui_state = current;
+    }
+
     fn execute_effects(&mut self, effects: Vec<UiEffect>) {
         for effect in effects {
             self.execute_effect(effect);
@@ -508,7 +619,9 @@ impl TuiRuntime {
     }
 
     fn dispatch_event(&mut self, event: UiEvent) {
+        crash::record_event(&event);
         let effects = update::update(&mut self.state, event);
+        crash::record_state(&self.state);
         if !effects.is_empty() {
             self.execute_effects(effects);
         }
@@ -544,6 +657,49 @@ impl TuiRuntime {
         });
     }
 
+    fn spawn_startup_context(&mut self) {
+        let config = self.state.tui.config.clone();
+        let root = self.state.tui.agent_opts.root.clone();
+        let incognito = self.state.tui.thread.incognito;
+        self.spawn_task(
+            TaskKind::StartupContext,
+            TaskMeta::None,
+            false,
+            move |_| async move {
+                let fallback_root = root.clone();
+                tokio::task::spawn_blocking(move || {
+                    handlers::load_startup_context(&config, &root, incognito)
+                })
+                .await
+                .unwrap_or_else(|error| UiEvent::StartupContextLoaded {
+                    root: fallback_root,
+                    result: Err(format!("Failed to build system prompt: {error}")),
+                })
+            },
+        );
+    }
+
+    /// Finishes a still-running startup context load synchronously, so a turn
+    /// submitted right after launch gets the full system prompt.
+    fn ensure_startup_context(&mut self) {
+        if !self
+            .state
+            .tui
+            .tasks
+            .state(TaskKind::StartupContext)
+            .is_running()
+        {
+            return;
+        }
+        // The reducer clears the task, so the background result is dropped.
+        let event = handlers::load_startup_context(
+            &self.state.tui.config,
+            &self.state.tui.agent_opts.root,
+            self.state.tui.thread.incognito,
+        );
+        self.dispatch_event(event);
+    }
+
     /// Executes a single effect by dispatching to the appropriate handler.
     ///
     /// Uses `spawn_task` for async task lifecycles.
@@ -565,6 +721,22 @@ impl TuiRuntime {
                     let _ = self.state.close_active_tab();
                 }
             }
+            UiEffect::CloseBackgroundTabs { tab_ids } => {
+                for tab in &self.state.background_tabs {
+                    if !tab_ids.contains(&tab.tab_id) {
+                        continue;
+                    }
+                    if let Some(cancel) = tab.agent_state.cancel_token() {
+                        cancel.cancel();
+                    }
+                    if let Some(cancel) = tab.tasks.state(TaskKind::Bash).cancel.clone() {
+                        cancel.cancel();
+                    }
+                }
+                self.state
+                    .background_tabs
+                    .retain(|tab| !tab_ids.contains(&tab.tab_id));
+            }
             UiEffect::CycleTab => {
                 crate::update::cycle_tab(&mut self.state, 1);
             }
@@ -597,11 +769,29 @@ impl TuiRuntime {
             }
 
             // Agent effects (still returns event for now - streaming is special)
-            UiEffect::StartAgentTurn => {
-                let event = handlers::spawn_agent_turn(&self.state.tui);
+            UiEffect::StartAgentTurn { thinking_override } => {
+                self.ensure_startup_context();
+                // A model switch can select another prompt variant; refresh
+                // the cached prompt so the turn and `/context` agree.
+                if handlers::prompt_variant_stale(&self.state.tui) {
+                    let event = handlers::refresh_system_prompt(
+                        &self.state.tui.config,
+                        &self.state.tui.agent_opts.root,
+                        self.state.tui.thread.incognito,
+                    );
+                    self.dispatch_event(event);
+                }
+                let event = handlers::spawn_agent_turn(
+                    &self.state.tui,
+                    thinking_override,
+                    self.ask_user.clone(),
+                );
                 self.dispatch_event(event);
             }
-            UiEffect::StartAgentTurnInBackgroundTab { tab_id } => {
+            UiEffect::StartAgentTurnInBackgroundTab {
+                tab_id,
+                thinking_override,
+            } => {
                 // Spawn the agent task against the background tab's
                 // `TuiState` and re-route the resulting `AgentSpawned`
                 // event so it lands on the same background tab. Without
@@ -616,17 +806,20 @@ impl TuiRuntime {
                 else {
                     return;
                 };
-                let event = handlers::spawn_agent_turn(tab);
+                let event =
+                    handlers::spawn_agent_turn(tab, thinking_override, self.ask_user.clone());
                 let routed = match event {
                     UiEvent::AgentSpawned {
                         rx,
                         cancel,
+                        soft_stop,
                         thread_handle,
                         messages,
                     } => UiEvent::BackgroundTabAgentSpawned {
                         tab_id,
                         rx,
                         cancel,
+                        soft_stop,
                         thread_handle,
                         messages,
                     },
@@ -637,6 +830,22 @@ impl TuiRuntime {
             UiEffect::InterruptAgent => {
                 handlers::interrupt_agent(&self.state.tui);
             }
+            UiEffect::SoftStopAgent => {
+                handlers::soft_stop_agent(&self.state.tui);
+            }
+            UiEffect::SteerAgent { note } => {
+                self.state.tui.steering.push(note);
+            }
+            UiEffect::RedoTurn { feedback } => {
+                let effects = crate::update::redo_turn(&mut self.state.tui, &feedback);
+                self.execute_effects(effects);
+            }
+            UiEffect::AnswerUserQuestion { question, answer } => match answer {
+                Some(answer) => {
+                    question.answer(answer);
+                }
+                None => question.dismiss("The user declined to answer."),
+            },
             UiEffect::InterruptBash => {
                 // Unified cancellation: call cancel() on the token
                 if let Some(cancel) = self.state.tui.tasks.state(TaskKind::Bash).cancel.clone() {
@@ -763,6 +972,24 @@ impl TuiRuntime {
                     // Could add an event for error reporting if needed
                 }
             }
+            UiEffect::RecordCommandUse { name } => {
+                if !self.state.tui.thread.incognito {
+                    zdx_engine::core::personal_stats::record_command(
+                        zdx_engine::core::personal_stats::CommandSource::Slash,
+                        name,
+                    );
+                }
+            }
+            UiEffect::ToggleTour => {
+                let saved = crate::tour::load_progress();
+                let effects = crate::tour::toggle(&mut self.state, saved);
+                self.execute_effects(effects);
+            }
+            UiEffect::SaveTourProgress { progress } => {
+                if let Err(err) = crate::tour::save_progress(progress) {
+                    tracing::warn!(error = %err, "failed to save tour progress");
+                }
+            }
             UiEffect::ReloadCustomCommands => {
                 let root = self.state.tui.agent_opts.root.clone();
                 let builtin_identifiers = crate::common::commands::builtin_command_identifiers();
@@ -854,6 +1081,11 @@ impl TuiRuntime {
                     handlers::thread_rename(thread_id, title)
                 });
             }
+            UiEffect::ApplyThreadAction { thread_ids, action } => {
+                self.spawn_task(TaskKind::ThreadAction, TaskMeta::None, false, move |_| {
+                    handlers::thread_apply_action(thread_ids, action)
+                });
+            }
             UiEffect::SuggestThreadTitle { thread_id, message } => {
                 let is_current = self
                     .state
@@ -871,6 +1103,13 @@ impl TuiRuntime {
                     thread_title::suggest_thread_title(thread_id, message, title_model, root)
                 });
             }
+            UiEffect::SummarizeThread { thread_id } => {
+                let root = self.state.tui.agent_opts.root.clone();
+                let title_model = self.state.tui.config.title_model.clone();
+                self.spawn_task(TaskKind::ThreadSummary, TaskMeta::None, false, move |_| {
+                    thread_summary::summarize_thread(thread_id, title_model, root)
+                });
+            }
             UiEffect::GenerateTldr { thread_id } => {
                 let root = self.state.tui.agent_opts.root.clone();
                 let tldr_model = self.state.tui.config.tldr_model.clone();
@@ -887,11 +1126,34 @@ impl TuiRuntime {
                     context_analyze::analyze_context(model_id, config, agent_opts, messages, mode)
                 });
             }
+            UiEffect::DebugRequest => {
+                self.ensure_startup_context();
+                let config = self.state.tui.config.clone();
+                let agent_opts = self.state.tui.agent_opts.clone();
+                let messages = self.state.tui.thread.messages.clone();
+                let system_prompt = self.state.tui.system_prompt.clone();
+                let thread_id = self
+                    .state
+                    .tui
+                    .thread
+                    .thread_handle
+                    .as_ref()
+                    .map(|handle| handle.id.clone());
+                self.spawn_task(TaskKind::DebugRequest, TaskMeta::None, false, move |_| {
+                    debug_request::build_debug_request(
+                        config,
+                        agent_opts,
+                        messages,
+                        system_prompt,
+                        thread_id,
+                    )
+                });
+            }
             UiEffect::CreateNewThread => {
                 let config = self.state.tui.config.clone();
                 let root = self.state.tui.agent_opts.root.clone();
 
-                let prompt_event = handlers::refresh_system_prompt(&config, &root);
+                let prompt_event = handlers::refresh_system_prompt(&config, &root, false);
                 self.dispatch_event(prompt_event);
 
                 self.spawn_task(TaskKind::ThreadCreate, TaskMeta::None, false, move |_| {
@@ -931,7 +1193,7 @@ impl TuiRuntime {
                         .tui
                         .transcript
                         .push_cell(crate::transcript::HistoryCell::system(
-                            "Stop the current task first.".to_string(),
+                            tr(Text::StopCurrentTaskFirst).to_string(),
                         ));
                     return;
                 }
@@ -944,6 +1206,34 @@ impl TuiRuntime {
                     handlers::thread_list_load(original_cells, mode)
                 });
             }
+            UiEffect::OpenQuickSwitch => {
+                if self.state.tui.agent_state.is_running() {
+                    self.state
+                        .tui
+                        .transcript
+                        .push_cell(crate::transcript::HistoryCell::system(
+                            tr(Text::StopCurrentTaskFirst).to_string(),
+                        ));
+                    return;
+                }
+                if self
+                    .state
+                    .tui
+                    .tasks
+                    .state(TaskKind::ThreadList)
+                    .is_running()
+                {
+                    return;
+                }
+                self.spawn_task(TaskKind::ThreadList, TaskMeta::None, false, |_| {
+                    handlers::thread_recent_load()
+                });
+            }
+            UiEffect::ListThreadsForCompletion => {
+                self.spawn_task(TaskKind::ThreadCompletion, TaskMeta::None, false, |_| {
+                    handlers::thread_completion_list_load()
+                });
+            }
             UiEffect::LoadThread { thread_id } => {
                 if self
                     .state
@@ -961,6 +1251,66 @@ impl TuiRuntime {
                     handlers::thread_load(thread_id, root)
                 });
             }
+            UiEffect::DuplicateThread { thread_id, turns } => {
+                let root = self.state.tui.agent_opts.root.clone();
+                self.spawn_task(TaskKind::ThreadLoad, TaskMeta::None, false, move |_| {
+                    handlers::thread_duplicate(thread_id, turns, root)
+                });
+            }
+            UiEffect::PinTurn { turn, pinned } => {
+                let thread = &mut self.state.tui.thread;
+                let notice = match thread.thread_handle.as_mut() {
+                    Some(handle) => handlers::pin_turn(handle, &thread.messages, turn, pinned),
+                    None => "Pinning requires an active thread.".to_string(),
+                };
+                self.state
+                    .tui
+                    .transcript
+                    .push_cell(crate::transcript::HistoryCell::system(notice));
+            }
+            UiEffect::SetMessagePinned { cell_index, pinned } => {
+                let tui = &mut self.state.tui;
+                let result = match (
+                    tui.thread.thread_handle.as_mut(),
+                    tui.transcript.cells().get(cell_index),
+                ) {
+                    (Some(handle), Some(cell)) => handlers::pin_message(handle, cell, pinned),
+                    _ => Err("Pinning requires an active thread.".to_string()),
+                };
+                let notice = match result {
+                    Ok(notice) => {
+                        tui.transcript.set_cell_pinned(cell_index, pinned);
+                        notice
+                    }
+                    Err(err) => err,
+                };
+                tui.transcript
+                    .push_cell(crate::transcript::HistoryCell::system(notice));
+            }
+            UiEffect::SetFilePinned { path, pinned } => {
+                let tui = &mut self.state.tui;
+                let notice = match tui.thread.thread_handle.as_mut() {
+                    Some(handle) => handlers::pin_file(handle, &tui.agent_opts.root, &path, pinned),
+                    None => "Pinning requires an active thread.".to_string(),
+                };
+                tui.transcript
+                    .push_cell(crate::transcript::HistoryCell::system(notice));
+            }
+            UiEffect::SetStyle { style } => {
+                let tui = &mut self.state.tui;
+                let notice = match tui.thread.thread_handle.as_mut() {
+                    Some(handle) => match handlers::set_style(handle, style.clone()) {
+                        Ok(notice) => {
+                            tui.thread.style = style;
+                            notice
+                        }
+                        Err(err) => err,
+                    },
+                    None => "Styles require an active thread.".to_string(),
+                };
+                tui.transcript
+                    .push_cell(crate::transcript::HistoryCell::system(notice));
+            }
             UiEffect::LoadThreadAsTab { thread_id } => {
                 let root = self.state.tui.agent_opts.root.clone();
                 self.spawn_task(
@@ -1020,7 +1370,7 @@ impl TuiRuntime {
                 self.dispatch_event(root_display_event);
 
                 let config = self.state.tui.config.clone();
-                let prompt_event = handlers::refresh_system_prompt(&config, &root);
+                let prompt_event = handlers::refresh_system_prompt(&config, &root, false);
                 self.dispatch_event(prompt_event);
 
                 self.spawn_task(TaskKind::ThreadCreate, TaskMeta::None, false, move |_| {
@@ -1033,7 +1383,11 @@ impl TuiRuntime {
             }
             UiEffect::RefreshSystemPrompt { path } => {
                 let config = self.state.tui.config.clone();
-                let event = handlers::refresh_system_prompt(&config, &path);
+                let event = handlers::refresh_system_prompt(
+                    &config,
+                    &path,
+                    self.state.tui.thread.incognito,
+                );
                 self.dispatch_event(event);
             }
             UiEffect::PreviewThread { thread_id } => {
@@ -1084,7 +1438,7 @@ impl TuiRuntime {
             } => {
                 let root = self.state.tui.agent_opts.root.clone();
                 let config = self.state.tui.config.clone();
-                let prompt_event = handlers::refresh_system_prompt(&config, &root);
+                let prompt_event = handlers::refresh_system_prompt(&config, &root, false);
                 self.dispatch_event(prompt_event);
 
                 self.spawn_task(TaskKind::ThreadCreate, TaskMeta::None, false, move |_| {
@@ -1104,6 +1458,42 @@ impl TuiRuntime {
                 });
             }
 
+            // `/improve` effect
+            UiEffect::ImprovePrompt { draft } => {
+                let root = self.state.tui.agent_opts.root.clone();
+                let model = self.state.tui.config.improve_model.clone();
+                let no_save = self.state.tui.thread.incognito;
+                self.spawn_task(
+                    TaskKind::PromptImprove,
+                    TaskMeta::None,
+                    true,
+                    move |cancel| {
+                        prompt_improve::prompt_improve_generation(
+                            draft, model, root, no_save, cancel,
+                        )
+                    },
+                );
+            }
+
+            // `/duel` effect
+            UiEffect::RunDuelTurn { side } => {
+                if let Some(session) = &self.state.tui.duel {
+                    let duel = session.duel.clone();
+                    let config = self.state.tui.config.clone();
+                    let agent_opts = self.state.tui.agent_opts.clone();
+                    let thread_id = self
+                        .state
+                        .tui
+                        .thread
+                        .thread_handle
+                        .as_ref()
+                        .map(|h| h.id.clone());
+                    self.spawn_task(TaskKind::Duel, TaskMeta::None, true, move |cancel| {
+                        duel_turn::duel_turn(duel, side, config, agent_opts, thread_id, cancel)
+                    });
+                }
+            }
+
             // File picker effects
             UiEffect::DiscoverFiles => {
                 let root = self.state.tui.agent_opts.root.clone();
@@ -1142,17 +1532,28 @@ impl TuiRuntime {
             }
 
             // Direct bash execution effects
-            UiEffect::ExecuteBash { command } => {
+            UiEffect::ExecuteBash { command, cwd } => {
                 // Only spawn if not already running a bash command
                 if !self.state.tui.tasks.state(TaskKind::Bash).is_running() {
+                    let history_size = self.state.tui.config.tui.bash_history_size;
+                    let entry = match &cwd {
+                        Some(dir) => format!("[{dir}] {command}"),
+                        None => command.clone(),
+                    };
+                    if !self.state.tui.thread.incognito
+                        && let Err(err) = zdx_engine::bash_history::record(&entry, history_size)
+                    {
+                        tracing::warn!(error = %err, "failed to record bash history");
+                    }
                     let id = format!("user-bash-{}", chrono::Utc::now().timestamp_millis());
                     let root = self.state.tui.agent_opts.root.clone();
                     let meta = TaskMeta::Bash {
                         id: id.clone(),
                         command: command.clone(),
+                        cwd: cwd.clone(),
                     };
                     self.spawn_task(TaskKind::Bash, meta, true, move |cancel| {
-                        handlers::bash_execution(id, command, root, cancel)
+                        handlers::bash_execution(id, command, cwd, root, cancel)
                     });
                 }
             }
@@ -1165,13 +1566,65 @@ impl TuiRuntime {
 
         let open_result = open_in_editor(path);
 
-        self.terminal = terminal::setup_terminal().context("Failed to setup terminal")?;
-        terminal::enable_input_features()?;
+        self.terminal = setup_terminal(self.scrollback.is_some())?;
+        terminal::enable_input_features(self.mouse_capture())?;
 
         open_result.context(format!("Failed to open {} in editor", path.display()))
     }
 }
 
+/// Creates the full-screen terminal, or the inline viewport for `zdx --inline`.
+/// Persisted bash-mode commands, preceded by allowlisted shell history when
+/// `[tui] import_shell_history` is on.
+fn load_bash_history(config: &zdx_engine::config::TuiConfig) -> Vec<String> {
+    let persisted = zdx_engine::bash_history::load(config.bash_history_size);
+    if !config.import_shell_history {
+        return persisted;
+    }
+    let imported = zdx_engine::bash_history::import_shell_history(
+        &config.shell_history_allowlist,
+        config.bash_history_size,
+    );
+    zdx_engine::bash_history::merge(imported, persisted)
+}
+
+fn setup_terminal(inline: bool) -> Result<Terminal<CrosstermBackend<Stdout>>> {
+    let terminal = if inline {
+        terminal::setup_inline_terminal(INLINE_VIEWPORT_HEIGHT)
+    } else {
+        terminal::setup_terminal()
+    };
+    terminal.context("Failed to setup terminal")
+}
+
+/// Prints `lines` above the inline viewport, into the terminal's scrollback.
+///
+/// Lines are pre-wrapped, so they are drawn with the transcript margin and no
+/// further wrapping.
+fn insert_scrollback_lines(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    lines: Vec<ratatui::text::Line<'static>>,
+) -> Result<()> {
+    use ratatui::widgets::{Paragraph, Widget};
+
+    // Keep each insert's buffer bounded for very long resumed threads.
+    const CHUNK_LINES: usize = 500;
+
+    let mut lines = lines;
+    while !lines.is_empty() {
+        let rest = lines.split_off(lines.len().min(CHUNK_LINES));
+        let height = lines.len() as u16;
+        terminal.insert_before(height, |buf| {
+            let area = buf
+                .area
+                .inner(ratatui::layout::Margin::new(render::TRANSCRIPT_MARGIN, 0));
+            Paragraph::new(std::mem::take(&mut lines)).render(area, buf);
+        })?;
+        lines = rest;
+    }
+    Ok(())
+}
+
 /// Drains agent events from an [`AgentState`] channel into `events`,
 /// folding consecutive delta events to reduce per-frame allocations.
 ///
@@ -1194,8 +1647,8 @@ where
     while let AgentState::Waiting { rx, .. } | AgentState::Streaming { rx, .. } = agent_state {
         let event = match rx.try_recv() {
             Ok(event) => event,
-            Err(mpsc::error::TryRecvError::Empty) => break,
-            Err(mpsc::error::TryRecvError::Disconnected) => {
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Closed) => {
                 if !assistant_delta.is_empty() {
                     events.push(wrap(AgentEvent::AssistantDelta {
                         text: std::mem::take(&mut assistant_delta),
@@ -1211,6 +1664,8 @@ where
                         kind: ErrorKind::Internal,
                         message: "Agent event stream disconnected unexpectedly".to_string(),
                         details: None,
+                        http_status: None,
+                        request_id: None,
                     },
                     final_text: String::new(),
                     messages: Vec::new(),
@@ -1337,7 +1792,7 @@ fn resolve_terminal() -> Option<&'static ResolvedTerminal> {
         .find(|t| term_program.eq_ignore_ascii_case(t.name))
         .or_else(|| {
             KNOWN_TERMINALS.iter().find(|t| {
-                Command::new("which")
+                Command::new(if cfg!(windows) { "where" } else { "which" })
                     .arg(t.bin)
                     .stdout(Stdio::null())
                     .stderr(Stdio::null())
@@ -1469,10 +1924,7 @@ fn map_forked_to_tab(event: UiEvent) -> UiEvent {
 
 #[cfg(test)]
 mod tests {
-    use std::sync::Arc;
-
     use serde_json::json;
-    use tokio::sync::mpsc;
     use tokio_util::sync::CancellationToken;
     use zdx_engine::core::events::TurnStatus;
 
@@ -1480,45 +1932,39 @@ mod tests {
 
     #[test]
     fn drain_agent_rx_folds_deltas_and_preserves_lifecycle_order() {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = zdx_engine::core::agent::create_event_channel();
         let mut agent_state = AgentState::Waiting {
             rx,
             cancel: CancellationToken::new(),
+            soft_stop: CancellationToken::new(),
         };
 
-        tx.send(Arc::new(AgentEvent::AssistantDelta {
+        tx.send(AgentEvent::AssistantDelta {
             text: "hel".to_string(),
-        }))
-        .unwrap();
-        tx.send(Arc::new(AgentEvent::AssistantDelta {
+        });
+        tx.send(AgentEvent::AssistantDelta {
             text: "lo".to_string(),
-        }))
-        .unwrap();
-        tx.send(Arc::new(AgentEvent::ToolRequested {
+        });
+        tx.send(AgentEvent::ToolRequested {
             id: "tool-1".to_string(),
             name: "read".to_string(),
             input: json!({}),
-        }))
-        .unwrap();
-        tx.send(Arc::new(AgentEvent::ReasoningDelta {
+        });
+        tx.send(AgentEvent::ReasoningDelta {
             text: "a".to_string(),
-        }))
-        .unwrap();
-        tx.send(Arc::new(AgentEvent::ReasoningDelta {
+        });
+        tx.send(AgentEvent::ReasoningDelta {
             text: "b".to_string(),
-        }))
-        .unwrap();
-        tx.send(Arc::new(AgentEvent::AssistantDelta {
+        });
+        tx.send(AgentEvent::AssistantDelta {
             text: "!".to_string(),
-        }))
-        .unwrap();
-        tx.send(Arc::new(AgentEvent::TurnFinished {
+        });
+        tx.send(AgentEvent::TurnFinished {
             status: TurnStatus::Completed,
             final_text: "hello!".to_string(),
             messages: Vec::new(),
             prior_message_count: 0,
-        }))
-        .unwrap();
+        });
 
         let mut events = Vec::new();
         drain_agent_rx(&mut agent_state, &mut events, UiEvent::Agent);
@@ -1554,16 +2000,16 @@ mod tests {
 
     #[test]
     fn drain_agent_rx_emits_failed_turn_when_channel_disconnects() {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = zdx_engine::core::agent::create_event_channel();
         let mut agent_state = AgentState::Waiting {
             rx,
             cancel: CancellationToken::new(),
+            soft_stop: CancellationToken::new(),
         };
 
-        tx.send(Arc::new(AgentEvent::AssistantDelta {
+        tx.send(AgentEvent::AssistantDelta {
             text: "partial".to_string(),
-        }))
-        .unwrap();
+        });
         drop(tx);
 
         let mut events = Vec::new();
@@ -1581,6 +2027,7 @@ mod tests {
                     kind: ErrorKind::Internal,
                     message,
                     details: None,
+                    ..
                 },
                 final_text,
                 messages,
@@ -1593,23 +2040,22 @@ mod tests {
 
     #[test]
     fn drain_agent_rx_does_not_emit_false_disconnect_after_real_turn_finished() {
-        let (tx, rx) = mpsc::unbounded_channel();
+        let (tx, rx) = zdx_engine::core::agent::create_event_channel();
         let mut agent_state = AgentState::Waiting {
             rx,
             cancel: CancellationToken::new(),
+            soft_stop: CancellationToken::new(),
         };
 
-        tx.send(Arc::new(AgentEvent::AssistantDelta {
+        tx.send(AgentEvent::AssistantDelta {
             text: "done".to_string(),
-        }))
-        .unwrap();
-        tx.send(Arc::new(AgentEvent::TurnFinished {
+        });
+        tx.send(AgentEvent::TurnFinished {
             status: TurnStatus::Completed,
             final_text: "done".to_string(),
             messages: Vec::new(),
             prior_message_count: 0,
-        }))
-        .unwrap();
+        });
         drop(tx);
 
         let mut events = Vec::new();
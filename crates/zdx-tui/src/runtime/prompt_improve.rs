@@ -0,0 +1,27 @@
+//! `/improve` prompt rewrite handler.
+//!
+//! Thin TUI adapter over `zdx_engine::core::prompt_improve_generation`: runs
+//! the shared engine generation and wraps the outcome in a `UiEvent` for the
+//! open improve overlay.
+
+use std::path::PathBuf;
+
+use tokio_util::sync::CancellationToken;
+use zdx_engine::core::prompt_improve_generation::improve_prompt;
+
+use crate::events::UiEvent;
+
+/// Rewrites `draft` and returns `UiEvent::PromptImproveResult`; cancellation
+/// is cooperative via the supplied token.
+pub async fn prompt_improve_generation(
+    draft: String,
+    model: String,
+    root: PathBuf,
+    no_save: bool,
+    cancel: Option<CancellationToken>,
+) -> UiEvent {
+    let result = improve_prompt(&draft, &model, &root, no_save, cancel)
+        .await
+        .map_err(|err| format!("{err:#}"));
+    UiEvent::PromptImproveResult { draft, result }
+}
@@ -0,0 +1,21 @@
+//! Idle-time thread summaries.
+//!
+//! Spawns a subagent to summarize the active thread once it has sat idle.
+//! The summary is written to the thread meta for the thread picker preview;
+//! nothing is shown in the transcript.
+
+use std::path::PathBuf;
+
+use zdx_engine::core::summary_generation;
+
+use crate::events::{ThreadUiEvent, UiEvent};
+
+/// Generates and persists a thread summary.
+///
+/// Returns `UiEvent::Thread(ThreadUiEvent::SummaryGenerated)` (summary None on failure).
+pub async fn summarize_thread(thread_id: String, title_model: String, root: PathBuf) -> UiEvent {
+    let summary = summary_generation::summarize_thread(&thread_id, &title_model, &root)
+        .await
+        .ok();
+    UiEvent::Thread(ThreadUiEvent::SummaryGenerated { thread_id, summary })
+}
@@ -21,6 +21,8 @@
 //! │   ├── auth: AuthState        (authentication status)
 //! │   └── agent_state: AgentState (execution state)
 //! ├── background_tabs: Vec<TuiState> (inactive tabs)
+//! ├── compare: Option<CompareSession> (open `/compare` branches)
+//! ├── tour: Option<TourStep>     (current `/tour` step)
 //! └── overlay: Option<Overlay>   (modal overlays)
 //! ```
 //!
@@ -40,25 +42,36 @@
 //!
 //! This allows overlay handlers to get `&mut self` and `&mut TuiState` simultaneously.
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
-use std::sync::Arc;
 use std::time::{Duration, Instant};
 
-use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use zdx_engine::config::Config;
-use zdx_engine::core::agent::{AgentOptions, ToolConfig};
-use zdx_engine::core::events::AgentEvent;
+use zdx_engine::core::agent::{
+    AgentOptions, ToolConfig, ToolSelection, TurnOptions, selected_tool_names,
+};
+use zdx_engine::core::ask_user::UserQuestion;
+use zdx_engine::core::duel::{is_duel_message, parse_turn};
+use zdx_engine::core::event_bus::EventSubscriber;
+use zdx_engine::core::regeneration::{is_regeneration_message, regeneration_feedback};
+use zdx_engine::core::steering::{SteeringQueue, is_steering_message};
 use zdx_engine::core::thread_persistence::Thread;
 use zdx_engine::custom_commands::CustomCommand;
-use zdx_engine::providers::{ChatContentBlock, ChatMessage, ProviderKind, resolve_provider};
+use zdx_engine::providers::{
+    ChatContentBlock, ChatMessage, ProviderKind, provider_for_model, resolve_provider,
+};
+use zdx_engine::tools::check::is_check_message;
+use zdx_engine::tools::protected_files::ProtectedApprovals;
 
 use crate::auth::AuthState;
 use crate::common::{TaskSeq, Tasks};
+use crate::compare::CompareSession;
+use crate::duel::DuelSession;
 use crate::input::InputState;
-use crate::overlays::Overlay;
+use crate::overlays::{Overlay, ThreadScope};
 use crate::thread::ThreadState;
+use crate::tour::TourStep;
 use crate::transcript::{CellId, HistoryCell, TranscriptState, reasoning_display_text};
 
 // ============================================================================
@@ -128,6 +141,8 @@ pub struct AppState {
     /// across tabs because reload-on-root-change is intentionally deferred
     /// (see `docs/plans/active/custom-commands.md` Slice 2).
     pub custom_commands: Vec<CustomCommand>,
+    /// `Ask_User` questions waiting for the current overlay to close.
+    pub pending_questions: VecDeque<UserQuestion>,
     /// Last terminal window/tab title written via OSC, used to dedupe the
     /// per-tick title refresh so it only emits when the value changes.
     pub last_term_title: Option<String>,
@@ -137,6 +152,10 @@ pub struct AppState {
     /// Whether the terminal window is currently focused.
     /// Used to throttle rendering when backgrounded.
     pub is_focused: bool,
+    /// Open `/compare` session, if any.
+    pub compare: Option<CompareSession>,
+    /// Current `/tour` step, while the tour is shown.
+    pub tour: Option<TourStep>,
 }
 
 impl AppState {
@@ -157,6 +176,8 @@ impl AppState {
                 &self.overlay,
                 Some(Overlay::ThreadPicker(picker)) if picker.should_show_copied()
             )
+            || matches!(&self.overlay, Some(Overlay::AskUser(ask)) if !ask.is_pending())
+            || (self.overlay.is_none() && !self.pending_questions.is_empty())
     }
 
     /// Creates a new `AppState`.
@@ -180,7 +201,9 @@ impl AppState {
         thread_handle: Option<Thread>,
         history: Vec<ChatMessage>,
     ) -> Self {
-        Self {
+        // Launching without a thread (`--no-thread`) starts in incognito mode.
+        let incognito = thread_handle.is_none();
+        let mut state = Self {
             tui: TuiState::with_history(
                 TabId(0),
                 TabKind::Main,
@@ -194,10 +217,15 @@ impl AppState {
             background_tabs: Vec::new(),
             next_tab_id: 1, // 0 is used for the initial tab
             custom_commands: Vec::new(),
+            pending_questions: VecDeque::new(),
             last_term_title: None,
             last_cmux_status: None,
             is_focused: true,
-        }
+            compare: None,
+            tour: None,
+        };
+        state.tui.thread.incognito = incognito;
+        state
     }
 
     /// Replaces the discovered custom commands. Intended for one-shot setup
@@ -242,6 +270,7 @@ impl AppState {
             .position(|t| t.tab_id == target_id)
         {
             let mut target = self.background_tabs.remove(pos);
+            target.input.bash_history = std::mem::take(&mut self.tui.input.bash_history);
             std::mem::swap(&mut self.tui, &mut target);
             self.background_tabs.push(target);
             self.tui.unseen_completion = false;
@@ -249,7 +278,8 @@ impl AppState {
     }
 
     /// Adds a new tab and switches to it (current tab moves to background).
-    pub fn push_tab(&mut self, tab: TuiState) {
+    pub fn push_tab(&mut self, mut tab: TuiState) {
+        tab.input.bash_history = std::mem::take(&mut self.tui.input.bash_history);
         let prev = std::mem::replace(&mut self.tui, tab);
         self.background_tabs.push(prev);
     }
@@ -258,7 +288,8 @@ impl AppState {
     /// Returns `true` if another tab became active, or `false` when there were
     /// no other tabs left.
     pub fn close_active_tab(&mut self) -> bool {
-        if let Some(target) = self.background_tabs.pop() {
+        if let Some(mut target) = self.background_tabs.pop() {
+            target.input.bash_history = std::mem::take(&mut self.tui.input.bash_history);
             self.tui = target;
             self.tui.unseen_completion = false;
             true
@@ -267,6 +298,19 @@ impl AppState {
         }
     }
 
+    /// Finds a tab (active or background) by ID.
+    pub fn tab(&self, tab_id: TabId) -> Option<&TuiState> {
+        self.all_tabs().find(|t| t.tab_id == tab_id)
+    }
+
+    /// Finds a tab (active or background) by ID and returns a mutable reference.
+    pub fn tab_mut(&mut self, tab_id: TabId) -> Option<&mut TuiState> {
+        if self.tui.tab_id == tab_id {
+            return Some(&mut self.tui);
+        }
+        self.background_tab_mut(tab_id)
+    }
+
     /// Finds a background tab by ID and returns a mutable reference.
     pub fn background_tab_mut(&mut self, tab_id: TabId) -> Option<&mut TuiState> {
         self.background_tabs.iter_mut().find(|t| t.tab_id == tab_id)
@@ -279,8 +323,8 @@ impl AppState {
 
 /// Agent execution state.
 ///
-/// Tracks the current agent task and its event channel.
-/// The task sends events through the channel, including `TurnFinished` when done.
+/// Tracks the current agent task and its event bus subscription.
+/// The task publishes events on the bus, including `TurnFinished` when done.
 #[derive(Debug)]
 pub enum AgentState {
     /// No agent task running, ready for input.
@@ -288,9 +332,11 @@ pub enum AgentState {
     /// Streaming response in progress.
     Streaming {
         /// Receiver for agent events.
-        rx: mpsc::UnboundedReceiver<Arc<AgentEvent>>,
+        rx: EventSubscriber,
         /// Cancellation token for this run.
         cancel: CancellationToken,
+        /// Soft-stop token for this run.
+        soft_stop: CancellationToken,
         /// ID of the streaming assistant cell in transcript.
         cell_id: CellId,
         /// Buffered delta text to apply on next tick (coalescing).
@@ -299,9 +345,11 @@ pub enum AgentState {
     /// Waiting for first response.
     Waiting {
         /// Receiver for agent events.
-        rx: mpsc::UnboundedReceiver<Arc<AgentEvent>>,
+        rx: EventSubscriber,
         /// Cancellation token for this run.
         cancel: CancellationToken,
+        /// Soft-stop token for this run.
+        soft_stop: CancellationToken,
     },
 }
 
@@ -318,6 +366,19 @@ impl AgentState {
             }
         }
     }
+    pub fn soft_stop_token(&self) -> Option<CancellationToken> {
+        match self {
+            AgentState::Idle => None,
+            AgentState::Waiting { soft_stop, .. } | AgentState::Streaming { soft_stop, .. } => {
+                Some(soft_stop.clone())
+            }
+        }
+    }
+    /// Returns true once a soft stop was requested for the running turn.
+    pub fn soft_stop_requested(&self) -> bool {
+        self.soft_stop_token()
+            .is_some_and(|token| token.is_cancelled())
+    }
 }
 
 /// Outcome of the most recent finished turn, used to render the idle cmux
@@ -374,8 +435,15 @@ pub struct TuiState {
     pub agent_opts: AgentOptions,
     /// System prompt for the agent.
     pub system_prompt: Option<String>,
+    /// `[[prompt_variants]]` entry `system_prompt` was built with; a turn
+    /// whose model selects a different variant rebuilds the prompt first.
+    pub system_prompt_variant: Option<String>,
     /// Current agent state.
     pub agent_state: AgentState,
+    /// Steering notes for the running turn (`/steer`), shared with the agent.
+    pub steering: SteeringQueue,
+    /// Running `/duel` discussion, if any.
+    pub duel: Option<DuelSession>,
     /// Outcome of the most recent finished turn, for the idle cmux status pill.
     pub last_turn_outcome: Option<TurnOutcome>,
     /// True when this tab finished a turn while in the background and the user
@@ -392,6 +460,8 @@ pub struct TuiState {
     pub status_line: crate::statusline::StatusLineAccumulator,
     /// Whether to show the debug status line.
     pub show_debug_status: bool,
+    /// Scope the thread picker opens with (last one chosen with Ctrl+S).
+    pub thread_picker_scope: ThreadScope,
     /// Input area rect (set during render, used for mouse click routing).
     pub input_area: std::cell::Cell<ratatui::layout::Rect>,
     /// Transcript content area rect (set during render, used for mouse click routing).
@@ -406,6 +476,11 @@ pub struct TuiState {
     pub(crate) active_threads_scanned_at: Option<Instant>,
     /// Suggested replies from the most recent reply (Ctrl+F to pick).
     pub last_followups: Vec<String>,
+    /// `(enabled, total)` tools when `/tools` narrowed the selection; shown
+    /// in the status line.
+    pub tool_filter: Option<(usize, usize)>,
+    /// Last key, paste, or mouse input or agent event; drives idle summaries.
+    pub last_activity: Instant,
 }
 
 impl TuiState {
@@ -421,7 +496,7 @@ impl TuiState {
         thread_handle: Option<Thread>,
         history: Vec<ChatMessage>,
     ) -> Self {
-        let tool_config = ToolConfig::default();
+        let tool_config = ToolConfig::from_config(&config);
         let agent_opts = AgentOptions {
             root,
             tool_config,
@@ -431,6 +506,11 @@ impl TuiState {
             activity_kind: Some("chat".to_string()),
             activity_parent_thread_id: None,
             activity_subagent_name: None,
+            soft_stop: None,
+            steering: None,
+            ask_user: None,
+            protected_approvals: ProtectedApprovals::default(),
+            turn: TurnOptions::default(),
         };
 
         // Cache display values at startup (avoids I/O during render)
@@ -453,7 +533,10 @@ impl TuiState {
             .collect();
 
         // Create transcript state with history
-        let transcript = TranscriptState::with_cells(transcript_cells);
+        let mut transcript = TranscriptState::with_cells(transcript_cells);
+        if config.tui.bash_tool_output_inline {
+            transcript.set_bash_tool_output_lines(config.tui.bash_output_lines);
+        }
 
         // Create input state with command history
         let mut input = InputState::new();
@@ -465,6 +548,10 @@ impl TuiState {
         // Create auth state
         let auth = AuthState::new();
 
+        let system_prompt_variant = config
+            .prompt_variant_for(&config.model)
+            .map(|variant| variant.name.clone());
+
         Self {
             tab_id,
             tab_kind,
@@ -482,7 +569,10 @@ impl TuiState {
             loaded_skills: Vec::new(),
             agent_opts,
             system_prompt,
+            system_prompt_variant,
             agent_state: AgentState::Idle,
+            steering: SteeringQueue::new(),
+            duel: None,
             last_turn_outcome: None,
             unseen_completion: false,
             spinner_frame: 0,
@@ -490,15 +580,39 @@ impl TuiState {
             display_path,
             status_line: crate::statusline::StatusLineAccumulator::new(),
             show_debug_status: false,
+            thread_picker_scope: ThreadScope::default(),
             input_area: std::cell::Cell::new(ratatui::layout::Rect::default()),
             transcript_area: std::cell::Cell::new(ratatui::layout::Rect::default()),
             optimistic_active_threads: HashMap::new(),
             active_threads_scan: HashSet::new(),
             active_threads_scanned_at: None,
             last_followups: Vec::new(),
+            tool_filter: None,
+            last_activity: Instant::now(),
         }
     }
 
+    /// Replaces the tool selection (e.g. from `--tools` / `--deny-tools`),
+    /// updates the status-line tool filter, and returns a notice for the
+    /// transcript.
+    pub fn apply_tool_selection(&mut self, selection: ToolSelection) -> String {
+        self.agent_opts.tool_config.selection = selection;
+        let provider = provider_for_model(&self.config.model);
+        let enabled = selected_tool_names(&self.config, &self.agent_opts, provider).len();
+        let total = self
+            .agent_opts
+            .tool_config
+            .registry
+            .definitions()
+            .iter()
+            .filter(|tool| {
+                self.config.subagents.enabled || !tool.name.eq_ignore_ascii_case("Invoke_Subagent")
+            })
+            .count();
+        self.tool_filter = Some((enabled, total));
+        format!("Tools: {enabled}/{total} enabled (--tools / --deny-tools)")
+    }
+
     pub fn mark_thread_running(&mut self, thread_id: String) {
         self.optimistic_active_threads
             .insert(thread_id, Instant::now());
@@ -511,6 +625,17 @@ impl TuiState {
         self.active_threads_scan.remove(thread_id);
     }
 
+    /// Copies the view toggles that follow the user across tabs (and are
+    /// saved in `ui_state.json`) from `parent`.
+    pub fn inherit_view_prefs(&mut self, parent: &TuiState) {
+        self.show_debug_status = parent.show_debug_status;
+        self.thread_picker_scope = parent.thread_picker_scope;
+        self.transcript
+            .set_subagent_details_collapsed(parent.transcript.subagent_details_collapsed);
+        self.transcript
+            .set_timestamp_mode(parent.transcript.timestamp_mode);
+    }
+
     pub fn snapshot_active_thread_ids(&mut self) -> HashSet<String> {
         // The on-disk scan gates submit-time behavior plus the load/preview
         // guards, so brief staleness is harmless. Throttle it so plain typing
@@ -552,14 +677,47 @@ impl TuiState {
         use zdx_engine::providers::MessageContent;
 
         let mut transcript = Vec::new();
+        // `/duel` turns are assistant messages headed with their speaker;
+        // the duel runs from its opening message to the next prompt.
+        let mut duel_speakers: Option<Vec<String>> = None;
 
         for msg in messages {
+            if msg.role == "user" {
+                if is_duel_message(msg) {
+                    duel_speakers.get_or_insert_with(Vec::new);
+                } else {
+                    duel_speakers = None;
+                }
+            }
+            if let Some(speakers) = duel_speakers.as_mut()
+                && let Some((speaker, reply)) = parse_turn(msg)
+            {
+                let index = speakers
+                    .iter()
+                    .position(|known| known == speaker)
+                    .unwrap_or_else(|| {
+                        speakers.push(speaker.to_string());
+                        speakers.len() - 1
+                    });
+                let second = !index.is_multiple_of(2);
+                transcript.push(HistoryCell::duel(speaker, second, reply));
+                continue;
+            }
             match &msg.content {
                 MessageContent::Text(t) => {
                     if t.is_empty() {
                         continue;
                     }
                     let cell = match msg.role.as_str() {
+                        "user" if is_steering_message(msg) => {
+                            HistoryCell::system(format!("Steering: {t}"))
+                        }
+                        "user" if is_check_message(msg) || is_duel_message(msg) => {
+                            HistoryCell::system(t)
+                        }
+                        "user" if is_regeneration_message(msg) => {
+                            HistoryCell::system(format!("Redo: {}", regeneration_feedback(t)))
+                        }
                         "user" => HistoryCell::user(t),
                         "assistant" => HistoryCell::assistant(t),
                         _ => continue,
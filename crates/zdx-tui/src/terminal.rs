@@ -8,6 +8,7 @@
 
 use std::io::{self, Stdout};
 use std::panic;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use anyhow::{Context, Result};
 use crossterm::event::{
@@ -19,8 +20,12 @@ use crossterm::execute;
 use crossterm::terminal::{
     EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
 };
-use ratatui::Terminal;
 use ratatui::backend::CrosstermBackend;
+use ratatui::{Terminal, TerminalOptions, Viewport};
+
+/// Whether the alternate screen is active, so restore only leaves it when it
+/// was entered (leaving it from inline mode would move the cursor).
+static IN_ALTERNATE_SCREEN: AtomicBool = AtomicBool::new(false);
 
 /// Sets up the terminal for the TUI.
 ///
@@ -36,15 +41,40 @@ pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
     enable_raw_mode().context("Failed to enable raw mode")?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen).context("Failed to enter alternate screen")?;
+    IN_ALTERNATE_SCREEN.store(true, Ordering::SeqCst);
     let backend = CrosstermBackend::new(stdout);
     let terminal = Terminal::new(backend).context("Failed to create terminal")?;
     Ok(terminal)
 }
 
+/// Sets up the terminal for inline mode (`zdx --inline`).
+///
+/// - Enables raw mode
+/// - Stays in the normal buffer with a `height`-row viewport at the cursor
+///
+/// Content inserted above the viewport scrolls into the terminal's own
+/// scrollback and remains there after exit.
+///
+/// # Errors
+/// Returns an error if the operation fails.
+pub fn setup_inline_terminal(height: u16) -> Result<Terminal<CrosstermBackend<Stdout>>> {
+    enable_raw_mode().context("Failed to enable raw mode")?;
+    let backend = CrosstermBackend::new(io::stdout());
+    let terminal = Terminal::with_options(
+        backend,
+        TerminalOptions {
+            viewport: Viewport::Inline(height),
+        },
+    )
+    .context("Failed to create terminal")?;
+    Ok(terminal)
+}
+
 /// Enables additional terminal features for the TUI event loop.
 ///
 /// - Enables bracketed paste mode
-/// - Enables mouse capture
+/// - Enables mouse capture (skipped when `mouse_capture` is false, e.g. in
+///   accessibility mode so the terminal's own selection keeps working)
 ///
 /// These are enabled separately from `setup_terminal()` because they need to be
 /// disabled before `restore_terminal()` in normal exit paths, but `restore_terminal()`
@@ -52,18 +82,41 @@ pub fn setup_terminal() -> Result<Terminal<CrosstermBackend<Stdout>>> {
 ///
 /// # Errors
 /// Returns an error if the operation fails.
-pub fn enable_input_features() -> Result<()> {
+pub fn enable_input_features(mouse_capture: bool) -> Result<()> {
+    execute!(io::stdout(), EnableBracketedPaste, EnableFocusChange)
+        .context("Failed to enable input features")?;
+    if mouse_capture {
+        execute!(io::stdout(), EnableMouseCapture).context("Failed to enable mouse capture")?;
+    }
+    push_keyboard_enhancement()?;
+    Ok(())
+}
+
+/// Enables kitty keyboard disambiguation where the backend supports it.
+///
+/// crossterm drives Windows consoles (including Windows Terminal) through the
+/// console API, which rejects keyboard enhancement flags, so this is a no-op
+/// there.
+fn push_keyboard_enhancement() -> Result<()> {
+    if cfg!(windows) {
+        return Ok(());
+    }
     execute!(
         io::stdout(),
-        EnableBracketedPaste,
-        EnableMouseCapture,
-        EnableFocusChange,
         PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES)
     )
-    .context("Failed to enable input features")?;
+    .context("Failed to enable keyboard enhancement")?;
     Ok(())
 }
 
+/// Pops the keyboard enhancement pushed by [`push_keyboard_enhancement`].
+fn pop_keyboard_enhancement() -> io::Result<()> {
+    if cfg!(windows) {
+        return Ok(());
+    }
+    execute!(io::stdout(), PopKeyboardEnhancementFlags)
+}
+
 /// Disables additional terminal features enabled by `enable_input_features()`.
 ///
 /// Call this before `restore_terminal()` in normal exit paths.
@@ -71,9 +124,9 @@ pub fn enable_input_features() -> Result<()> {
 /// # Errors
 /// Returns an error if the operation fails.
 pub fn disable_input_features() -> Result<()> {
+    pop_keyboard_enhancement().context("Failed to disable keyboard enhancement")?;
     execute!(
         io::stdout(),
-        PopKeyboardEnhancementFlags,
         DisableMouseCapture,
         DisableBracketedPaste,
         DisableFocusChange
@@ -86,7 +139,7 @@ pub fn disable_input_features() -> Result<()> {
 ///
 /// - Disables mouse capture (safe to call even if not enabled)
 /// - Disables bracketed paste (safe to call even if not enabled)
-/// - Leaves alternate screen
+/// - Leaves alternate screen (if entered)
 /// - Disables raw mode
 ///
 /// This function is idempotent and safe to call multiple times.
@@ -96,21 +149,24 @@ pub fn disable_input_features() -> Result<()> {
 pub fn restore_terminal() -> Result<()> {
     // Disable mouse, bracketed paste, and focus events first (safe even if not enabled)
     // These must be disabled before leaving raw mode
+    let _ = pop_keyboard_enhancement();
     let _ = execute!(
         io::stdout(),
-        PopKeyboardEnhancementFlags,
         DisableMouseCapture,
         DisableBracketedPaste,
         DisableFocusChange
     );
 
     // Leave alternate screen (while still in raw mode)
-    execute!(io::stdout(), LeaveAlternateScreen).context("Failed to leave alternate screen")?;
+    if IN_ALTERNATE_SCREEN.swap(false, Ordering::SeqCst) {
+        execute!(io::stdout(), LeaveAlternateScreen).context("Failed to leave alternate screen")?;
+    }
     disable_raw_mode().context("Failed to disable raw mode")?;
     Ok(())
 }
 
-/// Installs a panic hook that restores the terminal before printing the panic.
+/// Installs a panic hook that restores the terminal before printing the panic,
+/// then writes a crash report (see `crate::crash`) and prints its path.
 ///
 /// Call this BEFORE `setup_terminal()` to ensure terminal restore on panic.
 pub fn install_panic_hook() {
@@ -120,6 +176,9 @@ pub fn install_panic_hook() {
         let _ = restore_terminal();
         // Then call the original panic hook
         original_hook(panic_info);
+        if let Some(path) = crate::crash::write_report(panic_info) {
+            eprintln!("Crash report written to {}", path.display());
+        }
     }));
 }
 
@@ -0,0 +1,348 @@
+//! Onboarding tour (`/tour`).
+//!
+//! Walks through the core interactions one step at a time: sending a
+//! message, slash commands, the model picker, the thread picker, and
+//! interrupting a turn. Each step shows a callout above the input and
+//! advances when the user does the real thing, observed from the effects and
+//! overlays a key press produces. `/tour` again hides the tour; progress is
+//! saved to `$ZDX_HOME/tour.json`, so the next `/tour` resumes at the same
+//! step (and restarts once the tour is finished).
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
+use serde::{Deserialize, Serialize};
+use zdx_engine::config::paths;
+
+use crate::effects::UiEffect;
+use crate::mutations::TranscriptMutation;
+use crate::overlays::{Overlay, ThreadPickerMode};
+use crate::state::AppState;
+
+const PROGRESS_FILE: &str = "tour.json";
+
+/// Callout width, including borders.
+const CALLOUT_WIDTH: u16 = 56;
+
+/// One tour step, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TourStep {
+    SendMessage,
+    SlashCommands,
+    ModelPicker,
+    ThreadPicker,
+    Interrupt,
+}
+
+impl TourStep {
+    const ALL: [Self; 5] = [
+        Self::SendMessage,
+        Self::SlashCommands,
+        Self::ModelPicker,
+        Self::ThreadPicker,
+        Self::Interrupt,
+    ];
+
+    fn number(self) -> usize {
+        Self::ALL.iter().position(|step| *step == self).unwrap_or(0) + 1
+    }
+
+    fn next(self) -> Option<Self> {
+        Self::ALL.get(self.number()).copied()
+    }
+
+    fn title(self) -> &'static str {
+        match self {
+            Self::SendMessage => "Send a message",
+            Self::SlashCommands => "Run a slash command",
+            Self::ModelPicker => "Pick a model",
+            Self::ThreadPicker => "Browse threads",
+            Self::Interrupt => "Interrupt a turn",
+        }
+    }
+
+    fn hint(self) -> &'static str {
+        match self {
+            Self::SendMessage => {
+                "Type a question below and press Enter. Shift+Enter adds a new line."
+            }
+            Self::SlashCommands => {
+                "Type / in an empty input to list commands, or press Ctrl+O for the \
+                 command palette."
+            }
+            Self::ModelPicker => "Press Ctrl+L (or run /model) to switch models for this thread.",
+            Self::ThreadPicker => "Run /threads to browse saved threads and switch between them.",
+            Self::Interrupt => {
+                "Send a longer request, then press Esc while it runs: once to wrap up \
+                 after the running tools, twice to stop right away."
+            }
+        }
+    }
+
+    /// Whether the effects and overlay produced by one key press complete
+    /// this step.
+    fn completed_by(self, effects: &[UiEffect], overlay: Option<&Overlay>) -> bool {
+        match self {
+            Self::SendMessage => effects
+                .iter()
+                .any(|effect| matches!(effect, UiEffect::StartAgentTurn { .. })),
+            Self::SlashCommands => matches!(
+                overlay,
+                Some(Overlay::CommandPalette(_) | Overlay::SlashComplete(_))
+            ) || effects.iter().any(
+                |effect| matches!(effect, UiEffect::RecordCommandUse { name } if *name != "tour"),
+            ),
+            Self::ModelPicker => matches!(overlay, Some(Overlay::ModelPicker(_))),
+            Self::ThreadPicker => effects.iter().any(|effect| {
+                matches!(
+                    effect,
+                    UiEffect::OpenThreadPicker {
+                        mode: ThreadPickerMode::Switch
+                    }
+                )
+            }),
+            Self::Interrupt => effects
+                .iter()
+                .any(|effect| matches!(effect, UiEffect::SoftStopAgent | UiEffect::InterruptAgent)),
+        }
+    }
+}
+
+/// Saved tour progress.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TourProgress {
+    /// Step to resume at. `None` when the tour was never started or is done.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub step: Option<TourStep>,
+    #[serde(default)]
+    pub completed: bool,
+}
+
+impl TourProgress {
+    fn resume_step(self) -> TourStep {
+        self.step.unwrap_or(TourStep::SendMessage)
+    }
+}
+
+fn progress_path() -> PathBuf {
+    paths::zdx_home().join(PROGRESS_FILE)
+}
+
+/// Loads saved progress. A missing or unreadable file starts from scratch.
+pub fn load_progress() -> TourProgress {
+    fs::read_to_string(progress_path())
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves tour progress.
+///
+/// # Errors
+/// Returns an error if the progress file cannot be written.
+pub fn save_progress(progress: TourProgress) -> Result<()> {
+    let path = progress_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+    let json = serde_json::to_string(&progress)?;
+    fs::write(&path, json).with_context(|| format!("write {}", path.display()))
+}
+
+/// Shows or hides the tour. Showing resumes from `saved` (restarting a
+/// finished tour); hiding keeps the current step for the next `/tour`.
+pub fn toggle(app: &mut AppState, saved: TourProgress) -> Vec<UiEffect> {
+    let (message, progress) = if let Some(step) = app.tour.take() {
+        (
+            "Tour hidden. Run /tour to pick up where you left off.",
+            TourProgress {
+                step: Some(step),
+                completed: false,
+            },
+        )
+    } else {
+        let step = saved.resume_step();
+        app.tour = Some(step);
+        let message = if step == TourStep::SendMessage {
+            "Tour started. Follow the callout above the input; run /tour again to hide it."
+        } else {
+            "Tour resumed. Run /tour again to hide it."
+        };
+        (
+            message,
+            TourProgress {
+                step: Some(step),
+                completed: false,
+            },
+        )
+    };
+    app.tui
+        .transcript
+        .apply(TranscriptMutation::AppendSystemMessage(message.to_string()));
+    vec![UiEffect::SaveTourProgress { progress }]
+}
+
+/// Advances the tour when `effects` (from one terminal event) and the
+/// resulting overlay complete the current step.
+pub fn observe(app: &mut AppState, effects: &[UiEffect]) -> Option<UiEffect> {
+    let step = app.tour?;
+    if !step.completed_by(effects, app.overlay.as_ref()) {
+        return None;
+    }
+    app.tour = step.next();
+    if app.tour.is_none() {
+        app.tui
+            .transcript
+            .apply(TranscriptMutation::AppendSystemMessage(
+                "Tour complete. Ctrl+O lists every command; /tour runs the tour again.".to_string(),
+            ));
+    }
+    let progress = TourProgress {
+        step: app.tour,
+        completed: app.tour.is_none(),
+    };
+    Some(UiEffect::SaveTourProgress { progress })
+}
+
+/// Renders the current step as a callout anchored above the input, at the
+/// right edge. Nothing is drawn when the tour is hidden or the area is too
+/// small.
+pub fn render_callout(app: &AppState, frame: &mut Frame, area: Rect, input_y: u16) {
+    let Some(step) = app.tour else {
+        return;
+    };
+    let width = CALLOUT_WIDTH.min(area.width.saturating_sub(2));
+    if width < 24 {
+        return;
+    }
+    let text_width = usize::from(width - 2);
+    let hint_lines = step
+        .hint()
+        .len()
+        .div_ceil(text_width.saturating_sub(4))
+        .max(1);
+    let height = u16::try_from(hint_lines + 4).unwrap_or(u16::MAX);
+    if input_y < area.y + height {
+        return;
+    }
+    let callout = Rect {
+        x: area.x + area.width - width - 1,
+        y: input_y - height,
+        width,
+        height,
+    };
+
+    let lines = vec![
+        Line::from(Span::styled(
+            step.title(),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(step.hint()),
+        Line::default(),
+        Line::from(Span::styled(
+            "/tour hides this",
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Yellow))
+        .title(format!(" Tour {}/{} ", step.number(), TourStep::ALL.len()));
+    frame.render_widget(Clear, callout);
+    frame.render_widget(
+        Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+        callout,
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use zdx_engine::config::Config;
+
+    use super::*;
+
+    fn app() -> AppState {
+        AppState::new(Config::default(), PathBuf::new(), None, None)
+    }
+
+    #[test]
+    fn steps_advance_only_on_the_matching_action() {
+        let mut app = app();
+        toggle(&mut app, TourProgress::default());
+        assert_eq!(app.tour, Some(TourStep::SendMessage));
+
+        assert!(observe(&mut app, &[UiEffect::InterruptAgent]).is_none());
+        let saved = observe(
+            &mut app,
+            &[UiEffect::StartAgentTurn {
+                thinking_override: None,
+            }],
+        );
+        assert_eq!(app.tour, Some(TourStep::SlashCommands));
+        assert!(matches!(
+            saved,
+            Some(UiEffect::SaveTourProgress {
+                progress: TourProgress {
+                    step: Some(TourStep::SlashCommands),
+                    completed: false,
+                }
+            })
+        ));
+
+        // Running `/tour` itself does not count as trying a command.
+        assert!(observe(&mut app, &[UiEffect::RecordCommandUse { name: "tour" }]).is_none());
+        observe(&mut app, &[UiEffect::RecordCommandUse { name: "stats" }]);
+        assert_eq!(app.tour, Some(TourStep::ModelPicker));
+    }
+
+    #[test]
+    fn last_step_completes_the_tour() {
+        let mut app = app();
+        app.tour = Some(TourStep::Interrupt);
+        let saved = observe(&mut app, &[UiEffect::SoftStopAgent]);
+        assert_eq!(app.tour, None);
+        assert!(matches!(
+            saved,
+            Some(UiEffect::SaveTourProgress {
+                progress: TourProgress {
+                    step: None,
+                    completed: true,
+                }
+            })
+        ));
+    }
+
+    #[test]
+    fn hiding_keeps_the_step_and_showing_resumes_it() {
+        let mut app = app();
+        app.tour = Some(TourStep::ThreadPicker);
+        let effects = toggle(&mut app, TourProgress::default());
+        assert_eq!(app.tour, None);
+        let [UiEffect::SaveTourProgress { progress }] = effects.as_slice() else {
+            panic!("expected a progress save, got {effects:?}");
+        };
+
+        toggle(&mut app, *progress);
+        assert_eq!(app.tour, Some(TourStep::ThreadPicker));
+
+        // A finished tour starts over.
+        app.tour = None;
+        toggle(
+            &mut app,
+            TourProgress {
+                step: None,
+                completed: true,
+            },
+        );
+        assert_eq!(app.tour, Some(TourStep::SendMessage));
+    }
+}
@@ -0,0 +1,180 @@
+//! UI preferences persisted across launches in `$ZDX_HOME/ui_state.json`.
+//!
+//! Holds the view toggles flipped inside the TUI (subagent details, cell
+//! timestamps, the debug status line, the thread picker scope) and the last
+//! thread used in each project root. It is separate from `config.toml`: the
+//! runtime applies it at startup and rewrites it whenever the active tab's
+//! values change, so it never needs hand-editing. A missing or unreadable file
+//! means defaults.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use zdx_engine::config::paths;
+use zdx_transcript::TimestampMode;
+
+use crate::overlays::ThreadScope;
+use crate::state::{TabKind, TuiState};
+
+const STATE_FILE: &str = "ui_state.json";
+
+/// Saved UI preferences.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiState {
+    /// Last thread open in the main tab, keyed by canonical project root.
+    pub last_threads: BTreeMap<String, String>,
+    /// `/subagent-details`: live subagent progress collapsed in tool cells.
+    pub subagent_details_collapsed: bool,
+    /// `/timestamps`: how cell timestamps are shown.
+    pub timestamps: TimestampMode,
+    /// `/debug`: debug status line visible.
+    pub show_debug_status: bool,
+    /// Thread picker scope (Ctrl+S).
+    pub thread_picker_scope: ThreadScope,
+}
+
+impl UiState {
+    /// Applies the saved view toggles to a tab.
+    pub fn apply(&self, tui: &mut TuiState) {
+        tui.show_debug_status = self.show_debug_status;
+        tui.thread_picker_scope = self.thread_picker_scope;
+        tui.transcript
+            .set_subagent_details_collapsed(self.subagent_details_collapsed);
+        tui.transcript.set_timestamp_mode(self.timestamps);
+    }
+
+    /// Returns this state updated from the active tab: its view toggles and,
+    /// for a saved thread in the main tab, the last thread for its root.
+    #[must_use]
+    pub fn capture(&self, tui: &TuiState) -> Self {
+        let mut state = Self {
+            last_threads: self.last_threads.clone(),
+            subagent_details_collapsed: tui.transcript.subagent_details_collapsed,
+            timestamps: tui.transcript.timestamp_mode,
+            show_debug_status: tui.show_debug_status,
+            thread_picker_scope: tui.thread_picker_scope,
+        };
+        if matches!(tui.tab_kind, TabKind::Main)
+            && !tui.thread.incognito
+            && let Some(thread) = &tui.thread.thread_handle
+        {
+            state.record_last_thread(&tui.agent_opts.root, &thread.id);
+        }
+        state
+    }
+
+    /// Records `thread_id` as the last thread used in `root`.
+    fn record_last_thread(&mut self, root: &Path, thread_id: &str) {
+        self.last_threads
+            .insert(root_key(root), thread_id.to_string());
+    }
+
+    /// Last thread used in `root`, if any.
+    pub fn last_thread(&self, root: &Path) -> Option<&str> {
+        self.last_threads.get(&root_key(root)).map(String::as_str)
+    }
+}
+
+fn root_key(root: &Path) -> String {
+    root.canonicalize()
+        .unwrap_or_else(|_| root.to_path_buf())
+        .display()
+        .to_string()
+}
+
+fn state_path() -> PathBuf {
+    paths::zdx_home().join(STATE_FILE)
+}
+
+/// Loads saved UI state. A missing or unreadable file yields defaults.
+pub fn load() -> UiState {
+    load_from(&state_path())
+}
+
+/// Loads UI state from `path`. A missing or unreadable file yields defaults.
+pub fn load_from(path: &Path) -> UiState {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| serde_json::from_str(&contents).ok())
+        .unwrap_or_default()
+}
+
+/// Saves UI state.
+///
+/// # Errors
+/// Returns an error if the state file cannot be written.
+pub fn save(state: &UiState) -> Result<()> {
+    save_to(&state_path(), state)
+}
+
+/// Saves UI state to `path`, creating its parent directory.
+///
+/// # Errors
+/// Returns an error if the state file cannot be written.
+pub fn save_to(path: &Path, state: &UiState) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+    let json = serde_json::to_string_pretty(state)?;
+    fs::write(path, json).with_context(|| format!("write {}", path.display()))
+}
+
+/// Last thread used in `root`, read from the saved UI state.
+pub fn last_thread_for(root: &Path) -> Option<String> {
+    load().last_thread(root).map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use zdx_engine::config::Config;
+
+    use super::*;
+    use crate::state::AppState;
+
+    #[test]
+    fn capture_round_trips_view_toggles_and_last_thread() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let mut app = AppState::new(Config::default(), root.clone(), None, None);
+        app.tui.transcript.toggle_subagent_details();
+        app.tui.transcript.cycle_timestamps();
+        app.tui.show_debug_status = true;
+        app.tui.thread_picker_scope = ThreadScope::All;
+
+        // A tab without a saved thread keeps the toggles but records no thread.
+        let mut state = UiState::default().capture(&app.tui);
+        assert_eq!(state.last_thread(dir.path()), None);
+        state.record_last_thread(dir.path(), "ui-state-thread");
+
+        let path = dir.path().join("nested").join(STATE_FILE);
+        save_to(&path, &state).unwrap();
+        let restored = load_from(&path);
+        assert_eq!(restored, state);
+        assert_eq!(restored.last_thread(dir.path()), Some("ui-state-thread"));
+
+        let mut fresh = AppState::new(Config::default(), root, None, None);
+        restored.apply(&mut fresh.tui);
+        assert!(fresh.tui.transcript.subagent_details_collapsed);
+        assert_eq!(fresh.tui.transcript.timestamp_mode, TimestampMode::Absolute);
+        assert!(fresh.tui.show_debug_status);
+        assert_eq!(fresh.tui.thread_picker_scope, ThreadScope::All);
+    }
+
+    #[test]
+    fn missing_state_file_loads_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(load_from(&dir.path().join(STATE_FILE)), UiState::default());
+    }
+
+    #[test]
+    fn unknown_or_missing_fields_fall_back_to_defaults() {
+        let state: UiState = serde_json::from_str(r#"{"show_debug_status":true,"x":1}"#).unwrap();
+        assert!(state.show_debug_status);
+        assert_eq!(state.timestamps, TimestampMode::Off);
+        assert!(state.last_threads.is_empty());
+    }
+}
@@ -6,11 +6,14 @@
 //!
 //! This is the single source of truth for how events modify state.
 
+use std::path::Path;
+
 use crossterm::event::Event;
+use zdx_engine::core::{regeneration, thread_persistence};
 
 use crate::common::{TaskKind, TaskMeta};
 use crate::effects::UiEffect;
-use crate::events::{SkillUiEvent, ThreadUiEvent, UiEvent};
+use crate::events::{SkillUiEvent, StartupContext, ThreadUiEvent, UiEvent};
 use crate::input::{HandoffState, PromptBuilderState};
 use crate::mutations::{ConfigMutation, InputMutation, StateMutation, TranscriptMutation};
 use crate::overlays::{self, FilePickerState, Overlay};
@@ -25,10 +28,19 @@ use crate::{auth, input, render, thread, transcript};
 pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
     match event {
         UiEvent::Tick => {
-            // Advance spinner animation
-            app.tui.spinner_frame = app.tui.spinner_frame.wrapping_add(1);
+            // Advance spinner animation (frozen in accessibility mode so
+            // screen readers don't re-announce the status line every tick)
+            if !app.tui.config.tui.a11y {
+                app.tui.spinner_frame = app.tui.spinner_frame.wrapping_add(1);
+            }
             // Check if selection should be auto-cleared after copy
             app.tui.transcript.check_selection_timeout();
+            // Drop a question whose turn was canceled, then show any question
+            // that was waiting for an overlay to close
+            if matches!(&app.overlay, Some(overlays::Overlay::AskUser(ask)) if !ask.is_pending()) {
+                app.overlay = None;
+            }
+            open_next_question(app);
             // Apply pending streaming deltas each tick so final chunks render without input
             transcript::apply_pending_delta(&mut app.tui.transcript, &mut app.tui.agent_state);
             // Also coalesce background tab deltas
@@ -74,18 +86,34 @@ pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
                 }
                 _ => {}
             }
+            effects.extend(idle_summary_effect(&mut app.tui));
             effects
         }
         UiEvent::Frame { width, height } => {
             let tab_bar_height = u16::from(app.tab_count() > 1);
             handle_frame(&mut app.tui, width, height, tab_bar_height);
+            crate::compare::apply_pending_deltas(app);
             vec![]
         }
-        UiEvent::Terminal(term_event) => handle_terminal_event(app, term_event),
-        UiEvent::Agent(agent_event) => handle_agent_event(app, &agent_event),
+        UiEvent::Terminal(term_event) => {
+            if matches!(
+                term_event,
+                Event::Key(_) | Event::Paste(_) | Event::Mouse(_)
+            ) {
+                app.tui.last_activity = std::time::Instant::now();
+            }
+            let mut effects = handle_terminal_event(app, term_event);
+            effects.extend(crate::tour::observe(app, &effects));
+            effects
+        }
+        UiEvent::Agent(agent_event) => {
+            app.tui.last_activity = std::time::Instant::now();
+            handle_agent_event(app, &agent_event)
+        }
         UiEvent::AgentSpawned {
             rx,
             cancel,
+            soft_stop,
             thread_handle,
             messages,
         } => {
@@ -105,9 +133,26 @@ pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
             if let Some(msgs) = messages {
                 app.tui.thread.messages = msgs;
             }
-            app.tui.agent_state = AgentState::Waiting { rx, cancel };
+            app.tui.agent_state = AgentState::Waiting {
+                rx,
+                cancel,
+                soft_stop,
+            };
             app.tui.transcript.activate_pending_user_cell();
+            app.tui.transcript.disarm_error_retry();
             app.tui.status_line.start_turn();
+            if app.tui.config.tui.a11y {
+                app.tui
+                    .transcript
+                    .push_cell(HistoryCell::system(crate::common::i18n::tr(
+                        crate::common::i18n::Text::AgentStarted,
+                    )));
+            }
+            vec![]
+        }
+        UiEvent::UserQuestion(question) => {
+            app.pending_questions.push_back(question);
+            open_next_question(app);
             vec![]
         }
         UiEvent::BackgroundTabAgent { tab_id, event } => {
@@ -137,6 +182,7 @@ pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
             tab_id,
             rx,
             cancel,
+            soft_stop,
             thread_handle,
             messages,
         } => {
@@ -155,8 +201,13 @@ pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
                 if let Some(msgs) = messages {
                     tab.thread.messages = msgs;
                 }
-                tab.agent_state = AgentState::Waiting { rx, cancel };
+                tab.agent_state = AgentState::Waiting {
+                    rx,
+                    cancel,
+                    soft_stop,
+                };
                 tab.transcript.activate_pending_user_cell();
+                tab.transcript.disarm_error_retry();
                 tab.status_line.start_turn();
             }
             vec![]
@@ -189,6 +240,18 @@ pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
             apply_mutations(&mut app.tui, mutations);
             vec![]
         }
+        UiEvent::PromptImproveResult { draft, result } => {
+            if let Some(overlays::Overlay::Improve(state)) = &mut app.overlay
+                && state.draft == draft
+            {
+                state.set_result(result);
+            }
+            // If the overlay was closed, drop the result silently.
+            vec![]
+        }
+        UiEvent::DuelTurnResult { side, result } => {
+            crate::duel::handle_turn_result(&mut app.tui, side, result)
+        }
         UiEvent::FilesDiscovered(files) => {
             overlays::handle_files_discovered(&mut app.overlay, files);
             vec![]
@@ -208,8 +271,9 @@ pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
         UiEvent::BashExecuted {
             id,
             command,
+            cwd,
             result,
-        } => handle_bash_executed_event(app, &id, &command, &result),
+        } => handle_bash_executed_event(app, &id, &command, cwd.as_deref(), &result),
         UiEvent::RootDisplayResolved {
             path,
             git_branch,
@@ -226,6 +290,9 @@ pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
             vec![]
         }
         UiEvent::SystemPromptRefreshed { result } => handle_system_prompt_refreshed(app, result),
+        UiEvent::StartupContextLoaded { root, result } => {
+            handle_startup_context_loaded(app, &root, result)
+        }
 
         // Thread async result events - delegate to thread feature
         UiEvent::Thread(thread_event) => handle_thread_ui_event(app, thread_event),
@@ -252,6 +319,11 @@ pub fn update(app: &mut AppState, event: UiEvent) -> Vec<UiEffect> {
             // If the overlay was closed or switched threads, drop the result silently.
             vec![]
         }
+        UiEvent::DebugRequestResult { result } => {
+            let message = result.unwrap_or_else(|message| message);
+            app.tui.transcript.push_cell(HistoryCell::system(message));
+            vec![]
+        }
         UiEvent::ContextResult { result } => {
             if let Some(overlays::Overlay::Context(state)) = &mut app.overlay {
                 match result {
@@ -402,6 +474,10 @@ fn finalize_agent_event_for_tab(
         tui.status_line.mark_tool_used();
     }
 
+    if tui.config.tui.a11y {
+        push_a11y_announcement(tui, agent_event);
+    }
+
     // cmux progress bar reflects only the active tab (one per pane). The status
     // pill is entirely tick-driven; here `TurnFinished` only records
     // `last_turn_outcome` so the next tick can render the idle pill.
@@ -425,6 +501,15 @@ fn finalize_agent_event_for_tab(
         tui.mark_thread_finished(&thread_id);
     }
 
+    if should_dequeue {
+        // Notes submitted after the agent's last provider call would be lost
+        // with the finished turn; send them as the next prompts instead.
+        tui.input.requeue_front(tui.steering.drain());
+    }
+
+    if should_dequeue {
+        push_file_changes_cell(tui);
+    }
     maybe_push_timing_cell_for_tab(tui, should_dequeue);
     let continues = maybe_send_next_queued_prompt_for_tab(tui, should_dequeue, tab, effects);
 
@@ -471,6 +556,35 @@ fn spinner_glyph(spinner_frame: usize, divisor: usize) -> &'static str {
     SPINNER_GLYPHS[(spinner_frame / divisor) % SPINNER_GLYPHS.len()]
 }
 
+/// Requests a summary of the active thread once it has been idle for
+/// `[tui] idle_summary_secs`, unless it was already summarized at its current
+/// message count. The mark is set up front so a failed attempt isn't retried
+/// until the thread changes.
+fn idle_summary_effect(tui: &mut TuiState) -> Option<UiEffect> {
+    let idle_secs = tui.config.tui.idle_summary_secs;
+    if idle_secs == 0
+        || tui.agent_state.is_running()
+        || tui.tasks.state(TaskKind::ThreadSummary).is_running()
+        || tui.last_activity.elapsed() < std::time::Duration::from_secs(idle_secs)
+    {
+        return None;
+    }
+    let thread_id = tui.thread.thread_handle.as_ref()?.id.clone();
+    let message_count = tui.thread.messages.len();
+    // Wait for at least one exchange.
+    if message_count < 2
+        || tui
+            .thread
+            .summary_mark
+            .as_ref()
+            .is_some_and(|(id, count)| *id == thread_id && *count == message_count)
+    {
+        return None;
+    }
+    tui.thread.summary_mark = Some((thread_id.clone(), message_count));
+    Some(UiEffect::SummarizeThread { thread_id })
+}
+
 /// Trims a title and returns `None` when it is empty, so callers can apply a
 /// fallback or skip the segment.
 fn non_empty_trimmed(text: Option<&str>) -> Option<&str> {
@@ -592,6 +706,38 @@ fn todo_progress_from_output(
     Some((completed as f64 / total as f64, label))
 }
 
+/// Appends a plain-text status announcement for screen readers, so the
+/// transcript alone tells the story of the run in reading order.
+fn push_a11y_announcement(
+    tui: &mut crate::state::TuiState,
+    agent_event: &zdx_engine::core::events::AgentEvent,
+) {
+    use zdx_engine::core::events::{AgentEvent, TurnStatus};
+
+    use crate::common::i18n::{Text, tr};
+
+    let announcement = match agent_event {
+        AgentEvent::ToolStarted { name, .. } => format!("{}{name}", tr(Text::ToolStartedPrefix)),
+        AgentEvent::ToolCompleted { id, result } => {
+            let prefix = if result.is_ok() {
+                tr(Text::ToolFinishedPrefix)
+            } else {
+                tr(Text::ToolFailedPrefix)
+            };
+            let name = tui.transcript.tool_name_for(id).unwrap_or("tool");
+            format!("{prefix}{name}")
+        }
+        AgentEvent::TurnFinished { status, .. } => match status {
+            TurnStatus::Completed => tr(Text::AgentFinished),
+            TurnStatus::Interrupted => tr(Text::AgentStopped),
+            TurnStatus::Failed { .. } => tr(Text::AgentFailed),
+        }
+        .to_string(),
+        _ => return,
+    };
+    tui.transcript.push_cell(HistoryCell::system(announcement));
+}
+
 /// `Some(true)` completed, `Some(false)` failed, `None` interrupted (no
 /// notification — the user is already present).
 fn turn_notification_outcome(status: &zdx_engine::core::events::TurnStatus) -> Option<bool> {
@@ -603,6 +749,20 @@ fn turn_notification_outcome(status: &zdx_engine::core::events::TurnStatus) -> O
     }
 }
 
+/// Summarizes the files the turn's tool calls changed (since the last user
+/// message), if any.
+fn push_file_changes_cell(tui: &mut crate::state::TuiState) {
+    let cells = tui.transcript.cells();
+    let turn_start = cells
+        .iter()
+        .rposition(|cell| matches!(cell, HistoryCell::User { .. }))
+        .map_or(0, |index| index + 1);
+    let changes = transcript::collect_file_changes(&cells[turn_start..]);
+    if !changes.is_empty() {
+        tui.transcript.push_cell(HistoryCell::file_changes(changes));
+    }
+}
+
 fn maybe_push_timing_cell_for_tab(tui: &mut crate::state::TuiState, should_dequeue: bool) {
     if should_dequeue
         && let Some((duration, tool_count)) = tui.status_line.end_turn()
@@ -661,7 +821,7 @@ fn maybe_send_next_queued_prompt_for_tab(
 /// background tab). Mirrors `apply_mutations` for `AppState`, but works on
 /// any tab so background-tab queue draining can apply transcript/thread/
 /// input changes to the right tab.
-fn apply_tab_mutations(tui: &mut crate::state::TuiState, mutations: Vec<StateMutation>) {
+pub(crate) fn apply_tab_mutations(tui: &mut crate::state::TuiState, mutations: Vec<StateMutation>) {
     for mutation in mutations {
         match mutation {
             StateMutation::Transcript(m) => tui.transcript.apply(m),
@@ -673,10 +833,12 @@ fn apply_tab_mutations(tui: &mut crate::state::TuiState, mutations: Vec<StateMut
             | StateMutation::Config(_)
             | StateMutation::SetRootDisplay { .. }
             | StateMutation::SetActiveThreadOverrides { .. }
-            | StateMutation::SetSystemPrompt(_)
+            | StateMutation::SetSystemPrompt { .. }
             | StateMutation::SetLastSkillRepo(_)
             | StateMutation::SetLoadedSkills(_)
-            | StateMutation::ToggleDebugStatus => {
+            | StateMutation::ToggleDebugStatus
+            | StateMutation::SetThreadPickerScope(_)
+            | StateMutation::SetToolSelection { .. } => {
                 // App-level mutations never originate from a queued-prompt
                 // drain or transcript event; ignored here so the helper
                 // can stay focused on per-tab slices.
@@ -844,11 +1006,14 @@ fn handle_task_started_event(
             }
         }
         TaskKind::Bash => {
-            if let TaskMeta::Bash { id, command } = &started.meta {
-                let input = serde_json::json!({ "command": command });
-                app.tui
-                    .transcript
-                    .push_cell(HistoryCell::tool_running(id, "bash", input));
+            if let TaskMeta::Bash { id, command, cwd } = &started.meta {
+                let mut input = serde_json::json!({ "command": command });
+                if let Some(cwd) = cwd {
+                    input["cwd"] = serde_json::json!(cwd);
+                }
+                let mut cell = HistoryCell::tool_running(id, "bash", input);
+                cell.set_inline_output(app.tui.config.tui.bash_output_lines);
+                app.tui.transcript.push_cell(cell);
             }
         }
         TaskKind::VoiceRecord => app.tui.input.voice.start_recording(),
@@ -857,18 +1022,25 @@ fn handle_task_started_event(
         | TaskKind::SkillsFetch
         | TaskKind::SkillInstall
         | TaskKind::ThreadList
+        | TaskKind::ThreadCompletion
         | TaskKind::ThreadLoad
         | TaskKind::ThreadRename
+        | TaskKind::ThreadAction
         | TaskKind::ThreadTitle
+        | TaskKind::ThreadSummary
         | TaskKind::ThreadTldr
+        | TaskKind::PromptImprove
+        | TaskKind::Duel
         | TaskKind::ContextAnalyze
+        | TaskKind::DebugRequest
         | TaskKind::ThreadPreview
         | TaskKind::ThreadCreate
         | TaskKind::ThreadFork
         | TaskKind::ThreadWorktree
         | TaskKind::LoginExchange
         | TaskKind::LoginCallback
-        | TaskKind::ImageDecode => {}
+        | TaskKind::ImageDecode
+        | TaskKind::StartupContext => {}
     }
     vec![]
 }
@@ -949,6 +1121,7 @@ fn handle_bash_executed_event(
     app: &mut AppState,
     id: &str,
     command: &str,
+    cwd: Option<&str>,
     result: &zdx_engine::core::events::ToolOutput,
 ) -> Vec<UiEffect> {
     app.tui.transcript.set_tool_result_for(id, result.clone());
@@ -956,10 +1129,13 @@ fn handle_bash_executed_event(
         return vec![];
     }
 
+    // `$` commands run with color on; the model gets plain text.
+    let location = cwd.map(|dir| format!(" (in {dir})")).unwrap_or_default();
     let user_message = format!(
-        "[I executed a bash command]\n$ {}\n\nResult:\n{}",
+        "[I executed a bash command{}]\n$ {}\n\nResult:\n{}",
+        location,
         command,
-        result.to_json_string()
+        strip_output_colors(result).to_json_string()
     );
     app.tui
         .thread
@@ -970,18 +1146,77 @@ fn handle_bash_executed_event(
     }]
 }
 
+fn strip_output_colors(
+    result: &zdx_engine::core::events::ToolOutput,
+) -> zdx_engine::core::events::ToolOutput {
+    let mut result = result.clone();
+    if let zdx_engine::core::events::ToolOutput::Success { data, .. } = &mut result {
+        for key in ["stdout", "stderr"] {
+            if let Some(serde_json::Value::String(text)) = data.get_mut(key) {
+                *text = crate::transcript::strip_ansi(text);
+            }
+        }
+    }
+    result
+}
+
 fn handle_system_prompt_refreshed(
     app: &mut AppState,
-    result: Result<Option<String>, String>,
+    result: Result<(Option<String>, Option<String>), String>,
 ) -> Vec<UiEffect> {
     let mutation = match result {
-        Ok(prompt) => StateMutation::SetSystemPrompt(prompt),
+        Ok((prompt, variant)) => StateMutation::SetSystemPrompt { prompt, variant },
         Err(error) => StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(error)),
     };
     apply_mutations(&mut app.tui, vec![mutation]);
     vec![]
 }
 
+/// Applies the background startup load: the prompt (unless the tab already
+/// moved to another root), loaded skills, and context/skill/warning notes.
+fn handle_startup_context_loaded(
+    app: &mut AppState,
+    root: &Path,
+    result: Result<StartupContext, String>,
+) -> Vec<UiEffect> {
+    // A synchronous load (turn started early) supersedes the background task.
+    app.tui.tasks.state_mut(TaskKind::StartupContext).clear();
+    let context = match result {
+        Ok(context) => context,
+        Err(error) => {
+            apply_mutations(
+                &mut app.tui,
+                vec![StateMutation::Transcript(
+                    TranscriptMutation::AppendSystemMessage(error),
+                )],
+            );
+            return vec![];
+        }
+    };
+    let mut mutations = Vec::new();
+    if app.tui.agent_opts.root == root {
+        mutations.push(StateMutation::SetSystemPrompt {
+            prompt: context.prompt,
+            variant: context.prompt_variant,
+        });
+    }
+    let messages = context
+        .warnings
+        .into_iter()
+        .map(|warning| format!("Warning: {warning}"))
+        .chain(crate::thread_startup_messages(
+            None,
+            &context.loaded_agents_paths,
+            &context.loaded_skills,
+        ));
+    mutations.extend(messages.map(|message| {
+        StateMutation::Transcript(TranscriptMutation::AppendSystemMessage(message))
+    }));
+    mutations.push(StateMutation::SetLoadedSkills(context.loaded_skills));
+    apply_mutations(&mut app.tui, mutations);
+    vec![]
+}
+
 fn handle_thread_ui_event(app: &mut AppState, thread_event: ThreadUiEvent) -> Vec<UiEffect> {
     let preview_target = match app.overlay.as_ref() {
         Some(overlays::Overlay::ThreadPicker(picker)) => {
@@ -1010,6 +1245,25 @@ fn handle_thread_ui_event(app: &mut AppState, thread_event: ThreadUiEvent) -> Ve
         {
             vec![]
         }
+        ThreadUiEvent::CompletionListLoaded {
+            threads,
+            mut active_thread_ids,
+        } => {
+            if let Some(overlays::Overlay::SlashComplete(complete)) = &mut app.overlay {
+                active_thread_ids.extend(app.tui.snapshot_active_thread_ids());
+                complete.set_threads(threads, active_thread_ids);
+            }
+            vec![]
+        }
+        ThreadUiEvent::SummaryGenerated { thread_id, summary } => {
+            if let Some(overlays::Overlay::ThreadPicker(picker)) = &mut app.overlay
+                && let Some(thread) = picker.all_threads.iter_mut().find(|t| t.id == thread_id)
+                && summary.is_some()
+            {
+                thread.summary = summary;
+            }
+            vec![]
+        }
         ThreadUiEvent::OpenAsTab {
             cells,
             messages,
@@ -1039,7 +1293,23 @@ fn handle_thread_ui_event(app: &mut AppState, thread_event: ThreadUiEvent) -> Ve
             app.push_tab(tab);
             vec![]
         }
+        ThreadUiEvent::ActionApplied {
+            action,
+            thread_ids,
+            errors,
+        } if matches!(app.overlay, Some(overlays::Overlay::ThreadPicker(_))) => {
+            let Some(overlays::Overlay::ThreadPicker(picker)) = app.overlay.as_mut() else {
+                return vec![];
+            };
+            let update = picker.handle_action_applied(&action, &thread_ids, &errors);
+            apply_overlay_update(app, update)
+        }
         event => {
+            if let ThreadUiEvent::PreviewLoaded { thread_id, cells } = &event
+                && let Some(overlays::Overlay::ThreadPicker(picker)) = app.overlay.as_mut()
+            {
+                picker.set_preview(thread_id, cells);
+            }
             let (mut effects, mutations, overlay_action) = thread::handle_thread_event(event);
             apply_mutations(&mut app.tui, mutations);
             maybe_open_thread_picker_overlay(app, overlay_action, &mut effects);
@@ -1053,31 +1323,48 @@ fn maybe_open_thread_picker_overlay(
     overlay_action: thread::ThreadOverlayAction,
     effects: &mut Vec<UiEffect>,
 ) {
-    if let thread::ThreadOverlayAction::OpenThreadPicker {
-        threads,
-        mut active_thread_ids,
-        original_cells,
-        mode,
-    } = overlay_action
-        && app.overlay.is_none()
-    {
-        active_thread_ids.extend(app.tui.snapshot_active_thread_ids());
-        let current_thread_id = app
-            .tui
-            .thread
-            .thread_handle
-            .as_ref()
-            .map(|log| log.id.clone());
-        let (state, overlay_effects) = overlays::ThreadPickerState::open(
+    if app.overlay.is_some() {
+        return;
+    }
+    let current_thread_id = app
+        .tui
+        .thread
+        .thread_handle
+        .as_ref()
+        .map(|log| log.id.clone());
+    match overlay_action {
+        thread::ThreadOverlayAction::OpenThreadPicker {
             threads,
-            active_thread_ids,
+            mut active_thread_ids,
             original_cells,
-            &app.tui.agent_opts.root,
-            current_thread_id,
             mode,
-        );
-        app.overlay = Some(overlays::Overlay::ThreadPicker(state));
-        effects.extend(overlay_effects);
+        } => {
+            active_thread_ids.extend(app.tui.snapshot_active_thread_ids());
+            let (state, overlay_effects) = overlays::ThreadPickerState::open(
+                threads,
+                active_thread_ids,
+                original_cells,
+                &app.tui.agent_opts.root,
+                current_thread_id,
+                mode,
+                app.tui.thread_picker_scope,
+            );
+            app.overlay = Some(overlays::Overlay::ThreadPicker(state));
+            effects.extend(overlay_effects);
+        }
+        thread::ThreadOverlayAction::OpenQuickSwitch {
+            threads,
+            mut active_thread_ids,
+        } => {
+            active_thread_ids.extend(app.tui.snapshot_active_thread_ids());
+            let state = overlays::QuickSwitchState::open(
+                threads,
+                active_thread_ids,
+                current_thread_id.as_deref(),
+            );
+            app.overlay = Some(overlays::Overlay::QuickSwitch(state));
+        }
+        thread::ThreadOverlayAction::None => {}
     }
 }
 
@@ -1109,8 +1396,9 @@ fn apply_mutations(tui: &mut TuiState, mutations: Vec<StateMutation>) {
                 tui.config.model = model_override.unwrap_or_else(|| tui.base_model.clone());
                 tui.config.thinking_level = thinking_override.unwrap_or(tui.base_thinking_level);
             }
-            StateMutation::SetSystemPrompt(system_prompt) => {
-                tui.system_prompt = system_prompt;
+            StateMutation::SetSystemPrompt { prompt, variant } => {
+                tui.system_prompt = prompt;
+                tui.system_prompt_variant = variant;
             }
             StateMutation::SetLastSkillRepo(repo) => {
                 tui.last_skill_repo = Some(repo);
@@ -1124,6 +1412,13 @@ fn apply_mutations(tui: &mut TuiState, mutations: Vec<StateMutation>) {
             StateMutation::ToggleDebugStatus => {
                 tui.show_debug_status = !tui.show_debug_status;
             }
+            StateMutation::SetThreadPickerScope(scope) => {
+                tui.thread_picker_scope = scope;
+            }
+            StateMutation::SetToolSelection { selection, summary } => {
+                tui.agent_opts.tool_config.selection = selection;
+                tui.tool_filter = summary;
+            }
         }
     }
 }
@@ -1141,6 +1436,9 @@ fn apply_config_mutation(tui: &mut TuiState, mutation: ConfigMutation) {
         ConfigMutation::SetFastMode { provider, enabled } => {
             tui.config.set_fast_mode_for_provider(provider, enabled);
         }
+        ConfigMutation::SetModelParams(params) => {
+            tui.config.model_params = params;
+        }
     }
 }
 
@@ -1184,6 +1482,14 @@ fn apply_overlay_update(app: &mut AppState, update: overlays::OverlayUpdate) ->
                 });
             }
             app.overlay = None;
+            open_next_question(app);
+        }
+        overlays::OverlayTransition::Replace(overlay) => {
+            app.overlay = Some(*overlay);
+        }
+        overlays::OverlayTransition::Open(overlays::OverlayRequest::Confirm(mut confirm)) => {
+            confirm.attach_previous(app.overlay.take());
+            app.overlay = Some(overlays::Overlay::Confirm(*confirm));
         }
         overlays::OverlayTransition::Open(request) => {
             if matches!(app.overlay.as_ref(), Some(overlays::Overlay::FilePicker(_))) {
@@ -1211,6 +1517,21 @@ fn apply_overlay_update(app: &mut AppState, update: overlays::OverlayUpdate) ->
     effects
 }
 
+/// Shows the next still-pending `Ask_User` question once no overlay is open.
+fn open_next_question(app: &mut AppState) {
+    if app.overlay.is_some() {
+        return;
+    }
+    while let Some(question) = app.pending_questions.pop_front() {
+        if question.is_pending() {
+            app.overlay = Some(overlays::Overlay::AskUser(overlays::AskUserState::open(
+                question,
+            )));
+            return;
+        }
+    }
+}
+
 fn open_overlay_request(app: &mut AppState, request: &overlays::OverlayRequest) -> Vec<UiEffect> {
     match request {
         overlays::OverlayRequest::CommandPalette => {
@@ -1247,6 +1568,18 @@ fn open_overlay_request(app: &mut AppState, request: &overlays::OverlayRequest)
             app.overlay = Some(overlays::Overlay::ThinkingPicker(state));
             effects
         }
+        overlays::OverlayRequest::ToolPicker => {
+            app.overlay = Some(overlays::Overlay::ToolPicker(
+                overlays::ToolPickerState::open(&app.tui),
+            ));
+            vec![]
+        }
+        overlays::OverlayRequest::Params => {
+            app.overlay = Some(overlays::Overlay::Params(overlays::ParamsState::open(
+                app.tui.config.model_params,
+            )));
+            vec![]
+        }
         overlays::OverlayRequest::NewTab => {
             let tab_id = app.next_tab_id();
             let tab = create_main_tab(tab_id, &app.tui);
@@ -1268,16 +1601,31 @@ fn open_overlay_request(app: &mut AppState, request: &overlays::OverlayRequest)
             app.push_tab(btw_tab);
             vec![]
         }
+        overlays::OverlayRequest::Compare(request) => crate::compare::handle_request(app, request),
+        overlays::OverlayRequest::Duel(request) => crate::duel::handle_request(app, request),
         overlays::OverlayRequest::Login => {
             let (state, effects) = overlays::LoginState::open(&app.tui);
             app.overlay = Some(overlays::Overlay::Login(state));
             effects
         }
         overlays::OverlayRequest::FilePicker { trigger_pos } => {
-            let (state, effects) = overlays::FilePickerState::open(*trigger_pos);
+            let (mut state, effects) = overlays::FilePickerState::open(*trigger_pos);
+            state.set_recent(overlays::file_picker::recent_files(
+                app.tui.transcript.cells(),
+                &app.tui.agent_opts.root,
+            ));
             app.overlay = Some(overlays::Overlay::FilePicker(state));
             effects
         }
+        overlays::OverlayRequest::SlashComplete => {
+            let mut state =
+                overlays::SlashCompleteState::open(&app.tui.config, app.custom_commands.clone());
+            let Some(effects) = state.update_from_input(&app.tui.input) else {
+                return vec![];
+            };
+            app.overlay = Some(overlays::Overlay::SlashComplete(state));
+            effects
+        }
         overlays::OverlayRequest::Timeline => {
             let (state, effects, mutations) = overlays::TimelineState::open(
                 app.tui.transcript.cells(),
@@ -1328,6 +1676,29 @@ fn open_overlay_request(app: &mut AppState, request: &overlays::OverlayRequest)
             effects.push(UiEffect::GenerateTldr { thread_id });
             effects
         }
+        overlays::OverlayRequest::Improve { draft } => {
+            let mut effects = Vec::new();
+            let task = app.tui.tasks.state(TaskKind::PromptImprove);
+            if task.is_running() {
+                effects.push(UiEffect::CancelTask {
+                    kind: TaskKind::PromptImprove,
+                    token: task.cancel.clone(),
+                });
+            }
+            app.overlay = Some(overlays::Overlay::Improve(overlays::ImproveState::open(
+                draft.clone(),
+            )));
+            effects.push(UiEffect::ImprovePrompt {
+                draft: draft.clone(),
+            });
+            effects
+        }
+        overlays::OverlayRequest::Stats => {
+            app.overlay = Some(overlays::Overlay::Stats(overlays::StatsState::open(
+                &app.tui,
+            )));
+            vec![]
+        }
         overlays::OverlayRequest::Context => {
             // Cancel any in-flight context-analyze task so this new request
             // takes over (e.g. user closed overlay mid-analysis and reopened).
@@ -1366,10 +1737,14 @@ fn open_overlay_request(app: &mut AppState, request: &overlays::OverlayRequest)
             app.overlay = Some(overlays::Overlay::ToolDetail(state));
             vec![]
         }
+        // Confirm moves its state into the overlay; see `apply_overlay_update`.
+        overlays::OverlayRequest::Confirm(_) => {
+            unreachable!("Confirm handled in apply_overlay_update")
+        }
     }
 }
 
-fn build_btw_base_messages(tui: &TuiState) -> Vec<zdx_engine::providers::ChatMessage> {
+pub(crate) fn build_btw_base_messages(tui: &TuiState) -> Vec<zdx_engine::providers::ChatMessage> {
     let mut messages = tui.thread.messages.clone();
     let has_in_flight_turn = tui.agent_state.is_running() || tui.transcript.has_pending_user_cell();
     if has_in_flight_turn
@@ -1409,7 +1784,7 @@ pub fn cycle_tab(app: &mut AppState, direction: i32) {
 }
 
 /// Creates a new btw tab forked from the current tab's conversation.
-fn create_btw_tab(
+pub(crate) fn create_btw_tab(
     tab_id: TabId,
     base_messages: Vec<zdx_engine::providers::ChatMessage>,
     parent: &TuiState,
@@ -1425,13 +1800,21 @@ fn create_btw_tab(
 
     let agent_opts = parent.agent_opts.clone();
 
-    TuiState {
+    // An incognito btw tab never gets a thread, so it carries the forked
+    // context in its own messages instead.
+    let mut thread = ThreadState::new();
+    if parent.thread.incognito {
+        thread.incognito = true;
+        thread.messages.clone_from(&base_messages);
+    }
+
+    let mut tab = TuiState {
         tab_id,
         tab_kind: TabKind::Btw { base_messages },
         should_quit: false,
         input: InputState::new(),
         transcript,
-        thread: ThreadState::new(),
+        thread,
         task_seq: crate::common::TaskSeq::default(),
         tasks: crate::common::Tasks::default(),
         auth: crate::auth::AuthState::new(),
@@ -1442,7 +1825,10 @@ fn create_btw_tab(
         loaded_skills: parent.loaded_skills.clone(),
         agent_opts,
         system_prompt: parent.system_prompt.clone(),
+        system_prompt_variant: parent.system_prompt_variant.clone(),
         agent_state: AgentState::Idle,
+        steering: zdx_engine::core::steering::SteeringQueue::new(),
+        duel: None,
         last_turn_outcome: None,
         unseen_completion: false,
         spinner_frame: 0,
@@ -1450,13 +1836,18 @@ fn create_btw_tab(
         display_path: parent.display_path.clone(),
         status_line: crate::statusline::StatusLineAccumulator::new(),
         show_debug_status: false,
+        thread_picker_scope: crate::overlays::ThreadScope::default(),
         input_area: std::cell::Cell::new(ratatui::layout::Rect::default()),
         transcript_area: std::cell::Cell::new(ratatui::layout::Rect::default()),
         optimistic_active_threads: std::collections::HashMap::new(),
         active_threads_scan: std::collections::HashSet::new(),
         active_threads_scanned_at: None,
         last_followups: Vec::new(),
-    }
+        tool_filter: parent.tool_filter,
+        last_activity: std::time::Instant::now(),
+    };
+    tab.inherit_view_prefs(parent);
+    tab
 }
 
 fn create_main_tab(tab_id: TabId, parent: &TuiState) -> TuiState {
@@ -1474,7 +1865,7 @@ fn create_main_tab(tab_id: TabId, parent: &TuiState) -> TuiState {
         Vec::new(),
     );
     tab.last_skill_repo.clone_from(&parent.last_skill_repo);
-    tab.show_debug_status = parent.show_debug_status;
+    tab.inherit_view_prefs(parent);
     tab
 }
 
@@ -1523,7 +1914,7 @@ fn create_thread_tab(
 
     let agent_opts = parent.agent_opts.clone();
 
-    TuiState {
+    let mut tab = TuiState {
         tab_id,
         tab_kind: TabKind::Thread {
             title: title.cloned(),
@@ -1547,7 +1938,10 @@ fn create_thread_tab(
         loaded_skills: parent.loaded_skills.clone(),
         agent_opts,
         system_prompt: parent.system_prompt.clone(),
+        system_prompt_variant: parent.system_prompt_variant.clone(),
         agent_state: AgentState::Idle,
+        steering: zdx_engine::core::steering::SteeringQueue::new(),
+        duel: None,
         last_turn_outcome: None,
         unseen_completion: false,
         spinner_frame: 0,
@@ -1555,13 +1949,18 @@ fn create_thread_tab(
         display_path: parent.display_path.clone(),
         status_line: crate::statusline::StatusLineAccumulator::new(),
         show_debug_status: false,
+        thread_picker_scope: crate::overlays::ThreadScope::default(),
         input_area: std::cell::Cell::new(ratatui::layout::Rect::default()),
         transcript_area: std::cell::Cell::new(ratatui::layout::Rect::default()),
         optimistic_active_threads: std::collections::HashMap::new(),
         active_threads_scan: std::collections::HashSet::new(),
         active_threads_scanned_at: None,
         last_followups: Vec::new(),
-    }
+        tool_filter: parent.tool_filter,
+        last_activity: std::time::Instant::now(),
+    };
+    tab.inherit_view_prefs(parent);
+    tab
 }
 
 // ============================================================================
@@ -1686,26 +2085,104 @@ fn handle_terminal_event(app: &mut AppState, event: Event) -> Vec<UiEffect> {
     }
 }
 
+/// Re-runs a failed turn from the committed thread messages.
+///
+/// Returns `None` when there is nothing to retry, so the key falls through
+/// to normal input handling.
+fn retry_failed_turn(tui: &mut TuiState) -> Option<Vec<UiEffect>> {
+    if tui.agent_state.is_running()
+        || !tui.transcript.has_retryable_error()
+        || tui
+            .thread
+            .messages
+            .last()
+            .is_none_or(|message| message.role != "user")
+    {
+        return None;
+    }
+    Some(vec![UiEffect::StartAgentTurn {
+        thinking_override: None,
+    }])
+}
+
+/// Regenerates the latest response with `feedback` as steering (`/redo`).
+///
+/// Drops the previous attempt from the transcript and the model context,
+/// records the feedback (the thread log keeps the old attempt), and starts a
+/// new turn.
+pub fn redo_turn(tui: &mut TuiState, feedback: &str) -> Vec<UiEffect> {
+    use crate::common::i18n::{Text, tr};
+
+    let notice = |tui: &mut TuiState, message: &str| {
+        tui.transcript
+            .apply(TranscriptMutation::AppendSystemMessage(message.to_string()));
+        vec![]
+    };
+    if tui.agent_state.is_running() {
+        return notice(tui, tr(Text::StopCurrentTaskFirst));
+    }
+    if !regeneration::drop_latest_attempt(&mut tui.thread.messages) {
+        return notice(tui, "Nothing to redo yet. Send a message first.");
+    }
+    tui.transcript.drop_latest_response();
+
+    let message = regeneration::regeneration_message(feedback);
+    tui.transcript
+        .apply(TranscriptMutation::AppendSystemMessage(format!(
+            "Redo: {}",
+            feedback.trim()
+        )));
+    let mut effects = Vec::new();
+    if tui.thread.thread_handle.is_some() {
+        effects.extend(
+            thread_persistence::messages_to_events(std::slice::from_ref(&message))
+                .into_iter()
+                .map(|event| UiEffect::SaveThread { event }),
+        );
+    }
+    tui.thread.messages.push(message);
+    effects.push(UiEffect::StartAgentTurn {
+        thinking_override: None,
+    });
+    effects
+}
+
 fn handle_key(app: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<UiEffect> {
     use crossterm::event::{KeyCode, KeyModifiers};
 
     // Tab navigation — handled before overlays so it always works
     if app.tab_count() > 1 {
         match (key.modifiers, key.code) {
-            // Ctrl+PageDown: next tab
+            // Ctrl+PageDown / Ctrl+Tab: next tab
             (m, KeyCode::PageDown) if m.contains(KeyModifiers::CONTROL) => {
                 cycle_tab(app, 1);
                 return vec![];
             }
-            // Ctrl+PageUp: previous tab
+            (m, KeyCode::Tab) if m == KeyModifiers::CONTROL => {
+                cycle_tab(app, 1);
+                return vec![];
+            }
+            // Ctrl+PageUp / Ctrl+Shift+Tab: previous tab
             (m, KeyCode::PageUp) if m.contains(KeyModifiers::CONTROL) => {
                 cycle_tab(app, -1);
                 return vec![];
             }
+            (m, KeyCode::Tab | KeyCode::BackTab) if m.contains(KeyModifiers::CONTROL) => {
+                cycle_tab(app, -1);
+                return vec![];
+            }
             _ => {}
         }
     }
 
+    // Ctrl+N: open a new tab (Ctrl+T is the thinking picker).
+    if app.overlay.is_none()
+        && key.code == KeyCode::Char('n')
+        && key.modifiers == KeyModifiers::CONTROL
+    {
+        return open_overlay_request(app, &overlays::OverlayRequest::NewTab);
+    }
+
     // Ctrl+W: close current tab when idle and input is empty.
     // Otherwise keep the normal readline-style delete-word behavior in input handling.
     if app.overlay.is_none()
@@ -1717,6 +2194,17 @@ fn handle_key(app: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<UiEffe
         return vec![UiEffect::CloseCurrentTab];
     }
 
+    // r: retry the failed turn while its error cell offers a retry and the
+    // input is empty.
+    if app.overlay.is_none()
+        && key.code == KeyCode::Char('r')
+        && key.modifiers.is_empty()
+        && app.tui.input.get_text().is_empty()
+        && let Some(effects) = retry_failed_turn(&mut app.tui)
+    {
+        return effects;
+    }
+
     // Ctrl+F: open the follow-up suggestion picker on demand when idle and the
     // input is empty. Keeps the keyboard free for typing/dictation by default.
     if app.overlay.is_none()
@@ -1767,6 +2255,27 @@ fn handle_key(app: &mut AppState, key: crossterm::event::KeyEvent) -> Vec<UiEffe
         return vec![];
     }
 
+    if let Some(Overlay::SlashComplete(complete)) = app.overlay.as_mut() {
+        if overlays::SlashCompleteState::should_route_input_key(key) {
+            app.tui.input.textarea.input(key);
+            app.tui.input.sync_pending_pastes();
+            app.tui.input.sync_pending_images();
+            let Some(effects) = complete.update_from_input(&app.tui.input) else {
+                app.overlay = None;
+                return vec![];
+            };
+            return effects;
+        }
+        // Commands that parse their own arguments (`/style concise`) are
+        // submitted like any other input.
+        if key.code == crossterm::event::KeyCode::Enter
+            && let Some(text) = complete.submit_text()
+        {
+            app.overlay = None;
+            app.tui.input.set_text(&text);
+        }
+    }
+
     // Try to dispatch to the active overlay
     if let Some(mut update) = overlays::handle_overlay_key(&app.tui, &mut app.overlay, key) {
         apply_mutations(&mut app.tui, std::mem::take(&mut update.mutations));
@@ -1827,6 +2336,7 @@ mod tests {
 
     use zdx_engine::core::events::AgentEvent;
     use zdx_engine::core::thread_persistence::Thread;
+    use zdx_engine::providers::ChatMessage;
     use zdx_engine::skills::{Skill, SkillSource};
 
     use super::*;
@@ -1967,6 +2477,62 @@ mod tests {
         assert_eq!(cmux_pill_value(true, false, None, Some("Fix bug"), 0), None);
     }
 
+    #[test]
+    fn startup_context_applies_prompt_only_for_current_root() {
+        let config = zdx_engine::config::Config::default();
+        let root = PathBuf::from("/tmp/project");
+        let mut app = AppState::new(config, root.clone(), None, None);
+        let skill = Skill {
+            name: "review".to_string(),
+            description: "Review code".to_string(),
+            file_path: PathBuf::from("/tmp/skills/review/SKILL.md"),
+            base_dir: PathBuf::from("/tmp/skills/review"),
+            source: SkillSource::BuiltIn,
+        };
+        let context = |prompt: &str| StartupContext {
+            prompt: Some(prompt.to_string()),
+            prompt_variant: None,
+            loaded_agents_paths: vec![PathBuf::from("/tmp/project/AGENTS.md")],
+            loaded_skills: vec![skill.clone()],
+            warnings: vec!["AGENTS.md truncated".to_string()],
+        };
+
+        update(
+            &mut app,
+            UiEvent::StartupContextLoaded {
+                root: PathBuf::from("/tmp/elsewhere"),
+                result: Ok(context("stale")),
+            },
+        );
+        assert_eq!(app.tui.system_prompt, None);
+
+        update(
+            &mut app,
+            UiEvent::StartupContextLoaded {
+                root,
+                result: Ok(context("fresh")),
+            },
+        );
+        assert_eq!(app.tui.system_prompt.as_deref(), Some("fresh"));
+        assert_eq!(app.tui.loaded_skills.len(), 1);
+        let notes: Vec<&str> = app
+            .tui
+            .transcript
+            .cells()
+            .iter()
+            .filter_map(|cell| match cell {
+                HistoryCell::System { content, .. } => Some(content.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(notes.contains(&"Warning: AGENTS.md truncated"));
+        assert!(
+            notes
+                .iter()
+                .any(|note| note.starts_with("Loaded skills:\n  - review"))
+        );
+    }
+
     #[test]
     fn test_scroll_to_top() {
         let config = zdx_engine::config::Config::default();
@@ -2036,7 +2602,7 @@ mod tests {
         assert!(
             effects
                 .iter()
-                .any(|effect| matches!(effect, UiEffect::StartAgentTurn))
+                .any(|effect| matches!(effect, UiEffect::StartAgentTurn { .. }))
         );
         assert!(!app.tui.input.has_queued());
         let last_cell = app.tui.transcript.cells().last().expect("cell");
@@ -2046,6 +2612,177 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn r_retries_failed_turn_from_error_cell() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+        use zdx_engine::core::events::{ErrorKind, TurnStatus};
+
+        let config = zdx_engine::config::Config::default();
+        let mut app = AppState::new(config, PathBuf::new(), None, None);
+        app.tui.thread.messages = vec![zdx_engine::providers::ChatMessage::user("hi")];
+
+        update(
+            &mut app,
+            UiEvent::Agent(AgentEvent::TurnFinished {
+                status: TurnStatus::Failed {
+                    kind: ErrorKind::HttpStatus,
+                    message: "HTTP 529: Overloaded".to_string(),
+                    details: None,
+                    http_status: Some(529),
+                    request_id: Some("req_123".to_string()),
+                },
+                final_text: String::new(),
+                messages: Vec::new(),
+                prior_message_count: 0,
+            }),
+        );
+        assert!(matches!(
+            app.tui.transcript.cells().last(),
+            Some(HistoryCell::Error {
+                http_status: Some(529),
+                retry_available: true,
+                ..
+            })
+        ));
+
+        let press_r = || {
+            UiEvent::Terminal(Event::Key(KeyEvent::new(
+                KeyCode::Char('r'),
+                KeyModifiers::NONE,
+            )))
+        };
+        app.tui.input.set_text("draft");
+        let effects = update(&mut app, press_r());
+        assert!(
+            !effects
+                .iter()
+                .any(|effect| matches!(effect, UiEffect::StartAgentTurn { .. }))
+        );
+
+        app.tui.input.clear();
+        let effects = update(&mut app, press_r());
+        assert!(
+            effects
+                .iter()
+                .any(|effect| matches!(effect, UiEffect::StartAgentTurn { .. }))
+        );
+    }
+
+    #[test]
+    fn redo_replaces_the_latest_response_and_starts_a_turn() {
+        use zdx_engine::providers::ChatMessage;
+
+        let config = zdx_engine::config::Config::default();
+        let mut app = AppState::new(config, PathBuf::new(), None, None);
+        assert!(redo_turn(&mut app.tui, "shorter").is_empty());
+
+        app.tui.thread.messages = vec![
+            ChatMessage::user("explain"),
+            ChatMessage::assistant_text("a long answer", None),
+        ];
+        app.tui.transcript.apply(TranscriptMutation::ReplaceCells(
+            TuiState::build_transcript_from_history(&app.tui.thread.messages),
+        ));
+
+        let effects = redo_turn(&mut app.tui, "shorter");
+        assert!(matches!(
+            effects.as_slice(),
+            [UiEffect::StartAgentTurn {
+                thinking_override: None
+            }]
+        ));
+        assert_eq!(app.tui.thread.messages.len(), 2);
+        assert!(regeneration::is_regeneration_message(
+            &app.tui.thread.messages[1]
+        ));
+        let cells = app.tui.transcript.cells();
+        assert_eq!(cells.len(), 2);
+        assert!(matches!(&cells[0], HistoryCell::User { .. }));
+        assert!(matches!(
+            &cells[1],
+            HistoryCell::System { content, .. } if content == "Redo: shorter"
+        ));
+
+        // A reloaded thread shows the same cells.
+        let rebuilt = TuiState::build_transcript_from_history(&app.tui.thread.messages);
+        assert!(matches!(
+            &rebuilt[1],
+            HistoryCell::System { content, .. } if content == "Redo: shorter"
+        ));
+    }
+
+    #[test]
+    fn a11y_mode_announces_run_progress_and_freezes_spinner() {
+        let mut config = zdx_engine::config::Config::default();
+        config.tui.a11y = true;
+        let mut app = AppState::new(config, PathBuf::new(), None, None);
+
+        update(&mut app, UiEvent::Tick);
+        assert_eq!(app.tui.spinner_frame, 0);
+
+        update(
+            &mut app,
+            UiEvent::Agent(AgentEvent::ToolStarted {
+                id: "t1".to_string(),
+                name: "bash".to_string(),
+            }),
+        );
+        update(
+            &mut app,
+            UiEvent::Agent(AgentEvent::TurnFinished {
+                status: zdx_engine::core::events::TurnStatus::Completed,
+                final_text: String::new(),
+                messages: Vec::new(),
+                prior_message_count: 0,
+            }),
+        );
+
+        let announcements: Vec<&str> = app
+            .tui
+            .transcript
+            .cells()
+            .iter()
+            .filter_map(|cell| match cell {
+                HistoryCell::System { content, .. } => Some(content.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert!(announcements.contains(&"Tool started: bash"));
+        assert!(announcements.contains(&"Agent finished"));
+    }
+
+    #[test]
+    fn ctrl_n_opens_a_tab_and_ctrl_tab_cycles() {
+        use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+        let mut app = AppState::new(
+            zdx_engine::config::Config::default(),
+            PathBuf::new(),
+            None,
+            None,
+        );
+        let first = app.tui.tab_id;
+        let effects = handle_key(
+            &mut app,
+            KeyEvent::new(KeyCode::Char('n'), KeyModifiers::CONTROL),
+        );
+        assert!(matches!(effects.as_slice(), [UiEffect::CreateNewThread]));
+        assert_eq!(app.tab_count(), 2);
+        let second = app.tui.tab_id;
+        assert_ne!(first, second);
+
+        handle_key(&mut app, KeyEvent::new(KeyCode::Tab, KeyModifiers::CONTROL));
+        assert_eq!(app.tui.tab_id, first);
+        handle_key(
+            &mut app,
+            KeyEvent::new(
+                KeyCode::BackTab,
+                KeyModifiers::CONTROL | KeyModifiers::SHIFT,
+            ),
+        );
+        assert_eq!(app.tui.tab_id, second);
+    }
+
     /// Regression: a `TurnFinished` for a background tab must drain that
     /// tab's queue and emit `StartAgentTurnInBackgroundTab` for it,
     /// instead of leaving the queued prompt stranded. See the bug
@@ -2094,14 +2831,14 @@ mod tests {
         assert!(
             effects.iter().any(|effect| matches!(
                 effect,
-                UiEffect::StartAgentTurnInBackgroundTab { tab_id } if *tab_id == background_tab_id
+                UiEffect::StartAgentTurnInBackgroundTab { tab_id, .. } if *tab_id == background_tab_id
             )),
             "expected StartAgentTurnInBackgroundTab effect, got {effects:?}"
         );
         assert!(
             !effects
                 .iter()
-                .any(|effect| matches!(effect, UiEffect::StartAgentTurn)),
+                .any(|effect| matches!(effect, UiEffect::StartAgentTurn { .. })),
             "must not emit active-tab StartAgentTurn for a background tab drain"
         );
 
@@ -2131,6 +2868,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_idle_summary_fires_once_per_message_count() {
+        let config = zdx_engine::config::Config::default();
+        let mut app = AppState::new(config, PathBuf::new(), None, None);
+        let zdx_home = std::env::temp_dir().join(unique_thread_id("zdx-tui-update-tests"));
+        std::fs::create_dir_all(&zdx_home).unwrap();
+        unsafe {
+            std::env::set_var("ZDX_HOME", &zdx_home);
+        }
+        let thread_handle = Thread::with_id(unique_thread_id("idle-summary")).unwrap();
+        let thread_id = thread_handle.id.clone();
+        app.tui.thread.thread_handle = Some(thread_handle);
+        app.tui.thread.messages = vec![
+            ChatMessage::user("question"),
+            ChatMessage::assistant_text("answer", None),
+        ];
+
+        // Recent activity: not idle yet.
+        assert!(idle_summary_effect(&mut app.tui).is_none());
+
+        let idle = std::time::Duration::from_secs(app.tui.config.tui.idle_summary_secs + 1);
+        app.tui.last_activity = std::time::Instant::now().checked_sub(idle).unwrap();
+        assert!(matches!(
+            idle_summary_effect(&mut app.tui),
+            Some(UiEffect::SummarizeThread { thread_id: id }) if id == thread_id
+        ));
+        assert!(idle_summary_effect(&mut app.tui).is_none());
+
+        app.tui.thread.messages.push(ChatMessage::user("follow-up"));
+        assert!(idle_summary_effect(&mut app.tui).is_some());
+
+        app.tui.config.tui.idle_summary_secs = 0;
+        app.tui.thread.messages.push(ChatMessage::user("again"));
+        assert!(idle_summary_effect(&mut app.tui).is_none());
+    }
+
     #[test]
     fn test_thread_created_matches_startup_messages_and_prefills_initial_input() {
         let config = zdx_engine::config::Config::default();
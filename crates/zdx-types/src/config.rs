@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 ///
 /// Controls how much reasoning effort providers use before responding.
 /// Higher levels use more tokens but provide deeper reasoning.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum ThinkingLevel {
     /// No reasoning (default)
@@ -131,9 +131,105 @@ impl TextVerbosity {
     }
 }
 
+/// HTTP client settings for one provider (`[providers.<name>.http]`).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProviderHttpConfig {
+    /// Extra headers sent with every request (e.g. `OpenAI-Organization`,
+    /// gateway auth keys). Headers the provider sets itself take precedence.
+    pub headers: std::collections::BTreeMap<String, String>,
+    /// Proxy URL for all requests (`http://` or `https://`).
+    pub proxy: Option<String>,
+    /// PEM file with extra root certificates to trust (e.g. a corporate CA).
+    pub ca_bundle: Option<String>,
+    /// Skip TLS certificate verification. Only for debugging local gateways.
+    pub accept_invalid_certs: bool,
+}
+
+impl ProviderHttpConfig {
+    /// Whether no setting differs from the default client.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+/// Sampling controls from `[model_params]` (or the TUI `/params` overlay).
+///
+/// `None` leaves the provider default. Providers that reject a control (e.g.
+/// `temperature` on a reasoning model) have it dropped before the request is
+/// built; the values actually sent are recorded on usage events.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ModelParams {
+    /// Sampling temperature (0.0–2.0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f64>,
+    /// Nucleus sampling mass (0.0–1.0, exclusive of 0).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f64>,
+    /// Output token cap; takes precedence over top-level `max_tokens`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+}
+
+impl ModelParams {
+    /// Whether no control is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Checks each set value against its accepted range.
+    ///
+    /// # Errors
+    /// Returns a message naming the first out-of-range control.
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(temperature) = self.temperature
+            && !(0.0..=2.0).contains(&temperature)
+        {
+            return Err(format!(
+                "model_params.temperature must be between 0 and 2 (got {temperature})"
+            ));
+        }
+        if let Some(top_p) = self.top_p
+            && !(top_p > 0.0 && top_p <= 1.0)
+        {
+            return Err(format!(
+                "model_params.top_p must be greater than 0 and at most 1 (got {top_p})"
+            ));
+        }
+        if self.max_output_tokens == Some(0) {
+            return Err("model_params.max_output_tokens must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Compact `key=value` list of the set controls (e.g. `temperature=0.2
+    /// top_p=0.9`), or `"defaults"` when none are set.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(temperature) = self.temperature {
+            parts.push(format!("temperature={temperature}"));
+        }
+        if let Some(top_p) = self.top_p {
+            parts.push(format!("top_p={top_p}"));
+        }
+        if let Some(max) = self.max_output_tokens {
+            parts.push(format!("max_output_tokens={max}"));
+        }
+        if parts.is_empty() {
+            "defaults".to_string()
+        } else {
+            parts.join(" ")
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::ThinkingLevel;
+    use super::{ModelParams, ThinkingLevel};
 
     #[test]
     fn legacy_minimal_deserializes_as_low() {
@@ -141,4 +237,28 @@ mod tests {
         assert_eq!(level, ThinkingLevel::Low);
         assert_eq!(serde_json::to_string(&level).unwrap(), "\"low\"");
     }
+
+    #[test]
+    fn model_params_validate_ranges() {
+        assert!(ModelParams::default().validate().is_ok());
+        let ok = ModelParams {
+            temperature: Some(0.0),
+            top_p: Some(1.0),
+            max_output_tokens: Some(1),
+        };
+        assert!(ok.validate().is_ok());
+        assert_eq!(ok.summary(), "temperature=0 top_p=1 max_output_tokens=1");
+
+        let hot = ModelParams {
+            temperature: Some(2.5),
+            ..ModelParams::default()
+        };
+        assert!(hot.validate().unwrap_err().contains("temperature"));
+        let zero_p = ModelParams {
+            top_p: Some(0.0),
+            ..ModelParams::default()
+        };
+        assert!(zero_p.validate().unwrap_err().contains("top_p"));
+        assert_eq!(ModelParams::default().summary(), "defaults");
+    }
 }
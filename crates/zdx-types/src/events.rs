@@ -5,11 +5,12 @@ use std::fmt;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
+use crate::config::{ModelParams, ThinkingLevel};
 use crate::messages::{ChatMessage, ReasoningBlock};
 use crate::providers::ProviderErrorKind;
 
 /// Events emitted by the agent during execution.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum AgentEvent {
     /// Turn has started processing.
@@ -51,6 +52,15 @@ pub enum AgentEvent {
         delta: String,
     },
 
+    /// Provisional tool input parsed from the JSON streamed so far.
+    /// Used for UI streaming updates (not persisted); superseded by
+    /// `ToolInputCompleted`.
+    ToolInputPartial {
+        id: String,
+        name: String,
+        input: Value,
+    },
+
     /// A tool invocation has started execution.
     ToolStarted { id: String, name: String },
 
@@ -85,6 +95,32 @@ pub enum AgentEvent {
         details: Option<String>,
     },
 
+    /// The agent stopped a turn that looked stuck in a tool loop (too many
+    /// tool iterations, or the same tool calls repeated back to back). The
+    /// turn then finishes with the messages recorded so far.
+    LoopDetected {
+        /// Which safeguard tripped.
+        kind: LoopKind,
+        /// One-line human-readable explanation.
+        message: String,
+        /// Optional details (e.g. the repeated call).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        details: Option<String>,
+    },
+
+    /// The thinking-escalation policy raised the reasoning level mid-turn.
+    /// Subsequent requests in the turn use `to`.
+    ThinkingEscalated {
+        /// Level used before the escalation.
+        from: ThinkingLevel,
+        /// Level used from now on.
+        to: ThinkingLevel,
+        /// What prompted the escalation.
+        trigger: EscalationTrigger,
+        /// One-line human-readable explanation.
+        message: String,
+    },
+
     /// A transient provider failure was hit and the agent is backing off
     /// before retrying the request.
     ProviderRetry {
@@ -160,6 +196,10 @@ pub enum AgentEvent {
         /// arrived; `None` otherwise.
         #[serde(default, skip_serializing_if = "Option::is_none")]
         ttft_ms: Option<u64>,
+        /// `[model_params]` sent with the request (after provider gating).
+        /// `None` when everything was left at provider defaults.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        model_params: Option<ModelParams>,
     },
 }
 
@@ -179,6 +219,8 @@ pub enum ErrorKind {
     Parse,
     /// API-level error from provider
     ApiError,
+    /// Provider stream went idle past the stall timeout
+    StreamStall,
     /// Internal/unknown error
     Internal,
 }
@@ -197,6 +239,38 @@ pub enum NoticeKind {
     /// Generation stopped due to context window exhaustion
     /// (Anthropic `stop_reason=model_context_window_exceeded`).
     ContextWindowExceeded,
+    /// The thinking-escalation policy raised the reasoning level.
+    ThinkingEscalated,
+    /// The automatic `[tools.check]` run failed after file edits.
+    CheckFailed,
+    /// A web tool result was flagged or blocked by the prompt-injection guard.
+    ContentFlagged,
+    /// Older turns were left out of the request by `[providers.<id>].truncation`.
+    HistoryTruncated,
+    /// A response stream dropped mid-response and was resumed.
+    StreamResumed,
+    /// A subscription usage-limit window crossed a warning threshold.
+    UsageLimit,
+}
+
+/// Triggers for `AgentEvent::ThinkingEscalated`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EscalationTrigger {
+    /// The final answer contained an uncertainty phrase; it is retried.
+    Uncertainty,
+    /// Consecutive tool rounds had failing tool calls.
+    ToolFailures,
+}
+
+/// Safeguard categories for `AgentEvent::LoopDetected`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LoopKind {
+    /// The run hit the per-turn tool iteration limit.
+    MaxToolIterations,
+    /// The same tool calls (names and inputs) repeated too many times in a row.
+    RepeatedToolCall,
 }
 
 /// Terminal status for a turn.
@@ -210,6 +284,12 @@ pub enum TurnStatus {
         message: String,
         #[serde(skip_serializing_if = "Option::is_none")]
         details: Option<String>,
+        /// HTTP status of the failed provider response, when known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        http_status: Option<u16>,
+        /// Provider request ID of the failed response, when known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        request_id: Option<String>,
     },
 }
 
@@ -222,6 +302,7 @@ impl From<ProviderErrorKind> for ErrorKind {
             ProviderErrorKind::Timeout => ErrorKind::Timeout,
             ProviderErrorKind::Parse => ErrorKind::Parse,
             ProviderErrorKind::ApiError => ErrorKind::ApiError,
+            ProviderErrorKind::StreamStall => ErrorKind::StreamStall,
         }
     }
 }
@@ -235,6 +316,7 @@ impl fmt::Display for ErrorKind {
             ErrorKind::Timeout => write!(f, "timeout"),
             ErrorKind::Parse => write!(f, "parse"),
             ErrorKind::ApiError => write!(f, "api_error"),
+            ErrorKind::StreamStall => write!(f, "stream_stall"),
             ErrorKind::Internal => write!(f, "internal"),
         }
     }
@@ -246,8 +328,8 @@ pub enum ToolOutput {
     Success {
         ok: bool,
         data: Value,
-        /// Optional image content (not serialized to JSON).
-        image: Option<ImageContent>,
+        /// Image content sent alongside the JSON envelope (not serialized to JSON).
+        images: Vec<ImageContent>,
     },
     Failure {
         ok: bool,
@@ -318,7 +400,7 @@ impl<'de> Deserialize<'de> for ToolOutput {
             Ok(ToolOutput::Success {
                 ok: true,
                 data: raw.data.unwrap_or(Value::Null),
-                image: None,
+                images: Vec::new(),
             })
         } else if let Some(error) = raw.error {
             if error.code == CANCELED_ERROR_CODE {
@@ -347,7 +429,7 @@ impl ToolOutput {
         ToolOutput::Success {
             ok: true,
             data,
-            image: None,
+            images: Vec::new(),
         }
     }
 
@@ -356,7 +438,16 @@ impl ToolOutput {
         ToolOutput::Success {
             ok: true,
             data,
-            image: Some(image),
+            images: vec![image],
+        }
+    }
+
+    /// Creates a successful tool output with several images.
+    pub fn success_with_images(data: Value, images: Vec<ImageContent>) -> Self {
+        ToolOutput::Success {
+            ok: true,
+            data,
+            images,
         }
     }
 
@@ -405,11 +496,16 @@ impl ToolOutput {
         }
     }
 
-    /// Returns the image content if present.
+    /// Returns the first image if present.
     pub fn image(&self) -> Option<&ImageContent> {
+        self.images().first()
+    }
+
+    /// Returns all attached images.
+    pub fn images(&self) -> &[ImageContent] {
         match self {
-            ToolOutput::Success { image, .. } => image.as_ref(),
-            ToolOutput::Failure { .. } | ToolOutput::Canceled { .. } => None,
+            ToolOutput::Success { images, .. } => images,
+            ToolOutput::Failure { .. } | ToolOutput::Canceled { .. } => &[],
         }
     }
 
@@ -11,7 +11,8 @@ pub mod providers;
 pub mod tools;
 
 pub use events::{
-    AgentEvent, ErrorKind, ImageContent, NoticeKind, ToolError, ToolOutput, TurnStatus,
+    AgentEvent, ErrorKind, EscalationTrigger, ImageContent, LoopKind, NoticeKind, ToolError,
+    ToolOutput, TurnStatus,
 };
 pub use messages::{
     ChatContentBlock, ChatMessage, ContentBlockType, IdOrigin, MessageContent, ReasoningBlock,
@@ -22,4 +23,6 @@ pub use providers::{
 };
 pub use tools::{ToolDefinition, ToolResult, ToolResultBlock, ToolResultContent};
 pub mod config;
-pub use config::{TextVerbosity, ThinkingLevel};
+pub use config::{ModelParams, ProviderHttpConfig, TextVerbosity, ThinkingLevel};
+pub mod secrets;
+pub use secrets::is_secret_key;
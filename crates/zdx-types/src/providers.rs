@@ -24,6 +24,8 @@ pub enum ProviderErrorKind {
     Parse,
     /// API-level error returned by the provider (e.g., overloaded, `rate_limit`)
     ApiError,
+    /// The stream sent no data for longer than the idle timeout
+    StreamStall,
 }
 
 impl fmt::Display for ProviderErrorKind {
@@ -35,6 +37,7 @@ impl fmt::Display for ProviderErrorKind {
             ProviderErrorKind::Timeout => write!(f, "timeout"),
             ProviderErrorKind::Parse => write!(f, "parse"),
             ProviderErrorKind::ApiError => write!(f, "api_error"),
+            ProviderErrorKind::StreamStall => write!(f, "stream_stall"),
         }
     }
 }
@@ -54,6 +57,9 @@ pub struct ProviderError {
     pub message: String,
     /// Optional additional details (e.g., raw error body)
     pub details: Option<String>,
+    /// Provider request ID from response headers, for support requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
 }
 
 /// Substrings used by `ProviderError::is_retryable` to classify transient failures.
@@ -137,6 +143,7 @@ impl ProviderError {
             code: None,
             message: message.into(),
             details: None,
+            request_id: None,
         }
     }
 
@@ -156,6 +163,7 @@ impl ProviderError {
             code,
             message,
             details,
+            request_id: None,
         }
     }
 
@@ -164,6 +172,14 @@ impl ProviderError {
         Self::new(ProviderErrorKind::Timeout, message)
     }
 
+    /// Creates a stream-stall error after `idle` without any stream data.
+    pub fn stream_stall(idle: std::time::Duration) -> Self {
+        Self::new(
+            ProviderErrorKind::StreamStall,
+            format!("Provider stream stalled: no data for {}s", idle.as_secs()),
+        )
+    }
+
     /// Creates a transport error.
     pub fn transport(message: impl Into<String>) -> Self {
         Self::new(ProviderErrorKind::Transport, message)
@@ -182,13 +198,23 @@ impl ProviderError {
             code: Some(error_type.to_string()),
             message: format!("{error_type}: {message}"),
             details: None,
+            request_id: None,
         }
     }
 
+    /// Attaches the provider request ID reported in the response headers.
+    #[must_use]
+    pub fn with_request_id(mut self, request_id: Option<String>) -> Self {
+        self.request_id = request_id;
+        self
+    }
+
     /// Returns true if this error is transient and safe to retry automatically.
     pub fn is_retryable(&self) -> bool {
         match self.kind {
-            ProviderErrorKind::Transport | ProviderErrorKind::Timeout => return true,
+            ProviderErrorKind::Transport
+            | ProviderErrorKind::Timeout
+            | ProviderErrorKind::StreamStall => return true,
             ProviderErrorKind::Request | ProviderErrorKind::Parse => return false,
             ProviderErrorKind::HttpStatus | ProviderErrorKind::ApiError => {}
         }
@@ -0,0 +1,93 @@
+//! Secret-name classification shared by every redaction surface.
+
+/// Name words that mark a value as a credential.
+const SECRET_WORDS: &[&str] = &["token", "secret", "password", "authorization", "apikey"];
+
+/// Words that turn a following `key` word into a credential name
+/// (`api_key`, `x-api-key`, `privateKey`).
+const KEY_QUALIFIERS: &[&str] = &["api", "private"];
+
+/// Returns true when `name` (a JSON field, query parameter, environment
+/// variable, or dotted config path) names a credential.
+///
+/// The name is split into words on non-alphanumerics and camelCase
+/// boundaries, so `api_key`, `API_TOKEN`, `accessToken`, and
+/// `providers.x.api_key` match while `max_tokens` and `cache_key` do not. A
+/// bare `key` (as in Gemini's `?key=`) also matches.
+#[must_use]
+pub fn is_secret_key(name: &str) -> bool {
+    let words = words(name);
+    if words.len() == 1 && words[0] == "key" {
+        return true;
+    }
+    words.iter().enumerate().any(|(i, word)| {
+        SECRET_WORDS.contains(&word.as_str())
+            || (word == "key" && i > 0 && KEY_QUALIFIERS.contains(&words[i - 1].as_str()))
+    })
+}
+
+/// Lowercased words of `name`, split on non-alphanumerics and before an
+/// uppercase letter that follows a lowercase one.
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    let mut prev_lower = false;
+    for c in name.chars() {
+        if !c.is_ascii_alphanumeric() {
+            if !current.is_empty() {
+                words.push(std::mem::take(&mut current));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_ascii_uppercase() && prev_lower {
+            words.push(std::mem::take(&mut current));
+        }
+        prev_lower = c.is_ascii_lowercase();
+        current.push(c.to_ascii_lowercase());
+    }
+    if !current.is_empty() {
+        words.push(current);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_credential_names_across_naming_styles() {
+        for name in [
+            "api_key",
+            "apiKey",
+            "apikey",
+            "x-api-key",
+            "API_TOKEN",
+            "access_token",
+            "accessToken",
+            "refresh_token",
+            "client_secret",
+            "password",
+            "Authorization",
+            "private_key",
+            "key",
+            "providers.anthropic.api_key",
+            "telegram.bot_token",
+        ] {
+            assert!(is_secret_key(name), "{name} should be secret");
+        }
+        for name in [
+            "max_tokens",
+            "maxOutputTokens",
+            "cache_key",
+            "monkey",
+            "keyboard",
+            "model",
+            "file_path",
+            "",
+        ] {
+            assert!(!is_secret_key(name), "{name} should not be secret");
+        }
+    }
+}
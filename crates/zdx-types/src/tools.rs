@@ -78,21 +78,21 @@ pub struct ToolResult {
 impl ToolResult {
     /// Creates a `ToolResult` from a `ToolOutput`.
     ///
-    /// If the output contains image content, creates a Blocks content with
-    /// both text (JSON envelope) and image blocks. Otherwise, creates Text content.
+    /// If the output contains images, creates a Blocks content with the text
+    /// (JSON envelope) block followed by one block per image. Otherwise, creates Text content.
     pub fn from_output(tool_use_id: String, output: &ToolOutput) -> Self {
-        let content = match output.image() {
-            Some(image) => {
-                let text_block = ToolResultBlock::Text {
-                    text: output.to_json_string(),
-                };
-                let image_block = ToolResultBlock::Image {
-                    mime_type: image.mime_type.clone(),
-                    data: image.data.clone(),
-                };
-                ToolResultContent::Blocks(vec![text_block, image_block])
-            }
-            None => ToolResultContent::Text(output.to_json_string()),
+        let images = output.images();
+        let content = if images.is_empty() {
+            ToolResultContent::Text(output.to_json_string())
+        } else {
+            let mut blocks = vec![ToolResultBlock::Text {
+                text: output.to_json_string(),
+            }];
+            blocks.extend(images.iter().map(|image| ToolResultBlock::Image {
+                mime_type: image.mime_type.clone(),
+                data: image.data.clone(),
+            }));
+            ToolResultContent::Blocks(blocks)
         };
 
         Self {